@@ -2,6 +2,7 @@ use crate::{com::ComPtr, sync::Fence, CommandList, HRESULT};
 use winapi::um::d3d12;
 
 #[repr(u32)]
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
 pub enum Priority {
     Normal = d3d12::D3D12_COMMAND_QUEUE_PRIORITY_NORMAL,
     High = d3d12::D3D12_COMMAND_QUEUE_PRIORITY_HIGH,
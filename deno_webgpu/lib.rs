@@ -675,6 +675,9 @@ pub fn op_webgpu_request_device(
         label: Some(Cow::Owned(label)),
         required_features: required_features.into(),
         required_limits: required_limits.unwrap_or_default(),
+        flags: wgpu_types::DeviceFlags::empty(),
+        queue_priority: wgpu_types::QueuePriority::Normal,
+        validation: wgpu_types::DeviceValidation::new(),
     };
 
     let (device, queue, maybe_err) = gfx_select!(adapter => instance.adapter_request_device(
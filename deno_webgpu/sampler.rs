@@ -66,9 +66,11 @@ pub fn op_webgpu_create_sampler(
         mipmap_filter: args.mipmap_filter,
         lod_min_clamp: args.lod_min_clamp,
         lod_max_clamp: args.lod_max_clamp,
+        lod_bias: 0.0, // native-only
         compare: args.compare,
         anisotropy_clamp: args.max_anisotropy,
-        border_color: None, // native-only
+        border_color: None,                 // native-only
+        reduction_mode: Default::default(), // native-only
     };
 
     gfx_put!(device => instance.device_create_sampler(
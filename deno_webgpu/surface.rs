@@ -61,6 +61,12 @@ pub fn op_webgpu_surface_configure(
         alpha_mode: args.alpha_mode,
         view_formats: args.view_formats,
         desired_maximum_frame_latency: 2,
+        desired_minimum_image_count: None,
+        pre_transform: wgpu_types::SurfaceTransform::Identity,
+        color_space: wgpu_types::ColorSpace::Srgb,
+        array_layer_count: 1,
+        fullscreen: wgpu_types::FullscreenMode::Windowed,
+        display_mode: None,
     };
 
     let err = gfx_select!(device => instance.surface_configure(surface, device, &conf));
@@ -143,6 +143,7 @@ impl crate::framework::Example for Example {
             source: wgpu::ShaderSource::Wgsl(Cow::Borrowed(include_str!(
                 "../../../wgpu-hal/examples/halmark/shader.wgsl"
             ))),
+            diagnostic_filters: &[],
         });
 
         let global_bind_group_layout =
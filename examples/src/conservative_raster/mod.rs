@@ -88,6 +88,7 @@ impl crate::framework::Example for Example {
             source: wgpu::ShaderSource::Wgsl(Cow::Borrowed(include_str!(
                 "triangle_and_lines.wgsl"
             ))),
+            diagnostic_filters: &[],
         });
 
         let pipeline_triangle_conservative =
@@ -203,6 +204,7 @@ impl crate::framework::Example for Example {
             let shader = device.create_shader_module(wgpu::ShaderModuleDescriptor {
                 label: None,
                 source: wgpu::ShaderSource::Wgsl(Cow::Borrowed(include_str!("upscale.wgsl"))),
+                diagnostic_filters: &[],
             });
             (
                 device.create_render_pipeline(&wgpu::RenderPipelineDescriptor {
@@ -319,6 +319,9 @@ impl ExampleContext {
                     label: None,
                     required_features: (optional_features & adapter_features) | required_features,
                     required_limits: needed_limits,
+                    flags: wgpu::DeviceFlags::empty(),
+                    queue_priority: wgpu::QueuePriority::Normal,
+                    validation: wgpu::DeviceValidation::new(),
                 },
                 trace_dir.ok().as_ref().map(std::path::Path::new),
             )
@@ -572,9 +575,15 @@ impl<E: Example + wgpu::WasmNotSendSync> From<ExampleTestParams<E>>
                         width: params.width,
                         height: params.height,
                         desired_maximum_frame_latency: 2,
+                        desired_minimum_image_count: None,
                         present_mode: wgpu::PresentMode::Fifo,
                         alpha_mode: wgpu::CompositeAlphaMode::Auto,
                         view_formats: vec![format],
+                        pre_transform: wgpu::SurfaceTransform::Identity,
+                        color_space: wgpu::ColorSpace::Srgb,
+                        array_layer_count: 1,
+                        fullscreen: wgpu::FullscreenMode::Windowed,
+                        display_mode: None,
                     },
                     &ctx.adapter,
                     &ctx.device,
@@ -50,6 +50,9 @@ async fn execute_gpu(numbers: &[u32]) -> Option<Vec<u32>> {
                 label: None,
                 required_features: wgpu::Features::empty(),
                 required_limits: wgpu::Limits::downlevel_defaults(),
+                flags: wgpu::DeviceFlags::empty(),
+                queue_priority: wgpu::QueuePriority::Normal,
+                validation: wgpu::DeviceValidation::new(),
             },
             None,
         )
@@ -68,6 +71,7 @@ async fn execute_gpu_inner(
     let cs_module = device.create_shader_module(wgpu::ShaderModuleDescriptor {
         label: None,
         source: wgpu::ShaderSource::Wgsl(Cow::Borrowed(include_str!("shader.wgsl"))),
+        diagnostic_filters: &[],
     });
 
     // Gets the size in bytes of the buffer.
@@ -19,6 +19,9 @@ async fn run() {
                 label: None,
                 required_features: wgpu::Features::empty(),
                 required_limits: wgpu::Limits::downlevel_defaults(),
+                flags: wgpu::DeviceFlags::empty(),
+                queue_priority: wgpu::QueuePriority::Normal,
+                validation: wgpu::DeviceValidation::new(),
             },
             None,
         )
@@ -56,6 +59,7 @@ async fn execute(
     let shaders_module = device.create_shader_module(wgpu::ShaderModuleDescriptor {
         label: None,
         source: wgpu::ShaderSource::Wgsl(std::borrow::Cow::Borrowed(include_str!("shaders.wgsl"))),
+        diagnostic_filters: &[],
     });
 
     let storage_buffer = device.create_buffer(&wgpu::BufferDescriptor {
@@ -75,6 +75,9 @@ async fn run(event_loop: EventLoop<()>, viewports: Vec<(Arc<Window>, wgpu::Color
                 label: None,
                 required_features: wgpu::Features::empty(),
                 required_limits: wgpu::Limits::downlevel_defaults(),
+                flags: wgpu::DeviceFlags::empty(),
+                queue_priority: wgpu::QueuePriority::Normal,
+                validation: wgpu::DeviceValidation::new(),
             },
             None,
         )
@@ -32,6 +32,9 @@ async fn run() {
                 label: None,
                 required_features: wgpu::Features::empty(),
                 required_limits: wgpu::Limits::downlevel_defaults(),
+                flags: wgpu::DeviceFlags::empty(),
+                queue_priority: wgpu::QueuePriority::Normal,
+                validation: wgpu::DeviceValidation::new(),
             },
             None,
         )
@@ -41,6 +44,7 @@ async fn run() {
     let shader = device.create_shader_module(wgpu::ShaderModuleDescriptor {
         label: None,
         source: wgpu::ShaderSource::Wgsl(std::borrow::Cow::Borrowed(include_str!("shader.wgsl"))),
+        diagnostic_filters: &[],
     });
 
     let storage_buffer_a = device.create_buffer_init(&wgpu::util::BufferInitDescriptor {
@@ -172,6 +172,9 @@ impl WgpuContext {
                     label: None,
                     required_features: wgpu::Features::empty(),
                     required_limits: wgpu::Limits::downlevel_defaults(),
+                    flags: wgpu::DeviceFlags::empty(),
+                    queue_priority: wgpu::QueuePriority::Normal,
+                    validation: wgpu::DeviceValidation::new(),
                 },
                 None,
             )
@@ -184,6 +187,7 @@ impl WgpuContext {
             source: wgpu::ShaderSource::Wgsl(std::borrow::Cow::Borrowed(include_str!(
                 "shader.wgsl"
             ))),
+            diagnostic_filters: &[],
         });
 
         // This is where the GPU will read from and write to.
@@ -398,6 +398,7 @@ impl crate::framework::Example for Example {
                     mip_level_count: None,
                     base_array_layer: i as u32,
                     array_layer_count: Some(1),
+                    swizzle: wgpu::TextureComponentSwizzle::IDENTITY,
                 }))
             })
             .collect::<Vec<_>>();
@@ -451,6 +452,7 @@ impl crate::framework::Example for Example {
         let shader = device.create_shader_module(wgpu::ShaderModuleDescriptor {
             label: None,
             source: wgpu::ShaderSource::Wgsl(Cow::Borrowed(include_str!("shader.wgsl"))),
+            diagnostic_filters: &[],
         });
 
         let shadow_pass = {
@@ -171,6 +171,7 @@ impl crate::framework::Example for Example {
         let shader = device.create_shader_module(wgpu::ShaderModuleDescriptor {
             label: None,
             source: wgpu::ShaderSource::Wgsl(Cow::Borrowed(include_str!("shader.wgsl"))),
+            diagnostic_filters: &[],
         });
 
         let camera = Camera {
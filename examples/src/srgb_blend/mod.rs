@@ -106,6 +106,7 @@ impl<const SRGB: bool> crate::framework::Example for Example<SRGB> {
         let shader = device.create_shader_module(wgpu::ShaderModuleDescriptor {
             label: None,
             source: wgpu::ShaderSource::Wgsl(Cow::Borrowed(include_str!("shader.wgsl"))),
+            diagnostic_filters: &[],
         });
 
         let vertex_buffers = [wgpu::VertexBufferLayout {
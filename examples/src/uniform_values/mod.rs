@@ -115,6 +115,9 @@ impl WgpuContext {
                     label: None,
                     required_features: wgpu::Features::empty(),
                     required_limits: wgpu::Limits::downlevel_defaults(),
+                    flags: wgpu::DeviceFlags::empty(),
+                    queue_priority: wgpu::QueuePriority::Normal,
+                    validation: wgpu::DeviceValidation::new(),
                 },
                 None,
             )
@@ -126,6 +129,7 @@ impl WgpuContext {
             source: wgpu::ShaderSource::Wgsl(std::borrow::Cow::Borrowed(include_str!(
                 "shader.wgsl"
             ))),
+            diagnostic_filters: &[],
         });
 
         // (2)
@@ -496,10 +496,12 @@ impl crate::framework::Example for Example {
         let terrain_module = device.create_shader_module(wgpu::ShaderModuleDescriptor {
             label: Some("terrain"),
             source: wgpu::ShaderSource::Wgsl(Cow::Borrowed(include_str!("terrain.wgsl"))),
+            diagnostic_filters: &[],
         });
         let water_module = device.create_shader_module(wgpu::ShaderModuleDescriptor {
             label: Some("water"),
             source: wgpu::ShaderSource::Wgsl(Cow::Borrowed(include_str!("water.wgsl"))),
+            diagnostic_filters: &[],
         });
 
         // Create the render pipelines. These describe how the data will flow through the GPU, and what
@@ -94,6 +94,11 @@ struct Args {
     #[argh(switch, short = 'g')]
     generate_debug_symbols: bool,
 
+    /// along with `--generate-debug-symbols`, describe functions and local
+    /// variables for shader debuggers, only works for spv-out
+    #[argh(switch)]
+    non_semantic_debug_info: bool,
+
     /// compact the module's IR and revalidate.
     ///
     /// Output files will reflect the compacted IR. If you want to see the IR as
@@ -108,6 +113,14 @@ struct Args {
     #[argh(option)]
     before_compaction: Option<String>,
 
+    /// replace the names in the output with hashes, and write the names they
+    /// replace to the given file.
+    ///
+    /// The source isn't embedded in the output either, even with
+    /// `--generate-debug-symbols`.
+    #[argh(option)]
+    strip_names: Option<String>,
+
     /// bulk validation mode: all filenames are inputs to read and validate.
     #[argh(switch)]
     bulk_validate: bool,
@@ -449,6 +462,10 @@ fn run() -> anyhow::Result<()> {
                 .spv_out
                 .flags
                 .set(naga::back::spv::WriterFlags::DEBUG, true);
+            params.spv_out.flags.set(
+                naga::back::spv::WriterFlags::NON_SEMANTIC_DEBUG_INFO,
+                args.non_semantic_debug_info,
+            );
             params.spv_out.debug_info = Some(naga::back::spv::DebugInfo {
                 source_code: input_text,
                 file_name: input_path,
@@ -531,6 +548,13 @@ fn run() -> anyhow::Result<()> {
         info
     };
 
+    // Replace the names in the module, if requested.
+    if let Some(ref symbol_map) = args.strip_names {
+        let symbols = naga::proc::strip_names(&mut module);
+        fs::write(symbol_map, symbols.to_string())?;
+        params.spv_out.debug_info = None;
+    }
+
     // If no output was requested, then report validation results and stop here.
     //
     // If the user asked for output, don't stop: some output formats (".txt",
@@ -575,9 +599,14 @@ fn parse_input(input_path: &Path, input: Vec<u8>, params: &Parameters) -> anyhow
         }
         InputKind::Wgsl => {
             let input = String::from_utf8(input)?;
-            let result = naga::front::wgsl::parse_str(&input);
+            let result = naga::front::wgsl::Frontend::new().parse_with_warnings(&input);
             match result {
-                Ok(v) => (v, Some(input)),
+                Ok((v, warnings)) => {
+                    for warning in warnings {
+                        eprint!("{}", warning.emit_to_string_with_path(&input, input_path));
+                    }
+                    (v, Some(input))
+                }
                 Err(ref e) => {
                     let message = anyhow!(
                         "Could not parse WGSL:\n{}",
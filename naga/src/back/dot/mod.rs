@@ -110,6 +110,8 @@ impl StatementGraph {
                     "Continue"
                 }
                 S::Barrier(_flags) => "Barrier",
+                S::FragmentInterlock(crate::FragmentInterlock::Begin) => "BeginInterlock",
+                S::FragmentInterlock(crate::FragmentInterlock::End) => "EndInterlock",
                 S::Block(ref b) => {
                     let (other, last) = self.add(b, targets);
                     self.flow.push((id, other, ""));
@@ -662,6 +664,13 @@ fn write_function_expressions(
                 };
                 (string.into(), 3)
             }
+            E::PointerFromAddress {
+                address,
+                pointer: _,
+            } => {
+                edges.insert("address", address);
+                ("PointerFromAddress".into(), 3)
+            }
             E::CallResult(_function) => ("CallResult".into(), 4),
             E::AtomicResult { .. } => ("AtomicResult".into(), 4),
             E::WorkGroupUniformLoadResult { .. } => ("WorkGroupUniformLoadResult".into(), 4),
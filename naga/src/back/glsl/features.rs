@@ -52,6 +52,8 @@ bitflags::bitflags! {
         const TEXTURE_SHADOW_LOD = 1 << 23;
         /// Subgroup operations
         const SUBGROUP_OPERATIONS = 1 << 24;
+        /// Fragment shader critical sections
+        const FRAGMENT_SHADER_INTERLOCK = 1 << 25;
     }
 }
 
@@ -120,6 +122,7 @@ impl FeaturesManager {
         check_feature!(DYNAMIC_ARRAY_SIZE, 430, 310);
         check_feature!(DUAL_SOURCE_BLENDING, 330, 300 /* with extension */);
         check_feature!(SUBGROUP_OPERATIONS, 430, 310);
+        check_feature!(FRAGMENT_SHADER_INTERLOCK, 420);
         match version {
             Version::Embedded { is_webgl: true, .. } => check_feature!(MULTI_VIEW, 140, 300),
             _ => check_feature!(MULTI_VIEW, 140, 310),
@@ -278,6 +281,11 @@ impl FeaturesManager {
             )?;
         }
 
+        if self.0.contains(Features::FRAGMENT_SHADER_INTERLOCK) {
+            // https://registry.khronos.org/OpenGL/extensions/ARB/ARB_fragment_shader_interlock.txt
+            writeln!(out, "#extension GL_ARB_fragment_shader_interlock : require")?;
+        }
+
         Ok(())
     }
 }
@@ -313,6 +321,10 @@ impl<'a, W> Writer<'a, W> {
             self.features.request(Features::COMPUTE_SHADER)
         }
 
+        if self.entry_point.function.has_fragment_interlock() {
+            self.features.request(Features::FRAGMENT_SHADER_INTERLOCK)
+        }
+
         if self.multiview.is_some() {
             self.features.request(Features::MULTI_VIEW);
         }
@@ -113,7 +113,8 @@ impl crate::AddressSpace {
             | crate::AddressSpace::Uniform
             | crate::AddressSpace::Storage { .. }
             | crate::AddressSpace::Handle
-            | crate::AddressSpace::PushConstant => false,
+            | crate::AddressSpace::PushConstant
+            | crate::AddressSpace::PhysicalStorage { .. } => false,
         }
     }
 }
@@ -707,6 +708,11 @@ impl<'a, W: Write> Writer<'a, W> {
             }
         }
 
+        if self.features.contains(Features::FRAGMENT_SHADER_INTERLOCK) {
+            writeln!(self.out, "layout(pixel_interlock_ordered) in;")?;
+            writeln!(self.out)?;
+        }
+
         if self.entry_point.stage == ShaderStage::Vertex && self.options.version.is_webgl() {
             if let Some(multiview) = self.multiview.as_ref() {
                 writeln!(self.out, "layout(num_views = {multiview}) in;")?;
@@ -1185,6 +1191,8 @@ impl<'a, W: Write> Writer<'a, W> {
             // A global variable in the `Function` address space is a
             // contradiction in terms.
             crate::AddressSpace::Function => unreachable!(),
+            // Only pointers made from addresses can be in this address space.
+            crate::AddressSpace::PhysicalStorage { .. } => unreachable!(),
             // Textures and samplers are handled directly in `Writer::write`.
             crate::AddressSpace::Handle => unreachable!(),
         }
@@ -2291,6 +2299,13 @@ impl<'a, W: Write> Writer<'a, W> {
             Statement::Barrier(flags) => {
                 self.write_barrier(flags, level)?;
             }
+            Statement::FragmentInterlock(interlock) => {
+                let name = match interlock {
+                    crate::FragmentInterlock::Begin => "beginInvocationInterlockARB",
+                    crate::FragmentInterlock::End => "endInvocationInterlockARB",
+                };
+                writeln!(self.out, "{level}{name}();")?;
+            }
             // Stores in glsl are just variable assignments written as `pointer = value;`
             Statement::Store { pointer, value } => {
                 write!(self.out, "{level}")?;
@@ -3588,6 +3603,8 @@ impl<'a, W: Write> Writer<'a, W> {
                     Mf::Unpack2x16snorm => "unpackSnorm2x16",
                     Mf::Unpack2x16unorm => "unpackUnorm2x16",
                     Mf::Unpack2x16float => "unpackHalf2x16",
+                    // uniformity
+                    Mf::NonUniform => "",
                 };
 
                 let extract_bits = fun == Mf::ExtractBits;
@@ -3686,6 +3703,11 @@ impl<'a, W: Write> Writer<'a, W> {
                     write!(self.out, ")")?
                 }
             }
+            Expression::PointerFromAddress { .. } => {
+                return Err(Error::Custom(
+                    "Pointers made from addresses aren't supported".to_string(),
+                ));
+            }
             // `As` is always a call.
             // If `convert` is true the function name is the type
             // Else the function name is one of the glsl provided bitcast functions
@@ -4600,6 +4622,7 @@ const fn glsl_built_in(built_in: crate::BuiltIn, options: VaryingOptions) -> &'s
         }
         Bi::PointSize => "gl_PointSize",
         Bi::VertexIndex => "uint(gl_VertexID)",
+        Bi::ViewportIndex => "gl_ViewportIndex",
         // fragment
         Bi::FragDepth => "gl_FragDepth",
         Bi::PointCoord => "gl_PointCoord",
@@ -4635,6 +4658,7 @@ const fn glsl_storage_qualifier(space: crate::AddressSpace) -> Option<&'static s
     match space {
         As::Function => None,
         As::Private => None,
+        As::PhysicalStorage { .. } => None,
         As::Storage { .. } => Some("buffer"),
         As::Uniform => Some("uniform"),
         As::Handle => Some("uniform"),
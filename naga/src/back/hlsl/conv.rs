@@ -164,6 +164,7 @@ impl crate::BuiltIn {
             Self::CullDistance => "SV_CullDistance",
             Self::InstanceIndex => "SV_InstanceID",
             Self::VertexIndex => "SV_VertexID",
+            Self::ViewportIndex => "SV_ViewportArrayIndex",
             // fragment
             Self::FragDepth => "SV_Depth",
             Self::FrontFacing => "SV_IsFrontFace",
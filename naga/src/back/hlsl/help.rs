@@ -136,7 +136,7 @@ impl<'a, W: Write> super::Writer<'a, W> {
         class: crate::ImageClass,
     ) -> BackendResult {
         let access_str = match class {
-            crate::ImageClass::Storage { .. } => "RW",
+            crate::ImageClass::Storage { .. } => self.writable_prefix(),
             _ => "",
         };
         let dim_str = dim.to_hlsl_str();
@@ -188,7 +188,11 @@ impl<'a, W: Write> super::Writer<'a, W> {
 
         // Write function parameters
         write!(self.out, "(")?;
-        let access_str = if wal.writable { "RW" } else { "" };
+        let access_str = if wal.writable {
+            self.writable_prefix()
+        } else {
+            ""
+        };
         writeln!(
             self.out,
             "{access_str}ByteAddressBuffer {ARGUMENT_VARIABLE_NAME})"
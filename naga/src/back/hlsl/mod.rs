@@ -318,4 +318,12 @@ pub struct Writer<'a, W> {
     /// [`AccessIndex`]: crate::Expression::AccessIndex
     temp_access_chain: Vec<storage::SubAccess>,
     need_bake_expressions: back::NeedBakeExpressions,
+
+    /// Whether writable storage buffers and textures are declared as
+    /// rasterizer ordered views rather than plain UAVs, because a fragment
+    /// entry point of the module has a critical section.
+    ///
+    /// Rasterizer ordered views order every access to them, so the critical
+    /// section itself doesn't produce any code.
+    rasterizer_ordered_views: bool,
 }
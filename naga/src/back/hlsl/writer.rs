@@ -105,6 +105,7 @@ impl<'a, W: fmt::Write> super::Writer<'a, W> {
             wrapped: super::Wrapped::default(),
             temp_access_chain: Vec::new(),
             need_bake_expressions: Default::default(),
+            rasterizer_ordered_views: false,
         }
     }
 
@@ -122,6 +123,20 @@ impl<'a, W: fmt::Write> super::Writer<'a, W> {
         self.named_expressions.clear();
         self.wrapped.clear();
         self.need_bake_expressions.clear();
+        self.rasterizer_ordered_views = module
+            .entry_points
+            .iter()
+            .any(|ep| ep.function.has_fragment_interlock());
+    }
+
+    /// Returns the prefix of the types of writable storage buffers and
+    /// textures.
+    pub(super) fn writable_prefix(&self) -> &'static str {
+        if self.rasterizer_ordered_views {
+            "RasterizerOrdered"
+        } else {
+            "RW"
+        }
     }
 
     /// Helper method used to find which expressions of a given function require baking
@@ -747,6 +762,9 @@ impl<'a, W: fmt::Write> super::Writer<'a, W> {
         // https://docs.microsoft.com/en-us/windows/win32/direct3dhlsl/dx-graphics-hlsl-variable-register
         let register_ty = match global.space {
             crate::AddressSpace::Function => unreachable!("Function address space"),
+            crate::AddressSpace::PhysicalStorage { .. } => {
+                unreachable!("PhysicalStorage address space")
+            }
             crate::AddressSpace::Private => {
                 write!(self.out, "static ")?;
                 self.write_type(module, global.ty)?;
@@ -765,7 +783,7 @@ impl<'a, W: fmt::Write> super::Writer<'a, W> {
             }
             crate::AddressSpace::Storage { access } => {
                 let (prefix, register) = if access.contains(crate::StorageAccess::STORE) {
-                    ("RW", "u")
+                    (self.writable_prefix(), "u")
                 } else {
                     ("", "t")
                 };
@@ -1850,6 +1868,7 @@ impl<'a, W: fmt::Write> super::Writer<'a, W> {
             }
             Statement::Break => writeln!(self.out, "{level}break;")?,
             Statement::Continue => writeln!(self.out, "{level}continue;")?,
+            Statement::FragmentInterlock(_) => {}
             Statement::Barrier(barrier) => {
                 self.write_barrier(barrier, level)?;
             }
@@ -1930,6 +1949,10 @@ impl<'a, W: fmt::Write> super::Writer<'a, W> {
                     .unwrap();
 
                 let fun_str = fun.to_hlsl_suffix();
+                // Byte address buffers have separate methods for 64-bit atomics,
+                // available since Shader Model 6.6.
+                let value_width = func_ctx.resolve_type(value, &module.types).scalar_width();
+                let width_suffix = if value_width == Some(8) { "64" } else { "" };
                 write!(self.out, " {res_name}; ")?;
                 match pointer_space {
                     crate::AddressSpace::WorkGroup => {
@@ -1943,7 +1966,7 @@ impl<'a, W: fmt::Write> super::Writer<'a, W> {
                         // ownership of our reusable access chain buffer.
                         let chain = mem::take(&mut self.temp_access_chain);
                         let var_name = &self.names[&NameKey::GlobalVariable(var_handle)];
-                        write!(self.out, "{var_name}.Interlocked{fun_str}(")?;
+                        write!(self.out, "{var_name}.Interlocked{fun_str}{width_suffix}(")?;
                         self.write_storage_address(module, &chain, func_ctx)?;
                         self.temp_access_chain = chain;
                     }
@@ -2766,6 +2789,11 @@ impl<'a, W: fmt::Write> super::Writer<'a, W> {
                 self.write_expr(module, expr, func_ctx)?;
                 write!(self.out, ")")?;
             }
+            Expression::PointerFromAddress { .. } => {
+                return Err(Error::Custom(
+                    "HLSL has no pointers to buffer device addresses".to_string(),
+                ));
+            }
             Expression::As {
                 expr,
                 kind,
@@ -2930,6 +2958,9 @@ impl<'a, W: fmt::Write> super::Writer<'a, W> {
                     Mf::Unpack2x16unorm => Function::Unpack2x16unorm,
                     Mf::Unpack4x8snorm => Function::Unpack4x8snorm,
                     Mf::Unpack4x8unorm => Function::Unpack4x8unorm,
+                    // The index itself is wrapped in `NonUniformResourceIndex`
+                    // where it is used to access a binding array.
+                    Mf::NonUniform => Function::Regular(""),
                     _ => return Err(Error::Unimplemented(format!("write_expr_math {fun:?}"))),
                 };
 
@@ -266,6 +266,18 @@ impl crate::Statement {
     }
 }
 
+impl crate::Function {
+    /// Returns true if the function has a fragment shader critical section.
+    ///
+    /// Validation only allows [`crate::Statement::FragmentInterlock`] in the
+    /// body of entry points, so only that block is searched.
+    pub fn has_fragment_interlock(&self) -> bool {
+        self.body
+            .iter()
+            .any(|statement| matches!(*statement, crate::Statement::FragmentInterlock(_)))
+    }
+}
+
 bitflags::bitflags! {
     /// Ray flags, for a [`RayDesc`]'s `flags` field.
     ///
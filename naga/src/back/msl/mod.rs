@@ -262,6 +262,11 @@ impl Options {
                     crate::BuiltIn::InstanceIndex if self.lang_version < (1, 2) => {
                         return Err(Error::UnsupportedAttribute("instance_id".to_string()));
                     }
+                    crate::BuiltIn::ViewportIndex if self.lang_version < (2, 0) => {
+                        return Err(Error::UnsupportedAttribute(
+                            "viewport_array_index".to_string(),
+                        ));
+                    }
                     // macOS: Since Metal 2.2
                     // iOS: Since Metal 2.3 (check depends on https://github.com/gfx-rs/naga/issues/2164)
                     crate::BuiltIn::PrimitiveIndex if self.lang_version < (2, 2) => {
@@ -422,6 +427,7 @@ impl ResolvedBinding {
                     Bi::InstanceIndex => "instance_id",
                     Bi::PointSize => "point_size",
                     Bi::VertexIndex => "vertex_id",
+                    Bi::ViewportIndex => "viewport_array_index",
                     // fragment
                     Bi::FragDepth => "depth(any)",
                     Bi::PointCoord => "point_coord",
@@ -388,6 +388,22 @@ fn should_pack_struct_member(
     }
 }
 
+/// Returns true if `var` is a storage buffer or texture that can be written
+/// to, which can be put in a raster order group.
+fn is_writable_resource(module: &crate::Module, var: &crate::GlobalVariable) -> bool {
+    match var.space {
+        crate::AddressSpace::Storage { access } => access.contains(crate::StorageAccess::STORE),
+        crate::AddressSpace::Handle => matches!(
+            module.types[var.ty].inner,
+            crate::TypeInner::Image {
+                class: crate::ImageClass::Storage { access, .. },
+                ..
+            } if access.contains(crate::StorageAccess::STORE)
+        ),
+        _ => false,
+    }
+}
+
 fn needs_array_length(ty: Handle<crate::Type>, arena: &crate::UniqueArena<crate::Type>) -> bool {
     match arena[ty].inner {
         crate::TypeInner::Struct { ref members, .. } => {
@@ -422,7 +438,7 @@ impl crate::AddressSpace {
             | Self::WorkGroup
             | Self::PushConstant
             | Self::Handle => true,
-            Self::Function => false,
+            Self::Function | Self::PhysicalStorage { .. } => false,
         }
     }
 
@@ -439,7 +455,7 @@ impl crate::AddressSpace {
             // These translate to `constant` address space, no need for qualifiers.
             Self::Uniform | Self::PushConstant => false,
             // Not applicable.
-            Self::Handle | Self::Function => false,
+            Self::Handle | Self::Function | Self::PhysicalStorage { .. } => false,
         }
     }
 
@@ -447,7 +463,7 @@ impl crate::AddressSpace {
         match self {
             Self::Handle => None,
             Self::Uniform | Self::PushConstant => Some("constant"),
-            Self::Storage { .. } => Some("device"),
+            Self::Storage { .. } | Self::PhysicalStorage { .. } => Some("device"),
             Self::Private | Self::Function => Some("thread"),
             Self::WorkGroup => Some("threadgroup"),
         }
@@ -1834,6 +1850,8 @@ impl<W: Write> Writer<W> {
                     Mf::Unpack2x16snorm => "unpack_snorm2x16_to_float",
                     Mf::Unpack2x16unorm => "unpack_unorm2x16_to_float",
                     Mf::Unpack2x16float => "",
+                    // uniformity
+                    Mf::NonUniform => "",
                 };
 
                 match fun {
@@ -1909,6 +1927,9 @@ impl<W: Write> Writer<W> {
                     write!(self.out, " == 0 || ")?;
                     self.put_expression(arg, context, true)?;
                     write!(self.out, " == -1)")?;
+                } else if fun == Mf::NonUniform {
+                    // Metal has no notion of non-uniform resource indexing.
+                    self.put_expression(arg, context, is_scoped)?;
                 } else if fun == Mf::Unpack2x16float {
                     write!(self.out, "float2(as_type<half2>(")?;
                     self.put_expression(arg, context, false)?;
@@ -1982,6 +2003,30 @@ impl<W: Write> Writer<W> {
                     )?;
                 }
             }
+            crate::Expression::PointerFromAddress { address, pointer } => {
+                // Casting a `gpuAddress` to a pointer is new in MSL 3.0.
+                if context.lang_version < (3, 0) {
+                    return Err(Error::CapabilityNotSupported(
+                        crate::valid::Capabilities::BUFFER_DEVICE_ADDRESS,
+                    ));
+                }
+                let base = match context.module.types[pointer].inner {
+                    crate::TypeInner::Pointer { base, .. } => base,
+                    _ => unreachable!(),
+                };
+                let base_name = TypeContext {
+                    handle: base,
+                    gctx: context.module.to_ctx(),
+                    names: &self.names,
+                    access: crate::StorageAccess::empty(),
+                    binding: None,
+                    first_time: false,
+                };
+                // Pointers are references in the output, so dereference it.
+                write!(self.out, "(*reinterpret_cast<device {base_name}*>(")?;
+                self.put_expression(address, context, true)?;
+                write!(self.out, "))")?;
+            }
             crate::Expression::As {
                 expr,
                 kind,
@@ -2909,6 +2954,10 @@ impl<W: Write> Writer<W> {
                 crate::Statement::Barrier(flags) => {
                     self.write_barrier(flags, level)?;
                 }
+                // Entry points with a critical section put their writable
+                // resources in a raster order group, which orders every
+                // access to them instead.
+                crate::Statement::FragmentInterlock(_) => {}
                 crate::Statement::Store { pointer, value } => {
                     self.put_store(pointer, value, level, context)?
                 }
@@ -2982,6 +3031,11 @@ impl<W: Write> Writer<W> {
                     value,
                     result,
                 } => {
+                    // Metal only has 64-bit `min` and `max` atomics, which don't
+                    // return the previous value.
+                    if context.expression.resolve_type(value).scalar_width() == Some(8) {
+                        return Err(Error::FeatureNotImplemented("64-bit atomics".to_string()));
+                    }
                     write!(self.out, "{level}")?;
                     let res_name = format!("{}{}", back::BAKE_PREFIX, result.index());
                     self.start_baking_expression(result, &context.expression, &res_name)?;
@@ -3931,7 +3985,8 @@ impl<W: Write> Writer<W> {
                         }
                         crate::AddressSpace::Function
                         | crate::AddressSpace::Private
-                        | crate::AddressSpace::WorkGroup => {}
+                        | crate::AddressSpace::WorkGroup
+                        | crate::AddressSpace::PhysicalStorage { .. } => {}
                     }
                 }
                 if supports_array_length {
@@ -4176,6 +4231,13 @@ impl<W: Write> Writer<W> {
             let need_workgroup_variables_initialization =
                 self.need_workgroup_variables_initialization(options, ep, module, fun_info);
 
+            let raster_ordered = ep.function.has_fragment_interlock();
+            if raster_ordered && options.lang_version < (2, 0) {
+                return Err(Error::UnsupportedAttribute(
+                    "raster_order_group".to_string(),
+                ));
+            }
+
             if need_workgroup_variables_initialization && local_invocation_id.is_none() {
                 let separator = if is_first_argument {
                     is_first_argument = false;
@@ -4346,6 +4408,9 @@ impl<W: Write> Writer<W> {
                 if let Some(resolved) = resolved {
                     resolved.try_fmt(&mut self.out)?;
                 }
+                if raster_ordered && is_writable_resource(module, var) {
+                    write!(self.out, " [[raster_order_group(0)]]")?;
+                }
                 if let Some(value) = var.init {
                     write!(self.out, " = ")?;
                     self.put_const_expression(value, module, mod_info)?;
@@ -502,6 +502,12 @@ fn adjust_expr(new_pos: &[Handle<Expression>], expr: &mut Expression) {
         } => {
             adjust(expr);
         }
+        Expression::PointerFromAddress {
+            ref mut address,
+            pointer: _,
+        } => {
+            adjust(address);
+        }
         Expression::ArrayLength(ref mut expr) => {
             adjust(expr);
         }
@@ -707,7 +713,11 @@ fn adjust_stmt(new_pos: &[Handle<Expression>], stmt: &mut Statement) {
                 crate::RayQueryFunction::Terminate => {}
             }
         }
-        Statement::Break | Statement::Continue | Statement::Kill | Statement::Barrier(_) => {}
+        Statement::Break
+        | Statement::Continue
+        | Statement::Kill
+        | Statement::Barrier(_)
+        | Statement::FragmentInterlock(_) => {}
     }
 }
 
@@ -210,6 +210,9 @@ impl<'w> BlockContext<'w> {
                 let arg = &self.ir_function.arguments[index as usize];
                 self.ir_module.types[arg.ty].inner.pointer_space().is_some()
             }
+            // The pointer itself is emitted, but accesses through it are
+            // access chains, just like for variables.
+            crate::Expression::PointerFromAddress { .. } => true,
 
             // The chain rule: if this `Access...`'s `base` operand was
             // previously omitted, then omit this one, too.
@@ -1206,6 +1209,14 @@ impl<'w> BlockContext<'w> {
                     Mf::Unpack2x16float => MathOp::Ext(spirv::GLOp::UnpackHalf2x16),
                     Mf::Unpack2x16unorm => MathOp::Ext(spirv::GLOp::UnpackUnorm2x16),
                     Mf::Unpack2x16snorm => MathOp::Ext(spirv::GLOp::UnpackSnorm2x16),
+                    // Accesses through the result are decorated `NonUniform`, since
+                    // the analyzer treats it as non-uniform.
+                    Mf::NonUniform => MathOp::Custom(Instruction::unary(
+                        spirv::Op::CopyObject,
+                        result_type_id,
+                        id,
+                        arg0_id,
+                    )),
                 };
 
                 block.body.push(match math_op {
@@ -1229,13 +1240,14 @@ impl<'w> BlockContext<'w> {
                             match *self.fun_info[pointer].ty.inner_with(&self.ir_module.types) {
                                 crate::TypeInner::Pointer { base, space } => {
                                     match self.ir_module.types[base].inner {
-                                        crate::TypeInner::Atomic { .. } => Some(space),
+                                        crate::TypeInner::Atomic(scalar) => Some((space, scalar)),
                                         _ => None,
                                     }
                                 }
                                 _ => None,
                             };
-                        let instruction = if let Some(space) = atomic_space {
+                        let instruction = if let Some((space, scalar)) = atomic_space {
+                            self.require_atomic_scalar(scalar)?;
                             let (semantics, scope) = space.to_spirv_semantics_and_scope();
                             let scope_constant_id = self.get_scope_constant(scope as u32);
                             let semantics_id = self.get_index_constant(semantics.bits());
@@ -1247,7 +1259,10 @@ impl<'w> BlockContext<'w> {
                                 semantics_id,
                             )
                         } else {
-                            Instruction::load(result_type_id, id, pointer_id, None)
+                            let mut instruction =
+                                Instruction::load(result_type_id, id, pointer_id, None);
+                            instruction.add_alignment(self.physical_storage_alignment(pointer));
+                            instruction
                         };
                         block.body.push(instruction);
                         id
@@ -1282,6 +1297,16 @@ impl<'w> BlockContext<'w> {
             | crate::Expression::RayQueryProceedResult
             | crate::Expression::SubgroupBallotResult
             | crate::Expression::SubgroupOperationResult { .. } => self.cached[expr_handle],
+            crate::Expression::PointerFromAddress { address, .. } => {
+                let id = self.gen_id();
+                block.body.push(Instruction::unary(
+                    spirv::Op::ConvertUToPtr,
+                    result_type_id,
+                    id,
+                    self.cached[address],
+                ));
+                id
+            }
             crate::Expression::As {
                 expr,
                 kind,
@@ -1693,6 +1718,9 @@ impl<'w> BlockContext<'w> {
                 crate::Expression::FunctionArgument(index) => {
                     break self.function.parameter_id(index);
                 }
+                crate::Expression::PointerFromAddress { .. } => {
+                    break self.cached[expr_handle];
+                }
                 ref other => unimplemented!("Unexpected pointer expression {:?}", other),
             }
         };
@@ -1734,6 +1762,29 @@ impl<'w> BlockContext<'w> {
         Ok(expr_pointer)
     }
 
+    /// Return the alignment to declare on loads and stores through `pointer`,
+    /// if it is in the [`PhysicalStorage`] address space.
+    ///
+    /// [`PhysicalStorage`]: crate::AddressSpace::PhysicalStorage
+    fn physical_storage_alignment(&self, pointer: Handle<crate::Expression>) -> Option<u32> {
+        let types = &self.ir_module.types;
+        match *self.fun_info[pointer].ty.inner_with(types) {
+            crate::TypeInner::Pointer {
+                base,
+                space: crate::AddressSpace::PhysicalStorage { .. },
+            } => Some(helpers::physical_storage_alignment(
+                &types[base].inner,
+                types,
+            )),
+            crate::TypeInner::ValuePointer {
+                scalar,
+                space: crate::AddressSpace::PhysicalStorage { .. },
+                ..
+            } => Some(scalar.width as u32),
+            _ => None,
+        }
+    }
+
     /// Build the instructions for matrix - matrix column operations
     #[allow(clippy::too_many_arguments)]
     fn write_matrix_matrix_column_op(
@@ -1918,6 +1969,9 @@ impl<'w> BlockContext<'w> {
                     loc.line_number,
                     loc.line_position,
                 ));
+                if let Some(line) = self.writer.debug_line(*span, debug_info.source_code) {
+                    block.body.push(line);
+                }
             };
             match *statement {
                 crate::Statement::Emit(ref range) => {
@@ -1925,6 +1979,9 @@ impl<'w> BlockContext<'w> {
                         // omit const expressions as we've already cached those
                         if !self.expression_constness.is_const(handle) {
                             self.cache_expression_value(handle, &mut block)?;
+                            if let Some(debug_info) = debug_info {
+                                self.write_debug_value(handle, &mut block, debug_info);
+                            }
                         }
                     }
                 }
@@ -2101,7 +2158,10 @@ impl<'w> BlockContext<'w> {
                             debug_info.source_file_id,
                             loc.line_number,
                             loc.line_position,
-                        ))
+                        ));
+                        if let Some(line) = self.writer.debug_line(*span, debug_info.source_code) {
+                            block.body.push(line);
+                        }
                     }
                     block.body.push(Instruction::loop_merge(
                         merge_id,
@@ -2188,6 +2248,14 @@ impl<'w> BlockContext<'w> {
                 crate::Statement::Barrier(flags) => {
                     self.writer.write_barrier(flags, &mut block);
                 }
+                crate::Statement::FragmentInterlock(interlock) => {
+                    block.body.push(match interlock {
+                        crate::FragmentInterlock::Begin => {
+                            Instruction::begin_invocation_interlock()
+                        }
+                        crate::FragmentInterlock::End => Instruction::end_invocation_interlock(),
+                    });
+                }
                 crate::Statement::Store { pointer, value } => {
                     let value_id = self.cached[value];
                     match self.write_expression_pointer(pointer, &mut block, None)? {
@@ -2198,13 +2266,14 @@ impl<'w> BlockContext<'w> {
                             {
                                 crate::TypeInner::Pointer { base, space } => {
                                     match self.ir_module.types[base].inner {
-                                        crate::TypeInner::Atomic { .. } => Some(space),
+                                        crate::TypeInner::Atomic(scalar) => Some((space, scalar)),
                                         _ => None,
                                     }
                                 }
                                 _ => None,
                             };
-                            let instruction = if let Some(space) = atomic_space {
+                            let instruction = if let Some((space, scalar)) = atomic_space {
+                                self.require_atomic_scalar(scalar)?;
                                 let (semantics, scope) = space.to_spirv_semantics_and_scope();
                                 let scope_constant_id = self.get_scope_constant(scope as u32);
                                 let semantics_id = self.get_index_constant(semantics.bits());
@@ -2215,7 +2284,10 @@ impl<'w> BlockContext<'w> {
                                     value_id,
                                 )
                             } else {
-                                Instruction::store(pointer_id, value_id, None)
+                                let mut instruction =
+                                    Instruction::store(pointer_id, value_id, None);
+                                instruction.add_alignment(self.physical_storage_alignment(pointer));
+                                instruction
                             };
                             block.body.push(instruction);
                         }
@@ -2295,6 +2367,11 @@ impl<'w> BlockContext<'w> {
                         .inner_with(&self.ir_module.types)
                         .pointer_space()
                         .unwrap();
+                    if let crate::TypeInner::Scalar(scalar) =
+                        *self.fun_info[value].ty.inner_with(&self.ir_module.types)
+                    {
+                        self.require_atomic_scalar(scalar)?;
+                    }
                     let (semantics, scope) = space.to_spirv_semantics_and_scope();
                     let scope_constant_id = self.get_scope_constant(scope as u32);
                     let semantics_id = self.get_index_constant(semantics.bits());
@@ -48,6 +48,36 @@ pub(super) const fn map_storage_class(space: crate::AddressSpace) -> spirv::Stor
         crate::AddressSpace::Uniform => spirv::StorageClass::Uniform,
         crate::AddressSpace::WorkGroup => spirv::StorageClass::Workgroup,
         crate::AddressSpace::PushConstant => spirv::StorageClass::PushConstant,
+        crate::AddressSpace::PhysicalStorage { .. } => spirv::StorageClass::PhysicalStorageBuffer,
+    }
+}
+
+/// Return the alignment to declare on loads and stores of `ty` through a
+/// pointer in the [`PhysicalStorage`] address space.
+///
+/// Vulkan requires these to be decorated with the `Aligned` memory operand.
+/// Use the size of the widest scalar in `ty`, which addresses of values of
+/// that type are always aligned to.
+///
+/// [`PhysicalStorage`]: crate::AddressSpace::PhysicalStorage
+pub(super) fn physical_storage_alignment(
+    ty: &crate::TypeInner,
+    arena: &UniqueArena<crate::Type>,
+) -> u32 {
+    match *ty {
+        crate::TypeInner::Scalar(scalar)
+        | crate::TypeInner::Vector { scalar, .. }
+        | crate::TypeInner::Matrix { scalar, .. }
+        | crate::TypeInner::Atomic(scalar) => scalar.width as u32,
+        crate::TypeInner::Array { base, .. } => {
+            physical_storage_alignment(&arena[base].inner, arena)
+        }
+        crate::TypeInner::Struct { ref members, .. } => members
+            .iter()
+            .map(|member| physical_storage_alignment(&arena[member.ty].inner, arena))
+            .max()
+            .unwrap_or(1),
+        _ => 1,
     }
 }
 
@@ -73,7 +103,9 @@ impl crate::AddressSpace {
         self,
     ) -> (spirv::MemorySemantics, spirv::Scope) {
         match self {
-            Self::Storage { .. } => (spirv::MemorySemantics::UNIFORM_MEMORY, spirv::Scope::Device),
+            Self::Storage { .. } | Self::PhysicalStorage { .. } => {
+                (spirv::MemorySemantics::UNIFORM_MEMORY, spirv::Scope::Device)
+            }
             Self::WorkGroup => (
                 spirv::MemorySemantics::WORKGROUP_MEMORY,
                 spirv::Scope::Workgroup,
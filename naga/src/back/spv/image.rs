@@ -852,18 +852,30 @@ impl<'w> BlockContext<'w> {
         let sampled_image_type_id =
             self.get_type_id(LookupType::Local(LocalType::SampledImage { image_type_id }));
 
-        let sampler_id = self.get_handle_id(sampler);
         let coordinates_id = self
             .write_image_coordinates(coordinate, array_index, block)?
             .value_id;
 
-        let sampled_image_id = self.gen_id();
-        block.body.push(Instruction::sampled_image(
-            sampled_image_type_id,
-            sampled_image_id,
-            image_id,
-            sampler_id,
-        ));
+        // Images declared as combined image samplers are sampled with their own sampler.
+        let combined_id = match self.ir_function.expressions[image] {
+            crate::Expression::GlobalVariable(handle) => {
+                self.writer.global_variables[handle.index()].sampled_image_id
+            }
+            _ => 0,
+        };
+        let sampled_image_id = if combined_id != 0 {
+            combined_id
+        } else {
+            let sampler_id = self.get_handle_id(sampler);
+            let sampled_image_id = self.gen_id();
+            block.body.push(Instruction::sampled_image(
+                sampled_image_type_id,
+                sampled_image_id,
+                image_id,
+                sampler_id,
+            ));
+            sampled_image_id
+        };
         let id = self.gen_id();
 
         let depth_id = depth_ref.map(|handle| self.cached[handle]);
@@ -28,6 +28,13 @@ impl super::Instruction {
         instruction
     }
 
+    pub(super) fn string_bytes(bytes: &[u8], id: Word) -> Self {
+        let mut instruction = Self::new(Op::String);
+        instruction.set_result(id);
+        instruction.add_operands(helpers::str_bytes_to_words(bytes));
+        instruction
+    }
+
     pub(super) fn source(
         source_language: spirv::SourceLanguage,
         version: u32,
@@ -175,6 +182,24 @@ impl super::Instruction {
         instruction
     }
 
+    pub(super) fn debug_info_ext_inst(
+        set_id: Word,
+        op: super::non_semantic::DebugOp,
+        result_type_id: Word,
+        id: Word,
+        operands: &[Word],
+    ) -> Self {
+        let mut instruction = Self::new(Op::ExtInst);
+        instruction.set_type(result_type_id);
+        instruction.set_result(id);
+        instruction.add_operand(set_id);
+        instruction.add_operand(op as u32);
+        for operand in operands {
+            instruction.add_operand(*operand)
+        }
+        instruction
+    }
+
     //
     //  Mode-Setting Instructions
     //
@@ -482,6 +507,15 @@ impl super::Instruction {
         instruction
     }
 
+    /// Add the `Aligned` memory operand to a load or store, if `alignment`
+    /// is given. Accesses through physical pointers require it.
+    pub(super) fn add_alignment(&mut self, alignment: Option<u32>) {
+        if let Some(alignment) = alignment {
+            self.add_operand(spirv::MemoryAccess::ALIGNED.bits());
+            self.add_operand(alignment);
+        }
+    }
+
     pub(super) fn atomic_load(
         result_type_id: Word,
         id: Word,
@@ -625,6 +659,14 @@ impl super::Instruction {
         instruction
     }
 
+    pub(super) fn image(result_type_id: Word, id: Word, sampled_image: Word) -> Self {
+        let mut instruction = Self::new(Op::Image);
+        instruction.set_type(result_type_id);
+        instruction.set_result(id);
+        instruction.add_operand(sampled_image);
+        instruction
+    }
+
     pub(super) fn image_sample(
         result_type_id: Word,
         id: Word,
@@ -1074,6 +1116,14 @@ impl super::Instruction {
         instruction
     }
 
+    pub(super) const fn begin_invocation_interlock() -> Self {
+        Self::new(Op::BeginInvocationInterlockEXT)
+    }
+
+    pub(super) const fn end_invocation_interlock() -> Self {
+        Self::new(Op::EndInvocationInterlockEXT)
+    }
+
     // Group Instructions
 
     pub(super) fn group_non_uniform_ballot(
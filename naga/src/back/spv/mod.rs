@@ -10,6 +10,7 @@ mod image;
 mod index;
 mod instructions;
 mod layout;
+mod non_semantic;
 mod ray;
 mod recyclable;
 mod selection;
@@ -486,6 +487,12 @@ struct GlobalVariable {
     /// It is then used for all the global ops, such as `OpImageSample`.
     handle_id: Word,
 
+    /// For images declared as combined image samplers, this ID is recorded in
+    /// the function prelude block as `OpLoad` of the variable, and `handle_id`
+    /// is the `OpImage` of it. Sampling uses this instead of combining
+    /// `handle_id` with a sampler. Otherwise, this is 0.
+    sampled_image_id: Word,
+
     /// Actual ID used to access this variable.
     /// For wrapped buffer variables, this ID is `OpAccessChain` into the
     /// wrapper. Otherwise, the same as `var_id`.
@@ -505,6 +512,7 @@ impl GlobalVariable {
         Self {
             var_id: 0,
             handle_id: 0,
+            sampled_image_id: 0,
             access_id: 0,
         }
     }
@@ -513,6 +521,7 @@ impl GlobalVariable {
         Self {
             var_id: id,
             handle_id: 0,
+            sampled_image_id: 0,
             access_id: 0,
         }
     }
@@ -520,6 +529,7 @@ impl GlobalVariable {
     /// Prepare `self` for use within a single function.
     fn reset_for_function(&mut self) {
         self.handle_id = 0;
+        self.sampled_image_id = 0;
         self.access_id = 0;
     }
 }
@@ -592,6 +602,10 @@ struct BlockContext<'w> {
 
     /// Tracks the constness of `Expression`s residing in `self.ir_function.expressions`
     expression_constness: ExpressionConstnessTracker,
+
+    /// The `DebugFunction` of the function, if the writer emits
+    /// `NonSemantic.Shader.DebugInfo.100` instructions.
+    debug_scope_id: Option<Word>,
 }
 
 impl BlockContext<'_> {
@@ -624,6 +638,15 @@ impl BlockContext<'_> {
         self.writer
             .get_pointer_id(&self.ir_module.types, handle, class)
     }
+
+    /// Require the capabilities needed to operate atomically on `scalar` values.
+    fn require_atomic_scalar(&mut self, scalar: crate::Scalar) -> Result<(), Error> {
+        if scalar.width == 8 {
+            self.writer
+                .require_any("64-bit integer atomics", &[spirv::Capability::Int64Atomics])?;
+        }
+        Ok(())
+    }
 }
 
 #[derive(Clone, Copy, Default)]
@@ -673,6 +696,11 @@ pub struct Writer {
 
     gl450_ext_inst_id: Word,
 
+    /// The state of the `NonSemantic.Shader.DebugInfo.100` instructions, if
+    /// [`WriterFlags::NON_SEMANTIC_DEBUG_INFO`] applies to the module being
+    /// written.
+    non_semantic_debug_info: Option<non_semantic::NonSemanticDebugInfo>,
+
     // Just a temporary list of SPIR-V ids
     temp_list: Vec<Word>,
 }
@@ -693,6 +721,14 @@ bitflags::bitflags! {
         const FORCE_POINT_SIZE = 0x8;
         /// Clamp `BuiltIn::FragDepth` output between 0 and 1.
         const CLAMP_FRAG_DEPTH = 0x10;
+        /// Along with `DEBUG` and [`Options::debug_info`], emit
+        /// `NonSemantic.Shader.DebugInfo.100` instructions describing the
+        /// source, functions, local variables and the line of every statement,
+        /// which shader debuggers like RenderDoc need to step through the
+        /// source and show variables.
+        ///
+        /// Requires `VK_KHR_shader_non_semantic_info` or Vulkan 1.3.
+        const NON_SEMANTIC_DEBUG_INFO = 0x20;
     }
 }
 
@@ -702,6 +738,12 @@ bitflags::bitflags! {
 pub struct BindingInfo {
     /// If the binding is an unsized binding array, this overrides the size.
     pub binding_array_size: Option<u32>,
+    /// If the binding is an image, declare it as a combined image sampler.
+    ///
+    /// Sampling the image uses the sampler bound with it, such as an immutable
+    /// sampler with a Y'CbCr conversion, instead of the sampler in the shader.
+    #[cfg_attr(feature = "deserialize", serde(default))]
+    pub combined_image_sampler: bool,
 }
 
 // Using `BTreeMap` instead of `HashMap` so that we can hash itself.
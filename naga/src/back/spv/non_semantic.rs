@@ -0,0 +1,429 @@
+/*!
+Writing [`NonSemantic.Shader.DebugInfo.100`] instructions.
+
+Unlike `OpLine` and `OpName`, these instructions tell shader debuggers which
+function and scope every instruction belongs to, and which local variables
+hold which values, so they can step through the source and show variables.
+They are only written with [`WriterFlags::NON_SEMANTIC_DEBUG_INFO`].
+
+All numeric operands of these instructions are ids of `OpConstant`s.
+
+[`NonSemantic.Shader.DebugInfo.100`]: https://github.khronos.org/SPIRV-Registry/nonsemantic/NonSemantic.Shader.DebugInfo.100.html
+[`WriterFlags::NON_SEMANTIC_DEBUG_INFO`]: super::WriterFlags::NON_SEMANTIC_DEBUG_INFO
+*/
+
+use super::{block::DebugInfoInner, helpers, Block, BlockContext, Function, Instruction, Writer};
+use crate::{arena::Handle, Span};
+use spirv::Word;
+
+/// The `NonSemantic.Shader.DebugInfo.100` instructions we write.
+#[derive(Clone, Copy, Debug)]
+pub(super) enum DebugOp {
+    InfoNone = 0,
+    CompilationUnit = 1,
+    TypeBasic = 2,
+    TypeVector = 6,
+    TypeFunction = 8,
+    Function = 20,
+    Scope = 23,
+    LocalVariable = 26,
+    Declare = 28,
+    Value = 29,
+    Expression = 31,
+    Source = 35,
+    FunctionDefinition = 101,
+    SourceContinued = 102,
+    Line = 103,
+    TypeMatrix = 108,
+}
+
+/// The version of the instruction set.
+const VERSION: Word = 100;
+
+/// The version of DWARF the instructions correspond to.
+const DWARF_VERSION: Word = 5;
+
+/// The `FlagIsLocal` of `DebugInfoFlags`.
+const FLAG_IS_LOCAL: Word = 0x4;
+
+/// Values of `DebugBaseTypeAttributeEncoding`.
+const ENCODING_BOOLEAN: Word = 2;
+const ENCODING_FLOAT: Word = 3;
+const ENCODING_SIGNED: Word = 4;
+const ENCODING_UNSIGNED: Word = 6;
+
+/// The types that debug types are written for.
+#[derive(Clone, Copy, Debug, Hash, Eq, PartialEq)]
+enum DebugType {
+    Scalar(crate::Scalar),
+    Vector {
+        size: crate::VectorSize,
+        scalar: crate::Scalar,
+    },
+    Matrix {
+        columns: crate::VectorSize,
+        rows: crate::VectorSize,
+        scalar: crate::Scalar,
+    },
+}
+
+pub(super) struct NonSemanticDebugInfo {
+    /// The `OpExtInstImport` of the instruction set.
+    set_id: Word,
+
+    /// The `DebugSource` of the module's source code.
+    source_id: Word,
+
+    compilation_unit_id: Word,
+
+    /// An empty `DebugExpression`, for `DebugDeclare` and `DebugValue`.
+    expression_id: Word,
+
+    /// A `DebugInfoNone`, standing in for the types we don't describe.
+    none_id: Word,
+
+    /// The `OpString`s of the instructions' operands.
+    ///
+    /// These are written before all `OpName`s, which have to follow every
+    /// `OpString`.
+    strings: Vec<Instruction>,
+
+    types: crate::FastHashMap<DebugType, Word>,
+}
+
+impl NonSemanticDebugInfo {
+    pub(super) fn strings(&self) -> &[Instruction] {
+        &self.strings
+    }
+}
+
+impl Writer {
+    /// Starts writing `NonSemantic.Shader.DebugInfo.100` instructions for the
+    /// module whose source is described by `debug_info`.
+    pub(super) fn write_non_semantic_debug_info(&mut self, debug_info: &DebugInfoInner) {
+        self.use_extension("SPV_KHR_non_semantic_info");
+        let set_id = self.id_gen.next();
+        Instruction::ext_inst_import(set_id, "NonSemantic.Shader.DebugInfo.100")
+            .to_words(&mut self.logical_layout.ext_inst_imports);
+
+        self.non_semantic_debug_info = Some(NonSemanticDebugInfo {
+            set_id,
+            source_id: 0,
+            compilation_unit_id: 0,
+            expression_id: 0,
+            none_id: 0,
+            strings: Vec::new(),
+            types: crate::FastHashMap::default(),
+        });
+
+        // Long sources are split like they are for `OpSource`.
+        let chunk_ids: Vec<Word> =
+            helpers::string_to_byte_chunks(debug_info.source_code, u16::MAX as usize)
+                .into_iter()
+                .map(|chunk| {
+                    let id = self.id_gen.next();
+                    self.debug_state()
+                        .strings
+                        .push(Instruction::string_bytes(chunk, id));
+                    id
+                })
+                .collect();
+        let mut source_operands = vec![debug_info.source_file_id];
+        source_operands.extend(chunk_ids.first());
+        let source_id = self.write_debug_declaration(DebugOp::Source, &source_operands);
+        for &chunk_id in chunk_ids.iter().skip(1) {
+            self.write_debug_declaration(DebugOp::SourceContinued, &[chunk_id]);
+        }
+
+        let operands = [
+            self.get_index_constant(VERSION),
+            self.get_index_constant(DWARF_VERSION),
+            source_id,
+            self.get_index_constant(spirv::SourceLanguage::Unknown as Word),
+        ];
+        let compilation_unit_id = self.write_debug_declaration(DebugOp::CompilationUnit, &operands);
+        let expression_id = self.write_debug_declaration(DebugOp::Expression, &[]);
+        let none_id = self.write_debug_declaration(DebugOp::InfoNone, &[]);
+
+        let state = self.debug_state();
+        state.source_id = source_id;
+        state.compilation_unit_id = compilation_unit_id;
+        state.expression_id = expression_id;
+        state.none_id = none_id;
+    }
+
+    /// Returns the state of the instructions, which have to be enabled.
+    fn debug_state(&mut self) -> &mut NonSemanticDebugInfo {
+        self.non_semantic_debug_info
+            .as_mut()
+            .expect("NonSemantic.Shader.DebugInfo.100 instructions aren't enabled")
+    }
+
+    /// Returns a new instruction `op` of the instruction set.
+    pub(super) fn debug_instruction(&mut self, op: DebugOp, operands: &[Word]) -> Instruction {
+        let set_id = self.debug_state().set_id;
+        let id = self.id_gen.next();
+        Instruction::debug_info_ext_inst(set_id, op, self.void_type, id, operands)
+    }
+
+    /// Writes a new instruction `op` of the instruction set to the module's
+    /// declarations, and returns its id.
+    fn write_debug_declaration(&mut self, op: DebugOp, operands: &[Word]) -> Word {
+        let instruction = self.debug_instruction(op, operands);
+        let id = instruction.result_id.unwrap();
+        instruction.to_words(&mut self.logical_layout.declarations);
+        id
+    }
+
+    /// Returns the id of a new `OpString` of `string`.
+    fn debug_string(&mut self, string: &str) -> Word {
+        let id = self.id_gen.next();
+        self.debug_state()
+            .strings
+            .push(Instruction::string(string, id));
+        id
+    }
+
+    /// Returns the id of the debug type of values of type `inner`.
+    ///
+    /// Only scalars, vectors and matrices are described, other types are
+    /// `DebugInfoNone`.
+    fn debug_type(&mut self, inner: &crate::TypeInner) -> Word {
+        let none_id = self.debug_state().none_id;
+        let ty = match *inner {
+            crate::TypeInner::Scalar(scalar) => DebugType::Scalar(scalar),
+            crate::TypeInner::Vector { size, scalar } => DebugType::Vector { size, scalar },
+            crate::TypeInner::Matrix {
+                columns,
+                rows,
+                scalar,
+            } => DebugType::Matrix {
+                columns,
+                rows,
+                scalar,
+            },
+            _ => return none_id,
+        };
+        if let Some(&id) = self.debug_state().types.get(&ty) {
+            return id;
+        }
+
+        let id = match ty {
+            DebugType::Scalar(scalar) => {
+                let (name, encoding) = match scalar.kind {
+                    crate::ScalarKind::Bool => ("bool".to_string(), ENCODING_BOOLEAN),
+                    crate::ScalarKind::Sint => (format!("i{}", scalar.width * 8), ENCODING_SIGNED),
+                    crate::ScalarKind::Uint => {
+                        (format!("u{}", scalar.width * 8), ENCODING_UNSIGNED)
+                    }
+                    crate::ScalarKind::Float => (format!("f{}", scalar.width * 8), ENCODING_FLOAT),
+                    crate::ScalarKind::AbstractInt | crate::ScalarKind::AbstractFloat => {
+                        return none_id
+                    }
+                };
+                let operands = [
+                    self.debug_string(&name),
+                    self.get_index_constant(scalar.width as Word * 8),
+                    self.get_index_constant(encoding),
+                    self.get_index_constant(0),
+                ];
+                self.write_debug_declaration(DebugOp::TypeBasic, &operands)
+            }
+            DebugType::Vector { size, scalar } => {
+                let operands = [
+                    self.debug_type(&crate::TypeInner::Scalar(scalar)),
+                    self.get_index_constant(size as Word),
+                ];
+                self.write_debug_declaration(DebugOp::TypeVector, &operands)
+            }
+            DebugType::Matrix {
+                columns,
+                rows,
+                scalar,
+            } => {
+                let operands = [
+                    self.debug_type(&crate::TypeInner::Vector { size: rows, scalar }),
+                    self.get_index_constant(columns as Word),
+                    self.get_constant_scalar(crate::Literal::Bool(true)),
+                ];
+                self.write_debug_declaration(DebugOp::TypeMatrix, &operands)
+            }
+        };
+        self.debug_state().types.insert(ty, id);
+        id
+    }
+
+    /// Returns a `DebugLine` for `span` of `source`, if the instructions are
+    /// enabled.
+    pub(super) fn debug_line(&mut self, span: Span, source: &str) -> Option<Instruction> {
+        let source_id = self.non_semantic_debug_info.as_ref()?.source_id;
+        let end = span.to_range()?.end;
+        let start = span.location(source);
+        let end = Span::new(end as u32, end as u32).location(source);
+        let operands = [
+            source_id,
+            self.get_index_constant(start.line_number),
+            self.get_index_constant(end.line_number),
+            self.get_index_constant(start.line_position),
+            self.get_index_constant(end.line_position),
+        ];
+        Some(self.debug_instruction(DebugOp::Line, &operands))
+    }
+
+    /// Writes the `DebugFunction` of `ir_function`, and returns its id.
+    pub(super) fn write_debug_function(
+        &mut self,
+        ir_module: &crate::Module,
+        ir_function: &crate::Function,
+        source: &str,
+    ) -> Word {
+        let mut type_operands = vec![self.get_index_constant(0)];
+        let return_type = match ir_function.result {
+            Some(ref result) => self.debug_type(&ir_module.types[result.ty].inner),
+            None => self.void_type,
+        };
+        type_operands.push(return_type);
+        for argument in ir_function.arguments.iter() {
+            type_operands.push(self.debug_type(&ir_module.types[argument.ty].inner));
+        }
+        let function_type = self.write_debug_declaration(DebugOp::TypeFunction, &type_operands);
+
+        // Functions have no span of their own, so use their first statement's.
+        let span = ir_function
+            .body
+            .span_iter()
+            .map(|(_, &span)| span)
+            .find(Span::is_defined)
+            .unwrap_or_default();
+        let location = span.location(source);
+        let name = self.debug_string(ir_function.name.as_deref().unwrap_or_default());
+        let line = self.get_index_constant(location.line_number);
+        let state = self.debug_state();
+        let (source_id, compilation_unit_id) = (state.source_id, state.compilation_unit_id);
+        let operands = [
+            name,
+            function_type,
+            source_id,
+            line,
+            self.get_index_constant(location.line_position),
+            compilation_unit_id,
+            name,
+            self.get_index_constant(0),
+            line,
+        ];
+        self.write_debug_declaration(DebugOp::Function, &operands)
+    }
+
+    /// Writes a `DebugLocalVariable` called `name` of type `inner`, declared at
+    /// `span` of `source` in the scope `scope_id`, and returns its id.
+    fn write_debug_local_variable(
+        &mut self,
+        name: &str,
+        inner: &crate::TypeInner,
+        span: Span,
+        scope_id: Word,
+        source: &str,
+    ) -> Word {
+        let location = span.location(source);
+        let source_id = self.debug_state().source_id;
+        let operands = [
+            self.debug_string(name),
+            self.debug_type(inner),
+            source_id,
+            self.get_index_constant(location.line_number),
+            self.get_index_constant(location.line_position),
+            scope_id,
+            self.get_index_constant(FLAG_IS_LOCAL),
+        ];
+        self.write_debug_declaration(DebugOp::LocalVariable, &operands)
+    }
+
+    /// Puts every block of `function` in the scope `scope_id`, and marks its
+    /// entry block as the definition of the `OpFunction` `function_id`.
+    pub(super) fn write_debug_scopes(
+        &mut self,
+        function: &mut Function,
+        scope_id: Word,
+        function_id: Word,
+    ) {
+        for (index, block) in function.blocks.iter_mut().enumerate() {
+            let mut prologue = vec![self.debug_instruction(DebugOp::Scope, &[scope_id])];
+            if index == 0 {
+                prologue.push(
+                    self.debug_instruction(DebugOp::FunctionDefinition, &[scope_id, function_id]),
+                );
+            }
+            // `OpPhi`s have to come first in their block.
+            let start = block
+                .body
+                .iter()
+                .take_while(|instruction| instruction.op == spirv::Op::Phi)
+                .count();
+            block.body.splice(start..start, prologue);
+        }
+    }
+}
+
+impl BlockContext<'_> {
+    /// Writes a `DebugDeclare` for every named local variable to `block`.
+    pub(super) fn write_debug_declares(&mut self, block: &mut Block, debug_info: &DebugInfoInner) {
+        let Some(scope_id) = self.debug_scope_id else {
+            return;
+        };
+        let (ir_module, ir_function) = (self.ir_module, self.ir_function);
+        for (handle, variable) in ir_function.local_variables.iter() {
+            let Some(ref name) = variable.name else {
+                continue;
+            };
+            let local_variable = self.writer.write_debug_local_variable(
+                name,
+                &ir_module.types[variable.ty].inner,
+                ir_function.local_variables.get_span(handle),
+                scope_id,
+                debug_info.source_code,
+            );
+            let operands = [
+                local_variable,
+                self.function.variables[&handle].id,
+                self.writer.debug_state().expression_id,
+            ];
+            let instruction = self.writer.debug_instruction(DebugOp::Declare, &operands);
+            block.body.push(instruction);
+        }
+    }
+
+    /// Writes a `DebugValue` for `handle` to `block`, if it's a named
+    /// expression whose value has been cached.
+    pub(super) fn write_debug_value(
+        &mut self,
+        handle: Handle<crate::Expression>,
+        block: &mut Block,
+        debug_info: &DebugInfoInner,
+    ) {
+        let Some(scope_id) = self.debug_scope_id else {
+            return;
+        };
+        let (ir_module, ir_function, fun_info) = (self.ir_module, self.ir_function, self.fun_info);
+        let Some(name) = ir_function.named_expressions.get(&handle) else {
+            return;
+        };
+        let value_id = self.cached[handle];
+        if value_id == 0 {
+            return;
+        }
+        let local_variable = self.writer.write_debug_local_variable(
+            name,
+            fun_info[handle].ty.inner_with(&ir_module.types),
+            ir_function.expressions.get_span(handle),
+            scope_id,
+            debug_info.source_code,
+        );
+        let operands = [
+            local_variable,
+            value_id,
+            self.writer.debug_state().expression_id,
+        ];
+        let instruction = self.writer.debug_instruction(DebugOp::Value, &operands);
+        block.body.push(instruction);
+    }
+}
@@ -77,6 +77,7 @@ impl Writer {
             binding_map: options.binding_map.clone(),
             saved_cached: CachedExpressions::default(),
             gl450_ext_inst_id,
+            non_semantic_debug_info: None,
             temp_list: Vec::new(),
         })
     }
@@ -112,6 +113,7 @@ impl Writer {
             id_gen,
             void_type,
             gl450_ext_inst_id,
+            non_semantic_debug_info: None,
 
             // Recycled:
             capabilities_used: take(&mut self.capabilities_used).recycle(),
@@ -533,6 +535,13 @@ impl Writer {
             }
         }
 
+        let debug_scope_id = match *debug_info {
+            Some(ref debug_info) if self.non_semantic_debug_info.is_some() => {
+                Some(self.write_debug_function(ir_module, ir_function, debug_info.source_code))
+            }
+            _ => None,
+        };
+
         let function_type = self.get_function_type(lookup_function_type);
         function.signature = Some(Instruction::function(
             return_type_id,
@@ -573,9 +582,29 @@ impl Writer {
                     if var.space == crate::AddressSpace::Handle {
                         let var_type_id = self.get_type_id(LookupType::Handle(var.ty));
                         let id = self.id_gen.next();
-                        prelude
-                            .body
-                            .push(Instruction::load(var_type_id, id, gv.var_id, None));
+                        if self.is_combined_image_sampler(ir_module, var) {
+                            let sampled_image_type_id =
+                                self.get_type_id(LookupType::Local(LocalType::SampledImage {
+                                    image_type_id: var_type_id,
+                                }));
+                            let sampled_image_id = self.id_gen.next();
+                            prelude.body.push(Instruction::load(
+                                sampled_image_type_id,
+                                sampled_image_id,
+                                gv.var_id,
+                                None,
+                            ));
+                            prelude.body.push(Instruction::image(
+                                var_type_id,
+                                id,
+                                sampled_image_id,
+                            ));
+                            gv.sampled_image_id = sampled_image_id;
+                        } else {
+                            prelude
+                                .body
+                                .push(Instruction::load(var_type_id, id, gv.var_id, None));
+                        }
                         gv.access_id = gv.var_id;
                         gv.handle_id = id;
                     } else if global_needs_wrapper(ir_module, var) {
@@ -618,6 +647,7 @@ impl Writer {
             expression_constness: super::ExpressionConstnessTracker::from_arena(
                 &ir_function.expressions,
             ),
+            debug_scope_id,
         };
 
         // fill up the pre-emitted and const expressions
@@ -627,6 +657,9 @@ impl Writer {
                 || context.expression_constness.is_const(handle)
             {
                 context.cache_expression_value(handle, &mut prelude)?;
+                if let Some(ref debug_info) = *debug_info {
+                    context.write_debug_value(handle, &mut prelude, debug_info);
+                }
             }
         }
 
@@ -670,6 +703,10 @@ impl Writer {
             }
         }
 
+        if let Some(ref debug_info) = *debug_info {
+            context.write_debug_declares(&mut prelude, debug_info);
+        }
+
         let next_id = context.gen_id();
 
         context
@@ -719,6 +756,10 @@ impl Writer {
         self.saved_cached = cached;
         self.temp_list = temp_list;
 
+        if let Some(scope_id) = debug_scope_id {
+            self.write_debug_scopes(&mut function, scope_id, function_id);
+        }
+
         function.to_words(&mut self.logical_layout.function_definitions);
         Instruction::function_end().to_words(&mut self.logical_layout.function_definitions);
 
@@ -760,6 +801,17 @@ impl Writer {
             crate::ShaderStage::Vertex => spirv::ExecutionModel::Vertex,
             crate::ShaderStage::Fragment => {
                 self.write_execution_mode(function_id, spirv::ExecutionMode::OriginUpperLeft)?;
+                if entry_point.function.has_fragment_interlock() {
+                    self.require_any(
+                        "fragment shader interlock",
+                        &[spirv::Capability::FragmentShaderPixelInterlockEXT],
+                    )?;
+                    self.use_extension("SPV_EXT_fragment_shader_interlock");
+                    self.write_execution_mode(
+                        function_id,
+                        spirv::ExecutionMode::PixelInterlockOrderedEXT,
+                    )?;
+                }
                 if let Some(ref result) = entry_point.function.result {
                     if contains_builtin(
                         result.binding.as_ref(),
@@ -878,6 +930,16 @@ impl Writer {
             crate::TypeInner::RayQuery => {
                 self.require_any("Ray Query", &[spirv::Capability::RayQueryKHR])?;
             }
+            crate::TypeInner::Pointer {
+                space: crate::AddressSpace::PhysicalStorage { .. },
+                ..
+            } => {
+                self.require_any(
+                    "buffer device address",
+                    &[spirv::Capability::PhysicalStorageBufferAddresses],
+                )?;
+                self.use_extension("SPV_KHR_physical_storage_buffer");
+            }
             _ => {}
         }
         Ok(())
@@ -1563,6 +1625,14 @@ impl Writer {
                     Bi::InstanceIndex => BuiltIn::InstanceIndex,
                     Bi::PointSize => BuiltIn::PointSize,
                     Bi::VertexIndex => BuiltIn::VertexIndex,
+                    Bi::ViewportIndex => {
+                        self.require_any(
+                            "`viewport_index` built-in",
+                            &[spirv::Capability::ShaderViewportIndexLayerEXT],
+                        )?;
+                        self.use_extension("SPV_EXT_shader_viewport_index_layer");
+                        BuiltIn::ViewportIndex
+                    }
                     // fragment
                     Bi::FragDepth => BuiltIn::FragDepth,
                     Bi::PointCoord => BuiltIn::PointCoord,
@@ -1708,6 +1778,7 @@ impl Writer {
 
             if let Some(&BindingInfo {
                 binding_array_size: Some(remapped_binding_array_size),
+                ..
             }) = self.binding_map.get(res_binding)
             {
                 if let crate::TypeInner::BindingArray { base, .. } =
@@ -1750,6 +1821,16 @@ impl Writer {
             Instruction::type_pointer(pointer_type_id, class, wrapper_type_id)
                 .to_words(&mut self.logical_layout.declarations);
 
+            pointer_type_id
+        } else if self.is_combined_image_sampler(ir_module, global_variable) {
+            let sampled_image_type_id =
+                self.get_type_id(LookupType::Local(LocalType::SampledImage {
+                    image_type_id: inner_type_id,
+                }));
+            let pointer_type_id = self.id_gen.next();
+            Instruction::type_pointer(pointer_type_id, class, sampled_image_type_id)
+                .to_words(&mut self.logical_layout.declarations);
+
             pointer_type_id
         } else {
             // This is a global variable in the Storage address space. The only
@@ -1786,6 +1867,25 @@ impl Writer {
         Ok(id)
     }
 
+    /// Returns `true` if `global_variable` is an image that the binding map
+    /// declares as a combined image sampler.
+    fn is_combined_image_sampler(
+        &self,
+        ir_module: &crate::Module,
+        global_variable: &crate::GlobalVariable,
+    ) -> bool {
+        let combined = global_variable
+            .binding
+            .as_ref()
+            .and_then(|binding| self.binding_map.get(binding))
+            .map_or(false, |info| info.combined_image_sampler);
+        combined
+            && matches!(
+                ir_module.types[global_variable.ty].inner,
+                crate::TypeInner::Image { .. }
+            )
+    }
+
     /// Write the necessary decorations for a struct member.
     ///
     /// Emit decorations for the `index`'th member of the struct type
@@ -1944,6 +2044,10 @@ impl Writer {
                     0,
                     &debug_info_inner,
                 ));
+
+                if self.flags.contains(WriterFlags::NON_SEMANTIC_DEBUG_INFO) {
+                    self.write_non_semantic_debug_info(debug_info_inner.as_ref().unwrap());
+                }
             }
         }
 
@@ -2040,7 +2144,14 @@ impl Writer {
                 .to_words(&mut self.logical_layout.capabilities);
         }
 
-        let addressing_model = spirv::AddressingModel::Logical;
+        let addressing_model = if self
+            .capabilities_used
+            .contains(&spirv::Capability::PhysicalStorageBufferAddresses)
+        {
+            spirv::AddressingModel::PhysicalStorageBuffer64
+        } else {
+            spirv::AddressingModel::Logical
+        };
         let memory_model = spirv::MemoryModel::GLSL450;
         //self.check(addressing_model.required_capabilities())?;
         //self.check(memory_model.required_capabilities())?;
@@ -2049,6 +2160,11 @@ impl Writer {
             .to_words(&mut self.logical_layout.memory_model);
 
         if self.flags.contains(WriterFlags::DEBUG) {
+            if let Some(ref non_semantic_debug_info) = self.non_semantic_debug_info {
+                for string in non_semantic_debug_info.strings() {
+                    string.to_words(&mut self.logical_layout.debugs);
+                }
+            }
             for debug in self.debugs.iter() {
                 debug.to_words(&mut self.logical_layout.debugs);
             }
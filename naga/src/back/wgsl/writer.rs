@@ -929,6 +929,13 @@ impl<W: Write> Writer<W> {
                     writeln!(self.out, "{level}subgroupBarrier();")?;
                 }
             }
+            Statement::FragmentInterlock(interlock) => {
+                let name = match interlock {
+                    crate::FragmentInterlock::Begin => "beginInvocationInterlock",
+                    crate::FragmentInterlock::End => "endInvocationInterlock",
+                };
+                writeln!(self.out, "{level}{name}();")?;
+            }
             Statement::RayQuery { .. } => unreachable!(),
             Statement::SubgroupBallot { result, predicate } => {
                 write!(self.out, "{level}")?;
@@ -1542,6 +1549,13 @@ impl<W: Write> Writer<W> {
                 write!(self.out, "{name}")?;
             }
 
+            Expression::PointerFromAddress { address, pointer } => {
+                write!(self.out, "bitcast<")?;
+                self.write_type(module, pointer)?;
+                write!(self.out, ">(")?;
+                self.write_expr(module, address, func_ctx)?;
+                write!(self.out, ")")?;
+            }
             Expression::As {
                 expr,
                 kind,
@@ -1722,6 +1736,8 @@ impl<W: Write> Writer<W> {
                     Mf::Unpack2x16snorm => Function::Regular("unpack2x16snorm"),
                     Mf::Unpack2x16unorm => Function::Regular("unpack2x16unorm"),
                     Mf::Unpack2x16float => Function::Regular("unpack2x16float"),
+                    // uniformity
+                    Mf::NonUniform => Function::Regular("nonuniform"),
                     Mf::Inverse | Mf::Outer => {
                         return Err(Error::UnsupportedMathFunction(fun));
                     }
@@ -1917,6 +1933,7 @@ fn builtin_str(built_in: crate::BuiltIn) -> Result<&'static str, Error> {
         Bi::SampleMask => "sample_mask",
         Bi::PrimitiveIndex => "primitive_index",
         Bi::ViewIndex => "view_index",
+        Bi::ViewportIndex => "viewport_index",
         Bi::NumSubgroups => "num_subgroups",
         Bi::SubgroupId => "subgroup_id",
         Bi::SubgroupSize => "subgroup_size",
@@ -2066,6 +2083,13 @@ const fn address_space_str(
                     "storage"
                 }
             }
+            As::PhysicalStorage { access } => {
+                if access.contains(crate::StorageAccess::STORE) {
+                    return (Some("physical_storage"), Some("read_write"));
+                } else {
+                    "physical_storage"
+                }
+            }
             As::PushConstant => "push_constant",
             As::WorkGroup => "workgroup",
             As::Handle => return (None, None),
@@ -190,6 +190,10 @@ impl<'tracer> ExpressionTracer<'tracer> {
                     kind: _,
                     convert: _,
                 } => self.expressions_used.insert(expr),
+                Ex::PointerFromAddress { address, pointer } => {
+                    self.expressions_used.insert(address);
+                    self.types_used.insert(pointer);
+                }
                 Ex::AtomicResult { ty, comparison: _ } => self.types_used.insert(ty),
                 Ex::WorkGroupUniformLoadResult { ty } => self.types_used.insert(ty),
                 Ex::ArrayLength(expr) => self.expressions_used.insert(expr),
@@ -351,6 +355,13 @@ impl ModuleMap {
                 kind: _,
                 convert: _,
             } => adjust(expr),
+            Ex::PointerFromAddress {
+                ref mut address,
+                ref mut pointer,
+            } => {
+                adjust(address);
+                self.types.adjust(pointer);
+            }
             Ex::AtomicResult {
                 ref mut ty,
                 comparison: _,
@@ -136,6 +136,7 @@ impl FunctionTracer<'_> {
                     | St::Continue
                     | St::Kill
                     | St::Barrier(_)
+                    | St::FragmentInterlock(_)
                     | St::Return { value: None } => {}
                 }
             }
@@ -323,6 +324,7 @@ impl FunctionMap {
                     | St::Continue
                     | St::Kill
                     | St::Barrier(_)
+                    | St::FragmentInterlock(_)
                     | St::Return { value: None } => {}
                 }
             }
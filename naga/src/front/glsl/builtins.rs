@@ -753,6 +753,33 @@ fn inject_standard_builtins(
                 .overloads
                 .push(module.add_builtin(args, MacroCall::MathFunction(fun)));
         }
+        "nonuniformEXT" => {
+            // bits layout
+            // bit 0 through 1 - scalar kind
+            // bit 2 through 3 - dims
+            for bits in 0..0b10000 {
+                let scalar = match bits & 0b11 {
+                    0b00 => Scalar::F32,
+                    0b01 => Scalar::I32,
+                    0b10 => Scalar::U32,
+                    _ => Scalar::BOOL,
+                };
+                let size = match bits >> 2 {
+                    0b00 => None,
+                    0b01 => Some(VectorSize::Bi),
+                    0b10 => Some(VectorSize::Tri),
+                    _ => Some(VectorSize::Quad),
+                };
+                let ty = match size {
+                    Some(size) => TypeInner::Vector { size, scalar },
+                    None => TypeInner::Scalar(scalar),
+                };
+
+                declaration.overloads.push(
+                    module.add_builtin(vec![ty], MacroCall::MathFunction(MathFunction::NonUniform)),
+                )
+            }
+        }
         "atan" => {
             // bits layout
             // bit 0 - atan/atan2
@@ -168,6 +168,32 @@ impl<'source> ParsingContext<'source> {
                 },
                 Default::default(),
             )
+        } else if let Some(token) = self.bump_if(frontend, TokenValue::NonUniform) {
+            // `nonuniformEXT(value)` is lowered to the `NonUniform` math
+            // function, which marks `value` as non-uniform for the backends.
+            let mut meta = token.meta;
+            self.expect(frontend, TokenValue::LeftParen)?;
+            let args = self.parse_function_call_args(frontend, ctx, stmt, &mut meta)?;
+
+            if args.len() != 1 {
+                return Err(Error {
+                    kind: ErrorKind::SemanticError(
+                        "nonuniformEXT takes exactly one argument".into(),
+                    ),
+                    meta,
+                });
+            }
+
+            stmt.hir_exprs.append(
+                HirExpr {
+                    kind: HirExprKind::Call(FunctionCall {
+                        kind: FunctionCallKind::Function("nonuniformEXT".into()),
+                        args,
+                    }),
+                    meta,
+                },
+                Default::default(),
+            )
         } else if let TokenValue::Identifier(_) = self.expect_peek(frontend)?.value {
             let (name, mut meta) = self.expect_ident(frontend)?;
 
@@ -149,6 +149,7 @@ impl<'source> ParsingContext<'source> {
     pub fn peek_type_qualifier(&mut self, frontend: &mut Frontend) -> bool {
         self.peek(frontend).map_or(false, |t| match t.value {
             TokenValue::Invariant
+            | TokenValue::NonUniform
             | TokenValue::Interpolation(_)
             | TokenValue::Sampling(_)
             | TokenValue::PrecisionQualifier(_)
@@ -289,7 +290,8 @@ impl<'source> ParsingContext<'source> {
                     storage_access.0 &= access;
                     storage_access.1.subsume(token.meta);
                 }
-                TokenValue::Restrict => continue,
+                // The uniformity analysis finds the non-uniform values by itself
+                TokenValue::Restrict | TokenValue::NonUniform => continue,
                 _ => unreachable!(),
             };
         }
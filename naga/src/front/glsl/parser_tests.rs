@@ -352,6 +352,40 @@ fn textures() {
         .unwrap();
 }
 
+#[test]
+fn nonuniform_qualifier() {
+    let mut frontend = Frontend::default();
+
+    frontend
+        .parse(
+            &Options::from(ShaderStage::Fragment),
+            r#"
+        #version 450
+        #extension GL_EXT_nonuniform_qualifier : require
+        layout(location = 0) nonuniformEXT flat in int v_index;
+        layout(location = 0) out vec4 o_color;
+        void main() {
+            const vec4 colors[2] = { vec4(0.0), vec4(1.0) };
+            nonuniformEXT int index = nonuniformEXT(v_index);
+            o_color = colors[nonuniformEXT(index + 1) - 1];
+        }
+        "#,
+        )
+        .unwrap();
+
+    assert!(frontend
+        .parse(
+            &Options::from(ShaderStage::Fragment),
+            r#"
+        #version 450
+        void main() {
+            int index = nonuniformEXT(0, 1);
+        }
+        "#,
+        )
+        .is_err());
+}
+
 #[test]
 fn functions() {
     let mut frontend = Frontend::default();
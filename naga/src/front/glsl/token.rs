@@ -45,6 +45,9 @@ pub enum TokenValue {
     MemoryQualifier(crate::StorageAccess),
 
     Invariant,
+    /// The `nonuniformEXT` qualifier of `GL_EXT_nonuniform_qualifier`, which
+    /// is also used as a constructor to mark a single value as non-uniform.
+    NonUniform,
     Interpolation(Interpolation),
     Sampling(Sampling),
     Precision,
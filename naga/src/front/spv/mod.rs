@@ -4069,6 +4069,7 @@ impl<I: Iterator<Item = u32>> Frontend<I> {
                 | S::Return { .. }
                 | S::Kill
                 | S::Barrier(_)
+                | S::FragmentInterlock(_)
                 | S::Store { .. }
                 | S::ImageStore { .. }
                 | S::Atomic { .. }
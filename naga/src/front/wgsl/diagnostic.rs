@@ -0,0 +1,125 @@
+/*!
+[Diagnostic filters], which set how the diagnostics triggered by a rule are
+reported.
+
+A module can set them with `diagnostic(severity, rule);` directives, and a
+function with `@diagnostic(severity, rule)` attributes, which override the
+directives. [`Options::diagnostic_filters`] sets them for a whole module,
+below its directives.
+
+[Diagnostic filters]: https://gpuweb.github.io/gpuweb/wgsl/#diagnostics
+[`Options::diagnostic_filters`]: super::Options::diagnostic_filters
+*/
+
+/// How the diagnostics triggered by a rule are reported.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum Severity {
+    /// The diagnostic is ignored.
+    Off,
+    /// The diagnostic is returned as an informational [`ParseWarning`].
+    ///
+    /// [`ParseWarning`]: super::ParseWarning
+    Info,
+    /// The diagnostic is returned as a [`ParseWarning`].
+    ///
+    /// [`ParseWarning`]: super::ParseWarning
+    Warning,
+    /// The diagnostic fails parsing with a [`ParseError`].
+    ///
+    /// [`ParseError`]: super::ParseError
+    Error,
+}
+
+impl Severity {
+    /// Maps the name of a severity control to a severity.
+    pub fn from_ident(ident: &str) -> Option<Self> {
+        Some(match ident {
+            "off" => Self::Off,
+            "info" => Self::Info,
+            "warning" => Self::Warning,
+            "error" => Self::Error,
+            _ => return None,
+        })
+    }
+}
+
+/// A rule that triggers diagnostics.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum DiagnosticRule {
+    /// `derivative_uniformity`, triggered by derivatives and implicit level of
+    /// detail texture samples in non-uniform control flow.
+    ///
+    /// Naga doesn't analyze derivative uniformity yet, so this rule never
+    /// triggers, but filters may name it.
+    DerivativeUniformity,
+    /// `naga.unreachable_code`, triggered by the first statement following a
+    /// `return`, `break`, `continue` or `discard` in the same block.
+    UnreachableCode,
+}
+
+impl DiagnosticRule {
+    /// Maps the name of a rule to a rule.
+    pub fn from_name(name: &str) -> Option<Self> {
+        Some(match name {
+            "derivative_uniformity" => Self::DerivativeUniformity,
+            "naga.unreachable_code" => Self::UnreachableCode,
+            _ => return None,
+        })
+    }
+
+    /// Returns the name of the rule, as it is written in WGSL.
+    pub const fn name(self) -> &'static str {
+        match self {
+            Self::DerivativeUniformity => "derivative_uniformity",
+            Self::UnreachableCode => "naga.unreachable_code",
+        }
+    }
+
+    /// Returns the severity of the rule where no filter applies.
+    pub const fn default_severity(self) -> Severity {
+        match self {
+            Self::DerivativeUniformity => Severity::Error,
+            Self::UnreachableCode => Severity::Warning,
+        }
+    }
+}
+
+/// Sets the severity of the diagnostics triggered by `rule`.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct DiagnosticFilter {
+    pub severity: Severity,
+    pub rule: DiagnosticRule,
+}
+
+/// The severity of every rule in some part of a module.
+#[derive(Clone, Copy, Debug)]
+pub(super) struct Severities {
+    derivative_uniformity: Severity,
+    unreachable_code: Severity,
+}
+
+impl Default for Severities {
+    fn default() -> Self {
+        Self {
+            derivative_uniformity: DiagnosticRule::DerivativeUniformity.default_severity(),
+            unreachable_code: DiagnosticRule::UnreachableCode.default_severity(),
+        }
+    }
+}
+
+impl Severities {
+    pub(super) const fn get(&self, rule: DiagnosticRule) -> Severity {
+        match rule {
+            DiagnosticRule::DerivativeUniformity => self.derivative_uniformity,
+            DiagnosticRule::UnreachableCode => self.unreachable_code,
+        }
+    }
+
+    pub(super) fn apply(&mut self, filter: DiagnosticFilter) {
+        let severity = match filter.rule {
+            DiagnosticRule::DerivativeUniformity => &mut self.derivative_uniformity,
+            DiagnosticRule::UnreachableCode => &mut self.unreachable_code,
+        };
+        *severity = filter.severity;
+    }
+}
@@ -0,0 +1,82 @@
+/*!
+Language extensions that aren't part of standard WGSL, and that a module can
+name in `enable` directives.
+
+Naga accepts the features of these extensions without an `enable` directive,
+so existing shaders keep working. [`Options::available_extensions`] restricts
+them to the extensions a target supports, and modules that use or enable any
+other extension fail to parse, with an error pointing at the use or directive.
+
+[`Options::available_extensions`]: super::Options::available_extensions
+*/
+
+use super::parse::ast;
+use super::Error;
+
+bitflags::bitflags! {
+    /// A set of language extensions.
+    #[derive(Clone, Copy, Debug, Default, Eq, PartialEq, Hash)]
+    pub struct EnableExtensions: u8 {
+        /// `naga_push_constants`, for variables in the `push_constant`
+        /// address space.
+        const PUSH_CONSTANTS = 1 << 0;
+        /// `naga_binding_array`, for `binding_array` types.
+        const BINDING_ARRAY = 1 << 1;
+        /// `naga_ray_query`, for `acceleration_structure` and `ray_query`
+        /// types.
+        const RAY_QUERY = 1 << 2;
+        /// `naga_buffer_device_address`, for pointers in the
+        /// `physical_storage` address space, made from buffer device
+        /// addresses with `bitcast`.
+        const BUFFER_DEVICE_ADDRESS = 1 << 3;
+    }
+}
+
+/// The name of every extension, as it is written in `enable` directives.
+const REGISTRY: &[(&str, EnableExtensions)] = &[
+    ("naga_push_constants", EnableExtensions::PUSH_CONSTANTS),
+    ("naga_binding_array", EnableExtensions::BINDING_ARRAY),
+    ("naga_ray_query", EnableExtensions::RAY_QUERY),
+    (
+        "naga_buffer_device_address",
+        EnableExtensions::BUFFER_DEVICE_ADDRESS,
+    ),
+];
+
+impl EnableExtensions {
+    /// Maps the name of an extension to an extension.
+    pub fn from_name(name: &str) -> Option<Self> {
+        REGISTRY
+            .iter()
+            .find(|&&(registered, _)| registered == name)
+            .map(|&(_, extension)| extension)
+    }
+
+    /// Returns the name of the extension, as it is written in WGSL.
+    ///
+    /// Returns `None` unless `self` is a single extension.
+    pub fn name(self) -> Option<&'static str> {
+        REGISTRY
+            .iter()
+            .find(|&&(_, extension)| extension == self)
+            .map(|&(name, _)| name)
+    }
+}
+
+/// Checks that the extensions `tu` enables or uses are all `available`.
+pub(super) fn check<'a>(
+    tu: &ast::TranslationUnit<'a>,
+    available: EnableExtensions,
+) -> Result<(), Error<'a>> {
+    for &(extension, span) in tu.enable_extensions.iter() {
+        if !available.contains(extension) {
+            return Err(Error::UnavailableEnableExtension(span));
+        }
+    }
+    for &(extension, span) in tu.extension_uses.iter() {
+        if !available.contains(extension) {
+            return Err(Error::UnavailableExtensionUse { extension, span });
+        }
+    }
+    Ok(())
+}
@@ -1,3 +1,4 @@
+use crate::front::wgsl::diagnostic::Severity;
 use crate::front::wgsl::parse::lexer::Token;
 use crate::front::wgsl::Scalar;
 use crate::proc::{Alignment, ConstantEvaluatorError, ResolveError};
@@ -29,7 +30,14 @@ impl ParseError {
     }
 
     fn diagnostic(&self) -> Diagnostic<()> {
-        let diagnostic = Diagnostic::error()
+        self.diagnostic_with_severity(codespan_reporting::diagnostic::Severity::Error)
+    }
+
+    fn diagnostic_with_severity(
+        &self,
+        severity: codespan_reporting::diagnostic::Severity,
+    ) -> Diagnostic<()> {
+        let diagnostic = Diagnostic::new(severity)
             .with_message(self.message.to_string())
             .with_labels(
                 self.labels
@@ -103,6 +111,70 @@ impl std::error::Error for ParseError {
     }
 }
 
+/// A diagnostic triggered by a rule with [`Severity::Info`] or
+/// [`Severity::Warning`], returned by [`Frontend::parse_with_warnings`].
+///
+/// [`Frontend::parse_with_warnings`]: super::Frontend::parse_with_warnings
+#[derive(Clone, Debug)]
+pub struct ParseWarning {
+    severity: Severity,
+    inner: ParseError,
+}
+
+impl ParseWarning {
+    pub(crate) const fn new(severity: Severity, inner: ParseError) -> Self {
+        Self { severity, inner }
+    }
+
+    pub const fn severity(&self) -> Severity {
+        self.severity
+    }
+
+    pub fn labels(&self) -> impl ExactSizeIterator<Item = (Span, &str)> + '_ {
+        self.inner.labels()
+    }
+
+    pub fn message(&self) -> &str {
+        self.inner.message()
+    }
+
+    fn diagnostic(&self) -> Diagnostic<()> {
+        self.inner.diagnostic_with_severity(match self.severity {
+            Severity::Info => codespan_reporting::diagnostic::Severity::Note,
+            _ => codespan_reporting::diagnostic::Severity::Warning,
+        })
+    }
+
+    /// Emits a summary of the warning to a string.
+    pub fn emit_to_string(&self, source: &str) -> String {
+        self.emit_to_string_with_path(source, "wgsl")
+    }
+
+    /// Emits a summary of the warning to a string.
+    pub fn emit_to_string_with_path<P>(&self, source: &str, path: P) -> String
+    where
+        P: AsRef<std::path::Path>,
+    {
+        let path = path.as_ref().display().to_string();
+        let files = SimpleFile::new(path, source);
+        let config = codespan_reporting::term::Config::default();
+        let mut writer = NoColor::new(Vec::new());
+        term::emit(&mut writer, &config, &files, &self.diagnostic()).expect("cannot write warning");
+        String::from_utf8(writer.into_inner()).unwrap()
+    }
+
+    /// Returns a [`SourceLocation`] for the first label in the warning message.
+    pub fn location(&self, source: &str) -> Option<SourceLocation> {
+        self.inner.location(source)
+    }
+}
+
+impl std::fmt::Display for ParseWarning {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.inner.message)
+    }
+}
+
 #[derive(Copy, Clone, Debug, PartialEq)]
 pub enum ExpectedToken<'a> {
     Token(Token<'a>),
@@ -274,6 +346,36 @@ pub enum Error<'a> {
         limit: u8,
     },
     PipelineConstantIDValue(Span),
+    UnknownDiagnosticSeverity(Span),
+    /// Two diagnostic filters in the same scope set different severities for
+    /// the same rule.
+    ConflictingDiagnosticFilters {
+        previous: Span,
+        current: Span,
+    },
+    DirectiveAfterDeclaration(Span),
+    MisplacedDiagnosticAttribute(Span),
+    /// A diagnostic filter names a rule that isn't recognized. Only ever
+    /// reported as a warning.
+    UnknownDiagnosticRule(Span),
+    /// Triggers [`DiagnosticRule::UnreachableCode`].
+    ///
+    /// [`DiagnosticRule::UnreachableCode`]: super::DiagnosticRule::UnreachableCode
+    UnreachableCode(Span),
+    UnknownEnableExtension(Span),
+    /// An `enable` directive names an extension that isn't in
+    /// [`Options::available_extensions`].
+    ///
+    /// [`Options::available_extensions`]: super::Options::available_extensions
+    UnavailableEnableExtension(Span),
+    /// A feature belongs to an extension that isn't in
+    /// [`Options::available_extensions`].
+    ///
+    /// [`Options::available_extensions`]: super::Options::available_extensions
+    UnavailableExtensionUse {
+        extension: super::EnableExtensions,
+        span: Span,
+    },
 }
 
 impl<'a> Error<'a> {
@@ -790,6 +892,66 @@ impl<'a> Error<'a> {
                 )],
                 notes: vec![],
             },
+            Error::UnknownDiagnosticSeverity(span) => ParseError {
+                message: format!("unknown diagnostic severity: '{}'", &source[span]),
+                labels: vec![(span, "unknown severity".into())],
+                notes: vec!["expected one of 'off', 'info', 'warning' or 'error'".to_string()],
+            },
+            Error::ConflictingDiagnosticFilters { previous, current } => ParseError {
+                message: format!("conflicting diagnostic filters for '{}'", &source[current]),
+                labels: vec![
+                    (current, "set to a different severity here".into()),
+                    (previous, "previously set here".into()),
+                ],
+                notes: vec![],
+            },
+            Error::DirectiveAfterDeclaration(span) => ParseError {
+                message: "directives must come before any declaration".to_string(),
+                labels: vec![(span, "directive after a declaration".into())],
+                notes: vec![],
+            },
+            Error::MisplacedDiagnosticAttribute(span) => ParseError {
+                message: "the diagnostic attribute can only be applied to functions".to_string(),
+                labels: vec![(span, "not applied to a function".into())],
+                notes: vec![],
+            },
+            Error::UnknownDiagnosticRule(span) => ParseError {
+                message: format!("unknown diagnostic rule: '{}'", &source[span]),
+                labels: vec![(span, "this filter has no effect".into())],
+                notes: vec![],
+            },
+            Error::UnreachableCode(span) => ParseError {
+                message: "code is unreachable".to_string(),
+                labels: vec![(span, "this statement is never executed".into())],
+                notes: vec![format!(
+                    "reported by the '{}' diagnostic rule",
+                    super::DiagnosticRule::UnreachableCode.name()
+                )],
+            },
+            Error::UnknownEnableExtension(span) => ParseError {
+                message: format!("unknown extension: '{}'", &source[span]),
+                labels: vec![(span, "unknown extension".into())],
+                notes: vec![],
+            },
+            Error::UnavailableEnableExtension(span) => ParseError {
+                message: format!(
+                    "extension '{}' is not available on this target",
+                    &source[span]
+                ),
+                labels: vec![(span, "extension enabled here".into())],
+                notes: vec![],
+            },
+            Error::UnavailableExtensionUse { extension, span } => {
+                let name = extension.name().unwrap_or_default();
+                ParseError {
+                    message: format!(
+                        "'{}' requires extension '{name}', which is not available on this target",
+                        &source[span]
+                    ),
+                    labels: vec![(span, format!("requires '{name}'").into())],
+                    notes: vec![],
+                }
+            }
         }
     }
 }
@@ -1,5 +1,6 @@
 use std::num::NonZeroU32;
 
+use crate::front::wgsl::diagnostic::{DiagnosticFilter, DiagnosticRule, Severities, Severity};
 use crate::front::wgsl::error::{Error, ExpectedToken, InvalidAssignmentType};
 use crate::front::wgsl::index::Index;
 use crate::front::wgsl::parse::number::Number;
@@ -900,20 +901,33 @@ impl SubgroupGather {
 pub struct Lowerer<'source, 'temp> {
     index: &'temp Index<'source>,
     layouter: Layouter,
+    /// The severities of the diagnostic rules for the code being lowered.
+    severities: Severities,
+    /// The diagnostics reported with [`Severity::Info`] or [`Severity::Warning`].
+    warnings: Vec<(Severity, Error<'source>)>,
 }
 
 impl<'source, 'temp> Lowerer<'source, 'temp> {
-    pub fn new(index: &'temp Index<'source>) -> Self {
+    pub fn new(index: &'temp Index<'source>, severities: Severities) -> Self {
         Self {
             index,
             layouter: Layouter::default(),
+            severities,
+            warnings: Vec::new(),
         }
     }
 
+    /// Returns the diagnostics reported as warnings while lowering.
+    pub fn into_warnings(self) -> Vec<(Severity, Error<'source>)> {
+        self.warnings
+    }
+
     pub fn lower(
         &mut self,
         tu: &'temp ast::TranslationUnit<'source>,
     ) -> Result<crate::Module, Error<'source>> {
+        self.apply_diagnostic_filters(&tu.diagnostic_filters);
+
         let mut module = crate::Module::default();
 
         let mut ctx = GlobalContext {
@@ -1111,6 +1125,46 @@ impl<'source, 'temp> Lowerer<'source, 'temp> {
         Ok(module)
     }
 
+    /// Applies the diagnostic filters of a directive or attribute to the
+    /// severities of the rules.
+    fn apply_diagnostic_filters(&mut self, filters: &[ast::DiagnosticFilter<'source>]) {
+        for filter in filters {
+            match filter.rule {
+                Some(rule) => self.severities.apply(DiagnosticFilter {
+                    severity: filter.severity,
+                    rule,
+                }),
+                None => {
+                    // Rules in other namespaces belong to other implementations.
+                    let namespace = filter.rule_name.name.split_once('.');
+                    if namespace.map_or(true, |(namespace, _)| namespace == "naga") {
+                        self.warnings.push((
+                            Severity::Warning,
+                            Error::UnknownDiagnosticRule(filter.rule_name.span),
+                        ));
+                    }
+                }
+            }
+        }
+    }
+
+    /// Reports `error`, a diagnostic triggered by `rule`, according to the
+    /// severity of the rule.
+    fn report(
+        &mut self,
+        rule: DiagnosticRule,
+        error: Error<'source>,
+    ) -> Result<(), Error<'source>> {
+        match self.severities.get(rule) {
+            Severity::Off => Ok(()),
+            Severity::Error => Err(error),
+            severity => {
+                self.warnings.push((severity, error));
+                Ok(())
+            }
+        }
+    }
+
     fn function(
         &mut self,
         f: &ast::Function<'source>,
@@ -1178,8 +1232,11 @@ impl<'source, 'temp> Lowerer<'source, 'temp> {
             local_expression_kind_tracker: &mut local_expression_kind_tracker,
             global_expression_kind_tracker: ctx.global_expression_kind_tracker,
         };
+        let module_severities = self.severities;
+        self.apply_diagnostic_filters(&f.diagnostic_filters);
         let mut body = self.block(&f.body, false, &mut stmt_ctx)?;
         ensure_block_returns(&mut body);
+        self.severities = module_severities;
 
         function.body = body;
         function.named_expressions = named_expressions
@@ -1221,6 +1278,26 @@ impl<'source, 'temp> Lowerer<'source, 'temp> {
         is_inside_loop: bool,
         ctx: &mut StatementContext<'source, '_, '_>,
     ) -> Result<crate::Block, Error<'source>> {
+        let first_unreachable = b
+            .stmts
+            .iter()
+            .position(|stmt| {
+                matches!(
+                    stmt.kind,
+                    ast::StatementKind::Break
+                        | ast::StatementKind::Continue
+                        | ast::StatementKind::Return { .. }
+                        | ast::StatementKind::Kill
+                )
+            })
+            .and_then(|exit| b.stmts.get(exit + 1));
+        if let Some(stmt) = first_unreachable {
+            self.report(
+                DiagnosticRule::UnreachableCode,
+                Error::UnreachableCode(stmt.span),
+            )?;
+        }
+
         let mut block = crate::Block::default();
 
         for stmt in b.stmts.iter() {
@@ -1849,6 +1926,17 @@ impl<'source, 'temp> Lowerer<'source, 'temp> {
                 let element_scalar = match ctx.module.types[to_resolved].inner {
                     crate::TypeInner::Scalar(scalar) => scalar,
                     crate::TypeInner::Vector { scalar, .. } => scalar,
+                    // Casting a buffer device address to a pointer.
+                    crate::TypeInner::Pointer {
+                        space: crate::AddressSpace::PhysicalStorage { .. },
+                        ..
+                    } => {
+                        let expr = crate::Expression::PointerFromAddress {
+                            address: expr,
+                            pointer: to_resolved,
+                        };
+                        return ctx.append_expression(expr, span).map(Typed::Plain);
+                    }
                     _ => {
                         let ty = resolve!(ctx, expr);
                         let gctx = &ctx.module.to_ctx();
@@ -2198,6 +2286,28 @@ impl<'source, 'temp> Lowerer<'source, 'temp> {
                                 .push(crate::Statement::Barrier(crate::Barrier::SUB_GROUP), span);
                             return Ok(None);
                         }
+                        "beginInvocationInterlock" => {
+                            ctx.prepare_args(arguments, 0, span).finish()?;
+
+                            let rctx = ctx.runtime_expression_ctx(span)?;
+                            rctx.block.push(
+                                crate::Statement::FragmentInterlock(
+                                    crate::FragmentInterlock::Begin,
+                                ),
+                                span,
+                            );
+                            return Ok(None);
+                        }
+                        "endInvocationInterlock" => {
+                            ctx.prepare_args(arguments, 0, span).finish()?;
+
+                            let rctx = ctx.runtime_expression_ctx(span)?;
+                            rctx.block.push(
+                                crate::Statement::FragmentInterlock(crate::FragmentInterlock::End),
+                                span,
+                            );
+                            return Ok(None);
+                        }
                         "workgroupUniformLoad" => {
                             let mut args = ctx.prepare_args(arguments, 1, span);
                             let expr = args.next()?;
@@ -4,6 +4,8 @@ Frontend for [WGSL][wgsl] (WebGPU Shading Language).
 [wgsl]: https://gpuweb.github.io/gpuweb/wgsl.html
 */
 
+mod diagnostic;
+mod enable_extension;
 mod error;
 mod index;
 mod lower;
@@ -16,31 +18,82 @@ use crate::front::wgsl::error::Error;
 use crate::front::wgsl::parse::Parser;
 use thiserror::Error;
 
-pub use crate::front::wgsl::error::ParseError;
+use crate::front::wgsl::diagnostic::Severities;
+pub use crate::front::wgsl::diagnostic::{DiagnosticFilter, DiagnosticRule, Severity};
+pub use crate::front::wgsl::enable_extension::EnableExtensions;
+pub use crate::front::wgsl::error::{ParseError, ParseWarning};
 use crate::front::wgsl::lower::Lowerer;
 use crate::Scalar;
 
+/// Options for [`Frontend::new_with_options`].
+#[derive(Clone, Debug, Default)]
+pub struct Options {
+    /// Diagnostic filters that apply to the whole module, as if they were
+    /// `diagnostic` directives. Directives in the module override them.
+    pub diagnostic_filters: Vec<DiagnosticFilter>,
+    /// The language extensions that the target supports, or `None` if it
+    /// supports all of them.
+    ///
+    /// Modules that use or `enable` any other extension fail to parse.
+    pub available_extensions: Option<EnableExtensions>,
+}
+
 pub struct Frontend {
     parser: Parser,
+    options: Options,
 }
 
 impl Frontend {
     pub const fn new() -> Self {
+        Self::new_with_options(Options {
+            diagnostic_filters: Vec::new(),
+            available_extensions: None,
+        })
+    }
+
+    pub const fn new_with_options(options: Options) -> Self {
         Self {
             parser: Parser::new(),
+            options,
         }
     }
 
     pub fn parse(&mut self, source: &str) -> Result<crate::Module, ParseError> {
-        self.inner(source).map_err(|x| x.as_parse_error(source))
+        self.parse_with_warnings(source).map(|(module, _)| module)
     }
 
-    fn inner<'a>(&mut self, source: &'a str) -> Result<crate::Module, Error<'a>> {
+    /// Parses `source` like [`Frontend::parse`], and also returns the
+    /// diagnostics that were reported as warnings.
+    pub fn parse_with_warnings(
+        &mut self,
+        source: &str,
+    ) -> Result<(crate::Module, Vec<ParseWarning>), ParseError> {
+        let (module, warnings) = self.inner(source).map_err(|x| x.as_parse_error(source))?;
+        let warnings = warnings
+            .into_iter()
+            .map(|(severity, warning)| ParseWarning::new(severity, warning.as_parse_error(source)))
+            .collect();
+        Ok((module, warnings))
+    }
+
+    #[allow(clippy::type_complexity)]
+    fn inner<'a>(
+        &mut self,
+        source: &'a str,
+    ) -> Result<(crate::Module, Vec<(Severity, Error<'a>)>), Error<'a>> {
         let tu = self.parser.parse(source)?;
+        if let Some(available) = self.options.available_extensions {
+            enable_extension::check(&tu, available)?;
+        }
         let index = index::Index::generate(&tu)?;
-        let module = Lowerer::new(&index).lower(&tu)?;
+        let mut severities = Severities::default();
+        for &filter in self.options.diagnostic_filters.iter() {
+            severities.apply(filter);
+        }
+        let mut lowerer = Lowerer::new(&index, severities);
+        let module = lowerer.lower(&tu)?;
 
-        Ok(module)
+        Ok((module, lowerer.into_warnings()))
     }
 }
 
@@ -1,4 +1,6 @@
+use crate::front::wgsl::diagnostic::{DiagnosticRule, Severity};
 use crate::front::wgsl::parse::number::Number;
+use crate::front::wgsl::EnableExtensions;
 use crate::front::wgsl::Scalar;
 use crate::{Arena, FastIndexSet, Handle, Span};
 use std::hash::Hash;
@@ -24,6 +26,17 @@ pub struct TranslationUnit<'a> {
     /// These are referred to by `Handle<ast::Type<'a>>` values.
     /// User-defined types are referred to by name until lowering.
     pub types: Arena<Type<'a>>,
+
+    /// The filters set by `diagnostic` directives, for the whole module.
+    pub diagnostic_filters: Vec<DiagnosticFilter<'a>>,
+
+    /// The extensions named by `enable` directives, with the span of each
+    /// name.
+    pub enable_extensions: Vec<(EnableExtensions, Span)>,
+
+    /// The uses of features that belong to a language extension, whether the
+    /// module enables it or not.
+    pub extension_uses: Vec<(EnableExtensions, Span)>,
 }
 
 #[derive(Debug, Clone, Copy)]
@@ -101,6 +114,16 @@ pub struct FunctionResult<'a> {
     pub binding: Option<Binding<'a>>,
 }
 
+/// A `diagnostic` directive or attribute.
+#[derive(Debug, Clone, Copy)]
+pub struct DiagnosticFilter<'a> {
+    pub severity: Severity,
+    /// The rule, or `None` if its name isn't recognized, in which case the
+    /// filter has no effect.
+    pub rule: Option<DiagnosticRule>,
+    pub rule_name: Ident<'a>,
+}
+
 #[derive(Debug)]
 pub struct EntryPoint<'a> {
     pub stage: crate::ShaderStage,
@@ -117,6 +140,8 @@ pub struct Function<'a> {
     pub name: Ident<'a>,
     pub arguments: Vec<FunctionArgument<'a>>,
     pub result: Option<FunctionResult<'a>>,
+    /// The filters set by `@diagnostic` attributes, overriding the module's.
+    pub diagnostic_filters: Vec<DiagnosticFilter<'a>>,
 
     /// Local variable and function argument arena.
     ///
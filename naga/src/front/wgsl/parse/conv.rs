@@ -11,6 +11,9 @@ pub fn map_address_space(word: &str, span: Span) -> Result<crate::AddressSpace,
             access: crate::StorageAccess::default(),
         }),
         "push_constant" => Ok(crate::AddressSpace::PushConstant),
+        "physical_storage" => Ok(crate::AddressSpace::PhysicalStorage {
+            access: crate::StorageAccess::default(),
+        }),
         "function" => Ok(crate::AddressSpace::Function),
         _ => Err(Error::UnknownAddressSpace(span)),
     }
@@ -23,6 +26,7 @@ pub fn map_built_in(word: &str, span: Span) -> Result<crate::BuiltIn, Error<'_>>
         "vertex_index" => crate::BuiltIn::VertexIndex,
         "instance_index" => crate::BuiltIn::InstanceIndex,
         "view_index" => crate::BuiltIn::ViewIndex,
+        "viewport_index" => crate::BuiltIn::ViewportIndex,
         // fragment
         "front_facing" => crate::BuiltIn::FrontFacing,
         "frag_depth" => crate::BuiltIn::FragDepth,
@@ -249,6 +253,8 @@ pub fn map_standard_fun(word: &str) -> Option<crate::MathFunction> {
         "unpack2x16snorm" => Mf::Unpack2x16snorm,
         "unpack2x16unorm" => Mf::Unpack2x16unorm,
         "unpack2x16float" => Mf::Unpack2x16float,
+        // uniformity
+        "nonuniform" => Mf::NonUniform,
         _ => return None,
     })
 }
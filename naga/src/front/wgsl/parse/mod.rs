@@ -1,6 +1,8 @@
+use crate::front::wgsl::diagnostic::{DiagnosticRule, Severity};
 use crate::front::wgsl::error::{Error, ExpectedToken};
 use crate::front::wgsl::parse::lexer::{Lexer, Token};
 use crate::front::wgsl::parse::number::Number;
+use crate::front::wgsl::EnableExtensions;
 use crate::front::wgsl::Scalar;
 use crate::front::SymbolTable;
 use crate::{Arena, FastIndexSet, Handle, ShaderStage, Span};
@@ -221,15 +223,46 @@ impl<'a> BindingParser<'a> {
 
 pub struct Parser {
     rules: Vec<(Rule, usize)>,
+    /// The uses of features that belong to a language extension, moved to
+    /// [`TranslationUnit::extension_uses`] at the end of parsing.
+    ///
+    /// [`TranslationUnit::extension_uses`]: ast::TranslationUnit::extension_uses
+    extension_uses: Vec<(EnableExtensions, Span)>,
 }
 
 impl Parser {
     pub const fn new() -> Self {
-        Parser { rules: Vec::new() }
+        Parser {
+            rules: Vec::new(),
+            extension_uses: Vec::new(),
+        }
     }
 
     fn reset(&mut self) {
         self.rules.clear();
+        self.extension_uses.clear();
+    }
+
+    /// Parses the address space named `word`, noting the use of an extension
+    /// if it belongs to one.
+    fn address_space<'a>(
+        &mut self,
+        word: &'a str,
+        span: Span,
+    ) -> Result<crate::AddressSpace, Error<'a>> {
+        let space = conv::map_address_space(word, span)?;
+        match space {
+            crate::AddressSpace::PushConstant => {
+                self.extension_uses
+                    .push((EnableExtensions::PUSH_CONSTANTS, span));
+            }
+            crate::AddressSpace::PhysicalStorage { .. } => {
+                self.extension_uses
+                    .push((EnableExtensions::BUFFER_DEVICE_ADDRESS, span));
+            }
+            _ => {}
+        }
+        Ok(space)
     }
 
     fn push_rule_span(&mut self, rule: Rule, lexer: &mut Lexer<'_>) {
@@ -954,7 +987,7 @@ impl Parser {
                     };
                     crate::AddressSpace::Storage { access }
                 }
-                _ => conv::map_address_space(class_str, span)?,
+                _ => self.address_space(class_str, span)?,
             };
             lexer.expect(Token::Paren('>'))?;
         }
@@ -1219,10 +1252,12 @@ impl Parser {
             "ptr" => {
                 lexer.expect_generic_paren('<')?;
                 let (ident, span) = lexer.next_ident_with_span()?;
-                let mut space = conv::map_address_space(ident, span)?;
+                let mut space = self.address_space(ident, span)?;
                 lexer.expect(Token::Separator(','))?;
                 let base = self.type_decl(lexer, ctx)?;
-                if let crate::AddressSpace::Storage { ref mut access } = space {
+                if let crate::AddressSpace::Storage { ref mut access }
+                | crate::AddressSpace::PhysicalStorage { ref mut access } = space
+                {
                     *access = if lexer.skip(Token::Separator(',')) {
                         lexer.next_storage_access()?
                     } else {
@@ -1474,6 +1509,17 @@ impl Parser {
             }
         };
 
+        let extension = match ty {
+            ast::Type::BindingArray { .. } => Some(EnableExtensions::BINDING_ARRAY),
+            ast::Type::AccelerationStructure | ast::Type::RayQuery => {
+                Some(EnableExtensions::RAY_QUERY)
+            }
+            _ => None,
+        };
+        if let Some(extension) = extension {
+            self.extension_uses.push((extension, span));
+        }
+
         self.pop_rule_span(lexer);
 
         let handle = ctx.types.append(ty, Span::UNDEFINED);
@@ -2157,6 +2203,7 @@ impl Parser {
             name: fun_name,
             arguments,
             result,
+            diagnostic_filters: Vec::new(),
             body,
             locals,
         };
@@ -2167,6 +2214,49 @@ impl Parser {
         Ok(fun)
     }
 
+    /// Parses the `(severity, rule)` of a `diagnostic` directive or attribute,
+    /// and adds the filter to `filters`.
+    fn diagnostic_filter<'a>(
+        &mut self,
+        lexer: &mut Lexer<'a>,
+        filters: &mut Vec<ast::DiagnosticFilter<'a>>,
+    ) -> Result<(), Error<'a>> {
+        lexer.expect(Token::Paren('('))?;
+        let (severity, severity_span) = lexer.next_ident_with_span()?;
+        let severity = Severity::from_ident(severity)
+            .ok_or(Error::UnknownDiagnosticSeverity(severity_span))?;
+        lexer.expect(Token::Separator(','))?;
+        let (_, mut rule_span) = lexer.next_ident_with_span()?;
+        if lexer.skip(Token::Separator('.')) {
+            let (_, member_span) = lexer.next_ident_with_span()?;
+            rule_span = rule_span.until(&member_span);
+        }
+        let rule_name = ast::Ident {
+            name: &lexer.source[rule_span.to_range().unwrap()],
+            span: rule_span,
+        };
+        lexer.skip(Token::Separator(','));
+        lexer.expect(Token::Paren(')'))?;
+
+        if let Some(previous) = filters
+            .iter()
+            .find(|filter| filter.rule_name.name == rule_name.name)
+        {
+            if previous.severity != severity {
+                return Err(Error::ConflictingDiagnosticFilters {
+                    previous: previous.rule_name.span,
+                    current: rule_span,
+                });
+            }
+        }
+        filters.push(ast::DiagnosticFilter {
+            severity,
+            rule: DiagnosticRule::from_name(rule_name.name),
+            rule_name,
+        });
+        Ok(())
+    }
+
     fn global_decl<'a>(
         &mut self,
         lexer: &mut Lexer<'a>,
@@ -2181,6 +2271,8 @@ impl Parser {
         let (mut bind_index, mut bind_group) =
             (ParsedAttribute::default(), ParsedAttribute::default());
         let mut id = ParsedAttribute::default();
+        let mut diagnostic_filters = Vec::new();
+        let mut diagnostic_span = None;
 
         let mut dependencies = FastIndexSet::default();
         let mut ctx = ExpressionContext {
@@ -2248,6 +2340,10 @@ impl Parser {
                     };
                     early_depth_test.set(crate::EarlyDepthTest { conservative }, name_span)?;
                 }
+                ("diagnostic", name_span) => {
+                    self.diagnostic_filter(lexer, &mut diagnostic_filters)?;
+                    diagnostic_span.get_or_insert(name_span);
+                }
                 (_, word_span) => return Err(Error::UnknownAttribute(word_span)),
             }
         }
@@ -2269,6 +2365,32 @@ impl Parser {
         let start = lexer.start_byte_offset();
         let kind = match lexer.next() {
             (Token::Separator(';'), _) => None,
+            (Token::Word("diagnostic"), directive_span) => {
+                if !out.decls.is_empty() {
+                    return Err(Error::DirectiveAfterDeclaration(directive_span));
+                }
+                self.diagnostic_filter(lexer, &mut out.diagnostic_filters)?;
+                lexer.expect(Token::Separator(';'))?;
+                None
+            }
+            (Token::Word("enable"), directive_span) => {
+                if !out.decls.is_empty() {
+                    return Err(Error::DirectiveAfterDeclaration(directive_span));
+                }
+                loop {
+                    let (name, span) = lexer.next_ident_with_span()?;
+                    let extension = EnableExtensions::from_name(name)
+                        .ok_or(Error::UnknownEnableExtension(span))?;
+                    out.enable_extensions.push((extension, span));
+                    // A trailing comma is allowed.
+                    let more = lexer.skip(Token::Separator(','));
+                    if !more || lexer.peek().0 == Token::Separator(';') {
+                        break;
+                    }
+                }
+                lexer.expect(Token::Separator(';'))?;
+                None
+            }
             (Token::Word("struct"), _) => {
                 let name = lexer.next_ident()?;
 
@@ -2343,6 +2465,7 @@ impl Parser {
                     } else {
                         None
                     },
+                    diagnostic_filters: std::mem::take(&mut diagnostic_filters),
                     ..function
                 }))
             }
@@ -2350,6 +2473,10 @@ impl Parser {
             other => return Err(Error::Unexpected(other.1, ExpectedToken::GlobalItem)),
         };
 
+        if let Some(span) = diagnostic_span.filter(|_| !diagnostic_filters.is_empty()) {
+            return Err(Error::MisplacedDiagnosticAttribute(span));
+        }
+
         if let Some(kind) = kind {
             out.decls.append(
                 ast::GlobalDecl { kind, dependencies },
@@ -2385,6 +2512,7 @@ impl Parser {
             }
         }
 
+        tu.extension_uses = std::mem::take(&mut self.extension_uses);
         Ok(tu)
     }
 
@@ -636,3 +636,87 @@ fn parse_missing_workgroup_size() {
         Error::MissingWorkgroupSize(span) if span == Span::new(1, 8)
     ));
 }
+
+#[test]
+fn parse_diagnostic_filters() {
+    use crate::front::wgsl::{DiagnosticFilter, DiagnosticRule, Frontend, Options, Severity};
+
+    let shader = "
+        fn f() -> i32 {
+            return 1;
+            let a = 2;
+        }
+
+        @diagnostic(off, naga.unreachable_code)
+        fn g() -> i32 {
+            return 1;
+            let a = 2;
+        }";
+    let (_, warnings) = Frontend::new().parse_with_warnings(shader).unwrap();
+    assert_eq!(warnings.len(), 1);
+    assert_eq!(warnings[0].severity(), Severity::Warning);
+
+    // Options apply below directives and attributes.
+    let mut frontend = Frontend::new_with_options(Options {
+        diagnostic_filters: vec![DiagnosticFilter {
+            severity: Severity::Error,
+            rule: DiagnosticRule::UnreachableCode,
+        }],
+        ..Options::default()
+    });
+    assert!(frontend.parse(shader).is_err());
+    let with_directive = format!("diagnostic(info, naga.unreachable_code);{shader}");
+    let (_, warnings) = frontend.parse_with_warnings(&with_directive).unwrap();
+    assert_eq!(warnings.len(), 1);
+    assert_eq!(warnings[0].severity(), Severity::Info);
+
+    // Unknown rules are warned about, unless they belong to another implementation.
+    let (_, warnings) = Frontend::new()
+        .parse_with_warnings("diagnostic(off, unknown_rule); diagnostic(off, other.rule);")
+        .unwrap();
+    assert_eq!(warnings.len(), 1);
+    assert_eq!(warnings[0].severity(), Severity::Warning);
+}
+
+#[test]
+fn parse_enable_extensions() {
+    use crate::front::wgsl::{EnableExtensions, Frontend, Options};
+
+    let shader = "
+        enable naga_push_constants, naga_binding_array,;
+        var<push_constant> pc: u32;
+        @group(0) @binding(0)
+        var textures: binding_array<texture_2d<f32>>;";
+    Frontend::new().parse(shader).unwrap();
+
+    let mut frontend = Frontend::new_with_options(Options {
+        available_extensions: Some(
+            EnableExtensions::PUSH_CONSTANTS | EnableExtensions::BINDING_ARRAY,
+        ),
+        ..Options::default()
+    });
+    frontend.parse(shader).unwrap();
+
+    // An unavailable extension fails at its directive.
+    let mut frontend = Frontend::new_with_options(Options {
+        available_extensions: Some(EnableExtensions::PUSH_CONSTANTS),
+        ..Options::default()
+    });
+    let error = frontend.parse(shader).unwrap_err();
+    let (span, _) = error.labels().next().unwrap();
+    assert_eq!(&shader[span], "naga_binding_array");
+
+    // Or at its first use, if it isn't enabled.
+    let without_directive = shader.replace("naga_binding_array,", "");
+    let error = frontend.parse(&without_directive).unwrap_err();
+    let (span, _) = error.labels().next().unwrap();
+    assert_eq!(&without_directive[span], "binding_array");
+
+    let mut frontend = Frontend::new_with_options(Options {
+        available_extensions: Some(EnableExtensions::empty()),
+        ..Options::default()
+    });
+    let error = frontend.parse("var<push_constant> pc: u32;").unwrap_err();
+    let (span, _) = error.labels().next().unwrap();
+    assert_eq!(span.to_range(), Some(4..17));
+}
@@ -399,6 +399,12 @@ pub enum AddressSpace {
     Handle,
     /// Push constants.
     PushConstant,
+    /// Storage buffer data, accessed through an address in the address
+    /// space of the device, potentially mutable.
+    ///
+    /// Only pointers can be in this address space, and they are only
+    /// produced by [`Expression::PointerFromAddress`].
+    PhysicalStorage { access: StorageAccess },
 }
 
 /// Built-in inputs and outputs.
@@ -417,6 +423,7 @@ pub enum BuiltIn {
     InstanceIndex,
     PointSize,
     VertexIndex,
+    ViewportIndex,
     // fragment
     FragDepth,
     PointCoord,
@@ -1225,6 +1232,16 @@ pub enum MathFunction {
     Unpack2x16snorm,
     Unpack2x16unorm,
     Unpack2x16float,
+    // uniformity
+    /// Returns its argument, marked as possibly differing between invocations.
+    ///
+    /// Indexing a [`BindingArray`] with a value that isn't uniform needs this
+    /// for backends to lower the access as non-uniform, unless the validator
+    /// can already tell the index isn't uniform. Corresponds to WGSL's
+    /// `nonuniform` and GLSL's `nonuniformEXT`.
+    ///
+    /// [`BindingArray`]: TypeInner::BindingArray
+    NonUniform,
 }
 
 /// Sampling modifier to control the level of detail.
@@ -1343,6 +1360,19 @@ bitflags::bitflags! {
     }
 }
 
+/// Which end of a fragment shader critical section a
+/// [`Statement::FragmentInterlock`] is.
+#[derive(Clone, Copy, Debug, Hash, Eq, Ord, PartialEq, PartialOrd)]
+#[cfg_attr(feature = "serialize", derive(Serialize))]
+#[cfg_attr(feature = "deserialize", derive(Deserialize))]
+#[cfg_attr(feature = "arbitrary", derive(Arbitrary))]
+pub enum FragmentInterlock {
+    /// Starts the critical section.
+    Begin,
+    /// Ends the critical section.
+    End,
+}
+
 /// An expression that can be evaluated to obtain a value.
 ///
 /// This is a Single Static Assignment (SSA) scheme similar to SPIR-V.
@@ -1609,6 +1639,18 @@ pub enum Expression {
         /// Otherwise, bitcast.
         convert: Option<Bytes>,
     },
+    /// Reinterpret an address in the address space of the device as a pointer.
+    ///
+    /// The address is a `u64` scalar, such as the one returned by
+    /// `Buffer::gpu_address` in wgpu, offset to the value to point to. It
+    /// must be aligned to the alignment of the pointee type.
+    PointerFromAddress {
+        /// The address, which must be a 64-bit unsigned integer.
+        address: Handle<Expression>,
+        /// The type of the result, which must be a [`TypeInner::Pointer`]
+        /// in the [`AddressSpace::PhysicalStorage`] address space.
+        pointer: Handle<Type>,
+    },
     /// Result of calling another function.
     CallResult(Handle<Function>),
     /// Result of an atomic operation.
@@ -1854,6 +1896,17 @@ pub enum Statement {
     /// The `Barrier` flags control which memory accesses should be synchronized.
     /// If empty, this becomes purely an execution barrier.
     Barrier(Barrier),
+    /// Begins or ends the critical section of a fragment shader.
+    ///
+    /// The critical sections of the invocations covering a pixel run one at a
+    /// time, in primitive order, so that they can read, modify and write
+    /// storage buffers and textures at that pixel without racing, e.g. for
+    /// order-independent transparency.
+    ///
+    /// A fragment entry point has at most one critical section. Its begin
+    /// and end have to be statements of the body of the entry point itself,
+    /// in that order, and other functions can't have any.
+    FragmentInterlock(FragmentInterlock),
     /// Stores a value at an address.
     ///
     /// For [`TypeInner::Atomic`] type behind the pointer, the value
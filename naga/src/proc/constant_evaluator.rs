@@ -486,6 +486,8 @@ pub enum ConstantEvaluatorError {
     InvalidAccessIndexTy,
     #[error("Constants don't support array length expressions")]
     ArrayLength,
+    #[error("Constants don't support pointers made from addresses")]
+    PointerFromAddress,
     #[error("Cannot cast scalar components of expression `{from}` to type `{to}`")]
     InvalidCastArg { from: String, to: String },
     #[error("Cannot apply the unary op to the argument")]
@@ -870,6 +872,9 @@ impl<'a> ConstantEvaluator<'a> {
                     self.array_length(expr, span)
                 }
             },
+            Expression::PointerFromAddress { .. } => {
+                Err(ConstantEvaluatorError::PointerFromAddress)
+            }
             Expression::Load { .. } => Err(ConstantEvaluatorError::Load),
             Expression::LocalVariable(_) => Err(ConstantEvaluatorError::LocalVariable),
             Expression::Derivative { .. } => Err(ConstantEvaluatorError::Derivative),
@@ -1229,6 +1234,10 @@ impl<'a> ConstantEvaluator<'a> {
                 component_wise_concrete_int!(self, span, [arg], |e| { Ok([e.reverse_bits()]) })
             }
 
+            // uniformity
+            // A constant is always uniform, so there is nothing to mark.
+            crate::MathFunction::NonUniform => Ok(arg),
+
             fun => Err(ConstantEvaluatorError::NotImplemented(format!(
                 "{fun:?} built-in function"
             ))),
@@ -165,6 +165,10 @@ impl BoundsCheckPolicies {
             Some(crate::AddressSpace::Storage { access: _ } | crate::AddressSpace::Uniform) => {
                 self.buffer
             }
+            // Nothing is known about the memory behind a device address, not
+            // even the length of runtime-sized arrays, so there is nothing to
+            // check indices against.
+            Some(crate::AddressSpace::PhysicalStorage { .. }) => BoundsCheckPolicy::Unchecked,
             // This covers other address spaces, but also accessing vectors and
             // matrices by value, where no pointer is involved.
             _ => self.index,
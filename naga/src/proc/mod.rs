@@ -7,6 +7,7 @@ mod emitter;
 pub mod index;
 mod layouter;
 mod namer;
+mod strip;
 mod terminator;
 mod typifier;
 
@@ -17,6 +18,7 @@ pub use emitter::Emitter;
 pub use index::{BoundsCheckPolicies, BoundsCheckPolicy, IndexableLength, IndexableLengthError};
 pub use layouter::{Alignment, LayoutError, LayoutErrorInner, Layouter, TypeLayout};
 pub use namer::{EntryPointIndex, NameKey, Namer};
+pub use strip::{strip_names, SymbolMap};
 pub use terminator::ensure_block_returns;
 pub use typifier::{ResolveContext, ResolveError, TypeResolution};
 
@@ -409,7 +411,8 @@ impl super::AddressSpace {
             | crate::AddressSpace::Private
             | crate::AddressSpace::WorkGroup => Sa::LOAD | Sa::STORE,
             crate::AddressSpace::Uniform => Sa::LOAD,
-            crate::AddressSpace::Storage { access } => access,
+            crate::AddressSpace::Storage { access }
+            | crate::AddressSpace::PhysicalStorage { access } => access,
             crate::AddressSpace::Handle => Sa::LOAD,
             crate::AddressSpace::PushConstant => Sa::LOAD,
         }
@@ -498,6 +501,8 @@ impl super::MathFunction {
             Self::Unpack2x16snorm => 1,
             Self::Unpack2x16unorm => 1,
             Self::Unpack2x16float => 1,
+            // uniformity
+            Self::NonUniform => 1,
         }
     }
 }
@@ -0,0 +1,187 @@
+use crate::{FastIndexMap, UniqueArena};
+use std::fmt;
+
+/// The names [`strip_names`] removed from a module, keyed by the names that
+/// replaced them.
+///
+/// Since every name is replaced by a hash of itself, maps of different modules
+/// never disagree, and can be merged into one to symbolicate all of them.
+#[derive(Clone, Debug, Default)]
+pub struct SymbolMap {
+    names: FastIndexMap<String, String>,
+}
+
+impl SymbolMap {
+    /// Returns the original name of `stripped`, if it replaced one.
+    ///
+    /// Backends may append a suffix to a name to keep it unique, so a
+    /// name found in generated code may have to be trimmed after its last
+    /// `_` first.
+    pub fn get(&self, stripped: &str) -> Option<&str> {
+        self.names.get(stripped).map(String::as_str)
+    }
+
+    /// Returns the stripped names and the names they replaced.
+    pub fn iter(&self) -> impl Iterator<Item = (&str, &str)> {
+        self.names
+            .iter()
+            .map(|(stripped, name)| (stripped.as_str(), name.as_str()))
+    }
+
+    pub fn len(&self) -> usize {
+        self.names.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.names.is_empty()
+    }
+
+    /// Adds the names of `other` to this map.
+    pub fn extend(&mut self, other: SymbolMap) {
+        self.names.extend(other.names);
+    }
+
+    fn strip(&mut self, name: &mut Option<String>) {
+        if let Some(ref mut name) = *name {
+            let stripped = hash_name(name);
+            let original = std::mem::replace(name, stripped.clone());
+            self.names.entry(stripped).or_insert(original);
+        }
+    }
+}
+
+/// Writes one line per name, the stripped name followed by a tab and the
+/// original name.
+impl fmt::Display for SymbolMap {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for (stripped, name) in self.iter() {
+            writeln!(f, "{stripped}\t{name}")?;
+        }
+        Ok(())
+    }
+}
+
+/// Returns the name replacing `name`: `_` followed by the 64-bit FNV-1a hash
+/// of `name`, in hexadecimal.
+///
+/// The hash is stable across platforms and builds, so names in crash reports
+/// of shipped builds can be symbolicated with maps written anywhere else.
+fn hash_name(name: &str) -> String {
+    const OFFSET_BASIS: u64 = 0xcbf2_9ce4_8422_2325;
+    const PRIME: u64 = 0x0100_0000_01b3;
+
+    let hash = name.bytes().fold(OFFSET_BASIS, |hash, byte| {
+        (hash ^ byte as u64).wrapping_mul(PRIME)
+    });
+    format!("_{hash:016x}")
+}
+
+fn strip_function(function: &mut crate::Function, symbols: &mut SymbolMap) {
+    for argument in function.arguments.iter_mut() {
+        symbols.strip(&mut argument.name);
+    }
+    for (_, local) in function.local_variables.iter_mut() {
+        symbols.strip(&mut local.name);
+    }
+    for (_, name) in function.named_expressions.iter_mut() {
+        let mut stripped = Some(std::mem::take(name));
+        symbols.strip(&mut stripped);
+        *name = stripped.unwrap();
+    }
+}
+
+/// Replace the names in `module` with hashes of themselves, and return the
+/// names that were replaced.
+///
+/// This hides the names of types, struct members, constants, global
+/// variables, functions, and their arguments and locals from the code the
+/// backends generate, which usually carries them over, as well as from any
+/// debug information they emit. The names of entry points and overrides are
+/// kept, since pipelines refer to them.
+///
+/// Handles into `module` stay valid.
+pub fn strip_names(module: &mut crate::Module) -> SymbolMap {
+    let mut symbols = SymbolMap::default();
+
+    // Types are unique by value, names included, so the arena has to be
+    // rebuilt. Different names have different hashes, so no two types are
+    // merged, and every type keeps its handle.
+    let mut types = UniqueArena::new();
+    for (handle, ty) in module.types.iter() {
+        let mut ty = ty.clone();
+        symbols.strip(&mut ty.name);
+        if let crate::TypeInner::Struct {
+            ref mut members, ..
+        } = ty.inner
+        {
+            for member in members.iter_mut() {
+                symbols.strip(&mut member.name);
+            }
+        }
+        let new_handle = types.insert(ty, module.types.get_span(handle));
+        debug_assert_eq!(new_handle, handle);
+    }
+    module.types = types;
+
+    for (_, constant) in module.constants.iter_mut() {
+        symbols.strip(&mut constant.name);
+    }
+    for (_, global) in module.global_variables.iter_mut() {
+        symbols.strip(&mut global.name);
+    }
+    for (_, function) in module.functions.iter_mut() {
+        symbols.strip(&mut function.name);
+        strip_function(function, &mut symbols);
+    }
+    for entry_point in module.entry_points.iter_mut() {
+        strip_function(&mut entry_point.function, &mut symbols);
+    }
+
+    symbols
+}
+
+#[test]
+fn strip() {
+    let mut module = crate::Module::default();
+    let f32 = module.types.insert(
+        crate::Type {
+            name: None,
+            inner: crate::TypeInner::Scalar(crate::Scalar::F32),
+        },
+        Default::default(),
+    );
+    let ty = module.types.insert(
+        crate::Type {
+            name: Some("Light".to_string()),
+            inner: crate::TypeInner::Struct {
+                members: vec![crate::StructMember {
+                    name: Some("color".to_string()),
+                    ty: f32,
+                    binding: None,
+                    offset: 0,
+                }],
+                span: 4,
+            },
+        },
+        Default::default(),
+    );
+    module.functions.append(
+        crate::Function {
+            name: Some("shade".to_string()),
+            ..Default::default()
+        },
+        Default::default(),
+    );
+
+    let symbols = strip_names(&mut module);
+    assert_eq!(symbols.len(), 3);
+    let stripped = module.types[ty].name.as_deref().unwrap();
+    assert_eq!(stripped, "_3b0ab4b52326316f");
+    assert_eq!(symbols.get(stripped), Some("Light"));
+    for (_, function) in module.functions.iter() {
+        assert_eq!(
+            symbols.get(function.name.as_deref().unwrap()),
+            Some("shade")
+        );
+    }
+}
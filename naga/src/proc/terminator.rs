@@ -40,7 +40,8 @@ pub fn ensure_block_returns(block: &mut crate::Block) {
             | S::SubgroupBallot { .. }
             | S::SubgroupCollectiveOperation { .. }
             | S::SubgroupGather { .. }
-            | S::Barrier(_)),
+            | S::Barrier(_)
+            | S::FragmentInterlock(_)),
         )
         | None => block.push(S::Return { value: None }, Default::default()),
     }
@@ -665,7 +665,9 @@ impl<'a> ResolveContext<'a> {
                     Mf::Exp2 |
                     Mf::Log |
                     Mf::Log2 |
-                    Mf::Pow => res_arg.clone(),
+                    Mf::Pow |
+                    // uniformity
+                    Mf::NonUniform => res_arg.clone(),
                     Mf::Modf | Mf::Frexp => {
                         let (size, width) = match res_arg.inner_with(types) {
                             &Ti::Scalar(crate::Scalar {
@@ -866,6 +868,9 @@ impl<'a> ResolveContext<'a> {
                     )))
                 }
             },
+            crate::Expression::PointerFromAddress { pointer, .. } => {
+                TypeResolution::Handle(pointer)
+            }
             crate::Expression::CallResult(function) => {
                 let result = self.functions[function]
                     .result
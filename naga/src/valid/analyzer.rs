@@ -624,6 +624,8 @@ impl FunctionInfo {
                     // storage data is only uniform when read-only
                     As::Storage { access } => !access.contains(crate::StorageAccess::STORE),
                     As::Handle => false,
+                    // no global lives behind a device address
+                    As::PhysicalStorage { .. } => unreachable!(),
                 };
                 Uniformity {
                     non_uniform_result: if uniform { None } else { Some(handle) },
@@ -745,6 +747,18 @@ impl FunctionInfo {
                 non_uniform_result: self.add_ref(argument),
                 requirements: UniformityRequirements::empty(),
             },
+            E::Math {
+                fun: crate::MathFunction::NonUniform,
+                arg,
+                ..
+            } => {
+                // explicitly non-uniform, whatever the argument is
+                self.add_ref(arg);
+                Uniformity {
+                    non_uniform_result: Some(handle),
+                    requirements: UniformityRequirements::empty(),
+                }
+            }
             E::Math {
                 fun: _,
                 arg,
@@ -764,6 +778,20 @@ impl FunctionInfo {
                 non_uniform_result: self.add_ref(expr),
                 requirements: UniformityRequirements::empty(),
             },
+            E::PointerFromAddress { address, pointer } => {
+                let address_nur = self.add_ref(address);
+                // like storage buffers, the data is only uniform when read-only
+                let writable = resolve_context.types[pointer]
+                    .inner
+                    .pointer_space()
+                    .map_or(true, |space| {
+                        space.access().contains(crate::StorageAccess::STORE)
+                    });
+                Uniformity {
+                    non_uniform_result: if writable { Some(handle) } else { address_nur },
+                    requirements: UniformityRequirements::empty(),
+                }
+            }
             E::CallResult(function) => other_functions[function.index()].uniformity.clone(),
             E::AtomicResult { .. } | E::RayQueryProceedResult => Uniformity {
                 non_uniform_result: Some(handle),
@@ -869,6 +897,7 @@ impl FunctionInfo {
                     },
                     exit: ExitFlags::empty(),
                 },
+                S::FragmentInterlock(_) => FunctionUniformity::new(),
                 S::WorkGroupUniformLoad { pointer, .. } => {
                     let _condition_nur = self.add_ref(pointer);
 
@@ -26,6 +26,12 @@ pub enum ExpressionError {
     IndexOutOfBounds(Handle<crate::Expression>, u32),
     #[error("The expression {0:?} may only be indexed by a constant")]
     IndexMustBeConstant(Handle<crate::Expression>),
+    #[error("The address {0:?} must be a `u64` scalar")]
+    InvalidAddressType(Handle<crate::Expression>),
+    #[error("The type {0:?} made from an address must be a pointer in the `PhysicalStorage` address space")]
+    InvalidAddressPointerType(Handle<crate::Type>),
+    #[error("The binding array {0:?} is indexed by the non-uniform value {1:?}, which must be marked with `nonuniform`")]
+    NonUniformIndexNotMarked(Handle<crate::Expression>, Handle<crate::Expression>),
     #[error("Function argument {0:?} doesn't exist")]
     FunctionArgumentDoesntExist(u32),
     #[error("Loading of {0:?} can't be done")]
@@ -271,6 +277,27 @@ impl super::Validator {
                 if dynamic_indexing_restricted && function.expressions[index].is_dynamic_index() {
                     return Err(ExpressionError::IndexMustBeConstant(base));
                 }
+                if self.explicit_non_uniform_indexing
+                    && info[index].uniformity.non_uniform_result.is_some()
+                {
+                    let indexes_binding_array = match *base_type {
+                        Ti::BindingArray { .. } => true,
+                        Ti::Pointer { base, .. } => {
+                            matches!(module.types[base].inner, Ti::BindingArray { .. })
+                        }
+                        _ => false,
+                    };
+                    let marked = matches!(
+                        function.expressions[index],
+                        E::Math {
+                            fun: crate::MathFunction::NonUniform,
+                            ..
+                        }
+                    );
+                    if indexes_binding_array && !marked {
+                        return Err(ExpressionError::NonUniformIndexNotMarked(base, index));
+                    }
+                }
 
                 // If we know both the length and the index, we can do the
                 // bounds check now.
@@ -1343,6 +1370,15 @@ impl super::Validator {
                             return Err(ExpressionError::InvalidArgumentType(fun, 0, arg));
                         }
                     }
+                    Mf::NonUniform => {
+                        if arg1_ty.is_some() || arg2_ty.is_some() || arg3_ty.is_some() {
+                            return Err(ExpressionError::WrongArgumentCount(fun));
+                        }
+                        match *arg_ty {
+                            Ti::Scalar(_) | Ti::Vector { .. } => {}
+                            _ => return Err(ExpressionError::InvalidArgumentType(fun, 0, arg)),
+                        }
+                    }
                     Mf::Transpose => {
                         if arg1_ty.is_some() || arg2_ty.is_some() || arg3_ty.is_some() {
                             return Err(ExpressionError::WrongArgumentCount(fun));
@@ -1564,6 +1600,31 @@ impl super::Validator {
                 }
                 ShaderStages::all()
             }
+            E::PointerFromAddress { address, pointer } => {
+                if !self
+                    .capabilities
+                    .contains(super::Capabilities::BUFFER_DEVICE_ADDRESS)
+                {
+                    return Err(ExpressionError::MissingCapabilities(
+                        super::Capabilities::BUFFER_DEVICE_ADDRESS,
+                    ));
+                }
+                match resolver[address] {
+                    Ti::Scalar(Sc {
+                        kind: Sk::Uint,
+                        width: 8,
+                    }) => {}
+                    _ => return Err(ExpressionError::InvalidAddressType(address)),
+                }
+                match module.types[pointer].inner {
+                    Ti::Pointer {
+                        space: crate::AddressSpace::PhysicalStorage { .. },
+                        ..
+                    } => {}
+                    _ => return Err(ExpressionError::InvalidAddressPointerType(pointer)),
+                }
+                ShaderStages::all()
+            }
             E::CallResult(function) => mod_info.functions[function.index()].available_stages,
             E::AtomicResult { ty, comparison } => {
                 let scalar_predicate = |ty: &crate::TypeInner| match ty {
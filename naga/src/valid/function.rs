@@ -172,6 +172,8 @@ pub enum FunctionError {
     WorkgroupUniformLoadInvalidPointer(Handle<crate::Expression>),
     #[error("Subgroup operation is invalid")]
     InvalidSubgroup(#[from] SubgroupError),
+    #[error("A fragment interlock critical section has to begin and end once, in that order, in the body of an entry point")]
+    InvalidFragmentInterlock,
 }
 
 bitflags::bitflags! {
@@ -751,6 +753,18 @@ impl super::Validator {
                     stages &= super::ShaderStages::FRAGMENT;
                     finished = true;
                 }
+                S::FragmentInterlock(_) => {
+                    stages &= super::ShaderStages::FRAGMENT;
+                    if !self
+                        .capabilities
+                        .contains(super::Capabilities::FRAGMENT_SHADER_INTERLOCK)
+                    {
+                        return Err(FunctionError::MissingCapability(
+                            super::Capabilities::FRAGMENT_SHADER_INTERLOCK,
+                        )
+                        .with_span_static(span, "missing capability for this operation"));
+                    }
+                }
                 S::Barrier(barrier) => {
                     stages &= super::ShaderStages::COMPUTE;
                     if barrier.contains(crate::Barrier::SUB_GROUP) {
@@ -1298,7 +1312,80 @@ impl super::Validator {
                 )?
                 .stages;
             info.available_stages &= stages;
+            validate_fragment_interlocks(&fun.body, entry_point)?;
         }
         Ok(info)
     }
 }
+
+/// Checks that `body`, the body of a function, has no critical section if
+/// the function isn't an entry point, and otherwise at most one, with its
+/// begin and end directly in `body`.
+fn validate_fragment_interlocks(
+    body: &crate::Block,
+    entry_point: bool,
+) -> Result<(), WithSpan<FunctionError>> {
+    use crate::FragmentInterlock as Fi;
+
+    let mut expected = entry_point.then_some(Fi::Begin);
+    let mut begin_span = None;
+    for (statement, &span) in body.span_iter() {
+        let misplaced = match *statement {
+            crate::Statement::FragmentInterlock(interlock) if Some(interlock) == expected => {
+                expected = match interlock {
+                    Fi::Begin => {
+                        begin_span = Some(span);
+                        Some(Fi::End)
+                    }
+                    Fi::End => None,
+                };
+                continue;
+            }
+            crate::Statement::FragmentInterlock(_) => Some(span),
+            ref other => nested_blocks(other).find_map(find_fragment_interlock),
+        };
+        if let Some(span) = misplaced {
+            return Err(FunctionError::InvalidFragmentInterlock
+                .with_span_static(span, "misplaced fragment interlock"));
+        }
+    }
+    match (expected, begin_span) {
+        (Some(Fi::End), Some(span)) => Err(FunctionError::InvalidFragmentInterlock
+            .with_span_static(span, "critical section begins here but never ends")),
+        _ => Ok(()),
+    }
+}
+
+/// Returns the span of a [`crate::Statement::FragmentInterlock`] in `block`
+/// or the blocks nested in it, if there is one.
+fn find_fragment_interlock(block: &crate::Block) -> Option<crate::Span> {
+    block
+        .span_iter()
+        .find_map(|(statement, &span)| match *statement {
+            crate::Statement::FragmentInterlock(_) => Some(span),
+            ref other => nested_blocks(other).find_map(find_fragment_interlock),
+        })
+}
+
+/// Returns the blocks directly nested in `statement`.
+fn nested_blocks(statement: &crate::Statement) -> impl Iterator<Item = &crate::Block> {
+    let (first, second, cases): (_, _, &[crate::SwitchCase]) = match *statement {
+        crate::Statement::Block(ref block) => (Some(block), None, &[]),
+        crate::Statement::If {
+            ref accept,
+            ref reject,
+            ..
+        } => (Some(accept), Some(reject), &[]),
+        crate::Statement::Loop {
+            ref body,
+            ref continuing,
+            ..
+        } => (Some(body), Some(continuing), &[]),
+        crate::Statement::Switch { ref cases, .. } => (None, None, cases),
+        _ => (None, None, &[]),
+    };
+    first
+        .into_iter()
+        .chain(second)
+        .chain(cases.iter().map(|case| &case.body))
+}
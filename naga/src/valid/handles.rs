@@ -412,6 +412,10 @@ impl super::Validator {
             } => {
                 handle.check_dep(input)?;
             }
+            crate::Expression::PointerFromAddress { address, pointer } => {
+                validate_type(pointer)?;
+                handle.check_dep(address)?;
+            }
             crate::Expression::CallResult(function) => {
                 Self::validate_function_handle(function, functions)?;
                 if let Some(handle) = current_function {
@@ -602,7 +606,8 @@ impl super::Validator {
             crate::Statement::Break
             | crate::Statement::Continue
             | crate::Statement::Kill
-            | crate::Statement::Barrier(_) => Ok(()),
+            | crate::Statement::Barrier(_)
+            | crate::Statement::FragmentInterlock(_) => Ok(()),
         })
     }
 }
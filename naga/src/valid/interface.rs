@@ -169,6 +169,7 @@ impl VaryingContext<'_> {
                     Bi::CullDistance => Capabilities::CULL_DISTANCE,
                     Bi::PrimitiveIndex => Capabilities::PRIMITIVE_INDEX,
                     Bi::ViewIndex => Capabilities::MULTIVIEW,
+                    Bi::ViewportIndex => Capabilities::VIEWPORT_INDEX,
                     Bi::SampleIndex => Capabilities::MULTISAMPLED_SHADING,
                     Bi::NumSubgroups
                     | Bi::SubgroupId
@@ -206,6 +207,10 @@ impl VaryingContext<'_> {
                         self.stage == St::Vertex && self.output,
                         *ty_inner == Ti::Scalar(crate::Scalar::F32),
                     ),
+                    Bi::ViewportIndex => (
+                        self.stage == St::Vertex && self.output,
+                        *ty_inner == Ti::Scalar(crate::Scalar::U32),
+                    ),
                     Bi::PointCoord => (
                         self.stage == St::Fragment && !self.output,
                         *ty_inner
@@ -447,7 +452,7 @@ impl super::Validator {
         let type_info = &self.types[inner_ty.index()];
 
         let (required_type_flags, is_resource) = match var.space {
-            crate::AddressSpace::Function => {
+            crate::AddressSpace::Function | crate::AddressSpace::PhysicalStorage { .. } => {
                 return Err(GlobalVariableError::InvalidUsage(var.space))
             }
             crate::AddressSpace::Storage { access } => {
@@ -701,7 +706,9 @@ impl super::Validator {
             }
 
             let allowed_usage = match var.space {
-                crate::AddressSpace::Function => unreachable!(),
+                crate::AddressSpace::Function | crate::AddressSpace::PhysicalStorage { .. } => {
+                    unreachable!()
+                }
                 crate::AddressSpace::Uniform => GlobalUse::READ | GlobalUse::QUERY,
                 crate::AddressSpace::Storage { access } => storage_usage(access),
                 crate::AddressSpace::Handle => match module.types[var.ty].inner {
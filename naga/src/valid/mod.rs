@@ -114,6 +114,16 @@ bitflags::bitflags! {
         const SUBGROUP = 0x10000;
         /// Support for subgroup barriers.
         const SUBGROUP_BARRIER = 0x20000;
+        /// Support for [`BuiltIn::ViewportIndex`].
+        const VIEWPORT_INDEX = 0x40000;
+        /// Support for 64-bit signed and unsigned integer atomics.
+        const SHADER_INT64_ATOMICS = 0x80000;
+        /// Support for [`AddressSpace::PhysicalStorage`] pointers, made from
+        /// buffer device addresses with [`Expression::PointerFromAddress`].
+        const BUFFER_DEVICE_ADDRESS = 0x100000;
+        /// Support for fragment shader critical sections, with
+        /// [`crate::Statement::FragmentInterlock`].
+        const FRAGMENT_SHADER_INTERLOCK = 0x200000;
     }
 }
 
@@ -233,6 +243,7 @@ pub struct Validator {
     valid_expression_set: BitSet,
     override_ids: FastHashSet<u16>,
     allow_overrides: bool,
+    explicit_non_uniform_indexing: bool,
 }
 
 #[derive(Clone, Debug, thiserror::Error)]
@@ -385,6 +396,7 @@ impl Validator {
             valid_expression_set: BitSet::new(),
             override_ids: FastHashSet::default(),
             allow_overrides: true,
+            explicit_non_uniform_indexing: false,
         }
     }
 
@@ -398,6 +410,20 @@ impl Validator {
         self
     }
 
+    /// Require non-uniform indices into binding arrays to be marked.
+    ///
+    /// When enabled, indexing a [`BindingArray`] with a value that the
+    /// uniformity analysis can't prove to be uniform is an error, unless the
+    /// index is directly wrapped in [`MathFunction::NonUniform`]. Some drivers
+    /// silently produce wrong results for unmarked non-uniform indices.
+    ///
+    /// [`BindingArray`]: crate::TypeInner::BindingArray
+    /// [`MathFunction::NonUniform`]: crate::MathFunction::NonUniform
+    pub fn explicit_non_uniform_indexing(&mut self, enabled: bool) -> &mut Self {
+        self.explicit_non_uniform_indexing = enabled;
+        self
+    }
+
     /// Reset the validator internals
     pub fn reset(&mut self) {
         self.types.clear();
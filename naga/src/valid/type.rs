@@ -194,9 +194,12 @@ const fn ptr_space_argument_flag(space: crate::AddressSpace) -> TypeFlags {
     use crate::AddressSpace as As;
     match space {
         As::Function | As::Private => TypeFlags::ARGUMENT,
-        As::Uniform | As::Storage { .. } | As::Handle | As::PushConstant | As::WorkGroup => {
-            TypeFlags::empty()
-        }
+        As::Uniform
+        | As::Storage { .. }
+        | As::PhysicalStorage { .. }
+        | As::Handle
+        | As::PushConstant
+        | As::WorkGroup => TypeFlags::empty(),
     }
 }
 
@@ -359,7 +362,15 @@ impl super::Validator {
                     | crate::ScalarKind::Float
                     | crate::ScalarKind::AbstractInt
                     | crate::ScalarKind::AbstractFloat => false,
-                    crate::ScalarKind::Sint | crate::ScalarKind::Uint => width == 4,
+                    crate::ScalarKind::Sint | crate::ScalarKind::Uint => match width {
+                        4 => true,
+                        8 => {
+                            self.require_type_capability(Capabilities::SHADER_INT64_ATOMICS)?;
+                            self.check_width(crate::Scalar { kind, width })?;
+                            true
+                        }
+                        _ => false,
+                    },
                 };
                 if !good {
                     return Err(TypeError::InvalidAtomicWidth(kind, width));
@@ -377,6 +388,15 @@ impl super::Validator {
                     return Err(TypeError::InvalidPointerBase(base));
                 }
 
+                // Values behind a device address are laid out like the
+                // contents of storage buffers.
+                if let As::PhysicalStorage { .. } = space {
+                    self.require_type_capability(Capabilities::BUFFER_DEVICE_ADDRESS)?;
+                    if !base_info.flags.contains(TypeFlags::HOST_SHAREABLE) {
+                        return Err(TypeError::InvalidPointerBase(base));
+                    }
+                }
+
                 // Runtime-sized values can only live in the `Storage` address
                 // space, so it's useless to have a pointer to such a type in
                 // any other space.
@@ -390,7 +410,7 @@ impl super::Validator {
                 // may also present difficulties in code generation).
                 if !base_info.flags.contains(TypeFlags::SIZED) {
                     match space {
-                        As::Storage { .. } => {}
+                        As::Storage { .. } | As::PhysicalStorage { .. } => {}
                         _ => {
                             return Err(TypeError::InvalidPointerToUnsized { base, space });
                         }
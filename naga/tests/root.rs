@@ -1,4 +1,6 @@
 mod example_wgsl;
 mod snapshots;
 mod spirv_capabilities;
+mod spirv_combined_image_sampler;
+mod spirv_debug_info;
 mod wgsl_errors;
@@ -0,0 +1,83 @@
+/*!
+Test the SPIR-V backend's images declared as combined image samplers.
+*/
+
+#![cfg(all(feature = "wgsl-in", feature = "spv-out"))]
+
+use spirv::Op;
+
+const SOURCE: &str = "
+@group(0) @binding(0)
+var frame: texture_2d<f32>;
+@group(0) @binding(1)
+var frame_sampler: sampler;
+
+@fragment
+fn main(@location(0) uv: vec2<f32>) -> @location(0) vec4<f32> {
+    let size = vec2<f32>(textureDimensions(frame));
+    return textureSample(frame, frame_sampler, uv + 0.5 / size);
+}
+";
+
+/// Writes `SOURCE`, with the image declared as a combined image sampler if
+/// `combined` is set, and returns the opcodes of its instructions.
+fn write(combined: bool) -> Vec<Op> {
+    use naga::back::spv;
+    use naga::valid;
+
+    let module = naga::front::wgsl::parse_str(SOURCE).unwrap();
+    let info = valid::Validator::new(valid::ValidationFlags::all(), valid::Capabilities::all())
+        .validate(&module)
+        .expect("validation failed");
+
+    let mut binding_map = spv::BindingMap::default();
+    binding_map.insert(
+        naga::ResourceBinding {
+            group: 0,
+            binding: 0,
+        },
+        spv::BindingInfo {
+            binding_array_size: None,
+            combined_image_sampler: combined,
+        },
+    );
+    let options = spv::Options {
+        binding_map,
+        ..spv::Options::default()
+    };
+    let mut words = vec![];
+    let mut writer = spv::Writer::new(&options).unwrap();
+    writer
+        .write(&module, &info, None, &None, &mut words)
+        .unwrap();
+
+    // Skip the header.
+    let mut words = &words[5..];
+    let mut ops = Vec::new();
+    while let Some(&first) = words.first() {
+        let count = (first >> 16) as usize;
+        ops.push(Op::from_u32(first & 0xffff).unwrap());
+        words = &words[count..];
+    }
+    ops
+}
+
+#[test]
+fn combined_image_sampler() {
+    let ops = write(true);
+    // The sampled image is loaded from the variable, and the image is
+    // extracted from it for queries.
+    assert!(ops.contains(&Op::TypeSampledImage));
+    assert!(ops.contains(&Op::Image));
+    assert!(ops.contains(&Op::ImageQuerySizeLod));
+    assert!(ops.contains(&Op::ImageSampleImplicitLod));
+    // The sampler in the shader is ignored.
+    assert!(!ops.contains(&Op::SampledImage));
+}
+
+#[test]
+fn separate_image_and_sampler() {
+    let ops = write(false);
+    assert!(ops.contains(&Op::SampledImage));
+    assert!(!ops.contains(&Op::Image));
+}
@@ -0,0 +1,148 @@
+/*!
+Test the SPIR-V backend's `NonSemantic.Shader.DebugInfo.100` instructions.
+*/
+
+#![cfg(all(feature = "wgsl-in", feature = "spv-out"))]
+
+use spirv::Op;
+
+const SOURCE: &str = "
+fn square(x: f32) -> f32 {
+    let squared = x * x;
+    return squared;
+}
+
+@compute @workgroup_size(1)
+fn main() {
+    var total = vec2<f32>(0.0);
+    for (var i = 0u; i < 4u; i++) {
+        total.x += square(f32(i));
+    }
+}
+";
+
+// Opcodes of `NonSemantic.Shader.DebugInfo.100`.
+const DEBUG_FUNCTION: u32 = 20;
+const DEBUG_SCOPE: u32 = 23;
+const DEBUG_LOCAL_VARIABLE: u32 = 26;
+const DEBUG_DECLARE: u32 = 28;
+const DEBUG_VALUE: u32 = 29;
+const DEBUG_FUNCTION_DEFINITION: u32 = 101;
+const DEBUG_LINE: u32 = 103;
+
+/// Writes `SOURCE` with `flags`, and returns its instructions as opcodes and
+/// operands.
+fn write(flags: naga::back::spv::WriterFlags) -> Vec<(Op, Vec<u32>)> {
+    use naga::back::spv;
+    use naga::valid;
+
+    let module = naga::front::wgsl::parse_str(SOURCE).unwrap();
+    let info = valid::Validator::new(valid::ValidationFlags::all(), valid::Capabilities::all())
+        .validate(&module)
+        .expect("validation failed");
+
+    let options = spv::Options {
+        flags,
+        debug_info: Some(spv::DebugInfo {
+            source_code: SOURCE,
+            file_name: "debug_info.wgsl".as_ref(),
+        }),
+        ..spv::Options::default()
+    };
+    let mut words = vec![];
+    let mut writer = spv::Writer::new(&options).unwrap();
+    writer
+        .write(&module, &info, None, &options.debug_info, &mut words)
+        .unwrap();
+
+    // Skip the header.
+    let mut words = &words[5..];
+    let mut instructions = Vec::new();
+    while let Some(&first) = words.first() {
+        let count = (first >> 16) as usize;
+        let op = Op::from_u32(first & 0xffff).unwrap();
+        instructions.push((op, words[1..count].to_vec()));
+        words = &words[count..];
+    }
+    instructions
+}
+
+fn string(operands: &[u32]) -> String {
+    let bytes: Vec<u8> = operands
+        .iter()
+        .flat_map(|word| word.to_le_bytes())
+        .take_while(|&byte| byte != 0)
+        .collect();
+    String::from_utf8(bytes).unwrap()
+}
+
+fn debug_info_set_id(instructions: &[(Op, Vec<u32>)]) -> Option<u32> {
+    instructions.iter().find_map(|(op, operands)| {
+        (*op == Op::ExtInstImport && string(&operands[1..]) == "NonSemantic.Shader.DebugInfo.100")
+            .then_some(operands[0])
+    })
+}
+
+#[test]
+fn non_semantic_debug_info() {
+    use naga::back::spv::WriterFlags;
+
+    let instructions = write(WriterFlags::DEBUG | WriterFlags::NON_SEMANTIC_DEBUG_INFO);
+    let set_id = debug_info_set_id(&instructions).expect("the instruction set should be imported");
+    assert!(instructions.iter().any(|(op, operands)| {
+        *op == Op::Extension && string(operands) == "SPV_KHR_non_semantic_info"
+    }));
+
+    // The instructions of the set, grouped into the module's declarations and
+    // the blocks of every function.
+    let mut declarations = Vec::new();
+    let mut functions: Vec<Vec<Vec<u32>>> = Vec::new();
+    for (op, operands) in instructions.iter() {
+        match *op {
+            Op::Function => functions.push(Vec::new()),
+            Op::FunctionEnd => {}
+            Op::Label => functions.last_mut().unwrap().push(Vec::new()),
+            Op::ExtInst if operands[2] == set_id => {
+                match functions.last_mut().and_then(|blocks| blocks.last_mut()) {
+                    Some(block) => block.push(operands[3]),
+                    None => declarations.push(operands[3]),
+                }
+            }
+            _ => {}
+        }
+    }
+
+    let count = |op| {
+        declarations
+            .iter()
+            .filter(|&&declared| declared == op)
+            .count()
+    };
+    assert_eq!(count(DEBUG_FUNCTION), 2);
+    // `x`, `squared`, `total` and `i`.
+    assert_eq!(count(DEBUG_LOCAL_VARIABLE), 4);
+
+    assert_eq!(functions.len(), 2);
+    for blocks in functions.iter() {
+        for (index, ops) in blocks.iter().enumerate() {
+            assert_eq!(ops.first(), Some(&DEBUG_SCOPE));
+            assert_eq!(
+                ops.contains(&DEBUG_FUNCTION_DEFINITION),
+                index == 0,
+                "only entry blocks should define their function"
+            );
+        }
+
+        let ops: Vec<u32> = blocks.concat();
+        assert!(ops.contains(&DEBUG_LINE));
+        assert!(ops.contains(&DEBUG_DECLARE) || ops.contains(&DEBUG_VALUE));
+    }
+}
+
+#[test]
+fn non_semantic_debug_info_disabled() {
+    use naga::back::spv::WriterFlags;
+
+    let instructions = write(WriterFlags::DEBUG);
+    assert_eq!(debug_info_set_id(&instructions), None);
+}
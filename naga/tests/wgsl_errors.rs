@@ -726,6 +726,98 @@ fn reserved_keyword() {
     );
 }
 
+#[test]
+fn diagnostic_filters() {
+    check(
+        r#"
+            diagnostic(off, derivative_uniformity);
+            diagnostic(error, derivative_uniformity);
+        "#,
+        r###"error: conflicting diagnostic filters for 'derivative_uniformity'
+  ┌─ wgsl:2:29
+  │
+2 │             diagnostic(off, derivative_uniformity);
+  │                             ^^^^^^^^^^^^^^^^^^^^^ previously set here
+3 │             diagnostic(error, derivative_uniformity);
+  │                               ^^^^^^^^^^^^^^^^^^^^^ set to a different severity here
+
+"###,
+    );
+    check(
+        r#"
+            const a = 1;
+            diagnostic(off, derivative_uniformity);
+        "#,
+        r###"error: directives must come before any declaration
+  ┌─ wgsl:3:13
+  │
+3 │             diagnostic(off, derivative_uniformity);
+  │             ^^^^^^^^^^ directive after a declaration
+
+"###,
+    );
+    check(
+        r#"
+            @diagnostic(off, derivative_uniformity)
+            var<private> a: i32;
+        "#,
+        r###"error: the diagnostic attribute can only be applied to functions
+  ┌─ wgsl:2:14
+  │
+2 │             @diagnostic(off, derivative_uniformity)
+  │              ^^^^^^^^^^ not applied to a function
+
+"###,
+    );
+    check(
+        r#"
+            diagnostic(error, naga.unreachable_code);
+            fn f() {
+                return;
+                let a = 1;
+            }
+        "#,
+        r###"error: code is unreachable
+  ┌─ wgsl:5:17
+  │
+5 │                 let a = 1;
+  │                 ^^^^^^^^^^ this statement is never executed
+  │
+  = note: reported by the 'naga.unreachable_code' diagnostic rule
+
+"###,
+    );
+}
+
+#[test]
+fn enable_extensions() {
+    check(
+        r#"
+            enable naga_push_constants, naga_tensors;
+        "#,
+        r###"error: unknown extension: 'naga_tensors'
+  ┌─ wgsl:2:41
+  │
+2 │             enable naga_push_constants, naga_tensors;
+  │                                         ^^^^^^^^^^^^ unknown extension
+
+"###,
+    );
+    check(
+        r#"
+            const a = 1;
+            enable naga_ray_query;
+        "#,
+        r###"error: directives must come before any declaration
+  ┌─ wgsl:3:13
+  │
+3 │             enable naga_ray_query;
+  │             ^^^^^^ directive after a declaration
+
+"###,
+    );
+}
+
 #[test]
 fn module_scope_identifier_redefinition() {
     // const
@@ -966,6 +1058,140 @@ fn int64_capability() {
     }
 }
 
+#[test]
+fn int64_atomic_capability() {
+    check_validation! {
+        "var<workgroup> counter: atomic<u64>;",
+        "var<workgroup> counter: atomic<i64>;":
+        Err(naga::valid::ValidationError::Type {
+            source: naga::valid::TypeError::MissingCapability(naga::valid::Capabilities::SHADER_INT64_ATOMICS),
+            ..
+        }),
+        naga::valid::Capabilities::SHADER_INT64
+    }
+
+    check_validation! {
+        "var<workgroup> counter: atomic<u64>;":
+        Ok(_),
+        naga::valid::Capabilities::SHADER_INT64 | naga::valid::Capabilities::SHADER_INT64_ATOMICS
+    }
+}
+
+#[test]
+fn buffer_device_address_capability() {
+    check_validation! {
+        "enable naga_buffer_device_address;
+        fn load(address: u64) -> u32 {
+            let p = bitcast<ptr<physical_storage, u32, read_write>>(address);
+            *p += 1u;
+            return *p;
+        }":
+        Err(naga::valid::ValidationError::Type {
+            source: naga::valid::TypeError::MissingCapability(naga::valid::Capabilities::BUFFER_DEVICE_ADDRESS),
+            ..
+        }),
+        naga::valid::Capabilities::SHADER_INT64
+    }
+
+    check_validation! {
+        "enable naga_buffer_device_address;
+        fn load(address: u64) -> u32 {
+            let p = bitcast<ptr<physical_storage, u32, read_write>>(address);
+            *p += 1u;
+            return *p;
+        }":
+        Ok(_),
+        naga::valid::Capabilities::SHADER_INT64 | naga::valid::Capabilities::BUFFER_DEVICE_ADDRESS
+    }
+}
+
+#[test]
+fn fragment_interlock() {
+    check_validation! {
+        "
+        @group(0) @binding(0) var<storage, read_write> color: array<u32>;
+
+        @fragment
+        fn main(@builtin(position) position: vec4<f32>) {
+            let index = u32(position.x);
+            beginInvocationInterlock();
+            color[index] = color[index] + 1u;
+            endInvocationInterlock();
+        }
+        ":
+        Err(naga::valid::ValidationError::EntryPoint {
+            source: naga::valid::EntryPointError::Function(
+                naga::valid::FunctionError::MissingCapability(
+                    naga::valid::Capabilities::FRAGMENT_SHADER_INTERLOCK
+                )
+            ),
+            ..
+        })
+    }
+
+    check_validation! {
+        "
+        @group(0) @binding(0) var<storage, read_write> color: array<u32>;
+
+        @fragment
+        fn main(@builtin(position) position: vec4<f32>) {
+            let index = u32(position.x);
+            beginInvocationInterlock();
+            color[index] = color[index] + 1u;
+            endInvocationInterlock();
+        }
+        ":
+        Ok(_),
+        naga::valid::Capabilities::FRAGMENT_SHADER_INTERLOCK
+    }
+
+    check_validation! {
+        "
+        @fragment
+        fn main() {
+            beginInvocationInterlock();
+        }
+        ",
+        "
+        @fragment
+        fn main() {
+            endInvocationInterlock();
+            beginInvocationInterlock();
+        }
+        ",
+        "
+        @fragment
+        fn main(@builtin(position) position: vec4<f32>) {
+            if position.x > 0.5 {
+                beginInvocationInterlock();
+                endInvocationInterlock();
+            }
+        }
+        ":
+        Err(naga::valid::ValidationError::EntryPoint {
+            source: naga::valid::EntryPointError::Function(
+                naga::valid::FunctionError::InvalidFragmentInterlock
+            ),
+            ..
+        }),
+        naga::valid::Capabilities::FRAGMENT_SHADER_INTERLOCK
+    }
+
+    check_validation! {
+        "
+        fn critical_section() {
+            beginInvocationInterlock();
+            endInvocationInterlock();
+        }
+        ":
+        Err(naga::valid::ValidationError::Function {
+            source: naga::valid::FunctionError::InvalidFragmentInterlock,
+            ..
+        }),
+        naga::valid::Capabilities::FRAGMENT_SHADER_INTERLOCK
+    }
+}
+
 #[test]
 fn invalid_arrays() {
     check_validation! {
@@ -2114,6 +2340,46 @@ fn binding_array_non_struct() {
     }
 }
 
+#[test]
+fn binding_array_non_uniform_index_not_marked() {
+    let validate = |index: &str| {
+        let source = format!(
+            "
+            @group(0) @binding(0) var textures: binding_array<texture_2d<f32>, 4>;
+            @group(0) @binding(1) var samp: sampler;
+
+            @fragment
+            fn main(@location(0) @interpolate(flat) index: u32) -> @location(0) vec4<f32> {{
+                return textureSample(textures[{index}], samp, vec2(0.0));
+            }}
+            "
+        );
+        let module = naga::front::wgsl::parse_str(&source).unwrap();
+        naga::valid::Validator::new(
+            naga::valid::ValidationFlags::all(),
+            naga::valid::Capabilities::all(),
+        )
+        .explicit_non_uniform_indexing(true)
+        .validate(&module)
+        .map_err(|e| e.into_inner())
+    };
+
+    assert!(matches!(
+        validate("index"),
+        Err(naga::valid::ValidationError::EntryPoint {
+            source: naga::valid::EntryPointError::Function(
+                naga::valid::FunctionError::Expression {
+                    source: naga::valid::ExpressionError::NonUniformIndexNotMarked(..),
+                    ..
+                }
+            ),
+            ..
+        })
+    ));
+    assert!(validate("nonuniform(index)").is_ok());
+    assert!(validate("1u").is_ok());
+}
+
 #[test]
 fn compaction_preserves_spans() {
     let source = r#"
@@ -0,0 +1,444 @@
+/*! Reconstructs the GPU memory usage of a trace over its timeline.
+
+Traces don't record how the backend allocated memory, so it's modeled:
+buffers and textures are sized from their descriptors, sorted into heaps by
+how they are mapped, and placed by a first-fit allocator in each heap. This
+is enough to tell which labels hold the most memory, which resources are
+never freed, which are reallocated over and over, and how fragmented the
+heaps become.
+
+Usage: `memory-report <trace dir> [<folded stacks file>]`
+
+The report goes to the standard output. If a file is given, the resources
+alive at the peak of the memory usage are also written to it as folded stacks
+(`heap;kind;label bytes`), which flame graph tools like `inferno` turn into a
+chart.
+!*/
+
+#[cfg(not(target_arch = "wasm32"))]
+fn main() {
+    use wgc::device::trace;
+
+    use std::{
+        collections::HashMap,
+        fs,
+        io::Write as _,
+        path::{Path, PathBuf},
+    };
+
+    env_logger::init();
+
+    let mut args = std::env::args().skip(1);
+    let dir = match args.next() {
+        Some(arg) if Path::new(&arg).is_dir() => PathBuf::from(arg),
+        _ => panic!("Provide the dir path as the parameter"),
+    };
+    let folded_path = args.next().map(PathBuf::from);
+
+    log::info!("Loading trace '{:?}'", dir);
+    let file = fs::File::open(dir.join(trace::FILE_NAME)).unwrap();
+    let actions: Vec<trace::Action> = ron::de::from_reader(file).unwrap();
+    log::info!("Found {} actions", actions.len());
+
+    let events = actions
+        .into_iter()
+        .filter_map(|action| match action {
+            trace::Action::CreateBuffer(id, desc) => {
+                let heap = if desc.usage.contains(wgt::BufferUsages::MAP_READ) {
+                    HeapKind::Readback
+                } else if desc.usage.contains(wgt::BufferUsages::MAP_WRITE) {
+                    HeapKind::Upload
+                } else {
+                    HeapKind::DeviceLocal
+                };
+                Some(Event::Create {
+                    resource: Resource::Buffer(id),
+                    label: desc.label.as_deref().unwrap_or(UNLABELED).to_string(),
+                    heap,
+                    size: desc.size,
+                    alignment: BUFFER_ALIGNMENT,
+                })
+            }
+            trace::Action::CreateTexture(id, desc) => Some(Event::Create {
+                resource: Resource::Texture(id),
+                label: desc.label.as_deref().unwrap_or(UNLABELED).to_string(),
+                heap: HeapKind::DeviceLocal,
+                size: texture_size(&desc),
+                alignment: TEXTURE_ALIGNMENT,
+            }),
+            // Whichever comes first of destroying a resource and dropping
+            // its last handle releases its memory.
+            trace::Action::FreeBuffer(id) | trace::Action::DestroyBuffer(id) => {
+                Some(Event::Free(Resource::Buffer(id)))
+            }
+            trace::Action::FreeTexture(id) | trace::Action::DestroyTexture(id) => {
+                Some(Event::Free(Resource::Texture(id)))
+            }
+            trace::Action::Submit(index, _) => Some(Event::Submit(index)),
+            _ => None,
+        })
+        .collect::<Vec<_>>();
+
+    // Allocations of the live resources, with their heap, offset, size and label.
+    let mut live = HashMap::new();
+    let mut heaps = HeapKind::ALL.map(|_| Heap::default());
+    // Index of the event, total live bytes and latest submission at the peak.
+    let mut peak = (0, 0, None);
+    let mut last_submission = None;
+    let mut allocation_counts = HashMap::<_, (usize, u64)>::new();
+
+    println!("Timeline (live / extent per heap, at each submission):");
+    for (index, event) in events.iter().enumerate() {
+        match *event {
+            Event::Create {
+                resource,
+                ref label,
+                heap,
+                size,
+                alignment,
+            } => {
+                let offset = heaps[heap as usize].allocate(size, alignment);
+                live.insert(resource, (heap, offset, size, label));
+                let count = allocation_counts
+                    .entry((heap, resource.kind(), label))
+                    .or_default();
+                count.0 += 1;
+                count.1 += size;
+            }
+            Event::Free(resource) => {
+                if let Some((heap, offset, size, _)) = live.remove(&resource) {
+                    heaps[heap as usize].free(offset, size);
+                }
+            }
+            Event::Submit(submission) => {
+                let columns = HeapKind::ALL
+                    .iter()
+                    .map(|&heap| {
+                        let state = &heaps[heap as usize];
+                        format!(
+                            "{} {} / {}",
+                            heap.name(),
+                            format_bytes(state.live),
+                            format_bytes(state.extent)
+                        )
+                    })
+                    .collect::<Vec<_>>();
+                println!("  submission {submission}: {}", columns.join(", "));
+                last_submission = Some(submission);
+            }
+        }
+        let total = heaps.iter().map(|heap| heap.live).sum::<u64>();
+        if total > peak.1 {
+            peak = (index, total, last_submission);
+        }
+    }
+
+    println!();
+    println!("Heaps at the end of the trace:");
+    for heap in HeapKind::ALL {
+        let state = &heaps[heap as usize];
+        println!(
+            "  {}: {} live in {} ({} free ranges, largest {}, {:.1}% fragmentation)",
+            heap.name(),
+            format_bytes(state.live),
+            format_bytes(state.extent),
+            state.free_ranges.len(),
+            format_bytes(state.largest_free_range()),
+            state.fragmentation() * 100.0,
+        );
+    }
+
+    // Replay the events up to the peak to find out what was alive then.
+    let mut peak_live = HashMap::new();
+    for event in events.iter().take(peak.0 + 1) {
+        match *event {
+            Event::Create {
+                resource,
+                ref label,
+                heap,
+                size,
+                ..
+            } => {
+                peak_live.insert(resource, (heap, resource.kind(), label, size));
+            }
+            Event::Free(resource) => {
+                peak_live.remove(&resource);
+            }
+            Event::Submit(_) => {}
+        }
+    }
+    let mut peak_usage = HashMap::<_, (usize, u64)>::new();
+    for &(heap, kind, label, size) in peak_live.values() {
+        let usage = peak_usage.entry((heap, kind, label)).or_default();
+        usage.0 += 1;
+        usage.1 += size;
+    }
+    let mut peak_usage = peak_usage.into_iter().collect::<Vec<_>>();
+    peak_usage.sort_by(|a, b| b.1 .1.cmp(&a.1 .1).then(a.0.cmp(&b.0)));
+
+    println!();
+    match peak.2 {
+        Some(submission) => println!(
+            "Peak usage of {} after submission {submission}, by label:",
+            format_bytes(peak.1)
+        ),
+        None => println!(
+            "Peak usage of {} before the first submission, by label:",
+            format_bytes(peak.1)
+        ),
+    }
+    for &((heap, kind, label), (count, size)) in peak_usage.iter().take(REPORTED_LABELS) {
+        println!(
+            "  {:>12}  {count:>6} {kind}s  {}  {label}",
+            format_bytes(size),
+            heap.name()
+        );
+    }
+
+    let mut leaks = HashMap::<_, (usize, u64)>::new();
+    for (resource, &(heap, _, size, label)) in &live {
+        let leak = leaks.entry((heap, resource.kind(), label)).or_default();
+        leak.0 += 1;
+        leak.1 += size;
+    }
+    let mut leaks = leaks.into_iter().collect::<Vec<_>>();
+    leaks.sort_by(|a, b| b.1 .1.cmp(&a.1 .1).then(a.0.cmp(&b.0)));
+
+    println!();
+    println!("Never freed, by label:");
+    for &((heap, kind, label), (count, size)) in leaks.iter().take(REPORTED_LABELS) {
+        println!(
+            "  {:>12}  {count:>6} {kind}s  {}  {label}",
+            format_bytes(size),
+            heap.name()
+        );
+    }
+
+    let mut churn = allocation_counts
+        .into_iter()
+        .filter(|&(_, (count, _))| count > 1)
+        .collect::<Vec<_>>();
+    churn.sort_by(|a, b| b.1 .0.cmp(&a.1 .0).then(a.0.cmp(&b.0)));
+
+    println!();
+    println!("Most reallocated, by label:");
+    for &((heap, kind, label), (count, size)) in churn.iter().take(REPORTED_LABELS) {
+        println!(
+            "  {count:>6} {kind}s  {:>12} in total  {}  {label}",
+            format_bytes(size),
+            heap.name()
+        );
+    }
+
+    if let Some(path) = folded_path {
+        let mut file = std::io::BufWriter::new(fs::File::create(path).unwrap());
+        for &((heap, kind, label), (_, size)) in peak_usage.iter() {
+            // Folded stacks use `;` to separate frames and a space before the count.
+            let label = label.replace([';', ' '], "_");
+            writeln!(file, "{};{kind};{label} {size}", heap.name()).unwrap();
+        }
+        file.flush().unwrap();
+    }
+}
+
+/// Alignment of buffers in the modeled heaps.
+#[cfg(not(target_arch = "wasm32"))]
+const BUFFER_ALIGNMENT: u64 = 256;
+/// Alignment of textures in the modeled heaps, which is what most backends
+/// align images that aren't tiny to.
+#[cfg(not(target_arch = "wasm32"))]
+const TEXTURE_ALIGNMENT: u64 = 64 << 10;
+/// Label of the resources created without one.
+#[cfg(not(target_arch = "wasm32"))]
+const UNLABELED: &str = "<unlabeled>";
+/// Number of labels listed in each section of the report.
+#[cfg(not(target_arch = "wasm32"))]
+const REPORTED_LABELS: usize = 20;
+
+#[cfg(not(target_arch = "wasm32"))]
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+enum Resource {
+    Buffer(wgc::id::BufferId),
+    Texture(wgc::id::TextureId),
+}
+
+#[cfg(not(target_arch = "wasm32"))]
+impl Resource {
+    fn kind(self) -> &'static str {
+        match self {
+            Self::Buffer(_) => "buffer",
+            Self::Texture(_) => "texture",
+        }
+    }
+}
+
+#[cfg(not(target_arch = "wasm32"))]
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+enum HeapKind {
+    DeviceLocal,
+    /// Host visible memory of buffers mapped for writing.
+    Upload,
+    /// Host visible memory of buffers mapped for reading.
+    Readback,
+}
+
+#[cfg(not(target_arch = "wasm32"))]
+impl HeapKind {
+    const ALL: [Self; 3] = [Self::DeviceLocal, Self::Upload, Self::Readback];
+
+    fn name(self) -> &'static str {
+        match self {
+            Self::DeviceLocal => "device-local",
+            Self::Upload => "upload",
+            Self::Readback => "readback",
+        }
+    }
+}
+
+#[cfg(not(target_arch = "wasm32"))]
+enum Event {
+    Create {
+        resource: Resource,
+        label: String,
+        heap: HeapKind,
+        size: u64,
+        alignment: u64,
+    },
+    Free(Resource),
+    Submit(wgc::SubmissionIndex),
+}
+
+/// A heap growing as needed, with its allocations placed first-fit.
+#[cfg(not(target_arch = "wasm32"))]
+#[derive(Default)]
+struct Heap {
+    /// Bytes allocated to live resources.
+    live: u64,
+    /// End of the last allocation, the size the heap has to have.
+    extent: u64,
+    /// Sorted ranges below `extent` that aren't allocated.
+    free_ranges: Vec<std::ops::Range<u64>>,
+}
+
+#[cfg(not(target_arch = "wasm32"))]
+impl Heap {
+    fn allocate(&mut self, size: u64, alignment: u64) -> u64 {
+        self.live += size;
+        let fit = self.free_ranges.iter().enumerate().find_map(|(i, range)| {
+            let offset = wgt::math::align_to(range.start, alignment);
+            (offset + size <= range.end).then_some((i, offset))
+        });
+        if let Some((i, offset)) = fit {
+            let range = self.free_ranges[i].clone();
+            let (before, after) = (range.start..offset, offset + size..range.end);
+            self.free_ranges.splice(
+                i..i + 1,
+                [before, after]
+                    .into_iter()
+                    .filter(|range| !range.is_empty()),
+            );
+            return offset;
+        }
+
+        let offset = wgt::math::align_to(self.extent, alignment);
+        let padding = self.extent..offset;
+        self.extent = offset + size;
+        self.insert_free_range(padding);
+        offset
+    }
+
+    fn free(&mut self, offset: u64, size: u64) {
+        self.live -= size;
+        self.insert_free_range(offset..offset + size);
+        if let Some(last) = self.free_ranges.last() {
+            if last.end == self.extent {
+                self.extent = last.start;
+                self.free_ranges.pop();
+            }
+        }
+    }
+
+    fn insert_free_range(&mut self, range: std::ops::Range<u64>) {
+        if range.is_empty() {
+            return;
+        }
+        let i = self
+            .free_ranges
+            .partition_point(|other| other.start < range.start);
+        self.free_ranges.insert(i, range);
+        if i + 1 < self.free_ranges.len()
+            && self.free_ranges[i].end == self.free_ranges[i + 1].start
+        {
+            self.free_ranges[i].end = self.free_ranges.remove(i + 1).end;
+        }
+        if i > 0 && self.free_ranges[i - 1].end == self.free_ranges[i].start {
+            self.free_ranges[i - 1].end = self.free_ranges.remove(i).end;
+        }
+    }
+
+    fn largest_free_range(&self) -> u64 {
+        self.free_ranges
+            .iter()
+            .map(|range| range.end - range.start)
+            .max()
+            .unwrap_or(0)
+    }
+
+    /// Fraction of the heap that isn't allocated to live resources.
+    fn fragmentation(&self) -> f64 {
+        if self.extent == 0 {
+            0.0
+        } else {
+            (self.extent - self.live) as f64 / self.extent as f64
+        }
+    }
+}
+
+/// Estimates the memory a texture occupies, from the size of its texel blocks.
+#[cfg(not(target_arch = "wasm32"))]
+fn texture_size(desc: &wgc::resource::TextureDescriptor) -> u64 {
+    let (block_width, block_height) = desc.format.block_dimensions();
+    let block_size = desc
+        .format
+        .block_copy_size(None)
+        .unwrap_or(match desc.format {
+            wgt::TextureFormat::Depth32FloatStencil8 => 8,
+            wgt::TextureFormat::P010 => 3,
+            wgt::TextureFormat::NV12 => 2,
+            // `Depth24Plus` and `Depth24PlusStencil8` are usually packed in 4 bytes.
+            _ => 4,
+        });
+    let level_sizes = (0..desc.mip_level_count).map(|level| {
+        let size = desc
+            .size
+            .mip_level_size(level, desc.dimension)
+            .physical_size(desc.format);
+        (size.width / block_width) as u64
+            * (size.height / block_height) as u64
+            * size.depth_or_array_layers as u64
+            * block_size as u64
+    });
+    level_sizes.sum::<u64>() * desc.sample_count as u64
+}
+
+#[cfg(not(target_arch = "wasm32"))]
+fn format_bytes(bytes: u64) -> String {
+    const UNITS: [&str; 4] = ["KiB", "MiB", "GiB", "TiB"];
+
+    if bytes < 1 << 10 {
+        return format!("{bytes} B");
+    }
+    let mut value = bytes as f64;
+    let mut unit = "B";
+    for next_unit in UNITS {
+        if value < 1024.0 {
+            break;
+        }
+        value /= 1024.0;
+        unit = next_unit;
+    }
+    format!("{value:.2} {unit}")
+}
+
+#[cfg(target_arch = "wasm32")]
+fn main() {}
@@ -0,0 +1,103 @@
+/*! Replays the creation of the shader modules and pipelines of a trace.
+
+This is meant for traces recorded with `DeviceFlags::TRACE_SHADERS_ONLY`,
+which reproduce the compilation of every pipeline of an application, without
+the application. Actions using the pipelines are skipped.
+
+Usage: `replay-shaders <trace dir> [<output dir>]`
+
+If an output dir is given, the replay is traced into it, capturing the code
+the backend compiles every pipeline stage to on this machine.
+!*/
+
+#[cfg(not(target_arch = "wasm32"))]
+fn main() {
+    use player::GlobalPlay as _;
+    use wgc::{device::trace, gfx_select};
+
+    use std::{
+        fs,
+        path::{Path, PathBuf},
+    };
+
+    env_logger::init();
+
+    let mut args = std::env::args().skip(1);
+    let dir = match args.next() {
+        Some(arg) if Path::new(&arg).is_dir() => PathBuf::from(arg),
+        _ => panic!("Provide the dir path as the parameter"),
+    };
+    let output_dir = args.next().map(PathBuf::from);
+    if let Some(ref output_dir) = output_dir {
+        fs::create_dir_all(output_dir).unwrap();
+    }
+
+    log::info!("Loading trace '{:?}'", dir);
+    let file = fs::File::open(dir.join(trace::FILE_NAME)).unwrap();
+    let mut actions: Vec<trace::Action> = ron::de::from_reader(file).unwrap();
+    actions.reverse(); // allows us to pop from the top
+    log::info!("Found {} actions", actions.len());
+
+    let global = wgc::global::Global::new("replay-shaders", wgt::InstanceDescriptor::default());
+    let mut command_buffer_id_manager = wgc::identity::IdentityManager::new();
+
+    let device = match actions.pop() {
+        Some(trace::Action::Init { mut desc, backend }) => {
+            log::info!("Initializing the device for backend: {:?}", backend);
+            let adapter = global
+                .request_adapter(
+                    &wgc::instance::RequestAdapterOptions {
+                        power_preference: wgt::PowerPreference::None,
+                        force_fallback_adapter: false,
+                        compatible_surface: None,
+                    },
+                    wgc::instance::AdapterInputs::IdSet(&[wgc::id::AdapterId::zip(0, 0, backend)]),
+                )
+                .expect("Unable to find an adapter for selected backend");
+
+            let info = gfx_select!(adapter => global.adapter_get_info(adapter)).unwrap();
+            log::info!("Picked '{}'", info.name);
+            desc.flags |= wgt::DeviceFlags::TRACE_SHADERS_ONLY;
+            let id = wgc::id::Id::zip(1, 0, backend);
+            let (_, _, error) = gfx_select!(adapter => global.adapter_request_device(
+                adapter,
+                &desc,
+                output_dir.as_deref(),
+                Some(id),
+                Some(id.into_queue_id())
+            ));
+            if let Some(e) = error {
+                panic!("{:?}", e);
+            }
+            id
+        }
+        _ => panic!("Expected Action::Init"),
+    };
+
+    log::info!("Compiling pipelines");
+    let mut pipeline_count = 0;
+    while let Some(action) = actions.pop() {
+        match action {
+            trace::Action::CreateBindGroupLayout(..)
+            | trace::Action::CreateInlineBindGroupLayout(..)
+            | trace::Action::DestroyBindGroupLayout(_)
+            | trace::Action::CreatePipelineLayout(..)
+            | trace::Action::DestroyPipelineLayout(_)
+            | trace::Action::CreateShaderModule { .. }
+            | trace::Action::DestroyShaderModule(_)
+            | trace::Action::DestroyComputePipeline(_)
+            | trace::Action::DestroyRenderPipeline(_)
+            | trace::Action::CompiledShaderCode { .. } => {}
+            trace::Action::CreateComputePipeline { .. }
+            | trace::Action::CreateRenderPipeline { .. } => pipeline_count += 1,
+            _ => continue,
+        }
+        gfx_select!(device => global.process(device, action, &dir, &mut command_buffer_id_manager));
+    }
+
+    gfx_select!(device => global.device_poll(device, wgt::Maintain::wait())).unwrap();
+    println!("Compiled {pipeline_count} pipelines");
+}
+
+#[cfg(target_arch = "wasm32")]
+fn main() {}
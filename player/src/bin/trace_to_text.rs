@@ -0,0 +1,131 @@
+/*! Converts a trace into a normalized text form, meant for diffing.
+
+Every action is written on its own line, followed by one indented line per
+command for submissions. Ids are renumbered in the order they first appear,
+without their epoch and backend, and the data files are replaced by the size
+and hash of their contents. This way, the traces of two builds of an
+application only differ where the behavior of the builds does.
+
+Usage: `trace-to-text <trace dir> [<output file>]`
+
+The text goes to the standard output if no output file is given.
+!*/
+
+#[cfg(not(target_arch = "wasm32"))]
+fn main() {
+    use wgc::device::trace;
+
+    use std::{
+        collections::HashMap,
+        fs,
+        io::{self, Write as _},
+        path::{Path, PathBuf},
+    };
+
+    env_logger::init();
+
+    let mut args = std::env::args().skip(1);
+    let dir = match args.next() {
+        Some(arg) if Path::new(&arg).is_dir() => PathBuf::from(arg),
+        _ => panic!("Provide the dir path as the parameter"),
+    };
+    let mut output: Box<dyn io::Write> = match args.next() {
+        Some(path) => Box::new(io::BufWriter::new(fs::File::create(path).unwrap())),
+        None => Box::new(io::BufWriter::new(io::stdout().lock())),
+    };
+
+    log::info!("Loading trace '{:?}'", dir);
+    let file = fs::File::open(dir.join(trace::FILE_NAME)).unwrap();
+    let actions: Vec<trace::Action> = ron::de::from_reader(file).unwrap();
+    log::info!("Found {} actions", actions.len());
+
+    let mut ids = HashMap::new();
+    for mut action in actions {
+        match action {
+            trace::Action::CreateShaderModule { ref mut data, .. }
+            | trace::Action::CompiledShaderCode { ref mut data, .. }
+            | trace::Action::WriteBuffer { ref mut data, .. }
+            | trace::Action::WriteTexture { ref mut data, .. } => {
+                *data = describe_data(&dir.join(&*data));
+            }
+            _ => {}
+        }
+
+        match action {
+            trace::Action::Submit(index, commands) => {
+                writeln!(output, "Submit({index})").unwrap();
+                for command in commands {
+                    let text = ron::ser::to_string(&command).unwrap();
+                    writeln!(output, "    {}", renumber_ids(&text, &mut ids)).unwrap();
+                }
+            }
+            action => {
+                let text = ron::ser::to_string(&action).unwrap();
+                writeln!(output, "{}", renumber_ids(&text, &mut ids)).unwrap();
+            }
+        }
+    }
+    output.flush().unwrap();
+}
+
+/// Describes the contents of a data file of the trace by their size and
+/// 64-bit FNV-1a hash, which unlike the file name is stable across traces.
+#[cfg(not(target_arch = "wasm32"))]
+fn describe_data(path: &std::path::Path) -> String {
+    const FNV_OFFSET_BASIS: u64 = 0xcbf2_9ce4_8422_2325;
+    const FNV_PRIME: u64 = 0x0100_0000_01b3;
+
+    match std::fs::read(path) {
+        Ok(bytes) => {
+            let hash = bytes.iter().fold(FNV_OFFSET_BASIS, |hash, &byte| {
+                (hash ^ byte as u64).wrapping_mul(FNV_PRIME)
+            });
+            format!("{} bytes, fnv1a {:016x}", bytes.len(), hash)
+        }
+        Err(e) => {
+            log::warn!("Unable to read {:?}: {}", path, e);
+            "missing".to_string()
+        }
+    }
+}
+
+/// Replaces every `Id(index,epoch,backend)` of the serialized `text` outside
+/// of string literals by `Id(#n)`, where `n` is the order in which the id
+/// first appeared in the trace.
+#[cfg(not(target_arch = "wasm32"))]
+fn renumber_ids(text: &str, ids: &mut std::collections::HashMap<String, usize>) -> String {
+    const PREFIX: &str = "Id(";
+
+    let mut result = String::with_capacity(text.len());
+    let mut rest = text;
+    let mut in_string = false;
+    while let Some(c) = rest.chars().next() {
+        if in_string {
+            if c == '\\' {
+                let escaped = rest[1..].chars().next().map_or(0, char::len_utf8);
+                result.push_str(&rest[..1 + escaped]);
+                rest = &rest[1 + escaped..];
+                continue;
+            }
+            in_string = c != '"';
+        } else if c == '"' {
+            in_string = true;
+        } else if rest.starts_with(PREFIX)
+            && !result.ends_with(|c: char| c.is_alphanumeric() || c == '_')
+        {
+            if let Some(end) = rest.find(')') {
+                let next = ids.len();
+                let n = *ids.entry(rest[..end].to_string()).or_insert(next);
+                result.push_str(&format!("{PREFIX}#{n})"));
+                rest = &rest[end + 1..];
+                continue;
+            }
+        }
+        result.push(c);
+        rest = &rest[c.len_utf8()..];
+    }
+    result
+}
+
+#[cfg(target_arch = "wasm32")]
+fn main() {}
@@ -55,9 +55,28 @@ impl GlobalPlay for wgc::global::Global {
                 trace::Command::CopyTextureToTexture { src, dst, size } => self
                     .command_encoder_copy_texture_to_texture::<A>(encoder, &src, &dst, &size)
                     .unwrap(),
+                trace::Command::BlitTexture {
+                    src,
+                    src_size,
+                    dst,
+                    dst_size,
+                    filter,
+                } => self
+                    .command_encoder_blit_texture::<A>(
+                        encoder, &src, &src_size, &dst, &dst_size, filter,
+                    )
+                    .unwrap(),
                 trace::Command::ClearBuffer { dst, offset, size } => self
                     .command_encoder_clear_buffer::<A>(encoder, dst, offset, size)
                     .unwrap(),
+                trace::Command::FillBuffer {
+                    dst,
+                    offset,
+                    size,
+                    value,
+                } => self
+                    .command_encoder_fill_buffer::<A>(encoder, dst, offset, size, value)
+                    .unwrap(),
                 trace::Command::ClearTexture {
                     dst,
                     subresource_range,
@@ -215,6 +234,13 @@ impl GlobalPlay for wgc::global::Global {
                     panic!("{e}");
                 }
             }
+            Action::CreateInlineBindGroupLayout(id, desc) => {
+                let (_, error) =
+                    self.device_create_inline_bind_group_layout::<A>(device, &desc, Some(id));
+                if let Some(e) = error {
+                    panic!("{e}");
+                }
+            }
             Action::DestroyBindGroupLayout(id) => {
                 self.bind_group_layout_drop::<A>(id);
             }
@@ -302,6 +328,9 @@ impl GlobalPlay for wgc::global::Global {
             Action::DestroyRenderPipeline(id) => {
                 self.render_pipeline_drop::<A>(id);
             }
+            Action::CompiledShaderCode { .. } => {
+                // Only recorded for reference, the pipeline was compiled again above.
+            }
             Action::CreateRenderBundle { id, desc, base } => {
                 let bundle =
                     wgc::command::RenderBundleEncoder::new(&desc, device, Some(base)).unwrap();
@@ -112,6 +112,9 @@ impl Test<'_> {
                 label: None,
                 required_features: self.features,
                 required_limits: wgt::Limits::default(),
+                flags: wgt::DeviceFlags::empty(),
+                queue_priority: wgt::QueuePriority::Normal,
+                validation: wgt::DeviceValidation::new(),
             },
             None,
             Some(device_id),
@@ -1,5 +1,5 @@
 use wgpu::{Adapter, Device, Instance, Queue};
-use wgt::{Backends, Features, Limits};
+use wgt::{Backends, DeviceFlags, DeviceValidation, Features, Limits};
 
 /// Initialize the logger for the test runner.
 pub fn init_logger() {
@@ -90,6 +90,8 @@ pub async fn initialize_device(
     adapter: &Adapter,
     features: Features,
     limits: Limits,
+    flags: DeviceFlags,
+    validation: DeviceValidation,
 ) -> (Device, Queue) {
     let bundle = adapter
         .request_device(
@@ -97,6 +99,9 @@ pub async fn initialize_device(
                 label: None,
                 required_features: features,
                 required_limits: limits,
+                flags,
+                queue_priority: wgpu::QueuePriority::Normal,
+                validation,
             },
             None,
         )
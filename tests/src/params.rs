@@ -1,5 +1,5 @@
 use arrayvec::ArrayVec;
-use wgt::{DownlevelCapabilities, DownlevelFlags, Features, Limits};
+use wgt::{DeviceFlags, DeviceValidation, DownlevelCapabilities, DownlevelFlags, Features, Limits};
 
 use crate::{
     report::AdapterReport, FailureApplicationReasons, FailureBehavior, FailureCase,
@@ -19,6 +19,12 @@ pub struct TestParameters {
     pub required_downlevel_caps: DownlevelCapabilities,
     pub required_limits: Limits,
 
+    /// Flags the device is created with.
+    pub device_flags: DeviceFlags,
+
+    /// Validation level the device is created with.
+    pub device_validation: DeviceValidation,
+
     /// Conditions under which this test should be skipped.
     pub skips: Vec<FailureCase>,
 
@@ -32,6 +38,8 @@ impl Default for TestParameters {
             required_features: Features::empty(),
             required_downlevel_caps: LOWEST_DOWNLEVEL_PROPERTIES,
             required_limits: Limits::downlevel_webgl2_defaults(),
+            device_flags: DeviceFlags::empty(),
+            device_validation: DeviceValidation::new(),
             skips: Vec::new(),
             failures: Vec::new(),
         }
@@ -63,6 +71,18 @@ impl TestParameters {
         self
     }
 
+    /// Set the flags the device is created with.
+    pub fn device_flags(mut self, device_flags: DeviceFlags) -> Self {
+        self.device_flags |= device_flags;
+        self
+    }
+
+    /// Set the validation level the device is created with.
+    pub fn device_validation(mut self, device_validation: DeviceValidation) -> Self {
+        self.device_validation = device_validation;
+        self
+    }
+
     /// Mark the test as always failing, but not to be skipped.
     pub fn expect_fail(mut self, when: FailureCase) -> Self {
         self.failures.push(when);
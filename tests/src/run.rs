@@ -65,6 +65,8 @@ pub async fn execute_test(
         &adapter,
         config.params.required_features,
         config.params.required_limits.clone(),
+        config.params.device_flags,
+        config.params.device_validation,
     ));
 
     let context = TestingContext {
@@ -83,6 +83,7 @@ async fn bgl_dedupe(ctx: TestingContext) {
             .create_shader_module(wgpu::ShaderModuleDescriptor {
                 label: None,
                 source: wgpu::ShaderSource::Wgsl(SHADER_SRC.into()),
+                diagnostic_filters: &[],
             });
 
         let desc = wgpu::ComputePipelineDescriptor {
@@ -210,6 +211,7 @@ fn bgl_dedupe_with_dropped_user_handle(ctx: TestingContext) {
         .create_shader_module(wgpu::ShaderModuleDescriptor {
             label: None,
             source: wgpu::ShaderSource::Wgsl(SHADER_SRC.into()),
+            diagnostic_filters: &[],
         });
 
     let pipeline = ctx
@@ -256,6 +258,7 @@ fn bgl_dedupe_derived(ctx: TestingContext) {
         .create_shader_module(wgpu::ShaderModuleDescriptor {
             label: None,
             source: wgpu::ShaderSource::Wgsl(SHADER_SRC.into()),
+            diagnostic_filters: &[],
         });
 
     let pipeline = ctx
@@ -329,6 +332,7 @@ fn separate_programs_have_incompatible_derived_bgls(ctx: TestingContext) {
         .create_shader_module(wgpu::ShaderModuleDescriptor {
             label: None,
             source: wgpu::ShaderSource::Wgsl(SHADER_SRC.into()),
+            diagnostic_filters: &[],
         });
 
     let desc = wgpu::ComputePipelineDescriptor {
@@ -388,6 +392,7 @@ fn derived_bgls_incompatible_with_regular_bgls(ctx: TestingContext) {
         .create_shader_module(wgpu::ShaderModuleDescriptor {
             label: None,
             source: wgpu::ShaderSource::Wgsl(SHADER_SRC.into()),
+            diagnostic_filters: &[],
         });
 
     // Create a pipeline.
@@ -0,0 +1,217 @@
+//! Tests for `CommandEncoder::blit_texture`.
+
+use wgpu::util::{read_buffer, DeviceExt};
+use wgpu_test::{
+    fail, gpu_test, FailureCase, GpuTestConfiguration, TestParameters, TestingContext,
+};
+
+fn create_texture(
+    ctx: &TestingContext,
+    format: wgpu::TextureFormat,
+    width: u32,
+    height: u32,
+    usage: wgpu::TextureUsages,
+    data: &[u8],
+) -> wgpu::Texture {
+    ctx.device.create_texture_with_data(
+        &ctx.queue,
+        &wgpu::TextureDescriptor {
+            label: None,
+            size: wgpu::Extent3d {
+                width,
+                height,
+                depth_or_array_layers: 1,
+            },
+            mip_level_count: 1,
+            sample_count: 1,
+            dimension: wgpu::TextureDimension::D2,
+            format,
+            usage: usage | wgpu::TextureUsages::COPY_DST,
+            view_formats: &[],
+        },
+        wgpu::util::TextureDataOrder::LayerMajor,
+        data,
+    )
+}
+
+/// Blits the whole of `src` into the whole of a `width` by 1 texture and reads it back.
+async fn blit_row(
+    ctx: &TestingContext,
+    src: &wgpu::Texture,
+    width: u32,
+    filter: wgpu::FilterMode,
+) -> Vec<u8> {
+    let dst = create_texture(
+        ctx,
+        wgpu::TextureFormat::Rgba8Unorm,
+        width,
+        1,
+        wgpu::TextureUsages::COPY_SRC,
+        &vec![0; width as usize * 4],
+    );
+    let buffer = ctx.device.create_buffer(&wgpu::BufferDescriptor {
+        label: None,
+        size: width as u64 * 4,
+        usage: wgpu::BufferUsages::MAP_READ | wgpu::BufferUsages::COPY_DST,
+        mapped_at_creation: false,
+    });
+
+    let mut encoder = ctx
+        .device
+        .create_command_encoder(&wgpu::CommandEncoderDescriptor::default());
+    encoder.blit_texture(
+        src.as_image_copy(),
+        src.size(),
+        dst.as_image_copy(),
+        dst.size(),
+        filter,
+    );
+    encoder.copy_texture_to_buffer(
+        dst.as_image_copy(),
+        wgpu::ImageCopyBuffer {
+            buffer: &buffer,
+            layout: wgpu::ImageDataLayout::default(),
+        },
+        dst.size(),
+    );
+    ctx.queue.submit(Some(encoder.finish()));
+
+    read_buffer(&ctx.device, &ctx.queue, &buffer, ..)
+        .await
+        .unwrap()
+}
+
+#[gpu_test]
+static BLIT_TEXTURE_LINEAR_DOWNSAMPLE: GpuTestConfiguration = GpuTestConfiguration::new()
+    .parameters(TestParameters::default().features(wgpu::Features::TEXTURE_BLIT))
+    .run_async(|ctx| async move {
+        #[rustfmt::skip]
+        let src = create_texture(
+            &ctx,
+            wgpu::TextureFormat::Rgba8Unorm,
+            2,
+            2,
+            wgpu::TextureUsages::COPY_SRC,
+            &[
+                0, 40, 200, 255,   200, 40, 0, 255,
+                0, 40, 200, 255,   200, 40, 0, 255,
+            ],
+        );
+
+        let data = blit_row(&ctx, &src, 1, wgpu::FilterMode::Linear).await;
+        // The texel is the average of the 4 source texels.
+        for (value, expected) in data.into_iter().zip([100, 40, 100, 255]) {
+            assert!(value.abs_diff(expected) <= 1, "{value} != {expected}");
+        }
+    });
+
+#[gpu_test]
+static BLIT_TEXTURE_NEAREST_UPSCALE: GpuTestConfiguration = GpuTestConfiguration::new()
+    .parameters(TestParameters::default().features(wgpu::Features::TEXTURE_BLIT))
+    .run_async(|ctx| async move {
+        let src = create_texture(
+            &ctx,
+            wgpu::TextureFormat::Rgba8Unorm,
+            2,
+            1,
+            wgpu::TextureUsages::COPY_SRC,
+            &[10, 20, 30, 40, 50, 60, 70, 80],
+        );
+
+        let data = blit_row(&ctx, &src, 4, wgpu::FilterMode::Nearest).await;
+        assert_eq!(
+            data,
+            [10, 20, 30, 40, 10, 20, 30, 40, 50, 60, 70, 80, 50, 60, 70, 80]
+        );
+    });
+
+#[gpu_test]
+static BLIT_TEXTURE_INVALID: GpuTestConfiguration = GpuTestConfiguration::new()
+    .parameters(TestParameters::default().features(wgpu::Features::TEXTURE_BLIT))
+    .run_sync(|ctx| {
+        let blit = |src: &wgpu::Texture, dst: &wgpu::Texture, filter| {
+            let mut encoder = ctx
+                .device
+                .create_command_encoder(&wgpu::CommandEncoderDescriptor::default());
+            encoder.blit_texture(
+                src.as_image_copy(),
+                src.size(),
+                dst.as_image_copy(),
+                dst.size(),
+                filter,
+            );
+            encoder.finish()
+        };
+        let color =
+            |usage| create_texture(&ctx, wgpu::TextureFormat::Rgba8Unorm, 1, 1, usage, &[0; 4]);
+        let src = color(wgpu::TextureUsages::COPY_SRC);
+        let dst = color(wgpu::TextureUsages::empty());
+
+        // Integer formats can't be blitted.
+        let integer = create_texture(
+            &ctx,
+            wgpu::TextureFormat::Rgba8Uint,
+            1,
+            1,
+            wgpu::TextureUsages::COPY_SRC,
+            &[0; 4],
+        );
+        fail(&ctx.device, || {
+            blit(&integer, &dst, wgpu::FilterMode::Nearest)
+        });
+
+        // Nor can unfilterable formats be linearly filtered.
+        let unfilterable = create_texture(
+            &ctx,
+            wgpu::TextureFormat::R32Float,
+            1,
+            1,
+            wgpu::TextureUsages::COPY_SRC,
+            &[0; 4],
+        );
+        if !ctx
+            .device
+            .features()
+            .contains(wgpu::Features::FLOAT32_FILTERABLE)
+        {
+            fail(&ctx.device, || {
+                blit(&unfilterable, &dst, wgpu::FilterMode::Linear)
+            });
+        }
+        let _ = blit(&unfilterable, &dst, wgpu::FilterMode::Nearest);
+
+        // The source needs `COPY_SRC`.
+        fail(&ctx.device, || blit(&dst, &src, wgpu::FilterMode::Linear));
+
+        let _ = blit(&src, &dst, wgpu::FilterMode::Linear);
+    });
+
+#[gpu_test]
+static BLIT_TEXTURE_WITHOUT_FEATURE: GpuTestConfiguration = GpuTestConfiguration::new()
+    .parameters(
+        // Blits aren't implemented on WebGPU at all.
+        TestParameters::default().skip(FailureCase::backend(wgpu::Backends::BROWSER_WEBGPU)),
+    )
+    .run_sync(|ctx| {
+        let texture = create_texture(
+            &ctx,
+            wgpu::TextureFormat::Rgba8Unorm,
+            1,
+            1,
+            wgpu::TextureUsages::COPY_SRC,
+            &[0; 4],
+        );
+        fail(&ctx.device, || {
+            let mut encoder = ctx
+                .device
+                .create_command_encoder(&wgpu::CommandEncoderDescriptor::default());
+            encoder.blit_texture(
+                texture.as_image_copy(),
+                texture.size(),
+                texture.as_image_copy(),
+                texture.size(),
+                wgpu::FilterMode::Nearest,
+            );
+            encoder.finish()
+        });
+    });
@@ -191,6 +191,7 @@ static MINIMUM_BUFFER_BINDING_SIZE_LAYOUT: GpuTestConfiguration = GpuTestConfigu
                         }
             "#,
                 )),
+                diagnostic_filters: &[],
             });
 
         let bind_group_layout =
@@ -260,6 +261,7 @@ static MINIMUM_BUFFER_BINDING_SIZE_DISPATCH: GpuTestConfiguration = GpuTestConfi
                         }
             "#,
                 )),
+                diagnostic_filters: &[],
             });
 
         let bind_group_layout =
@@ -387,3 +389,124 @@ static CLEAR_OFFSET_PLUS_SIZE_OUTSIDE_U64_BOUNDS: GpuTestConfiguration =
                 "but these added together exceed `u64::MAX`"
             )));
         });
+
+#[gpu_test]
+static PERSISTENT_MAPPING: GpuTestConfiguration = GpuTestConfiguration::new()
+    .parameters(TestParameters::default().features(wgpu::Features::PERSISTENT_MAPPING))
+    .run_async(|ctx| async move {
+        // The write buffer stays mapped while it is copied from twice.
+        let write_buf = ctx.device.create_buffer(&wgpu::BufferDescriptor {
+            label: None,
+            size: 16,
+            usage: wgpu::BufferUsages::MAP_WRITE | wgpu::BufferUsages::COPY_SRC,
+            mapped_at_creation: true,
+        });
+        let read_buf = ctx.device.create_buffer(&wgpu::BufferDescriptor {
+            label: None,
+            size: 32,
+            usage: wgpu::BufferUsages::MAP_READ | wgpu::BufferUsages::COPY_DST,
+            mapped_at_creation: false,
+        });
+
+        for (value, offset) in [(1, 0), (2, 16)] {
+            // SAFETY: The previous copy from the buffer has completed.
+            unsafe { write_buf.slice(..).get_mapped_range_mut_unchecked() }.fill(value);
+            let mut encoder = ctx
+                .device
+                .create_command_encoder(&wgpu::CommandEncoderDescriptor::default());
+            encoder.copy_buffer_to_buffer(&write_buf, 0, &read_buf, offset, 16);
+            ctx.queue.submit(Some(encoder.finish()));
+            // Wait for the copy before writing again.
+            ctx.async_poll(wgpu::Maintain::wait())
+                .await
+                .panic_on_timeout();
+        }
+        write_buf.unmap();
+
+        read_buf
+            .slice(..)
+            .map_async(wgpu::MapMode::Read, Result::unwrap);
+        ctx.async_poll(wgpu::Maintain::wait())
+            .await
+            .panic_on_timeout();
+        let view = read_buf.slice(..).get_mapped_range();
+        assert_eq!(&view[..16], &[1; 16]);
+        assert_eq!(&view[16..], &[2; 16]);
+    });
+
+#[gpu_test]
+static RESIZE_KEEPS_CONTENTS: GpuTestConfiguration =
+    GpuTestConfiguration::new().run_async(|ctx| async move {
+        let mut buffer = ctx.device.create_buffer(&wgpu::BufferDescriptor {
+            label: None,
+            size: 8,
+            usage: wgpu::BufferUsages::COPY_SRC | wgpu::BufferUsages::COPY_DST,
+            mapped_at_creation: false,
+        });
+        ctx.queue
+            .write_buffer(&buffer, 0, &[1, 2, 3, 4, 5, 6, 7, 8]);
+
+        let mut encoder = ctx
+            .device
+            .create_command_encoder(&wgpu::CommandEncoderDescriptor::default());
+        buffer.resize(&ctx.device, 16, Some(&mut encoder));
+        ctx.queue.submit(Some(encoder.finish()));
+        assert_eq!(buffer.size(), 16);
+
+        let contents = wgpu::util::read_buffer(&ctx.device, &ctx.queue, &buffer, ..).await;
+        assert_eq!(
+            contents.unwrap(),
+            [1, 2, 3, 4, 5, 6, 7, 8, 0, 0, 0, 0, 0, 0, 0, 0]
+        );
+
+        // Without an encoder, the new allocation starts out zeroed.
+        buffer.resize(&ctx.device, 4, None);
+        let contents = wgpu::util::read_buffer(&ctx.device, &ctx.queue, &buffer, ..).await;
+        assert_eq!(contents.unwrap(), [0; 4]);
+    });
+
+#[gpu_test]
+static UNMAP_BEFORE_MAPPING_ABORTS: GpuTestConfiguration = GpuTestConfiguration::new()
+    .parameters(
+        TestParameters::default()
+            // The browser doesn't tell why a mapping failed.
+            .skip(FailureCase::backend(wgpu::Backends::BROWSER_WEBGPU)),
+    )
+    .run_async(|ctx| async move {
+        let buffer = ctx.device.create_buffer(&wgpu::BufferDescriptor {
+            label: None,
+            size: 16,
+            usage: wgpu::BufferUsages::MAP_READ,
+            mapped_at_creation: false,
+        });
+
+        let result = std::sync::Arc::new(std::sync::Mutex::new(None));
+        let callback_result = result.clone();
+        buffer.slice(..).map_async(wgpu::MapMode::Read, move |r| {
+            *callback_result.lock().unwrap() = Some(r);
+        });
+        buffer.unmap();
+        ctx.async_poll(wgpu::Maintain::wait())
+            .await
+            .panic_on_timeout();
+
+        let error = result.lock().unwrap().take().unwrap().unwrap_err();
+        assert_eq!(error.kind(), wgpu::BufferAsyncErrorKind::Aborted);
+        assert!(!error.is_retryable());
+    });
+
+#[gpu_test]
+static MAP_WITH_RETRY_FAILED_MAPPINGS: GpuTestConfiguration = GpuTestConfiguration::new()
+    .parameters(TestParameters::default().device_flags(wgpu::DeviceFlags::RETRY_FAILED_MAPPINGS))
+    .run_async(|ctx| async move {
+        let buffer = ctx.device.create_buffer(&wgpu::BufferDescriptor {
+            label: None,
+            size: 16,
+            usage: wgpu::BufferUsages::COPY_DST | wgpu::BufferUsages::MAP_READ,
+            mapped_at_creation: false,
+        });
+        ctx.queue.write_buffer(&buffer, 0, &[7; 16]);
+
+        let contents = wgpu::util::read_buffer(&ctx.device, &ctx.queue, &buffer, ..).await;
+        assert_eq!(contents.unwrap(), [7; 16]);
+    });
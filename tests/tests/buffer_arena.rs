@@ -0,0 +1,35 @@
+use std::num::NonZeroU64;
+
+use wgpu::util::BufferArena;
+use wgpu_test::{gpu_test, GpuTestConfiguration};
+
+#[gpu_test]
+static REUSE_RETIRED_ALLOCATIONS: GpuTestConfiguration =
+    GpuTestConfiguration::new().run_async(|ctx| async move {
+        let mut arena = BufferArena::new(&ctx.device, None, wgpu::BufferUsages::UNIFORM, 4096);
+        let size = NonZeroU64::new(16).unwrap();
+
+        let first = arena.allocate(&ctx.device, size);
+        let second = arena.allocate(&ctx.device, size);
+        assert_eq!(first.buffer().global_id(), second.buffer().global_id());
+        assert_eq!(first.offset() % arena.alignment(), 0);
+        assert_eq!(second.offset() % arena.alignment(), 0);
+        assert!(second.offset() >= first.offset() + arena.alignment());
+
+        let first_offset = first.offset();
+        arena.free(first);
+        arena.retire(ctx.queue.submit(None));
+
+        // The range of `first` isn't reused before the submission has completed.
+        let third = arena.allocate(&ctx.device, size);
+        assert_ne!(third.offset(), first_offset);
+
+        ctx.async_poll(wgpu::Maintain::wait())
+            .await
+            .panic_on_timeout();
+        arena.recall(&ctx.queue);
+
+        let fourth = arena.allocate(&ctx.device, size);
+        assert_eq!(fourth.offset(), first_offset);
+        assert_eq!(fourth.buffer().global_id(), second.buffer().global_id());
+    });
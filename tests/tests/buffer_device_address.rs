@@ -0,0 +1,38 @@
+use wgpu_test::{gpu_test, GpuTestConfiguration, TestParameters};
+
+#[gpu_test]
+static STORAGE_BUFFERS_HAVE_STABLE_ADDRESSES: GpuTestConfiguration = GpuTestConfiguration::new()
+    .parameters(TestParameters::default().features(wgpu::Features::BUFFER_DEVICE_ADDRESS))
+    .run_sync(|ctx| {
+        let create = || {
+            ctx.device.create_buffer(&wgpu::BufferDescriptor {
+                label: None,
+                size: 1024,
+                usage: wgpu::BufferUsages::STORAGE,
+                mapped_at_creation: false,
+            })
+        };
+        let a = create();
+        let b = create();
+
+        let address = a.gpu_address();
+        assert_ne!(address, 0);
+        assert_eq!(a.gpu_address(), address);
+        assert_ne!(b.gpu_address(), address);
+    });
+
+#[gpu_test]
+static ADDRESS_REQUIRES_STORAGE_USAGE: GpuTestConfiguration = GpuTestConfiguration::new()
+    .parameters(TestParameters::default().features(wgpu::Features::BUFFER_DEVICE_ADDRESS))
+    .run_sync(|ctx| {
+        let buffer = ctx.device.create_buffer(&wgpu::BufferDescriptor {
+            label: None,
+            size: 1024,
+            usage: wgpu::BufferUsages::UNIFORM,
+            mapped_at_creation: false,
+        });
+
+        wgpu_test::fail(&ctx.device, || {
+            buffer.gpu_address();
+        });
+    });
@@ -13,7 +13,8 @@ const ALWAYS_VALID: &[Bu; 4] = &[
     Bu::MAP_WRITE.union(Bu::COPY_SRC),
 ];
 // MAP_READ can only be paired with COPY_DST and MAP_WRITE can only be paired with COPY_SRC
-// (unless Features::MAPPABlE_PRIMARY_BUFFERS is enabled).
+// (unless Features::MAPPABlE_PRIMARY_BUFFERS is enabled or the adapter has
+// DownlevelFlags::UNIFIED_MEMORY_MAPPABLE_BUFFERS).
 const NEEDS_MAPPABLE_PRIMARY_BUFFERS: &[Bu; 7] = &[
     Bu::MAP_READ.union(Bu::COPY_DST.union(Bu::COPY_SRC)),
     Bu::MAP_WRITE.union(Bu::COPY_SRC.union(Bu::COPY_DST)),
@@ -44,16 +45,37 @@ fn try_create(ctx: TestingContext, usages: &[(bool, &[wgpu::BufferUsages])]) {
 
 #[gpu_test]
 static BUFFER_USAGE: GpuTestConfiguration = GpuTestConfiguration::new().run_sync(|ctx| {
+    let unified_memory = ctx
+        .adapter_downlevel_capabilities
+        .flags
+        .contains(wgpu::DownlevelFlags::UNIFIED_MEMORY_MAPPABLE_BUFFERS);
     try_create(
         ctx,
         &[
             (false, ALWAYS_VALID),
-            (true, NEEDS_MAPPABLE_PRIMARY_BUFFERS),
+            (!unified_memory, NEEDS_MAPPABLE_PRIMARY_BUFFERS),
             (true, ALWAYS_FAIL),
         ],
     );
 });
 
+#[gpu_test]
+static BUFFER_USAGE_UNIFIED_MEMORY: GpuTestConfiguration = GpuTestConfiguration::new()
+    .parameters(
+        TestParameters::default()
+            .downlevel_flags(wgpu::DownlevelFlags::UNIFIED_MEMORY_MAPPABLE_BUFFERS),
+    )
+    .run_sync(|ctx| {
+        try_create(
+            ctx,
+            &[
+                (false, ALWAYS_VALID),
+                (false, NEEDS_MAPPABLE_PRIMARY_BUFFERS),
+                (true, ALWAYS_FAIL),
+            ],
+        );
+    });
+
 #[gpu_test]
 static BUFFER_USAGE_MAPPABLE_PRIMARY_BUFFERS: GpuTestConfiguration = GpuTestConfiguration::new()
     .parameters(TestParameters::default().features(wgpu::Features::MAPPABLE_PRIMARY_BUFFERS))
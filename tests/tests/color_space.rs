@@ -0,0 +1,164 @@
+//! Tests for color space conversion, see `wgpu::util::ColorSpaceConverter`.
+
+use wgpu::{util::ColorSpaceConverter, ColorSpace};
+use wgpu_test::{gpu_test, GpuTestConfiguration};
+
+#[gpu_test]
+static CONVERT_SRGB_TO_DISPLAY_P3: GpuTestConfiguration =
+    GpuTestConfiguration::new().run_async(|ctx| async move {
+        let format = wgpu::TextureFormat::Rgba32Float;
+        let size = wgpu::Extent3d {
+            width: 1,
+            height: 1,
+            depth_or_array_layers: 1,
+        };
+        let make_texture = |usage| {
+            ctx.device.create_texture(&wgpu::TextureDescriptor {
+                label: None,
+                dimension: wgpu::TextureDimension::D2,
+                size,
+                format,
+                usage,
+                mip_level_count: 1,
+                sample_count: 1,
+                view_formats: &[],
+            })
+        };
+        let source =
+            make_texture(wgpu::TextureUsages::COPY_DST | wgpu::TextureUsages::TEXTURE_BINDING);
+        let mut target =
+            make_texture(wgpu::TextureUsages::COPY_SRC | wgpu::TextureUsages::RENDER_ATTACHMENT);
+        target.set_color_space(ColorSpace::DisplayP3);
+
+        ctx.queue.write_texture(
+            source.as_image_copy(),
+            bytemuck::cast_slice(&[1.0f32, 0.0, 0.0, 1.0]),
+            wgpu::ImageDataLayout {
+                offset: 0,
+                bytes_per_row: None,
+                rows_per_image: None,
+            },
+            size,
+        );
+
+        let read_buffer = ctx.device.create_buffer(&wgpu::BufferDescriptor {
+            label: None,
+            size: 16,
+            usage: wgpu::BufferUsages::MAP_READ | wgpu::BufferUsages::COPY_DST,
+            mapped_at_creation: false,
+        });
+
+        let converter = ColorSpaceConverter::new(&ctx.device, format);
+        let mut encoder = ctx
+            .device
+            .create_command_encoder(&wgpu::CommandEncoderDescriptor { label: None });
+        converter.convert(&ctx.device, &mut encoder, &source, &target);
+        encoder.copy_texture_to_buffer(
+            target.as_image_copy(),
+            wgpu::ImageCopyBuffer {
+                buffer: &read_buffer,
+                layout: wgpu::ImageDataLayout {
+                    offset: 0,
+                    bytes_per_row: None,
+                    rows_per_image: None,
+                },
+            },
+            size,
+        );
+        ctx.queue.submit(Some(encoder.finish()));
+
+        let slice = read_buffer.slice(..);
+        slice.map_async(wgpu::MapMode::Read, |_| ());
+        ctx.async_poll(wgpu::Maintain::wait())
+            .await
+            .panic_on_timeout();
+        let data: Vec<f32> = bytemuck::cast_slice(&slice.get_mapped_range()).to_vec();
+
+        // sRGB red is inside of the Display P3 gamut.
+        let expected = [0.8225, 0.0332, 0.0171, 1.0];
+        for (value, reference) in data.iter().zip(expected) {
+            assert!((value - reference).abs() < 1e-3, "{data:?} != {expected:?}");
+        }
+    });
+
+#[gpu_test]
+static CONVERT_SCRGB_TO_HDR10: GpuTestConfiguration =
+    GpuTestConfiguration::new().run_async(|ctx| async move {
+        let size = wgpu::Extent3d {
+            width: 1,
+            height: 1,
+            depth_or_array_layers: 1,
+        };
+        let make_texture = |format, usage| {
+            ctx.device.create_texture(&wgpu::TextureDescriptor {
+                label: None,
+                dimension: wgpu::TextureDimension::D2,
+                size,
+                format,
+                usage,
+                mip_level_count: 1,
+                sample_count: 1,
+                view_formats: &[],
+            })
+        };
+        let source = make_texture(
+            wgpu::TextureFormat::Rgba32Float,
+            wgpu::TextureUsages::COPY_DST | wgpu::TextureUsages::TEXTURE_BINDING,
+        );
+        let mut target = make_texture(
+            wgpu::TextureFormat::Rgb10a2Unorm,
+            wgpu::TextureUsages::COPY_SRC | wgpu::TextureUsages::RENDER_ATTACHMENT,
+        );
+        target.set_color_space(ColorSpace::Hdr10);
+
+        // scRGB white, 80 nits.
+        ctx.queue.write_texture(
+            source.as_image_copy(),
+            bytemuck::cast_slice(&[1.0f32, 1.0, 1.0, 1.0]),
+            wgpu::ImageDataLayout {
+                offset: 0,
+                bytes_per_row: None,
+                rows_per_image: None,
+            },
+            size,
+        );
+
+        let read_buffer = ctx.device.create_buffer(&wgpu::BufferDescriptor {
+            label: None,
+            size: 4,
+            usage: wgpu::BufferUsages::MAP_READ | wgpu::BufferUsages::COPY_DST,
+            mapped_at_creation: false,
+        });
+
+        let converter = ColorSpaceConverter::new(&ctx.device, target.format());
+        let mut encoder = ctx
+            .device
+            .create_command_encoder(&wgpu::CommandEncoderDescriptor { label: None });
+        converter.convert(&ctx.device, &mut encoder, &source, &target);
+        encoder.copy_texture_to_buffer(
+            target.as_image_copy(),
+            wgpu::ImageCopyBuffer {
+                buffer: &read_buffer,
+                layout: wgpu::ImageDataLayout {
+                    offset: 0,
+                    bytes_per_row: None,
+                    rows_per_image: None,
+                },
+            },
+            size,
+        );
+        ctx.queue.submit(Some(encoder.finish()));
+
+        let slice = read_buffer.slice(..);
+        slice.map_async(wgpu::MapMode::Read, |_| ());
+        ctx.async_poll(wgpu::Maintain::wait())
+            .await
+            .panic_on_timeout();
+        let texel = u32::from_le_bytes(slice.get_mapped_range()[..4].try_into().unwrap());
+        let channels = [texel & 0x3ff, (texel >> 10) & 0x3ff, (texel >> 20) & 0x3ff];
+
+        // 80 nits are 0.4859 in PQ.
+        for channel in channels {
+            assert!(channel.abs_diff(497) <= 2, "{channels:?}");
+        }
+    });
@@ -0,0 +1,112 @@
+//! Tests for `DeviceFlags::COMPATIBILITY_MODE`.
+
+use wgpu_test::{fail, gpu_test, valid, FailureCase, GpuTestConfiguration, TestParameters};
+
+fn parameters() -> TestParameters {
+    TestParameters::default()
+        .device_flags(wgpu::DeviceFlags::COMPATIBILITY_MODE)
+        // Device flags are ignored on WebGPU.
+        .skip(FailureCase::backend(wgpu::Backends::BROWSER_WEBGPU))
+}
+
+fn array_texture(
+    ctx: &wgpu_test::TestingContext,
+    view_formats: &[wgpu::TextureFormat],
+) -> wgpu::Texture {
+    ctx.device.create_texture(&wgpu::TextureDescriptor {
+        label: None,
+        size: wgpu::Extent3d {
+            width: 4,
+            height: 4,
+            depth_or_array_layers: 12,
+        },
+        mip_level_count: 1,
+        sample_count: 1,
+        dimension: wgpu::TextureDimension::D2,
+        format: wgpu::TextureFormat::Rgba8Unorm,
+        usage: wgpu::TextureUsages::TEXTURE_BINDING,
+        view_formats,
+    })
+}
+
+#[gpu_test]
+static COMPATIBILITY_MODE_REJECTS_REINTERPRETATION: GpuTestConfiguration =
+    GpuTestConfiguration::new()
+        .parameters(parameters())
+        .run_sync(|ctx| {
+            valid(&ctx.device, || array_texture(&ctx, &[]));
+            fail(&ctx.device, || {
+                array_texture(&ctx, &[wgpu::TextureFormat::Rgba8UnormSrgb])
+            });
+        });
+
+#[gpu_test]
+static COMPATIBILITY_MODE_REJECTS_CUBE_ARRAYS: GpuTestConfiguration = GpuTestConfiguration::new()
+    .parameters(parameters())
+    .run_sync(|ctx| {
+        let texture = array_texture(&ctx, &[]);
+        fail(&ctx.device, || {
+            texture.create_view(&wgpu::TextureViewDescriptor {
+                dimension: Some(wgpu::TextureViewDimension::CubeArray),
+                ..Default::default()
+            })
+        });
+    });
+
+#[gpu_test]
+static COMPATIBILITY_MODE_BINDS_WHOLE_TEXTURES: GpuTestConfiguration = GpuTestConfiguration::new()
+    .parameters(parameters())
+    .run_sync(|ctx| {
+        let texture = ctx.device.create_texture(&wgpu::TextureDescriptor {
+            label: None,
+            size: wgpu::Extent3d {
+                width: 4,
+                height: 4,
+                depth_or_array_layers: 2,
+            },
+            mip_level_count: 1,
+            sample_count: 1,
+            dimension: wgpu::TextureDimension::D2,
+            format: wgpu::TextureFormat::Rgba8Unorm,
+            usage: wgpu::TextureUsages::TEXTURE_BINDING,
+            view_formats: &[],
+        });
+        let bind_group = |dimension, base_array_layer| {
+            let layout = ctx
+                .device
+                .create_bind_group_layout(&wgpu::BindGroupLayoutDescriptor {
+                    label: None,
+                    entries: &[wgpu::BindGroupLayoutEntry {
+                        binding: 0,
+                        visibility: wgpu::ShaderStages::FRAGMENT,
+                        ty: wgpu::BindingType::Texture {
+                            sample_type: wgpu::TextureSampleType::Float { filterable: true },
+                            view_dimension: dimension,
+                            multisampled: false,
+                        },
+                        count: None,
+                    }],
+                });
+            let view = texture.create_view(&wgpu::TextureViewDescriptor {
+                dimension: Some(dimension),
+                base_array_layer,
+                ..Default::default()
+            });
+            ctx.device.create_bind_group(&wgpu::BindGroupDescriptor {
+                label: None,
+                layout: &layout,
+                entries: &[wgpu::BindGroupEntry {
+                    binding: 0,
+                    resource: wgpu::BindingResource::TextureView(&view),
+                }],
+            })
+        };
+
+        valid(&ctx.device, || {
+            bind_group(wgpu::TextureViewDimension::D2Array, 0)
+        });
+        // A single layer of an array texture can't be bound.
+        fail(&ctx.device, || {
+            bind_group(wgpu::TextureViewDimension::D2, 1)
+        });
+    });
@@ -0,0 +1,165 @@
+//! Tests for `wgpu::util::ConstantTables`.
+
+use wgpu::util::{read_buffer, ConstantTables, ConstantTablesBuilder};
+use wgpu_test::{gpu_test, GpuTestConfiguration, TestingContext};
+
+/// Runs the WGSL `source`, which has the `tables` prepended, and returns the
+/// `len` values it writes to its `output` array.
+async fn run_with_tables(
+    ctx: &TestingContext,
+    tables: &ConstantTables,
+    source: &str,
+    len: usize,
+) -> Vec<u32> {
+    let shader = ctx
+        .device
+        .create_shader_module(wgpu::ShaderModuleDescriptor {
+            label: None,
+            source: wgpu::ShaderSource::Wgsl(tables.wgsl(source).into()),
+            diagnostic_filters: &[],
+        });
+
+    let output = ctx.device.create_buffer(&wgpu::BufferDescriptor {
+        label: None,
+        size: len as wgpu::BufferAddress * 4,
+        usage: wgpu::BufferUsages::STORAGE | wgpu::BufferUsages::COPY_SRC,
+        mapped_at_creation: false,
+    });
+    let output_layout = ctx
+        .device
+        .create_bind_group_layout(&wgpu::BindGroupLayoutDescriptor {
+            label: None,
+            entries: &[wgpu::BindGroupLayoutEntry {
+                binding: 0,
+                visibility: wgpu::ShaderStages::COMPUTE,
+                ty: wgpu::BindingType::Buffer {
+                    ty: wgpu::BufferBindingType::Storage { read_only: false },
+                    has_dynamic_offset: false,
+                    min_binding_size: None,
+                },
+                count: None,
+            }],
+        });
+    let output_group = ctx.device.create_bind_group(&wgpu::BindGroupDescriptor {
+        label: None,
+        layout: &output_layout,
+        entries: &[wgpu::BindGroupEntry {
+            binding: 0,
+            resource: output.as_entire_binding(),
+        }],
+    });
+    let pipeline_layout = ctx
+        .device
+        .create_pipeline_layout(&wgpu::PipelineLayoutDescriptor {
+            label: None,
+            bind_group_layouts: &[&output_layout, tables.bind_group_layout()],
+            push_constant_ranges: &[],
+        });
+    let pipeline = ctx
+        .device
+        .create_compute_pipeline(&wgpu::ComputePipelineDescriptor {
+            label: None,
+            layout: Some(&pipeline_layout),
+            module: &shader,
+            entry_point: "main",
+            compilation_options: Default::default(),
+        });
+
+    let mut encoder = ctx
+        .device
+        .create_command_encoder(&wgpu::CommandEncoderDescriptor::default());
+    {
+        let mut pass = encoder.begin_compute_pass(&wgpu::ComputePassDescriptor::default());
+        pass.set_pipeline(&pipeline);
+        pass.set_bind_group(0, &output_group, &[]);
+        pass.set_bind_group(1, tables.bind_group(), &[]);
+        pass.dispatch_workgroups(1, 1, 1);
+    }
+    ctx.queue.submit(Some(encoder.finish()));
+
+    let data = read_buffer(&ctx.device, &ctx.queue, &output, ..)
+        .await
+        .unwrap();
+    data.chunks_exact(4)
+        .map(|bytes| u32::from_le_bytes(bytes.try_into().unwrap()))
+        .collect()
+}
+
+#[gpu_test]
+static CONSTANT_TABLES_ARE_READABLE: GpuTestConfiguration =
+    GpuTestConfiguration::new().run_async(|ctx| async move {
+        let squares = (0..64u32).map(|i| i * i).collect::<Vec<_>>();
+        let offsets = [[1.0f32, 2.0, 3.0, 4.0], [5.0, 6.0, 7.0, 8.0]];
+        let tables = ConstantTablesBuilder::new()
+            .table("SQUARES", &squares)
+            .table("OFFSETS", &offsets)
+            .build(&ctx.device, 1, wgpu::ShaderStages::COMPUTE);
+
+        let values = run_with_tables(
+            &ctx,
+            &tables,
+            "
+            @group(0) @binding(0) var<storage, read_write> output: array<u32, 3>;
+
+            @compute @workgroup_size(1)
+            fn main() {
+                output[0] = SQUARES[7];
+                output[1] = SQUARES[63];
+                output[2] = u32(OFFSETS[1].z);
+            }
+            ",
+            3,
+        )
+        .await;
+        assert_eq!(values, [49, 3969, 7]);
+    });
+
+#[gpu_test]
+static CONSTANT_TABLES_FROM_WGSL: GpuTestConfiguration =
+    GpuTestConfiguration::new().run_async(|ctx| async move {
+        let (builder, source) = ConstantTablesBuilder::new()
+            .tables_from_wgsl(
+                "
+                const SQUARES = array<u32, 4>(0u, 1u, 4u, 9u);
+                const OFFSETS = array<vec2<f32>, 4>(vec2(1.0), vec2(2.0, 3.0), vec2<f32>(), vec2(5.0));
+                const SMALL = array<f32, 2>(1.0, 2.0);
+
+                @group(0) @binding(0) var<storage, read_write> output: array<u32, 3>;
+
+                @compute @workgroup_size(1)
+                fn main() {
+                    output[0] = SQUARES[3];
+                    output[1] = u32(OFFSETS[1].y);
+                    output[2] = u32(SMALL[1]);
+                }
+                ",
+                4,
+            )
+            .unwrap();
+        assert!(!source.contains("SQUARES ="));
+        assert!(!source.contains("OFFSETS ="));
+        assert!(source.contains("SMALL ="));
+
+        let tables = builder.build(&ctx.device, 1, wgpu::ShaderStages::COMPUTE);
+        assert!(tables.declarations().contains("SQUARES: array<u32, 4>"));
+        assert!(tables
+            .declarations()
+            .contains("OFFSETS: array<vec2<f32>, 4>"));
+        assert!(!tables.declarations().contains("SMALL"));
+
+        let values = run_with_tables(&ctx, &tables, &source, 3).await;
+        assert_eq!(values, [9, 3, 2]);
+    });
+
+#[gpu_test]
+static CONSTANT_TABLES_OVER_LIMIT: GpuTestConfiguration =
+    GpuTestConfiguration::new().run_sync(|ctx| {
+        let max_tables = ctx.device.limits().max_storage_buffers_per_shader_stage;
+        let builder = (0..=max_tables).fold(ConstantTablesBuilder::new(), |builder, i| {
+            builder.table(&format!("TABLE_{i}"), &[i])
+        });
+        let result = std::panic::catch_unwind(std::panic::AssertUnwindSafe(|| {
+            builder.build(&ctx.device, 1, wgpu::ShaderStages::COMPUTE)
+        }));
+        assert!(result.is_err());
+    });
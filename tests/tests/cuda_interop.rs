@@ -0,0 +1,133 @@
+//! Tests for the CUDA interop helpers of `wgpu::util`.
+#![cfg(any(
+    windows,
+    all(
+        unix,
+        not(target_arch = "wasm32"),
+        not(target_os = "macos"),
+        not(target_os = "ios")
+    )
+))]
+
+use wgpu::util::{
+    cuda_channel_format, export_buffer_for_cuda, export_semaphore_for_cuda,
+    export_texture_for_cuda, CudaChannelFormatKind, CudaExternalSemaphoreHandleType,
+    CUDA_ARRAY_LAYERED, CUDA_ARRAY_SURFACE_LOAD_STORE, CUDA_EXTERNAL_MEMORY_DEDICATED,
+};
+use wgpu_test::{gpu_test, GpuTestConfiguration, TestParameters};
+
+#[cfg(unix)]
+mod platform {
+    pub const MEMORY: wgpu::ExternalMemoryHandleType = wgpu::ExternalMemoryHandleType::OpaqueFd;
+    pub const CUDA_MEMORY: wgpu::util::CudaExternalMemoryHandleType =
+        wgpu::util::CudaExternalMemoryHandleType::OpaqueFd;
+    pub const SEMAPHORE: wgpu::ExternalSemaphoreHandleType =
+        wgpu::ExternalSemaphoreHandleType::OpaqueFd;
+    pub const FEATURES: wgpu::Features =
+        wgpu::Features::EXTERNAL_MEMORY_FD.union(wgpu::Features::EXTERNAL_SEMAPHORE_FD);
+}
+#[cfg(windows)]
+mod platform {
+    pub const MEMORY: wgpu::ExternalMemoryHandleType =
+        wgpu::ExternalMemoryHandleType::D3D12Resource;
+    pub const CUDA_MEMORY: wgpu::util::CudaExternalMemoryHandleType =
+        wgpu::util::CudaExternalMemoryHandleType::D3D12Resource;
+    pub const SEMAPHORE: wgpu::ExternalSemaphoreHandleType =
+        wgpu::ExternalSemaphoreHandleType::D3D12Fence;
+    pub const FEATURES: wgpu::Features = wgpu::Features::EXTERNAL_MEMORY_WIN32;
+}
+
+#[gpu_test]
+static CUDA_BUFFER_IS_WHOLE_MEMORY: GpuTestConfiguration = GpuTestConfiguration::new()
+    .parameters(TestParameters::default().features(platform::FEATURES))
+    .run_sync(|ctx| {
+        let buffer = ctx.device.create_exportable_buffer(
+            &wgpu::BufferDescriptor {
+                label: None,
+                size: 1000,
+                usage: wgpu::BufferUsages::STORAGE,
+                mapped_at_creation: false,
+            },
+            platform::MEMORY,
+        );
+
+        let (memory, desc) = export_buffer_for_cuda(&buffer).unwrap();
+        assert_eq!(memory.handle_type, platform::CUDA_MEMORY);
+        assert_eq!(memory.flags, CUDA_EXTERNAL_MEMORY_DEDICATED);
+        assert!(memory.size >= 1000);
+        assert_eq!((desc.offset, desc.size, desc.flags), (0, 1000, 0));
+    });
+
+#[gpu_test]
+static CUDA_ARRAY_MATCHES_TEXTURE: GpuTestConfiguration = GpuTestConfiguration::new()
+    .parameters(TestParameters::default().features(platform::FEATURES))
+    .run_sync(|ctx| {
+        let texture = ctx.device.create_exportable_texture(
+            &wgpu::TextureDescriptor {
+                label: None,
+                size: wgpu::Extent3d {
+                    width: 64,
+                    height: 32,
+                    depth_or_array_layers: 3,
+                },
+                mip_level_count: 2,
+                sample_count: 1,
+                dimension: wgpu::TextureDimension::D2,
+                format: wgpu::TextureFormat::Rgba32Float,
+                usage: wgpu::TextureUsages::STORAGE_BINDING,
+                view_formats: &[],
+            },
+            platform::MEMORY,
+            &[],
+        );
+
+        let (memory, desc) = export_texture_for_cuda(&texture).unwrap();
+        assert_eq!(memory.handle_type, platform::CUDA_MEMORY);
+        assert_eq!(desc.extent, [64, 32, 3]);
+        assert_eq!(desc.num_levels, 2);
+        assert_eq!(
+            desc.flags,
+            CUDA_ARRAY_LAYERED | CUDA_ARRAY_SURFACE_LOAD_STORE
+        );
+        assert_eq!(
+            desc.format,
+            cuda_channel_format(wgpu::TextureFormat::Rgba32Float).unwrap()
+        );
+    });
+
+#[gpu_test]
+static CUDA_SEMAPHORE_IS_TIMELINE: GpuTestConfiguration = GpuTestConfiguration::new()
+    .parameters(
+        TestParameters::default().features(wgpu::Features::TIMELINE_SEMAPHORE | platform::FEATURES),
+    )
+    .run_sync(|ctx| {
+        let semaphore = ctx.device.create_exportable_timeline_semaphore(
+            &wgpu::TimelineSemaphoreDescriptor::default(),
+            platform::SEMAPHORE,
+        );
+
+        let exported = export_semaphore_for_cuda(&semaphore).unwrap();
+        let expected = match platform::SEMAPHORE {
+            wgpu::ExternalSemaphoreHandleType::OpaqueFd => {
+                CudaExternalSemaphoreHandleType::TimelineSemaphoreFd
+            }
+            wgpu::ExternalSemaphoreHandleType::D3D12Fence => {
+                CudaExternalSemaphoreHandleType::D3D12Fence
+            }
+        };
+        assert_eq!(exported.handle_type, expected);
+    });
+
+#[gpu_test]
+static CUDA_CHANNEL_FORMATS: GpuTestConfiguration = GpuTestConfiguration::new().run_sync(|_ctx| {
+    let format = cuda_channel_format(wgpu::TextureFormat::Rg16Snorm).unwrap();
+    assert_eq!((format.x, format.y, format.z, format.w), (16, 16, 0, 0));
+    assert_eq!(format.kind, CudaChannelFormatKind::Signed);
+
+    let format = cuda_channel_format(wgpu::TextureFormat::Rgba8Unorm).unwrap();
+    assert_eq!((format.x, format.y, format.z, format.w), (8, 8, 8, 8));
+    assert_eq!(format.kind, CudaChannelFormatKind::Unsigned);
+
+    assert!(cuda_channel_format(wgpu::TextureFormat::Rgba8UnormSrgb).is_none());
+    assert!(cuda_channel_format(wgpu::TextureFormat::Depth32Float).is_none());
+});
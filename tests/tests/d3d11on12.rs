@@ -0,0 +1,94 @@
+//! Tests for textures shared with D3D11 devices through D3D11On12.
+#![cfg(windows)]
+
+use wgpu_test::{gpu_test, FailureCase, GpuTestConfiguration, TestParameters};
+
+const SIZE: wgpu::Extent3d = wgpu::Extent3d {
+    width: 64,
+    height: 64,
+    depth_or_array_layers: 1,
+};
+
+#[gpu_test]
+static D3D11_ACCESS_KEEPS_TEXTURE_CONTENTS: GpuTestConfiguration = GpuTestConfiguration::new()
+    .parameters(TestParameters::default().skip(FailureCase::backend(
+        wgpu::Backends::all() - wgpu::Backends::DX12,
+    )))
+    .run_async(|ctx| async move {
+        let texture = ctx.device.create_texture(&wgpu::TextureDescriptor {
+            label: None,
+            size: SIZE,
+            mip_level_count: 1,
+            sample_count: 1,
+            dimension: wgpu::TextureDimension::D2,
+            format: wgpu::TextureFormat::Rgba8Unorm,
+            usage: wgpu::TextureUsages::RENDER_ATTACHMENT
+                | wgpu::TextureUsages::TEXTURE_BINDING
+                | wgpu::TextureUsages::COPY_SRC,
+            view_formats: &[],
+        });
+        let mut encoder = ctx
+            .device
+            .create_command_encoder(&wgpu::CommandEncoderDescriptor::default());
+        encoder.begin_render_pass(&wgpu::RenderPassDescriptor {
+            label: None,
+            color_attachments: &[Some(wgpu::RenderPassColorAttachment {
+                view: &texture.create_view(&Default::default()),
+                resolve_target: None,
+                ops: wgpu::Operations {
+                    load: wgpu::LoadOp::Clear(wgpu::Color::BLUE),
+                    store: wgpu::StoreOp::Store,
+                },
+            })],
+            depth_stencil_attachment: None,
+            timestamp_writes: None,
+            occlusion_query_set: None,
+        });
+        ctx.queue.submit(Some(encoder.finish()));
+
+        let d3d11_device = ctx.queue.create_d3d11on12_device().unwrap();
+        let wrapped = unsafe { texture.wrap_for_d3d11(&d3d11_device) }.unwrap();
+        assert!(!wrapped.raw().is_null());
+        unsafe {
+            ctx.queue
+                .acquire_texture_for_d3d11(&texture, &wrapped, &d3d11_device);
+            d3d11_device.release_texture(&wrapped);
+        }
+
+        let readback = ctx.device.create_buffer(&wgpu::BufferDescriptor {
+            label: None,
+            size: (SIZE.width * SIZE.height * 4) as wgpu::BufferAddress,
+            usage: wgpu::BufferUsages::COPY_DST | wgpu::BufferUsages::COPY_SRC,
+            mapped_at_creation: false,
+        });
+        let mut encoder = ctx
+            .device
+            .create_command_encoder(&wgpu::CommandEncoderDescriptor::default());
+        encoder.copy_texture_to_buffer(
+            texture.as_image_copy(),
+            wgpu::ImageCopyBuffer {
+                buffer: &readback,
+                layout: wgpu::ImageDataLayout {
+                    offset: 0,
+                    bytes_per_row: Some(SIZE.width * 4),
+                    rows_per_image: None,
+                },
+            },
+            SIZE,
+        );
+        ctx.queue.submit(Some(encoder.finish()));
+
+        let contents = wgpu::util::read_buffer(&ctx.device, &ctx.queue, &readback, ..)
+            .await
+            .unwrap();
+        for texel in contents.chunks_exact(4) {
+            assert_eq!(texel, [0, 0, 255, 255]);
+        }
+    });
+
+#[gpu_test]
+static D3D11ON12_DEVICE_REQUIRES_DX12: GpuTestConfiguration = GpuTestConfiguration::new()
+    .parameters(TestParameters::default().skip(FailureCase::backend(wgpu::Backends::DX12)))
+    .run_sync(|ctx| {
+        wgpu_test::fail(&ctx.device, || ctx.queue.create_d3d11on12_device());
+    });
@@ -0,0 +1,51 @@
+//! Tests for `wgpu::util::DebugOverlay`.
+
+use wgpu::util::DebugOverlay;
+use wgpu_test::{gpu_test, image, GpuTestConfiguration};
+
+#[gpu_test]
+static DRAW_OVERLAY_RECT: GpuTestConfiguration =
+    GpuTestConfiguration::new().run_async(|ctx| async move {
+        let format = wgpu::TextureFormat::Rgba8Unorm;
+        let target = ctx.device.create_texture(&wgpu::TextureDescriptor {
+            label: Some("target"),
+            size: wgpu::Extent3d {
+                width: 4,
+                height: 2,
+                depth_or_array_layers: 1,
+            },
+            mip_level_count: 1,
+            sample_count: 1,
+            dimension: wgpu::TextureDimension::D2,
+            format,
+            usage: wgpu::TextureUsages::COPY_SRC | wgpu::TextureUsages::RENDER_ATTACHMENT,
+            view_formats: &[],
+        });
+        let view = target.create_view(&wgpu::TextureViewDescriptor::default());
+
+        let mut overlay = DebugOverlay::new(&ctx.device, &ctx.queue, format);
+        overlay.set_enabled(false);
+        // Ignored while the overlay is disabled.
+        overlay.rect([0.0, 0.0], [4.0, 2.0], [1.0, 0.0, 0.0, 1.0]);
+        overlay.set_enabled(true);
+        overlay.rect([1.0, 0.0], [2.0, 1.0], [0.0, 0.0, 1.0, 1.0]);
+
+        let readback_buffer = image::ReadbackBuffers::new(&ctx.device, &target);
+        let mut encoder = ctx
+            .device
+            .create_command_encoder(&wgpu::CommandEncoderDescriptor::default());
+        // New textures are zeroed, so the overlay is drawn over transparent black.
+        overlay.render(&ctx.device, &ctx.queue, &mut encoder, &view, [4, 2]);
+        readback_buffer.copy_from(&ctx.device, &mut encoder, &target);
+        ctx.queue.submit(Some(encoder.finish()));
+
+        readback_buffer
+            .assert_buffer_contents(
+                &ctx,
+                &[
+                    0, 0, 0, 0, 0, 0, 255, 255, 0, 0, 255, 255, 0, 0, 0, 0, //
+                    0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0,
+                ],
+            )
+            .await;
+    });
@@ -1,4 +1,4 @@
-use std::sync::atomic::AtomicBool;
+use std::sync::{atomic::AtomicBool, Arc};
 
 use wgpu_test::{fail, gpu_test, FailureCase, GpuTestConfiguration, TestParameters};
 
@@ -95,6 +95,26 @@ static MULTIPLE_DEVICES: GpuTestConfiguration = GpuTestConfiguration::new()
         let _ = vec![create_device_and_queue(), create_device_and_queue()];
     });
 
+// Realtime priority is usually not permitted, which must fall back instead of failing.
+#[gpu_test]
+static REQUEST_DEVICE_REALTIME_PRIORITY: GpuTestConfiguration = GpuTestConfiguration::new()
+    .run_async(|ctx| async move {
+        let (device, queue) = ctx
+            .adapter
+            .request_device(
+                &wgpu::DeviceDescriptor {
+                    queue_priority: wgpu::QueuePriority::Realtime,
+                    ..Default::default()
+                },
+                None,
+            )
+            .await
+            .unwrap();
+
+        queue.submit(None);
+        device.poll(wgpu::Maintain::Wait).panic_on_timeout();
+    });
+
 #[cfg(not(all(target_arch = "wasm32", not(target_os = "emscripten"))))]
 #[gpu_test]
 static REQUEST_DEVICE_ERROR_MESSAGE_NATIVE: GpuTestConfiguration =
@@ -242,6 +262,7 @@ static DEVICE_DESTROY_THEN_MORE: GpuTestConfiguration = GpuTestConfiguration::ne
             .create_shader_module(wgpu::ShaderModuleDescriptor {
                 label: None,
                 source: wgpu::ShaderSource::Wgsl(std::borrow::Cow::Borrowed("")),
+                diagnostic_filters: &[],
             });
 
         // Create some command encoders.
@@ -459,6 +480,7 @@ static DEVICE_DESTROY_THEN_MORE: GpuTestConfiguration = GpuTestConfiguration::ne
                 .create_shader_module(wgpu::ShaderModuleDescriptor {
                     label: None,
                     source: wgpu::ShaderSource::Wgsl(std::borrow::Cow::Borrowed("")),
+                    diagnostic_filters: &[],
                 });
         });
 
@@ -628,6 +650,85 @@ static DEVICE_LOST_REPLACED_CALLBACK: GpuTestConfiguration = GpuTestConfiguratio
         );
     });
 
+#[gpu_test]
+static SIMULATED_LOSS_CALLS_LOST_CALLBACK: GpuTestConfiguration = GpuTestConfiguration::new()
+    .parameters(
+        TestParameters::default().skip(FailureCase::backend(wgpu::Backends::BROWSER_WEBGPU)),
+    )
+    .run_sync(|ctx| {
+        // This test checks that simulating device loss calls the
+        // DeviceLostClosure with the given reason, and makes the device invalid.
+        static WAS_CALLED: AtomicBool = AtomicBool::new(false);
+
+        let callback = Box::new(|reason, message| {
+            WAS_CALLED.store(true, std::sync::atomic::Ordering::SeqCst);
+            assert_eq!(reason, wgt::DeviceLostReason::Unknown);
+            assert_eq!(message, "Device loss was simulated.");
+        });
+        ctx.device.set_device_lost_callback(callback);
+
+        ctx.device.simulate_loss(wgt::DeviceLostReason::Unknown);
+
+        assert!(
+            WAS_CALLED.load(std::sync::atomic::Ordering::SeqCst),
+            "Device lost callback should have been called."
+        );
+
+        // Creating resources on the lost device should fail.
+        fail(&ctx.device, || {
+            ctx.device.create_buffer(&wgpu::BufferDescriptor {
+                label: None,
+                size: 256,
+                usage: wgpu::BufferUsages::COPY_DST,
+                mapped_at_creation: false,
+            });
+        });
+    });
+
+#[gpu_test]
+static SIMULATED_LOSS_RESOLVES_PENDING_CALLBACKS: GpuTestConfiguration =
+    GpuTestConfiguration::new()
+        .parameters(
+            TestParameters::default().skip(FailureCase::backend(wgpu::Backends::BROWSER_WEBGPU)),
+        )
+        .run_sync(|ctx| {
+            // This test checks that the callbacks of work that was pending when
+            // the device was lost have been called once `simulate_loss` returns.
+            let buffer = ctx.device.create_buffer(&wgpu::BufferDescriptor {
+                label: None,
+                size: 256,
+                usage: wgpu::BufferUsages::COPY_DST | wgpu::BufferUsages::MAP_READ,
+                mapped_at_creation: false,
+            });
+            let mut encoder = ctx
+                .device
+                .create_command_encoder(&wgpu::CommandEncoderDescriptor::default());
+            encoder.clear_buffer(&buffer, 0, None);
+            ctx.queue.submit(Some(encoder.finish()));
+
+            let work_done = Arc::new(AtomicBool::new(false));
+            let mapped = Arc::new(AtomicBool::new(false));
+            ctx.queue.on_submitted_work_done({
+                let work_done = Arc::clone(&work_done);
+                move || work_done.store(true, std::sync::atomic::Ordering::SeqCst)
+            });
+            buffer.slice(..).map_async(wgpu::MapMode::Read, {
+                let mapped = Arc::clone(&mapped);
+                move |_| mapped.store(true, std::sync::atomic::Ordering::SeqCst)
+            });
+
+            ctx.device.simulate_loss(wgt::DeviceLostReason::Unknown);
+
+            assert!(
+                work_done.load(std::sync::atomic::Ordering::SeqCst),
+                "Submitted work done callback should have been called."
+            );
+            assert!(
+                mapped.load(std::sync::atomic::Ordering::SeqCst),
+                "Buffer mapping callback should have been called."
+            );
+        });
+
 #[gpu_test]
 static DROPPED_GLOBAL_THEN_DEVICE_LOST: GpuTestConfiguration = GpuTestConfiguration::new()
     .parameters(TestParameters::default().skip(FailureCase::always()))
@@ -698,6 +799,7 @@ fn vs_main() -> @builtin(position) vec4<f32> {
                     source: wgpu::ShaderSource::Wgsl(
                         trivial_shaders_with_some_reversed_bindings.into(),
                     ),
+                    diagnostic_filters: &[],
                 });
 
         let my_texture = ctx.device.create_texture(&wgt::TextureDescriptor {
@@ -724,6 +826,7 @@ fn vs_main() -> @builtin(position) vec4<f32> {
             mip_level_count: None,
             base_array_layer: 0,
             array_layer_count: None,
+            swizzle: wgt::TextureComponentSwizzle::IDENTITY,
         });
 
         let my_sampler = ctx
@@ -0,0 +1,80 @@
+//! Tests for exporting textures as dma-bufs with `wgpu::util`.
+#![cfg(all(
+    unix,
+    not(target_arch = "wasm32"),
+    not(target_os = "macos"),
+    not(target_os = "ios")
+))]
+
+use std::os::fd::AsRawFd;
+
+use wgpu::util::{drm_fourcc, export_texture_as_dmabuf, DRM_FORMAT_MOD_INVALID};
+use wgpu_test::{gpu_test, GpuTestConfiguration, TestParameters, TestingContext};
+
+fn create_texture(
+    ctx: &TestingContext,
+    format: wgpu::TextureFormat,
+    depth_or_array_layers: u32,
+) -> wgpu::Texture {
+    ctx.device.create_exportable_texture(
+        &wgpu::TextureDescriptor {
+            label: None,
+            size: wgpu::Extent3d {
+                width: 64,
+                height: 32,
+                depth_or_array_layers,
+            },
+            mip_level_count: 1,
+            sample_count: 1,
+            dimension: wgpu::TextureDimension::D2,
+            format,
+            usage: wgpu::TextureUsages::RENDER_ATTACHMENT | wgpu::TextureUsages::COPY_SRC,
+            view_formats: &[],
+        },
+        wgpu::ExternalMemoryHandleType::DmaBuf,
+        &[],
+    )
+}
+
+#[gpu_test]
+static DMABUF_EXPORT_DESCRIBES_PLANES: GpuTestConfiguration = GpuTestConfiguration::new()
+    .parameters(TestParameters::default().features(wgpu::Features::EXTERNAL_MEMORY_DMA_BUF))
+    .run_sync(|ctx| {
+        let format = wgpu::TextureFormat::Rgba8Unorm;
+        let texture = create_texture(&ctx, format, 1);
+
+        let exported = export_texture_as_dmabuf(&texture).unwrap();
+        assert_eq!((exported.width, exported.height), (64, 32));
+        assert_eq!(exported.fourcc, u32::from_le_bytes(*b"AB24"));
+        assert_eq!(Some(exported.fourcc), drm_fourcc(format));
+        assert_ne!(exported.modifier, DRM_FORMAT_MOD_INVALID);
+        assert_eq!(
+            (u64::from(exported.modifier_hi()) << 32) | u64::from(exported.modifier_lo()),
+            exported.modifier
+        );
+
+        let supported = ctx
+            .device
+            .drm_format_modifiers(format, texture.usage())
+            .into_iter()
+            .find(|supported| supported.modifier == exported.modifier)
+            .unwrap();
+        assert_eq!(exported.planes.len(), supported.plane_count as usize);
+        // Every row holds at least the texels of the texture.
+        assert!(exported.planes[0].stride >= 64 * 4);
+
+        // Every plane owns its own descriptor.
+        for (i, a) in exported.planes.iter().enumerate() {
+            for b in &exported.planes[i + 1..] {
+                assert_ne!(a.fd.as_raw_fd(), b.fd.as_raw_fd());
+            }
+        }
+    });
+
+#[gpu_test]
+static DMABUF_EXPORT_REJECTS_ARRAY_TEXTURES: GpuTestConfiguration = GpuTestConfiguration::new()
+    .parameters(TestParameters::default().features(wgpu::Features::EXTERNAL_MEMORY_DMA_BUF))
+    .run_sync(|ctx| {
+        let texture = create_texture(&ctx, wgpu::TextureFormat::Rgba8Unorm, 2);
+        assert!(export_texture_as_dmabuf(&texture).is_none());
+    });
@@ -0,0 +1,109 @@
+//! Tests for `wgpu::util::DrawArgsConverter`.
+
+use wgpu::util::{read_buffer, BufferInitDescriptor, DeviceExt, DrawArgsConverter, DrawArgsLayout};
+use wgpu_test::{gpu_test, GpuTestConfiguration, TestParameters};
+
+/// Words between the draws in the source, which a culling shader fills with
+/// the arguments and an object id.
+const STRIDE: usize = 8;
+const PADDING: u32 = 0xDEAD;
+
+#[gpu_test]
+static CONVERT_STRIDED_INDEXED_DRAWS: GpuTestConfiguration = GpuTestConfiguration::new()
+    .parameters(
+        TestParameters::default()
+            .downlevel_flags(
+                wgpu::DownlevelFlags::COMPUTE_SHADERS | wgpu::DownlevelFlags::INDIRECT_EXECUTION,
+            )
+            .limits(wgpu::Limits::downlevel_defaults()),
+    )
+    .run_async(|ctx| async move {
+        let draws: [[u32; 5]; 3] = [[3, 1, 0, 0, 0], [6, 2, 3, 5, 0], [9, 4, 6, 0, 7]];
+        let mut source = Vec::new();
+        for draw in draws {
+            source.extend(draw);
+            source.resize(source.len() + STRIDE - draw.len(), PADDING);
+        }
+        let source: Vec<u8> = source.iter().flat_map(|w| w.to_ne_bytes()).collect();
+        let source = ctx.device.create_buffer_init(&BufferInitDescriptor {
+            label: None,
+            contents: &source,
+            usage: wgpu::BufferUsages::STORAGE,
+        });
+        // Draws don't have to start at the beginning of either buffer.
+        let target = ctx.device.create_buffer(&wgpu::BufferDescriptor {
+            label: None,
+            size: 4 + 15 * 4,
+            usage: wgpu::BufferUsages::STORAGE
+                | wgpu::BufferUsages::INDIRECT
+                | wgpu::BufferUsages::COPY_SRC,
+            mapped_at_creation: false,
+        });
+
+        let converter = DrawArgsConverter::new(&ctx.device);
+        let mut encoder = ctx
+            .device
+            .create_command_encoder(&wgpu::CommandEncoderDescriptor::default());
+        let draw_count = converter.convert(
+            &ctx.device,
+            &mut encoder,
+            source.slice(STRIDE as u64 * 4..),
+            DrawArgsLayout {
+                indexed: true,
+                stride: STRIDE as u64 * 4,
+            },
+            target.slice(4..),
+        );
+        ctx.queue.submit(Some(encoder.finish()));
+        assert_eq!(draw_count, 2);
+
+        let contents = read_buffer(&ctx.device, &ctx.queue, &target, 4..4 + 10 * 4)
+            .await
+            .unwrap();
+        let words: Vec<u32> = contents
+            .chunks_exact(4)
+            .map(|w| u32::from_ne_bytes(w.try_into().unwrap()))
+            .collect();
+        let last = if ctx
+            .device
+            .features()
+            .contains(wgpu::Features::INDIRECT_FIRST_INSTANCE)
+        {
+            draws[2]
+        } else {
+            // Draws that start at another instance are skipped.
+            [9, 0, 6, 0, 0]
+        };
+        assert_eq!(words, [draws[1], last].concat());
+    });
+
+#[gpu_test]
+static CONVERT_NO_DRAWS: GpuTestConfiguration = GpuTestConfiguration::new()
+    .parameters(
+        TestParameters::default()
+            .downlevel_flags(wgpu::DownlevelFlags::COMPUTE_SHADERS)
+            .limits(wgpu::Limits::downlevel_defaults()),
+    )
+    .run_sync(|ctx| {
+        let buffer = ctx.device.create_buffer(&wgpu::BufferDescriptor {
+            label: None,
+            size: 16,
+            usage: wgpu::BufferUsages::STORAGE | wgpu::BufferUsages::INDIRECT,
+            mapped_at_creation: false,
+        });
+
+        let converter = DrawArgsConverter::new(&ctx.device);
+        let mut encoder = ctx
+            .device
+            .create_command_encoder(&wgpu::CommandEncoderDescriptor::default());
+        // Too small for an indexed draw.
+        let draw_count = converter.convert(
+            &ctx.device,
+            &mut encoder,
+            buffer.slice(..),
+            DrawArgsLayout::DRAW_INDEXED,
+            buffer.slice(..),
+        );
+        assert_eq!(draw_count, 0);
+        ctx.queue.submit(Some(encoder.finish()));
+    });
@@ -0,0 +1,106 @@
+//! Tests for `wgpu::util::DrawBatcher`.
+
+use wgpu::util::{BatchedDraw, DrawBatcher};
+use wgpu_test::{gpu_test, image, GpuTestConfiguration, TestingContext};
+
+const TEXTURE_WIDTH: u32 = 4;
+
+fn create_pipeline(
+    ctx: &TestingContext,
+    shader: &wgpu::ShaderModule,
+    fragment_entry_point: &str,
+) -> wgpu::RenderPipeline {
+    ctx.device
+        .create_render_pipeline(&wgpu::RenderPipelineDescriptor {
+            label: Some(fragment_entry_point),
+            layout: None,
+            vertex: wgpu::VertexState {
+                module: shader,
+                entry_point: "vs_main",
+                compilation_options: Default::default(),
+                buffers: &[],
+            },
+            primitive: wgpu::PrimitiveState {
+                topology: wgpu::PrimitiveTopology::PointList,
+                ..Default::default()
+            },
+            depth_stencil: None,
+            multisample: wgpu::MultisampleState::default(),
+            fragment: Some(wgpu::FragmentState {
+                module: shader,
+                entry_point: fragment_entry_point,
+                compilation_options: Default::default(),
+                targets: &[Some(wgpu::TextureFormat::Rgba8Unorm.into())],
+            }),
+            multiview: None,
+        })
+}
+
+#[gpu_test]
+static SORT_AND_MERGE: GpuTestConfiguration =
+    GpuTestConfiguration::new().run_async(|ctx| async move {
+        let texture = ctx.device.create_texture(&wgpu::TextureDescriptor {
+            label: Some("Offscreen texture"),
+            size: wgpu::Extent3d {
+                width: TEXTURE_WIDTH,
+                height: 1,
+                depth_or_array_layers: 1,
+            },
+            mip_level_count: 1,
+            sample_count: 1,
+            dimension: wgpu::TextureDimension::D2,
+            format: wgpu::TextureFormat::Rgba8Unorm,
+            usage: wgpu::TextureUsages::COPY_SRC | wgpu::TextureUsages::RENDER_ATTACHMENT,
+            view_formats: &[],
+        });
+        let view = texture.create_view(&wgpu::TextureViewDescriptor::default());
+        let shader = ctx
+            .device
+            .create_shader_module(wgpu::include_wgsl!("points.wgsl"));
+        let red = create_pipeline(&ctx, &shader, "fs_red");
+        let green = create_pipeline(&ctx, &shader, "fs_green");
+
+        let mut batcher = DrawBatcher::new();
+        for (pipeline, vertex) in [(&red, 0), (&green, 2), (&red, 1), (&green, 3)] {
+            batcher.push(
+                BatchedDraw::new(pipeline, vertex..vertex + 1, 0..1)
+                    .topology(wgpu::PrimitiveTopology::PointList),
+            );
+        }
+        assert_eq!(batcher.len(), 4);
+
+        let readback_buffer = image::ReadbackBuffers::new(&ctx.device, &texture);
+        let mut encoder = ctx
+            .device
+            .create_command_encoder(&wgpu::CommandEncoderDescriptor::default());
+        {
+            let mut pass = encoder.begin_render_pass(&wgpu::RenderPassDescriptor {
+                label: Some("Renderpass"),
+                color_attachments: &[Some(wgpu::RenderPassColorAttachment {
+                    view: &view,
+                    resolve_target: None,
+                    ops: wgpu::Operations {
+                        load: wgpu::LoadOp::Clear(wgpu::Color::TRANSPARENT),
+                        store: wgpu::StoreOp::Store,
+                    },
+                })],
+                depth_stencil_attachment: None,
+                timestamp_writes: None,
+                occlusion_query_set: None,
+            });
+            // Each pipeline draws its two points with a single draw call.
+            assert_eq!(batcher.encode(&mut pass), 2);
+        }
+        assert!(batcher.is_empty());
+        readback_buffer.copy_from(&ctx.device, &mut encoder, &texture);
+        ctx.queue.submit(Some(encoder.finish()));
+
+        readback_buffer
+            .assert_buffer_contents(
+                &ctx,
+                &[
+                    255, 0, 0, 255, 255, 0, 0, 255, 0, 255, 0, 255, 0, 255, 0, 255,
+                ],
+            )
+            .await;
+    });
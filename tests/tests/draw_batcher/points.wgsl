@@ -0,0 +1,16 @@
+// Draws a point into the pixel column `vertex_index` of a 4x1 target.
+@vertex
+fn vs_main(@builtin(vertex_index) vertex_index: u32) -> @builtin(position) vec4<f32> {
+    let x = (f32(vertex_index) + 0.5) / 2.0 - 1.0;
+    return vec4<f32>(x, 0.0, 0.0, 1.0);
+}
+
+@fragment
+fn fs_red() -> @location(0) vec4<f32> {
+    return vec4<f32>(1.0, 0.0, 0.0, 1.0);
+}
+
+@fragment
+fn fs_green() -> @location(0) vec4<f32> {
+    return vec4<f32>(0.0, 1.0, 0.0, 1.0);
+}
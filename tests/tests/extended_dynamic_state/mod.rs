@@ -0,0 +1,220 @@
+//! Tests for `RenderPass::set_cull_mode` and friends, see
+//! `Features::EXTENDED_DYNAMIC_STATE`.
+
+use wgpu_test::{fail, gpu_test, image, GpuTestConfiguration, TestParameters, TestingContext};
+
+const TEXTURE_WIDTH: u32 = 4;
+const TEXTURE_HEIGHT: u32 = 1;
+const DEPTH_FORMAT: wgpu::TextureFormat = wgpu::TextureFormat::Depth32Float;
+
+fn create_target(ctx: &TestingContext, format: wgpu::TextureFormat) -> wgpu::Texture {
+    ctx.device.create_texture(&wgpu::TextureDescriptor {
+        label: Some("Offscreen texture"),
+        size: wgpu::Extent3d {
+            width: TEXTURE_WIDTH,
+            height: TEXTURE_HEIGHT,
+            depth_or_array_layers: 1,
+        },
+        mip_level_count: 1,
+        sample_count: 1,
+        dimension: wgpu::TextureDimension::D2,
+        format,
+        usage: wgpu::TextureUsages::COPY_SRC | wgpu::TextureUsages::RENDER_ATTACHMENT,
+        view_formats: &[],
+    })
+}
+
+fn create_pipeline(
+    ctx: &TestingContext,
+    primitive: wgpu::PrimitiveState,
+    depth_stencil: Option<wgpu::DepthStencilState>,
+) -> wgpu::RenderPipeline {
+    let shader = ctx
+        .device
+        .create_shader_module(wgpu::include_wgsl!("triangle.wgsl"));
+    ctx.device
+        .create_render_pipeline(&wgpu::RenderPipelineDescriptor {
+            label: Some("Pipeline"),
+            layout: None,
+            vertex: wgpu::VertexState {
+                module: &shader,
+                entry_point: "vs_main",
+                compilation_options: Default::default(),
+                buffers: &[],
+            },
+            primitive,
+            depth_stencil,
+            multisample: wgpu::MultisampleState::default(),
+            fragment: Some(wgpu::FragmentState {
+                module: &shader,
+                entry_point: "fs_main",
+                compilation_options: Default::default(),
+                targets: &[Some(wgpu::TextureFormat::Rgba8Unorm.into())],
+            }),
+            multiview: None,
+        })
+}
+
+fn begin_pass<'a>(
+    encoder: &'a mut wgpu::CommandEncoder,
+    view: &'a wgpu::TextureView,
+    depth_view: Option<&'a wgpu::TextureView>,
+) -> wgpu::RenderPass<'a> {
+    encoder.begin_render_pass(&wgpu::RenderPassDescriptor {
+        label: Some("Renderpass"),
+        color_attachments: &[Some(wgpu::RenderPassColorAttachment {
+            view,
+            resolve_target: None,
+            ops: wgpu::Operations {
+                load: wgpu::LoadOp::Clear(wgpu::Color::TRANSPARENT),
+                store: wgpu::StoreOp::Store,
+            },
+        })],
+        depth_stencil_attachment: depth_view.map(|view| wgpu::RenderPassDepthStencilAttachment {
+            view,
+            depth_ops: Some(wgpu::Operations {
+                load: wgpu::LoadOp::Clear(0.5),
+                store: wgpu::StoreOp::Store,
+            }),
+            stencil_ops: None,
+        }),
+        timestamp_writes: None,
+        occlusion_query_set: None,
+    })
+}
+
+/// Restricts drawing to the pixel column `x`.
+fn set_column(pass: &mut wgpu::RenderPass, x: u32) {
+    pass.set_viewport(x as f32, 0.0, 1.0, TEXTURE_HEIGHT as f32, 0.0, 1.0);
+}
+
+#[gpu_test]
+static CULL_MODE_AND_FRONT_FACE: GpuTestConfiguration = GpuTestConfiguration::new()
+    .parameters(TestParameters::default().features(wgpu::Features::EXTENDED_DYNAMIC_STATE))
+    .run_async(|ctx| async move {
+        let texture = create_target(&ctx, wgpu::TextureFormat::Rgba8Unorm);
+        let view = texture.create_view(&wgpu::TextureViewDescriptor::default());
+        let pipeline = create_pipeline(
+            &ctx,
+            wgpu::PrimitiveState {
+                cull_mode: Some(wgpu::Face::Back),
+                ..Default::default()
+            },
+            None,
+        );
+
+        let readback_buffer = image::ReadbackBuffers::new(&ctx.device, &texture);
+        let mut encoder = ctx
+            .device
+            .create_command_encoder(&wgpu::CommandEncoderDescriptor::default());
+        {
+            let mut pass = begin_pass(&mut encoder, &view, None);
+            pass.set_pipeline(&pipeline);
+            set_column(&mut pass, 0);
+            pass.draw(0..3, 0..1);
+
+            // The triangle is now a back face.
+            pass.set_front_face(wgpu::FrontFace::Cw);
+            set_column(&mut pass, 1);
+            pass.draw(0..3, 0..1);
+
+            pass.set_cull_mode(None);
+            set_column(&mut pass, 2);
+            pass.draw(0..3, 0..1);
+
+            // Setting the pipeline again restores its state.
+            pass.set_cull_mode(Some(wgpu::Face::Back));
+            pass.set_pipeline(&pipeline);
+            set_column(&mut pass, 3);
+            pass.draw(0..3, 0..1);
+        }
+        readback_buffer.copy_from(&ctx.device, &mut encoder, &texture);
+        ctx.queue.submit(Some(encoder.finish()));
+
+        let mut expected = [255; (TEXTURE_WIDTH * TEXTURE_HEIGHT * 4) as usize];
+        expected[4..8].fill(0);
+        readback_buffer
+            .assert_buffer_contents(&ctx, &expected)
+            .await;
+    });
+
+#[gpu_test]
+static DEPTH_COMPARE: GpuTestConfiguration = GpuTestConfiguration::new()
+    .parameters(TestParameters::default().features(wgpu::Features::EXTENDED_DYNAMIC_STATE))
+    .run_async(|ctx| async move {
+        let texture = create_target(&ctx, wgpu::TextureFormat::Rgba8Unorm);
+        let view = texture.create_view(&wgpu::TextureViewDescriptor::default());
+        let depth_texture = create_target(&ctx, DEPTH_FORMAT);
+        let depth_view = depth_texture.create_view(&wgpu::TextureViewDescriptor::default());
+        // The triangle at depth 0 passes against the cleared depth of 0.5.
+        let pipeline = create_pipeline(
+            &ctx,
+            wgpu::PrimitiveState::default(),
+            Some(wgpu::DepthStencilState {
+                format: DEPTH_FORMAT,
+                depth_write_enabled: false,
+                depth_compare: wgpu::CompareFunction::Less,
+                stencil: wgpu::StencilState::default(),
+                bias: wgpu::DepthBiasState::default(),
+            }),
+        );
+        // Even without a depth test of its own.
+        let always_pipeline = create_pipeline(
+            &ctx,
+            wgpu::PrimitiveState::default(),
+            Some(wgpu::DepthStencilState {
+                format: DEPTH_FORMAT,
+                depth_write_enabled: false,
+                depth_compare: wgpu::CompareFunction::Always,
+                stencil: wgpu::StencilState::default(),
+                bias: wgpu::DepthBiasState::default(),
+            }),
+        );
+
+        let readback_buffer = image::ReadbackBuffers::new(&ctx.device, &texture);
+        let mut encoder = ctx
+            .device
+            .create_command_encoder(&wgpu::CommandEncoderDescriptor::default());
+        {
+            let mut pass = begin_pass(&mut encoder, &view, Some(&depth_view));
+            pass.set_pipeline(&pipeline);
+            set_column(&mut pass, 0);
+            pass.draw(0..3, 0..1);
+
+            pass.set_depth_compare(wgpu::CompareFunction::Greater);
+            set_column(&mut pass, 1);
+            pass.draw(0..3, 0..1);
+
+            pass.set_pipeline(&always_pipeline);
+            set_column(&mut pass, 2);
+            pass.draw(0..3, 0..1);
+
+            pass.set_depth_compare(wgpu::CompareFunction::Never);
+            set_column(&mut pass, 3);
+            pass.draw(0..3, 0..1);
+        }
+        readback_buffer.copy_from(&ctx.device, &mut encoder, &texture);
+        ctx.queue.submit(Some(encoder.finish()));
+
+        let mut expected = [255; (TEXTURE_WIDTH * TEXTURE_HEIGHT * 4) as usize];
+        expected[4..8].fill(0);
+        expected[12..].fill(0);
+        readback_buffer
+            .assert_buffer_contents(&ctx, &expected)
+            .await;
+    });
+
+#[gpu_test]
+static DYNAMIC_STATE_REQUIRES_FEATURE: GpuTestConfiguration =
+    GpuTestConfiguration::new().run_sync(|ctx| {
+        let texture = create_target(&ctx, wgpu::TextureFormat::Rgba8Unorm);
+        let view = texture.create_view(&wgpu::TextureViewDescriptor::default());
+
+        let mut encoder = ctx
+            .device
+            .create_command_encoder(&wgpu::CommandEncoderDescriptor::default());
+        fail(&ctx.device, || {
+            let mut pass = begin_pass(&mut encoder, &view, None);
+            pass.set_cull_mode(None);
+        });
+    });
@@ -0,0 +1,74 @@
+//! Tests for `wgpu::util::ExternalImageCopier`.
+
+use wgpu::util::{ExternalImageCopier, ImageCopyPixels};
+use wgpu_test::{gpu_test, image, GpuTestConfiguration};
+
+#[gpu_test]
+static COPY_PIXELS_FLIPPED_AND_PREMULTIPLIED: GpuTestConfiguration = GpuTestConfiguration::new()
+    .run_async(|ctx| async move {
+        let format = wgpu::TextureFormat::Rgba8Unorm;
+        let texture = ctx.device.create_texture(&wgpu::TextureDescriptor {
+            label: None,
+            size: wgpu::Extent3d {
+                width: 3,
+                height: 2,
+                depth_or_array_layers: 1,
+            },
+            mip_level_count: 1,
+            sample_count: 1,
+            dimension: wgpu::TextureDimension::D2,
+            format,
+            usage: wgpu::TextureUsages::COPY_SRC | wgpu::TextureUsages::RENDER_ATTACHMENT,
+            view_formats: &[],
+        });
+
+        #[rustfmt::skip]
+        let pixels = [
+            255, 0, 0, 255,   0, 255, 0, 128,
+            0, 0, 255, 255,   255, 255, 255, 255,
+        ];
+        let copier = ExternalImageCopier::new(&ctx.device, format);
+        copier.copy_external_image_to_texture(
+            &ctx.device,
+            &ctx.queue,
+            &ImageCopyPixels {
+                data: &pixels,
+                width: 2,
+                height: 2,
+                color_space: wgpu::PredefinedColorSpace::Srgb,
+                premultiplied_alpha: false,
+                origin: wgpu::Origin2d::ZERO,
+                flip_y: true,
+            },
+            wgpu::ImageCopyTextureTagged {
+                texture: &texture,
+                mip_level: 0,
+                origin: wgpu::Origin3d { x: 1, y: 0, z: 0 },
+                aspect: wgpu::TextureAspect::All,
+                color_space: wgpu::PredefinedColorSpace::Srgb,
+                premultiplied_alpha: true,
+            },
+            wgpu::Extent3d {
+                width: 2,
+                height: 2,
+                depth_or_array_layers: 1,
+            },
+        );
+
+        let readback_buffer = image::ReadbackBuffers::new(&ctx.device, &texture);
+        let mut encoder = ctx
+            .device
+            .create_command_encoder(&wgpu::CommandEncoderDescriptor::default());
+        readback_buffer.copy_from(&ctx.device, &mut encoder, &texture);
+        ctx.queue.submit(Some(encoder.finish()));
+
+        // The first column is left as it is.
+        #[rustfmt::skip]
+        let expected = [
+            0, 0, 0, 0,   0, 0, 255, 255,   255, 255, 255, 255,
+            0, 0, 0, 0,   255, 0, 0, 255,   0, 128, 0, 128,
+        ];
+        readback_buffer
+            .assert_buffer_contents(&ctx, &expected)
+            .await;
+    });
@@ -0,0 +1,202 @@
+//! Tests for sharing buffer and texture memory through file descriptors and
+//! NT handles.
+#![cfg(any(
+    windows,
+    all(
+        unix,
+        not(target_arch = "wasm32"),
+        not(target_os = "macos"),
+        not(target_os = "ios")
+    )
+))]
+
+use wgpu::util::{read_buffer, DeviceExt};
+use wgpu_test::{gpu_test, GpuTestConfiguration, TestParameters};
+
+const SIZE: wgpu::BufferAddress = 256;
+
+#[cfg(unix)]
+const HANDLE_TYPE: wgpu::ExternalMemoryHandleType = wgpu::ExternalMemoryHandleType::OpaqueFd;
+#[cfg(unix)]
+const FEATURE: wgpu::Features = wgpu::Features::EXTERNAL_MEMORY_FD;
+#[cfg(windows)]
+const HANDLE_TYPE: wgpu::ExternalMemoryHandleType = wgpu::ExternalMemoryHandleType::D3D12Resource;
+#[cfg(windows)]
+const FEATURE: wgpu::Features = wgpu::Features::EXTERNAL_MEMORY_WIN32;
+
+#[gpu_test]
+static IMPORTED_BUFFER_SHARES_MEMORY: GpuTestConfiguration = GpuTestConfiguration::new()
+    .parameters(TestParameters::default().features(FEATURE))
+    .run_async(|ctx| async move {
+        let desc = wgpu::BufferDescriptor {
+            label: None,
+            size: SIZE,
+            usage: wgpu::BufferUsages::COPY_SRC | wgpu::BufferUsages::COPY_DST,
+            mapped_at_creation: false,
+        };
+        let exported = ctx.device.create_exportable_buffer(&desc, HANDLE_TYPE);
+
+        // Exported memory starts zeroed like that of any other buffer.
+        let contents = read_buffer(&ctx.device, &ctx.queue, &exported, ..)
+            .await
+            .unwrap();
+        assert!(contents.iter().all(|&byte| byte == 0));
+
+        let data: Vec<u8> = (0..SIZE as u32).map(|i| i as u8).collect();
+        let source = ctx
+            .device
+            .create_buffer_init(&wgpu::util::BufferInitDescriptor {
+                label: None,
+                contents: &data,
+                usage: wgpu::BufferUsages::COPY_SRC,
+            });
+        let mut encoder = ctx
+            .device
+            .create_command_encoder(&wgpu::CommandEncoderDescriptor::default());
+        encoder.copy_buffer_to_buffer(&source, 0, &exported, 0, SIZE);
+        ctx.queue.submit(Some(encoder.finish()));
+
+        let memory = exported.export_memory().unwrap();
+        assert_eq!(memory.handle_type, HANDLE_TYPE);
+        assert!(memory.size >= SIZE);
+
+        // The imported buffer sees what was written through the exported one.
+        let imported = unsafe { ctx.device.import_buffer(&desc, memory) };
+        let contents = read_buffer(&ctx.device, &ctx.queue, &imported, ..)
+            .await
+            .unwrap();
+        assert_eq!(contents, data);
+    });
+
+#[gpu_test]
+static EXPORTABLE_BUFFER_CANT_BE_MAPPABLE: GpuTestConfiguration = GpuTestConfiguration::new()
+    .parameters(TestParameters::default().features(FEATURE))
+    .run_sync(|ctx| {
+        wgpu_test::fail(&ctx.device, || {
+            ctx.device.create_exportable_buffer(
+                &wgpu::BufferDescriptor {
+                    label: None,
+                    size: SIZE,
+                    usage: wgpu::BufferUsages::MAP_READ | wgpu::BufferUsages::COPY_DST,
+                    mapped_at_creation: false,
+                },
+                HANDLE_TYPE,
+            )
+        });
+    });
+
+#[gpu_test]
+static IMPORTED_TEXTURE_SHARES_MEMORY: GpuTestConfiguration = GpuTestConfiguration::new()
+    .parameters(TestParameters::default().features(FEATURE))
+    .run_async(|ctx| async move {
+        let desc = wgpu::TextureDescriptor {
+            label: None,
+            size: wgpu::Extent3d {
+                width: 64,
+                height: 64,
+                depth_or_array_layers: 1,
+            },
+            mip_level_count: 1,
+            sample_count: 1,
+            dimension: wgpu::TextureDimension::D2,
+            format: wgpu::TextureFormat::Rgba8Unorm,
+            usage: wgpu::TextureUsages::RENDER_ATTACHMENT | wgpu::TextureUsages::COPY_SRC,
+            view_formats: &[],
+        };
+        let exported = ctx
+            .device
+            .create_exportable_texture(&desc, HANDLE_TYPE, &[]);
+
+        let mut encoder = ctx
+            .device
+            .create_command_encoder(&wgpu::CommandEncoderDescriptor::default());
+        encoder.begin_render_pass(&wgpu::RenderPassDescriptor {
+            label: None,
+            color_attachments: &[Some(wgpu::RenderPassColorAttachment {
+                view: &exported.create_view(&Default::default()),
+                resolve_target: None,
+                ops: wgpu::Operations {
+                    load: wgpu::LoadOp::Clear(wgpu::Color::RED),
+                    store: wgpu::StoreOp::Store,
+                },
+            })],
+            depth_stencil_attachment: None,
+            timestamp_writes: None,
+            occlusion_query_set: None,
+        });
+        ctx.queue.submit(Some(encoder.finish()));
+
+        let memory = exported.export_memory().unwrap();
+        assert_eq!(memory.handle_type, HANDLE_TYPE);
+
+        // The imported texture sees what was rendered to the exported one.
+        let imported = unsafe { ctx.device.import_texture(&desc, memory) };
+        let readback = ctx.device.create_buffer(&wgpu::BufferDescriptor {
+            label: None,
+            size: 64 * 64 * 4,
+            usage: wgpu::BufferUsages::COPY_DST | wgpu::BufferUsages::COPY_SRC,
+            mapped_at_creation: false,
+        });
+        let mut encoder = ctx
+            .device
+            .create_command_encoder(&wgpu::CommandEncoderDescriptor::default());
+        encoder.copy_texture_to_buffer(
+            imported.as_image_copy(),
+            wgpu::ImageCopyBuffer {
+                buffer: &readback,
+                layout: wgpu::ImageDataLayout {
+                    offset: 0,
+                    bytes_per_row: Some(64 * 4),
+                    rows_per_image: None,
+                },
+            },
+            desc.size,
+        );
+        ctx.queue.submit(Some(encoder.finish()));
+
+        let contents = read_buffer(&ctx.device, &ctx.queue, &readback, ..)
+            .await
+            .unwrap();
+        for texel in contents.chunks_exact(4) {
+            assert_eq!(texel, [255, 0, 0, 255]);
+        }
+    });
+
+#[cfg(unix)]
+#[gpu_test]
+static EXPORTED_TEXTURE_HAS_DRM_FORMAT_MODIFIER: GpuTestConfiguration = GpuTestConfiguration::new()
+    .parameters(TestParameters::default().features(wgpu::Features::EXTERNAL_MEMORY_DMA_BUF))
+    .run_sync(|ctx| {
+        let usage = wgpu::TextureUsages::RENDER_ATTACHMENT | wgpu::TextureUsages::COPY_SRC;
+        let modifiers = ctx
+            .device
+            .drm_format_modifiers(wgpu::TextureFormat::Rgba8Unorm, usage);
+        assert!(!modifiers.is_empty());
+
+        let texture = ctx.device.create_exportable_texture(
+            &wgpu::TextureDescriptor {
+                label: None,
+                size: wgpu::Extent3d {
+                    width: 64,
+                    height: 64,
+                    depth_or_array_layers: 1,
+                },
+                mip_level_count: 1,
+                sample_count: 1,
+                dimension: wgpu::TextureDimension::D2,
+                format: wgpu::TextureFormat::Rgba8Unorm,
+                usage,
+                view_formats: &[],
+            },
+            wgpu::ExternalMemoryHandleType::DmaBuf,
+            &[],
+        );
+
+        let memory = texture.export_memory().unwrap();
+        let modifier = memory.drm_format_modifier.unwrap();
+        let supported = modifiers
+            .iter()
+            .find(|supported| supported.modifier == modifier)
+            .unwrap();
+        assert_eq!(memory.planes.len(), supported.plane_count as usize);
+    });
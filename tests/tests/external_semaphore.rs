@@ -0,0 +1,89 @@
+//! Tests for sharing timeline semaphores through file descriptors and NT
+//! handles, and for Linux sync files.
+#![cfg(any(
+    windows,
+    all(
+        unix,
+        not(target_arch = "wasm32"),
+        not(target_os = "macos"),
+        not(target_os = "ios")
+    )
+))]
+
+use std::time::Duration;
+
+use wgpu::{Features, TimelineSemaphoreDescriptor};
+use wgpu_test::{gpu_test, GpuTestConfiguration, TestParameters};
+
+#[cfg(unix)]
+const HANDLE_TYPE: wgpu::ExternalSemaphoreHandleType = wgpu::ExternalSemaphoreHandleType::OpaqueFd;
+#[cfg(unix)]
+const FEATURE: Features = Features::EXTERNAL_SEMAPHORE_FD;
+#[cfg(windows)]
+const HANDLE_TYPE: wgpu::ExternalSemaphoreHandleType =
+    wgpu::ExternalSemaphoreHandleType::D3D12Fence;
+#[cfg(windows)]
+const FEATURE: Features = Features::EXTERNAL_MEMORY_WIN32;
+
+#[gpu_test]
+static IMPORTED_SEMAPHORE_SHARES_VALUE: GpuTestConfiguration = GpuTestConfiguration::new()
+    .parameters(TestParameters::default().features(Features::TIMELINE_SEMAPHORE | FEATURE))
+    .run_sync(|ctx| {
+        let exported = ctx.device.create_exportable_timeline_semaphore(
+            &TimelineSemaphoreDescriptor {
+                label: None,
+                initial_value: 1,
+            },
+            HANDLE_TYPE,
+        );
+        let semaphore = exported.export().unwrap();
+        assert_eq!(semaphore.handle_type, HANDLE_TYPE);
+
+        // The imported semaphore starts with the value of the exported one.
+        let imported = unsafe {
+            ctx.device
+                .import_timeline_semaphore(&TimelineSemaphoreDescriptor::default(), semaphore)
+        };
+        assert_eq!(imported.value(), 1);
+
+        exported.signal(2);
+        assert!(imported.wait(2, Duration::from_secs(5)));
+
+        // Submissions waiting on one side are released by the other.
+        ctx.queue
+            .submit_with_semaphores(None, &[(&imported, 2)], &[(&imported, 3)]);
+        assert!(exported.wait(3, Duration::from_secs(5)));
+    });
+
+#[gpu_test]
+static PLAIN_SEMAPHORE_CANT_BE_EXPORTED: GpuTestConfiguration = GpuTestConfiguration::new()
+    .parameters(TestParameters::default().features(Features::TIMELINE_SEMAPHORE | FEATURE))
+    .run_sync(|ctx| {
+        let semaphore = ctx
+            .device
+            .create_timeline_semaphore(&TimelineSemaphoreDescriptor::default());
+        wgpu_test::fail(&ctx.device, || assert!(semaphore.export().is_none()));
+    });
+
+#[cfg(unix)]
+#[gpu_test]
+static SUBMIT_WAITS_FOR_SYNC_FILE: GpuTestConfiguration = GpuTestConfiguration::new()
+    .parameters(
+        TestParameters::default()
+            .features(Features::TIMELINE_SEMAPHORE | Features::EXTERNAL_SEMAPHORE_FD),
+    )
+    .run_sync(|ctx| {
+        let semaphore = ctx
+            .device
+            .create_timeline_semaphore(&TimelineSemaphoreDescriptor::default());
+
+        // The next submission waits for the sync file of value 1 before signaling 2.
+        let sync_file = ctx.queue.export_sync_file(&semaphore, 1).unwrap();
+        unsafe { ctx.queue.wait_for_sync_file(sync_file) };
+        ctx.queue
+            .submit_with_semaphores(None, &[], &[(&semaphore, 2)]);
+        assert!(!semaphore.wait(2, Duration::ZERO));
+
+        semaphore.signal(1);
+        assert!(semaphore.wait(2, Duration::from_secs(5)));
+    });
@@ -0,0 +1,60 @@
+//! Tests for `CommandEncoder::fill_buffer`.
+
+use wgpu::util::{read_buffer, DeviceExt};
+use wgpu_test::{fail, gpu_test, FailureCase, GpuTestConfiguration, TestParameters};
+
+#[gpu_test]
+static FILL_BUFFER_WITH_PATTERN: GpuTestConfiguration = GpuTestConfiguration::new()
+    .parameters(
+        TestParameters::default().skip(FailureCase::backend(wgpu::Backends::BROWSER_WEBGPU)),
+    )
+    .run_async(|ctx| async move {
+        let buffer = ctx
+            .device
+            .create_buffer_init(&wgpu::util::BufferInitDescriptor {
+                label: None,
+                contents: &[0xFF; 24],
+                usage: wgpu::BufferUsages::COPY_SRC | wgpu::BufferUsages::COPY_DST,
+            });
+
+        let mut encoder = ctx
+            .device
+            .create_command_encoder(&wgpu::CommandEncoderDescriptor::default());
+        encoder.fill_buffer(&buffer, 4, Some(8), 0xDEADBEEF);
+        // A pattern of the same byte repeated.
+        encoder.fill_buffer(&buffer, 16, None, 0x2A2A2A2A);
+        ctx.queue.submit(Some(encoder.finish()));
+
+        let contents = read_buffer(&ctx.device, &ctx.queue, &buffer, ..)
+            .await
+            .unwrap();
+        assert_eq!(
+            contents,
+            [
+                0xFF, 0xFF, 0xFF, 0xFF, 0xEF, 0xBE, 0xAD, 0xDE, 0xEF, 0xBE, 0xAD, 0xDE, 0xFF, 0xFF,
+                0xFF, 0xFF, 0x2A, 0x2A, 0x2A, 0x2A, 0x2A, 0x2A, 0x2A, 0x2A,
+            ]
+        );
+    });
+
+#[gpu_test]
+static FILL_BUFFER_UNALIGNED: GpuTestConfiguration = GpuTestConfiguration::new()
+    .parameters(
+        TestParameters::default().skip(FailureCase::backend(wgpu::Backends::BROWSER_WEBGPU)),
+    )
+    .run_sync(|ctx| {
+        let buffer = ctx.device.create_buffer(&wgpu::BufferDescriptor {
+            label: None,
+            size: 16,
+            usage: wgpu::BufferUsages::COPY_DST,
+            mapped_at_creation: false,
+        });
+
+        fail(&ctx.device, || {
+            let mut encoder = ctx
+                .device
+                .create_command_encoder(&wgpu::CommandEncoderDescriptor::default());
+            encoder.fill_buffer(&buffer, 2, Some(4), 0x01020304);
+            encoder.finish()
+        });
+    });
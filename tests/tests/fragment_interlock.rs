@@ -0,0 +1,140 @@
+//! Tests for fragment shader critical sections, with
+//! `Features::FRAGMENT_SHADER_INTERLOCK`.
+
+use wgpu::util::read_buffer;
+use wgpu_test::{fail, gpu_test, GpuTestConfiguration, TestParameters};
+
+const SIZE: u32 = 4;
+const LAYERS: u32 = 64;
+
+const SHADER: &str = "
+const SIZE: u32 = 4u;
+
+@group(0) @binding(0) var<storage, read_write> counts: array<u32>;
+
+@vertex
+fn vs_main(@builtin(vertex_index) index: u32) -> @builtin(position) vec4<f32> {
+    // A triangle covering the whole target.
+    let uv = vec2<f32>(f32((index << 1u) & 2u), f32(index & 2u));
+    return vec4<f32>(uv * 2.0 - 1.0, 0.0, 1.0);
+}
+
+@fragment
+fn fs_main(@builtin(position) position: vec4<f32>) -> @location(0) vec4<f32> {
+    let index = u32(position.y) * SIZE + u32(position.x);
+    beginInvocationInterlock();
+    // Not atomic: only the critical section keeps the fragments of the
+    // overlapping triangles from racing.
+    counts[index] = counts[index] + 1u;
+    endInvocationInterlock();
+    return vec4<f32>(1.0);
+}
+";
+
+fn shader_module(ctx: &wgpu_test::TestingContext) -> wgpu::ShaderModule {
+    ctx.device
+        .create_shader_module(wgpu::ShaderModuleDescriptor {
+            label: None,
+            source: wgpu::ShaderSource::Wgsl(SHADER.into()),
+            diagnostic_filters: &[],
+        })
+}
+
+#[gpu_test]
+static FRAGMENT_INTERLOCK_WITHOUT_FEATURE: GpuTestConfiguration = GpuTestConfiguration::new()
+    .parameters(TestParameters::default())
+    .run_sync(|ctx| {
+        fail(&ctx.device, || {
+            shader_module(&ctx);
+        });
+    });
+
+#[gpu_test]
+static FRAGMENT_INTERLOCK_ORDERED_ACCESS: GpuTestConfiguration = GpuTestConfiguration::new()
+    .parameters(
+        TestParameters::default()
+            .features(wgpu::Features::FRAGMENT_SHADER_INTERLOCK)
+            .downlevel_flags(wgpu::DownlevelFlags::FRAGMENT_WRITABLE_STORAGE),
+    )
+    .run_async(|ctx| async move {
+        let module = shader_module(&ctx);
+        let pipeline = ctx
+            .device
+            .create_render_pipeline(&wgpu::RenderPipelineDescriptor {
+                label: None,
+                layout: None,
+                vertex: wgpu::VertexState {
+                    module: &module,
+                    entry_point: "vs_main",
+                    compilation_options: Default::default(),
+                    buffers: &[],
+                },
+                primitive: wgpu::PrimitiveState::default(),
+                depth_stencil: None,
+                multisample: wgpu::MultisampleState::default(),
+                fragment: Some(wgpu::FragmentState {
+                    module: &module,
+                    entry_point: "fs_main",
+                    compilation_options: Default::default(),
+                    targets: &[Some(wgpu::TextureFormat::Rgba8Unorm.into())],
+                }),
+                multiview: None,
+            });
+
+        let counts = ctx.device.create_buffer(&wgpu::BufferDescriptor {
+            label: None,
+            size: (SIZE * SIZE * 4) as u64,
+            usage: wgpu::BufferUsages::STORAGE | wgpu::BufferUsages::COPY_SRC,
+            mapped_at_creation: false,
+        });
+        let bind_group = ctx.device.create_bind_group(&wgpu::BindGroupDescriptor {
+            label: None,
+            layout: &pipeline.get_bind_group_layout(0),
+            entries: &[wgpu::BindGroupEntry {
+                binding: 0,
+                resource: counts.as_entire_binding(),
+            }],
+        });
+
+        let target = ctx.device.create_texture(&wgpu::TextureDescriptor {
+            label: None,
+            size: wgpu::Extent3d {
+                width: SIZE,
+                height: SIZE,
+                depth_or_array_layers: 1,
+            },
+            mip_level_count: 1,
+            sample_count: 1,
+            dimension: wgpu::TextureDimension::D2,
+            format: wgpu::TextureFormat::Rgba8Unorm,
+            usage: wgpu::TextureUsages::RENDER_ATTACHMENT,
+            view_formats: &[],
+        });
+        let view = target.create_view(&wgpu::TextureViewDescriptor::default());
+
+        let mut encoder = ctx
+            .device
+            .create_command_encoder(&wgpu::CommandEncoderDescriptor::default());
+        let mut pass = encoder.begin_render_pass(&wgpu::RenderPassDescriptor {
+            label: None,
+            color_attachments: &[Some(wgpu::RenderPassColorAttachment {
+                view: &view,
+                resolve_target: None,
+                ops: wgpu::Operations::default(),
+            })],
+            depth_stencil_attachment: None,
+            timestamp_writes: None,
+            occlusion_query_set: None,
+        });
+        pass.set_pipeline(&pipeline);
+        pass.set_bind_group(0, &bind_group, &[]);
+        pass.draw(0..3, 0..LAYERS);
+        drop(pass);
+        ctx.queue.submit(Some(encoder.finish()));
+
+        let data = read_buffer(&ctx.device, &ctx.queue, &counts, ..)
+            .await
+            .unwrap();
+        let data: Vec<u32> = bytemuck::pod_collect_to_vec(&data);
+        assert_eq!(data, [LAYERS; (SIZE * SIZE) as usize]);
+    });
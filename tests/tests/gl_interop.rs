@@ -0,0 +1,145 @@
+//! Tests for textures shared with other GL contexts.
+#![cfg(all(
+    unix,
+    not(target_arch = "wasm32"),
+    not(any(target_os = "macos", target_os = "ios"))
+))]
+
+use wgpu_test::{gpu_test, GpuTestConfiguration, TestParameters, TestingContext};
+
+const SIZE: wgpu::Extent3d = wgpu::Extent3d {
+    width: 64,
+    height: 64,
+    depth_or_array_layers: 1,
+};
+
+fn descriptor(usage: wgpu::TextureUsages) -> wgpu::TextureDescriptor<'static> {
+    wgpu::TextureDescriptor {
+        label: None,
+        size: SIZE,
+        mip_level_count: 1,
+        sample_count: 1,
+        dimension: wgpu::TextureDimension::D2,
+        format: wgpu::TextureFormat::Rgba8Unorm,
+        usage,
+        view_formats: &[],
+    }
+}
+
+/// Create a texture with a name and storage for an EGLImage, cleared to blue.
+fn blue_texture(ctx: &TestingContext) -> wgpu::Texture {
+    let texture = ctx.device.create_texture(&descriptor(
+        wgpu::TextureUsages::RENDER_ATTACHMENT | wgpu::TextureUsages::TEXTURE_BINDING,
+    ));
+
+    let mut encoder = ctx
+        .device
+        .create_command_encoder(&wgpu::CommandEncoderDescriptor::default());
+    encoder.begin_render_pass(&wgpu::RenderPassDescriptor {
+        label: None,
+        color_attachments: &[Some(wgpu::RenderPassColorAttachment {
+            view: &texture.create_view(&Default::default()),
+            resolve_target: None,
+            ops: wgpu::Operations {
+                load: wgpu::LoadOp::Clear(wgpu::Color::BLUE),
+                store: wgpu::StoreOp::Store,
+            },
+        })],
+        depth_stencil_attachment: None,
+        timestamp_writes: None,
+        occlusion_query_set: None,
+    });
+    ctx.queue.submit(Some(encoder.finish()));
+    texture
+}
+
+async fn assert_blue(ctx: &TestingContext, texture: &wgpu::Texture) {
+    let readback = ctx.device.create_buffer(&wgpu::BufferDescriptor {
+        label: None,
+        size: (SIZE.width * SIZE.height * 4) as wgpu::BufferAddress,
+        usage: wgpu::BufferUsages::COPY_DST | wgpu::BufferUsages::COPY_SRC,
+        mapped_at_creation: false,
+    });
+    let mut encoder = ctx
+        .device
+        .create_command_encoder(&wgpu::CommandEncoderDescriptor::default());
+    encoder.copy_texture_to_buffer(
+        texture.as_image_copy(),
+        wgpu::ImageCopyBuffer {
+            buffer: &readback,
+            layout: wgpu::ImageDataLayout {
+                offset: 0,
+                bytes_per_row: Some(SIZE.width * 4),
+                rows_per_image: None,
+            },
+        },
+        SIZE,
+    );
+    ctx.queue.submit(Some(encoder.finish()));
+
+    let contents = wgpu::util::read_buffer(&ctx.device, &ctx.queue, &readback, ..)
+        .await
+        .unwrap();
+    for texel in contents.chunks_exact(4) {
+        assert_eq!(texel, [0, 0, 255, 255]);
+    }
+}
+
+#[gpu_test]
+static IMPORTED_GL_TEXTURE_SHARES_CONTENTS: GpuTestConfiguration = GpuTestConfiguration::new()
+    .parameters(TestParameters::default().features(wgpu::Features::GL_TEXTURE_INTEROP))
+    .run_async(|ctx| async move {
+        let exported = blue_texture(&ctx);
+        let name = exported.gl_texture_name().unwrap();
+
+        let imported = unsafe {
+            ctx.device
+                .import_gl_texture(&descriptor(wgpu::TextureUsages::COPY_SRC), name)
+        };
+        assert_blue(&ctx, &imported).await;
+
+        // The texture still belongs to the first one.
+        drop(imported);
+        assert_eq!(exported.gl_texture_name(), Some(name));
+    });
+
+#[gpu_test]
+static IMPORTED_EGL_IMAGE_SHARES_CONTENTS: GpuTestConfiguration = GpuTestConfiguration::new()
+    .parameters(TestParameters::default().features(wgpu::Features::GL_TEXTURE_INTEROP))
+    .run_async(|ctx| async move {
+        let exported = blue_texture(&ctx);
+        let image = exported.create_egl_image().unwrap();
+        assert!(!image.is_null());
+
+        let imported = unsafe {
+            ctx.device
+                .import_egl_image(&descriptor(wgpu::TextureUsages::COPY_SRC), image)
+        };
+        assert_blue(&ctx, &imported).await;
+    });
+
+#[gpu_test]
+static EGL_IMAGE_TEXTURE_MUST_HAVE_ONE_MIP: GpuTestConfiguration = GpuTestConfiguration::new()
+    .parameters(TestParameters::default().features(wgpu::Features::GL_TEXTURE_INTEROP))
+    .run_sync(|ctx| {
+        let image = blue_texture(&ctx).create_egl_image().unwrap();
+        wgpu_test::fail(&ctx.device, || unsafe {
+            ctx.device.import_egl_image(
+                &wgpu::TextureDescriptor {
+                    mip_level_count: 2,
+                    ..descriptor(wgpu::TextureUsages::TEXTURE_BINDING)
+                },
+                image,
+            )
+        });
+    });
+
+#[gpu_test]
+static RENDERBUFFER_HAS_NO_GL_TEXTURE_NAME: GpuTestConfiguration = GpuTestConfiguration::new()
+    .parameters(TestParameters::default().features(wgpu::Features::GL_TEXTURE_INTEROP))
+    .run_sync(|ctx| {
+        let texture = ctx
+            .device
+            .create_texture(&descriptor(wgpu::TextureUsages::RENDER_ATTACHMENT));
+        wgpu_test::fail(&ctx.device, || texture.gl_texture_name());
+    });
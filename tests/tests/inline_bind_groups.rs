@@ -0,0 +1,192 @@
+//! Tests for `RenderPass::set_bind_group_inline`, see `Features::INLINE_BIND_GROUPS`.
+
+use wgpu::util::DeviceExt;
+use wgpu_test::{fail, gpu_test, image, GpuTestConfiguration, TestParameters, TestingContext};
+
+const SHADER: &str = r#"
+@group(0) @binding(0)
+var<uniform> color: vec4<f32>;
+
+@vertex
+fn vs_main(@builtin(vertex_index) index: u32) -> @builtin(position) vec4<f32> {
+    let uv = vec2<f32>(f32((index << 1u) & 2u), f32(index & 2u));
+    return vec4<f32>(uv * 2.0 - 1.0, 0.0, 1.0);
+}
+
+@fragment
+fn fs_main() -> @location(0) vec4<f32> {
+    return color;
+}
+"#;
+
+const TEXTURE_WIDTH: u32 = 4;
+
+const COLORS: [[f32; 4]; TEXTURE_WIDTH as usize] = [
+    [1.0, 0.0, 0.0, 1.0],
+    [0.0, 1.0, 0.0, 1.0],
+    [0.0, 0.0, 1.0, 1.0],
+    [1.0, 1.0, 1.0, 1.0],
+];
+
+fn create_inline_layout(ctx: &TestingContext) -> wgpu::BindGroupLayout {
+    ctx.device
+        .create_inline_bind_group_layout(&wgpu::BindGroupLayoutDescriptor {
+            label: Some("Inline layout"),
+            entries: &[wgpu::BindGroupLayoutEntry {
+                binding: 0,
+                visibility: wgpu::ShaderStages::FRAGMENT,
+                ty: wgpu::BindingType::Buffer {
+                    ty: wgpu::BufferBindingType::Uniform,
+                    has_dynamic_offset: false,
+                    min_binding_size: wgpu::BufferSize::new(16),
+                },
+                count: None,
+            }],
+        })
+}
+
+#[gpu_test]
+static DRAW_WITH_INLINE_BIND_GROUPS: GpuTestConfiguration = GpuTestConfiguration::new()
+    .parameters(TestParameters::default().features(wgpu::Features::INLINE_BIND_GROUPS))
+    .run_async(|ctx| async move {
+        let texture = ctx.device.create_texture(&wgpu::TextureDescriptor {
+            label: Some("Offscreen texture"),
+            size: wgpu::Extent3d {
+                width: TEXTURE_WIDTH,
+                height: 1,
+                depth_or_array_layers: 1,
+            },
+            mip_level_count: 1,
+            sample_count: 1,
+            dimension: wgpu::TextureDimension::D2,
+            format: wgpu::TextureFormat::Rgba8Unorm,
+            usage: wgpu::TextureUsages::COPY_SRC | wgpu::TextureUsages::RENDER_ATTACHMENT,
+            view_formats: &[],
+        });
+        let view = texture.create_view(&wgpu::TextureViewDescriptor::default());
+
+        let bind_group_layout = create_inline_layout(&ctx);
+        let pipeline_layout = ctx
+            .device
+            .create_pipeline_layout(&wgpu::PipelineLayoutDescriptor {
+                label: None,
+                bind_group_layouts: &[&bind_group_layout],
+                push_constant_ranges: &[],
+            });
+        let shader = ctx
+            .device
+            .create_shader_module(wgpu::ShaderModuleDescriptor {
+                label: None,
+                source: wgpu::ShaderSource::Wgsl(SHADER.into()),
+                diagnostic_filters: &[],
+            });
+        let pipeline = ctx
+            .device
+            .create_render_pipeline(&wgpu::RenderPipelineDescriptor {
+                label: Some("Pipeline"),
+                layout: Some(&pipeline_layout),
+                vertex: wgpu::VertexState {
+                    module: &shader,
+                    entry_point: "vs_main",
+                    compilation_options: Default::default(),
+                    buffers: &[],
+                },
+                primitive: wgpu::PrimitiveState::default(),
+                depth_stencil: None,
+                multisample: wgpu::MultisampleState::default(),
+                fragment: Some(wgpu::FragmentState {
+                    module: &shader,
+                    entry_point: "fs_main",
+                    compilation_options: Default::default(),
+                    targets: &[Some(wgpu::TextureFormat::Rgba8Unorm.into())],
+                }),
+                multiview: None,
+            });
+
+        let buffers: Vec<_> = COLORS
+            .iter()
+            .map(|color| {
+                let contents: Vec<u8> = color.iter().flat_map(|c| c.to_le_bytes()).collect();
+                ctx.device
+                    .create_buffer_init(&wgpu::util::BufferInitDescriptor {
+                        label: Some("Color"),
+                        contents: &contents,
+                        usage: wgpu::BufferUsages::UNIFORM,
+                    })
+            })
+            .collect();
+
+        let readback_buffer = image::ReadbackBuffers::new(&ctx.device, &texture);
+        let mut encoder = ctx
+            .device
+            .create_command_encoder(&wgpu::CommandEncoderDescriptor::default());
+        {
+            let mut pass = encoder.begin_render_pass(&wgpu::RenderPassDescriptor {
+                label: Some("Renderpass"),
+                color_attachments: &[Some(wgpu::RenderPassColorAttachment {
+                    view: &view,
+                    resolve_target: None,
+                    ops: wgpu::Operations {
+                        load: wgpu::LoadOp::Clear(wgpu::Color::TRANSPARENT),
+                        store: wgpu::StoreOp::Store,
+                    },
+                })],
+                depth_stencil_attachment: None,
+                timestamp_writes: None,
+                occlusion_query_set: None,
+            });
+            pass.set_pipeline(&pipeline);
+            // Every draw covers one pixel column with the color of its own buffer.
+            for (x, buffer) in buffers.iter().enumerate() {
+                pass.set_viewport(x as f32, 0.0, 1.0, 1.0, 0.0, 1.0);
+                pass.set_bind_group_inline(
+                    0,
+                    &[wgpu::BindGroupEntry {
+                        binding: 0,
+                        resource: buffer.as_entire_binding(),
+                    }],
+                );
+                pass.draw(0..3, 0..1);
+            }
+        }
+        readback_buffer.copy_from(&ctx.device, &mut encoder, &texture);
+        ctx.queue.submit(Some(encoder.finish()));
+
+        let expected: Vec<u8> = COLORS
+            .iter()
+            .flatten()
+            .map(|&c| (c * 255.0) as u8)
+            .collect();
+        readback_buffer
+            .assert_buffer_contents(&ctx, &expected)
+            .await;
+    });
+
+#[gpu_test]
+static INLINE_LAYOUT_REJECTS_BIND_GROUPS: GpuTestConfiguration = GpuTestConfiguration::new()
+    .parameters(TestParameters::default().features(wgpu::Features::INLINE_BIND_GROUPS))
+    .run_sync(|ctx| {
+        let layout = create_inline_layout(&ctx);
+        let buffer = ctx.device.create_buffer(&wgpu::BufferDescriptor {
+            label: None,
+            size: 16,
+            usage: wgpu::BufferUsages::UNIFORM,
+            mapped_at_creation: false,
+        });
+        fail(&ctx.device, || {
+            ctx.device.create_bind_group(&wgpu::BindGroupDescriptor {
+                label: None,
+                layout: &layout,
+                entries: &[wgpu::BindGroupEntry {
+                    binding: 0,
+                    resource: buffer.as_entire_binding(),
+                }],
+            })
+        });
+    });
+
+#[gpu_test]
+static INLINE_LAYOUT_REQUIRES_FEATURE: GpuTestConfiguration =
+    GpuTestConfiguration::new().run_sync(|ctx| {
+        fail(&ctx.device, || create_inline_layout(&ctx));
+    });
@@ -0,0 +1,94 @@
+//! Tests for `Features::SHADER_INT64_ATOMICS`.
+
+use wgpu::util::read_buffer;
+use wgpu_test::{gpu_test, GpuTestConfiguration, TestParameters};
+
+const SHADER: &str = r#"
+struct Results {
+    sum: atomic<u64>,
+    max: atomic<u64>,
+    min: atomic<i64>,
+}
+
+@group(0) @binding(0)
+var<storage, read_write> results: Results;
+
+var<workgroup> local_sum: atomic<u64>;
+
+@compute @workgroup_size(64)
+fn main(@builtin(local_invocation_index) index: u32) {
+    // Packed like a visibility buffer: the depth in the high bits, the id in the low bits.
+    let packed = (u64(index) << 32u) | u64(63u - index);
+    atomicMax(&results.max, packed);
+    atomicMin(&results.min, -i64(index) * 0x100000000li);
+
+    atomicAdd(&local_sum, 0x100000000lu);
+    workgroupBarrier();
+    if index == 0u {
+        atomicAdd(&results.sum, atomicLoad(&local_sum));
+    }
+}
+"#;
+
+#[gpu_test]
+static INT64_ATOMICS: GpuTestConfiguration = GpuTestConfiguration::new()
+    .parameters(
+        TestParameters::default()
+            .features(wgpu::Features::SHADER_INT64 | wgpu::Features::SHADER_INT64_ATOMICS),
+    )
+    .run_async(|ctx| async move {
+        let module = ctx
+            .device
+            .create_shader_module(wgpu::ShaderModuleDescriptor {
+                label: None,
+                source: wgpu::ShaderSource::Wgsl(SHADER.into()),
+                diagnostic_filters: &[],
+            });
+        let pipeline = ctx
+            .device
+            .create_compute_pipeline(&wgpu::ComputePipelineDescriptor {
+                label: None,
+                layout: None,
+                module: &module,
+                entry_point: "main",
+                compilation_options: Default::default(),
+            });
+
+        let buffer = ctx.device.create_buffer(&wgpu::BufferDescriptor {
+            label: None,
+            size: 24,
+            usage: wgpu::BufferUsages::STORAGE | wgpu::BufferUsages::COPY_SRC,
+            mapped_at_creation: false,
+        });
+        let bind_group = ctx.device.create_bind_group(&wgpu::BindGroupDescriptor {
+            label: None,
+            layout: &pipeline.get_bind_group_layout(0),
+            entries: &[wgpu::BindGroupEntry {
+                binding: 0,
+                resource: buffer.as_entire_binding(),
+            }],
+        });
+
+        let mut encoder = ctx
+            .device
+            .create_command_encoder(&wgpu::CommandEncoderDescriptor::default());
+        {
+            let mut pass = encoder.begin_compute_pass(&wgpu::ComputePassDescriptor::default());
+            pass.set_pipeline(&pipeline);
+            pass.set_bind_group(0, &bind_group, &[]);
+            pass.dispatch_workgroups(2, 1, 1);
+        }
+        ctx.queue.submit(Some(encoder.finish()));
+
+        let bytes = read_buffer(&ctx.device, &ctx.queue, &buffer, ..)
+            .await
+            .unwrap();
+        let values: Vec<u64> = bytes
+            .chunks_exact(8)
+            .map(|chunk| u64::from_le_bytes(chunk.try_into().unwrap()))
+            .collect();
+
+        assert_eq!(values[0], 128 << 32);
+        assert_eq!(values[1], 63 << 32);
+        assert_eq!(values[2] as i64, -63 << 32);
+    });
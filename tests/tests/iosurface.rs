@@ -0,0 +1,112 @@
+//! Tests for textures backed by IOSurfaces.
+#![cfg(any(target_os = "macos", target_os = "ios"))]
+
+use wgpu_test::{gpu_test, GpuTestConfiguration, TestParameters};
+
+const SIZE: wgpu::Extent3d = wgpu::Extent3d {
+    width: 64,
+    height: 64,
+    depth_or_array_layers: 1,
+};
+
+fn descriptor(usage: wgpu::TextureUsages) -> wgpu::TextureDescriptor<'static> {
+    wgpu::TextureDescriptor {
+        label: None,
+        size: SIZE,
+        mip_level_count: 1,
+        sample_count: 1,
+        dimension: wgpu::TextureDimension::D2,
+        format: wgpu::TextureFormat::Bgra8Unorm,
+        usage,
+        view_formats: &[],
+    }
+}
+
+#[gpu_test]
+static IMPORTED_IOSURFACE_SHARES_CONTENTS: GpuTestConfiguration = GpuTestConfiguration::new()
+    .parameters(TestParameters::default().features(wgpu::Features::IOSURFACE))
+    .run_async(|ctx| async move {
+        let exported = ctx.device.create_iosurface_texture(&descriptor(
+            wgpu::TextureUsages::RENDER_ATTACHMENT | wgpu::TextureUsages::COPY_SRC,
+        ));
+
+        let mut encoder = ctx
+            .device
+            .create_command_encoder(&wgpu::CommandEncoderDescriptor::default());
+        encoder.begin_render_pass(&wgpu::RenderPassDescriptor {
+            label: None,
+            color_attachments: &[Some(wgpu::RenderPassColorAttachment {
+                view: &exported.create_view(&Default::default()),
+                resolve_target: None,
+                ops: wgpu::Operations {
+                    load: wgpu::LoadOp::Clear(wgpu::Color::BLUE),
+                    store: wgpu::StoreOp::Store,
+                },
+            })],
+            depth_stencil_attachment: None,
+            timestamp_writes: None,
+            occlusion_query_set: None,
+        });
+        ctx.queue.submit(Some(encoder.finish()));
+
+        let iosurface = exported.iosurface().unwrap();
+        assert!(!iosurface.is_null());
+
+        // A texture on the same surface sees what was rendered to the first one.
+        let imported = unsafe {
+            ctx.device
+                .import_iosurface(&descriptor(wgpu::TextureUsages::COPY_SRC), iosurface, 0)
+        };
+        let readback = ctx.device.create_buffer(&wgpu::BufferDescriptor {
+            label: None,
+            size: (SIZE.width * SIZE.height * 4) as wgpu::BufferAddress,
+            usage: wgpu::BufferUsages::COPY_DST | wgpu::BufferUsages::COPY_SRC,
+            mapped_at_creation: false,
+        });
+        let mut encoder = ctx
+            .device
+            .create_command_encoder(&wgpu::CommandEncoderDescriptor::default());
+        encoder.copy_texture_to_buffer(
+            imported.as_image_copy(),
+            wgpu::ImageCopyBuffer {
+                buffer: &readback,
+                layout: wgpu::ImageDataLayout {
+                    offset: 0,
+                    bytes_per_row: Some(SIZE.width * 4),
+                    rows_per_image: None,
+                },
+            },
+            SIZE,
+        );
+        ctx.queue.submit(Some(encoder.finish()));
+
+        let contents = wgpu::util::read_buffer(&ctx.device, &ctx.queue, &readback, ..)
+            .await
+            .unwrap();
+        for texel in contents.chunks_exact(4) {
+            assert_eq!(texel, [255, 0, 0, 255]);
+        }
+    });
+
+#[gpu_test]
+static IOSURFACE_TEXTURE_MUST_HAVE_ONE_MIP: GpuTestConfiguration = GpuTestConfiguration::new()
+    .parameters(TestParameters::default().features(wgpu::Features::IOSURFACE))
+    .run_sync(|ctx| {
+        wgpu_test::fail(&ctx.device, || {
+            ctx.device
+                .create_iosurface_texture(&wgpu::TextureDescriptor {
+                    mip_level_count: 2,
+                    ..descriptor(wgpu::TextureUsages::TEXTURE_BINDING)
+                })
+        });
+    });
+
+#[gpu_test]
+static ORDINARY_TEXTURE_HAS_NO_IOSURFACE: GpuTestConfiguration = GpuTestConfiguration::new()
+    .parameters(TestParameters::default().features(wgpu::Features::IOSURFACE))
+    .run_sync(|ctx| {
+        let texture = ctx
+            .device
+            .create_texture(&descriptor(wgpu::TextureUsages::TEXTURE_BINDING));
+        wgpu_test::fail(&ctx.device, || texture.iosurface());
+    });
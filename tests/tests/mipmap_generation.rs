@@ -0,0 +1,113 @@
+//! Tests for `wgpu::util::generate_mipmaps`.
+
+use wgpu::util::{generate_mipmaps, read_buffer};
+use wgpu_test::{gpu_test, GpuTestConfiguration, TestingContext};
+
+/// Creates a texture with two mip levels and `layers` layers of `width`x`height`
+/// texels, and writes `texels` to the first level of every layer.
+fn create_texture(
+    ctx: &TestingContext,
+    format: wgpu::TextureFormat,
+    width: u32,
+    height: u32,
+    layers: u32,
+    texels: &[u8],
+) -> wgpu::Texture {
+    let texture = ctx.device.create_texture(&wgpu::TextureDescriptor {
+        label: None,
+        size: wgpu::Extent3d {
+            width,
+            height,
+            depth_or_array_layers: layers,
+        },
+        mip_level_count: 2,
+        sample_count: 1,
+        dimension: wgpu::TextureDimension::D2,
+        format,
+        usage: wgpu::TextureUsages::TEXTURE_BINDING
+            | wgpu::TextureUsages::RENDER_ATTACHMENT
+            | wgpu::TextureUsages::COPY_SRC
+            | wgpu::TextureUsages::COPY_DST,
+        view_formats: &[],
+    });
+    let texel_size = format.block_copy_size(None).unwrap();
+    ctx.queue.write_texture(
+        texture.as_image_copy(),
+        &texels.repeat(layers as usize),
+        wgpu::ImageDataLayout {
+            offset: 0,
+            bytes_per_row: Some(width * texel_size),
+            rows_per_image: Some(height),
+        },
+        texture.size(),
+    );
+    texture
+}
+
+/// Generates the mip levels of `texture`, whose second level has to be a single
+/// texel, and returns that texel of every layer.
+async fn generate_and_read_texel(ctx: &TestingContext, texture: &wgpu::Texture) -> Vec<u8> {
+    let layers = texture.depth_or_array_layers();
+    let buffer = ctx.device.create_buffer(&wgpu::BufferDescriptor {
+        label: None,
+        size: (layers * wgpu::COPY_BYTES_PER_ROW_ALIGNMENT) as u64,
+        usage: wgpu::BufferUsages::COPY_SRC | wgpu::BufferUsages::COPY_DST,
+        mapped_at_creation: false,
+    });
+
+    let mut encoder = ctx
+        .device
+        .create_command_encoder(&wgpu::CommandEncoderDescriptor::default());
+    generate_mipmaps(&ctx.device, &mut encoder, texture);
+    encoder.copy_texture_to_buffer(
+        wgpu::ImageCopyTexture {
+            mip_level: 1,
+            ..texture.as_image_copy()
+        },
+        wgpu::ImageCopyBuffer {
+            buffer: &buffer,
+            layout: wgpu::ImageDataLayout {
+                offset: 0,
+                bytes_per_row: Some(wgpu::COPY_BYTES_PER_ROW_ALIGNMENT),
+                rows_per_image: Some(1),
+            },
+        },
+        wgpu::Extent3d {
+            width: 1,
+            height: 1,
+            depth_or_array_layers: layers,
+        },
+    );
+    ctx.queue.submit(Some(encoder.finish()));
+
+    let texel_size = texture.format().block_copy_size(None).unwrap() as usize;
+    read_buffer(&ctx.device, &ctx.queue, &buffer, ..)
+        .await
+        .unwrap()
+        .chunks(wgpu::COPY_BYTES_PER_ROW_ALIGNMENT as usize)
+        .flat_map(|row| row[..texel_size].to_vec())
+        .collect()
+}
+
+#[gpu_test]
+static GENERATE_MIPMAPS_NON_POWER_OF_TWO: GpuTestConfiguration = GpuTestConfiguration::new()
+    .run_async(|ctx| async move {
+        // Each texel of a level covers a third of every texel of a level three
+        // texels wide above it, so the last texel isn't skipped.
+        let texture = create_texture(&ctx, wgpu::TextureFormat::R8Unorm, 3, 1, 2, &[0, 0, 255]);
+        let texels = generate_and_read_texel(&ctx, &texture).await;
+        assert_eq!(texels, [85, 85]);
+    });
+
+#[gpu_test]
+static GENERATE_MIPMAPS_SRGB_CUBE: GpuTestConfiguration =
+    GpuTestConfiguration::new().run_async(|ctx| async move {
+        // Averaging in linear space gives a quarter of white, which is 137 in sRGB,
+        // rather than the 64 of averaging the encoded values.
+        // Each of the six layers of a cube map gets its own mip levels.
+        let mut texels = [0, 0, 0, 255].repeat(4);
+        texels[..3].copy_from_slice(&[255; 3]);
+        let texture = create_texture(&ctx, wgpu::TextureFormat::Rgba8UnormSrgb, 2, 2, 6, &texels);
+        let texels = generate_and_read_texel(&ctx, &texture).await;
+        assert_eq!(texels, [137, 137, 137, 255].repeat(6));
+    });
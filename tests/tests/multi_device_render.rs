@@ -0,0 +1,80 @@
+//! Tests for `util::MultiDeviceRenderer`.
+#![cfg(not(target_arch = "wasm32"))]
+
+use wgpu::util::{MultiDeviceRenderer, TileRenderDescriptor};
+use wgpu_test::{gpu_test, GpuTestConfiguration};
+
+const WIDTH: u32 = 100;
+const HEIGHT: u32 = 70;
+const TILE_SIZE: u32 = 32;
+
+#[gpu_test]
+static MULTI_DEVICE_RENDER_TILES: GpuTestConfiguration =
+    GpuTestConfiguration::new().run_sync(|ctx| {
+        // The same device twice still keeps two tiles in flight.
+        let mut renderer = MultiDeviceRenderer::new(
+            [(&ctx.device, &ctx.queue), (&ctx.device, &ctx.queue)],
+            |_device, _queue| (),
+        );
+        assert_eq!(renderer.device_count(), 2);
+
+        let mut last_done = 0;
+        let mut last_per_device = Vec::new();
+        let image = renderer
+            .render(
+                &TileRenderDescriptor {
+                    label: Some("tiles"),
+                    width: WIDTH,
+                    height: HEIGHT,
+                    tile_size: TILE_SIZE,
+                    format: wgpu::TextureFormat::Rgba8Unorm,
+                    usage: wgpu::TextureUsages::RENDER_ATTACHMENT,
+                },
+                |context, encoder| {
+                    // Clear every tile to its position, and the device it is rendered on.
+                    let view = context.target.create_view(&Default::default());
+                    encoder.begin_render_pass(&wgpu::RenderPassDescriptor {
+                        label: None,
+                        color_attachments: &[Some(wgpu::RenderPassColorAttachment {
+                            view: &view,
+                            resolve_target: None,
+                            ops: wgpu::Operations {
+                                load: wgpu::LoadOp::Clear(wgpu::Color {
+                                    r: (context.tile.x / TILE_SIZE) as f64 / 255.0,
+                                    g: (context.tile.y / TILE_SIZE) as f64 / 255.0,
+                                    b: context.device_index as f64 / 255.0,
+                                    a: 1.0,
+                                }),
+                                store: wgpu::StoreOp::Store,
+                            },
+                        })],
+                        depth_stencil_attachment: None,
+                        timestamp_writes: None,
+                        occlusion_query_set: None,
+                    });
+                },
+                |progress| {
+                    assert_eq!(progress.tile_count, 12);
+                    assert_eq!(progress.tiles_done, last_done + 1);
+                    assert_eq!(
+                        progress.tiles_per_device.iter().sum::<usize>(),
+                        progress.tiles_done
+                    );
+                    last_done = progress.tiles_done;
+                    last_per_device = progress.tiles_per_device.to_vec();
+                },
+            )
+            .unwrap();
+
+        assert_eq!(last_done, 12);
+        assert_eq!(last_per_device.len(), 2);
+        assert_eq!(image.len(), (WIDTH * HEIGHT * 4) as usize);
+        for (index, texel) in image.chunks_exact(4).enumerate() {
+            let x = index as u32 % WIDTH;
+            let y = index as u32 / WIDTH;
+            assert_eq!(texel[0] as u32, x / TILE_SIZE, "texel ({x}, {y})");
+            assert_eq!(texel[1] as u32, y / TILE_SIZE, "texel ({x}, {y})");
+            assert!(texel[2] < 2, "texel ({x}, {y})");
+            assert_eq!(texel[3], 255, "texel ({x}, {y})");
+        }
+    });
@@ -0,0 +1,71 @@
+//! Tests for additional queues, see `Limits::max_additional_queues`.
+
+use wgpu::{
+    BufferDescriptor, BufferUsages, CommandEncoderDescriptor, Limits, Maintain, MapMode,
+    QueueDescriptor,
+};
+
+use wgpu_test::{gpu_test, GpuTestConfiguration, TestParameters};
+
+const SIZE: u64 = 256;
+
+#[gpu_test]
+static COPY_ACROSS_QUEUES: GpuTestConfiguration = GpuTestConfiguration::new()
+    .parameters(TestParameters::default().limits(Limits {
+        max_additional_queues: 1,
+        ..Limits::downlevel_webgl2_defaults()
+    }))
+    .run_async(|ctx| async move {
+        let second_queue = ctx.device.create_queue(&QueueDescriptor {
+            label: Some("second queue"),
+        });
+
+        let make_buffer = |usage| {
+            ctx.device.create_buffer(&BufferDescriptor {
+                label: None,
+                size: SIZE,
+                usage,
+                mapped_at_creation: false,
+            })
+        };
+        let first = make_buffer(BufferUsages::COPY_SRC | BufferUsages::COPY_DST);
+        let second = make_buffer(BufferUsages::COPY_SRC | BufferUsages::COPY_DST);
+        let readback = make_buffer(BufferUsages::MAP_READ | BufferUsages::COPY_DST);
+
+        let data: Vec<u8> = (0..SIZE).map(|i| i as u8).collect();
+        ctx.queue.write_buffer(&first, 0, &data);
+
+        let mut encoder = ctx
+            .device
+            .create_command_encoder(&CommandEncoderDescriptor::default());
+        encoder.copy_buffer_to_buffer(&first, 0, &second, 0, SIZE);
+        ctx.queue.submit(Some(encoder.finish()));
+
+        // This submission has to wait for the one on the primary queue.
+        let mut encoder = ctx
+            .device
+            .create_command_encoder(&CommandEncoderDescriptor::default());
+        encoder.copy_buffer_to_buffer(&second, 0, &readback, 0, SIZE);
+        let index = second_queue.submit(Some(encoder.finish()));
+
+        readback.slice(..).map_async(MapMode::Read, Result::unwrap);
+        ctx.async_poll(Maintain::wait_for(index))
+            .await
+            .panic_on_timeout();
+
+        assert_eq!(&*readback.slice(..).get_mapped_range(), &data[..]);
+    });
+
+#[gpu_test]
+static QUEUE_REUSE: GpuTestConfiguration = GpuTestConfiguration::new()
+    .parameters(TestParameters::default().limits(Limits {
+        max_additional_queues: 1,
+        ..Limits::downlevel_webgl2_defaults()
+    }))
+    .run_sync(|ctx| {
+        // Dropping an additional queue gives it back to the device.
+        for _ in 0..2 {
+            let queue = ctx.device.create_queue(&QueueDescriptor { label: None });
+            queue.submit(None);
+        }
+    });
@@ -0,0 +1,154 @@
+//! Tests for `RenderPass::set_viewports`, see `Features::MULTI_VIEWPORT`.
+
+use wgpu_test::{fail, gpu_test, image, GpuTestConfiguration, TestParameters, TestingContext};
+
+const TEXTURE_WIDTH: u32 = 4;
+const TEXTURE_HEIGHT: u32 = 1;
+
+fn create_target(ctx: &TestingContext) -> wgpu::Texture {
+    ctx.device.create_texture(&wgpu::TextureDescriptor {
+        label: Some("Offscreen texture"),
+        size: wgpu::Extent3d {
+            width: TEXTURE_WIDTH,
+            height: TEXTURE_HEIGHT,
+            depth_or_array_layers: 1,
+        },
+        mip_level_count: 1,
+        sample_count: 1,
+        dimension: wgpu::TextureDimension::D2,
+        format: wgpu::TextureFormat::Rgba8Unorm,
+        usage: wgpu::TextureUsages::COPY_SRC | wgpu::TextureUsages::RENDER_ATTACHMENT,
+        view_formats: &[],
+    })
+}
+
+/// One viewport per pixel column of the target.
+fn pixel_viewports() -> Vec<wgpu::Viewport> {
+    (0..TEXTURE_WIDTH)
+        .map(|x| wgpu::Viewport {
+            x: x as f32,
+            y: 0.0,
+            width: 1.0,
+            height: TEXTURE_HEIGHT as f32,
+            min_depth: 0.0,
+            max_depth: 1.0,
+        })
+        .collect()
+}
+
+fn begin_pass<'a>(
+    encoder: &'a mut wgpu::CommandEncoder,
+    view: &'a wgpu::TextureView,
+) -> wgpu::RenderPass<'a> {
+    encoder.begin_render_pass(&wgpu::RenderPassDescriptor {
+        label: Some("Renderpass"),
+        color_attachments: &[Some(wgpu::RenderPassColorAttachment {
+            view,
+            resolve_target: None,
+            ops: wgpu::Operations {
+                load: wgpu::LoadOp::Clear(wgpu::Color::TRANSPARENT),
+                store: wgpu::StoreOp::Store,
+            },
+        })],
+        depth_stencil_attachment: None,
+        timestamp_writes: None,
+        occlusion_query_set: None,
+    })
+}
+
+#[gpu_test]
+static DRAW_TO_SELECTED_VIEWPORTS: GpuTestConfiguration = GpuTestConfiguration::new()
+    .parameters(TestParameters::default().features(wgpu::Features::MULTI_VIEWPORT))
+    .run_async(|ctx| async move {
+        let texture = create_target(&ctx);
+        let view = texture.create_view(&wgpu::TextureViewDescriptor::default());
+
+        let shader = ctx
+            .device
+            .create_shader_module(wgpu::include_wgsl!("viewport_index.wgsl"));
+        let pipeline = ctx
+            .device
+            .create_render_pipeline(&wgpu::RenderPipelineDescriptor {
+                label: Some("Pipeline"),
+                layout: None,
+                vertex: wgpu::VertexState {
+                    module: &shader,
+                    entry_point: "vs_main",
+                    compilation_options: Default::default(),
+                    buffers: &[],
+                },
+                primitive: wgpu::PrimitiveState::default(),
+                depth_stencil: None,
+                multisample: wgpu::MultisampleState::default(),
+                fragment: Some(wgpu::FragmentState {
+                    module: &shader,
+                    entry_point: "fs_main",
+                    compilation_options: Default::default(),
+                    targets: &[Some(wgpu::TextureFormat::Rgba8Unorm.into())],
+                }),
+                multiview: None,
+            });
+
+        let readback_buffer = image::ReadbackBuffers::new(&ctx.device, &texture);
+        let mut encoder = ctx
+            .device
+            .create_command_encoder(&wgpu::CommandEncoderDescriptor::default());
+        {
+            let mut pass = begin_pass(&mut encoder, &view);
+            pass.set_pipeline(&pipeline);
+            pass.set_viewports(&pixel_viewports());
+            // The scissor rectangle of the last viewport discards everything.
+            pass.set_scissor_rects(&[
+                wgpu::ScissorRect {
+                    x: 0,
+                    y: 0,
+                    width: TEXTURE_WIDTH,
+                    height: TEXTURE_HEIGHT,
+                },
+                wgpu::ScissorRect {
+                    x: 0,
+                    y: 0,
+                    width: TEXTURE_WIDTH,
+                    height: TEXTURE_HEIGHT,
+                },
+                wgpu::ScissorRect {
+                    x: 0,
+                    y: 0,
+                    width: TEXTURE_WIDTH,
+                    height: TEXTURE_HEIGHT,
+                },
+                wgpu::ScissorRect::default(),
+            ]);
+            // Instances 1 to 3 select the viewports of the last three columns.
+            pass.draw(0..3, 1..4);
+        }
+        readback_buffer.copy_from(&ctx.device, &mut encoder, &texture);
+        ctx.queue.submit(Some(encoder.finish()));
+
+        let mut expected = [255; (TEXTURE_WIDTH * TEXTURE_HEIGHT * 4) as usize];
+        expected[..4].fill(0);
+        expected[12..].fill(0);
+        readback_buffer
+            .assert_buffer_contents(&ctx, &expected)
+            .await;
+    });
+
+#[gpu_test]
+static SET_VIEWPORTS_REQUIRES_FEATURE: GpuTestConfiguration =
+    GpuTestConfiguration::new().run_sync(|ctx| {
+        let texture = create_target(&ctx);
+        let view = texture.create_view(&wgpu::TextureViewDescriptor::default());
+
+        let mut encoder = ctx
+            .device
+            .create_command_encoder(&wgpu::CommandEncoderDescriptor::default());
+        // A single viewport doesn't need the feature.
+        {
+            let mut pass = begin_pass(&mut encoder, &view);
+            pass.set_viewports(&pixel_viewports()[..1]);
+        }
+        fail(&ctx.device, || {
+            let mut pass = begin_pass(&mut encoder, &view);
+            pass.set_viewports(&pixel_viewports());
+        });
+    });
@@ -0,0 +1,23 @@
+struct VertexOutput {
+    @builtin(position) position: vec4<f32>,
+    @builtin(viewport_index) viewport_index: u32,
+}
+
+// Draws one triangle covering the whole viewport selected by the instance index.
+@vertex
+fn vs_main(
+    @builtin(vertex_index) vertex_index: u32,
+    @builtin(instance_index) instance_index: u32,
+) -> VertexOutput {
+    let x = i32(vertex_index) / 2;
+    let y = i32(vertex_index) & 1;
+    var out: VertexOutput;
+    out.position = vec4<f32>(f32(x) * 4.0 - 1.0, 1.0 - f32(y) * 4.0, 0.0, 1.0);
+    out.viewport_index = instance_index;
+    return out;
+}
+
+@fragment
+fn fs_main() -> @location(0) vec4<f32> {
+    return vec4<f32>(1.0);
+}
@@ -0,0 +1,100 @@
+//! Tests for `wgpu::util::PassGraph`.
+
+use wgpu::util::{PassGraph, PassKind, PassQueue, PassUsage};
+use wgpu_test::{gpu_test, GpuTestConfiguration};
+
+#[gpu_test]
+static PASS_GRAPH_SCHEDULES_INDEPENDENT_COMPUTE: GpuTestConfiguration = GpuTestConfiguration::new()
+    .run_sync(|ctx| {
+        let buffer = |label| {
+            ctx.device.create_buffer(&wgpu::BufferDescriptor {
+                label: Some(label),
+                size: 16,
+                usage: wgpu::BufferUsages::STORAGE | wgpu::BufferUsages::UNIFORM,
+                mapped_at_creation: false,
+            })
+        };
+        let particles = buffer("particles");
+        let lights = buffer("lights");
+        let texture = ctx.device.create_texture(&wgpu::TextureDescriptor {
+            label: Some("color"),
+            size: wgpu::Extent3d {
+                width: 1,
+                height: 1,
+                depth_or_array_layers: 1,
+            },
+            mip_level_count: 1,
+            sample_count: 1,
+            dimension: wgpu::TextureDimension::D2,
+            format: wgpu::TextureFormat::Rgba8Unorm,
+            usage: wgpu::TextureUsages::RENDER_ATTACHMENT,
+            view_formats: &[],
+        });
+
+        let layout_entries = [
+            wgpu::BindGroupLayoutEntry {
+                binding: 0,
+                visibility: wgpu::ShaderStages::COMPUTE,
+                ty: wgpu::BindingType::Buffer {
+                    ty: wgpu::BufferBindingType::Storage { read_only: false },
+                    has_dynamic_offset: false,
+                    min_binding_size: None,
+                },
+                count: None,
+            },
+            wgpu::BindGroupLayoutEntry {
+                binding: 1,
+                visibility: wgpu::ShaderStages::COMPUTE,
+                ty: wgpu::BindingType::Buffer {
+                    ty: wgpu::BufferBindingType::Uniform,
+                    has_dynamic_offset: false,
+                    min_binding_size: None,
+                },
+                count: None,
+            },
+        ];
+        let entries = [
+            wgpu::BindGroupEntry {
+                binding: 0,
+                resource: particles.as_entire_binding(),
+            },
+            wgpu::BindGroupEntry {
+                binding: 1,
+                resource: lights.as_entire_binding(),
+            },
+        ];
+
+        let mut graph = PassGraph::new();
+        let simulate = graph.add_pass(
+            PassKind::Compute,
+            PassUsage::new().bind_group(&layout_entries, &entries),
+        );
+        let shadows = graph.add_pass(
+            PassKind::Render,
+            PassUsage::new()
+                .read_buffer(&lights)
+                .write_texture(&texture),
+        );
+        let draw_particles = graph.add_pass(
+            PassKind::Render,
+            PassUsage::new()
+                .read_buffer(&particles)
+                .write_texture(&texture),
+        );
+        let schedule = graph.schedule();
+
+        // Reading the same buffer doesn't make passes depend on each other.
+        assert!(schedule.is_independent(simulate, shadows));
+        assert_eq!(schedule.dependencies(draw_particles), [simulate, shadows]);
+        assert_eq!(schedule.queue(simulate), PassQueue::AsyncCompute);
+        assert_eq!(
+            schedule.passes(PassQueue::Main).collect::<Vec<_>>(),
+            [shadows, draw_particles]
+        );
+        assert_eq!(
+            schedule
+                .cross_queue_dependencies(draw_particles)
+                .collect::<Vec<_>>(),
+            [simulate]
+        );
+    });
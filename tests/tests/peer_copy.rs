@@ -0,0 +1,88 @@
+//! Tests for `wgpu::util::copy_buffer_to_device` and `copy_texture_to_device`.
+
+use wgpu::util::{copy_buffer_to_device, copy_texture_to_device, DeviceExt, TextureDataOrder};
+use wgpu_test::{gpu_test, image, FailureCase, GpuTestConfiguration, TestParameters};
+
+#[gpu_test]
+static COPY_BUFFER_AND_TEXTURE_TO_DEVICE: GpuTestConfiguration = GpuTestConfiguration::new()
+    .parameters(
+        // Polling doesn't wait on the web.
+        TestParameters::default().skip(FailureCase::backend(wgpu::Backends::BROWSER_WEBGPU)),
+    )
+    .run_async(|ctx| async move {
+        let (device2, queue2) = ctx
+            .adapter
+            .request_device(&wgpu::DeviceDescriptor::default(), None)
+            .await
+            .unwrap();
+
+        let source = ctx
+            .device
+            .create_buffer_init(&wgpu::util::BufferInitDescriptor {
+                label: Some("source"),
+                contents: &[1, 2, 3, 4, 5, 6, 7, 8],
+                usage: wgpu::BufferUsages::COPY_SRC,
+            });
+        let destination = device2.create_buffer(&wgpu::BufferDescriptor {
+            label: Some("destination"),
+            size: 8,
+            usage: wgpu::BufferUsages::COPY_DST | wgpu::BufferUsages::MAP_READ,
+            mapped_at_creation: false,
+        });
+        copy_buffer_to_device(
+            &ctx.device,
+            &ctx.queue,
+            &source.slice(4..),
+            &queue2,
+            &destination,
+            4,
+        )
+        .unwrap();
+        queue2.submit(None);
+        let slice = destination.slice(..);
+        slice.map_async(wgpu::MapMode::Read, |_| ());
+        device2.poll(wgpu::Maintain::Wait);
+        assert_eq!(&*slice.get_mapped_range(), &[0, 0, 0, 0, 5, 6, 7, 8]);
+
+        let size = wgpu::Extent3d {
+            width: 2,
+            height: 2,
+            depth_or_array_layers: 1,
+        };
+        let texels: Vec<u8> = (0..16).collect();
+        let descriptor = wgpu::TextureDescriptor {
+            label: None,
+            size,
+            mip_level_count: 1,
+            sample_count: 1,
+            dimension: wgpu::TextureDimension::D2,
+            format: wgpu::TextureFormat::Rgba8Unorm,
+            usage: wgpu::TextureUsages::COPY_SRC | wgpu::TextureUsages::COPY_DST,
+            view_formats: &[],
+        };
+        // The other way around this time, to read the texture back on `ctx.device`.
+        let source = device2.create_texture_with_data(
+            &queue2,
+            &descriptor,
+            TextureDataOrder::LayerMajor,
+            &texels,
+        );
+        let destination = ctx.device.create_texture(&descriptor);
+        copy_texture_to_device(
+            &device2,
+            &queue2,
+            source.as_image_copy(),
+            &ctx.queue,
+            destination.as_image_copy(),
+            size,
+        )
+        .unwrap();
+
+        let readback_buffer = image::ReadbackBuffers::new(&ctx.device, &destination);
+        let mut encoder = ctx
+            .device
+            .create_command_encoder(&wgpu::CommandEncoderDescriptor::default());
+        readback_buffer.copy_from(&ctx.device, &mut encoder, &destination);
+        ctx.queue.submit(Some(encoder.finish()));
+        readback_buffer.assert_buffer_contents(&ctx, &texels).await;
+    });
@@ -0,0 +1,43 @@
+//! Tests for `Device::set_performance_hint` and
+//! `Instance::set_process_performance_hint`.
+
+use wgpu_test::{gpu_test, GpuTestConfiguration};
+
+#[gpu_test]
+static PERFORMANCE_HINT: GpuTestConfiguration = GpuTestConfiguration::new().run_sync(|ctx| {
+    for hint in [
+        wgpu::PerformanceHint::LowPower,
+        wgpu::PerformanceHint::Sustained,
+        wgpu::PerformanceHint::Burst,
+    ] {
+        ctx.device.set_performance_hint(hint);
+
+        // The device keeps working whatever the platform made of the hint.
+        let mut encoder = ctx
+            .device
+            .create_command_encoder(&wgpu::CommandEncoderDescriptor::default());
+        encoder.insert_debug_marker("hinted");
+        ctx.queue.submit(Some(encoder.finish()));
+        ctx.device.poll(wgpu::Maintain::wait());
+    }
+});
+
+#[gpu_test]
+static PROCESS_PERFORMANCE_HINT: GpuTestConfiguration =
+    GpuTestConfiguration::new().run_sync(|ctx| {
+        // End on the default, as the hint outlives the test.
+        for hint in [
+            wgpu::PerformanceHint::LowPower,
+            wgpu::PerformanceHint::Sustained,
+            wgpu::PerformanceHint::Burst,
+        ] {
+            ctx.instance.set_process_performance_hint(hint);
+
+            let mut encoder = ctx
+                .device
+                .create_command_encoder(&wgpu::CommandEncoderDescriptor::default());
+            encoder.insert_debug_marker("hinted");
+            ctx.queue.submit(Some(encoder.finish()));
+            ctx.device.poll(wgpu::Maintain::wait());
+        }
+    });
@@ -1,3 +1,4 @@
+use wgpu::util::DeviceExt;
 use wgpu_test::{fail, gpu_test, FailureCase, GpuTestConfiguration, TestParameters};
 
 // Create an invalid shader and a compute pipeline that uses it
@@ -19,6 +20,7 @@ static PIPELINE_DEFAULT_LAYOUT_BAD_MODULE: GpuTestConfiguration = GpuTestConfigu
                 .create_shader_module(wgpu::ShaderModuleDescriptor {
                     label: None,
                     source: wgpu::ShaderSource::Wgsl("not valid wgsl".into()),
+                    diagnostic_filters: &[],
                 });
 
             let pipeline = ctx
@@ -34,3 +36,76 @@ static PIPELINE_DEFAULT_LAYOUT_BAD_MODULE: GpuTestConfiguration = GpuTestConfigu
             pipeline.get_bind_group_layout(0);
         });
     });
+
+const DOUBLE_SHADER: &str = r#"
+@group(0) @binding(0)
+var<storage, read_write> values: array<u32>;
+
+@compute @workgroup_size(1)
+fn main(@builtin(global_invocation_id) id: vec3<u32>) {
+    values[id.x] = values[id.x] * 2u;
+}
+"#;
+
+// Pipelines compiled on a separate thread, because of a compile timeout, work
+// like any other.
+#[gpu_test]
+static PIPELINE_WITH_COMPILE_TIMEOUT: GpuTestConfiguration = GpuTestConfiguration::new()
+    .parameters(
+        TestParameters::default()
+            .test_features_limits()
+            .skip(FailureCase::backend(wgpu::Backends::BROWSER_WEBGPU)),
+    )
+    .run_async(|ctx| async move {
+        ctx.device
+            .set_pipeline_compile_timeout(Some(std::time::Duration::from_secs(60)));
+
+        let module = ctx
+            .device
+            .create_shader_module(wgpu::ShaderModuleDescriptor {
+                label: None,
+                source: wgpu::ShaderSource::Wgsl(DOUBLE_SHADER.into()),
+                diagnostic_filters: &[],
+            });
+        let pipeline = ctx
+            .device
+            .create_compute_pipeline(&wgpu::ComputePipelineDescriptor {
+                label: None,
+                layout: None,
+                module: &module,
+                entry_point: "main",
+                compilation_options: Default::default(),
+            });
+
+        let buffer = ctx
+            .device
+            .create_buffer_init(&wgpu::util::BufferInitDescriptor {
+                label: None,
+                contents: bytemuck::cast_slice(&[1u32, 2, 3, 4]),
+                usage: wgpu::BufferUsages::STORAGE | wgpu::BufferUsages::COPY_SRC,
+            });
+        let bind_group = ctx.device.create_bind_group(&wgpu::BindGroupDescriptor {
+            label: None,
+            layout: &pipeline.get_bind_group_layout(0),
+            entries: &[wgpu::BindGroupEntry {
+                binding: 0,
+                resource: buffer.as_entire_binding(),
+            }],
+        });
+
+        let mut encoder = ctx
+            .device
+            .create_command_encoder(&wgpu::CommandEncoderDescriptor::default());
+        {
+            let mut pass = encoder.begin_compute_pass(&wgpu::ComputePassDescriptor::default());
+            pass.set_pipeline(&pipeline);
+            pass.set_bind_group(0, &bind_group, &[]);
+            pass.dispatch_workgroups(4, 1, 1);
+        }
+        ctx.queue.submit(Some(encoder.finish()));
+
+        let contents = wgpu::util::read_buffer(&ctx.device, &ctx.queue, &buffer, ..)
+            .await
+            .unwrap();
+        assert_eq!(contents, bytemuck::cast_slice::<u32, u8>(&[2, 4, 6, 8]));
+    });
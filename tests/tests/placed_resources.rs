@@ -0,0 +1,85 @@
+//! Tests for buffers placed in a `MemoryHeap`.
+
+use wgpu::util::read_buffer;
+use wgpu_test::{fail, gpu_test, GpuTestConfiguration, TestParameters, TestingContext};
+
+const BUFFER_DESCRIPTOR: wgpu::BufferDescriptor<'static> = wgpu::BufferDescriptor {
+    label: Some("placed"),
+    size: 256,
+    usage: wgpu::BufferUsages::COPY_SRC.union(wgpu::BufferUsages::COPY_DST),
+    mapped_at_creation: false,
+};
+
+/// Creates a heap that fits exactly one buffer created from [`BUFFER_DESCRIPTOR`].
+fn create_heap(ctx: &TestingContext) -> (wgpu::MemoryHeap, wgpu::MemoryRequirements) {
+    let requirements = ctx.device.buffer_memory_requirements(&BUFFER_DESCRIPTOR);
+    let heap = ctx.device.create_memory_heap(&wgpu::MemoryHeapDescriptor {
+        label: Some("heap"),
+        size: requirements.size,
+    });
+    (heap, requirements)
+}
+
+#[gpu_test]
+static ALIASED_BUFFERS_TAKE_OVER_MEMORY: GpuTestConfiguration = GpuTestConfiguration::new()
+    .parameters(TestParameters::default().features(wgpu::Features::PLACED_RESOURCES))
+    .run_async(|ctx| async move {
+        let (heap, requirements) = create_heap(&ctx);
+        assert!(requirements.size >= BUFFER_DESCRIPTOR.size);
+        assert!(requirements.alignment > 0);
+
+        let first = ctx
+            .device
+            .create_placed_buffer(&heap, 0, &BUFFER_DESCRIPTOR);
+        let second = ctx
+            .device
+            .create_placed_buffer(&heap, 0, &BUFFER_DESCRIPTOR);
+
+        ctx.queue.write_buffer(&first, 0, &[0xAB; 256]);
+        let contents = read_buffer(&ctx.device, &ctx.queue, &first, ..)
+            .await
+            .unwrap();
+        assert_eq!(contents, [0xAB; 256]);
+
+        // Using the second buffer takes the memory over, and it starts out zeroed.
+        let contents = read_buffer(&ctx.device, &ctx.queue, &second, ..)
+            .await
+            .unwrap();
+        assert_eq!(contents, [0; 256]);
+
+        ctx.queue.write_buffer(&first, 0, &[0xCD; 256]);
+        let contents = read_buffer(&ctx.device, &ctx.queue, &first, ..)
+            .await
+            .unwrap();
+        assert_eq!(contents, [0xCD; 256]);
+    });
+
+#[gpu_test]
+static PLACED_BUFFER_OUT_OF_BOUNDS_IS_AN_ERROR: GpuTestConfiguration = GpuTestConfiguration::new()
+    .parameters(TestParameters::default().features(wgpu::Features::PLACED_RESOURCES))
+    .run_sync(|ctx| {
+        let (heap, requirements) = create_heap(&ctx);
+
+        fail(&ctx.device, || {
+            ctx.device
+                .create_placed_buffer(&heap, requirements.alignment, &BUFFER_DESCRIPTOR)
+        });
+    });
+
+#[gpu_test]
+static MAPPABLE_PLACED_BUFFER_IS_AN_ERROR: GpuTestConfiguration = GpuTestConfiguration::new()
+    .parameters(TestParameters::default().features(wgpu::Features::PLACED_RESOURCES))
+    .run_sync(|ctx| {
+        let (heap, _) = create_heap(&ctx);
+
+        fail(&ctx.device, || {
+            ctx.device.create_placed_buffer(
+                &heap,
+                0,
+                &wgpu::BufferDescriptor {
+                    usage: wgpu::BufferUsages::MAP_READ | wgpu::BufferUsages::COPY_DST,
+                    ..BUFFER_DESCRIPTOR
+                },
+            )
+        });
+    });
@@ -1,13 +1,18 @@
-use std::num::NonZeroU64;
+use std::{
+    num::NonZeroU64,
+    sync::{Arc, Mutex},
+    time::Duration,
+};
 
 use wgpu::{
+    util::{read_buffer, BufferInitDescriptor, DeviceExt},
     BindGroup, BindGroupDescriptor, BindGroupEntry, BindGroupLayout, BindGroupLayoutDescriptor,
     BindGroupLayoutEntry, BindingResource, BindingType, Buffer, BufferBindingType,
     BufferDescriptor, BufferUsages, CommandBuffer, CommandEncoderDescriptor, ComputePassDescriptor,
-    Maintain, ShaderStages,
+    DeviceFlags, Maintain, MapMode, ShaderStages,
 };
 
-use wgpu_test::{gpu_test, GpuTestConfiguration, TestingContext};
+use wgpu_test::{gpu_test, FailureCase, GpuTestConfiguration, TestParameters, TestingContext};
 
 struct DummyWorkData {
     _buffer: Buffer,
@@ -125,3 +130,264 @@ static WAIT_OUT_OF_ORDER: GpuTestConfiguration =
             .await
             .panic_on_timeout();
     });
+
+#[gpu_test]
+static WAIT_FOR_SUBMISSION: GpuTestConfiguration = GpuTestConfiguration::new()
+    .parameters(
+        TestParameters::default().skip(FailureCase::backend(wgpu::Backends::BROWSER_WEBGPU)),
+    )
+    .run_sync(|ctx| {
+        let data1 = DummyWorkData::new(&ctx);
+        let data2 = DummyWorkData::new(&ctx);
+
+        let index1 = ctx.queue.submit(Some(data1.cmd_buf));
+        let index2 = ctx.queue.submit(Some(data2.cmd_buf));
+        assert!(ctx
+            .device
+            .wait_for_submission(&index2, Duration::from_secs(60)));
+
+        // Earlier submissions are complete too.
+        assert!(ctx.queue.is_submission_complete(&index1));
+        assert!(ctx.queue.is_submission_complete(&index2));
+        assert!(ctx.device.wait_for_submission(&index1, Duration::ZERO));
+    });
+
+#[gpu_test]
+static SUBMIT_BATCHING: GpuTestConfiguration = GpuTestConfiguration::new()
+    .parameters(
+        TestParameters::default().skip(FailureCase::backend(wgpu::Backends::BROWSER_WEBGPU)),
+    )
+    .run_sync(|ctx| {
+        let data1 = DummyWorkData::new(&ctx);
+        let data2 = DummyWorkData::new(&ctx);
+        let data3 = DummyWorkData::new(&ctx);
+
+        ctx.queue.set_submit_batching(true);
+        let index1 = ctx.queue.submit(Some(data1.cmd_buf));
+        let index2 = ctx.queue.submit(Some(data2.cmd_buf));
+        // Nothing has been submitted yet, so nothing can be complete.
+        assert!(!ctx.queue.is_submission_complete(&index1));
+
+        ctx.queue.flush();
+        assert!(ctx
+            .device
+            .wait_for_submission(&index1, Duration::from_secs(60)));
+        // Both are part of the same submission.
+        assert!(ctx.queue.is_submission_complete(&index2));
+
+        // Turning batching off submits the rest.
+        let index3 = ctx.queue.submit(Some(data3.cmd_buf));
+        ctx.queue.set_submit_batching(false);
+        assert!(ctx
+            .device
+            .wait_for_submission(&index3, Duration::from_secs(60)));
+    });
+
+#[gpu_test]
+static SUBMIT_BATCHING_FLUSHED_BY_POLL: GpuTestConfiguration = GpuTestConfiguration::new()
+    .parameters(
+        TestParameters::default().skip(FailureCase::backend(wgpu::Backends::BROWSER_WEBGPU)),
+    )
+    .run_async(|ctx| async move {
+        let data = DummyWorkData::new(&ctx);
+
+        ctx.queue.set_submit_batching(true);
+        let index = ctx.queue.submit(Some(data.cmd_buf));
+        ctx.async_poll(Maintain::wait_for(index.clone()))
+            .await
+            .panic_on_timeout();
+        assert!(ctx.queue.is_submission_complete(&index));
+    });
+
+#[gpu_test]
+static SUBMIT_BATCHING_BEFORE_WRITE: GpuTestConfiguration = GpuTestConfiguration::new()
+    .parameters(
+        TestParameters::default().skip(FailureCase::backend(wgpu::Backends::BROWSER_WEBGPU)),
+    )
+    .run_async(|ctx| async move {
+        let src = ctx.device.create_buffer_init(&BufferInitDescriptor {
+            label: None,
+            contents: bytemuck::cast_slice(&[1u32, 2, 3, 4]),
+            usage: BufferUsages::COPY_SRC | BufferUsages::COPY_DST,
+        });
+        let dst = ctx.device.create_buffer(&BufferDescriptor {
+            label: None,
+            size: 16,
+            usage: BufferUsages::COPY_SRC | BufferUsages::COPY_DST,
+            mapped_at_creation: false,
+        });
+        let mut encoder = ctx
+            .device
+            .create_command_encoder(&CommandEncoderDescriptor::default());
+        encoder.copy_buffer_to_buffer(&src, 0, &dst, 0, 16);
+
+        ctx.queue.set_submit_batching(true);
+        ctx.queue.submit(Some(encoder.finish()));
+        // The write is made after the batched copy, even though the batch is
+        // only flushed afterwards.
+        ctx.queue
+            .write_buffer(&src, 0, bytemuck::cast_slice(&[5u32, 6, 7, 8]));
+        ctx.queue.flush();
+
+        let data = read_buffer(&ctx.device, &ctx.queue, &dst, ..)
+            .await
+            .unwrap();
+        assert_eq!(bytemuck::cast_slice::<u8, u32>(&data), [1, 2, 3, 4]);
+        let data = read_buffer(&ctx.device, &ctx.queue, &src, ..)
+            .await
+            .unwrap();
+        assert_eq!(bytemuck::cast_slice::<u8, u32>(&data), [5, 6, 7, 8]);
+    });
+
+#[gpu_test]
+static DETERMINISTIC_CALLBACK_ORDER: GpuTestConfiguration = GpuTestConfiguration::new()
+    .parameters(
+        TestParameters::default()
+            .device_flags(DeviceFlags::DETERMINISTIC_CALLBACKS)
+            // Device flags are ignored on WebGPU.
+            .skip(FailureCase::backend(wgpu::Backends::BROWSER_WEBGPU)),
+    )
+    .run_async(|ctx| async move {
+        let events = Arc::new(Mutex::new(Vec::new()));
+
+        let buffers: Vec<_> = (0..2)
+            .map(|_| {
+                ctx.device.create_buffer(&BufferDescriptor {
+                    label: None,
+                    size: 16,
+                    usage: BufferUsages::MAP_READ | BufferUsages::COPY_DST,
+                    mapped_at_creation: false,
+                })
+            })
+            .collect();
+
+        for (i, buffer) in buffers.iter().enumerate() {
+            let mut encoder = ctx
+                .device
+                .create_command_encoder(&CommandEncoderDescriptor::default());
+            encoder.clear_buffer(buffer, 0, None);
+            ctx.queue.submit(Some(encoder.finish()));
+
+            let map_events = events.clone();
+            buffer.slice(..).map_async(MapMode::Read, move |result| {
+                result.unwrap();
+                map_events.lock().unwrap().push(format!("map {i}"));
+            });
+            let done_events = events.clone();
+            ctx.queue.on_submitted_work_done(move || {
+                done_events.lock().unwrap().push(format!("done {i}"));
+            });
+        }
+
+        // Submitting never invokes callbacks, even if earlier work has completed.
+        ctx.queue.submit(None);
+        assert!(events.lock().unwrap().is_empty());
+
+        ctx.async_poll(Maintain::wait()).await.panic_on_timeout();
+
+        assert_eq!(
+            *events.lock().unwrap(),
+            ["map 0", "done 0", "map 1", "done 1"]
+        );
+    });
+
+#[gpu_test]
+static BACKGROUND_POLL_MAPS_BUFFER: GpuTestConfiguration = GpuTestConfiguration::new()
+    .parameters(
+        TestParameters::default()
+            .device_flags(DeviceFlags::BACKGROUND_POLL)
+            // There is no background thread on the web.
+            .skip(FailureCase::backend(wgpu::Backends::BROWSER_WEBGPU))
+            .skip(FailureCase::webgl2()),
+    )
+    .run_async(|ctx| async move {
+        let buffer = ctx.device.create_buffer(&BufferDescriptor {
+            label: None,
+            size: 16,
+            usage: BufferUsages::MAP_READ | BufferUsages::COPY_DST,
+            mapped_at_creation: false,
+        });
+
+        let mut encoder = ctx
+            .device
+            .create_command_encoder(&CommandEncoderDescriptor::default());
+        encoder.clear_buffer(&buffer, 0, None);
+        ctx.queue.submit(Some(encoder.finish()));
+
+        let (sender, receiver) = std::sync::mpsc::channel();
+        buffer.slice(..).map_async(MapMode::Read, move |result| {
+            sender.send(result).unwrap();
+        });
+
+        // The callback fires without the device being polled.
+        receiver
+            .recv_timeout(Duration::from_secs(5))
+            .expect("the buffer was never mapped")
+            .unwrap();
+        assert_eq!(*buffer.slice(..).get_mapped_range(), [0; 16]);
+    });
+
+#[gpu_test]
+static POLL_WORK_SEPARATELY: GpuTestConfiguration = GpuTestConfiguration::new()
+    .parameters(
+        TestParameters::default()
+            // The browser polls the device on its own.
+            .skip(FailureCase::backend(wgpu::Backends::BROWSER_WEBGPU)),
+    )
+    .run_async(|ctx| async move {
+        let buffer = ctx.device.create_buffer(&BufferDescriptor {
+            label: None,
+            size: 16,
+            usage: BufferUsages::MAP_READ | BufferUsages::COPY_DST,
+            mapped_at_creation: false,
+        });
+        let mut encoder = ctx
+            .device
+            .create_command_encoder(&CommandEncoderDescriptor::default());
+        encoder.clear_buffer(&buffer, 0, None);
+        ctx.queue.submit(Some(encoder.finish()));
+
+        let mapped = Arc::new(Mutex::new(false));
+        let callback_mapped = mapped.clone();
+        buffer.slice(..).map_async(MapMode::Read, move |result| {
+            result.unwrap();
+            *callback_mapped.lock().unwrap() = true;
+        });
+
+        // Retiring the submission alone doesn't map the buffer.
+        ctx.device
+            .poll_work(Maintain::wait(), wgpu::MaintainWork::SUBMISSIONS)
+            .panic_on_timeout();
+        assert!(!*mapped.lock().unwrap());
+
+        ctx.device
+            .poll_work(Maintain::Poll, wgpu::MaintainWork::MAPPINGS)
+            .panic_on_timeout();
+        assert!(*mapped.lock().unwrap());
+    });
+
+#[gpu_test]
+static SUBMISSION_TOKENS_INCREASE_ACROSS_DEVICES: GpuTestConfiguration =
+    GpuTestConfiguration::new().run_async(|ctx| async move {
+        let first = ctx.queue.submit([]);
+        let second = ctx.queue.submit([]);
+        assert!(first.token() < second.token());
+        assert!(first < second);
+        assert_eq!(first.token(), first.clone().token());
+
+        // A recreated device continues where the previous one stopped.
+        let (device, queue) = ctx
+            .adapter
+            .request_device(&Default::default(), None)
+            .await
+            .unwrap();
+        let third = queue.submit([]);
+        assert!(second.token() < third.token());
+        drop((queue, device));
+
+        let stored = third.token().get();
+        assert_eq!(wgpu::SubmissionToken::from_raw(stored), Some(third.token()));
+        assert_eq!(wgpu::SubmissionToken::from_raw(0), None);
+
+        ctx.async_poll(Maintain::wait()).await.panic_on_timeout();
+    });
@@ -44,6 +44,7 @@ async fn partial_update_test(ctx: TestingContext) {
         .create_shader_module(wgpu::ShaderModuleDescriptor {
             label: Some("shader"),
             source: wgpu::ShaderSource::Wgsl(SHADER.into()),
+            diagnostic_filters: &[],
         });
 
     let bgl = ctx
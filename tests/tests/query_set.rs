@@ -21,3 +21,83 @@ static DROP_FAILED_TIMESTAMP_QUERY_SET: GpuTestConfiguration = GpuTestConfigurat
 
         assert!(pollster::block_on(ctx.device.pop_error_scope()).is_some());
     });
+
+#[gpu_test]
+static READ_TIMESTAMP_RESULTS: GpuTestConfiguration = GpuTestConfiguration::new()
+    .parameters(TestParameters::default().features(wgpu::Features::TIMESTAMP_QUERY))
+    .run_async(|ctx| async move {
+        let query_set = ctx.device.create_query_set(&wgpu::QuerySetDescriptor {
+            label: None,
+            ty: wgpu::QueryType::Timestamp,
+            count: 2,
+        });
+
+        // Reading twice reuses the buffers of the first read.
+        for _ in 0..2 {
+            let mut encoder = ctx
+                .device
+                .create_command_encoder(&wgpu::CommandEncoderDescriptor::default());
+            encoder.begin_compute_pass(&wgpu::ComputePassDescriptor {
+                label: None,
+                timestamp_writes: Some(wgpu::ComputePassTimestampWrites {
+                    query_set: &query_set,
+                    beginning_of_pass_write_index: Some(0),
+                    end_of_pass_write_index: Some(1),
+                }),
+            });
+            ctx.queue.submit(Some(encoder.finish()));
+
+            let results = query_set.read_results_async(&ctx.device, &ctx.queue, 0..2);
+            ctx.async_poll(wgpu::Maintain::wait())
+                .await
+                .panic_on_timeout();
+            let wgpu::QueryResults::Timestamp(timestamps) = results.await.unwrap() else {
+                panic!("timestamp queries should have timestamp results");
+            };
+            assert_eq!(timestamps.len(), 2);
+            assert!(timestamps[0] <= timestamps[1]);
+        }
+    });
+
+#[gpu_test]
+static NORMALIZE_TIMESTAMPS: GpuTestConfiguration = GpuTestConfiguration::new()
+    .parameters(TestParameters::default().features(wgpu::Features::TIMESTAMP_QUERY))
+    .run_async(|ctx| async move {
+        let query_set = ctx.device.create_query_set(&wgpu::QuerySetDescriptor {
+            label: None,
+            ty: wgpu::QueryType::Timestamp,
+            count: 2,
+        });
+        let mut normalizer = wgpu::util::TimestampNormalizer::new(&ctx.queue);
+
+        let mut previous = f64::MIN;
+        for _ in 0..3 {
+            let mut encoder = ctx
+                .device
+                .create_command_encoder(&wgpu::CommandEncoderDescriptor::default());
+            encoder.begin_compute_pass(&wgpu::ComputePassDescriptor {
+                label: None,
+                timestamp_writes: Some(wgpu::ComputePassTimestampWrites {
+                    query_set: &query_set,
+                    beginning_of_pass_write_index: Some(0),
+                    end_of_pass_write_index: Some(1),
+                }),
+            });
+            ctx.queue.submit(Some(encoder.finish()));
+
+            let results = query_set.read_results_async(&ctx.device, &ctx.queue, 0..2);
+            ctx.async_poll(wgpu::Maintain::wait())
+                .await
+                .panic_on_timeout();
+            let wgpu::QueryResults::Timestamp(mut timestamps) = results.await.unwrap() else {
+                panic!("timestamp queries should have timestamp results");
+            };
+
+            normalizer.recalibrate(&ctx.queue);
+            normalizer.normalize(&mut timestamps);
+            assert!(normalizer.period().is_finite());
+            // Normalized timestamps never go back, across frames too.
+            assert!(previous <= timestamps[0] && timestamps[0] <= timestamps[1]);
+            previous = timestamps[1];
+        }
+    });
@@ -1,6 +1,6 @@
 //! Tests for buffer copy validation.
 
-use wgpu_test::{fail, gpu_test, GpuTestConfiguration};
+use wgpu_test::{fail, gpu_test, GpuTestConfiguration, TestParameters};
 
 #[gpu_test]
 static QUEUE_WRITE_TEXTURE_OVERFLOW: GpuTestConfiguration =
@@ -45,3 +45,48 @@ static QUEUE_WRITE_TEXTURE_OVERFLOW: GpuTestConfiguration =
             );
         });
     });
+
+#[gpu_test]
+static QUEUE_WRITE_BUFFER_AFTER_UNMAP: GpuTestConfiguration = GpuTestConfiguration::new()
+    .parameters(TestParameters::default().device_flags(wgpu::DeviceFlags::TRANSFER_QUEUE))
+    .run_async(|ctx| async move {
+        // Uploads made after other pending writes to the same buffer must land
+        // after them, even if the device uses a dedicated transfer queue.
+        const SIZE: u64 = 256;
+
+        let buffer = ctx.device.create_buffer(&wgpu::BufferDescriptor {
+            label: None,
+            size: SIZE,
+            usage: wgpu::BufferUsages::COPY_SRC | wgpu::BufferUsages::COPY_DST,
+            mapped_at_creation: true,
+        });
+        buffer.slice(..).get_mapped_range_mut().fill(1);
+        buffer.unmap();
+
+        ctx.queue.write_buffer(&buffer, 0, &[2; SIZE as usize]);
+
+        let readback = ctx.device.create_buffer(&wgpu::BufferDescriptor {
+            label: None,
+            size: SIZE,
+            usage: wgpu::BufferUsages::MAP_READ | wgpu::BufferUsages::COPY_DST,
+            mapped_at_creation: false,
+        });
+        let mut encoder = ctx
+            .device
+            .create_command_encoder(&wgpu::CommandEncoderDescriptor::default());
+        encoder.copy_buffer_to_buffer(&buffer, 0, &readback, 0, SIZE);
+        ctx.queue.submit(Some(encoder.finish()));
+
+        readback
+            .slice(..)
+            .map_async(wgpu::MapMode::Read, Result::unwrap);
+        ctx.async_poll(wgpu::Maintain::wait())
+            .await
+            .panic_on_timeout();
+
+        assert!(readback
+            .slice(..)
+            .get_mapped_range()
+            .iter()
+            .all(|&byte| byte == 2));
+    });
@@ -0,0 +1,24 @@
+//! Tests for `wgpu::util::read_buffer`.
+
+use wgpu::util::{read_buffer, DeviceExt};
+use wgpu_test::{gpu_test, GpuTestConfiguration};
+
+#[gpu_test]
+static READ_BUFFER_RANGES: GpuTestConfiguration =
+    GpuTestConfiguration::new().run_async(|ctx| async move {
+        let buffer = ctx
+            .device
+            .create_buffer_init(&wgpu::util::BufferInitDescriptor {
+                label: None,
+                contents: &[1, 2, 3, 4, 5, 6, 7, 8, 9, 10, 11, 12],
+                usage: wgpu::BufferUsages::COPY_SRC | wgpu::BufferUsages::COPY_DST,
+            });
+
+        let all = read_buffer(&ctx.device, &ctx.queue, &buffer, ..).await;
+        assert_eq!(all.unwrap(), [1, 2, 3, 4, 5, 6, 7, 8, 9, 10, 11, 12]);
+
+        // Writes submitted before the read are visible to it.
+        ctx.queue.write_buffer(&buffer, 4, &[0; 4]);
+        let middle = read_buffer(&ctx.device, &ctx.queue, &buffer, 4..12).await;
+        assert_eq!(middle.unwrap(), [0, 0, 0, 0, 9, 10, 11, 12]);
+    });
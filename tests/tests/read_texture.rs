@@ -0,0 +1,60 @@
+//! Tests for `wgpu::util::read_texture`.
+
+use wgpu::util::{read_texture, DeviceExt};
+use wgpu_test::{gpu_test, GpuTestConfiguration};
+
+#[gpu_test]
+static READ_TEXTURE_REGION_IN_TILES: GpuTestConfiguration =
+    GpuTestConfiguration::new().run_async(|ctx| async move {
+        let (width, height, layers) = (300, 70, 2);
+        let texel = |x: u32, y: u32, layer: u32| [x as u8, y as u8, layer as u8, (x >> 8) as u8];
+        let contents: Vec<u8> = (0..layers)
+            .flat_map(|layer| (0..height).flat_map(move |y| (0..width).map(move |x| (x, y, layer))))
+            .flat_map(|(x, y, layer)| texel(x, y, layer))
+            .collect();
+        let texture = ctx.device.create_texture_with_data(
+            &ctx.queue,
+            &wgpu::TextureDescriptor {
+                label: None,
+                size: wgpu::Extent3d {
+                    width,
+                    height,
+                    depth_or_array_layers: layers,
+                },
+                mip_level_count: 1,
+                sample_count: 1,
+                dimension: wgpu::TextureDimension::D2,
+                format: wgpu::TextureFormat::Rgba8Unorm,
+                usage: wgpu::TextureUsages::COPY_SRC | wgpu::TextureUsages::COPY_DST,
+                view_formats: &[],
+            },
+            wgpu::util::TextureDataOrder::LayerMajor,
+            &contents,
+        );
+
+        let origin = wgpu::Origin3d { x: 5, y: 3, z: 1 };
+        let size = wgpu::Extent3d {
+            width: 260,
+            height: 40,
+            depth_or_array_layers: 1,
+        };
+        let expected: Vec<u8> = (0..size.height)
+            .flat_map(|y| (0..size.width).map(move |x| (x, y)))
+            .flat_map(|(x, y)| texel(origin.x + x, origin.y + y, origin.z))
+            .collect();
+
+        // Whole rows fit in the staging buffer at once.
+        let region = wgpu::ImageCopyTexture {
+            texture: &texture,
+            mip_level: 0,
+            origin,
+            aspect: wgpu::TextureAspect::All,
+        };
+        let texels = read_texture(&ctx.device, &ctx.queue, region, size, 1 << 20).await;
+        assert!(texels.unwrap() == expected);
+
+        // A budget of 1 KiB splits rows of 1040 bytes into tiles of 256 and 4
+        // texels, one row at a time.
+        let texels = read_texture(&ctx.device, &ctx.queue, region, size, 1024).await;
+        assert!(texels.unwrap() == expected);
+    });
@@ -0,0 +1,170 @@
+//! Tests for `wgpu::util::ResourceRegistry`.
+
+use wgpu::util::{read_buffer, RegistryBindGroupEntry, RegistryBindingResource, ResourceRegistry};
+use wgpu_test::{gpu_test, GpuTestConfiguration};
+
+#[gpu_test]
+static REBUILD_ON_NEW_DEVICE: GpuTestConfiguration =
+    GpuTestConfiguration::new().run_async(|ctx| async move {
+        let mut registry = ResourceRegistry::new();
+        let buffer = registry.create_buffer_with_content(
+            &ctx.device,
+            &ctx.queue,
+            &wgpu::BufferDescriptor {
+                label: Some("uniforms"),
+                size: 16,
+                usage: wgpu::BufferUsages::UNIFORM
+                    | wgpu::BufferUsages::COPY_SRC
+                    | wgpu::BufferUsages::COPY_DST,
+                mapped_at_creation: false,
+            },
+            |queue, buffer| queue.write_buffer(buffer, 0, &[7; 16]),
+        );
+        let texture = registry.create_texture(
+            &ctx.device,
+            &wgpu::TextureDescriptor {
+                label: None,
+                size: wgpu::Extent3d {
+                    width: 4,
+                    height: 4,
+                    depth_or_array_layers: 1,
+                },
+                mip_level_count: 1,
+                sample_count: 1,
+                dimension: wgpu::TextureDimension::D2,
+                format: wgpu::TextureFormat::Rgba8Unorm,
+                usage: wgpu::TextureUsages::TEXTURE_BINDING,
+                view_formats: &[],
+            },
+        );
+        let view = registry.create_texture_view(texture, &Default::default());
+        let sampler = registry.create_sampler(&ctx.device, &Default::default());
+        let layout = registry.create_bind_group_layout(
+            &ctx.device,
+            &wgpu::BindGroupLayoutDescriptor {
+                label: None,
+                entries: &[
+                    wgpu::BindGroupLayoutEntry {
+                        binding: 0,
+                        visibility: wgpu::ShaderStages::FRAGMENT,
+                        ty: wgpu::BindingType::Buffer {
+                            ty: wgpu::BufferBindingType::Uniform,
+                            has_dynamic_offset: false,
+                            min_binding_size: None,
+                        },
+                        count: None,
+                    },
+                    wgpu::BindGroupLayoutEntry {
+                        binding: 1,
+                        visibility: wgpu::ShaderStages::FRAGMENT,
+                        ty: wgpu::BindingType::Texture {
+                            sample_type: wgpu::TextureSampleType::Float { filterable: true },
+                            view_dimension: wgpu::TextureViewDimension::D2,
+                            multisampled: false,
+                        },
+                        count: None,
+                    },
+                    wgpu::BindGroupLayoutEntry {
+                        binding: 2,
+                        visibility: wgpu::ShaderStages::FRAGMENT,
+                        ty: wgpu::BindingType::Sampler(wgpu::SamplerBindingType::Filtering),
+                        count: None,
+                    },
+                ],
+            },
+        );
+        let entries = [
+            RegistryBindGroupEntry {
+                binding: 0,
+                resource: RegistryBindingResource::Buffer {
+                    buffer,
+                    offset: 0,
+                    size: None,
+                },
+            },
+            RegistryBindGroupEntry {
+                binding: 1,
+                resource: RegistryBindingResource::TextureView(view),
+            },
+            RegistryBindGroupEntry {
+                binding: 2,
+                resource: RegistryBindingResource::Sampler(sampler),
+            },
+        ];
+        registry.create_bind_group(&ctx.device, None, layout, &entries);
+        registry.create_pipeline_layout(&ctx.device, None, &[layout], &[]);
+
+        let (device2, queue2) = ctx
+            .adapter
+            .request_device(&wgpu::DeviceDescriptor::default(), None)
+            .await
+            .unwrap();
+        registry.rebuild_on(&device2, &queue2);
+
+        // The buffer was created again and refilled by its content callback.
+        let contents = read_buffer(&device2, &queue2, registry.buffer(buffer), ..).await;
+        assert_eq!(contents.unwrap(), [7; 16]);
+    });
+
+#[gpu_test]
+static RESTORE_SNAPSHOT_DEDUPLICATES: GpuTestConfiguration =
+    GpuTestConfiguration::new().run_sync(|ctx| {
+        let mut registry = ResourceRegistry::new();
+        let buffer = registry.create_buffer(
+            &ctx.device,
+            &wgpu::BufferDescriptor {
+                label: None,
+                size: 16,
+                usage: wgpu::BufferUsages::UNIFORM,
+                mapped_at_creation: false,
+            },
+        );
+        let sampler_a = registry.create_sampler(&ctx.device, &Default::default());
+        let sampler_b = registry.create_sampler(&ctx.device, &Default::default());
+        let layout_desc = wgpu::BindGroupLayoutDescriptor {
+            label: None,
+            entries: &[wgpu::BindGroupLayoutEntry {
+                binding: 0,
+                visibility: wgpu::ShaderStages::VERTEX,
+                ty: wgpu::BindingType::Buffer {
+                    ty: wgpu::BufferBindingType::Uniform,
+                    has_dynamic_offset: false,
+                    min_binding_size: None,
+                },
+                count: None,
+            }],
+        };
+        let layout_a = registry.create_bind_group_layout(&ctx.device, &layout_desc);
+        let layout_b = registry.create_bind_group_layout(&ctx.device, &layout_desc);
+        let entries = [RegistryBindGroupEntry {
+            binding: 0,
+            resource: RegistryBindingResource::Buffer {
+                buffer,
+                offset: 0,
+                size: None,
+            },
+        }];
+        let bind_group = registry.create_bind_group(&ctx.device, None, layout_b, &entries);
+        registry.create_pipeline_layout(&ctx.device, None, &[layout_a], &[]);
+        assert_ne!(
+            registry.sampler(sampler_a).global_id(),
+            registry.sampler(sampler_b).global_id()
+        );
+
+        let snapshot = registry.snapshot();
+        let old_bind_group = registry.bind_group(bind_group).global_id();
+        // Created after the snapshot, so dropped by the restore.
+        registry.create_sampler(&ctx.device, &Default::default());
+
+        registry.restore(&ctx.device, &snapshot);
+
+        assert_ne!(registry.bind_group(bind_group).global_id(), old_bind_group);
+        assert_eq!(
+            registry.sampler(sampler_a).global_id(),
+            registry.sampler(sampler_b).global_id()
+        );
+        assert_eq!(
+            registry.bind_group_layout(layout_a).global_id(),
+            registry.bind_group_layout(layout_b).global_id()
+        );
+    });
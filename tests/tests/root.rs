@@ -7,38 +7,92 @@ mod regression {
 
 mod bgra8unorm_storage;
 mod bind_group_layout_dedup;
+mod blit_texture;
 mod buffer;
+mod buffer_arena;
 mod buffer_copy;
+mod buffer_device_address;
 mod buffer_usages;
 mod clear_texture;
+mod color_space;
+mod compatibility_mode;
+mod constant_tables;
 mod create_surface_error;
+mod cuda_interop;
+mod d3d11on12;
+mod debug_overlay;
 mod device;
+mod dmabuf_export;
+mod draw_args_converter;
+mod draw_batcher;
 mod encoder;
+mod extended_dynamic_state;
+mod external_image_copier;
+mod external_memory;
+mod external_semaphore;
 mod external_texture;
+mod fill_buffer;
 mod float32_filterable;
+mod fragment_interlock;
+mod gl_interop;
+mod inline_bind_groups;
 mod instance;
+mod int64_atomics;
+mod iosurface;
 mod life_cycle;
 mod mem_leaks;
+mod mipmap_generation;
+mod multi_device_render;
+mod multi_queue;
+mod multi_viewport;
 mod nv12_texture;
 mod occlusion_query;
 mod partially_bounded_arrays;
+mod pass_graph;
+mod peer_copy;
+mod performance_hint;
 mod pipeline;
+mod placed_resources;
 mod poll;
 mod push_constants;
 mod query_set;
 mod queue_transfer;
+mod read_buffer;
+mod read_texture;
 mod resource_descriptor_accessor;
 mod resource_error;
+mod resource_registry;
+mod sampler_border_color;
+mod sampler_lod_bias;
+mod sampler_reduction;
 mod scissor_tests;
+mod separate_stencil_state;
 mod shader;
+mod shader_diagnostics;
 mod shader_primitive_index;
 mod shader_view_format;
+mod sparse_buffer;
+mod sparse_texture;
+mod sprite;
+mod storage_buffer_binding_array;
 mod subgroup_operations;
+mod submit_middleware;
+mod surface_capabilities_headless;
 mod texture_bounds;
+mod texture_expiry;
+mod texture_format_features;
+mod texture_reinterpretation;
+mod texture_transcode;
 mod texture_view_creation;
+mod texture_view_swizzle;
+mod timeline_semaphore;
 mod transfer;
+mod transient_attachment;
+mod unchecked_passes;
+mod validation_level;
 mod vertex_indices;
 mod write_texture;
+mod ycbcr_texture;
 mod zero_init_texture_after_discard;
 
 wgpu_test::gpu_test_main!();
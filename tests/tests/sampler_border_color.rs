@@ -0,0 +1,40 @@
+//! Tests for samplers with a `SamplerBorderColor::Custom` border.
+
+use wgpu_test::{fail, gpu_test, GpuTestConfiguration, TestParameters};
+
+fn custom_border_sampler(color: [f32; 4]) -> wgpu::SamplerDescriptor<'static> {
+    wgpu::SamplerDescriptor {
+        address_mode_u: wgpu::AddressMode::ClampToBorder,
+        address_mode_v: wgpu::AddressMode::ClampToBorder,
+        border_color: Some(wgpu::SamplerBorderColor::Custom(color)),
+        ..Default::default()
+    }
+}
+
+#[gpu_test]
+static SAMPLER_CUSTOM_BORDER_COLOR: GpuTestConfiguration = GpuTestConfiguration::new()
+    .parameters(TestParameters::default().features(
+        wgpu::Features::ADDRESS_MODE_CLAMP_TO_BORDER
+            | wgpu::Features::ADDRESS_MODE_CLAMP_TO_CUSTOM_BORDER,
+    ))
+    .run_sync(|ctx| {
+        ctx.device
+            .create_sampler(&custom_border_sampler([0.25, 0.5, 0.75, 1.0]));
+        ctx.device.create_sampler(&wgpu::SamplerDescriptor {
+            compare: Some(wgpu::CompareFunction::LessEqual),
+            ..custom_border_sampler([1.0; 4])
+        });
+    });
+
+#[gpu_test]
+static SAMPLER_CUSTOM_BORDER_COLOR_WITHOUT_FEATURE: GpuTestConfiguration =
+    GpuTestConfiguration::new()
+        .parameters(
+            TestParameters::default().features(wgpu::Features::ADDRESS_MODE_CLAMP_TO_BORDER),
+        )
+        .run_sync(|ctx| {
+            fail(&ctx.device, || {
+                ctx.device
+                    .create_sampler(&custom_border_sampler([0.25, 0.5, 0.75, 1.0]));
+            });
+        });
@@ -0,0 +1,40 @@
+//! Tests for samplers with a `lod_bias`.
+
+use wgpu_test::{fail, gpu_test, GpuTestConfiguration, TestParameters};
+
+#[gpu_test]
+static SAMPLER_LOD_BIAS: GpuTestConfiguration = GpuTestConfiguration::new()
+    .parameters(TestParameters::default().downlevel_flags(wgpu::DownlevelFlags::SAMPLER_LOD_BIAS))
+    .run_sync(|ctx| {
+        // Biases beyond the range of the adapter are clamped.
+        for lod_bias in [-1000.0, -1.5, 0.5, 1000.0] {
+            ctx.device.create_sampler(&wgpu::SamplerDescriptor {
+                lod_bias,
+                ..Default::default()
+            });
+        }
+        fail(&ctx.device, || {
+            ctx.device.create_sampler(&wgpu::SamplerDescriptor {
+                lod_bias: f32::NAN,
+                ..Default::default()
+            });
+        });
+    });
+
+#[gpu_test]
+static SAMPLER_LOD_BIAS_UNSUPPORTED: GpuTestConfiguration =
+    GpuTestConfiguration::new().run_sync(|ctx| {
+        if ctx
+            .adapter_downlevel_capabilities
+            .flags
+            .contains(wgpu::DownlevelFlags::SAMPLER_LOD_BIAS)
+        {
+            return;
+        }
+        fail(&ctx.device, || {
+            ctx.device.create_sampler(&wgpu::SamplerDescriptor {
+                lod_bias: 1.0,
+                ..Default::default()
+            });
+        });
+    });
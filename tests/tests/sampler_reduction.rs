@@ -0,0 +1,149 @@
+//! Tests for samplers with a `SamplerReductionMode`.
+
+use wgpu::util::{read_buffer, DeviceExt};
+use wgpu::SamplerReductionMode;
+use wgpu_test::{fail, gpu_test, GpuTestConfiguration, TestParameters};
+
+const SHADER: &str = "
+@group(0) @binding(0) var texture: texture_2d<f32>;
+@group(0) @binding(1) var texture_sampler: sampler;
+@group(0) @binding(2) var<storage, read_write> texel: u32;
+
+@compute @workgroup_size(1)
+fn main() {
+    // The center of the texture, between its four texels.
+    let value = textureSampleLevel(texture, texture_sampler, vec2<f32>(0.5), 0.0);
+    texel = u32(round(value.r * 255.0));
+}
+";
+
+fn parameters() -> TestParameters {
+    TestParameters::default()
+        .features(wgpu::Features::SAMPLER_REDUCTION_MODE)
+        .downlevel_flags(wgpu::DownlevelFlags::COMPUTE_SHADERS)
+        .limits(wgpu::Limits::downlevel_defaults())
+}
+
+fn linear_sampler(reduction_mode: SamplerReductionMode) -> wgpu::SamplerDescriptor<'static> {
+    wgpu::SamplerDescriptor {
+        mag_filter: wgpu::FilterMode::Linear,
+        min_filter: wgpu::FilterMode::Linear,
+        reduction_mode,
+        ..Default::default()
+    }
+}
+
+#[gpu_test]
+static SAMPLER_REDUCTION_WITHOUT_FEATURE: GpuTestConfiguration = GpuTestConfiguration::new()
+    .parameters(TestParameters::default())
+    .run_sync(|ctx| {
+        ctx.device
+            .create_sampler(&linear_sampler(SamplerReductionMode::WeightedAverage));
+        fail(&ctx.device, || {
+            ctx.device
+                .create_sampler(&linear_sampler(SamplerReductionMode::Min));
+        });
+    });
+
+#[gpu_test]
+static SAMPLER_REDUCTION_WITH_COMPARISON: GpuTestConfiguration = GpuTestConfiguration::new()
+    .parameters(parameters())
+    .run_sync(|ctx| {
+        fail(&ctx.device, || {
+            ctx.device.create_sampler(&wgpu::SamplerDescriptor {
+                compare: Some(wgpu::CompareFunction::LessEqual),
+                ..linear_sampler(SamplerReductionMode::Max)
+            });
+        });
+    });
+
+#[gpu_test]
+static SAMPLER_REDUCTION_SAMPLE: GpuTestConfiguration = GpuTestConfiguration::new()
+    .parameters(parameters())
+    .run_async(|ctx| async move {
+        let module = ctx
+            .device
+            .create_shader_module(wgpu::ShaderModuleDescriptor {
+                label: None,
+                source: wgpu::ShaderSource::Wgsl(SHADER.into()),
+                diagnostic_filters: &[],
+            });
+        let pipeline = ctx
+            .device
+            .create_compute_pipeline(&wgpu::ComputePipelineDescriptor {
+                label: None,
+                layout: None,
+                module: &module,
+                entry_point: "main",
+                compilation_options: Default::default(),
+            });
+        let buffer = ctx.device.create_buffer(&wgpu::BufferDescriptor {
+            label: None,
+            size: 4,
+            usage: wgpu::BufferUsages::STORAGE | wgpu::BufferUsages::COPY_SRC,
+            mapped_at_creation: false,
+        });
+
+        let texture = ctx.device.create_texture_with_data(
+            &ctx.queue,
+            &wgpu::TextureDescriptor {
+                label: None,
+                size: wgpu::Extent3d {
+                    width: 2,
+                    height: 2,
+                    depth_or_array_layers: 1,
+                },
+                mip_level_count: 1,
+                sample_count: 1,
+                dimension: wgpu::TextureDimension::D2,
+                format: wgpu::TextureFormat::R8Unorm,
+                usage: wgpu::TextureUsages::TEXTURE_BINDING | wgpu::TextureUsages::COPY_DST,
+                view_formats: &[],
+            },
+            wgpu::util::TextureDataOrder::LayerMajor,
+            &[10, 20, 30, 40],
+        );
+        let view = texture.create_view(&wgpu::TextureViewDescriptor::default());
+
+        for (reduction_mode, expected) in [
+            (SamplerReductionMode::WeightedAverage, 25),
+            (SamplerReductionMode::Min, 10),
+            (SamplerReductionMode::Max, 40),
+        ] {
+            let sampler = ctx.device.create_sampler(&linear_sampler(reduction_mode));
+            let bind_group = ctx.device.create_bind_group(&wgpu::BindGroupDescriptor {
+                label: None,
+                layout: &pipeline.get_bind_group_layout(0),
+                entries: &[
+                    wgpu::BindGroupEntry {
+                        binding: 0,
+                        resource: wgpu::BindingResource::TextureView(&view),
+                    },
+                    wgpu::BindGroupEntry {
+                        binding: 1,
+                        resource: wgpu::BindingResource::Sampler(&sampler),
+                    },
+                    wgpu::BindGroupEntry {
+                        binding: 2,
+                        resource: buffer.as_entire_binding(),
+                    },
+                ],
+            });
+
+            let mut encoder = ctx
+                .device
+                .create_command_encoder(&wgpu::CommandEncoderDescriptor::default());
+            let mut pass = encoder.begin_compute_pass(&wgpu::ComputePassDescriptor::default());
+            pass.set_pipeline(&pipeline);
+            pass.set_bind_group(0, &bind_group, &[]);
+            pass.dispatch_workgroups(1, 1, 1);
+            drop(pass);
+            ctx.queue.submit(Some(encoder.finish()));
+
+            let data = read_buffer(&ctx.device, &ctx.queue, &buffer, ..)
+                .await
+                .unwrap();
+            let data: Vec<u32> = bytemuck::pod_collect_to_vec(&data);
+            assert_eq!(data, [expected], "reduction mode {reduction_mode:?}");
+        }
+    });
@@ -0,0 +1,205 @@
+//! Tests for `RenderPass::set_stencil_reference_separate` and
+//! `RenderPass::set_stencil_write_mask`, see `Features::SEPARATE_STENCIL_STATE`.
+
+use wgpu_test::{fail, gpu_test, GpuTestConfiguration, TestParameters, TestingContext};
+
+const TEXTURE_WIDTH: u32 = 4;
+const FORMAT: wgpu::TextureFormat = wgpu::TextureFormat::Stencil8;
+
+fn create_target(ctx: &TestingContext) -> wgpu::Texture {
+    ctx.device.create_texture(&wgpu::TextureDescriptor {
+        label: Some("Stencil texture"),
+        size: wgpu::Extent3d {
+            width: TEXTURE_WIDTH,
+            height: 1,
+            depth_or_array_layers: 1,
+        },
+        mip_level_count: 1,
+        sample_count: 1,
+        dimension: wgpu::TextureDimension::D2,
+        format: FORMAT,
+        usage: wgpu::TextureUsages::COPY_SRC | wgpu::TextureUsages::RENDER_ATTACHMENT,
+        view_formats: &[],
+    })
+}
+
+/// A pipeline replacing the stencil value with the reference value.
+fn create_pipeline(ctx: &TestingContext) -> wgpu::RenderPipeline {
+    let shader = ctx
+        .device
+        .create_shader_module(wgpu::include_wgsl!("triangle.wgsl"));
+    let face = wgpu::StencilFaceState {
+        compare: wgpu::CompareFunction::Always,
+        fail_op: wgpu::StencilOperation::Keep,
+        depth_fail_op: wgpu::StencilOperation::Keep,
+        pass_op: wgpu::StencilOperation::Replace,
+    };
+    ctx.device
+        .create_render_pipeline(&wgpu::RenderPipelineDescriptor {
+            label: Some("Pipeline"),
+            layout: None,
+            vertex: wgpu::VertexState {
+                module: &shader,
+                entry_point: "vs_main",
+                compilation_options: Default::default(),
+                buffers: &[],
+            },
+            primitive: wgpu::PrimitiveState::default(),
+            depth_stencil: Some(wgpu::DepthStencilState {
+                format: FORMAT,
+                depth_write_enabled: false,
+                depth_compare: wgpu::CompareFunction::Always,
+                stencil: wgpu::StencilState {
+                    front: face,
+                    back: face,
+                    read_mask: 0xff,
+                    write_mask: 0xff,
+                },
+                bias: wgpu::DepthBiasState::default(),
+            }),
+            multisample: wgpu::MultisampleState::default(),
+            fragment: None,
+            multiview: None,
+        })
+}
+
+fn begin_pass<'a>(
+    encoder: &'a mut wgpu::CommandEncoder,
+    view: &'a wgpu::TextureView,
+    stencil_ops: Option<wgpu::Operations<u32>>,
+) -> wgpu::RenderPass<'a> {
+    encoder.begin_render_pass(&wgpu::RenderPassDescriptor {
+        label: Some("Renderpass"),
+        color_attachments: &[],
+        depth_stencil_attachment: Some(wgpu::RenderPassDepthStencilAttachment {
+            view,
+            depth_ops: None,
+            stencil_ops,
+        }),
+        timestamp_writes: None,
+        occlusion_query_set: None,
+    })
+}
+
+/// Draws the front (`back: false`) or back face of a triangle into the pixel column `x`.
+fn draw_column(pass: &mut wgpu::RenderPass, x: u32, back: bool) {
+    pass.set_viewport(x as f32, 0.0, 1.0, 1.0, 0.0, 1.0);
+    let instance = back as u32;
+    pass.draw(0..3, instance..instance + 1);
+}
+
+#[gpu_test]
+static SEPARATE_STENCIL_STATE: GpuTestConfiguration = GpuTestConfiguration::new()
+    .parameters(
+        TestParameters::default()
+            .features(wgpu::Features::SEPARATE_STENCIL_STATE)
+            .downlevel_flags(wgpu::DownlevelFlags::DEPTH_TEXTURE_AND_BUFFER_COPIES),
+    )
+    .run_async(|ctx| async move {
+        let texture = create_target(&ctx);
+        let view = texture.create_view(&wgpu::TextureViewDescriptor::default());
+        let pipeline = create_pipeline(&ctx);
+
+        let bytes_per_row = wgpu::COPY_BYTES_PER_ROW_ALIGNMENT;
+        let readback_buffer = ctx.device.create_buffer(&wgpu::BufferDescriptor {
+            label: None,
+            size: bytes_per_row as u64,
+            usage: wgpu::BufferUsages::MAP_READ | wgpu::BufferUsages::COPY_DST,
+            mapped_at_creation: false,
+        });
+
+        let mut encoder = ctx
+            .device
+            .create_command_encoder(&wgpu::CommandEncoderDescriptor::default());
+        {
+            let mut pass = begin_pass(
+                &mut encoder,
+                &view,
+                Some(wgpu::Operations {
+                    load: wgpu::LoadOp::Clear(0),
+                    store: wgpu::StoreOp::Store,
+                }),
+            );
+            pass.set_pipeline(&pipeline);
+            pass.set_stencil_reference_separate(1, 2);
+            draw_column(&mut pass, 0, false);
+            draw_column(&mut pass, 1, true);
+
+            pass.set_stencil_reference(0xff);
+            pass.set_stencil_write_mask(0x0f, 0x0f);
+            draw_column(&mut pass, 2, false);
+
+            // Setting the pipeline again restores its write masks.
+            pass.set_pipeline(&pipeline);
+            draw_column(&mut pass, 3, true);
+        }
+        encoder.copy_texture_to_buffer(
+            wgpu::ImageCopyTexture {
+                texture: &texture,
+                mip_level: 0,
+                origin: wgpu::Origin3d::ZERO,
+                aspect: wgpu::TextureAspect::StencilOnly,
+            },
+            wgpu::ImageCopyBuffer {
+                buffer: &readback_buffer,
+                layout: wgpu::ImageDataLayout {
+                    offset: 0,
+                    bytes_per_row: Some(bytes_per_row),
+                    rows_per_image: None,
+                },
+            },
+            texture.size(),
+        );
+        ctx.queue.submit(Some(encoder.finish()));
+
+        let slice = readback_buffer.slice(..);
+        slice.map_async(wgpu::MapMode::Read, |_| ());
+        ctx.async_poll(wgpu::Maintain::wait())
+            .await
+            .panic_on_timeout();
+        let data = slice.get_mapped_range();
+        assert_eq!(&data[..TEXTURE_WIDTH as usize], &[1, 2, 0x0f, 0xff]);
+    });
+
+#[gpu_test]
+static WRITE_MASK_ON_READ_ONLY_STENCIL: GpuTestConfiguration = GpuTestConfiguration::new()
+    .parameters(TestParameters::default().features(wgpu::Features::SEPARATE_STENCIL_STATE))
+    .run_sync(|ctx| {
+        let texture = create_target(&ctx);
+        let view = texture.create_view(&wgpu::TextureViewDescriptor::default());
+
+        let mut encoder = ctx
+            .device
+            .create_command_encoder(&wgpu::CommandEncoderDescriptor::default());
+        {
+            // Masks of zero don't write to the stencil aspect.
+            let mut pass = begin_pass(&mut encoder, &view, None);
+            pass.set_stencil_write_mask(0, 0);
+        }
+        fail(&ctx.device, || {
+            let mut pass = begin_pass(&mut encoder, &view, None);
+            pass.set_stencil_write_mask(0, 1);
+        });
+    });
+
+#[gpu_test]
+static SEPARATE_STENCIL_STATE_REQUIRES_FEATURE: GpuTestConfiguration = GpuTestConfiguration::new()
+    .run_sync(|ctx| {
+        let texture = create_target(&ctx);
+        let view = texture.create_view(&wgpu::TextureViewDescriptor::default());
+
+        let mut encoder = ctx
+            .device
+            .create_command_encoder(&wgpu::CommandEncoderDescriptor::default());
+        fail(&ctx.device, || {
+            let mut pass = begin_pass(
+                &mut encoder,
+                &view,
+                Some(wgpu::Operations {
+                    load: wgpu::LoadOp::Load,
+                    store: wgpu::StoreOp::Store,
+                }),
+            );
+            pass.set_stencil_reference_separate(1, 2);
+        });
+    });
@@ -0,0 +1,12 @@
+// Draws one triangle covering the whole viewport, counter-clockwise for
+// instance 0 and mirrored to clockwise for instance 1.
+@vertex
+fn vs_main(
+    @builtin(vertex_index) vertex_index: u32,
+    @builtin(instance_index) instance_index: u32,
+) -> @builtin(position) vec4<f32> {
+    let x = i32(vertex_index) / 2;
+    let y = i32(vertex_index) & 1;
+    let mirror = 1.0 - f32(instance_index) * 2.0;
+    return vec4<f32>((f32(x) * 4.0 - 1.0) * mirror, 1.0 - f32(y) * 4.0, 0.0, 1.0);
+}
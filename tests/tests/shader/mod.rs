@@ -298,6 +298,7 @@ async fn shader_input_output_test(
         let sm = ctx.device.create_shader_module(ShaderModuleDescriptor {
             label: Some(&format!("shader {test_name}")),
             source: ShaderSource::Wgsl(Cow::Borrowed(&processed)),
+            diagnostic_filters: &[],
         });
 
         let pipeline = ctx
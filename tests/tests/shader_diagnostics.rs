@@ -0,0 +1,49 @@
+//! Tests for WGSL diagnostic filters set through `ShaderModuleDescriptor`.
+
+use wgpu_test::{fail, gpu_test, FailureCase, GpuTestConfiguration, TestParameters};
+
+const UNREACHABLE_CODE_SHADER: &str = "
+@compute @workgroup_size(1)
+fn main() {
+    return;
+    let unreachable = 1;
+}
+";
+
+const UNREACHABLE_CODE_ERROR: wgpu::DiagnosticFilter = wgpu::DiagnosticFilter {
+    rule: wgpu::DiagnosticRule::UnreachableCode,
+    severity: wgpu::DiagnosticSeverity::Error,
+};
+
+#[gpu_test]
+static UNREACHABLE_CODE_AS_ERROR: GpuTestConfiguration = GpuTestConfiguration::new()
+    .parameters(
+        TestParameters::default().skip(FailureCase::backend(wgpu::Backends::BROWSER_WEBGPU)),
+    )
+    .run_sync(|ctx| {
+        // Unreachable code is only a warning by default.
+        ctx.device
+            .create_shader_module(wgpu::ShaderModuleDescriptor {
+                label: None,
+                source: wgpu::ShaderSource::Wgsl(UNREACHABLE_CODE_SHADER.into()),
+                diagnostic_filters: &[],
+            });
+
+        fail(&ctx.device, || {
+            ctx.device
+                .create_shader_module(wgpu::ShaderModuleDescriptor {
+                    label: None,
+                    source: wgpu::ShaderSource::Wgsl(UNREACHABLE_CODE_SHADER.into()),
+                    diagnostic_filters: &[UNREACHABLE_CODE_ERROR],
+                })
+        });
+
+        // Directives in the shader override the descriptor.
+        let source = format!("diagnostic(off, naga.unreachable_code);{UNREACHABLE_CODE_SHADER}");
+        ctx.device
+            .create_shader_module(wgpu::ShaderModuleDescriptor {
+                label: None,
+                source: wgpu::ShaderSource::Wgsl(source.into()),
+                diagnostic_filters: &[UNREACHABLE_CODE_ERROR],
+            });
+    });
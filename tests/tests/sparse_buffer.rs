@@ -0,0 +1,83 @@
+use wgpu::{SparseBufferBind, SPARSE_BUFFER_PAGE_SIZE as PAGE};
+use wgpu_test::{gpu_test, GpuTestConfiguration, TestParameters, TestingContext};
+
+fn bind(page: u64, commit: bool) -> SparseBufferBind {
+    SparseBufferBind {
+        offset: page * PAGE,
+        size: PAGE,
+        commit,
+    }
+}
+
+/// Copies the first bytes of each of `pages` of `buffer` out, and reads them back.
+async fn read_pages(ctx: &TestingContext, buffer: &wgpu::Buffer, pages: &[u64]) -> Vec<u8> {
+    const SIZE: u64 = 256;
+    let readback = ctx.device.create_buffer(&wgpu::BufferDescriptor {
+        label: None,
+        size: SIZE * pages.len() as u64,
+        usage: wgpu::BufferUsages::COPY_DST | wgpu::BufferUsages::MAP_READ,
+        mapped_at_creation: false,
+    });
+    let mut encoder = ctx
+        .device
+        .create_command_encoder(&wgpu::CommandEncoderDescriptor::default());
+    for (index, &page) in pages.iter().enumerate() {
+        encoder.copy_buffer_to_buffer(buffer, page * PAGE, &readback, index as u64 * SIZE, SIZE);
+    }
+    ctx.queue.submit(Some(encoder.finish()));
+
+    readback.slice(..).map_async(wgpu::MapMode::Read, |_| ());
+    ctx.async_poll(wgpu::Maintain::wait())
+        .await
+        .panic_on_timeout();
+    let data = readback.slice(..).get_mapped_range().to_vec();
+    data
+}
+
+#[gpu_test]
+static COMMIT_AND_DECOMMIT_PAGES: GpuTestConfiguration = GpuTestConfiguration::new()
+    .parameters(TestParameters::default().features(wgpu::Features::SPARSE_BUFFER))
+    .run_async(|ctx| async move {
+        let buffer = ctx.device.create_sparse_buffer(&wgpu::BufferDescriptor {
+            label: Some("sparse"),
+            size: 4 * PAGE,
+            usage: wgpu::BufferUsages::COPY_SRC | wgpu::BufferUsages::COPY_DST,
+            mapped_at_creation: false,
+        });
+
+        ctx.queue
+            .bind_sparse(&buffer, &[bind(0, true), bind(2, true)]);
+        ctx.queue.write_buffer(&buffer, 2 * PAGE, &[0xAB; 256]);
+        let data = read_pages(&ctx, &buffer, &[0, 2]).await;
+        assert!(data[..256].iter().all(|&byte| byte == 0));
+        assert!(data[256..].iter().all(|&byte| byte == 0xAB));
+
+        // Pages are zeroed again when they are committed after being decommitted.
+        ctx.queue.bind_sparse(&buffer, &[bind(2, false)]);
+        ctx.queue.bind_sparse(&buffer, &[bind(2, true)]);
+        let data = read_pages(&ctx, &buffer, &[2]).await;
+        assert!(data.iter().all(|&byte| byte == 0));
+    });
+
+#[gpu_test]
+static UNALIGNED_BIND_IS_AN_ERROR: GpuTestConfiguration = GpuTestConfiguration::new()
+    .parameters(TestParameters::default().features(wgpu::Features::SPARSE_BUFFER))
+    .run_sync(|ctx| {
+        let buffer = ctx.device.create_sparse_buffer(&wgpu::BufferDescriptor {
+            label: None,
+            size: 2 * PAGE,
+            usage: wgpu::BufferUsages::STORAGE,
+            mapped_at_creation: false,
+        });
+
+        wgpu_test::fail(&ctx.device, || {
+            ctx.queue.bind_sparse(
+                &buffer,
+                &[SparseBufferBind {
+                    offset: PAGE / 2,
+                    size: PAGE,
+                    commit: true,
+                }],
+            );
+        });
+    });
@@ -0,0 +1,145 @@
+use wgpu::{Extent3d, Origin3d, SparseTextureBind};
+use wgpu_test::{gpu_test, GpuTestConfiguration, TestParameters, TestingContext};
+
+/// Creates a sparse texture two tiles wide and one tile high.
+fn create_texture(ctx: &TestingContext) -> (wgpu::Texture, Extent3d) {
+    let probe = ctx
+        .device
+        .create_sparse_texture(&texture_descriptor(Extent3d {
+            width: 1024,
+            height: 1024,
+            depth_or_array_layers: 1,
+        }));
+    let tile_size = probe.sparse_tiles().unwrap().tile_size;
+    probe.destroy();
+
+    let texture = ctx
+        .device
+        .create_sparse_texture(&texture_descriptor(Extent3d {
+            width: 2 * tile_size.width,
+            height: tile_size.height,
+            depth_or_array_layers: 1,
+        }));
+    (texture, tile_size)
+}
+
+fn texture_descriptor(size: Extent3d) -> wgpu::TextureDescriptor<'static> {
+    wgpu::TextureDescriptor {
+        label: Some("sparse"),
+        size,
+        mip_level_count: 1,
+        sample_count: 1,
+        dimension: wgpu::TextureDimension::D2,
+        format: wgpu::TextureFormat::Rgba8Unorm,
+        usage: wgpu::TextureUsages::COPY_SRC | wgpu::TextureUsages::COPY_DST,
+        view_formats: &[],
+    }
+}
+
+fn bind(tile: u32, tile_size: Extent3d, commit: bool) -> SparseTextureBind {
+    SparseTextureBind {
+        mip_level: 0,
+        array_layer: 0,
+        origin: Origin3d {
+            x: tile * tile_size.width,
+            y: 0,
+            z: 0,
+        },
+        size: tile_size,
+        commit,
+    }
+}
+
+/// Copies the first texel of each of `tiles` of `texture` out, and reads them back.
+async fn read_tiles(
+    ctx: &TestingContext,
+    texture: &wgpu::Texture,
+    tile_size: Extent3d,
+    tiles: &[u32],
+) -> Vec<u8> {
+    const STRIDE: u64 = 256;
+    let readback = ctx.device.create_buffer(&wgpu::BufferDescriptor {
+        label: None,
+        size: STRIDE * tiles.len() as u64,
+        usage: wgpu::BufferUsages::COPY_DST | wgpu::BufferUsages::MAP_READ,
+        mapped_at_creation: false,
+    });
+    let mut encoder = ctx
+        .device
+        .create_command_encoder(&wgpu::CommandEncoderDescriptor::default());
+    for (index, &tile) in tiles.iter().enumerate() {
+        encoder.copy_texture_to_buffer(
+            wgpu::ImageCopyTexture {
+                texture,
+                mip_level: 0,
+                origin: bind(tile, tile_size, true).origin,
+                aspect: wgpu::TextureAspect::All,
+            },
+            wgpu::ImageCopyBuffer {
+                buffer: &readback,
+                layout: wgpu::ImageDataLayout {
+                    offset: index as u64 * STRIDE,
+                    bytes_per_row: None,
+                    rows_per_image: None,
+                },
+            },
+            Extent3d::default(),
+        );
+    }
+    ctx.queue.submit(Some(encoder.finish()));
+
+    readback.slice(..).map_async(wgpu::MapMode::Read, |_| ());
+    ctx.async_poll(wgpu::Maintain::wait())
+        .await
+        .panic_on_timeout();
+    let data = readback.slice(..).get_mapped_range().to_vec();
+    data.chunks(STRIDE as usize)
+        .flat_map(|texel| texel[..4].to_vec())
+        .collect()
+}
+
+#[gpu_test]
+static COMMIT_AND_DECOMMIT_TILES: GpuTestConfiguration = GpuTestConfiguration::new()
+    .parameters(TestParameters::default().features(wgpu::Features::SPARSE_TEXTURE))
+    .run_async(|ctx| async move {
+        let (texture, tile_size) = create_texture(&ctx);
+
+        ctx.queue.bind_sparse_texture(
+            &texture,
+            &[bind(0, tile_size, true), bind(1, tile_size, true)],
+        );
+        ctx.queue.write_texture(
+            wgpu::ImageCopyTexture {
+                texture: &texture,
+                mip_level: 0,
+                origin: bind(1, tile_size, true).origin,
+                aspect: wgpu::TextureAspect::All,
+            },
+            &[0xAB; 4],
+            wgpu::ImageDataLayout::default(),
+            Extent3d::default(),
+        );
+        let data = read_tiles(&ctx, &texture, tile_size, &[0, 1]).await;
+        assert_eq!(data, [0, 0, 0, 0, 0xAB, 0xAB, 0xAB, 0xAB]);
+
+        // Tiles are zeroed again when they are committed after being decommitted.
+        ctx.queue
+            .bind_sparse_texture(&texture, &[bind(1, tile_size, false)]);
+        ctx.queue
+            .bind_sparse_texture(&texture, &[bind(1, tile_size, true)]);
+        let data = read_tiles(&ctx, &texture, tile_size, &[1]).await;
+        assert_eq!(data, [0; 4]);
+    });
+
+#[gpu_test]
+static UNALIGNED_TEXTURE_BIND_IS_AN_ERROR: GpuTestConfiguration = GpuTestConfiguration::new()
+    .parameters(TestParameters::default().features(wgpu::Features::SPARSE_TEXTURE))
+    .run_sync(|ctx| {
+        let (texture, tile_size) = create_texture(&ctx);
+
+        let mut unaligned = bind(0, tile_size, true);
+        unaligned.origin.x = tile_size.width / 2;
+        wgpu_test::fail(&ctx.device, || {
+            ctx.queue.bind_sparse_texture(&texture, &[unaligned]);
+        });
+    });
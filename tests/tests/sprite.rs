@@ -0,0 +1,96 @@
+//! Tests for `wgpu::util::SpriteRenderer`.
+
+use wgpu::util::{DeviceExt, Sprite, SpriteRenderer, TextureDataOrder};
+use wgpu_test::{gpu_test, image, GpuTestConfiguration};
+
+#[gpu_test]
+static DRAW_SPRITES_FROM_ATLAS: GpuTestConfiguration =
+    GpuTestConfiguration::new().run_async(|ctx| async move {
+        let format = wgpu::TextureFormat::Rgba8Unorm;
+        let atlas = ctx.device.create_texture_with_data(
+            &ctx.queue,
+            &wgpu::TextureDescriptor {
+                label: Some("atlas"),
+                size: wgpu::Extent3d {
+                    width: 2,
+                    height: 1,
+                    depth_or_array_layers: 1,
+                },
+                mip_level_count: 1,
+                sample_count: 1,
+                dimension: wgpu::TextureDimension::D2,
+                format,
+                usage: wgpu::TextureUsages::TEXTURE_BINDING,
+                view_formats: &[],
+            },
+            TextureDataOrder::LayerMajor,
+            &[255, 0, 0, 255, 0, 255, 0, 255],
+        );
+        let target = ctx.device.create_texture(&wgpu::TextureDescriptor {
+            label: Some("target"),
+            size: wgpu::Extent3d {
+                width: 4,
+                height: 1,
+                depth_or_array_layers: 1,
+            },
+            mip_level_count: 1,
+            sample_count: 1,
+            dimension: wgpu::TextureDimension::D2,
+            format,
+            usage: wgpu::TextureUsages::COPY_SRC | wgpu::TextureUsages::RENDER_ATTACHMENT,
+            view_formats: &[],
+        });
+        let view = target.create_view(&wgpu::TextureViewDescriptor::default());
+
+        let mut sprites = SpriteRenderer::new(&ctx.device, format);
+        let atlas = sprites.create_texture(&ctx.device, &atlas);
+        sprites.draw(
+            &atlas,
+            Sprite::new([0.0, 0.0], [1.0, 1.0], [0.0, 0.0, 1.0, 1.0]),
+        );
+        sprites.draw(
+            &atlas,
+            Sprite::new([2.0, 0.0], [1.0, 1.0], [1.0, 0.0, 1.0, 1.0]),
+        );
+        // A transparent tint leaves the target as it is.
+        sprites.draw(
+            &atlas,
+            Sprite {
+                color: [0.0, 0.0, 0.0, 0.0],
+                ..Sprite::new([3.0, 0.0], [1.0, 1.0], [1.0, 0.0, 1.0, 1.0])
+            },
+        );
+        sprites.prepare(&ctx.device, &ctx.queue, [4, 1]);
+
+        let readback_buffer = image::ReadbackBuffers::new(&ctx.device, &target);
+        let mut encoder = ctx
+            .device
+            .create_command_encoder(&wgpu::CommandEncoderDescriptor::default());
+        {
+            let mut pass = encoder.begin_render_pass(&wgpu::RenderPassDescriptor {
+                label: None,
+                color_attachments: &[Some(wgpu::RenderPassColorAttachment {
+                    view: &view,
+                    resolve_target: None,
+                    ops: wgpu::Operations {
+                        load: wgpu::LoadOp::Clear(wgpu::Color::TRANSPARENT),
+                        store: wgpu::StoreOp::Store,
+                    },
+                })],
+                depth_stencil_attachment: None,
+                timestamp_writes: None,
+                occlusion_query_set: None,
+            });
+            // All sprites come from the same atlas.
+            assert_eq!(sprites.render(&mut pass), 1);
+        }
+        readback_buffer.copy_from(&ctx.device, &mut encoder, &target);
+        ctx.queue.submit(Some(encoder.finish()));
+
+        readback_buffer
+            .assert_buffer_contents(
+                &ctx,
+                &[255, 0, 0, 255, 0, 0, 0, 0, 0, 255, 0, 255, 0, 0, 0, 0],
+            )
+            .await;
+    });
@@ -0,0 +1,188 @@
+//! Tests for binding arrays of storage buffers.
+
+use std::num::NonZeroU32;
+
+use wgpu::util::{read_buffer, DeviceExt};
+use wgpu_test::{gpu_test, GpuTestConfiguration, TestParameters};
+
+const BUFFER_COUNT: u32 = 4;
+const VALUES_PER_BUFFER: u32 = 16;
+
+// Every invocation adds to a value of a different buffer of the runtime-sized
+// binding array, picked with a non-uniform index.
+const SHADER: &str = r#"
+struct Values {
+    values: array<u32>,
+}
+
+@group(0) @binding(0)
+var<storage, read_write> buffers: binding_array<Values>;
+
+@compute @workgroup_size(16)
+fn main(@builtin(global_invocation_id) id: vec3<u32>) {
+    let buffer = id.x % 4u;
+    let index = id.x / 4u;
+    buffers[buffer].values[index] += id.x * arrayLength(&buffers[buffer].values);
+}
+"#;
+
+fn features() -> wgpu::Features {
+    wgpu::Features::BUFFER_BINDING_ARRAY
+        | wgpu::Features::STORAGE_RESOURCE_BINDING_ARRAY
+        | wgpu::Features::SAMPLED_TEXTURE_AND_STORAGE_BUFFER_ARRAY_NON_UNIFORM_INDEXING
+}
+
+fn bind_group_layout(device: &wgpu::Device) -> wgpu::BindGroupLayout {
+    device.create_bind_group_layout(&wgpu::BindGroupLayoutDescriptor {
+        label: None,
+        entries: &[wgpu::BindGroupLayoutEntry {
+            binding: 0,
+            visibility: wgpu::ShaderStages::COMPUTE,
+            ty: wgpu::BindingType::Buffer {
+                ty: wgpu::BufferBindingType::Storage { read_only: false },
+                has_dynamic_offset: false,
+                min_binding_size: None,
+            },
+            count: NonZeroU32::new(BUFFER_COUNT),
+        }],
+    })
+}
+
+#[gpu_test]
+static NON_UNIFORM_STORAGE_BUFFER_ARRAY: GpuTestConfiguration = GpuTestConfiguration::new()
+    .parameters(TestParameters::default().features(features()))
+    .run_async(|ctx| async move {
+        let module = ctx
+            .device
+            .create_shader_module(wgpu::ShaderModuleDescriptor {
+                label: None,
+                source: wgpu::ShaderSource::Wgsl(SHADER.into()),
+                diagnostic_filters: &[],
+            });
+        let bind_group_layout = bind_group_layout(&ctx.device);
+        let pipeline_layout = ctx
+            .device
+            .create_pipeline_layout(&wgpu::PipelineLayoutDescriptor {
+                label: None,
+                bind_group_layouts: &[&bind_group_layout],
+                push_constant_ranges: &[],
+            });
+        let pipeline = ctx
+            .device
+            .create_compute_pipeline(&wgpu::ComputePipelineDescriptor {
+                label: None,
+                layout: Some(&pipeline_layout),
+                module: &module,
+                entry_point: "main",
+                compilation_options: Default::default(),
+            });
+
+        let initial: Vec<u32> = (0..VALUES_PER_BUFFER).collect();
+        let buffers: Vec<wgpu::Buffer> = (0..BUFFER_COUNT)
+            .map(|_| {
+                ctx.device
+                    .create_buffer_init(&wgpu::util::BufferInitDescriptor {
+                        label: None,
+                        contents: bytemuck::cast_slice(&initial),
+                        usage: wgpu::BufferUsages::STORAGE | wgpu::BufferUsages::COPY_SRC,
+                    })
+            })
+            .collect();
+        let bindings: Vec<wgpu::BufferBinding> = buffers
+            .iter()
+            .map(|buffer| buffer.as_entire_buffer_binding())
+            .collect();
+        let bind_group = ctx.device.create_bind_group(&wgpu::BindGroupDescriptor {
+            label: None,
+            layout: &bind_group_layout,
+            entries: &[wgpu::BindGroupEntry {
+                binding: 0,
+                resource: wgpu::BindingResource::BufferArray(&bindings),
+            }],
+        });
+
+        let mut encoder = ctx
+            .device
+            .create_command_encoder(&wgpu::CommandEncoderDescriptor::default());
+        {
+            let mut pass = encoder.begin_compute_pass(&wgpu::ComputePassDescriptor::default());
+            pass.set_pipeline(&pipeline);
+            pass.set_bind_group(0, &bind_group, &[]);
+            pass.dispatch_workgroups(BUFFER_COUNT * VALUES_PER_BUFFER / 16, 1, 1);
+        }
+        ctx.queue.submit(Some(encoder.finish()));
+
+        for (buffer_index, buffer) in buffers.iter().enumerate() {
+            let contents = read_buffer(&ctx.device, &ctx.queue, buffer, ..)
+                .await
+                .unwrap();
+            let values: &[u32] = bytemuck::cast_slice(&contents);
+            for (index, &value) in values.iter().enumerate() {
+                let id = index as u32 * BUFFER_COUNT + buffer_index as u32;
+                assert_eq!(
+                    value,
+                    index as u32 + id * VALUES_PER_BUFFER,
+                    "buffer {buffer_index}, value {index}"
+                );
+            }
+        }
+    });
+
+#[gpu_test]
+static RUNTIME_SIZED_BINDING_ARRAY_NEEDS_LAYOUT: GpuTestConfiguration = GpuTestConfiguration::new()
+    .parameters(TestParameters::default().features(features()))
+    .run_sync(|ctx| {
+        let module = ctx
+            .device
+            .create_shader_module(wgpu::ShaderModuleDescriptor {
+                label: None,
+                source: wgpu::ShaderSource::Wgsl(SHADER.into()),
+                diagnostic_filters: &[],
+            });
+
+        // The size of the array can't be derived from the shader.
+        wgpu_test::fail(&ctx.device, || {
+            ctx.device
+                .create_compute_pipeline(&wgpu::ComputePipelineDescriptor {
+                    label: None,
+                    layout: None,
+                    module: &module,
+                    entry_point: "main",
+                    compilation_options: Default::default(),
+                })
+        });
+
+        // Nor can it go in a layout entry that isn't an array.
+        let bind_group_layout =
+            ctx.device
+                .create_bind_group_layout(&wgpu::BindGroupLayoutDescriptor {
+                    label: None,
+                    entries: &[wgpu::BindGroupLayoutEntry {
+                        binding: 0,
+                        visibility: wgpu::ShaderStages::COMPUTE,
+                        ty: wgpu::BindingType::Buffer {
+                            ty: wgpu::BufferBindingType::Storage { read_only: false },
+                            has_dynamic_offset: false,
+                            min_binding_size: None,
+                        },
+                        count: None,
+                    }],
+                });
+        let pipeline_layout = ctx
+            .device
+            .create_pipeline_layout(&wgpu::PipelineLayoutDescriptor {
+                label: None,
+                bind_group_layouts: &[&bind_group_layout],
+                push_constant_ranges: &[],
+            });
+        wgpu_test::fail(&ctx.device, || {
+            ctx.device
+                .create_compute_pipeline(&wgpu::ComputePipelineDescriptor {
+                    label: None,
+                    layout: Some(&pipeline_layout),
+                    module: &module,
+                    entry_point: "main",
+                    compilation_options: Default::default(),
+                })
+        });
+    });
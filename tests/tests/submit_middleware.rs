@@ -0,0 +1,77 @@
+//! Tests for `wgpu::SubmitMiddleware`.
+
+use std::sync::{Arc, Mutex};
+
+use wgpu::util::{BufferInitDescriptor, DeviceExt};
+use wgpu_test::{gpu_test, GpuTestConfiguration};
+
+/// Appends a copy of `source` into `destination` to every submission.
+#[derive(Debug)]
+struct AppendCopy {
+    device: Arc<wgpu::Device>,
+    source: wgpu::Buffer,
+    destination: Arc<wgpu::Buffer>,
+}
+
+impl wgpu::SubmitMiddleware for AppendCopy {
+    fn on_submit(&self, _queue: &wgpu::Queue, command_buffers: &mut Vec<wgpu::CommandBuffer>) {
+        let mut encoder = self
+            .device
+            .create_command_encoder(&wgpu::CommandEncoderDescriptor::default());
+        encoder.copy_buffer_to_buffer(&self.source, 0, &self.destination, 0, 4);
+        command_buffers.push(encoder.finish());
+    }
+}
+
+/// Records how many command buffers each submission has.
+#[derive(Debug)]
+struct CountCommandBuffers(Arc<Mutex<Vec<usize>>>);
+
+impl wgpu::SubmitMiddleware for CountCommandBuffers {
+    fn on_submit(&self, _queue: &wgpu::Queue, command_buffers: &mut Vec<wgpu::CommandBuffer>) {
+        self.0.lock().unwrap().push(command_buffers.len());
+    }
+}
+
+#[gpu_test]
+static SUBMIT_MIDDLEWARE_APPENDS_IN_ORDER: GpuTestConfiguration = GpuTestConfiguration::new()
+    .run_async(|ctx| async move {
+        let source = ctx.device.create_buffer_init(&BufferInitDescriptor {
+            label: Some("source"),
+            contents: &[1, 2, 3, 4],
+            usage: wgpu::BufferUsages::COPY_SRC,
+        });
+        let destination = Arc::new(ctx.device.create_buffer(&wgpu::BufferDescriptor {
+            label: Some("destination"),
+            size: 4,
+            usage: wgpu::BufferUsages::COPY_DST | wgpu::BufferUsages::MAP_READ,
+            mapped_at_creation: false,
+        }));
+        let counts = Arc::new(Mutex::new(Vec::new()));
+
+        ctx.queue.add_submit_middleware(AppendCopy {
+            device: ctx.device.clone(),
+            source,
+            destination: destination.clone(),
+        });
+        ctx.queue
+            .add_submit_middleware(CountCommandBuffers(counts.clone()));
+
+        let encoder = ctx
+            .device
+            .create_command_encoder(&wgpu::CommandEncoderDescriptor::default());
+        ctx.queue.submit(Some(encoder.finish()));
+        // Middleware added later sees the command buffers appended before it.
+        assert_eq!(*counts.lock().unwrap(), [2]);
+
+        ctx.queue.clear_submit_middleware();
+        ctx.queue.submit(None);
+        assert_eq!(*counts.lock().unwrap(), [2]);
+
+        let slice = destination.slice(..);
+        slice.map_async(wgpu::MapMode::Read, |_| ());
+        ctx.async_poll(wgpu::Maintain::wait())
+            .await
+            .panic_on_timeout();
+        assert_eq!(&*slice.get_mapped_range(), &[1, 2, 3, 4]);
+    });
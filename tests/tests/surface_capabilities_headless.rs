@@ -0,0 +1,90 @@
+//! Tests for `Adapter::get_surface_capabilities_headless`.
+
+use raw_window_handle::{DisplayHandle, RawDisplayHandle};
+use wgpu_test::{gpu_test, GpuTestConfiguration, TestingContext};
+
+/// A display handle of the kind windows on this platform would have, and
+/// the Vulkan WSI extension their surfaces are created with.
+fn platform_display() -> (RawDisplayHandle, &'static str) {
+    use raw_window_handle as rwh;
+
+    if cfg!(target_arch = "wasm32") {
+        (rwh::WebDisplayHandle::new().into(), "")
+    } else if cfg!(windows) {
+        (
+            rwh::WindowsDisplayHandle::new().into(),
+            "VK_KHR_win32_surface",
+        )
+    } else if cfg!(target_os = "macos") {
+        (
+            rwh::AppKitDisplayHandle::new().into(),
+            "VK_EXT_metal_surface",
+        )
+    } else if cfg!(target_os = "ios") {
+        (
+            rwh::UiKitDisplayHandle::new().into(),
+            "VK_EXT_metal_surface",
+        )
+    } else if cfg!(target_os = "android") {
+        (
+            rwh::AndroidDisplayHandle::new().into(),
+            "VK_KHR_android_surface",
+        )
+    } else {
+        // Only the kind of display matters, so no X server is needed.
+        (
+            rwh::XlibDisplayHandle::new(None, 0).into(),
+            "VK_KHR_xlib_surface",
+        )
+    }
+}
+
+/// Whether the Vulkan instance of `ctx` enabled `VK_EXT_headless_surface`
+/// along with the WSI extension `wsi_extension`.
+#[cfg(not(any(target_arch = "wasm32", target_os = "macos", target_os = "ios")))]
+fn vulkan_has_headless_surface(ctx: &TestingContext, wsi_extension: &str) -> bool {
+    unsafe {
+        ctx.adapter
+            .as_hal::<wgpu::hal::api::Vulkan, _, _>(|adapter| {
+                let extensions = adapter.unwrap().shared_instance().extensions();
+                let enabled = |name: &str| {
+                    extensions
+                        .iter()
+                        .any(|ext| ext.to_bytes() == name.as_bytes())
+                };
+                enabled("VK_EXT_headless_surface") && enabled(wsi_extension)
+            })
+    }
+}
+
+#[cfg(any(target_arch = "wasm32", target_os = "macos", target_os = "ios"))]
+fn vulkan_has_headless_surface(_ctx: &TestingContext, _wsi_extension: &str) -> bool {
+    false
+}
+
+#[gpu_test]
+static SURFACE_CAPABILITIES_HEADLESS: GpuTestConfiguration =
+    GpuTestConfiguration::new().run_sync(|ctx| {
+        let (raw_display, wsi_extension) = platform_display();
+        let display = unsafe { DisplayHandle::borrow_raw(raw_display) };
+        let caps = ctx.adapter.get_surface_capabilities_headless(&display);
+
+        let expect_caps = match ctx.adapter_info.backend {
+            wgpu::Backend::Vulkan => vulkan_has_headless_surface(&ctx, wsi_extension),
+            // None of these depend on the window a surface is created for.
+            wgpu::Backend::Metal | wgpu::Backend::Dx12 | wgpu::Backend::BrowserWebGpu => true,
+            // GL can't tell without the config of a real window.
+            _ => false,
+        };
+
+        if expect_caps {
+            assert!(!caps.formats.is_empty());
+            assert!(!caps.present_modes.is_empty());
+            assert!(!caps.alpha_modes.is_empty());
+            assert!(caps.usages.contains(wgpu::TextureUsages::RENDER_ATTACHMENT));
+        } else {
+            // The documented result when the backend can't tell.
+            assert!(caps.formats.is_empty());
+            assert!(caps.present_modes.is_empty());
+        }
+    });
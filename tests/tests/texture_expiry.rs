@@ -0,0 +1,72 @@
+use wgpu_test::{fail, gpu_test, GpuTestConfiguration, TestingContext};
+
+fn create_texture(ctx: &TestingContext) -> wgpu::Texture {
+    ctx.device.create_texture(&wgpu::TextureDescriptor {
+        label: Some("frame"),
+        size: wgpu::Extent3d {
+            width: 4,
+            height: 4,
+            depth_or_array_layers: 1,
+        },
+        mip_level_count: 1,
+        sample_count: 1,
+        dimension: wgpu::TextureDimension::D2,
+        format: wgpu::TextureFormat::Rgba8Unorm,
+        usage: wgpu::TextureUsages::TEXTURE_BINDING | wgpu::TextureUsages::COPY_SRC,
+        view_formats: &[],
+    })
+}
+
+fn create_bind_group(
+    ctx: &TestingContext,
+    layout: &wgpu::BindGroupLayout,
+    view: &wgpu::TextureView,
+) -> wgpu::BindGroup {
+    ctx.device.create_bind_group(&wgpu::BindGroupDescriptor {
+        label: None,
+        layout,
+        entries: &[wgpu::BindGroupEntry {
+            binding: 0,
+            resource: wgpu::BindingResource::TextureView(view),
+        }],
+    })
+}
+
+#[gpu_test]
+static EXPIRED_TEXTURE_FAILS_VALIDATION: GpuTestConfiguration = GpuTestConfiguration::new()
+    .run_sync(|ctx| {
+        let texture = create_texture(&ctx);
+        let view = texture.create_view(&wgpu::TextureViewDescriptor::default());
+        let layout = ctx
+            .device
+            .create_bind_group_layout(&wgpu::BindGroupLayoutDescriptor {
+                label: None,
+                entries: &[wgpu::BindGroupLayoutEntry {
+                    binding: 0,
+                    visibility: wgpu::ShaderStages::FRAGMENT,
+                    ty: wgpu::BindingType::Texture {
+                        sample_type: wgpu::TextureSampleType::Float { filterable: true },
+                        view_dimension: wgpu::TextureViewDimension::D2,
+                        multisampled: false,
+                    },
+                    count: None,
+                }],
+            });
+
+        // Bind groups created before the expiry stay valid.
+        let _bind_group = create_bind_group(&ctx, &layout, &view);
+
+        let expiry = texture.expiry();
+        assert!(!expiry.is_expired());
+        expiry.expire();
+        assert!(texture.expiry().is_expired());
+
+        fail(&ctx.device, || {
+            create_bind_group(&ctx, &layout, &view);
+        });
+
+        // Other textures are unaffected.
+        let other = create_texture(&ctx);
+        let other_view = other.create_view(&wgpu::TextureViewDescriptor::default());
+        create_bind_group(&ctx, &layout, &other_view);
+    });
@@ -0,0 +1,102 @@
+//! Tests for `Device::get_texture_format_features`.
+
+use wgpu_test::{fail, gpu_test, GpuTestConfiguration, TestParameters, TestingContext};
+
+fn create_multisampled_texture(
+    ctx: &TestingContext,
+    format: wgpu::TextureFormat,
+    sample_count: u32,
+) -> wgpu::Texture {
+    ctx.device.create_texture(&wgpu::TextureDescriptor {
+        label: None,
+        size: wgpu::Extent3d {
+            width: 4,
+            height: 4,
+            depth_or_array_layers: 1,
+        },
+        mip_level_count: 1,
+        sample_count,
+        dimension: wgpu::TextureDimension::D2,
+        format,
+        usage: wgpu::TextureUsages::RENDER_ATTACHMENT,
+        view_formats: &[],
+    })
+}
+
+/// Textures can be created with exactly the sample counts of the mask.
+fn check_sample_counts(ctx: &TestingContext, format: wgpu::TextureFormat) {
+    let mask = ctx
+        .device
+        .get_texture_format_features(format)
+        .flags
+        .sample_count_mask();
+    assert_ne!(mask & 1, 0, "{format:?} doesn't support a single sample");
+
+    for sample_count in [1, 2, 4, 8, 16] {
+        if mask & sample_count != 0 {
+            let _ = create_multisampled_texture(ctx, format, sample_count);
+        } else {
+            fail(&ctx.device, || {
+                create_multisampled_texture(ctx, format, sample_count)
+            });
+        }
+    }
+}
+
+#[gpu_test]
+static TEXTURE_FORMAT_FEATURES_GUARANTEED_SAMPLE_COUNTS: GpuTestConfiguration =
+    GpuTestConfiguration::new().run_sync(|ctx| {
+        let format = wgpu::TextureFormat::Rgba8Unorm;
+        // Without adapter specific format features, the device accepts what
+        // WebGPU guarantees, whatever the adapter supports. Downlevel adapters
+        // can't guarantee it, so they report their own format features.
+        if ctx
+            .adapter_downlevel_capabilities
+            .flags
+            .contains(wgpu::DownlevelFlags::WEBGPU_TEXTURE_FORMAT_SUPPORT)
+        {
+            assert_eq!(
+                ctx.device.get_texture_format_features(format),
+                format.guaranteed_format_features(ctx.device.features())
+            );
+        }
+        check_sample_counts(&ctx, format);
+    });
+
+#[gpu_test]
+static TEXTURE_FORMAT_FEATURES_ADAPTER_SAMPLE_COUNTS: GpuTestConfiguration =
+    GpuTestConfiguration::new()
+        .parameters(
+            TestParameters::default()
+                .features(wgpu::Features::TEXTURE_ADAPTER_SPECIFIC_FORMAT_FEATURES),
+        )
+        .run_sync(|ctx| {
+            for format in [
+                wgpu::TextureFormat::Rgba8Unorm,
+                wgpu::TextureFormat::Rgba16Float,
+                wgpu::TextureFormat::Rgba8Uint,
+                wgpu::TextureFormat::Depth32Float,
+            ] {
+                assert_eq!(
+                    ctx.device.get_texture_format_features(format),
+                    ctx.adapter.get_texture_format_features(format),
+                );
+                check_sample_counts(&ctx, format);
+            }
+        });
+
+#[gpu_test]
+static TEXTURE_FORMAT_FEATURES_MISSING_FEATURE: GpuTestConfiguration = GpuTestConfiguration::new()
+    .run_sync(|ctx| {
+        let format = wgpu::TextureFormat::Bc1RgbaUnorm;
+        if ctx
+            .device
+            .features()
+            .contains(wgpu::Features::TEXTURE_COMPRESSION_BC)
+        {
+            return;
+        }
+        let features = ctx.device.get_texture_format_features(format);
+        assert!(features.allowed_usages.is_empty());
+        assert_eq!(features.flags.sample_count_mask(), 1);
+    });
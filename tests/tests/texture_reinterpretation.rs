@@ -0,0 +1,331 @@
+//! Tests for `Features::TEXTURE_FORMAT_REINTERPRETATION`.
+
+use wgpu::util::{read_buffer, DeviceExt};
+use wgpu_test::{fail, gpu_test, GpuTestConfiguration, TestParameters, TestingContext};
+
+const LOAD_SHADER: &str = "
+@group(0) @binding(0) var texture: texture_2d<u32>;
+@group(0) @binding(1) var<storage, read_write> texel: u32;
+
+@compute @workgroup_size(1)
+fn main() {
+    texel = textureLoad(texture, vec2<i32>(0), 0).r;
+}
+";
+
+const STORE_SHADER: &str = "
+@group(0) @binding(0) var blocks: texture_storage_2d<rg32uint, write>;
+
+@compute @workgroup_size(1)
+fn main() {
+    textureStore(blocks, vec2<i32>(0), vec4<u32>(0x01234567u, 0x89abcdefu, 0u, 0u));
+}
+";
+
+const TEXEL: [u8; 4] = [10, 20, 30, 40];
+
+fn parameters() -> TestParameters {
+    TestParameters::default()
+        .features(wgpu::Features::TEXTURE_FORMAT_REINTERPRETATION)
+        .downlevel_flags(wgpu::DownlevelFlags::COMPUTE_SHADERS)
+        .limits(wgpu::Limits::downlevel_defaults())
+}
+
+fn create_texture(
+    ctx: &TestingContext,
+    format: wgpu::TextureFormat,
+    usage: wgpu::TextureUsages,
+    view_formats: &[wgpu::TextureFormat],
+) -> wgpu::Texture {
+    ctx.device.create_texture(&wgpu::TextureDescriptor {
+        label: None,
+        size: wgpu::Extent3d::default(),
+        mip_level_count: 1,
+        sample_count: 1,
+        dimension: wgpu::TextureDimension::D2,
+        format,
+        usage,
+        view_formats,
+    })
+}
+
+fn create_texel_texture(
+    ctx: &TestingContext,
+    usage: wgpu::TextureUsages,
+    view_formats: &[wgpu::TextureFormat],
+) -> wgpu::Texture {
+    ctx.device.create_texture_with_data(
+        &ctx.queue,
+        &wgpu::TextureDescriptor {
+            label: None,
+            size: wgpu::Extent3d::default(),
+            mip_level_count: 1,
+            sample_count: 1,
+            dimension: wgpu::TextureDimension::D2,
+            format: wgpu::TextureFormat::Rgba8Unorm,
+            usage: usage | wgpu::TextureUsages::COPY_DST,
+            view_formats,
+        },
+        wgpu::util::TextureDataOrder::LayerMajor,
+        &TEXEL,
+    )
+}
+
+fn run_compute(ctx: &TestingContext, shader: &str, resources: &[wgpu::BindingResource]) {
+    let module = ctx
+        .device
+        .create_shader_module(wgpu::ShaderModuleDescriptor {
+            label: None,
+            source: wgpu::ShaderSource::Wgsl(shader.into()),
+            diagnostic_filters: &[],
+        });
+    let pipeline = ctx
+        .device
+        .create_compute_pipeline(&wgpu::ComputePipelineDescriptor {
+            label: None,
+            layout: None,
+            module: &module,
+            entry_point: "main",
+            compilation_options: Default::default(),
+        });
+    let entries: Vec<_> = resources
+        .iter()
+        .enumerate()
+        .map(|(binding, resource)| wgpu::BindGroupEntry {
+            binding: binding as u32,
+            resource: resource.clone(),
+        })
+        .collect();
+    let bind_group = ctx.device.create_bind_group(&wgpu::BindGroupDescriptor {
+        label: None,
+        layout: &pipeline.get_bind_group_layout(0),
+        entries: &entries,
+    });
+
+    let mut encoder = ctx
+        .device
+        .create_command_encoder(&wgpu::CommandEncoderDescriptor::default());
+    let mut pass = encoder.begin_compute_pass(&wgpu::ComputePassDescriptor::default());
+    pass.set_pipeline(&pipeline);
+    pass.set_bind_group(0, &bind_group, &[]);
+    pass.dispatch_workgroups(1, 1, 1);
+    drop(pass);
+    ctx.queue.submit(Some(encoder.finish()));
+}
+
+/// Copies the first texel block of `texture` into a buffer that can be read.
+fn copy_block(ctx: &TestingContext, texture: &wgpu::Texture) -> wgpu::Buffer {
+    let (block_width, block_height) = texture.format().block_dimensions();
+    let buffer = ctx.device.create_buffer(&wgpu::BufferDescriptor {
+        label: None,
+        size: texture.format().block_copy_size(None).unwrap() as u64,
+        usage: wgpu::BufferUsages::COPY_SRC | wgpu::BufferUsages::COPY_DST,
+        mapped_at_creation: false,
+    });
+    let mut encoder = ctx
+        .device
+        .create_command_encoder(&wgpu::CommandEncoderDescriptor::default());
+    encoder.copy_texture_to_buffer(
+        texture.as_image_copy(),
+        wgpu::ImageCopyBuffer {
+            buffer: &buffer,
+            layout: wgpu::ImageDataLayout::default(),
+        },
+        wgpu::Extent3d {
+            width: block_width,
+            height: block_height,
+            depth_or_array_layers: 1,
+        },
+    );
+    ctx.queue.submit(Some(encoder.finish()));
+    buffer
+}
+
+#[gpu_test]
+static TEXTURE_REINTERPRETATION_VIEW: GpuTestConfiguration = GpuTestConfiguration::new()
+    .parameters(parameters())
+    .run_async(|ctx| async move {
+        let texture = create_texel_texture(
+            &ctx,
+            wgpu::TextureUsages::TEXTURE_BINDING,
+            &[wgpu::TextureFormat::R32Uint],
+        );
+        let view = texture.create_view(&wgpu::TextureViewDescriptor {
+            format: Some(wgpu::TextureFormat::R32Uint),
+            ..Default::default()
+        });
+        let buffer = ctx.device.create_buffer(&wgpu::BufferDescriptor {
+            label: None,
+            size: 4,
+            usage: wgpu::BufferUsages::STORAGE | wgpu::BufferUsages::COPY_SRC,
+            mapped_at_creation: false,
+        });
+        run_compute(
+            &ctx,
+            LOAD_SHADER,
+            &[
+                wgpu::BindingResource::TextureView(&view),
+                buffer.as_entire_binding(),
+            ],
+        );
+
+        let data = read_buffer(&ctx.device, &ctx.queue, &buffer, ..)
+            .await
+            .unwrap();
+        assert_eq!(data, TEXEL);
+    });
+
+#[gpu_test]
+static TEXTURE_REINTERPRETATION_BLOCK_STORAGE: GpuTestConfiguration = GpuTestConfiguration::new()
+    .parameters(parameters().features(wgpu::Features::TEXTURE_COMPRESSION_BC))
+    .run_async(|ctx| async move {
+        // Compressed textures can be written block by block through views that
+        // see each block as a texel, although their own format can't be stored to.
+        let texture = ctx.device.create_texture(&wgpu::TextureDescriptor {
+            label: None,
+            size: wgpu::Extent3d {
+                width: 4,
+                height: 4,
+                depth_or_array_layers: 1,
+            },
+            mip_level_count: 1,
+            sample_count: 1,
+            dimension: wgpu::TextureDimension::D2,
+            format: wgpu::TextureFormat::Bc1RgbaUnorm,
+            usage: wgpu::TextureUsages::STORAGE_BINDING | wgpu::TextureUsages::COPY_SRC,
+            view_formats: &[wgpu::TextureFormat::Rg32Uint],
+        });
+        let view = texture.create_view(&wgpu::TextureViewDescriptor {
+            format: Some(wgpu::TextureFormat::Rg32Uint),
+            ..Default::default()
+        });
+        run_compute(
+            &ctx,
+            STORE_SHADER,
+            &[wgpu::BindingResource::TextureView(&view)],
+        );
+
+        let buffer = copy_block(&ctx, &texture);
+        let data = read_buffer(&ctx.device, &ctx.queue, &buffer, ..)
+            .await
+            .unwrap();
+        let data: Vec<u32> = bytemuck::pod_collect_to_vec(&data);
+        assert_eq!(data, [0x01234567, 0x89abcdef]);
+    });
+
+#[gpu_test]
+static TEXTURE_REINTERPRETATION_COPY: GpuTestConfiguration = GpuTestConfiguration::new()
+    .parameters(parameters())
+    .run_async(|ctx| async move {
+        let src = create_texel_texture(&ctx, wgpu::TextureUsages::COPY_SRC, &[]);
+        let dst = create_texture(
+            &ctx,
+            wgpu::TextureFormat::Rg16Uint,
+            wgpu::TextureUsages::COPY_SRC | wgpu::TextureUsages::COPY_DST,
+            &[],
+        );
+
+        let mut encoder = ctx
+            .device
+            .create_command_encoder(&wgpu::CommandEncoderDescriptor::default());
+        encoder.copy_texture_to_texture(
+            src.as_image_copy(),
+            dst.as_image_copy(),
+            wgpu::Extent3d::default(),
+        );
+        ctx.queue.submit(Some(encoder.finish()));
+
+        let buffer = copy_block(&ctx, &dst);
+        let data = read_buffer(&ctx.device, &ctx.queue, &buffer, ..)
+            .await
+            .unwrap();
+        assert_eq!(data, TEXEL);
+    });
+
+#[gpu_test]
+static TEXTURE_REINTERPRETATION_INVALID: GpuTestConfiguration = GpuTestConfiguration::new()
+    .parameters(parameters().features(wgpu::Features::TEXTURE_COMPRESSION_BC))
+    .run_sync(|ctx| {
+        // Texel blocks of different sizes can't be reinterpreted.
+        fail(&ctx.device, || {
+            create_texture(
+                &ctx,
+                wgpu::TextureFormat::Rgba8Unorm,
+                wgpu::TextureUsages::TEXTURE_BINDING,
+                &[wgpu::TextureFormat::Rg8Unorm],
+            )
+        });
+
+        // Nor can texels be viewed as blocks.
+        fail(&ctx.device, || {
+            create_texture(
+                &ctx,
+                wgpu::TextureFormat::Rg32Uint,
+                wgpu::TextureUsages::TEXTURE_BINDING,
+                &[wgpu::TextureFormat::Bc1RgbaUnorm],
+            )
+        });
+
+        // Views that see blocks as texels can only have one mip level.
+        let texture = ctx.device.create_texture(&wgpu::TextureDescriptor {
+            label: None,
+            size: wgpu::Extent3d {
+                width: 8,
+                height: 8,
+                depth_or_array_layers: 1,
+            },
+            mip_level_count: 2,
+            sample_count: 1,
+            dimension: wgpu::TextureDimension::D2,
+            format: wgpu::TextureFormat::Bc1RgbaUnorm,
+            usage: wgpu::TextureUsages::TEXTURE_BINDING | wgpu::TextureUsages::COPY_SRC,
+            view_formats: &[wgpu::TextureFormat::Rg32Uint],
+        });
+        fail(&ctx.device, || {
+            texture.create_view(&wgpu::TextureViewDescriptor {
+                format: Some(wgpu::TextureFormat::Rg32Uint),
+                ..Default::default()
+            })
+        });
+        let _ = texture.create_view(&wgpu::TextureViewDescriptor {
+            format: Some(wgpu::TextureFormat::Rg32Uint),
+            mip_level_count: Some(1),
+            ..Default::default()
+        });
+
+        // Copies can't change the block dimensions.
+        let blocks = create_texture(
+            &ctx,
+            wgpu::TextureFormat::Rg32Uint,
+            wgpu::TextureUsages::COPY_DST,
+            &[],
+        );
+        let mut encoder = ctx
+            .device
+            .create_command_encoder(&wgpu::CommandEncoderDescriptor::default());
+        fail(&ctx.device, || {
+            encoder.copy_texture_to_texture(
+                texture.as_image_copy(),
+                blocks.as_image_copy(),
+                wgpu::Extent3d {
+                    width: 4,
+                    height: 4,
+                    depth_or_array_layers: 1,
+                },
+            );
+            encoder.finish()
+        });
+    });
+
+#[gpu_test]
+static TEXTURE_REINTERPRETATION_WITHOUT_FEATURE: GpuTestConfiguration = GpuTestConfiguration::new()
+    .run_sync(|ctx| {
+        fail(&ctx.device, || {
+            create_texture(
+                &ctx,
+                wgpu::TextureFormat::Rgba8Unorm,
+                wgpu::TextureUsages::TEXTURE_BINDING,
+                &[wgpu::TextureFormat::Rgba8Uint],
+            )
+        });
+    });
@@ -0,0 +1,93 @@
+//! Tests for `wgpu::util::DeviceExt::create_texture_with_data_or_transcode`.
+
+use wgpu::util::{DeviceExt, TextureDataOrder};
+use wgpu_test::{gpu_test, image, GpuTestConfiguration, TestingContext};
+
+/// Creates a texture of `format` from `data`, which has to be transcoded as the
+/// test device doesn't enable texture compression features, and checks its texels.
+async fn transcode(
+    ctx: &TestingContext,
+    format: wgpu::TextureFormat,
+    size: u32,
+    data: &[u8],
+    expected_format: wgpu::TextureFormat,
+    expected: &[u8],
+) {
+    let texture = ctx.device.create_texture_with_data_or_transcode(
+        &ctx.queue,
+        &wgpu::TextureDescriptor {
+            label: None,
+            size: wgpu::Extent3d {
+                width: size,
+                height: size,
+                depth_or_array_layers: 1,
+            },
+            mip_level_count: 1,
+            sample_count: 1,
+            dimension: wgpu::TextureDimension::D2,
+            format,
+            usage: wgpu::TextureUsages::COPY_SRC | wgpu::TextureUsages::TEXTURE_BINDING,
+            view_formats: &[],
+        },
+        TextureDataOrder::LayerMajor,
+        data,
+    );
+    assert_eq!(texture.format(), expected_format);
+
+    let readback_buffer = image::ReadbackBuffers::new(&ctx.device, &texture);
+    let mut encoder = ctx
+        .device
+        .create_command_encoder(&wgpu::CommandEncoderDescriptor::default());
+    readback_buffer.copy_from(&ctx.device, &mut encoder, &texture);
+    ctx.queue.submit(Some(encoder.finish()));
+    readback_buffer.assert_buffer_contents(ctx, expected).await;
+}
+
+#[gpu_test]
+static TRANSCODE_BC1: GpuTestConfiguration =
+    GpuTestConfiguration::new().run_async(|ctx| async move {
+        // Red and blue endpoints, with every row going from red to blue.
+        let indices = u32::from_le_bytes([0b01_11_10_00; 4]);
+        let mut block = vec![0x00, 0xF8, 0x1F, 0x00];
+        block.extend(indices.to_le_bytes());
+
+        let row = [
+            [255, 0, 0, 255],
+            [170, 0, 85, 255],
+            [85, 0, 170, 255],
+            [0, 0, 255, 255],
+        ];
+        let expected = row.repeat(4).concat();
+        transcode(
+            &ctx,
+            wgpu::TextureFormat::Bc1RgbaUnorm,
+            4,
+            &block,
+            wgpu::TextureFormat::Rgba8Unorm,
+            &expected,
+        )
+        .await;
+    });
+
+#[gpu_test]
+static TRANSCODE_ASTC: GpuTestConfiguration =
+    GpuTestConfiguration::new().run_async(|ctx| async move {
+        // A block with a single color, which is orange.
+        let mut block = 0x1FCu128 | (0b11 << 10) | (((1 << 52) - 1) << 12);
+        block |= (0xFF00 << 64) | (0x8000 << 80) | (0xFFFF << 112);
+
+        let data = block.to_le_bytes().repeat(4);
+        let expected = [255, 128, 0, 255].repeat(8 * 8);
+        transcode(
+            &ctx,
+            wgpu::TextureFormat::Astc {
+                block: wgpu::AstcBlock::B4x4,
+                channel: wgpu::AstcChannel::UnormSrgb,
+            },
+            8,
+            &data,
+            wgpu::TextureFormat::Rgba8UnormSrgb,
+            &expected,
+        )
+        .await;
+    });
@@ -0,0 +1,209 @@
+//! Tests for texture views with a `TextureComponentSwizzle`.
+
+use wgpu::util::{read_buffer, DeviceExt};
+use wgpu::{ComponentSwizzle, TextureComponentSwizzle};
+use wgpu_test::{
+    fail, gpu_test, FailureCase, GpuTestConfiguration, TestParameters, TestingContext,
+};
+
+const SHADER: &str = "
+@group(0) @binding(0) var texture: texture_2d<f32>;
+@group(0) @binding(1) var<storage, read_write> texel: array<u32, 4>;
+
+@compute @workgroup_size(1)
+fn main() {
+    let value = textureLoad(texture, vec2<i32>(0), 0);
+    for (var i = 0; i < 4; i++) {
+        texel[i] = u32(round(value[i] * 255.0));
+    }
+}
+";
+
+const SWIZZLE: TextureComponentSwizzle = TextureComponentSwizzle {
+    r: ComponentSwizzle::B,
+    g: ComponentSwizzle::Zero,
+    b: ComponentSwizzle::R,
+    a: ComponentSwizzle::One,
+};
+
+fn create_texture(ctx: &TestingContext, usage: wgpu::TextureUsages) -> wgpu::Texture {
+    ctx.device.create_texture_with_data(
+        &ctx.queue,
+        &wgpu::TextureDescriptor {
+            label: None,
+            size: wgpu::Extent3d::default(),
+            mip_level_count: 1,
+            sample_count: 1,
+            dimension: wgpu::TextureDimension::D2,
+            format: wgpu::TextureFormat::Rgba8Unorm,
+            usage: usage | wgpu::TextureUsages::COPY_DST,
+            view_formats: &[],
+        },
+        wgpu::util::TextureDataOrder::LayerMajor,
+        &[10, 20, 30, 40],
+    )
+}
+
+fn parameters() -> TestParameters {
+    TestParameters::default()
+        .features(wgpu::Features::TEXTURE_COMPONENT_SWIZZLE)
+        .downlevel_flags(wgpu::DownlevelFlags::COMPUTE_SHADERS)
+        .limits(wgpu::Limits::downlevel_defaults())
+}
+
+#[gpu_test]
+static TEXTURE_VIEW_SWIZZLE_LOAD: GpuTestConfiguration = GpuTestConfiguration::new()
+    .parameters(parameters())
+    .run_async(|ctx| async move {
+        let module = ctx
+            .device
+            .create_shader_module(wgpu::ShaderModuleDescriptor {
+                label: None,
+                source: wgpu::ShaderSource::Wgsl(SHADER.into()),
+                diagnostic_filters: &[],
+            });
+        let pipeline = ctx
+            .device
+            .create_compute_pipeline(&wgpu::ComputePipelineDescriptor {
+                label: None,
+                layout: None,
+                module: &module,
+                entry_point: "main",
+                compilation_options: Default::default(),
+            });
+        let buffer = ctx.device.create_buffer(&wgpu::BufferDescriptor {
+            label: None,
+            size: 16,
+            usage: wgpu::BufferUsages::STORAGE | wgpu::BufferUsages::COPY_SRC,
+            mapped_at_creation: false,
+        });
+
+        let texture = create_texture(&ctx, wgpu::TextureUsages::TEXTURE_BINDING);
+        for (swizzle, expected) in [
+            (TextureComponentSwizzle::IDENTITY, [10, 20, 30, 40]),
+            (SWIZZLE, [30, 0, 10, 255]),
+        ] {
+            let view = texture.create_view(&wgpu::TextureViewDescriptor {
+                swizzle,
+                ..Default::default()
+            });
+            let bind_group = ctx.device.create_bind_group(&wgpu::BindGroupDescriptor {
+                label: None,
+                layout: &pipeline.get_bind_group_layout(0),
+                entries: &[
+                    wgpu::BindGroupEntry {
+                        binding: 0,
+                        resource: wgpu::BindingResource::TextureView(&view),
+                    },
+                    wgpu::BindGroupEntry {
+                        binding: 1,
+                        resource: buffer.as_entire_binding(),
+                    },
+                ],
+            });
+
+            let mut encoder = ctx
+                .device
+                .create_command_encoder(&wgpu::CommandEncoderDescriptor::default());
+            let mut pass = encoder.begin_compute_pass(&wgpu::ComputePassDescriptor::default());
+            pass.set_pipeline(&pipeline);
+            pass.set_bind_group(0, &bind_group, &[]);
+            pass.dispatch_workgroups(1, 1, 1);
+            drop(pass);
+            ctx.queue.submit(Some(encoder.finish()));
+
+            let data = read_buffer(&ctx.device, &ctx.queue, &buffer, ..)
+                .await
+                .unwrap();
+            let data: Vec<u32> = bytemuck::pod_collect_to_vec(&data);
+            assert_eq!(data, expected, "swizzle {swizzle:?}");
+        }
+    });
+
+#[gpu_test]
+static TEXTURE_VIEW_SWIZZLE_STORAGE: GpuTestConfiguration = GpuTestConfiguration::new()
+    .parameters(parameters())
+    .run_sync(|ctx| {
+        let texture = create_texture(&ctx, wgpu::TextureUsages::STORAGE_BINDING);
+        let view = texture.create_view(&wgpu::TextureViewDescriptor {
+            swizzle: SWIZZLE,
+            ..Default::default()
+        });
+        let layout = ctx
+            .device
+            .create_bind_group_layout(&wgpu::BindGroupLayoutDescriptor {
+                label: None,
+                entries: &[wgpu::BindGroupLayoutEntry {
+                    binding: 0,
+                    visibility: wgpu::ShaderStages::COMPUTE,
+                    ty: wgpu::BindingType::StorageTexture {
+                        access: wgpu::StorageTextureAccess::WriteOnly,
+                        format: wgpu::TextureFormat::Rgba8Unorm,
+                        view_dimension: wgpu::TextureViewDimension::D2,
+                    },
+                    count: None,
+                }],
+            });
+
+        // Swizzled views can only be sampled.
+        fail(&ctx.device, || {
+            let _ = ctx.device.create_bind_group(&wgpu::BindGroupDescriptor {
+                label: None,
+                layout: &layout,
+                entries: &[wgpu::BindGroupEntry {
+                    binding: 0,
+                    resource: wgpu::BindingResource::TextureView(&view),
+                }],
+            });
+        });
+    });
+
+#[gpu_test]
+static TEXTURE_VIEW_SWIZZLE_RENDER_ATTACHMENT: GpuTestConfiguration = GpuTestConfiguration::new()
+    .parameters(parameters())
+    .run_sync(|ctx| {
+        let texture = create_texture(&ctx, wgpu::TextureUsages::RENDER_ATTACHMENT);
+        let view = texture.create_view(&wgpu::TextureViewDescriptor {
+            swizzle: SWIZZLE,
+            ..Default::default()
+        });
+
+        fail(&ctx.device, || {
+            let mut encoder = ctx
+                .device
+                .create_command_encoder(&wgpu::CommandEncoderDescriptor::default());
+            let _ = encoder.begin_render_pass(&wgpu::RenderPassDescriptor {
+                label: None,
+                color_attachments: &[Some(wgpu::RenderPassColorAttachment {
+                    view: &view,
+                    resolve_target: None,
+                    ops: wgpu::Operations::default(),
+                })],
+                depth_stencil_attachment: None,
+                timestamp_writes: None,
+                occlusion_query_set: None,
+            });
+            encoder.finish()
+        });
+    });
+
+#[gpu_test]
+static TEXTURE_VIEW_SWIZZLE_WITHOUT_FEATURE: GpuTestConfiguration = GpuTestConfiguration::new()
+    .parameters(
+        // The swizzle is ignored on WebGPU.
+        TestParameters::default().skip(FailureCase::backend(wgpu::Backends::BROWSER_WEBGPU)),
+    )
+    .run_sync(|ctx| {
+        let texture = create_texture(&ctx, wgpu::TextureUsages::TEXTURE_BINDING);
+        fail(&ctx.device, || {
+            let _ = texture.create_view(&wgpu::TextureViewDescriptor {
+                swizzle: SWIZZLE,
+                ..Default::default()
+            });
+        });
+        // The identity needs no feature.
+        let _ = texture.create_view(&wgpu::TextureViewDescriptor {
+            swizzle: TextureComponentSwizzle::IDENTITY,
+            ..Default::default()
+        });
+    });
@@ -0,0 +1,88 @@
+//! Tests for timeline semaphores, see `Features::TIMELINE_SEMAPHORE`.
+
+use std::time::Duration;
+
+use wgpu::{
+    BufferDescriptor, BufferUsages, CommandEncoderDescriptor, Features, Maintain, MapMode,
+    TimelineSemaphoreDescriptor,
+};
+
+use wgpu_test::{gpu_test, GpuTestConfiguration, TestParameters};
+
+const SIZE: u64 = 256;
+
+#[gpu_test]
+static HOST_SIGNAL_AND_WAIT: GpuTestConfiguration = GpuTestConfiguration::new()
+    .parameters(TestParameters::default().features(Features::TIMELINE_SEMAPHORE))
+    .run_sync(|ctx| {
+        let semaphore = ctx
+            .device
+            .create_timeline_semaphore(&TimelineSemaphoreDescriptor {
+                label: None,
+                initial_value: 1,
+            });
+        assert_eq!(semaphore.value(), 1);
+
+        semaphore.signal(5);
+        assert_eq!(semaphore.value(), 5);
+        assert!(semaphore.wait(5, Duration::ZERO));
+        assert!(!semaphore.wait(6, Duration::ZERO));
+    });
+
+#[gpu_test]
+static SUBMIT_WAITS_AND_SIGNALS: GpuTestConfiguration = GpuTestConfiguration::new()
+    .parameters(TestParameters::default().features(Features::TIMELINE_SEMAPHORE))
+    .run_async(|ctx| async move {
+        let semaphore = ctx
+            .device
+            .create_timeline_semaphore(&TimelineSemaphoreDescriptor::default());
+
+        let make_buffer = |usage| {
+            ctx.device.create_buffer(&BufferDescriptor {
+                label: None,
+                size: SIZE,
+                usage,
+                mapped_at_creation: false,
+            })
+        };
+        let source = make_buffer(BufferUsages::COPY_SRC | BufferUsages::COPY_DST);
+        let readback = make_buffer(BufferUsages::MAP_READ | BufferUsages::COPY_DST);
+
+        let data: Vec<u8> = (0..SIZE).map(|i| i as u8).collect();
+        ctx.queue.write_buffer(&source, 0, &data);
+
+        // The copy can't start before the host signals 1, and signals 2 itself.
+        let mut encoder = ctx
+            .device
+            .create_command_encoder(&CommandEncoderDescriptor::default());
+        encoder.copy_buffer_to_buffer(&source, 0, &readback, 0, SIZE);
+        ctx.queue.submit_with_semaphores(
+            Some(encoder.finish()),
+            &[(&semaphore, 1)],
+            &[(&semaphore, 2)],
+        );
+        assert!(!semaphore.wait(2, Duration::ZERO));
+
+        semaphore.signal(1);
+        assert!(semaphore.wait(2, Duration::from_secs(5)));
+
+        readback.slice(..).map_async(MapMode::Read, Result::unwrap);
+        ctx.async_poll(Maintain::wait()).await.panic_on_timeout();
+
+        assert_eq!(&*readback.slice(..).get_mapped_range(), &data[..]);
+    });
+
+#[gpu_test]
+static SIGNAL_VALUE_MUST_INCREASE: GpuTestConfiguration = GpuTestConfiguration::new()
+    .parameters(TestParameters::default().features(Features::TIMELINE_SEMAPHORE))
+    .run_sync(|ctx| {
+        let semaphore = ctx
+            .device
+            .create_timeline_semaphore(&TimelineSemaphoreDescriptor {
+                label: None,
+                initial_value: 3,
+            });
+
+        wgpu_test::fail(&ctx.device, || semaphore.signal(3));
+        assert_eq!(semaphore.value(), 3);
+    });
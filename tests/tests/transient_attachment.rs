@@ -0,0 +1,187 @@
+//! Tests for textures with `TextureUsages::TRANSIENT`.
+
+use wgpu_test::{
+    fail, gpu_test, FailureCase, GpuTestConfiguration, TestParameters, TestingContext,
+};
+
+const SIZE: wgpu::Extent3d = wgpu::Extent3d {
+    width: 1,
+    height: 1,
+    depth_or_array_layers: 1,
+};
+
+const CLEAR: wgpu::Operations<wgpu::Color> = wgpu::Operations {
+    load: wgpu::LoadOp::Clear(wgpu::Color::RED),
+    store: wgpu::StoreOp::Discard,
+};
+
+fn create_texture(
+    ctx: &TestingContext,
+    format: wgpu::TextureFormat,
+    sample_count: u32,
+    usage: wgpu::TextureUsages,
+) -> wgpu::Texture {
+    ctx.device.create_texture(&wgpu::TextureDescriptor {
+        label: None,
+        size: SIZE,
+        mip_level_count: 1,
+        sample_count,
+        dimension: wgpu::TextureDimension::D2,
+        format,
+        usage,
+        view_formats: &[],
+    })
+}
+
+fn create_transient_view(
+    ctx: &TestingContext,
+    format: wgpu::TextureFormat,
+    sample_count: u32,
+) -> wgpu::TextureView {
+    create_texture(
+        ctx,
+        format,
+        sample_count,
+        wgpu::TextureUsages::RENDER_ATTACHMENT | wgpu::TextureUsages::TRANSIENT,
+    )
+    .create_view(&wgpu::TextureViewDescriptor::default())
+}
+
+fn validation_parameters() -> TestParameters {
+    // `TRANSIENT` is dropped on WebGPU, so nothing is validated.
+    TestParameters::default().skip(FailureCase::backend(wgpu::Backends::BROWSER_WEBGPU))
+}
+
+/// Records a pass with a color attachment of `view`, resolved to `resolve_target`.
+fn color_pass(
+    ctx: &TestingContext,
+    view: &wgpu::TextureView,
+    resolve_target: Option<&wgpu::TextureView>,
+    ops: wgpu::Operations<wgpu::Color>,
+) -> wgpu::CommandBuffer {
+    let mut encoder = ctx
+        .device
+        .create_command_encoder(&wgpu::CommandEncoderDescriptor::default());
+    encoder.begin_render_pass(&wgpu::RenderPassDescriptor {
+        label: None,
+        color_attachments: &[Some(wgpu::RenderPassColorAttachment {
+            view,
+            resolve_target,
+            ops,
+        })],
+        depth_stencil_attachment: None,
+        timestamp_writes: None,
+        occlusion_query_set: None,
+    });
+    encoder.finish()
+}
+
+#[gpu_test]
+static TRANSIENT_ATTACHMENT_RESOLVE: GpuTestConfiguration =
+    GpuTestConfiguration::new().run_async(|ctx| async move {
+        let format = wgpu::TextureFormat::Rgba8Unorm;
+        let color = create_transient_view(&ctx, format, 4);
+        let depth = create_transient_view(&ctx, wgpu::TextureFormat::Depth32Float, 4);
+        let target = create_texture(
+            &ctx,
+            format,
+            1,
+            wgpu::TextureUsages::RENDER_ATTACHMENT | wgpu::TextureUsages::COPY_SRC,
+        );
+        let target_view = target.create_view(&wgpu::TextureViewDescriptor::default());
+        let read_buffer = ctx.device.create_buffer(&wgpu::BufferDescriptor {
+            label: None,
+            size: 4,
+            usage: wgpu::BufferUsages::MAP_READ | wgpu::BufferUsages::COPY_DST,
+            mapped_at_creation: false,
+        });
+
+        let mut encoder = ctx
+            .device
+            .create_command_encoder(&wgpu::CommandEncoderDescriptor::default());
+        encoder.begin_render_pass(&wgpu::RenderPassDescriptor {
+            label: None,
+            color_attachments: &[Some(wgpu::RenderPassColorAttachment {
+                view: &color,
+                resolve_target: Some(&target_view),
+                ops: CLEAR,
+            })],
+            depth_stencil_attachment: Some(wgpu::RenderPassDepthStencilAttachment {
+                view: &depth,
+                depth_ops: Some(wgpu::Operations {
+                    load: wgpu::LoadOp::Clear(1.0),
+                    store: wgpu::StoreOp::Discard,
+                }),
+                stencil_ops: None,
+            }),
+            timestamp_writes: None,
+            occlusion_query_set: None,
+        });
+        encoder.copy_texture_to_buffer(
+            target.as_image_copy(),
+            wgpu::ImageCopyBuffer {
+                buffer: &read_buffer,
+                layout: wgpu::ImageDataLayout::default(),
+            },
+            SIZE,
+        );
+        ctx.queue.submit(Some(encoder.finish()));
+
+        let slice = read_buffer.slice(..);
+        slice.map_async(wgpu::MapMode::Read, |_| ());
+        ctx.async_poll(wgpu::Maintain::wait())
+            .await
+            .panic_on_timeout();
+        // The contents of the transient texture only reach memory through the
+        // resolve.
+        assert_eq!(&slice.get_mapped_range()[..], [255, 0, 0, 255]);
+    });
+
+#[gpu_test]
+static TRANSIENT_ATTACHMENT_INVALID_USAGE: GpuTestConfiguration = GpuTestConfiguration::new()
+    .parameters(validation_parameters())
+    .run_sync(|ctx| {
+        for usage in [
+            wgpu::TextureUsages::TRANSIENT,
+            wgpu::TextureUsages::TRANSIENT
+                | wgpu::TextureUsages::RENDER_ATTACHMENT
+                | wgpu::TextureUsages::TEXTURE_BINDING,
+            wgpu::TextureUsages::TRANSIENT
+                | wgpu::TextureUsages::RENDER_ATTACHMENT
+                | wgpu::TextureUsages::COPY_SRC,
+        ] {
+            fail(&ctx.device, || {
+                create_texture(&ctx, wgpu::TextureFormat::Rgba8Unorm, 1, usage)
+            });
+        }
+    });
+
+#[gpu_test]
+static TRANSIENT_ATTACHMENT_INVALID_OPS: GpuTestConfiguration = GpuTestConfiguration::new()
+    .parameters(validation_parameters())
+    .run_sync(|ctx| {
+        let format = wgpu::TextureFormat::Rgba8Unorm;
+        let view = create_transient_view(&ctx, format, 1);
+
+        // Transient textures can't be loaded or stored.
+        for ops in [
+            wgpu::Operations {
+                load: wgpu::LoadOp::Load,
+                store: wgpu::StoreOp::Discard,
+            },
+            wgpu::Operations {
+                load: wgpu::LoadOp::Clear(wgpu::Color::RED),
+                store: wgpu::StoreOp::Store,
+            },
+        ] {
+            fail(&ctx.device, || color_pass(&ctx, &view, None, ops));
+        }
+
+        // Nor be resolved to.
+        let multisampled = create_transient_view(&ctx, format, 4);
+        fail(&ctx.device, || {
+            color_pass(&ctx, &multisampled, Some(&view), CLEAR)
+        });
+
+        let _ = color_pass(&ctx, &view, None, CLEAR);
+    });
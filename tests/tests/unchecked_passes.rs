@@ -0,0 +1,131 @@
+//! Tests for devices created without the validation of passes, with
+//! `DeviceValidation::with_level(ValidationLevel::Standard)`, as shipping
+//! builds whose command encoding was validated in testing do.
+
+use wgpu::{
+    util::{read_buffer, BufferInitDescriptor, DeviceExt},
+    DeviceValidation, ValidationLevel,
+};
+use wgpu_test::{fail, gpu_test, FailureCase, GpuTestConfiguration, TestParameters};
+
+const SHADER: &str = "
+@group(0) @binding(0) var<storage, read_write> data: array<u32>;
+
+@compute @workgroup_size(1)
+fn main(@builtin(global_invocation_id) id: vec3<u32>) {
+    data[id.x] += id.x + 1u;
+}
+";
+
+fn parameters() -> TestParameters {
+    TestParameters::default()
+        // SAFETY: the passes of these tests are valid.
+        .device_validation(unsafe { DeviceValidation::with_level(ValidationLevel::Standard) })
+        .downlevel_flags(
+            wgpu::DownlevelFlags::COMPUTE_SHADERS | wgpu::DownlevelFlags::INDIRECT_EXECUTION,
+        )
+        .limits(wgpu::Limits::downlevel_defaults())
+        // The browser always validates in full.
+        .skip(FailureCase::backend(wgpu::Backends::BROWSER_WEBGPU))
+}
+
+#[gpu_test]
+static UNCHECKED_PASSES_DISPATCH: GpuTestConfiguration = GpuTestConfiguration::new()
+    .parameters(parameters())
+    .run_async(|ctx| async move {
+        let module = ctx
+            .device
+            .create_shader_module(wgpu::ShaderModuleDescriptor {
+                label: None,
+                source: wgpu::ShaderSource::Wgsl(SHADER.into()),
+                diagnostic_filters: &[],
+            });
+        let pipeline = ctx
+            .device
+            .create_compute_pipeline(&wgpu::ComputePipelineDescriptor {
+                label: None,
+                layout: None,
+                module: &module,
+                entry_point: "main",
+                compilation_options: Default::default(),
+            });
+        let buffer = ctx.device.create_buffer(&wgpu::BufferDescriptor {
+            label: None,
+            size: 16,
+            usage: wgpu::BufferUsages::STORAGE | wgpu::BufferUsages::COPY_SRC,
+            mapped_at_creation: false,
+        });
+        let indirect = ctx.device.create_buffer_init(&BufferInitDescriptor {
+            label: None,
+            contents: bytemuck::cast_slice(&[2u32, 1, 1]),
+            usage: wgpu::BufferUsages::INDIRECT,
+        });
+        let bind_group = ctx.device.create_bind_group(&wgpu::BindGroupDescriptor {
+            label: None,
+            layout: &pipeline.get_bind_group_layout(0),
+            entries: &[wgpu::BindGroupEntry {
+                binding: 0,
+                resource: buffer.as_entire_binding(),
+            }],
+        });
+
+        // Valid passes still run as they would with validation, with the
+        // resource states and zero initialization of the buffer tracked.
+        let mut encoder = ctx
+            .device
+            .create_command_encoder(&wgpu::CommandEncoderDescriptor::default());
+        let mut pass = encoder.begin_compute_pass(&wgpu::ComputePassDescriptor::default());
+        pass.set_pipeline(&pipeline);
+        pass.set_bind_group(0, &bind_group, &[]);
+        pass.dispatch_workgroups(4, 1, 1);
+        drop(pass);
+        let mut pass = encoder.begin_compute_pass(&wgpu::ComputePassDescriptor::default());
+        pass.set_pipeline(&pipeline);
+        pass.set_bind_group(0, &bind_group, &[]);
+        pass.dispatch_workgroups_indirect(&indirect, 0);
+        drop(pass);
+        ctx.queue.submit(Some(encoder.finish()));
+
+        let data = read_buffer(&ctx.device, &ctx.queue, &buffer, ..)
+            .await
+            .unwrap();
+        let data: Vec<u32> = bytemuck::pod_collect_to_vec(&data);
+        assert_eq!(data, [2, 4, 3, 4]);
+    });
+
+#[gpu_test]
+static UNCHECKED_PASSES_VALIDATE_COPIES: GpuTestConfiguration = GpuTestConfiguration::new()
+    .parameters(parameters())
+    .run_sync(|ctx| {
+        let buffer = ctx.device.create_buffer(&wgpu::BufferDescriptor {
+            label: None,
+            size: 16,
+            usage: wgpu::BufferUsages::STORAGE,
+            mapped_at_creation: false,
+        });
+
+        // Commands outside of passes are still validated.
+        let mut encoder = ctx
+            .device
+            .create_command_encoder(&wgpu::CommandEncoderDescriptor::default());
+        fail(&ctx.device, || {
+            encoder.copy_buffer_to_buffer(&buffer, 0, &buffer, 8, 8);
+            encoder.finish()
+        });
+    });
+
+#[gpu_test]
+static UNCHECKED_PASSES_REQUIRE_PIPELINE: GpuTestConfiguration = GpuTestConfiguration::new()
+    .parameters(parameters())
+    .run_sync(|ctx| {
+        // Dispatches without a pipeline never reach the backend.
+        let mut encoder = ctx
+            .device
+            .create_command_encoder(&wgpu::CommandEncoderDescriptor::default());
+        fail(&ctx.device, || {
+            let mut pass = encoder.begin_compute_pass(&wgpu::ComputePassDescriptor::default());
+            pass.dispatch_workgroups(1, 1, 1);
+            drop(pass);
+            encoder.finish()
+        });
+    });
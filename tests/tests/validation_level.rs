@@ -0,0 +1,226 @@
+//! Tests for `ValidationLevel`, `DeviceDescriptor::validation` and
+//! `Device::set_validation_level`.
+
+use wgpu::{
+    util::{read_buffer, BufferInitDescriptor, DeviceExt},
+    DeviceValidation, ValidationLevel,
+};
+use wgpu_test::{
+    fail, gpu_test, FailureCase, GpuTestConfiguration, TestParameters, TestingContext,
+};
+
+const SHADER: &str = "
+@group(0) @binding(0) var<storage, read_write> data: array<u32>;
+
+@compute @workgroup_size(1)
+fn main(@builtin(global_invocation_id) id: vec3<u32>) {
+    data[id.x] = id.x + 1u;
+}
+";
+
+fn parameters() -> TestParameters {
+    TestParameters::default()
+        .downlevel_flags(wgpu::DownlevelFlags::COMPUTE_SHADERS)
+        .limits(wgpu::Limits::downlevel_defaults())
+}
+
+fn create_storage_buffer(ctx: &TestingContext) -> wgpu::Buffer {
+    ctx.device.create_buffer(&wgpu::BufferDescriptor {
+        label: None,
+        size: 16,
+        usage: wgpu::BufferUsages::STORAGE | wgpu::BufferUsages::COPY_SRC,
+        mapped_at_creation: false,
+    })
+}
+
+#[gpu_test]
+static VALIDATION_LEVEL_DISPATCH: GpuTestConfiguration = GpuTestConfiguration::new()
+    .parameters(parameters())
+    .run_async(|ctx| async move {
+        for level in [
+            ValidationLevel::Minimal,
+            ValidationLevel::Standard,
+            ValidationLevel::Full,
+        ] {
+            // SAFETY: the workload is valid.
+            unsafe { ctx.device.set_validation_level(level) };
+
+            let module = ctx
+                .device
+                .create_shader_module(wgpu::ShaderModuleDescriptor {
+                    label: None,
+                    source: wgpu::ShaderSource::Wgsl(SHADER.into()),
+                    diagnostic_filters: &[],
+                });
+            let pipeline = ctx
+                .device
+                .create_compute_pipeline(&wgpu::ComputePipelineDescriptor {
+                    label: None,
+                    layout: None,
+                    module: &module,
+                    entry_point: "main",
+                    compilation_options: Default::default(),
+                });
+            let buffer = create_storage_buffer(&ctx);
+            let bind_group = ctx.device.create_bind_group(&wgpu::BindGroupDescriptor {
+                label: None,
+                layout: &pipeline.get_bind_group_layout(0),
+                entries: &[wgpu::BindGroupEntry {
+                    binding: 0,
+                    resource: buffer.as_entire_binding(),
+                }],
+            });
+
+            let mut encoder = ctx
+                .device
+                .create_command_encoder(&wgpu::CommandEncoderDescriptor::default());
+            let mut pass = encoder.begin_compute_pass(&wgpu::ComputePassDescriptor::default());
+            pass.set_pipeline(&pipeline);
+            pass.set_bind_group(0, &bind_group, &[]);
+            pass.dispatch_workgroups(4, 1, 1);
+            drop(pass);
+            ctx.queue.submit(Some(encoder.finish()));
+
+            let data = read_buffer(&ctx.device, &ctx.queue, &buffer, ..)
+                .await
+                .unwrap();
+            let data: Vec<u32> = bytemuck::pod_collect_to_vec(&data);
+            assert_eq!(data, [1, 2, 3, 4], "level {level:?}");
+        }
+    });
+
+#[gpu_test]
+static VALIDATION_LEVEL_MINIMAL: GpuTestConfiguration = GpuTestConfiguration::new()
+    .parameters(parameters())
+    .run_sync(|ctx| {
+        unsafe { ctx.device.set_validation_level(ValidationLevel::Minimal) };
+
+        // The creation of objects is still validated.
+        fail(&ctx.device, || {
+            ctx.device.create_buffer(&wgpu::BufferDescriptor {
+                label: None,
+                size: 16,
+                usage: wgpu::BufferUsages::MAP_READ | wgpu::BufferUsages::MAP_WRITE,
+                mapped_at_creation: false,
+            })
+        });
+
+        // And so are commands outside of passes.
+        let buffer = create_storage_buffer(&ctx);
+        let mut encoder = ctx
+            .device
+            .create_command_encoder(&wgpu::CommandEncoderDescriptor::default());
+        fail(&ctx.device, || {
+            encoder.copy_buffer_to_buffer(&buffer, 0, &buffer, 8, 8);
+            encoder.finish()
+        });
+    });
+
+#[gpu_test]
+static VALIDATION_LEVEL_RAISED: GpuTestConfiguration = GpuTestConfiguration::new()
+    .parameters(parameters())
+    .run_sync(|ctx| {
+        unsafe { ctx.device.set_validation_level(ValidationLevel::Standard) };
+        unsafe { ctx.device.set_validation_level(ValidationLevel::Full) };
+
+        // Passes are validated again once the level is back to full.
+        let mut encoder = ctx
+            .device
+            .create_command_encoder(&wgpu::CommandEncoderDescriptor::default());
+        fail(&ctx.device, || {
+            let mut pass = encoder.begin_compute_pass(&wgpu::ComputePassDescriptor::default());
+            pass.dispatch_workgroups(1, 1, 1);
+            drop(pass);
+            encoder.finish()
+        });
+    });
+
+#[gpu_test]
+static VALIDATION_LEVEL_STANDARD_INDIRECT: GpuTestConfiguration = GpuTestConfiguration::new()
+    .parameters(parameters().downlevel_flags(wgpu::DownlevelFlags::INDIRECT_EXECUTION))
+    .run_async(|ctx| async move {
+        unsafe { ctx.device.set_validation_level(ValidationLevel::Standard) };
+
+        let module = ctx
+            .device
+            .create_shader_module(wgpu::ShaderModuleDescriptor {
+                label: None,
+                source: wgpu::ShaderSource::Wgsl(SHADER.into()),
+                diagnostic_filters: &[],
+            });
+        let pipeline = ctx
+            .device
+            .create_compute_pipeline(&wgpu::ComputePipelineDescriptor {
+                label: None,
+                layout: None,
+                module: &module,
+                entry_point: "main",
+                compilation_options: Default::default(),
+            });
+        let buffer = create_storage_buffer(&ctx);
+        let indirect = ctx.device.create_buffer_init(&BufferInitDescriptor {
+            label: None,
+            contents: bytemuck::cast_slice(&[2u32, 1, 1]),
+            usage: wgpu::BufferUsages::INDIRECT,
+        });
+        let bind_group = ctx.device.create_bind_group(&wgpu::BindGroupDescriptor {
+            label: None,
+            layout: &pipeline.get_bind_group_layout(0),
+            entries: &[wgpu::BindGroupEntry {
+                binding: 0,
+                resource: buffer.as_entire_binding(),
+            }],
+        });
+
+        // The resource states and zero initialization of the buffer are still
+        // tracked for indirect dispatches.
+        let mut encoder = ctx
+            .device
+            .create_command_encoder(&wgpu::CommandEncoderDescriptor::default());
+        let mut pass = encoder.begin_compute_pass(&wgpu::ComputePassDescriptor::default());
+        pass.set_pipeline(&pipeline);
+        pass.set_bind_group(0, &bind_group, &[]);
+        pass.dispatch_workgroups_indirect(&indirect, 0);
+        drop(pass);
+        ctx.queue.submit(Some(encoder.finish()));
+
+        let data = read_buffer(&ctx.device, &ctx.queue, &buffer, ..)
+            .await
+            .unwrap();
+        let data: Vec<u32> = bytemuck::pod_collect_to_vec(&data);
+        assert_eq!(data, [1, 2, 0, 0]);
+    });
+
+#[gpu_test]
+static VALIDATION_LEVEL_STANDARD_MISSING_PIPELINE: GpuTestConfiguration =
+    GpuTestConfiguration::new()
+        .parameters(parameters())
+        .run_sync(|ctx| {
+            unsafe { ctx.device.set_validation_level(ValidationLevel::Standard) };
+
+            // Dispatches without a pipeline never reach the backend.
+            let mut encoder = ctx
+                .device
+                .create_command_encoder(&wgpu::CommandEncoderDescriptor::default());
+            fail(&ctx.device, || {
+                let mut pass = encoder.begin_compute_pass(&wgpu::ComputePassDescriptor::default());
+                pass.dispatch_workgroups(1, 1, 1);
+                drop(pass);
+                encoder.finish()
+            });
+        });
+
+#[gpu_test]
+static VALIDATION_LEVEL_AT_CREATION: GpuTestConfiguration = GpuTestConfiguration::new()
+    .parameters(
+        parameters()
+            .device_validation(unsafe { DeviceValidation::with_level(ValidationLevel::Standard) })
+            .skip(FailureCase::backend(wgpu::Backends::BROWSER_WEBGPU)),
+    )
+    .run_sync(|ctx| {
+        assert_eq!(ctx.device.validation_level(), ValidationLevel::Standard);
+
+        // The level set at creation can still be changed afterwards.
+        unsafe { ctx.device.set_validation_level(ValidationLevel::Full) };
+        assert_eq!(ctx.device.validation_level(), ValidationLevel::Full);
+    });
@@ -191,3 +191,92 @@ static WRITE_TEXTURE_SUBSET_3D: GpuTestConfiguration =
             assert_eq!(*byte, 0);
         }
     });
+
+#[gpu_test]
+static WRITE_TEXTURE_WITH: GpuTestConfiguration =
+    GpuTestConfiguration::new().run_async(|ctx| async move {
+        let size = wgpu::Extent3d {
+            width: 17,
+            height: 3,
+            depth_or_array_layers: 2,
+        };
+        let tex = ctx.device.create_texture(&wgpu::TextureDescriptor {
+            label: None,
+            dimension: wgpu::TextureDimension::D2,
+            size,
+            format: wgpu::TextureFormat::R8Uint,
+            usage: wgpu::TextureUsages::COPY_DST | wgpu::TextureUsages::COPY_SRC,
+            mip_level_count: 1,
+            sample_count: 1,
+            view_formats: &[],
+        });
+        let texel = |layer: u32, row: u32, x: u32| (layer * 64 + row * 20 + x) as u8;
+
+        {
+            let mut view = ctx
+                .queue
+                .write_texture_with(
+                    wgpu::ImageCopyTexture {
+                        texture: &tex,
+                        mip_level: 0,
+                        origin: wgpu::Origin3d::ZERO,
+                        aspect: wgpu::TextureAspect::All,
+                    },
+                    size,
+                )
+                .unwrap();
+            let bytes_per_row = view.bytes_per_row();
+            let rows_per_image = view.rows_per_image();
+            assert!(bytes_per_row >= size.width);
+            assert_eq!(rows_per_image, size.height);
+            for layer in 0..size.depth_or_array_layers {
+                for row in 0..size.height {
+                    let start = ((layer * rows_per_image + row) * bytes_per_row) as usize;
+                    for x in 0..size.width {
+                        view[start + x as usize] = texel(layer, row, x);
+                    }
+                }
+            }
+        }
+
+        let bytes_per_row = wgpu::COPY_BYTES_PER_ROW_ALIGNMENT;
+        let read_buffer = ctx.device.create_buffer(&wgpu::BufferDescriptor {
+            label: None,
+            size: (bytes_per_row * size.height * size.depth_or_array_layers) as u64,
+            usage: wgpu::BufferUsages::MAP_READ | wgpu::BufferUsages::COPY_DST,
+            mapped_at_creation: false,
+        });
+
+        let mut encoder = ctx
+            .device
+            .create_command_encoder(&wgpu::CommandEncoderDescriptor { label: None });
+        encoder.copy_texture_to_buffer(
+            tex.as_image_copy(),
+            wgpu::ImageCopyBuffer {
+                buffer: &read_buffer,
+                layout: wgpu::ImageDataLayout {
+                    offset: 0,
+                    bytes_per_row: Some(bytes_per_row),
+                    rows_per_image: Some(size.height),
+                },
+            },
+            size,
+        );
+        ctx.queue.submit(Some(encoder.finish()));
+
+        let slice = read_buffer.slice(..);
+        slice.map_async(wgpu::MapMode::Read, |_| ());
+        ctx.async_poll(wgpu::Maintain::wait())
+            .await
+            .panic_on_timeout();
+        let data: Vec<u8> = slice.get_mapped_range().to_vec();
+
+        for layer in 0..size.depth_or_array_layers {
+            for row in 0..size.height {
+                let start = ((layer * size.height + row) * bytes_per_row) as usize;
+                for x in 0..size.width {
+                    assert_eq!(data[start + x as usize], texel(layer, row, x));
+                }
+            }
+        }
+    });
@@ -0,0 +1,239 @@
+//! Tests for sampling multi-planar textures with a sampler Y'CbCr conversion,
+//! and for p010 textures.
+
+use wgpu_test::{fail, gpu_test, GpuTestConfiguration, TestParameters, TestingContext};
+
+const SHADER: &str = "
+@vertex
+fn vs_main(@builtin(vertex_index) index: u32) -> @builtin(position) vec4<f32> {
+    let uv = vec2<f32>(f32((index << 1u) & 2u), f32(index & 2u));
+    return vec4<f32>(uv * 2.0 - 1.0, 0.0, 1.0);
+}
+
+@group(0) @binding(0) var frame: texture_2d<f32>;
+@group(0) @binding(1) var frame_sampler: sampler;
+
+@fragment
+fn fs_main(@builtin(position) position: vec4<f32>) -> @location(0) vec4<f32> {
+    let uv = position.xy / vec2<f32>(textureDimensions(frame));
+    return textureSample(frame, frame_sampler, uv);
+}
+";
+
+const SIZE: wgpu::Extent3d = wgpu::Extent3d {
+    width: 64,
+    height: 64,
+    depth_or_array_layers: 1,
+};
+
+fn create_texture(ctx: &TestingContext, format: wgpu::TextureFormat) -> wgpu::Texture {
+    ctx.device.create_texture(&wgpu::TextureDescriptor {
+        label: None,
+        dimension: wgpu::TextureDimension::D2,
+        size: SIZE,
+        format,
+        usage: wgpu::TextureUsages::TEXTURE_BINDING,
+        mip_level_count: 1,
+        sample_count: 1,
+        view_formats: &[],
+    })
+}
+
+fn create_bind_group_layout(
+    ctx: &TestingContext,
+    format: wgpu::TextureFormat,
+) -> wgpu::BindGroupLayout {
+    ctx.device
+        .create_bind_group_layout(&wgpu::BindGroupLayoutDescriptor {
+            label: None,
+            entries: &[
+                wgpu::BindGroupLayoutEntry {
+                    binding: 0,
+                    visibility: wgpu::ShaderStages::FRAGMENT,
+                    ty: wgpu::BindingType::YcbcrTexture {
+                        format,
+                        conversion: wgpu::SamplerYcbcrConversion::default(),
+                    },
+                    count: None,
+                },
+                wgpu::BindGroupLayoutEntry {
+                    binding: 1,
+                    visibility: wgpu::ShaderStages::FRAGMENT,
+                    ty: wgpu::BindingType::Sampler(wgpu::SamplerBindingType::Filtering),
+                    count: None,
+                },
+            ],
+        })
+}
+
+fn create_bind_group(
+    ctx: &TestingContext,
+    layout: &wgpu::BindGroupLayout,
+    view: &wgpu::TextureView,
+) -> wgpu::BindGroup {
+    let sampler = ctx.device.create_sampler(&wgpu::SamplerDescriptor {
+        min_filter: wgpu::FilterMode::Linear,
+        mag_filter: wgpu::FilterMode::Linear,
+        ..Default::default()
+    });
+    ctx.device.create_bind_group(&wgpu::BindGroupDescriptor {
+        label: None,
+        layout,
+        entries: &[
+            wgpu::BindGroupEntry {
+                binding: 0,
+                resource: wgpu::BindingResource::TextureView(view),
+            },
+            wgpu::BindGroupEntry {
+                binding: 1,
+                resource: wgpu::BindingResource::Sampler(&sampler),
+            },
+        ],
+    })
+}
+
+#[gpu_test]
+static YCBCR_TEXTURE_SAMPLING: GpuTestConfiguration =
+    GpuTestConfiguration::new()
+        .parameters(TestParameters::default().features(
+            wgpu::Features::TEXTURE_FORMAT_NV12 | wgpu::Features::SAMPLER_YCBCR_CONVERSION,
+        ))
+        .run_sync(|ctx| {
+            let target_format = wgpu::TextureFormat::Rgba8Unorm;
+            let shader = ctx
+                .device
+                .create_shader_module(wgpu::ShaderModuleDescriptor {
+                    label: None,
+                    source: wgpu::ShaderSource::Wgsl(SHADER.into()),
+                    diagnostic_filters: &[],
+                });
+            let bind_group_layout = create_bind_group_layout(&ctx, wgpu::TextureFormat::NV12);
+            let layout = ctx
+                .device
+                .create_pipeline_layout(&wgpu::PipelineLayoutDescriptor {
+                    label: None,
+                    bind_group_layouts: &[&bind_group_layout],
+                    push_constant_ranges: &[],
+                });
+            let pipeline = ctx
+                .device
+                .create_render_pipeline(&wgpu::RenderPipelineDescriptor {
+                    label: None,
+                    layout: Some(&layout),
+                    vertex: wgpu::VertexState {
+                        module: &shader,
+                        entry_point: "vs_main",
+                        compilation_options: Default::default(),
+                        buffers: &[],
+                    },
+                    fragment: Some(wgpu::FragmentState {
+                        module: &shader,
+                        entry_point: "fs_main",
+                        compilation_options: Default::default(),
+                        targets: &[Some(target_format.into())],
+                    }),
+                    primitive: wgpu::PrimitiveState::default(),
+                    depth_stencil: None,
+                    multisample: wgpu::MultisampleState::default(),
+                    multiview: None,
+                });
+
+            // Views of all the planes are sampled as RGB.
+            let texture = create_texture(&ctx, wgpu::TextureFormat::NV12);
+            let view = texture.create_view(&wgpu::TextureViewDescriptor::default());
+            let bind_group = create_bind_group(&ctx, &bind_group_layout, &view);
+
+            let target = ctx.device.create_texture(&wgpu::TextureDescriptor {
+                label: None,
+                size: SIZE,
+                mip_level_count: 1,
+                sample_count: 1,
+                dimension: wgpu::TextureDimension::D2,
+                format: target_format,
+                usage: wgpu::TextureUsages::RENDER_ATTACHMENT,
+                view_formats: &[],
+            });
+            let target_view = target.create_view(&wgpu::TextureViewDescriptor::default());
+
+            let mut encoder = ctx
+                .device
+                .create_command_encoder(&wgpu::CommandEncoderDescriptor::default());
+            let mut pass = encoder.begin_render_pass(&wgpu::RenderPassDescriptor {
+                label: None,
+                color_attachments: &[Some(wgpu::RenderPassColorAttachment {
+                    ops: wgpu::Operations::default(),
+                    resolve_target: None,
+                    view: &target_view,
+                })],
+                depth_stencil_attachment: None,
+                timestamp_writes: None,
+                occlusion_query_set: None,
+            });
+            pass.set_pipeline(&pipeline);
+            pass.set_bind_group(0, &bind_group, &[]);
+            pass.draw(0..3, 0..1);
+            drop(pass);
+            ctx.queue.submit(Some(encoder.finish()));
+        });
+
+#[gpu_test]
+static YCBCR_TEXTURE_PLANE_VIEW: GpuTestConfiguration =
+    GpuTestConfiguration::new()
+        .parameters(TestParameters::default().features(
+            wgpu::Features::TEXTURE_FORMAT_NV12 | wgpu::Features::SAMPLER_YCBCR_CONVERSION,
+        ))
+        .run_sync(|ctx| {
+            let bind_group_layout = create_bind_group_layout(&ctx, wgpu::TextureFormat::NV12);
+            let texture = create_texture(&ctx, wgpu::TextureFormat::NV12);
+            let view = texture.create_view(&wgpu::TextureViewDescriptor {
+                format: Some(wgpu::TextureFormat::R8Unorm),
+                aspect: wgpu::TextureAspect::Plane0,
+                ..Default::default()
+            });
+            fail(&ctx.device, || {
+                let _ = create_bind_group(&ctx, &bind_group_layout, &view);
+            });
+        });
+
+#[gpu_test]
+static YCBCR_TEXTURE_NON_PLANAR_FORMAT: GpuTestConfiguration = GpuTestConfiguration::new()
+    .parameters(TestParameters::default().features(wgpu::Features::SAMPLER_YCBCR_CONVERSION))
+    .run_sync(|ctx| {
+        fail(&ctx.device, || {
+            let _ = create_bind_group_layout(&ctx, wgpu::TextureFormat::R8Unorm);
+        });
+    });
+
+#[gpu_test]
+static YCBCR_TEXTURE_MISSING_FEATURE: GpuTestConfiguration =
+    GpuTestConfiguration::new().run_sync(|ctx| {
+        // Backends without Y'CbCr conversions, the browser included, never
+        // get to see such a layout.
+        fail(&ctx.device, || {
+            let _ = create_bind_group_layout(&ctx, wgpu::TextureFormat::NV12);
+        });
+    });
+
+#[gpu_test]
+static P010_TEXTURE_VIEW_PLANES: GpuTestConfiguration = GpuTestConfiguration::new()
+    .parameters(TestParameters::default().features(wgpu::Features::TEXTURE_FORMAT_P010))
+    .run_sync(|ctx| {
+        let texture = create_texture(&ctx, wgpu::TextureFormat::P010);
+        let _ = texture.create_view(&wgpu::TextureViewDescriptor {
+            format: Some(wgpu::TextureFormat::R16Unorm),
+            aspect: wgpu::TextureAspect::Plane0,
+            ..Default::default()
+        });
+        let _ = texture.create_view(&wgpu::TextureViewDescriptor {
+            format: Some(wgpu::TextureFormat::Rg16Unorm),
+            aspect: wgpu::TextureAspect::Plane1,
+            ..Default::default()
+        });
+        fail(&ctx.device, || {
+            let _ = texture.create_view(&wgpu::TextureViewDescriptor {
+                format: Some(wgpu::TextureFormat::R8Unorm),
+                aspect: wgpu::TextureAspect::Plane0,
+                ..Default::default()
+            });
+        });
+    });
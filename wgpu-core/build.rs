@@ -8,6 +8,7 @@ fn main() {
         dx12: { all(target_os = "windows", feature = "dx12") },
         gles: { all(feature = "gles") },
         metal: { all(any(target_os = "ios", target_os = "macos"), feature = "metal") },
-        vulkan: { all(not(target_arch = "wasm32"), feature = "vulkan") }
+        vulkan: { all(not(target_arch = "wasm32"), feature = "vulkan") },
+        noop: { feature = "noop" }
     }
 }
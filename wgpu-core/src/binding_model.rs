@@ -40,10 +40,16 @@ pub enum BindGroupLayoutEntryError {
     SampleTypeFloatFilterableBindingMultisampled,
     #[error("Multisampled texture binding view dimension must be 2d, got {0:?}")]
     Non2DMultisampled(wgt::TextureViewDimension),
+    #[error("Y'CbCr texture binding format must be multi-planar, got {0:?}")]
+    YcbcrFormatNotMultiPlanar(wgt::TextureFormat),
     #[error(transparent)]
     MissingFeatures(#[from] MissingFeatures),
     #[error(transparent)]
     MissingDownlevelFlags(#[from] MissingDownlevelFlags),
+    #[error(
+        "Inline bind group layouts can only have buffer bindings without dynamic offsets or arrays"
+    )]
+    InlineUnsupported,
 }
 
 #[derive(Clone, Debug, Error)]
@@ -65,6 +71,13 @@ pub enum CreateBindGroupLayoutError {
     InvalidBindingIndex { binding: u32, maximum: u32 },
     #[error("Invalid visibility {0:?}")]
     InvalidVisibility(wgt::ShaderStages),
+    #[error(transparent)]
+    MissingFeatures(#[from] MissingFeatures),
+    #[error(
+        "Inline bind group layout has {0} bindings, but at most {} are allowed",
+        wgt::MAX_INLINE_BINDINGS
+    )]
+    TooManyInlineBindings(usize),
 }
 
 //TODO: refactor this to move out `enum BindingError`.
@@ -76,12 +89,16 @@ pub enum CreateBindGroupError {
     Device(#[from] DeviceError),
     #[error("Bind group layout is invalid")]
     InvalidLayout,
+    #[error("Bind groups can't be created with an inline bind group layout")]
+    InlineLayout,
     #[error("Buffer {0:?} is invalid or destroyed")]
     InvalidBuffer(BufferId),
     #[error("Texture view {0:?} is invalid")]
     InvalidTextureView(TextureViewId),
     #[error("Texture {0:?} is invalid")]
     InvalidTexture(TextureId),
+    #[error("Texture {0:?} is expired")]
+    ExpiredTexture(TextureId),
     #[error("Sampler {0:?} is invalid")]
     InvalidSampler(SamplerId),
     #[error(
@@ -165,6 +182,25 @@ pub enum CreateBindGroupError {
     },
     #[error("Storage texture bindings must have a single mip level, but given a view with mip_level_count = {mip_level_count:?} at binding {binding}")]
     InvalidStorageTextureMipLevelCount { binding: u32, mip_level_count: u32 },
+    #[error("Storage texture binding {binding} must be given a view without a swizzle, but given a view with swizzle = {swizzle:?}")]
+    InvalidStorageTextureSwizzle {
+        binding: u32,
+        swizzle: wgt::TextureComponentSwizzle,
+    },
+    #[error("Y'CbCr texture binding {binding} expects format = {layout_format:?}, but given a view with format = {view_format:?}")]
+    InvalidYcbcrTextureFormat {
+        binding: u32,
+        layout_format: wgt::TextureFormat,
+        view_format: wgt::TextureFormat,
+    },
+    #[error("Y'CbCr texture binding {binding} must be given a 2D view of all the planes of a single mip level and array layer, without a swizzle")]
+    InvalidYcbcrTextureView { binding: u32 },
+    #[error("Texture binding {binding} must be given a view of all {total} array layers of its texture in compatibility mode, but given a view of layers {layers:?}")]
+    PartialTextureViewInCompatibilityMode {
+        binding: u32,
+        layers: Range<u32>,
+        total: u32,
+    },
     #[error("Sampler binding {binding} expects comparison = {layout_cmp}, but given a sampler with comparison = {sampler_cmp}")]
     WrongSamplerComparison {
         binding: u32,
@@ -204,6 +240,9 @@ impl PrettyError for CreateBindGroupError {
             Self::InvalidTextureView(id) => {
                 fmt.texture_view_label(&id);
             }
+            Self::ExpiredTexture(id) => {
+                fmt.texture_label(&id);
+            }
             Self::InvalidSampler(id) => {
                 fmt.sampler_label(&id);
             }
@@ -367,6 +406,11 @@ impl BindingTypeMaxCountValidator {
                 self.storage_textures.add(binding.visibility, count);
             }
             wgt::BindingType::AccelerationStructure => todo!(),
+            wgt::BindingType::YcbcrTexture { .. } => {
+                // The texture comes with a sampler of its own.
+                self.sampled_textures.add(binding.visibility, count);
+                self.samplers.add(binding.visibility, count);
+            }
         }
     }
 
@@ -554,6 +598,10 @@ pub enum CreatePipelineLayoutError {
     TooManyBindings(BindingTypeMaxCountError),
     #[error("Bind group layout count {actual} exceeds device bind group limit {max}")]
     TooManyGroups { actual: usize, max: usize },
+    #[error(
+        "Bind group layouts at index {first} and {second} are both inline, but only one is allowed"
+    )]
+    TooManyInlineGroups { first: usize, second: usize },
 }
 
 impl PrettyError for CreatePipelineLayoutError {
@@ -760,6 +808,16 @@ pub struct BufferBinding {
     pub size: Option<wgt::BufferSize>,
 }
 
+/// A binding of an inline bind group, set by [`RenderCommand::SetBindGroupInline`].
+///
+/// [`RenderCommand::SetBindGroupInline`]: crate::command::RenderCommand::SetBindGroupInline
+#[derive(Clone, Debug, Hash, Eq, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct InlineBinding {
+    pub binding: u32,
+    pub buffer: BufferBinding,
+}
+
 // Note: Duplicated in `wgpu-rs` as `BindingResource`
 // They're different enough that it doesn't make sense to share a common type
 #[derive(Debug, Clone)]
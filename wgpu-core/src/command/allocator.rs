@@ -54,7 +54,8 @@ impl<A: HalApi> CommandAllocator<A> {
 
     /// Free the pool of command encoders.
     ///
-    /// This is only called when the `Device` is dropped.
+    /// This is called when the `Device` is dropped, and when mapping a buffer
+    /// runs out of memory with [`wgt::DeviceFlags::RETRY_FAILED_MAPPINGS`].
     pub(crate) fn dispose(&self, device: &A::Device) {
         let mut free_encoders = self.free_encoders.lock();
         resource_log!("CommandAllocator::dispose encoders {}", free_encoders.len());
@@ -1,12 +1,12 @@
 use std::sync::Arc;
 
 use crate::{
-    binding_model::{BindGroup, LateMinBufferBindingSizeMismatch, PipelineLayout},
+    binding_model::{BindGroup, BindGroupLayout, LateMinBufferBindingSizeMismatch, PipelineLayout},
     device::SHADER_STAGE_COUNT,
     hal_api::HalApi,
     id::BindGroupId,
     pipeline::LateSizedBufferGroup,
-    resource::Resource,
+    resource::{Buffer, Resource},
 };
 
 use arrayvec::ArrayVec;
@@ -63,6 +63,7 @@ mod compat {
                 let expected_bgl_type = match expected_bgl.origin {
                     bgl::Origin::Derived => "implicit",
                     bgl::Origin::Pool => "explicit",
+                    bgl::Origin::Inline => "inline",
                 };
                 let expected_label = expected_bgl.label();
                 diff.push(format!(
@@ -77,6 +78,7 @@ mod compat {
                     let assigned_bgl_type = match assigned_bgl.origin {
                         bgl::Origin::Derived => "implicit",
                         bgl::Origin::Pool => "explicit",
+                        bgl::Origin::Inline => "inline",
                     };
                     let assigned_label = assigned_bgl.label();
                     diff.push(format!(
@@ -226,10 +228,31 @@ struct LateBufferBinding {
     bound_size: wgt::BufferAddress,
 }
 
+/// A validated buffer binding of an inline bind group.
+#[derive(Debug, Clone)]
+pub(super) struct InlineBufferBinding<A: HalApi> {
+    pub(super) buffer: Arc<Buffer<A>>,
+    pub(super) offset: wgt::BufferAddress,
+    pub(super) size: Option<wgt::BufferSize>,
+}
+
+impl<A: HalApi> InlineBufferBinding<A> {
+    fn bound_size(&self) -> wgt::BufferAddress {
+        match self.size {
+            Some(size) => size.get(),
+            None => self.buffer.size - self.offset,
+        }
+    }
+}
+
 #[derive(Debug)]
 pub(super) struct EntryPayload<A: HalApi> {
     pub(super) group: Option<Arc<BindGroup<A>>>,
     pub(super) dynamic_offsets: Vec<wgt::DynamicOffset>,
+    /// The layout of the inline bind group set in place of `group`.
+    pub(super) inline_layout: Option<Arc<BindGroupLayout<A>>>,
+    /// The buffers of the inline bind group, sorted by binding index.
+    pub(super) inline_buffers: Vec<InlineBufferBinding<A>>,
     late_buffer_bindings: Vec<LateBufferBinding>,
     /// Since `LateBufferBinding` may contain information about the bindings
     /// not used by the pipeline, we need to know when to stop validating.
@@ -241,6 +264,8 @@ impl<A: HalApi> Default for EntryPayload<A> {
         Self {
             group: None,
             dynamic_offsets: Default::default(),
+            inline_layout: None,
+            inline_buffers: Default::default(),
             late_buffer_bindings: Default::default(),
             late_bindings_effective_count: Default::default(),
         }
@@ -251,9 +276,28 @@ impl<A: HalApi> EntryPayload<A> {
     fn reset(&mut self) {
         self.group = None;
         self.dynamic_offsets.clear();
+        self.inline_layout = None;
+        self.inline_buffers.clear();
         self.late_buffer_bindings.clear();
         self.late_bindings_effective_count = 0;
     }
+
+    /// Fill out the actual binding sizes for buffers,
+    /// whose layout doesn't specify `min_binding_size`.
+    fn assign_late_buffer_binding_sizes(
+        &mut self,
+        sizes: impl Iterator<Item = wgt::BufferAddress>,
+    ) {
+        for (index, bound_size) in sizes.enumerate() {
+            match self.late_buffer_bindings.get_mut(index) {
+                Some(late_binding) => late_binding.bound_size = bound_size,
+                None => self.late_buffer_bindings.push(LateBufferBinding {
+                    shader_expect_size: 0,
+                    bound_size,
+                }),
+            }
+        }
+    }
 }
 
 #[derive(Debug, Default)]
@@ -334,36 +378,59 @@ impl<A: HalApi> Binder<A> {
         payload.group = Some(bind_group.clone());
         payload.dynamic_offsets.clear();
         payload.dynamic_offsets.extend_from_slice(offsets);
-
-        // Fill out the actual binding sizes for buffers,
-        // whose layout doesn't specify `min_binding_size`.
-        for (late_binding, late_size) in payload
-            .late_buffer_bindings
-            .iter_mut()
-            .zip(bind_group.late_buffer_binding_sizes.iter())
-        {
-            late_binding.bound_size = late_size.get();
-        }
-        if bind_group.late_buffer_binding_sizes.len() > payload.late_buffer_bindings.len() {
-            for late_size in
-                bind_group.late_buffer_binding_sizes[payload.late_buffer_bindings.len()..].iter()
-            {
-                payload.late_buffer_bindings.push(LateBufferBinding {
-                    shader_expect_size: 0,
-                    bound_size: late_size.get(),
-                });
-            }
-        }
+        payload.inline_layout = None;
+        payload.inline_buffers.clear();
+        payload.assign_late_buffer_binding_sizes(
+            bind_group
+                .late_buffer_binding_sizes
+                .iter()
+                .map(|late_size| late_size.get()),
+        );
 
         let bind_range = self.manager.assign(index, bind_group.layout.clone());
         &self.payloads[bind_range]
     }
 
+    /// Assign the buffers of an inline bind group with the given layout, sorted
+    /// by binding index.
+    pub(super) fn assign_inline_group<'a>(
+        &'a mut self,
+        index: usize,
+        layout: &Arc<BindGroupLayout<A>>,
+        buffers: &[InlineBufferBinding<A>],
+    ) -> &'a [EntryPayload<A>] {
+        log::trace!("\tBinding [{}] = inline group", index);
+
+        let payload = &mut self.payloads[index];
+        payload.group = None;
+        payload.dynamic_offsets.clear();
+        payload.inline_layout = Some(layout.clone());
+        payload.inline_buffers.clear();
+        payload.inline_buffers.extend_from_slice(buffers);
+        let late_sizes = layout
+            .entries
+            .values()
+            .zip(buffers)
+            .filter_map(|(entry, binding)| match entry.ty {
+                wgt::BindingType::Buffer {
+                    min_binding_size: None,
+                    ..
+                } => Some(binding.bound_size()),
+                _ => None,
+            });
+        payload.assign_late_buffer_binding_sizes(late_sizes);
+
+        let bind_range = self.manager.assign(index, layout.clone());
+        &self.payloads[bind_range]
+    }
+
     pub(super) fn list_active(&self) -> impl Iterator<Item = BindGroupId> + '_ {
         let payloads = &self.payloads;
+        // Inline bind groups aren't tracked, they have no bind group.
         self.manager
             .list_active()
-            .map(move |index| payloads[index].group.as_ref().unwrap().as_info().id())
+            .filter_map(move |index| payloads[index].group.as_ref())
+            .map(|group| group.as_info().id())
     }
 
     pub(super) fn invalid_mask(&self) -> BindGroupMask {
@@ -395,6 +395,9 @@ impl RenderBundleEncoder {
 
         let base = self.base.as_ref();
         let mut next_dynamic_offset = 0;
+        // Below `ValidationLevel::Standard`, only what is needed to record the
+        // commands is checked.
+        let validate = device.validation_level() >= wgt::ValidationLevel::Standard;
 
         for &command in base.commands {
             match command {
@@ -446,7 +449,7 @@ impl RenderBundleEncoder {
                     {
                         let (alignment, limit_name) =
                             buffer_binding_type_alignment(&device.limits, info.binding_type);
-                        if offset % alignment as u64 != 0 {
+                        if validate && offset % alignment as u64 != 0 {
                             return Err(RenderCommandError::UnalignedBufferOffset(
                                 offset, limit_name, alignment,
                             ))
@@ -480,18 +483,20 @@ impl RenderBundleEncoder {
                     self.check_valid_to_use(pipeline.device.info.id())
                         .map_pass_err(scope)?;
 
-                    self.context
-                        .check_compatible(&pipeline.pass_context, RenderPassCompatibilityCheckType::RenderPipeline)
-                        .map_err(RenderCommandError::IncompatiblePipelineTargets)
-                        .map_pass_err(scope)?;
-
-                    if (pipeline.flags.contains(PipelineFlags::WRITES_DEPTH)
-                        && self.is_depth_read_only)
-                        || (pipeline.flags.contains(PipelineFlags::WRITES_STENCIL)
-                            && self.is_stencil_read_only)
-                    {
-                        return Err(RenderCommandError::IncompatiblePipelineRods)
-                            .map_pass_err(scope);
+                    if validate {
+                        self.context
+                            .check_compatible(&pipeline.pass_context, RenderPassCompatibilityCheckType::RenderPipeline)
+                            .map_err(RenderCommandError::IncompatiblePipelineTargets)
+                            .map_pass_err(scope)?;
+
+                        if (pipeline.flags.contains(PipelineFlags::WRITES_DEPTH)
+                            && self.is_depth_read_only)
+                            || (pipeline.flags.contains(PipelineFlags::WRITES_STENCIL)
+                                && self.is_stencil_read_only)
+                        {
+                            return Err(RenderCommandError::IncompatiblePipelineRods)
+                                .map_pass_err(scope);
+                        }
                     }
 
                     let pipeline_state = PipelineState::new(pipeline);
@@ -521,8 +526,10 @@ impl RenderBundleEncoder {
                         .map_pass_err(scope)?;
                     self.check_valid_to_use(buffer.device.info.id())
                         .map_pass_err(scope)?;
-                    check_buffer_usage(buffer_id, buffer.usage, wgt::BufferUsages::INDEX)
-                        .map_pass_err(scope)?;
+                    if validate {
+                        check_buffer_usage(buffer_id, buffer.usage, wgt::BufferUsages::INDEX)
+                            .map_pass_err(scope)?;
+                    }
 
                     let end = match size {
                         Some(s) => offset + s.get(),
@@ -560,8 +567,10 @@ impl RenderBundleEncoder {
                         .map_pass_err(scope)?;
                     self.check_valid_to_use(buffer.device.info.id())
                         .map_pass_err(scope)?;
-                    check_buffer_usage(buffer_id, buffer.usage, wgt::BufferUsages::VERTEX)
-                        .map_pass_err(scope)?;
+                    if validate {
+                        check_buffer_usage(buffer_id, buffer.usage, wgt::BufferUsages::VERTEX)
+                            .map_pass_err(scope)?;
+                    }
 
                     let end = match size {
                         Some(s) => offset + s.get(),
@@ -585,9 +594,11 @@ impl RenderBundleEncoder {
 
                     let pipeline_state = state.pipeline(scope)?;
 
-                    pipeline_state.pipeline.layout
-                        .validate_push_constant_ranges(stages, offset, end_offset)
-                        .map_pass_err(scope)?;
+                    if validate {
+                        pipeline_state.pipeline.layout
+                            .validate_push_constant_ranges(stages, offset, end_offset)
+                            .map_pass_err(scope)?;
+                    }
 
                     commands.push(ArcRenderCommand::SetPushConstant { stages, offset, size_bytes, values_offset });
                 }
@@ -605,14 +616,16 @@ impl RenderBundleEncoder {
                     let pipeline = state.pipeline(scope)?;
                     let used_bind_groups = pipeline.used_bind_groups;
 
-                    validate_draw(
-                        &state.vertex[..],
-                        &pipeline.steps,
-                        first_vertex,
-                        vertex_count,
-                        first_instance,
-                        instance_count,
-                    ).map_pass_err(scope)?;
+                    if validate {
+                        validate_draw(
+                            &state.vertex[..],
+                            &pipeline.steps,
+                            first_vertex,
+                            vertex_count,
+                            first_instance,
+                            instance_count,
+                        ).map_pass_err(scope)?;
+                    }
 
                     if instance_count > 0 && vertex_count > 0 {
                         commands.extend(state.flush_vertices());
@@ -644,15 +657,17 @@ impl RenderBundleEncoder {
                         None => return Err(DrawError::MissingIndexBuffer).map_pass_err(scope),
                     };
 
-                    validate_indexed_draw(
-                        &state.vertex[..],
-                        &pipeline.steps,
-                        index,
-                        first_index,
-                        index_count,
-                        first_instance,
-                        instance_count,
-                    ).map_pass_err(scope)?;
+                    if validate {
+                        validate_indexed_draw(
+                            &state.vertex[..],
+                            &pipeline.steps,
+                            index,
+                            first_index,
+                            index_count,
+                            first_instance,
+                            instance_count,
+                        ).map_pass_err(scope)?;
+                    }
 
                     if instance_count > 0 && index_count > 0 {
                         commands.extend(state.flush_index());
@@ -687,8 +702,10 @@ impl RenderBundleEncoder {
                         .map_pass_err(scope)?;
                     self.check_valid_to_use(buffer.device.info.id())
                         .map_pass_err(scope)?;
-                    check_buffer_usage(buffer_id, buffer.usage, wgt::BufferUsages::INDIRECT)
-                        .map_pass_err(scope)?;
+                    if validate {
+                        check_buffer_usage(buffer_id, buffer.usage, wgt::BufferUsages::INDIRECT)
+                            .map_pass_err(scope)?;
+                    }
 
                     buffer_memory_init_actions.extend(buffer.initialization_status.read().create_action(
                         buffer,
@@ -726,8 +743,10 @@ impl RenderBundleEncoder {
                         .map_pass_err(scope)?;
                     self.check_valid_to_use(buffer.device.info.id())
                         .map_pass_err(scope)?;
-                    check_buffer_usage(buffer_id, buffer.usage, wgt::BufferUsages::INDIRECT)
-                        .map_pass_err(scope)?;
+                    if validate {
+                        check_buffer_usage(buffer_id, buffer.usage, wgt::BufferUsages::INDIRECT)
+                            .map_pass_err(scope)?;
+                    }
 
                     buffer_memory_init_actions.extend(buffer.initialization_status.read().create_action(
                         buffer,
@@ -758,8 +777,18 @@ impl RenderBundleEncoder {
                 RenderCommand::ExecuteBundle(_)
                 | RenderCommand::SetBlendConstant(_)
                 | RenderCommand::SetStencilReference(_)
+                | RenderCommand::SetStencilReferenceSeparate { .. }
+                | RenderCommand::SetStencilWriteMask { .. }
+                | RenderCommand::SetBindGroupInline { .. }
+                | RenderCommand::SetFrontFace(_)
+                | RenderCommand::SetCullMode(_)
+                | RenderCommand::SetDepthCompare(_)
                 | RenderCommand::SetViewport { .. }
-                | RenderCommand::SetScissor(_) => unreachable!("not supported by a render bundle"),
+                | RenderCommand::SetScissor(_)
+                | RenderCommand::SetIndexedViewport { .. }
+                | RenderCommand::SetIndexedScissor { .. } => {
+                    unreachable!("not supported by a render bundle")
+                }
             }
         }
 
@@ -770,6 +799,7 @@ impl RenderBundleEncoder {
                 dynamic_offsets: state.flat_dynamic_offsets,
                 string_data: Vec::new(),
                 push_constant_data: Vec::new(),
+                inline_bindings: Vec::new(),
             },
             is_depth_read_only: self.is_depth_read_only,
             is_stencil_read_only: self.is_stencil_read_only,
@@ -1075,8 +1105,16 @@ impl<A: HalApi> RenderBundle<A> {
                 Cmd::ExecuteBundle(_)
                 | Cmd::SetBlendConstant(_)
                 | Cmd::SetStencilReference(_)
+                | Cmd::SetStencilReferenceSeparate { .. }
+                | Cmd::SetStencilWriteMask { .. }
+                | Cmd::SetBindGroupInline { .. }
+                | Cmd::SetFrontFace(_)
+                | Cmd::SetCullMode(_)
+                | Cmd::SetDepthCompare(_)
                 | Cmd::SetViewport { .. }
-                | Cmd::SetScissor(_) => unreachable!(),
+                | Cmd::SetScissor(_)
+                | Cmd::SetIndexedViewport { .. }
+                | Cmd::SetIndexedScissor { .. } => unreachable!(),
             }
         }
 
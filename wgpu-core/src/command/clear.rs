@@ -87,6 +87,33 @@ impl Global {
         profiling::scope!("CommandEncoder::clear_buffer");
         api_log!("CommandEncoder::clear_buffer {dst:?}");
 
+        self.fill_buffer_impl::<A>(command_encoder_id, dst, offset, size, None)
+    }
+
+    /// Fills a range of a buffer with `value`, repeated every 4 bytes.
+    pub fn command_encoder_fill_buffer<A: HalApi>(
+        &self,
+        command_encoder_id: CommandEncoderId,
+        dst: BufferId,
+        offset: BufferAddress,
+        size: Option<BufferAddress>,
+        value: u32,
+    ) -> Result<(), ClearError> {
+        profiling::scope!("CommandEncoder::fill_buffer");
+        api_log!("CommandEncoder::fill_buffer {dst:?} with {value:#010x}");
+
+        self.fill_buffer_impl::<A>(command_encoder_id, dst, offset, size, Some(value))
+    }
+
+    /// Clears the buffer range if `value` is `None`, fills it with `value` otherwise.
+    fn fill_buffer_impl<A: HalApi>(
+        &self,
+        command_encoder_id: CommandEncoderId,
+        dst: BufferId,
+        offset: BufferAddress,
+        size: Option<BufferAddress>,
+        value: Option<u32>,
+    ) -> Result<(), ClearError> {
         let hub = A::hub(self);
 
         let cmd_buf = CommandBuffer::get_encoder(hub, command_encoder_id)
@@ -96,7 +123,15 @@ impl Global {
 
         #[cfg(feature = "trace")]
         if let Some(ref mut list) = cmd_buf_data.commands {
-            list.push(TraceCommand::ClearBuffer { dst, offset, size });
+            list.push(match value {
+                None => TraceCommand::ClearBuffer { dst, offset, size },
+                Some(value) => TraceCommand::FillBuffer {
+                    dst,
+                    offset,
+                    size,
+                    value,
+                },
+            });
         }
 
         let (dst_buffer, dst_pending) = {
@@ -167,7 +202,10 @@ impl Global {
         let cmd_buf_raw = cmd_buf_data.encoder.open()?;
         unsafe {
             cmd_buf_raw.transition_buffers(dst_barrier.into_iter());
-            cmd_buf_raw.clear_buffer(dst_raw, offset..end_offset);
+            match value {
+                None => cmd_buf_raw.clear_buffer(dst_raw, offset..end_offset),
+                Some(value) => cmd_buf_raw.fill_buffer(dst_raw, offset..end_offset, value),
+            }
         }
         Ok(())
     }
@@ -209,6 +247,15 @@ impl Global {
             return Err(DeviceError::WrongDevice.into());
         }
 
+        // Transient textures only hold contents within the render passes using them.
+        if dst_texture
+            .desc
+            .usage
+            .contains(wgt::TextureUsages::TRANSIENT)
+        {
+            return Err(ClearError::NoValidTextureClearMode(dst));
+        }
+
         // Check if subresource aspects are valid.
         let clear_aspects =
             hal::FormatAspects::new(dst_texture.desc.format, subresource_range.aspect);
@@ -362,75 +409,27 @@ fn clear_texture_via_buffer_copies<A: HalApi>(
 
     // Gather list of zero_buffer copies and issue a single command then to perform them
     let mut zero_buffer_copy_regions = Vec::new();
-    let buffer_copy_pitch = alignments.buffer_copy_pitch.get() as u32;
-    let (block_width, block_height) = texture_desc.format.block_dimensions();
-    let block_size = texture_desc.format.block_copy_size(None).unwrap();
-
-    let bytes_per_row_alignment = get_lowest_common_denom(buffer_copy_pitch, block_size);
-
     for mip_level in range.mip_range {
-        let mut mip_size = texture_desc.mip_level_size(mip_level).unwrap();
-        // Round to multiple of block size
-        mip_size.width = align_to(mip_size.width, block_width);
-        mip_size.height = align_to(mip_size.height, block_height);
-
-        let bytes_per_row = align_to(
-            mip_size.width / block_width * block_size,
-            bytes_per_row_alignment,
-        );
-
-        let max_rows_per_copy = crate::device::ZERO_BUFFER_SIZE as u32 / bytes_per_row;
-        // round down to a multiple of rows needed by the texture format
-        let max_rows_per_copy = max_rows_per_copy / block_height * block_height;
-        assert!(
-            max_rows_per_copy > 0,
-            "Zero buffer size is too small to fill a single row \
-            of a texture with format {:?} and desc {:?}",
-            texture_desc.format,
-            texture_desc.size
-        );
-
-        let z_range = 0..(if texture_desc.dimension == wgt::TextureDimension::D3 {
+        let mip_size = texture_desc.mip_level_size(mip_level).unwrap();
+        let depth = if texture_desc.dimension == wgt::TextureDimension::D3 {
             mip_size.depth_or_array_layers
         } else {
             1
-        });
+        };
 
         for array_layer in range.layer_range.clone() {
-            // TODO: Only doing one layer at a time for volume textures right now.
-            for z in z_range.clone() {
-                // May need multiple copies for each subresource! However, we
-                // assume that we never need to split a row.
-                let mut num_rows_left = mip_size.height;
-                while num_rows_left > 0 {
-                    let num_rows = num_rows_left.min(max_rows_per_copy);
-
-                    zero_buffer_copy_regions.push(hal::BufferTextureCopy {
-                        buffer_layout: wgt::ImageDataLayout {
-                            offset: 0,
-                            bytes_per_row: Some(bytes_per_row),
-                            rows_per_image: None,
-                        },
-                        texture_base: hal::TextureCopyBase {
-                            mip_level,
-                            array_layer,
-                            origin: wgt::Origin3d {
-                                x: 0, // Always full rows
-                                y: mip_size.height - num_rows_left,
-                                z,
-                            },
-                            aspect: hal::FormatAspects::COLOR,
-                        },
-                        size: hal::CopyExtent {
-                            width: mip_size.width, // full row
-                            height: num_rows,
-                            depth: 1, // Only single slice of volume texture at a time right now
-                        },
-                    });
-
-                    num_rows_left -= num_rows;
-                }
-            }
+            push_zero_buffer_copies(
+                texture_desc,
+                alignments,
+                mip_level,
+                array_layer,
+                wgt::Origin3d::ZERO,
+                wgt::Extent3d {
+                    depth_or_array_layers: depth,
+                    ..mip_size
+                },
+                &mut zero_buffer_copy_regions,
+            );
         }
     }
 
@@ -439,6 +438,76 @@ fn clear_texture_via_buffer_copies<A: HalApi>(
     }
 }
 
+/// Push the copies from the zero buffer that zero a region of one mip level
+/// and array layer of a color texture to `regions`.
+pub(crate) fn push_zero_buffer_copies(
+    texture_desc: &wgt::TextureDescriptor<(), Vec<wgt::TextureFormat>>,
+    alignments: &hal::Alignments,
+    mip_level: u32,
+    array_layer: u32,
+    origin: wgt::Origin3d,
+    size: wgt::Extent3d,
+    regions: &mut Vec<hal::BufferTextureCopy>,
+) {
+    let buffer_copy_pitch = alignments.buffer_copy_pitch.get() as u32;
+    let (block_width, block_height) = texture_desc.format.block_dimensions();
+    let block_size = texture_desc.format.block_copy_size(None).unwrap();
+
+    let bytes_per_row_alignment = get_lowest_common_denom(buffer_copy_pitch, block_size);
+
+    // Round to multiple of block size
+    let width = align_to(size.width, block_width);
+    let height = align_to(size.height, block_height);
+
+    let bytes_per_row = align_to(width / block_width * block_size, bytes_per_row_alignment);
+
+    let max_rows_per_copy = crate::device::ZERO_BUFFER_SIZE as u32 / bytes_per_row;
+    // round down to a multiple of rows needed by the texture format
+    let max_rows_per_copy = max_rows_per_copy / block_height * block_height;
+    assert!(
+        max_rows_per_copy > 0,
+        "Zero buffer size is too small to fill a single row \
+        of a texture with format {:?} and desc {:?}",
+        texture_desc.format,
+        texture_desc.size
+    );
+
+    // TODO: Only doing one layer at a time for volume textures right now.
+    for z in origin.z..origin.z + size.depth_or_array_layers {
+        // May need multiple copies for each subresource! However, we
+        // assume that we never need to split a row.
+        let mut num_rows_left = height;
+        while num_rows_left > 0 {
+            let num_rows = num_rows_left.min(max_rows_per_copy);
+
+            regions.push(hal::BufferTextureCopy {
+                buffer_layout: wgt::ImageDataLayout {
+                    offset: 0,
+                    bytes_per_row: Some(bytes_per_row),
+                    rows_per_image: None,
+                },
+                texture_base: hal::TextureCopyBase {
+                    mip_level,
+                    array_layer,
+                    origin: wgt::Origin3d {
+                        x: origin.x, // Always full rows
+                        y: origin.y + height - num_rows_left,
+                        z,
+                    },
+                    aspect: hal::FormatAspects::COLOR,
+                },
+                size: hal::CopyExtent {
+                    width, // full row
+                    height: num_rows,
+                    depth: 1, // Only single slice of volume texture at a time right now
+                },
+            });
+
+            num_rows_left -= num_rows;
+        }
+    }
+}
+
 fn clear_texture_via_render_passes<A: HalApi>(
     dst_texture: &Texture<A>,
     range: TextureInitRange,
@@ -247,6 +247,14 @@ impl<'a, A: HalApi> State<'a, A> {
         Ok(())
     }
 
+    /// Check the state that dispatches need even when passes aren't validated.
+    fn is_bound(&self) -> Result<(), DispatchError> {
+        if self.pipeline.is_none() {
+            return Err(DispatchError::MissingPipeline);
+        }
+        Ok(())
+    }
+
     // `extra_buffer` is there to represent the indirect buffer that is also
     // part of the usage scope.
     fn flush_states(
@@ -320,6 +328,7 @@ impl Global {
                 dynamic_offsets: base.dynamic_offsets,
                 string_data: base.string_data,
                 push_constant_data: base.push_constant_data,
+                inline_bindings: base.inline_bindings,
             },
             timestamp_writes,
         )
@@ -339,6 +348,7 @@ impl Global {
         let cmd_buf: Arc<CommandBuffer<A>> =
             CommandBuffer::get_encoder(hub, encoder_id).map_pass_err(pass_scope)?;
         let device = &cmd_buf.device;
+        let validate = device.validates_passes();
         if !device.is_valid() {
             return Err(ComputePassErrorInner::InvalidDevice(
                 cmd_buf.device.as_info().id(),
@@ -358,6 +368,7 @@ impl Global {
                     dynamic_offsets: base.dynamic_offsets.to_vec(),
                     string_data: base.string_data.to_vec(),
                     push_constant_data: base.push_constant_data.to_vec(),
+                    inline_bindings: base.inline_bindings.to_vec(),
                 },
                 timestamp_writes: timestamp_writes.cloned(),
             });
@@ -484,9 +495,11 @@ impl Global {
                     dynamic_offset_count += num_dynamic_offsets;
 
                     let bind_group = tracker.bind_groups.insert_single(bind_group.clone());
-                    bind_group
-                        .validate_dynamic_bindings(*index, &temp_offsets, &cmd_buf.limits)
-                        .map_pass_err(scope)?;
+                    if validate {
+                        bind_group
+                            .validate_dynamic_bindings(*index, &temp_offsets, &cmd_buf.limits)
+                            .map_pass_err(scope)?;
+                    }
 
                     buffer_memory_init_actions.extend(
                         bind_group.used_buffer_ranges.iter().filter_map(|action| {
@@ -639,7 +652,11 @@ impl Global {
                         indirect: false,
                         pipeline: state.pipeline,
                     };
-                    state.is_ready().map_pass_err(scope)?;
+                    if validate {
+                        state.is_ready().map_pass_err(scope)?;
+                    } else {
+                        state.is_bound().map_pass_err(scope)?;
+                    }
 
                     state
                         .flush_states(
@@ -653,9 +670,10 @@ impl Global {
 
                     let groups_size_limit = cmd_buf.limits.max_compute_workgroups_per_dimension;
 
-                    if groups[0] > groups_size_limit
-                        || groups[1] > groups_size_limit
-                        || groups[2] > groups_size_limit
+                    if validate
+                        && (groups[0] > groups_size_limit
+                            || groups[1] > groups_size_limit
+                            || groups[2] > groups_size_limit)
                     {
                         return Err(ComputePassErrorInner::Dispatch(
                             DispatchError::InvalidGroupSize {
@@ -677,7 +695,11 @@ impl Global {
                         pipeline: state.pipeline,
                     };
 
-                    state.is_ready().map_pass_err(scope)?;
+                    if validate {
+                        state.is_ready().map_pass_err(scope)?;
+                    } else {
+                        state.is_bound().map_pass_err(scope)?;
+                    }
 
                     device
                         .require_downlevel_flags(wgt::DownlevelFlags::INDIRECT_EXECUTION)
@@ -688,8 +710,10 @@ impl Global {
                         .buffers
                         .insert_merge_single(buffer.clone(), hal::BufferUses::INDIRECT)
                         .map_pass_err(scope)?;
-                    check_buffer_usage(buffer_id, buffer.usage, wgt::BufferUsages::INDIRECT)
-                        .map_pass_err(scope)?;
+                    if validate {
+                        check_buffer_usage(buffer_id, buffer.usage, wgt::BufferUsages::INDIRECT)
+                            .map_pass_err(scope)?;
+                    }
 
                     let end_offset = offset + mem::size_of::<wgt::DispatchIndirectArgs>() as u64;
                     if end_offset > buffer.size {
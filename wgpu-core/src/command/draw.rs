@@ -2,7 +2,9 @@
 !*/
 
 use crate::{
-    binding_model::{BindGroup, LateMinBufferBindingSizeMismatch, PushConstantUploadError},
+    binding_model::{
+        BindGroup, CreateBindGroupError, LateMinBufferBindingSizeMismatch, PushConstantUploadError,
+    },
     error::ErrorFormatter,
     hal_api::HalApi,
     id,
@@ -75,6 +77,10 @@ pub enum RenderCommandError {
     InvalidRenderBundle(id::RenderBundleId),
     #[error("Bind group index {index} is greater than the device's requested `max_bind_group` limit {max}")]
     BindGroupIndexOutOfRange { index: u32, max: u32 },
+    #[error("The current pipeline has no inline bind group layout at index {index}")]
+    MissingInlineBindGroupLayout { index: u32 },
+    #[error("Inline bind group is invalid")]
+    InvalidInlineBindGroup(#[from] CreateBindGroupError),
     #[error("Vertex buffer index {index} is greater than the device's requested `max_vertex_buffers` limit {max}")]
     VertexBufferIndexOutOfRange { index: u32, max: u32 },
     #[error("Dynamic buffer offset {0} does not respect device's requested `{1}` limit {2}")]
@@ -89,6 +95,8 @@ pub enum RenderCommandError {
     IncompatiblePipelineTargets(#[from] crate::device::RenderPassCompatibilityError),
     #[error("Pipeline writes to depth/stencil, while the pass has read-only depth/stencil")]
     IncompatiblePipelineRods,
+    #[error("Stencil write mask {0:#x} is non-zero, while the pass has read-only stencil")]
+    StencilWriteMaskOnReadOnlyStencil(u32),
     #[error(transparent)]
     UsageConflict(#[from] UsageConflict),
     #[error("Buffer {0:?} is destroyed")]
@@ -105,6 +113,8 @@ pub enum RenderCommandError {
     InvalidViewportDepth(f32, f32),
     #[error("Scissor {0:?} is not contained in the render target {1:?}")]
     InvalidScissorRect(Rect<u32>, wgt::Extent3d),
+    #[error("Viewport or scissor index {index} is out of range, the maximum is {max}")]
+    ViewportIndexOutOfRange { index: u32, max: u32 },
     #[error("Support for {0} is not implemented yet")]
     Unimplemented(&'static str),
 }
@@ -148,6 +158,14 @@ pub enum RenderCommand {
         num_dynamic_offsets: usize,
         bind_group_id: id::BindGroupId,
     },
+    /// Set the bindings of the inline bind group at `index`, which requires
+    /// [`wgt::Features::INLINE_BIND_GROUPS`].
+    ///
+    /// The bindings are the next `num_bindings` values of [`BasePass::inline_bindings`].
+    SetBindGroupInline {
+        index: u32,
+        num_bindings: usize,
+    },
     SetPipeline(id::RenderPipelineId),
     SetIndexBuffer {
         buffer_id: id::BufferId,
@@ -163,6 +181,27 @@ pub enum RenderCommand {
     },
     SetBlendConstant(Color),
     SetStencilReference(u32),
+    /// Set different stencil reference values for front and back faces, which
+    /// requires [`wgt::Features::SEPARATE_STENCIL_STATE`].
+    SetStencilReferenceSeparate {
+        front: u32,
+        back: u32,
+    },
+    /// Override the stencil write masks of the current pipeline, which requires
+    /// [`wgt::Features::SEPARATE_STENCIL_STATE`].
+    SetStencilWriteMask {
+        front: u32,
+        back: u32,
+    },
+    /// Override the front face of the current pipeline, which requires
+    /// [`wgt::Features::EXTENDED_DYNAMIC_STATE`].
+    SetFrontFace(wgt::FrontFace),
+    /// Override the cull mode of the current pipeline, which requires
+    /// [`wgt::Features::EXTENDED_DYNAMIC_STATE`].
+    SetCullMode(Option<wgt::Face>),
+    /// Override the depth compare function of the current pipeline, which requires
+    /// [`wgt::Features::EXTENDED_DYNAMIC_STATE`].
+    SetDepthCompare(wgt::CompareFunction),
     SetViewport {
         rect: Rect<f32>,
         //TODO: use half-float to reduce the size?
@@ -170,6 +209,20 @@ pub enum RenderCommand {
         depth_max: f32,
     },
     SetScissor(Rect<u32>),
+    /// Set the viewport at `index`, which requires [`wgt::Features::MULTI_VIEWPORT`]
+    /// unless `index` is 0.
+    SetIndexedViewport {
+        index: u32,
+        rect: Rect<f32>,
+        depth_min: f32,
+        depth_max: f32,
+    },
+    /// Set the scissor rectangle at `index`, which requires
+    /// [`wgt::Features::MULTI_VIEWPORT`] unless `index` is 0.
+    SetIndexedScissor {
+        index: u32,
+        rect: Rect<u32>,
+    },
 
     /// Set a range of push constants to values stored in [`BasePass::push_constant_data`].
     ///
@@ -260,6 +313,12 @@ pub enum ArcRenderCommand<A: HalApi> {
         num_dynamic_offsets: usize,
         bind_group: Arc<BindGroup<A>>,
     },
+    /// Set the bindings of the inline bind group at `index`, which requires
+    /// [`wgt::Features::INLINE_BIND_GROUPS`].
+    SetBindGroupInline {
+        index: u32,
+        num_bindings: usize,
+    },
     SetPipeline(Arc<RenderPipeline<A>>),
     SetIndexBuffer {
         buffer: Arc<Buffer<A>>,
@@ -275,12 +334,47 @@ pub enum ArcRenderCommand<A: HalApi> {
     },
     SetBlendConstant(Color),
     SetStencilReference(u32),
+    /// Set different stencil reference values for front and back faces, which
+    /// requires [`wgt::Features::SEPARATE_STENCIL_STATE`].
+    SetStencilReferenceSeparate {
+        front: u32,
+        back: u32,
+    },
+    /// Override the stencil write masks of the current pipeline, which requires
+    /// [`wgt::Features::SEPARATE_STENCIL_STATE`].
+    SetStencilWriteMask {
+        front: u32,
+        back: u32,
+    },
+    /// Override the front face of the current pipeline, which requires
+    /// [`wgt::Features::EXTENDED_DYNAMIC_STATE`].
+    SetFrontFace(wgt::FrontFace),
+    /// Override the cull mode of the current pipeline, which requires
+    /// [`wgt::Features::EXTENDED_DYNAMIC_STATE`].
+    SetCullMode(Option<wgt::Face>),
+    /// Override the depth compare function of the current pipeline, which requires
+    /// [`wgt::Features::EXTENDED_DYNAMIC_STATE`].
+    SetDepthCompare(wgt::CompareFunction),
     SetViewport {
         rect: Rect<f32>,
         depth_min: f32,
         depth_max: f32,
     },
     SetScissor(Rect<u32>),
+    /// Set the viewport at `index`, which requires [`wgt::Features::MULTI_VIEWPORT`]
+    /// unless `index` is 0.
+    SetIndexedViewport {
+        index: u32,
+        rect: Rect<f32>,
+        depth_min: f32,
+        depth_max: f32,
+    },
+    /// Set the scissor rectangle at `index`, which requires
+    /// [`wgt::Features::MULTI_VIEWPORT`] unless `index` is 0.
+    SetIndexedScissor {
+        index: u32,
+        rect: Rect<u32>,
+    },
 
     /// Set a range of push constants to values stored in [`BasePass::push_constant_data`].
     ///
@@ -12,7 +12,7 @@ mod transfer;
 
 use std::sync::Arc;
 
-pub(crate) use self::clear::clear_texture;
+pub(crate) use self::clear::{clear_texture, push_zero_buffer_copies};
 pub use self::{
     bundle::*, clear::ClearError, compute::*, compute_command::ComputeCommand, draw::*, query::*,
     render::*, transfer::*,
@@ -21,6 +21,7 @@ pub(crate) use allocator::CommandAllocator;
 
 use self::memory_init::CommandBufferTextureMemoryActions;
 
+use crate::binding_model::InlineBinding;
 use crate::device::{Device, DeviceError};
 use crate::error::{ErrorFormatter, PrettyError};
 use crate::hub::Hub;
@@ -494,6 +495,7 @@ pub struct BasePassRef<'a, C> {
     pub dynamic_offsets: &'a [wgt::DynamicOffset],
     pub string_data: &'a [u8],
     pub push_constant_data: &'a [u32],
+    pub inline_bindings: &'a [InlineBinding],
 }
 
 /// A stream of commands for a render pass or compute pass.
@@ -533,6 +535,13 @@ pub struct BasePass<C> {
     /// See the documentation for [`RenderCommand::SetPushConstant`]
     /// and [`ComputeCommand::SetPushConstant`] for details.
     pub push_constant_data: Vec<u32>,
+
+    /// Bindings consumed by [`RenderCommand::SetBindGroupInline`] commands.
+    ///
+    /// Each successive `SetBindGroupInline` consumes the next `num_bindings`
+    /// values from this list.
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub inline_bindings: Vec<InlineBinding>,
 }
 
 impl<C: Clone> BasePass<C> {
@@ -543,6 +552,7 @@ impl<C: Clone> BasePass<C> {
             dynamic_offsets: Vec::new(),
             string_data: Vec::new(),
             push_constant_data: Vec::new(),
+            inline_bindings: Vec::new(),
         }
     }
 
@@ -554,6 +564,7 @@ impl<C: Clone> BasePass<C> {
             dynamic_offsets: base.dynamic_offsets.to_vec(),
             string_data: base.string_data.to_vec(),
             push_constant_data: base.push_constant_data.to_vec(),
+            inline_bindings: base.inline_bindings.to_vec(),
         }
     }
 
@@ -564,6 +575,7 @@ impl<C: Clone> BasePass<C> {
             dynamic_offsets: &self.dynamic_offsets,
             string_data: &self.string_data,
             push_constant_data: &self.push_constant_data,
+            inline_bindings: &self.inline_bindings,
         }
     }
 }
@@ -798,6 +810,12 @@ impl BindGroupStateChange {
         }
         false
     }
+    /// Forget the bind group at `index`, after something else was bound there.
+    fn forget(&mut self, index: u32) {
+        if let Some(current_bind_group) = self.last_states.get_mut(index as usize) {
+            current_bind_group.reset();
+        }
+    }
     fn reset(&mut self) {
         self.last_states = [StateChange::new(); hal::MAX_BIND_GROUPS];
     }
@@ -835,6 +853,22 @@ pub enum PassErrorScope {
     SetViewport,
     #[error("In a set_scissor_rect command")]
     SetScissorRect,
+    #[error("In a set_viewports command")]
+    SetViewports,
+    #[error("In a set_scissor_rects command")]
+    SetScissorRects,
+    #[error("In a set_stencil_reference_separate command")]
+    SetStencilReferenceSeparate,
+    #[error("In a set_stencil_write_mask command")]
+    SetStencilWriteMask,
+    #[error("In a set_bind_group_inline command")]
+    SetBindGroupInline,
+    #[error("In a set_front_face command")]
+    SetFrontFace,
+    #[error("In a set_cull_mode command")]
+    SetCullMode,
+    #[error("In a set_depth_compare command")]
+    SetDepthCompare,
     #[error("In a draw command, indexed:{indexed} indirect:{indirect}")]
     Draw {
         indexed: bool,
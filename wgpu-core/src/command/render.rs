@@ -2,18 +2,20 @@ use crate::resource::Resource;
 use crate::snatch::SnatchGuard;
 use crate::{
     api_log,
-    binding_model::BindError,
+    binding_model::{
+        self, BindError, BindGroupLayout, BufferBinding, CreateBindGroupError, InlineBinding,
+    },
     command::{
         self,
-        bind::Binder,
+        bind::{Binder, InlineBufferBinding},
         end_occlusion_query, end_pipeline_statistics_query,
         memory_init::{fixup_discarded_surfaces, SurfacesInDiscardState},
         BasePass, BasePassRef, BindGroupStateChange, CommandBuffer, CommandEncoderError,
         CommandEncoderStatus, DrawError, ExecutionError, MapPassErr, PassErrorScope, QueryUseError,
-        RenderCommand, RenderCommandError, StateChange,
+        Rect, RenderCommand, RenderCommandError, StateChange,
     },
     device::{
-        AttachmentData, Device, DeviceError, MissingDownlevelFlags, MissingFeatures,
+        bgl, AttachmentData, Device, DeviceError, MissingDownlevelFlags, MissingFeatures,
         RenderPassCompatibilityCheckType, RenderPassCompatibilityError, RenderPassContext,
     },
     error::{ErrorFormatter, PrettyError},
@@ -113,6 +115,12 @@ impl<V> PassChannel<V> {
         };
         ops
     }
+
+    /// Whether this channel leaves no contents in memory before or after the
+    /// pass, as those of transient textures must.
+    fn is_transient(&self) -> bool {
+        self.load_op == LoadOp::Clear && self.store_op == StoreOp::Discard
+    }
 }
 
 /// Describes a color attachment to a render pass.
@@ -433,7 +441,8 @@ struct State<A: HalApi> {
     pipeline_flags: PipelineFlags,
     binder: Binder<A>,
     blend_constant: OptionalState,
-    stencil_reference: u32,
+    /// Front and back stencil reference values.
+    stencil_reference: [u32; 2],
     pipeline: Option<id::RenderPipelineId>,
     index: IndexState,
     vertex: VertexState,
@@ -487,6 +496,17 @@ impl<A: HalApi> State<A> {
         Ok(())
     }
 
+    /// Check the state that draws need even when passes aren't validated.
+    fn is_bound(&self, indexed: bool) -> Result<(), DrawError> {
+        if self.pipeline.is_none() {
+            return Err(DrawError::MissingPipeline);
+        }
+        if indexed && self.index.format.is_none() {
+            return Err(DrawError::MissingIndexBuffer);
+        }
+        Ok(())
+    }
+
     /// Reset the `RenderBundle`-related states.
     fn reset_bundle(&mut self) {
         self.binder.reset();
@@ -557,6 +577,10 @@ pub enum RenderPassErrorInner {
     },
     #[error("No color attachments or depth attachments were provided, at least one attachment of any kind must be provided")]
     MissingAttachments,
+    #[error("The {location} is of a transient texture, so it must be cleared and discarded")]
+    TransientAttachmentOps { location: AttachmentErrorLocation },
+    #[error("The {location} is of a transient texture, which can't be a resolve target")]
+    TransientResolveTarget { location: AttachmentErrorLocation },
     #[error("The {location} is not renderable:")]
     TextureViewIsNotRenderable {
         location: AttachmentErrorLocation,
@@ -906,6 +930,16 @@ impl<'a, 'd, A: HalApi> RenderPassInfo<'a, 'd, A> {
                 ));
             }
 
+            if view.parent.desc.usage.contains(TextureUsages::TRANSIENT)
+                && !((!ds_aspects.contains(hal::FormatAspects::DEPTH) || at.depth.is_transient())
+                    && (!ds_aspects.contains(hal::FormatAspects::STENCIL)
+                        || at.stencil.is_transient()))
+            {
+                return Err(RenderPassErrorInner::TransientAttachmentOps {
+                    location: AttachmentErrorLocation::Depth,
+                });
+            }
+
             if !ds_aspects.contains(hal::FormatAspects::STENCIL)
                 || (at.stencil.load_op == at.depth.load_op
                     && at.stencil.store_op == at.depth.store_op)
@@ -1048,6 +1082,21 @@ impl<'a, 'd, A: HalApi> RenderPassInfo<'a, 'd, A> {
                 ));
             }
 
+            if color_view
+                .parent
+                .desc
+                .usage
+                .contains(TextureUsages::TRANSIENT)
+                && !at.channel.is_transient()
+            {
+                return Err(RenderPassErrorInner::TransientAttachmentOps {
+                    location: AttachmentErrorLocation::Color {
+                        index,
+                        resolve: false,
+                    },
+                });
+            }
+
             Self::add_pass_texture_init_actions(
                 &at.channel,
                 texture_memory_actions,
@@ -1071,6 +1120,17 @@ impl<'a, 'd, A: HalApi> RenderPassInfo<'a, 'd, A> {
                     resolve: true,
                 };
 
+                if resolve_view
+                    .parent
+                    .desc
+                    .usage
+                    .contains(TextureUsages::TRANSIENT)
+                {
+                    return Err(RenderPassErrorInner::TransientResolveTarget {
+                        location: resolve_location,
+                    });
+                }
+
                 let render_extent = resolve_view.render_extent.map_err(|reason| {
                     RenderPassErrorInner::TextureViewIsNotRenderable {
                         location: resolve_location,
@@ -1347,6 +1407,7 @@ impl Global {
             CommandBuffer::get_encoder(hub, encoder_id).map_pass_err(pass_scope)?;
         let device = &cmd_buf.device;
         let snatch_guard = device.snatchable_lock.read();
+        let validate = device.validates_passes();
 
         let (scope, pending_discard_init_fixups) = {
             let mut cmd_buf_data = cmd_buf.data.lock();
@@ -1428,7 +1489,7 @@ impl Global {
                 pipeline_flags: PipelineFlags::empty(),
                 binder: Binder::new(),
                 blend_constant: OptionalState::Unused,
-                stencil_reference: 0,
+                stencil_reference: [0; 2],
                 pipeline: None,
                 index: IndexState::default(),
                 vertex: VertexState::default(),
@@ -1436,6 +1497,8 @@ impl Global {
             };
             let mut temp_offsets = Vec::new();
             let mut dynamic_offset_count = 0;
+            let mut temp_inline_buffers = Vec::new();
+            let mut inline_binding_count = 0;
             let mut string_offset = 0;
             let mut active_query = None;
 
@@ -1475,9 +1538,11 @@ impl Global {
                             return Err(DeviceError::WrongDevice).map_pass_err(scope);
                         }
 
-                        bind_group
-                            .validate_dynamic_bindings(index, &temp_offsets, &cmd_buf.limits)
-                            .map_pass_err(scope)?;
+                        if validate {
+                            bind_group
+                                .validate_dynamic_bindings(index, &temp_offsets, &cmd_buf.limits)
+                                .map_pass_err(scope)?;
+                        }
 
                         // merge the resource tracker in
                         unsafe {
@@ -1523,6 +1588,152 @@ impl Global {
                                             &e.dynamic_offsets,
                                         );
                                     }
+                                } else if let Some(group_layout) = e.inline_layout.as_ref() {
+                                    set_inline_bind_group(
+                                        raw,
+                                        pipeline_layout,
+                                        index + i as u32,
+                                        group_layout,
+                                        &e.inline_buffers,
+                                        &snatch_guard,
+                                    )
+                                    .map_pass_err(scope)?;
+                                }
+                            }
+                        }
+                    }
+                    RenderCommand::SetBindGroupInline {
+                        index,
+                        num_bindings,
+                    } => {
+                        api_log!("RenderPass::set_bind_group_inline {index}");
+
+                        let scope = PassErrorScope::SetBindGroupInline;
+                        device
+                            .require_features(wgt::Features::INLINE_BIND_GROUPS)
+                            .map_pass_err(scope)?;
+
+                        let bindings = &base.inline_bindings
+                            [inline_binding_count..inline_binding_count + num_bindings];
+                        inline_binding_count += num_bindings;
+
+                        let max_bind_groups = device.limits.max_bind_groups;
+                        if index >= max_bind_groups {
+                            return Err(RenderCommandError::BindGroupIndexOutOfRange {
+                                index,
+                                max: max_bind_groups,
+                            })
+                            .map_pass_err(scope);
+                        }
+
+                        // The layout of an inline group comes from the current pipeline.
+                        let pipeline_layout = state.binder.pipeline_layout.clone();
+                        let group_layout = pipeline_layout
+                            .as_ref()
+                            .and_then(|layout| layout.bind_group_layouts.get(index as usize))
+                            .filter(|group_layout| group_layout.origin == bgl::Origin::Inline)
+                            .cloned()
+                            .ok_or(RenderCommandError::MissingInlineBindGroupLayout { index })
+                            .map_pass_err(scope)?;
+
+                        validate_inline_bindings(&group_layout, bindings)
+                            .map_err(RenderCommandError::from)
+                            .map_pass_err(scope)?;
+
+                        temp_inline_buffers.clear();
+                        for decl in group_layout.entries.values() {
+                            let bb = &bindings
+                                .iter()
+                                .find(|entry| entry.binding == decl.binding)
+                                .unwrap()
+                                .buffer;
+                            let (pub_usage, internal_use) = match decl.ty {
+                                wgt::BindingType::Buffer {
+                                    ty: wgt::BufferBindingType::Uniform,
+                                    ..
+                                } => (BufferUsages::UNIFORM, hal::BufferUses::UNIFORM),
+                                wgt::BindingType::Buffer {
+                                    ty: wgt::BufferBindingType::Storage { read_only },
+                                    ..
+                                } => (
+                                    BufferUsages::STORAGE,
+                                    if read_only {
+                                        hal::BufferUses::STORAGE_READ
+                                    } else {
+                                        hal::BufferUses::STORAGE_READ_WRITE
+                                    },
+                                ),
+                                _ => unreachable!(),
+                            };
+
+                            let buffer = info
+                                .usage_scope
+                                .buffers
+                                .merge_single(&*buffer_guard, bb.buffer_id, internal_use)
+                                .map_pass_err(scope)?;
+
+                            if buffer.device.as_info().id() != device.as_info().id() {
+                                return Err(DeviceError::WrongDevice).map_pass_err(scope);
+                            }
+
+                            check_buffer_usage(bb.buffer_id, buffer.usage, pub_usage)
+                                .map_pass_err(scope)?;
+                            if buffer.raw.get(&snatch_guard).is_none() {
+                                return Err(RenderCommandError::DestroyedBuffer(bb.buffer_id))
+                                    .map_pass_err(scope);
+                            }
+
+                            let bind_size =
+                                validate_inline_buffer_range(bb, buffer.size, decl, &device.limits)
+                                    .map_err(RenderCommandError::from)
+                                    .map_pass_err(scope)?;
+
+                            buffer_memory_init_actions.extend(
+                                buffer.initialization_status.read().create_action(
+                                    buffer,
+                                    bb.offset..bb.offset + bind_size,
+                                    MemoryInitKind::NeedsInitializedMemory,
+                                ),
+                            );
+
+                            temp_inline_buffers.push(InlineBufferBinding {
+                                buffer: buffer.clone(),
+                                offset: bb.offset,
+                                size: bb.size,
+                            });
+                        }
+
+                        let entries = state.binder.assign_inline_group(
+                            index as usize,
+                            &group_layout,
+                            &temp_inline_buffers,
+                        );
+                        if !entries.is_empty() {
+                            let pipeline_layout = pipeline_layout.as_ref().unwrap().raw();
+                            for (i, e) in entries.iter().enumerate() {
+                                if let Some(group) = e.group.as_ref() {
+                                    let raw_bg = group
+                                        .raw(&snatch_guard)
+                                        .ok_or(RenderPassErrorInner::InvalidBindGroup(i))
+                                        .map_pass_err(scope)?;
+                                    unsafe {
+                                        raw.set_bind_group(
+                                            pipeline_layout,
+                                            index + i as u32,
+                                            raw_bg,
+                                            &e.dynamic_offsets,
+                                        );
+                                    }
+                                } else if let Some(group_layout) = e.inline_layout.as_ref() {
+                                    set_inline_bind_group(
+                                        raw,
+                                        pipeline_layout,
+                                        index + i as u32,
+                                        group_layout,
+                                        &e.inline_buffers,
+                                        &snatch_guard,
+                                    )
+                                    .map_pass_err(scope)?;
                                 }
                             }
                         }
@@ -1571,8 +1782,13 @@ impl Global {
                         }
 
                         if pipeline.flags.contains(PipelineFlags::STENCIL_REFERENCE) {
+                            let [front, back] = state.stencil_reference;
                             unsafe {
-                                raw.set_stencil_reference(state.stencil_reference);
+                                if front == back {
+                                    raw.set_stencil_reference(front);
+                                } else {
+                                    raw.set_stencil_reference_separate(front, back);
+                                }
                             }
                         }
 
@@ -1604,6 +1820,16 @@ impl Global {
                                                 &e.dynamic_offsets,
                                             );
                                         }
+                                    } else if let Some(group_layout) = e.inline_layout.as_ref() {
+                                        set_inline_bind_group(
+                                            raw,
+                                            pipeline.layout.raw(),
+                                            start_index as u32 + i as u32,
+                                            group_layout,
+                                            &e.inline_buffers,
+                                            &snatch_guard,
+                                        )
+                                        .map_pass_err(scope)?;
                                     }
                                 }
                             }
@@ -1672,8 +1898,10 @@ impl Global {
                             return Err(DeviceError::WrongDevice).map_pass_err(scope);
                         }
 
-                        check_buffer_usage(buffer_id, buffer.usage, BufferUsages::INDEX)
-                            .map_pass_err(scope)?;
+                        if validate {
+                            check_buffer_usage(buffer_id, buffer.usage, BufferUsages::INDEX)
+                                .map_pass_err(scope)?;
+                        }
                         let buf_raw = buffer
                             .raw
                             .get(&snatch_guard)
@@ -1734,8 +1962,10 @@ impl Global {
                             .map_pass_err(scope);
                         }
 
-                        check_buffer_usage(buffer_id, buffer.usage, BufferUsages::VERTEX)
-                            .map_pass_err(scope)?;
+                        if validate {
+                            check_buffer_usage(buffer_id, buffer.usage, BufferUsages::VERTEX)
+                                .map_pass_err(scope)?;
+                        }
                         let buf_raw = buffer
                             .raw
                             .get(&snatch_guard)
@@ -1791,7 +2021,7 @@ impl Global {
                     RenderCommand::SetStencilReference(value) => {
                         api_log!("RenderPass::set_stencil_reference {value}");
 
-                        state.stencil_reference = value;
+                        state.stencil_reference = [value; 2];
                         if state
                             .pipeline_flags
                             .contains(PipelineFlags::STENCIL_REFERENCE)
@@ -1801,6 +2031,73 @@ impl Global {
                             }
                         }
                     }
+                    RenderCommand::SetStencilReferenceSeparate { front, back } => {
+                        api_log!("RenderPass::set_stencil_reference_separate {front} {back}");
+
+                        let scope = PassErrorScope::SetStencilReferenceSeparate;
+                        device
+                            .require_features(wgt::Features::SEPARATE_STENCIL_STATE)
+                            .map_pass_err(scope)?;
+                        state.stencil_reference = [front, back];
+                        if state
+                            .pipeline_flags
+                            .contains(PipelineFlags::STENCIL_REFERENCE)
+                        {
+                            unsafe {
+                                raw.set_stencil_reference_separate(front, back);
+                            }
+                        }
+                    }
+                    RenderCommand::SetStencilWriteMask { front, back } => {
+                        api_log!("RenderPass::set_stencil_write_mask {front:#x} {back:#x}");
+
+                        let scope = PassErrorScope::SetStencilWriteMask;
+                        device
+                            .require_features(wgt::Features::SEPARATE_STENCIL_STATE)
+                            .map_pass_err(scope)?;
+                        if info.is_stencil_read_only && (front | back) != 0 {
+                            return Err(RenderCommandError::StencilWriteMaskOnReadOnlyStencil(
+                                front | back,
+                            ))
+                            .map_pass_err(scope);
+                        }
+                        unsafe {
+                            raw.set_stencil_write_mask(front, back);
+                        }
+                    }
+                    RenderCommand::SetFrontFace(front_face) => {
+                        api_log!("RenderPass::set_front_face {front_face:?}");
+
+                        let scope = PassErrorScope::SetFrontFace;
+                        device
+                            .require_features(wgt::Features::EXTENDED_DYNAMIC_STATE)
+                            .map_pass_err(scope)?;
+                        unsafe {
+                            raw.set_front_face(front_face);
+                        }
+                    }
+                    RenderCommand::SetCullMode(cull_mode) => {
+                        api_log!("RenderPass::set_cull_mode {cull_mode:?}");
+
+                        let scope = PassErrorScope::SetCullMode;
+                        device
+                            .require_features(wgt::Features::EXTENDED_DYNAMIC_STATE)
+                            .map_pass_err(scope)?;
+                        unsafe {
+                            raw.set_cull_mode(cull_mode);
+                        }
+                    }
+                    RenderCommand::SetDepthCompare(compare) => {
+                        api_log!("RenderPass::set_depth_compare {compare:?}");
+
+                        let scope = PassErrorScope::SetDepthCompare;
+                        device
+                            .require_features(wgt::Features::EXTENDED_DYNAMIC_STATE)
+                            .map_pass_err(scope)?;
+                        unsafe {
+                            raw.set_depth_compare(compare);
+                        }
+                    }
                     RenderCommand::SetViewport {
                         ref rect,
                         depth_min,
@@ -1809,33 +2106,37 @@ impl Global {
                         api_log!("RenderPass::set_viewport {rect:?}");
 
                         let scope = PassErrorScope::SetViewport;
-                        if rect.x < 0.0
-                            || rect.y < 0.0
-                            || rect.w <= 0.0
-                            || rect.h <= 0.0
-                            || rect.x + rect.w > info.extent.width as f32
-                            || rect.y + rect.h > info.extent.height as f32
-                        {
-                            return Err(RenderCommandError::InvalidViewportRect(
-                                *rect,
-                                info.extent,
-                            ))
-                            .map_pass_err(scope);
+                        let r = validate_viewport(rect, depth_min, depth_max, info.extent)
+                            .map_pass_err(scope)?;
+                        unsafe {
+                            raw.set_viewport(&r, depth_min..depth_max);
                         }
-                        if !(0.0..=1.0).contains(&depth_min) || !(0.0..=1.0).contains(&depth_max) {
-                            return Err(RenderCommandError::InvalidViewportDepth(
-                                depth_min, depth_max,
-                            ))
+                    }
+                    RenderCommand::SetIndexedViewport {
+                        index,
+                        ref rect,
+                        depth_min,
+                        depth_max,
+                    } => {
+                        api_log!("RenderPass::set_viewports {index} {rect:?}");
+
+                        let scope = PassErrorScope::SetViewports;
+                        if index != 0 {
+                            device
+                                .require_features(wgt::Features::MULTI_VIEWPORT)
+                                .map_pass_err(scope)?;
+                        }
+                        if index >= wgt::MAX_VIEWPORTS {
+                            return Err(RenderCommandError::ViewportIndexOutOfRange {
+                                index,
+                                max: wgt::MAX_VIEWPORTS - 1,
+                            })
                             .map_pass_err(scope);
                         }
-                        let r = hal::Rect {
-                            x: rect.x,
-                            y: rect.y,
-                            w: rect.w,
-                            h: rect.h,
-                        };
+                        let r = validate_viewport(rect, depth_min, depth_max, info.extent)
+                            .map_pass_err(scope)?;
                         unsafe {
-                            raw.set_viewport(&r, depth_min..depth_max);
+                            raw.set_indexed_viewport(index, &r, depth_min..depth_max);
                         }
                     }
                     RenderCommand::SetPushConstant {
@@ -1882,22 +2183,32 @@ impl Global {
                         api_log!("RenderPass::set_scissor_rect {rect:?}");
 
                         let scope = PassErrorScope::SetScissorRect;
-                        if rect.x + rect.w > info.extent.width
-                            || rect.y + rect.h > info.extent.height
-                        {
-                            return Err(RenderCommandError::InvalidScissorRect(*rect, info.extent))
-                                .map_pass_err(scope);
-                        }
-                        let r = hal::Rect {
-                            x: rect.x,
-                            y: rect.y,
-                            w: rect.w,
-                            h: rect.h,
-                        };
+                        let r = validate_scissor(rect, info.extent).map_pass_err(scope)?;
                         unsafe {
                             raw.set_scissor_rect(&r);
                         }
                     }
+                    RenderCommand::SetIndexedScissor { index, ref rect } => {
+                        api_log!("RenderPass::set_scissor_rects {index} {rect:?}");
+
+                        let scope = PassErrorScope::SetScissorRects;
+                        if index != 0 {
+                            device
+                                .require_features(wgt::Features::MULTI_VIEWPORT)
+                                .map_pass_err(scope)?;
+                        }
+                        if index >= wgt::MAX_VIEWPORTS {
+                            return Err(RenderCommandError::ViewportIndexOutOfRange {
+                                index,
+                                max: wgt::MAX_VIEWPORTS - 1,
+                            })
+                            .map_pass_err(scope);
+                        }
+                        let r = validate_scissor(rect, info.extent).map_pass_err(scope)?;
+                        unsafe {
+                            raw.set_indexed_scissor_rect(index, &r);
+                        }
+                    }
                     RenderCommand::Draw {
                         vertex_count,
                         instance_count,
@@ -1914,11 +2225,15 @@ impl Global {
                             indirect: false,
                             pipeline: state.pipeline,
                         };
-                        state.is_ready(indexed).map_pass_err(scope)?;
+                        if validate {
+                            state.is_ready(indexed).map_pass_err(scope)?;
+                        } else {
+                            state.is_bound(indexed).map_pass_err(scope)?;
+                        }
 
                         let last_vertex = first_vertex as u64 + vertex_count as u64;
                         let vertex_limit = state.vertex.vertex_limit;
-                        if last_vertex > vertex_limit {
+                        if validate && last_vertex > vertex_limit {
                             return Err(DrawError::VertexBeyondLimit {
                                 last_vertex,
                                 vertex_limit,
@@ -1928,7 +2243,7 @@ impl Global {
                         }
                         let last_instance = first_instance as u64 + instance_count as u64;
                         let instance_limit = state.vertex.instance_limit;
-                        if last_instance > instance_limit {
+                        if validate && last_instance > instance_limit {
                             return Err(DrawError::InstanceBeyondLimit {
                                 last_instance,
                                 instance_limit,
@@ -1963,11 +2278,15 @@ impl Global {
                             indirect: false,
                             pipeline: state.pipeline,
                         };
-                        state.is_ready(indexed).map_pass_err(scope)?;
+                        if validate {
+                            state.is_ready(indexed).map_pass_err(scope)?;
+                        } else {
+                            state.is_bound(indexed).map_pass_err(scope)?;
+                        }
 
                         let last_index = first_index as u64 + index_count as u64;
                         let index_limit = state.index.limit;
-                        if last_index > index_limit {
+                        if validate && last_index > index_limit {
                             return Err(DrawError::IndexBeyondLimit {
                                 last_index,
                                 index_limit,
@@ -1976,7 +2295,7 @@ impl Global {
                         }
                         let last_instance = first_instance as u64 + instance_count as u64;
                         let instance_limit = state.vertex.instance_limit;
-                        if last_instance > instance_limit {
+                        if validate && last_instance > instance_limit {
                             return Err(DrawError::InstanceBeyondLimit {
                                 last_instance,
                                 instance_limit,
@@ -2010,7 +2329,11 @@ impl Global {
                             indirect: true,
                             pipeline: state.pipeline,
                         };
-                        state.is_ready(indexed).map_pass_err(scope)?;
+                        if validate {
+                            state.is_ready(indexed).map_pass_err(scope)?;
+                        } else {
+                            state.is_bound(indexed).map_pass_err(scope)?;
+                        }
 
                         let stride = match indexed {
                             false => mem::size_of::<wgt::DrawIndirectArgs>(),
@@ -2031,12 +2354,14 @@ impl Global {
                             .buffers
                             .merge_single(&*buffer_guard, buffer_id, hal::BufferUses::INDIRECT)
                             .map_pass_err(scope)?;
-                        check_buffer_usage(
-                            buffer_id,
-                            indirect_buffer.usage,
-                            BufferUsages::INDIRECT,
-                        )
-                        .map_pass_err(scope)?;
+                        if validate {
+                            check_buffer_usage(
+                                buffer_id,
+                                indirect_buffer.usage,
+                                BufferUsages::INDIRECT,
+                            )
+                            .map_pass_err(scope)?;
+                        }
                         let indirect_raw = indirect_buffer
                             .raw
                             .get(&snatch_guard)
@@ -2088,7 +2413,11 @@ impl Global {
                             indirect: true,
                             pipeline: state.pipeline,
                         };
-                        state.is_ready(indexed).map_pass_err(scope)?;
+                        if validate {
+                            state.is_ready(indexed).map_pass_err(scope)?;
+                        } else {
+                            state.is_bound(indexed).map_pass_err(scope)?;
+                        }
 
                         let stride = match indexed {
                             false => mem::size_of::<wgt::DrawIndirectArgs>(),
@@ -2107,12 +2436,14 @@ impl Global {
                             .buffers
                             .merge_single(&*buffer_guard, buffer_id, hal::BufferUses::INDIRECT)
                             .map_pass_err(scope)?;
-                        check_buffer_usage(
-                            buffer_id,
-                            indirect_buffer.usage,
-                            BufferUsages::INDIRECT,
-                        )
-                        .map_pass_err(scope)?;
+                        if validate {
+                            check_buffer_usage(
+                                buffer_id,
+                                indirect_buffer.usage,
+                                BufferUsages::INDIRECT,
+                            )
+                            .map_pass_err(scope)?;
+                        }
                         let indirect_raw = indirect_buffer
                             .raw
                             .get(&snatch_guard)
@@ -2128,8 +2459,14 @@ impl Global {
                                 hal::BufferUses::INDIRECT,
                             )
                             .map_pass_err(scope)?;
-                        check_buffer_usage(buffer_id, count_buffer.usage, BufferUsages::INDIRECT)
+                        if validate {
+                            check_buffer_usage(
+                                buffer_id,
+                                count_buffer.usage,
+                                BufferUsages::INDIRECT,
+                            )
                             .map_pass_err(scope)?;
+                        }
                         let count_raw = count_buffer
                             .raw
                             .get(&snatch_guard)
@@ -2459,12 +2796,187 @@ impl Global {
     }
 }
 
+fn validate_viewport(
+    rect: &Rect<f32>,
+    depth_min: f32,
+    depth_max: f32,
+    extent: wgt::Extent3d,
+) -> Result<hal::Rect<f32>, RenderCommandError> {
+    if rect.x < 0.0
+        || rect.y < 0.0
+        || rect.w <= 0.0
+        || rect.h <= 0.0
+        || rect.x + rect.w > extent.width as f32
+        || rect.y + rect.h > extent.height as f32
+    {
+        return Err(RenderCommandError::InvalidViewportRect(*rect, extent));
+    }
+    if !(0.0..=1.0).contains(&depth_min) || !(0.0..=1.0).contains(&depth_max) {
+        return Err(RenderCommandError::InvalidViewportDepth(
+            depth_min, depth_max,
+        ));
+    }
+    Ok(hal::Rect {
+        x: rect.x,
+        y: rect.y,
+        w: rect.w,
+        h: rect.h,
+    })
+}
+
+fn validate_scissor(
+    rect: &Rect<u32>,
+    extent: wgt::Extent3d,
+) -> Result<hal::Rect<u32>, RenderCommandError> {
+    if rect.x + rect.w > extent.width || rect.y + rect.h > extent.height {
+        return Err(RenderCommandError::InvalidScissorRect(*rect, extent));
+    }
+    Ok(hal::Rect {
+        x: rect.x,
+        y: rect.y,
+        w: rect.w,
+        h: rect.h,
+    })
+}
+
+/// Checks that `bindings` has exactly one binding for each entry of an inline
+/// bind group layout.
+fn validate_inline_bindings<A: HalApi>(
+    layout: &BindGroupLayout<A>,
+    bindings: &[InlineBinding],
+) -> Result<(), CreateBindGroupError> {
+    if bindings.len() != layout.entries.len() {
+        return Err(CreateBindGroupError::BindingsNumMismatch {
+            expected: layout.entries.len(),
+            actual: bindings.len(),
+        });
+    }
+    for (i, entry) in bindings.iter().enumerate() {
+        if !layout.entries.contains_key(entry.binding) {
+            return Err(CreateBindGroupError::MissingBindingDeclaration(
+                entry.binding,
+            ));
+        }
+        if bindings[..i]
+            .iter()
+            .any(|other| other.binding == entry.binding)
+        {
+            return Err(CreateBindGroupError::DuplicateBinding(entry.binding));
+        }
+    }
+    Ok(())
+}
+
+/// Validates the range of a buffer bound to an inline bind group, returning the
+/// size of the bound range.
+fn validate_inline_buffer_range(
+    bb: &BufferBinding,
+    buffer_size: BufferAddress,
+    decl: &wgt::BindGroupLayoutEntry,
+    limits: &wgt::Limits,
+) -> Result<BufferAddress, CreateBindGroupError> {
+    use CreateBindGroupError as Error;
+
+    let wgt::BindingType::Buffer {
+        ty,
+        min_binding_size,
+        ..
+    } = decl.ty
+    else {
+        unreachable!()
+    };
+    let range_limit = match ty {
+        wgt::BufferBindingType::Uniform => limits.max_uniform_buffer_binding_size,
+        wgt::BufferBindingType::Storage { .. } => limits.max_storage_buffer_binding_size,
+    };
+
+    let (align, align_limit_name) = binding_model::buffer_binding_type_alignment(limits, ty);
+    if bb.offset % align as u64 != 0 {
+        return Err(Error::UnalignedBufferOffset(
+            bb.offset,
+            align_limit_name,
+            align,
+        ));
+    }
+
+    let bind_size = match bb.size {
+        Some(size) => {
+            let end = bb.offset + size.get();
+            if end > buffer_size {
+                return Err(Error::BindingRangeTooLarge {
+                    buffer: bb.buffer_id,
+                    range: bb.offset..end,
+                    size: buffer_size,
+                });
+            }
+            size.get()
+        }
+        None => {
+            if buffer_size < bb.offset {
+                return Err(Error::BindingRangeTooLarge {
+                    buffer: bb.buffer_id,
+                    range: bb.offset..bb.offset,
+                    size: buffer_size,
+                });
+            }
+            buffer_size - bb.offset
+        }
+    };
+
+    if bind_size > range_limit as u64 {
+        return Err(Error::BufferRangeTooLarge {
+            binding: decl.binding,
+            given: bind_size as u32,
+            limit: range_limit,
+        });
+    }
+
+    match min_binding_size {
+        Some(min_size) if min_size.get() > bind_size => Err(Error::BindingSizeTooSmall {
+            buffer: bb.buffer_id,
+            actual: bind_size,
+            min: min_size.get(),
+        }),
+        None if bind_size == 0 => Err(Error::BindingZeroSize(bb.buffer_id)),
+        _ => Ok(bind_size),
+    }
+}
+
+/// Sets an inline bind group with the buffers of `buffers`.
+fn set_inline_bind_group<A: HalApi>(
+    raw: &mut A::CommandEncoder,
+    pipeline_layout: &A::PipelineLayout,
+    index: u32,
+    group_layout: &BindGroupLayout<A>,
+    buffers: &[InlineBufferBinding<A>],
+    snatch_guard: &SnatchGuard,
+) -> Result<(), RenderCommandError> {
+    let mut raw_buffers = ArrayVec::<_, { wgt::MAX_INLINE_BINDINGS as usize }>::new();
+    for binding in buffers {
+        let buffer_id = binding.buffer.as_info().id();
+        let buffer = binding
+            .buffer
+            .raw
+            .get(snatch_guard)
+            .ok_or(RenderCommandError::DestroyedBuffer(buffer_id))?;
+        raw_buffers.push(hal::BufferBinding {
+            buffer,
+            offset: binding.offset,
+            size: binding.size,
+        });
+    }
+    unsafe {
+        raw.set_bind_group_inline(pipeline_layout, index, group_layout.raw(), &raw_buffers);
+    }
+    Ok(())
+}
+
 pub mod render_commands {
     use super::{
         super::{Rect, RenderCommand},
         RenderPass,
     };
-    use crate::id;
+    use crate::{binding_model::InlineBinding, id};
     use std::{convert::TryInto, num::NonZeroU32};
     use wgt::{BufferAddress, BufferSize, Color, DynamicOffset, IndexFormat};
 
@@ -2496,6 +3008,21 @@ pub mod render_commands {
         });
     }
 
+    pub fn wgpu_render_pass_set_bind_group_inline(
+        pass: &mut RenderPass,
+        index: u32,
+        bindings: &[InlineBinding],
+    ) {
+        // Inline bind groups are never redundant, and replace the bind group at `index`.
+        pass.current_bind_groups.forget(index);
+        pass.base.inline_bindings.extend_from_slice(bindings);
+
+        pass.base.commands.push(RenderCommand::SetBindGroupInline {
+            index,
+            num_bindings: bindings.len(),
+        });
+    }
+
     pub fn wgpu_render_pass_set_pipeline(pass: &mut RenderPass, pipeline_id: id::RenderPipelineId) {
         if pass.current_pipeline.set_and_check_redundant(pipeline_id) {
             return;
@@ -2543,6 +3070,43 @@ pub mod render_commands {
             .push(RenderCommand::SetStencilReference(value));
     }
 
+    pub fn wgpu_render_pass_set_stencil_reference_separate(
+        pass: &mut RenderPass,
+        front: u32,
+        back: u32,
+    ) {
+        pass.base
+            .commands
+            .push(RenderCommand::SetStencilReferenceSeparate { front, back });
+    }
+
+    pub fn wgpu_render_pass_set_stencil_write_mask(pass: &mut RenderPass, front: u32, back: u32) {
+        pass.base
+            .commands
+            .push(RenderCommand::SetStencilWriteMask { front, back });
+    }
+
+    pub fn wgpu_render_pass_set_front_face(pass: &mut RenderPass, front_face: wgt::FrontFace) {
+        pass.base
+            .commands
+            .push(RenderCommand::SetFrontFace(front_face));
+    }
+
+    pub fn wgpu_render_pass_set_cull_mode(pass: &mut RenderPass, cull_mode: Option<wgt::Face>) {
+        pass.base
+            .commands
+            .push(RenderCommand::SetCullMode(cull_mode));
+    }
+
+    pub fn wgpu_render_pass_set_depth_compare(
+        pass: &mut RenderPass,
+        compare: wgt::CompareFunction,
+    ) {
+        pass.base
+            .commands
+            .push(RenderCommand::SetDepthCompare(compare));
+    }
+
     pub fn wgpu_render_pass_set_viewport(
         pass: &mut RenderPass,
         x: f32,
@@ -2571,6 +3135,36 @@ pub mod render_commands {
             .push(RenderCommand::SetScissor(Rect { x, y, w, h }));
     }
 
+    pub fn wgpu_render_pass_set_viewports(pass: &mut RenderPass, viewports: &[wgt::Viewport]) {
+        for (index, viewport) in viewports.iter().enumerate() {
+            pass.base.commands.push(RenderCommand::SetIndexedViewport {
+                index: index as u32,
+                rect: Rect {
+                    x: viewport.x,
+                    y: viewport.y,
+                    w: viewport.width,
+                    h: viewport.height,
+                },
+                depth_min: viewport.min_depth,
+                depth_max: viewport.max_depth,
+            });
+        }
+    }
+
+    pub fn wgpu_render_pass_set_scissor_rects(pass: &mut RenderPass, rects: &[wgt::ScissorRect]) {
+        for (index, rect) in rects.iter().enumerate() {
+            pass.base.commands.push(RenderCommand::SetIndexedScissor {
+                index: index as u32,
+                rect: Rect {
+                    x: rect.x,
+                    y: rect.y,
+                    w: rect.width,
+                    h: rect.height,
+                },
+            });
+        }
+    }
+
     /// # Safety
     ///
     /// This function is unsafe as there is no guarantee that the given pointer is
@@ -4,7 +4,7 @@ use crate::{
     api_log,
     command::{clear_texture, CommandBuffer, CommandEncoderError},
     conv,
-    device::{Device, DeviceError, MissingDownlevelFlags},
+    device::{Device, DeviceError, MissingDownlevelFlags, MissingFeatures},
     error::{ErrorFormatter, PrettyError},
     global::Global,
     hal_api::HalApi,
@@ -128,7 +128,7 @@ pub enum TransferError {
     #[error("The entire texture must be copied when copying from depth texture")]
     InvalidDepthTextureExtent,
     #[error(
-        "Source format ({src_format:?}) and destination format ({dst_format:?}) are not copy-compatible (they may only differ in srgb-ness)"
+        "Source format ({src_format:?}) and destination format ({dst_format:?}) are not copy-compatible (they may only differ in srgb-ness, or in the interpretation of their texel blocks with `Features::TEXTURE_FORMAT_REINTERPRETATION`)"
     )]
     TextureFormatsNotCopyCompatible {
         src_format: wgt::TextureFormat,
@@ -142,6 +142,20 @@ pub enum TransferError {
     InvalidSampleCount { sample_count: u32 },
     #[error("Requested mip level {requested} does no exist (count: {count})")]
     InvalidMipLevel { requested: u32, count: u32 },
+    #[error(transparent)]
+    MissingFeatures(#[from] MissingFeatures),
+    #[error("Blits are only supported between 2D textures, got a {0:?} texture")]
+    InvalidBlitDimension(wgt::TextureDimension),
+    #[error("Blits copy a single array layer, got a size with {0} layers")]
+    InvalidBlitLayerCount(u32),
+    #[error("Blitted textures must have a sample count of 1, got {0}")]
+    InvalidBlitSampleCount(u32),
+    #[error("Format {0:?} can't be blitted, only uncompressed color formats with a float sample type can")]
+    UnsupportedBlitFormat(wgt::TextureFormat),
+    #[error("Source format {0:?} isn't filterable, so it can only be blitted with `FilterMode::Nearest`")]
+    UnfilterableBlitSource(wgt::TextureFormat),
+    #[error("Destination format {0:?} isn't renderable, so it can't be blitted to")]
+    UnrenderableBlitDestination(wgt::TextureFormat),
 }
 
 impl PrettyError for TransferError {
@@ -1154,12 +1168,18 @@ impl Global {
 
         // src and dst texture format must be copy-compatible
         // https://gpuweb.github.io/gpuweb/#copy-compatible
-        if src_texture.desc.format.remove_srgb_suffix()
-            != dst_texture.desc.format.remove_srgb_suffix()
-        {
+        let (src_format, dst_format) = (src_texture.desc.format, dst_texture.desc.format);
+        // `Features::TEXTURE_FORMAT_REINTERPRETATION` also allows copies between
+        // formats with texel blocks of the same size and dimensions.
+        let reinterpretable = device
+            .features
+            .contains(wgt::Features::TEXTURE_FORMAT_REINTERPRETATION)
+            && src_format.block_dimensions() == dst_format.block_dimensions()
+            && src_format.is_reinterpretable_as(dst_format);
+        if src_format.remove_srgb_suffix() != dst_format.remove_srgb_suffix() && !reinterpretable {
             return Err(TransferError::TextureFormatsNotCopyCompatible {
-                src_format: src_texture.desc.format,
-                dst_format: dst_texture.desc.format,
+                src_format,
+                dst_format,
             }
             .into());
         }
@@ -1272,4 +1292,200 @@ impl Global {
 
         Ok(())
     }
+
+    /// Copies a region of a texture into a region of another texture,
+    /// scaling it with `filter` if the sizes differ.
+    pub fn command_encoder_blit_texture<A: HalApi>(
+        &self,
+        command_encoder_id: CommandEncoderId,
+        source: &ImageCopyTexture,
+        source_size: &Extent3d,
+        destination: &ImageCopyTexture,
+        destination_size: &Extent3d,
+        filter: wgt::FilterMode,
+    ) -> Result<(), CopyError> {
+        profiling::scope!("CommandEncoder::blit_texture");
+        api_log!(
+            "CommandEncoder::blit_texture {:?} {source_size:?} -> {:?} {destination_size:?} with {filter:?}",
+            source.texture,
+            destination.texture
+        );
+
+        let hub = A::hub(self);
+
+        let cmd_buf = CommandBuffer::get_encoder(hub, command_encoder_id)?;
+        let device = &cmd_buf.device;
+        if !device.is_valid() {
+            return Err(TransferError::InvalidDevice(cmd_buf.device.as_info().id()).into());
+        }
+        device
+            .require_features(wgt::Features::TEXTURE_BLIT)
+            .map_err(TransferError::from)?;
+
+        let snatch_guard = device.snatchable_lock.read();
+
+        let mut cmd_buf_data = cmd_buf.data.lock();
+        let cmd_buf_data = cmd_buf_data.as_mut().unwrap();
+
+        #[cfg(feature = "trace")]
+        if let Some(ref mut list) = cmd_buf_data.commands {
+            list.push(TraceCommand::BlitTexture {
+                src: *source,
+                src_size: *source_size,
+                dst: *destination,
+                dst_size: *destination_size,
+                filter,
+            });
+        }
+        let encoder = &mut cmd_buf_data.encoder;
+        let tracker = &mut cmd_buf_data.trackers;
+        let texture_memory_actions = &mut cmd_buf_data.texture_memory_actions;
+
+        for size in [source_size, destination_size] {
+            if size.depth_or_array_layers != 1 {
+                return Err(
+                    TransferError::InvalidBlitLayerCount(size.depth_or_array_layers).into(),
+                );
+            }
+        }
+        if source_size.width == 0
+            || source_size.height == 0
+            || destination_size.width == 0
+            || destination_size.height == 0
+        {
+            log::trace!("Ignoring blit_texture of size 0");
+            return Ok(());
+        }
+
+        let src_texture = hub
+            .textures
+            .get(source.texture)
+            .map_err(|_| TransferError::InvalidTexture(source.texture))?;
+        let dst_texture = hub
+            .textures
+            .get(destination.texture)
+            .map_err(|_| TransferError::InvalidTexture(destination.texture))?;
+
+        if src_texture.device.as_info().id() != device.as_info().id() {
+            return Err(DeviceError::WrongDevice.into());
+        }
+        if dst_texture.device.as_info().id() != device.as_info().id() {
+            return Err(DeviceError::WrongDevice.into());
+        }
+
+        for texture in [&src_texture, &dst_texture] {
+            let desc = &texture.desc;
+            if desc.dimension != wgt::TextureDimension::D2 {
+                return Err(TransferError::InvalidBlitDimension(desc.dimension).into());
+            }
+            if desc.sample_count != 1 {
+                return Err(TransferError::InvalidBlitSampleCount(desc.sample_count).into());
+            }
+            let is_float = matches!(
+                desc.format.sample_type(None, Some(device.features)),
+                Some(wgt::TextureSampleType::Float { .. })
+            );
+            if !is_float || desc.format.is_compressed() {
+                return Err(TransferError::UnsupportedBlitFormat(desc.format).into());
+            }
+        }
+        if filter == wgt::FilterMode::Linear
+            && !src_texture
+                .format_features
+                .flags
+                .contains(wgt::TextureFormatFeatureFlags::FILTERABLE)
+        {
+            return Err(TransferError::UnfilterableBlitSource(src_texture.desc.format).into());
+        }
+        if !dst_texture
+            .format_features
+            .allowed_usages
+            .contains(TextureUsages::RENDER_ATTACHMENT)
+        {
+            return Err(TransferError::UnrenderableBlitDestination(dst_texture.desc.format).into());
+        }
+
+        let (src_size, _) =
+            validate_texture_copy_range(source, &src_texture.desc, CopySide::Source, source_size)?;
+        let (dst_size, _) = validate_texture_copy_range(
+            destination,
+            &dst_texture.desc,
+            CopySide::Destination,
+            destination_size,
+        )?;
+
+        let (src_range, src_base) = extract_texture_selector(source, source_size, &src_texture)?;
+        let (dst_range, dst_base) =
+            extract_texture_selector(destination, destination_size, &dst_texture)?;
+
+        // Handle texture init *before* dealing with barrier transitions so we
+        // have an easier time inserting "immediate-inits" that may be required
+        // by prior discards in rare cases.
+        handle_src_texture_init(
+            encoder,
+            tracker,
+            texture_memory_actions,
+            device,
+            source,
+            source_size,
+            &src_texture,
+            &snatch_guard,
+        )?;
+        handle_dst_texture_init(
+            encoder,
+            tracker,
+            texture_memory_actions,
+            device,
+            destination,
+            destination_size,
+            &dst_texture,
+            &snatch_guard,
+        )?;
+
+        let src_pending = cmd_buf_data
+            .trackers
+            .textures
+            .set_single(&src_texture, src_range, hal::TextureUses::COPY_SRC)
+            .ok_or(TransferError::InvalidTexture(source.texture))?;
+        let src_raw = src_texture
+            .raw(&snatch_guard)
+            .ok_or(TransferError::InvalidTexture(source.texture))?;
+        if !src_texture.desc.usage.contains(TextureUsages::COPY_SRC) {
+            return Err(TransferError::MissingCopySrcUsageFlag.into());
+        }
+
+        let mut barriers: ArrayVec<_, 2> = src_pending
+            .map(|pending| pending.into_hal(src_raw))
+            .collect();
+
+        let dst_pending = cmd_buf_data
+            .trackers
+            .textures
+            .set_single(&dst_texture, dst_range, hal::TextureUses::COPY_DST)
+            .ok_or(TransferError::InvalidTexture(destination.texture))?;
+        let dst_raw = dst_texture
+            .raw(&snatch_guard)
+            .ok_or(TransferError::InvalidTexture(destination.texture))?;
+        if !dst_texture.desc.usage.contains(TextureUsages::COPY_DST) {
+            return Err(
+                TransferError::MissingCopyDstUsageFlag(None, Some(destination.texture)).into(),
+            );
+        }
+
+        barriers.extend(dst_pending.map(|pending| pending.into_hal(dst_raw)));
+
+        let region = hal::TextureBlit {
+            src_base,
+            src_size,
+            dst_base,
+            dst_size,
+        };
+        let cmd_buf_raw = cmd_buf_data.encoder.open()?;
+        unsafe {
+            cmd_buf_raw.transition_textures(barriers.into_iter());
+            cmd_buf_raw.blit_texture(src_raw, hal::TextureUses::COPY_SRC, dst_raw, region, filter);
+        }
+
+        Ok(())
+    }
 }
@@ -12,6 +12,9 @@ pub enum Origin {
     Pool,
     /// The bind group layout was derived and is not present in the BGL resource pool.
     Derived,
+    /// The bind group layout was created by the user for inline bind groups and is not
+    /// present in the BGL resource pool.
+    Inline,
 }
 
 /// A HashMap-like structure that stores a BindGroupLayouts [`wgt::BindGroupLayoutEntry`]s.
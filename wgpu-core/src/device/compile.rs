@@ -0,0 +1,258 @@
+//! Compiling pipelines on a thread of their own, to give up on them after a timeout.
+//!
+//! Some drivers hang compiling pathological shaders, and no backend can cancel a
+//! compile that is in progress. If a device has a pipeline compile timeout set,
+//! its pipelines are compiled on a separate thread instead, and pipeline creation
+//! fails once the timeout elapses. The thread keeps running, and destroys the
+//! pipeline should the compile finish after all.
+//!
+//! See [`Global::device_set_pipeline_compile_timeout`].
+//!
+//! [`Global::device_set_pipeline_compile_timeout`]: crate::global::Global::device_set_pipeline_compile_timeout
+
+use std::{num::NonZeroU32, sync::Arc, time::Duration};
+#[cfg(send_sync)]
+use std::{sync::mpsc, thread};
+
+use hal::Device as _;
+
+use crate::{
+    binding_model::PipelineLayout, device::Device, hal_api::HalApi, pipeline::ShaderModule,
+};
+
+/// The result of [`compile_with_timeout`].
+pub(crate) enum Compiled<T> {
+    Done(Result<T, hal::PipelineError>),
+    TimedOut,
+}
+
+/// Runs `compile` on a new thread and waits at most `timeout` for it to finish.
+///
+/// If it doesn't finish in time, `discard` is called on that thread with the
+/// object `compile` creates once it's done.
+#[cfg(send_sync)]
+fn compile_with_timeout<T: Send + 'static>(
+    timeout: Duration,
+    compile: impl FnOnce() -> Result<T, hal::PipelineError> + Send + 'static,
+    discard: impl FnOnce(T) + Send + 'static,
+) -> Compiled<T> {
+    // Without a buffer, sending only succeeds if the result is received in time.
+    let (sender, receiver) = mpsc::sync_channel(0);
+    let spawned = thread::Builder::new()
+        .name("wgpu pipeline compile".to_string())
+        .spawn(move || {
+            if let Err(mpsc::SendError(Ok(raw))) = sender.send(compile()) {
+                log::warn!("Discarding pipeline that finished compiling after the timeout");
+                discard(raw);
+            }
+        });
+    if let Err(err) = spawned {
+        log::error!("Unable to spawn a thread for compiling a pipeline: {err}");
+        return Compiled::Done(Err(hal::PipelineError::Device(
+            hal::DeviceError::ResourceCreationFailed,
+        )));
+    }
+
+    match receiver.recv_timeout(timeout) {
+        Ok(result) => Compiled::Done(result),
+        Err(mpsc::RecvTimeoutError::Timeout) => Compiled::TimedOut,
+        Err(mpsc::RecvTimeoutError::Disconnected) => {
+            log::error!("Thread compiling a pipeline panicked");
+            Compiled::Done(Err(hal::PipelineError::Device(
+                hal::DeviceError::ResourceCreationFailed,
+            )))
+        }
+    }
+}
+
+/// Without threads, the pipeline is compiled right away, however long it takes.
+#[cfg(not(send_sync))]
+fn compile_with_timeout<T>(
+    _timeout: Duration,
+    compile: impl FnOnce() -> Result<T, hal::PipelineError>,
+    _discard: impl FnOnce(T),
+) -> Compiled<T> {
+    Compiled::Done(compile())
+}
+
+/// Owned counterpart of a [`hal::ProgrammableStage`].
+struct OwnedProgrammableStage<A: HalApi> {
+    module: Arc<ShaderModule<A>>,
+    entry_point: String,
+    constants: naga::back::PipelineConstants,
+    zero_initialize_workgroup_memory: bool,
+    code_capture: Option<Arc<hal::ShaderCodeCapture>>,
+}
+
+impl<A: HalApi> OwnedProgrammableStage<A> {
+    /// Copies `stage`, whose code has to be captured by `code_capture`, if any.
+    fn new(
+        stage: &hal::ProgrammableStage<A>,
+        module: &Arc<ShaderModule<A>>,
+        code_capture: Option<Arc<hal::ShaderCodeCapture>>,
+    ) -> Self {
+        Self {
+            module: module.clone(),
+            entry_point: stage.entry_point.to_string(),
+            constants: stage.constants.clone(),
+            zero_initialize_workgroup_memory: stage.zero_initialize_workgroup_memory,
+            code_capture,
+        }
+    }
+
+    fn to_hal(&self) -> hal::ProgrammableStage<A> {
+        hal::ProgrammableStage {
+            module: self.module.raw(),
+            entry_point: &self.entry_point,
+            constants: &self.constants,
+            zero_initialize_workgroup_memory: self.zero_initialize_workgroup_memory,
+            code_capture: self.code_capture.as_deref(),
+        }
+    }
+}
+
+/// Owned counterpart of a [`hal::VertexBufferLayout`].
+struct OwnedVertexBufferLayout {
+    array_stride: wgt::BufferAddress,
+    step_mode: wgt::VertexStepMode,
+    attributes: Vec<wgt::VertexAttribute>,
+}
+
+/// Owned counterpart of a [`hal::ComputePipelineDescriptor`], which can be
+/// sent to the thread compiling it.
+pub(crate) struct OwnedComputePipelineDescriptor<A: HalApi> {
+    label: Option<String>,
+    layout: Arc<PipelineLayout<A>>,
+    stage: OwnedProgrammableStage<A>,
+}
+
+impl<A: HalApi> OwnedComputePipelineDescriptor<A> {
+    /// Copies `desc`, which has to refer to the raw objects of `layout` and `module`.
+    pub(crate) fn new(
+        desc: &hal::ComputePipelineDescriptor<A>,
+        layout: &Arc<PipelineLayout<A>>,
+        module: &Arc<ShaderModule<A>>,
+        code_capture: Option<Arc<hal::ShaderCodeCapture>>,
+    ) -> Self {
+        Self {
+            label: desc.label.map(str::to_string),
+            layout: layout.clone(),
+            stage: OwnedProgrammableStage::new(&desc.stage, module, code_capture),
+        }
+    }
+
+    /// Compiles the pipeline, giving up after `timeout`.
+    pub(crate) fn compile(
+        self,
+        device: &Arc<Device<A>>,
+        timeout: Duration,
+    ) -> Compiled<A::ComputePipeline> {
+        let compiling_device = device.clone();
+        let discarding_device = device.clone();
+        compile_with_timeout(
+            timeout,
+            move || {
+                let desc = hal::ComputePipelineDescriptor {
+                    label: self.label.as_deref(),
+                    layout: self.layout.raw(),
+                    stage: self.stage.to_hal(),
+                };
+                unsafe { compiling_device.raw().create_compute_pipeline(&desc) }
+            },
+            move |raw| unsafe { discarding_device.raw().destroy_compute_pipeline(raw) },
+        )
+    }
+}
+
+/// Owned counterpart of a [`hal::RenderPipelineDescriptor`], which can be
+/// sent to the thread compiling it.
+pub(crate) struct OwnedRenderPipelineDescriptor<A: HalApi> {
+    label: Option<String>,
+    layout: Arc<PipelineLayout<A>>,
+    vertex_buffers: Vec<OwnedVertexBufferLayout>,
+    vertex_stage: OwnedProgrammableStage<A>,
+    primitive: wgt::PrimitiveState,
+    depth_stencil: Option<wgt::DepthStencilState>,
+    multisample: wgt::MultisampleState,
+    fragment_stage: Option<OwnedProgrammableStage<A>>,
+    color_targets: Vec<Option<wgt::ColorTargetState>>,
+    multiview: Option<NonZeroU32>,
+}
+
+impl<A: HalApi> OwnedRenderPipelineDescriptor<A> {
+    /// Copies `desc`, which has to refer to the raw objects of `layout` and the
+    /// shader modules.
+    pub(crate) fn new(
+        desc: &hal::RenderPipelineDescriptor<A>,
+        layout: &Arc<PipelineLayout<A>>,
+        vertex_module: &Arc<ShaderModule<A>>,
+        vertex_code_capture: Option<Arc<hal::ShaderCodeCapture>>,
+        fragment_module: Option<&Arc<ShaderModule<A>>>,
+        fragment_code_capture: Option<Arc<hal::ShaderCodeCapture>>,
+    ) -> Self {
+        Self {
+            label: desc.label.map(str::to_string),
+            layout: layout.clone(),
+            vertex_buffers: desc
+                .vertex_buffers
+                .iter()
+                .map(|vb| OwnedVertexBufferLayout {
+                    array_stride: vb.array_stride,
+                    step_mode: vb.step_mode,
+                    attributes: vb.attributes.to_vec(),
+                })
+                .collect(),
+            vertex_stage: OwnedProgrammableStage::new(
+                &desc.vertex_stage,
+                vertex_module,
+                vertex_code_capture,
+            ),
+            primitive: desc.primitive,
+            depth_stencil: desc.depth_stencil.clone(),
+            multisample: desc.multisample,
+            fragment_stage: desc.fragment_stage.as_ref().zip(fragment_module).map(
+                |(stage, module)| OwnedProgrammableStage::new(stage, module, fragment_code_capture),
+            ),
+            color_targets: desc.color_targets.to_vec(),
+            multiview: desc.multiview,
+        }
+    }
+
+    /// Compiles the pipeline, giving up after `timeout`.
+    pub(crate) fn compile(
+        self,
+        device: &Arc<Device<A>>,
+        timeout: Duration,
+    ) -> Compiled<A::RenderPipeline> {
+        let compiling_device = device.clone();
+        let discarding_device = device.clone();
+        compile_with_timeout(
+            timeout,
+            move || {
+                let vertex_buffers = self
+                    .vertex_buffers
+                    .iter()
+                    .map(|vb| hal::VertexBufferLayout {
+                        array_stride: vb.array_stride,
+                        step_mode: vb.step_mode,
+                        attributes: &vb.attributes,
+                    })
+                    .collect::<Vec<_>>();
+                let desc = hal::RenderPipelineDescriptor {
+                    label: self.label.as_deref(),
+                    layout: self.layout.raw(),
+                    vertex_buffers: &vertex_buffers,
+                    vertex_stage: self.vertex_stage.to_hal(),
+                    primitive: self.primitive,
+                    depth_stencil: self.depth_stencil.clone(),
+                    multisample: self.multisample,
+                    fragment_stage: self.fragment_stage.as_ref().map(|stage| stage.to_hal()),
+                    color_targets: &self.color_targets,
+                    multiview: self.multiview,
+                };
+                unsafe { compiling_device.raw().create_render_pipeline(&desc) }
+            },
+            move |raw| unsafe { discarding_device.raw().destroy_render_pipeline(raw) },
+        )
+    }
+}
@@ -0,0 +1,156 @@
+//! D3D11 devices layered on the queues of DX12 devices through D3D11On12,
+//! sharing textures with APIs that only speak D3D11.
+
+use crate::{
+    api_log,
+    device::DeviceError,
+    global::Global,
+    hal_api::HalApi,
+    id,
+    resource::{D3D11On12Error, TextureInner},
+};
+
+use hal::{
+    api::Dx12,
+    dx12::{D3D11On12Device, D3D11Texture},
+    CommandEncoder as _,
+};
+
+use std::sync::atomic::Ordering;
+
+impl Global {
+    /// Create a D3D11 device submitting its work to the DX12 queue `queue_id`.
+    ///
+    /// The D3D11 device is owned by the caller, and keeps the queue alive.
+    pub fn queue_create_d3d11on12_device(
+        &self,
+        queue_id: id::QueueId,
+    ) -> Result<D3D11On12Device, D3D11On12Error> {
+        api_log!("Queue::create_d3d11on12_device {queue_id:?}");
+
+        if queue_id.backend() != wgt::Backend::Dx12 {
+            return Err(D3D11On12Error::Unsupported(queue_id.backend()));
+        }
+
+        let queue = Dx12::hub(self)
+            .queues
+            .get(queue_id)
+            .map_err(|_| DeviceError::InvalidQueueId)?;
+        let device = queue.device.as_ref().unwrap();
+        if !device.is_valid() {
+            return Err(DeviceError::Lost.into());
+        }
+
+        device
+            .raw()
+            .create_d3d11on12_device(queue.raw.as_ref().unwrap())
+            .map_err(|error| DeviceError::from(error).into())
+    }
+
+    /// Wrap `texture_id` as a D3D11 resource of `d3d11_device`, bindable as
+    /// allowed by the usage of the texture.
+    ///
+    /// # Safety
+    ///
+    /// - `d3d11_device` must have been created on a queue of the device of the
+    ///   texture.
+    /// - The returned texture must not outlive the texture.
+    pub unsafe fn texture_wrap_for_d3d11(
+        &self,
+        texture_id: id::TextureId,
+        d3d11_device: &D3D11On12Device,
+    ) -> Result<D3D11Texture, D3D11On12Error> {
+        api_log!("Texture::wrap_for_d3d11 {texture_id:?}");
+
+        if texture_id.backend() != wgt::Backend::Dx12 {
+            return Err(D3D11On12Error::Unsupported(texture_id.backend()));
+        }
+
+        let texture = Dx12::hub(self)
+            .textures
+            .get(texture_id)
+            .map_err(|_| D3D11On12Error::InvalidTexture(texture_id))?;
+        let snatch_guard = texture.device.snatchable_lock.read();
+        // Surface textures belong to the swapchain, and can't be wrapped.
+        let raw = match texture.inner.get(&snatch_guard) {
+            Some(TextureInner::Native { raw }) => raw,
+            _ => return Err(D3D11On12Error::InvalidTexture(texture_id)),
+        };
+
+        unsafe { d3d11_device.wrap_texture(raw, texture.desc.usage) }
+            .map_err(|error| DeviceError::from(error).into())
+    }
+
+    /// Hand `texture_id` over to `d3d11_device`, after the work submitted to
+    /// `queue_id` so far.
+    ///
+    /// The texture is transitioned to the state D3D11On12 expects, and has to
+    /// be released with [`D3D11On12Device::release_texture`] before it's used
+    /// through wgpu again.
+    ///
+    /// # Safety
+    ///
+    /// - `wrapped` must have been wrapped from the texture for `d3d11_device`,
+    ///   which must have been created on `queue_id`.
+    pub unsafe fn queue_acquire_texture_for_d3d11(
+        &self,
+        queue_id: id::QueueId,
+        texture_id: id::TextureId,
+        d3d11_device: &D3D11On12Device,
+        wrapped: &D3D11Texture,
+    ) -> Result<(), D3D11On12Error> {
+        api_log!("Queue::acquire_texture_for_d3d11 {queue_id:?} {texture_id:?}");
+
+        if queue_id.backend() != wgt::Backend::Dx12 {
+            return Err(D3D11On12Error::Unsupported(queue_id.backend()));
+        }
+
+        let hub = Dx12::hub(self);
+        let queue = hub
+            .queues
+            .get(queue_id)
+            .map_err(|_| DeviceError::InvalidQueueId)?;
+        let device = queue.device.as_ref().unwrap();
+        let texture = hub
+            .textures
+            .get(texture_id)
+            .map_err(|_| D3D11On12Error::InvalidTexture(texture_id))?;
+        if texture.device.as_info().id() != device.as_info().id() {
+            return Err(DeviceError::WrongDevice.into());
+        }
+
+        {
+            let snatch_guard = device.snatchable_lock.read();
+            let raw = texture
+                .raw(&snatch_guard)
+                .ok_or(D3D11On12Error::InvalidTexture(texture_id))?;
+            let mut pending_writes = device.pending_writes.lock();
+            let pending_writes = pending_writes.as_mut().unwrap();
+            let encoder = pending_writes.activate();
+            // `PRESENT` is the common state, which D3D11On12 wraps textures in.
+            let mut trackers = device.trackers.lock();
+            let transition = trackers
+                .textures
+                .set_single(
+                    &texture,
+                    texture.full_range.clone(),
+                    hal::TextureUses::PRESENT,
+                )
+                .ok_or(D3D11On12Error::InvalidTexture(texture_id))?;
+            unsafe {
+                encoder.transition_textures(transition.map(|pending| pending.into_hal(raw)));
+            }
+            drop(trackers);
+            pending_writes.insert_texture(texture_id, texture.clone());
+            texture
+                .info
+                .use_at(device.active_submission_index.load(Ordering::Relaxed) + 1);
+        }
+
+        // Submit the transition ahead of the work of the D3D11 device, which
+        // goes to the same queue.
+        self.queue_submit::<Dx12>(queue_id, &[])?;
+        unsafe { d3d11_device.acquire_texture(wrapped) };
+        Ok(())
+    }
+}
@@ -0,0 +1,642 @@
+//! Buffers and textures whose memory is shared with other APIs and processes
+//! through file descriptors or NT handles.
+//!
+//! Only the Vulkan backend on Unix and the DX12 backend support external
+//! memory. The hal entry points are inherent methods of their devices, which
+//! [`ExternalMemoryApi`] forwards to.
+
+#[cfg(feature = "trace")]
+use crate::device::trace;
+use crate::{
+    api_log, conv,
+    device::{Device, DeviceError, MissingFeatures},
+    global::Global,
+    hal_api::HalApi,
+    id::{self, DeviceId},
+    init_tracker::{BufferInitTracker, TextureInitTracker},
+    lock::{rank, RwLock},
+    resource::{self, Buffer, CreateBufferError, CreateTextureError, ExportMemoryError, Texture},
+    LabelHelpers as _,
+};
+
+use std::sync::Arc;
+
+/// The feature [`Global::buffer_export_memory`] and
+/// [`Global::texture_export_memory`] report missing on other backends.
+#[cfg(unix)]
+const EXTERNAL_MEMORY_FEATURE: wgt::Features = wgt::Features::EXTERNAL_MEMORY_FD;
+#[cfg(windows)]
+const EXTERNAL_MEMORY_FEATURE: wgt::Features = wgt::Features::EXTERNAL_MEMORY_WIN32;
+
+/// A backend whose buffers and textures can be exported and imported.
+trait ExternalMemoryApi: HalApi {
+    /// Only Vulkan knows about DRM format modifiers.
+    fn drm_format_modifiers(
+        _device: &Self::Device,
+        _format: wgt::TextureFormat,
+        _usage: hal::TextureUses,
+    ) -> Vec<wgt::DrmFormatModifier> {
+        Vec::new()
+    }
+
+    unsafe fn create_exportable_buffer(
+        device: &Self::Device,
+        desc: &hal::BufferDescriptor,
+        handle_type: wgt::ExternalMemoryHandleType,
+    ) -> Result<Self::Buffer, hal::DeviceError>;
+
+    unsafe fn import_buffer(
+        device: &Self::Device,
+        desc: &hal::BufferDescriptor,
+        memory: wgt::ExternalMemory,
+    ) -> Result<Self::Buffer, hal::DeviceError>;
+
+    unsafe fn create_exportable_texture(
+        device: &Self::Device,
+        desc: &hal::TextureDescriptor,
+        handle_type: wgt::ExternalMemoryHandleType,
+        drm_format_modifiers: &[u64],
+    ) -> Result<Self::Texture, hal::DeviceError>;
+
+    unsafe fn import_texture(
+        device: &Self::Device,
+        desc: &hal::TextureDescriptor,
+        memory: wgt::ExternalMemory,
+    ) -> Result<Self::Texture, hal::DeviceError>;
+
+    fn buffer_handle_type(buffer: &Self::Buffer) -> Option<wgt::ExternalMemoryHandleType>;
+
+    fn texture_handle_type(texture: &Self::Texture) -> Option<wgt::ExternalMemoryHandleType>;
+
+    unsafe fn export_buffer_memory(
+        device: &Self::Device,
+        buffer: &Self::Buffer,
+    ) -> Result<wgt::ExternalMemory, hal::DeviceError>;
+
+    unsafe fn export_texture_memory(
+        device: &Self::Device,
+        texture: &Self::Texture,
+    ) -> Result<wgt::ExternalMemory, hal::DeviceError>;
+}
+
+macro_rules! impl_external_memory_api {
+    ($api:ty $(, $item:item)*) => {
+        impl ExternalMemoryApi for $api {
+            $($item)*
+
+            unsafe fn create_exportable_buffer(
+                device: &Self::Device,
+                desc: &hal::BufferDescriptor,
+                handle_type: wgt::ExternalMemoryHandleType,
+            ) -> Result<Self::Buffer, hal::DeviceError> {
+                unsafe { device.create_exportable_buffer(desc, handle_type) }
+            }
+
+            unsafe fn import_buffer(
+                device: &Self::Device,
+                desc: &hal::BufferDescriptor,
+                memory: wgt::ExternalMemory,
+            ) -> Result<Self::Buffer, hal::DeviceError> {
+                unsafe { device.import_buffer(desc, memory) }
+            }
+
+            unsafe fn create_exportable_texture(
+                device: &Self::Device,
+                desc: &hal::TextureDescriptor,
+                handle_type: wgt::ExternalMemoryHandleType,
+                drm_format_modifiers: &[u64],
+            ) -> Result<Self::Texture, hal::DeviceError> {
+                unsafe { device.create_exportable_texture(desc, handle_type, drm_format_modifiers) }
+            }
+
+            unsafe fn import_texture(
+                device: &Self::Device,
+                desc: &hal::TextureDescriptor,
+                memory: wgt::ExternalMemory,
+            ) -> Result<Self::Texture, hal::DeviceError> {
+                unsafe { device.import_texture(desc, memory) }
+            }
+
+            fn buffer_handle_type(buffer: &Self::Buffer) -> Option<wgt::ExternalMemoryHandleType> {
+                buffer.external_memory_handle_type()
+            }
+
+            fn texture_handle_type(
+                texture: &Self::Texture,
+            ) -> Option<wgt::ExternalMemoryHandleType> {
+                texture.external_memory_handle_type()
+            }
+
+            unsafe fn export_buffer_memory(
+                device: &Self::Device,
+                buffer: &Self::Buffer,
+            ) -> Result<wgt::ExternalMemory, hal::DeviceError> {
+                unsafe { device.export_buffer_memory(buffer) }
+            }
+
+            unsafe fn export_texture_memory(
+                device: &Self::Device,
+                texture: &Self::Texture,
+            ) -> Result<wgt::ExternalMemory, hal::DeviceError> {
+                unsafe { device.export_texture_memory(texture) }
+            }
+        }
+    };
+}
+
+#[cfg(all(vulkan, unix))]
+impl_external_memory_api!(
+    hal::api::Vulkan,
+    fn drm_format_modifiers(
+        device: &Self::Device,
+        format: wgt::TextureFormat,
+        usage: hal::TextureUses,
+    ) -> Vec<wgt::DrmFormatModifier> {
+        device.drm_format_modifiers(format, usage)
+    }
+);
+#[cfg(dx12)]
+impl_external_memory_api!(hal::api::Dx12);
+
+/// Where the memory of a buffer or texture comes from.
+enum ExternalMemory<'a> {
+    /// New memory that can be exported through the given handle type. Textures
+    /// in dma-bufs use one of the listed DRM format modifiers, or any supported
+    /// one if the list is empty.
+    Exportable(wgt::ExternalMemoryHandleType, &'a [u64]),
+    /// Memory exported by another device, API or process.
+    Imported(wgt::ExternalMemory),
+}
+
+impl ExternalMemory<'_> {
+    fn handle_type(&self) -> wgt::ExternalMemoryHandleType {
+        match *self {
+            Self::Exportable(handle_type, _) => handle_type,
+            Self::Imported(ref memory) => memory.handle_type,
+        }
+    }
+}
+
+impl<A: ExternalMemoryApi> Device<A> {
+    fn create_external_buffer(
+        self: &Arc<Self>,
+        desc: &resource::BufferDescriptor,
+        memory: ExternalMemory<'_>,
+    ) -> Result<Buffer<A>, CreateBufferError> {
+        self.require_features(memory.handle_type().required_features())?;
+
+        // Other APIs can't observe our mappings, so we don't allow them.
+        if desc.mapped_at_creation
+            || desc
+                .usage
+                .intersects(wgt::BufferUsages::MAP_READ | wgt::BufferUsages::MAP_WRITE)
+        {
+            return Err(CreateBufferError::MappableExternal(desc.usage));
+        }
+
+        let hal_desc = self.buffer_hal_descriptor(desc, hal::MemoryFlags::empty())?;
+        let imported = matches!(memory, ExternalMemory::Imported(_));
+        let raw = match memory {
+            ExternalMemory::Exportable(handle_type, _) => unsafe {
+                A::create_exportable_buffer(self.raw(), &hal_desc, handle_type)
+            },
+            ExternalMemory::Imported(memory) => unsafe {
+                A::import_buffer(self.raw(), &hal_desc, memory)
+            },
+        }
+        .map_err(DeviceError::from)?;
+
+        let mut buffer = self.create_buffer_from_hal(raw, desc);
+        // Imported memory keeps whatever its exporter put in it, but new memory
+        // is zeroed before use like that of any other buffer.
+        if !imported {
+            buffer.initialization_status = RwLock::new(
+                rank::BUFFER_INITIALIZATION_STATUS,
+                BufferInitTracker::new(hal_desc.size),
+            );
+        }
+        Ok(buffer)
+    }
+
+    fn create_external_texture(
+        self: &Arc<Self>,
+        desc: &resource::TextureDescriptor,
+        memory: ExternalMemory<'_>,
+    ) -> Result<Texture<A>, CreateTextureError> {
+        self.require_features(memory.handle_type().required_features())
+            .map_err(CreateTextureError::MissingExternalMemoryFeature)?;
+
+        let (hal_desc, format_features) =
+            self.texture_hal_descriptor(&self.adapter, desc, hal::MemoryFlags::empty())?;
+        let hal_usage = hal_desc.usage;
+        let imported = matches!(memory, ExternalMemory::Imported(_));
+        let raw = match memory {
+            ExternalMemory::Exportable(handle_type, drm_format_modifiers) => unsafe {
+                A::create_exportable_texture(
+                    self.raw(),
+                    &hal_desc,
+                    handle_type,
+                    drm_format_modifiers,
+                )
+            },
+            ExternalMemory::Imported(memory) => unsafe {
+                A::import_texture(self.raw(), &hal_desc, memory)
+            },
+        }
+        .map_err(DeviceError::from)?;
+
+        let clear_mode = self.create_texture_clear_mode(&raw, hal_usage, desc)?;
+        let mut texture =
+            self.create_texture_from_hal(raw, hal_usage, desc, format_features, clear_mode);
+        if imported {
+            texture.initialization_status = RwLock::new(
+                rank::TEXTURE_INITIALIZATION_STATUS,
+                TextureInitTracker::new(desc.mip_level_count, 0),
+            );
+        }
+        Ok(texture)
+    }
+}
+
+impl Global {
+    /// Return the DRM format modifiers that textures of `format` with `usage`
+    /// can be exported or imported as dma-bufs with.
+    ///
+    /// Returns an empty list if the device doesn't support
+    /// [`wgt::Features::EXTERNAL_MEMORY_DMA_BUF`].
+    pub fn device_drm_format_modifiers(
+        &self,
+        device_id: DeviceId,
+        format: wgt::TextureFormat,
+        usage: wgt::TextureUsages,
+    ) -> Result<Vec<wgt::DrmFormatModifier>, DeviceError> {
+        match device_id.backend() {
+            #[cfg(all(vulkan, unix))]
+            wgt::Backend::Vulkan => {
+                self.drm_format_modifiers::<hal::api::Vulkan>(device_id, format, usage)
+            }
+            #[cfg(dx12)]
+            wgt::Backend::Dx12 => {
+                self.drm_format_modifiers::<hal::api::Dx12>(device_id, format, usage)
+            }
+            _ => Ok(Vec::new()),
+        }
+    }
+
+    /// Create a buffer whose memory can be exported with
+    /// [`Global::buffer_export_memory`].
+    pub fn device_create_exportable_buffer(
+        &self,
+        device_id: DeviceId,
+        desc: &resource::BufferDescriptor,
+        handle_type: wgt::ExternalMemoryHandleType,
+        id_in: Option<id::BufferId>,
+    ) -> (id::BufferId, Option<CreateBufferError>) {
+        profiling::scope!("Device::create_exportable_buffer");
+
+        self.create_external_buffer(
+            device_id,
+            desc,
+            ExternalMemory::Exportable(handle_type, &[]),
+            id_in,
+        )
+    }
+
+    /// Create a buffer backed by memory exported elsewhere.
+    ///
+    /// # Safety
+    ///
+    /// - `memory` must hold a valid handle of its handle type.
+    /// - `memory` must be at least as large as the buffer described by `desc`.
+    /// - Accesses to the memory from elsewhere must be synchronized with
+    ///   accesses through the buffer.
+    pub unsafe fn device_import_buffer(
+        &self,
+        device_id: DeviceId,
+        desc: &resource::BufferDescriptor,
+        memory: wgt::ExternalMemory,
+        id_in: Option<id::BufferId>,
+    ) -> (id::BufferId, Option<CreateBufferError>) {
+        profiling::scope!("Device::import_buffer");
+
+        self.create_external_buffer(device_id, desc, ExternalMemory::Imported(memory), id_in)
+    }
+
+    /// Create a texture whose memory can be exported with
+    /// [`Global::texture_export_memory`].
+    ///
+    /// Textures in dma-bufs use one of `drm_format_modifiers`, or any modifier
+    /// the device supports for them if the list is empty.
+    pub fn device_create_exportable_texture(
+        &self,
+        device_id: DeviceId,
+        desc: &resource::TextureDescriptor,
+        handle_type: wgt::ExternalMemoryHandleType,
+        drm_format_modifiers: &[u64],
+        id_in: Option<id::TextureId>,
+    ) -> (id::TextureId, Option<CreateTextureError>) {
+        profiling::scope!("Device::create_exportable_texture");
+
+        self.create_external_texture(
+            device_id,
+            desc,
+            ExternalMemory::Exportable(handle_type, drm_format_modifiers),
+            id_in,
+        )
+    }
+
+    /// Create a texture backed by memory exported elsewhere.
+    ///
+    /// # Safety
+    ///
+    /// - `memory` must hold a valid handle of its handle type.
+    /// - `memory` must hold an image described by `desc`, laid out with its
+    ///   DRM format modifier and planes for dma-bufs.
+    /// - Accesses to the memory from elsewhere must be synchronized with
+    ///   accesses through the texture.
+    pub unsafe fn device_import_texture(
+        &self,
+        device_id: DeviceId,
+        desc: &resource::TextureDescriptor,
+        memory: wgt::ExternalMemory,
+        id_in: Option<id::TextureId>,
+    ) -> (id::TextureId, Option<CreateTextureError>) {
+        profiling::scope!("Device::import_texture");
+
+        self.create_external_texture(device_id, desc, ExternalMemory::Imported(memory), id_in)
+    }
+
+    /// Export the memory of a buffer created with
+    /// [`Global::device_create_exportable_buffer`].
+    pub fn buffer_export_memory(
+        &self,
+        buffer_id: id::BufferId,
+    ) -> Result<wgt::ExternalMemory, ExportMemoryError> {
+        api_log!("Buffer::export_memory {buffer_id:?}");
+
+        match buffer_id.backend() {
+            #[cfg(all(vulkan, unix))]
+            wgt::Backend::Vulkan => self.export_buffer_memory::<hal::api::Vulkan>(buffer_id),
+            #[cfg(dx12)]
+            wgt::Backend::Dx12 => self.export_buffer_memory::<hal::api::Dx12>(buffer_id),
+            _ => Err(MissingFeatures(EXTERNAL_MEMORY_FEATURE).into()),
+        }
+    }
+
+    /// Export the memory of a texture created with
+    /// [`Global::device_create_exportable_texture`].
+    pub fn texture_export_memory(
+        &self,
+        texture_id: id::TextureId,
+    ) -> Result<wgt::ExternalMemory, ExportMemoryError> {
+        api_log!("Texture::export_memory {texture_id:?}");
+
+        match texture_id.backend() {
+            #[cfg(all(vulkan, unix))]
+            wgt::Backend::Vulkan => self.export_texture_memory::<hal::api::Vulkan>(texture_id),
+            #[cfg(dx12)]
+            wgt::Backend::Dx12 => self.export_texture_memory::<hal::api::Dx12>(texture_id),
+            _ => Err(MissingFeatures(EXTERNAL_MEMORY_FEATURE).into()),
+        }
+    }
+
+    fn drm_format_modifiers<A: ExternalMemoryApi>(
+        &self,
+        device_id: DeviceId,
+        format: wgt::TextureFormat,
+        usage: wgt::TextureUsages,
+    ) -> Result<Vec<wgt::DrmFormatModifier>, DeviceError> {
+        let hub = A::hub(self);
+        let device = hub
+            .devices
+            .get(device_id)
+            .map_err(|_| DeviceError::Invalid)?;
+        if !device.is_valid() {
+            return Err(DeviceError::Lost);
+        }
+        if !device
+            .features
+            .contains(wgt::Features::EXTERNAL_MEMORY_DMA_BUF)
+        {
+            return Ok(Vec::new());
+        }
+
+        let usage = conv::map_texture_usage(usage, format.into());
+        Ok(A::drm_format_modifiers(device.raw(), format, usage))
+    }
+
+    fn export_buffer_memory<A: ExternalMemoryApi>(
+        &self,
+        buffer_id: id::BufferId,
+    ) -> Result<wgt::ExternalMemory, ExportMemoryError> {
+        let hub = A::hub(self);
+        let buffer = hub
+            .buffers
+            .get(buffer_id)
+            .map_err(|_| ExportMemoryError::InvalidBuffer(buffer_id))?;
+        let device = &buffer.device;
+        let snatch_guard = device.snatchable_lock.read();
+        let raw = buffer
+            .raw(&snatch_guard)
+            .ok_or(ExportMemoryError::InvalidBuffer(buffer_id))?;
+        if A::buffer_handle_type(raw).is_none() {
+            return Err(ExportMemoryError::InvalidBuffer(buffer_id));
+        }
+
+        unsafe { A::export_buffer_memory(device.raw(), raw) }
+            .map_err(|error| DeviceError::from(error).into())
+    }
+
+    fn export_texture_memory<A: ExternalMemoryApi>(
+        &self,
+        texture_id: id::TextureId,
+    ) -> Result<wgt::ExternalMemory, ExportMemoryError> {
+        let hub = A::hub(self);
+        let texture = hub
+            .textures
+            .get(texture_id)
+            .map_err(|_| ExportMemoryError::InvalidTexture(texture_id))?;
+        let device = &texture.device;
+        let snatch_guard = device.snatchable_lock.read();
+        let raw = texture
+            .raw(&snatch_guard)
+            .ok_or(ExportMemoryError::InvalidTexture(texture_id))?;
+        if A::texture_handle_type(raw).is_none() {
+            return Err(ExportMemoryError::InvalidTexture(texture_id));
+        }
+
+        unsafe { A::export_texture_memory(device.raw(), raw) }
+            .map_err(|error| DeviceError::from(error).into())
+    }
+
+    fn create_external_buffer(
+        &self,
+        device_id: DeviceId,
+        desc: &resource::BufferDescriptor,
+        memory: ExternalMemory<'_>,
+        id_in: Option<id::BufferId>,
+    ) -> (id::BufferId, Option<CreateBufferError>) {
+        match device_id.backend() {
+            #[cfg(all(vulkan, unix))]
+            wgt::Backend::Vulkan => {
+                self.create_external_buffer_impl::<hal::api::Vulkan>(device_id, desc, memory, id_in)
+            }
+            #[cfg(dx12)]
+            wgt::Backend::Dx12 => {
+                self.create_external_buffer_impl::<hal::api::Dx12>(device_id, desc, memory, id_in)
+            }
+            _ => {
+                let error = MissingFeatures(memory.handle_type().required_features());
+                crate::gfx_select!(device_id => self.external_buffer_error(desc, error.into(), id_in))
+            }
+        }
+    }
+
+    fn create_external_texture(
+        &self,
+        device_id: DeviceId,
+        desc: &resource::TextureDescriptor,
+        memory: ExternalMemory<'_>,
+        id_in: Option<id::TextureId>,
+    ) -> (id::TextureId, Option<CreateTextureError>) {
+        match device_id.backend() {
+            #[cfg(all(vulkan, unix))]
+            wgt::Backend::Vulkan => self
+                .create_external_texture_impl::<hal::api::Vulkan>(device_id, desc, memory, id_in),
+            #[cfg(dx12)]
+            wgt::Backend::Dx12 => {
+                self.create_external_texture_impl::<hal::api::Dx12>(device_id, desc, memory, id_in)
+            }
+            _ => {
+                let error = CreateTextureError::MissingExternalMemoryFeature(MissingFeatures(
+                    memory.handle_type().required_features(),
+                ));
+                crate::gfx_select!(device_id => self.external_texture_error(desc, error, id_in))
+            }
+        }
+    }
+
+    fn create_external_buffer_impl<A: ExternalMemoryApi>(
+        &self,
+        device_id: DeviceId,
+        desc: &resource::BufferDescriptor,
+        memory: ExternalMemory<'_>,
+        id_in: Option<id::BufferId>,
+    ) -> (id::BufferId, Option<CreateBufferError>) {
+        let hub = A::hub(self);
+        let fid = hub.buffers.prepare(id_in);
+
+        let error = loop {
+            let device = match hub.devices.get(device_id) {
+                Ok(device) => device,
+                Err(_) => break DeviceError::Invalid.into(),
+            };
+            if !device.is_valid() {
+                break DeviceError::Lost.into();
+            }
+
+            // NB: The contents of imported memory are not recorded in the replay
+            #[cfg(feature = "trace")]
+            if let Some(trace) = device.trace.lock().as_mut() {
+                trace.add(trace::Action::CreateBuffer(fid.id(), desc.clone()));
+            }
+
+            let buffer = match device.create_external_buffer(desc, memory) {
+                Ok(buffer) => buffer,
+                Err(e) => break e,
+            };
+
+            let (id, buffer) = fid.assign(Arc::new(buffer));
+            api_log!("Device::create_external_buffer -> {id:?}");
+
+            device
+                .trackers
+                .lock()
+                .buffers
+                .insert_single(buffer, hal::BufferUses::empty());
+
+            return (id, None);
+        };
+
+        log::error!("Device::create_external_buffer error: {error}");
+
+        let id = fid.assign_error(desc.label.borrow_or_default());
+        (id, Some(error))
+    }
+
+    fn create_external_texture_impl<A: ExternalMemoryApi>(
+        &self,
+        device_id: DeviceId,
+        desc: &resource::TextureDescriptor,
+        memory: ExternalMemory<'_>,
+        id_in: Option<id::TextureId>,
+    ) -> (id::TextureId, Option<CreateTextureError>) {
+        let hub = A::hub(self);
+        let fid = hub.textures.prepare(id_in);
+
+        let error = loop {
+            let device = match hub.devices.get(device_id) {
+                Ok(device) => device,
+                Err(_) => break DeviceError::Invalid.into(),
+            };
+            if !device.is_valid() {
+                break DeviceError::Lost.into();
+            }
+
+            // NB: The contents of imported memory are not recorded in the replay
+            #[cfg(feature = "trace")]
+            if let Some(ref mut trace) = *device.trace.lock() {
+                trace.add(trace::Action::CreateTexture(fid.id(), desc.clone()));
+            }
+
+            let texture = match device.create_external_texture(desc, memory) {
+                Ok(texture) => texture,
+                Err(e) => break e,
+            };
+
+            let (id, resource) = fid.assign(Arc::new(texture));
+            api_log!("Device::create_external_texture({desc:?}) -> {id:?}");
+
+            device
+                .trackers
+                .lock()
+                .textures
+                .insert_single(resource, hal::TextureUses::UNINITIALIZED);
+
+            return (id, None);
+        };
+
+        log::error!("Device::create_external_texture error: {error}");
+
+        let id = fid.assign_error(desc.label.borrow_or_default());
+        (id, Some(error))
+    }
+
+    /// Assign an error id to a buffer on a backend without external memory.
+    fn external_buffer_error<A: HalApi>(
+        &self,
+        desc: &resource::BufferDescriptor,
+        error: CreateBufferError,
+        id_in: Option<id::BufferId>,
+    ) -> (id::BufferId, Option<CreateBufferError>) {
+        log::error!("Device::create_external_buffer error: {error}");
+
+        let fid = A::hub(self).buffers.prepare(id_in);
+        let id = fid.assign_error(desc.label.borrow_or_default());
+        (id, Some(error))
+    }
+
+    /// Assign an error id to a texture on a backend without external memory.
+    fn external_texture_error<A: HalApi>(
+        &self,
+        desc: &resource::TextureDescriptor,
+        error: CreateTextureError,
+        id_in: Option<id::TextureId>,
+    ) -> (id::TextureId, Option<CreateTextureError>) {
+        log::error!("Device::create_external_texture error: {error}");
+
+        let fid = A::hub(self).textures.prepare(id_in);
+        let id = fid.assign_error(desc.label.borrow_or_default());
+        (id, Some(error))
+    }
+}
@@ -0,0 +1,418 @@
+//! Timeline semaphores shared with other APIs and processes through file
+//! descriptors or NT handles, and Linux sync files.
+//!
+//! Like external memory, only the Vulkan backend on Unix and the DX12 backend
+//! support external semaphores, and only Vulkan supports sync files.
+
+use crate::{
+    api_log,
+    device::{Device, DeviceError, MissingFeatures},
+    global::Global,
+    hal_api::HalApi,
+    id::{self, DeviceId},
+    resource::{self, CreateTimelineSemaphoreError, ExternalSemaphoreError, TimelineSemaphore},
+    LabelHelpers as _,
+};
+
+use std::sync::{atomic::AtomicU64, Arc};
+
+/// The feature [`Global::timeline_semaphore_export`] reports missing on other
+/// backends.
+#[cfg(unix)]
+const EXTERNAL_SEMAPHORE_FEATURE: wgt::Features = wgt::Features::EXTERNAL_SEMAPHORE_FD;
+#[cfg(windows)]
+const EXTERNAL_SEMAPHORE_FEATURE: wgt::Features = wgt::Features::EXTERNAL_MEMORY_WIN32;
+
+/// A backend whose timeline semaphores can be exported and imported.
+trait ExternalSemaphoreApi: HalApi {
+    unsafe fn create_exportable_fence(
+        device: &Self::Device,
+        handle_type: wgt::ExternalSemaphoreHandleType,
+    ) -> Result<Self::Fence, hal::DeviceError>;
+
+    unsafe fn import_fence(
+        device: &Self::Device,
+        semaphore: wgt::ExternalSemaphore,
+    ) -> Result<Self::Fence, hal::DeviceError>;
+
+    unsafe fn export_fence(
+        device: &Self::Device,
+        fence: &Self::Fence,
+    ) -> Result<wgt::ExternalSemaphore, hal::DeviceError>;
+}
+
+#[cfg(all(vulkan, unix))]
+impl ExternalSemaphoreApi for hal::api::Vulkan {
+    unsafe fn create_exportable_fence(
+        device: &Self::Device,
+        _handle_type: wgt::ExternalSemaphoreHandleType,
+    ) -> Result<Self::Fence, hal::DeviceError> {
+        unsafe { device.create_exportable_fence() }
+    }
+
+    unsafe fn import_fence(
+        device: &Self::Device,
+        semaphore: wgt::ExternalSemaphore,
+    ) -> Result<Self::Fence, hal::DeviceError> {
+        unsafe { device.import_fence(semaphore) }
+    }
+
+    unsafe fn export_fence(
+        device: &Self::Device,
+        fence: &Self::Fence,
+    ) -> Result<wgt::ExternalSemaphore, hal::DeviceError> {
+        unsafe { device.export_fence(fence) }
+    }
+}
+
+#[cfg(dx12)]
+impl ExternalSemaphoreApi for hal::api::Dx12 {
+    unsafe fn create_exportable_fence(
+        device: &Self::Device,
+        _handle_type: wgt::ExternalSemaphoreHandleType,
+    ) -> Result<Self::Fence, hal::DeviceError> {
+        use hal::Device as _;
+
+        // Every fence is created shared.
+        unsafe { device.create_fence() }
+    }
+
+    unsafe fn import_fence(
+        device: &Self::Device,
+        semaphore: wgt::ExternalSemaphore,
+    ) -> Result<Self::Fence, hal::DeviceError> {
+        use std::os::windows::io::AsHandle as _;
+
+        // Opening the fence doesn't take ownership of the handle, so it is
+        // closed once the fence has been opened.
+        unsafe { device.import_fence(semaphore.handle.as_handle()) }
+    }
+
+    unsafe fn export_fence(
+        device: &Self::Device,
+        fence: &Self::Fence,
+    ) -> Result<wgt::ExternalSemaphore, hal::DeviceError> {
+        Ok(wgt::ExternalSemaphore {
+            handle: unsafe { device.export_fence_handle(fence) }?,
+            handle_type: wgt::ExternalSemaphoreHandleType::D3D12Fence,
+        })
+    }
+}
+
+/// Where the fence of a timeline semaphore comes from.
+enum ExternalSemaphore {
+    /// A new fence that can be exported through the given handle type.
+    Exportable(wgt::ExternalSemaphoreHandleType),
+    /// A fence exported by another device, API or process.
+    Imported(wgt::ExternalSemaphore),
+}
+
+impl ExternalSemaphore {
+    fn handle_type(&self) -> wgt::ExternalSemaphoreHandleType {
+        match *self {
+            Self::Exportable(handle_type) => handle_type,
+            Self::Imported(ref semaphore) => semaphore.handle_type,
+        }
+    }
+}
+
+impl<A: ExternalSemaphoreApi> Device<A> {
+    fn create_external_timeline_semaphore(
+        self: &Arc<Self>,
+        desc: &resource::TimelineSemaphoreDescriptor,
+        semaphore: ExternalSemaphore,
+    ) -> Result<TimelineSemaphore<A>, CreateTimelineSemaphoreError> {
+        use hal::Device as _;
+
+        let handle_type = semaphore.handle_type();
+        self.require_features(wgt::Features::TIMELINE_SEMAPHORE | handle_type.required_features())?;
+
+        let (raw, value) = match semaphore {
+            ExternalSemaphore::Exportable(handle_type) => {
+                let mut raw = unsafe { A::create_exportable_fence(self.raw(), handle_type) }
+                    .map_err(DeviceError::from)?;
+                if desc.initial_value != 0 {
+                    if let Err(err) =
+                        unsafe { self.raw().signal_fence(&mut raw, desc.initial_value) }
+                    {
+                        unsafe { self.raw().destroy_fence(raw) };
+                        return Err(DeviceError::from(err).into());
+                    }
+                }
+                (raw, desc.initial_value)
+            }
+            // The imported semaphore keeps the value it has elsewhere.
+            ExternalSemaphore::Imported(semaphore) => {
+                let raw =
+                    unsafe { A::import_fence(self.raw(), semaphore) }.map_err(DeviceError::from)?;
+                match unsafe { self.raw().get_fence_value(&raw) } {
+                    Ok(value) => (raw, value),
+                    Err(err) => {
+                        unsafe { self.raw().destroy_fence(raw) };
+                        return Err(DeviceError::from(err).into());
+                    }
+                }
+            }
+        };
+
+        let mut semaphore = self.create_timeline_semaphore_from_hal(raw, desc);
+        semaphore.last_signaled_value = AtomicU64::new(value);
+        semaphore.handle_type = Some(handle_type);
+        Ok(semaphore)
+    }
+}
+
+impl Global {
+    /// Create a timeline semaphore that can be exported with
+    /// [`Global::timeline_semaphore_export`].
+    pub fn device_create_exportable_timeline_semaphore(
+        &self,
+        device_id: DeviceId,
+        desc: &resource::TimelineSemaphoreDescriptor,
+        handle_type: wgt::ExternalSemaphoreHandleType,
+        id_in: Option<id::TimelineSemaphoreId>,
+    ) -> (
+        id::TimelineSemaphoreId,
+        Option<CreateTimelineSemaphoreError>,
+    ) {
+        profiling::scope!("Device::create_exportable_timeline_semaphore");
+
+        self.create_external_timeline_semaphore(
+            device_id,
+            desc,
+            ExternalSemaphore::Exportable(handle_type),
+            id_in,
+        )
+    }
+
+    /// Create a timeline semaphore sharing the fence exported elsewhere.
+    ///
+    /// The semaphore starts with the current value of the shared one, rather
+    /// than `desc.initial_value`.
+    ///
+    /// # Safety
+    ///
+    /// - `semaphore` must hold a valid handle of its handle type.
+    /// - The semaphore must only be signaled with increasing values, by every
+    ///   device, API and process it is shared with.
+    pub unsafe fn device_import_timeline_semaphore(
+        &self,
+        device_id: DeviceId,
+        desc: &resource::TimelineSemaphoreDescriptor,
+        semaphore: wgt::ExternalSemaphore,
+        id_in: Option<id::TimelineSemaphoreId>,
+    ) -> (
+        id::TimelineSemaphoreId,
+        Option<CreateTimelineSemaphoreError>,
+    ) {
+        profiling::scope!("Device::import_timeline_semaphore");
+
+        self.create_external_timeline_semaphore(
+            device_id,
+            desc,
+            ExternalSemaphore::Imported(semaphore),
+            id_in,
+        )
+    }
+
+    /// Export a timeline semaphore created with
+    /// [`Global::device_create_exportable_timeline_semaphore`] or
+    /// [`Global::device_import_timeline_semaphore`].
+    pub fn timeline_semaphore_export(
+        &self,
+        semaphore_id: id::TimelineSemaphoreId,
+    ) -> Result<wgt::ExternalSemaphore, ExternalSemaphoreError> {
+        api_log!("TimelineSemaphore::export {semaphore_id:?}");
+
+        match semaphore_id.backend() {
+            #[cfg(all(vulkan, unix))]
+            wgt::Backend::Vulkan => {
+                self.export_timeline_semaphore::<hal::api::Vulkan>(semaphore_id)
+            }
+            #[cfg(dx12)]
+            wgt::Backend::Dx12 => self.export_timeline_semaphore::<hal::api::Dx12>(semaphore_id),
+            _ => Err(MissingFeatures(EXTERNAL_SEMAPHORE_FEATURE).into()),
+        }
+    }
+
+    /// Make the next submission to `queue_id` wait until the Linux sync file
+    /// `fd` signals.
+    ///
+    /// Requires [`wgt::Features::EXTERNAL_SEMAPHORE_FD`].
+    ///
+    /// # Safety
+    ///
+    /// - `fd` must be a sync file.
+    #[cfg(unix)]
+    pub unsafe fn queue_wait_for_sync_file(
+        &self,
+        queue_id: id::QueueId,
+        fd: std::os::fd::OwnedFd,
+    ) -> Result<(), ExternalSemaphoreError> {
+        api_log!("Queue::wait_for_sync_file {queue_id:?}");
+
+        match queue_id.backend() {
+            #[cfg(vulkan)]
+            wgt::Backend::Vulkan => {
+                let queue = hal::api::Vulkan::hub(self)
+                    .queues
+                    .get(queue_id)
+                    .map_err(|_| DeviceError::InvalidQueueId)?;
+                let device = queue.device.as_ref().unwrap();
+                device.require_features(wgt::Features::EXTERNAL_SEMAPHORE_FD)?;
+
+                unsafe { queue.raw.as_ref().unwrap().wait_for_sync_fd(fd) }
+                    .map_err(|error| DeviceError::from(error).into())
+            }
+            _ => Err(MissingFeatures(wgt::Features::EXTERNAL_SEMAPHORE_FD).into()),
+        }
+    }
+
+    /// Return a Linux sync file that signals once `semaphore_id` reaches
+    /// `value`, which a submission to `queue_id` waits for.
+    ///
+    /// Requires [`wgt::Features::EXTERNAL_SEMAPHORE_FD`].
+    #[cfg(unix)]
+    pub fn queue_export_sync_file(
+        &self,
+        queue_id: id::QueueId,
+        semaphore_id: id::TimelineSemaphoreId,
+        value: u64,
+    ) -> Result<std::os::fd::OwnedFd, ExternalSemaphoreError> {
+        api_log!("Queue::export_sync_file {queue_id:?} {semaphore_id:?} {value}");
+
+        match queue_id.backend() {
+            #[cfg(vulkan)]
+            wgt::Backend::Vulkan => {
+                let hub = hal::api::Vulkan::hub(self);
+                let queue = hub
+                    .queues
+                    .get(queue_id)
+                    .map_err(|_| DeviceError::InvalidQueueId)?;
+                let device = queue.device.as_ref().unwrap();
+                device.require_features(wgt::Features::EXTERNAL_SEMAPHORE_FD)?;
+                let semaphore = hub
+                    .timeline_semaphores
+                    .get(semaphore_id)
+                    .map_err(|_| ExternalSemaphoreError::Invalid(semaphore_id))?;
+                if semaphore.device.as_info().id() != device.as_info().id() {
+                    return Err(DeviceError::WrongDevice.into());
+                }
+
+                // The queue is only submitted to with the fence locked.
+                let _fence = device.fence.write();
+                let raw = semaphore.raw.lock();
+                unsafe {
+                    queue
+                        .raw
+                        .as_ref()
+                        .unwrap()
+                        .export_sync_fd(raw.as_ref().unwrap(), value)
+                }
+                .map_err(|error| DeviceError::from(error).into())
+            }
+            _ => Err(MissingFeatures(wgt::Features::EXTERNAL_SEMAPHORE_FD).into()),
+        }
+    }
+
+    fn export_timeline_semaphore<A: ExternalSemaphoreApi>(
+        &self,
+        semaphore_id: id::TimelineSemaphoreId,
+    ) -> Result<wgt::ExternalSemaphore, ExternalSemaphoreError> {
+        let semaphore = A::hub(self)
+            .timeline_semaphores
+            .get(semaphore_id)
+            .map_err(|_| ExternalSemaphoreError::Invalid(semaphore_id))?;
+        if semaphore.handle_type.is_none() {
+            return Err(ExternalSemaphoreError::NotExternal(semaphore_id));
+        }
+
+        let raw = semaphore.raw.lock();
+        unsafe { A::export_fence(semaphore.device.raw(), raw.as_ref().unwrap()) }
+            .map_err(|error| DeviceError::from(error).into())
+    }
+
+    fn create_external_timeline_semaphore(
+        &self,
+        device_id: DeviceId,
+        desc: &resource::TimelineSemaphoreDescriptor,
+        semaphore: ExternalSemaphore,
+        id_in: Option<id::TimelineSemaphoreId>,
+    ) -> (
+        id::TimelineSemaphoreId,
+        Option<CreateTimelineSemaphoreError>,
+    ) {
+        match device_id.backend() {
+            #[cfg(all(vulkan, unix))]
+            wgt::Backend::Vulkan => self
+                .create_external_timeline_semaphore_impl::<hal::api::Vulkan>(
+                    device_id, desc, semaphore, id_in,
+                ),
+            #[cfg(dx12)]
+            wgt::Backend::Dx12 => self.create_external_timeline_semaphore_impl::<hal::api::Dx12>(
+                device_id, desc, semaphore, id_in,
+            ),
+            _ => {
+                let error = MissingFeatures(semaphore.handle_type().required_features());
+                crate::gfx_select!(device_id => self.external_timeline_semaphore_error(desc, error.into(), id_in))
+            }
+        }
+    }
+
+    fn create_external_timeline_semaphore_impl<A: ExternalSemaphoreApi>(
+        &self,
+        device_id: DeviceId,
+        desc: &resource::TimelineSemaphoreDescriptor,
+        semaphore: ExternalSemaphore,
+        id_in: Option<id::TimelineSemaphoreId>,
+    ) -> (
+        id::TimelineSemaphoreId,
+        Option<CreateTimelineSemaphoreError>,
+    ) {
+        let hub = A::hub(self);
+        let fid = hub.timeline_semaphores.prepare(id_in);
+
+        let error = loop {
+            let device = match hub.devices.get(device_id) {
+                Ok(device) => device,
+                Err(_) => break DeviceError::Invalid.into(),
+            };
+            if !device.is_valid() {
+                break DeviceError::Lost.into();
+            }
+
+            let semaphore = match device.create_external_timeline_semaphore(desc, semaphore) {
+                Ok(semaphore) => semaphore,
+                Err(e) => break e,
+            };
+
+            let (id, _) = fid.assign(Arc::new(semaphore));
+            api_log!("Device::create_external_timeline_semaphore -> {id:?}");
+
+            return (id, None);
+        };
+
+        log::error!("Device::create_external_timeline_semaphore error: {error}");
+
+        let id = fid.assign_error(desc.label.borrow_or_default());
+        (id, Some(error))
+    }
+
+    /// Assign an error id to a timeline semaphore on a backend without
+    /// external semaphores.
+    fn external_timeline_semaphore_error<A: HalApi>(
+        &self,
+        desc: &resource::TimelineSemaphoreDescriptor,
+        error: CreateTimelineSemaphoreError,
+        id_in: Option<id::TimelineSemaphoreId>,
+    ) -> (
+        id::TimelineSemaphoreId,
+        Option<CreateTimelineSemaphoreError>,
+    ) {
+        log::error!("Device::create_external_timeline_semaphore error: {error}");
+
+        let fid = A::hub(self).timeline_semaphores.prepare(id_in);
+        let id = fid.assign_error(desc.label.borrow_or_default());
+        (id, Some(error))
+    }
+}
@@ -0,0 +1,250 @@
+//! Textures shared with other GL contexts, as GL texture names or EGLImages.
+
+#[cfg(feature = "trace")]
+use crate::device::trace;
+use crate::{
+    api_log,
+    device::{Device, DeviceError, MissingFeatures},
+    global::Global,
+    hal_api::HalApi,
+    id::{self, DeviceId},
+    init_tracker::TextureInitTracker,
+    lock::{rank, RwLock},
+    resource::{self, CreateTextureError, ExportMemoryError, Texture},
+    LabelHelpers as _,
+};
+
+use hal::api::Gles;
+
+use std::{ffi::c_void, num::NonZeroU32, sync::Arc};
+
+/// The GL storage an imported texture is created on.
+#[derive(Clone, Copy, Debug)]
+enum GlTextureSource {
+    /// A texture name of a context sharing objects with the device.
+    Name(NonZeroU32),
+    /// An `EGLImage` of the display of the device.
+    EglImage(*mut c_void),
+}
+
+impl Device<Gles> {
+    fn import_gl_texture(
+        self: &Arc<Self>,
+        desc: &resource::TextureDescriptor,
+        source: GlTextureSource,
+    ) -> Result<Texture<Gles>, CreateTextureError> {
+        self.require_features(wgt::Features::GL_TEXTURE_INTEROP)
+            .map_err(CreateTextureError::MissingExternalMemoryFeature)?;
+
+        if let GlTextureSource::EglImage(_) = source {
+            if desc.dimension != wgt::TextureDimension::D2
+                || desc.size.depth_or_array_layers != 1
+                || desc.mip_level_count != 1
+                || desc.sample_count != 1
+            {
+                return Err(CreateTextureError::InvalidEglImage {
+                    dimension: desc.dimension,
+                    layers: desc.size.depth_or_array_layers,
+                    mip_level_count: desc.mip_level_count,
+                    sample_count: desc.sample_count,
+                });
+            }
+        }
+
+        let (hal_desc, format_features) =
+            self.texture_hal_descriptor(&self.adapter, desc, hal::MemoryFlags::empty())?;
+        let hal_usage = hal_desc.usage;
+        let raw = match source {
+            // The texture belongs to the other context, which deletes it.
+            GlTextureSource::Name(name) => unsafe {
+                self.raw()
+                    .texture_from_raw(name, &hal_desc, Some(Box::new(())))
+            },
+            GlTextureSource::EglImage(image) => unsafe {
+                self.raw()
+                    .texture_from_egl_image(image, &hal_desc)
+                    .map_err(DeviceError::from)?
+            },
+        };
+
+        let clear_mode = self.create_texture_clear_mode(&raw, hal_usage, desc)?;
+        let mut texture =
+            self.create_texture_from_hal(raw, hal_usage, desc, format_features, clear_mode);
+        // Keep what the other context drew into the texture.
+        texture.initialization_status = RwLock::new(
+            rank::TEXTURE_INITIALIZATION_STATUS,
+            TextureInitTracker::new(desc.mip_level_count, 0),
+        );
+        Ok(texture)
+    }
+}
+
+impl Global {
+    /// Create a texture on `name`, a GL texture of a context sharing objects
+    /// with the device.
+    ///
+    /// # Safety
+    ///
+    /// - `name` must be a texture of the size, format, dimension and mip level
+    ///   count of `desc`, that outlives the texture.
+    /// - Accesses to the texture from the other context must be synchronized
+    ///   with accesses through wgpu.
+    pub unsafe fn device_import_gl_texture(
+        &self,
+        device_id: DeviceId,
+        desc: &resource::TextureDescriptor,
+        name: NonZeroU32,
+        id_in: Option<id::TextureId>,
+    ) -> (id::TextureId, Option<CreateTextureError>) {
+        profiling::scope!("Device::import_gl_texture");
+
+        self.import_gl_texture(device_id, desc, GlTextureSource::Name(name), id_in)
+    }
+
+    /// Create a texture on `image`, an `EGLImage`.
+    ///
+    /// # Safety
+    ///
+    /// - `image` must be a valid `EGLImage` of the display of the device,
+    ///   holding an image of the size and format of `desc`.
+    /// - Accesses to the image from elsewhere must be synchronized with
+    ///   accesses through the texture.
+    pub unsafe fn device_import_egl_image(
+        &self,
+        device_id: DeviceId,
+        desc: &resource::TextureDescriptor,
+        image: *mut c_void,
+        id_in: Option<id::TextureId>,
+    ) -> (id::TextureId, Option<CreateTextureError>) {
+        profiling::scope!("Device::import_egl_image");
+
+        self.import_gl_texture(device_id, desc, GlTextureSource::EglImage(image), id_in)
+    }
+
+    /// Return the GL texture name a texture is backed by.
+    ///
+    /// Textures created with only [`wgt::TextureUsages::RENDER_ATTACHMENT`]
+    /// are renderbuffers, and have no texture name.
+    pub fn texture_gl_name(
+        &self,
+        texture_id: id::TextureId,
+    ) -> Result<NonZeroU32, ExportMemoryError> {
+        api_log!("Texture::gl_name {texture_id:?}");
+
+        if texture_id.backend() != wgt::Backend::Gl {
+            return Err(MissingFeatures(wgt::Features::GL_TEXTURE_INTEROP).into());
+        }
+
+        let hub = Gles::hub(self);
+        let texture = hub
+            .textures
+            .get(texture_id)
+            .map_err(|_| ExportMemoryError::InvalidTexture(texture_id))?;
+        let snatch_guard = texture.device.snatchable_lock.read();
+        match texture
+            .raw(&snatch_guard)
+            .ok_or(ExportMemoryError::InvalidTexture(texture_id))?
+            .inner
+        {
+            hal::gles::TextureInner::Texture { raw, .. } => Ok(raw.0),
+            _ => Err(ExportMemoryError::InvalidTexture(texture_id)),
+        }
+    }
+
+    /// Create an `EGLImage` sharing the first mip level of a 2D texture,
+    /// which the caller must destroy with `eglDestroyImageKHR`.
+    pub fn texture_create_egl_image(
+        &self,
+        texture_id: id::TextureId,
+    ) -> Result<*mut c_void, ExportMemoryError> {
+        api_log!("Texture::create_egl_image {texture_id:?}");
+
+        if texture_id.backend() != wgt::Backend::Gl {
+            return Err(MissingFeatures(wgt::Features::GL_TEXTURE_INTEROP).into());
+        }
+
+        let hub = Gles::hub(self);
+        let texture = hub
+            .textures
+            .get(texture_id)
+            .map_err(|_| ExportMemoryError::InvalidTexture(texture_id))?;
+        let snatch_guard = texture.device.snatchable_lock.read();
+        let raw = texture
+            .raw(&snatch_guard)
+            .ok_or(ExportMemoryError::InvalidTexture(texture_id))?;
+        texture
+            .device
+            .raw()
+            .create_egl_image(raw)
+            .map_err(|e| DeviceError::from(e).into())
+    }
+
+    fn import_gl_texture(
+        &self,
+        device_id: DeviceId,
+        desc: &resource::TextureDescriptor,
+        source: GlTextureSource,
+        id_in: Option<id::TextureId>,
+    ) -> (id::TextureId, Option<CreateTextureError>) {
+        if device_id.backend() != wgt::Backend::Gl {
+            let error = CreateTextureError::MissingExternalMemoryFeature(MissingFeatures(
+                wgt::Features::GL_TEXTURE_INTEROP,
+            ));
+            return crate::gfx_select!(device_id => self.gl_texture_error(desc, error, id_in));
+        }
+
+        let hub = Gles::hub(self);
+        let fid = hub.textures.prepare(id_in);
+
+        let error = loop {
+            let device = match hub.devices.get(device_id) {
+                Ok(device) => device,
+                Err(_) => break DeviceError::Invalid.into(),
+            };
+            if !device.is_valid() {
+                break DeviceError::Lost.into();
+            }
+
+            // NB: The contents of imported textures are not recorded in the replay
+            #[cfg(feature = "trace")]
+            if let Some(ref mut trace) = *device.trace.lock() {
+                trace.add(trace::Action::CreateTexture(fid.id(), desc.clone()));
+            }
+
+            let texture = match device.import_gl_texture(desc, source) {
+                Ok(texture) => texture,
+                Err(e) => break e,
+            };
+
+            let (id, resource) = fid.assign(Arc::new(texture));
+            api_log!("Device::import_gl_texture({desc:?}, {source:?}) -> {id:?}");
+
+            device
+                .trackers
+                .lock()
+                .textures
+                .insert_single(resource, hal::TextureUses::UNINITIALIZED);
+
+            return (id, None);
+        };
+
+        log::error!("Device::import_gl_texture error: {error}");
+
+        let id = fid.assign_error(desc.label.borrow_or_default());
+        (id, Some(error))
+    }
+
+    /// Assign an error id to a texture on a backend without GL textures.
+    fn gl_texture_error<A: HalApi>(
+        &self,
+        desc: &resource::TextureDescriptor,
+        error: CreateTextureError,
+        id_in: Option<id::TextureId>,
+    ) -> (id::TextureId, Option<CreateTextureError>) {
+        log::error!("Device::import_gl_texture error: {error}");
+
+        let fid = A::hub(self).textures.prepare(id_in);
+        let id = fid.assign_error(desc.label.borrow_or_default());
+        (id, Some(error))
+    }
+}
@@ -32,6 +32,27 @@ use std::{
 
 use super::{ImplicitPipelineIds, InvalidDevice, UserClosures};
 
+fn map_surface_capabilities(mut hal_caps: hal::SurfaceCapabilities) -> wgt::SurfaceCapabilities {
+    hal_caps.formats.sort_by_key(|f| !f.is_srgb());
+
+    let usages = conv::map_texture_usage_from_hal(hal_caps.usage);
+
+    wgt::SurfaceCapabilities {
+        formats: hal_caps.formats,
+        present_modes: hal_caps.present_modes,
+        alpha_modes: hal_caps.composite_alpha_modes,
+        usages,
+        transforms: hal_caps.transforms,
+        current_transform: hal_caps.current_transform,
+        color_spaces: hal_caps.color_spaces,
+        max_array_layers: hal_caps.max_array_layers,
+        fullscreen_modes: hal_caps.fullscreen_modes,
+        display_modes: hal_caps.display_modes,
+        min_image_count: *hal_caps.image_count.start(),
+        max_image_count: *hal_caps.image_count.end(),
+    }
+}
+
 impl Global {
     pub fn adapter_is_surface_supported<A: HalApi>(
         &self,
@@ -58,19 +79,40 @@ impl Global {
     ) -> Result<wgt::SurfaceCapabilities, instance::GetSurfaceSupportError> {
         profiling::scope!("Surface::get_capabilities");
         self.fetch_adapter_and_surface::<A, _, _>(surface_id, adapter_id, |adapter, surface| {
-            let mut hal_caps = surface.get_capabilities(adapter)?;
-
-            hal_caps.formats.sort_by_key(|f| !f.is_srgb());
+            let hal_caps = surface.get_capabilities(adapter)?;
+            Ok(map_surface_capabilities(hal_caps))
+        })
+    }
 
-            let usages = conv::map_texture_usage_from_hal(hal_caps.usage);
+    /// Return the capabilities a surface created on `display_handle` would
+    /// likely have with `adapter_id`, without needing a window.
+    ///
+    /// Fails with [`GetSurfaceSupportError::Unsupported`] if the backend can't
+    /// tell without a real surface.
+    ///
+    /// [`GetSurfaceSupportError::Unsupported`]: instance::GetSurfaceSupportError::Unsupported
+    #[cfg(feature = "raw-window-handle")]
+    pub fn adapter_get_surface_capabilities_headless<A: HalApi>(
+        &self,
+        adapter_id: AdapterId,
+        display_handle: raw_window_handle::RawDisplayHandle,
+    ) -> Result<wgt::SurfaceCapabilities, instance::GetSurfaceSupportError> {
+        profiling::scope!("Adapter::get_surface_capabilities_headless");
+        use hal::Adapter as _;
 
-            Ok(wgt::SurfaceCapabilities {
-                formats: hal_caps.formats,
-                present_modes: hal_caps.present_modes,
-                alpha_modes: hal_caps.composite_alpha_modes,
-                usages,
-            })
-        })
+        let hub = A::hub(self);
+        let adapter = hub
+            .adapters
+            .get(adapter_id)
+            .map_err(|_| instance::GetSurfaceSupportError::InvalidAdapter)?;
+        let hal_caps = unsafe {
+            adapter
+                .raw
+                .adapter
+                .headless_surface_capabilities(display_handle)
+        }
+        .ok_or(instance::GetSurfaceSupportError::Unsupported)?;
+        Ok(map_surface_capabilities(hal_caps))
     }
 
     fn fetch_adapter_and_surface<
@@ -125,6 +167,31 @@ impl Global {
         Ok(device.limits.clone())
     }
 
+    /// The features of `format` on `device_id`, which are the ones of the
+    /// adapter with `Features::TEXTURE_ADAPTER_SPECIFIC_FORMAT_FEATURES`, and
+    /// the ones WebGPU guarantees otherwise.
+    ///
+    /// Formats that need features the device doesn't have support nothing.
+    pub fn device_get_texture_format_features<A: HalApi>(
+        &self,
+        device_id: DeviceId,
+        format: wgt::TextureFormat,
+    ) -> Result<wgt::TextureFormatFeatures, InvalidDevice> {
+        let hub = A::hub(self);
+
+        let device = hub.devices.get(device_id).map_err(|_| InvalidDevice)?;
+        if !device.is_valid() {
+            return Err(InvalidDevice);
+        }
+
+        Ok(device
+            .describe_format_features(&device.adapter, format)
+            .unwrap_or(wgt::TextureFormatFeatures {
+                allowed_usages: wgt::TextureUsages::empty(),
+                flags: wgt::TextureFormatFeatureFlags::empty(),
+            }))
+    }
+
     pub fn device_downlevel_properties<A: HalApi>(
         &self,
         device_id: DeviceId,
@@ -177,7 +244,7 @@ impl Global {
                 trace.add(trace::Action::CreateBuffer(fid.id(), desc));
             }
 
-            let buffer = match device.create_buffer(desc, false) {
+            let buffer = match device.create_buffer(desc, hal::MemoryFlags::empty(), None) {
                 Ok(buffer) => buffer,
                 Err(e) => {
                     break e;
@@ -212,6 +279,7 @@ impl Global {
                     ptr,
                     range: 0..map_size,
                     host: HostMap::Write,
+                    checked_views: false,
                 };
                 hal::BufferUses::MAP_WRITE
             } else {
@@ -224,13 +292,14 @@ impl Global {
                     usage: wgt::BufferUsages::MAP_WRITE | wgt::BufferUsages::COPY_SRC,
                     mapped_at_creation: false,
                 };
-                let stage = match device.create_buffer(&stage_desc, true) {
-                    Ok(stage) => Arc::new(stage),
-                    Err(e) => {
-                        to_destroy.push(buffer);
-                        break e;
-                    }
-                };
+                let stage =
+                    match device.create_buffer(&stage_desc, hal::MemoryFlags::TRANSIENT, None) {
+                        Ok(stage) => Arc::new(stage),
+                        Err(e) => {
+                            to_destroy.push(buffer);
+                            break e;
+                        }
+                    };
 
                 let snatch_guard = device.snatchable_lock.read();
                 let stage_raw = stage.raw(&snatch_guard).unwrap();
@@ -282,6 +351,132 @@ impl Global {
         (id, Some(error))
     }
 
+    /// Create a buffer that reserves `desc.size` bytes without any memory,
+    /// whose pages are committed with [`Global::queue_bind_sparse`].
+    ///
+    /// Requires [`wgt::Features::SPARSE_BUFFER`]. Sparse buffers can't be
+    /// mapped.
+    pub fn device_create_sparse_buffer<A: HalApi>(
+        &self,
+        device_id: DeviceId,
+        desc: &resource::BufferDescriptor,
+        id_in: Option<id::BufferId>,
+    ) -> (id::BufferId, Option<CreateBufferError>) {
+        profiling::scope!("Device::create_sparse_buffer");
+
+        let hub = A::hub(self);
+        let fid = hub.buffers.prepare(id_in);
+
+        let error = loop {
+            let device = match hub.devices.get(device_id) {
+                Ok(device) => device,
+                Err(_) => break DeviceError::Invalid.into(),
+            };
+            if !device.is_valid() {
+                break DeviceError::Lost.into();
+            }
+
+            #[cfg(feature = "trace")]
+            if let Some(ref mut trace) = *device.trace.lock() {
+                trace.add(trace::Action::CreateBuffer(fid.id(), desc.clone()));
+            }
+
+            let buffer = match device.create_sparse_buffer(desc) {
+                Ok(buffer) => buffer,
+                Err(e) => break e,
+            };
+
+            let (id, resource) = fid.assign(Arc::new(buffer));
+            api_log!("Device::create_sparse_buffer({desc:?}) -> {id:?}");
+
+            device
+                .trackers
+                .lock()
+                .buffers
+                .insert_single(resource, hal::BufferUses::empty());
+
+            return (id, None);
+        };
+
+        let id = fid.assign_error(desc.label.borrow_or_default());
+        (id, Some(error))
+    }
+
+    /// Create a buffer using the memory of `heap_id` from `offset` on.
+    ///
+    /// Requires [`wgt::Features::PLACED_RESOURCES`]. `offset` has to be aligned
+    /// as returned by [`Global::device_buffer_memory_requirements`], and placed
+    /// buffers can't be mapped.
+    pub fn device_create_placed_buffer<A: HalApi>(
+        &self,
+        device_id: DeviceId,
+        heap_id: id::MemoryHeapId,
+        offset: wgt::BufferAddress,
+        desc: &resource::BufferDescriptor,
+        id_in: Option<id::BufferId>,
+    ) -> (id::BufferId, Option<CreateBufferError>) {
+        profiling::scope!("Device::create_placed_buffer");
+
+        let hub = A::hub(self);
+        let fid = hub.buffers.prepare(id_in);
+
+        let error = loop {
+            let device = match hub.devices.get(device_id) {
+                Ok(device) => device,
+                Err(_) => break DeviceError::Invalid.into(),
+            };
+            if !device.is_valid() {
+                break DeviceError::Lost.into();
+            }
+            let heap = match hub.memory_heaps.get(heap_id) {
+                Ok(heap) => heap,
+                Err(_) => break resource::PlacementError::InvalidHeap(heap_id).into(),
+            };
+
+            #[cfg(feature = "trace")]
+            if let Some(ref mut trace) = *device.trace.lock() {
+                trace.add(trace::Action::CreateBuffer(fid.id(), desc.clone()));
+            }
+
+            let buffer = match device.create_placed_buffer(desc, &heap, offset) {
+                Ok(buffer) => buffer,
+                Err(e) => break e,
+            };
+
+            let (id, resource) = fid.assign(Arc::new(buffer));
+            api_log!("Device::create_placed_buffer({heap_id:?}, {offset}, {desc:?}) -> {id:?}");
+
+            device
+                .trackers
+                .lock()
+                .buffers
+                .insert_single(resource, hal::BufferUses::empty());
+
+            return (id, None);
+        };
+
+        let id = fid.assign_error(desc.label.borrow_or_default());
+        (id, Some(error))
+    }
+
+    /// Return the size and alignment of the memory that a buffer created with
+    /// `desc` by [`Global::device_create_placed_buffer`] takes up.
+    pub fn device_buffer_memory_requirements<A: HalApi>(
+        &self,
+        device_id: DeviceId,
+        desc: &resource::BufferDescriptor,
+    ) -> Result<wgt::MemoryRequirements, CreateBufferError> {
+        let hub = A::hub(self);
+        let device = hub
+            .devices
+            .get(device_id)
+            .map_err(|_| DeviceError::Invalid)?;
+        if !device.is_valid() {
+            return Err(DeviceError::Lost.into());
+        }
+        device.buffer_memory_requirements(desc)
+    }
+
     /// Assign `id_in` an error with the given `label`.
     ///
     /// Ensure that future attempts to use `id_in` as a buffer ID will propagate
@@ -389,7 +584,7 @@ impl Global {
         //assert!(buffer isn't used by the GPU);
 
         #[cfg(feature = "trace")]
-        if let Some(ref mut trace) = *device.trace.lock() {
+        if let Some(trace) = device.trace.lock().as_mut().filter(|t| !t.shaders_only) {
             let data_path = trace.make_binary("bin", data);
             trace.add(trace::Action::WriteBuffer {
                 id: buffer_id,
@@ -479,6 +674,51 @@ impl Global {
         A::hub(self).buffers.label_for_resource(id)
     }
 
+    /// Return the label `buffer_id` was created with, or an empty string if it
+    /// had none. Unlike [`Global::buffer_label`], which is meant for error
+    /// messages, this doesn't make up a label from the id.
+    pub fn buffer_get_label<A: HalApi>(
+        &self,
+        buffer_id: id::BufferId,
+    ) -> Result<String, BufferAccessError> {
+        let hub = A::hub(self);
+
+        let buffer = hub
+            .buffers
+            .get(buffer_id)
+            .map_err(|_| BufferAccessError::Invalid)?;
+        Ok(buffer.label())
+    }
+
+    pub fn buffer_device_address<A: HalApi>(
+        &self,
+        buffer_id: id::BufferId,
+    ) -> Result<BufferAddress, resource::BufferDeviceAddressError> {
+        profiling::scope!("Buffer::device_address");
+        api_log!("Buffer::device_address {buffer_id:?}");
+
+        let hub = A::hub(self);
+
+        let buffer = hub
+            .buffers
+            .get(buffer_id)
+            .map_err(|_| resource::BufferDeviceAddressError::Invalid)?;
+        buffer
+            .device
+            .require_features(wgt::Features::BUFFER_DEVICE_ADDRESS)?;
+        if !buffer.usage.contains(wgt::BufferUsages::STORAGE) {
+            return Err(resource::BufferDeviceAddressError::MissingStorageUsage(
+                buffer.usage,
+            ));
+        }
+
+        let snatch_guard = buffer.device.snatchable_lock.read();
+        let raw = buffer
+            .raw(&snatch_guard)
+            .ok_or(resource::BufferDeviceAddressError::Destroyed)?;
+        Ok(unsafe { buffer.device.raw().get_buffer_device_address(raw) })
+    }
+
     pub fn buffer_destroy<A: HalApi>(
         &self,
         buffer_id: id::BufferId,
@@ -492,6 +732,8 @@ impl Global {
             .buffers
             .get(buffer_id)
             .map_err(|_| resource::DestroyError::Invalid)?;
+        // The batched submissions may still use the buffer.
+        self.flush_submit_batch_or_log::<A>(&buffer.device);
 
         let _ = buffer.unmap();
 
@@ -567,13 +809,68 @@ impl Global {
                 trace.add(trace::Action::CreateTexture(fid.id(), desc.clone()));
             }
 
-            let texture = match device.create_texture(&device.adapter, desc) {
+            let texture =
+                match device.create_texture(&device.adapter, desc, hal::MemoryFlags::empty(), None)
+                {
+                    Ok(texture) => texture,
+                    Err(error) => break error,
+                };
+
+            let (id, resource) = fid.assign(Arc::new(texture));
+            api_log!("Device::create_texture({desc:?}) -> {id:?}");
+
+            device
+                .trackers
+                .lock()
+                .textures
+                .insert_single(resource, hal::TextureUses::UNINITIALIZED);
+
+            return (id, None);
+        };
+
+        log::error!("Device::create_texture error: {error}");
+
+        let id = fid.assign_error(desc.label.borrow_or_default());
+        (id, Some(error))
+    }
+
+    /// Create a texture without any memory, whose tiles are committed with
+    /// [`Global::queue_bind_sparse_texture`].
+    ///
+    /// Requires [`wgt::Features::SPARSE_TEXTURE`]. The size of the tiles is
+    /// returned by [`Global::texture_sparse_tiles`].
+    pub fn device_create_sparse_texture<A: HalApi>(
+        &self,
+        device_id: DeviceId,
+        desc: &resource::TextureDescriptor,
+        id_in: Option<id::TextureId>,
+    ) -> (id::TextureId, Option<resource::CreateTextureError>) {
+        profiling::scope!("Device::create_sparse_texture");
+
+        let hub = A::hub(self);
+
+        let fid = hub.textures.prepare(id_in);
+
+        let error = loop {
+            let device = match hub.devices.get(device_id) {
+                Ok(device) => device,
+                Err(_) => break DeviceError::Invalid.into(),
+            };
+            if !device.is_valid() {
+                break DeviceError::Lost.into();
+            }
+            #[cfg(feature = "trace")]
+            if let Some(ref mut trace) = *device.trace.lock() {
+                trace.add(trace::Action::CreateTexture(fid.id(), desc.clone()));
+            }
+
+            let texture = match device.create_sparse_texture(&device.adapter, desc) {
                 Ok(texture) => texture,
                 Err(error) => break error,
             };
 
             let (id, resource) = fid.assign(Arc::new(texture));
-            api_log!("Device::create_texture({desc:?}) -> {id:?}");
+            api_log!("Device::create_sparse_texture({desc:?}) -> {id:?}");
 
             device
                 .trackers
@@ -584,12 +881,88 @@ impl Global {
             return (id, None);
         };
 
-        log::error!("Device::create_texture error: {error}");
+        log::error!("Device::create_sparse_texture error: {error}");
 
         let id = fid.assign_error(desc.label.borrow_or_default());
         (id, Some(error))
     }
 
+    /// Create a texture using the memory of `heap_id` from `offset` on.
+    ///
+    /// Requires [`wgt::Features::PLACED_RESOURCES`]. `offset` has to be aligned
+    /// as returned by [`Global::device_texture_memory_requirements`].
+    pub fn device_create_placed_texture<A: HalApi>(
+        &self,
+        device_id: DeviceId,
+        heap_id: id::MemoryHeapId,
+        offset: wgt::BufferAddress,
+        desc: &resource::TextureDescriptor,
+        id_in: Option<id::TextureId>,
+    ) -> (id::TextureId, Option<resource::CreateTextureError>) {
+        profiling::scope!("Device::create_placed_texture");
+
+        let hub = A::hub(self);
+
+        let fid = hub.textures.prepare(id_in);
+
+        let error = loop {
+            let device = match hub.devices.get(device_id) {
+                Ok(device) => device,
+                Err(_) => break DeviceError::Invalid.into(),
+            };
+            if !device.is_valid() {
+                break DeviceError::Lost.into();
+            }
+            let heap = match hub.memory_heaps.get(heap_id) {
+                Ok(heap) => heap,
+                Err(_) => break resource::PlacementError::InvalidHeap(heap_id).into(),
+            };
+            #[cfg(feature = "trace")]
+            if let Some(ref mut trace) = *device.trace.lock() {
+                trace.add(trace::Action::CreateTexture(fid.id(), desc.clone()));
+            }
+
+            let texture = match device.create_placed_texture(&device.adapter, desc, &heap, offset) {
+                Ok(texture) => texture,
+                Err(error) => break error,
+            };
+
+            let (id, resource) = fid.assign(Arc::new(texture));
+            api_log!("Device::create_placed_texture({heap_id:?}, {offset}, {desc:?}) -> {id:?}");
+
+            device
+                .trackers
+                .lock()
+                .textures
+                .insert_single(resource, hal::TextureUses::UNINITIALIZED);
+
+            return (id, None);
+        };
+
+        log::error!("Device::create_placed_texture error: {error}");
+
+        let id = fid.assign_error(desc.label.borrow_or_default());
+        (id, Some(error))
+    }
+
+    /// Return the size and alignment of the memory that a texture created with
+    /// `desc` by [`Global::device_create_placed_texture`] takes up.
+    pub fn device_texture_memory_requirements<A: HalApi>(
+        &self,
+        device_id: DeviceId,
+        desc: &resource::TextureDescriptor,
+    ) -> Result<wgt::MemoryRequirements, resource::CreateTextureError> {
+        let hub = A::hub(self);
+        let device = hub
+            .devices
+            .get(device_id)
+            .map_err(|_| DeviceError::Invalid)?;
+        if !device.is_valid() {
+            return Err(DeviceError::Lost.into());
+        }
+        device.texture_memory_requirements(&device.adapter, desc)
+    }
+
     /// # Safety
     ///
     /// - `hal_texture` must be created from `device_id` corresponding raw handle.
@@ -723,6 +1096,19 @@ impl Global {
         A::hub(self).textures.label_for_resource(id)
     }
 
+    /// Return the tiles of a texture created with
+    /// [`Global::device_create_sparse_texture`], or `None` for other textures.
+    pub fn texture_sparse_tiles<A: HalApi>(
+        &self,
+        texture_id: id::TextureId,
+    ) -> Option<wgt::SparseTextureTiles> {
+        let hub = A::hub(self);
+        hub.textures
+            .get(texture_id)
+            .ok()
+            .and_then(|texture| texture.sparse)
+    }
+
     pub fn texture_destroy<A: HalApi>(
         &self,
         texture_id: id::TextureId,
@@ -736,10 +1122,27 @@ impl Global {
             .textures
             .get(texture_id)
             .map_err(|_| resource::DestroyError::Invalid)?;
+        // The batched submissions may still use the texture.
+        self.flush_submit_batch_or_log::<A>(&texture.device);
 
         texture.destroy()
     }
 
+    /// Return the expiry of `texture_id`, for the producer of the frame the
+    /// texture holds to expire it when it reuses the frame.
+    ///
+    /// Returns `None` if the texture is invalid.
+    pub fn texture_expiry<A: HalApi>(
+        &self,
+        texture_id: id::TextureId,
+    ) -> Option<wgt::TextureExpiry> {
+        api_log!("Texture::expiry {texture_id:?}");
+
+        let hub = A::hub(self);
+        let texture = hub.textures.get(texture_id).ok()?;
+        Some(texture.expiry.clone())
+    }
+
     pub fn texture_drop<A: HalApi>(&self, texture_id: id::TextureId, wait: bool) {
         profiling::scope!("Texture::drop");
         api_log!("Texture::drop {texture_id:?}");
@@ -1012,6 +1415,65 @@ impl Global {
         (id, Some(error))
     }
 
+    /// Create a bind group layout for inline bind groups, whose bindings are set with
+    /// `set_bind_group_inline` instead of a bind group.
+    ///
+    /// Unlike other bind group layouts, inline bind group layouts are never deduplicated,
+    /// so pipeline layouts have to use this exact layout.
+    pub fn device_create_inline_bind_group_layout<A: HalApi>(
+        &self,
+        device_id: DeviceId,
+        desc: &binding_model::BindGroupLayoutDescriptor,
+        id_in: Option<id::BindGroupLayoutId>,
+    ) -> (
+        id::BindGroupLayoutId,
+        Option<binding_model::CreateBindGroupLayoutError>,
+    ) {
+        profiling::scope!("Device::create_inline_bind_group_layout");
+
+        let hub = A::hub(self);
+        let fid = hub.bind_group_layouts.prepare(id_in);
+
+        let error = loop {
+            let device = match hub.devices.get(device_id) {
+                Ok(device) => device,
+                Err(_) => break DeviceError::Invalid.into(),
+            };
+            if !device.is_valid() {
+                break DeviceError::Lost.into();
+            }
+
+            #[cfg(feature = "trace")]
+            if let Some(ref mut trace) = *device.trace.lock() {
+                trace.add(trace::Action::CreateInlineBindGroupLayout(
+                    fid.id(),
+                    desc.clone(),
+                ));
+            }
+
+            let entry_map = match bgl::EntryMap::from_entries(&device.limits, &desc.entries) {
+                Ok(map) => map,
+                Err(e) => break e,
+            };
+
+            let layout = match device.create_bind_group_layout(
+                &desc.label,
+                entry_map,
+                bgl::Origin::Inline,
+            ) {
+                Ok(layout) => layout,
+                Err(e) => break e,
+            };
+
+            let (id, _) = fid.assign(Arc::new(layout));
+            api_log!("Device::create_inline_bind_group_layout -> {id:?}");
+            return (id, None);
+        };
+
+        let id = fid.assign_error(desc.label.borrow_or_default());
+        (id, Some(error))
+    }
+
     pub fn bind_group_layout_label<A: HalApi>(&self, id: id::BindGroupLayoutId) -> String {
         A::hub(self).bind_group_layouts.label_for_resource(id)
     }
@@ -1461,9 +1923,137 @@ impl Global {
                 Err(e) => break e,
             };
 
-            let (id, resource) = fid.assign(Arc::new(render_bundle));
-            api_log!("RenderBundleEncoder::finish -> {id:?}");
-            device.trackers.lock().bundles.insert_single(resource);
+            let (id, resource) = fid.assign(Arc::new(render_bundle));
+            api_log!("RenderBundleEncoder::finish -> {id:?}");
+            device.trackers.lock().bundles.insert_single(resource);
+            return (id, None);
+        };
+
+        let id = fid.assign_error(desc.label.borrow_or_default());
+        (id, Some(error))
+    }
+
+    pub fn render_bundle_label<A: HalApi>(&self, id: id::RenderBundleId) -> String {
+        A::hub(self).render_bundles.label_for_resource(id)
+    }
+
+    pub fn render_bundle_drop<A: HalApi>(&self, render_bundle_id: id::RenderBundleId) {
+        profiling::scope!("RenderBundle::drop");
+        api_log!("RenderBundle::drop {render_bundle_id:?}");
+
+        let hub = A::hub(self);
+
+        if let Some(bundle) = hub.render_bundles.unregister(render_bundle_id) {
+            bundle
+                .device
+                .lock_life()
+                .suspected_resources
+                .render_bundles
+                .insert(bundle.info.tracker_index(), bundle.clone());
+        }
+    }
+
+    pub fn device_create_query_set<A: HalApi>(
+        &self,
+        device_id: DeviceId,
+        desc: &resource::QuerySetDescriptor,
+        id_in: Option<id::QuerySetId>,
+    ) -> (id::QuerySetId, Option<resource::CreateQuerySetError>) {
+        profiling::scope!("Device::create_query_set");
+
+        let hub = A::hub(self);
+        let fid = hub.query_sets.prepare(id_in);
+
+        let error = loop {
+            let device = match hub.devices.get(device_id) {
+                Ok(device) => device,
+                Err(_) => break DeviceError::Invalid.into(),
+            };
+            if !device.is_valid() {
+                break DeviceError::Lost.into();
+            }
+
+            #[cfg(feature = "trace")]
+            if let Some(ref mut trace) = *device.trace.lock() {
+                trace.add(trace::Action::CreateQuerySet {
+                    id: fid.id(),
+                    desc: desc.clone(),
+                });
+            }
+
+            let query_set = match device.create_query_set(desc) {
+                Ok(query_set) => query_set,
+                Err(err) => break err,
+            };
+
+            let (id, resource) = fid.assign(Arc::new(query_set));
+            api_log!("Device::create_query_set -> {id:?}");
+            device.trackers.lock().query_sets.insert_single(resource);
+
+            return (id, None);
+        };
+
+        let id = fid.assign_error("");
+        (id, Some(error))
+    }
+
+    pub fn query_set_drop<A: HalApi>(&self, query_set_id: id::QuerySetId) {
+        profiling::scope!("QuerySet::drop");
+        api_log!("QuerySet::drop {query_set_id:?}");
+
+        let hub = A::hub(self);
+
+        if let Some(query_set) = hub.query_sets.unregister(query_set_id) {
+            let device = &query_set.device;
+
+            #[cfg(feature = "trace")]
+            if let Some(ref mut trace) = *device.trace.lock() {
+                trace.add(trace::Action::DestroyQuerySet(query_set_id));
+            }
+
+            device
+                .lock_life()
+                .suspected_resources
+                .query_sets
+                .insert(query_set.info.tracker_index(), query_set.clone());
+        }
+    }
+
+    pub fn query_set_label<A: HalApi>(&self, id: id::QuerySetId) -> String {
+        A::hub(self).query_sets.label_for_resource(id)
+    }
+
+    pub fn device_create_timeline_semaphore<A: HalApi>(
+        &self,
+        device_id: DeviceId,
+        desc: &resource::TimelineSemaphoreDescriptor,
+        id_in: Option<id::TimelineSemaphoreId>,
+    ) -> (
+        id::TimelineSemaphoreId,
+        Option<resource::CreateTimelineSemaphoreError>,
+    ) {
+        profiling::scope!("Device::create_timeline_semaphore");
+
+        let hub = A::hub(self);
+        let fid = hub.timeline_semaphores.prepare(id_in);
+
+        let error = loop {
+            let device = match hub.devices.get(device_id) {
+                Ok(device) => device,
+                Err(_) => break DeviceError::Invalid.into(),
+            };
+            if !device.is_valid() {
+                break DeviceError::Lost.into();
+            }
+
+            let semaphore = match device.create_timeline_semaphore(desc) {
+                Ok(semaphore) => semaphore,
+                Err(err) => break err,
+            };
+
+            let (id, _) = fid.assign(Arc::new(semaphore));
+            api_log!("Device::create_timeline_semaphore -> {id:?}");
+
             return (id, None);
         };
 
@@ -1471,36 +2061,65 @@ impl Global {
         (id, Some(error))
     }
 
-    pub fn render_bundle_label<A: HalApi>(&self, id: id::RenderBundleId) -> String {
-        A::hub(self).render_bundles.label_for_resource(id)
-    }
-
-    pub fn render_bundle_drop<A: HalApi>(&self, render_bundle_id: id::RenderBundleId) {
-        profiling::scope!("RenderBundle::drop");
-        api_log!("RenderBundle::drop {render_bundle_id:?}");
+    /// # Safety
+    ///
+    /// - `hal_fence` must be created from `device_id` corresponding raw handle.
+    /// - `hal_fence` must be a timeline semaphore if the device has them.
+    /// - The current value of `hal_fence` must be `desc.initial_value`.
+    pub unsafe fn create_timeline_semaphore_from_hal<A: HalApi>(
+        &self,
+        hal_fence: A::Fence,
+        device_id: DeviceId,
+        desc: &resource::TimelineSemaphoreDescriptor,
+        id_in: Option<id::TimelineSemaphoreId>,
+    ) -> (
+        id::TimelineSemaphoreId,
+        Option<resource::CreateTimelineSemaphoreError>,
+    ) {
+        profiling::scope!("Device::create_timeline_semaphore");
 
         let hub = A::hub(self);
+        let fid = hub.timeline_semaphores.prepare(id_in);
 
-        if let Some(bundle) = hub.render_bundles.unregister(render_bundle_id) {
-            bundle
-                .device
-                .lock_life()
-                .suspected_resources
-                .render_bundles
-                .insert(bundle.info.tracker_index(), bundle.clone());
-        }
+        let error = loop {
+            let device = match hub.devices.get(device_id) {
+                Ok(device) => device,
+                Err(_) => break DeviceError::Invalid.into(),
+            };
+            if !device.is_valid() {
+                break DeviceError::Lost.into();
+            }
+            if let Err(err) = device.require_features(wgt::Features::TIMELINE_SEMAPHORE) {
+                break err.into();
+            }
+
+            let semaphore = device.create_timeline_semaphore_from_hal(hal_fence, desc);
+
+            let (id, _) = fid.assign(Arc::new(semaphore));
+            api_log!("Device::create_timeline_semaphore -> {id:?}");
+
+            return (id, None);
+        };
+
+        let id = fid.assign_error(desc.label.borrow_or_default());
+        (id, Some(error))
     }
 
-    pub fn device_create_query_set<A: HalApi>(
+    /// Allocate a memory heap that buffers and textures can be placed in with
+    /// [`Global::device_create_placed_buffer`] and
+    /// [`Global::device_create_placed_texture`].
+    ///
+    /// Requires [`wgt::Features::PLACED_RESOURCES`].
+    pub fn device_create_memory_heap<A: HalApi>(
         &self,
         device_id: DeviceId,
-        desc: &resource::QuerySetDescriptor,
-        id_in: Option<id::QuerySetId>,
-    ) -> (id::QuerySetId, Option<resource::CreateQuerySetError>) {
-        profiling::scope!("Device::create_query_set");
+        desc: &resource::MemoryHeapDescriptor,
+        id_in: Option<id::MemoryHeapId>,
+    ) -> (id::MemoryHeapId, Option<resource::CreateMemoryHeapError>) {
+        profiling::scope!("Device::create_memory_heap");
 
         let hub = A::hub(self);
-        let fid = hub.query_sets.prepare(id_in);
+        let fid = hub.memory_heaps.prepare(id_in);
 
         let error = loop {
             let device = match hub.devices.get(device_id) {
@@ -1511,54 +2130,131 @@ impl Global {
                 break DeviceError::Lost.into();
             }
 
-            #[cfg(feature = "trace")]
-            if let Some(ref mut trace) = *device.trace.lock() {
-                trace.add(trace::Action::CreateQuerySet {
-                    id: fid.id(),
-                    desc: desc.clone(),
-                });
-            }
-
-            let query_set = match device.create_query_set(desc) {
-                Ok(query_set) => query_set,
+            let heap = match device.create_memory_heap(desc) {
+                Ok(heap) => heap,
                 Err(err) => break err,
             };
 
-            let (id, resource) = fid.assign(Arc::new(query_set));
-            api_log!("Device::create_query_set -> {id:?}");
-            device.trackers.lock().query_sets.insert_single(resource);
+            let (id, _) = fid.assign(Arc::new(heap));
+            api_log!("Device::create_memory_heap({desc:?}) -> {id:?}");
 
             return (id, None);
         };
 
-        let id = fid.assign_error("");
+        let id = fid.assign_error(desc.label.borrow_or_default());
         (id, Some(error))
     }
 
-    pub fn query_set_drop<A: HalApi>(&self, query_set_id: id::QuerySetId) {
-        profiling::scope!("QuerySet::drop");
-        api_log!("QuerySet::drop {query_set_id:?}");
+    pub fn memory_heap_drop<A: HalApi>(&self, heap_id: id::MemoryHeapId) {
+        profiling::scope!("MemoryHeap::drop");
+        api_log!("MemoryHeap::drop {heap_id:?}");
 
-        let hub = A::hub(self);
+        // The resources placed in the heap keep it alive.
+        A::hub(self).memory_heaps.unregister(heap_id);
+    }
 
-        if let Some(query_set) = hub.query_sets.unregister(query_set_id) {
-            let device = &query_set.device;
+    pub fn memory_heap_label<A: HalApi>(&self, id: id::MemoryHeapId) -> String {
+        A::hub(self).memory_heaps.label_for_resource(id)
+    }
 
-            #[cfg(feature = "trace")]
-            if let Some(ref mut trace) = *device.trace.lock() {
-                trace.add(trace::Action::DestroyQuerySet(query_set_id));
-            }
+    pub fn timeline_semaphore_drop<A: HalApi>(&self, semaphore_id: id::TimelineSemaphoreId) {
+        profiling::scope!("TimelineSemaphore::drop");
+        api_log!("TimelineSemaphore::drop {semaphore_id:?}");
 
-            device
-                .lock_life()
-                .suspected_resources
-                .query_sets
-                .insert(query_set.info.tracker_index(), query_set.clone());
+        // Submissions still in flight keep the semaphore alive.
+        A::hub(self).timeline_semaphores.unregister(semaphore_id);
+    }
+
+    pub fn timeline_semaphore_label<A: HalApi>(&self, id: id::TimelineSemaphoreId) -> String {
+        A::hub(self).timeline_semaphores.label_for_resource(id)
+    }
+
+    /// Return the current value of a timeline semaphore.
+    pub fn timeline_semaphore_get_value<A: HalApi>(
+        &self,
+        semaphore_id: id::TimelineSemaphoreId,
+    ) -> Result<u64, resource::TimelineSemaphoreError> {
+        let semaphore = A::hub(self)
+            .timeline_semaphores
+            .get(semaphore_id)
+            .map_err(|_| resource::TimelineSemaphoreError::Invalid(semaphore_id))?;
+
+        let raw = semaphore.raw.lock();
+        let value = unsafe {
+            semaphore
+                .device
+                .raw()
+                .get_fence_value(raw.as_ref().unwrap())
         }
+        .map_err(DeviceError::from)?;
+        Ok(value)
     }
 
-    pub fn query_set_label<A: HalApi>(&self, id: id::QuerySetId) -> String {
-        A::hub(self).query_sets.label_for_resource(id)
+    /// Set a timeline semaphore to `value` from the host.
+    ///
+    /// `value` has to exceed the values the semaphore was signaled with before.
+    pub fn timeline_semaphore_signal<A: HalApi>(
+        &self,
+        semaphore_id: id::TimelineSemaphoreId,
+        value: u64,
+    ) -> Result<(), resource::TimelineSemaphoreError> {
+        api_log!("TimelineSemaphore::signal {semaphore_id:?} {value}");
+
+        let semaphore = A::hub(self)
+            .timeline_semaphores
+            .get(semaphore_id)
+            .map_err(|_| resource::TimelineSemaphoreError::Invalid(semaphore_id))?;
+
+        let mut raw = semaphore.raw.lock();
+        semaphore.begin_signal(value)?;
+        unsafe {
+            semaphore
+                .device
+                .raw()
+                .signal_fence(raw.as_mut().unwrap(), value)
+        }
+        .map_err(DeviceError::from)?;
+        Ok(())
+    }
+
+    /// Block until a timeline semaphore reaches `value`, for at most
+    /// `timeout_ms` milliseconds.
+    ///
+    /// Returns `false` if the timeout expired first.
+    pub fn timeline_semaphore_wait<A: HalApi>(
+        &self,
+        semaphore_id: id::TimelineSemaphoreId,
+        value: u64,
+        timeout_ms: u32,
+    ) -> Result<bool, resource::TimelineSemaphoreError> {
+        profiling::scope!("TimelineSemaphore::wait");
+
+        let semaphore = A::hub(self)
+            .timeline_semaphores
+            .get(semaphore_id)
+            .map_err(|_| resource::TimelineSemaphoreError::Invalid(semaphore_id))?;
+
+        // Submissions that signal the semaphore need its fence as well, so
+        // rather than holding the lock for the whole timeout, wait in slices.
+        const WAIT_SLICE_MS: u32 = 1;
+        let mut remaining_ms = timeout_ms;
+        loop {
+            let slice_ms = remaining_ms.min(WAIT_SLICE_MS);
+            let done = {
+                let raw = semaphore.raw.lock();
+                unsafe {
+                    semaphore
+                        .device
+                        .raw()
+                        .wait(raw.as_ref().unwrap(), value, slice_ms)
+                }
+                .map_err(DeviceError::from)?
+            };
+            if done || remaining_ms == 0 {
+                return Ok(done);
+            }
+            remaining_ms -= slice_ms;
+        }
     }
 
     pub fn device_create_render_pipeline<A: HalApi>(
@@ -1936,9 +2632,44 @@ impl Global {
             if !caps.usage.contains(config.usage) {
                 return Err(E::UnsupportedUsage);
             }
+            if !caps.transforms.contains(&config.pre_transform) {
+                return Err(E::UnsupportedTransform {
+                    requested: config.pre_transform,
+                    available: caps.transforms.clone(),
+                });
+            }
+            if !caps
+                .color_spaces
+                .contains(&(config.format, config.color_space))
+            {
+                return Err(E::UnsupportedColorSpace {
+                    format: config.format,
+                    color_space: config.color_space,
+                });
+            }
             if width == 0 || height == 0 {
                 return Err(E::ZeroArea);
             }
+            let array_layer_count = config.extent.depth_or_array_layers;
+            if array_layer_count == 0 || array_layer_count > caps.max_array_layers {
+                return Err(E::UnsupportedArrayLayerCount {
+                    requested: array_layer_count,
+                    max: caps.max_array_layers,
+                });
+            }
+            if !caps.fullscreen_modes.contains(&config.fullscreen) {
+                return Err(E::UnsupportedFullscreenMode {
+                    requested: config.fullscreen,
+                    available: caps.fullscreen_modes.clone(),
+                });
+            }
+            if let Some(display_mode) = config.display_mode {
+                if config.fullscreen != wgt::FullscreenMode::Exclusive
+                    || !caps.display_modes.contains(&display_mode)
+                {
+                    return Err(E::UnsupportedDisplayMode(display_mode));
+                }
+            }
             Ok(())
         }
 
@@ -2008,18 +2739,27 @@ impl Global {
                     *caps.maximum_frame_latency.start(),
                     *caps.maximum_frame_latency.end(),
                 );
+                let image_count = config
+                    .desired_minimum_image_count
+                    .unwrap_or(maximum_frame_latency + 1)
+                    .clamp(*caps.image_count.start(), *caps.image_count.end());
                 let mut hal_config = hal::SurfaceConfiguration {
                     maximum_frame_latency,
+                    image_count,
                     present_mode: config.present_mode,
                     composite_alpha_mode: config.alpha_mode,
                     format: config.format,
                     extent: wgt::Extent3d {
                         width: config.width,
                         height: config.height,
-                        depth_or_array_layers: 1,
+                        depth_or_array_layers: config.array_layer_count,
                     },
                     usage: conv::map_texture_usage(config.usage, hal::FormatAspects::COLOR),
                     view_formats: hal_view_formats,
+                    pre_transform: config.pre_transform,
+                    color_space: config.color_space,
+                    fullscreen: config.fullscreen,
+                    display_mode: config.display_mode,
                 };
 
                 if let Err(error) = validate_surface_configuration(
@@ -2034,9 +2774,14 @@ impl Global {
                 let snatch_guard = device.snatchable_lock.read();
                 let fence = device.fence.read();
                 let fence = fence.as_ref().unwrap();
-                match device.maintain(fence, wgt::Maintain::Wait, snatch_guard) {
+                match device.maintain(
+                    fence,
+                    wgt::Maintain::Wait,
+                    wgt::MaintainWork::all(),
+                    snatch_guard,
+                ) {
                     Ok((closures, _)) => {
-                        user_callbacks = closures;
+                        user_callbacks = device.defer_closures(closures);
                     }
                     Err(e) => {
                         break e.into();
@@ -2081,6 +2826,8 @@ impl Global {
                     device: super::any_device::AnyDevice::new(device.clone()),
                     config: config.clone(),
                     acquired_texture: None,
+                    statistics: Default::default(),
+                    maximum_frame_latency: caps.maximum_frame_latency,
                 });
             }
 
@@ -2113,45 +2860,132 @@ impl Global {
         device_id: DeviceId,
         maintain: wgt::Maintain<queue::WrappedSubmissionIndex>,
     ) -> Result<bool, WaitIdleError> {
-        api_log!("Device::poll");
+        self.device_poll_work::<A>(device_id, maintain, wgt::MaintainWork::all())
+    }
+
+    /// Like [`Global::device_poll`], but only do the parts of the maintenance
+    /// in `work`.
+    pub fn device_poll_work<A: HalApi>(
+        &self,
+        device_id: DeviceId,
+        maintain: wgt::Maintain<queue::WrappedSubmissionIndex>,
+        work: wgt::MaintainWork,
+    ) -> Result<bool, WaitIdleError> {
+        api_log!("Device::poll {work:?}");
 
         let hub = A::hub(self);
         let device = hub
             .devices
             .get(device_id)
             .map_err(|_| DeviceError::Invalid)?;
+        self.flush_submit_batch_or_log::<A>(&device);
 
-        if let wgt::Maintain::WaitForSubmissionIndex(submission_index) = maintain {
-            if submission_index.queue_id != device_id.into_queue_id() {
-                return Err(WaitIdleError::WrongSubmissionIndex(
-                    submission_index.queue_id,
-                    device_id,
-                ));
-            }
+        if let Some(&submission_index) = maintain.submission_index() {
+            Self::check_submission_index(hub, device_id, submission_index)?;
         }
 
         let DevicePoll {
             closures,
             queue_empty,
-        } = Self::poll_single_device(&device, maintain)?;
+        } = Self::poll_single_device(&device, maintain, work)?;
 
         closures.fire();
 
         Ok(queue_empty)
     }
 
-    fn poll_single_device<A: HalApi>(
+    /// Block until the submission `submission_index`, and all submissions before
+    /// it, have completed on every queue of `device_id`, or `timeout_ms` has passed.
+    ///
+    /// Unlike [`Global::device_poll`], this doesn't map buffers or call any
+    /// callbacks. Return `false` if the wait timed out.
+    pub fn device_wait_for_submission<A: HalApi>(
+        &self,
+        device_id: DeviceId,
+        submission_index: queue::WrappedSubmissionIndex,
+        timeout_ms: u32,
+    ) -> Result<bool, WaitIdleError> {
+        api_log!("Device::wait_for_submission {submission_index:?}");
+
+        let hub = A::hub(self);
+        let device = hub
+            .devices
+            .get(device_id)
+            .map_err(|_| DeviceError::Invalid)?;
+        self.flush_submit_batch_or_log::<A>(&device);
+        Self::check_submission_index(hub, device_id, submission_index)?;
+
+        // Submitting needs the fence as well, so rather than holding the lock
+        // for the whole timeout, wait in slices.
+        const WAIT_SLICE_MS: u32 = 1;
+        let mut remaining_ms = timeout_ms;
+        loop {
+            let slice_ms = remaining_ms.min(WAIT_SLICE_MS);
+            let done = {
+                let fence = device.fence.read();
+                device.wait_for_index(fence.as_ref().unwrap(), submission_index.index, slice_ms)?
+            };
+            if done || remaining_ms == 0 {
+                return Ok(done);
+            }
+            remaining_ms -= slice_ms;
+        }
+    }
+
+    /// Submit the batch pending on `device` before polling it, see
+    /// [`Global::queue_set_submit_batching`].
+    ///
+    /// There is no submission to report errors of the batch to, so they are
+    /// logged instead.
+    pub(crate) fn flush_submit_batch_or_log<A: HalApi>(&self, device: &crate::device::Device<A>) {
+        if let Err(err) = self.flush_submit_batch::<A>(device) {
+            log::error!("Failed to submit the batched submissions: {err}");
+        }
+    }
+
+    /// Check that `submission_index` is from one of the queues of `device_id`.
+    pub(crate) fn check_submission_index<A: HalApi>(
+        hub: &crate::hub::Hub<A>,
+        device_id: DeviceId,
+        submission_index: queue::WrappedSubmissionIndex,
+    ) -> Result<(), WaitIdleError> {
+        // Submissions to the device's additional queues can be waited for too.
+        let same_device = submission_index.queue_id == device_id.into_queue_id()
+            || hub
+                .queues
+                .get(submission_index.queue_id)
+                .map_or(false, |queue| {
+                    queue.device.as_ref().unwrap().as_info().id() == device_id
+                });
+        if same_device {
+            Ok(())
+        } else {
+            Err(WaitIdleError::WrongSubmissionIndex(
+                submission_index.queue_id,
+                device_id,
+            ))
+        }
+    }
+
+    pub(crate) fn poll_single_device<A: HalApi>(
         device: &crate::device::Device<A>,
         maintain: wgt::Maintain<queue::WrappedSubmissionIndex>,
+        work: wgt::MaintainWork,
     ) -> Result<DevicePoll, WaitIdleError> {
         let snatch_guard = device.snatchable_lock.read();
         let fence = device.fence.read();
         let fence = fence.as_ref().unwrap();
-        let (closures, queue_empty) = device.maintain(fence, maintain, snatch_guard)?;
+        let (new_closures, queue_empty) = device.maintain(fence, maintain, work, snatch_guard)?;
+
+        // Closures held back from earlier calls come first.
+        let mut closures = device.take_deferred_closures();
+        closures.extend(new_closures);
 
         // Some deferred destroys are scheduled in maintain so run this right after
         // to avoid holding on to them until the next device poll.
-        device.deferred_resource_destruction();
+        if work.contains(wgt::MaintainWork::CLEANUP) {
+            device.deferred_resource_destruction();
+        }
 
         Ok(DevicePoll {
             closures,
@@ -2173,6 +3007,16 @@ impl Global {
         profiling::scope!("poll_device");
 
         let hub = A::hub(self);
+        let devices: Vec<_> = hub
+            .devices
+            .read()
+            .iter(A::VARIANT)
+            .map(|(_, device)| device.clone())
+            .collect();
+        for device in devices {
+            self.flush_submit_batch_or_log::<A>(&device);
+        }
+
         let mut all_queue_empty = true;
         {
             let device_guard = hub.devices.read();
@@ -2187,7 +3031,7 @@ impl Global {
                 let DevicePoll {
                     closures: cbs,
                     queue_empty,
-                } = Self::poll_single_device(device, maintain)?;
+                } = Self::poll_single_device(device, maintain, wgt::MaintainWork::all())?;
 
                 all_queue_empty &= queue_empty;
 
@@ -2229,6 +3073,11 @@ impl Global {
             all_queue_empty &=
                 self.poll_all_devices_of_api::<hal::api::Gles>(force_wait, &mut closures)?;
         }
+        #[cfg(noop)]
+        {
+            all_queue_empty &=
+                self.poll_all_devices_of_api::<hal::api::Empty>(force_wait, &mut closures)?;
+        }
 
         closures.fire();
 
@@ -2359,8 +3208,120 @@ impl Global {
         let hub = A::hub(self);
 
         if let Ok(device) = hub.devices.get(device_id) {
-            device.lose(message);
+            device.lose(DeviceLostReason::Unknown, message);
+        }
+    }
+
+    /// Lose `device_id` as if its adapter had been reset, calling the device
+    /// lost closure with `reason`, for testing how applications recover.
+    ///
+    /// Unlike a real loss, work that was already submitted still runs. This
+    /// waits for it, so that the closures of pending buffer mappings and of
+    /// `Queue::on_submitted_work_done` have been called when it returns, like
+    /// after a real loss once the device is polled.
+    #[cfg(any(debug_assertions, feature = "test-hooks"))]
+    pub fn device_simulate_loss<A: HalApi>(
+        &self,
+        device_id: DeviceId,
+        reason: DeviceLostReason,
+    ) -> Result<(), WaitIdleError> {
+        api_log!("Device::simulate_loss {device_id:?} {reason:?}");
+
+        let hub = A::hub(self);
+        let device = hub
+            .devices
+            .get(device_id)
+            .map_err(|_| DeviceError::Invalid)?;
+        if !device.is_valid() {
+            return Ok(());
+        }
+        self.flush_submit_batch_or_log::<A>(&device);
+
+        device.lose(reason, "Device loss was simulated.");
+
+        let DevicePoll { closures, .. } =
+            Self::poll_single_device(&device, wgt::Maintain::Wait, wgt::MaintainWork::all())?;
+        closures.fire();
+        Ok(())
+    }
+
+    /// Make creating pipelines on `device_id` fail if compiling them takes
+    /// longer than `timeout`, or wait indefinitely if it's `None`.
+    ///
+    /// Drivers can't cancel a compile, so with a timeout set, pipelines are
+    /// compiled on a thread of their own, which keeps running after the timeout
+    /// elapses. Has no effect on platforms without threads.
+    pub fn device_set_pipeline_compile_timeout<A: HalApi>(
+        &self,
+        device_id: DeviceId,
+        timeout: Option<std::time::Duration>,
+    ) -> Result<(), DeviceError> {
+        api_log!("Device::set_pipeline_compile_timeout {device_id:?} {timeout:?}");
+
+        let hub = A::hub(self);
+        let device = hub
+            .devices
+            .get(device_id)
+            .map_err(|_| DeviceError::Invalid)?;
+        *device.pipeline_compile_timeout.lock() = timeout;
+        Ok(())
+    }
+
+    /// Change how much of the usage of `device_id` is validated.
+    ///
+    /// Commands are validated at the level set when they are recorded.
+    ///
+    /// # Safety
+    ///
+    /// Below [`wgt::ValidationLevel::Full`], usage that is no longer validated
+    /// is passed to the backend as-is, and must be valid.
+    pub unsafe fn device_set_validation_level<A: HalApi>(
+        &self,
+        device_id: DeviceId,
+        level: wgt::ValidationLevel,
+    ) -> Result<(), DeviceError> {
+        api_log!("Device::set_validation_level {device_id:?} {level:?}");
+
+        let hub = A::hub(self);
+        let device = hub
+            .devices
+            .get(device_id)
+            .map_err(|_| DeviceError::Invalid)?;
+        device.set_validation_level(level);
+        Ok(())
+    }
+
+    /// Return how much of the usage of `device_id` is validated.
+    pub fn device_validation_level<A: HalApi>(
+        &self,
+        device_id: DeviceId,
+    ) -> Result<wgt::ValidationLevel, DeviceError> {
+        let hub = A::hub(self);
+        let device = hub
+            .devices
+            .get(device_id)
+            .map_err(|_| DeviceError::Invalid)?;
+        Ok(device.validation_level())
+    }
+
+    /// Tell the platform how `device_id` should trade performance for power.
+    pub fn device_set_performance_hint<A: HalApi>(
+        &self,
+        device_id: DeviceId,
+        hint: wgt::PerformanceHint,
+    ) -> Result<(), DeviceError> {
+        api_log!("Device::set_performance_hint {device_id:?} {hint:?}");
+
+        let hub = A::hub(self);
+        let device = hub
+            .devices
+            .get(device_id)
+            .map_err(|_| DeviceError::Invalid)?;
+        if !device.is_valid() {
+            return Err(DeviceError::Lost);
         }
+        unsafe { device.raw().set_performance_hint(hint) };
+        Ok(())
     }
 
     pub fn queue_drop<A: HalApi>(&self, queue_id: QueueId) {
@@ -2368,6 +3329,10 @@ impl Global {
         api_log!("Queue::drop {queue_id:?}");
 
         let hub = A::hub(self);
+        // This is the last chance to submit what the queue deferred.
+        if let Ok(queue) = hub.queues.get(queue_id) {
+            self.flush_submit_batch_or_log::<A>(queue.device.as_ref().unwrap());
+        }
         if let Some(queue) = hub.queues.unregister(queue_id) {
             drop(queue);
         }
@@ -2385,11 +3350,15 @@ impl Global {
         // User callbacks must not be called while holding buffer_map_async_inner's locks, so we
         // defer the error callback if it needs to be called immediately (typically when running
         // into errors).
-        if let Err((mut operation, err)) =
-            self.buffer_map_async_inner::<A>(buffer_id, offset, size, op)
+        if let Err((operation, err)) = self.buffer_map_async_inner::<A>(buffer_id, offset, size, op)
         {
-            if let Some(callback) = operation.callback.take() {
-                callback.call(Err(err.clone()));
+            let closures = UserClosures {
+                mappings: vec![(operation, Err(err.clone()))],
+                ..Default::default()
+            };
+            match A::hub(self).buffers.get(buffer_id) {
+                Ok(buffer) => buffer.device.defer_closures(closures).fire(),
+                Err(_) => closures.fire(),
             }
             log::error!("Buffer::map_async error: {err}");
             return Err(err);
@@ -2425,6 +3394,8 @@ impl Global {
                     return Err((op, BufferAccessError::Invalid));
                 }
             };
+            // The mapping has to wait for the batched submissions.
+            self.flush_submit_batch_or_log::<A>(&buffer.device);
             {
                 let snatch_guard = buffer.device.snatchable_lock.read();
                 if buffer.is_destroyed(&snatch_guard) {
@@ -2517,10 +3488,18 @@ impl Global {
         };
 
         buffer.device.lock_life().map(&buffer);
+        buffer.device.wake_background_poll();
 
         Ok(())
     }
 
+    /// Return a pointer to `size` bytes of the mapping of `buffer_id` at `offset`.
+    ///
+    /// With [`wgt::Features::PERSISTENT_MAPPING`], this fails while a submission
+    /// using the buffer is running, and keeps later submissions from using the
+    /// buffer until it is unmapped, since the returned range may still be in
+    /// use. See [`Global::buffer_get_mapped_range_unchecked`] to write to a
+    /// buffer between the submissions using it.
     pub fn buffer_get_mapped_range<A: HalApi>(
         &self,
         buffer_id: id::BufferId,
@@ -2530,6 +3509,39 @@ impl Global {
         profiling::scope!("Buffer::get_mapped_range");
         api_log!("Buffer::get_mapped_range {buffer_id:?} offset {offset:?} size {size:?}");
 
+        self.buffer_get_mapped_range_inner::<A>(buffer_id, offset, size, true)
+    }
+
+    /// Like [`Global::buffer_get_mapped_range`], but leaves a buffer mapped
+    /// for writing usable by submissions with
+    /// [`wgt::Features::PERSISTENT_MAPPING`].
+    ///
+    /// # Safety
+    ///
+    /// - The returned range must not be accessed while a submission using the
+    ///   buffer is running, which is only known once it has completed, for
+    ///   example with [`Global::queue_is_submission_complete`].
+    pub unsafe fn buffer_get_mapped_range_unchecked<A: HalApi>(
+        &self,
+        buffer_id: id::BufferId,
+        offset: BufferAddress,
+        size: Option<BufferAddress>,
+    ) -> Result<(*mut u8, u64), BufferAccessError> {
+        profiling::scope!("Buffer::get_mapped_range_unchecked");
+        api_log!(
+            "Buffer::get_mapped_range_unchecked {buffer_id:?} offset {offset:?} size {size:?}"
+        );
+
+        self.buffer_get_mapped_range_inner::<A>(buffer_id, offset, size, false)
+    }
+
+    fn buffer_get_mapped_range_inner<A: HalApi>(
+        &self,
+        buffer_id: id::BufferId,
+        offset: BufferAddress,
+        size: Option<BufferAddress>,
+        checked: bool,
+    ) -> Result<(*mut u8, u64), BufferAccessError> {
         let hub = A::hub(self);
 
         let buffer = hub
@@ -2558,7 +3570,19 @@ impl Global {
         if range_size % wgt::COPY_BUFFER_ALIGNMENT != 0 {
             return Err(BufferAccessError::UnalignedRangeSize { range_size });
         }
-        let map_state = &*buffer.map_state.lock();
+
+        // Only buffers mapped for writing with `PERSISTENT_MAPPING` can be used
+        // by submissions, but checking for a running one is cheap enough.
+        let device = &buffer.device;
+        if checked && device.features.contains(wgt::Features::PERSISTENT_MAPPING) {
+            let fence = device.fence.read();
+            let last_done_index = device.get_last_done_index(fence.as_ref().unwrap())?;
+            if buffer.info.submission_index() > last_done_index {
+                return Err(BufferAccessError::InUse);
+            }
+        }
+
+        let map_state = &mut *buffer.map_state.lock();
         match *map_state {
             resource::BufferMapState::Init { ref ptr, .. } => {
                 // offset (u64) can not be < 0, so no need to validate the lower bound
@@ -2571,7 +3595,10 @@ impl Global {
                 unsafe { Ok((ptr.as_ptr().offset(offset as isize), range_size)) }
             }
             resource::BufferMapState::Active {
-                ref ptr, ref range, ..
+                ref ptr,
+                ref range,
+                ref mut checked_views,
+                ..
             } => {
                 if offset < range.start {
                     return Err(BufferAccessError::OutOfBoundsUnderrun {
@@ -2585,6 +3612,7 @@ impl Global {
                         max: range.end,
                     });
                 }
+                *checked_views |= checked;
                 // ptr points to the beginning of the range we mapped in map_async
                 // rather than the beginning of the buffer.
                 let relative_offset = (offset - range.start) as isize;
@@ -2605,6 +3633,9 @@ impl Global {
             .buffers
             .get(buffer_id)
             .map_err(|_| BufferAccessError::Invalid)?;
+        // Unmapping can record a copy into the pending writes, which must not
+        // be submitted ahead of the batched submissions.
+        self.flush_submit_batch_or_log::<A>(&buffer.device);
 
         let snatch_guard = buffer.device.snatchable_lock.read();
         if buffer.is_destroyed(&snatch_guard) {
@@ -2620,7 +3651,7 @@ impl Global {
     }
 }
 
-struct DevicePoll {
-    closures: UserClosures,
-    queue_empty: bool,
+pub(crate) struct DevicePoll {
+    pub(crate) closures: UserClosures,
+    pub(crate) queue_empty: bool,
 }
@@ -0,0 +1,210 @@
+//! Textures backed by IOSurfaces, which Metal shares images with CoreVideo,
+//! AVFoundation and other processes through.
+
+#[cfg(feature = "trace")]
+use crate::device::trace;
+use crate::{
+    api_log,
+    device::{Device, DeviceError, MissingFeatures},
+    global::Global,
+    hal_api::HalApi,
+    id::{self, DeviceId},
+    init_tracker::TextureInitTracker,
+    lock::{rank, RwLock},
+    resource::{self, CreateTextureError, ExportMemoryError, Texture},
+    LabelHelpers as _,
+};
+
+use hal::api::Metal;
+
+use std::{ffi::c_void, sync::Arc};
+
+impl Device<Metal> {
+    /// Create a texture on `plane` of `iosurface`, or on a new IOSurface if
+    /// there is none.
+    fn create_iosurface_texture(
+        self: &Arc<Self>,
+        desc: &resource::TextureDescriptor,
+        iosurface: Option<(*mut c_void, u32)>,
+    ) -> Result<Texture<Metal>, CreateTextureError> {
+        self.require_features(wgt::Features::IOSURFACE)
+            .map_err(CreateTextureError::MissingExternalMemoryFeature)?;
+
+        if desc.dimension != wgt::TextureDimension::D2
+            || desc.size.depth_or_array_layers != 1
+            || desc.mip_level_count != 1
+            || desc.sample_count != 1
+        {
+            return Err(CreateTextureError::InvalidIOSurface {
+                dimension: desc.dimension,
+                layers: desc.size.depth_or_array_layers,
+                mip_level_count: desc.mip_level_count,
+                sample_count: desc.sample_count,
+            });
+        }
+        if iosurface.is_none() && !hal::metal::Device::supports_iosurface_format(desc.format) {
+            return Err(CreateTextureError::UnsupportedIOSurfaceFormat(desc.format));
+        }
+
+        let (hal_desc, format_features) =
+            self.texture_hal_descriptor(&self.adapter, desc, hal::MemoryFlags::empty())?;
+        let hal_usage = hal_desc.usage;
+        let raw = match iosurface {
+            Some((iosurface, plane)) => unsafe {
+                self.raw()
+                    .texture_from_iosurface(&hal_desc, iosurface, plane)
+            },
+            None => unsafe { self.raw().create_iosurface_texture(&hal_desc) },
+        }
+        .map_err(DeviceError::from)?;
+
+        let clear_mode = self.create_texture_clear_mode(&raw, hal_usage, desc)?;
+        let mut texture =
+            self.create_texture_from_hal(raw, hal_usage, desc, format_features, clear_mode);
+        // Surfaces from elsewhere keep what was drawn into them, but new ones
+        // are cleared before use like any other texture.
+        if iosurface.is_some() {
+            texture.initialization_status = RwLock::new(
+                rank::TEXTURE_INITIALIZATION_STATUS,
+                TextureInitTracker::new(1, 0),
+            );
+        }
+        Ok(texture)
+    }
+}
+
+impl Global {
+    /// Create a texture backed by a new IOSurface, which
+    /// [`Global::texture_iosurface`] returns to share it.
+    pub fn device_create_iosurface_texture(
+        &self,
+        device_id: DeviceId,
+        desc: &resource::TextureDescriptor,
+        id_in: Option<id::TextureId>,
+    ) -> (id::TextureId, Option<CreateTextureError>) {
+        profiling::scope!("Device::create_iosurface_texture");
+
+        self.create_iosurface_texture(device_id, desc, None, id_in)
+    }
+
+    /// Create a texture on `plane` of `iosurface`, an `IOSurfaceRef`.
+    ///
+    /// # Safety
+    ///
+    /// - `iosurface` must be a valid `IOSurfaceRef`, whose `plane` holds an
+    ///   image of the size and format of `desc`.
+    /// - Accesses to the surface from elsewhere must be synchronized with
+    ///   accesses through the texture.
+    pub unsafe fn device_import_iosurface(
+        &self,
+        device_id: DeviceId,
+        desc: &resource::TextureDescriptor,
+        iosurface: *mut c_void,
+        plane: u32,
+        id_in: Option<id::TextureId>,
+    ) -> (id::TextureId, Option<CreateTextureError>) {
+        profiling::scope!("Device::import_iosurface");
+
+        self.create_iosurface_texture(device_id, desc, Some((iosurface, plane)), id_in)
+    }
+
+    /// Return the `IOSurfaceRef` a texture created with
+    /// [`Global::device_create_iosurface_texture`] or
+    /// [`Global::device_import_iosurface`] is backed by.
+    ///
+    /// The surface isn't retained for the caller.
+    pub fn texture_iosurface(
+        &self,
+        texture_id: id::TextureId,
+    ) -> Result<*mut c_void, ExportMemoryError> {
+        api_log!("Texture::iosurface {texture_id:?}");
+
+        if texture_id.backend() != wgt::Backend::Metal {
+            return Err(MissingFeatures(wgt::Features::IOSURFACE).into());
+        }
+
+        let hub = Metal::hub(self);
+        let texture = hub
+            .textures
+            .get(texture_id)
+            .map_err(|_| ExportMemoryError::InvalidTexture(texture_id))?;
+        let snatch_guard = texture.device.snatchable_lock.read();
+        let iosurface = texture
+            .raw(&snatch_guard)
+            .ok_or(ExportMemoryError::InvalidTexture(texture_id))?
+            .iosurface();
+        if iosurface.is_null() {
+            return Err(ExportMemoryError::InvalidTexture(texture_id));
+        }
+        Ok(iosurface)
+    }
+
+    fn create_iosurface_texture(
+        &self,
+        device_id: DeviceId,
+        desc: &resource::TextureDescriptor,
+        iosurface: Option<(*mut c_void, u32)>,
+        id_in: Option<id::TextureId>,
+    ) -> (id::TextureId, Option<CreateTextureError>) {
+        if device_id.backend() != wgt::Backend::Metal {
+            let error = CreateTextureError::MissingExternalMemoryFeature(MissingFeatures(
+                wgt::Features::IOSURFACE,
+            ));
+            return crate::gfx_select!(device_id => self.iosurface_texture_error(desc, error, id_in));
+        }
+
+        let hub = Metal::hub(self);
+        let fid = hub.textures.prepare(id_in);
+
+        let error = loop {
+            let device = match hub.devices.get(device_id) {
+                Ok(device) => device,
+                Err(_) => break DeviceError::Invalid.into(),
+            };
+            if !device.is_valid() {
+                break DeviceError::Lost.into();
+            }
+
+            // NB: The contents of imported surfaces are not recorded in the replay
+            #[cfg(feature = "trace")]
+            if let Some(ref mut trace) = *device.trace.lock() {
+                trace.add(trace::Action::CreateTexture(fid.id(), desc.clone()));
+            }
+
+            let texture = match device.create_iosurface_texture(desc, iosurface) {
+                Ok(texture) => texture,
+                Err(e) => break e,
+            };
+
+            let (id, resource) = fid.assign(Arc::new(texture));
+            api_log!("Device::create_iosurface_texture({desc:?}) -> {id:?}");
+
+            device
+                .trackers
+                .lock()
+                .textures
+                .insert_single(resource, hal::TextureUses::UNINITIALIZED);
+
+            return (id, None);
+        };
+
+        log::error!("Device::create_iosurface_texture error: {error}");
+
+        let id = fid.assign_error(desc.label.borrow_or_default());
+        (id, Some(error))
+    }
+
+    /// Assign an error id to a texture on a backend without IOSurfaces.
+    fn iosurface_texture_error<A: HalApi>(
+        &self,
+        desc: &resource::TextureDescriptor,
+        error: CreateTextureError,
+        id_in: Option<id::TextureId>,
+    ) -> (id::TextureId, Option<CreateTextureError>) {
+        log::error!("Device::create_iosurface_texture error: {error}");
+
+        let fid = A::hub(self).textures.prepare(id_in);
+        let id = fid.assign_error(desc.label.borrow_or_default());
+        (id, Some(error))
+    }
+}
@@ -3,7 +3,7 @@ use crate::{
     command::RenderBundle,
     device::{
         queue::{EncoderInFlight, SubmittedWorkDoneClosure, TempResource},
-        DeviceError, DeviceLostClosure,
+        DeviceError, DeviceLostClosure, OrderedClosure, UserClosures,
     },
     hal_api::HalApi,
     id,
@@ -11,12 +11,13 @@ use crate::{
     pipeline::{ComputePipeline, RenderPipeline},
     resource::{
         self, Buffer, DestroyedBuffer, DestroyedTexture, QuerySet, Resource, Sampler,
-        StagingBuffer, Texture, TextureView,
+        StagingBuffer, Texture, TextureView, TimelineSemaphore,
     },
     snatch::SnatchGuard,
-    track::{ResourceTracker, Tracker, TrackerIndex},
-    FastHashMap, SubmissionIndex,
+    track::{ResourceTracker, Tracker, TrackerIndexMap},
+    SubmissionIndex,
 };
+use hal::Device as _;
 use smallvec::SmallVec;
 
 use std::sync::Arc;
@@ -24,39 +25,39 @@ use thiserror::Error;
 
 /// A struct that keeps lists of resources that are no longer needed by the user.
 pub(crate) struct ResourceMaps<A: HalApi> {
-    pub buffers: FastHashMap<TrackerIndex, Arc<Buffer<A>>>,
-    pub staging_buffers: FastHashMap<TrackerIndex, Arc<StagingBuffer<A>>>,
-    pub textures: FastHashMap<TrackerIndex, Arc<Texture<A>>>,
-    pub texture_views: FastHashMap<TrackerIndex, Arc<TextureView<A>>>,
-    pub samplers: FastHashMap<TrackerIndex, Arc<Sampler<A>>>,
-    pub bind_groups: FastHashMap<TrackerIndex, Arc<BindGroup<A>>>,
-    pub bind_group_layouts: FastHashMap<TrackerIndex, Arc<BindGroupLayout<A>>>,
-    pub render_pipelines: FastHashMap<TrackerIndex, Arc<RenderPipeline<A>>>,
-    pub compute_pipelines: FastHashMap<TrackerIndex, Arc<ComputePipeline<A>>>,
-    pub pipeline_layouts: FastHashMap<TrackerIndex, Arc<PipelineLayout<A>>>,
-    pub render_bundles: FastHashMap<TrackerIndex, Arc<RenderBundle<A>>>,
-    pub query_sets: FastHashMap<TrackerIndex, Arc<QuerySet<A>>>,
-    pub destroyed_buffers: FastHashMap<TrackerIndex, Arc<DestroyedBuffer<A>>>,
-    pub destroyed_textures: FastHashMap<TrackerIndex, Arc<DestroyedTexture<A>>>,
+    pub buffers: TrackerIndexMap<Arc<Buffer<A>>>,
+    pub staging_buffers: TrackerIndexMap<Arc<StagingBuffer<A>>>,
+    pub textures: TrackerIndexMap<Arc<Texture<A>>>,
+    pub texture_views: TrackerIndexMap<Arc<TextureView<A>>>,
+    pub samplers: TrackerIndexMap<Arc<Sampler<A>>>,
+    pub bind_groups: TrackerIndexMap<Arc<BindGroup<A>>>,
+    pub bind_group_layouts: TrackerIndexMap<Arc<BindGroupLayout<A>>>,
+    pub render_pipelines: TrackerIndexMap<Arc<RenderPipeline<A>>>,
+    pub compute_pipelines: TrackerIndexMap<Arc<ComputePipeline<A>>>,
+    pub pipeline_layouts: TrackerIndexMap<Arc<PipelineLayout<A>>>,
+    pub render_bundles: TrackerIndexMap<Arc<RenderBundle<A>>>,
+    pub query_sets: TrackerIndexMap<Arc<QuerySet<A>>>,
+    pub destroyed_buffers: TrackerIndexMap<Arc<DestroyedBuffer<A>>>,
+    pub destroyed_textures: TrackerIndexMap<Arc<DestroyedTexture<A>>>,
 }
 
 impl<A: HalApi> ResourceMaps<A> {
     pub(crate) fn new() -> Self {
         ResourceMaps {
-            buffers: FastHashMap::default(),
-            staging_buffers: FastHashMap::default(),
-            textures: FastHashMap::default(),
-            texture_views: FastHashMap::default(),
-            samplers: FastHashMap::default(),
-            bind_groups: FastHashMap::default(),
-            bind_group_layouts: FastHashMap::default(),
-            render_pipelines: FastHashMap::default(),
-            compute_pipelines: FastHashMap::default(),
-            pipeline_layouts: FastHashMap::default(),
-            render_bundles: FastHashMap::default(),
-            query_sets: FastHashMap::default(),
-            destroyed_buffers: FastHashMap::default(),
-            destroyed_textures: FastHashMap::default(),
+            buffers: TrackerIndexMap::default(),
+            staging_buffers: TrackerIndexMap::default(),
+            textures: TrackerIndexMap::default(),
+            texture_views: TrackerIndexMap::default(),
+            samplers: TrackerIndexMap::default(),
+            bind_groups: TrackerIndexMap::default(),
+            bind_group_layouts: TrackerIndexMap::default(),
+            render_pipelines: TrackerIndexMap::default(),
+            compute_pipelines: TrackerIndexMap::default(),
+            pipeline_layouts: TrackerIndexMap::default(),
+            render_bundles: TrackerIndexMap::default(),
+            query_sets: TrackerIndexMap::default(),
+            destroyed_buffers: TrackerIndexMap::default(),
+            destroyed_textures: TrackerIndexMap::default(),
         }
     }
 
@@ -127,6 +128,23 @@ impl<A: HalApi> ResourceMaps<A> {
     }
 }
 
+/// Pools [`LifetimeTracker::handle_mapping`] empties before mapping a buffer
+/// once more, after it ran out of memory with
+/// [`wgt::DeviceFlags::RETRY_FAILED_MAPPINGS`].
+pub(crate) struct MappingPurge<'a, A: HalApi> {
+    pub command_allocator: &'a crate::command::CommandAllocator<A>,
+    pub transfer_command_allocator: Option<&'a crate::command::CommandAllocator<A>>,
+}
+
+impl<A: HalApi> MappingPurge<'_, A> {
+    fn purge(&self, raw: &A::Device) {
+        self.command_allocator.dispose(raw);
+        if let Some(transfer_command_allocator) = self.transfer_command_allocator {
+            transfer_command_allocator.dispose(raw);
+        }
+    }
+}
+
 /// A command submitted to the GPU for execution.
 ///
 /// ## Keeping resources alive while the GPU is using them
@@ -165,6 +183,14 @@ struct ActiveSubmission<A: HalApi> {
     /// submission has completed.
     index: SubmissionIndex,
 
+    /// The additional queue this submission was made to, or `None` for the
+    /// device's primary queue.
+    ///
+    /// See [`Device::additional_queues`].
+    ///
+    /// [`Device::additional_queues`]: super::Device::additional_queues
+    queue: Option<usize>,
+
     /// Resources to be freed once this queue submission has completed.
     ///
     /// When the device is polled, for completed submissions,
@@ -193,6 +219,21 @@ struct ActiveSubmission<A: HalApi> {
     /// [`wgpu_hal::Queue::submit`]: hal::Queue::submit
     encoders: Vec<EncoderInFlight<A>>,
 
+    /// Like `encoders`, for the uploads submitted to the device's transfer
+    /// queue ahead of this submission.
+    ///
+    /// This submission waits for those uploads, so they are done once it is.
+    /// See [`Device::transfer_queue`].
+    ///
+    /// [`Device::transfer_queue`]: super::Device::transfer_queue
+    transfer_encoders: Vec<EncoderInFlight<A>>,
+
+    /// Timeline semaphores this submission waits for or signals.
+    ///
+    /// The fences backing them have to outlive the submission, even if the
+    /// user drops the semaphores right away.
+    timeline_semaphores: Vec<Arc<TimelineSemaphore<A>>>,
+
     /// List of queue "on_submitted_work_done" closures to be called once this
     /// submission has completed.
     work_done_closures: SmallVec<[SubmittedWorkDoneClosure; 1]>,
@@ -282,6 +323,15 @@ pub(crate) struct LifetimeTracker<A: HalApi> {
     /// device.lose or by the UserCallbacks returned from maintain when the device
     /// has been destroyed and its queues are empty.
     pub device_lost_closure: Option<DeviceLostClosure>,
+
+    /// Callbacks held back until the device is next polled.
+    ///
+    /// Only used by devices created with
+    /// [`wgt::DeviceFlags::DETERMINISTIC_CALLBACKS`]. See
+    /// [`Device::defer_closures`].
+    ///
+    /// [`Device::defer_closures`]: super::Device::defer_closures
+    pub deferred_closures: UserClosures,
 }
 
 impl<A: HalApi> LifetimeTracker<A> {
@@ -295,6 +345,7 @@ impl<A: HalApi> LifetimeTracker<A> {
             ready_to_map: Vec::new(),
             work_done_closures: SmallVec::new(),
             device_lost_closure: None,
+            deferred_closures: UserClosures::default(),
         }
     }
 
@@ -307,8 +358,11 @@ impl<A: HalApi> LifetimeTracker<A> {
     pub fn track_submission(
         &mut self,
         index: SubmissionIndex,
+        queue: Option<usize>,
         temp_resources: impl Iterator<Item = TempResource<A>>,
         encoders: Vec<EncoderInFlight<A>>,
+        transfer_encoders: Vec<EncoderInFlight<A>>,
+        timeline_semaphores: Vec<Arc<TimelineSemaphore<A>>>,
     ) {
         let mut last_resources = ResourceMaps::new();
         for res in temp_resources {
@@ -343,13 +397,27 @@ impl<A: HalApi> LifetimeTracker<A> {
 
         self.active.push(ActiveSubmission {
             index,
+            queue,
             last_resources,
             mapped: Vec::new(),
             encoders,
+            transfer_encoders,
+            timeline_semaphores,
             work_done_closures: SmallVec::new(),
         });
     }
 
+    /// Return the queue the submission `index` was made to, if it is still in flight.
+    ///
+    /// The outer `None` means the submission has completed already; the inner
+    /// one stands for the device's primary queue.
+    pub fn submission_queue(&self, index: SubmissionIndex) -> Option<Option<usize>> {
+        self.active
+            .binary_search_by_key(&index, |a| a.index)
+            .ok()
+            .map(|i| self.active[i].queue)
+    }
+
     pub fn post_submit(&mut self) {
         for v in self.future_suspected_buffers.drain(..) {
             self.suspected_resources
@@ -390,6 +458,8 @@ impl<A: HalApi> LifetimeTracker<A> {
         &mut self,
         last_done: SubmissionIndex,
         command_allocator: &crate::command::CommandAllocator<A>,
+        transfer_command_allocator: Option<&crate::command::CommandAllocator<A>>,
+        raw: &A::Device,
     ) -> SmallVec<[SubmittedWorkDoneClosure; 1]> {
         profiling::scope!("triage_submissions");
 
@@ -409,11 +479,106 @@ impl<A: HalApi> LifetimeTracker<A> {
                 let raw = unsafe { encoder.land() };
                 command_allocator.release_encoder(raw);
             }
+            for encoder in a.transfer_encoders {
+                let raw = unsafe { encoder.land() };
+                transfer_command_allocator.unwrap().release_encoder(raw);
+            }
             work_done_closures.extend(a.work_done_closures);
         }
+        if done_count != 0 {
+            Self::recycle_bind_groups(raw);
+        }
         work_done_closures
     }
 
+    /// Let the backend reuse the descriptors of bind groups dropped along with
+    /// the [`last_resources`] of completed submissions.
+    ///
+    /// [`last_resources`]: ActiveSubmission::last_resources
+    fn recycle_bind_groups(raw: &A::Device) {
+        profiling::scope!("recycle_bind_groups");
+        unsafe { raw.recycle_bind_groups() };
+    }
+
+    /// Sort out the consequences of completed submissions, one submission at a
+    /// time.
+    ///
+    /// This is the equivalent of [`triage_submissions`] followed by
+    /// [`handle_mapping`], used by devices created with
+    /// [`wgt::DeviceFlags::DETERMINISTIC_CALLBACKS`]. Rather than returning all
+    /// mapping notifications ahead of all work done closures, the returned list
+    /// first holds the mappings of buffers that are not used by any submission
+    /// still in flight, then, for each submission up through `last_done` in
+    /// order, the mappings of the buffers it used followed by its work done
+    /// closures.
+    ///
+    /// [`triage_mapped`] must be called first, so that buffers waiting on a
+    /// completed submission are attributed to it.
+    ///
+    /// [`triage_submissions`]: LifetimeTracker::triage_submissions
+    /// [`handle_mapping`]: LifetimeTracker::handle_mapping
+    /// [`triage_mapped`]: LifetimeTracker::triage_mapped
+    #[must_use]
+    pub(crate) fn triage_submissions_in_order(
+        &mut self,
+        last_done: SubmissionIndex,
+        command_allocator: &crate::command::CommandAllocator<A>,
+        transfer_command_allocator: Option<&crate::command::CommandAllocator<A>>,
+        raw: &A::Device,
+        trackers: &Mutex<Tracker<A>>,
+        snatch_guard: &SnatchGuard,
+        purge: Option<&MappingPurge<'_, A>>,
+    ) -> Vec<OrderedClosure> {
+        profiling::scope!("triage_submissions_in_order");
+
+        let done_count = self
+            .active
+            .iter()
+            .position(|a| a.index > last_done)
+            .unwrap_or(self.active.len());
+
+        let mut closures: Vec<_> = self
+            .handle_mapping(raw, trackers, snatch_guard, purge)
+            .into_iter()
+            .map(OrderedClosure::Mapping)
+            .collect();
+        closures.extend(
+            self.work_done_closures
+                .drain(..)
+                .map(OrderedClosure::SubmittedWorkDone),
+        );
+
+        let done: Vec<_> = self.active.drain(..done_count).collect();
+        for a in done {
+            log::debug!("Active submission {} is done", a.index);
+            self.ready_to_map.extend(a.mapped);
+            for encoder in a.encoders {
+                let raw_encoder = unsafe { encoder.land() };
+                command_allocator.release_encoder(raw_encoder);
+            }
+            for encoder in a.transfer_encoders {
+                let raw_encoder = unsafe { encoder.land() };
+                transfer_command_allocator
+                    .unwrap()
+                    .release_encoder(raw_encoder);
+            }
+            closures.extend(
+                self.handle_mapping(raw, trackers, snatch_guard, purge)
+                    .into_iter()
+                    .map(OrderedClosure::Mapping),
+            );
+            closures.extend(
+                a.work_done_closures
+                    .into_iter()
+                    .map(OrderedClosure::SubmittedWorkDone),
+            );
+        }
+        if done_count != 0 {
+            Self::recycle_bind_groups(raw);
+        }
+        closures
+    }
+
     pub fn schedule_resource_destruction(
         &mut self,
         temp_resource: TempResource<A>,
@@ -469,10 +634,10 @@ impl<A: HalApi> LifetimeTracker<A> {
 
 impl<A: HalApi> LifetimeTracker<A> {
     fn triage_resources<R>(
-        resources_map: &mut FastHashMap<TrackerIndex, Arc<R>>,
+        resources_map: &mut TrackerIndexMap<Arc<R>>,
         active: &mut [ActiveSubmission<A>],
         trackers: &mut impl ResourceTracker,
-        get_resource_map: impl Fn(&mut ResourceMaps<A>) -> &mut FastHashMap<TrackerIndex, Arc<R>>,
+        get_resource_map: impl Fn(&mut ResourceMaps<A>) -> &mut TrackerIndexMap<Arc<R>>,
     ) -> Vec<Arc<R>>
     where
         R: Resource,
@@ -829,6 +994,9 @@ impl<A: HalApi> LifetimeTracker<A> {
     ///
     /// Return a list of mapping notifications to send.
     ///
+    /// If mapping a buffer runs out of memory and `purge` is given, the pools
+    /// of `purge` are emptied, and the mapping is tried once more.
+    ///
     /// See the documentation for [`LifetimeTracker`] for details.
     #[must_use]
     pub(crate) fn handle_mapping(
@@ -836,12 +1004,15 @@ impl<A: HalApi> LifetimeTracker<A> {
         raw: &A::Device,
         trackers: &Mutex<Tracker<A>>,
         snatch_guard: &SnatchGuard,
+        purge: Option<&MappingPurge<'_, A>>,
     ) -> Vec<super::BufferMapPendingClosure> {
         if self.ready_to_map.is_empty() {
             return Vec::new();
         }
         let mut pending_callbacks: Vec<super::BufferMapPendingClosure> =
             Vec::with_capacity(self.ready_to_map.len());
+        // The pools are only emptied once, however many mappings fail.
+        let mut purged = false;
 
         for buffer in self.ready_to_map.drain(..) {
             let tracker_index = buffer.info.tracker_index();
@@ -875,21 +1046,34 @@ impl<A: HalApi> LifetimeTracker<A> {
                 let status = if pending_mapping.range.start != pending_mapping.range.end {
                     log::debug!("Buffer {tracker_index:?} map state -> Active");
                     let host = pending_mapping.op.host;
+                    let offset = pending_mapping.range.start;
                     let size = pending_mapping.range.end - pending_mapping.range.start;
-                    match super::map_buffer(
-                        raw,
-                        &buffer,
-                        pending_mapping.range.start,
-                        size,
-                        host,
-                        snatch_guard,
-                    ) {
+                    let map = || super::map_buffer(raw, &buffer, offset, size, host, snatch_guard);
+                    let mut result = map();
+                    let out_of_memory = matches!(
+                        result,
+                        Err(resource::BufferAccessError::Device(
+                            DeviceError::OutOfMemory
+                        ))
+                    );
+                    if let (true, Some(purge)) = (out_of_memory, purge) {
+                        if !purged {
+                            log::warn!(
+                                "Out of memory mapping buffer {tracker_index:?}, \
+                                 retrying after freeing pooled memory"
+                            );
+                            purge.purge(raw);
+                            purged = true;
+                        }
+                        result = map();
+                    }
+                    match result {
                         Ok(ptr) => {
                             *buffer.map_state.lock() = resource::BufferMapState::Active {
                                 ptr,
-                                range: pending_mapping.range.start
-                                    ..pending_mapping.range.start + size,
+                                range: offset..offset + size,
                                 host,
+                                checked_views: false,
                             };
                             Ok(())
                         }
@@ -903,6 +1087,7 @@ impl<A: HalApi> LifetimeTracker<A> {
                         ptr: std::ptr::NonNull::dangling(),
                         range: pending_mapping.range,
                         host: pending_mapping.op.host,
+                        checked_views: false,
                     };
                     Ok(())
                 };
@@ -19,7 +19,18 @@ use std::{iter, num::NonZeroU32, ptr};
 
 pub mod any_device;
 pub(crate) mod bgl;
+mod compile;
+#[cfg(all(dx12, feature = "d3d11on12"))]
+mod d3d11on12;
+#[cfg(any(all(vulkan, unix), dx12))]
+mod external_memory;
+#[cfg(any(all(vulkan, unix), dx12))]
+mod external_semaphore;
+#[cfg(all(gles, unix, not(target_arch = "wasm32")))]
+mod gl_interop;
 pub mod global;
+#[cfg(metal)]
+mod iosurface;
 mod life;
 pub mod queue;
 pub mod resource;
@@ -38,6 +49,7 @@ const IMPLICIT_BIND_GROUP_LAYOUT_ERROR_LABEL: &str = "Implicit BindGroupLayout i
 const ENTRYPOINT_FAILURE_ERROR: &str = "The given EntryPoint is Invalid";
 
 pub type DeviceDescriptor<'a> = wgt::DeviceDescriptor<Label<'a>>;
+pub type QueueDescriptor<'a> = wgt::QueueDescriptor<Label<'a>>;
 
 #[repr(C)]
 #[derive(Clone, Copy, Debug, Eq, PartialEq)]
@@ -166,10 +178,22 @@ impl RenderPassContext {
 
 pub type BufferMapPendingClosure = (BufferMapOperation, BufferAccessResult);
 
+/// A user callback whose position relative to other callbacks matters.
+///
+/// Devices created with [`wgt::DeviceFlags::DETERMINISTIC_CALLBACKS`] collect
+/// their callbacks as a single list of these, rather than separate lists of
+/// mappings and work done closures, so that they can be fired in exactly the
+/// order in which their submissions completed.
+pub enum OrderedClosure {
+    Mapping(BufferMapPendingClosure),
+    SubmittedWorkDone(queue::SubmittedWorkDoneClosure),
+}
+
 #[derive(Default)]
 pub struct UserClosures {
     pub mappings: Vec<BufferMapPendingClosure>,
     pub submissions: SmallVec<[queue::SubmittedWorkDoneClosure; 1]>,
+    pub ordered: Vec<OrderedClosure>,
     pub device_lost_invocations: SmallVec<[DeviceLostInvocation; 1]>,
 }
 
@@ -177,11 +201,26 @@ impl UserClosures {
     fn extend(&mut self, other: Self) {
         self.mappings.extend(other.mappings);
         self.submissions.extend(other.submissions);
+        self.ordered.extend(other.ordered);
         self.device_lost_invocations
             .extend(other.device_lost_invocations);
     }
 
-    fn fire(self) {
+    /// Move all mappings and work done closures into `self.ordered`, keeping
+    /// the relative order in which [`UserClosures::fire`] would call them.
+    fn into_ordered(mut self) -> Self {
+        let mappings = self.mappings.drain(..).map(OrderedClosure::Mapping);
+        let submissions = self
+            .submissions
+            .drain(..)
+            .map(OrderedClosure::SubmittedWorkDone);
+        let mut ordered: Vec<_> = mappings.chain(submissions).collect();
+        ordered.append(&mut self.ordered);
+        self.ordered = ordered;
+        self
+    }
+
+    pub(crate) fn fire(self) {
         // Note: this logic is specifically moved out of `handle_mapping()` in order to
         // have nothing locked by the time we execute users callback code.
 
@@ -195,6 +234,16 @@ impl UserClosures {
         for closure in self.submissions {
             closure.call();
         }
+        for closure in self.ordered {
+            match closure {
+                OrderedClosure::Mapping((mut operation, status)) => {
+                    if let Some(callback) = operation.callback.take() {
+                        callback.call(status);
+                    }
+                }
+                OrderedClosure::SubmittedWorkDone(closure) => closure.call(),
+            }
+        }
         for invocation in self.device_lost_invocations {
             invocation
                 .closure
@@ -7,7 +7,9 @@ use crate::{
         ClearError, CommandAllocator, CommandBuffer, CopySide, ImageCopyTexture, TransferError,
     },
     conv,
-    device::{life::ResourceMaps, DeviceError, WaitIdleError},
+    device::{
+        life::ResourceMaps, DeviceError, HostMap, MissingFeatures, UserClosures, WaitIdleError,
+    },
     get_lowest_common_denom,
     global::Global,
     hal_api::HalApi,
@@ -17,9 +19,11 @@ use crate::{
     lock::{rank, Mutex},
     resource::{
         Buffer, BufferAccessError, BufferMapState, DestroyedBuffer, DestroyedTexture, Resource,
-        ResourceInfo, ResourceType, StagingBuffer, Texture, TextureInner,
+        ResourceInfo, ResourceType, StagingBuffer, Texture, TextureInner, TimelineSemaphoreError,
     },
-    resource_log, track, FastHashMap, SubmissionIndex,
+    resource_log,
+    track::{self, TextureSelector},
+    FastHashMap, FastHashSet, LabelHelpers as _, SubmissionIndex,
 };
 
 use hal::{CommandEncoder as _, Device as _, Queue as _};
@@ -27,7 +31,10 @@ use smallvec::SmallVec;
 
 use std::{
     iter, mem, ptr,
-    sync::{atomic::Ordering, Arc},
+    sync::{
+        atomic::{AtomicBool, AtomicU64, Ordering},
+        Arc,
+    },
 };
 use thiserror::Error;
 
@@ -36,6 +43,11 @@ use super::Device;
 pub struct Queue<A: HalApi> {
     pub(crate) device: Option<Arc<Device<A>>>,
     pub(crate) raw: Option<A::Queue>,
+    /// Index into [`Device::additional_queues`], or `None` for the primary queue.
+    pub(crate) additional_index: Option<usize>,
+    /// Whether submissions are deferred into [`Device::submit_batch`], see
+    /// [`Global::queue_set_submit_batching`].
+    pub(crate) batch_submits: AtomicBool,
     pub(crate) info: ResourceInfo<Queue<A>>,
 }
 
@@ -56,10 +68,26 @@ impl<A: HalApi> Resource for Queue<A> {
 impl<A: HalApi> Drop for Queue<A> {
     fn drop(&mut self) {
         let queue = self.raw.take().unwrap();
-        self.device.as_ref().unwrap().release_queue(queue);
+        let device = self.device.as_ref().unwrap();
+        match self.additional_index {
+            Some(index) => device.release_additional_queue(index, queue),
+            None => device.release_queue(queue),
+        }
     }
 }
 
+/// Submissions deferred by a queue in batching mode, to be made as one
+/// submission when the batch is flushed.
+///
+/// See [`Global::queue_set_submit_batching`].
+#[derive(Debug)]
+pub(crate) struct SubmitBatch {
+    queue_id: QueueId,
+    command_buffer_ids: Vec<id::CommandBufferId>,
+    waits: Vec<(id::TimelineSemaphoreId, u64)>,
+    signals: Vec<(id::TimelineSemaphoreId, u64)>,
+}
+
 /// Number of command buffers that we generate from the same pool
 /// for the write_xxx commands, before the pool is recycled.
 ///
@@ -128,6 +156,27 @@ impl SubmittedWorkDoneClosure {
 pub struct WrappedSubmissionIndex {
     pub queue_id: QueueId,
     pub index: SubmissionIndex,
+    /// Process-wide token of the submission, see [`next_submission_token`].
+    ///
+    /// Unlike `index`, which submissions in the same batch share, every
+    /// submission gets its own token.
+    pub token: u64,
+}
+
+/// Hand out the token of a submission, which is greater than the tokens of
+/// all the submissions made before it by any device of the process.
+///
+/// Must be called under the lock that orders the submission on its device,
+/// along with assigning its index, so that tokens increase in submission order.
+fn next_submission_token() -> u64 {
+    static LAST_TOKEN: AtomicU64 = AtomicU64::new(0);
+
+    let last = LAST_TOKEN
+        .fetch_update(Ordering::Relaxed, Ordering::Relaxed, |last| {
+            last.checked_add(1)
+        })
+        .expect("Ran out of submission tokens");
+    last + 1
 }
 
 /// A texture or buffer to be freed soon.
@@ -208,6 +257,24 @@ pub(crate) struct PendingWrites<A: HalApi> {
     pub dst_buffers: FastHashMap<id::BufferId, Arc<Buffer<A>>>,
     pub dst_textures: FastHashMap<id::TextureId, Arc<Texture<A>>>,
 
+    /// The subsets of `dst_buffers` and `dst_textures` written by
+    /// `command_encoder`, rather than by `transfer`.
+    ///
+    /// The uploads of `transfer` run first, so further writes to these have
+    /// to stay on `command_encoder` to keep their order.
+    encoder_dst_buffers: FastHashSet<id::BufferId>,
+    encoder_dst_textures: FastHashSet<id::TextureId>,
+
+    /// Uploads recorded for the device's transfer queue, if it has one.
+    pub transfer: Option<TransferWrites<A>>,
+
+    /// Indices of the submissions that last used the resources written here,
+    /// before they were written.
+    ///
+    /// If any of those submissions went to another queue, the submission
+    /// carrying these writes has to wait for it. See [`Device::additional_queues`].
+    pub dependencies: FastHashSet<SubmissionIndex>,
+
     /// All command buffers allocated from `command_encoder`.
     pub executing_command_buffers: Vec<A::CommandBuffer>,
 }
@@ -220,6 +287,10 @@ impl<A: HalApi> PendingWrites<A> {
             temp_resources: Vec::new(),
             dst_buffers: FastHashMap::default(),
             dst_textures: FastHashMap::default(),
+            encoder_dst_buffers: FastHashSet::default(),
+            encoder_dst_textures: FastHashSet::default(),
+            transfer: None,
+            dependencies: FastHashSet::default(),
             executing_command_buffers: Vec::new(),
         }
     }
@@ -233,10 +304,59 @@ impl<A: HalApi> PendingWrites<A> {
                 .reset_all(self.executing_command_buffers.into_iter());
             device.destroy_command_encoder(self.command_encoder);
         }
+        if let Some(transfer) = self.transfer.take() {
+            transfer.dispose(device);
+        }
 
         self.temp_resources.clear();
     }
 
+    /// Record that `command_encoder` writes to `buffer`.
+    pub fn insert_buffer(&mut self, id: id::BufferId, buffer: Arc<Buffer<A>>) {
+        self.encoder_dst_buffers.insert(id);
+        self.dst_buffers.insert(id, buffer);
+    }
+
+    /// Record that `command_encoder` writes to `texture`.
+    pub fn insert_texture(&mut self, id: id::TextureId, texture: Arc<Texture<A>>) {
+        self.encoder_dst_textures.insert(id);
+        self.dst_textures.insert(id, texture);
+    }
+
+    /// Return true if an upload to the buffer `id` can go to `transfer`.
+    ///
+    /// Buffers created from raw handles may not be usable on the transfer
+    /// queue, so their uploads stay on `command_encoder`.
+    fn can_transfer_buffer(&self, id: id::BufferId, buffer: &Buffer<A>) -> bool {
+        self.transfer.is_some() && !buffer.imported && !self.encoder_dst_buffers.contains(&id)
+    }
+
+    /// Return true if an upload to the texture `id` can go to `transfer`.
+    ///
+    /// Textures created from raw handles may not be usable on the transfer
+    /// queue either.
+    fn can_transfer_texture(&self, id: id::TextureId, texture: &Texture<A>) -> bool {
+        self.transfer.is_some() && !texture.imported && !self.encoder_dst_textures.contains(&id)
+    }
+
+    /// Return the encoder to record an upload into, `transfer` if
+    /// `on_transfer_queue` is true, for a resource last used by the
+    /// submission `last_submission_index`.
+    fn upload_encoder(
+        &mut self,
+        on_transfer_queue: bool,
+        last_submission_index: SubmissionIndex,
+    ) -> &mut A::CommandEncoder {
+        if on_transfer_queue {
+            let transfer = self.transfer.as_mut().unwrap();
+            transfer.depend_on(last_submission_index);
+            transfer.activate()
+        } else {
+            self.depend_on(last_submission_index);
+            self.activate()
+        }
+    }
+
     pub fn consume_temp(&mut self, resource: TempResource<A>) {
         self.temp_resources.push(resource);
     }
@@ -246,9 +366,18 @@ impl<A: HalApi> PendingWrites<A> {
             .push(TempResource::StagingBuffer(buffer));
     }
 
+    /// Record that a resource last used by the submission `index` is written here.
+    pub fn depend_on(&mut self, index: SubmissionIndex) {
+        if index != 0 {
+            self.dependencies.insert(index);
+        }
+    }
+
     fn pre_submit(&mut self) -> Result<Option<&A::CommandBuffer>, DeviceError> {
         self.dst_buffers.clear();
         self.dst_textures.clear();
+        self.encoder_dst_buffers.clear();
+        self.encoder_dst_textures.clear();
         if self.is_recording {
             let cmd_buf = unsafe { self.command_encoder.end_encoding()? };
             self.is_recording = false;
@@ -297,6 +426,124 @@ impl<A: HalApi> PendingWrites<A> {
             }
             self.is_recording = false;
         }
+        if let Some(ref mut transfer) = self.transfer {
+            transfer.deactivate();
+        }
+    }
+}
+
+/// The part of [`PendingWrites`] recorded for the device's transfer queue.
+///
+/// On adapters with a dedicated copy queue, the staging copies of
+/// `queue_write_buffer` and `queue_write_texture` are recorded here, so that
+/// large uploads run on the copy engine instead of stalling the queue they
+/// are made on. Uploads that have to be ordered after other pending writes
+/// stay in `PendingWrites::command_encoder`.
+///
+/// The commands are submitted to [`Device::transfer_queue`] right before the
+/// submission they belong to, which waits for them on the GPU. That
+/// submission completing implies the uploads are done too, so the staging
+/// buffers are freed with it, and the encoders are tracked along with it.
+///
+/// [`Device::transfer_queue`]: super::Device::transfer_queue
+#[derive(Debug)]
+pub(crate) struct TransferWrites<A: HalApi> {
+    pub command_encoder: A::CommandEncoder,
+    pub is_recording: bool,
+
+    /// Signaled with the index of the submission the uploads belong to.
+    pub fence: A::Fence,
+
+    /// Indices of the submissions that last used the resources written here,
+    /// before they were written.
+    ///
+    /// The transfer queue has to wait for those still in flight, whichever
+    /// queue they were made to.
+    pub dependencies: FastHashSet<SubmissionIndex>,
+
+    /// All command buffers allocated from `command_encoder`.
+    pub executing_command_buffers: Vec<A::CommandBuffer>,
+}
+
+impl<A: HalApi> TransferWrites<A> {
+    pub fn new(command_encoder: A::CommandEncoder, fence: A::Fence) -> Self {
+        Self {
+            command_encoder,
+            is_recording: false,
+            fence,
+            dependencies: FastHashSet::default(),
+            executing_command_buffers: Vec::new(),
+        }
+    }
+
+    fn dispose(mut self, device: &A::Device) {
+        unsafe {
+            if self.is_recording {
+                self.command_encoder.discard_encoding();
+            }
+            self.command_encoder
+                .reset_all(self.executing_command_buffers.into_iter());
+            device.destroy_command_encoder(self.command_encoder);
+            device.destroy_fence(self.fence);
+        }
+    }
+
+    /// Record that a resource last used by the submission `index` is written here.
+    fn depend_on(&mut self, index: SubmissionIndex) {
+        if index != 0 {
+            self.dependencies.insert(index);
+        }
+    }
+
+    /// Finish recording, returning true if there is a command buffer to
+    /// submit, the last of `executing_command_buffers`.
+    fn pre_submit(&mut self) -> Result<bool, DeviceError> {
+        if self.is_recording {
+            let cmd_buf = unsafe { self.command_encoder.end_encoding()? };
+            self.is_recording = false;
+            self.executing_command_buffers.push(cmd_buf);
+            return Ok(true);
+        }
+        Ok(false)
+    }
+
+    #[must_use]
+    fn post_submit(
+        &mut self,
+        command_allocator: &CommandAllocator<A>,
+        device: &A::Device,
+        queue: &A::Queue,
+    ) -> Option<EncoderInFlight<A>> {
+        if self.executing_command_buffers.len() >= WRITE_COMMAND_BUFFERS_PER_POOL {
+            let new_encoder = command_allocator.acquire_encoder(device, queue).unwrap();
+            Some(EncoderInFlight {
+                raw: mem::replace(&mut self.command_encoder, new_encoder),
+                cmd_buffers: mem::take(&mut self.executing_command_buffers),
+            })
+        } else {
+            None
+        }
+    }
+
+    fn activate(&mut self) -> &mut A::CommandEncoder {
+        if !self.is_recording {
+            unsafe {
+                self.command_encoder
+                    .begin_encoding(Some("(wgpu internal) TransferWrites"))
+                    .unwrap();
+            }
+            self.is_recording = true;
+        }
+        &mut self.command_encoder
+    }
+
+    fn deactivate(&mut self) {
+        if self.is_recording {
+            unsafe {
+                self.command_encoder.discard_encoding();
+            }
+            self.is_recording = false;
+        }
     }
 }
 
@@ -349,6 +596,15 @@ impl<A: HalApi> StagingBuffer<A> {
 #[error("Queue is invalid")]
 pub struct InvalidQueue;
 
+#[derive(Clone, Debug, Error)]
+#[non_exhaustive]
+pub enum CreateQueueError {
+    #[error(transparent)]
+    Device(#[from] DeviceError),
+    #[error("All {0} additional queues of the device are already in use")]
+    NoFreeQueue(u32),
+}
+
 #[derive(Clone, Debug, Error)]
 #[non_exhaustive]
 pub enum QueueWriteError {
@@ -378,6 +634,8 @@ pub enum QueueSubmitError {
     DestroyedBuffer(id::BufferId),
     #[error("Texture {0:?} is destroyed")]
     DestroyedTexture(id::TextureId),
+    #[error("Texture {0:?} is expired")]
+    ExpiredTexture(id::TextureId),
     #[error(transparent)]
     Unmap(#[from] BufferAccessError),
     #[error("Buffer {0:?} is still mapped")]
@@ -386,13 +644,207 @@ pub enum QueueSubmitError {
     SurfaceOutputDropped,
     #[error("Surface was unconfigured before the command buffer got submitted")]
     SurfaceUnconfigured,
+    #[error(transparent)]
+    TimelineSemaphore(#[from] TimelineSemaphoreError),
+    #[error("Resources placed at overlapping ranges of MemoryHeap {0:?} are used by the same command buffer")]
+    OverlappingPlacedResources(id::MemoryHeapId),
     #[error("GPU got stuck :(")]
     StuckGpu,
 }
 
+/// Let the placed buffers and textures used by a command buffer take their
+/// memory over from the overlapping resources used before, see
+/// [`MemoryHeap::activate`](crate::resource::MemoryHeap::activate).
+///
+/// If any of them does, an aliasing barrier is recorded into `encoder`, and
+/// they are initialized again before the command buffer runs.
+fn activate_placed_resources<A: HalApi>(
+    encoder: &mut A::CommandEncoder,
+    trackers: &mut track::Tracker<A>,
+    buffers: &[Arc<Buffer<A>>],
+    textures: &[Arc<Texture<A>>],
+) -> Result<(), QueueSubmitError> {
+    let placements = buffers
+        .iter()
+        .filter_map(|buffer| buffer.placement.as_ref())
+        .chain(
+            textures
+                .iter()
+                .filter_map(|texture| texture.placement.as_ref()),
+        )
+        .collect::<Vec<_>>();
+    for (i, placement) in placements.iter().enumerate() {
+        if placements[i + 1..]
+            .iter()
+            .any(|other| placement.overlaps(other))
+        {
+            return Err(QueueSubmitError::OverlappingPlacedResources(
+                placement.heap.info.id(),
+            ));
+        }
+    }
+
+    let mut activated = false;
+    for buffer in buffers {
+        activated |= buffer.activate_placement();
+    }
+    for texture in textures {
+        if texture.activate_placement() {
+            // The memory is taken over from undefined contents.
+            trackers.textures.remove(texture.info.tracker_index());
+            trackers
+                .textures
+                .insert_single(texture.clone(), hal::TextureUses::UNINITIALIZED);
+            activated = true;
+        }
+    }
+    if activated {
+        unsafe { encoder.aliasing_barrier() };
+    }
+    Ok(())
+}
+
+#[derive(Clone, Debug, Error)]
+#[non_exhaustive]
+pub enum QueueBindSparseError {
+    #[error(transparent)]
+    Queue(#[from] DeviceError),
+    #[error(transparent)]
+    Submit(#[from] QueueSubmitError),
+    #[error(transparent)]
+    MissingFeatures(#[from] MissingFeatures),
+    #[error("Sparse bindings can only be made on the primary queue of a device")]
+    AdditionalQueue,
+    #[error("Buffer {0:?} is invalid")]
+    InvalidBuffer(id::BufferId),
+    #[error("Buffer {0:?} is destroyed")]
+    DestroyedBuffer(id::BufferId),
+    #[error("Buffer {0:?} was not created with `Device::create_sparse_buffer`")]
+    NotSparse(id::BufferId),
+    #[error(
+        "Bind of {size} bytes at offset {offset} is not aligned to the sparse page size ({})",
+        wgt::SPARSE_BUFFER_PAGE_SIZE
+    )]
+    UnalignedBind {
+        offset: wgt::BufferAddress,
+        size: wgt::BufferAddress,
+    },
+    #[error("Bind of {size} bytes at offset {offset} overruns a buffer of {buffer_size} bytes")]
+    BindOverrun {
+        offset: wgt::BufferAddress,
+        size: wgt::BufferAddress,
+        buffer_size: wgt::BufferAddress,
+    },
+    #[error("Texture {0:?} is invalid")]
+    InvalidTexture(id::TextureId),
+    #[error("Texture {0:?} is destroyed")]
+    DestroyedTexture(id::TextureId),
+    #[error("Texture {0:?} was not created with `Device::create_sparse_texture`")]
+    NotSparseTexture(id::TextureId),
+    #[error(
+        "Bind of mip level {mip_level} and array layer {array_layer} is outside of the texture"
+    )]
+    InvalidSubresource { mip_level: u32, array_layer: u32 },
+    #[error(
+        "Bind of {size:?} at {origin:?} overruns mip level {mip_level} of size {level_size:?}"
+    )]
+    TextureBindOverrun {
+        mip_level: u32,
+        origin: wgt::Origin3d,
+        size: wgt::Extent3d,
+        level_size: wgt::Extent3d,
+    },
+    #[error("Bind of {size:?} at {origin:?} is not aligned to the tile size {tile_size:?}")]
+    UnalignedTextureBind {
+        origin: wgt::Origin3d,
+        size: wgt::Extent3d,
+        tile_size: wgt::Extent3d,
+    },
+    #[error("Bind of mip level {0} in the mip tail doesn't cover the whole level")]
+    PartialMipTail(u32),
+}
+
+/// A validated write of linear data into a texture, with the layout of its
+/// staging copy.
+struct TextureWrite<A: HalApi> {
+    dst: Arc<Texture<A>>,
+    size: wgt::Extent3d,
+    hal_copy_size: hal::CopyExtent,
+    array_layer_count: u32,
+    selector: TextureSelector,
+    dst_base: hal::TextureCopyBase,
+    block_size: u32,
+    width_blocks: u32,
+    height_blocks: u32,
+    /// Rows of the staging copy are aligned for buffer to texture copies.
+    stage_bytes_per_row: u32,
+}
+
+impl<A: HalApi> TextureWrite<A> {
+    fn stage_size(&self, block_rows_per_image: u32) -> wgt::BufferAddress {
+        let block_rows_in_copy =
+            (self.size.depth_or_array_layers - 1) * block_rows_per_image + self.height_blocks;
+        self.stage_bytes_per_row as u64 * block_rows_in_copy as u64
+    }
+}
+
 //TODO: move out common parts of write_xxx.
 
 impl Global {
+    /// Create one of the additional queues of `device_id`.
+    ///
+    /// The number of queues available is set by
+    /// [`wgt::Limits::max_additional_queues`] when requesting the device.
+    /// Dropping the queue makes it available again.
+    pub fn device_create_queue<A: HalApi>(
+        &self,
+        device_id: DeviceId,
+        desc: &super::QueueDescriptor,
+        id_in: Option<QueueId>,
+    ) -> (QueueId, Option<CreateQueueError>) {
+        profiling::scope!("Device::create_queue");
+
+        let hub = A::hub(self);
+        let fid = hub.queues.prepare(id_in);
+
+        let error = loop {
+            let device = match hub.devices.get(device_id) {
+                Ok(device) => device,
+                Err(_) => break DeviceError::Invalid.into(),
+            };
+            if !device.is_valid() {
+                break DeviceError::Lost.into();
+            }
+
+            let (additional_index, raw) = {
+                let mut additional_queues = device.additional_queues.lock();
+                match additional_queues
+                    .iter_mut()
+                    .enumerate()
+                    .find_map(|(i, additional)| Some((i, additional.raw.take()?)))
+                {
+                    Some(free) => free,
+                    None => break CreateQueueError::NoFreeQueue(additional_queues.len() as u32),
+                }
+            };
+
+            let queue = Queue {
+                device: Some(device.clone()),
+                raw: Some(raw),
+                additional_index: Some(additional_index),
+                batch_submits: AtomicBool::new(false),
+                info: ResourceInfo::new(desc.label.borrow_or_default(), None),
+            };
+            let (id, _) = fid.assign(Arc::new(queue));
+            api_log!("Device::create_queue -> {id:?}");
+
+            return (id, None);
+        };
+
+        let id = fid.assign_error(desc.label.borrow_or_default());
+        (id, Some(error))
+    }
+
     pub fn queue_write_buffer<A: HalApi>(
         &self,
         queue_id: QueueId,
@@ -419,6 +871,8 @@ impl Global {
             .map_err(|_| DeviceError::InvalidQueueId)?;
 
         let device = queue.device.as_ref().unwrap();
+        // The batched submissions come before this write.
+        self.flush_submit_batch_or_log::<A>(device);
 
         {
             let queue_device_id = device.as_info().id();
@@ -433,7 +887,7 @@ impl Global {
         let data_size = data.len() as wgt::BufferAddress;
 
         #[cfg(feature = "trace")]
-        if let Some(ref mut trace) = *device.trace.lock() {
+        if let Some(trace) = device.trace.lock().as_mut().filter(|t| !t.shaders_only) {
             let data_path = trace.make_binary("bin", data);
             trace.add(Action::WriteBuffer {
                 id: buffer_id,
@@ -522,6 +976,8 @@ impl Global {
             .map_err(|_| DeviceError::InvalidQueueId)?;
 
         let device = queue.device.as_ref().unwrap();
+        // The batched submissions come before this write.
+        self.flush_submit_batch_or_log::<A>(device);
 
         let staging_buffer = hub.staging_buffers.unregister(staging_buffer_id);
         if staging_buffer.is_none() {
@@ -639,9 +1095,6 @@ impl Global {
         let src_buffer_size = staging_buffer.size;
         self.queue_validate_write_buffer_impl(&dst, buffer_id, buffer_offset, src_buffer_size)?;
 
-        dst.info
-            .use_at(device.active_submission_index.load(Ordering::Relaxed) + 1);
-
         let region = wgt::BufferSize::new(src_buffer_size).map(|size| hal::BufferCopy {
             src_offset: 0,
             dst_offset: buffer_offset,
@@ -653,8 +1106,18 @@ impl Global {
             usage: hal::BufferUses::MAP_WRITE..hal::BufferUses::COPY_SRC,
         })
         .chain(transition.map(|pending| pending.into_hal(&dst, &snatch_guard)));
-        let encoder = pending_writes.activate();
+        let last_submission_index = dst.info.submission_index();
+        // Placed buffers take their memory over in queue order, which the
+        // transfer queue isn't part of.
+        let on_transfer_queue =
+            dst.placement.is_none() && pending_writes.can_transfer_buffer(buffer_id, &dst);
+        let encoder = pending_writes.upload_encoder(on_transfer_queue, last_submission_index);
+        dst.info
+            .use_at(device.active_submission_index.load(Ordering::Relaxed) + 1);
         unsafe {
+            if dst.activate_placement() {
+                encoder.aliasing_barrier();
+            }
             encoder.transition_buffers(barriers);
             encoder.copy_buffer_to_buffer(
                 inner_buffer.as_ref().unwrap(),
@@ -663,7 +1126,11 @@ impl Global {
             );
         }
         let dst = hub.buffers.get(buffer_id).unwrap();
-        pending_writes.dst_buffers.insert(buffer_id, dst.clone());
+        if on_transfer_queue {
+            pending_writes.dst_buffers.insert(buffer_id, dst.clone());
+        } else {
+            pending_writes.insert_buffer(buffer_id, dst.clone());
+        }
 
         // Ensure the overwritten bytes are marked as initialized so
         // they don't need to be nulled prior to mapping or binding.
@@ -695,9 +1162,11 @@ impl Global {
             .map_err(|_| DeviceError::InvalidQueueId)?;
 
         let device = queue.device.as_ref().unwrap();
+        // The batched submissions come before this write.
+        self.flush_submit_batch_or_log::<A>(device);
 
         #[cfg(feature = "trace")]
-        if let Some(ref mut trace) = *device.trace.lock() {
+        if let Some(trace) = device.trace.lock().as_mut().filter(|t| !t.shaders_only) {
             let data_path = trace.make_binary("bin", data);
             trace.add(Action::WriteTexture {
                 to: *destination,
@@ -712,45 +1181,13 @@ impl Global {
             return Ok(());
         }
 
-        let dst = hub
-            .textures
-            .get(destination.texture)
-            .map_err(|_| TransferError::InvalidTexture(destination.texture))?;
-
-        if dst.device.as_info().id().into_queue_id() != queue_id {
-            return Err(DeviceError::WrongDevice.into());
-        }
-
-        if !dst.desc.usage.contains(wgt::TextureUsages::COPY_DST) {
-            return Err(
-                TransferError::MissingCopyDstUsageFlag(None, Some(destination.texture)).into(),
-            );
-        }
-
-        // Note: Doing the copy range validation early is important because ensures that the
-        // dimensions are not going to cause overflow in other parts of the validation.
-        let (hal_copy_size, array_layer_count) =
-            validate_texture_copy_range(destination, &dst.desc, CopySide::Destination, size)?;
-
-        let (selector, dst_base) = extract_texture_selector(destination, size, &dst)?;
-
-        if !dst_base.aspect.is_one() {
-            return Err(TransferError::CopyAspectNotOne.into());
-        }
-
-        if !conv::is_valid_copy_dst_texture_format(dst.desc.format, destination.aspect) {
-            return Err(TransferError::CopyToForbiddenTextureFormat {
-                format: dst.desc.format,
-                aspect: destination.aspect,
-            }
-            .into());
-        }
+        let write = self.validate_texture_write(device, destination, size)?;
 
         // Note: `_source_bytes_per_array_layer` is ignored since we
         // have a staging copy, and it can have a different value.
         let (_, _source_bytes_per_array_layer) = validate_linear_texture_data(
             data_layout,
-            dst.desc.format,
+            write.dst.desc.format,
             destination.aspect,
             data.len() as wgt::BufferAddress,
             CopySide::Source,
@@ -758,100 +1195,21 @@ impl Global {
             false,
         )?;
 
-        if dst.desc.format.is_depth_stencil_format() {
-            device
-                .require_downlevel_flags(wgt::DownlevelFlags::DEPTH_TEXTURE_AND_BUFFER_COPIES)
-                .map_err(TransferError::from)?;
-        }
-
-        let (block_width, block_height) = dst.desc.format.block_dimensions();
-        let width_blocks = size.width / block_width;
-        let height_blocks = size.height / block_height;
-
         let block_rows_per_image = data_layout.rows_per_image.unwrap_or(
             // doesn't really matter because we need this only if we copy
             // more than one layer, and then we validate for this being not
             // None
-            height_blocks,
+            write.height_blocks,
         );
+        let stage_bytes_per_row = write.stage_bytes_per_row;
+        let stage_size = write.stage_size(block_rows_per_image);
 
-        let block_size = dst
-            .desc
-            .format
-            .block_copy_size(Some(destination.aspect))
-            .unwrap();
-        let bytes_per_row_alignment =
-            get_lowest_common_denom(device.alignments.buffer_copy_pitch.get() as u32, block_size);
-        let stage_bytes_per_row =
-            wgt::math::align_to(block_size * width_blocks, bytes_per_row_alignment);
-
-        let block_rows_in_copy =
-            (size.depth_or_array_layers - 1) * block_rows_per_image + height_blocks;
-        let stage_size = stage_bytes_per_row as u64 * block_rows_in_copy as u64;
+        let bytes_per_row = data_layout
+            .bytes_per_row
+            .unwrap_or(write.width_blocks * write.block_size);
 
         let mut pending_writes = device.pending_writes.lock();
         let pending_writes = pending_writes.as_mut().unwrap();
-        let encoder = pending_writes.activate();
-
-        // If the copy does not fully cover the layers, we need to initialize to
-        // zero *first* as we don't keep track of partial texture layer inits.
-        //
-        // Strictly speaking we only need to clear the areas of a layer
-        // untouched, but this would get increasingly messy.
-        let init_layer_range = if dst.desc.dimension == wgt::TextureDimension::D3 {
-            // volume textures don't have a layer range as array volumes aren't supported
-            0..1
-        } else {
-            destination.origin.z..destination.origin.z + size.depth_or_array_layers
-        };
-        let mut dst_initialization_status = dst.initialization_status.write();
-        if dst_initialization_status.mips[destination.mip_level as usize]
-            .check(init_layer_range.clone())
-            .is_some()
-        {
-            if has_copy_partial_init_tracker_coverage(size, destination.mip_level, &dst.desc) {
-                for layer_range in dst_initialization_status.mips[destination.mip_level as usize]
-                    .drain(init_layer_range)
-                    .collect::<Vec<std::ops::Range<u32>>>()
-                {
-                    let mut trackers = device.trackers.lock();
-                    crate::command::clear_texture(
-                        &dst,
-                        TextureInitRange {
-                            mip_range: destination.mip_level..(destination.mip_level + 1),
-                            layer_range,
-                        },
-                        encoder,
-                        &mut trackers.textures,
-                        &device.alignments,
-                        device.zero_buffer.as_ref().unwrap(),
-                        &device.snatchable_lock.read(),
-                    )
-                    .map_err(QueueWriteError::from)?;
-                }
-            } else {
-                dst_initialization_status.mips[destination.mip_level as usize]
-                    .drain(init_layer_range);
-            }
-        }
-
-        let snatch_guard = device.snatchable_lock.read();
-
-        // Re-get `dst` immutably here, so that the mutable borrow of the
-        // `texture_guard.get` above ends in time for the `clear_texture`
-        // call above. Since we've held `texture_guard` the whole time, we know
-        // the texture hasn't gone away in the mean time, so we can unwrap.
-        let dst = hub.textures.get(destination.texture).unwrap();
-        dst.info
-            .use_at(device.active_submission_index.load(Ordering::Relaxed) + 1);
-
-        let dst_raw = dst
-            .raw(&snatch_guard)
-            .ok_or(TransferError::InvalidTexture(destination.texture))?;
-
-        let bytes_per_row = data_layout
-            .bytes_per_row
-            .unwrap_or(width_blocks * block_size);
 
         // Platform validation requires that the staging buffer always be
         // freed, even if an error occurs. All paths from here must call
@@ -878,7 +1236,7 @@ impl Global {
             let copy_bytes_per_row = stage_bytes_per_row.min(bytes_per_row) as usize;
             for layer in 0..size.depth_or_array_layers {
                 let rows_offset = layer * block_rows_per_image;
-                for row in 0..height_blocks {
+                for row in 0..write.height_blocks {
                     unsafe {
                         ptr::copy_nonoverlapping(
                             data.as_ptr().offset(
@@ -900,8 +1258,322 @@ impl Global {
             return Err(e.into());
         }
 
-        let regions = (0..array_layer_count).map(|rel_array_layer| {
-            let mut texture_base = dst_base.clone();
+        let result = self.queue_write_staging_texture_impl(
+            device,
+            pending_writes,
+            &staging_buffer,
+            destination,
+            &write,
+            block_rows_per_image,
+        );
+
+        pending_writes.consume(staging_buffer);
+        result
+    }
+
+    /// Creates a staging buffer for a write of `size` texels into
+    /// `destination`, laid out the way the copy into the texture needs it.
+    ///
+    /// Returns the size of the data and its layout alongside the mapping.
+    /// The write is scheduled by [`Global::queue_write_staging_texture`].
+    pub fn queue_create_staging_texture<A: HalApi>(
+        &self,
+        queue_id: QueueId,
+        destination: &ImageCopyTexture,
+        size: &wgt::Extent3d,
+        id_in: Option<id::StagingBufferId>,
+    ) -> Result<
+        (
+            id::StagingBufferId,
+            *mut u8,
+            wgt::BufferAddress,
+            wgt::ImageDataLayout,
+        ),
+        QueueWriteError,
+    > {
+        profiling::scope!("Queue::create_staging_texture");
+        let hub = A::hub(self);
+
+        let queue = hub
+            .queues
+            .get(queue_id)
+            .map_err(|_| DeviceError::InvalidQueueId)?;
+
+        let device = queue.device.as_ref().unwrap();
+
+        let write = self.validate_texture_write(device, destination, size)?;
+        let stage_size = write.stage_size(write.height_blocks);
+
+        // Empty writes are ignored, but still need a buffer to hand out.
+        let (staging_buffer, staging_buffer_ptr) = prepare_staging_buffer(
+            device,
+            stage_size.max(wgt::COPY_BUFFER_ALIGNMENT),
+            device.instance_flags,
+        )?;
+
+        let fid = hub.staging_buffers.prepare(id_in);
+        let (id, _) = fid.assign(Arc::new(staging_buffer));
+        resource_log!("Queue::create_staging_texture {id:?}");
+
+        let layout = wgt::ImageDataLayout {
+            offset: 0,
+            bytes_per_row: Some(write.stage_bytes_per_row),
+            rows_per_image: Some(write.height_blocks),
+        };
+        Ok((id, staging_buffer_ptr, stage_size, layout))
+    }
+
+    /// Schedules the write of a staging buffer created by
+    /// [`Global::queue_create_staging_texture`] into `destination`.
+    pub fn queue_write_staging_texture<A: HalApi>(
+        &self,
+        queue_id: QueueId,
+        destination: &ImageCopyTexture,
+        size: &wgt::Extent3d,
+        staging_buffer_id: id::StagingBufferId,
+    ) -> Result<(), QueueWriteError> {
+        profiling::scope!("Queue::write_staging_texture");
+        api_log!(
+            "Queue::write_staging_texture {:?} {size:?}",
+            destination.texture
+        );
+        let hub = A::hub(self);
+
+        let queue = hub
+            .queues
+            .get(queue_id)
+            .map_err(|_| DeviceError::InvalidQueueId)?;
+
+        let device = queue.device.as_ref().unwrap();
+        // The batched submissions come before this write.
+        self.flush_submit_batch_or_log::<A>(device);
+
+        let staging_buffer = hub
+            .staging_buffers
+            .unregister(staging_buffer_id)
+            .ok_or(TransferError::InvalidTexture(destination.texture))?;
+        let mut pending_writes = device.pending_writes.lock();
+        let pending_writes = pending_writes.as_mut().unwrap();
+
+        // At this point, we have taken ownership of the staging_buffer from the
+        // user. Platform validation requires that the staging buffer always
+        // be freed, even if an error occurs. All paths from here must call
+        // `device.pending_writes.consume`.
+        if size.width == 0 || size.height == 0 || size.depth_or_array_layers == 0 {
+            log::trace!("Ignoring write_texture of size 0");
+            pending_writes.consume(staging_buffer);
+            return Ok(());
+        }
+
+        let write = match self.validate_texture_write(device, destination, size) {
+            Ok(write) => write,
+            Err(e) => {
+                pending_writes.consume(staging_buffer);
+                return Err(e);
+            }
+        };
+        let stage_size = write.stage_size(write.height_blocks);
+        if stage_size > staging_buffer.size {
+            pending_writes.consume(staging_buffer);
+            return Err(TransferError::BufferOverrun {
+                start_offset: 0,
+                end_offset: stage_size,
+                buffer_size: staging_buffer.size,
+                side: CopySide::Source,
+            }
+            .into());
+        }
+
+        if let Err(flush_error) = unsafe { staging_buffer.flush(device.raw()) } {
+            pending_writes.consume(staging_buffer);
+            return Err(flush_error.into());
+        }
+
+        let result = self.queue_write_staging_texture_impl(
+            device,
+            pending_writes,
+            &staging_buffer,
+            destination,
+            &write,
+            write.height_blocks,
+        );
+
+        pending_writes.consume(staging_buffer);
+        result
+    }
+
+    fn validate_texture_write<A: HalApi>(
+        &self,
+        device: &Device<A>,
+        destination: &ImageCopyTexture,
+        size: &wgt::Extent3d,
+    ) -> Result<TextureWrite<A>, QueueWriteError> {
+        let hub = A::hub(self);
+
+        let dst = hub
+            .textures
+            .get(destination.texture)
+            .map_err(|_| TransferError::InvalidTexture(destination.texture))?;
+
+        if dst.device.as_info().id() != device.as_info().id() {
+            return Err(DeviceError::WrongDevice.into());
+        }
+
+        if !dst.desc.usage.contains(wgt::TextureUsages::COPY_DST) {
+            return Err(
+                TransferError::MissingCopyDstUsageFlag(None, Some(destination.texture)).into(),
+            );
+        }
+
+        // Note: Doing the copy range validation early is important because ensures that the
+        // dimensions are not going to cause overflow in other parts of the validation.
+        let (hal_copy_size, array_layer_count) =
+            validate_texture_copy_range(destination, &dst.desc, CopySide::Destination, size)?;
+
+        let (selector, dst_base) = extract_texture_selector(destination, size, &dst)?;
+
+        if !dst_base.aspect.is_one() {
+            return Err(TransferError::CopyAspectNotOne.into());
+        }
+
+        if !conv::is_valid_copy_dst_texture_format(dst.desc.format, destination.aspect) {
+            return Err(TransferError::CopyToForbiddenTextureFormat {
+                format: dst.desc.format,
+                aspect: destination.aspect,
+            }
+            .into());
+        }
+
+        if dst.desc.format.is_depth_stencil_format() {
+            device
+                .require_downlevel_flags(wgt::DownlevelFlags::DEPTH_TEXTURE_AND_BUFFER_COPIES)
+                .map_err(TransferError::from)?;
+        }
+
+        let (block_width, block_height) = dst.desc.format.block_dimensions();
+        let width_blocks = size.width / block_width;
+        let height_blocks = size.height / block_height;
+
+        let block_size = dst
+            .desc
+            .format
+            .block_copy_size(Some(destination.aspect))
+            .unwrap();
+        let bytes_per_row_alignment =
+            get_lowest_common_denom(device.alignments.buffer_copy_pitch.get() as u32, block_size);
+        let stage_bytes_per_row =
+            wgt::math::align_to(block_size * width_blocks, bytes_per_row_alignment);
+
+        Ok(TextureWrite {
+            dst,
+            hal_copy_size,
+            array_layer_count,
+            selector,
+            dst_base,
+            block_size,
+            width_blocks,
+            height_blocks,
+            stage_bytes_per_row,
+            size: *size,
+        })
+    }
+
+    /// Records the copy of a filled and flushed staging buffer into the
+    /// destination of `write`. The caller consumes the staging buffer.
+    fn queue_write_staging_texture_impl<A: HalApi>(
+        &self,
+        device: &Device<A>,
+        pending_writes: &mut PendingWrites<A>,
+        staging_buffer: &StagingBuffer<A>,
+        destination: &ImageCopyTexture,
+        write: &TextureWrite<A>,
+        block_rows_per_image: u32,
+    ) -> Result<(), QueueWriteError> {
+        let dst = &write.dst;
+
+        // If the copy does not fully cover the layers, we need to initialize to
+        // zero *first* as we don't keep track of partial texture layer inits.
+        //
+        // Strictly speaking we only need to clear the areas of a layer
+        // untouched, but this would get increasingly messy.
+        let init_layer_range = if dst.desc.dimension == wgt::TextureDimension::D3 {
+            // volume textures don't have a layer range as array volumes aren't supported
+            0..1
+        } else {
+            destination.origin.z..destination.origin.z + write.size.depth_or_array_layers
+        };
+        if dst.activate_placement() {
+            let mut trackers = device.trackers.lock();
+            trackers.textures.remove(dst.info.tracker_index());
+            trackers
+                .textures
+                .insert_single(dst.clone(), hal::TextureUses::UNINITIALIZED);
+            unsafe { pending_writes.activate().aliasing_barrier() };
+        }
+        let mut dst_initialization_status = dst.initialization_status.write();
+        let needs_init = dst_initialization_status.mips[destination.mip_level as usize]
+            .check(init_layer_range.clone())
+            .is_some();
+        let needs_clear = needs_init
+            && has_copy_partial_init_tracker_coverage(
+                &write.size,
+                destination.mip_level,
+                &dst.desc,
+            );
+
+        // Clears may need render passes, and surface textures belong to the
+        // queue presenting them, so neither can go to the transfer queue.
+        // Neither can placed textures, which take their memory over in queue
+        // order.
+        let is_surface = matches!(
+            dst.inner.get(&device.snatchable_lock.read()),
+            Some(TextureInner::Surface { .. })
+        );
+        let on_transfer_queue = !needs_clear
+            && !is_surface
+            && dst.placement.is_none()
+            && pending_writes.can_transfer_texture(destination.texture, &dst);
+        let encoder = pending_writes.upload_encoder(on_transfer_queue, dst.info.submission_index());
+
+        if needs_init {
+            if needs_clear {
+                for layer_range in dst_initialization_status.mips[destination.mip_level as usize]
+                    .drain(init_layer_range)
+                    .collect::<Vec<std::ops::Range<u32>>>()
+                {
+                    let mut trackers = device.trackers.lock();
+                    crate::command::clear_texture(
+                        dst,
+                        TextureInitRange {
+                            mip_range: destination.mip_level..(destination.mip_level + 1),
+                            layer_range,
+                        },
+                        encoder,
+                        &mut trackers.textures,
+                        &device.alignments,
+                        device.zero_buffer.as_ref().unwrap(),
+                        &device.snatchable_lock.read(),
+                    )
+                    .map_err(QueueWriteError::from)?;
+                }
+            } else {
+                dst_initialization_status.mips[destination.mip_level as usize]
+                    .drain(init_layer_range);
+            }
+        }
+
+        let snatch_guard = device.snatchable_lock.read();
+
+        dst.info
+            .use_at(device.active_submission_index.load(Ordering::Relaxed) + 1);
+
+        let dst_raw = dst
+            .raw(&snatch_guard)
+            .ok_or(TransferError::InvalidTexture(destination.texture))?;
+
+        let stage_bytes_per_row = write.stage_bytes_per_row;
+        let regions = (0..write.array_layer_count).map(|rel_array_layer| {
+            let mut texture_base = write.dst_base.clone();
             texture_base.array_layer += rel_array_layer;
             hal::BufferTextureCopy {
                 buffer_layout: wgt::ImageDataLayout {
@@ -912,7 +1584,7 @@ impl Global {
                     rows_per_image: Some(block_rows_per_image),
                 },
                 texture_base,
-                size: hal_copy_size,
+                size: write.hal_copy_size,
             }
         });
 
@@ -926,7 +1598,7 @@ impl Global {
             let mut trackers = device.trackers.lock();
             let transition = trackers
                 .textures
-                .set_single(&dst, selector, hal::TextureUses::COPY_DST)
+                .set_single(dst, write.selector.clone(), hal::TextureUses::COPY_DST)
                 .ok_or(TransferError::InvalidTexture(destination.texture))?;
             unsafe {
                 encoder.transition_textures(transition.map(|pending| pending.into_hal(dst_raw)));
@@ -935,10 +1607,13 @@ impl Global {
             }
         }
 
-        pending_writes.consume(staging_buffer);
-        pending_writes
-            .dst_textures
-            .insert(destination.texture, dst.clone());
+        if on_transfer_queue {
+            pending_writes
+                .dst_textures
+                .insert(destination.texture, dst.clone());
+        } else {
+            pending_writes.insert_texture(destination.texture, dst.clone());
+        }
 
         Ok(())
     }
@@ -961,6 +1636,8 @@ impl Global {
             .map_err(|_| DeviceError::InvalidQueueId)?;
 
         let device = queue.device.as_ref().unwrap();
+        // The batched submissions come before this write.
+        self.flush_submit_batch_or_log::<A>(device);
 
         if size.width == 0 || size.height == 0 || size.depth_or_array_layers == 0 {
             log::trace!("Ignoring write_texture of size 0");
@@ -1061,7 +1738,9 @@ impl Global {
             extract_texture_selector(&destination.to_untagged(), &size, &dst)?;
 
         let mut pending_writes = device.pending_writes.lock();
-        let encoder = pending_writes.as_mut().unwrap().activate();
+        let pending_writes = pending_writes.as_mut().unwrap();
+        pending_writes.depend_on(dst.info.submission_index());
+        let encoder = pending_writes.activate();
 
         // If the copy does not fully cover the layers, we need to initialize to
         // zero *first* as we don't keep track of partial texture layer inits.
@@ -1145,11 +1824,263 @@ impl Global {
         &self,
         queue_id: QueueId,
         command_buffer_ids: &[id::CommandBufferId],
+    ) -> Result<WrappedSubmissionIndex, QueueSubmitError> {
+        self.queue_submit_with_semaphores::<A>(queue_id, command_buffer_ids, &[], &[])
+    }
+
+    /// Like [`Global::queue_submit`], with timeline semaphore operations
+    /// around the submission.
+    ///
+    /// The command buffers don't begin executing before each semaphore in
+    /// `waits` has reached its value, and each semaphore in `signals` is set
+    /// to its value once they have completed. Signal values have to exceed
+    /// those the semaphores were signaled with before.
+    ///
+    /// See [`wgt::Features::TIMELINE_SEMAPHORE`].
+    pub fn queue_submit_with_semaphores<A: HalApi>(
+        &self,
+        queue_id: QueueId,
+        command_buffer_ids: &[id::CommandBufferId],
+        waits: &[(id::TimelineSemaphoreId, u64)],
+        signals: &[(id::TimelineSemaphoreId, u64)],
     ) -> Result<WrappedSubmissionIndex, QueueSubmitError> {
         profiling::scope!("Queue::submit");
         api_log!("Queue::submit {queue_id:?}");
 
-        let (submit_index, callbacks) = {
+        let hub = A::hub(self);
+        let queue = hub
+            .queues
+            .get(queue_id)
+            .map_err(|_| DeviceError::InvalidQueueId)?;
+        let device = queue.device.as_ref().unwrap();
+        let batching = queue.batch_submits.load(Ordering::Relaxed);
+
+        let mut closures = UserClosures::default();
+        let mut batch = device.submit_batch.lock();
+        // Submissions are made in order, so a batch of another queue, or of
+        // this one before batching was turned off, goes first.
+        if batch
+            .as_ref()
+            .map_or(false, |pending| !batching || pending.queue_id != queue_id)
+        {
+            let pending = batch.take().unwrap();
+            let (_, batch_closures) = self.submit_now::<A>(
+                pending.queue_id,
+                &pending.command_buffer_ids,
+                &pending.waits,
+                &pending.signals,
+            )?;
+            closures.extend(batch_closures);
+        }
+        let submission_index = if batching {
+            self.validate_batched_submit::<A>(
+                device,
+                batch.as_ref(),
+                command_buffer_ids,
+                waits,
+                signals,
+            )?;
+            let pending = batch.get_or_insert_with(|| SubmitBatch {
+                queue_id,
+                command_buffer_ids: Vec::new(),
+                waits: Vec::new(),
+                signals: Vec::new(),
+            });
+            pending
+                .command_buffer_ids
+                .extend_from_slice(command_buffer_ids);
+            pending.waits.extend_from_slice(waits);
+            pending.signals.extend_from_slice(signals);
+            // Nothing is submitted to the device while a batch is pending, so
+            // the batch gets the next submission index.
+            WrappedSubmissionIndex {
+                queue_id,
+                index: device.active_submission_index.load(Ordering::Relaxed) + 1,
+                token: next_submission_token(),
+            }
+        } else {
+            let (submission_index, submit_closures) =
+                self.submit_now::<A>(queue_id, command_buffer_ids, waits, signals)?;
+            closures.extend(submit_closures);
+            submission_index
+        };
+        drop(batch);
+
+        // the closures should execute with nothing locked!
+        closures.fire();
+
+        Ok(submission_index)
+    }
+
+    /// Check a submission that is about to be added to a batch, so that its
+    /// errors are reported by the submission rather than when the batch is
+    /// flushed.
+    ///
+    /// This checks the same as [`Global::submit_now`]. The resources can't
+    /// change in between, since destroying or mapping them flushes the batch.
+    fn validate_batched_submit<A: HalApi>(
+        &self,
+        device: &Device<A>,
+        pending: Option<&SubmitBatch>,
+        command_buffer_ids: &[id::CommandBufferId],
+        waits: &[(id::TimelineSemaphoreId, u64)],
+        signals: &[(id::TimelineSemaphoreId, u64)],
+    ) -> Result<(), QueueSubmitError> {
+        let hub = A::hub(self);
+
+        let get_semaphore = |id: id::TimelineSemaphoreId| -> Result<_, QueueSubmitError> {
+            let semaphore = hub
+                .timeline_semaphores
+                .get(id)
+                .map_err(|_| TimelineSemaphoreError::Invalid(id))?;
+            if semaphore.device.as_info().id() != device.as_info().id() {
+                return Err(DeviceError::WrongDevice.into());
+            }
+            Ok(semaphore)
+        };
+        for &(id, _) in waits {
+            get_semaphore(id)?;
+        }
+        // The values signaled by the batch so far have to be exceeded as well.
+        let pending_signals = pending.map_or(&[][..], |pending| &pending.signals);
+        for (i, &(id, value)) in signals.iter().enumerate() {
+            let semaphore = get_semaphore(id)?;
+            let last = pending_signals
+                .iter()
+                .chain(&signals[..i])
+                .filter(|&&(other, _)| other == id)
+                .map(|&(_, value)| value)
+                .fold(
+                    semaphore.last_signaled_value.load(Ordering::Relaxed),
+                    u64::max,
+                );
+            if value <= last {
+                return Err(TimelineSemaphoreError::ValueNotIncreasing { value, last }.into());
+            }
+        }
+
+        let snatch_guard = device.snatchable_lock.read();
+        for &cmb_id in command_buffer_ids {
+            // Invalid and unfinished command buffers are dropped by the
+            // submission without an error.
+            let cmdbuf = match hub.command_buffers.get(cmb_id) {
+                Ok(cmdbuf) => cmdbuf,
+                Err(_) => continue,
+            };
+            if cmdbuf.device.as_info().id() != device.as_info().id() {
+                return Err(DeviceError::WrongDevice.into());
+            }
+            if !cmdbuf.is_finished() {
+                continue;
+            }
+
+            let cmd_buf_data = cmdbuf.data.lock();
+            let cmd_buf_trackers = &cmd_buf_data.as_ref().unwrap().trackers;
+            for buffer in cmd_buf_trackers.buffers.used_resources() {
+                if buffer.is_destroyed(&snatch_guard) {
+                    return Err(QueueSubmitError::DestroyedBuffer(buffer.info.id()));
+                }
+                if buffer.is_unique() {
+                    continue;
+                }
+                match *buffer.map_state.lock() {
+                    BufferMapState::Idle => (),
+                    BufferMapState::Active {
+                        host: HostMap::Write,
+                        checked_views: false,
+                        ..
+                    } if device.features.contains(wgt::Features::PERSISTENT_MAPPING) => {}
+                    _ => return Err(QueueSubmitError::BufferStillMapped(buffer.info.id())),
+                }
+            }
+            for texture in cmd_buf_trackers.textures.used_resources() {
+                if texture.expiry.is_expired() {
+                    return Err(QueueSubmitError::ExpiredTexture(texture.info.id()));
+                }
+                if texture.is_destroyed(&snatch_guard) {
+                    return Err(QueueSubmitError::DestroyedTexture(texture.info.id()));
+                }
+            }
+        }
+        Ok(())
+    }
+
+    /// Defer submissions to `queue_id` until they are flushed, or stop doing so.
+    ///
+    /// Command buffers submitted to a queue in batching mode are validated
+    /// right away, but only submitted when the batch is flushed, as a single
+    /// submission that waits for and signals the timeline semaphores of all of
+    /// them. The submission index returned for each of them is the one of the
+    /// batch. The batch is flushed by [`Global::queue_flush`], when presenting,
+    /// polling or waiting for a submission of the device, before any other
+    /// submission to the device, including when batching is turned off, and
+    /// before writing to a queue or mapping or destroying a resource.
+    pub fn queue_set_submit_batching<A: HalApi>(
+        &self,
+        queue_id: QueueId,
+        enabled: bool,
+    ) -> Result<(), QueueSubmitError> {
+        api_log!("Queue::set_submit_batching {queue_id:?} {enabled}");
+
+        let hub = A::hub(self);
+        let queue = hub
+            .queues
+            .get(queue_id)
+            .map_err(|_| DeviceError::InvalidQueueId)?;
+        queue.batch_submits.store(enabled, Ordering::Relaxed);
+        if !enabled {
+            self.flush_submit_batch::<A>(queue.device.as_ref().unwrap())?;
+        }
+        Ok(())
+    }
+
+    /// Submit the batch of submissions deferred by a queue of the device of
+    /// `queue_id`, see [`Global::queue_set_submit_batching`].
+    pub fn queue_flush<A: HalApi>(&self, queue_id: QueueId) -> Result<(), QueueSubmitError> {
+        profiling::scope!("Queue::flush");
+        api_log!("Queue::flush {queue_id:?}");
+
+        let hub = A::hub(self);
+        let queue = hub
+            .queues
+            .get(queue_id)
+            .map_err(|_| DeviceError::InvalidQueueId)?;
+        self.flush_submit_batch::<A>(queue.device.as_ref().unwrap())
+    }
+
+    /// Submit the batch pending on `device`, if there is one.
+    pub(crate) fn flush_submit_batch<A: HalApi>(
+        &self,
+        device: &Device<A>,
+    ) -> Result<(), QueueSubmitError> {
+        // The lock is held until the batch has been submitted, so that no
+        // submission can take the index of the batch.
+        let mut batch = device.submit_batch.lock();
+        if let Some(pending) = batch.take() {
+            let (_, closures) = self.submit_now::<A>(
+                pending.queue_id,
+                &pending.command_buffer_ids,
+                &pending.waits,
+                &pending.signals,
+            )?;
+            drop(batch);
+            closures.fire();
+        }
+        Ok(())
+    }
+
+    /// Submit right away, regardless of batching.
+    ///
+    /// `Device::submit_batch` must be locked by the caller, which has to fire
+    /// the returned closures once it isn't anymore.
+    fn submit_now<A: HalApi>(
+        &self,
+        queue_id: QueueId,
+        command_buffer_ids: &[id::CommandBufferId],
+        waits: &[(id::TimelineSemaphoreId, u64)],
+        signals: &[(id::TimelineSemaphoreId, u64)],
+    ) -> Result<(WrappedSubmissionIndex, UserClosures), QueueSubmitError> {
+        let (submit_index, token, callbacks) = {
             let hub = A::hub(self);
 
             let queue = hub
@@ -1159,6 +2090,30 @@ impl Global {
 
             let device = queue.device.as_ref().unwrap();
 
+            // Look the semaphores up before anything is submitted.
+            let get_semaphore =
+                |&(id, value): &(id::TimelineSemaphoreId, u64)| -> Result<_, QueueSubmitError> {
+                    let semaphore = hub
+                        .timeline_semaphores
+                        .get(id)
+                        .map_err(|_| TimelineSemaphoreError::Invalid(id))?;
+                    if semaphore.device.as_info().id() != device.as_info().id() {
+                        return Err(DeviceError::WrongDevice.into());
+                    }
+                    Ok((semaphore, value))
+                };
+            let semaphore_waits = waits
+                .iter()
+                .map(get_semaphore)
+                .collect::<Result<Vec<_>, _>>()?;
+            let semaphore_signals = signals
+                .iter()
+                .map(get_semaphore)
+                .collect::<Result<Vec<_>, _>>()?;
+            for (semaphore, value) in semaphore_signals.iter() {
+                semaphore.begin_signal(*value)?;
+            }
+
             let snatch_guard = device.snatchable_lock.read();
 
             // Fence lock must be acquired after the snatch lock everywhere to avoid deadlocks.
@@ -1168,8 +2123,14 @@ impl Global {
                 .active_submission_index
                 .fetch_add(1, Ordering::Relaxed)
                 + 1;
+            let token = next_submission_token();
             let mut active_executions = Vec::new();
 
+            // Submissions that last used the buffers and textures of this one. Those
+            // made to other queues have to be waited for before this one can run.
+            let track_dependencies = device.limits.max_additional_queues != 0;
+            let mut dependencies = FastHashSet::default();
+
             let mut used_surface_textures = track::TextureUsageScope::default();
 
             // Use a hashmap here to deduplicate the surface textures that are used in the command buffers.
@@ -1203,7 +2164,7 @@ impl Global {
                             Err(_) => continue,
                         };
 
-                        if cmdbuf.device.as_info().id().into_queue_id() != queue_id {
+                        if cmdbuf.device.as_info().id() != device.as_info().id() {
                             return Err(DeviceError::WrongDevice.into());
                         }
 
@@ -1229,6 +2190,11 @@ impl Global {
                             continue;
                         }
 
+                        // Placed resources take their memory over before the
+                        // command buffer runs.
+                        let mut placed_buffers = Vec::new();
+                        let mut placed_textures = Vec::new();
+
                         // optimize the tracked states
                         // cmdbuf.trackers.optimize();
                         {
@@ -1246,6 +2212,12 @@ impl Global {
                                         ));
                                     }
                                 };
+                                if track_dependencies {
+                                    dependencies.insert(buffer.info.submission_index());
+                                }
+                                if buffer.placement.is_some() {
+                                    placed_buffers.push(buffer.clone());
+                                }
                                 buffer.info.use_at(submit_index);
                                 if buffer.is_unique() {
                                     if let BufferMapState::Active { .. } = *buffer.map_state.lock()
@@ -1262,6 +2234,27 @@ impl Global {
                                 } else {
                                     match *buffer.map_state.lock() {
                                         BufferMapState::Idle => (),
+                                        BufferMapState::Active {
+                                            host: HostMap::Write,
+                                            checked_views: false,
+                                            ..
+                                        } if device
+                                            .features
+                                            .contains(wgt::Features::PERSISTENT_MAPPING) =>
+                                        {
+                                            // The buffer stays mapped, make the writes so far
+                                            // visible to the device.
+                                            if let Some(ref range) =
+                                                *buffer.sync_mapped_writes.lock()
+                                            {
+                                                unsafe {
+                                                    device.raw().flush_mapped_ranges(
+                                                        raw_buf,
+                                                        iter::once(range.clone()),
+                                                    )
+                                                };
+                                            }
+                                        }
                                         _ => {
                                             return Err(QueueSubmitError::BufferStillMapped(
                                                 buffer.info.id(),
@@ -1271,6 +2264,11 @@ impl Global {
                                 }
                             }
                             for texture in cmd_buf_trackers.textures.used_resources() {
+                                if texture.expiry.is_expired() {
+                                    return Err(QueueSubmitError::ExpiredTexture(
+                                        texture.info.id(),
+                                    ));
+                                }
                                 let tracker_index = texture.info.tracker_index();
                                 let should_extend = match texture.inner.get(&snatch_guard) {
                                     None => {
@@ -1289,6 +2287,12 @@ impl Global {
                                         true
                                     }
                                 };
+                                if track_dependencies {
+                                    dependencies.insert(texture.info.submission_index());
+                                }
+                                if texture.placement.is_some() {
+                                    placed_textures.push(texture.clone());
+                                }
                                 texture.info.use_at(submit_index);
                                 if texture.is_unique() {
                                     temp_suspected
@@ -1404,6 +2408,12 @@ impl Global {
 
                         //Note: locking the trackers has to be done after the storages
                         let mut trackers = device.trackers.lock();
+                        activate_placed_resources(
+                            &mut baked.encoder,
+                            &mut trackers,
+                            &placed_buffers,
+                            &placed_textures,
+                        )?;
                         baked
                             .initialize_buffer_memory(&mut *trackers, &snatch_guard)
                             .map_err(|err| QueueSubmitError::DestroyedBuffer(err.0))?;
@@ -1510,6 +2520,50 @@ impl Global {
                 }
             }
 
+            if let Some(ref mut transfer) = pending_writes.transfer {
+                if transfer.pre_submit()? {
+                    let transfer_queue = &device.transfer_queue.as_ref().unwrap().raw;
+
+                    // The uploads wait for the last submissions still in flight
+                    // that used their destinations, on every queue.
+                    let mut transfer_waits =
+                        FastHashMap::<Option<usize>, SubmissionIndex>::default();
+                    {
+                        let life_tracker = device.lock_life();
+                        for dependency in transfer.dependencies.drain() {
+                            if let Some(other) = life_tracker.submission_queue(dependency) {
+                                let wait = transfer_waits.entry(other).or_default();
+                                *wait = (*wait).max(dependency);
+                            }
+                        }
+                    }
+
+                    let additional_queues = device.additional_queues.lock();
+                    unsafe {
+                        for (&other, &value) in transfer_waits.iter() {
+                            let other_fence = match other {
+                                Some(index) => &additional_queues[index].fence,
+                                None => &*fence,
+                            };
+                            transfer_queue
+                                .wait_for_fence(other_fence, value)
+                                .map_err(DeviceError::from)?;
+                        }
+                        let cmd_buf = transfer.executing_command_buffers.last().unwrap();
+                        transfer_queue
+                            .submit(&[cmd_buf], &[], (&mut transfer.fence, submit_index))
+                            .map_err(DeviceError::from)?;
+                        queue
+                            .raw
+                            .as_ref()
+                            .unwrap()
+                            .wait_for_fence(&transfer.fence, submit_index)
+                            .map_err(DeviceError::from)?;
+                    }
+                }
+                transfer.dependencies.clear();
+            }
+
             let refs = pending_writes
                 .pre_submit()?
                 .into_iter()
@@ -1530,14 +2584,66 @@ impl Global {
                 });
             }
 
+            dependencies.extend(pending_writes.dependencies.drain());
+
+            // Find the last submission on each other queue that this one depends on.
+            let mut queue_waits = FastHashMap::<Option<usize>, SubmissionIndex>::default();
+            {
+                let life_tracker = device.lock_life();
+                for dependency in dependencies {
+                    match life_tracker.submission_queue(dependency) {
+                        Some(other) if other != queue.additional_index => {
+                            let wait = queue_waits.entry(other).or_default();
+                            *wait = (*wait).max(dependency);
+                        }
+                        _ => {}
+                    }
+                }
+            }
+
+            let mut additional_queues = device.additional_queues.lock();
             unsafe {
-                queue
-                    .raw
-                    .as_ref()
-                    .unwrap()
-                    .submit(&refs, &submit_surface_textures, (fence, submit_index))
+                let raw_queue = queue.raw.as_ref().unwrap();
+                for (&other, &value) in queue_waits.iter() {
+                    let other_fence = match other {
+                        Some(index) => &additional_queues[index].fence,
+                        None => &*fence,
+                    };
+                    raw_queue
+                        .wait_for_fence(other_fence, value)
+                        .map_err(DeviceError::from)?;
+                }
+                for (semaphore, value) in semaphore_waits.iter() {
+                    raw_queue
+                        .wait_for_fence(semaphore.raw.lock().as_ref().unwrap(), *value)
+                        .map_err(DeviceError::from)?;
+                }
+                let signal_fence = match queue.additional_index {
+                    Some(index) => &mut additional_queues[index].fence,
+                    None => &mut *fence,
+                };
+                raw_queue
+                    .submit(
+                        &refs,
+                        &submit_surface_textures,
+                        (signal_fence, submit_index),
+                    )
                     .map_err(DeviceError::from)?;
+                // Signals made by later submissions to the same queue happen
+                // after the work of this one.
+                for (semaphore, value) in semaphore_signals.iter() {
+                    raw_queue
+                        .submit(&[], &[], (semaphore.raw.lock().as_mut().unwrap(), *value))
+                        .map_err(DeviceError::from)?;
+                }
             }
+            match queue.additional_index {
+                Some(index) => additional_queues[index].last_submission_index = submit_index,
+                None => device
+                    .last_primary_submission_index
+                    .store(submit_index, Ordering::Relaxed),
+            }
+            drop(additional_queues);
 
             profiling::scope!("cleanup");
             if let Some(pending_execution) = pending_writes.post_submit(
@@ -1547,19 +2653,42 @@ impl Global {
             ) {
                 active_executions.push(pending_execution);
             }
+            let mut transfer_executions = Vec::new();
+            if let (Some(transfer), Some(transfer_queue)) = (
+                pending_writes.transfer.as_mut(),
+                device.transfer_queue.as_ref(),
+            ) {
+                transfer_executions.extend(transfer.post_submit(
+                    &transfer_queue.command_allocator,
+                    device.raw(),
+                    &transfer_queue.raw,
+                ));
+            }
 
             // this will register the new submission to the life time tracker
             let mut pending_write_resources = mem::take(&mut pending_writes.temp_resources);
             device.lock_life().track_submission(
                 submit_index,
+                queue.additional_index,
                 pending_write_resources.drain(..),
                 active_executions,
+                transfer_executions,
+                semaphore_waits
+                    .into_iter()
+                    .chain(semaphore_signals)
+                    .map(|(semaphore, _)| semaphore)
+                    .collect(),
             );
 
             // This will schedule destruction of all resources that are no longer needed
             // by the user but used in the command stream, among other things.
-            let (closures, _) = match device.maintain(fence, wgt::Maintain::Poll, snatch_guard) {
-                Ok(closures) => closures,
+            let (closures, _) = match device.maintain(
+                fence,
+                wgt::Maintain::Poll,
+                wgt::MaintainWork::all(),
+                snatch_guard,
+            ) {
+                Ok((closures, queue_empty)) => (device.defer_closures(closures), queue_empty),
                 Err(WaitIdleError::Device(err)) => return Err(QueueSubmitError::Queue(err)),
                 Err(WaitIdleError::StuckGpu) => return Err(QueueSubmitError::StuckGpu),
                 Err(WaitIdleError::WrongSubmissionIndex(..)) => unreachable!(),
@@ -1569,16 +2698,366 @@ impl Global {
             // want to retain its heap allocation.
             pending_writes.temp_resources = pending_write_resources;
             device.lock_life().post_submit();
+            device.wake_background_poll();
 
-            (submit_index, closures)
+            (submit_index, token, closures)
         };
 
-        // the closures should execute with nothing locked!
-        callbacks.fire();
+        Ok((
+            WrappedSubmissionIndex {
+                queue_id,
+                index: submit_index,
+                token,
+            },
+            callbacks,
+        ))
+    }
+
+    /// Commit pages of the sparse buffer `buffer_id` to memory, or decommit
+    /// them, on the primary queue `queue_id`.
+    ///
+    /// The binds count as a submission: they happen after the submissions
+    /// made before, and before the ones made after, including the writes
+    /// staged by [`Global::queue_write_buffer`] that haven't been submitted
+    /// yet. Committed pages are zeroed before the next submission uses them.
+    pub fn queue_bind_sparse<A: HalApi>(
+        &self,
+        queue_id: QueueId,
+        buffer_id: id::BufferId,
+        binds: &[wgt::SparseBufferBind],
+    ) -> Result<WrappedSubmissionIndex, QueueBindSparseError> {
+        profiling::scope!("Queue::bind_sparse");
+        api_log!("Queue::bind_sparse {buffer_id:?} {binds:?}");
+
+        let hub = A::hub(self);
+        let queue = hub
+            .queues
+            .get(queue_id)
+            .map_err(|_| DeviceError::InvalidQueueId)?;
+        let device = queue.device.as_ref().unwrap();
+        device.require_features(wgt::Features::SPARSE_BUFFER)?;
+        // Only the queue family of the primary queue is checked for sparse
+        // binding support.
+        if queue.additional_index.is_some() {
+            return Err(QueueBindSparseError::AdditionalQueue);
+        }
+
+        let buffer = hub
+            .buffers
+            .get(buffer_id)
+            .map_err(|_| QueueBindSparseError::InvalidBuffer(buffer_id))?;
+        if buffer.device.as_info().id() != device.as_info().id() {
+            return Err(DeviceError::WrongDevice.into());
+        }
+        if !buffer.sparse {
+            return Err(QueueBindSparseError::NotSparse(buffer_id));
+        }
+        for bind in binds {
+            let end = match bind.offset.checked_add(bind.size) {
+                Some(end) if end <= buffer.size => end,
+                _ => {
+                    return Err(QueueBindSparseError::BindOverrun {
+                        offset: bind.offset,
+                        size: bind.size,
+                        buffer_size: buffer.size,
+                    })
+                }
+            };
+            if bind.offset % wgt::SPARSE_BUFFER_PAGE_SIZE != 0
+                || (end % wgt::SPARSE_BUFFER_PAGE_SIZE != 0 && end != buffer.size)
+            {
+                return Err(QueueBindSparseError::UnalignedBind {
+                    offset: bind.offset,
+                    size: bind.size,
+                });
+            }
+        }
+
+        // The batch was submitted before the binds.
+        self.flush_submit_batch::<A>(device)?;
+
+        let snatch_guard = device.snatchable_lock.read();
+        let raw_buffer = buffer
+            .raw(&snatch_guard)
+            .ok_or(QueueBindSparseError::DestroyedBuffer(buffer_id))?;
+
+        // Fence lock must be acquired after the snatch lock everywhere to avoid deadlocks.
+        let mut fence = device.fence.write();
+        let fence = fence.as_mut().unwrap();
+        let submit_index = device
+            .active_submission_index
+            .fetch_add(1, Ordering::Relaxed)
+            + 1;
+        let token = next_submission_token();
+        unsafe {
+            queue.raw.as_ref().unwrap().bind_sparse_buffer(
+                device.raw(),
+                raw_buffer,
+                binds,
+                (fence, submit_index),
+            )
+        }
+        .map_err(DeviceError::from)?;
+        device
+            .last_primary_submission_index
+            .store(submit_index, Ordering::Relaxed);
+        buffer.info.use_at(submit_index);
+        device.lock_life().track_submission(
+            submit_index,
+            None,
+            iter::empty(),
+            Vec::new(),
+            Vec::new(),
+            Vec::new(),
+        );
+
+        let mut initialization_status = buffer.initialization_status.write();
+        for bind in binds.iter().filter(|bind| bind.commit) {
+            let end = wgt::math::align_to(bind.offset + bind.size, wgt::COPY_BUFFER_ALIGNMENT);
+            initialization_status.discard_range(bind.offset..end);
+        }
+
+        Ok(WrappedSubmissionIndex {
+            queue_id,
+            index: submit_index,
+            token,
+        })
+    }
+
+    /// Commit tiles of the sparse texture `texture_id` to memory, or decommit
+    /// them, on the primary queue `queue_id`.
+    ///
+    /// The binds count as a submission, like those of
+    /// [`Global::queue_bind_sparse`]. Writes to the texture staged by
+    /// [`Global::queue_write_texture`] before are submitted before the binds,
+    /// and the committed tiles are zeroed before the next submission.
+    pub fn queue_bind_sparse_texture<A: HalApi>(
+        &self,
+        queue_id: QueueId,
+        texture_id: id::TextureId,
+        binds: &[wgt::SparseTextureBind],
+    ) -> Result<WrappedSubmissionIndex, QueueBindSparseError> {
+        profiling::scope!("Queue::bind_sparse_texture");
+        api_log!("Queue::bind_sparse_texture {texture_id:?} {binds:?}");
+
+        let hub = A::hub(self);
+        let queue = hub
+            .queues
+            .get(queue_id)
+            .map_err(|_| DeviceError::InvalidQueueId)?;
+        let device = queue.device.as_ref().unwrap();
+        device.require_features(wgt::Features::SPARSE_TEXTURE)?;
+        // Only the queue family of the primary queue is checked for sparse
+        // binding support.
+        if queue.additional_index.is_some() {
+            return Err(QueueBindSparseError::AdditionalQueue);
+        }
+
+        let texture = hub
+            .textures
+            .get(texture_id)
+            .map_err(|_| QueueBindSparseError::InvalidTexture(texture_id))?;
+        if texture.device.as_info().id() != device.as_info().id() {
+            return Err(DeviceError::WrongDevice.into());
+        }
+        let tiles = texture
+            .sparse
+            .ok_or(QueueBindSparseError::NotSparseTexture(texture_id))?;
+        let tile_size = tiles.tile_size;
+        let is_3d = texture.desc.dimension == wgt::TextureDimension::D3;
+        for bind in binds {
+            let level_size = texture
+                .desc
+                .mip_level_size(bind.mip_level)
+                .filter(|_| bind.array_layer < texture.desc.array_layer_count())
+                .ok_or(QueueBindSparseError::InvalidSubresource {
+                    mip_level: bind.mip_level,
+                    array_layer: bind.array_layer,
+                })?;
+            let level_size = wgt::Extent3d {
+                depth_or_array_layers: if is_3d {
+                    level_size.depth_or_array_layers
+                } else {
+                    1
+                },
+                ..level_size
+            };
+            let end = |origin: u32, size: u32, level: u32| {
+                origin.checked_add(size).filter(|&end| end <= level)
+            };
+            let (Some(end_x), Some(end_y), Some(end_z)) = (
+                end(bind.origin.x, bind.size.width, level_size.width),
+                end(bind.origin.y, bind.size.height, level_size.height),
+                end(
+                    bind.origin.z,
+                    bind.size.depth_or_array_layers,
+                    level_size.depth_or_array_layers,
+                ),
+            ) else {
+                return Err(QueueBindSparseError::TextureBindOverrun {
+                    mip_level: bind.mip_level,
+                    origin: bind.origin,
+                    size: bind.size,
+                    level_size,
+                });
+            };
+            if bind.mip_level >= tiles.mip_tail_first_level {
+                if bind.origin != wgt::Origin3d::ZERO || bind.size != level_size {
+                    return Err(QueueBindSparseError::PartialMipTail(bind.mip_level));
+                }
+                continue;
+            }
+            let aligned = |origin: u32, end: u32, level: u32, tile: u32| {
+                origin % tile == 0 && (end % tile == 0 || end == level)
+            };
+            if !aligned(bind.origin.x, end_x, level_size.width, tile_size.width)
+                || !aligned(bind.origin.y, end_y, level_size.height, tile_size.height)
+                || !aligned(
+                    bind.origin.z,
+                    end_z,
+                    level_size.depth_or_array_layers,
+                    tile_size.depth_or_array_layers,
+                )
+            {
+                return Err(QueueBindSparseError::UnalignedTextureBind {
+                    origin: bind.origin,
+                    size: bind.size,
+                    tile_size,
+                });
+            }
+        }
+
+        // The batch was submitted before the binds.
+        self.flush_submit_batch::<A>(device)?;
+
+        // So are the writes staged to the texture, which zeroing the committed
+        // tiles would overwrite otherwise.
+        let has_staged_writes = device
+            .pending_writes
+            .lock()
+            .as_ref()
+            .unwrap()
+            .dst_textures
+            .contains_key(&texture_id);
+        if has_staged_writes {
+            let batch = device.submit_batch.lock();
+            let (_, closures) = self.submit_now::<A>(queue_id, &[], &[], &[])?;
+            drop(batch);
+            closures.fire();
+        }
+
+        let snatch_guard = device.snatchable_lock.read();
+        let raw_texture = texture
+            .raw(&snatch_guard)
+            .ok_or(QueueBindSparseError::DestroyedTexture(texture_id))?;
+
+        // Fence lock must be acquired after the snatch lock everywhere to avoid deadlocks.
+        let mut fence_guard = device.fence.write();
+        let fence = fence_guard.as_mut().unwrap();
+        let submit_index = device
+            .active_submission_index
+            .fetch_add(1, Ordering::Relaxed)
+            + 1;
+        let token = next_submission_token();
+        unsafe {
+            queue.raw.as_ref().unwrap().bind_sparse_texture(
+                device.raw(),
+                raw_texture,
+                binds,
+                (fence, submit_index),
+            )
+        }
+        .map_err(DeviceError::from)?;
+        device
+            .last_primary_submission_index
+            .store(submit_index, Ordering::Relaxed);
+        texture.info.use_at(submit_index);
+        device.lock_life().track_submission(
+            submit_index,
+            None,
+            iter::empty(),
+            Vec::new(),
+            Vec::new(),
+            Vec::new(),
+        );
+        drop(fence_guard);
+
+        // Zero the committed tiles with copies from the zero buffer.
+        let mut regions = Vec::new();
+        let mut selector = TextureSelector {
+            mips: u32::MAX..0,
+            layers: u32::MAX..0,
+        };
+        let mut zeroed_mip_tails = FastHashSet::default();
+        let is_empty = |size: wgt::Extent3d| {
+            size.width == 0 || size.height == 0 || size.depth_or_array_layers == 0
+        };
+        for bind in binds
+            .iter()
+            .filter(|bind| bind.commit && !is_empty(bind.size))
+        {
+            let mips = if bind.mip_level < tiles.mip_tail_first_level {
+                bind.mip_level..bind.mip_level + 1
+            } else if zeroed_mip_tails.insert(bind.array_layer) {
+                tiles.mip_tail_first_level..texture.desc.mip_level_count
+            } else {
+                continue;
+            };
+            for mip_level in mips.clone() {
+                let (origin, size) = if mip_level == bind.mip_level {
+                    (bind.origin, bind.size)
+                } else {
+                    let size = texture.desc.mip_level_size(mip_level).unwrap();
+                    let size = wgt::Extent3d {
+                        depth_or_array_layers: if is_3d { size.depth_or_array_layers } else { 1 },
+                        ..size
+                    };
+                    (wgt::Origin3d::ZERO, size)
+                };
+                crate::command::push_zero_buffer_copies(
+                    &texture.desc,
+                    &device.alignments,
+                    mip_level,
+                    bind.array_layer,
+                    origin,
+                    size,
+                    &mut regions,
+                );
+            }
+            selector.mips = selector.mips.start.min(mips.start)..selector.mips.end.max(mips.end);
+            selector.layers = selector.layers.start.min(bind.array_layer)
+                ..selector.layers.end.max(bind.array_layer + 1);
+        }
+
+        if !regions.is_empty() {
+            let mut pending_writes = device.pending_writes.lock();
+            let pending_writes = pending_writes.as_mut().unwrap();
+            let encoder = pending_writes.activate();
+            let mut trackers = device.trackers.lock();
+            let transition = trackers
+                .textures
+                .set_single(&texture, selector, hal::TextureUses::COPY_DST)
+                .ok_or(QueueBindSparseError::InvalidTexture(texture_id))?;
+            unsafe {
+                encoder
+                    .transition_textures(transition.map(|pending| pending.into_hal(raw_texture)));
+                encoder.copy_buffer_to_texture(
+                    device.zero_buffer.as_ref().unwrap(),
+                    raw_texture,
+                    regions.into_iter(),
+                );
+            }
+            drop(trackers);
+            pending_writes.insert_texture(texture_id, texture.clone());
+            texture
+                .info
+                .use_at(device.active_submission_index.load(Ordering::Relaxed) + 1);
+        }
 
         Ok(WrappedSubmissionIndex {
             queue_id,
             index: submit_index,
+            token,
         })
     }
 
@@ -1593,6 +3072,38 @@ impl Global {
         }
     }
 
+    /// Sample the timestamp clock of `queue_id` together with the time of the
+    /// CPU, or return `None` if the backend can't.
+    pub fn queue_get_timestamp_calibration<A: HalApi>(
+        &self,
+        queue_id: QueueId,
+    ) -> Result<Option<wgt::TimestampCalibration>, InvalidQueue> {
+        let hub = A::hub(self);
+        match hub.queues.get(queue_id) {
+            Ok(queue) => Ok(unsafe { queue.raw.as_ref().unwrap().get_timestamp_calibration() }),
+            Err(_) => Err(InvalidQueue),
+        }
+    }
+
+    /// Return whether the submission `submission_index`, and all submissions
+    /// before it, have completed on every queue of the device of `queue_id`.
+    ///
+    /// This doesn't map buffers or call any callbacks, see [`Global::device_poll`].
+    pub fn queue_is_submission_complete<A: HalApi>(
+        &self,
+        queue_id: QueueId,
+        submission_index: WrappedSubmissionIndex,
+    ) -> Result<bool, WaitIdleError> {
+        let hub = A::hub(self);
+        let queue = hub.queues.get(queue_id).map_err(|_| DeviceError::Invalid)?;
+        let device = queue.device.as_ref().unwrap();
+        Self::check_submission_index(hub, device.as_info().id(), submission_index)?;
+
+        let fence = device.fence.read();
+        let last_done_index = device.get_last_done_index(fence.as_ref().unwrap())?;
+        Ok(last_done_index >= submission_index.index)
+    }
+
     pub fn queue_on_submitted_work_done<A: HalApi>(
         &self,
         queue_id: QueueId,
@@ -1603,12 +3114,13 @@ impl Global {
         //TODO: flush pending writes
         let hub = A::hub(self);
         match hub.queues.get(queue_id) {
-            Ok(queue) => queue
-                .device
-                .as_ref()
-                .unwrap()
-                .lock_life()
-                .add_work_done_closure(closure),
+            Ok(queue) => {
+                let device = queue.device.as_ref().unwrap();
+                // The closure belongs to the last submission, which may be a
+                // pending batch.
+                self.flush_submit_batch_or_log::<A>(device);
+                device.lock_life().add_work_done_closure(closure)
+            }
             Err(_) => return Err(InvalidQueue),
         }
         Ok(())
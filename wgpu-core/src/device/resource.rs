@@ -4,7 +4,7 @@ use crate::{
     binding_model::{self, BindGroup, BindGroupLayout, BindGroupLayoutEntryError},
     command, conv,
     device::{
-        bgl,
+        bgl, compile,
         life::{LifetimeTracker, WaitIdleError},
         queue::PendingWrites,
         AttachmentData, DeviceLostInvocation, MissingDownlevelFlags, MissingFeatures,
@@ -24,8 +24,8 @@ use crate::{
     pool::ResourcePool,
     registry::Registry,
     resource::{
-        self, Buffer, QuerySet, Resource, ResourceInfo, ResourceType, Sampler, Texture,
-        TextureView, TextureViewNotRenderableReason,
+        self, Buffer, MemoryHeap, PlacementError, QuerySet, Resource, ResourceInfo, ResourceType,
+        Sampler, Texture, TextureView, TextureViewNotRenderableReason, TimelineSemaphore,
     },
     resource_log,
     snatch::{SnatchGuard, SnatchLock, Snatchable},
@@ -53,9 +53,10 @@ use std::{
     iter,
     num::NonZeroU32,
     sync::{
-        atomic::{AtomicBool, AtomicU64, Ordering},
+        atomic::{AtomicBool, AtomicU64, AtomicU8, Ordering},
         Arc, Weak,
     },
+    time::Duration,
 };
 
 use super::{
@@ -104,6 +105,23 @@ pub struct Device<A: HalApi> {
     // NOTE: if both are needed, the `snatchable_lock` must be consistently acquired before the
     // `fence` lock to avoid deadlocks.
     pub(crate) fence: RwLock<Option<A::Fence>>,
+    /// Index of the last submission made to the primary queue, the one
+    /// signaling `fence`.
+    pub(crate) last_primary_submission_index: AtomicU64,
+    /// Queues created in addition to the primary one, as requested by
+    /// [`wgt::Limits::max_additional_queues`].
+    ///
+    /// All queues share `active_submission_index`, but every queue signals
+    /// its own fence. Must be locked after `fence`.
+    pub(crate) additional_queues: Mutex<Vec<AdditionalQueue<A>>>,
+    /// Queue that the uploads of `Queue::write_buffer` and `write_texture` go
+    /// to, on adapters that have one. See [`queue::TransferWrites`].
+    pub(crate) transfer_queue: Option<TransferQueue<A>>,
+    /// Submissions deferred by a queue in batching mode, see
+    /// [`Global::queue_set_submit_batching`](crate::global::Global::queue_set_submit_batching).
+    ///
+    /// Must be locked before anything a submission locks.
+    pub(crate) submit_batch: Mutex<Option<queue::SubmitBatch>>,
     pub(crate) snatchable_lock: SnatchLock,
 
     /// Is this device valid? Valid is closely associated with "lose the device",
@@ -137,11 +155,38 @@ pub struct Device<A: HalApi> {
     pub(crate) features: wgt::Features,
     pub(crate) downlevel: wgt::DownlevelCapabilities,
     pub(crate) instance_flags: wgt::InstanceFlags,
+    pub(crate) flags: wgt::DeviceFlags,
+    /// The [`wgt::ValidationLevel`] of the device, which can be changed at any
+    /// time with [`Global::device_set_validation_level`](crate::global::Global::device_set_validation_level).
+    validation_level: AtomicU8,
     pub(crate) pending_writes: Mutex<Option<PendingWrites<A>>>,
     pub(crate) deferred_destroy: Mutex<Vec<DeferredDestroy<A>>>,
     #[cfg(feature = "trace")]
     pub(crate) trace: Mutex<Option<trace::Trace>>,
     pub(crate) usage_scopes: UsageScopePool<A>,
+    /// The thread polling this device, if it was created with
+    /// [`wgt::DeviceFlags::BACKGROUND_POLL`].
+    background_poll: OnceCell<std::thread::Thread>,
+    /// How long to wait for pipelines to compile, see
+    /// [`Global::device_set_pipeline_compile_timeout`](crate::global::Global::device_set_pipeline_compile_timeout).
+    pub(crate) pipeline_compile_timeout: Mutex<Option<Duration>>,
+}
+
+/// A queue of a [`Device`] besides its primary one.
+pub(crate) struct AdditionalQueue<A: HalApi> {
+    /// The raw queue, while it isn't owned by a [`Queue`].
+    pub(crate) raw: Option<A::Queue>,
+    /// Fence signaled with the index of each submission made to this queue.
+    pub(crate) fence: A::Fence,
+    /// Index of the last submission made to this queue.
+    pub(crate) last_submission_index: SubmissionIndex,
+}
+
+/// The queue of [`Device::transfer_queue`].
+pub(crate) struct TransferQueue<A: HalApi> {
+    pub(crate) raw: A::Queue,
+    /// Encoders for `raw`, which can't use those made for the other queues.
+    pub(crate) command_allocator: command::CommandAllocator<A>,
 }
 
 pub(crate) enum DeferredDestroy<A: HalApi> {
@@ -170,9 +215,19 @@ impl<A: HalApi> Drop for Device<A> {
         unsafe {
             raw.destroy_buffer(self.zero_buffer.take().unwrap());
             raw.destroy_fence(self.fence.write().take().unwrap());
+            for additional in self.additional_queues.lock().drain(..) {
+                raw.destroy_fence(additional.fence);
+                raw.destroy_queue(additional.raw.unwrap());
+            }
+            if let Some(transfer_queue) = self.transfer_queue.take() {
+                transfer_queue.command_allocator.dispose(&raw);
+                raw.destroy_queue(transfer_queue.raw);
+            }
             let queue = self.queue_to_drop.take().unwrap();
             raw.exit(queue);
         }
+        // Let the background poll thread notice that the device is gone.
+        self.wake_background_poll();
     }
 }
 
@@ -206,12 +261,31 @@ impl<A: HalApi> Device<A> {
             Err(MissingDownlevelFlags(flags))
         }
     }
+
+    pub(crate) fn validation_level(&self) -> wgt::ValidationLevel {
+        match self.validation_level.load(Ordering::Relaxed) {
+            0 => wgt::ValidationLevel::Minimal,
+            1 => wgt::ValidationLevel::Standard,
+            _ => wgt::ValidationLevel::Full,
+        }
+    }
+
+    pub(crate) fn set_validation_level(&self, level: wgt::ValidationLevel) {
+        self.validation_level.store(level as u8, Ordering::Relaxed);
+    }
+
+    /// Whether the commands recorded into render and compute passes are validated.
+    pub(crate) fn validates_passes(&self) -> bool {
+        self.validation_level() == wgt::ValidationLevel::Full
+    }
 }
 
 impl<A: HalApi> Device<A> {
     pub(crate) fn new(
         raw_device: A::Device,
         raw_queue: &A::Queue,
+        raw_additional_queues: Vec<A::Queue>,
+        raw_transfer_queue: Option<A::Queue>,
         adapter: &Arc<Adapter<A>>,
         desc: &DeviceDescriptor,
         trace_path: Option<&std::path::Path>,
@@ -223,6 +297,18 @@ impl<A: HalApi> Device<A> {
         }
         let fence =
             unsafe { raw_device.create_fence() }.map_err(|_| CreateDeviceError::OutOfMemory)?;
+        let additional_queues = raw_additional_queues
+            .into_iter()
+            .map(|raw| {
+                let fence = unsafe { raw_device.create_fence() }
+                    .map_err(|_| CreateDeviceError::OutOfMemory)?;
+                Ok(AdditionalQueue {
+                    raw: Some(raw),
+                    fence,
+                    last_submission_index: 0,
+                })
+            })
+            .collect::<Result<Vec<_>, CreateDeviceError>>()?;
 
         let command_allocator = command::CommandAllocator::new();
         let pending_encoder = command_allocator
@@ -230,6 +316,23 @@ impl<A: HalApi> Device<A> {
             .map_err(|_| CreateDeviceError::OutOfMemory)?;
         let mut pending_writes = queue::PendingWrites::<A>::new(pending_encoder);
 
+        let transfer_queue = match raw_transfer_queue {
+            Some(raw) => {
+                let command_allocator = command::CommandAllocator::new();
+                let encoder = command_allocator
+                    .acquire_encoder(&raw_device, &raw)
+                    .map_err(|_| CreateDeviceError::OutOfMemory)?;
+                let fence = unsafe { raw_device.create_fence() }
+                    .map_err(|_| CreateDeviceError::OutOfMemory)?;
+                pending_writes.transfer = Some(queue::TransferWrites::new(encoder, fence));
+                Some(TransferQueue {
+                    raw,
+                    command_allocator,
+                })
+            }
+            None => None,
+        };
+
         // Create zeroed buffer used for texture clears.
         let zero_buffer = unsafe {
             raw_device
@@ -261,7 +364,10 @@ impl<A: HalApi> Device<A> {
         }
 
         let alignments = adapter.raw.capabilities.alignments.clone();
-        let downlevel = adapter.raw.capabilities.downlevel.clone();
+        let mut downlevel = adapter.raw.capabilities.downlevel.clone();
+        if desc.flags.contains(wgt::DeviceFlags::COMPATIBILITY_MODE) {
+            downlevel.flags &= wgt::DownlevelFlags::compatibility_mode();
+        }
 
         Ok(Self {
             raw: Some(raw_device),
@@ -273,6 +379,10 @@ impl<A: HalApi> Device<A> {
             command_allocator,
             active_submission_index: AtomicU64::new(0),
             fence: RwLock::new(rank::DEVICE_FENCE, Some(fence)),
+            last_primary_submission_index: AtomicU64::new(0),
+            additional_queues: Mutex::new(rank::DEVICE_ADDITIONAL_QUEUES, additional_queues),
+            transfer_queue,
+            submit_batch: Mutex::new(rank::DEVICE_SUBMIT_BATCH, None),
             snatchable_lock: unsafe { SnatchLock::new(rank::DEVICE_SNATCHABLE_LOCK) },
             valid: AtomicBool::new(true),
             trackers: Mutex::new(rank::DEVICE_TRACKERS, Tracker::new()),
@@ -288,6 +398,8 @@ impl<A: HalApi> Device<A> {
                 rank::DEVICE_TRACE,
                 trace_path.and_then(|path| match trace::Trace::new(path) {
                     Ok(mut trace) => {
+                        trace.shaders_only =
+                            desc.flags.contains(wgt::DeviceFlags::TRACE_SHADERS_ONLY);
                         trace.add(trace::Action::Init {
                             desc: desc.clone(),
                             backend: A::VARIANT,
@@ -305,9 +417,13 @@ impl<A: HalApi> Device<A> {
             features: desc.required_features,
             downlevel,
             instance_flags,
+            flags: desc.flags,
+            validation_level: AtomicU8::new(desc.validation.level() as u8),
             pending_writes: Mutex::new(rank::DEVICE_PENDING_WRITES, Some(pending_writes)),
             deferred_destroy: Mutex::new(rank::DEVICE_DEFERRED_DESTROY, Vec::new()),
             usage_scopes: Mutex::new(rank::DEVICE_USAGE_SCOPES, Default::default()),
+            background_poll: OnceCell::new(),
+            pipeline_compile_timeout: Mutex::new(rank::DEVICE_PIPELINE_COMPILE_TIMEOUT, None),
         })
     }
 
@@ -319,6 +435,69 @@ impl<A: HalApi> Device<A> {
         assert!(self.queue_to_drop.set(queue).is_ok());
     }
 
+    /// Give the raw queue of the additional queue `index` back to the device,
+    /// so that it can be handed out again.
+    pub(crate) fn release_additional_queue(&self, index: usize, queue: A::Queue) {
+        let mut additional_queues = self.additional_queues.lock();
+        assert!(additional_queues[index].raw.replace(queue).is_none());
+    }
+
+    /// Return the index of the last submission known to have completed.
+    ///
+    /// Submissions to different queues may complete out of order, so this
+    /// only considers a submission done once everything before it on every
+    /// queue is done too. With a single queue, this is just the value of
+    /// `fence`.
+    pub(crate) fn get_last_done_index(
+        &self,
+        fence: &A::Fence,
+    ) -> Result<SubmissionIndex, DeviceError> {
+        let raw = self.raw();
+        let mut last_done = self.active_submission_index.load(Ordering::Relaxed);
+        let value = unsafe { raw.get_fence_value(fence) }?;
+        if value < self.last_primary_submission_index.load(Ordering::Relaxed) {
+            last_done = last_done.min(value);
+        }
+        for additional in self.additional_queues.lock().iter() {
+            let value = unsafe { raw.get_fence_value(&additional.fence) }?;
+            if value < additional.last_submission_index {
+                last_done = last_done.min(value);
+            }
+        }
+        Ok(last_done)
+    }
+
+    /// Wait until the submission `index`, and all submissions before it, have
+    /// completed on every queue.
+    ///
+    /// Return `false` if the wait timed out.
+    pub(crate) fn wait_for_index(
+        &self,
+        fence: &A::Fence,
+        index: SubmissionIndex,
+        timeout_ms: u32,
+    ) -> Result<bool, DeviceError> {
+        let raw = self.raw();
+        let primary_index = index.min(self.last_primary_submission_index.load(Ordering::Relaxed));
+        if !unsafe { raw.wait(fence, primary_index, timeout_ms) }? {
+            return Ok(false);
+        }
+        for additional in self.additional_queues.lock().iter() {
+            let value = index.min(additional.last_submission_index);
+            if !unsafe { raw.wait(&additional.fence, value, timeout_ms) }? {
+                return Ok(false);
+            }
+        }
+        Ok(true)
+    }
+
+    /// The pool of encoders for the transfer queue, if the device has one.
+    pub(crate) fn transfer_command_allocator(&self) -> Option<&command::CommandAllocator<A>> {
+        self.transfer_queue
+            .as_ref()
+            .map(|transfer_queue| &transfer_queue.command_allocator)
+    }
+
     pub(crate) fn lock_life<'a>(&'a self) -> MutexGuard<'a, LifetimeTracker<A>> {
         self.life_tracker.lock()
     }
@@ -399,39 +578,47 @@ impl<A: HalApi> Device<A> {
         &'this self,
         fence: &A::Fence,
         maintain: wgt::Maintain<queue::WrappedSubmissionIndex>,
+        work: wgt::MaintainWork,
         snatch_guard: SnatchGuard,
     ) -> Result<(UserClosures, bool), WaitIdleError> {
         profiling::scope!("Device::maintain");
-        let last_done_index = if maintain.is_wait() {
-            let index_to_wait_for = match maintain {
-                wgt::Maintain::WaitForSubmissionIndex(submission_index) => {
-                    // We don't need to check to see if the queue id matches
-                    // as we already checked this from inside the poll call.
-                    submission_index.index
-                }
-                _ => self.active_submission_index.load(Ordering::Relaxed),
-            };
-            unsafe {
-                self.raw
-                    .as_ref()
-                    .unwrap()
-                    .wait(fence, index_to_wait_for, CLEANUP_WAIT_MS)
-                    .map_err(DeviceError::from)?
-            };
-            index_to_wait_for
-        } else {
-            unsafe {
-                self.raw
-                    .as_ref()
-                    .unwrap()
-                    .get_fence_value(fence)
-                    .map_err(DeviceError::from)?
+        let last_done_index = match maintain {
+            // We don't need to check to see if the queue id matches
+            // as we already checked this from inside the poll call.
+            wgt::Maintain::WaitForSubmissionIndex(submission_index) => {
+                self.wait_for_index(fence, submission_index.index, CLEANUP_WAIT_MS)?;
+                submission_index.index
+            }
+            wgt::Maintain::Wait => {
+                let index_to_wait_for = self.active_submission_index.load(Ordering::Relaxed);
+                self.wait_for_index(fence, index_to_wait_for, CLEANUP_WAIT_MS)?;
+                index_to_wait_for
+            }
+            wgt::Maintain::Poll => self.get_last_done_index(fence)?,
+            wgt::Maintain::PollForSubmissionIndex(submission_index) => {
+                self.get_last_done_index(fence)?.min(submission_index.index)
             }
         };
 
+        let deterministic = self
+            .flags
+            .contains(wgt::DeviceFlags::DETERMINISTIC_CALLBACKS);
+        let submissions = work.contains(wgt::MaintainWork::SUBMISSIONS);
+        let mappings = work.contains(wgt::MaintainWork::MAPPINGS);
+
         let mut life_tracker = self.lock_life();
-        let submission_closures =
-            life_tracker.triage_submissions(last_done_index, &self.command_allocator);
+        // In deterministic mode, submissions are triaged together with their
+        // mappings below, once the suspected and mapped buffers are sorted out.
+        let submission_closures = if deterministic || !submissions {
+            SmallVec::new()
+        } else {
+            life_tracker.triage_submissions(
+                last_done_index,
+                &self.command_allocator,
+                self.transfer_command_allocator(),
+                self.raw(),
+            )
+        };
 
         {
             // Normally, `temp_suspected` exists only to save heap
@@ -439,20 +626,53 @@ impl<A: HalApi> Device<A> {
             // call, and cleared by the end. But `Global::queue_submit` is
             // fallible; if it exits early, it may leave some resources in
             // `temp_suspected`.
-            let temp_suspected = self
-                .temp_suspected
-                .lock()
-                .replace(ResourceMaps::new())
-                .unwrap();
+            if work.contains(wgt::MaintainWork::CLEANUP) {
+                let temp_suspected = self
+                    .temp_suspected
+                    .lock()
+                    .replace(ResourceMaps::new())
+                    .unwrap();
 
-            life_tracker.suspected_resources.extend(temp_suspected);
+                life_tracker.suspected_resources.extend(temp_suspected);
 
-            life_tracker.triage_suspected(&self.trackers);
-            life_tracker.triage_mapped();
+                life_tracker.triage_suspected(&self.trackers);
+            }
+            if mappings {
+                life_tracker.triage_mapped();
+            }
         }
 
-        let mapping_closures =
-            life_tracker.handle_mapping(self.raw(), &self.trackers, &snatch_guard);
+        let purge = self
+            .flags
+            .contains(wgt::DeviceFlags::RETRY_FAILED_MAPPINGS)
+            .then(|| life::MappingPurge {
+                command_allocator: &self.command_allocator,
+                transfer_command_allocator: self.transfer_command_allocator(),
+            });
+        let (mapping_closures, ordered_closures) = if deterministic && !(submissions && mappings) {
+            (Vec::new(), Vec::new())
+        } else if deterministic {
+            let ordered = life_tracker.triage_submissions_in_order(
+                last_done_index,
+                &self.command_allocator,
+                self.transfer_command_allocator(),
+                self.raw(),
+                &self.trackers,
+                &snatch_guard,
+                purge.as_ref(),
+            );
+            (Vec::new(), ordered)
+        } else if mappings {
+            let mappings = life_tracker.handle_mapping(
+                self.raw(),
+                &self.trackers,
+                &snatch_guard,
+                purge.as_ref(),
+            );
+            (mappings, Vec::new())
+        } else {
+            (Vec::new(), Vec::new())
+        };
 
         let queue_empty = life_tracker.queue_empty();
 
@@ -490,11 +710,102 @@ impl<A: HalApi> Device<A> {
         let closures = UserClosures {
             mappings: mapping_closures,
             submissions: submission_closures,
+            ordered: ordered_closures,
             device_lost_invocations,
         };
         Ok((closures, queue_empty))
     }
 
+    /// Prepare `closures` to be fired by a caller other than [`Global::device_poll`].
+    ///
+    /// If this device was created with [`wgt::DeviceFlags::DETERMINISTIC_CALLBACKS`],
+    /// `closures` are held back until the device is next polled, and nothing is
+    /// returned. Otherwise, `closures` are returned unchanged.
+    ///
+    /// [`Global::device_poll`]: crate::global::Global::device_poll
+    pub(crate) fn defer_closures(&self, closures: UserClosures) -> UserClosures {
+        if !self
+            .flags
+            .contains(wgt::DeviceFlags::DETERMINISTIC_CALLBACKS)
+        {
+            return closures;
+        }
+        self.lock_life()
+            .deferred_closures
+            .extend(closures.into_ordered());
+        UserClosures::default()
+    }
+
+    /// Take the closures held back by [`Device::defer_closures`].
+    pub(crate) fn take_deferred_closures(&self) -> UserClosures {
+        std::mem::take(&mut self.lock_life().deferred_closures)
+    }
+
+    /// Spawn the thread polling this device, if it was created with
+    /// [`wgt::DeviceFlags::BACKGROUND_POLL`].
+    ///
+    /// The thread only holds a weak reference to the device while it waits,
+    /// and exits once the device is dropped or lost.
+    pub(crate) fn start_background_poll(self: &Arc<Self>) {
+        if !self.flags.contains(wgt::DeviceFlags::BACKGROUND_POLL) {
+            return;
+        }
+        #[cfg(not(target_arch = "wasm32"))]
+        {
+            // How long to wait between polls while there is no work in flight.
+            // Submissions and mappings wake the thread up early.
+            const IDLE_TIMEOUT: std::time::Duration = std::time::Duration::from_millis(100);
+            // How long to wait between polls while there is work in flight.
+            const BUSY_TIMEOUT: std::time::Duration = std::time::Duration::from_millis(1);
+
+            let device = Arc::downgrade(self);
+            let spawned = std::thread::Builder::new()
+                .name("wgpu background poll".to_string())
+                .spawn(move || loop {
+                    let Some(device) = device.upgrade() else {
+                        break;
+                    };
+                    if !device.is_valid() {
+                        break;
+                    }
+                    let queue_empty = match crate::global::Global::poll_single_device(
+                        &device,
+                        wgt::Maintain::Poll,
+                        wgt::MaintainWork::all(),
+                    ) {
+                        Ok(poll) => {
+                            poll.closures.fire();
+                            poll.queue_empty
+                        }
+                        Err(err) => {
+                            log::error!("Background poll of device failed: {err}");
+                            break;
+                        }
+                    };
+                    drop(device);
+                    std::thread::park_timeout(if queue_empty {
+                        IDLE_TIMEOUT
+                    } else {
+                        BUSY_TIMEOUT
+                    });
+                });
+            match spawned {
+                Ok(handle) => {
+                    let _ = self.background_poll.set(handle.thread().clone());
+                }
+                Err(err) => log::error!("Unable to spawn the background poll thread: {err}"),
+            }
+        }
+    }
+
+    /// Wake the thread polling this device up, to look for work that just
+    /// got submitted or mapped.
+    pub(crate) fn wake_background_poll(&self) {
+        if let Some(thread) = self.background_poll.get() {
+            thread.unpark();
+        }
+    }
+
     pub(crate) fn untrack(&self, trackers: &Tracker<A>) {
         let mut temp_suspected = self
             .temp_suspected
@@ -565,13 +876,12 @@ impl<A: HalApi> Device<A> {
         self.lock_life().suspected_resources.extend(temp_suspected);
     }
 
-    pub(crate) fn create_buffer(
-        self: &Arc<Self>,
-        desc: &resource::BufferDescriptor,
-        transient: bool,
-    ) -> Result<Buffer<A>, resource::CreateBufferError> {
-        debug_assert_eq!(self.as_info().id().backend(), A::VARIANT);
-
+    /// Validate `desc` and return the descriptor to create the raw buffer with.
+    pub(crate) fn buffer_hal_descriptor<'a>(
+        &self,
+        desc: &'a resource::BufferDescriptor,
+        memory_flags: hal::MemoryFlags,
+    ) -> Result<hal::BufferDescriptor<'a>, resource::CreateBufferError> {
         if desc.size > self.limits.max_buffer_size {
             return Err(resource::CreateBufferError::MaxBufferSize {
                 requested: desc.size,
@@ -599,6 +909,10 @@ impl<A: HalApi> Device<A> {
         if !self
             .features
             .contains(wgt::Features::MAPPABLE_PRIMARY_BUFFERS)
+            && !self
+                .downlevel
+                .flags
+                .contains(wgt::DownlevelFlags::UNIFIED_MEMORY_MAPPABLE_BUFFERS)
         {
             use wgt::BufferUsages as Bu;
             let write_mismatch = desc.usage.contains(Bu::MAP_WRITE)
@@ -640,25 +954,69 @@ impl<A: HalApi> Device<A> {
             actual_size
         };
 
-        let mut memory_flags = hal::MemoryFlags::empty();
-        memory_flags.set(hal::MemoryFlags::TRANSIENT, transient);
+        let mut memory_flags = memory_flags;
+        if self.features.contains(wgt::Features::BUFFER_DEVICE_ADDRESS)
+            && desc.usage.contains(wgt::BufferUsages::STORAGE)
+        {
+            memory_flags |= hal::MemoryFlags::DEVICE_ADDRESS;
+        }
 
-        let hal_desc = hal::BufferDescriptor {
+        Ok(hal::BufferDescriptor {
             label: desc.label.to_hal(self.instance_flags),
             size: aligned_size,
             usage,
             memory_flags,
+        })
+    }
+
+    /// Create a buffer, placed at `offset` in a memory heap if `heap` is given.
+    pub(crate) fn create_buffer(
+        self: &Arc<Self>,
+        desc: &resource::BufferDescriptor,
+        memory_flags: hal::MemoryFlags,
+        heap: Option<(&Arc<MemoryHeap<A>>, wgt::BufferAddress)>,
+    ) -> Result<Buffer<A>, resource::CreateBufferError> {
+        debug_assert_eq!(self.as_info().id().backend(), A::VARIANT);
+
+        let hal_desc = self.buffer_hal_descriptor(desc, memory_flags)?;
+        let (buffer, placement) = match heap {
+            None => {
+                let buffer =
+                    unsafe { self.raw().create_buffer(&hal_desc) }.map_err(DeviceError::from)?;
+                (buffer, None)
+            }
+            Some((heap, offset)) => {
+                let requirements = unsafe { self.raw().get_buffer_memory_requirements(&hal_desc) }
+                    .map_err(DeviceError::from)?;
+                let placement = heap.place(offset, requirements)?;
+                let buffer = unsafe {
+                    self.raw()
+                        .create_placed_buffer(&hal_desc, heap.raw(), offset)
+                }
+                .map_err(DeviceError::from)?;
+                (buffer, Some(placement))
+            }
         };
-        let buffer = unsafe { self.raw().create_buffer(&hal_desc) }.map_err(DeviceError::from)?;
+
+        let aligned_size = hal_desc.size;
+        let sparse = hal_desc.memory_flags.contains(hal::MemoryFlags::SPARSE);
+        let mut initialization_status = BufferInitTracker::new(aligned_size);
+        if sparse {
+            // There is no memory to initialize until pages are committed.
+            initialization_status.drain(0..aligned_size);
+        }
 
         Ok(Buffer {
             raw: Snatchable::new(buffer),
             device: self.clone(),
             usage: desc.usage,
             size: desc.size,
+            sparse,
+            placement,
+            imported: false,
             initialization_status: RwLock::new(
                 rank::BUFFER_INITIALIZATION_STATUS,
-                BufferInitTracker::new(aligned_size),
+                initialization_status,
             ),
             sync_mapped_writes: Mutex::new(rank::BUFFER_SYNC_MAPPED_WRITES, None),
             map_state: Mutex::new(rank::BUFFER_MAP_STATE, resource::BufferMapState::Idle),
@@ -670,6 +1028,61 @@ impl<A: HalApi> Device<A> {
         })
     }
 
+    pub(crate) fn create_sparse_buffer(
+        self: &Arc<Self>,
+        desc: &resource::BufferDescriptor,
+    ) -> Result<Buffer<A>, resource::CreateBufferError> {
+        self.require_features(wgt::Features::SPARSE_BUFFER)?;
+
+        if desc.mapped_at_creation
+            || desc
+                .usage
+                .intersects(wgt::BufferUsages::MAP_READ | wgt::BufferUsages::MAP_WRITE)
+        {
+            return Err(resource::CreateBufferError::MappableSparse(desc.usage));
+        }
+
+        self.create_buffer(desc, hal::MemoryFlags::SPARSE, None)
+    }
+
+    pub(crate) fn create_placed_buffer(
+        self: &Arc<Self>,
+        desc: &resource::BufferDescriptor,
+        heap: &Arc<MemoryHeap<A>>,
+        offset: wgt::BufferAddress,
+    ) -> Result<Buffer<A>, resource::CreateBufferError> {
+        self.require_features(wgt::Features::PLACED_RESOURCES)
+            .map_err(PlacementError::from)?;
+
+        if heap.device.as_info().id() != self.as_info().id() {
+            return Err(DeviceError::WrongDevice.into());
+        }
+
+        if desc.mapped_at_creation
+            || desc
+                .usage
+                .intersects(wgt::BufferUsages::MAP_READ | wgt::BufferUsages::MAP_WRITE)
+        {
+            return Err(PlacementError::Mappable(desc.usage).into());
+        }
+
+        self.create_buffer(desc, hal::MemoryFlags::empty(), Some((heap, offset)))
+    }
+
+    /// Return the memory a buffer created with `desc` takes up in a heap.
+    pub(crate) fn buffer_memory_requirements(
+        &self,
+        desc: &resource::BufferDescriptor,
+    ) -> Result<wgt::MemoryRequirements, resource::CreateBufferError> {
+        self.require_features(wgt::Features::PLACED_RESOURCES)
+            .map_err(PlacementError::from)?;
+
+        let hal_desc = self.buffer_hal_descriptor(desc, hal::MemoryFlags::empty())?;
+        let requirements = unsafe { self.raw().get_buffer_memory_requirements(&hal_desc) }
+            .map_err(DeviceError::from)?;
+        Ok(requirements)
+    }
+
     pub(crate) fn create_texture_from_hal(
         self: &Arc<Self>,
         hal_texture: A::Texture,
@@ -686,6 +1099,9 @@ impl<A: HalApi> Device<A> {
             desc: desc.map_label(|_| ()),
             hal_usage,
             format_features,
+            sparse: None,
+            placement: None,
+            imported: true,
             initialization_status: RwLock::new(
                 rank::TEXTURE_INITIALIZATION_STATUS,
                 TextureInitTracker::new(desc.mip_level_count, desc.array_layer_count()),
@@ -701,6 +1117,7 @@ impl<A: HalApi> Device<A> {
             clear_mode: RwLock::new(rank::TEXTURE_CLEAR_MODE, clear_mode),
             views: Mutex::new(rank::TEXTURE_VIEWS, Vec::new()),
             bind_groups: Mutex::new(rank::TEXTURE_BIND_GROUPS, Vec::new()),
+            expiry: Default::default(),
         }
     }
 
@@ -716,6 +1133,9 @@ impl<A: HalApi> Device<A> {
             device: self.clone(),
             usage: desc.usage,
             size: desc.size,
+            sparse: false,
+            placement: None,
+            imported: true,
             initialization_status: RwLock::new(
                 rank::BUFFER_INITIALIZATION_STATUS,
                 BufferInitTracker::new(0),
@@ -730,17 +1150,29 @@ impl<A: HalApi> Device<A> {
         }
     }
 
-    pub(crate) fn create_texture(
-        self: &Arc<Self>,
+    /// Validate `desc` and return the descriptor to create the raw texture
+    /// with, along with the features of its format.
+    pub(crate) fn texture_hal_descriptor<'a>(
+        &self,
         adapter: &Adapter<A>,
-        desc: &resource::TextureDescriptor,
-    ) -> Result<Texture<A>, resource::CreateTextureError> {
+        desc: &'a resource::TextureDescriptor,
+        memory_flags: hal::MemoryFlags,
+    ) -> Result<
+        (hal::TextureDescriptor<'a>, wgt::TextureFormatFeatures),
+        resource::CreateTextureError,
+    > {
         use resource::{CreateTextureError, TextureDimensionError};
 
         if desc.usage.is_empty() || desc.usage.contains_invalid_bits() {
             return Err(CreateTextureError::InvalidUsage(desc.usage));
         }
 
+        if desc.usage.contains(wgt::TextureUsages::TRANSIENT)
+            && desc.usage != wgt::TextureUsages::TRANSIENT | wgt::TextureUsages::RENDER_ATTACHMENT
+        {
+            return Err(CreateTextureError::InvalidTransientUsage(desc.usage));
+        }
+
         conv::check_texture_dimension_size(
             desc.dimension,
             desc.size,
@@ -886,14 +1318,45 @@ impl<A: HalApi> Device<A> {
             });
         }
 
-        let missing_allowed_usages = desc.usage - format_features.allowed_usages;
+        let mut hal_view_formats = vec![];
+        // Usages for shader bindings allowed by view formats that reinterpret
+        // the texture, which a texture of the format itself may not have.
+        let mut view_allowed_usages = wgt::TextureUsages::empty();
+        for format in desc.view_formats.iter() {
+            if desc.format == *format {
+                continue;
+            }
+            if desc.format.remove_srgb_suffix() != format.remove_srgb_suffix() {
+                if !desc.format.is_reinterpretable_as(*format) {
+                    return Err(CreateTextureError::InvalidViewFormat(*format, desc.format));
+                }
+                self.require_features(wgt::Features::TEXTURE_FORMAT_REINTERPRETATION)
+                    .map_err(|error| CreateTextureError::MissingFeatures(*format, error))?;
+                let view_format_features = self
+                    .describe_format_features(adapter, *format)
+                    .map_err(|error| CreateTextureError::MissingFeatures(*format, error))?;
+                view_allowed_usages |= view_format_features.allowed_usages
+                    & (wgt::TextureUsages::TEXTURE_BINDING | wgt::TextureUsages::STORAGE_BINDING);
+            }
+            hal_view_formats.push(*format);
+        }
+        if !hal_view_formats.is_empty() {
+            self.require_downlevel_flags(wgt::DownlevelFlags::VIEW_FORMATS)?;
+        }
+
+        // Any format can be transient, since that is only a hint.
+        let missing_allowed_usages = desc.usage
+            - wgt::TextureUsages::TRANSIENT
+            - format_features.allowed_usages
+            - view_allowed_usages;
         if !missing_allowed_usages.is_empty() {
             // detect downlevel incompatibilities
             let wgpu_allowed_usages = desc
                 .format
                 .guaranteed_format_features(self.features)
                 .allowed_usages;
-            let wgpu_missing_usages = desc.usage - wgpu_allowed_usages;
+            let wgpu_missing_usages =
+                desc.usage - wgt::TextureUsages::TRANSIENT - wgpu_allowed_usages;
             return Err(CreateTextureError::InvalidFormatUsages(
                 missing_allowed_usages,
                 desc.format,
@@ -901,21 +1364,12 @@ impl<A: HalApi> Device<A> {
             ));
         }
 
-        let mut hal_view_formats = vec![];
-        for format in desc.view_formats.iter() {
-            if desc.format == *format {
-                continue;
-            }
-            if desc.format.remove_srgb_suffix() != format.remove_srgb_suffix() {
-                return Err(CreateTextureError::InvalidViewFormat(*format, desc.format));
-            }
-            hal_view_formats.push(*format);
+        let sparse = memory_flags.contains(hal::MemoryFlags::SPARSE);
+        let mut hal_usage = conv::map_texture_usage_for_texture(desc, &format_features);
+        if sparse {
+            // Committed tiles are zeroed with copies.
+            hal_usage |= hal::TextureUses::COPY_DST;
         }
-        if !hal_view_formats.is_empty() {
-            self.require_downlevel_flags(wgt::DownlevelFlags::VIEW_FORMATS)?;
-        }
-
-        let hal_usage = conv::map_texture_usage_for_texture(desc, &format_features);
 
         let hal_desc = hal::TextureDescriptor {
             label: desc.label.to_hal(self.instance_flags),
@@ -925,18 +1379,82 @@ impl<A: HalApi> Device<A> {
             dimension: desc.dimension,
             format: desc.format,
             usage: hal_usage,
-            memory_flags: hal::MemoryFlags::empty(),
+            memory_flags,
             view_formats: hal_view_formats,
         };
+        Ok((hal_desc, format_features))
+    }
 
-        let raw_texture = unsafe {
-            self.raw
-                .as_ref()
-                .unwrap()
-                .create_texture(&hal_desc)
-                .map_err(DeviceError::from)?
+    /// Create a texture, placed at `offset` in a memory heap if `heap` is given.
+    pub(crate) fn create_texture(
+        self: &Arc<Self>,
+        adapter: &Adapter<A>,
+        desc: &resource::TextureDescriptor,
+        memory_flags: hal::MemoryFlags,
+        heap: Option<(&Arc<MemoryHeap<A>>, wgt::BufferAddress)>,
+    ) -> Result<Texture<A>, resource::CreateTextureError> {
+        // Placed and sparse textures are backed by memory we allocate.
+        let memory_flags = if desc.usage.contains(wgt::TextureUsages::TRANSIENT)
+            && heap.is_none()
+            && !memory_flags.contains(hal::MemoryFlags::SPARSE)
+        {
+            memory_flags | hal::MemoryFlags::MEMORYLESS
+        } else {
+            memory_flags
+        };
+        let (hal_desc, format_features) =
+            self.texture_hal_descriptor(adapter, desc, memory_flags)?;
+        let hal_usage = hal_desc.usage;
+        let sparse = memory_flags.contains(hal::MemoryFlags::SPARSE);
+
+        let (raw_texture, placement) = match heap {
+            None => {
+                let raw_texture =
+                    unsafe { self.raw().create_texture(&hal_desc) }.map_err(DeviceError::from)?;
+                (raw_texture, None)
+            }
+            Some((heap, offset)) => {
+                let requirements = unsafe { self.raw().get_texture_memory_requirements(&hal_desc) }
+                    .map_err(DeviceError::from)?;
+                let placement = heap.place(offset, requirements)?;
+                let raw_texture = unsafe {
+                    self.raw()
+                        .create_placed_texture(&hal_desc, heap.raw(), offset)
+                }
+                .map_err(DeviceError::from)?;
+                (raw_texture, Some(placement))
+            }
         };
 
+        let clear_mode = self.create_texture_clear_mode(&raw_texture, hal_usage, desc)?;
+
+        let mut texture =
+            self.create_texture_from_hal(raw_texture, hal_usage, desc, format_features, clear_mode);
+        texture.hal_usage = hal_usage;
+        texture.placement = placement;
+        texture.imported = false;
+        if sparse {
+            let snatch_guard = self.snatchable_lock.read();
+            let raw = texture.raw(&snatch_guard).unwrap();
+            texture.sparse = Some(unsafe { self.raw().get_sparse_texture_tiles(raw) });
+            drop(snatch_guard);
+            // There is no memory to initialize until tiles are committed.
+            let mut initialization_status = texture.initialization_status.write();
+            for mip in initialization_status.mips.iter_mut() {
+                mip.drain(0..desc.array_layer_count());
+            }
+        }
+        Ok(texture)
+    }
+
+    /// Create the views a texture with `hal_usage` is cleared with, or pick
+    /// another way to clear it.
+    pub(crate) fn create_texture_clear_mode(
+        &self,
+        raw_texture: &A::Texture,
+        hal_usage: hal::TextureUses,
+        desc: &resource::TextureDescriptor,
+    ) -> Result<resource::TextureClearMode<A>, DeviceError> {
         let clear_mode = if hal_usage
             .intersects(hal::TextureUses::DEPTH_STENCIL_WRITE | hal::TextureUses::COLOR_TARGET)
         {
@@ -973,6 +1491,7 @@ impl<A: HalApi> Device<A> {
                                     base_array_layer: array_layer,
                                     array_layer_count: Some(1),
                                 },
+                                swizzle: wgt::TextureComponentSwizzle::IDENTITY,
                             };
                             clear_views.push(Some(
                                 unsafe { self.raw().create_texture_view(&raw_texture, &desc) }
@@ -999,11 +1518,68 @@ impl<A: HalApi> Device<A> {
         } else {
             resource::TextureClearMode::BufferCopy
         };
+        Ok(clear_mode)
+    }
 
-        let mut texture =
-            self.create_texture_from_hal(raw_texture, hal_usage, desc, format_features, clear_mode);
-        texture.hal_usage = hal_usage;
-        Ok(texture)
+    pub(crate) fn create_sparse_texture(
+        self: &Arc<Self>,
+        adapter: &Adapter<A>,
+        desc: &resource::TextureDescriptor,
+    ) -> Result<Texture<A>, resource::CreateTextureError> {
+        self.require_features(wgt::Features::SPARSE_TEXTURE)
+            .map_err(resource::CreateTextureError::MissingSparseFeature)?;
+
+        if desc.dimension == wgt::TextureDimension::D1
+            || desc.sample_count != 1
+            || desc.format.is_depth_stencil_format()
+            || desc.format.is_multi_planar_format()
+        {
+            return Err(resource::CreateTextureError::InvalidSparse {
+                dimension: desc.dimension,
+                format: desc.format,
+                sample_count: desc.sample_count,
+            });
+        }
+
+        self.create_texture(adapter, desc, hal::MemoryFlags::SPARSE, None)
+    }
+
+    pub(crate) fn create_placed_texture(
+        self: &Arc<Self>,
+        adapter: &Adapter<A>,
+        desc: &resource::TextureDescriptor,
+        heap: &Arc<MemoryHeap<A>>,
+        offset: wgt::BufferAddress,
+    ) -> Result<Texture<A>, resource::CreateTextureError> {
+        self.require_features(wgt::Features::PLACED_RESOURCES)
+            .map_err(PlacementError::from)?;
+
+        if heap.device.as_info().id() != self.as_info().id() {
+            return Err(DeviceError::WrongDevice.into());
+        }
+
+        self.create_texture(
+            adapter,
+            desc,
+            hal::MemoryFlags::empty(),
+            Some((heap, offset)),
+        )
+    }
+
+    /// Return the memory a texture created with `desc` takes up in a heap.
+    pub(crate) fn texture_memory_requirements(
+        &self,
+        adapter: &Adapter<A>,
+        desc: &resource::TextureDescriptor,
+    ) -> Result<wgt::MemoryRequirements, resource::CreateTextureError> {
+        self.require_features(wgt::Features::PLACED_RESOURCES)
+            .map_err(PlacementError::from)?;
+
+        let (hal_desc, _) =
+            self.texture_hal_descriptor(adapter, desc, hal::MemoryFlags::empty())?;
+        let requirements = unsafe { self.raw().get_texture_memory_requirements(&hal_desc) }
+            .map_err(DeviceError::from)?;
+        Ok(requirements)
     }
 
     pub(crate) fn create_texture_view(
@@ -1091,6 +1667,10 @@ impl<A: HalApi> Device<A> {
             });
         }
 
+        if !desc.swizzle.is_identity() {
+            self.require_features(wgt::Features::TEXTURE_COMPONENT_SWIZZLE)?;
+        }
+
         // check if multisampled texture is seen as anything but 2D
         if texture.desc.sample_count > 1 && resolved_dimension != wgt::TextureViewDimension::D2 {
             return Err(
@@ -1129,6 +1709,7 @@ impl<A: HalApi> Device<A> {
                 }
             }
             TextureViewDimension::CubeArray => {
+                self.require_downlevel_flags(wgt::DownlevelFlags::CUBE_ARRAY_TEXTURES)?;
                 if resolved_array_layer_count % 6 != 0 {
                     return Err(
                         resource::CreateTextureViewError::InvalidCubemapArrayTextureDepth {
@@ -1183,6 +1764,34 @@ impl<A: HalApi> Device<A> {
             });
         };
 
+        // Views that reinterpret the texture have the features of their own
+        // format, see `Features::TEXTURE_FORMAT_REINTERPRETATION`.
+        let reinterprets = desc.range.aspect == wgt::TextureAspect::All
+            && resolved_format.remove_srgb_suffix() != texture.desc.format.remove_srgb_suffix();
+        let format_features = if reinterprets {
+            let format_features = self.describe_format_features(&self.adapter, resolved_format)?;
+            if texture.desc.format.is_compressed()
+                && !resolved_format.is_compressed()
+                && (resolved_mip_level_count != 1 || resolved_array_layer_count != 1)
+            {
+                return Err(
+                    resource::CreateTextureViewError::InvalidBlockTexelViewRange {
+                        mip_level_count: resolved_mip_level_count,
+                        array_layer_count: resolved_array_layer_count,
+                    },
+                );
+            }
+            format_features
+        } else {
+            texture.format_features
+        };
+        // Textures with view formats that reinterpret them may have usages their
+        // own format doesn't allow.
+        let has_reinterpreting_view_formats =
+            texture.desc.view_formats.iter().any(|format| {
+                format.remove_srgb_suffix() != texture.desc.format.remove_srgb_suffix()
+            });
+
         // https://gpuweb.github.io/gpuweb/#abstract-opdef-renderable-texture-view
         let render_extent = 'b: loop {
             if !texture
@@ -1193,6 +1802,14 @@ impl<A: HalApi> Device<A> {
                 break 'b Err(TextureViewNotRenderableReason::Usage(texture.desc.usage));
             }
 
+            if reinterprets
+                && !format_features
+                    .allowed_usages
+                    .contains(wgt::TextureUsages::RENDER_ATTACHMENT)
+            {
+                break 'b Err(TextureViewNotRenderableReason::Format(resolved_format));
+            }
+
             if !(resolved_dimension == TextureViewDimension::D2
                 || (self.features.contains(wgt::Features::MULTIVIEW)
                     && resolved_dimension == TextureViewDimension::D2Array))
@@ -1220,6 +1837,10 @@ impl<A: HalApi> Device<A> {
                 break 'b Err(TextureViewNotRenderableReason::Aspects(aspects));
             }
 
+            if !desc.swizzle.is_identity() {
+                break 'b Err(TextureViewNotRenderableReason::Swizzle(desc.swizzle));
+            }
+
             break 'b Ok(texture
                 .desc
                 .compute_render_extent(desc.range.base_mip_level));
@@ -1244,7 +1865,22 @@ impl<A: HalApi> Device<A> {
             } else {
                 hal::TextureUses::RESOURCE
             };
-            texture.hal_usage & mask_copy & mask_dimension & mask_mip_level
+            let mask_swizzle = if desc.swizzle.is_identity() {
+                hal::TextureUses::all()
+            } else {
+                hal::TextureUses::RESOURCE
+            };
+            let mask_format = if has_reinterpreting_view_formats {
+                conv::map_texture_usage(format_features.allowed_usages, aspects)
+            } else {
+                hal::TextureUses::all()
+            };
+            texture.hal_usage
+                & mask_copy
+                & mask_dimension
+                & mask_mip_level
+                & mask_swizzle
+                & mask_format
         };
 
         log::debug!(
@@ -1274,6 +1910,7 @@ impl<A: HalApi> Device<A> {
             dimension: resolved_dimension,
             usage,
             range: resolved_range,
+            swizzle: desc.swizzle,
         };
 
         let raw = unsafe {
@@ -1298,8 +1935,9 @@ impl<A: HalApi> Device<A> {
                 format: resolved_format,
                 dimension: resolved_dimension,
                 range: resolved_range,
+                swizzle: desc.swizzle,
             },
-            format_features: texture.format_features,
+            format_features,
             render_extent,
             samples: texture.desc.sample_count,
             selector,
@@ -1326,6 +1964,21 @@ impl<A: HalApi> Device<A> {
             self.require_features(wgt::Features::ADDRESS_MODE_CLAMP_TO_ZERO)?;
         }
 
+        if let Some(wgt::SamplerBorderColor::Custom(_)) = desc.border_color {
+            self.require_features(wgt::Features::ADDRESS_MODE_CLAMP_TO_CUSTOM_BORDER)?;
+        }
+
+        if desc.reduction_mode != wgt::SamplerReductionMode::WeightedAverage {
+            self.require_features(wgt::Features::SAMPLER_REDUCTION_MODE)?;
+            if desc.compare.is_some() {
+                return Err(
+                    resource::CreateSamplerError::InvalidReductionModeWithComparison(
+                        desc.reduction_mode,
+                    ),
+                );
+            }
+        }
+
         if desc.lod_min_clamp < 0.0 {
             return Err(resource::CreateSamplerError::InvalidLodMinClamp(
                 desc.lod_min_clamp,
@@ -1338,6 +1991,13 @@ impl<A: HalApi> Device<A> {
             });
         }
 
+        if !desc.lod_bias.is_finite() {
+            return Err(resource::CreateSamplerError::InvalidLodBias(desc.lod_bias));
+        }
+        if desc.lod_bias != 0.0 {
+            self.require_downlevel_flags(wgt::DownlevelFlags::SAMPLER_LOD_BIAS)?;
+        }
+
         if desc.anisotropy_clamp < 1 {
             return Err(resource::CreateSamplerError::InvalidAnisotropy(
                 desc.anisotropy_clamp,
@@ -1395,9 +2055,11 @@ impl<A: HalApi> Device<A> {
             min_filter: desc.min_filter,
             mipmap_filter: desc.mipmap_filter,
             lod_clamp: desc.lod_min_clamp..desc.lod_max_clamp,
+            lod_bias: desc.lod_bias,
             compare: desc.compare,
             anisotropy_clamp,
             border_color: desc.border_color,
+            reduction_mode: desc.reduction_mode,
         };
 
         let raw = unsafe {
@@ -1425,17 +2087,36 @@ impl<A: HalApi> Device<A> {
         desc: &pipeline::ShaderModuleDescriptor<'a>,
         source: pipeline::ShaderModuleSource<'a>,
     ) -> Result<pipeline::ShaderModule<A>, pipeline::CreateShaderModuleError> {
-        let (module, source) = match source {
+        let (mut module, source) = match source {
             #[cfg(feature = "wgsl")]
             pipeline::ShaderModuleSource::Wgsl(code) => {
-                profiling::scope!("naga::front::wgsl::parse_str");
-                let module = naga::front::wgsl::parse_str(&code).map_err(|inner| {
-                    pipeline::CreateShaderModuleError::Parsing(pipeline::ShaderError {
-                        source: code.to_string(),
-                        label: desc.label.as_ref().map(|l| l.to_string()),
-                        inner: Box::new(inner),
-                    })
-                })?;
+                profiling::scope!("naga::front::wgsl::Frontend::parse_with_warnings");
+                let options = naga::front::wgsl::Options {
+                    diagnostic_filters: desc
+                        .diagnostic_filters
+                        .iter()
+                        .map(validation::map_diagnostic_filter)
+                        .collect(),
+                    available_extensions: Some(self.wgsl_extensions()),
+                };
+                let (module, warnings) = naga::front::wgsl::Frontend::new_with_options(options)
+                    .parse_with_warnings(&code)
+                    .map_err(|inner| {
+                        pipeline::CreateShaderModuleError::Parsing(pipeline::ShaderError {
+                            source: code.to_string(),
+                            label: desc.label.as_ref().map(|l| l.to_string()),
+                            inner: Box::new(inner),
+                        })
+                    })?;
+                for warning in warnings {
+                    let message = warning.emit_to_string(&code);
+                    match warning.severity() {
+                        naga::front::wgsl::Severity::Info => {
+                            log::info!("Shader module {:?}: {message}", desc.label)
+                        }
+                        _ => log::warn!("Shader module {:?}: {message}", desc.label),
+                    }
+                }
                 (Cow::Owned(module), code.into_owned())
             }
             #[cfg(feature = "spirv")]
@@ -1480,20 +2161,30 @@ impl<A: HalApi> Device<A> {
             };
         }
 
+        let strip_names = self
+            .instance_flags
+            .contains(wgt::InstanceFlags::STRIP_SHADER_NAMES);
+        if strip_names {
+            let symbols = naga::proc::strip_names(module.to_mut());
+            write_shader_symbols(&symbols);
+        }
+
         profiling::scope!("naga::validate");
-        let debug_source =
-            if self.instance_flags.contains(wgt::InstanceFlags::DEBUG) && !source.is_empty() {
-                Some(hal::DebugSource {
-                    file_name: Cow::Owned(
-                        desc.label
-                            .as_ref()
-                            .map_or("shader".to_string(), |l| l.to_string()),
-                    ),
-                    source_code: Cow::Owned(source.clone()),
-                })
-            } else {
-                None
-            };
+        let debug_source = if self.instance_flags.contains(wgt::InstanceFlags::DEBUG)
+            && !strip_names
+            && !source.is_empty()
+        {
+            Some(hal::DebugSource {
+                file_name: Cow::Owned(
+                    desc.label
+                        .as_ref()
+                        .map_or("shader".to_string(), |l| l.to_string()),
+                ),
+                source_code: Cow::Owned(source.clone()),
+            })
+        } else {
+            None
+        };
 
         let info = self
             .create_validator(naga::valid::ValidationFlags::all())
@@ -1514,7 +2205,11 @@ impl<A: HalApi> Device<A> {
             debug_source,
         });
         let hal_desc = hal::ShaderModuleDescriptor {
-            label: desc.label.to_hal(self.instance_flags),
+            label: if strip_names {
+                None
+            } else {
+                desc.label.to_hal(self.instance_flags)
+            },
             runtime_checks: desc.shader_bound_checks.runtime_checks(),
         };
         let raw = match unsafe {
@@ -1546,109 +2241,19 @@ impl<A: HalApi> Device<A> {
         })
     }
 
+    /// Returns the WGSL language extensions that shaders may use on this
+    /// device.
+    #[cfg(feature = "wgsl")]
+    fn wgsl_extensions(&self) -> naga::front::wgsl::EnableExtensions {
+        validation::wgsl_extensions(self.features)
+    }
+
     /// Create a validator with the given validation flags.
     pub fn create_validator(
         self: &Arc<Self>,
         flags: naga::valid::ValidationFlags,
     ) -> naga::valid::Validator {
-        use naga::valid::Capabilities as Caps;
-        let mut caps = Caps::empty();
-        caps.set(
-            Caps::PUSH_CONSTANT,
-            self.features.contains(wgt::Features::PUSH_CONSTANTS),
-        );
-        caps.set(
-            Caps::FLOAT64,
-            self.features.contains(wgt::Features::SHADER_F64),
-        );
-        caps.set(
-            Caps::PRIMITIVE_INDEX,
-            self.features
-                .contains(wgt::Features::SHADER_PRIMITIVE_INDEX),
-        );
-        caps.set(
-            Caps::SAMPLED_TEXTURE_AND_STORAGE_BUFFER_ARRAY_NON_UNIFORM_INDEXING,
-            self.features.contains(
-                wgt::Features::SAMPLED_TEXTURE_AND_STORAGE_BUFFER_ARRAY_NON_UNIFORM_INDEXING,
-            ),
-        );
-        caps.set(
-            Caps::UNIFORM_BUFFER_AND_STORAGE_TEXTURE_ARRAY_NON_UNIFORM_INDEXING,
-            self.features.contains(
-                wgt::Features::UNIFORM_BUFFER_AND_STORAGE_TEXTURE_ARRAY_NON_UNIFORM_INDEXING,
-            ),
-        );
-        // TODO: This needs a proper wgpu feature
-        caps.set(
-            Caps::SAMPLER_NON_UNIFORM_INDEXING,
-            self.features.contains(
-                wgt::Features::SAMPLED_TEXTURE_AND_STORAGE_BUFFER_ARRAY_NON_UNIFORM_INDEXING,
-            ),
-        );
-        caps.set(
-            Caps::STORAGE_TEXTURE_16BIT_NORM_FORMATS,
-            self.features
-                .contains(wgt::Features::TEXTURE_FORMAT_16BIT_NORM),
-        );
-        caps.set(
-            Caps::MULTIVIEW,
-            self.features.contains(wgt::Features::MULTIVIEW),
-        );
-        caps.set(
-            Caps::EARLY_DEPTH_TEST,
-            self.features
-                .contains(wgt::Features::SHADER_EARLY_DEPTH_TEST),
-        );
-        caps.set(
-            Caps::SHADER_INT64,
-            self.features.contains(wgt::Features::SHADER_INT64),
-        );
-        caps.set(
-            Caps::MULTISAMPLED_SHADING,
-            self.downlevel
-                .flags
-                .contains(wgt::DownlevelFlags::MULTISAMPLED_SHADING),
-        );
-        caps.set(
-            Caps::DUAL_SOURCE_BLENDING,
-            self.features.contains(wgt::Features::DUAL_SOURCE_BLENDING),
-        );
-        caps.set(
-            Caps::CUBE_ARRAY_TEXTURES,
-            self.downlevel
-                .flags
-                .contains(wgt::DownlevelFlags::CUBE_ARRAY_TEXTURES),
-        );
-        caps.set(
-            Caps::SUBGROUP,
-            self.features
-                .intersects(wgt::Features::SUBGROUP | wgt::Features::SUBGROUP_VERTEX),
-        );
-        caps.set(
-            Caps::SUBGROUP_BARRIER,
-            self.features.intersects(wgt::Features::SUBGROUP_BARRIER),
-        );
-
-        let mut subgroup_stages = naga::valid::ShaderStages::empty();
-        subgroup_stages.set(
-            naga::valid::ShaderStages::COMPUTE | naga::valid::ShaderStages::FRAGMENT,
-            self.features.contains(wgt::Features::SUBGROUP),
-        );
-        subgroup_stages.set(
-            naga::valid::ShaderStages::VERTEX,
-            self.features.contains(wgt::Features::SUBGROUP_VERTEX),
-        );
-
-        let subgroup_operations = if caps.contains(Caps::SUBGROUP) {
-            use naga::valid::SubgroupOperationSet as S;
-            S::BASIC | S::VOTE | S::ARITHMETIC | S::BALLOT | S::SHUFFLE | S::SHUFFLE_RELATIVE
-        } else {
-            naga::valid::SubgroupOperationSet::empty()
-        };
-        let mut validator = naga::valid::Validator::new(flags, caps);
-        validator.subgroup_stages(subgroup_stages);
-        validator.subgroup_operations(subgroup_operations);
-        validator
+        validation::create_validator(self.features, self.downlevel.flags, self.flags, flags)
     }
 
     #[allow(unused_unsafe)]
@@ -1741,9 +2346,37 @@ impl<A: HalApi> Device<A> {
             No,
         }
 
+        let inline = origin == bgl::Origin::Inline;
+        if inline {
+            self.require_features(wgt::Features::INLINE_BIND_GROUPS)?;
+            if entry_map.len() > wgt::MAX_INLINE_BINDINGS as usize {
+                return Err(
+                    binding_model::CreateBindGroupLayoutError::TooManyInlineBindings(
+                        entry_map.len(),
+                    ),
+                );
+            }
+        }
+
         for entry in entry_map.values() {
             use wgt::BindingType as Bt;
 
+            if inline
+                && (entry.count.is_some()
+                    || !matches!(
+                        entry.ty,
+                        Bt::Buffer {
+                            has_dynamic_offset: false,
+                            ..
+                        }
+                    ))
+            {
+                return Err(binding_model::CreateBindGroupLayoutError::Entry {
+                    binding: entry.binding,
+                    error: BindGroupLayoutEntryError::InlineUnsupported,
+                });
+            }
+
             let mut required_features = wgt::Features::empty();
             let mut required_downlevel_flags = wgt::DownlevelFlags::empty();
             let (array_feature, writable_storage) = match entry.ty {
@@ -1857,6 +2490,18 @@ impl<A: HalApi> Device<A> {
                     )
                 }
                 Bt::AccelerationStructure => todo!(),
+                Bt::YcbcrTexture { format, .. } => {
+                    if !format.is_multi_planar_format() {
+                        return Err(binding_model::CreateBindGroupLayoutError::Entry {
+                            binding: entry.binding,
+                            error: BindGroupLayoutEntryError::YcbcrFormatNotMultiPlanar(format),
+                        });
+                    }
+                    required_features |=
+                        wgt::Features::SAMPLER_YCBCR_CONVERSION | format.required_features();
+                    // The sampler of each binding is baked into the layout.
+                    (None, WritableStorage::No)
+                }
             };
 
             // Validate the count parameter
@@ -1907,7 +2552,8 @@ impl<A: HalApi> Device<A> {
                 })?;
         }
 
-        let bgl_flags = conv::bind_group_layout_flags(self.features);
+        let mut bgl_flags = conv::bind_group_layout_flags(self.features);
+        bgl_flags.set(hal::BindGroupLayoutFlags::INLINE, inline);
 
         let hal_bindings = entry_map.values().copied().collect::<Vec<_>>();
         let label = label.to_hal(self.instance_flags);
@@ -2153,9 +2799,24 @@ impl<A: HalApi> Device<A> {
         if texture.device.as_info().id() != view.device.as_info().id() {
             return Err(DeviceError::WrongDevice.into());
         }
+        if texture.expiry.is_expired() {
+            return Err(Error::ExpiredTexture(texture_id));
+        }
 
         check_texture_usage(texture.desc.usage, pub_usage)?;
 
+        // Compatibility mode can't switch between views of some of the layers.
+        if self.flags.contains(wgt::DeviceFlags::COMPATIBILITY_MODE)
+            && pub_usage == wgt::TextureUsages::TEXTURE_BINDING
+            && view.selector.layers != (0..texture.desc.array_layer_count())
+        {
+            return Err(Error::PartialTextureViewInCompatibilityMode {
+                binding,
+                layers: view.selector.layers.clone(),
+                total: texture.desc.array_layer_count(),
+            });
+        }
+
         used_texture_ranges.push(TextureInitTrackerAction {
             texture: texture.clone(),
             range: TextureInitRange {
@@ -2185,6 +2846,9 @@ impl<A: HalApi> Device<A> {
         hub: &Hub<A>,
     ) -> Result<BindGroup<A>, binding_model::CreateBindGroupError> {
         use crate::binding_model::{BindingResource as Br, CreateBindGroupError as Error};
+        if layout.origin == bgl::Origin::Inline {
+            return Err(Error::InlineLayout);
+        }
         {
             // Check that the number of entries in the descriptor matches
             // the number of entries in the layout.
@@ -2482,23 +3146,22 @@ impl<A: HalApi> Device<A> {
                 let compat_sample_type = view
                     .desc
                     .format
-                    .sample_type(Some(view.desc.range.aspect), Some(self.features))
-                    .unwrap();
+                    .sample_type(Some(view.desc.range.aspect), Some(self.features));
                 match (sample_type, compat_sample_type) {
-                    (Tst::Uint, Tst::Uint) |
-                    (Tst::Sint, Tst::Sint) |
-                    (Tst::Depth, Tst::Depth) |
+                    (Tst::Uint, Some(Tst::Uint)) |
+                    (Tst::Sint, Some(Tst::Sint)) |
+                    (Tst::Depth, Some(Tst::Depth)) |
                     // if we expect non-filterable, accept anything float
-                    (Tst::Float { filterable: false }, Tst::Float { .. }) |
+                    (Tst::Float { filterable: false }, Some(Tst::Float { .. })) |
                     // if we expect filterable, require it
-                    (Tst::Float { filterable: true }, Tst::Float { filterable: true }) |
+                    (Tst::Float { filterable: true }, Some(Tst::Float { filterable: true })) |
                     // if we expect non-filterable, also accept depth
-                    (Tst::Float { filterable: false }, Tst::Depth) => {}
+                    (Tst::Float { filterable: false }, Some(Tst::Depth)) => {}
                     // if we expect filterable, also accept Float that is defined as
                     // unfilterable if filterable feature is explicitly enabled (only hit
                     // if wgt::Features::TEXTURE_ADAPTER_SPECIFIC_FORMAT_FEATURES is
                     // enabled)
-                    (Tst::Float { filterable: true }, Tst::Float { .. }) if view.format_features.flags.contains(wgt::TextureFormatFeatureFlags::FILTERABLE) => {}
+                    (Tst::Float { filterable: true }, Some(Tst::Float { .. })) if view.format_features.flags.contains(wgt::TextureFormatFeatureFlags::FILTERABLE) => {}
                     _ => {
                         return Err(Error::InvalidTextureSampleType {
                             binding,
@@ -2519,6 +3182,30 @@ impl<A: HalApi> Device<A> {
                     hal::TextureUses::RESOURCE,
                 ))
             }
+            wgt::BindingType::YcbcrTexture { format, .. } => {
+                if format != view.desc.format {
+                    return Err(Error::InvalidYcbcrTextureFormat {
+                        binding,
+                        layout_format: format,
+                        view_format: view.desc.format,
+                    });
+                }
+                let mip_level_count = view.selector.mips.end - view.selector.mips.start;
+                let layer_count = view.selector.layers.end - view.selector.layers.start;
+                if view.desc.range.aspect != wgt::TextureAspect::All
+                    || view.desc.dimension != TextureViewDimension::D2
+                    || mip_level_count != 1
+                    || layer_count != 1
+                    || view.samples != 1
+                    || !view.desc.swizzle.is_identity()
+                {
+                    return Err(Error::InvalidYcbcrTextureView { binding });
+                }
+                Ok((
+                    wgt::TextureUsages::TEXTURE_BINDING,
+                    hal::TextureUses::RESOURCE,
+                ))
+            }
             wgt::BindingType::StorageTexture {
                 access,
                 format,
@@ -2538,6 +3225,12 @@ impl<A: HalApi> Device<A> {
                         view_dimension: view.desc.dimension,
                     });
                 }
+                if !view.desc.swizzle.is_identity() {
+                    return Err(Error::InvalidStorageTextureSwizzle {
+                        binding,
+                        swizzle: view.desc.swizzle,
+                    });
+                }
 
                 let mip_level_count = view.selector.mips.end - view.selector.mips.start;
                 if mip_level_count != 1 {
@@ -2648,11 +3341,21 @@ impl<A: HalApi> Device<A> {
         }
 
         // Validate total resource counts and check for a matching device
-        for bgl in &bind_group_layouts {
+        let mut inline_index = None;
+        for (index, bgl) in bind_group_layouts.iter().enumerate() {
             if bgl.device.as_info().id() != self.as_info().id() {
                 return Err(DeviceError::WrongDevice.into());
             }
 
+            if bgl.origin == bgl::Origin::Inline {
+                if let Some(first) = inline_index.replace(index) {
+                    return Err(Error::TooManyInlineGroups {
+                        first,
+                        second: index,
+                    });
+                }
+            }
+
             count_validator.merge(&bgl.binding_count_validator);
         }
 
@@ -2735,6 +3438,32 @@ impl<A: HalApi> Device<A> {
         Ok(pipeline_layout_registry.get(ids.root_id).unwrap())
     }
 
+    /// Returns a capture of the code a pipeline stage is compiled to, if the
+    /// device is being traced.
+    fn shader_code_capture(&self) -> Option<Arc<hal::ShaderCodeCapture>> {
+        #[cfg(feature = "trace")]
+        if self.trace.lock().is_some() {
+            return Some(Arc::new(hal::ShaderCodeCapture::new()));
+        }
+        None
+    }
+
+    /// Records the code captured for `stage` of a pipeline in the trace.
+    #[cfg_attr(not(feature = "trace"), allow(unused_variables))]
+    fn trace_shader_code(&self, stage_bit: wgt::ShaderStages, stage: &hal::ProgrammableStage<A>) {
+        #[cfg(feature = "trace")]
+        if let (Some(capture), Some(trace)) = (stage.code_capture, self.trace.lock().as_mut()) {
+            for code in capture.take() {
+                let data = trace.make_binary(code.extension, &code.code);
+                trace.add(trace::Action::CompiledShaderCode {
+                    stage: stage_bit,
+                    entry_point: stage.entry_point.to_string(),
+                    data,
+                });
+            }
+        }
+    }
+
     pub(crate) fn create_compute_pipeline(
         self: &Arc<Self>,
         desc: &pipeline::ComputePipelineDescriptor,
@@ -2827,6 +3556,7 @@ impl<A: HalApi> Device<A> {
         let late_sized_buffer_groups =
             Device::make_late_sized_buffer_groups(&shader_binding_sizes, &pipeline_layout);
 
+        let code_capture = self.shader_code_capture();
         let pipeline_desc = hal::ComputePipelineDescriptor {
             label: desc.label.to_hal(self.instance_flags),
             layout: pipeline_layout.raw(),
@@ -2835,16 +3565,37 @@ impl<A: HalApi> Device<A> {
                 entry_point: final_entry_point_name.as_ref(),
                 constants: desc.stage.constants.as_ref(),
                 zero_initialize_workgroup_memory: desc.stage.zero_initialize_workgroup_memory,
+                code_capture: code_capture.as_deref(),
             },
         };
 
-        let raw = unsafe {
-            self.raw
-                .as_ref()
-                .unwrap()
-                .create_compute_pipeline(&pipeline_desc)
-        }
-        .map_err(|err| match err {
+        let compile_timeout = *self.pipeline_compile_timeout.lock();
+        let raw = match compile_timeout {
+            Some(timeout) => {
+                let owned = compile::OwnedComputePipelineDescriptor::new(
+                    &pipeline_desc,
+                    &pipeline_layout,
+                    &shader_module,
+                    code_capture.clone(),
+                );
+                match owned.compile(self, timeout) {
+                    compile::Compiled::Done(result) => result,
+                    compile::Compiled::TimedOut => {
+                        return Err(pipeline::CreateComputePipelineError::CompileTimeout(
+                            timeout,
+                        ))
+                    }
+                }
+            }
+            None => unsafe {
+                self.raw
+                    .as_ref()
+                    .unwrap()
+                    .create_compute_pipeline(&pipeline_desc)
+            },
+        };
+        self.trace_shader_code(wgt::ShaderStages::COMPUTE, &pipeline_desc.stage);
+        let raw = raw.map_err(|err| match err {
             hal::PipelineError::Device(error) => {
                 pipeline::CreateComputePipelineError::Device(error.into())
             }
@@ -3207,6 +3958,9 @@ impl<A: HalApi> Device<A> {
             sc
         };
 
+        let vertex_code_capture = self.shader_code_capture();
+        let fragment_code_capture = self.shader_code_capture();
+
         let vertex_shader_module;
         let vertex_entry_point_name;
         let vertex_stage = {
@@ -3251,6 +4005,7 @@ impl<A: HalApi> Device<A> {
                 entry_point: &vertex_entry_point_name,
                 constants: stage_desc.constants.as_ref(),
                 zero_initialize_workgroup_memory: stage_desc.zero_initialize_workgroup_memory,
+                code_capture: vertex_code_capture.as_deref(),
             }
         };
 
@@ -3314,6 +4069,7 @@ impl<A: HalApi> Device<A> {
                     zero_initialize_workgroup_memory: fragment_state
                         .stage
                         .zero_initialize_workgroup_memory,
+                    code_capture: fragment_code_capture.as_deref(),
                 })
             }
             None => None,
@@ -3415,13 +4171,36 @@ impl<A: HalApi> Device<A> {
             color_targets,
             multiview: desc.multiview,
         };
-        let raw = unsafe {
-            self.raw
-                .as_ref()
-                .unwrap()
-                .create_render_pipeline(&pipeline_desc)
+        let compile_timeout = *self.pipeline_compile_timeout.lock();
+        let raw = match compile_timeout {
+            Some(timeout) => {
+                let owned = compile::OwnedRenderPipelineDescriptor::new(
+                    &pipeline_desc,
+                    &pipeline_layout,
+                    &vertex_shader_module,
+                    vertex_code_capture.clone(),
+                    fragment_shader_module.as_ref(),
+                    fragment_code_capture.clone(),
+                );
+                match owned.compile(self, timeout) {
+                    compile::Compiled::Done(result) => result,
+                    compile::Compiled::TimedOut => {
+                        return Err(pipeline::CreateRenderPipelineError::CompileTimeout(timeout))
+                    }
+                }
+            }
+            None => unsafe {
+                self.raw
+                    .as_ref()
+                    .unwrap()
+                    .create_render_pipeline(&pipeline_desc)
+            },
+        };
+        self.trace_shader_code(wgt::ShaderStages::VERTEX, &pipeline_desc.vertex_stage);
+        if let Some(ref fragment_stage) = pipeline_desc.fragment_stage {
+            self.trace_shader_code(wgt::ShaderStages::FRAGMENT, fragment_stage);
         }
-        .map_err(|err| match err {
+        let raw = raw.map_err(|err| match err {
             hal::PipelineError::Device(error) => {
                 pipeline::CreateRenderPipelineError::Device(error.into())
             }
@@ -3464,7 +4243,16 @@ impl<A: HalApi> Device<A> {
             if !ds.is_depth_read_only() {
                 flags |= pipeline::PipelineFlags::WRITES_DEPTH;
             }
-            if !ds.is_stencil_read_only(desc.primitive.cull_mode) {
+            // Passes can change the cull mode with `EXTENDED_DYNAMIC_STATE`.
+            let cull_mode = if self
+                .features
+                .contains(wgt::Features::EXTENDED_DYNAMIC_STATE)
+            {
+                None
+            } else {
+                desc.primitive.cull_mode
+            };
+            if !ds.is_stencil_read_only(cull_mode) {
                 flags |= pipeline::PipelineFlags::WRITES_STENCIL;
             }
         }
@@ -3542,26 +4330,17 @@ impl<A: HalApi> Device<A> {
     ) -> Result<(), WaitIdleError> {
         let guard = self.fence.read();
         let fence = guard.as_ref().unwrap();
-        let last_done_index = unsafe {
-            self.raw
-                .as_ref()
-                .unwrap()
-                .get_fence_value(fence)
-                .map_err(DeviceError::from)?
-        };
+        let last_done_index = self.get_last_done_index(fence)?;
         if last_done_index < submission_index {
             log::info!("Waiting for submission {:?}", submission_index);
-            unsafe {
-                self.raw
-                    .as_ref()
-                    .unwrap()
-                    .wait(fence, submission_index, !0)
-                    .map_err(DeviceError::from)?
-            };
+            self.wait_for_index(fence, submission_index, !0)?;
             drop(guard);
-            let closures = self
-                .lock_life()
-                .triage_submissions(submission_index, &self.command_allocator);
+            let closures = self.lock_life().triage_submissions(
+                submission_index,
+                &self.command_allocator,
+                self.transfer_command_allocator(),
+                self.raw(),
+            );
             assert!(
                 closures.is_empty(),
                 "wait_for_submit is not expected to work with closures"
@@ -3606,7 +4385,62 @@ impl<A: HalApi> Device<A> {
         })
     }
 
-    pub(crate) fn lose(&self, message: &str) {
+    pub(crate) fn create_timeline_semaphore(
+        self: &Arc<Self>,
+        desc: &resource::TimelineSemaphoreDescriptor,
+    ) -> Result<TimelineSemaphore<A>, resource::CreateTimelineSemaphoreError> {
+        self.require_features(wgt::Features::TIMELINE_SEMAPHORE)?;
+
+        let mut raw = unsafe { self.raw().create_fence() }.map_err(DeviceError::from)?;
+        if desc.initial_value != 0 {
+            if let Err(err) = unsafe { self.raw().signal_fence(&mut raw, desc.initial_value) } {
+                unsafe { self.raw().destroy_fence(raw) };
+                return Err(DeviceError::from(err).into());
+            }
+        }
+        Ok(self.create_timeline_semaphore_from_hal(raw, desc))
+    }
+
+    /// `desc.initial_value` has to be the current value of `hal_fence`.
+    pub(crate) fn create_timeline_semaphore_from_hal(
+        self: &Arc<Self>,
+        hal_fence: A::Fence,
+        desc: &resource::TimelineSemaphoreDescriptor,
+    ) -> TimelineSemaphore<A> {
+        TimelineSemaphore {
+            raw: Mutex::new(rank::TIMELINE_SEMAPHORE_RAW, Some(hal_fence)),
+            last_signaled_value: AtomicU64::new(desc.initial_value),
+            handle_type: None,
+            device: self.clone(),
+            info: ResourceInfo::new(desc.label.borrow_or_default(), None),
+        }
+    }
+
+    pub(crate) fn create_memory_heap(
+        self: &Arc<Self>,
+        desc: &resource::MemoryHeapDescriptor,
+    ) -> Result<MemoryHeap<A>, resource::CreateMemoryHeapError> {
+        self.require_features(wgt::Features::PLACED_RESOURCES)?;
+
+        if desc.size == 0 {
+            return Err(resource::CreateMemoryHeapError::ZeroSize);
+        }
+
+        let hal_desc = hal::MemoryHeapDescriptor {
+            label: desc.label.to_hal(self.instance_flags),
+            size: desc.size,
+        };
+        let raw = unsafe { self.raw().create_memory_heap(&hal_desc) }.map_err(DeviceError::from)?;
+        Ok(MemoryHeap {
+            raw: Some(raw),
+            size: desc.size,
+            active: Mutex::new(rank::MEMORY_HEAP_ACTIVE, Vec::new()),
+            device: self.clone(),
+            info: ResourceInfo::new(desc.label.borrow_or_default(), None),
+        })
+    }
+
+    pub(crate) fn lose(&self, reason: DeviceLostReason, message: &str) {
         // Follow the steps at https://gpuweb.github.io/gpuweb/#lose-the-device.
 
         // Mark the device explicitly as invalid. This is checked in various
@@ -3621,7 +4455,7 @@ impl<A: HalApi> Device<A> {
         drop(life_lock);
 
         if let Some(device_lost_closure) = closure {
-            device_lost_closure.call(DeviceLostReason::Unknown, message.to_string());
+            device_lost_closure.call(reason, message.to_string());
         }
 
         // 2) Complete any outstanding mapAsync() steps.
@@ -3678,18 +4512,20 @@ impl<A: HalApi> Device<A> {
     pub(crate) fn prepare_to_die(&self) {
         self.pending_writes.lock().as_mut().unwrap().deactivate();
         let current_index = self.active_submission_index.load(Ordering::Relaxed);
-        if let Err(error) = unsafe {
+        if let Err(error) = {
             let fence = self.fence.read();
             let fence = fence.as_ref().unwrap();
-            self.raw
-                .as_ref()
-                .unwrap()
-                .wait(fence, current_index, CLEANUP_WAIT_MS)
+            self.wait_for_index(fence, current_index, CLEANUP_WAIT_MS)
         } {
             log::error!("failed to wait for the device: {error}");
         }
         let mut life_tracker = self.lock_life();
-        let _ = life_tracker.triage_submissions(current_index, &self.command_allocator);
+        let _ = life_tracker.triage_submissions(
+            current_index,
+            &self.command_allocator,
+            self.transfer_command_allocator(),
+            self.raw(),
+        );
         if let Some(device_lost_closure) = life_tracker.device_lost_closure.take() {
             // It's important to not hold the lock while calling the closure.
             drop(life_tracker);
@@ -3702,6 +4538,24 @@ impl<A: HalApi> Device<A> {
     }
 }
 
+/// Appends `symbols` to the file named by the `WGPU_SHADER_SYMBOL_MAP` environment
+/// variable, if it is set.
+fn write_shader_symbols(symbols: &naga::proc::SymbolMap) {
+    use std::io::Write as _;
+
+    let Some(path) = std::env::var_os("WGPU_SHADER_SYMBOL_MAP") else {
+        return;
+    };
+    let result = std::fs::OpenOptions::new()
+        .create(true)
+        .append(true)
+        .open(&path)
+        .and_then(|mut file| write!(file, "{symbols}"));
+    if let Err(error) = result {
+        log::warn!("Failed to write shader symbols to {path:?}: {error}");
+    }
+}
+
 impl<A: HalApi> Resource for Device<A> {
     const TYPE: ResourceType = "Device";
 
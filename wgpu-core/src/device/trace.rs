@@ -67,6 +67,10 @@ pub enum Action<'a> {
         id::BindGroupLayoutId,
         crate::binding_model::BindGroupLayoutDescriptor<'a>,
     ),
+    CreateInlineBindGroupLayout(
+        id::BindGroupLayoutId,
+        crate::binding_model::BindGroupLayoutDescriptor<'a>,
+    ),
     DestroyBindGroupLayout(id::BindGroupLayoutId),
     CreatePipelineLayout(
         id::PipelineLayoutId,
@@ -98,6 +102,16 @@ pub enum Action<'a> {
         implicit_context: Option<super::ImplicitPipelineContext>,
     },
     DestroyRenderPipeline(id::RenderPipelineId),
+    /// The code the backend compiled a stage of the pipeline created by the
+    /// preceding action to, such as SPIR-V, HLSL or DXIL, named by the
+    /// extension of `data`.
+    ///
+    /// It's only recorded for reference, and ignored when replaying.
+    CompiledShaderCode {
+        stage: wgt::ShaderStages,
+        entry_point: String,
+        data: FileName,
+    },
     CreateRenderBundle {
         id: id::RenderBundleId,
         desc: crate::command::RenderBundleEncoderDescriptor<'a>,
@@ -149,11 +163,24 @@ pub enum Command {
         dst: crate::command::ImageCopyTexture,
         size: wgt::Extent3d,
     },
+    BlitTexture {
+        src: crate::command::ImageCopyTexture,
+        src_size: wgt::Extent3d,
+        dst: crate::command::ImageCopyTexture,
+        dst_size: wgt::Extent3d,
+        filter: wgt::FilterMode,
+    },
     ClearBuffer {
         dst: id::BufferId,
         offset: wgt::BufferAddress,
         size: Option<wgt::BufferAddress>,
     },
+    FillBuffer {
+        dst: id::BufferId,
+        offset: wgt::BufferAddress,
+        size: Option<wgt::BufferAddress>,
+        value: u32,
+    },
     ClearTexture {
         dst: id::TextureId,
         subresource_range: wgt::ImageSubresourceRange,
@@ -185,6 +212,30 @@ pub enum Command {
     },
 }
 
+#[cfg(feature = "trace")]
+impl Action<'_> {
+    /// Returns `true` if the action is needed to replay the creation of
+    /// pipelines, as opposed to the use of them.
+    fn creates_pipelines(&self) -> bool {
+        matches!(
+            *self,
+            Action::Init { .. }
+                | Action::CreateBindGroupLayout(..)
+                | Action::CreateInlineBindGroupLayout(..)
+                | Action::DestroyBindGroupLayout(_)
+                | Action::CreatePipelineLayout(..)
+                | Action::DestroyPipelineLayout(_)
+                | Action::CreateShaderModule { .. }
+                | Action::DestroyShaderModule(_)
+                | Action::CreateComputePipeline { .. }
+                | Action::DestroyComputePipeline(_)
+                | Action::CreateRenderPipeline { .. }
+                | Action::DestroyRenderPipeline(_)
+                | Action::CompiledShaderCode { .. }
+        )
+    }
+}
+
 #[cfg(feature = "trace")]
 #[derive(Debug)]
 pub struct Trace {
@@ -192,6 +243,9 @@ pub struct Trace {
     file: std::fs::File,
     config: ron::ser::PrettyConfig,
     binary_id: usize,
+    /// Only record the actions creating pipelines, as requested by
+    /// [`wgt::DeviceFlags::TRACE_SHADERS_ONLY`].
+    pub(crate) shaders_only: bool,
 }
 
 #[cfg(feature = "trace")]
@@ -205,6 +259,7 @@ impl Trace {
             file,
             config: ron::ser::PrettyConfig::default(),
             binary_id: 0,
+            shaders_only: false,
         })
     }
 
@@ -216,6 +271,9 @@ impl Trace {
     }
 
     pub(crate) fn add(&mut self, action: Action) {
+        if self.shaders_only && !action.creates_pipelines() {
+            return;
+        }
         match ron::ser::to_string_pretty(&action, self.config.clone()) {
             Ok(string) => {
                 let _ = writeln!(self.file, "{},", string);
@@ -22,6 +22,8 @@ pub struct GlobalReport {
     pub dx12: Option<HubReport>,
     #[cfg(gles)]
     pub gl: Option<HubReport>,
+    #[cfg(noop)]
+    pub empty: Option<HubReport>,
 }
 
 impl GlobalReport {
@@ -38,6 +40,8 @@ impl GlobalReport {
             Backend::Dx12 => self.dx12.as_ref().unwrap(),
             #[cfg(gles)]
             Backend::Gl => self.gl.as_ref().unwrap(),
+            #[cfg(noop)]
+            Backend::Empty => self.empty.as_ref().unwrap(),
             _ => panic!("HubReport is not supported on this backend"),
         }
     }
@@ -124,6 +128,12 @@ impl Global {
             } else {
                 None
             },
+            #[cfg(noop)]
+            empty: if self.instance.empty.is_some() {
+                Some(self.hubs.empty.generate_report())
+            } else {
+                None
+            },
         }
     }
 }
@@ -151,6 +161,10 @@ impl Drop for Global {
         {
             self.hubs.gl.clear(&surfaces_locked, true);
         }
+        #[cfg(noop)]
+        {
+            self.hubs.empty.clear(&surfaces_locked, true);
+        }
 
         // destroy surfaces
         for element in surfaces_locked.map.drain(..) {
@@ -14,6 +14,28 @@ pub trait HalApi: hal::Api + 'static + WasmNotSendSync {
     fn surface_as_hal(surface: &Surface) -> Option<&Self::Surface>;
 }
 
+#[cfg(noop)]
+impl HalApi for hal::api::Empty {
+    const VARIANT: Backend = Backend::Empty;
+    fn create_instance_from_hal(name: &str, hal_instance: Self::Instance) -> Instance {
+        Instance {
+            name: name.to_owned(),
+            empty: Some(hal_instance),
+            ..Default::default()
+        }
+    }
+    fn instance_as_hal(instance: &Instance) -> Option<&Self::Instance> {
+        instance.empty.as_ref()
+    }
+    fn hub(global: &Global) -> &Hub<Self> {
+        &global.hubs.empty
+    }
+    fn surface_as_hal(_: &Surface) -> Option<&Self::Surface> {
+        None
+    }
+}
+
+#[cfg(not(noop))]
 impl HalApi for hal::api::Empty {
     const VARIANT: Backend = Backend::Empty;
     fn create_instance_from_hal(_: &str, _: Self::Instance) -> Instance {
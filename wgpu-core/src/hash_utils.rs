@@ -2,16 +2,26 @@
 //!
 //! Named hash_utils to prevent clashing with the std::hash module.
 
+/// Fast, non-cryptographic hasher used by the maps and sets of wgpu-core.
+///
+/// This is FxHash, or AHash with the `ahash` feature. Either way it's seeded
+/// with fixed keys, so iteration orders don't change from run to run.
+#[cfg(not(feature = "ahash"))]
+pub type FastHasher = rustc_hash::FxHasher;
+/// Fast, non-cryptographic hasher used by the maps and sets of wgpu-core.
+#[cfg(feature = "ahash")]
+pub type FastHasher = ahash::AHasher;
+
+/// Builds [`FastHasher`]s.
+pub type FastBuildHasher = std::hash::BuildHasherDefault<FastHasher>;
+
 /// HashMap using a fast, non-cryptographic hash algorithm.
-pub type FastHashMap<K, V> =
-    std::collections::HashMap<K, V, std::hash::BuildHasherDefault<rustc_hash::FxHasher>>;
+pub type FastHashMap<K, V> = std::collections::HashMap<K, V, FastBuildHasher>;
 /// HashSet using a fast, non-cryptographic hash algorithm.
-pub type FastHashSet<K> =
-    std::collections::HashSet<K, std::hash::BuildHasherDefault<rustc_hash::FxHasher>>;
+pub type FastHashSet<K> = std::collections::HashSet<K, FastBuildHasher>;
 
 /// IndexMap using a fast, non-cryptographic hash algorithm.
-pub type FastIndexMap<K, V> =
-    indexmap::IndexMap<K, V, std::hash::BuildHasherDefault<rustc_hash::FxHasher>>;
+pub type FastIndexMap<K, V> = indexmap::IndexMap<K, V, FastBuildHasher>;
 
 /// HashMap that uses pre-hashed keys and an identity hasher.
 ///
@@ -20,7 +30,7 @@ pub type FastIndexMap<K, V> =
 pub type PreHashedMap<K, V> =
     std::collections::HashMap<PreHashedKey<K>, V, std::hash::BuildHasherDefault<IdentityHasher>>;
 
-/// A pre-hashed key using FxHash which allows the hashing operation to be disconnected
+/// A pre-hashed key using [`FastHasher`] which allows the hashing operation to be disconnected
 /// from the storage in the map.
 pub struct PreHashedKey<K>(u64, std::marker::PhantomData<fn() -> K>);
 
@@ -56,7 +66,7 @@ impl<K: std::hash::Hash> PreHashedKey<K> {
     pub fn from_key(key: &K) -> Self {
         use std::hash::Hasher;
 
-        let mut hasher = rustc_hash::FxHasher::default();
+        let mut hasher = FastHasher::default();
         key.hash(&mut hasher);
         Self(hasher.finish(), std::marker::PhantomData)
     }
@@ -112,7 +112,10 @@ use crate::{
     instance::{Adapter, Surface},
     pipeline::{ComputePipeline, RenderPipeline, ShaderModule},
     registry::{Registry, RegistryReport},
-    resource::{Buffer, QuerySet, Sampler, StagingBuffer, Texture, TextureView},
+    resource::{
+        Buffer, MemoryHeap, QuerySet, Sampler, StagingBuffer, Texture, TextureView,
+        TimelineSemaphore,
+    },
     storage::{Element, Storage},
 };
 use std::fmt::Debug;
@@ -135,6 +138,8 @@ pub struct HubReport {
     pub textures: RegistryReport,
     pub texture_views: RegistryReport,
     pub samplers: RegistryReport,
+    pub timeline_semaphores: RegistryReport,
+    pub memory_heaps: RegistryReport,
 }
 
 impl HubReport {
@@ -186,6 +191,8 @@ pub struct Hub<A: HalApi> {
     pub(crate) textures: Registry<Texture<A>>,
     pub(crate) texture_views: Registry<TextureView<A>>,
     pub(crate) samplers: Registry<Sampler<A>>,
+    pub(crate) timeline_semaphores: Registry<TimelineSemaphore<A>>,
+    pub(crate) memory_heaps: Registry<MemoryHeap<A>>,
 }
 
 impl<A: HalApi> Hub<A> {
@@ -208,6 +215,8 @@ impl<A: HalApi> Hub<A> {
             textures: Registry::new(A::VARIANT),
             texture_views: Registry::new(A::VARIANT),
             samplers: Registry::new(A::VARIANT),
+            timeline_semaphores: Registry::new(A::VARIANT),
+            memory_heaps: Registry::new(A::VARIANT),
         }
     }
 
@@ -236,6 +245,8 @@ impl<A: HalApi> Hub<A> {
         self.compute_pipelines.write().map.clear();
         self.render_pipelines.write().map.clear();
         self.query_sets.write().map.clear();
+        self.timeline_semaphores.write().map.clear();
+        self.memory_heaps.write().map.clear();
 
         for element in surface_guard.map.iter() {
             if let Element::Occupied(ref surface, _epoch) = *element {
@@ -285,6 +296,8 @@ impl<A: HalApi> Hub<A> {
             textures: self.textures.generate_report(),
             texture_views: self.texture_views.generate_report(),
             samplers: self.samplers.generate_report(),
+            timeline_semaphores: self.timeline_semaphores.generate_report(),
+            memory_heaps: self.memory_heaps.generate_report(),
         }
     }
 }
@@ -298,7 +311,7 @@ pub struct Hubs {
     pub(crate) dx12: Hub<hal::api::Dx12>,
     #[cfg(gles)]
     pub(crate) gl: Hub<hal::api::Gles>,
-    #[cfg(all(not(vulkan), not(metal), not(dx12), not(gles)))]
+    #[cfg(any(noop, all(not(vulkan), not(metal), not(dx12), not(gles))))]
     pub(crate) empty: Hub<hal::api::Empty>,
 }
 
@@ -313,7 +326,7 @@ impl Hubs {
             dx12: Hub::new(),
             #[cfg(gles)]
             gl: Hub::new(),
-            #[cfg(all(not(vulkan), not(metal), not(dx12), not(gles)))]
+            #[cfg(any(noop, all(not(vulkan), not(metal), not(dx12), not(gles))))]
             empty: Hub::new(),
         }
     }
@@ -320,6 +320,8 @@ ids! {
     pub type RenderBundleEncoderId RenderBundleEncoder;
     pub type RenderBundleId RenderBundle;
     pub type QuerySetId QuerySet;
+    pub type TimelineSemaphoreId TimelineSemaphore;
+    pub type MemoryHeapId MemoryHeap;
 }
 
 impl CommandEncoderId {
@@ -23,12 +23,22 @@ impl BufferInitTracker {
 
     /// Creates an action if it would have any effect on the initialization
     /// status and shrinks the range if possible.
+    ///
+    /// Placed buffers can lose their contents to overlapping resources before
+    /// the action is executed, so their actions are always kept.
     pub(crate) fn create_action<A: HalApi>(
         &self,
         buffer: &Arc<Buffer<A>>,
         query_range: Range<wgt::BufferAddress>,
         kind: MemoryInitKind,
     ) -> Option<BufferInitTrackerAction<A>> {
+        if buffer.placement.is_some() && !query_range.is_empty() {
+            return Some(BufferInitTrackerAction {
+                buffer: buffer.clone(),
+                range: query_range,
+                kind,
+            });
+        }
         self.check(query_range)
             .map(|range| BufferInitTrackerAction {
                 buffer: buffer.clone(),
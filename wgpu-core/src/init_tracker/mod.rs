@@ -202,6 +202,35 @@ where
             next_index: index,
         }
     }
+
+    /// Makes a range uninitialized, whatever its state was before.
+    pub(crate) fn discard_range(&mut self, range: Range<Idx>) {
+        if range.start >= range.end {
+            return;
+        }
+        self.drain(range.clone()).for_each(drop);
+        // Nothing overlaps the range anymore, so the first range ending after
+        // its start either ends right at it or comes after it.
+        let index = self
+            .uninitialized_ranges
+            .partition_point(|r| r.end < range.start);
+        let mut merged = range;
+        let mut removed = index..index;
+        if let Some(previous) = self.uninitialized_ranges.get(index) {
+            if previous.end == merged.start {
+                merged.start = previous.start;
+                removed.end += 1;
+            }
+        }
+        if let Some(next) = self.uninitialized_ranges.get(removed.end) {
+            if next.start == merged.end {
+                merged.end = next.end;
+                removed.end += 1;
+            }
+        }
+        self.uninitialized_ranges.drain(removed);
+        self.uninitialized_ranges.insert(index, merged);
+    }
 }
 
 impl InitTracker<u32> {
@@ -373,6 +402,22 @@ mod test {
         assert_eq!(tracker.uninitialized_ranges[1], 7..10);
     }
 
+    #[test]
+    fn discard_range_splits_and_merges_ranges() {
+        let mut tracker = Tracker::new(30);
+        tracker.drain(0..30);
+        tracker.discard_range(10..20);
+        assert_eq!(tracker.uninitialized_ranges.as_slice(), &[10..20]);
+        tracker.discard_range(5..10);
+        tracker.discard_range(20..25);
+        assert_eq!(tracker.uninitialized_ranges.as_slice(), &[5..25]);
+        tracker.discard_range(0..2);
+        tracker.discard_range(7..12);
+        assert_eq!(tracker.uninitialized_ranges.as_slice(), &[0..2, 5..25]);
+        tracker.discard_range(2..5);
+        assert_eq!(tracker.uninitialized_ranges.as_slice(), &[0..25]);
+    }
+
     #[test]
     fn discard_merges_ranges() {
         let mut tracker = Tracker::new(10);
@@ -57,10 +57,19 @@ impl TextureInitTracker {
         }
     }
 
+    /// Checks if an action has any effect on the initialization status and
+    /// shrinks its range if possible.
+    ///
+    /// Placed textures can lose their contents to overlapping resources before
+    /// the action is executed, so their actions are always kept.
     pub(crate) fn check_action<A: HalApi>(
         &self,
         action: &TextureInitTrackerAction<A>,
     ) -> Option<TextureInitTrackerAction<A>> {
+        if action.texture.placement.is_some() {
+            return Some(action.clone());
+        }
+
         let mut mip_range_start = std::usize::MAX;
         let mut mip_range_end = std::usize::MIN;
         let mut layer_range_start = std::u32::MAX;
@@ -1,5 +1,5 @@
 use std::collections::HashMap;
-use std::sync::Arc;
+use std::sync::{atomic::AtomicBool, Arc};
 
 use crate::{
     api_log,
@@ -66,6 +66,8 @@ pub struct Instance {
     pub dx12: Option<HalInstance<hal::api::Dx12>>,
     #[cfg(gles)]
     pub gl: Option<HalInstance<hal::api::Gles>>,
+    #[cfg(noop)]
+    pub empty: Option<HalInstance<hal::api::Empty>>,
     pub flags: wgt::InstanceFlags,
 }
 
@@ -109,6 +111,8 @@ impl Instance {
             dx12: init(hal::api::Dx12, &instance_desc),
             #[cfg(gles)]
             gl: init(hal::api::Gles, &instance_desc),
+            #[cfg(noop)]
+            empty: init(hal::api::Empty, &instance_desc),
             flags: instance_desc.flags,
         }
     }
@@ -301,6 +305,8 @@ impl<A: HalApi> Adapter<A> {
         if let Ok(device) = Device::new(
             hal_device.device,
             &hal_device.queue,
+            hal_device.additional_queues,
+            hal_device.transfer_queue,
             self,
             desc,
             trace_path,
@@ -309,6 +315,8 @@ impl<A: HalApi> Adapter<A> {
             let queue = Queue {
                 device: None,
                 raw: Some(hal_device.queue),
+                additional_index: None,
+                batch_submits: AtomicBool::new(false),
                 info: ResourceInfo::new("<Queue>", None),
             };
             return Ok((device, queue));
@@ -363,9 +371,12 @@ impl<A: HalApi> Adapter<A> {
         }
 
         let open = unsafe {
-            self.raw
-                .adapter
-                .open(desc.required_features, &desc.required_limits)
+            self.raw.adapter.open(
+                desc.required_features,
+                &desc.required_limits,
+                desc.queue_priority,
+                desc.flags.contains(wgt::DeviceFlags::TRANSFER_QUEUE),
+            )
         }
         .map_err(|err| match err {
             hal::DeviceError::Lost => RequestDeviceError::DeviceLost,
@@ -617,6 +628,55 @@ impl Global {
         Ok(id)
     }
 
+    /// Creates a surface presenting directly to the display connected to the
+    /// DRM connector `connector_id`, without a windowing system, through the
+    /// Vulkan backend.
+    ///
+    /// # Safety
+    ///
+    /// - `fd` must be a file descriptor of a DRM device, with DRM master
+    ///   rights, that stays open as long as the returned [`SurfaceId`] is
+    ///   being used.
+    #[cfg(all(vulkan, target_os = "linux"))]
+    pub unsafe fn instance_create_surface_from_drm(
+        &self,
+        fd: i32,
+        plane: u32,
+        connector_id: u32,
+        mode: wgt::DisplayMode,
+        id_in: Option<SurfaceId>,
+    ) -> Result<SurfaceId, CreateSurfaceError> {
+        profiling::scope!("Instance::create_surface_from_drm");
+
+        let instance = self
+            .instance
+            .vulkan
+            .as_ref()
+            .ok_or(CreateSurfaceError::BackendNotEnabled(Backend::Vulkan))?;
+        let raw = unsafe { instance.create_surface_from_drm(fd, plane, connector_id, mode) }
+            .map_err(|err| {
+                CreateSurfaceError::FailedToCreateSurfaceForAnyBackend(HashMap::from([(
+                    Backend::Vulkan,
+                    err,
+                )]))
+            })?;
+
+        let surface = Surface {
+            presentation: Mutex::new(rank::SURFACE_PRESENTATION, None),
+            info: ResourceInfo::new("<Surface>", None),
+            vulkan: Some(raw),
+            #[cfg(metal)]
+            metal: None,
+            #[cfg(dx12)]
+            dx12: None,
+            #[cfg(gles)]
+            gl: None,
+        };
+
+        let (id, _) = self.surfaces.prepare(id_in).assign(Arc::new(surface));
+        Ok(id)
+    }
+
     #[cfg(dx12)]
     fn instance_create_surface_dx12(
         &self,
@@ -752,6 +812,13 @@ impl Global {
         }
     }
 
+    /// Tell the OS how the whole process should trade performance for power,
+    /// see [`hal::auxil::power::set_process_performance_hint`].
+    pub fn set_process_performance_hint(&self, hint: wgt::PerformanceHint) {
+        api_log!("Instance::set_process_performance_hint {hint:?}");
+        hal::auxil::power::set_process_performance_hint(hint);
+    }
+
     pub fn enumerate_adapters(&self, inputs: AdapterInputs<markers::Adapter>) -> Vec<AdapterId> {
         profiling::scope!("Instance::enumerate_adapters");
         api_log!("Instance::enumerate_adapters");
@@ -776,6 +843,13 @@ impl Global {
         self.enumerate(hal::api::Dx12, &self.instance.dx12, &inputs, &mut adapters);
         #[cfg(gles)]
         self.enumerate(hal::api::Gles, &self.instance.gl, &inputs, &mut adapters);
+        #[cfg(noop)]
+        self.enumerate(
+            hal::api::Empty,
+            &self.instance.empty,
+            &inputs,
+            &mut adapters,
+        );
 
         adapters
     }
@@ -892,6 +966,15 @@ impl Global {
             desc.force_fallback_adapter,
             &mut device_types,
         );
+        #[cfg(noop)]
+        let (id_empty, adapters_empty) = gather(
+            hal::api::Empty,
+            self.instance.empty.as_ref(),
+            &inputs,
+            compatible_surface,
+            desc.force_fallback_adapter,
+            &mut device_types,
+        );
 
         if device_types.is_empty() {
             return Err(RequestAdapterError::NotFound);
@@ -960,6 +1043,10 @@ impl Global {
         if let Some(id) = self.select(&mut selected, id_gl, adapters_gl) {
             return Ok(id);
         }
+        #[cfg(noop)]
+        if let Some(id) = self.select(&mut selected, id_empty, adapters_empty) {
+            return Ok(id);
+        }
         let _ = selected;
 
         log::warn!("Some adapters are present, but enumerating them failed!");
@@ -987,6 +1074,8 @@ impl Global {
             Backend::Dx12 => fid.assign(Arc::new(Adapter::new(hal_adapter))),
             #[cfg(gles)]
             Backend::Gl => fid.assign(Arc::new(Adapter::new(hal_adapter))),
+            #[cfg(noop)]
+            Backend::Empty => fid.assign(Arc::new(Adapter::new(hal_adapter))),
             _ => unreachable!(),
         };
         resource_log!("Created Adapter {:?}", id);
@@ -1119,6 +1208,7 @@ impl Global {
             resource_log!("Created Queue {:?}", queue_id);
 
             device.set_queue(queue);
+            device.start_background_poll();
 
             return (device_id, queue_id, None);
         };
@@ -1171,6 +1261,7 @@ impl Global {
             resource_log!("Created Queue {:?}", queue_id);
 
             device.set_queue(queue);
+            device.start_background_poll();
 
             return (device_id, queue_id, None);
         };
@@ -1193,6 +1284,7 @@ impl Global {
 /// - metal  = "metal" or "mtl"
 /// - gles   = "opengl" or "gles" or "gl"
 /// - webgpu = "webgpu"
+/// - noop   = "noop"
 pub fn parse_backends_from_comma_list(string: &str) -> Backends {
     let mut backends = Backends::empty();
     for backend in string.to_lowercase().split(',') {
@@ -1202,6 +1294,7 @@ pub fn parse_backends_from_comma_list(string: &str) -> Backends {
             "metal" | "mtl" => Backends::METAL,
             "opengl" | "gles" | "gl" => Backends::GL,
             "webgpu" => Backends::BROWSER_WEBGPU,
+            "noop" => Backends::NOOP,
             b => {
                 log::warn!("unknown backend string '{}'", b);
                 continue;
@@ -69,6 +69,8 @@ mod pool;
 pub mod present;
 pub mod registry;
 pub mod resource;
+#[cfg(feature = "shader-translation")]
+pub mod shader_translation;
 mod snatch;
 pub mod storage;
 mod track;
@@ -230,9 +232,12 @@ define_backend_caller! { gfx_if_vulkan, gfx_if_vulkan_hidden, "vulkan" if all(fe
 define_backend_caller! { gfx_if_metal, gfx_if_metal_hidden, "metal" if all(feature = "metal", any(target_os = "macos", target_os = "ios")) }
 define_backend_caller! { gfx_if_dx12, gfx_if_dx12_hidden, "dx12" if all(feature = "dx12", windows) }
 define_backend_caller! { gfx_if_gles, gfx_if_gles_hidden, "gles" if feature = "gles" }
-define_backend_caller! { gfx_if_empty, gfx_if_empty_hidden, "empty" if all(
-    not(any(feature = "metal", feature = "vulkan", feature = "gles")),
-    any(target_os = "macos", target_os = "ios"),
+define_backend_caller! { gfx_if_empty, gfx_if_empty_hidden, "noop" if any(
+    feature = "noop",
+    all(
+        not(any(feature = "metal", feature = "vulkan", feature = "gles")),
+        any(target_os = "macos", target_os = "ios"),
+    ),
 ) }
 
 /// Dispatch on an [`Id`]'s backend to a backend-generic method.
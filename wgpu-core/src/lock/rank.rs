@@ -87,6 +87,16 @@ macro_rules! define_lock_ranks {
 }
 
 define_lock_ranks! {
+    rank DEVICE_SUBMIT_BATCH "Device::submit_batch" followed by {
+        REGISTRY_STORAGE,
+        TIMELINE_SEMAPHORE_RAW,
+        DEVICE_SNATCHABLE_LOCK,
+        DEVICE_FENCE,
+        DEVICE_TEMP_SUSPECTED,
+        DEVICE_PENDING_WRITES,
+        DEVICE_LIFE_TRACKER,
+        DEVICE_TRACE,
+    }
     rank DEVICE_TEMP_SUSPECTED "Device::temp_suspected" followed by {
         SHARED_TRACKER_INDEX_ALLOCATOR_INNER,
         COMMAND_BUFFER_DATA,
@@ -112,20 +122,24 @@ define_lock_ranks! {
         // COMMAND_BUFFER_DATA,
     }
     rank BUFFER_MAP_STATE "Buffer::map_state" followed by {
+        BUFFER_SYNC_MAPPED_WRITES,
         DEVICE_PENDING_WRITES,
         SHARED_TRACKER_INDEX_ALLOCATOR_INNER,
         DEVICE_TRACE,
     }
     rank DEVICE_PENDING_WRITES "Device::pending_writes" followed by {
         COMMAND_ALLOCATOR_FREE_ENCODERS,
+        DEVICE_ADDITIONAL_QUEUES,
         SHARED_TRACKER_INDEX_ALLOCATOR_INNER,
         DEVICE_LIFE_TRACKER,
+        MEMORY_HEAP_ACTIVE,
     }
     rank DEVICE_LIFE_TRACKER "Device::life_tracker" followed by {
         COMMAND_ALLOCATOR_FREE_ENCODERS,
         // Uncomment this to see an interesting cycle.
         // DEVICE_TEMP_SUSPECTED,
         DEVICE_TRACE,
+        TIMELINE_SEMAPHORE_RAW,
     }
     rank COMMAND_ALLOCATOR_FREE_ENCODERS "CommandAllocator::free_encoders" followed by {
         SHARED_TRACKER_INDEX_ALLOCATOR_INNER,
@@ -135,13 +149,16 @@ define_lock_ranks! {
     rank BUFFER_BIND_GROUP_STATE_BUFFERS "BufferBindGroupState::buffers" followed by { }
     rank BUFFER_INITIALIZATION_STATUS "Buffer::initialization_status" followed by { }
     rank BUFFER_SYNC_MAPPED_WRITES "Buffer::sync_mapped_writes" followed by { }
+    rank DEVICE_ADDITIONAL_QUEUES "Device::additional_queues" followed by { TIMELINE_SEMAPHORE_RAW }
     rank DEVICE_DEFERRED_DESTROY "Device::deferred_destroy" followed by { }
-    rank DEVICE_FENCE "Device::fence" followed by { }
+    rank DEVICE_FENCE "Device::fence" followed by { DEVICE_ADDITIONAL_QUEUES, TIMELINE_SEMAPHORE_RAW }
+    rank DEVICE_PIPELINE_COMPILE_TIMEOUT "Device::pipeline_compile_timeout" followed by { }
     #[allow(dead_code)]
     rank DEVICE_TRACE "Device::trace" followed by { }
-    rank DEVICE_TRACKERS "Device::trackers" followed by { }
+    rank DEVICE_TRACKERS "Device::trackers" followed by { MEMORY_HEAP_ACTIVE }
     rank DEVICE_USAGE_SCOPES "Device::usage_scopes" followed by { }
     rank IDENTITY_MANAGER_VALUES "IdentityManager::values" followed by { }
+    rank MEMORY_HEAP_ACTIVE "MemoryHeap::active" followed by { }
     rank REGISTRY_STORAGE "Registry::storage" followed by { }
     rank RENDER_BUNDLE_SCOPE_BUFFERS "RenderBundleScope::buffers" followed by { }
     rank RENDER_BUNDLE_SCOPE_TEXTURES "RenderBundleScope::textures" followed by { }
@@ -158,6 +175,7 @@ define_lock_ranks! {
     rank TEXTURE_INITIALIZATION_STATUS "Texture::initialization_status" followed by { }
     rank TEXTURE_CLEAR_MODE "Texture::clear_mode" followed by { }
     rank TEXTURE_VIEWS "Texture::views" followed by { }
+    rank TIMELINE_SEMAPHORE_RAW "TimelineSemaphore::raw" followed by { }
 
     #[cfg(test)]
     rank PAWN "pawn" followed by { ROOK, BISHOP }
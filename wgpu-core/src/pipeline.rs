@@ -43,6 +43,10 @@ pub struct ShaderModuleDescriptor<'a> {
     pub label: Label<'a>,
     #[cfg_attr(feature = "serde", serde(default))]
     pub shader_bound_checks: wgt::ShaderBoundChecks,
+    /// Severities of WGSL diagnostic rules for the whole shader. Ignored for
+    /// other shader sources.
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub diagnostic_filters: Cow<'a, [wgt::DiagnosticFilter]>,
 }
 
 #[derive(Debug)]
@@ -290,6 +294,8 @@ pub enum CreateComputePipelineError {
     Internal(String),
     #[error(transparent)]
     MissingDownlevelFlags(#[from] MissingDownlevelFlags),
+    #[error("Pipeline didn't compile within {0:?}")]
+    CompileTimeout(std::time::Duration),
 }
 
 #[derive(Debug)]
@@ -501,6 +507,8 @@ pub enum CreateRenderPipelineError {
     PipelineExpectsShaderToUseDualSourceBlending,
     #[error("Shader entry point expects the pipeline to make use of dual-source blending.")]
     ShaderExpectsPipelineToUseDualSourceBlending,
+    #[error("Pipeline didn't compile within {0:?}")]
+    CompileTimeout(std::time::Duration),
 }
 
 bitflags::bitflags! {
@@ -9,14 +9,14 @@ When this texture is presented, we remove it from the device tracker as well as
 extract it from the hub.
 !*/
 
-use std::{borrow::Borrow, sync::Arc};
+use std::{borrow::Borrow, collections::VecDeque, ops::RangeInclusive, sync::Arc, time::Duration};
 
 #[cfg(feature = "trace")]
 use crate::device::trace::Action;
 use crate::{
     conv,
     device::any_device::AnyDevice,
-    device::{DeviceError, MissingDownlevelFlags, WaitIdleError},
+    device::{DeviceError, MissingDownlevelFlags, MissingFeatures, WaitIdleError},
     global::Global,
     hal_api::HalApi,
     hal_label, id,
@@ -27,17 +27,83 @@ use crate::{
     track,
 };
 
-use hal::{Queue as _, Surface as _};
+use hal::{Adapter as _, Queue as _, Surface as _};
+use smallvec::SmallVec;
 use thiserror::Error;
 use wgt::SurfaceStatus as Status;
 
 const FRAME_TIMEOUT_MS: u32 = 1000;
 
+/// Number of presents a [`PresentStatistics`] waits for the timings of.
+const MAX_PENDING_PRESENTS: usize = 64;
+
 #[derive(Debug)]
 pub(crate) struct Presentation {
     pub(crate) device: AnyDevice,
     pub(crate) config: wgt::SurfaceConfiguration<Vec<wgt::TextureFormat>>,
     pub(crate) acquired_texture: Option<id::TextureId>,
+    pub(crate) statistics: PresentStatistics,
+    /// The range the maximum frame latency of the surface can be set in.
+    pub(crate) maximum_frame_latency: RangeInclusive<u32>,
+}
+
+/// The presents to a surface since it was configured, matched against the
+/// presentation timings of the backend to build [`wgt::PresentationReport`]s.
+#[derive(Debug, Default)]
+pub(crate) struct PresentStatistics {
+    present_count: u64,
+    /// The presents whose fate isn't known yet, with the time they were
+    /// presented at, oldest first.
+    pending: VecDeque<(u64, wgt::PresentationTimestamp)>,
+}
+
+impl PresentStatistics {
+    /// Record a present to the backend, made at `time`.
+    fn record_present(&mut self, time: wgt::PresentationTimestamp) {
+        self.present_count += 1;
+        // Backends without timings never report the presents.
+        if self.pending.len() == MAX_PENDING_PRESENTS {
+            self.pending.pop_front();
+        }
+        self.pending.push_back((self.present_count, time));
+    }
+
+    /// Report the presents shown by `timings`, oldest first, and the presents
+    /// before them that were never shown.
+    fn report(&mut self, timings: &[wgt::PresentationTiming]) -> wgt::PresentationReport {
+        let mut frames = Vec::new();
+        for timing in timings {
+            while let Some(&(present_id, presented_at)) = self.pending.front() {
+                if present_id > timing.present_id {
+                    break;
+                }
+                self.pending.pop_front();
+                if present_id < timing.present_id {
+                    frames.push(wgt::FramePresentation {
+                        present_id,
+                        dropped: true,
+                        actual_present_time: None,
+                        wait: None,
+                    });
+                    continue;
+                }
+
+                let actual = timing.actual_present_time;
+                let wait = (!presented_at.is_invalid() && actual >= presented_at)
+                    .then(|| Duration::from_nanos((actual.0 - presented_at.0) as u64));
+                frames.push(wgt::FramePresentation {
+                    present_id,
+                    dropped: false,
+                    actual_present_time: Some(actual),
+                    wait,
+                });
+            }
+        }
+        wgt::PresentationReport {
+            present_count: self.present_count,
+            frames,
+        }
+    }
 }
 
 #[derive(Clone, Debug, Error)]
@@ -53,6 +119,10 @@ pub enum SurfaceError {
     AlreadyAcquired,
     #[error("Acquired frame is still referenced")]
     StillReferenced,
+    #[error("Unable to set HDR metadata: {0}")]
+    HdrMetadata(&'static str),
+    #[error(transparent)]
+    MissingFeatures(#[from] MissingFeatures),
 }
 
 #[derive(Clone, Debug, Error)]
@@ -93,6 +163,25 @@ pub enum ConfigureSurfaceError {
         requested: wgt::CompositeAlphaMode,
         available: Vec<wgt::CompositeAlphaMode>,
     },
+    #[error("Requested pre-transform {requested:?} is not in the list of supported transforms: {available:?}")]
+    UnsupportedTransform {
+        requested: wgt::SurfaceTransform,
+        available: Vec<wgt::SurfaceTransform>,
+    },
+    #[error("Color space {color_space:?} is not supported with format {format:?}")]
+    UnsupportedColorSpace {
+        format: wgt::TextureFormat,
+        color_space: wgt::ColorSpace,
+    },
+    #[error("Requested {requested} array layers, but the surface supports between 1 and {max}")]
+    UnsupportedArrayLayerCount { requested: u32, max: u32 },
+    #[error("Requested fullscreen mode {requested:?} is not in the list of supported fullscreen modes: {available:?}")]
+    UnsupportedFullscreenMode {
+        requested: wgt::FullscreenMode,
+        available: Vec<wgt::FullscreenMode>,
+    },
+    #[error("Display mode {0:?} is not supported, display modes can only be chosen with exclusive fullscreen, among the modes of the display")]
+    UnsupportedDisplayMode(wgt::DisplayMode),
     #[error("Requested usage is not supported")]
     UnsupportedUsage,
     #[error("Gpu got stuck :(")]
@@ -168,12 +257,13 @@ impl Global {
             Ok(Some(ast)) => {
                 drop(fence_guard);
 
+                let array_layer_count = config.array_layer_count;
                 let texture_desc = wgt::TextureDescriptor {
                     label: (),
                     size: wgt::Extent3d {
                         width: config.width,
                         height: config.height,
-                        depth_or_array_layers: 1,
+                        depth_or_array_layers: array_layer_count,
                     },
                     sample_count: 1,
                     mip_level_count: 1,
@@ -188,24 +278,42 @@ impl Global {
                     flags: wgt::TextureFormatFeatureFlags::MULTISAMPLE_X4
                         | wgt::TextureFormatFeatureFlags::MULTISAMPLE_RESOLVE,
                 };
-                let clear_view_desc = hal::TextureViewDescriptor {
-                    label: hal_label(
-                        Some("(wgpu internal) clear surface texture view"),
-                        self.instance.flags,
-                    ),
-                    format: config.format,
-                    dimension: wgt::TextureViewDimension::D2,
-                    usage: hal::TextureUses::COLOR_TARGET,
-                    range: wgt::ImageSubresourceRange::default(),
-                };
-                let clear_view = unsafe {
-                    hal::Device::create_texture_view(
-                        device.raw(),
-                        ast.texture.borrow(),
-                        &clear_view_desc,
-                    )
+                let mut clear_views = SmallVec::new();
+                for array_layer in 0..array_layer_count {
+                    let clear_view_desc = hal::TextureViewDescriptor {
+                        label: hal_label(
+                            Some("(wgpu internal) clear surface texture view"),
+                            self.instance.flags,
+                        ),
+                        format: config.format,
+                        dimension: wgt::TextureViewDimension::D2,
+                        usage: hal::TextureUses::COLOR_TARGET,
+                        range: wgt::ImageSubresourceRange {
+                            base_array_layer: array_layer,
+                            array_layer_count: Some(1),
+                            ..Default::default()
+                        },
+                        swizzle: wgt::TextureComponentSwizzle::IDENTITY,
+                    };
+                    let clear_view = unsafe {
+                        hal::Device::create_texture_view(
+                            device.raw(),
+                            ast.texture.borrow(),
+                            &clear_view_desc,
+                        )
+                    };
+                    match clear_view {
+                        Ok(clear_view) => clear_views.push(Some(clear_view)),
+                        Err(error) => {
+                            for clear_view in clear_views.into_iter().flatten() {
+                                unsafe {
+                                    hal::Device::destroy_texture_view(device.raw(), clear_view)
+                                };
+                            }
+                            return Err(DeviceError::from(error).into());
+                        }
+                    }
                 }
-                .map_err(DeviceError::from)?;
 
                 let mut presentation = surface.presentation.lock();
                 let present = presentation.as_mut().unwrap();
@@ -218,12 +326,15 @@ impl Global {
                     desc: texture_desc,
                     hal_usage,
                     format_features,
+                    sparse: None,
+                    placement: None,
+                    imported: false,
                     initialization_status: RwLock::new(
                         rank::TEXTURE_INITIALIZATION_STATUS,
-                        TextureInitTracker::new(1, 1),
+                        TextureInitTracker::new(1, array_layer_count),
                     ),
                     full_range: track::TextureSelector {
-                        layers: 0..1,
+                        layers: 0..array_layer_count,
                         mips: 0..1,
                     },
                     info: ResourceInfo::new(
@@ -232,12 +343,11 @@ impl Global {
                     ),
                     clear_mode: RwLock::new(
                         rank::TEXTURE_CLEAR_MODE,
-                        resource::TextureClearMode::Surface {
-                            clear_view: Some(clear_view),
-                        },
+                        resource::TextureClearMode::Surface { clear_views },
                     ),
                     views: Mutex::new(rank::TEXTURE_VIEWS, Vec::new()),
                     bind_groups: Mutex::new(rank::TEXTURE_BIND_GROUPS, Vec::new()),
+                    expiry: Default::default(),
                 };
 
                 let (id, resource) = fid.assign(Arc::new(texture));
@@ -296,6 +406,16 @@ impl Global {
             .get(surface_id)
             .map_err(|_| SurfaceError::Invalid)?;
 
+        // The frame has to include the work deferred by a queue in batching mode.
+        let device = surface
+            .presentation
+            .lock()
+            .as_ref()
+            .and_then(|present| present.device.downcast_clone::<A>());
+        if let Some(device) = device {
+            self.flush_submit_batch_or_log::<A>(&device);
+        }
+
         let mut presentation = surface.presentation.lock();
         let present = match presentation.as_mut() {
             Some(present) => present,
@@ -346,6 +466,9 @@ impl Global {
                             log::error!("Presented frame is from a different surface");
                             Err(hal::SurfaceError::Lost)
                         } else {
+                            let now =
+                                unsafe { device.adapter.raw.adapter.get_presentation_timestamp() };
+                            present.statistics.record_present(now);
                             unsafe {
                                 queue
                                     .raw
@@ -444,4 +567,308 @@ impl Global {
 
         Ok(())
     }
+    /// Call `f` with the hal surface of `surface_id`, which has to be
+    /// configured on a valid device of backend `A`.
+    fn with_configured_surface<A: HalApi, R>(
+        &self,
+        surface_id: id::SurfaceId,
+        f: impl FnOnce(&A::Surface) -> R,
+    ) -> Result<R, SurfaceError> {
+        let surface = self
+            .surfaces
+            .get(surface_id)
+            .map_err(|_| SurfaceError::Invalid)?;
+        let presentation = surface.presentation.lock();
+        let present = presentation.as_ref().ok_or(SurfaceError::NotConfigured)?;
+        let device = present
+            .device
+            .downcast_ref::<A>()
+            .ok_or(SurfaceError::NotConfigured)?;
+        if !device.is_valid() {
+            return Err(DeviceError::Lost.into());
+        }
+
+        let suf = A::surface_as_hal(&surface);
+        Ok(f(suf.unwrap()))
+    }
+
+    /// Describe the HDR content presented to `surface_id` to the display.
+    ///
+    /// Fails with [`SurfaceError::HdrMetadata`] if the backend or the display
+    /// doesn't take HDR metadata.
+    pub fn surface_set_hdr_metadata<A: HalApi>(
+        &self,
+        surface_id: id::SurfaceId,
+        metadata: &wgt::HdrMetadata,
+    ) -> Result<(), SurfaceError> {
+        profiling::scope!("SwapChain::set_hdr_metadata");
+
+        let result = self.with_configured_surface::<A, _>(surface_id, |suf| unsafe {
+            suf.set_hdr_metadata(metadata)
+        })?;
+        match result {
+            Ok(()) => Ok(()),
+            Err(hal::SurfaceError::Device(err)) => Err(DeviceError::from(err).into()),
+            Err(hal::SurfaceError::Lost) => Err(SurfaceError::HdrMetadata("surface is lost")),
+            Err(hal::SurfaceError::Outdated) => {
+                Err(SurfaceError::HdrMetadata("surface is outdated"))
+            }
+            Err(hal::SurfaceError::Other(msg)) => Err(SurfaceError::HdrMetadata(msg)),
+        }
+    }
+
+    /// Ask for the next frame presented to `surface_id` to be shown no
+    /// earlier than `time`.
+    ///
+    /// Returns `false` if the backend can't schedule presents, in which case
+    /// the next frame is shown as soon as possible.
+    pub fn surface_set_next_present_time<A: HalApi>(
+        &self,
+        surface_id: id::SurfaceId,
+        time: wgt::PresentationTimestamp,
+    ) -> Result<bool, SurfaceError> {
+        profiling::scope!("SwapChain::set_next_present_time");
+
+        self.with_configured_surface::<A, _>(surface_id, |suf| unsafe {
+            suf.set_next_present_time(time)
+        })
+    }
+
+    /// Limit the next frame presented to `surface_id` to the regions of
+    /// `damage`, which are clipped to the surface.
+    ///
+    /// Returns `false` if the backend can't present partially, in which case
+    /// the whole frame is presented.
+    pub fn surface_set_next_present_damage<A: HalApi>(
+        &self,
+        surface_id: id::SurfaceId,
+        damage: &[wgt::DamageRect],
+    ) -> Result<bool, SurfaceError> {
+        profiling::scope!("SwapChain::set_next_present_damage");
+
+        let surface = self
+            .surfaces
+            .get(surface_id)
+            .map_err(|_| SurfaceError::Invalid)?;
+        let presentation = surface.presentation.lock();
+        let present = presentation.as_ref().ok_or(SurfaceError::NotConfigured)?;
+        let device = present
+            .device
+            .downcast_ref::<A>()
+            .ok_or(SurfaceError::NotConfigured)?;
+        if !device.is_valid() {
+            return Err(DeviceError::Lost.into());
+        }
+
+        let damage = damage
+            .iter()
+            .filter_map(|rect| rect.clip(present.config.width, present.config.height))
+            .collect::<Vec<_>>();
+        let suf = A::surface_as_hal(&surface);
+        Ok(unsafe { suf.unwrap().set_next_present_damage(&damage) })
+    }
+
+    /// Get the timings of the frames presented to `surface_id` that were
+    /// shown since the last call, oldest first.
+    ///
+    /// Backends that don't report presentation timings return none.
+    pub fn surface_presentation_timings<A: HalApi>(
+        &self,
+        surface_id: id::SurfaceId,
+    ) -> Result<Vec<wgt::PresentationTiming>, SurfaceError> {
+        profiling::scope!("SwapChain::presentation_timings");
+
+        self.with_configured_surface::<A, _>(surface_id, |suf| unsafe {
+            suf.presentation_timings()
+        })
+    }
+
+    /// Report the frames presented to `surface_id` that were shown or dropped
+    /// since the last report.
+    ///
+    /// The report is built from the presentation timings of the backend, and
+    /// takes the timings [`Global::surface_presentation_timings`] would
+    /// return. Backends that only report the last frame shown, like DX12,
+    /// report the frames shown between two reports as dropped.
+    pub fn surface_presentation_report<A: HalApi>(
+        &self,
+        surface_id: id::SurfaceId,
+    ) -> Result<wgt::PresentationReport, SurfaceError> {
+        profiling::scope!("SwapChain::presentation_report");
+
+        let surface = self
+            .surfaces
+            .get(surface_id)
+            .map_err(|_| SurfaceError::Invalid)?;
+        let mut presentation = surface.presentation.lock();
+        let present = presentation.as_mut().ok_or(SurfaceError::NotConfigured)?;
+        let device = present
+            .device
+            .downcast_ref::<A>()
+            .ok_or(SurfaceError::NotConfigured)?;
+        if !device.is_valid() {
+            return Err(DeviceError::Lost.into());
+        }
+
+        let suf = A::surface_as_hal(&surface);
+        let timings = unsafe { suf.unwrap().presentation_timings() };
+        Ok(present.statistics.report(&timings))
+    }
+
+    /// Get the refresh duration of the display `surface_id` is presented to,
+    /// if the backend knows it.
+    pub fn surface_refresh_duration<A: HalApi>(
+        &self,
+        surface_id: id::SurfaceId,
+    ) -> Result<Option<std::time::Duration>, SurfaceError> {
+        profiling::scope!("SwapChain::refresh_duration");
+
+        self.with_configured_surface::<A, _>(surface_id, |suf| unsafe { suf.refresh_duration() })
+    }
+
+    /// Get the number of textures the presentation engine created for
+    /// `surface_id`, which can be more than the configuration asked for.
+    pub fn surface_image_count<A: HalApi>(
+        &self,
+        surface_id: id::SurfaceId,
+    ) -> Result<u32, SurfaceError> {
+        profiling::scope!("SwapChain::image_count");
+
+        self.with_configured_surface::<A, _>(surface_id, |suf| unsafe { suf.image_count() })
+    }
+
+    /// Block until `surface_id` can take a new frame without more than its
+    /// maximum frame latency of frames waiting to be shown.
+    ///
+    /// Returns [`Status::Timeout`] if `timeout` elapses first, and the status
+    /// the next frame would be acquired with if the surface is lost or
+    /// outdated. Requires [`wgt::Features::PRESENT_WAIT`].
+    pub fn surface_wait_for_present<A: HalApi>(
+        &self,
+        surface_id: id::SurfaceId,
+        timeout: Option<Duration>,
+    ) -> Result<Status, SurfaceError> {
+        profiling::scope!("SwapChain::wait_for_present");
+
+        let surface = self
+            .surfaces
+            .get(surface_id)
+            .map_err(|_| SurfaceError::Invalid)?;
+        let presentation = surface.presentation.lock();
+        let present = presentation.as_ref().ok_or(SurfaceError::NotConfigured)?;
+        let device = present
+            .device
+            .downcast_ref::<A>()
+            .ok_or(SurfaceError::NotConfigured)?;
+        if !device.is_valid() {
+            return Err(DeviceError::Lost.into());
+        }
+        device.require_features(wgt::Features::PRESENT_WAIT)?;
+
+        let suf = A::surface_as_hal(&surface);
+        match unsafe { suf.unwrap().wait_for_present(timeout) } {
+            Ok(true) => Ok(Status::Good),
+            Ok(false) => Ok(Status::Timeout),
+            Err(hal::SurfaceError::Lost) => Ok(Status::Lost),
+            Err(hal::SurfaceError::Outdated) => Ok(Status::Outdated),
+            Err(hal::SurfaceError::Device(err)) => Err(DeviceError::from(err).into()),
+            Err(hal::SurfaceError::Other(msg)) => {
+                log::error!("wait for present error: {}", msg);
+                Ok(Status::Lost)
+            }
+        }
+    }
+
+    /// Change the maximum frame latency of `surface_id` without configuring
+    /// it again. `maximum_frame_latency` is clamped to the range the surface
+    /// supports, like [`wgt::SurfaceConfiguration::desired_maximum_frame_latency`].
+    pub fn surface_set_maximum_frame_latency<A: HalApi>(
+        &self,
+        surface_id: id::SurfaceId,
+        maximum_frame_latency: u32,
+    ) -> Result<(), SurfaceError> {
+        profiling::scope!("SwapChain::set_maximum_frame_latency");
+
+        let surface = self
+            .surfaces
+            .get(surface_id)
+            .map_err(|_| SurfaceError::Invalid)?;
+        let mut presentation = surface.presentation.lock();
+        let present = presentation.as_mut().ok_or(SurfaceError::NotConfigured)?;
+        let device = present
+            .device
+            .downcast_ref::<A>()
+            .ok_or(SurfaceError::NotConfigured)?;
+        if !device.is_valid() {
+            return Err(DeviceError::Lost.into());
+        }
+
+        let maximum_frame_latency = maximum_frame_latency.clamp(
+            *present.maximum_frame_latency.start(),
+            *present.maximum_frame_latency.end(),
+        );
+        let suf = A::surface_as_hal(&surface);
+        unsafe {
+            suf.unwrap()
+                .set_maximum_frame_latency(maximum_frame_latency)
+        };
+        present.config.desired_maximum_frame_latency = maximum_frame_latency;
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn timing(present_id: u64, time: u128) -> wgt::PresentationTiming {
+        wgt::PresentationTiming {
+            present_id,
+            desired_present_time: None,
+            actual_present_time: wgt::PresentationTimestamp(time),
+        }
+    }
+
+    #[test]
+    fn report_shown_and_dropped_frames() {
+        let mut statistics = PresentStatistics::default();
+        for time in [100, 200, 300, 400] {
+            statistics.record_present(wgt::PresentationTimestamp(time));
+        }
+
+        // The second present was never shown, the fourth is still queued.
+        let report = statistics.report(&[timing(1, 150), timing(3, 380)]);
+        assert_eq!(report.present_count, 4);
+        let frames: Vec<_> = report
+            .frames
+            .iter()
+            .map(|frame| (frame.present_id, frame.dropped, frame.wait))
+            .collect();
+        assert_eq!(
+            frames,
+            [
+                (1, false, Some(Duration::from_nanos(50))),
+                (2, true, None),
+                (3, false, Some(Duration::from_nanos(80))),
+            ]
+        );
+        assert_eq!(report.dropped_frames(), 1);
+        assert_eq!(report.max_wait(), Some(Duration::from_nanos(80)));
+
+        let report = statistics.report(&[timing(4, 450)]);
+        assert_eq!(report.frames.len(), 1);
+        assert_eq!(report.frames[0].wait, Some(Duration::from_nanos(50)));
+    }
+
+    #[test]
+    fn report_without_presentation_clock() {
+        let mut statistics = PresentStatistics::default();
+        statistics.record_present(wgt::PresentationTimestamp::INVALID_TIMESTAMP);
+        let report = statistics.report(&[timing(1, 150)]);
+        assert!(!report.frames[0].dropped);
+        assert_eq!(report.frames[0].wait, None);
+
+        // Timings of presents that aren't pending anymore are ignored.
+        assert!(statistics.report(&[timing(1, 150)]).frames.is_empty());
+    }
 }
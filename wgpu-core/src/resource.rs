@@ -4,13 +4,13 @@ use crate::{
     binding_model::BindGroup,
     device::{
         queue, resource::DeferredDestroy, BufferMapPendingClosure, Device, DeviceError, HostMap,
-        MissingDownlevelFlags, MissingFeatures,
+        MissingDownlevelFlags, MissingFeatures, UserClosures,
     },
     global::Global,
     hal_api::HalApi,
     id::{
-        AdapterId, BufferId, CommandEncoderId, DeviceId, Id, Marker, SurfaceId, TextureId,
-        TextureViewId,
+        AdapterId, BufferId, CommandEncoderId, DeviceId, Id, Marker, MemoryHeapId, QueueId,
+        SurfaceId, TextureId, TextureViewId, TimelineSemaphoreId,
     },
     init_tracker::{BufferInitTracker, TextureInitTracker},
     lock::{Mutex, RwLock},
@@ -33,7 +33,7 @@ use std::{
     ops::Range,
     ptr::NonNull,
     sync::{
-        atomic::{AtomicUsize, Ordering},
+        atomic::{AtomicU64, AtomicUsize, Ordering},
         Arc, Weak,
     },
 };
@@ -211,6 +211,10 @@ pub(crate) enum BufferMapState<A: HalApi> {
         ptr: NonNull<u8>,
         range: hal::MemoryRange,
         host: HostMap,
+        /// Whether views of the mapping were handed out by
+        /// `Global::buffer_get_mapped_range`. They may outlive a submission,
+        /// so the buffer can't be used by submissions until it is unmapped.
+        checked_views: bool,
     },
     /// Not mapped
     Idle,
@@ -367,6 +371,8 @@ pub enum BufferAccessError {
     },
     #[error("Buffer map aborted")]
     MapAborted,
+    #[error("Buffer is used by a submission that hasn't completed")]
+    InUse,
 }
 
 pub type BufferAccessResult = Result<(), BufferAccessError>;
@@ -387,6 +393,16 @@ pub struct Buffer<A: HalApi> {
     pub(crate) device: Arc<Device<A>>,
     pub(crate) usage: wgt::BufferUsages,
     pub(crate) size: wgt::BufferAddress,
+    /// Whether the buffer was created with `Device::create_sparse_buffer`, so
+    /// that its pages are committed to memory by `Queue::bind_sparse`.
+    pub(crate) sparse: bool,
+    /// Where the buffer is placed if it was created with
+    /// `Device::create_placed_buffer`.
+    pub(crate) placement: Option<Placement<A>>,
+    /// Whether the raw buffer was made outside of `Device::create_buffer`,
+    /// as with `Device::create_buffer_from_hal`, so that it can't be assumed
+    /// to be shared with the transfer queue.
+    pub(crate) imported: bool,
     pub(crate) initialization_status: RwLock<BufferInitTracker>,
     pub(crate) sync_mapped_writes: Mutex<Option<hal::MemoryRange>>,
     pub(crate) info: ResourceInfo<Buffer<A>>,
@@ -417,16 +433,35 @@ impl<A: HalApi> Buffer<A> {
         self.raw.get(guard)
     }
 
+    /// Make a placed buffer take its memory over from the resources that
+    /// overlap it, see [`MemoryHeap::activate`].
+    ///
+    /// Returns `true` if it did, in which case the buffer is uninitialized.
+    pub(crate) fn activate_placement(self: &Arc<Self>) -> bool {
+        let Some(ref placement) = self.placement else {
+            return false;
+        };
+        let resource = PlacedResource::Buffer(Arc::downgrade(self));
+        if !placement.heap.activate(&placement.range, resource) {
+            return false;
+        }
+        let size = wgt::math::align_to(self.size, wgt::COPY_BUFFER_ALIGNMENT);
+        self.initialization_status.write().discard_range(0..size);
+        true
+    }
+
     pub(crate) fn is_destroyed(&self, guard: &SnatchGuard) -> bool {
         self.raw.get(guard).is_none()
     }
 
     // Note: This must not be called while holding a lock.
     pub(crate) fn unmap(self: &Arc<Self>) -> Result<(), BufferAccessError> {
-        if let Some((mut operation, status)) = self.unmap_inner()? {
-            if let Some(callback) = operation.callback.take() {
-                callback.call(status);
-            }
+        if let Some(closure) = self.unmap_inner()? {
+            let closures = UserClosures {
+                mappings: vec![closure],
+                ..Default::default()
+            };
+            self.device.defer_closures(closures).fire();
         }
 
         Ok(())
@@ -449,7 +484,7 @@ impl<A: HalApi> Buffer<A> {
                 needs_flush,
             } => {
                 #[cfg(feature = "trace")]
-                if let Some(ref mut trace) = *device.trace.lock() {
+                if let Some(trace) = device.trace.lock().as_mut().filter(|t| !t.shaders_only) {
                     let data = trace.make_binary("bin", unsafe {
                         std::slice::from_raw_parts(ptr.as_ptr(), self.size as usize)
                     });
@@ -501,7 +536,7 @@ impl<A: HalApi> Buffer<A> {
                     }
                 }
                 pending_writes.consume_temp(queue::TempResource::Buffer(stage_buffer));
-                pending_writes.dst_buffers.insert(buffer_id, self.clone());
+                pending_writes.insert_buffer(buffer_id, self.clone());
             }
             resource::BufferMapState::Idle => {
                 return Err(BufferAccessError::NotMapped);
@@ -509,10 +544,12 @@ impl<A: HalApi> Buffer<A> {
             resource::BufferMapState::Waiting(pending) => {
                 return Ok(Some((pending.op, Err(BufferAccessError::MapAborted))));
             }
-            resource::BufferMapState::Active { ptr, range, host } => {
+            resource::BufferMapState::Active {
+                ptr, range, host, ..
+            } => {
                 if host == HostMap::Write {
                     #[cfg(feature = "trace")]
-                    if let Some(ref mut trace) = *device.trace.lock() {
+                    if let Some(trace) = device.trace.lock().as_mut().filter(|t| !t.shaders_only) {
                         let size = range.end - range.start;
                         let data = trace.make_binary("bin", unsafe {
                             std::slice::from_raw_parts(ptr.as_ptr(), size as usize)
@@ -603,6 +640,27 @@ pub enum CreateBufferError {
     MaxBufferSize { requested: u64, maximum: u64 },
     #[error(transparent)]
     MissingDownlevelFlags(#[from] MissingDownlevelFlags),
+    #[error(transparent)]
+    MissingFeatures(#[from] MissingFeatures),
+    #[error("Sparse buffers can't be mapped, requested usage {0:?}")]
+    MappableSparse(wgt::BufferUsages),
+    #[error(transparent)]
+    Placement(#[from] PlacementError),
+    #[error("Buffers with external memory can't be mapped, requested usage {0:?}")]
+    MappableExternal(wgt::BufferUsages),
+}
+
+#[derive(Clone, Debug, Error)]
+#[non_exhaustive]
+pub enum BufferDeviceAddressError {
+    #[error("Buffer is invalid")]
+    Invalid,
+    #[error("Buffer is destroyed")]
+    Destroyed,
+    #[error(transparent)]
+    MissingFeatures(#[from] MissingFeatures),
+    #[error("Only buffers with `BufferUsages::STORAGE` have an address, the buffer has {0:?}")]
+    MissingStorageUsage(wgt::BufferUsages),
 }
 
 impl<A: HalApi> Resource for Buffer<A> {
@@ -754,8 +812,9 @@ pub enum TextureClearMode<A: HalApi> {
         clear_views: SmallVec<[Option<A::TextureView>; 1]>,
         is_color: bool,
     },
+    // View for clear via RenderPass for every array layer of a surface texture
     Surface {
-        clear_view: Option<A::TextureView>,
+        clear_views: SmallVec<[Option<A::TextureView>; 1]>,
     },
     // Texture can't be cleared, attempting to do so will cause panic.
     // (either because it is impossible for the type of texture or it is being destroyed)
@@ -769,12 +828,26 @@ pub struct Texture<A: HalApi> {
     pub(crate) desc: wgt::TextureDescriptor<(), Vec<wgt::TextureFormat>>,
     pub(crate) hal_usage: hal::TextureUses,
     pub(crate) format_features: wgt::TextureFormatFeatures,
+    /// The tiles of the texture if it was created with
+    /// `Device::create_sparse_texture`, so that they are committed to memory
+    /// by `Queue::bind_sparse_texture`.
+    pub(crate) sparse: Option<wgt::SparseTextureTiles>,
+    /// Where the texture is placed if it was created with
+    /// `Device::create_placed_texture`.
+    pub(crate) placement: Option<Placement<A>>,
+    /// Whether the raw texture was made outside of `Device::create_texture`,
+    /// as with `Device::create_texture_from_hal`, so that it can't be assumed
+    /// to be shared with the transfer queue.
+    pub(crate) imported: bool,
     pub(crate) initialization_status: RwLock<TextureInitTracker>,
     pub(crate) full_range: TextureSelector,
     pub(crate) info: ResourceInfo<Texture<A>>,
     pub(crate) clear_mode: RwLock<TextureClearMode<A>>,
     pub(crate) views: Mutex<Vec<Weak<TextureView<A>>>>,
     pub(crate) bind_groups: Mutex<Vec<Weak<BindGroup<A>>>>,
+    /// Set by the producer of the frame the texture holds when it reuses it,
+    /// see [`Global::texture_expiry`](crate::global::Global::texture_expiry).
+    pub(crate) expiry: wgt::TextureExpiry,
 }
 
 impl<A: HalApi> Drop for Texture<A> {
@@ -785,13 +858,15 @@ impl<A: HalApi> Drop for Texture<A> {
         let clear_mode = &mut *clear_mode;
         match *clear_mode {
             TextureClearMode::Surface {
-                ref mut clear_view, ..
+                ref mut clear_views,
             } => {
-                if let Some(view) = clear_view.take() {
-                    unsafe {
-                        self.device.raw().destroy_texture_view(view);
+                clear_views.iter_mut().for_each(|clear_view| {
+                    if let Some(view) = clear_view.take() {
+                        unsafe {
+                            self.device.raw().destroy_texture_view(view);
+                        }
                     }
-                }
+                });
             }
             TextureClearMode::RenderPass {
                 ref mut clear_views,
@@ -822,6 +897,25 @@ impl<A: HalApi> Drop for Texture<A> {
 }
 
 impl<A: HalApi> Texture<A> {
+    /// Make a placed texture take its memory over from the resources that
+    /// overlap it, see [`MemoryHeap::activate`].
+    ///
+    /// Returns `true` if it did, in which case the texture is uninitialized.
+    /// Its state in the device tracker has to be reset to
+    /// `TextureUses::UNINITIALIZED` by the caller.
+    pub(crate) fn activate_placement(self: &Arc<Self>) -> bool {
+        let Some(ref placement) = self.placement else {
+            return false;
+        };
+        let resource = PlacedResource::Texture(Arc::downgrade(self));
+        if !placement.heap.activate(&placement.range, resource) {
+            return false;
+        }
+        *self.initialization_status.write() =
+            TextureInitTracker::new(self.desc.mip_level_count, self.desc.array_layer_count());
+        true
+    }
+
     pub(crate) fn raw<'a>(&'a self, snatch_guard: &'a SnatchGuard) -> Option<&'a A::Texture> {
         self.inner.get(snatch_guard)?.raw()
     }
@@ -849,7 +943,9 @@ impl<A: HalApi> Texture<A> {
             TextureClearMode::None => {
                 panic!("Given texture can't be cleared")
             }
-            TextureClearMode::Surface { ref clear_view, .. } => clear_view.as_ref().unwrap(),
+            TextureClearMode::Surface { ref clear_views } => {
+                clear_views[depth_or_layer as usize].as_ref().unwrap()
+            }
             TextureClearMode::RenderPass {
                 ref clear_views, ..
             } => {
@@ -943,6 +1039,40 @@ impl Global {
         hal_texture_callback(hal_texture)
     }
 
+    /// Like [`Global::texture_as_hal`], giving access to the surface texture
+    /// a texture acquired from a surface wraps, such as the swapchain image
+    /// index and semaphores.
+    ///
+    /// The callback receives `None` if the texture isn't an acquired surface
+    /// texture.
+    ///
+    /// # Safety
+    ///
+    /// - The raw surface texture handle must not be manually destroyed or
+    ///   presented
+    pub unsafe fn surface_texture_as_hal<
+        A: HalApi,
+        F: FnOnce(Option<&A::SurfaceTexture>) -> R,
+        R,
+    >(
+        &self,
+        id: TextureId,
+        hal_surface_texture_callback: F,
+    ) -> R {
+        profiling::scope!("SurfaceTexture::as_hal");
+
+        let hub = A::hub(self);
+        let texture_opt = { hub.textures.try_get(id).ok().flatten() };
+        let texture = texture_opt.as_ref().unwrap();
+        let snatch_guard = texture.device.snatchable_lock.read();
+        let hal_surface_texture = match texture.inner.get(&snatch_guard) {
+            Some(TextureInner::Surface { raw: Some(raw), .. }) => Some(raw),
+            _ => None,
+        };
+
+        hal_surface_texture_callback(hal_surface_texture)
+    }
+
     /// # Safety
     ///
     /// - The raw texture view handle must not be manually destroyed
@@ -996,6 +1126,25 @@ impl Global {
         hal_device_callback(hal_device)
     }
 
+    /// # Safety
+    ///
+    /// - The raw queue handle must not be manually destroyed
+    /// - Work must not be submitted to the raw queue concurrently with
+    ///   submissions through `wgpu`
+    pub unsafe fn queue_as_hal<A: HalApi, F: FnOnce(Option<&A::Queue>) -> R, R>(
+        &self,
+        id: QueueId,
+        hal_queue_callback: F,
+    ) -> R {
+        profiling::scope!("Queue::as_hal");
+
+        let hub = A::hub(self);
+        let queue = hub.queues.try_get(id).ok().flatten();
+        let hal_queue = queue.as_ref().and_then(|queue| queue.raw.as_ref());
+
+        hal_queue_callback(hal_queue)
+    }
+
     /// # Safety
     ///
     /// - The raw fence handle must not be manually destroyed
@@ -1013,6 +1162,25 @@ impl Global {
         hal_fence_callback(hal_fence.as_deref().unwrap().as_ref())
     }
 
+    /// # Safety
+    ///
+    /// - The raw fence handle must not be manually destroyed
+    /// - The fence must not be signaled with values lower than those
+    ///   already signaled through `wgpu`
+    pub unsafe fn timeline_semaphore_as_hal<A: HalApi, F: FnOnce(Option<&A::Fence>) -> R, R>(
+        &self,
+        id: TimelineSemaphoreId,
+        hal_fence_callback: F,
+    ) -> R {
+        profiling::scope!("TimelineSemaphore::as_hal");
+
+        let hub = A::hub(self);
+        let semaphore = hub.timeline_semaphores.try_get(id).ok().flatten();
+        let hal_fence = semaphore.as_ref().map(|semaphore| semaphore.raw.lock());
+
+        hal_fence_callback(hal_fence.as_deref().and_then(Option::as_ref))
+    }
+
     /// # Safety
     /// - The raw surface handle must not be manually destroyed
     pub unsafe fn surface_as_hal<A: HalApi, F: FnOnce(Option<&A::Surface>) -> R, R>(
@@ -1168,6 +1336,8 @@ pub enum CreateTextureError {
     CreateTextureView(#[from] CreateTextureViewError),
     #[error("Invalid usage flags {0:?}")]
     InvalidUsage(wgt::TextureUsages),
+    #[error("Texture usage TRANSIENT can only be combined with RENDER_ATTACHMENT, not {0:?}")]
+    InvalidTransientUsage(wgt::TextureUsages),
     #[error(transparent)]
     InvalidDimension(#[from] TextureDimensionError),
     #[error("Depth texture ({1:?}) can't be created as {0:?}")]
@@ -1183,7 +1353,7 @@ pub enum CreateTextureError {
         if *.2 { " due to downlevel restrictions" } else { "" }
     )]
     InvalidFormatUsages(wgt::TextureUsages, wgt::TextureFormat, bool),
-    #[error("The view format {0:?} is not compatible with texture format {1:?}, only changing srgb-ness is allowed.")]
+    #[error("The view format {0:?} is not compatible with texture format {1:?}, only changing srgb-ness or reinterpreting texel blocks of the same size is allowed.")]
     InvalidViewFormat(wgt::TextureFormat, wgt::TextureFormat),
     #[error("Texture usages {0:?} are not allowed on a texture of dimensions {1:?}")]
     InvalidDimensionUsages(wgt::TextureUsages, wgt::TextureDimension),
@@ -1199,6 +1369,48 @@ pub enum CreateTextureError {
     MissingFeatures(wgt::TextureFormat, #[source] MissingFeatures),
     #[error(transparent)]
     MissingDownlevelFlags(#[from] MissingDownlevelFlags),
+    #[error(transparent)]
+    MissingSparseFeature(MissingFeatures),
+    #[error("Sparse textures must be 2D or 3D, single sampled and of a color format, not {dimension:?} of {format:?} with {sample_count} samples")]
+    InvalidSparse {
+        dimension: wgt::TextureDimension,
+        format: wgt::TextureFormat,
+        sample_count: u32,
+    },
+    #[error(transparent)]
+    Placement(#[from] PlacementError),
+    #[error(transparent)]
+    MissingExternalMemoryFeature(MissingFeatures),
+    #[error("IOSurface textures must be 2D, with a single layer, mip level and sample, not {dimension:?} with {layers} layers, {mip_level_count} mip levels and {sample_count} samples")]
+    InvalidIOSurface {
+        dimension: wgt::TextureDimension,
+        layers: u32,
+        mip_level_count: u32,
+        sample_count: u32,
+    },
+    #[error("Texture format {0:?} can't be held by IOSurfaces created by wgpu")]
+    UnsupportedIOSurfaceFormat(wgt::TextureFormat),
+    #[error("EGLImage textures must be 2D, with a single layer, mip level and sample, not {dimension:?} with {layers} layers, {mip_level_count} mip levels and {sample_count} samples")]
+    InvalidEglImage {
+        dimension: wgt::TextureDimension,
+        layers: u32,
+        mip_level_count: u32,
+        sample_count: u32,
+    },
+}
+
+/// Error exporting the memory of a buffer or texture.
+#[derive(Clone, Debug, Error)]
+#[non_exhaustive]
+pub enum ExportMemoryError {
+    #[error(transparent)]
+    Device(#[from] DeviceError),
+    #[error("Buffer {0:?} is invalid or destroyed")]
+    InvalidBuffer(BufferId),
+    #[error("Texture {0:?} is invalid or destroyed")]
+    InvalidTexture(TextureId),
+    #[error(transparent)]
+    MissingFeatures(#[from] MissingFeatures),
 }
 
 impl<A: HalApi> Resource for Texture<A> {
@@ -1243,6 +1455,10 @@ pub struct TextureViewDescriptor<'a> {
     pub dimension: Option<wgt::TextureViewDimension>,
     /// Range within the texture that is accessible via this view.
     pub range: wgt::ImageSubresourceRange,
+    /// Rearrangement of the components read through this view.
+    ///
+    /// Views with anything but the identity can only be bound as sampled textures.
+    pub swizzle: wgt::TextureComponentSwizzle,
 }
 
 #[derive(Debug)]
@@ -1251,6 +1467,7 @@ pub(crate) struct HalTextureViewDescriptor {
     pub format: wgt::TextureFormat,
     pub dimension: wgt::TextureViewDimension,
     pub range: wgt::ImageSubresourceRange,
+    pub swizzle: wgt::TextureComponentSwizzle,
 }
 
 impl HalTextureViewDescriptor {
@@ -1273,6 +1490,10 @@ pub enum TextureViewNotRenderableReason {
         "The aspects of this texture view are a subset of the aspects in the original texture. Aspects: {0:?}"
     )]
     Aspects(hal::FormatAspects),
+    #[error("This texture view swizzles its components. Swizzle: {0:?}")]
+    Swizzle(wgt::TextureComponentSwizzle),
+    #[error("The format of this texture view can't be rendered to. View format: {0:?}")]
+    Format(wgt::TextureFormat),
 }
 
 #[derive(Debug)]
@@ -1360,6 +1581,15 @@ pub enum CreateTextureViewError {
         texture: wgt::TextureFormat,
         view: wgt::TextureFormat,
     },
+    #[error("Texture views that see the blocks of a compressed texture as texels must have a single mip level and array layer, not {mip_level_count} mip levels and {array_layer_count} array layers")]
+    InvalidBlockTexelViewRange {
+        mip_level_count: u32,
+        array_layer_count: u32,
+    },
+    #[error(transparent)]
+    MissingFeatures(#[from] MissingFeatures),
+    #[error(transparent)]
+    MissingDownlevelFlags(#[from] MissingDownlevelFlags),
 }
 
 #[derive(Clone, Debug, Error)]
@@ -1400,6 +1630,9 @@ pub struct SamplerDescriptor<'a> {
     pub lod_min_clamp: f32,
     /// Maximum level of detail (i.e. mip level) to use
     pub lod_max_clamp: f32,
+    /// Bias added to the level of detail before clamping.
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub lod_bias: f32,
     /// If this is enabled, this is a comparison sampler using the given comparison function.
     pub compare: Option<wgt::CompareFunction>,
     /// Must be at least 1. If this is not 1, all filter modes must be linear.
@@ -1407,6 +1640,9 @@ pub struct SamplerDescriptor<'a> {
     /// Border color to use when address_mode is
     /// [`AddressMode::ClampToBorder`](wgt::AddressMode::ClampToBorder)
     pub border_color: Option<wgt::SamplerBorderColor>,
+    /// How the sampler combines the texels it filters.
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub reduction_mode: wgt::SamplerReductionMode,
 }
 
 #[derive(Debug)]
@@ -1472,6 +1708,8 @@ pub enum CreateSamplerError {
         lod_min_clamp: f32,
         lod_max_clamp: f32,
     },
+    #[error("Invalid lodBias: {0}. Must be finite.")]
+    InvalidLodBias(f32),
     #[error("Invalid anisotropic clamp: {0}. Must be at least 1.")]
     InvalidAnisotropy(u16),
     #[error("Invalid filter mode for {filter_type:?}: {filter_mode:?}. When anistropic clamp is not 1 (it is {anisotropic_clamp}), all filter modes must be linear.")]
@@ -1480,11 +1718,17 @@ pub enum CreateSamplerError {
         filter_mode: wgt::FilterMode,
         anisotropic_clamp: u16,
     },
+    #[error(
+        "Invalid reduction mode {0:?}. Comparison samplers can only use the weighted average."
+    )]
+    InvalidReductionModeWithComparison(wgt::SamplerReductionMode),
     #[error("Cannot create any more samplers")]
     TooManyObjects,
     /// AddressMode::ClampToBorder requires feature ADDRESS_MODE_CLAMP_TO_BORDER.
     #[error(transparent)]
     MissingFeatures(#[from] MissingFeatures),
+    #[error(transparent)]
+    MissingDownlevelFlags(#[from] MissingDownlevelFlags),
 }
 
 impl<A: HalApi> Resource for Sampler<A> {
@@ -1561,6 +1805,286 @@ impl<A: HalApi> QuerySet<A> {
     }
 }
 
+pub type TimelineSemaphoreDescriptor<'a> = wgt::TimelineSemaphoreDescriptor<Label<'a>>;
+
+/// A counter that queue submissions and the host can wait for and signal.
+///
+/// See [`wgt::Features::TIMELINE_SEMAPHORE`].
+#[derive(Debug)]
+pub struct TimelineSemaphore<A: HalApi> {
+    /// The fence backing this semaphore. Submissions that signal it need
+    /// mutable access.
+    pub(crate) raw: Mutex<Option<A::Fence>>,
+    /// The highest value this semaphore has been signaled with through `wgpu`.
+    ///
+    /// Signals have to increase the value of the semaphore. They don't,
+    /// necessarily, have completed yet.
+    pub(crate) last_signaled_value: AtomicU64,
+    /// The handle type the semaphore can be exported through, if it was
+    /// created exportable or imported.
+    pub(crate) handle_type: Option<wgt::ExternalSemaphoreHandleType>,
+    pub(crate) device: Arc<Device<A>>,
+    pub(crate) info: ResourceInfo<Self>,
+}
+
+impl<A: HalApi> Drop for TimelineSemaphore<A> {
+    fn drop(&mut self) {
+        resource_log!("Destroy raw TimelineSemaphore {:?}", self.info.label());
+        if let Some(raw) = self.raw.lock().take() {
+            unsafe {
+                use hal::Device;
+                self.device.raw().destroy_fence(raw);
+            }
+        }
+    }
+}
+
+impl<A: HalApi> Resource for TimelineSemaphore<A> {
+    const TYPE: ResourceType = "TimelineSemaphore";
+
+    type Marker = crate::id::markers::TimelineSemaphore;
+
+    fn as_info(&self) -> &ResourceInfo<Self> {
+        &self.info
+    }
+
+    fn as_info_mut(&mut self) -> &mut ResourceInfo<Self> {
+        &mut self.info
+    }
+}
+
+impl<A: HalApi> TimelineSemaphore<A> {
+    /// Record that `value` is going to be signaled, making sure it exceeds
+    /// the values signaled before.
+    pub(crate) fn begin_signal(&self, value: u64) -> Result<(), TimelineSemaphoreError> {
+        let last = self.last_signaled_value.fetch_max(value, Ordering::Relaxed);
+        if value <= last {
+            return Err(TimelineSemaphoreError::ValueNotIncreasing { value, last });
+        }
+        Ok(())
+    }
+}
+
+#[derive(Clone, Debug, Error)]
+#[non_exhaustive]
+pub enum CreateTimelineSemaphoreError {
+    #[error(transparent)]
+    Device(#[from] DeviceError),
+    #[error(transparent)]
+    MissingFeatures(#[from] MissingFeatures),
+}
+
+#[derive(Clone, Debug, Error)]
+#[non_exhaustive]
+pub enum TimelineSemaphoreError {
+    #[error(transparent)]
+    Device(#[from] DeviceError),
+    #[error("TimelineSemaphore {0:?} is invalid")]
+    Invalid(TimelineSemaphoreId),
+    #[error("Signal value {value} is not greater than {last}, the last value the semaphore was signaled with")]
+    ValueNotIncreasing { value: u64, last: u64 },
+}
+
+/// Error exporting a timeline semaphore, or synchronizing with a sync file.
+#[derive(Clone, Debug, Error)]
+#[non_exhaustive]
+pub enum ExternalSemaphoreError {
+    #[error(transparent)]
+    Device(#[from] DeviceError),
+    #[error("TimelineSemaphore {0:?} is invalid")]
+    Invalid(TimelineSemaphoreId),
+    #[error("TimelineSemaphore {0:?} was neither created exportable nor imported")]
+    NotExternal(TimelineSemaphoreId),
+    #[error(transparent)]
+    MissingFeatures(#[from] MissingFeatures),
+}
+
+/// Error sharing a texture with a D3D11 device layered on a DX12 queue.
+#[derive(Clone, Debug, Error)]
+#[non_exhaustive]
+pub enum D3D11On12Error {
+    #[error(transparent)]
+    Device(#[from] DeviceError),
+    #[error("Texture {0:?} is invalid or was acquired from a surface")]
+    InvalidTexture(TextureId),
+    #[error("D3D11On12 devices can only be created on the DX12 backend, not on {0:?}")]
+    Unsupported(wgt::Backend),
+    #[error(transparent)]
+    Submit(#[from] queue::QueueSubmitError),
+}
+
+pub type MemoryHeapDescriptor<'a> = wgt::MemoryHeapDescriptor<Label<'a>>;
+
+/// A block of memory that buffers and textures can be placed in, possibly
+/// overlapping each other.
+///
+/// Of the resources placed at overlapping ranges, only the one used last has
+/// defined contents. Using one makes it take the memory over at submission,
+/// see [`MemoryHeap::activate`].
+///
+/// See [`wgt::Features::PLACED_RESOURCES`].
+#[derive(Debug)]
+pub struct MemoryHeap<A: HalApi> {
+    pub(crate) raw: Option<A::MemoryHeap>,
+    pub(crate) size: wgt::BufferAddress,
+    /// The ranges taken over by resources, which don't overlap.
+    pub(crate) active: Mutex<Vec<(Range<wgt::BufferAddress>, PlacedResource<A>)>>,
+    pub(crate) device: Arc<Device<A>>,
+    pub(crate) info: ResourceInfo<Self>,
+}
+
+impl<A: HalApi> Drop for MemoryHeap<A> {
+    fn drop(&mut self) {
+        resource_log!("Destroy raw MemoryHeap {:?}", self.info.label());
+        if let Some(raw) = self.raw.take() {
+            unsafe {
+                use hal::Device;
+                self.device.raw().destroy_memory_heap(raw);
+            }
+        }
+    }
+}
+
+impl<A: HalApi> Resource for MemoryHeap<A> {
+    const TYPE: ResourceType = "MemoryHeap";
+
+    type Marker = crate::id::markers::MemoryHeap;
+
+    fn as_info(&self) -> &ResourceInfo<Self> {
+        &self.info
+    }
+
+    fn as_info_mut(&mut self) -> &mut ResourceInfo<Self> {
+        &mut self.info
+    }
+}
+
+impl<A: HalApi> MemoryHeap<A> {
+    pub(crate) fn raw(&self) -> &A::MemoryHeap {
+        self.raw.as_ref().unwrap()
+    }
+
+    /// Check that a resource with `requirements` can be placed at `offset`.
+    pub(crate) fn place(
+        self: &Arc<Self>,
+        offset: wgt::BufferAddress,
+        requirements: wgt::MemoryRequirements,
+    ) -> Result<Placement<A>, PlacementError> {
+        if offset % requirements.alignment != 0 {
+            return Err(PlacementError::UnalignedOffset {
+                offset,
+                alignment: requirements.alignment,
+            });
+        }
+        if offset
+            .checked_add(requirements.size)
+            .map_or(true, |end| end > self.size)
+        {
+            return Err(PlacementError::OutOfBounds {
+                offset,
+                size: requirements.size,
+                heap_size: self.size,
+            });
+        }
+        Ok(Placement {
+            heap: self.clone(),
+            range: offset..offset + requirements.size,
+        })
+    }
+
+    /// Let `resource` take over the memory in `range`.
+    ///
+    /// Returns `false` if it already has. Otherwise, the contents of the
+    /// resource are undefined, as are those of the resources it took the
+    /// memory over from.
+    pub(crate) fn activate(
+        &self,
+        range: &Range<wgt::BufferAddress>,
+        resource: PlacedResource<A>,
+    ) -> bool {
+        let mut active = self.active.lock();
+        if active.iter().any(|(_, other)| other.ptr_eq(&resource)) {
+            return false;
+        }
+        active.retain(|(other_range, other)| {
+            other.is_alive() && (other_range.end <= range.start || range.end <= other_range.start)
+        });
+        active.push((range.clone(), resource));
+        true
+    }
+}
+
+/// Where a buffer or texture is placed in a [`MemoryHeap`].
+#[derive(Debug)]
+pub struct Placement<A: HalApi> {
+    pub(crate) heap: Arc<MemoryHeap<A>>,
+    pub(crate) range: Range<wgt::BufferAddress>,
+}
+
+impl<A: HalApi> Placement<A> {
+    pub(crate) fn overlaps(&self, other: &Self) -> bool {
+        Arc::ptr_eq(&self.heap, &other.heap)
+            && self.range.start < other.range.end
+            && other.range.start < self.range.end
+    }
+}
+
+/// A resource that has taken over memory of a [`MemoryHeap`].
+#[derive(Debug)]
+pub(crate) enum PlacedResource<A: HalApi> {
+    Buffer(Weak<Buffer<A>>),
+    Texture(Weak<Texture<A>>),
+}
+
+impl<A: HalApi> PlacedResource<A> {
+    fn ptr_eq(&self, other: &Self) -> bool {
+        match (self, other) {
+            (Self::Buffer(a), Self::Buffer(b)) => Weak::ptr_eq(a, b),
+            (Self::Texture(a), Self::Texture(b)) => Weak::ptr_eq(a, b),
+            _ => false,
+        }
+    }
+
+    fn is_alive(&self) -> bool {
+        match self {
+            Self::Buffer(buffer) => buffer.strong_count() != 0,
+            Self::Texture(texture) => texture.strong_count() != 0,
+        }
+    }
+}
+
+#[derive(Clone, Debug, Error)]
+#[non_exhaustive]
+pub enum CreateMemoryHeapError {
+    #[error(transparent)]
+    Device(#[from] DeviceError),
+    #[error(transparent)]
+    MissingFeatures(#[from] MissingFeatures),
+    #[error("Memory heaps can't be empty")]
+    ZeroSize,
+}
+
+#[derive(Clone, Debug, Error)]
+#[non_exhaustive]
+pub enum PlacementError {
+    #[error(transparent)]
+    MissingFeatures(#[from] MissingFeatures),
+    #[error("MemoryHeap {0:?} is invalid")]
+    InvalidHeap(MemoryHeapId),
+    #[error("Placed buffers can't be mapped, requested usage {0:?}")]
+    Mappable(wgt::BufferUsages),
+    #[error("Offset {offset} is not a multiple of the required alignment {alignment}")]
+    UnalignedOffset { offset: u64, alignment: u64 },
+    #[error(
+        "Resource of {size} bytes at offset {offset} doesn't fit in a heap of {heap_size} bytes"
+    )]
+    OutOfBounds {
+        offset: u64,
+        size: u64,
+        heap_size: u64,
+    },
+}
+
 #[derive(Clone, Debug, Error)]
 #[non_exhaustive]
 pub enum DestroyError {
@@ -0,0 +1,880 @@
+//! Translation of WGSL to the shading languages of the backends, without a
+//! device.
+//!
+//! [`translate_wgsl`] parses and validates WGSL like
+//! [`Global::device_create_shader_module`] does, lays the bind group layouts
+//! out like the pipeline layouts of the backend, and generates the code the
+//! backend would hand to its driver, so that shader libraries can
+//! snapshot-test their translations for every backend in CI.
+//!
+//! The layouts and options mirror the ones `wgpu-hal` builds. What depends on
+//! the adapter is given by the [`TranslationDescriptor`]: its features and
+//! downlevel flags, and the capabilities of the [`TranslationTarget`]. Debug
+//! information of [`wgt::InstanceFlags::DEBUG`] is never generated.
+//!
+//! [`Global::device_create_shader_module`]: crate::global::Global::device_create_shader_module
+
+use crate::{pipeline::ShaderError, validation};
+
+use std::{collections::BTreeMap, num::NonZeroU32};
+
+use thiserror::Error;
+use wgt::{BindGroupLayoutEntry, BindingType, BufferBindingType};
+
+/// The backend to generate the code of, with the capabilities of the adapter
+/// the code depends on.
+#[derive(Clone, Debug)]
+pub enum TranslationTarget {
+    /// SPIR-V, as given to Vulkan.
+    SpirV {
+        /// The device supports `robustBufferAccess`, so buffer accesses aren't
+        /// checked in the shader.
+        robust_buffer_access: bool,
+        /// The device supports `robustImageAccess`, so image loads aren't
+        /// checked in the shader.
+        robust_image_access: bool,
+        /// The device supports `shaderZeroInitializeWorkgroupMemory`.
+        native_zero_initialize_workgroup_memory: bool,
+        /// Varyings are named. Disabled on Qualcomm devices.
+        label_varyings: bool,
+        /// Entry points are compiled separately, with their pipeline layout.
+        /// This is forced by the `SEPARATE_ENTRY_POINTS` workaround, pipeline
+        /// constants and [`wgt::Features::SAMPLER_YCBCR_CONVERSION`].
+        separate_entry_points: bool,
+    },
+    /// HLSL, as given to DXC or FXC by DX12.
+    Hlsl {
+        shader_model: naga::back::hlsl::ShaderModel,
+    },
+    /// MSL, as given to Metal.
+    Msl {
+        /// The (major, minor) version of the Metal Shading Language.
+        lang_version: (u8, u8),
+    },
+    /// GLSL, as given to OpenGL and OpenGL ES.
+    Glsl {
+        version: naga::back::glsl::Version,
+        /// The context supports `GL_EXT_texture_shadow_lod`.
+        texture_shadow_lod: bool,
+        /// The context supports `gl_BaseVertex` and `gl_BaseInstance`.
+        draw_parameters: bool,
+    },
+}
+
+/// Describes a shader stage to translate, and the adapter it's translated
+/// for.
+#[derive(Clone, Debug)]
+pub struct TranslationDescriptor<'a> {
+    /// The name of the entry point.
+    pub entry_point: &'a str,
+    pub stage: naga::ShaderStage,
+    /// The features enabled on the device.
+    pub features: wgt::Features,
+    /// The downlevel flags of the adapter.
+    pub downlevel_flags: wgt::DownlevelFlags,
+    /// The entries of the bind group layouts of the pipeline layout, indexed by
+    /// group.
+    pub bind_group_layouts: &'a [&'a [BindGroupLayoutEntry]],
+    /// The push constant ranges of the pipeline layout.
+    pub push_constant_ranges: &'a [wgt::PushConstantRange],
+    /// Values of the pipeline-overridable constants of the shader.
+    pub constants: &'a naga::back::PipelineConstants,
+    /// Whether the shader module is created with run-time bounds checks, see
+    /// [`wgt::ShaderBoundChecks`].
+    pub runtime_checks: bool,
+    /// Whether workgroup memory is zero initialized by the pipeline.
+    pub zero_initialize_workgroup_memory: bool,
+    /// Whether the pipeline draws point lists. Only used by MSL.
+    pub point_list: bool,
+    /// The number of views the render pass renders to. Only used by GLSL.
+    pub multiview: Option<NonZeroU32>,
+    pub target: TranslationTarget,
+}
+
+/// The code generated for a backend.
+#[derive(Clone, Debug, PartialEq)]
+pub enum TranslatedShader {
+    SpirV(Vec<u32>),
+    Source(String),
+}
+
+//Note: `Clone` would require `WithSpan: Clone`.
+#[derive(Debug, Error)]
+#[non_exhaustive]
+pub enum TranslationError {
+    #[error(transparent)]
+    Parsing(#[from] ShaderError<naga::front::wgsl::ParseError>),
+    #[error(transparent)]
+    Validation(#[from] ShaderError<naga::WithSpan<naga::valid::ValidationError>>),
+    #[error("Binding {binding} of group {group} has a type the backend doesn't support")]
+    UnsupportedBinding { group: u32, binding: u32 },
+    #[error("Unable to find entry point {0:?} of stage {1:?}")]
+    EntryPoint(String, naga::ShaderStage),
+    #[error("Failed to generate the backend-specific code: {0}")]
+    Generation(String),
+}
+
+fn map_naga_stage(stage: naga::ShaderStage) -> wgt::ShaderStages {
+    match stage {
+        naga::ShaderStage::Vertex => wgt::ShaderStages::VERTEX,
+        naga::ShaderStage::Fragment => wgt::ShaderStages::FRAGMENT,
+        naga::ShaderStage::Compute => wgt::ShaderStages::COMPUTE,
+    }
+}
+
+/// Translate the entry point `desc.entry_point` of the WGSL `source` to the
+/// code `desc.target` would be given to its driver, without a device.
+pub fn translate_wgsl(
+    source: &str,
+    desc: &TranslationDescriptor,
+) -> Result<TranslatedShader, TranslationError> {
+    let options = naga::front::wgsl::Options {
+        diagnostic_filters: Vec::new(),
+        available_extensions: Some(validation::wgsl_extensions(desc.features)),
+    };
+    let (module, _warnings) = naga::front::wgsl::Frontend::new_with_options(options)
+        .parse_with_warnings(source)
+        .map_err(|inner| ShaderError {
+            source: source.to_string(),
+            label: None,
+            inner: Box::new(inner),
+        })?;
+    let info = validation::create_validator(
+        desc.features,
+        desc.downlevel_flags,
+        wgt::DeviceFlags::empty(),
+        naga::valid::ValidationFlags::all(),
+    )
+    .validate(&module)
+    .map_err(|inner| ShaderError {
+        source: source.to_string(),
+        label: None,
+        inner: Box::new(inner),
+    })?;
+
+    if !module
+        .entry_points
+        .iter()
+        .any(|ep| ep.stage == desc.stage && ep.name == desc.entry_point)
+    {
+        return Err(TranslationError::EntryPoint(
+            desc.entry_point.to_string(),
+            desc.stage,
+        ));
+    }
+
+    // Bind group layouts reach the backends sorted by binding.
+    let bind_group_layouts = desc
+        .bind_group_layouts
+        .iter()
+        .map(|entries| {
+            let mut entries = entries.to_vec();
+            entries.sort_by_key(|entry| entry.binding);
+            entries
+        })
+        .collect::<Vec<_>>();
+
+    match desc.target {
+        TranslationTarget::SpirV { .. } => {
+            translate_spirv(&module, &info, desc, &bind_group_layouts)
+        }
+        TranslationTarget::Hlsl { .. } => translate_hlsl(&module, &info, desc, &bind_group_layouts),
+        TranslationTarget::Msl { .. } => translate_msl(&module, &info, desc, &bind_group_layouts),
+        TranslationTarget::Glsl { .. } => translate_glsl(&module, &info, desc, &bind_group_layouts),
+    }
+}
+
+fn process_overrides<'a>(
+    module: &'a naga::Module,
+    info: &'a naga::valid::ModuleInfo,
+    desc: &TranslationDescriptor,
+) -> Result<
+    (
+        std::borrow::Cow<'a, naga::Module>,
+        std::borrow::Cow<'a, naga::valid::ModuleInfo>,
+    ),
+    TranslationError,
+> {
+    naga::back::pipeline_constants::process_overrides(module, info, desc.constants)
+        .map_err(|e| TranslationError::Generation(format!("{e}")))
+}
+
+/// Mirrors the `naga_options` of the Vulkan adapter and the compilation of
+/// shader modules and stages of the Vulkan device.
+fn translate_spirv(
+    module: &naga::Module,
+    info: &naga::valid::ModuleInfo,
+    desc: &TranslationDescriptor,
+    bind_group_layouts: &[Vec<BindGroupLayoutEntry>],
+) -> Result<TranslatedShader, TranslationError> {
+    use naga::back::spv;
+    use naga::proc::{BoundsCheckPolicies, BoundsCheckPolicy};
+
+    let TranslationTarget::SpirV {
+        robust_buffer_access,
+        robust_image_access,
+        native_zero_initialize_workgroup_memory,
+        label_varyings,
+        separate_entry_points,
+    } = desc.target
+    else {
+        unreachable!()
+    };
+    let features = desc.features;
+
+    let mut capabilities = vec![
+        spv::Capability::Shader,
+        spv::Capability::Matrix,
+        spv::Capability::Sampled1D,
+        spv::Capability::Image1D,
+        spv::Capability::ImageQuery,
+        spv::Capability::DerivativeControl,
+        spv::Capability::StorageImageExtendedFormats,
+    ];
+    if desc
+        .downlevel_flags
+        .contains(wgt::DownlevelFlags::CUBE_ARRAY_TEXTURES)
+    {
+        capabilities.push(spv::Capability::SampledCubeArray);
+    }
+    if desc
+        .downlevel_flags
+        .contains(wgt::DownlevelFlags::MULTISAMPLED_SHADING)
+    {
+        capabilities.push(spv::Capability::SampleRateShading);
+    }
+    if features.contains(wgt::Features::MULTIVIEW) {
+        capabilities.push(spv::Capability::MultiView);
+    }
+    if features.contains(wgt::Features::SHADER_PRIMITIVE_INDEX) {
+        capabilities.push(spv::Capability::Geometry);
+    }
+    if features.contains(wgt::Features::MULTI_VIEWPORT) {
+        capabilities.push(spv::Capability::MultiViewport);
+        capabilities.push(spv::Capability::ShaderViewportIndexLayerEXT);
+    }
+    if features.intersects(wgt::Features::SUBGROUP | wgt::Features::SUBGROUP_VERTEX) {
+        capabilities.push(spv::Capability::GroupNonUniform);
+        capabilities.push(spv::Capability::GroupNonUniformVote);
+        capabilities.push(spv::Capability::GroupNonUniformArithmetic);
+        capabilities.push(spv::Capability::GroupNonUniformBallot);
+        capabilities.push(spv::Capability::GroupNonUniformShuffle);
+        capabilities.push(spv::Capability::GroupNonUniformShuffleRelative);
+    }
+    if features.intersects(
+        wgt::Features::SAMPLED_TEXTURE_AND_STORAGE_BUFFER_ARRAY_NON_UNIFORM_INDEXING
+            | wgt::Features::UNIFORM_BUFFER_AND_STORAGE_TEXTURE_ARRAY_NON_UNIFORM_INDEXING,
+    ) {
+        capabilities.push(spv::Capability::ShaderNonUniform);
+    }
+    if features.contains(wgt::Features::BGRA8UNORM_STORAGE) {
+        capabilities.push(spv::Capability::StorageImageWriteWithoutFormat);
+    }
+    if features.contains(wgt::Features::RAY_QUERY) {
+        capabilities.push(spv::Capability::RayQueryKHR);
+    }
+    if features.contains(wgt::Features::SHADER_INT64) {
+        capabilities.push(spv::Capability::Int64);
+    }
+    if features.contains(wgt::Features::SHADER_INT64_ATOMICS) {
+        capabilities.push(spv::Capability::Int64Atomics);
+    }
+    if features.contains(wgt::Features::BUFFER_DEVICE_ADDRESS) {
+        capabilities.push(spv::Capability::PhysicalStorageBufferAddresses);
+    }
+
+    let mut flags = spv::WriterFlags::FORCE_POINT_SIZE;
+    flags.set(spv::WriterFlags::LABEL_VARYINGS, label_varyings);
+
+    let mut options = spv::Options {
+        lang_version: if features
+            .intersects(wgt::Features::SUBGROUP | wgt::Features::SUBGROUP_VERTEX)
+        {
+            (1, 3)
+        } else {
+            (1, 0)
+        },
+        flags,
+        capabilities: Some(capabilities.into_iter().collect()),
+        bounds_check_policies: BoundsCheckPolicies {
+            index: BoundsCheckPolicy::Restrict,
+            buffer: if robust_buffer_access {
+                BoundsCheckPolicy::Unchecked
+            } else {
+                BoundsCheckPolicy::Restrict
+            },
+            image_load: if robust_image_access {
+                BoundsCheckPolicy::Unchecked
+            } else {
+                BoundsCheckPolicy::Restrict
+            },
+            image_store: BoundsCheckPolicy::Unchecked,
+            binding_array: BoundsCheckPolicy::Unchecked,
+        },
+        zero_initialize_workgroup_memory: if native_zero_initialize_workgroup_memory {
+            spv::ZeroInitializeWorkgroupMemoryMode::Native
+        } else {
+            spv::ZeroInitializeWorkgroupMemoryMode::Polyfill
+        },
+        binding_map: BTreeMap::default(),
+        debug_info: None,
+    };
+    if !desc.runtime_checks {
+        options.bounds_check_policies = BoundsCheckPolicies {
+            index: BoundsCheckPolicy::Unchecked,
+            buffer: BoundsCheckPolicy::Unchecked,
+            image_load: BoundsCheckPolicy::Unchecked,
+            image_store: BoundsCheckPolicy::Unchecked,
+            binding_array: BoundsCheckPolicy::Unchecked,
+        };
+    }
+
+    if !separate_entry_points
+        && module.overrides.is_empty()
+        && !features.contains(wgt::Features::SAMPLER_YCBCR_CONVERSION)
+    {
+        // The whole module is written when the shader module is created.
+        return spv::write_vec(module, info, &options, None)
+            .map(TranslatedShader::SpirV)
+            .map_err(|e| TranslationError::Generation(format!("{e}")));
+    }
+
+    // Like the Vulkan backend, binding arrays are keyed by the index of their
+    // entry in the layout.
+    for (group, entries) in bind_group_layouts.iter().enumerate() {
+        for (index, entry) in entries.iter().enumerate() {
+            if let Some(count) = entry.count {
+                options.binding_map.insert(
+                    naga::ResourceBinding {
+                        group: group as u32,
+                        binding: index as u32,
+                    },
+                    spv::BindingInfo {
+                        binding_array_size: Some(count.get()),
+                        combined_image_sampler: false,
+                    },
+                );
+            }
+        }
+        for entry in entries.iter() {
+            if let BindingType::YcbcrTexture { .. } = entry.ty {
+                options
+                    .binding_map
+                    .entry(naga::ResourceBinding {
+                        group: group as u32,
+                        binding: entry.binding,
+                    })
+                    .or_default()
+                    .combined_image_sampler = true;
+            }
+        }
+    }
+    if !desc.zero_initialize_workgroup_memory {
+        options.zero_initialize_workgroup_memory = spv::ZeroInitializeWorkgroupMemoryMode::None;
+    }
+
+    let (module, info) = process_overrides(module, info, desc)?;
+    let pipeline_options = spv::PipelineOptions {
+        entry_point: desc.entry_point.to_string(),
+        shader_stage: desc.stage,
+    };
+    spv::write_vec(&module, &info, &options, Some(&pipeline_options))
+        .map(TranslatedShader::SpirV)
+        .map_err(|e| TranslationError::Generation(format!("{e}")))
+}
+
+/// Mirrors the root signature layout of the pipeline layouts of the DX12
+/// device, and the loading of its shaders.
+fn translate_hlsl(
+    module: &naga::Module,
+    info: &naga::valid::ModuleInfo,
+    desc: &TranslationDescriptor,
+    bind_group_layouts: &[Vec<BindGroupLayoutEntry>],
+) -> Result<TranslatedShader, TranslationError> {
+    use naga::back::hlsl;
+
+    let TranslationTarget::Hlsl { shader_model } = desc.target else {
+        unreachable!()
+    };
+
+    let mut binding_map = hlsl::BindingMap::default();
+    let (mut bind_cbv, mut bind_srv, mut bind_uav, mut bind_sampler) = (
+        hlsl::BindTarget::default(),
+        hlsl::BindTarget::default(),
+        hlsl::BindTarget::default(),
+        hlsl::BindTarget::default(),
+    );
+
+    // Push constants are root constants, in a space of their own.
+    let mut push_constants_target = None;
+    if !desc.push_constant_ranges.is_empty() {
+        push_constants_target = Some(bind_cbv.clone());
+        bind_cbv.register += 1;
+        bind_cbv.space += 1;
+    }
+
+    for (group, entries) in bind_group_layouts.iter().enumerate() {
+        let mut insert = |entry: &BindGroupLayoutEntry, bt: &mut hlsl::BindTarget| {
+            binding_map.insert(
+                naga::ResourceBinding {
+                    group: group as u32,
+                    binding: entry.binding,
+                },
+                hlsl::BindTarget {
+                    binding_array_size: entry.count.map(NonZeroU32::get),
+                    ..bt.clone()
+                },
+            );
+            bt.register += entry.count.map_or(1, NonZeroU32::get);
+        };
+
+        // SRV/CBV/UAV descriptor tables
+        for entry in entries.iter() {
+            let bt = match entry.ty {
+                BindingType::Buffer {
+                    has_dynamic_offset: true,
+                    ..
+                }
+                | BindingType::Sampler { .. } => continue,
+                BindingType::Buffer {
+                    ty: BufferBindingType::Uniform,
+                    ..
+                } => &mut bind_cbv,
+                BindingType::Buffer {
+                    ty: BufferBindingType::Storage { read_only: true },
+                    ..
+                }
+                | BindingType::Texture { .. } => &mut bind_srv,
+                BindingType::Buffer {
+                    ty: BufferBindingType::Storage { read_only: false },
+                    ..
+                }
+                | BindingType::StorageTexture { .. } => &mut bind_uav,
+                BindingType::AccelerationStructure | BindingType::YcbcrTexture { .. } => {
+                    return Err(TranslationError::UnsupportedBinding {
+                        group: group as u32,
+                        binding: entry.binding,
+                    })
+                }
+            };
+            insert(entry, bt);
+        }
+
+        // Sampler descriptor tables
+        for entry in entries.iter() {
+            if let BindingType::Sampler { .. } = entry.ty {
+                insert(entry, &mut bind_sampler);
+            }
+        }
+
+        // Root descriptors, for buffers with dynamic offsets
+        for entry in entries.iter() {
+            let bt = match entry.ty {
+                BindingType::Buffer {
+                    has_dynamic_offset: true,
+                    ty: BufferBindingType::Uniform,
+                    ..
+                } => &mut bind_cbv,
+                BindingType::Buffer {
+                    has_dynamic_offset: true,
+                    ty: BufferBindingType::Storage { read_only: true },
+                    ..
+                } => &mut bind_srv,
+                BindingType::Buffer {
+                    has_dynamic_offset: true,
+                    ty: BufferBindingType::Storage { read_only: false },
+                    ..
+                } => &mut bind_uav,
+                _ => continue,
+            };
+            insert(entry, bt);
+        }
+    }
+
+    // Pipeline layouts created by `wgpu-core` always have the special
+    // constants of `first_vertex` and `first_instance`.
+    let options = hlsl::Options {
+        shader_model,
+        binding_map,
+        fake_missing_bindings: false,
+        special_constants_binding: Some(bind_cbv),
+        push_constants_target,
+        zero_initialize_workgroup_memory: desc.zero_initialize_workgroup_memory,
+    };
+
+    let (module, info) = process_overrides(module, info, desc)?;
+    let mut source = String::new();
+    hlsl::Writer::new(&mut source, &options)
+        .write(&module, &info)
+        .map_err(|e| TranslationError::Generation(format!("{e:?}")))?;
+    Ok(TranslatedShader::Source(source))
+}
+
+/// Mirrors the per-stage resource slots of the pipeline layouts of the Metal
+/// device, and the loading of its shaders.
+fn translate_msl(
+    module: &naga::Module,
+    info: &naga::valid::ModuleInfo,
+    desc: &TranslationDescriptor,
+    bind_group_layouts: &[Vec<BindGroupLayoutEntry>],
+) -> Result<TranslatedShader, TranslationError> {
+    use naga::back::msl;
+    use naga::proc::{BoundsCheckPolicies, BoundsCheckPolicy};
+
+    let TranslationTarget::Msl { lang_version } = desc.target else {
+        unreachable!()
+    };
+    let stage_bit = map_naga_stage(desc.stage);
+
+    let (mut buffers, mut textures, mut samplers) = (0u32, 0u32, 0u32);
+
+    // First, place the push constants
+    let mut pc_limit = 0;
+    for pcr in desc.push_constant_ranges {
+        if pcr.stages.contains(stage_bit) {
+            pc_limit = (pcr.range.end / 4).max(pc_limit);
+        }
+    }
+    const LIMIT_MASK: u32 = 3;
+    if pc_limit > LIMIT_MASK {
+        pc_limit = (pc_limit + LIMIT_MASK) & !LIMIT_MASK;
+    }
+    let mut push_constant_buffer = None;
+    if pc_limit != 0 {
+        push_constant_buffer = Some(buffers as msl::Slot);
+        buffers += 1;
+    }
+
+    // Second, place the described resources
+    let mut resources = msl::BindingMap::default();
+    let mut sizes_count = 0;
+    for (group, entries) in bind_group_layouts.iter().enumerate() {
+        for entry in entries.iter() {
+            if !entry.visibility.contains(stage_bit) {
+                continue;
+            }
+
+            let mut target = msl::BindTarget::default();
+            let count = entry.count.map_or(1, NonZeroU32::get);
+            target.binding_array_size = entry.count.map(NonZeroU32::get);
+            match entry.ty {
+                BindingType::Buffer { ty, .. } => {
+                    target.buffer = Some(buffers as _);
+                    buffers += count;
+                    if let BufferBindingType::Storage { read_only } = ty {
+                        target.mutable = !read_only;
+                        sizes_count += 1;
+                    }
+                }
+                BindingType::Sampler { .. } => {
+                    target.sampler = Some(msl::BindSamplerTarget::Resource(samplers as _));
+                    samplers += count;
+                }
+                BindingType::Texture { .. } => {
+                    target.texture = Some(textures as _);
+                    textures += count;
+                }
+                BindingType::StorageTexture { access, .. } => {
+                    target.texture = Some(textures as _);
+                    textures += count;
+                    target.mutable = match access {
+                        wgt::StorageTextureAccess::ReadOnly => false,
+                        wgt::StorageTextureAccess::WriteOnly => true,
+                        wgt::StorageTextureAccess::ReadWrite => true,
+                    };
+                }
+                BindingType::AccelerationStructure | BindingType::YcbcrTexture { .. } => {
+                    return Err(TranslationError::UnsupportedBinding {
+                        group: group as u32,
+                        binding: entry.binding,
+                    })
+                }
+            }
+
+            resources.insert(
+                naga::ResourceBinding {
+                    group: group as u32,
+                    binding: entry.binding,
+                },
+                target,
+            );
+        }
+    }
+
+    // Finally, the sizes buffer
+    let sizes_buffer = (sizes_count != 0).then_some(buffers as msl::Slot);
+
+    let bounds_check_policy = if desc.runtime_checks {
+        BoundsCheckPolicy::ReadZeroSkipWrite
+    } else {
+        BoundsCheckPolicy::Unchecked
+    };
+    let options = msl::Options {
+        lang_version,
+        inline_samplers: Default::default(),
+        spirv_cross_compatibility: false,
+        fake_missing_bindings: false,
+        per_entry_point_map: msl::EntryPointResourceMap::from([(
+            desc.entry_point.to_string(),
+            msl::EntryPointResources {
+                resources,
+                push_constant_buffer,
+                sizes_buffer,
+            },
+        )]),
+        bounds_check_policies: BoundsCheckPolicies {
+            index: bounds_check_policy,
+            buffer: bounds_check_policy,
+            image_load: bounds_check_policy,
+            image_store: BoundsCheckPolicy::Unchecked,
+            binding_array: BoundsCheckPolicy::Unchecked,
+        },
+        zero_initialize_workgroup_memory: desc.zero_initialize_workgroup_memory,
+    };
+    let pipeline_options = msl::PipelineOptions {
+        allow_and_force_point_size: desc.point_list,
+    };
+
+    let (module, info) = process_overrides(module, info, desc)?;
+    let (source, _) = msl::write_string(&module, &info, &options, &pipeline_options)
+        .map_err(|e| TranslationError::Generation(format!("{e:?}")))?;
+    Ok(TranslatedShader::Source(source))
+}
+
+/// Mirrors the texture units and binding points of the pipeline layouts of
+/// the GLES device, and the creation of its shaders.
+fn translate_glsl(
+    module: &naga::Module,
+    info: &naga::valid::ModuleInfo,
+    desc: &TranslationDescriptor,
+    bind_group_layouts: &[Vec<BindGroupLayoutEntry>],
+) -> Result<TranslatedShader, TranslationError> {
+    use naga::back::glsl;
+    use naga::proc::{BoundsCheckPolicies, BoundsCheckPolicy};
+
+    let TranslationTarget::Glsl {
+        version,
+        texture_shadow_lod,
+        draw_parameters,
+    } = desc.target
+    else {
+        unreachable!()
+    };
+
+    let mut writer_flags = glsl::WriterFlags::ADJUST_COORDINATE_SPACE;
+    writer_flags.set(glsl::WriterFlags::TEXTURE_SHADOW_LOD, texture_shadow_lod);
+    writer_flags.set(glsl::WriterFlags::DRAW_PARAMETERS, draw_parameters);
+    writer_flags.set(glsl::WriterFlags::FORCE_POINT_SIZE, true);
+
+    let mut binding_map = glsl::BindingMap::default();
+    let mut num_samplers = 0u8;
+    let mut num_textures = 0u8;
+    let mut num_images = 0u8;
+    let mut num_uniform_buffers = 0u8;
+    let mut num_storage_buffers = 0u8;
+    for (group, entries) in bind_group_layouts.iter().enumerate() {
+        for entry in entries.iter() {
+            let counter = match entry.ty {
+                BindingType::Sampler { .. } => &mut num_samplers,
+                BindingType::Texture { .. } => &mut num_textures,
+                BindingType::StorageTexture { .. } => &mut num_images,
+                BindingType::Buffer {
+                    ty: BufferBindingType::Uniform,
+                    ..
+                } => &mut num_uniform_buffers,
+                BindingType::Buffer {
+                    ty: BufferBindingType::Storage { .. },
+                    ..
+                } => &mut num_storage_buffers,
+                BindingType::AccelerationStructure | BindingType::YcbcrTexture { .. } => {
+                    return Err(TranslationError::UnsupportedBinding {
+                        group: group as u32,
+                        binding: entry.binding,
+                    })
+                }
+            };
+            binding_map.insert(
+                naga::ResourceBinding {
+                    group: group as u32,
+                    binding: entry.binding,
+                },
+                *counter,
+            );
+            *counter += entry.count.map_or(1, |c| c.get() as u8);
+        }
+    }
+
+    let options = glsl::Options {
+        version,
+        writer_flags,
+        binding_map,
+        zero_initialize_workgroup_memory: desc.zero_initialize_workgroup_memory,
+    };
+    let pipeline_options = glsl::PipelineOptions {
+        shader_stage: desc.stage,
+        entry_point: desc.entry_point.to_string(),
+        multiview: desc.multiview,
+    };
+
+    // The image bounds checks require the TEXTURE_LEVELS feature available in
+    // GL core 4.3+.
+    let image_check = match version {
+        glsl::Version::Desktop(version) if version >= 430 => BoundsCheckPolicy::ReadZeroSkipWrite,
+        _ => BoundsCheckPolicy::Unchecked,
+    };
+    let policies = BoundsCheckPolicies {
+        index: BoundsCheckPolicy::Unchecked,
+        buffer: BoundsCheckPolicy::Unchecked,
+        image_load: image_check,
+        image_store: BoundsCheckPolicy::Unchecked,
+        binding_array: BoundsCheckPolicy::Unchecked,
+    };
+
+    let (module, info) = process_overrides(module, info, desc)?;
+    let mut source = String::new();
+    glsl::Writer::new(
+        &mut source,
+        &module,
+        &info,
+        &options,
+        &pipeline_options,
+        policies,
+    )
+    .and_then(|mut writer| writer.write())
+    .map_err(|e| TranslationError::Generation(format!("{e}")))?;
+    Ok(TranslatedShader::Source(source))
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    const SHADER: &str = "
+        @group(0) @binding(0) var<uniform> scale: f32;
+        @group(0) @binding(1) var<storage, read_write> data: array<f32>;
+
+        @compute @workgroup_size(64)
+        fn main(@builtin(global_invocation_id) id: vec3<u32>) {
+            data[id.x] *= scale;
+        }
+    ";
+
+    fn translate(target: TranslationTarget) -> Result<TranslatedShader, TranslationError> {
+        let entries = [
+            BindGroupLayoutEntry {
+                binding: 1,
+                visibility: wgt::ShaderStages::COMPUTE,
+                ty: BindingType::Buffer {
+                    ty: BufferBindingType::Storage { read_only: false },
+                    has_dynamic_offset: false,
+                    min_binding_size: None,
+                },
+                count: None,
+            },
+            BindGroupLayoutEntry {
+                binding: 0,
+                visibility: wgt::ShaderStages::COMPUTE,
+                ty: BindingType::Buffer {
+                    ty: BufferBindingType::Uniform,
+                    has_dynamic_offset: false,
+                    min_binding_size: None,
+                },
+                count: None,
+            },
+        ];
+        translate_wgsl(
+            SHADER,
+            &TranslationDescriptor {
+                entry_point: "main",
+                stage: naga::ShaderStage::Compute,
+                features: wgt::Features::empty(),
+                downlevel_flags: wgt::DownlevelFlags::all(),
+                bind_group_layouts: &[&entries],
+                push_constant_ranges: &[],
+                constants: &Default::default(),
+                runtime_checks: true,
+                zero_initialize_workgroup_memory: true,
+                point_list: false,
+                multiview: None,
+                target,
+            },
+        )
+    }
+
+    fn source(shader: TranslatedShader) -> String {
+        match shader {
+            TranslatedShader::Source(source) => source,
+            TranslatedShader::SpirV(_) => panic!("expected source code"),
+        }
+    }
+
+    #[test]
+    fn hlsl_registers() {
+        let hlsl = source(
+            translate(TranslationTarget::Hlsl {
+                shader_model: naga::back::hlsl::ShaderModel::V5_1,
+            })
+            .unwrap(),
+        );
+        assert!(hlsl.contains("register(b0)"), "{hlsl}");
+        assert!(hlsl.contains("register(u0)"), "{hlsl}");
+        // The special constants come after the bind groups.
+        assert!(hlsl.contains("register(b1)"), "{hlsl}");
+    }
+
+    #[test]
+    fn msl_buffer_slots() {
+        let msl = source(
+            translate(TranslationTarget::Msl {
+                lang_version: (2, 0),
+            })
+            .unwrap(),
+        );
+        assert!(msl.contains("[[buffer(0)]]"), "{msl}");
+        assert!(msl.contains("[[buffer(1)]]"), "{msl}");
+        // The sizes of the storage buffers come last.
+        assert!(msl.contains("[[buffer(2)]]"), "{msl}");
+    }
+
+    #[test]
+    fn spirv_is_valid() {
+        let TranslatedShader::SpirV(words) = translate(TranslationTarget::SpirV {
+            robust_buffer_access: false,
+            robust_image_access: false,
+            native_zero_initialize_workgroup_memory: false,
+            label_varyings: true,
+            separate_entry_points: false,
+        })
+        .unwrap() else {
+            panic!("expected SPIR-V")
+        };
+        assert_eq!(words[0], 0x0723_0203);
+    }
+
+    #[test]
+    fn missing_entry_point() {
+        let err = translate_wgsl(
+            SHADER,
+            &TranslationDescriptor {
+                entry_point: "missing",
+                stage: naga::ShaderStage::Compute,
+                features: wgt::Features::empty(),
+                downlevel_flags: wgt::DownlevelFlags::all(),
+                bind_group_layouts: &[],
+                push_constant_ranges: &[],
+                constants: &Default::default(),
+                runtime_checks: true,
+                zero_initialize_workgroup_memory: true,
+                point_list: false,
+                multiview: None,
+                target: TranslationTarget::Glsl {
+                    version: naga::back::glsl::Version::Desktop(430),
+                    texture_shadow_lod: false,
+                    draw_parameters: false,
+                },
+            },
+        )
+        .unwrap_err();
+        assert!(matches!(err, TranslationError::EntryPoint(..)));
+    }
+}
@@ -0,0 +1,173 @@
+use super::TrackerIndex;
+use crate::FastHashMap;
+use smallvec::SmallVec;
+
+/// Number of entries a [`TrackerIndexMap`] stores inline before it spills
+/// into a hash map.
+const INLINE_ENTRIES: usize = 4;
+
+/// A map from [`TrackerIndex`] to `T`, optimized for holding a few entries.
+///
+/// Most of the maps of suspected and retired resources only ever see a
+/// handful of resources of their type between two triages, so the map keeps
+/// up to [`INLINE_ENTRIES`] entries inline and searches them linearly, which
+/// neither allocates nor hashes. Beyond that, it spills into a
+/// [`FastHashMap`], and keeps using it after being cleared, so that the
+/// allocation is reused by churn-heavy workloads.
+#[derive(Debug)]
+pub(crate) enum TrackerIndexMap<T> {
+    Inline(SmallVec<[(TrackerIndex, T); INLINE_ENTRIES]>),
+    Spilled(FastHashMap<TrackerIndex, T>),
+}
+
+impl<T> Default for TrackerIndexMap<T> {
+    fn default() -> Self {
+        Self::Inline(SmallVec::new())
+    }
+}
+
+impl<T> TrackerIndexMap<T> {
+    pub fn len(&self) -> usize {
+        match *self {
+            Self::Inline(ref entries) => entries.len(),
+            Self::Spilled(ref map) => map.len(),
+        }
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Inserts `value` at `index`, returning the value previously there.
+    pub fn insert(&mut self, index: TrackerIndex, value: T) -> Option<T> {
+        let entries = match *self {
+            Self::Inline(ref mut entries) => entries,
+            Self::Spilled(ref mut map) => return map.insert(index, value),
+        };
+        if let Some(entry) = entries.iter_mut().find(|&&mut (i, _)| i == index) {
+            return Some(std::mem::replace(&mut entry.1, value));
+        }
+        if entries.len() < INLINE_ENTRIES {
+            entries.push((index, value));
+            return None;
+        }
+
+        let mut map = FastHashMap::with_capacity_and_hasher(2 * INLINE_ENTRIES, Default::default());
+        map.extend(entries.drain(..));
+        map.insert(index, value);
+        *self = Self::Spilled(map);
+        None
+    }
+
+    pub fn clear(&mut self) {
+        match *self {
+            Self::Inline(ref mut entries) => entries.clear(),
+            Self::Spilled(ref mut map) => map.clear(),
+        }
+    }
+
+    /// Retains only the entries for which `f` returns `true`.
+    pub fn retain(&mut self, mut f: impl FnMut(&TrackerIndex, &mut T) -> bool) {
+        match *self {
+            Self::Inline(ref mut entries) => {
+                entries.retain(|&mut (ref index, ref mut value)| f(index, value))
+            }
+            Self::Spilled(ref mut map) => map.retain(f),
+        }
+    }
+
+    /// Removes all entries, returning them in an unspecified order.
+    pub fn drain(&mut self) -> impl Iterator<Item = (TrackerIndex, T)> + '_ {
+        let (inline, spilled) = match *self {
+            Self::Inline(ref mut entries) => (Some(entries.drain(..)), None),
+            Self::Spilled(ref mut map) => (None, Some(map.drain())),
+        };
+        inline
+            .into_iter()
+            .flatten()
+            .chain(spilled.into_iter().flatten())
+    }
+
+    pub fn values(&self) -> impl Iterator<Item = &T> {
+        let (inline, spilled) = match *self {
+            Self::Inline(ref entries) => (Some(entries.iter()), None),
+            Self::Spilled(ref map) => (None, Some(map.values())),
+        };
+        inline
+            .into_iter()
+            .flatten()
+            .map(|(_, value)| value)
+            .chain(spilled.into_iter().flatten())
+    }
+}
+
+impl<T> Extend<(TrackerIndex, T)> for TrackerIndexMap<T> {
+    fn extend<I: IntoIterator<Item = (TrackerIndex, T)>>(&mut self, entries: I) {
+        for (index, value) in entries {
+            self.insert(index, value);
+        }
+    }
+}
+
+impl<T> FromIterator<(TrackerIndex, T)> for TrackerIndexMap<T> {
+    fn from_iter<I: IntoIterator<Item = (TrackerIndex, T)>>(entries: I) -> Self {
+        let mut map = Self::default();
+        map.extend(entries);
+        map
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn sorted<T: Copy>(map: &mut TrackerIndexMap<T>) -> Vec<(u32, T)> {
+        let mut entries: Vec<_> = map.drain().map(|(index, value)| (index.0, value)).collect();
+        entries.sort_by_key(|&(index, _)| index);
+        entries
+    }
+
+    #[test]
+    fn inline_insert_replaces() {
+        let mut map = TrackerIndexMap::default();
+        assert_eq!(map.insert(TrackerIndex(1), 'a'), None);
+        assert_eq!(map.insert(TrackerIndex(2), 'b'), None);
+        assert_eq!(map.insert(TrackerIndex(1), 'c'), Some('a'));
+        assert!(matches!(map, TrackerIndexMap::Inline(_)));
+        assert_eq!(sorted(&mut map), [(1, 'c'), (2, 'b')]);
+        assert!(map.is_empty());
+    }
+
+    #[test]
+    fn spills_past_inline_capacity() {
+        let mut map: TrackerIndexMap<u32> = (0..INLINE_ENTRIES as u32)
+            .map(|i| (TrackerIndex(i), i * 10))
+            .collect();
+        assert!(matches!(map, TrackerIndexMap::Inline(_)));
+
+        map.insert(TrackerIndex(100), 1000);
+        assert!(matches!(map, TrackerIndexMap::Spilled(_)));
+        assert_eq!(map.len(), INLINE_ENTRIES + 1);
+        assert_eq!(map.insert(TrackerIndex(0), 7), Some(0));
+
+        map.retain(|index, _| index.0 % 2 == 0);
+        let mut values: Vec<_> = map.values().copied().collect();
+        values.sort();
+        assert_eq!(values, [7, 20, 1000]);
+
+        // The hash map is kept for reuse.
+        map.clear();
+        assert!(map.is_empty());
+        assert!(matches!(map, TrackerIndexMap::Spilled(_)));
+    }
+
+    #[test]
+    fn retain_inline() {
+        let mut map: TrackerIndexMap<u32> = (0..3).map(|i| (TrackerIndex(i), i)).collect();
+        map.retain(|_, value| {
+            *value += 1;
+            *value != 2
+        });
+        assert_eq!(sorted(&mut map), [(0, 1), (2, 3)]);
+    }
+}
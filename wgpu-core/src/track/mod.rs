@@ -96,6 +96,7 @@ Device <- CommandBuffer = insert(device.start, device.end, buffer.start, buffer.
 */
 
 mod buffer;
+mod map;
 mod metadata;
 mod range;
 mod stateless;
@@ -114,6 +115,7 @@ use std::{fmt, ops, sync::Arc};
 use thiserror::Error;
 
 pub(crate) use buffer::{BufferBindGroupState, BufferTracker, BufferUsageScope};
+pub(crate) use map::TrackerIndexMap;
 use metadata::{ResourceMetadata, ResourceMetadataProvider};
 pub(crate) use stateless::{StatelessBindGroupSate, StatelessTracker};
 pub(crate) use texture::{
@@ -30,6 +30,8 @@ struct Resource {
     bind: naga::ResourceBinding,
     ty: ResourceType,
     class: naga::AddressSpace,
+    /// Size of the binding array the resource is declared as, if it is one.
+    binding_array: Option<naga::ArraySize>,
 }
 
 #[derive(Clone, Copy, Debug)]
@@ -222,6 +224,12 @@ pub enum BindingError {
         "Storage texture with access {0:?} doesn't have a matching supported `StorageTextureAccess`"
     )]
     UnsupportedTextureStorageAccess(naga::StorageAccess),
+    #[error("Binding is a binding array in the shader, but has no `count` in the pipeline layout")]
+    NotBindingArray,
+    #[error("Binding array of {shader} elements in the shader is larger than the {binding} elements in the pipeline layout")]
+    BindingArrayTooLarge { shader: u32, binding: u32 },
+    #[error("Binding is a runtime-sized binding array, whose size can only come from an explicit pipeline layout")]
+    UnsizedBindingArray,
 }
 
 #[derive(Clone, Debug, Error)]
@@ -405,8 +413,40 @@ fn map_storage_format_from_naga(format: naga::StorageFormat) -> wgt::TextureForm
     }
 }
 
+#[cfg(feature = "wgsl")]
+pub(crate) fn map_diagnostic_filter(
+    filter: &wgt::DiagnosticFilter,
+) -> naga::front::wgsl::DiagnosticFilter {
+    use naga::front::wgsl::{DiagnosticRule as Dr, Severity as S};
+
+    naga::front::wgsl::DiagnosticFilter {
+        severity: match filter.severity {
+            wgt::DiagnosticSeverity::Off => S::Off,
+            wgt::DiagnosticSeverity::Info => S::Info,
+            wgt::DiagnosticSeverity::Warning => S::Warning,
+            wgt::DiagnosticSeverity::Error => S::Error,
+        },
+        rule: match filter.rule {
+            wgt::DiagnosticRule::DerivativeUniformity => Dr::DerivativeUniformity,
+            wgt::DiagnosticRule::UnreachableCode => Dr::UnreachableCode,
+        },
+    }
+}
+
 impl Resource {
     fn check_binding_use(&self, entry: &BindGroupLayoutEntry) -> Result<(), BindingError> {
+        // Runtime-sized binding arrays take the size of the layout entry.
+        match (self.binding_array, entry.count) {
+            (Some(_), None) => return Err(BindingError::NotBindingArray),
+            (Some(naga::ArraySize::Constant(size)), Some(count)) if size > count => {
+                return Err(BindingError::BindingArrayTooLarge {
+                    shader: size.get(),
+                    binding: count.get(),
+                })
+            }
+            _ => {}
+        }
+
         match self.ty {
             ResourceType::Buffer { size } => {
                 let min_size = match entry.ty {
@@ -458,6 +498,7 @@ impl Resource {
                 let view_dimension = match entry.ty {
                     BindingType::Texture { view_dimension, .. }
                     | BindingType::StorageTexture { view_dimension, .. } => view_dimension,
+                    BindingType::YcbcrTexture { .. } => wgt::TextureViewDimension::D2,
                     _ => {
                         return Err(BindingError::WrongTextureViewDimension {
                             dim,
@@ -530,6 +571,10 @@ impl Resource {
                             access: naga_access,
                         }
                     }
+                    BindingType::YcbcrTexture { .. } => naga::ImageClass::Sampled {
+                        kind: naga::ScalarKind::Float,
+                        multi: false,
+                    },
                     _ => return Err(BindingError::WrongType),
                 };
                 if class != expected_class {
@@ -716,7 +761,7 @@ impl NumericType {
             | Tf::Depth24PlusStencil8 => {
                 panic!("Unexpected depth format")
             }
-            Tf::NV12 => panic!("Unexpected nv12 format"),
+            Tf::NV12 | Tf::P010 => panic!("Unexpected multi-planar format"),
             Tf::Rgb9e5Ufloat => (NumericDimension::Vector(Vs::Tri), Scalar::F32),
             Tf::Bc1RgbaUnorm
             | Tf::Bc1RgbaUnormSrgb
@@ -899,9 +944,11 @@ impl Interface {
             };
             let naga_ty = &module.types[var.ty].inner;
 
-            let inner_ty = match *naga_ty {
-                naga::TypeInner::BindingArray { base, .. } => &module.types[base].inner,
-                ref ty => ty,
+            let (inner_ty, binding_array) = match *naga_ty {
+                naga::TypeInner::BindingArray { base, size } => {
+                    (&module.types[base].inner, Some(size))
+                }
+                ref ty => (ty, None),
             };
 
             let ty = match *inner_ty {
@@ -934,6 +981,7 @@ impl Interface {
                     bind,
                     ty,
                     class: var.space,
+                    binding_array,
                 },
                 Default::default(),
             );
@@ -1073,9 +1121,18 @@ impl Interface {
                             Ok(ty) => ty,
                             Err(error) => break 'err Err(error),
                         };
+                        let count = match res.binding_array {
+                            None => None,
+                            Some(naga::ArraySize::Constant(size)) => Some(size),
+                            Some(naga::ArraySize::Dynamic) => {
+                                break 'err Err(BindingError::UnsizedBindingArray)
+                            }
+                        };
 
                         match map.entry(res.bind.binding) {
-                            indexmap::map::Entry::Occupied(e) if e.get().ty != ty => {
+                            indexmap::map::Entry::Occupied(e)
+                                if e.get().ty != ty || e.get().count != count =>
+                            {
                                 break 'err Err(BindingError::InconsistentlyDerivedType)
                             }
                             indexmap::map::Entry::Occupied(e) => {
@@ -1086,7 +1143,7 @@ impl Interface {
                                     binding: res.bind.binding,
                                     ty,
                                     visibility: stage_bit,
-                                    count: None,
+                                    count,
                                 });
                             }
                         }
@@ -1122,6 +1179,10 @@ impl Interface {
                 );
                 let texture_sample_type = match texture_layout.ty {
                     BindingType::Texture { sample_type, .. } => sample_type,
+                    // Converted textures are always filtered by their own sampler.
+                    BindingType::YcbcrTexture { .. } => {
+                        wgt::TextureSampleType::Float { filterable: true }
+                    }
                     _ => unreachable!(),
                 };
 
@@ -1330,3 +1391,139 @@ pub fn validate_color_attachment_bytes_per_sample(
 
     Ok(())
 }
+
+/// Returns the WGSL language extensions that shaders may use on a device with
+/// `features`.
+#[cfg(feature = "wgsl")]
+pub(crate) fn wgsl_extensions(features: wgt::Features) -> naga::front::wgsl::EnableExtensions {
+    use naga::front::wgsl::EnableExtensions as Ext;
+    let mut extensions = Ext::empty();
+    extensions.set(
+        Ext::PUSH_CONSTANTS,
+        features.contains(wgt::Features::PUSH_CONSTANTS),
+    );
+    extensions.set(
+        Ext::BINDING_ARRAY,
+        features.intersects(
+            wgt::Features::TEXTURE_BINDING_ARRAY
+                | wgt::Features::BUFFER_BINDING_ARRAY
+                | wgt::Features::STORAGE_RESOURCE_BINDING_ARRAY,
+        ),
+    );
+    extensions.set(Ext::RAY_QUERY, features.contains(wgt::Features::RAY_QUERY));
+    extensions.set(
+        Ext::BUFFER_DEVICE_ADDRESS,
+        features.contains(wgt::Features::BUFFER_DEVICE_ADDRESS),
+    );
+    extensions
+}
+
+/// Create a validator with the given validation flags, for a device with
+/// `features`, `downlevel_flags` and `device_flags`.
+pub(crate) fn create_validator(
+    features: wgt::Features,
+    downlevel_flags: wgt::DownlevelFlags,
+    device_flags: wgt::DeviceFlags,
+    flags: naga::valid::ValidationFlags,
+) -> naga::valid::Validator {
+    use naga::valid::Capabilities as Caps;
+    let mut caps = Caps::empty();
+    caps.set(
+        Caps::PUSH_CONSTANT,
+        features.contains(wgt::Features::PUSH_CONSTANTS),
+    );
+    caps.set(Caps::FLOAT64, features.contains(wgt::Features::SHADER_F64));
+    caps.set(
+        Caps::PRIMITIVE_INDEX,
+        features.contains(wgt::Features::SHADER_PRIMITIVE_INDEX),
+    );
+    caps.set(
+        Caps::VIEWPORT_INDEX,
+        features.contains(wgt::Features::MULTI_VIEWPORT),
+    );
+    caps.set(
+        Caps::SAMPLED_TEXTURE_AND_STORAGE_BUFFER_ARRAY_NON_UNIFORM_INDEXING,
+        features
+            .contains(wgt::Features::SAMPLED_TEXTURE_AND_STORAGE_BUFFER_ARRAY_NON_UNIFORM_INDEXING),
+    );
+    caps.set(
+        Caps::UNIFORM_BUFFER_AND_STORAGE_TEXTURE_ARRAY_NON_UNIFORM_INDEXING,
+        features
+            .contains(wgt::Features::UNIFORM_BUFFER_AND_STORAGE_TEXTURE_ARRAY_NON_UNIFORM_INDEXING),
+    );
+    // TODO: This needs a proper wgpu feature
+    caps.set(
+        Caps::SAMPLER_NON_UNIFORM_INDEXING,
+        features
+            .contains(wgt::Features::SAMPLED_TEXTURE_AND_STORAGE_BUFFER_ARRAY_NON_UNIFORM_INDEXING),
+    );
+    caps.set(
+        Caps::STORAGE_TEXTURE_16BIT_NORM_FORMATS,
+        features.contains(wgt::Features::TEXTURE_FORMAT_16BIT_NORM),
+    );
+    caps.set(Caps::MULTIVIEW, features.contains(wgt::Features::MULTIVIEW));
+    caps.set(
+        Caps::EARLY_DEPTH_TEST,
+        features.contains(wgt::Features::SHADER_EARLY_DEPTH_TEST),
+    );
+    caps.set(
+        Caps::SHADER_INT64,
+        features.contains(wgt::Features::SHADER_INT64),
+    );
+    caps.set(
+        Caps::SHADER_INT64_ATOMICS,
+        features.contains(wgt::Features::SHADER_INT64_ATOMICS),
+    );
+    caps.set(
+        Caps::MULTISAMPLED_SHADING,
+        downlevel_flags.contains(wgt::DownlevelFlags::MULTISAMPLED_SHADING),
+    );
+    caps.set(
+        Caps::DUAL_SOURCE_BLENDING,
+        features.contains(wgt::Features::DUAL_SOURCE_BLENDING),
+    );
+    caps.set(
+        Caps::CUBE_ARRAY_TEXTURES,
+        downlevel_flags.contains(wgt::DownlevelFlags::CUBE_ARRAY_TEXTURES),
+    );
+    caps.set(
+        Caps::SUBGROUP,
+        features.intersects(wgt::Features::SUBGROUP | wgt::Features::SUBGROUP_VERTEX),
+    );
+    caps.set(
+        Caps::SUBGROUP_BARRIER,
+        features.intersects(wgt::Features::SUBGROUP_BARRIER),
+    );
+    caps.set(
+        Caps::BUFFER_DEVICE_ADDRESS,
+        features.contains(wgt::Features::BUFFER_DEVICE_ADDRESS),
+    );
+    caps.set(
+        Caps::FRAGMENT_SHADER_INTERLOCK,
+        features.contains(wgt::Features::FRAGMENT_SHADER_INTERLOCK),
+    );
+
+    let mut subgroup_stages = naga::valid::ShaderStages::empty();
+    subgroup_stages.set(
+        naga::valid::ShaderStages::COMPUTE | naga::valid::ShaderStages::FRAGMENT,
+        features.contains(wgt::Features::SUBGROUP),
+    );
+    subgroup_stages.set(
+        naga::valid::ShaderStages::VERTEX,
+        features.contains(wgt::Features::SUBGROUP_VERTEX),
+    );
+
+    let subgroup_operations = if caps.contains(Caps::SUBGROUP) {
+        use naga::valid::SubgroupOperationSet as S;
+        S::BASIC | S::VOTE | S::ARITHMETIC | S::BALLOT | S::SHUFFLE | S::SHUFFLE_RELATIVE
+    } else {
+        naga::valid::SubgroupOperationSet::empty()
+    };
+    let mut validator = naga::valid::Validator::new(flags, caps);
+    validator.subgroup_stages(subgroup_stages);
+    validator.subgroup_operations(subgroup_operations);
+    validator.explicit_non_uniform_indexing(
+        device_flags.contains(wgt::DeviceFlags::EXPLICIT_NON_UNIFORM_INDEXING),
+    );
+    validator
+}
@@ -123,9 +123,14 @@ impl<A: hal::Api> Example<A> {
             .ok_or("failed to get surface capabilities")?;
         log::info!("Surface caps: {:#?}", surface_caps);
 
-        let hal::OpenDevice { device, queue } = unsafe {
+        let hal::OpenDevice { device, queue, .. } = unsafe {
             adapter
-                .open(wgt::Features::empty(), &wgt::Limits::default())
+                .open(
+                    wgt::Features::empty(),
+                    &wgt::Limits::default(),
+                    wgt::QueuePriority::Normal,
+                    false,
+                )
                 .unwrap()
         };
 
@@ -135,6 +140,10 @@ impl<A: hal::Api> Example<A> {
                 *surface_caps.maximum_frame_latency.start(),
                 *surface_caps.maximum_frame_latency.end(),
             ),
+            image_count: (DESIRED_MAX_LATENCY + 1).clamp(
+                *surface_caps.image_count.start(),
+                *surface_caps.image_count.end(),
+            ),
             present_mode: wgt::PresentMode::Fifo,
             composite_alpha_mode: wgt::CompositeAlphaMode::Opaque,
             format: wgt::TextureFormat::Bgra8UnormSrgb,
@@ -145,6 +154,10 @@ impl<A: hal::Api> Example<A> {
             },
             usage: hal::TextureUses::COLOR_TARGET,
             view_formats: vec![],
+            pre_transform: wgt::SurfaceTransform::Identity,
+            color_space: wgt::ColorSpace::Srgb,
+            fullscreen: wgt::FullscreenMode::Windowed,
+            display_mode: None,
         };
         unsafe {
             surface.configure(&device, &surface_config).unwrap();
@@ -253,6 +266,7 @@ impl<A: hal::Api> Example<A> {
                 entry_point: "vs_main",
                 constants: &constants,
                 zero_initialize_workgroup_memory: true,
+                code_capture: None,
             },
             vertex_buffers: &[],
             fragment_stage: Some(hal::ProgrammableStage {
@@ -260,6 +274,7 @@ impl<A: hal::Api> Example<A> {
                 entry_point: "fs_main",
                 constants: &constants,
                 zero_initialize_workgroup_memory: true,
+                code_capture: None,
             }),
             primitive: wgt::PrimitiveState {
                 topology: wgt::PrimitiveTopology::TriangleStrip,
@@ -369,9 +384,11 @@ impl<A: hal::Api> Example<A> {
             min_filter: wgt::FilterMode::Nearest,
             mipmap_filter: wgt::FilterMode::Nearest,
             lod_clamp: 0.0..32.0,
+            lod_bias: 0.0,
             compare: None,
             anisotropy_clamp: 1,
             border_color: None,
+            reduction_mode: wgt::SamplerReductionMode::WeightedAverage,
         };
         let sampler = unsafe { device.create_sampler(&sampler_desc).unwrap() };
 
@@ -426,6 +443,7 @@ impl<A: hal::Api> Example<A> {
             dimension: wgt::TextureViewDimension::D2,
             usage: hal::TextureUses::RESOURCE,
             range: wgt::ImageSubresourceRange::default(),
+            swizzle: wgt::TextureComponentSwizzle::IDENTITY,
         };
         let texture_view = unsafe { device.create_texture_view(&texture, &view_desc).unwrap() };
 
@@ -670,6 +688,7 @@ impl<A: hal::Api> Example<A> {
             dimension: wgt::TextureViewDimension::D2,
             usage: hal::TextureUses::COLOR_TARGET,
             range: wgt::ImageSubresourceRange::default(),
+            swizzle: wgt::TextureComponentSwizzle::IDENTITY,
         };
         let surface_tex_view = unsafe {
             self.device
@@ -249,8 +249,16 @@ impl<A: hal::Api> Example<A> {
             .expect("Surface doesn't support presentation");
         log::info!("Surface caps: {:#?}", surface_caps);
 
-        let hal::OpenDevice { device, queue } =
-            unsafe { adapter.open(features, &wgt::Limits::default()).unwrap() };
+        let hal::OpenDevice { device, queue, .. } = unsafe {
+            adapter
+                .open(
+                    features,
+                    &wgt::Limits::default(),
+                    wgt::QueuePriority::Normal,
+                    false,
+                )
+                .unwrap()
+        };
 
         let window_size: (u32, u32) = window.inner_size().into();
         dbg!(&surface_caps.formats);
@@ -266,6 +274,9 @@ impl<A: hal::Api> Example<A> {
             maximum_frame_latency: DESIRED_MAX_LATENCY
                 .max(*surface_caps.maximum_frame_latency.start())
                 .min(*surface_caps.maximum_frame_latency.end()),
+            image_count: (DESIRED_MAX_LATENCY + 1)
+                .max(*surface_caps.image_count.start())
+                .min(*surface_caps.image_count.end()),
             present_mode: wgt::PresentMode::Fifo,
             composite_alpha_mode: wgt::CompositeAlphaMode::Opaque,
             format: surface_format,
@@ -276,6 +287,10 @@ impl<A: hal::Api> Example<A> {
             },
             usage: hal::TextureUses::COLOR_TARGET | hal::TextureUses::COPY_DST,
             view_formats: vec![surface_format],
+            pre_transform: wgt::SurfaceTransform::Identity,
+            color_space: wgt::ColorSpace::Srgb,
+            fullscreen: wgt::FullscreenMode::Windowed,
+            display_mode: None,
         };
         unsafe {
             surface.configure(&device, &surface_config).unwrap();
@@ -372,6 +387,7 @@ impl<A: hal::Api> Example<A> {
                     entry_point: "main",
                     constants: &Default::default(),
                     zero_initialize_workgroup_memory: true,
+                    code_capture: None,
                 },
             })
         }
@@ -556,6 +572,7 @@ impl<A: hal::Api> Example<A> {
             dimension: wgt::TextureViewDimension::D2,
             usage: hal::TextureUses::STORAGE_READ_WRITE | hal::TextureUses::COPY_SRC,
             range: wgt::ImageSubresourceRange::default(),
+            swizzle: wgt::TextureComponentSwizzle::IDENTITY,
         };
         let texture_view = unsafe { device.create_texture_view(&texture, &view_desc).unwrap() };
 
@@ -894,6 +911,7 @@ impl<A: hal::Api> Example<A> {
             dimension: wgt::TextureViewDimension::D2,
             usage: hal::TextureUses::COPY_DST,
             range: wgt::ImageSubresourceRange::default(),
+            swizzle: wgt::TextureComponentSwizzle::IDENTITY,
         };
         let surface_tex_view = unsafe {
             self.device
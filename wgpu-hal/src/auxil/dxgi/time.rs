@@ -68,6 +68,17 @@ impl PresentationTimer {
         }
     }
 
+    /// Converts a QueryPerformanceCounter value, like the `SyncQPCTime` of
+    /// DXGI frame statistics, to nanoseconds.
+    ///
+    /// Returns `None` if this timer doesn't use QueryPerformanceCounter.
+    pub fn qpc_to_ns(&self, counter: u64) -> Option<u128> {
+        match *self {
+            PresentationTimer::Dxgi { frequency } => Some(qpc_to_ns(counter, frequency)),
+            PresentationTimer::IPresentationManager { .. } => None,
+        }
+    }
+
     /// Gets the current time in nanoseconds.
     pub fn get_timestamp_ns(&self) -> u128 {
         // Always do u128 math _after_ hitting the timing function.
@@ -77,8 +88,7 @@ impl PresentationTimer {
                 let success = unsafe { QueryPerformanceCounter(&mut counter) };
                 assert_ne!(success, 0);
 
-                // counter * (1_000_000_000 / freq) but re-ordered to make more precise
-                (unsafe { *counter.QuadPart() } as u128 * 1_000_000_000) / frequency as u128
+                qpc_to_ns(unsafe { *counter.QuadPart() } as u64, frequency)
             }
             PresentationTimer::IPresentationManager {
                 fnQueryInterruptTimePrecise,
@@ -92,3 +102,8 @@ impl PresentationTimer {
         }
     }
 }
+
+fn qpc_to_ns(counter: u64, frequency: u64) -> u128 {
+    // counter * (1_000_000_000 / freq) but re-ordered to make more precise
+    (counter as u128 * 1_000_000_000) / frequency as u128
+}
@@ -1,6 +1,7 @@
 #[cfg(dx12)]
 pub(super) mod dxgi;
 
+pub mod power;
 #[cfg(all(native, feature = "renderdoc"))]
 pub(super) mod renderdoc;
 
@@ -0,0 +1,119 @@
+//! Process-wide power hints, which the graphics APIs have none of.
+//!
+//! Unlike [`crate::Device::set_performance_hint`], these change how the OS
+//! treats the whole process, so they are only applied when the application
+//! asks for them explicitly.
+
+/// Tell the OS how the process should trade performance for power.
+///
+/// The hint applies to the whole process, so the last one set wins. The GPU
+/// clocks follow the load, so this mostly changes how the threads that record
+/// and submit the work are scheduled:
+///
+/// - On Windows, [`wgt::PerformanceHint::LowPower`] opts the process into
+///   EcoQoS, which runs it on efficient cores at low clocks, and
+///   [`wgt::PerformanceHint::Burst`] opts it out.
+/// - On Apple platforms, [`wgt::PerformanceHint::Sustained`] and
+///   [`wgt::PerformanceHint::Burst`] declare a user initiated activity to
+///   `NSProcessInfo`, which keeps App Nap and timer coalescing from slowing
+///   the process down, and the latter marks it as latency critical.
+/// - Other platforms have no equivalent, and ignore the hint.
+pub fn set_process_performance_hint(hint: wgt::PerformanceHint) {
+    #[cfg(windows)]
+    {
+        use winapi::um::processthreadsapi::{
+            GetCurrentProcess, SetProcessInformation, PROCESS_INFORMATION_CLASS,
+        };
+
+        // winapi 0.3 only knows of `ProcessMemoryPriority`, these are from
+        // `processthreadsapi.h` of the Windows 10 SDK.
+        #[allow(non_upper_case_globals)]
+        const ProcessPowerThrottling: PROCESS_INFORMATION_CLASS = 4;
+        const PROCESS_POWER_THROTTLING_CURRENT_VERSION: u32 = 1;
+        const PROCESS_POWER_THROTTLING_EXECUTION_SPEED: u32 = 0x1;
+        #[allow(non_camel_case_types, non_snake_case)]
+        #[repr(C)]
+        struct PROCESS_POWER_THROTTLING_STATE {
+            Version: u32,
+            ControlMask: u32,
+            StateMask: u32,
+        }
+
+        let (control_mask, state_mask) = match hint {
+            wgt::PerformanceHint::LowPower => (
+                PROCESS_POWER_THROTTLING_EXECUTION_SPEED,
+                PROCESS_POWER_THROTTLING_EXECUTION_SPEED,
+            ),
+            // Leave it to the system.
+            wgt::PerformanceHint::Sustained => (0, 0),
+            wgt::PerformanceHint::Burst => (PROCESS_POWER_THROTTLING_EXECUTION_SPEED, 0),
+        };
+        let mut state = PROCESS_POWER_THROTTLING_STATE {
+            Version: PROCESS_POWER_THROTTLING_CURRENT_VERSION,
+            ControlMask: control_mask,
+            StateMask: state_mask,
+        };
+        let result = unsafe {
+            SetProcessInformation(
+                GetCurrentProcess(),
+                ProcessPowerThrottling,
+                &mut state as *mut _ as *mut _,
+                std::mem::size_of_val(&state) as u32,
+            )
+        };
+        if result == 0 {
+            log::warn!(
+                "Unable to set the power throttling of the process: {}",
+                std::io::Error::last_os_error()
+            );
+        }
+    }
+
+    #[cfg(any(target_os = "macos", target_os = "ios"))]
+    {
+        use objc::{class, msg_send, sel, sel_impl};
+        use parking_lot::{lock_api::RawMutex as _, Mutex};
+
+        /// The token of the activity begun by the last hint.
+        struct Activity(objc::rc::StrongPtr);
+        // The token is only used to end the activity, which any thread can do.
+        unsafe impl Send for Activity {}
+
+        static ACTIVITY: Mutex<Option<Activity>> =
+            Mutex::const_new(parking_lot::RawMutex::INIT, None);
+
+        // `NSActivityUserInitiatedAllowingIdleSystemSleep` and
+        // `NSActivityLatencyCritical`.
+        const USER_INITIATED: u64 = 0x00EF_FFFF;
+        const LATENCY_CRITICAL: u64 = 0xFF_0000_0000;
+        let options = match hint {
+            wgt::PerformanceHint::LowPower => None,
+            wgt::PerformanceHint::Sustained => Some(USER_INITIATED),
+            wgt::PerformanceHint::Burst => Some(USER_INITIATED | LATENCY_CRITICAL),
+        };
+
+        let mut activity = ACTIVITY.lock();
+        objc::rc::autoreleasepool(|| {
+            let process_info: *mut objc::runtime::Object =
+                unsafe { msg_send![class!(NSProcessInfo), processInfo] };
+            if let Some(Activity(token)) = activity.take() {
+                let () = unsafe { msg_send![process_info, endActivity: *token] };
+            }
+            if let Some(options) = options {
+                let reason: *mut objc::runtime::Object = unsafe {
+                    msg_send![
+                        class!(NSString),
+                        stringWithUTF8String: b"wgpu performance hint\0".as_ptr()
+                    ]
+                };
+                let token: *mut objc::runtime::Object = unsafe {
+                    msg_send![process_info, beginActivityWithOptions: options reason: reason]
+                };
+                *activity = Some(Activity(unsafe { objc::rc::StrongPtr::retain(token) }));
+            }
+        });
+    }
+
+    #[cfg(not(any(windows, target_os = "macos", target_os = "ios")))]
+    let _ = hint;
+}
@@ -1,14 +1,19 @@
 use crate::{
     auxil::{self, dxgi::result::HResult as _},
-    dx12::{shader_compilation, SurfaceTarget},
+    dx12::{conv, shader_compilation, SurfaceTarget},
 };
 use parking_lot::Mutex;
 use std::{mem, ptr, sync::Arc, thread};
 use winapi::{
     shared::{
-        dxgi, dxgi1_2, dxgiformat::DXGI_FORMAT_B8G8R8A8_UNORM, minwindef::DWORD, windef, winerror,
+        dxgi, dxgi1_2, dxgi1_6, dxgicommon,
+        dxgiformat::{DXGI_FORMAT_B8G8R8A8_UNORM, DXGI_FORMAT_P010},
+        dxgitype,
+        minwindef::DWORD,
+        ntdef, windef, winerror,
     },
     um::{d3d12 as d3d12_ty, d3d12sdklayers, winuser},
+    Interface as _,
 };
 
 impl Drop for super::Adapter {
@@ -28,6 +33,150 @@ impl Drop for super::Adapter {
 }
 
 impl super::Adapter {
+    /// Capabilities of a flip model swap chain presented with this adapter.
+    fn swap_chain_capabilities(
+        &self,
+        supports_allow_tearing: bool,
+        supports_hdr10: bool,
+        current_extent: Option<wgt::Extent3d>,
+    ) -> crate::SurfaceCapabilities {
+        let mut present_modes = vec![wgt::PresentMode::Mailbox, wgt::PresentMode::Fifo];
+        if supports_allow_tearing {
+            present_modes.push(wgt::PresentMode::Immediate);
+        }
+
+        let formats = vec![
+            wgt::TextureFormat::Bgra8UnormSrgb,
+            wgt::TextureFormat::Bgra8Unorm,
+            wgt::TextureFormat::Rgba8UnormSrgb,
+            wgt::TextureFormat::Rgba8Unorm,
+            wgt::TextureFormat::Rgb10a2Unorm,
+            wgt::TextureFormat::Rgba16Float,
+        ];
+        let mut color_spaces: Vec<_> = formats
+            .iter()
+            .map(|&format| (format, wgt::ColorSpace::Srgb))
+            .collect();
+        if supports_hdr10 {
+            color_spaces.push((wgt::TextureFormat::Rgb10a2Unorm, wgt::ColorSpace::Hdr10));
+        }
+
+        crate::SurfaceCapabilities {
+            formats,
+            // See https://learn.microsoft.com/en-us/windows/win32/api/dxgi/nf-dxgi-idxgidevice1-setmaximumframelatency
+            maximum_frame_latency: 1..=16,
+            // Flip model swap chains have 2 to 16 buffers.
+            image_count: 2..=16,
+            current_extent,
+            usage: crate::TextureUses::COLOR_TARGET
+                | crate::TextureUses::COPY_SRC
+                | crate::TextureUses::COPY_DST,
+            present_modes,
+            composite_alpha_modes: vec![wgt::CompositeAlphaMode::Opaque],
+            // DXGI rotates swap chains itself and only for fullscreen outputs.
+            transforms: vec![wgt::SurfaceTransform::Identity],
+            current_transform: wgt::SurfaceTransform::Identity,
+            color_spaces,
+            max_array_layers: 1,
+            fullscreen_modes: vec![wgt::FullscreenMode::Windowed],
+            display_modes: Vec::new(),
+        }
+    }
+
+    /// The output showing `wnd_handle`, if this adapter drives it.
+    fn window_output(&self, wnd_handle: windef::HWND) -> Option<d3d12::ComPtr<dxgi::IDXGIOutput>> {
+        let monitor =
+            unsafe { winuser::MonitorFromWindow(wnd_handle, winuser::MONITOR_DEFAULTTONEAREST) };
+
+        for index in 0.. {
+            let mut output = d3d12::ComPtr::<dxgi::IDXGIOutput>::null();
+            // Fails with `DXGI_ERROR_NOT_FOUND` past the last output.
+            if unsafe { self.raw.EnumOutputs(index, output.mut_self()) }
+                .into_result()
+                .is_err()
+            {
+                break;
+            }
+            let mut desc: dxgi::DXGI_OUTPUT_DESC = unsafe { mem::zeroed() };
+            if unsafe { output.GetDesc(&mut desc) }.into_result().is_ok() && desc.Monitor == monitor
+            {
+                return Some(output);
+            }
+        }
+        None
+    }
+
+    /// Whether the output showing `wnd_handle` has HDR enabled, so that swap
+    /// chains presented to it can use the HDR10 color space.
+    fn window_supports_hdr10(&self, wnd_handle: windef::HWND) -> bool {
+        let Some(output) = self.window_output(wnd_handle) else {
+            return false;
+        };
+        // `IDXGIOutput6` needs Windows 10 1703, and HDR isn't supported before.
+        let Ok(output6) = (unsafe { output.cast::<dxgi1_6::IDXGIOutput6>() }).into_result() else {
+            return false;
+        };
+        let mut desc: dxgi1_6::DXGI_OUTPUT_DESC1 = unsafe { mem::zeroed() };
+        unsafe { output6.GetDesc1(&mut desc) }.into_result().is_ok()
+            && desc.ColorSpace == dxgicommon::DXGI_COLOR_SPACE_RGB_FULL_G2084_NONE_P2020
+    }
+
+    /// Modes of the output showing `wnd_handle`, from the largest and fastest.
+    fn display_modes(&self, wnd_handle: windef::HWND) -> Vec<wgt::DisplayMode> {
+        let Some(output) = self.window_output(wnd_handle) else {
+            return Vec::new();
+        };
+
+        // Modes are listed for every format alike, so any displayable one
+        // does.
+        let mut count = 0;
+        let hr = unsafe {
+            output.GetDisplayModeList(DXGI_FORMAT_B8G8R8A8_UNORM, 0, &mut count, ptr::null_mut())
+        };
+        if let Err(err) = hr.into_result() {
+            log::warn!("GetDisplayModeList failed: {}", err);
+            return Vec::new();
+        }
+        let mut raw_modes =
+            vec![unsafe { mem::zeroed::<dxgitype::DXGI_MODE_DESC>() }; count as usize];
+        let hr = unsafe {
+            output.GetDisplayModeList(
+                DXGI_FORMAT_B8G8R8A8_UNORM,
+                0,
+                &mut count,
+                raw_modes.as_mut_ptr(),
+            )
+        };
+        if let Err(err) = hr.into_result() {
+            log::warn!("GetDisplayModeList failed: {}", err);
+            return Vec::new();
+        }
+        raw_modes.truncate(count as usize);
+
+        // The same mode is listed once per scanline ordering and scaling.
+        let mut modes: Vec<_> = raw_modes
+            .iter()
+            .filter(|mode| mode.RefreshRate.Denominator != 0)
+            .map(|mode| wgt::DisplayMode {
+                width: mode.Width,
+                height: mode.Height,
+                refresh_rate_millihertz: (u64::from(mode.RefreshRate.Numerator) * 1000
+                    / u64::from(mode.RefreshRate.Denominator))
+                    as u32,
+            })
+            .collect();
+
+        modes.sort_by(|a, b| {
+            (b.width, b.height, b.refresh_rate_millihertz).cmp(&(
+                a.width,
+                a.height,
+                a.refresh_rate_millihertz,
+            ))
+        });
+        modes.dedup();
+        modes
+    }
+
     pub unsafe fn report_live_objects(&self) {
         if let Ok(debug_device) = unsafe {
             self.raw
@@ -52,6 +201,7 @@ impl super::Adapter {
         library: &Arc<d3d12::D3D12Lib>,
         instance_flags: wgt::InstanceFlags,
         dxc_container: Option<Arc<shader_compilation::DxcContainer>>,
+        supports_allow_tearing: bool,
     ) -> Option<crate::ExposedAdapter<super::Api>> {
         // Create the device so that we can get the capabilities.
         let device = {
@@ -117,6 +267,28 @@ impl super::Adapter {
 
         let mut workarounds = super::Workarounds::default();
 
+        // The UMD version is only reported for the DXGI device interface,
+        // but it is the same driver that serves D3D12.
+        let driver_version = {
+            let mut umd_version: ntdef::LARGE_INTEGER = unsafe { mem::zeroed() };
+            let hr = unsafe {
+                adapter
+                    .unwrap_adapter2()
+                    .CheckInterfaceSupport(&dxgi::IDXGIDevice::uuidof(), &mut umd_version)
+            };
+            if hr == 0 {
+                let version = unsafe { *umd_version.QuadPart() } as u64;
+                Some(wgt::DriverVersion {
+                    major: (version >> 48) as u32 & 0xffff,
+                    minor: (version >> 32) as u32 & 0xffff,
+                    patch: (version >> 16) as u32 & 0xffff,
+                    build: version as u32 & 0xffff,
+                })
+            } else {
+                None
+            }
+        };
+
         let info = wgt::AdapterInfo {
             backend: wgt::Backend::Dx12,
             name: device_name,
@@ -132,6 +304,36 @@ impl super::Adapter {
             },
             driver: String::new(),
             driver_info: String::new(),
+            driver_version,
+            device_uuid: None,
+            device_luid: Some({
+                let luid = desc.AdapterLuid;
+                let mut bytes = [0; 8];
+                bytes[..4].copy_from_slice(&luid.LowPart.to_ne_bytes());
+                bytes[4..].copy_from_slice(&luid.HighPart.to_ne_bytes());
+                bytes
+            }),
+            memory_heaps: {
+                let mut heaps = Vec::with_capacity(2);
+                if desc.DedicatedVideoMemory != 0 {
+                    heaps.push(wgt::MemoryHeapInfo {
+                        size: desc.DedicatedVideoMemory as u64,
+                        device_local: true,
+                        host_visible: features_architecture.UMA != 0,
+                    });
+                }
+                if desc.SharedSystemMemory != 0 {
+                    heaps.push(wgt::MemoryHeapInfo {
+                        size: desc.SharedSystemMemory as u64,
+                        device_local: features_architecture.UMA != 0,
+                        host_visible: true,
+                    });
+                }
+                heaps
+            },
+            unified_memory: features_architecture.UMA != 0,
+            // Mapping all of VRAM needs GPU upload heaps, which aren't exposed yet.
+            resizable_bar: false,
         };
 
         let mut options: d3d12_ty::D3D12_FEATURE_DATA_D3D12_OPTIONS = unsafe { mem::zeroed() };
@@ -271,10 +473,12 @@ impl super::Adapter {
             | wgt::Features::DEPTH32FLOAT_STENCIL8
             | wgt::Features::INDIRECT_FIRST_INSTANCE
             | wgt::Features::MAPPABLE_PRIMARY_BUFFERS
+            | wgt::Features::PERSISTENT_MAPPING
             | wgt::Features::MULTI_DRAW_INDIRECT
             | wgt::Features::MULTI_DRAW_INDIRECT_COUNT
             | wgt::Features::ADDRESS_MODE_CLAMP_TO_BORDER
             | wgt::Features::ADDRESS_MODE_CLAMP_TO_ZERO
+            | wgt::Features::ADDRESS_MODE_CLAMP_TO_CUSTOM_BORDER
             | wgt::Features::POLYGON_MODE_LINE
             | wgt::Features::TEXTURE_ADAPTER_SPECIFIC_FORMAT_FEATURES
             | wgt::Features::TIMESTAMP_QUERY
@@ -287,7 +491,12 @@ impl super::Adapter {
             | wgt::Features::SHADER_PRIMITIVE_INDEX
             | wgt::Features::RG11B10UFLOAT_RENDERABLE
             | wgt::Features::DUAL_SOURCE_BLENDING
-            | wgt::Features::TEXTURE_FORMAT_NV12;
+            | wgt::Features::TEXTURE_FORMAT_NV12
+            | wgt::Features::TIMELINE_SEMAPHORE
+            | wgt::Features::BUFFER_DEVICE_ADDRESS
+            | wgt::Features::INLINE_BIND_GROUPS
+            | wgt::Features::TEXTURE_COMPONENT_SWIZZLE
+            | wgt::Features::EXTERNAL_MEMORY_WIN32;
 
         //TODO: in order to expose this, we need to run a compute shader
         // that extract the necessary statistics out of the D3D12 result.
@@ -305,6 +514,18 @@ impl super::Adapter {
                 != d3d12_ty::D3D12_CONSERVATIVE_RASTERIZATION_TIER_NOT_SUPPORTED,
         );
 
+        // Min/max reduction filtering comes with tiled resources tier 2.
+        features.set(
+            wgt::Features::SAMPLER_REDUCTION_MODE,
+            options.TiledResourcesTier >= d3d12_ty::D3D12_TILED_RESOURCES_TIER_2,
+        );
+
+        // Writing `SV_ViewportArrayIndex` from a vertex shader.
+        features.set(
+            wgt::Features::MULTI_VIEWPORT,
+            options.VPAndRTArrayIndexFromAnyShaderFeedingRasterizerSupportedWithoutGSEmulation != 0,
+        );
+
         features.set(
             wgt::Features::TEXTURE_BINDING_ARRAY
                 | wgt::Features::UNIFORM_BUFFER_AND_STORAGE_TEXTURE_ARRAY_NON_UNIFORM_INDEXING
@@ -331,6 +552,23 @@ impl super::Adapter {
             bgra8unorm_storage_supported,
         );
 
+        let p010_supported = {
+            let mut p010_info: d3d12_ty::D3D12_FEATURE_DATA_FORMAT_SUPPORT =
+                unsafe { mem::zeroed() };
+            p010_info.Format = DXGI_FORMAT_P010;
+            let hr = unsafe {
+                device.CheckFeatureSupport(
+                    d3d12_ty::D3D12_FEATURE_FORMAT_SUPPORT,
+                    &mut p010_info as *mut _ as *mut _,
+                    mem::size_of::<d3d12_ty::D3D12_FEATURE_DATA_FORMAT_SUPPORT>() as _,
+                )
+            };
+            hr == 0
+                && (p010_info.Support1 & d3d12_ty::D3D12_FORMAT_SUPPORT1_TEXTURE2D != 0)
+                && (p010_info.Support1 & d3d12_ty::D3D12_FORMAT_SUPPORT1_SHADER_SAMPLE != 0)
+        };
+        features.set(wgt::Features::TEXTURE_FORMAT_P010, p010_supported);
+
         let mut features1: d3d12_ty::D3D12_FEATURE_DATA_D3D12_OPTIONS1 = unsafe { mem::zeroed() };
         let hr = unsafe {
             device.CheckFeatureSupport(
@@ -347,6 +585,27 @@ impl super::Adapter {
                 && features1.Int64ShaderOps != 0,
         );
 
+        // 64-bit atomics on raw buffers are required by Shader Model 6.6,
+        // but atomics on groupshared memory are optional.
+        let atomic_int64_on_group_shared_supported = {
+            let mut features9: crate::dx12::types::D3D12_FEATURE_DATA_D3D12_OPTIONS9 =
+                unsafe { mem::zeroed() };
+            let hr = unsafe {
+                device.CheckFeatureSupport(
+                    37, // D3D12_FEATURE_D3D12_OPTIONS9
+                    &mut features9 as *mut _ as *mut _,
+                    mem::size_of::<crate::dx12::types::D3D12_FEATURE_DATA_D3D12_OPTIONS9>() as _,
+                )
+            };
+            hr == 0 && features9.AtomicInt64OnGroupSharedSupported != 0
+        };
+        features.set(
+            wgt::Features::SHADER_INT64_ATOMICS,
+            features.contains(wgt::Features::SHADER_INT64)
+                && shader_model >= naga::back::hlsl::ShaderModel::V6_6
+                && atomic_int64_on_group_shared_supported,
+        );
+
         features.set(
             wgt::Features::SUBGROUP,
             shader_model >= naga::back::hlsl::ShaderModel::V6_0
@@ -354,6 +613,11 @@ impl super::Adapter {
                 && features1.WaveOps != 0,
         );
 
+        features.set(
+            wgt::Features::FRAGMENT_SHADER_INTERLOCK,
+            options.ROVsSupported != 0,
+        );
+
         // float32-filterable should always be available on d3d12
         features.set(wgt::Features::FLOAT32_FILTERABLE, true);
 
@@ -366,6 +630,11 @@ impl super::Adapter {
         // https://github.com/gfx-rs/wgpu/issues/2471
         downlevel.flags -=
             wgt::DownlevelFlags::VERTEX_AND_INSTANCE_INDEX_RESPECTS_RESPECTIVE_FIRST_VALUE_IN_INDIRECT_DRAW;
+        downlevel.flags.set(
+            wgt::DownlevelFlags::UNIFIED_MEMORY_MAPPABLE_BUFFERS,
+            features_architecture.UMA != 0,
+        );
+        downlevel.flags |= wgt::DownlevelFlags::SAMPLER_LOD_BIAS;
 
         // See https://learn.microsoft.com/en-us/windows/win32/direct3d12/hardware-feature-levels#feature-level-support
         let max_color_attachments = 8;
@@ -382,6 +651,7 @@ impl super::Adapter {
                 presentation_timer,
                 workarounds,
                 dxc_container,
+                supports_allow_tearing,
             },
             info,
             features,
@@ -460,6 +730,7 @@ impl super::Adapter {
                     // store buffer sizes using 32 bit ints (a situation we have already encountered with vulkan).
                     max_buffer_size: i32::MAX as u64,
                     max_non_sampler_bindings: 1_000_000,
+                    max_additional_queues: 4,
                 },
                 alignments: crate::Alignments {
                     buffer_copy_offset: wgt::BufferSize::new(
@@ -484,19 +755,43 @@ impl crate::Adapter for super::Adapter {
         &self,
         _features: wgt::Features,
         limits: &wgt::Limits,
+        queue_priority: wgt::QueuePriority,
+        _transfer_queue: bool,
     ) -> Result<crate::OpenDevice<super::Api>, crate::DeviceError> {
-        let queue = {
-            profiling::scope!("ID3D12Device::CreateCommandQueue");
+        let create_queue = |priority| {
             self.device
                 .create_command_queue(
                     d3d12::CmdListType::Direct,
-                    d3d12::Priority::Normal,
+                    priority,
                     d3d12::CommandQueueFlags::empty(),
                     0,
                 )
-                .into_device_result("Queue creation")?
+                .into_device_result("Queue creation")
+        };
+        let mut priority = conv::map_queue_priority(queue_priority);
+        let queue = {
+            profiling::scope!("ID3D12Device::CreateCommandQueue");
+            match create_queue(priority) {
+                // Global realtime priority is reserved to privileged processes.
+                Err(_) if priority == d3d12::Priority::GlobalRealtime => {
+                    log::warn!("Realtime queue priority is not permitted, falling back to high");
+                    priority = d3d12::Priority::High;
+                    create_queue(priority)?
+                }
+                result => result?,
+            }
         };
 
+        let additional_queues = (0..limits.max_additional_queues)
+            .map(|_| {
+                let raw = create_queue(priority)?;
+                Ok(super::Queue {
+                    raw,
+                    temp_lists: Mutex::new(Vec::new()),
+                })
+            })
+            .collect::<Result<Vec<_>, crate::DeviceError>>()?;
+
         let device = super::Device::new(
             self.device.clone(),
             queue.clone(),
@@ -511,6 +806,8 @@ impl crate::Adapter for super::Adapter {
                 raw: queue,
                 temp_lists: Mutex::new(Vec::new()),
             },
+            additional_queues,
+            transfer_queue: None,
         })
     }
 
@@ -684,29 +981,40 @@ impl crate::Adapter for super::Adapter {
             }
         };
 
-        let mut present_modes = vec![wgt::PresentMode::Mailbox, wgt::PresentMode::Fifo];
-        if surface.supports_allow_tearing {
-            present_modes.push(wgt::PresentMode::Immediate);
+        // Composition swap chains don't have an output to check for HDR.
+        let supports_hdr10 = match surface.target {
+            SurfaceTarget::WndHandle(wnd_handle) => self.window_supports_hdr10(wnd_handle),
+            _ => false,
+        };
+        let mut caps = self.swap_chain_capabilities(
+            surface.supports_allow_tearing,
+            supports_hdr10,
+            current_extent,
+        );
+        // Only window swap chains can be fullscreen, composition ones are
+        // placed by their visual.
+        if let SurfaceTarget::WndHandle(wnd_handle) = surface.target {
+            caps.fullscreen_modes = vec![
+                wgt::FullscreenMode::Windowed,
+                wgt::FullscreenMode::Borderless,
+                wgt::FullscreenMode::Exclusive,
+            ];
+            caps.display_modes = self.display_modes(wnd_handle);
         }
+        Some(caps)
+    }
 
-        Some(crate::SurfaceCapabilities {
-            formats: vec![
-                wgt::TextureFormat::Bgra8UnormSrgb,
-                wgt::TextureFormat::Bgra8Unorm,
-                wgt::TextureFormat::Rgba8UnormSrgb,
-                wgt::TextureFormat::Rgba8Unorm,
-                wgt::TextureFormat::Rgb10a2Unorm,
-                wgt::TextureFormat::Rgba16Float,
-            ],
-            // See https://learn.microsoft.com/en-us/windows/win32/api/dxgi/nf-dxgi-idxgidevice1-setmaximumframelatency
-            maximum_frame_latency: 1..=16,
-            current_extent,
-            usage: crate::TextureUses::COLOR_TARGET
-                | crate::TextureUses::COPY_SRC
-                | crate::TextureUses::COPY_DST,
-            present_modes,
-            composite_alpha_modes: vec![wgt::CompositeAlphaMode::Opaque],
-        })
+    unsafe fn headless_surface_capabilities(
+        &self,
+        display_handle: raw_window_handle::RawDisplayHandle,
+    ) -> Option<crate::SurfaceCapabilities> {
+        match display_handle {
+            raw_window_handle::RawDisplayHandle::Windows(_) => {
+                // Without a window, there is no output to check for HDR.
+                Some(self.swap_chain_capabilities(self.supports_allow_tearing, false, None))
+            }
+            _ => None,
+        }
     }
 
     unsafe fn get_presentation_timestamp(&self) -> wgt::PresentationTimestamp {
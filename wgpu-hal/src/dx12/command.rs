@@ -1,8 +1,12 @@
 use crate::auxil::{self, dxgi::result::HResult as _};
 
-use super::conv;
-use std::{mem, ops::Range, ptr};
-use winapi::um::d3d12 as d3d12_ty;
+use super::{conv, null_comptr_check};
+use std::{mem, ops::Range, ptr, slice};
+use winapi::{
+    shared::{dxgiformat, dxgitype},
+    um::d3d12 as d3d12_ty,
+    Interface,
+};
 
 fn make_box(origin: &wgt::Origin3d, size: &crate::CopyExtent) -> d3d12_ty::D3D12_BOX {
     d3d12_ty::D3D12_BOX {
@@ -247,6 +251,61 @@ impl super::CommandEncoder {
             }
         }
     }
+
+    /// Creates an upload buffer of `size` bytes filled with `value`.
+    unsafe fn create_fill_pattern(
+        &self,
+        value: u32,
+        size: wgt::BufferAddress,
+    ) -> Result<d3d12::Resource, crate::DeviceError> {
+        let raw_desc = d3d12_ty::D3D12_RESOURCE_DESC {
+            Dimension: d3d12_ty::D3D12_RESOURCE_DIMENSION_BUFFER,
+            Alignment: 0,
+            Width: size,
+            Height: 1,
+            DepthOrArraySize: 1,
+            MipLevels: 1,
+            Format: dxgiformat::DXGI_FORMAT_UNKNOWN,
+            SampleDesc: dxgitype::DXGI_SAMPLE_DESC {
+                Count: 1,
+                Quality: 0,
+            },
+            Layout: d3d12_ty::D3D12_TEXTURE_LAYOUT_ROW_MAJOR,
+            Flags: d3d12_ty::D3D12_RESOURCE_FLAG_NONE,
+        };
+        let heap_properties = d3d12_ty::D3D12_HEAP_PROPERTIES {
+            Type: d3d12_ty::D3D12_HEAP_TYPE_UPLOAD,
+            CPUPageProperty: d3d12_ty::D3D12_CPU_PAGE_PROPERTY_UNKNOWN,
+            MemoryPoolPreference: d3d12_ty::D3D12_MEMORY_POOL_UNKNOWN,
+            CreationNodeMask: 0,
+            VisibleNodeMask: 0,
+        };
+
+        let mut resource = d3d12::Resource::null();
+        unsafe {
+            self.device
+                .CreateCommittedResource(
+                    &heap_properties,
+                    d3d12_ty::D3D12_HEAP_FLAG_NONE,
+                    &raw_desc,
+                    d3d12_ty::D3D12_RESOURCE_STATE_GENERIC_READ,
+                    ptr::null(),
+                    &d3d12_ty::ID3D12Resource::uuidof(),
+                    resource.mut_void(),
+                )
+                .into_device_result("Fill pattern buffer creation")?
+        };
+        null_comptr_check(&resource)?;
+
+        let mut mapped = ptr::null_mut();
+        unsafe { resource.Map(0, ptr::null(), &mut mapped) }
+            .into_device_result("Fill pattern buffer mapping")?;
+        let words = size as usize / 4;
+        unsafe { slice::from_raw_parts_mut(mapped.cast::<u32>(), words) }.fill(value.to_le());
+        unsafe { resource.Unmap(0, ptr::null()) };
+
+        Ok(resource)
+    }
 }
 
 impl crate::CommandEncoder for super::CommandEncoder {
@@ -300,7 +359,10 @@ impl crate::CommandEncoder for super::CommandEncoder {
         let raw = self.list.take().unwrap();
         raw.close()
             .into_device_result("GraphicsCommandList::close")?;
-        Ok(super::CommandBuffer { raw })
+        Ok(super::CommandBuffer {
+            raw,
+            fill_patterns: mem::take(&mut self.temp.fill_patterns),
+        })
     }
     unsafe fn reset_all<I: Iterator<Item = super::CommandBuffer>>(&mut self, command_buffers: I) {
         for cmd_buf in command_buffers {
@@ -476,6 +538,10 @@ impl crate::CommandEncoder for super::CommandEncoder {
         }
     }
 
+    unsafe fn aliasing_barrier(&mut self) {
+        unimplemented!()
+    }
+
     unsafe fn clear_buffer(&mut self, buffer: &super::Buffer, range: crate::MemoryRange) {
         let list = self.list.as_ref().unwrap();
         let mut offset = range.start;
@@ -494,6 +560,44 @@ impl crate::CommandEncoder for super::CommandEncoder {
         }
     }
 
+    unsafe fn fill_buffer(
+        &mut self,
+        buffer: &super::Buffer,
+        range: crate::MemoryRange,
+        value: u32,
+    ) {
+        if value == 0 {
+            return unsafe { self.clear_buffer(buffer, range) };
+        }
+
+        // D3D12 has no command to fill buffers, so copy from an upload buffer
+        // holding the pattern instead.
+        let pattern_size = (range.end - range.start).min(super::ZERO_BUFFER_SIZE);
+        let pattern_buffer = match unsafe { self.create_fill_pattern(value, pattern_size) } {
+            Ok(pattern_buffer) => pattern_buffer,
+            Err(err) => {
+                log::error!("Failed to create fill pattern buffer: {err}");
+                return;
+            }
+        };
+        let list = self.list.as_ref().unwrap();
+        let mut offset = range.start;
+        while offset < range.end {
+            let size = pattern_size.min(range.end - offset);
+            unsafe {
+                list.CopyBufferRegion(
+                    buffer.resource.as_mut_ptr(),
+                    offset,
+                    pattern_buffer.as_mut_ptr(),
+                    0,
+                    size,
+                )
+            };
+            offset += size;
+        }
+        self.temp.fill_patterns.push(pattern_buffer);
+    }
+
     unsafe fn copy_buffer_to_buffer<T>(
         &mut self,
         src: &super::Buffer,
@@ -516,6 +620,17 @@ impl crate::CommandEncoder for super::CommandEncoder {
         }
     }
 
+    unsafe fn blit_texture(
+        &mut self,
+        _src: &super::Texture,
+        _src_usage: crate::TextureUses,
+        _dst: &super::Texture,
+        _region: crate::TextureBlit,
+        _filter: wgt::FilterMode,
+    ) {
+        unreachable!("`TEXTURE_BLIT` isn't exposed")
+    }
+
     unsafe fn copy_texture_to_texture<T>(
         &mut self,
         src: &super::Texture,
@@ -789,8 +904,10 @@ impl crate::CommandEncoder for super::CommandEncoder {
             right: desc.extent.width as i32,
             bottom: desc.extent.height as i32,
         };
-        unsafe { list.RSSetViewports(1, &raw_vp) };
-        unsafe { list.RSSetScissorRects(1, &raw_rect) };
+        self.pass.viewports = [raw_vp; wgt::MAX_VIEWPORTS as usize];
+        self.pass.scissor_rects = [raw_rect; wgt::MAX_VIEWPORTS as usize];
+        unsafe { list.RSSetViewports(wgt::MAX_VIEWPORTS, self.pass.viewports.as_ptr()) };
+        unsafe { list.RSSetScissorRects(wgt::MAX_VIEWPORTS, self.pass.scissor_rects.as_ptr()) };
     }
 
     unsafe fn end_render_pass(&mut self) {
@@ -921,6 +1038,34 @@ impl crate::CommandEncoder for super::CommandEncoder {
             self.reset_signature(&layout.shared);
         };
     }
+    unsafe fn set_bind_group_inline(
+        &mut self,
+        layout: &super::PipelineLayout,
+        index: u32,
+        _group_layout: &super::BindGroupLayout,
+        buffers: &[crate::BufferBinding<super::Api>],
+    ) {
+        log::trace!("Set inline group[{}]", index);
+        let info = &layout.bind_group_infos[index as usize];
+        let mut root_index = info.base_root_index as usize;
+
+        // Inline groups only have root descriptors
+        for (&kind, binding) in info.dynamic_buffers.iter().zip(buffers) {
+            log::trace!("\tBind element[{}] = inline", root_index);
+            self.pass.root_elements[root_index] = super::RootElement::DynamicOffsetBuffer {
+                kind,
+                address: binding.resolve_address(),
+            };
+            root_index += 1;
+        }
+
+        if self.pass.layout.signature == layout.shared.signature {
+            self.pass.dirty_root_elements |= (1 << root_index) - (1 << info.base_root_index);
+        } else {
+            // D3D12 requires full reset on signature change
+            self.reset_signature(&layout.shared);
+        };
+    }
     unsafe fn set_push_constants(
         &mut self,
         layout: &super::PipelineLayout,
@@ -1017,7 +1162,18 @@ impl crate::CommandEncoder for super::CommandEncoder {
     }
 
     unsafe fn set_viewport(&mut self, rect: &crate::Rect<f32>, depth_range: Range<f32>) {
-        let raw_vp = d3d12_ty::D3D12_VIEWPORT {
+        unsafe { self.set_indexed_viewport(0, rect, depth_range) };
+    }
+    unsafe fn set_scissor_rect(&mut self, rect: &crate::Rect<u32>) {
+        unsafe { self.set_indexed_scissor_rect(0, rect) };
+    }
+    unsafe fn set_indexed_viewport(
+        &mut self,
+        index: u32,
+        rect: &crate::Rect<f32>,
+        depth_range: Range<f32>,
+    ) {
+        self.pass.viewports[index as usize] = d3d12_ty::D3D12_VIEWPORT {
             TopLeftX: rect.x,
             TopLeftY: rect.y,
             Width: rect.w,
@@ -1025,23 +1181,48 @@ impl crate::CommandEncoder for super::CommandEncoder {
             MinDepth: depth_range.start,
             MaxDepth: depth_range.end,
         };
-        unsafe { self.list.as_ref().unwrap().RSSetViewports(1, &raw_vp) };
+        unsafe {
+            self.list
+                .as_ref()
+                .unwrap()
+                .RSSetViewports(wgt::MAX_VIEWPORTS, self.pass.viewports.as_ptr())
+        };
     }
-    unsafe fn set_scissor_rect(&mut self, rect: &crate::Rect<u32>) {
-        let raw_rect = d3d12_ty::D3D12_RECT {
+    unsafe fn set_indexed_scissor_rect(&mut self, index: u32, rect: &crate::Rect<u32>) {
+        self.pass.scissor_rects[index as usize] = d3d12_ty::D3D12_RECT {
             left: rect.x as i32,
             top: rect.y as i32,
             right: (rect.x + rect.w) as i32,
             bottom: (rect.y + rect.h) as i32,
         };
-        unsafe { self.list.as_ref().unwrap().RSSetScissorRects(1, &raw_rect) };
+        unsafe {
+            self.list
+                .as_ref()
+                .unwrap()
+                .RSSetScissorRects(wgt::MAX_VIEWPORTS, self.pass.scissor_rects.as_ptr())
+        };
     }
     unsafe fn set_stencil_reference(&mut self, value: u32) {
         self.list.as_ref().unwrap().set_stencil_reference(value);
     }
+    unsafe fn set_stencil_reference_separate(&mut self, _front: u32, _back: u32) {
+        unreachable!("`SEPARATE_STENCIL_STATE` isn't exposed")
+    }
+    unsafe fn set_stencil_write_mask(&mut self, _front: u32, _back: u32) {
+        unreachable!("`SEPARATE_STENCIL_STATE` isn't exposed")
+    }
     unsafe fn set_blend_constants(&mut self, color: &[f32; 4]) {
         self.list.as_ref().unwrap().set_blend_factor(*color);
     }
+    unsafe fn set_front_face(&mut self, _front_face: wgt::FrontFace) {
+        unreachable!("`EXTENDED_DYNAMIC_STATE` isn't exposed")
+    }
+    unsafe fn set_cull_mode(&mut self, _cull_mode: Option<wgt::Face>) {
+        unreachable!("`EXTENDED_DYNAMIC_STATE` isn't exposed")
+    }
+    unsafe fn set_depth_compare(&mut self, _compare: wgt::CompareFunction) {
+        unreachable!("`EXTENDED_DYNAMIC_STATE` isn't exposed")
+    }
 
     unsafe fn draw(
         &mut self,
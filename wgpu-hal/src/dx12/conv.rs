@@ -63,6 +63,18 @@ pub fn map_filter_mode(mode: wgt::FilterMode) -> d3d12_ty::D3D12_FILTER_TYPE {
     }
 }
 
+pub fn map_reduction_mode(
+    mode: wgt::SamplerReductionMode,
+) -> d3d12_ty::D3D12_FILTER_REDUCTION_TYPE {
+    match mode {
+        wgt::SamplerReductionMode::WeightedAverage => {
+            d3d12_ty::D3D12_FILTER_REDUCTION_TYPE_STANDARD
+        }
+        wgt::SamplerReductionMode::Min => d3d12_ty::D3D12_FILTER_REDUCTION_TYPE_MINIMUM,
+        wgt::SamplerReductionMode::Max => d3d12_ty::D3D12_FILTER_REDUCTION_TYPE_MAXIMUM,
+    }
+}
+
 pub fn map_comparison(func: wgt::CompareFunction) -> d3d12_ty::D3D12_COMPARISON_FUNC {
     use wgt::CompareFunction as Cf;
     match func {
@@ -83,6 +95,7 @@ pub fn map_border_color(border_color: Option<wgt::SamplerBorderColor>) -> [f32;
         Some(Sbc::TransparentBlack) | Some(Sbc::Zero) | None => [0.0; 4],
         Some(Sbc::OpaqueBlack) => [0.0, 0.0, 0.0, 1.0],
         Some(Sbc::OpaqueWhite) => [1.0; 4],
+        Some(Sbc::Custom(color)) => color,
     }
 }
 
@@ -113,6 +126,9 @@ pub fn map_binding_type(ty: &wgt::BindingType) -> d3d12::DescriptorRangeType {
         }
         | Bt::StorageTexture { .. } => d3d12::DescriptorRangeType::UAV,
         Bt::AccelerationStructure => todo!(),
+        Bt::YcbcrTexture { .. } => {
+            unreachable!("YcbcrTexture is gated by SAMPLER_YCBCR_CONVERSION")
+        }
     }
 }
 
@@ -353,3 +369,11 @@ pub fn map_depth_stencil(ds: &wgt::DepthStencilState) -> d3d12_ty::D3D12_DEPTH_S
         BackFace: map_stencil_face(&ds.stencil.back),
     }
 }
+
+pub fn map_queue_priority(priority: wgt::QueuePriority) -> d3d12::Priority {
+    match priority {
+        wgt::QueuePriority::Normal => d3d12::Priority::Normal,
+        wgt::QueuePriority::High => d3d12::Priority::High,
+        wgt::QueuePriority::Realtime => d3d12::Priority::GlobalRealtime,
+    }
+}
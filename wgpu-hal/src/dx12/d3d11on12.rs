@@ -0,0 +1,182 @@
+//! D3D11 devices layered on a D3D12 device and queue through D3D11On12, for
+//! APIs that only speak D3D11, like Media Foundation and DXVA.
+
+use std::ptr;
+
+use winapi::{
+    shared::winerror,
+    um::{d3d11, d3d11on12, d3d12 as d3d12_ty, d3dcommon, unknwnbase::IUnknown},
+    Interface as _,
+};
+
+use crate::auxil::dxgi::result::HResult as _;
+use crate::DeviceError;
+
+/// A D3D11 device recording into the queue of a [`super::Device`].
+///
+/// The device isn't `Sync`, since the immediate context isn't thread safe.
+pub struct D3D11On12Device {
+    device: d3d12::ComPtr<d3d11::ID3D11Device>,
+    context: d3d12::ComPtr<d3d11::ID3D11DeviceContext>,
+    on12: d3d12::ComPtr<d3d11on12::ID3D11On12Device>,
+}
+
+unsafe impl Send for D3D11On12Device {}
+
+impl std::fmt::Debug for D3D11On12Device {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("D3D11On12Device").finish_non_exhaustive()
+    }
+}
+
+/// A [`super::Texture`] wrapped as a D3D11 resource of a [`D3D11On12Device`].
+///
+/// The resource is in `D3D12_RESOURCE_STATE_COMMON` whenever it isn't
+/// acquired by the D3D11 device.
+pub struct D3D11Texture {
+    raw: d3d12::ComPtr<d3d11::ID3D11Resource>,
+}
+
+unsafe impl Send for D3D11Texture {}
+unsafe impl Sync for D3D11Texture {}
+
+impl std::fmt::Debug for D3D11Texture {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("D3D11Texture").finish_non_exhaustive()
+    }
+}
+
+impl D3D11Texture {
+    /// The D3D11 resource, which can be cast to the `ID3D11Texture*` of its
+    /// dimension.
+    pub fn raw(&self) -> &d3d12::ComPtr<d3d11::ID3D11Resource> {
+        &self.raw
+    }
+}
+
+impl super::Device {
+    /// Creates a D3D11 device on this device, submitting its work to `queue`.
+    pub fn create_d3d11on12_device(
+        &self,
+        queue: &super::Queue,
+    ) -> Result<D3D11On12Device, DeviceError> {
+        let mut device = d3d12::ComPtr::<d3d11::ID3D11Device>::null();
+        let mut context = d3d12::ComPtr::<d3d11::ID3D11DeviceContext>::null();
+        let queues = [queue.raw.as_mut_ptr().cast::<IUnknown>()];
+        let mut feature_level: d3dcommon::D3D_FEATURE_LEVEL = 0;
+        let hr = unsafe {
+            profiling::scope!("D3D11On12CreateDevice");
+            d3d11on12::D3D11On12CreateDevice(
+                self.raw.as_mut_ptr().cast(),
+                d3d11::D3D11_CREATE_DEVICE_BGRA_SUPPORT,
+                ptr::null(),
+                0,
+                queues.as_ptr(),
+                queues.len() as u32,
+                0,
+                device.mut_self(),
+                context.mut_self(),
+                &mut feature_level,
+            )
+        };
+        hr.into_device_result("D3D11On12 device creation")?;
+        log::info!("Created a D3D11On12 device with feature level {feature_level:#x}");
+
+        let on12 = unsafe { device.cast::<d3d11on12::ID3D11On12Device>() }
+            .into_device_result("ID3D11On12Device query")?;
+        Ok(D3D11On12Device {
+            device,
+            context,
+            on12,
+        })
+    }
+}
+
+impl D3D11On12Device {
+    pub fn raw_device(&self) -> &d3d12::ComPtr<d3d11::ID3D11Device> {
+        &self.device
+    }
+
+    pub fn raw_context(&self) -> &d3d12::ComPtr<d3d11::ID3D11DeviceContext> {
+        &self.context
+    }
+
+    /// Wraps `texture` as a D3D11 resource, bindable as allowed by `usage`.
+    ///
+    /// # Safety
+    ///
+    /// - `texture` must be a texture of the device this device was created on,
+    ///   created with `usage`, that outlives the returned texture.
+    pub unsafe fn wrap_texture(
+        &self,
+        texture: &super::Texture,
+        usage: wgt::TextureUsages,
+    ) -> Result<D3D11Texture, DeviceError> {
+        let mut bind_flags = 0;
+        if usage.contains(wgt::TextureUsages::TEXTURE_BINDING) {
+            bind_flags |= d3d11::D3D11_BIND_SHADER_RESOURCE;
+        }
+        if usage.contains(wgt::TextureUsages::STORAGE_BINDING) {
+            bind_flags |= d3d11::D3D11_BIND_UNORDERED_ACCESS;
+        }
+        if usage.contains(wgt::TextureUsages::RENDER_ATTACHMENT) {
+            bind_flags |= if texture.format.is_depth_stencil_format() {
+                d3d11::D3D11_BIND_DEPTH_STENCIL
+            } else {
+                d3d11::D3D11_BIND_RENDER_TARGET
+            };
+        }
+        let flags = d3d11on12::D3D11_RESOURCE_FLAGS {
+            BindFlags: bind_flags,
+            MiscFlags: 0,
+            CPUAccessFlags: 0,
+            StructureByteStride: 0,
+        };
+
+        let mut raw = d3d12::ComPtr::<d3d11::ID3D11Resource>::null();
+        let hr = unsafe {
+            self.on12.CreateWrappedResource(
+                texture.resource.as_mut_ptr().cast(),
+                &flags,
+                d3d12_ty::D3D12_RESOURCE_STATE_COMMON,
+                d3d12_ty::D3D12_RESOURCE_STATE_COMMON,
+                &d3d11::ID3D11Resource::uuidof(),
+                raw.mut_void(),
+            )
+        };
+        if hr == winerror::E_INVALIDARG {
+            log::error!("Texture can't be wrapped with D3D11 bind flags {bind_flags:#x}");
+        }
+        hr.into_device_result("D3D11 resource wrapping")?;
+        Ok(D3D11Texture { raw })
+    }
+
+    /// Makes `texture` usable by the D3D11 device.
+    ///
+    /// # Safety
+    ///
+    /// - The texture must be in `D3D12_RESOURCE_STATE_COMMON` on the queue of
+    ///   this device, and not be used through D3D12 until it's released.
+    pub unsafe fn acquire_texture(&self, texture: &D3D11Texture) {
+        let mut resources = [texture.raw.as_mut_ptr()];
+        unsafe {
+            self.on12
+                .AcquireWrappedResources(resources.as_mut_ptr(), resources.len() as u32)
+        };
+    }
+
+    /// Hands `texture` back to D3D12, and submits the work recorded by the D3D11
+    /// device to the queue, leaving the texture in `D3D12_RESOURCE_STATE_COMMON`.
+    ///
+    /// # Safety
+    ///
+    /// - The texture must have been acquired with [`Self::acquire_texture`].
+    pub unsafe fn release_texture(&self, texture: &D3D11Texture) {
+        let mut resources = [texture.raw.as_mut_ptr()];
+        unsafe {
+            self.on12
+                .ReleaseWrappedResources(resources.as_mut_ptr(), resources.len() as u32);
+            self.context.Flush();
+        }
+    }
+}
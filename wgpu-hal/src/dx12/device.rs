@@ -299,6 +299,13 @@ impl super::Device {
             naga_stage,
             source
         );
+        if let Some(capture) = stage.code_capture {
+            capture.capture("hlsl", source);
+            if let Ok(ref compiled) = result {
+                let (extension, code) = compiled.code();
+                capture.capture(extension, code);
+            }
+        }
         result
     }
 
@@ -326,6 +333,7 @@ impl super::Device {
             mip_level_count,
             sample_count,
             allocation: None,
+            shared_size: None,
         }
     }
 
@@ -337,30 +345,205 @@ impl super::Device {
             resource,
             size,
             allocation: None,
+            shared_size: None,
         }
     }
-}
 
-impl crate::Device for super::Device {
-    type A = super::Api;
+    /// Creates a buffer in a shared heap, whose NT handle can be exported
+    /// with [`Self::export_buffer_memory`].
+    ///
+    /// The buffer can't be mapped.
+    ///
+    /// # Safety
+    ///
+    /// - `desc` must be valid, like for [`crate::Device::create_buffer`].
+    /// - `handle_type` must be [`wgt::ExternalMemoryHandleType::D3D12Resource`].
+    pub unsafe fn create_exportable_buffer(
+        &self,
+        desc: &crate::BufferDescriptor,
+        handle_type: wgt::ExternalMemoryHandleType,
+    ) -> Result<super::Buffer, DeviceError> {
+        assert_eq!(handle_type, wgt::ExternalMemoryHandleType::D3D12Resource);
+        let raw_desc = Self::buffer_resource_desc(desc);
+        let resource = unsafe { self.create_shared_resource(&raw_desc, desc.label)? };
+        Ok(super::Buffer {
+            resource,
+            size: raw_desc.Width,
+            allocation: None,
+            shared_size: Some(raw_desc.Width),
+        })
+    }
 
-    unsafe fn exit(mut self, _queue: super::Queue) {
-        self.rtv_pool.lock().free_handle(self.null_rtv_handle);
-        self.mem_allocator = None;
+    /// Creates a texture in a shared heap, whose NT handle can be exported
+    /// with [`Self::export_texture_memory`].
+    ///
+    /// DX12 has no DRM format modifiers, `_drm_format_modifiers` is ignored.
+    ///
+    /// # Safety
+    ///
+    /// - `desc` must be valid, like for [`crate::Device::create_texture`].
+    /// - `handle_type` must be [`wgt::ExternalMemoryHandleType::D3D12Resource`].
+    pub unsafe fn create_exportable_texture(
+        &self,
+        desc: &crate::TextureDescriptor,
+        handle_type: wgt::ExternalMemoryHandleType,
+        _drm_format_modifiers: &[u64],
+    ) -> Result<super::Texture, DeviceError> {
+        assert_eq!(handle_type, wgt::ExternalMemoryHandleType::D3D12Resource);
+        let raw_desc = self.texture_resource_desc(desc);
+        let resource = unsafe { self.create_shared_resource(&raw_desc, desc.label)? };
+        Ok(super::Texture {
+            resource,
+            format: desc.format,
+            dimension: desc.dimension,
+            size: desc.size,
+            mip_level_count: desc.mip_level_count,
+            sample_count: desc.sample_count,
+            allocation: None,
+            shared_size: Some(unsafe { self.texture_footprint_size(desc, &raw_desc) }),
+        })
     }
 
-    unsafe fn create_buffer(
+    /// Opens the buffer shared through `memory.handle`.
+    ///
+    /// The handle is closed once the buffer is opened, the buffer keeps the
+    /// shared heap alive.
+    ///
+    /// # Safety
+    ///
+    /// - `memory.handle` must be an NT handle of an `ID3D12Resource` buffer,
+    ///   at least as large as `desc.size`.
+    /// - `desc.usage` must be supported by the resource flags of the buffer.
+    pub unsafe fn import_buffer(
         &self,
         desc: &crate::BufferDescriptor,
+        memory: wgt::ExternalMemory,
     ) -> Result<super::Buffer, DeviceError> {
-        let mut resource = d3d12::Resource::null();
+        let resource = unsafe { self.open_shared_resource(&memory, desc.label)? };
+        Ok(super::Buffer {
+            resource,
+            size: desc.size,
+            allocation: None,
+            shared_size: Some(memory.size),
+        })
+    }
+
+    /// Opens the texture shared through `memory.handle`.
+    ///
+    /// The handle is closed once the texture is opened, the texture keeps the
+    /// shared heap alive.
+    ///
+    /// # Safety
+    ///
+    /// - `memory.handle` must be an NT handle of an `ID3D12Resource` texture
+    ///   matching `desc`.
+    pub unsafe fn import_texture(
+        &self,
+        desc: &crate::TextureDescriptor,
+        memory: wgt::ExternalMemory,
+    ) -> Result<super::Texture, DeviceError> {
+        let resource = unsafe { self.open_shared_resource(&memory, desc.label)? };
+        Ok(super::Texture {
+            resource,
+            format: desc.format,
+            dimension: desc.dimension,
+            size: desc.size,
+            mip_level_count: desc.mip_level_count,
+            sample_count: desc.sample_count,
+            allocation: None,
+            shared_size: Some(memory.size),
+        })
+    }
+
+    /// Returns a new NT handle of `buffer`, owned by the caller.
+    ///
+    /// # Panics
+    ///
+    /// - `buffer` wasn't created with [`Self::create_exportable_buffer`] or
+    ///   [`Self::import_buffer`].
+    pub unsafe fn export_buffer_memory(
+        &self,
+        buffer: &super::Buffer,
+    ) -> Result<wgt::ExternalMemory, DeviceError> {
+        let size = buffer
+            .shared_size
+            .expect("Buffer was not created in a shared heap");
+        Ok(wgt::ExternalMemory {
+            handle: unsafe { self.create_shared_handle(buffer.resource.as_mut_ptr().cast())? },
+            handle_type: wgt::ExternalMemoryHandleType::D3D12Resource,
+            size,
+            drm_format_modifier: None,
+            planes: Vec::new(),
+        })
+    }
+
+    /// Returns a new NT handle of `texture`, owned by the caller.
+    ///
+    /// # Panics
+    ///
+    /// - `texture` wasn't created with [`Self::create_exportable_texture`] or
+    ///   [`Self::import_texture`].
+    pub unsafe fn export_texture_memory(
+        &self,
+        texture: &super::Texture,
+    ) -> Result<wgt::ExternalMemory, DeviceError> {
+        let size = texture
+            .shared_size
+            .expect("Texture was not created in a shared heap");
+        Ok(wgt::ExternalMemory {
+            handle: unsafe { self.create_shared_handle(texture.resource.as_mut_ptr().cast())? },
+            handle_type: wgt::ExternalMemoryHandleType::D3D12Resource,
+            size,
+            drm_format_modifier: None,
+            planes: Vec::new(),
+        })
+    }
+
+    /// Returns a new NT handle of `fence`, owned by the caller.
+    ///
+    /// Other devices and processes synchronize with the work of this device
+    /// by waiting for, and signaling, the same fence values.
+    pub unsafe fn export_fence_handle(
+        &self,
+        fence: &super::Fence,
+    ) -> Result<std::os::windows::io::OwnedHandle, DeviceError> {
+        unsafe { self.create_shared_handle(fence.raw.as_mut_ptr().cast()) }
+    }
+
+    /// Opens the fence shared through `handle`, like one from
+    /// `ID3D11Device5::CreateFence` with `D3D11_FENCE_FLAG_SHARED`.
+    ///
+    /// # Safety
+    ///
+    /// - `handle` must be an NT handle of a shared fence.
+    pub unsafe fn import_fence(
+        &self,
+        handle: std::os::windows::io::BorrowedHandle<'_>,
+    ) -> Result<super::Fence, DeviceError> {
+        use std::os::windows::io::AsRawHandle as _;
+
+        let mut raw = d3d12::Fence::null();
+        let hr = unsafe {
+            self.raw.OpenSharedHandle(
+                handle.as_raw_handle().cast(),
+                &d3d12_ty::ID3D12Fence::uuidof(),
+                raw.mut_void(),
+            )
+        };
+        hr.into_device_result("Fence import")?;
+        null_comptr_check(&raw)?;
+
+        Ok(super::Fence { raw })
+    }
+
+    fn buffer_resource_desc(desc: &crate::BufferDescriptor) -> d3d12_ty::D3D12_RESOURCE_DESC {
         let mut size = desc.size;
         if desc.usage.contains(crate::BufferUses::UNIFORM) {
             let align_mask = d3d12_ty::D3D12_CONSTANT_BUFFER_DATA_PLACEMENT_ALIGNMENT as u64 - 1;
             size = ((size - 1) | align_mask) + 1;
         }
 
-        let raw_desc = d3d12_ty::D3D12_RESOURCE_DESC {
+        d3d12_ty::D3D12_RESOURCE_DESC {
             Dimension: d3d12_ty::D3D12_RESOURCE_DIMENSION_BUFFER,
             Alignment: 0,
             Width: size,
@@ -374,8 +557,168 @@ impl crate::Device for super::Device {
             },
             Layout: d3d12_ty::D3D12_TEXTURE_LAYOUT_ROW_MAJOR,
             Flags: conv::map_buffer_usage_to_resource_flags(desc.usage),
+        }
+    }
+
+    fn texture_resource_desc(
+        &self,
+        desc: &crate::TextureDescriptor,
+    ) -> d3d12_ty::D3D12_RESOURCE_DESC {
+        d3d12_ty::D3D12_RESOURCE_DESC {
+            Dimension: conv::map_texture_dimension(desc.dimension),
+            Alignment: 0,
+            Width: desc.size.width as u64,
+            Height: desc.size.height,
+            DepthOrArraySize: desc.size.depth_or_array_layers as u16,
+            MipLevels: desc.mip_level_count as u16,
+            Format: auxil::dxgi::conv::map_texture_format_for_resource(
+                desc.format,
+                desc.usage,
+                !desc.view_formats.is_empty(),
+                self.private_caps.casting_fully_typed_format_supported,
+            ),
+            SampleDesc: dxgitype::DXGI_SAMPLE_DESC {
+                Count: desc.sample_count,
+                Quality: 0,
+            },
+            Layout: d3d12_ty::D3D12_TEXTURE_LAYOUT_UNKNOWN,
+            Flags: conv::map_texture_usage_to_resource_flags(desc.usage),
+        }
+    }
+
+    /// Creates a committed resource in its own shared heap.
+    unsafe fn create_shared_resource(
+        &self,
+        raw_desc: &d3d12_ty::D3D12_RESOURCE_DESC,
+        label: crate::Label,
+    ) -> Result<d3d12::Resource, DeviceError> {
+        let heap_properties = d3d12_ty::D3D12_HEAP_PROPERTIES {
+            Type: d3d12_ty::D3D12_HEAP_TYPE_DEFAULT,
+            CPUPageProperty: d3d12_ty::D3D12_CPU_PAGE_PROPERTY_UNKNOWN,
+            MemoryPoolPreference: d3d12_ty::D3D12_MEMORY_POOL_UNKNOWN,
+            CreationNodeMask: 0,
+            VisibleNodeMask: 0,
         };
 
+        let mut resource = d3d12::Resource::null();
+        let hr = unsafe {
+            self.raw.CreateCommittedResource(
+                &heap_properties,
+                d3d12_ty::D3D12_HEAP_FLAG_SHARED,
+                raw_desc,
+                d3d12_ty::D3D12_RESOURCE_STATE_COMMON,
+                ptr::null(),
+                &d3d12_ty::ID3D12Resource::uuidof(),
+                resource.mut_void(),
+            )
+        };
+        hr.into_device_result("Shared resource creation")?;
+        null_comptr_check(&resource)?;
+
+        if let Some(label) = label {
+            let cwstr = conv::map_label(label);
+            unsafe { resource.SetName(cwstr.as_ptr()) };
+        }
+        Ok(resource)
+    }
+
+    /// Opens the resource shared through `memory.handle`.
+    unsafe fn open_shared_resource(
+        &self,
+        memory: &wgt::ExternalMemory,
+        label: crate::Label,
+    ) -> Result<d3d12::Resource, DeviceError> {
+        use std::os::windows::io::AsRawHandle as _;
+
+        assert_eq!(
+            memory.handle_type,
+            wgt::ExternalMemoryHandleType::D3D12Resource
+        );
+        let mut resource = d3d12::Resource::null();
+        let hr = unsafe {
+            self.raw.OpenSharedHandle(
+                memory.handle.as_raw_handle().cast(),
+                &d3d12_ty::ID3D12Resource::uuidof(),
+                resource.mut_void(),
+            )
+        };
+        hr.into_device_result("Resource import")?;
+        null_comptr_check(&resource)?;
+
+        if let Some(label) = label {
+            let cwstr = conv::map_label(label);
+            unsafe { resource.SetName(cwstr.as_ptr()) };
+        }
+        Ok(resource)
+    }
+
+    /// Returns a new NT handle of the shared `object`, owned by the caller.
+    unsafe fn create_shared_handle(
+        &self,
+        object: *mut d3d12_ty::ID3D12DeviceChild,
+    ) -> Result<std::os::windows::io::OwnedHandle, DeviceError> {
+        use std::os::windows::io::FromRawHandle as _;
+
+        let mut handle = ptr::null_mut();
+        let hr = unsafe {
+            self.raw.CreateSharedHandle(
+                object,
+                ptr::null(),
+                winapi::um::winnt::GENERIC_ALL,
+                ptr::null(),
+                &mut handle,
+            )
+        };
+        hr.into_device_result("Shared handle creation")?;
+        Ok(unsafe { std::os::windows::io::OwnedHandle::from_raw_handle(handle.cast()) })
+    }
+
+    /// Returns the number of bytes all subresources of a texture take up.
+    unsafe fn texture_footprint_size(
+        &self,
+        desc: &crate::TextureDescriptor,
+        raw_desc: &d3d12_ty::D3D12_RESOURCE_DESC,
+    ) -> wgt::BufferAddress {
+        let planes = if desc.format.is_combined_depth_stencil_format() {
+            2
+        } else {
+            desc.format.planes().unwrap_or(1)
+        };
+        let mut total_bytes = 0;
+        unsafe {
+            self.raw.GetCopyableFootprints(
+                raw_desc,
+                0,
+                desc.mip_level_count * desc.array_layer_count() * planes,
+                0,
+                ptr::null_mut(),
+                ptr::null_mut(),
+                ptr::null_mut(),
+                &mut total_bytes,
+            )
+        };
+        total_bytes
+    }
+}
+
+impl crate::Device for super::Device {
+    type A = super::Api;
+
+    unsafe fn exit(mut self, _queue: super::Queue) {
+        self.rtv_pool.lock().free_handle(self.null_rtv_handle);
+        self.mem_allocator = None;
+    }
+
+    unsafe fn destroy_queue(&self, _queue: super::Queue) {}
+
+    unsafe fn create_buffer(
+        &self,
+        desc: &crate::BufferDescriptor,
+    ) -> Result<super::Buffer, DeviceError> {
+        let mut resource = d3d12::Resource::null();
+        let raw_desc = Self::buffer_resource_desc(desc);
+        let size = raw_desc.Width;
+
         let (hr, allocation) =
             super::suballocation::create_buffer_resource(self, desc, raw_desc, &mut resource)?;
 
@@ -389,6 +732,7 @@ impl crate::Device for super::Device {
             resource,
             size,
             allocation,
+            shared_size: None,
         })
     }
 
@@ -429,6 +773,9 @@ impl crate::Device for super::Device {
 
     unsafe fn flush_mapped_ranges<I>(&self, _buffer: &super::Buffer, _ranges: I) {}
     unsafe fn invalidate_mapped_ranges<I>(&self, _buffer: &super::Buffer, _ranges: I) {}
+    unsafe fn get_buffer_device_address(&self, buffer: &super::Buffer) -> wgt::BufferAddress {
+        buffer.resource.gpu_virtual_address()
+    }
 
     unsafe fn create_texture(
         &self,
@@ -438,26 +785,7 @@ impl crate::Device for super::Device {
 
         let mut resource = d3d12::Resource::null();
 
-        let raw_desc = d3d12_ty::D3D12_RESOURCE_DESC {
-            Dimension: conv::map_texture_dimension(desc.dimension),
-            Alignment: 0,
-            Width: desc.size.width as u64,
-            Height: desc.size.height,
-            DepthOrArraySize: desc.size.depth_or_array_layers as u16,
-            MipLevels: desc.mip_level_count as u16,
-            Format: auxil::dxgi::conv::map_texture_format_for_resource(
-                desc.format,
-                desc.usage,
-                !desc.view_formats.is_empty(),
-                self.private_caps.casting_fully_typed_format_supported,
-            ),
-            SampleDesc: dxgitype::DXGI_SAMPLE_DESC {
-                Count: desc.sample_count,
-                Quality: 0,
-            },
-            Layout: d3d12_ty::D3D12_TEXTURE_LAYOUT_UNKNOWN,
-            Flags: conv::map_texture_usage_to_resource_flags(desc.usage),
-        };
+        let raw_desc = self.texture_resource_desc(desc);
 
         let (hr, allocation) = create_texture_resource(self, desc, raw_desc, &mut resource)?;
 
@@ -475,6 +803,7 @@ impl crate::Device for super::Device {
             mip_level_count: desc.mip_level_count,
             sample_count: desc.sample_count,
             allocation,
+            shared_size: None,
         })
     }
 
@@ -488,6 +817,56 @@ impl crate::Device for super::Device {
         }
     }
 
+    unsafe fn get_sparse_texture_tiles(
+        &self,
+        _texture: &super::Texture,
+    ) -> wgt::SparseTextureTiles {
+        unimplemented!()
+    }
+
+    unsafe fn create_memory_heap(
+        &self,
+        _desc: &crate::MemoryHeapDescriptor,
+    ) -> Result<(), crate::DeviceError> {
+        unimplemented!()
+    }
+
+    unsafe fn destroy_memory_heap(&self, _heap: ()) {
+        unimplemented!()
+    }
+
+    unsafe fn get_buffer_memory_requirements(
+        &self,
+        _desc: &crate::BufferDescriptor,
+    ) -> Result<wgt::MemoryRequirements, crate::DeviceError> {
+        unimplemented!()
+    }
+
+    unsafe fn get_texture_memory_requirements(
+        &self,
+        _desc: &crate::TextureDescriptor,
+    ) -> Result<wgt::MemoryRequirements, crate::DeviceError> {
+        unimplemented!()
+    }
+
+    unsafe fn create_placed_buffer(
+        &self,
+        _desc: &crate::BufferDescriptor,
+        _heap: &(),
+        _offset: wgt::BufferAddress,
+    ) -> Result<super::Buffer, crate::DeviceError> {
+        unimplemented!()
+    }
+
+    unsafe fn create_placed_texture(
+        &self,
+        _desc: &crate::TextureDescriptor,
+        _heap: &(),
+        _offset: wgt::BufferAddress,
+    ) -> Result<super::Texture, crate::DeviceError> {
+        unimplemented!()
+    }
+
     unsafe fn create_texture_view(
         &self,
         texture: &super::Texture,
@@ -623,7 +1002,7 @@ impl crate::Device for super::Device {
 
         let reduction = match desc.compare {
             Some(_) => d3d12_ty::D3D12_FILTER_REDUCTION_TYPE_COMPARISON,
-            None => d3d12_ty::D3D12_FILTER_REDUCTION_TYPE_STANDARD,
+            None => conv::map_reduction_mode(desc.reduction_mode),
         };
         let mut filter = conv::map_filter_mode(desc.min_filter) << d3d12_ty::D3D12_MIN_FILTER_SHIFT
             | conv::map_filter_mode(desc.mag_filter) << d3d12_ty::D3D12_MAG_FILTER_SHIFT
@@ -644,7 +1023,10 @@ impl crate::Device for super::Device {
                 conv::map_address_mode(desc.address_modes[1]),
                 conv::map_address_mode(desc.address_modes[2]),
             ],
-            0.0,
+            desc.lod_bias.clamp(
+                d3d12_ty::D3D12_MIP_LOD_BIAS_MIN,
+                d3d12_ty::D3D12_MIP_LOD_BIAS_MAX,
+            ),
             desc.anisotropy_clamp as u32,
             conv::map_comparison(desc.compare.unwrap_or(wgt::CompareFunction::Always)),
             border_color,
@@ -689,8 +1071,22 @@ impl crate::Device for super::Device {
         &self,
         desc: &crate::BindGroupLayoutDescriptor,
     ) -> Result<super::BindGroupLayout, DeviceError> {
+        // Inline bind groups are made of root descriptors, like buffers with dynamic offsets.
+        let mut entries = desc.entries.to_vec();
+        if desc.flags.contains(crate::BindGroupLayoutFlags::INLINE) {
+            for entry in entries.iter_mut() {
+                if let wgt::BindingType::Buffer {
+                    ref mut has_dynamic_offset,
+                    ..
+                } = entry.ty
+                {
+                    *has_dynamic_offset = true;
+                }
+            }
+        }
+
         let (mut num_buffer_views, mut num_samplers, mut num_texture_views) = (0, 0, 0);
-        for entry in desc.entries.iter() {
+        for entry in entries.iter() {
             let count = entry.count.map_or(1, NonZeroU32::get);
             match entry.ty {
                 wgt::BindingType::Buffer {
@@ -703,12 +1099,15 @@ impl crate::Device for super::Device {
                 }
                 wgt::BindingType::Sampler { .. } => num_samplers += count,
                 wgt::BindingType::AccelerationStructure => todo!(),
+                wgt::BindingType::YcbcrTexture { .. } => {
+                    unreachable!("YcbcrTexture is gated by SAMPLER_YCBCR_CONVERSION")
+                }
             }
         }
 
         let num_views = num_buffer_views + num_texture_views;
         Ok(super::BindGroupLayout {
-            entries: desc.entries.to_vec(),
+            entries,
             cpu_heap_views: if num_views != 0 {
                 let heap = descriptor::CpuHeap::new(
                     self.raw.clone(),
@@ -1229,6 +1628,9 @@ impl crate::Device for super::Device {
                     }
                 }
                 wgt::BindingType::AccelerationStructure => todo!(),
+                wgt::BindingType::YcbcrTexture { .. } => {
+                    unreachable!("YcbcrTexture is gated by SAMPLER_YCBCR_CONVERSION")
+                }
             }
         }
 
@@ -1275,6 +1677,7 @@ impl crate::Device for super::Device {
             self.shared.heap_samplers.free_slice(dual);
         }
     }
+    unsafe fn recycle_bind_groups(&self) {}
 
     unsafe fn create_shader_module(
         &self,
@@ -1658,6 +2061,14 @@ impl crate::Device for super::Device {
         }
     }
 
+    unsafe fn signal_fence(
+        &self,
+        fence: &mut super::Fence,
+        value: crate::FenceValue,
+    ) -> Result<(), DeviceError> {
+        fence.raw.signal(value).into_device_result("Fence signal")
+    }
+
     unsafe fn start_capture(&self) -> bool {
         #[cfg(feature = "renderdoc")]
         {
@@ -1678,6 +2089,11 @@ impl crate::Device for super::Device {
         }
     }
 
+    unsafe fn set_performance_hint(&self, _hint: wgt::PerformanceHint) {
+        // The stable power state of D3D12 is a profiling tool that only
+        // developer mode grants.
+    }
+
     unsafe fn get_acceleration_structure_build_sizes<'a>(
         &self,
         _desc: &crate::GetAccelerationStructureBuildSizesDescriptor<'a, super::Api>,
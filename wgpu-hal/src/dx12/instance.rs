@@ -153,7 +153,13 @@ impl crate::Instance for super::Instance {
         adapters
             .into_iter()
             .filter_map(|raw| {
-                super::Adapter::expose(raw, &self.library, self.flags, self.dxc_container.clone())
+                super::Adapter::expose(
+                    raw,
+                    &self.library,
+                    self.flags,
+                    self.dxc_container.clone(),
+                    self.supports_allow_tearing,
+                )
             })
             .collect()
     }
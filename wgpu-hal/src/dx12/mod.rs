@@ -36,6 +36,8 @@ Otherwise, we pass a range corresponding only to the current bind group.
 mod adapter;
 mod command;
 mod conv;
+#[cfg(feature = "d3d11on12")]
+mod d3d11on12;
 mod descriptor;
 mod device;
 mod instance;
@@ -45,12 +47,14 @@ mod types;
 mod view;
 
 use crate::auxil::{self, dxgi::result::HResult as _};
+#[cfg(feature = "d3d11on12")]
+pub use d3d11on12::{D3D11On12Device, D3D11Texture};
 
 use arrayvec::ArrayVec;
 use parking_lot::{Mutex, RwLock};
-use std::{ffi, fmt, mem, num::NonZeroU32, sync::Arc};
+use std::{ffi, fmt, mem, num::NonZeroU32, ptr, sync::Arc};
 use winapi::{
-    shared::{dxgi, dxgi1_4, dxgitype, windef, winerror},
+    shared::{dxgi, dxgi1_2, dxgi1_4, dxgi1_5, dxgicommon, dxgitype, windef, winerror},
     um::{d3d12 as d3d12_ty, dcomp, synchapi, winbase, winnt},
     Interface as _,
 };
@@ -74,6 +78,7 @@ impl crate::Api for Api {
     type TextureView = TextureView;
     type Sampler = Sampler;
     type QuerySet = QuerySet;
+    type MemoryHeap = ();
     type Fence = Fence;
 
     type BindGroupLayout = BindGroupLayout;
@@ -152,10 +157,23 @@ struct SwapChain {
     // when the swapchain is destroyed
     resources: Vec<d3d12::Resource>,
     waitable: winnt::HANDLE,
+    /// Whether `wait_for_present` already waited on `waitable` for the next
+    /// acquire, which mustn't wait on it again.
+    waited: bool,
     acquired_count: usize,
     present_mode: wgt::PresentMode,
     format: wgt::TextureFormat,
     size: wgt::Extent3d,
+    /// `GetLastPresentCount` when the swap chain was configured, since
+    /// `ResizeBuffers` doesn't reset the count of presents.
+    base_present_count: u32,
+    /// The last present count reported by `presentation_timings`.
+    reported_present_count: u32,
+    /// Whether the swap chain is in exclusive fullscreen, which DXGI requires
+    /// to be left before the swap chain is released.
+    exclusive: bool,
+    /// The dirty rectangles of the next present.
+    next_damage: Vec<windef::RECT>,
 }
 
 enum SurfaceTarget {
@@ -215,6 +233,7 @@ pub struct Adapter {
     #[allow(unused)]
     workarounds: Workarounds,
     dxc_container: Option<Arc<shader_compilation::DxcContainer>>,
+    supports_allow_tearing: bool,
 }
 
 unsafe impl Send for Adapter {}
@@ -277,12 +296,15 @@ unsafe impl Sync for Queue {}
 struct Temp {
     marker: Vec<u16>,
     barriers: Vec<d3d12_ty::D3D12_RESOURCE_BARRIER>,
+    /// Buffers holding fill patterns, which have to outlive the command list.
+    fill_patterns: Vec<d3d12::Resource>,
 }
 
 impl Temp {
     fn clear(&mut self) {
         self.marker.clear();
         self.barriers.clear();
+        self.fill_patterns.clear();
     }
 }
 
@@ -326,6 +348,10 @@ struct PassState {
     dirty_root_elements: u64,
     vertex_buffers: [d3d12_ty::D3D12_VERTEX_BUFFER_VIEW; crate::MAX_VERTEX_BUFFERS],
     dirty_vertex_buffers: usize,
+    /// All viewports and scissor rectangles are set at once, as `RSSetViewports`
+    /// and `RSSetScissorRects` unbind the ones they are not given.
+    viewports: [d3d12_ty::D3D12_VIEWPORT; wgt::MAX_VIEWPORTS as usize],
+    scissor_rects: [d3d12_ty::D3D12_RECT; wgt::MAX_VIEWPORTS as usize],
     kind: PassKind,
 }
 
@@ -350,6 +376,8 @@ impl PassState {
             dirty_root_elements: 0,
             vertex_buffers: [unsafe { mem::zeroed() }; crate::MAX_VERTEX_BUFFERS],
             dirty_vertex_buffers: 0,
+            viewports: [unsafe { mem::zeroed() }; wgt::MAX_VIEWPORTS as usize],
+            scissor_rects: [unsafe { mem::zeroed() }; wgt::MAX_VIEWPORTS as usize],
             kind: PassKind::Transfer,
         }
     }
@@ -390,6 +418,7 @@ impl fmt::Debug for CommandEncoder {
 #[derive(Debug)]
 pub struct CommandBuffer {
     raw: d3d12::GraphicsCommandList,
+    fill_patterns: Vec<d3d12::Resource>,
 }
 
 unsafe impl Send for CommandBuffer {}
@@ -400,11 +429,23 @@ pub struct Buffer {
     resource: d3d12::Resource,
     size: wgt::BufferAddress,
     allocation: Option<suballocation::AllocationWrapper>,
+    /// Size of the shared heap of the buffer, if it was created exportable or
+    /// imported.
+    shared_size: Option<wgt::BufferAddress>,
 }
 
 unsafe impl Send for Buffer {}
 unsafe impl Sync for Buffer {}
 
+impl Buffer {
+    /// The handle type the buffer can be exported through, if it was created
+    /// exportable or imported.
+    pub fn external_memory_handle_type(&self) -> Option<wgt::ExternalMemoryHandleType> {
+        self.shared_size
+            .map(|_| wgt::ExternalMemoryHandleType::D3D12Resource)
+    }
+}
+
 impl crate::BufferBinding<'_, Api> {
     fn resolve_size(&self) -> wgt::BufferAddress {
         match self.size {
@@ -427,12 +468,22 @@ pub struct Texture {
     mip_level_count: u32,
     sample_count: u32,
     allocation: Option<suballocation::AllocationWrapper>,
+    /// Size of the shared heap of the texture, if it was created exportable or
+    /// imported.
+    shared_size: Option<wgt::BufferAddress>,
 }
 
 unsafe impl Send for Texture {}
 unsafe impl Sync for Texture {}
 
 impl Texture {
+    /// The handle type the texture can be exported through, if it was created
+    /// exportable or imported.
+    pub fn external_memory_handle_type(&self) -> Option<wgt::ExternalMemoryHandleType> {
+        self.shared_size
+            .map(|_| wgt::ExternalMemoryHandleType::D3D12Resource)
+    }
+
     fn array_layer_count(&self) -> u32 {
         match self.dimension {
             wgt::TextureDimension::D1 | wgt::TextureDimension::D3 => 1,
@@ -589,6 +640,19 @@ impl CompiledShader {
         }
     }
 
+    /// Returns the file extension and bytes of the DXIL or DXBC of the shader.
+    fn code(&self) -> (&'static str, &[u8]) {
+        match *self {
+            CompiledShader::Dxc(ref shader) => ("dxil", shader),
+            CompiledShader::Fxc(ref shader) => ("dxbc", unsafe {
+                std::slice::from_raw_parts(
+                    shader.GetBufferPointer() as *const u8,
+                    shader.GetBufferSize(),
+                )
+            }),
+        }
+    }
+
     unsafe fn destroy(self) {}
 }
 
@@ -617,6 +681,13 @@ pub struct AccelerationStructure {}
 
 impl SwapChain {
     unsafe fn release_resources(self) -> d3d12::ComPtr<dxgi1_4::IDXGISwapChain3> {
+        if self.exclusive {
+            if let Err(err) =
+                unsafe { self.raw.SetFullscreenState(0, ptr::null_mut()) }.into_result()
+            {
+                log::warn!("Unable to leave exclusive fullscreen: {}", err);
+            }
+        }
         self.raw
     }
 
@@ -658,6 +729,11 @@ impl crate::Surface for Surface {
         if self.supports_allow_tearing {
             flags |= dxgi::DXGI_SWAP_CHAIN_FLAG_ALLOW_TEARING;
         }
+        // Lets exclusive fullscreen change the mode of the display, instead of
+        // stretching the swap chain over the current one.
+        if let SurfaceTarget::WndHandle(_) = self.target {
+            flags |= dxgi::DXGI_SWAP_CHAIN_FLAG_ALLOW_MODE_SWITCH;
+        }
 
         // While `configure`s contract ensures that no work on the GPU's main queues
         // are in flight, we still need to wait for the present queue to be idle.
@@ -669,10 +745,10 @@ impl crate::Surface for Surface {
         // https://learn.microsoft.com/en-us/windows/win32/api/dxgi/nf-dxgi-idxgidevice1-setmaximumframelatency
         debug_assert!(config.maximum_frame_latency <= 15);
 
-        // Nvidia recommends to use 1-2 more buffers than the maximum latency
-        // https://developer.nvidia.com/blog/advanced-api-performance-swap-chains/
-        // For high latency extra buffers seems excessive, so go with a minimum of 3 and beyond that add 1.
-        let swap_chain_buffer = (config.maximum_frame_latency + 1).min(16);
+        // Flip model swap chains have 2 to 16 buffers.
+        // https://learn.microsoft.com/en-us/windows/win32/api/dxgi/ns-dxgi-dxgi_swap_chain_desc1
+        debug_assert!((2..=16).contains(&config.image_count));
+        let swap_chain_buffer = config.image_count;
 
         let swap_chain = match self.swap_chain.write().take() {
             //Note: this path doesn't properly re-initialize all of the things
@@ -811,6 +887,80 @@ impl crate::Surface for Surface {
             | SurfaceTarget::SwapChainPanel(_) => {}
         }
 
+        // 16 bit float swap chains show linear scRGB, whose values beyond 1.0
+        // are brighter than SDR white, and HDR10 ones the PQ encoded values.
+        let color_space = match (config.format, config.color_space) {
+            (wgt::TextureFormat::Rgba16Float, wgt::ColorSpace::Srgb) => {
+                dxgicommon::DXGI_COLOR_SPACE_RGB_FULL_G10_NONE_P709
+            }
+            (_, wgt::ColorSpace::Hdr10) => dxgicommon::DXGI_COLOR_SPACE_RGB_FULL_G2084_NONE_P2020,
+            _ => dxgicommon::DXGI_COLOR_SPACE_RGB_FULL_G22_NONE_P709,
+        };
+        let mut support = 0;
+        let supported = unsafe { swap_chain.CheckColorSpaceSupport(color_space, &mut support) }
+            .into_result()
+            .is_ok()
+            && support & dxgi1_4::DXGI_SWAP_CHAIN_COLOR_SPACE_SUPPORT_FLAG_PRESENT != 0;
+        if !supported {
+            log::error!(
+                "Color space {:?} is not supported by the display, is HDR enabled?",
+                config.color_space
+            );
+            return Err(crate::SurfaceError::Other("unsupported color space"));
+        }
+        if let Err(err) = unsafe { swap_chain.SetColorSpace1(color_space) }.into_result() {
+            log::error!("SetColorSpace1 failed: {}", err);
+            return Err(crate::SurfaceError::Other(
+                "IDXGISwapChain3::SetColorSpace1",
+            ));
+        }
+
+        // Flip model swap chains covering their display are already presented
+        // without composition when nothing shows over them, so borderless
+        // fullscreen is up to the window alone.
+        let exclusive = config.fullscreen == wgt::FullscreenMode::Exclusive;
+        if exclusive {
+            if let Some(mode) = config.display_mode {
+                let mode_desc = dxgitype::DXGI_MODE_DESC {
+                    Width: mode.width,
+                    Height: mode.height,
+                    RefreshRate: dxgitype::DXGI_RATIONAL {
+                        Numerator: mode.refresh_rate_millihertz,
+                        Denominator: 1000,
+                    },
+                    Format: non_srgb_format,
+                    ScanlineOrdering: dxgitype::DXGI_MODE_SCANLINE_ORDER_UNSPECIFIED,
+                    Scaling: dxgitype::DXGI_MODE_SCALING_UNSPECIFIED,
+                };
+                if let Err(err) = unsafe { swap_chain.ResizeTarget(&mode_desc) }.into_result() {
+                    log::error!("ResizeTarget failed: {}", err);
+                    return Err(crate::SurfaceError::Other("IDXGISwapChain::ResizeTarget"));
+                }
+            }
+            // Fails while the window isn't in the foreground.
+            if let Err(err) =
+                unsafe { swap_chain.SetFullscreenState(1, ptr::null_mut()) }.into_result()
+            {
+                log::error!("SetFullscreenState failed: {}", err);
+                return Err(crate::SurfaceError::Other("exclusive fullscreen"));
+            }
+            // DXGI requires the buffers to be resized after every transition.
+            let result = unsafe {
+                swap_chain.ResizeBuffers(
+                    swap_chain_buffer,
+                    config.extent.width,
+                    config.extent.height,
+                    non_srgb_format,
+                    flags,
+                )
+            };
+            if let Err(err) = result.into_result() {
+                log::error!("ResizeBuffers failed: {}", err);
+                unsafe { swap_chain.SetFullscreenState(0, ptr::null_mut()) };
+                return Err(crate::SurfaceError::Other("window is in use"));
+            }
+        }
+
         unsafe { swap_chain.SetMaximumFrameLatency(config.maximum_frame_latency) };
         let waitable = unsafe { swap_chain.GetFrameLatencyWaitableObject() };
 
@@ -823,15 +973,27 @@ impl crate::Surface for Surface {
             resources.push(resource);
         }
 
+        let mut base_present_count = 0;
+        if let Err(err) =
+            unsafe { swap_chain.GetLastPresentCount(&mut base_present_count) }.into_result()
+        {
+            log::warn!("GetLastPresentCount failed: {}", err);
+        }
+
         let mut swapchain = self.swap_chain.write();
         *swapchain = Some(SwapChain {
             raw: swap_chain,
             resources,
             waitable,
+            waited: false,
             acquired_count: 0,
             present_mode: config.present_mode,
             format: config.format,
             size: config.extent,
+            base_present_count,
+            reported_present_count: base_present_count,
+            exclusive,
+            next_damage: Vec::new(),
         });
 
         Ok(())
@@ -861,7 +1023,9 @@ impl crate::Surface for Surface {
         let mut swapchain = self.swap_chain.write();
         let sc = swapchain.as_mut().unwrap();
 
-        unsafe { sc.wait(timeout) }?;
+        if !std::mem::take(&mut sc.waited) {
+            unsafe { sc.wait(timeout) }?;
+        }
 
         let base_index = unsafe { sc.raw.GetCurrentBackBufferIndex() } as usize;
         let index = (base_index + sc.acquired_count) % sc.resources.len();
@@ -875,6 +1039,7 @@ impl crate::Surface for Surface {
             mip_level_count: 1,
             sample_count: 1,
             allocation: None,
+            shared_size: None,
         };
         Ok(Some(crate::AcquiredSurfaceTexture {
             texture,
@@ -886,6 +1051,130 @@ impl crate::Surface for Surface {
         let sc = swapchain.as_mut().unwrap();
         sc.acquired_count -= 1;
     }
+
+    unsafe fn set_hdr_metadata(
+        &self,
+        metadata: &wgt::HdrMetadata,
+    ) -> Result<(), crate::SurfaceError> {
+        let swapchain = self.swap_chain.read();
+        let sc = swapchain.as_ref().unwrap();
+        let swap_chain4 = match unsafe { sc.raw.cast::<dxgi1_5::IDXGISwapChain4>() }.into_result() {
+            Ok(swap_chain4) => swap_chain4,
+            Err(err) => {
+                log::error!("Unable to cast swap chain: {}", err);
+                return Err(crate::SurfaceError::Other("swap chain cast to 4"));
+            }
+        };
+
+        // Chromaticities are in units of 0.00002, the minimum luminance in
+        // units of 0.0001 nits.
+        let xy = |[x, y]: [f32; 2]| [(x * 50000.0) as u16, (y * 50000.0) as u16];
+        let mut raw_metadata = dxgi1_5::DXGI_HDR_METADATA_HDR10 {
+            RedPrimary: xy(metadata.red_primary),
+            GreenPrimary: xy(metadata.green_primary),
+            BluePrimary: xy(metadata.blue_primary),
+            WhitePoint: xy(metadata.white_point),
+            MaxMasteringLuminance: metadata.max_luminance as u32,
+            MinMasteringLuminance: (metadata.min_luminance * 10000.0) as u32,
+            MaxContentLightLevel: metadata.max_content_light_level as u16,
+            MaxFrameAverageLightLevel: metadata.max_frame_average_light_level as u16,
+        };
+        let result = unsafe {
+            swap_chain4.SetHDRMetaData(
+                dxgi1_5::DXGI_HDR_METADATA_TYPE_HDR10,
+                mem::size_of_val(&raw_metadata) as u32,
+                <*mut _>::cast(&mut raw_metadata),
+            )
+        };
+        if let Err(err) = result.into_result() {
+            log::error!("SetHDRMetaData failed: {}", err);
+            return Err(crate::SurfaceError::Other(
+                "IDXGISwapChain4::SetHDRMetaData",
+            ));
+        }
+        Ok(())
+    }
+
+    unsafe fn set_next_present_time(&self, _time: wgt::PresentationTimestamp) -> bool {
+        false
+    }
+
+    unsafe fn set_next_present_damage(&self, damage: &[wgt::DamageRect]) -> bool {
+        let mut swapchain = self.swap_chain.write();
+        let sc = swapchain.as_mut().unwrap();
+        sc.next_damage.clear();
+        sc.next_damage
+            .extend(damage.iter().map(|rect| windef::RECT {
+                left: rect.x as i32,
+                top: rect.y as i32,
+                right: (rect.x + rect.width) as i32,
+                bottom: (rect.y + rect.height) as i32,
+            }));
+        true
+    }
+
+    unsafe fn presentation_timings(&self) -> Vec<wgt::PresentationTiming> {
+        let mut swapchain = self.swap_chain.write();
+        let sc = swapchain.as_mut().unwrap();
+
+        // DXGI only keeps the statistics of the last vertical blank, which
+        // covers the last present that reached the screen.
+        let mut stats: dxgi::DXGI_FRAME_STATISTICS = unsafe { mem::zeroed() };
+        if let Err(err) = unsafe { sc.raw.GetFrameStatistics(&mut stats) }.into_result() {
+            log::debug!("GetFrameStatistics failed: {}", err);
+            return Vec::new();
+        }
+        if stats.PresentCount.wrapping_sub(sc.reported_present_count) as i32 <= 0 {
+            return Vec::new();
+        }
+        sc.reported_present_count = stats.PresentCount;
+
+        let timer = auxil::dxgi::time::PresentationTimer::new_dxgi();
+        let Some(actual_present_time) =
+            timer.qpc_to_ns(unsafe { *stats.SyncQPCTime.QuadPart() } as u64)
+        else {
+            return Vec::new();
+        };
+        vec![wgt::PresentationTiming {
+            present_id: stats
+                .PresentCount
+                .wrapping_sub(sc.base_present_count)
+                .into(),
+            desired_present_time: None,
+            actual_present_time: wgt::PresentationTimestamp(actual_present_time),
+        }]
+    }
+
+    unsafe fn refresh_duration(&self) -> Option<std::time::Duration> {
+        None
+    }
+
+    unsafe fn image_count(&self) -> u32 {
+        let swap_chain = self.swap_chain.read();
+        swap_chain.as_ref().unwrap().resources.len() as u32
+    }
+
+    unsafe fn wait_for_present(
+        &self,
+        timeout: Option<std::time::Duration>,
+    ) -> Result<bool, crate::SurfaceError> {
+        let mut swap_chain = self.swap_chain.write();
+        let sc = swap_chain.as_mut().unwrap();
+        if !sc.waited {
+            sc.waited = unsafe { sc.wait(timeout) }?;
+        }
+        Ok(sc.waited)
+    }
+
+    unsafe fn set_maximum_frame_latency(&self, maximum_frame_latency: u32) {
+        let swap_chain = self.swap_chain.read();
+        let sc = swap_chain.as_ref().unwrap();
+        if let Err(err) =
+            unsafe { sc.raw.SetMaximumFrameLatency(maximum_frame_latency) }.into_result()
+        {
+            log::warn!("SetMaximumFrameLatency failed: {}", err);
+        }
+    }
 }
 
 impl crate::Queue for Queue {
@@ -920,6 +1209,32 @@ impl crate::Queue for Queue {
 
         Ok(())
     }
+    unsafe fn wait_for_fence(
+        &self,
+        fence: &Fence,
+        value: crate::FenceValue,
+    ) -> Result<(), crate::DeviceError> {
+        profiling::scope!("ID3D12CommandQueue::Wait");
+        unsafe { self.raw.Wait(fence.raw.as_mut_ptr(), value) }.into_device_result("Wait for fence")
+    }
+    unsafe fn bind_sparse_buffer(
+        &self,
+        _device: &Device,
+        _buffer: &Buffer,
+        _binds: &[wgt::SparseBufferBind],
+        _signal_fence: (&mut Fence, crate::FenceValue),
+    ) -> Result<(), crate::DeviceError> {
+        unimplemented!()
+    }
+    unsafe fn bind_sparse_texture(
+        &self,
+        _device: &Device,
+        _texture: &Texture,
+        _binds: &[wgt::SparseTextureBind],
+        _signal_fence: (&mut Fence, crate::FenceValue),
+    ) -> Result<(), crate::DeviceError> {
+        unimplemented!()
+    }
     unsafe fn present(
         &self,
         surface: &Surface,
@@ -937,8 +1252,21 @@ impl crate::Queue for Queue {
             m => unreachable!("Cannot make surface with present mode {m:?}"),
         };
 
-        profiling::scope!("IDXGISwapchain3::Present");
-        unsafe { sc.raw.Present(interval, flags) };
+        if sc.next_damage.is_empty() {
+            profiling::scope!("IDXGISwapchain3::Present");
+            unsafe { sc.raw.Present(interval, flags) };
+        } else {
+            // Dirty rectangles let DWM only recompose the regions that changed.
+            let parameters = dxgi1_2::DXGI_PRESENT_PARAMETERS {
+                DirtyRectsCount: sc.next_damage.len() as u32,
+                pDirtyRects: sc.next_damage.as_mut_ptr(),
+                pScrollRect: ptr::null_mut(),
+                pScrollOffset: ptr::null_mut(),
+            };
+            profiling::scope!("IDXGISwapchain3::Present1");
+            unsafe { sc.raw.Present1(interval, flags, &parameters) };
+            sc.next_damage.clear();
+        }
 
         Ok(())
     }
@@ -948,6 +1276,27 @@ impl crate::Queue for Queue {
         unsafe { self.raw.GetTimestampFrequency(&mut frequency) };
         (1_000_000_000.0 / frequency as f64) as f32
     }
+
+    unsafe fn get_timestamp_calibration(&self) -> Option<wgt::TimestampCalibration> {
+        let mut gpu_timestamp = 0u64;
+        let mut cpu_ticks = 0u64;
+        let hr = unsafe {
+            self.raw
+                .GetClockCalibration(&mut gpu_timestamp, &mut cpu_ticks)
+        };
+        if let Err(err) = hr.into_result() {
+            log::error!("GetClockCalibration failed: {err}");
+            return None;
+        }
+        // The CPU time is a value of QueryPerformanceCounter, like presentation timestamps.
+        let mut frequency: winapi::um::winnt::LARGE_INTEGER = unsafe { mem::zeroed() };
+        unsafe { winapi::um::profileapi::QueryPerformanceFrequency(&mut frequency) };
+        let frequency = unsafe { *frequency.QuadPart() } as u128;
+        Some(wgt::TimestampCalibration {
+            gpu_timestamp,
+            cpu_timestamp: (cpu_ticks as u128 * 1_000_000_000 / frequency) as u64,
+        })
+    }
 }
 
 /// A shorthand for producing a `ResourceCreationFailed` error if a ComPtr is null.
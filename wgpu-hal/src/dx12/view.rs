@@ -14,6 +14,7 @@ pub(super) struct ViewDescriptor {
     array_layer_count: u32,
     mip_level_base: u32,
     mip_level_count: u32,
+    swizzle: wgt::TextureComponentSwizzle,
 }
 
 impl crate::TextureViewDescriptor<'_> {
@@ -30,10 +31,39 @@ impl crate::TextureViewDescriptor<'_> {
             mip_level_count: self.range.mip_level_count.unwrap_or(!0),
             array_layer_base: self.range.base_array_layer,
             array_layer_count: self.range.array_layer_count.unwrap_or(!0),
+            swizzle: self.swizzle,
         }
     }
 }
 
+fn map_swizzle(swizzle: wgt::TextureComponentSwizzle) -> u32 {
+    fn map_component(component: wgt::ComponentSwizzle) -> u32 {
+        match component {
+            wgt::ComponentSwizzle::R => {
+                d3d12_ty::D3D12_SHADER_COMPONENT_MAPPING_FROM_MEMORY_COMPONENT_0
+            }
+            wgt::ComponentSwizzle::G => {
+                d3d12_ty::D3D12_SHADER_COMPONENT_MAPPING_FROM_MEMORY_COMPONENT_1
+            }
+            wgt::ComponentSwizzle::B => {
+                d3d12_ty::D3D12_SHADER_COMPONENT_MAPPING_FROM_MEMORY_COMPONENT_2
+            }
+            wgt::ComponentSwizzle::A => {
+                d3d12_ty::D3D12_SHADER_COMPONENT_MAPPING_FROM_MEMORY_COMPONENT_3
+            }
+            wgt::ComponentSwizzle::Zero => d3d12_ty::D3D12_SHADER_COMPONENT_MAPPING_FORCE_VALUE_0,
+            wgt::ComponentSwizzle::One => d3d12_ty::D3D12_SHADER_COMPONENT_MAPPING_FORCE_VALUE_1,
+        }
+    }
+
+    // D3D12_ENCODE_SHADER_4_COMPONENT_MAPPING
+    map_component(swizzle.r)
+        | map_component(swizzle.g) << 3
+        | map_component(swizzle.b) << 6
+        | map_component(swizzle.a) << 9
+        | 1 << 12
+}
+
 fn aspects_to_plane(aspects: crate::FormatAspects) -> u32 {
     match aspects {
         crate::FormatAspects::STENCIL => 1,
@@ -48,7 +78,7 @@ impl ViewDescriptor {
         let mut desc = d3d12_ty::D3D12_SHADER_RESOURCE_VIEW_DESC {
             Format: self.srv_uav_format?,
             ViewDimension: 0,
-            Shader4ComponentMapping: D3D12_DEFAULT_SHADER_4_COMPONENT_MAPPING,
+            Shader4ComponentMapping: map_swizzle(self.swizzle),
             u: unsafe { mem::zeroed() },
         };
 
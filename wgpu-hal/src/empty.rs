@@ -1,6 +1,6 @@
 #![allow(unused_variables)]
 
-use std::ops::Range;
+use std::{cell::UnsafeCell, ops::Range, ptr::NonNull};
 
 #[derive(Clone, Debug)]
 pub struct Api;
@@ -10,6 +10,23 @@ pub struct Encoder;
 #[derive(Debug)]
 pub struct Resource;
 
+/// Buffers that can be mapped are backed by host memory, so the contents
+/// written by mapping are there when mapping them again.
+#[derive(Debug)]
+pub struct Buffer {
+    storage: Box<[UnsafeCell<u8>]>,
+}
+
+// The storage is only accessed through mappings, synchronized by wgpu-core.
+unsafe impl Sync for Buffer {}
+
+/// Submissions complete immediately, so a fence just stores the last value it
+/// was signaled with.
+#[derive(Debug)]
+pub struct Fence {
+    value: crate::FenceValue,
+}
+
 type DeviceResult<T> = Result<T, crate::DeviceError>;
 
 impl crate::Api for Api {
@@ -22,13 +39,14 @@ impl crate::Api for Api {
     type CommandEncoder = Encoder;
     type CommandBuffer = Resource;
 
-    type Buffer = Resource;
+    type Buffer = Buffer;
     type Texture = Resource;
     type SurfaceTexture = Resource;
     type TextureView = Resource;
     type Sampler = Resource;
     type QuerySet = Resource;
-    type Fence = Resource;
+    type MemoryHeap = Resource;
+    type Fence = Fence;
     type AccelerationStructure = Resource;
 
     type BindGroupLayout = Resource;
@@ -54,7 +72,27 @@ impl crate::Instance for Context {
     }
     unsafe fn destroy_surface(&self, surface: Context) {}
     unsafe fn enumerate_adapters(&self) -> Vec<crate::ExposedAdapter<Api>> {
-        Vec::new()
+        vec![crate::ExposedAdapter {
+            adapter: Context,
+            info: wgt::AdapterInfo {
+                name: String::from("Noop"),
+                vendor: 0,
+                device: 0,
+                device_type: wgt::DeviceType::Cpu,
+                driver: String::from("wgpu"),
+                driver_info: String::new(),
+                backend: wgt::Backend::Empty,
+            },
+            features: wgt::Features::empty(),
+            capabilities: crate::Capabilities {
+                limits: wgt::Limits::default(),
+                alignments: crate::Alignments {
+                    buffer_copy_offset: wgt::BufferSize::new(4).unwrap(),
+                    buffer_copy_pitch: wgt::BufferSize::new(256).unwrap(),
+                },
+                downlevel: wgt::DownlevelCapabilities::default(),
+            },
+        }]
     }
 }
 
@@ -74,11 +112,39 @@ impl crate::Surface for Context {
     unsafe fn acquire_texture(
         &self,
         timeout: Option<std::time::Duration>,
-        fence: &Resource,
+        fence: &Fence,
     ) -> Result<Option<crate::AcquiredSurfaceTexture<Api>>, crate::SurfaceError> {
         Ok(None)
     }
     unsafe fn discard_texture(&self, texture: Resource) {}
+    unsafe fn set_hdr_metadata(
+        &self,
+        metadata: &wgt::HdrMetadata,
+    ) -> Result<(), crate::SurfaceError> {
+        Ok(())
+    }
+    unsafe fn set_next_present_time(&self, time: wgt::PresentationTimestamp) -> bool {
+        false
+    }
+    unsafe fn set_next_present_damage(&self, damage: &[wgt::DamageRect]) -> bool {
+        false
+    }
+    unsafe fn presentation_timings(&self) -> Vec<wgt::PresentationTiming> {
+        Vec::new()
+    }
+    unsafe fn refresh_duration(&self) -> Option<std::time::Duration> {
+        None
+    }
+    unsafe fn image_count(&self) -> u32 {
+        0
+    }
+    unsafe fn wait_for_present(
+        &self,
+        timeout: Option<std::time::Duration>,
+    ) -> Result<bool, crate::SurfaceError> {
+        Ok(true)
+    }
+    unsafe fn set_maximum_frame_latency(&self, maximum_frame_latency: u32) {}
 }
 
 impl crate::Adapter for Context {
@@ -88,19 +154,32 @@ impl crate::Adapter for Context {
         &self,
         features: wgt::Features,
         _limits: &wgt::Limits,
+        _queue_priority: wgt::QueuePriority,
+        _transfer_queue: bool,
     ) -> DeviceResult<crate::OpenDevice<Api>> {
-        Err(crate::DeviceError::Lost)
+        Ok(crate::OpenDevice {
+            device: Context,
+            queue: Context,
+            additional_queues: Vec::new(),
+            transfer_queue: None,
+        })
     }
     unsafe fn texture_format_capabilities(
         &self,
         format: wgt::TextureFormat,
     ) -> crate::TextureFormatCapabilities {
-        crate::TextureFormatCapabilities::empty()
+        crate::TextureFormatCapabilities::all()
     }
 
     unsafe fn surface_capabilities(&self, surface: &Context) -> Option<crate::SurfaceCapabilities> {
         None
     }
+    unsafe fn headless_surface_capabilities(
+        &self,
+        display_handle: raw_window_handle::RawDisplayHandle,
+    ) -> Option<crate::SurfaceCapabilities> {
+        None
+    }
 
     unsafe fn get_presentation_timestamp(&self) -> wgt::PresentationTimestamp {
         wgt::PresentationTimestamp::INVALID_TIMESTAMP
@@ -114,8 +193,32 @@ impl crate::Queue for Context {
         &self,
         command_buffers: &[&Resource],
         surface_textures: &[&Resource],
-        signal_fence: (&mut Resource, crate::FenceValue),
+        signal_fence: (&mut Fence, crate::FenceValue),
     ) -> DeviceResult<()> {
+        signal_fence.0.value = signal_fence.1;
+        Ok(())
+    }
+    unsafe fn wait_for_fence(&self, fence: &Fence, value: crate::FenceValue) -> DeviceResult<()> {
+        Ok(())
+    }
+    unsafe fn bind_sparse_buffer(
+        &self,
+        device: &Context,
+        buffer: &Buffer,
+        binds: &[wgt::SparseBufferBind],
+        signal_fence: (&mut Fence, crate::FenceValue),
+    ) -> DeviceResult<()> {
+        signal_fence.0.value = signal_fence.1;
+        Ok(())
+    }
+    unsafe fn bind_sparse_texture(
+        &self,
+        device: &Context,
+        texture: &Resource,
+        binds: &[wgt::SparseTextureBind],
+        signal_fence: (&mut Fence, crate::FenceValue),
+    ) -> DeviceResult<()> {
+        signal_fence.0.value = signal_fence.1;
         Ok(())
     }
     unsafe fn present(
@@ -129,33 +232,95 @@ impl crate::Queue for Context {
     unsafe fn get_timestamp_period(&self) -> f32 {
         1.0
     }
+
+    unsafe fn get_timestamp_calibration(&self) -> Option<wgt::TimestampCalibration> {
+        None
+    }
 }
 
 impl crate::Device for Context {
     type A = Api;
 
     unsafe fn exit(self, queue: Context) {}
-    unsafe fn create_buffer(&self, desc: &crate::BufferDescriptor) -> DeviceResult<Resource> {
-        Ok(Resource)
-    }
-    unsafe fn destroy_buffer(&self, buffer: Resource) {}
+    unsafe fn destroy_queue(&self, queue: Context) {}
+    unsafe fn create_buffer(&self, desc: &crate::BufferDescriptor) -> DeviceResult<Buffer> {
+        let mappable = desc
+            .usage
+            .intersects(crate::BufferUses::MAP_READ | crate::BufferUses::MAP_WRITE);
+        let size = if mappable { desc.size as usize } else { 0 };
+        Ok(Buffer {
+            storage: (0..size).map(|_| UnsafeCell::new(0)).collect(),
+        })
+    }
+    unsafe fn destroy_buffer(&self, buffer: Buffer) {}
     unsafe fn map_buffer(
         &self,
-        buffer: &Resource,
+        buffer: &Buffer,
         range: crate::MemoryRange,
     ) -> DeviceResult<crate::BufferMapping> {
-        Err(crate::DeviceError::Lost)
+        let ptr = buffer.storage[range.start as usize..].as_ptr();
+        Ok(crate::BufferMapping {
+            ptr: NonNull::new(UnsafeCell::raw_get(ptr)).unwrap(),
+            is_coherent: true,
+        })
     }
-    unsafe fn unmap_buffer(&self, buffer: &Resource) -> DeviceResult<()> {
+    unsafe fn unmap_buffer(&self, buffer: &Buffer) -> DeviceResult<()> {
         Ok(())
     }
-    unsafe fn flush_mapped_ranges<I>(&self, buffer: &Resource, ranges: I) {}
-    unsafe fn invalidate_mapped_ranges<I>(&self, buffer: &Resource, ranges: I) {}
+    unsafe fn flush_mapped_ranges<I>(&self, buffer: &Buffer, ranges: I) {}
+    unsafe fn invalidate_mapped_ranges<I>(&self, buffer: &Buffer, ranges: I) {}
+    unsafe fn get_buffer_device_address(&self, buffer: &Buffer) -> wgt::BufferAddress {
+        Default::default()
+    }
 
     unsafe fn create_texture(&self, desc: &crate::TextureDescriptor) -> DeviceResult<Resource> {
         Ok(Resource)
     }
     unsafe fn destroy_texture(&self, texture: Resource) {}
+    unsafe fn get_sparse_texture_tiles(&self, texture: &Resource) -> wgt::SparseTextureTiles {
+        wgt::SparseTextureTiles {
+            tile_size: wgt::Extent3d::default(),
+            mip_tail_first_level: 0,
+        }
+    }
+    unsafe fn create_memory_heap(
+        &self,
+        desc: &crate::MemoryHeapDescriptor,
+    ) -> DeviceResult<Resource> {
+        Ok(Resource)
+    }
+    unsafe fn destroy_memory_heap(&self, heap: Resource) {}
+    unsafe fn get_buffer_memory_requirements(
+        &self,
+        desc: &crate::BufferDescriptor,
+    ) -> DeviceResult<wgt::MemoryRequirements> {
+        Ok(wgt::MemoryRequirements {
+            size: desc.size,
+            alignment: 1,
+        })
+    }
+    unsafe fn get_texture_memory_requirements(
+        &self,
+        desc: &crate::TextureDescriptor,
+    ) -> DeviceResult<wgt::MemoryRequirements> {
+        Ok(wgt::MemoryRequirements::default())
+    }
+    unsafe fn create_placed_buffer(
+        &self,
+        desc: &crate::BufferDescriptor,
+        heap: &Resource,
+        offset: wgt::BufferAddress,
+    ) -> DeviceResult<Buffer> {
+        unsafe { self.create_buffer(desc) }
+    }
+    unsafe fn create_placed_texture(
+        &self,
+        desc: &crate::TextureDescriptor,
+        heap: &Resource,
+        offset: wgt::BufferAddress,
+    ) -> DeviceResult<Resource> {
+        Ok(Resource)
+    }
     unsafe fn create_texture_view(
         &self,
         texture: &Resource,
@@ -198,6 +363,7 @@ impl crate::Device for Context {
         Ok(Resource)
     }
     unsafe fn destroy_bind_group(&self, group: Resource) {}
+    unsafe fn recycle_bind_groups(&self) {}
 
     unsafe fn create_shader_module(
         &self,
@@ -229,26 +395,31 @@ impl crate::Device for Context {
         Ok(Resource)
     }
     unsafe fn destroy_query_set(&self, set: Resource) {}
-    unsafe fn create_fence(&self) -> DeviceResult<Resource> {
-        Ok(Resource)
+    unsafe fn create_fence(&self) -> DeviceResult<Fence> {
+        Ok(Fence { value: 0 })
     }
-    unsafe fn destroy_fence(&self, fence: Resource) {}
-    unsafe fn get_fence_value(&self, fence: &Resource) -> DeviceResult<crate::FenceValue> {
-        Ok(0)
+    unsafe fn destroy_fence(&self, fence: Fence) {}
+    unsafe fn get_fence_value(&self, fence: &Fence) -> DeviceResult<crate::FenceValue> {
+        Ok(fence.value)
     }
     unsafe fn wait(
         &self,
-        fence: &Resource,
+        fence: &Fence,
         value: crate::FenceValue,
         timeout_ms: u32,
     ) -> DeviceResult<bool> {
         Ok(true)
     }
+    unsafe fn signal_fence(&self, fence: &mut Fence, value: crate::FenceValue) -> DeviceResult<()> {
+        fence.value = value;
+        Ok(())
+    }
 
     unsafe fn start_capture(&self) -> bool {
         false
     }
     unsafe fn stop_capture(&self) {}
+    unsafe fn set_performance_hint(&self, _hint: wgt::PerformanceHint) {}
     unsafe fn create_acceleration_structure(
         &self,
         desc: &crate::AccelerationStructureDescriptor,
@@ -294,9 +465,12 @@ impl crate::CommandEncoder for Encoder {
     {
     }
 
-    unsafe fn clear_buffer(&mut self, buffer: &Resource, range: crate::MemoryRange) {}
+    unsafe fn aliasing_barrier(&mut self) {}
 
-    unsafe fn copy_buffer_to_buffer<T>(&mut self, src: &Resource, dst: &Resource, regions: T) {}
+    unsafe fn clear_buffer(&mut self, buffer: &Buffer, range: crate::MemoryRange) {}
+    unsafe fn fill_buffer(&mut self, buffer: &Buffer, range: crate::MemoryRange, value: u32) {}
+
+    unsafe fn copy_buffer_to_buffer<T>(&mut self, src: &Buffer, dst: &Buffer, regions: T) {}
 
     #[cfg(webgl)]
     unsafe fn copy_external_image_to_texture<T>(
@@ -319,13 +493,23 @@ impl crate::CommandEncoder for Encoder {
     ) {
     }
 
-    unsafe fn copy_buffer_to_texture<T>(&mut self, src: &Resource, dst: &Resource, regions: T) {}
+    unsafe fn blit_texture(
+        &mut self,
+        src: &Resource,
+        src_usage: crate::TextureUses,
+        dst: &Resource,
+        region: crate::TextureBlit,
+        filter: wgt::FilterMode,
+    ) {
+    }
+
+    unsafe fn copy_buffer_to_texture<T>(&mut self, src: &Buffer, dst: &Resource, regions: T) {}
 
     unsafe fn copy_texture_to_buffer<T>(
         &mut self,
         src: &Resource,
         src_usage: crate::TextureUses,
-        dst: &Resource,
+        dst: &Buffer,
         regions: T,
     ) {
     }
@@ -338,7 +522,7 @@ impl crate::CommandEncoder for Encoder {
         &mut self,
         set: &Resource,
         range: Range<u32>,
-        buffer: &Resource,
+        buffer: &Buffer,
         offset: wgt::BufferAddress,
         stride: wgt::BufferSize,
     ) {
@@ -357,6 +541,14 @@ impl crate::CommandEncoder for Encoder {
         dynamic_offsets: &[wgt::DynamicOffset],
     ) {
     }
+    unsafe fn set_bind_group_inline(
+        &mut self,
+        layout: &Resource,
+        index: u32,
+        group_layout: &Resource,
+        buffers: &[crate::BufferBinding<Api>],
+    ) {
+    }
     unsafe fn set_push_constants(
         &mut self,
         layout: &Resource,
@@ -382,8 +574,21 @@ impl crate::CommandEncoder for Encoder {
     }
     unsafe fn set_viewport(&mut self, rect: &crate::Rect<f32>, depth_range: Range<f32>) {}
     unsafe fn set_scissor_rect(&mut self, rect: &crate::Rect<u32>) {}
+    unsafe fn set_indexed_viewport(
+        &mut self,
+        index: u32,
+        rect: &crate::Rect<f32>,
+        depth_range: Range<f32>,
+    ) {
+    }
+    unsafe fn set_indexed_scissor_rect(&mut self, index: u32, rect: &crate::Rect<u32>) {}
     unsafe fn set_stencil_reference(&mut self, value: u32) {}
+    unsafe fn set_stencil_reference_separate(&mut self, front: u32, back: u32) {}
+    unsafe fn set_stencil_write_mask(&mut self, front: u32, back: u32) {}
     unsafe fn set_blend_constants(&mut self, color: &[f32; 4]) {}
+    unsafe fn set_front_face(&mut self, front_face: wgt::FrontFace) {}
+    unsafe fn set_cull_mode(&mut self, cull_mode: Option<wgt::Face>) {}
+    unsafe fn set_depth_compare(&mut self, compare: wgt::CompareFunction) {}
 
     unsafe fn draw(
         &mut self,
@@ -404,32 +609,32 @@ impl crate::CommandEncoder for Encoder {
     }
     unsafe fn draw_indirect(
         &mut self,
-        buffer: &Resource,
+        buffer: &Buffer,
         offset: wgt::BufferAddress,
         draw_count: u32,
     ) {
     }
     unsafe fn draw_indexed_indirect(
         &mut self,
-        buffer: &Resource,
+        buffer: &Buffer,
         offset: wgt::BufferAddress,
         draw_count: u32,
     ) {
     }
     unsafe fn draw_indirect_count(
         &mut self,
-        buffer: &Resource,
+        buffer: &Buffer,
         offset: wgt::BufferAddress,
-        count_buffer: &Resource,
+        count_buffer: &Buffer,
         count_offset: wgt::BufferAddress,
         max_count: u32,
     ) {
     }
     unsafe fn draw_indexed_indirect_count(
         &mut self,
-        buffer: &Resource,
+        buffer: &Buffer,
         offset: wgt::BufferAddress,
-        count_buffer: &Resource,
+        count_buffer: &Buffer,
         count_offset: wgt::BufferAddress,
         max_count: u32,
     ) {
@@ -443,7 +648,7 @@ impl crate::CommandEncoder for Encoder {
     unsafe fn set_compute_pipeline(&mut self, pipeline: &Resource) {}
 
     unsafe fn dispatch(&mut self, count: [u32; 3]) {}
-    unsafe fn dispatch_indirect(&mut self, buffer: &Resource, offset: wgt::BufferAddress) {}
+    unsafe fn dispatch_indirect(&mut self, buffer: &Buffer, offset: wgt::BufferAddress) {}
 
     unsafe fn build_acceleration_structures<'a, T>(
         &mut self,
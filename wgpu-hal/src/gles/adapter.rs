@@ -207,6 +207,12 @@ impl super::Adapter {
             driver,
             driver_info,
             backend: wgt::Backend::Gl,
+            driver_version: None,
+            device_uuid: None,
+            device_luid: None,
+            memory_heaps: Vec::new(),
+            unified_memory: false,
+            resizable_bar: false,
         }
     }
 
@@ -397,6 +403,8 @@ impl super::Adapter {
             | wgt::DownlevelFlags::NON_POWER_OF_TWO_MIPMAPPED_TEXTURES
             | wgt::DownlevelFlags::CUBE_ARRAY_TEXTURES
             | wgt::DownlevelFlags::COMPARISON_SAMPLERS
+            // Always on ES 3.0 and WebGL2, and enabled by `Adapter::open` on desktop GL 3.2+.
+            | wgt::DownlevelFlags::SEAMLESS_CUBE_MAP_FILTERING
             | wgt::DownlevelFlags::VERTEX_AND_INSTANCE_INDEX_RESPECTS_RESPECTIVE_FIRST_VALUE_IN_INDIRECT_DRAW;
         downlevel_flags.set(wgt::DownlevelFlags::COMPUTE_SHADERS, supports_compute);
         downlevel_flags.set(
@@ -447,6 +455,16 @@ impl super::Adapter {
             wgt::DownlevelFlags::MULTISAMPLED_SHADING,
             supported((3, 2), (4, 0)) || extensions.contains("OES_sample_variables"),
         );
+        // `TEXTURE_LOD_BIAS` is a desktop GL sampler parameter.
+        let max_texture_lod_bias = if full_ver.is_some() {
+            unsafe { gl.get_parameter_f32(glow::MAX_TEXTURE_LOD_BIAS) }
+        } else {
+            0.0
+        };
+        downlevel_flags.set(
+            wgt::DownlevelFlags::SAMPLER_LOD_BIAS,
+            max_texture_lod_bias > 0.0,
+        );
         let query_buffers = extensions.contains("GL_ARB_query_buffer_object")
             || extensions.contains("GL_AMD_query_buffer_object");
         if query_buffers {
@@ -457,9 +475,12 @@ impl super::Adapter {
             | wgt::Features::TEXTURE_ADAPTER_SPECIFIC_FORMAT_FEATURES
             | wgt::Features::CLEAR_TEXTURE
             | wgt::Features::PUSH_CONSTANTS
-            | wgt::Features::DEPTH32FLOAT_STENCIL8;
+            | wgt::Features::DEPTH32FLOAT_STENCIL8
+            | wgt::Features::INLINE_BIND_GROUPS;
         features.set(
-            wgt::Features::ADDRESS_MODE_CLAMP_TO_BORDER | wgt::Features::ADDRESS_MODE_CLAMP_TO_ZERO,
+            wgt::Features::ADDRESS_MODE_CLAMP_TO_BORDER
+                | wgt::Features::ADDRESS_MODE_CLAMP_TO_ZERO
+                | wgt::Features::ADDRESS_MODE_CLAMP_TO_CUSTOM_BORDER,
             extensions.contains("GL_EXT_texture_border_clamp")
                 || extensions.contains("GL_ARB_texture_border_clamp"),
         );
@@ -492,6 +513,11 @@ impl super::Adapter {
             supported((3, 1), (4, 2)) || extensions.contains("GL_ARB_shader_image_load_store"),
         );
         features.set(wgt::Features::SHADER_UNUSED_VERTEX_OUTPUT, true);
+        // WebGL requires the same stencil reference and write mask for both faces.
+        features.set(
+            wgt::Features::SEPARATE_STENCIL_STATE,
+            !cfg!(any(webgl, Emscripten)),
+        );
         if extensions.contains("GL_ARB_timer_query") {
             features.set(wgt::Features::TIMESTAMP_QUERY, true);
             features.set(wgt::Features::TIMESTAMP_QUERY_INSIDE_ENCODERS, true);
@@ -574,6 +600,22 @@ impl super::Adapter {
                 || extensions.contains("OES_texture_float_linear"),
         );
 
+        // WebGL doesn't have the texture swizzle parameters.
+        features.set(
+            wgt::Features::TEXTURE_COMPONENT_SWIZZLE,
+            !cfg!(any(webgl, Emscripten))
+                && (supported((3, 0), (3, 3)) || extensions.contains("GL_ARB_texture_swizzle")),
+        );
+
+        // EGLImages are only reachable through EGL on native platforms.
+        features.set(
+            wgt::Features::GL_TEXTURE_INTEROP,
+            cfg!(all(native, not(windows))) && extensions.contains("GL_OES_EGL_image"),
+        );
+
+        // `glBlitFramebuffer` is core in GLES 3.0 and GL 3.0.
+        features.insert(wgt::Features::TEXTURE_BLIT);
+
         if es_ver.is_none() {
             features |= wgt::Features::POLYGON_MODE_LINE | wgt::Features::POLYGON_MODE_POINT;
         }
@@ -798,6 +840,7 @@ impl super::Adapter {
             max_compute_workgroups_per_dimension,
             max_buffer_size: i32::MAX as u64,
             max_non_sampler_bindings: std::u32::MAX,
+            max_additional_queues: 0,
         };
 
         let mut workarounds = super::Workarounds::empty();
@@ -825,6 +868,11 @@ impl super::Adapter {
 
         let downlevel_defaults = wgt::DownlevelLimits {};
         let max_samples = unsafe { gl.get_parameter_i32(glow::MAX_SAMPLES) };
+        let max_integer_samples = if supported((3, 1), (3, 2)) {
+            unsafe { gl.get_parameter_i32(glow::MAX_INTEGER_SAMPLES) }
+        } else {
+            0
+        };
 
         // Drop the GL guard so we can move the context into AdapterShared
         // ( on Wasm the gl handle is just a ref so we tell clippy to allow
@@ -844,6 +892,8 @@ impl super::Adapter {
                     program_cache: Default::default(),
                     es: es_ver.is_some(),
                     max_msaa_samples: max_samples,
+                    max_integer_msaa_samples: max_integer_samples,
+                    max_texture_lod_bias,
                 }),
             },
             info: Self::make_info(vendor, renderer, version),
@@ -950,10 +1000,16 @@ impl crate::Adapter for super::Adapter {
         &self,
         features: wgt::Features,
         _limits: &wgt::Limits,
+        _queue_priority: wgt::QueuePriority,
+        _transfer_queue: bool,
     ) -> Result<crate::OpenDevice<super::Api>, crate::DeviceError> {
         let gl = &self.shared.context.lock();
         unsafe { gl.pixel_store_i32(glow::UNPACK_ALIGNMENT, 1) };
         unsafe { gl.pixel_store_i32(glow::PACK_ALIGNMENT, 1) };
+        // ES and WebGL always filter cube maps seamlessly, desktop GL only when asked to.
+        if !self.shared.es {
+            unsafe { gl.enable(glow::TEXTURE_CUBE_MAP_SEAMLESS) };
+        }
         let main_vao =
             unsafe { gl.create_vertex_array() }.map_err(|_| crate::DeviceError::OutOfMemory)?;
         unsafe { gl.bind_vertex_array(Some(main_vao)) };
@@ -1001,6 +1057,8 @@ impl crate::Adapter for super::Adapter {
                 draw_buffer_count: AtomicU8::new(1),
                 current_index_buffer: Mutex::new(None),
             },
+            additional_queues: Vec::new(),
+            transfer_queue: None,
         })
     }
 
@@ -1011,23 +1069,23 @@ impl crate::Adapter for super::Adapter {
         use crate::TextureFormatCapabilities as Tfc;
         use wgt::TextureFormat as Tf;
 
-        let sample_count = {
-            let max_samples = self.shared.max_msaa_samples;
-            if max_samples >= 16 {
-                Tfc::MULTISAMPLE_X2
-                    | Tfc::MULTISAMPLE_X4
-                    | Tfc::MULTISAMPLE_X8
-                    | Tfc::MULTISAMPLE_X16
-            } else if max_samples >= 8 {
-                Tfc::MULTISAMPLE_X2 | Tfc::MULTISAMPLE_X4 | Tfc::MULTISAMPLE_X8
-            } else {
-                // The lowest supported level in GLE3.0/WebGL2 is 4X
-                // (see GL_MAX_SAMPLES in https://registry.khronos.org/OpenGL-Refpages/es3.0/html/glGet.xhtml).
-                // On some platforms, like iOS Safari, `get_parameter_i32(MAX_SAMPLES)` returns 0,
-                // so we always fall back to supporting 4x here.
-                Tfc::MULTISAMPLE_X2 | Tfc::MULTISAMPLE_X4
-            }
+        let sample_counts_up_to = |max_samples| {
+            [
+                (2, Tfc::MULTISAMPLE_X2),
+                (4, Tfc::MULTISAMPLE_X4),
+                (8, Tfc::MULTISAMPLE_X8),
+                (16, Tfc::MULTISAMPLE_X16),
+            ]
+            .into_iter()
+            .filter(|&(count, _)| count <= max_samples)
+            .fold(Tfc::empty(), |flags, (_, flag)| flags | flag)
         };
+        // The lowest supported level in GLE3.0/WebGL2 is 4X
+        // (see GL_MAX_SAMPLES in https://registry.khronos.org/OpenGL-Refpages/es3.0/html/glGet.xhtml).
+        // On some platforms, like iOS Safari, `get_parameter_i32(MAX_SAMPLES)` returns 0,
+        // so we always fall back to supporting 4x here.
+        let sample_count = sample_counts_up_to(self.shared.max_msaa_samples.max(4));
+        let integer_sample_count = sample_counts_up_to(self.shared.max_integer_msaa_samples);
 
         // Base types are pulled from the table in the OpenGLES 3.0 spec in section 3.8.
         //
@@ -1040,6 +1098,8 @@ impl crate::Adapter for super::Adapter {
         let filterable = unfilterable | Tfc::SAMPLED_LINEAR;
         let renderable =
             unfilterable | Tfc::COLOR_ATTACHMENT | sample_count | Tfc::MULTISAMPLE_RESOLVE;
+        let integer_renderable =
+            unfilterable | Tfc::COLOR_ATTACHMENT | integer_sample_count | Tfc::MULTISAMPLE_RESOLVE;
         let filterable_renderable = filterable | renderable | Tfc::COLOR_ATTACHMENT_BLEND;
         let storage = base | Tfc::STORAGE | Tfc::STORAGE_READ_WRITE;
 
@@ -1085,22 +1145,22 @@ impl crate::Adapter for super::Adapter {
         match format {
             Tf::R8Unorm => filterable_renderable,
             Tf::R8Snorm => filterable,
-            Tf::R8Uint => renderable,
-            Tf::R8Sint => renderable,
-            Tf::R16Uint => renderable,
-            Tf::R16Sint => renderable,
+            Tf::R8Uint => integer_renderable,
+            Tf::R8Sint => integer_renderable,
+            Tf::R16Uint => integer_renderable,
+            Tf::R16Sint => integer_renderable,
             Tf::R16Unorm => empty,
             Tf::R16Snorm => empty,
             Tf::R16Float => filterable | half_float_renderable,
             Tf::Rg8Unorm => filterable_renderable,
             Tf::Rg8Snorm => filterable,
-            Tf::Rg8Uint => renderable,
-            Tf::Rg8Sint => renderable,
-            Tf::R32Uint => renderable | storage,
-            Tf::R32Sint => renderable | storage,
+            Tf::Rg8Uint => integer_renderable,
+            Tf::Rg8Sint => integer_renderable,
+            Tf::R32Uint => integer_renderable | storage,
+            Tf::R32Sint => integer_renderable | storage,
             Tf::R32Float => unfilterable | storage | float_renderable | texture_float_linear,
-            Tf::Rg16Uint => renderable,
-            Tf::Rg16Sint => renderable,
+            Tf::Rg16Uint => integer_renderable,
+            Tf::Rg16Sint => integer_renderable,
             Tf::Rg16Unorm => empty,
             Tf::Rg16Snorm => empty,
             Tf::Rg16Float => filterable | half_float_renderable,
@@ -1108,21 +1168,21 @@ impl crate::Adapter for super::Adapter {
             Tf::Rgba8UnormSrgb => filterable_renderable,
             Tf::Bgra8Unorm | Tf::Bgra8UnormSrgb => filterable_renderable,
             Tf::Rgba8Snorm => filterable | storage,
-            Tf::Rgba8Uint => renderable | storage,
-            Tf::Rgba8Sint => renderable | storage,
-            Tf::Rgb10a2Uint => renderable,
+            Tf::Rgba8Uint => integer_renderable | storage,
+            Tf::Rgba8Sint => integer_renderable | storage,
+            Tf::Rgb10a2Uint => integer_renderable,
             Tf::Rgb10a2Unorm => filterable_renderable,
             Tf::Rg11b10Float => filterable | float_renderable,
-            Tf::Rg32Uint => renderable,
-            Tf::Rg32Sint => renderable,
+            Tf::Rg32Uint => integer_renderable,
+            Tf::Rg32Sint => integer_renderable,
             Tf::Rg32Float => unfilterable | float_renderable | texture_float_linear,
-            Tf::Rgba16Uint => renderable | storage,
-            Tf::Rgba16Sint => renderable | storage,
+            Tf::Rgba16Uint => integer_renderable | storage,
+            Tf::Rgba16Sint => integer_renderable | storage,
             Tf::Rgba16Unorm => empty,
             Tf::Rgba16Snorm => empty,
             Tf::Rgba16Float => filterable | storage | half_float_renderable,
-            Tf::Rgba32Uint => renderable | storage,
-            Tf::Rgba32Sint => renderable | storage,
+            Tf::Rgba32Uint => integer_renderable | storage,
+            Tf::Rgba32Sint => integer_renderable | storage,
             Tf::Rgba32Float => unfilterable | storage | float_renderable | texture_float_linear,
             Tf::Stencil8
             | Tf::Depth16Unorm
@@ -1130,7 +1190,7 @@ impl crate::Adapter for super::Adapter {
             | Tf::Depth32FloatStencil8
             | Tf::Depth24Plus
             | Tf::Depth24PlusStencil8 => depth,
-            Tf::NV12 => empty,
+            Tf::NV12 | Tf::P010 => empty,
             Tf::Rgb9e5Ufloat => filterable,
             Tf::Bc1RgbaUnorm
             | Tf::Bc1RgbaUnormSrgb
@@ -1191,6 +1251,10 @@ impl crate::Adapter for super::Adapter {
             {
                 formats.push(wgt::TextureFormat::Rgba16Float)
             }
+            let color_spaces = formats
+                .iter()
+                .map(|&format| (format, wgt::ColorSpace::Srgb))
+                .collect();
 
             Some(crate::SurfaceCapabilities {
                 formats,
@@ -1201,14 +1265,31 @@ impl crate::Adapter for super::Adapter {
                 },
                 composite_alpha_modes: vec![wgt::CompositeAlphaMode::Opaque], //TODO
                 maximum_frame_latency: 2..=2, //TODO, unused currently
+                // The default framebuffer is double buffered.
+                image_count: 2..=2,
                 current_extent: None,
                 usage: crate::TextureUses::COLOR_TARGET,
+                transforms: vec![wgt::SurfaceTransform::Identity],
+                current_transform: wgt::SurfaceTransform::Identity,
+                color_spaces,
+                max_array_layers: 1,
+                fullscreen_modes: vec![wgt::FullscreenMode::Windowed],
+                display_modes: Vec::new(),
             })
         } else {
             None
         }
     }
 
+    unsafe fn headless_surface_capabilities(
+        &self,
+        _display_handle: raw_window_handle::RawDisplayHandle,
+    ) -> Option<crate::SurfaceCapabilities> {
+        // Whether a surface is presentable, and supports sRGB, depends on the
+        // config chosen for its window.
+        None
+    }
+
     unsafe fn get_presentation_timestamp(&self) -> wgt::PresentationTimestamp {
         wgt::PresentationTimestamp::INVALID_TIMESTAMP
     }
@@ -125,6 +125,26 @@ impl super::CommandEncoder {
         }
     }
 
+    fn rebind_stencil_ops(&mut self) {
+        fn make(s: &super::StencilSide, face: u32) -> C {
+            C::SetStencilOps {
+                face,
+                write_mask: s.mask_write,
+                ops: s.ops.clone(),
+            }
+        }
+
+        let s = &self.state.stencil;
+        if s.front.ops == s.back.ops && s.front.mask_write == s.back.mask_write {
+            self.cmd_buffer
+                .commands
+                .push(make(&s.front, glow::FRONT_AND_BACK));
+        } else {
+            self.cmd_buffer.commands.push(make(&s.front, glow::FRONT));
+            self.cmd_buffer.commands.push(make(&s.back, glow::BACK));
+        }
+    }
+
     fn rebind_vertex_data(&mut self, first_instance: u32) {
         if self
             .private_caps
@@ -326,6 +346,10 @@ impl crate::CommandEncoder for super::CommandEncoder {
         }
     }
 
+    unsafe fn aliasing_barrier(&mut self) {
+        unimplemented!()
+    }
+
     unsafe fn clear_buffer(&mut self, buffer: &super::Buffer, range: crate::MemoryRange) {
         self.cmd_buffer.commands.push(C::ClearBuffer {
             dst: buffer.clone(),
@@ -334,6 +358,20 @@ impl crate::CommandEncoder for super::CommandEncoder {
         });
     }
 
+    unsafe fn fill_buffer(
+        &mut self,
+        buffer: &super::Buffer,
+        range: crate::MemoryRange,
+        value: u32,
+    ) {
+        self.cmd_buffer.commands.push(C::FillBuffer {
+            dst: buffer.clone(),
+            dst_target: buffer.target,
+            range,
+            value,
+        });
+    }
+
     unsafe fn copy_buffer_to_buffer<T>(
         &mut self,
         src: &super::Buffer,
@@ -406,6 +444,31 @@ impl crate::CommandEncoder for super::CommandEncoder {
         }
     }
 
+    unsafe fn blit_texture(
+        &mut self,
+        src: &super::Texture,
+        _src_usage: crate::TextureUses,
+        dst: &super::Texture,
+        region: crate::TextureBlit,
+        filter: wgt::FilterMode,
+    ) {
+        let (src_raw, src_target) = src.inner.as_native();
+        let (dst_raw, dst_target) = dst.inner.as_native();
+        // The blit is done into the draw framebuffer, which shouldn't keep
+        // the attachments and state of a previous pass.
+        self.cmd_buffer
+            .commands
+            .push(C::ResetFramebuffer { is_default: false });
+        self.cmd_buffer.commands.push(C::BlitTexture {
+            src: src_raw,
+            src_target,
+            dst: dst_raw,
+            dst_target,
+            blit: region,
+            filter,
+        });
+    }
+
     unsafe fn copy_buffer_to_texture<T>(
         &mut self,
         src: &super::Buffer,
@@ -753,6 +816,7 @@ impl crate::CommandEncoder for super::CommandEncoder {
                     target,
                     aspects,
                     ref mip_levels,
+                    swizzle,
                 } => {
                     dirty_textures |= 1 << slot;
                     self.state.texture_slots[slot as usize].tex_target = target;
@@ -762,6 +826,7 @@ impl crate::CommandEncoder for super::CommandEncoder {
                         target,
                         aspects,
                         mip_levels: mip_levels.clone(),
+                        swizzle,
                     });
                 }
                 super::RawBinding::Image(ref binding) => {
@@ -776,6 +841,41 @@ impl crate::CommandEncoder for super::CommandEncoder {
         self.rebind_sampler_states(dirty_textures, dirty_samplers);
     }
 
+    unsafe fn set_bind_group_inline(
+        &mut self,
+        layout: &super::PipelineLayout,
+        index: u32,
+        _group_layout: &super::BindGroupLayout,
+        buffers: &[crate::BufferBinding<super::Api>],
+    ) {
+        let group_info = &layout.group_infos[index as usize];
+
+        for (binding_layout, bb) in group_info.entries.iter().zip(buffers) {
+            let slot = group_info.binding_to_slot[binding_layout.binding as usize] as u32;
+            let target = match binding_layout.ty {
+                wgt::BindingType::Buffer {
+                    ty: wgt::BufferBindingType::Uniform,
+                    ..
+                } => glow::UNIFORM_BUFFER,
+                wgt::BindingType::Buffer {
+                    ty: wgt::BufferBindingType::Storage { .. },
+                    ..
+                } => glow::SHADER_STORAGE_BUFFER,
+                _ => unreachable!(),
+            };
+            self.cmd_buffer.commands.push(C::BindBuffer {
+                target,
+                slot,
+                buffer: bb.buffer.raw.unwrap(),
+                offset: bb.offset as i32,
+                size: match bb.size {
+                    Some(s) => s.get() as i32,
+                    None => (bb.buffer.size - bb.offset) as i32,
+                },
+            });
+        }
+    }
+
     unsafe fn set_push_constants(
         &mut self,
         _layout: &super::PipelineLayout,
@@ -913,26 +1013,7 @@ impl crate::CommandEncoder for super::CommandEncoder {
             aspects |= crate::FormatAspects::STENCIL;
             self.state.stencil = stencil.clone();
             self.rebind_stencil_func();
-            if stencil.front.ops == stencil.back.ops
-                && stencil.front.mask_write == stencil.back.mask_write
-            {
-                self.cmd_buffer.commands.push(C::SetStencilOps {
-                    face: glow::FRONT_AND_BACK,
-                    write_mask: stencil.front.mask_write,
-                    ops: stencil.front.ops.clone(),
-                });
-            } else {
-                self.cmd_buffer.commands.push(C::SetStencilOps {
-                    face: glow::FRONT,
-                    write_mask: stencil.front.mask_write,
-                    ops: stencil.front.ops.clone(),
-                });
-                self.cmd_buffer.commands.push(C::SetStencilOps {
-                    face: glow::BACK,
-                    write_mask: stencil.back.mask_write,
-                    ops: stencil.back.ops.clone(),
-                });
-            }
+            self.rebind_stencil_ops();
         }
         self.cmd_buffer
             .commands
@@ -1015,14 +1096,47 @@ impl crate::CommandEncoder for super::CommandEncoder {
             h: rect.h as i32,
         }));
     }
+    unsafe fn set_indexed_viewport(
+        &mut self,
+        index: u32,
+        rect: &crate::Rect<f32>,
+        depth: Range<f32>,
+    ) {
+        // `MULTI_VIEWPORT` isn't exposed, so there is only the first viewport.
+        debug_assert_eq!(index, 0);
+        unsafe { self.set_viewport(rect, depth) };
+    }
+    unsafe fn set_indexed_scissor_rect(&mut self, index: u32, rect: &crate::Rect<u32>) {
+        debug_assert_eq!(index, 0);
+        unsafe { self.set_scissor_rect(rect) };
+    }
     unsafe fn set_stencil_reference(&mut self, value: u32) {
         self.state.stencil.front.reference = value;
         self.state.stencil.back.reference = value;
         self.rebind_stencil_func();
     }
+    unsafe fn set_stencil_reference_separate(&mut self, front: u32, back: u32) {
+        self.state.stencil.front.reference = front;
+        self.state.stencil.back.reference = back;
+        self.rebind_stencil_func();
+    }
+    unsafe fn set_stencil_write_mask(&mut self, front: u32, back: u32) {
+        self.state.stencil.front.mask_write = front;
+        self.state.stencil.back.mask_write = back;
+        self.rebind_stencil_ops();
+    }
     unsafe fn set_blend_constants(&mut self, color: &[f32; 4]) {
         self.cmd_buffer.commands.push(C::SetBlendConstant(*color));
     }
+    unsafe fn set_front_face(&mut self, _front_face: wgt::FrontFace) {
+        unreachable!("`EXTENDED_DYNAMIC_STATE` isn't exposed")
+    }
+    unsafe fn set_cull_mode(&mut self, _cull_mode: Option<wgt::Face>) {
+        unreachable!("`EXTENDED_DYNAMIC_STATE` isn't exposed")
+    }
+    unsafe fn set_depth_compare(&mut self, _compare: wgt::CompareFunction) {
+        unreachable!("`EXTENDED_DYNAMIC_STATE` isn't exposed")
+    }
 
     unsafe fn draw(
         &mut self,
@@ -87,7 +87,7 @@ impl super::AdapterShared {
                 glow::DEPTH_STENCIL,
                 glow::UNSIGNED_INT_24_8,
             ),
-            Tf::NV12 => unreachable!(),
+            Tf::NV12 | Tf::P010 => unreachable!(),
             Tf::Rgb9e5Ufloat => (glow::RGB9_E5, glow::RGB, glow::UNSIGNED_INT_5_9_9_9_REV),
             Tf::Bc1RgbaUnorm => (glow::COMPRESSED_RGBA_S3TC_DXT1_EXT, glow::RGBA, 0),
             Tf::Bc1RgbaUnormSrgb => (glow::COMPRESSED_SRGB_ALPHA_S3TC_DXT1_EXT, glow::RGBA, 0),
@@ -269,6 +269,18 @@ pub fn map_compare_func(fun: wgt::CompareFunction) -> u32 {
     }
 }
 
+pub(super) fn map_swizzle_component(component: wgt::ComponentSwizzle) -> u32 {
+    use wgt::ComponentSwizzle as Cs;
+    match component {
+        Cs::Zero => glow::ZERO,
+        Cs::One => glow::ONE,
+        Cs::R => glow::RED,
+        Cs::G => glow::GREEN,
+        Cs::B => glow::BLUE,
+        Cs::A => glow::ALPHA,
+    }
+}
+
 pub fn map_primitive_topology(topology: wgt::PrimitiveTopology) -> u32 {
     use wgt::PrimitiveTopology as Pt;
     match topology {
@@ -286,6 +286,9 @@ impl super::Device {
         })?;
 
         log::debug!("Naga generated shader:\n{}", output);
+        if let Some(capture) = stage.code_capture {
+            capture.capture("glsl", output.as_bytes());
+        }
 
         context.consume_reflection(
             gl,
@@ -515,6 +518,10 @@ impl crate::Device for super::Device {
         unsafe { gl.delete_buffer(queue.zero_buffer) };
     }
 
+    unsafe fn destroy_queue(&self, _queue: super::Queue) {
+        unreachable!("GLES devices have no additional queues")
+    }
+
     unsafe fn create_buffer(
         &self,
         desc: &crate::BufferDescriptor,
@@ -718,6 +725,9 @@ impl crate::Device for super::Device {
     unsafe fn invalidate_mapped_ranges<I>(&self, _buffer: &super::Buffer, _ranges: I) {
         //TODO: do we need to do anything?
     }
+    unsafe fn get_buffer_device_address(&self, _buffer: &super::Buffer) -> wgt::BufferAddress {
+        unimplemented!()
+    }
 
     unsafe fn create_texture(
         &self,
@@ -972,6 +982,56 @@ impl crate::Device for super::Device {
         drop(texture.drop_guard);
     }
 
+    unsafe fn get_sparse_texture_tiles(
+        &self,
+        _texture: &super::Texture,
+    ) -> wgt::SparseTextureTiles {
+        unimplemented!()
+    }
+
+    unsafe fn create_memory_heap(
+        &self,
+        _desc: &crate::MemoryHeapDescriptor,
+    ) -> Result<(), crate::DeviceError> {
+        unimplemented!()
+    }
+
+    unsafe fn destroy_memory_heap(&self, _heap: ()) {
+        unimplemented!()
+    }
+
+    unsafe fn get_buffer_memory_requirements(
+        &self,
+        _desc: &crate::BufferDescriptor,
+    ) -> Result<wgt::MemoryRequirements, crate::DeviceError> {
+        unimplemented!()
+    }
+
+    unsafe fn get_texture_memory_requirements(
+        &self,
+        _desc: &crate::TextureDescriptor,
+    ) -> Result<wgt::MemoryRequirements, crate::DeviceError> {
+        unimplemented!()
+    }
+
+    unsafe fn create_placed_buffer(
+        &self,
+        _desc: &crate::BufferDescriptor,
+        _heap: &(),
+        _offset: wgt::BufferAddress,
+    ) -> Result<super::Buffer, crate::DeviceError> {
+        unimplemented!()
+    }
+
+    unsafe fn create_placed_texture(
+        &self,
+        _desc: &crate::TextureDescriptor,
+        _heap: &(),
+        _offset: wgt::BufferAddress,
+    ) -> Result<super::Texture, crate::DeviceError> {
+        unimplemented!()
+    }
+
     unsafe fn create_texture_view(
         &self,
         texture: &super::Texture,
@@ -984,6 +1044,7 @@ impl crate::Device for super::Device {
             mip_levels: desc.range.mip_range(texture.mip_level_count),
             array_layers: desc.range.layer_range(texture.array_layer_count),
             format: texture.format,
+            swizzle: desc.swizzle,
         })
     }
     unsafe fn destroy_texture_view(&self, _view: super::TextureView) {}
@@ -1031,6 +1092,7 @@ impl crate::Device for super::Device {
                 }
                 wgt::SamplerBorderColor::OpaqueBlack => [0.0, 0.0, 0.0, 1.0],
                 wgt::SamplerBorderColor::OpaqueWhite => [1.0; 4],
+                wgt::SamplerBorderColor::Custom(color) => color,
             };
             unsafe { gl.sampler_parameter_f32_slice(raw, glow::TEXTURE_BORDER_COLOR, &border) };
         }
@@ -1049,7 +1111,16 @@ impl crate::Device for super::Device {
             };
         }
 
-        //set_param_float(glow::TEXTURE_LOD_BIAS, info.lod_bias.0);
+        if desc.lod_bias != 0.0 {
+            let max_bias = self.shared.max_texture_lod_bias;
+            unsafe {
+                gl.sampler_parameter_f32(
+                    raw,
+                    glow::TEXTURE_LOD_BIAS,
+                    desc.lod_bias.clamp(-max_bias, max_bias),
+                )
+            };
+        }
 
         if let Some(compare) = desc.compare {
             unsafe {
@@ -1167,6 +1238,9 @@ impl crate::Device for super::Device {
                         ..
                     } => &mut num_storage_buffers,
                     wgt::BindingType::AccelerationStructure => unimplemented!(),
+                    wgt::BindingType::YcbcrTexture { .. } => {
+                        unreachable!("YcbcrTexture is gated by SAMPLER_YCBCR_CONVERSION")
+                    }
                 };
 
                 binding_to_slot[entry.binding as usize] = *counter;
@@ -1243,6 +1317,7 @@ impl crate::Device for super::Device {
                         target,
                         aspects: view.aspects,
                         mip_levels: view.mip_levels.clone(),
+                        swizzle: view.swizzle,
                     }
                 }
                 wgt::BindingType::StorageTexture {
@@ -1266,6 +1341,9 @@ impl crate::Device for super::Device {
                     })
                 }
                 wgt::BindingType::AccelerationStructure => unimplemented!(),
+                wgt::BindingType::YcbcrTexture { .. } => {
+                    unreachable!("YcbcrTexture is gated by SAMPLER_YCBCR_CONVERSION")
+                }
             };
             contents.push(binding);
         }
@@ -1275,6 +1353,7 @@ impl crate::Device for super::Device {
         })
     }
     unsafe fn destroy_bind_group(&self, _group: super::BindGroup) {}
+    unsafe fn recycle_bind_groups(&self) {}
 
     unsafe fn create_shader_module(
         &self,
@@ -1497,6 +1576,15 @@ impl crate::Device for super::Device {
         Ok(true)
     }
 
+    unsafe fn signal_fence(
+        &self,
+        fence: &mut super::Fence,
+        value: crate::FenceValue,
+    ) -> Result<(), crate::DeviceError> {
+        fence.last_completed = value;
+        Ok(())
+    }
+
     unsafe fn start_capture(&self) -> bool {
         #[cfg(all(native, feature = "renderdoc"))]
         return unsafe {
@@ -1513,6 +1601,9 @@ impl crate::Device for super::Device {
                 .end_frame_capture(ptr::null_mut(), ptr::null_mut())
         }
     }
+    unsafe fn set_performance_hint(&self, _hint: wgt::PerformanceHint) {
+        // GL has no power hints of its own.
+    }
     unsafe fn create_acceleration_structure(
         &self,
         _desc: &crate::AccelerationStructureDescriptor,
@@ -18,6 +18,8 @@ const EGL_PLATFORM_ANGLE_DEBUG_LAYERS_ENABLED: u32 = 0x3451;
 const EGL_PLATFORM_SURFACELESS_MESA: u32 = 0x31DD;
 const EGL_GL_COLORSPACE_KHR: u32 = 0x309D;
 const EGL_GL_COLORSPACE_SRGB_KHR: u32 = 0x3089;
+const EGL_GL_TEXTURE_2D_KHR: u32 = 0x30B1;
+const EGL_GL_TEXTURE_LEVEL_KHR: i32 = 0x30BC;
 
 type XOpenDisplayFun =
     unsafe extern "system" fn(display_name: *const raw::c_char) -> *mut raw::c_void;
@@ -51,6 +53,24 @@ type WlEglWindowResizeFun = unsafe extern "system" fn(
 
 type WlEglWindowDestroyFun = unsafe extern "system" fn(window: *const raw::c_void);
 
+type EglCreateImageKhrFun = unsafe extern "system" fn(
+    display: khronos_egl::EGLDisplay,
+    context: khronos_egl::EGLContext,
+    target: khronos_egl::Enum,
+    buffer: khronos_egl::EGLClientBuffer,
+    attrib_list: *const khronos_egl::Int,
+) -> khronos_egl::EGLImage;
+
+type EglSwapBuffersWithDamageFun = unsafe extern "system" fn(
+    display: khronos_egl::EGLDisplay,
+    surface: khronos_egl::EGLSurface,
+    rects: *const khronos_egl::Int,
+    n_rects: khronos_egl::Int,
+) -> khronos_egl::Boolean;
+
+type GlEglImageTargetTexture2dOesFun =
+    unsafe extern "system" fn(target: u32, image: khronos_egl::EGLImage);
+
 type EglLabel = *const raw::c_void;
 
 #[allow(clippy::upper_case_acronyms)]
@@ -1072,6 +1092,117 @@ impl super::Device {
     pub fn context(&self) -> &AdapterContext {
         &self.shared.context
     }
+
+    /// Creates a 2D texture whose storage is `image`, an `EGLImage`, with
+    /// `glEGLImageTargetTexture2DOES`.
+    ///
+    /// The texture owns its GL texture, but not the image, which may be
+    /// destroyed once the texture is created.
+    ///
+    /// Fails on adapters created with [`super::Adapter::new_external`], which
+    /// have no EGL context to load the extension with.
+    ///
+    /// # Safety
+    ///
+    /// - `image` must be a valid `EGLImage` of the display of the adapter,
+    ///   holding an image of the size and format of `desc`.
+    /// - `desc` must describe a 2D texture with a single layer, mip level and
+    ///   sample.
+    pub unsafe fn texture_from_egl_image(
+        &self,
+        image: *mut raw::c_void,
+        desc: &crate::TextureDescriptor,
+    ) -> Result<super::Texture, crate::DeviceError> {
+        let egl = self
+            .shared
+            .context
+            .egl
+            .as_ref()
+            .ok_or(crate::DeviceError::ResourceCreationFailed)?;
+        let image_target_texture: GlEglImageTargetTexture2dOesFun = {
+            let addr = egl
+                .instance
+                .get_proc_address("glEGLImageTargetTexture2DOES")
+                .ok_or(crate::DeviceError::ResourceCreationFailed)?;
+            unsafe { std::mem::transmute(addr) }
+        };
+
+        let gl = &self.shared.context.lock();
+        let raw = unsafe { gl.create_texture() }.map_err(|_| crate::DeviceError::OutOfMemory)?;
+        unsafe { gl.bind_texture(glow::TEXTURE_2D, Some(raw)) };
+        unsafe { image_target_texture(glow::TEXTURE_2D, image) };
+        let error = unsafe { gl.get_error() };
+        unsafe { gl.bind_texture(glow::TEXTURE_2D, None) };
+        if error != glow::NO_ERROR {
+            log::error!("glEGLImageTargetTexture2DOES failed: 0x{error:x}");
+            unsafe { gl.delete_texture(raw) };
+            return Err(crate::DeviceError::ResourceCreationFailed);
+        }
+
+        Ok(super::Texture {
+            inner: super::TextureInner::Texture {
+                raw,
+                target: glow::TEXTURE_2D,
+            },
+            drop_guard: None,
+            mip_level_count: desc.mip_level_count,
+            array_layer_count: desc.array_layer_count(),
+            format: desc.format,
+            format_desc: self.shared.describe_texture_format(desc.format),
+            copy_size: desc.copy_extent(),
+        })
+    }
+
+    /// Creates an `EGLImage` sharing the first mip level of `texture` with
+    /// `eglCreateImageKHR`, which the caller must destroy with
+    /// `eglDestroyImageKHR`.
+    ///
+    /// Fails for textures that aren't 2D GL textures, and on adapters created
+    /// with [`super::Adapter::new_external`].
+    pub fn create_egl_image(
+        &self,
+        texture: &super::Texture,
+    ) -> Result<*mut raw::c_void, crate::DeviceError> {
+        let raw = match texture.inner {
+            super::TextureInner::Texture {
+                raw,
+                target: glow::TEXTURE_2D,
+            } => raw,
+            _ => return Err(crate::DeviceError::ResourceCreationFailed),
+        };
+        let egl = self
+            .shared
+            .context
+            .egl
+            .as_ref()
+            .ok_or(crate::DeviceError::ResourceCreationFailed)?;
+        let create_image: EglCreateImageKhrFun = {
+            let addr = egl
+                .instance
+                .get_proc_address("eglCreateImageKHR")
+                .ok_or(crate::DeviceError::ResourceCreationFailed)?;
+            unsafe { std::mem::transmute(addr) }
+        };
+
+        let attributes = [EGL_GL_TEXTURE_LEVEL_KHR, 0, khronos_egl::NONE];
+        // Flush so the other context sees the contents of the texture.
+        let gl = &self.shared.context.lock();
+        unsafe { gl.flush() };
+        let image = unsafe {
+            create_image(
+                egl.display.as_ptr(),
+                egl.raw.as_ptr(),
+                EGL_GL_TEXTURE_2D_KHR,
+                raw.0.get() as usize as khronos_egl::EGLClientBuffer,
+                attributes.as_ptr(),
+            )
+        };
+        if image.is_null() {
+            log::error!("eglCreateImageKHR failed: {:?}", egl.instance.get_error());
+            return Err(crate::DeviceError::ResourceCreationFailed);
+        }
+        Ok(image)
+    }
 }
 
 #[derive(Debug)]
@@ -1086,6 +1217,10 @@ pub struct Swapchain {
     format_desc: super::TextureFormatDesc,
     #[allow(unused)]
     sample_type: wgt::TextureSampleType,
+    /// `eglSwapBuffersWithDamageKHR`, if the display supports it.
+    swap_buffers_with_damage: Option<EglSwapBuffersWithDamageFun>,
+    /// Damage of the next present, with a top-left origin.
+    next_damage: Vec<wgt::DamageRect>,
 }
 
 #[derive(Debug)]
@@ -1109,8 +1244,9 @@ impl Surface {
         context: &AdapterContext,
     ) -> Result<(), crate::SurfaceError> {
         let gl = unsafe { context.get_without_egl_lock() };
-        let swapchain = self.swapchain.read();
-        let sc = swapchain.as_ref().unwrap();
+        let mut swapchain = self.swapchain.write();
+        let sc = swapchain.as_mut().unwrap();
+        let damage = std::mem::take(&mut sc.next_damage);
 
         self.egl
             .instance
@@ -1161,14 +1297,47 @@ impl Surface {
 
         unsafe { gl.bind_framebuffer(glow::READ_FRAMEBUFFER, None) };
 
-        self.egl
-            .instance
-            .swap_buffers(self.egl.display, sc.surface)
-            .map_err(|e| {
-                log::error!("swap_buffers failed: {}", e);
-                crate::SurfaceError::Lost
-                // TODO: should we unset the current context here?
-            })?;
+        match sc.swap_buffers_with_damage {
+            Some(swap_buffers_with_damage) if !damage.is_empty() => {
+                // EGL rectangles have a bottom-left origin.
+                let rects = damage
+                    .iter()
+                    .flat_map(|rect| {
+                        [
+                            rect.x as khronos_egl::Int,
+                            (sc.extent.height - rect.y - rect.height) as khronos_egl::Int,
+                            rect.width as khronos_egl::Int,
+                            rect.height as khronos_egl::Int,
+                        ]
+                    })
+                    .collect::<Vec<_>>();
+                let result = unsafe {
+                    swap_buffers_with_damage(
+                        self.egl.display.as_ptr(),
+                        sc.surface.as_ptr(),
+                        rects.as_ptr(),
+                        damage.len() as khronos_egl::Int,
+                    )
+                };
+                if result != khronos_egl::TRUE {
+                    log::error!(
+                        "swap_buffers_with_damage failed: {:?}",
+                        self.egl.instance.get_error()
+                    );
+                    return Err(crate::SurfaceError::Lost);
+                }
+            }
+            _ => {
+                self.egl
+                    .instance
+                    .swap_buffers(self.egl.display, sc.surface)
+                    .map_err(|e| {
+                        log::error!("swap_buffers failed: {}", e);
+                        crate::SurfaceError::Lost
+                        // TODO: should we unset the current context here?
+                    })?;
+            }
+        }
         self.egl
             .instance
             .make_current(self.egl.display, None, None, None)
@@ -1392,6 +1561,30 @@ impl crate::Surface for Surface {
         unsafe { gl.bind_renderbuffer(glow::RENDERBUFFER, None) };
         unsafe { gl.bind_framebuffer(glow::READ_FRAMEBUFFER, None) };
 
+        let display_extensions = self
+            .egl
+            .instance
+            .query_string(Some(self.egl.display), khronos_egl::EXTENSIONS)
+            .map_or(String::new(), |cstr| cstr.to_string_lossy().into_owned());
+        let swap_buffers_with_damage = [
+            (
+                "EGL_KHR_swap_buffers_with_damage",
+                "eglSwapBuffersWithDamageKHR",
+            ),
+            (
+                "EGL_EXT_swap_buffers_with_damage",
+                "eglSwapBuffersWithDamageEXT",
+            ),
+        ]
+        .into_iter()
+        .filter(|&(extension, _)| {
+            display_extensions
+                .split_whitespace()
+                .any(|e| e == extension)
+        })
+        .find_map(|(_, name)| self.egl.instance.get_proc_address(name))
+        .map(|addr| unsafe { std::mem::transmute::<_, EglSwapBuffersWithDamageFun>(addr) });
+
         let mut swapchain = self.swapchain.write();
         *swapchain = Some(Swapchain {
             surface,
@@ -1402,6 +1595,8 @@ impl crate::Surface for Surface {
             format: config.format,
             format_desc,
             sample_type: wgt::TextureSampleType::Float { filterable: false },
+            swap_buffers_with_damage,
+            next_damage: Vec::new(),
         });
 
         Ok(())
@@ -1455,4 +1650,47 @@ impl crate::Surface for Surface {
         }))
     }
     unsafe fn discard_texture(&self, _texture: super::Texture) {}
+
+    unsafe fn set_hdr_metadata(
+        &self,
+        _metadata: &wgt::HdrMetadata,
+    ) -> Result<(), crate::SurfaceError> {
+        Err(crate::SurfaceError::Other("HDR metadata is not supported"))
+    }
+
+    unsafe fn set_next_present_time(&self, _time: wgt::PresentationTimestamp) -> bool {
+        false
+    }
+
+    unsafe fn set_next_present_damage(&self, damage: &[wgt::DamageRect]) -> bool {
+        let mut swapchain = self.swapchain.write();
+        let sc = swapchain.as_mut().unwrap();
+        if sc.swap_buffers_with_damage.is_none() {
+            return false;
+        }
+        sc.next_damage.clear();
+        sc.next_damage.extend_from_slice(damage);
+        true
+    }
+
+    unsafe fn presentation_timings(&self) -> Vec<wgt::PresentationTiming> {
+        Vec::new()
+    }
+
+    unsafe fn refresh_duration(&self) -> Option<std::time::Duration> {
+        None
+    }
+
+    unsafe fn image_count(&self) -> u32 {
+        2
+    }
+
+    unsafe fn wait_for_present(
+        &self,
+        _timeout: Option<std::time::Duration>,
+    ) -> Result<bool, crate::SurfaceError> {
+        Ok(true)
+    }
+
+    unsafe fn set_maximum_frame_latency(&self, _maximum_frame_latency: u32) {}
 }
@@ -152,6 +152,7 @@ impl crate::Api for Api {
     type TextureView = TextureView;
     type Sampler = Sampler;
     type QuerySet = QuerySet;
+    type MemoryHeap = ();
     type Fence = Fence;
     type AccelerationStructure = ();
 
@@ -256,6 +257,15 @@ struct AdapterShared {
     /// Cached here so it doesn't need to be queried every time texture format capabilities are requested.
     /// (this has been shown to be a significant enough overhead)
     max_msaa_samples: i32,
+
+    /// Result of `gl.get_parameter_i32(glow::MAX_INTEGER_SAMPLES)`, or zero
+    /// where multisampled integer renderbuffers aren't allowed, like on
+    /// GLES 3.0 and WebGL2.
+    max_integer_msaa_samples: i32,
+
+    /// Result of `gl.get_parameter_f32(glow::MAX_TEXTURE_LOD_BIAS)`, or zero on GLES, which
+    /// doesn't have sampler LOD biases.
+    max_texture_lod_bias: f32,
 }
 
 pub struct Adapter {
@@ -435,6 +445,7 @@ pub struct TextureView {
     mip_levels: Range<u32>,
     array_layers: Range<u32>,
     format: wgt::TextureFormat,
+    swizzle: wgt::TextureComponentSwizzle,
 }
 
 #[derive(Debug)]
@@ -491,6 +502,7 @@ enum RawBinding {
         target: BindTarget,
         aspects: crate::FormatAspects,
         mip_levels: Range<u32>,
+        swizzle: wgt::TextureComponentSwizzle,
         //TODO: array layers
     },
     Image(ImageBinding),
@@ -793,6 +805,12 @@ enum Command {
         dst_target: BindTarget,
         range: crate::MemoryRange,
     },
+    FillBuffer {
+        dst: Buffer,
+        dst_target: BindTarget,
+        range: crate::MemoryRange,
+        value: u32,
+    },
     CopyBufferToBuffer {
         src: Buffer,
         src_target: BindTarget,
@@ -816,6 +834,14 @@ enum Command {
         dst_target: BindTarget,
         copy: crate::TextureCopy,
     },
+    BlitTexture {
+        src: glow::Texture,
+        src_target: BindTarget,
+        dst: glow::Texture,
+        dst_target: BindTarget,
+        blit: crate::TextureBlit,
+        filter: wgt::FilterMode,
+    },
     CopyBufferToTexture {
         src: Buffer,
         #[allow(unused)]
@@ -926,6 +952,7 @@ enum Command {
         target: BindTarget,
         aspects: crate::FormatAspects,
         mip_levels: Range<u32>,
+        swizzle: wgt::TextureComponentSwizzle,
     },
     BindImage {
         slot: u32,
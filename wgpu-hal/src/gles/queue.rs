@@ -347,6 +347,29 @@ impl super::Queue {
                         .fill(0);
                 }
             },
+            C::FillBuffer {
+                ref dst,
+                dst_target,
+                ref range,
+                value,
+            } => {
+                let pattern = value
+                    .to_le_bytes()
+                    .repeat((range.end - range.start) as usize / 4);
+                match dst.raw {
+                    Some(buffer) => {
+                        unsafe { gl.bind_buffer(dst_target, Some(buffer)) };
+                        unsafe {
+                            gl.buffer_sub_data_u8_slice(dst_target, range.start as i32, &pattern)
+                        };
+                    }
+                    None => {
+                        dst.data.as_ref().unwrap().lock().unwrap().as_mut_slice()
+                            [range.start as usize..range.end as usize]
+                            .copy_from_slice(&pattern);
+                    }
+                }
+            }
             C::CopyBufferToBuffer {
                 ref src,
                 src_target,
@@ -621,6 +644,85 @@ impl super::Queue {
                     };
                 }
             }
+            C::BlitTexture {
+                src,
+                src_target,
+                dst,
+                dst_target,
+                ref blit,
+                filter,
+            } => {
+                unsafe { gl.bind_framebuffer(glow::READ_FRAMEBUFFER, Some(self.copy_fbo)) };
+                if is_layered_target(src_target) {
+                    unsafe {
+                        gl.framebuffer_texture_layer(
+                            glow::READ_FRAMEBUFFER,
+                            glow::COLOR_ATTACHMENT0,
+                            Some(src),
+                            blit.src_base.mip_level as i32,
+                            blit.src_base.array_layer as i32,
+                        )
+                    };
+                } else {
+                    unsafe {
+                        gl.framebuffer_texture_2d(
+                            glow::READ_FRAMEBUFFER,
+                            glow::COLOR_ATTACHMENT0,
+                            get_2d_target(src_target, blit.src_base.array_layer),
+                            Some(src),
+                            blit.src_base.mip_level as i32,
+                        )
+                    };
+                }
+                unsafe { gl.read_buffer(glow::COLOR_ATTACHMENT0) };
+
+                // The draw framebuffer was reset by the preceding `ResetFramebuffer`.
+                if is_layered_target(dst_target) {
+                    unsafe {
+                        gl.framebuffer_texture_layer(
+                            glow::DRAW_FRAMEBUFFER,
+                            glow::COLOR_ATTACHMENT0,
+                            Some(dst),
+                            blit.dst_base.mip_level as i32,
+                            blit.dst_base.array_layer as i32,
+                        )
+                    };
+                } else {
+                    unsafe {
+                        gl.framebuffer_texture_2d(
+                            glow::DRAW_FRAMEBUFFER,
+                            glow::COLOR_ATTACHMENT0,
+                            get_2d_target(dst_target, blit.dst_base.array_layer),
+                            Some(dst),
+                            blit.dst_base.mip_level as i32,
+                        )
+                    };
+                }
+                unsafe { gl.draw_buffers(&[glow::COLOR_ATTACHMENT0]) };
+
+                let src_x = blit.src_base.origin.x as i32;
+                let src_y = blit.src_base.origin.y as i32;
+                let dst_x = blit.dst_base.origin.x as i32;
+                let dst_y = blit.dst_base.origin.y as i32;
+                unsafe {
+                    gl.blit_framebuffer(
+                        src_x,
+                        src_y,
+                        src_x + blit.src_size.width as i32,
+                        src_y + blit.src_size.height as i32,
+                        dst_x,
+                        dst_y,
+                        dst_x + blit.dst_size.width as i32,
+                        dst_y + blit.dst_size.height as i32,
+                        glow::COLOR_BUFFER_BIT,
+                        match filter {
+                            wgt::FilterMode::Nearest => glow::NEAREST,
+                            wgt::FilterMode::Linear => glow::LINEAR,
+                        },
+                    )
+                };
+                unsafe { gl.bind_framebuffer(glow::READ_FRAMEBUFFER, None) };
+            }
             C::CopyBufferToTexture {
                 ref src,
                 src_target: _,
@@ -1429,6 +1531,7 @@ impl super::Queue {
                 target,
                 aspects,
                 ref mip_levels,
+                swizzle,
             } => {
                 unsafe { gl.active_texture(glow::TEXTURE0 + slot) };
                 unsafe { gl.bind_texture(target, Some(texture)) };
@@ -1463,6 +1566,24 @@ impl super::Queue {
                         };
                     }
                 }
+
+                // The swizzle is a parameter of the texture, not of the view, so it
+                // is set on every bind, to reset the swizzle of any other view.
+                if self
+                    .features
+                    .contains(wgt::Features::TEXTURE_COMPONENT_SWIZZLE)
+                {
+                    let channels = [
+                        (glow::TEXTURE_SWIZZLE_R, swizzle.r),
+                        (glow::TEXTURE_SWIZZLE_G, swizzle.g),
+                        (glow::TEXTURE_SWIZZLE_B, swizzle.b),
+                        (glow::TEXTURE_SWIZZLE_A, swizzle.a),
+                    ];
+                    for (parameter, component) in channels {
+                        let value = super::conv::map_swizzle_component(component);
+                        unsafe { gl.tex_parameter_i32(target, parameter, value as i32) };
+                    }
+                }
             }
             C::BindImage { slot, ref binding } => {
                 unsafe {
@@ -1788,6 +1909,35 @@ impl crate::Queue for super::Queue {
         Ok(())
     }
 
+    unsafe fn wait_for_fence(
+        &self,
+        _fence: &super::Fence,
+        _value: crate::FenceValue,
+    ) -> Result<(), crate::DeviceError> {
+        // There is only ever one queue, and all of its submissions run in order.
+        Ok(())
+    }
+
+    unsafe fn bind_sparse_buffer(
+        &self,
+        _device: &super::Device,
+        _buffer: &super::Buffer,
+        _binds: &[wgt::SparseBufferBind],
+        _signal_fence: (&mut super::Fence, crate::FenceValue),
+    ) -> Result<(), crate::DeviceError> {
+        unimplemented!()
+    }
+
+    unsafe fn bind_sparse_texture(
+        &self,
+        _device: &super::Device,
+        _texture: &super::Texture,
+        _binds: &[wgt::SparseTextureBind],
+        _signal_fence: (&mut super::Fence, crate::FenceValue),
+    ) -> Result<(), crate::DeviceError> {
+        unimplemented!()
+    }
+
     unsafe fn present(
         &self,
         surface: &super::Surface,
@@ -1799,6 +1949,10 @@ impl crate::Queue for super::Queue {
     unsafe fn get_timestamp_period(&self) -> f32 {
         1.0
     }
+
+    unsafe fn get_timestamp_calibration(&self) -> Option<wgt::TimestampCalibration> {
+        None
+    }
 }
 
 #[cfg(send_sync)]
@@ -454,4 +454,40 @@ impl crate::Surface for Surface {
     }
 
     unsafe fn discard_texture(&self, _texture: super::Texture) {}
+
+    unsafe fn set_hdr_metadata(
+        &self,
+        _metadata: &wgt::HdrMetadata,
+    ) -> Result<(), crate::SurfaceError> {
+        Err(crate::SurfaceError::Other("HDR metadata is not supported"))
+    }
+
+    unsafe fn set_next_present_time(&self, _time: wgt::PresentationTimestamp) -> bool {
+        false
+    }
+
+    unsafe fn set_next_present_damage(&self, _damage: &[wgt::DamageRect]) -> bool {
+        false
+    }
+
+    unsafe fn presentation_timings(&self) -> Vec<wgt::PresentationTiming> {
+        Vec::new()
+    }
+
+    unsafe fn refresh_duration(&self) -> Option<std::time::Duration> {
+        None
+    }
+
+    unsafe fn image_count(&self) -> u32 {
+        2
+    }
+
+    unsafe fn wait_for_present(
+        &self,
+        _timeout: Option<std::time::Duration>,
+    ) -> Result<bool, crate::SurfaceError> {
+        Ok(true)
+    }
+
+    unsafe fn set_maximum_frame_latency(&self, _maximum_frame_latency: u32) {}
 }
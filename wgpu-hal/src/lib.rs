@@ -406,6 +406,9 @@ pub trait Api: Clone + fmt::Debug + Sized {
     type TextureView: fmt::Debug + WasmNotSendSync;
     type Sampler: fmt::Debug + WasmNotSendSync;
     type QuerySet: fmt::Debug + WasmNotSendSync;
+    /// A block of device memory that buffers and textures are placed in with
+    /// [`Device::create_placed_buffer`] and [`Device::create_placed_texture`].
+    type MemoryHeap: fmt::Debug + WasmNotSendSync;
 
     /// A value you can block on to wait for something to finish.
     ///
@@ -548,15 +551,112 @@ pub trait Surface: WasmNotSendSync {
     /// [`SurfaceTexture`]: Api::SurfaceTexture
     /// [`self.acquire_texture`]: Surface::acquire_texture
     unsafe fn discard_texture(&self, texture: <Self::A as Api>::SurfaceTexture);
+
+    /// Describe the content presented to `self` to the display, for it to tone
+    /// map HDR content it can't show as is.
+    ///
+    /// Return [`SurfaceError::Other`] if the backend or the display doesn't
+    /// take HDR metadata.
+    ///
+    /// # Safety
+    ///
+    /// - The surface `self` must currently be configured on some [`Device`].
+    unsafe fn set_hdr_metadata(&self, metadata: &wgt::HdrMetadata) -> Result<(), SurfaceError>;
+
+    /// Show the frame of the next [`Queue::present`] to `self` no earlier than
+    /// `time`, a timestamp of the clock of
+    /// [`Adapter::get_presentation_timestamp`].
+    ///
+    /// Return `false` if presents to `self` can't be scheduled, in which case
+    /// the next frame is shown as it would have been without this call.
+    ///
+    /// # Safety
+    ///
+    /// - The surface `self` must currently be configured on some [`Device`].
+    unsafe fn set_next_present_time(&self, time: wgt::PresentationTimestamp) -> bool;
+
+    /// Tell the compositor that the frame of the next [`Queue::present`] to
+    /// `self` only differs from the previous one in `damage`, so that it only
+    /// has to repaint these regions.
+    ///
+    /// Return `false` if the backend can't present damage, in which case the
+    /// whole frame is repainted.
+    ///
+    /// # Safety
+    ///
+    /// - The surface `self` must currently be configured on some [`Device`].
+    /// - The rectangles of `damage` must be within the extent of the surface,
+    ///   and not empty.
+    unsafe fn set_next_present_damage(&self, damage: &[wgt::DamageRect]) -> bool;
+
+    /// Return when the frames presented to `self` since the last call were
+    /// shown, oldest first.
+    ///
+    /// Timings only become available some time after the frame was shown, and
+    /// the frames that were never shown, or that the backend can't tell about,
+    /// are left out. The result is empty on backends without presentation
+    /// timing.
+    ///
+    /// # Safety
+    ///
+    /// - The surface `self` must currently be configured on some [`Device`].
+    unsafe fn presentation_timings(&self) -> Vec<wgt::PresentationTiming>;
+
+    /// Return the time between two refreshes of the display `self` is shown
+    /// on, if known.
+    ///
+    /// # Safety
+    ///
+    /// - The surface `self` must currently be configured on some [`Device`].
+    unsafe fn refresh_duration(&self) -> Option<std::time::Duration>;
+
+    /// Return the number of textures of the swapchain of `self`, which can be
+    /// more than [`SurfaceConfiguration::image_count`].
+    ///
+    /// # Safety
+    ///
+    /// - The surface `self` must currently be configured on some [`Device`].
+    unsafe fn image_count(&self) -> u32;
+
+    /// Block until `self` can take a new frame without more than
+    /// [`SurfaceConfiguration::maximum_frame_latency`] frames waiting to be
+    /// shown, or until `timeout` elapses, in which case return `Ok(false)`.
+    /// If `timeout` is `None`, wait indefinitely.
+    ///
+    /// Backends that can't wait return `Ok(true)` immediately.
+    ///
+    /// # Safety
+    ///
+    /// - The surface `self` must currently be configured on some [`Device`].
+    unsafe fn wait_for_present(
+        &self,
+        timeout: Option<std::time::Duration>,
+    ) -> Result<bool, SurfaceError>;
+
+    /// Change the [`SurfaceConfiguration::maximum_frame_latency`] of `self`
+    /// without configuring it again. Must be in the
+    /// `SurfaceCapabilities::maximum_frame_latency` range.
+    ///
+    /// # Safety
+    ///
+    /// - The surface `self` must currently be configured on some [`Device`].
+    unsafe fn set_maximum_frame_latency(&self, maximum_frame_latency: u32);
 }
 
 pub trait Adapter: WasmNotSendSync {
     type A: Api;
 
+    /// Open a device on this adapter.
+    ///
+    /// If `transfer_queue` is true, the backend may also open an
+    /// [`OpenDevice::transfer_queue`], at the cost of sharing the buffers and
+    /// textures the device creates between the queues.
     unsafe fn open(
         &self,
         features: wgt::Features,
         limits: &wgt::Limits,
+        queue_priority: wgt::QueuePriority,
+        transfer_queue: bool,
     ) -> Result<OpenDevice<Self::A>, DeviceError>;
 
     /// Return the set of supported capabilities for a texture format.
@@ -573,6 +673,17 @@ pub trait Adapter: WasmNotSendSync {
         surface: &<Self::A as Api>::Surface,
     ) -> Option<SurfaceCapabilities>;
 
+    /// Returns the capabilities a surface on `display_handle` would have,
+    /// without requiring a window.
+    ///
+    /// Nothing specific to a window is known, so `current_extent` is always
+    /// `None`. `None` means the capabilities can't be determined without a
+    /// surface on this backend, or presentation to the display isn't supported.
+    unsafe fn headless_surface_capabilities(
+        &self,
+        display_handle: raw_window_handle::RawDisplayHandle,
+    ) -> Option<SurfaceCapabilities>;
+
     /// Creates a [`PresentationTimestamp`] using the adapter's WSI.
     ///
     /// [`PresentationTimestamp`]: wgt::PresentationTimestamp
@@ -584,6 +695,12 @@ pub trait Device: WasmNotSendSync {
 
     /// Exit connection to this logical device.
     unsafe fn exit(self, queue: <Self::A as Api>::Queue);
+
+    /// Free one of the queues returned in [`OpenDevice::additional_queues`],
+    /// or [`OpenDevice::transfer_queue`].
+    ///
+    /// The queue must not have any submissions still executing.
+    unsafe fn destroy_queue(&self, queue: <Self::A as Api>::Queue);
     /// Creates a new buffer.
     ///
     /// The initial usage is `BufferUses::empty()`.
@@ -605,6 +722,13 @@ pub trait Device: WasmNotSendSync {
     unsafe fn invalidate_mapped_ranges<I>(&self, buffer: &<Self::A as Api>::Buffer, ranges: I)
     where
         I: Iterator<Item = MemoryRange>;
+    /// Returns the address of `buffer` in the address space of the device.
+    ///
+    /// The buffer must have been created with [`MemoryFlags::DEVICE_ADDRESS`].
+    unsafe fn get_buffer_device_address(
+        &self,
+        buffer: &<Self::A as Api>::Buffer,
+    ) -> wgt::BufferAddress;
 
     /// Creates a new texture.
     ///
@@ -614,6 +738,59 @@ pub trait Device: WasmNotSendSync {
         desc: &TextureDescriptor,
     ) -> Result<<Self::A as Api>::Texture, DeviceError>;
     unsafe fn destroy_texture(&self, texture: <Self::A as Api>::Texture);
+    /// Returns the tiles that the memory of `texture` is committed in.
+    ///
+    /// The texture must have been created with [`MemoryFlags::SPARSE`].
+    unsafe fn get_sparse_texture_tiles(
+        &self,
+        texture: &<Self::A as Api>::Texture,
+    ) -> wgt::SparseTextureTiles;
+
+    /// Allocates a block of device local memory to place buffers and textures in.
+    unsafe fn create_memory_heap(
+        &self,
+        desc: &MemoryHeapDescriptor,
+    ) -> Result<<Self::A as Api>::MemoryHeap, DeviceError>;
+    /// Frees the memory of `heap`.
+    ///
+    /// The buffers and textures placed in it must have been destroyed.
+    unsafe fn destroy_memory_heap(&self, heap: <Self::A as Api>::MemoryHeap);
+    /// Returns the memory a buffer created with `desc` by
+    /// [`Device::create_placed_buffer`] takes up in a heap.
+    unsafe fn get_buffer_memory_requirements(
+        &self,
+        desc: &BufferDescriptor,
+    ) -> Result<wgt::MemoryRequirements, DeviceError>;
+    /// Returns the memory a texture created with `desc` by
+    /// [`Device::create_placed_texture`] takes up in a heap.
+    unsafe fn get_texture_memory_requirements(
+        &self,
+        desc: &TextureDescriptor,
+    ) -> Result<wgt::MemoryRequirements, DeviceError>;
+    /// Creates a buffer using the memory of `heap` from `offset` on.
+    ///
+    /// `offset` must be aligned, and the buffer must fit in the heap, as
+    /// returned by [`Device::get_buffer_memory_requirements`]. The buffer
+    /// can't be mapped.
+    unsafe fn create_placed_buffer(
+        &self,
+        desc: &BufferDescriptor,
+        heap: &<Self::A as Api>::MemoryHeap,
+        offset: wgt::BufferAddress,
+    ) -> Result<<Self::A as Api>::Buffer, DeviceError>;
+    /// Creates a texture using the memory of `heap` from `offset` on.
+    ///
+    /// `offset` must be aligned, and the texture must fit in the heap, as
+    /// returned by [`Device::get_texture_memory_requirements`].
+    ///
+    /// The initial usage for all subresources is `TextureUses::UNINITIALIZED`.
+    unsafe fn create_placed_texture(
+        &self,
+        desc: &TextureDescriptor,
+        heap: &<Self::A as Api>::MemoryHeap,
+        offset: wgt::BufferAddress,
+    ) -> Result<<Self::A as Api>::Texture, DeviceError>;
+
     unsafe fn create_texture_view(
         &self,
         texture: &<Self::A as Api>::Texture,
@@ -651,6 +828,12 @@ pub trait Device: WasmNotSendSync {
         desc: &BindGroupDescriptor<Self::A>,
     ) -> Result<<Self::A as Api>::BindGroup, DeviceError>;
     unsafe fn destroy_bind_group(&self, group: <Self::A as Api>::BindGroup);
+    /// Return the descriptors of destroyed bind groups to their pools.
+    ///
+    /// Backends may defer freeing the descriptors of a bind group passed to
+    /// [`Device::destroy_bind_group`] until this is called, so that destroying
+    /// bind groups doesn't contend with creating them on other threads.
+    unsafe fn recycle_bind_groups(&self);
 
     unsafe fn create_shader_module(
         &self,
@@ -692,7 +875,8 @@ pub trait Device: WasmNotSendSync {
     /// wait for values yet to be submitted. (This restriction accommodates
     /// implementations like the `vulkan` backend's [`FencePool`] that must
     /// allocate a distinct synchronization object for each fence value one is
-    /// able to wait for.)
+    /// able to wait for. Fences backed by timeline semaphores, or by DX12
+    /// fences, can be waited for ahead of the signal.)
     ///
     /// Calling `wait` with a lower [`FenceValue`] than `fence`'s current value
     /// returns immediately.
@@ -706,9 +890,29 @@ pub trait Device: WasmNotSendSync {
         timeout_ms: u32,
     ) -> Result<bool, DeviceError>;
 
+    /// Set `fence` to `value` from the host.
+    ///
+    /// Queues waiting for `fence` to reach `value` with
+    /// [`Queue::wait_for_fence`] may proceed once this returns.
+    ///
+    /// `value` must be greater than the current value of `fence`, and than
+    /// any value a submission not yet completed is going to store in it.
+    unsafe fn signal_fence(
+        &self,
+        fence: &mut <Self::A as Api>::Fence,
+        value: FenceValue,
+    ) -> Result<(), DeviceError>;
+
     unsafe fn start_capture(&self) -> bool;
     unsafe fn stop_capture(&self);
 
+    /// Tell the platform how this device should trade performance for power.
+    ///
+    /// Only state of the device itself may change, process-wide hints are left
+    /// to [`auxil::power::set_process_performance_hint`]. Backends without an
+    /// equivalent ignore the hint.
+    unsafe fn set_performance_hint(&self, hint: wgt::PerformanceHint);
+
     unsafe fn create_acceleration_structure(
         &self,
         desc: &AccelerationStructureDescriptor,
@@ -800,12 +1004,101 @@ pub trait Queue: WasmNotSendSync {
         surface_textures: &[&<Self::A as Api>::SurfaceTexture],
         signal_fence: (&mut <Self::A as Api>::Fence, FenceValue),
     ) -> Result<(), DeviceError>;
+
+    /// Make the next submission to this queue wait until `fence` reaches `value`.
+    ///
+    /// This orders work across the queues of a single device: the commands of
+    /// the next [`submit`] call don't begin executing until the submission
+    /// that signals `fence` with `value` has completed, and its results are
+    /// visible to them. The wait happens on the GPU where the backend allows it.
+    ///
+    /// Valid usage:
+    ///
+    /// - `fence` must have been created by the [`Device`][d] associated
+    ///   with this queue.
+    ///
+    /// - A submission that signals `fence` with `value` or greater must
+    ///   already have been made to another queue, so that the wait is
+    ///   guaranteed to finish. With timeline semaphores, this may also be a
+    ///   later [`Device::signal_fence`] call, or a signal made outside of
+    ///   `wgpu-hal` to a fence shared with another API.
+    ///
+    /// [`submit`]: Queue::submit
+    /// [d]: Api::Device
+    unsafe fn wait_for_fence(
+        &self,
+        fence: &<Self::A as Api>::Fence,
+        value: FenceValue,
+    ) -> Result<(), DeviceError>;
+    /// Commit pages of `buffer` to memory, or decommit them, and update
+    /// `fence` to `value` once that is done.
+    ///
+    /// This is ordered with the submissions to this queue like another
+    /// [`submit`] call: the binds happen after the submissions made before,
+    /// and before the ones made after.
+    ///
+    /// The memory of decommitted pages is released by a later call for the
+    /// same buffer once `fence` has reached `value`, or when the buffer is
+    /// destroyed.
+    ///
+    /// Valid usage:
+    ///
+    /// - `buffer` must have been created by `device` with
+    ///   [`MemoryFlags::SPARSE`].
+    ///
+    /// - The offsets and sizes of `binds` must be multiples of
+    ///   [`wgt::SPARSE_BUFFER_PAGE_SIZE`], except that a range may end at the
+    ///   end of the buffer. The ranges must be within the buffer.
+    ///
+    /// - All calls for `buffer` must pass the same `fence`.
+    ///
+    /// [`submit`]: Queue::submit
+    unsafe fn bind_sparse_buffer(
+        &self,
+        device: &<Self::A as Api>::Device,
+        buffer: &<Self::A as Api>::Buffer,
+        binds: &[wgt::SparseBufferBind],
+        signal_fence: (&mut <Self::A as Api>::Fence, FenceValue),
+    ) -> Result<(), DeviceError>;
+    /// Commit tiles of `texture` to memory, or decommit them, and update
+    /// `fence` to `value` once that is done.
+    ///
+    /// This is ordered with the submissions to this queue like
+    /// [`bind_sparse_buffer`], and releases the memory of decommitted tiles
+    /// the same way.
+    ///
+    /// Valid usage:
+    ///
+    /// - `texture` must have been created by `device` with
+    ///   [`MemoryFlags::SPARSE`].
+    ///
+    /// - The regions of `binds` must be within their mip level, and aligned
+    ///   to the tiles returned by [`Device::get_sparse_texture_tiles`], except
+    ///   that a region may end at the edge of the level. Regions of levels in
+    ///   the mip tail must cover the whole level.
+    ///
+    /// - All calls for `texture` must pass the same `fence`.
+    ///
+    /// [`bind_sparse_buffer`]: Queue::bind_sparse_buffer
+    unsafe fn bind_sparse_texture(
+        &self,
+        device: &<Self::A as Api>::Device,
+        texture: &<Self::A as Api>::Texture,
+        binds: &[wgt::SparseTextureBind],
+        signal_fence: (&mut <Self::A as Api>::Fence, FenceValue),
+    ) -> Result<(), DeviceError>;
     unsafe fn present(
         &self,
         surface: &<Self::A as Api>::Surface,
         texture: <Self::A as Api>::SurfaceTexture,
     ) -> Result<(), SurfaceError>;
     unsafe fn get_timestamp_period(&self) -> f32;
+    /// Sample the timestamp clock of this queue together with the time of
+    /// the CPU, or return `None` if the backend can't.
+    ///
+    /// The CPU time must come from the clock presentation timestamps use, see
+    /// [`wgt::PresentationTimestamp`].
+    unsafe fn get_timestamp_calibration(&self) -> Option<wgt::TimestampCalibration>;
 }
 
 /// Encoder and allocation pool for `CommandBuffer`s.
@@ -930,10 +1223,31 @@ pub trait CommandEncoder: WasmNotSendSync + fmt::Debug {
     where
         T: Iterator<Item = TextureBarrier<'a, Self::A>>;
 
+    /// Make all memory accesses recorded before visible to those recorded after.
+    ///
+    /// This lets a resource placed in a [`Api::MemoryHeap`] take over memory
+    /// that overlapping resources used before. Textures taking over memory
+    /// must also be transitioned from `TextureUses::UNINITIALIZED`.
+    unsafe fn aliasing_barrier(&mut self);
+
     // copy operations
 
     unsafe fn clear_buffer(&mut self, buffer: &<Self::A as Api>::Buffer, range: MemoryRange);
 
+    /// Fill `range` of `buffer` with `value`, repeated every 4 bytes.
+    ///
+    /// The bytes of `value` are written in little-endian order.
+    ///
+    /// # Safety
+    ///
+    /// - The start and end of `range` must be multiples of 4.
+    unsafe fn fill_buffer(
+        &mut self,
+        buffer: &<Self::A as Api>::Buffer,
+        range: MemoryRange,
+        value: u32,
+    );
+
     unsafe fn copy_buffer_to_buffer<T>(
         &mut self,
         src: &<Self::A as Api>::Buffer,
@@ -969,6 +1283,20 @@ pub trait CommandEncoder: WasmNotSendSync + fmt::Debug {
     ) where
         T: Iterator<Item = TextureCopy>;
 
+    /// Copy a region of one texture into a region of another, scaling it
+    /// with `filter` if the sizes differ.
+    /// Works with a single array layer of 2D color textures.
+    /// Note: `dst` current usage has to be `TextureUses::COPY_DST`.
+    /// Note: requires `Features::TEXTURE_BLIT`.
+    unsafe fn blit_texture(
+        &mut self,
+        src: &<Self::A as Api>::Texture,
+        src_usage: TextureUses,
+        dst: &<Self::A as Api>::Texture,
+        region: TextureBlit,
+        filter: wgt::FilterMode,
+    );
+
     /// Copy from buffer to texture.
     /// Works with a single array layer.
     /// Note: `dst` current usage has to be `TextureUses::COPY_DST`.
@@ -1005,6 +1333,24 @@ pub trait CommandEncoder: WasmNotSendSync + fmt::Debug {
         dynamic_offsets: &[wgt::DynamicOffset],
     );
 
+    /// Sets the bind group at `index` to the given buffers without a bind group
+    /// object, assuming the layout of all the preceding groups to be taken from
+    /// `layout`.
+    ///
+    /// # Safety
+    ///
+    /// - `group_layout` must have been created with [`BindGroupLayoutFlags::INLINE`]
+    ///   and be the bind group layout at `index` of `layout`.
+    /// - `buffers` must contain one binding for each entry of `group_layout`, sorted
+    ///   by binding index.
+    unsafe fn set_bind_group_inline(
+        &mut self,
+        layout: &<Self::A as Api>::PipelineLayout,
+        index: u32,
+        group_layout: &<Self::A as Api>::BindGroupLayout,
+        buffers: &[BufferBinding<'_, Self::A>],
+    );
+
     /// Sets a range in push constant data.
     ///
     /// IMPORTANT: while the data is passed as words, the offset is in bytes!
@@ -1062,8 +1408,40 @@ pub trait CommandEncoder: WasmNotSendSync + fmt::Debug {
     unsafe fn set_vertex_buffer<'a>(&mut self, index: u32, binding: BufferBinding<'a, Self::A>);
     unsafe fn set_viewport(&mut self, rect: &Rect<f32>, depth_range: Range<f32>);
     unsafe fn set_scissor_rect(&mut self, rect: &Rect<u32>);
+    /// Sets the viewport at `index`, which is below [`wgt::MAX_VIEWPORTS`]. Indices other
+    /// than 0 require [`wgt::Features::MULTI_VIEWPORT`].
+    ///
+    /// [`set_viewport`](CommandEncoder::set_viewport) sets the viewport at index 0.
+    unsafe fn set_indexed_viewport(
+        &mut self,
+        index: u32,
+        rect: &Rect<f32>,
+        depth_range: Range<f32>,
+    );
+    /// Sets the scissor rectangle at `index`, see
+    /// [`set_indexed_viewport`](CommandEncoder::set_indexed_viewport).
+    unsafe fn set_indexed_scissor_rect(&mut self, index: u32, rect: &Rect<u32>);
     unsafe fn set_stencil_reference(&mut self, value: u32);
+    /// Sets different stencil reference values for front and back faces.
+    unsafe fn set_stencil_reference_separate(&mut self, front: u32, back: u32);
+    /// Overrides the stencil write masks of the current pipeline until the next
+    /// [`set_render_pipeline`](CommandEncoder::set_render_pipeline).
+    unsafe fn set_stencil_write_mask(&mut self, front: u32, back: u32);
     unsafe fn set_blend_constants(&mut self, color: &[f32; 4]);
+    /// Overrides the front face of the current pipeline until the next
+    /// [`set_render_pipeline`](CommandEncoder::set_render_pipeline).
+    ///
+    /// Requires [`wgt::Features::EXTENDED_DYNAMIC_STATE`], like
+    /// [`set_cull_mode`](CommandEncoder::set_cull_mode) and
+    /// [`set_depth_compare`](CommandEncoder::set_depth_compare).
+    unsafe fn set_front_face(&mut self, front_face: wgt::FrontFace);
+    /// Overrides the cull mode of the current pipeline, see
+    /// [`set_front_face`](CommandEncoder::set_front_face).
+    unsafe fn set_cull_mode(&mut self, cull_mode: Option<wgt::Face>);
+    /// Overrides the depth compare function of the current pipeline, see
+    /// [`set_front_face`](CommandEncoder::set_front_face). Only pipelines with a
+    /// depth aspect in their depth stencil state are affected.
+    unsafe fn set_depth_compare(&mut self, compare: wgt::CompareFunction);
 
     unsafe fn draw(
         &mut self,
@@ -1162,6 +1540,10 @@ bitflags!(
     pub struct BindGroupLayoutFlags: u32 {
         /// Allows for bind group binding arrays to be shorter than the array in the BGL.
         const PARTIALLY_BOUND = 1 << 0;
+        /// The bind group layout is only used with
+        /// [`CommandEncoder::set_bind_group_inline`], and only has buffer entries
+        /// without dynamic offsets.
+        const INLINE = 1 << 1;
     }
 );
 
@@ -1265,7 +1647,7 @@ impl From<wgt::TextureFormat> for FormatAspects {
             wgt::TextureFormat::Depth32FloatStencil8 | wgt::TextureFormat::Depth24PlusStencil8 => {
                 Self::DEPTH_STENCIL
             }
-            wgt::TextureFormat::NV12 => Self::PLANE_0 | Self::PLANE_1,
+            wgt::TextureFormat::NV12 | wgt::TextureFormat::P010 => Self::PLANE_0 | Self::PLANE_1,
             _ => Self::COLOR,
         }
     }
@@ -1276,6 +1658,23 @@ bitflags!(
     pub struct MemoryFlags: u32 {
         const TRANSIENT = 1 << 0;
         const PREFER_COHERENT = 1 << 1;
+        /// Create the buffer or texture without any memory, to be committed page
+        /// by page with [`Queue::bind_sparse_buffer`], or tile by tile with
+        /// [`Queue::bind_sparse_texture`].
+        ///
+        /// Only valid for buffers if [`wgt::Features::SPARSE_BUFFER`] is enabled,
+        /// and for textures if [`wgt::Features::SPARSE_TEXTURE`] is.
+        const SPARSE = 1 << 2;
+        /// Make the address of the buffer available with
+        /// [`Device::get_buffer_device_address`].
+        ///
+        /// Only valid for buffers, if [`wgt::Features::BUFFER_DEVICE_ADDRESS`] is enabled.
+        const DEVICE_ADDRESS = 1 << 3;
+        /// Back the texture with on-chip tile memory only, if the backend can.
+        ///
+        /// Only valid for textures only used as render pass attachments, whose
+        /// contents are never loaded or stored.
+        const MEMORYLESS = 1 << 4;
     }
 );
 
@@ -1422,6 +1821,12 @@ pub struct SurfaceCapabilities {
     /// - `maximum_frame_latency.end` must be larger or equal to `maximum_frame_latency.start`.
     pub maximum_frame_latency: RangeInclusive<u32>,
 
+    /// Range for the minimum number of swapchain textures.
+    ///
+    /// - `image_count.start` must be at least 1.
+    /// - `image_count.end` must be larger or equal to `image_count.start`.
+    pub image_count: RangeInclusive<u32>,
+
     /// Current extent of the surface, if known.
     pub current_extent: Option<wgt::Extent3d>,
 
@@ -1439,6 +1844,33 @@ pub struct SurfaceCapabilities {
     ///
     /// Must be at least one.
     pub composite_alpha_modes: Vec<wgt::CompositeAlphaMode>,
+
+    /// List of supported pre-transforms.
+    ///
+    /// Must contain `SurfaceTransform::Identity`, which backends must accept
+    /// even if the surface only supports its current transform.
+    pub transforms: Vec<wgt::SurfaceTransform>,
+
+    /// Current rotation of the display relative to its native orientation.
+    pub current_transform: wgt::SurfaceTransform,
+
+    /// List of supported combinations of format and color space.
+    ///
+    /// Must contain `ColorSpace::Srgb` for each of `formats`.
+    pub color_spaces: Vec<(wgt::TextureFormat, wgt::ColorSpace)>,
+
+    /// Maximum number of array layers of the surface textures.
+    ///
+    /// Must be at least 1.
+    pub max_array_layers: u32,
+
+    /// List of supported fullscreen modes.
+    ///
+    /// Must contain `FullscreenMode::Windowed`.
+    pub fullscreen_modes: Vec<wgt::FullscreenMode>,
+
+    /// List of modes the display can be driven in with exclusive fullscreen.
+    pub display_modes: Vec<wgt::DisplayMode>,
 }
 
 #[derive(Debug)]
@@ -1454,6 +1886,26 @@ pub struct AcquiredSurfaceTexture<A: Api> {
 pub struct OpenDevice<A: Api> {
     pub device: A::Device,
     pub queue: A::Queue,
+    /// Queues created in addition to `queue`, as requested by
+    /// [`wgt::Limits::max_additional_queues`].
+    ///
+    /// Command buffers created from encoders associated with `queue` may be
+    /// submitted to any of these queues. Each of them must be freed with
+    /// [`Device::destroy_queue`] before the device exits.
+    pub additional_queues: Vec<A::Queue>,
+    /// A queue dedicated to copies, usually backed by a DMA engine, that can
+    /// run uploads without holding up `queue`.
+    ///
+    /// Only command buffers created from encoders associated with this queue
+    /// may be submitted to it, and those encoders may only record buffer and
+    /// texture transitions and copies from buffers. Barriers recorded there
+    /// don't order anything that happened on other queues; use
+    /// [`Queue::wait_for_fence`] for that. Buffers and textures created by the
+    /// device can be used on this queue and on `queue` without any ownership
+    /// transfer, but those created from raw handles may not be used here.
+    ///
+    /// Must be freed with [`Device::destroy_queue`] before the device exits.
+    pub transfer_queue: Option<A::Queue>,
 }
 
 #[derive(Clone, Debug)]
@@ -1505,6 +1957,12 @@ impl TextureDescriptor<'_> {
     }
 }
 
+#[derive(Clone, Debug)]
+pub struct MemoryHeapDescriptor<'a> {
+    pub label: Label<'a>,
+    pub size: wgt::BufferAddress,
+}
+
 /// TextureView descriptor.
 ///
 /// Valid usage:
@@ -1519,6 +1977,8 @@ pub struct TextureViewDescriptor<'a> {
     pub dimension: wgt::TextureViewDimension,
     pub usage: TextureUses,
     pub range: wgt::ImageSubresourceRange,
+    /// Must be the identity unless `usage` is `RESOURCE`.
+    pub swizzle: wgt::TextureComponentSwizzle,
 }
 
 #[derive(Clone, Debug)]
@@ -1529,12 +1989,17 @@ pub struct SamplerDescriptor<'a> {
     pub min_filter: wgt::FilterMode,
     pub mipmap_filter: wgt::FilterMode,
     pub lod_clamp: Range<f32>,
+    /// Must be finite, and zero unless `SAMPLER_LOD_BIAS` is supported.
+    /// Backends clamp it to the range they support.
+    pub lod_bias: f32,
     pub compare: Option<wgt::CompareFunction>,
     // Must in the range [1, 16].
     //
     // Anisotropic filtering must be supported if this is not 1.
     pub anisotropy_clamp: u16,
     pub border_color: Option<wgt::SamplerBorderColor>,
+    /// Must be `WeightedAverage` if `compare` is set.
+    pub reduction_mode: wgt::SamplerReductionMode,
 }
 
 /// BindGroupLayout descriptor.
@@ -1693,6 +2158,47 @@ pub struct ProgrammableStage<'a, A: Api> {
     /// This is required by the WebGPU spec, but may have overhead which can be avoided
     /// for cross-platform applications
     pub zero_initialize_workgroup_memory: bool,
+    /// If set, receives the code the backend compiles this stage to before passing
+    /// it on to the driver.
+    pub code_capture: Option<&'a ShaderCodeCapture>,
+}
+
+/// Code a backend compiled a pipeline stage to, see [`ShaderCodeCapture`].
+#[derive(Clone, Debug)]
+pub struct CapturedShaderCode {
+    /// The file extension of the code, like `spv`, `hlsl`, `dxil`, `metal` or `glsl`.
+    pub extension: &'static str,
+    pub code: Vec<u8>,
+}
+
+/// Collects the code backends compile pipeline stages to, so that it can be
+/// inspected or sent to driver vendors along with the shaders it came from.
+///
+/// Backends capture every intermediate form they generate, such as both the
+/// HLSL and the DXIL of a stage. Stages compiled from code passed through to the
+/// backend as it is aren't captured.
+#[derive(Debug, Default)]
+pub struct ShaderCodeCapture {
+    code: Mutex<Vec<CapturedShaderCode>>,
+}
+
+impl ShaderCodeCapture {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Called by backends with code they compiled.
+    pub fn capture(&self, extension: &'static str, code: impl Into<Vec<u8>>) {
+        self.code.lock().push(CapturedShaderCode {
+            extension,
+            code: code.into(),
+        });
+    }
+
+    /// Returns the code captured so far, and clears it.
+    pub fn take(&self) -> Vec<CapturedShaderCode> {
+        std::mem::take(&mut *self.code.lock())
+    }
 }
 
 // Rust gets confused about the impl requirements for `A`
@@ -1703,6 +2209,7 @@ impl<A: Api> Clone for ProgrammableStage<'_, A> {
             entry_point: self.entry_point,
             constants: self.constants,
             zero_initialize_workgroup_memory: self.zero_initialize_workgroup_memory,
+            code_capture: self.code_capture,
         }
     }
 }
@@ -1758,6 +2265,9 @@ pub struct SurfaceConfiguration {
     /// Maximum number of queued frames. Must be in
     /// `SurfaceCapabilities::maximum_frame_latency` range.
     pub maximum_frame_latency: u32,
+    /// Minimum number of swapchain textures. Must be in
+    /// `SurfaceCapabilities::image_count` range.
+    pub image_count: u32,
     /// Vertical synchronization mode.
     pub present_mode: wgt::PresentMode,
     /// Alpha composition mode.
@@ -1765,13 +2275,26 @@ pub struct SurfaceConfiguration {
     /// Format of the surface textures.
     pub format: wgt::TextureFormat,
     /// Requested texture extent. Must be in
-    /// `SurfaceCapabilities::extents` range.
+    /// `SurfaceCapabilities::extents` range, with at most
+    /// `SurfaceCapabilities::max_array_layers` array layers.
     pub extent: wgt::Extent3d,
     /// Allowed usage of surface textures,
     pub usage: TextureUses,
     /// Allows views of swapchain texture to have a different format
     /// than the texture does.
     pub view_formats: Vec<wgt::TextureFormat>,
+    /// Rotation applied by the application before presenting. Must be in
+    /// `SurfaceCapabilities::transforms`.
+    pub pre_transform: wgt::SurfaceTransform,
+    /// Color space the surface textures are displayed in. Must be in
+    /// `SurfaceCapabilities::color_spaces` together with `format`.
+    pub color_space: wgt::ColorSpace,
+    /// How the surface covers the display. Must be in
+    /// `SurfaceCapabilities::fullscreen_modes`.
+    pub fullscreen: wgt::FullscreenMode,
+    /// Mode to drive the display in. Only set with exclusive fullscreen, to
+    /// one of `SurfaceCapabilities::display_modes`.
+    pub display_mode: Option<wgt::DisplayMode>,
 }
 
 #[derive(Debug, Clone)]
@@ -1826,6 +2349,14 @@ pub struct TextureCopy {
     pub size: CopyExtent,
 }
 
+#[derive(Clone, Debug)]
+pub struct TextureBlit {
+    pub src_base: TextureCopyBase,
+    pub src_size: CopyExtent,
+    pub dst_base: TextureCopyBase,
+    pub dst_size: CopyExtent,
+}
+
 #[derive(Clone, Debug)]
 pub struct BufferTextureCopy {
     pub buffer_layout: wgt::ImageDataLayout,
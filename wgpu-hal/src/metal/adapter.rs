@@ -16,6 +16,78 @@ impl super::Adapter {
     pub(super) fn new(shared: Arc<super::AdapterShared>) -> Self {
         Self { shared }
     }
+
+    /// Capabilities of a `CAMetalLayer` presented with this adapter.
+    fn layer_capabilities(
+        &self,
+        current_extent: Option<wgt::Extent3d>,
+    ) -> crate::SurfaceCapabilities {
+        let mut formats = vec![
+            wgt::TextureFormat::Bgra8Unorm,
+            wgt::TextureFormat::Bgra8UnormSrgb,
+            wgt::TextureFormat::Rgba16Float,
+        ];
+        if self.shared.private_caps.format_rgb10a2_unorm_all {
+            formats.push(wgt::TextureFormat::Rgb10a2Unorm);
+        }
+
+        let mut color_spaces: Vec<_> = formats
+            .iter()
+            .map(|&format| (format, wgt::ColorSpace::Srgb))
+            .collect();
+        // `CAMetalLayer::colorspace` is only respected on macOS.
+        if cfg!(target_os = "macos") {
+            color_spaces.extend(
+                formats
+                    .iter()
+                    .map(|&format| (format, wgt::ColorSpace::DisplayP3)),
+            );
+            color_spaces.push((wgt::TextureFormat::Rgba16Float, wgt::ColorSpace::Bt2020));
+            if formats.contains(&wgt::TextureFormat::Rgb10a2Unorm) {
+                color_spaces.push((wgt::TextureFormat::Rgb10a2Unorm, wgt::ColorSpace::Hdr10));
+            }
+        }
+
+        let pc = &self.shared.private_caps;
+        crate::SurfaceCapabilities {
+            formats,
+            // We use this here to govern the maximum number of drawables + 1.
+            // See https://developer.apple.com/documentation/quartzcore/cametallayer/2938720-maximumdrawablecount
+            maximum_frame_latency: if pc.can_set_maximum_drawables_count {
+                1..=2
+            } else {
+                // 3 is the default value for maximum drawables in `CAMetalLayer` documentation
+                // iOS 10.3 was tested to use 3 on iphone5s
+                2..=2
+            },
+            // `maximumDrawableCount` can only be 2 or 3.
+            image_count: if pc.can_set_maximum_drawables_count {
+                2..=3
+            } else {
+                3..=3
+            },
+            present_modes: if pc.can_set_display_sync {
+                vec![wgt::PresentMode::Fifo, wgt::PresentMode::Immediate]
+            } else {
+                vec![wgt::PresentMode::Fifo]
+            },
+            composite_alpha_modes: vec![
+                wgt::CompositeAlphaMode::Opaque,
+                wgt::CompositeAlphaMode::PostMultiplied,
+            ],
+
+            current_extent,
+            usage: crate::TextureUses::COLOR_TARGET
+                | crate::TextureUses::COPY_SRC
+                | crate::TextureUses::COPY_DST,
+            transforms: vec![wgt::SurfaceTransform::Identity],
+            current_transform: wgt::SurfaceTransform::Identity,
+            color_spaces,
+            max_array_layers: 1,
+            fullscreen_modes: vec![wgt::FullscreenMode::Windowed],
+            display_modes: Vec::new(),
+        }
+    }
 }
 
 impl crate::Adapter for super::Adapter {
@@ -25,6 +97,8 @@ impl crate::Adapter for super::Adapter {
         &self,
         features: wgt::Features,
         _limits: &wgt::Limits,
+        _queue_priority: wgt::QueuePriority,
+        _transfer_queue: bool,
     ) -> Result<crate::OpenDevice<super::Api>, crate::DeviceError> {
         let queue = self
             .shared
@@ -67,6 +141,8 @@ impl crate::Adapter for super::Adapter {
                 raw: Arc::new(Mutex::new(queue)),
                 timestamp_period,
             },
+            additional_queues: Vec::new(),
+            transfer_queue: None,
         })
     }
 
@@ -234,7 +310,7 @@ impl crate::Adapter for super::Adapter {
                 }
                 flags
             }
-            Tf::NV12 => return Tfc::empty(),
+            Tf::NV12 | Tf::P010 => return Tfc::empty(),
             Tf::Rgb9e5Ufloat => {
                 if pc.msaa_apple3 {
                     all_caps
@@ -310,42 +386,15 @@ impl crate::Adapter for super::Adapter {
             None
         };
 
-        let mut formats = vec![
-            wgt::TextureFormat::Bgra8Unorm,
-            wgt::TextureFormat::Bgra8UnormSrgb,
-            wgt::TextureFormat::Rgba16Float,
-        ];
-        if self.shared.private_caps.format_rgb10a2_unorm_all {
-            formats.push(wgt::TextureFormat::Rgb10a2Unorm);
-        }
-
-        let pc = &self.shared.private_caps;
-        Some(crate::SurfaceCapabilities {
-            formats,
-            // We use this here to govern the maximum number of drawables + 1.
-            // See https://developer.apple.com/documentation/quartzcore/cametallayer/2938720-maximumdrawablecount
-            maximum_frame_latency: if pc.can_set_maximum_drawables_count {
-                1..=2
-            } else {
-                // 3 is the default value for maximum drawables in `CAMetalLayer` documentation
-                // iOS 10.3 was tested to use 3 on iphone5s
-                2..=2
-            },
-            present_modes: if pc.can_set_display_sync {
-                vec![wgt::PresentMode::Fifo, wgt::PresentMode::Immediate]
-            } else {
-                vec![wgt::PresentMode::Fifo]
-            },
-            composite_alpha_modes: vec![
-                wgt::CompositeAlphaMode::Opaque,
-                wgt::CompositeAlphaMode::PostMultiplied,
-            ],
+        Some(self.layer_capabilities(current_extent))
+    }
 
-            current_extent,
-            usage: crate::TextureUses::COLOR_TARGET
-                | crate::TextureUses::COPY_SRC
-                | crate::TextureUses::COPY_DST,
-        })
+    unsafe fn headless_surface_capabilities(
+        &self,
+        _display_handle: raw_window_handle::RawDisplayHandle,
+    ) -> Option<crate::SurfaceCapabilities> {
+        // Nothing about a `CAMetalLayer` depends on the window it's attached to.
+        Some(self.layer_capabilities(None))
     }
 
     unsafe fn get_presentation_timestamp(&self) -> wgt::PresentationTimestamp {
@@ -809,6 +858,10 @@ impl super::PrivateCapabilities {
             supports_preserve_invariance: version.at_least((11, 0), (13, 0), os_is_mac),
             // Metal 2.2 on mac, 2.3 on iOS.
             supports_shader_primitive_index: version.at_least((10, 15), (14, 0), os_is_mac),
+            // `setViewports:count:` and `[[viewport_array_index]]` are macOS only.
+            supports_multi_viewport: os_is_mac && version.at_least((10, 13), (11, 0), os_is_mac),
+            supports_raster_order_groups: version.at_least((10, 13), (11, 0), os_is_mac)
+                && device.raster_order_groups_supported(),
             has_unified_memory: if version.at_least((10, 15), (13, 0), os_is_mac) {
                 Some(device.has_unified_memory())
             } else {
@@ -823,6 +876,12 @@ impl super::PrivateCapabilities {
             int64: family_check
                 && (device.supports_family(MTLGPUFamily::Apple3)
                     || device.supports_family(MTLGPUFamily::Metal3)),
+            // `MTLBuffer.gpuAddress` is only available from Metal 3.
+            buffer_device_address: family_check && device.supports_family(MTLGPUFamily::Metal3),
+            texture_swizzle: family_check,
+            // https://developer.apple.com/metal/Metal-Feature-Set-Tables.pdf#page=3
+            supports_memoryless_storage: family_check
+                && device.supports_family(MTLGPUFamily::Apple2),
         }
     }
 
@@ -839,6 +898,7 @@ impl super::PrivateCapabilities {
 
         let mut features = F::empty()
             | F::MAPPABLE_PRIMARY_BUFFERS
+            | F::PERSISTENT_MAPPING
             | F::VERTEX_WRITABLE_STORAGE
             | F::TEXTURE_ADAPTER_SPECIFIC_FORMAT_FEATURES
             | F::PUSH_CONSTANTS
@@ -847,9 +907,13 @@ impl super::PrivateCapabilities {
             | F::TEXTURE_FORMAT_16BIT_NORM
             | F::SHADER_F16
             | F::DEPTH32FLOAT_STENCIL8
-            | F::BGRA8UNORM_STORAGE;
+            | F::BGRA8UNORM_STORAGE
+            | F::EXTENDED_DYNAMIC_STATE
+            | F::INLINE_BIND_GROUPS
+            | F::IOSURFACE;
 
         features.set(F::FLOAT32_FILTERABLE, self.supports_float_filtering);
+        features.set(F::TEXTURE_COMPONENT_SWIZZLE, self.texture_swizzle);
         features.set(
             F::INDIRECT_FIRST_INSTANCE | F::MULTI_DRAW_INDIRECT,
             self.indirect_draw_dispatch,
@@ -878,6 +942,10 @@ impl super::PrivateCapabilities {
             F::SHADER_PRIMITIVE_INDEX,
             self.supports_shader_primitive_index,
         );
+        features.set(
+            F::MULTI_VIEWPORT,
+            self.supports_multi_viewport && self.msl_version >= MTLLanguageVersion::V2_0,
+        );
 
         features.set(
             F::TEXTURE_BINDING_ARRAY
@@ -898,6 +966,11 @@ impl super::PrivateCapabilities {
             F::SHADER_INT64,
             self.int64 && self.msl_version >= MTLLanguageVersion::V2_3,
         );
+        features.set(F::BUFFER_DEVICE_ADDRESS, self.buffer_device_address);
+        features.set(
+            F::FRAGMENT_SHADER_INTERLOCK,
+            self.supports_raster_order_groups && self.msl_version >= MTLLanguageVersion::V2_0,
+        );
 
         features.set(
             F::ADDRESS_MODE_CLAMP_TO_BORDER,
@@ -942,6 +1015,10 @@ impl super::PrivateCapabilities {
         downlevel
             .flags
             .set(wgt::DownlevelFlags::ANISOTROPIC_FILTERING, true);
+        downlevel.flags.set(
+            wgt::DownlevelFlags::UNIFIED_MEMORY_MAPPABLE_BUFFERS,
+            self.has_unified_memory.unwrap_or(false),
+        );
 
         let base = wgt::Limits::default();
         crate::Capabilities {
@@ -984,6 +1061,7 @@ impl super::PrivateCapabilities {
                 max_compute_workgroups_per_dimension: 0xFFFF,
                 max_buffer_size: self.max_buffer_size,
                 max_non_sampler_bindings: std::u32::MAX,
+                max_additional_queues: 0,
             },
             alignments: crate::Alignments {
                 buffer_copy_offset: wgt::BufferSize::new(self.buffer_alignment).unwrap(),
@@ -1057,7 +1135,7 @@ impl super::PrivateCapabilities {
                     Depth32Float_Stencil8
                 }
             }
-            Tf::NV12 => unreachable!(),
+            Tf::NV12 | Tf::P010 => unreachable!(),
             Tf::Rgb9e5Ufloat => RGB9E5Float,
             Tf::Bc1RgbaUnorm => BC1_RGBA,
             Tf::Bc1RgbaUnormSrgb => BC1_RGBA_sRGB,
@@ -1,9 +1,12 @@
 use super::{conv, AsNative, TimestampQuerySupport};
 use crate::CommandEncoder as _;
+use objc::{msg_send, sel, sel_impl};
 use std::{borrow::Cow, mem, ops::Range};
 
 // has to match `Temp::binding_sizes`
 const WORD_SIZE: usize = 4;
+/// Largest buffer allocated for a fill pattern, larger fills copy it repeatedly.
+const MAX_FILL_PATTERN_SIZE: wgt::BufferAddress = 64 << 10;
 
 impl Default for super::CommandState {
     fn default() -> Self {
@@ -19,11 +22,42 @@ impl Default for super::CommandState {
             work_group_memory_sizes: Vec::new(),
             push_constants: Vec::new(),
             pending_timer_queries: Vec::new(),
+            depth_compare_states: Vec::new(),
+            viewports: [metal::MTLViewport {
+                originX: 0.0,
+                originY: 0.0,
+                width: 0.0,
+                height: 0.0,
+                znear: 0.0,
+                zfar: 1.0,
+            }; wgt::MAX_VIEWPORTS as usize],
+            scissor_rects: [metal::MTLScissorRect {
+                x: 0,
+                y: 0,
+                width: 0,
+                height: 0,
+            }; wgt::MAX_VIEWPORTS as usize],
         }
     }
 }
 
 impl super::CommandEncoder {
+    fn map_viewport(&self, rect: &crate::Rect<f32>, depth_range: Range<f32>) -> metal::MTLViewport {
+        let zfar = if self.shared.disabilities.broken_viewport_near_depth {
+            depth_range.end - depth_range.start
+        } else {
+            depth_range.end
+        };
+        metal::MTLViewport {
+            originX: rect.x as _,
+            originY: rect.y as _,
+            width: rect.w as _,
+            height: rect.h as _,
+            znear: depth_range.start as _,
+            zfar: zfar as _,
+        }
+    }
+
     fn enter_blit(&mut self) -> &metal::BlitCommandEncoderRef {
         if self.state.blit.is_none() {
             debug_assert!(self.state.render.is_none() && self.state.compute.is_none());
@@ -202,6 +236,7 @@ impl crate::CommandEncoder for super::CommandEncoder {
             encoder.end_encoding();
         }
         self.raw_cmd_buf = None;
+        self.temp.fill_patterns.clear();
     }
 
     unsafe fn end_encoding(&mut self) -> Result<super::CommandBuffer, crate::DeviceError> {
@@ -218,6 +253,7 @@ impl crate::CommandEncoder for super::CommandEncoder {
 
         Ok(super::CommandBuffer {
             raw: self.raw_cmd_buf.take().unwrap(),
+            fill_patterns: mem::take(&mut self.temp.fill_patterns),
         })
     }
 
@@ -240,11 +276,47 @@ impl crate::CommandEncoder for super::CommandEncoder {
     {
     }
 
+    unsafe fn aliasing_barrier(&mut self) {
+        unimplemented!()
+    }
+
     unsafe fn clear_buffer(&mut self, buffer: &super::Buffer, range: crate::MemoryRange) {
         let encoder = self.enter_blit();
         encoder.fill_buffer(&buffer.raw, conv::map_range(&range), 0);
     }
 
+    unsafe fn fill_buffer(
+        &mut self,
+        buffer: &super::Buffer,
+        range: crate::MemoryRange,
+        value: u32,
+    ) {
+        let bytes = value.to_le_bytes();
+        if bytes.iter().all(|&byte| byte == bytes[0]) {
+            let encoder = self.enter_blit();
+            encoder.fill_buffer(&buffer.raw, conv::map_range(&range), bytes[0]);
+            return;
+        }
+
+        // Blit encoders can only fill with a single byte, so copy from a buffer
+        // holding the pattern instead.
+        let pattern_size = (range.end - range.start).min(MAX_FILL_PATTERN_SIZE);
+        let pattern = bytes.repeat(pattern_size as usize / 4);
+        let pattern_buffer = self.shared.device.lock().new_buffer_with_data(
+            pattern.as_ptr().cast(),
+            pattern_size,
+            metal::MTLResourceOptions::StorageModeShared,
+        );
+        let encoder = self.enter_blit();
+        let mut offset = range.start;
+        while offset < range.end {
+            let size = pattern_size.min(range.end - offset);
+            encoder.copy_from_buffer(&pattern_buffer, 0, &buffer.raw, offset, size);
+            offset += size;
+        }
+        self.temp.fill_patterns.push(pattern_buffer);
+    }
+
     unsafe fn copy_buffer_to_buffer<T>(
         &mut self,
         src: &super::Buffer,
@@ -265,6 +337,17 @@ impl crate::CommandEncoder for super::CommandEncoder {
         }
     }
 
+    unsafe fn blit_texture(
+        &mut self,
+        _src: &super::Texture,
+        _src_usage: crate::TextureUses,
+        _dst: &super::Texture,
+        _region: crate::TextureBlit,
+        _filter: wgt::FilterMode,
+    ) {
+        unreachable!("`TEXTURE_BLIT` isn't exposed")
+    }
+
     unsafe fn copy_texture_to_texture<T>(
         &mut self,
         src: &super::Texture,
@@ -623,6 +706,22 @@ impl crate::CommandEncoder for super::CommandEncoder {
             }
             self.state.render = Some(encoder.to_owned());
         });
+
+        // Matches the defaults of the encoder for the first viewport.
+        self.state.viewports = [metal::MTLViewport {
+            originX: 0.0,
+            originY: 0.0,
+            width: desc.extent.width as _,
+            height: desc.extent.height as _,
+            znear: 0.0,
+            zfar: 1.0,
+        }; wgt::MAX_VIEWPORTS as usize];
+        self.state.scissor_rects = [metal::MTLScissorRect {
+            x: 0,
+            y: 0,
+            width: desc.extent.width as _,
+            height: desc.extent.height as _,
+        }; wgt::MAX_VIEWPORTS as usize];
     }
 
     unsafe fn end_render_pass(&mut self) {
@@ -796,6 +895,33 @@ impl crate::CommandEncoder for super::CommandEncoder {
         }
     }
 
+    unsafe fn set_bind_group_inline(
+        &mut self,
+        layout: &super::PipelineLayout,
+        group_index: u32,
+        group_layout: &super::BindGroupLayout,
+        buffers: &[crate::BufferBinding<super::Api>],
+    ) {
+        // Metal binds buffers directly to the encoder, so an inline group is
+        // a bind group that only lives for this call.
+        let mut group = super::BindGroup::default();
+        for (&stage, counter) in super::NAGA_STAGES.iter().zip(group.counters.iter_mut()) {
+            let stage_bit = crate::auxil::map_naga_stage(stage);
+            for (entry, source) in group_layout.entries.iter().zip(buffers) {
+                if !entry.visibility.contains(stage_bit) {
+                    continue;
+                }
+                if let wgt::BindingType::Buffer { ty, .. } = entry.ty {
+                    group
+                        .buffers
+                        .push(super::BufferResource::new(source, ty, None, entry.binding));
+                    counter.buffers += 1;
+                }
+            }
+        }
+        unsafe { self.set_bind_group(layout, group_index, &group, &[]) };
+    }
+
     unsafe fn set_push_constants(
         &mut self,
         layout: &super::PipelineLayout,
@@ -875,6 +1001,9 @@ impl crate::CommandEncoder for super::CommandEncoder {
             encoder.set_depth_stencil_state(state);
             encoder.set_depth_bias(bias.constant as f32, bias.slope_scale, bias.clamp);
         }
+        self.state
+            .depth_compare_states
+            .clone_from(&pipeline.depth_compare_states);
 
         {
             if let Some((index, sizes)) = self
@@ -930,20 +1059,10 @@ impl crate::CommandEncoder for super::CommandEncoder {
     }
 
     unsafe fn set_viewport(&mut self, rect: &crate::Rect<f32>, depth_range: Range<f32>) {
-        let zfar = if self.shared.disabilities.broken_viewport_near_depth {
-            depth_range.end - depth_range.start
-        } else {
-            depth_range.end
-        };
+        let viewport = self.map_viewport(rect, depth_range);
+        self.state.viewports[0] = viewport;
         let encoder = self.state.render.as_ref().unwrap();
-        encoder.set_viewport(metal::MTLViewport {
-            originX: rect.x as _,
-            originY: rect.y as _,
-            width: rect.w as _,
-            height: rect.h as _,
-            znear: depth_range.start as _,
-            zfar: zfar as _,
-        });
+        encoder.set_viewport(viewport);
     }
     unsafe fn set_scissor_rect(&mut self, rect: &crate::Rect<u32>) {
         //TODO: support empty scissors by modifying the viewport
@@ -953,17 +1072,63 @@ impl crate::CommandEncoder for super::CommandEncoder {
             width: rect.w as _,
             height: rect.h as _,
         };
+        self.state.scissor_rects[0] = scissor;
         let encoder = self.state.render.as_ref().unwrap();
         encoder.set_scissor_rect(scissor);
     }
+    unsafe fn set_indexed_viewport(
+        &mut self,
+        index: u32,
+        rect: &crate::Rect<f32>,
+        depth_range: Range<f32>,
+    ) {
+        self.state.viewports[index as usize] = self.map_viewport(rect, depth_range);
+        let encoder = self.state.render.as_ref().unwrap();
+        let viewports = self.state.viewports.as_ptr();
+        let count = wgt::MAX_VIEWPORTS as metal::NSUInteger;
+        let () = unsafe { msg_send![encoder.as_ref(), setViewports: viewports count: count] };
+    }
+    unsafe fn set_indexed_scissor_rect(&mut self, index: u32, rect: &crate::Rect<u32>) {
+        self.state.scissor_rects[index as usize] = metal::MTLScissorRect {
+            x: rect.x as _,
+            y: rect.y as _,
+            width: rect.w as _,
+            height: rect.h as _,
+        };
+        let encoder = self.state.render.as_ref().unwrap();
+        let rects = self.state.scissor_rects.as_ptr();
+        let count = wgt::MAX_VIEWPORTS as metal::NSUInteger;
+        let () = unsafe { msg_send![encoder.as_ref(), setScissorRects: rects count: count] };
+    }
     unsafe fn set_stencil_reference(&mut self, value: u32) {
         let encoder = self.state.render.as_ref().unwrap();
         encoder.set_stencil_front_back_reference_value(value, value);
     }
+    unsafe fn set_stencil_reference_separate(&mut self, front: u32, back: u32) {
+        let encoder = self.state.render.as_ref().unwrap();
+        encoder.set_stencil_front_back_reference_value(front, back);
+    }
+    unsafe fn set_stencil_write_mask(&mut self, _front: u32, _back: u32) {
+        unreachable!("`SEPARATE_STENCIL_STATE` isn't exposed")
+    }
     unsafe fn set_blend_constants(&mut self, color: &[f32; 4]) {
         let encoder = self.state.render.as_ref().unwrap();
         encoder.set_blend_color(color[0], color[1], color[2], color[3]);
     }
+    unsafe fn set_front_face(&mut self, front_face: wgt::FrontFace) {
+        let encoder = self.state.render.as_ref().unwrap();
+        encoder.set_front_facing_winding(conv::map_winding(front_face));
+    }
+    unsafe fn set_cull_mode(&mut self, cull_mode: Option<wgt::Face>) {
+        let encoder = self.state.render.as_ref().unwrap();
+        encoder.set_cull_mode(conv::map_cull_mode(cull_mode));
+    }
+    unsafe fn set_depth_compare(&mut self, compare: wgt::CompareFunction) {
+        let encoder = self.state.render.as_ref().unwrap();
+        if let Some(state) = self.state.depth_compare_states.get(compare as usize - 1) {
+            encoder.set_depth_stencil_state(state);
+        }
+    }
 
     unsafe fn draw(
         &mut self,
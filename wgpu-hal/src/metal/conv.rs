@@ -43,6 +43,36 @@ pub fn map_texture_view_dimension(dim: wgt::TextureViewDimension) -> metal::MTLT
     }
 }
 
+/// `MTLTextureSwizzleChannels`, which `metal` doesn't expose.
+#[repr(C)]
+pub struct TextureSwizzleChannels {
+    pub red: u8,
+    pub green: u8,
+    pub blue: u8,
+    pub alpha: u8,
+}
+
+pub fn map_swizzle(swizzle: wgt::TextureComponentSwizzle) -> TextureSwizzleChannels {
+    // `MTLTextureSwizzle`
+    fn map_component(component: wgt::ComponentSwizzle) -> u8 {
+        match component {
+            wgt::ComponentSwizzle::Zero => 0,
+            wgt::ComponentSwizzle::One => 1,
+            wgt::ComponentSwizzle::R => 2,
+            wgt::ComponentSwizzle::G => 3,
+            wgt::ComponentSwizzle::B => 4,
+            wgt::ComponentSwizzle::A => 5,
+        }
+    }
+
+    TextureSwizzleChannels {
+        red: map_component(swizzle.r),
+        green: map_component(swizzle.g),
+        blue: map_component(swizzle.b),
+        alpha: map_component(swizzle.a),
+    }
+}
+
 pub fn map_compare_function(fun: wgt::CompareFunction) -> metal::MTLCompareFunction {
     use metal::MTLCompareFunction::*;
     use wgt::CompareFunction as Cf;
@@ -84,7 +114,7 @@ pub fn map_border_color(border_color: wgt::SamplerBorderColor) -> metal::MTLSamp
         wgt::SamplerBorderColor::TransparentBlack => TransparentBlack,
         wgt::SamplerBorderColor::OpaqueBlack => OpaqueBlack,
         wgt::SamplerBorderColor::OpaqueWhite => OpaqueWhite,
-        wgt::SamplerBorderColor::Zero => unreachable!(),
+        wgt::SamplerBorderColor::Zero | wgt::SamplerBorderColor::Custom(_) => unreachable!(),
     }
 }
 
@@ -1,3 +1,4 @@
+use objc::{msg_send, sel, sel_impl};
 use parking_lot::Mutex;
 use std::{
     num::NonZeroU32,
@@ -132,6 +133,9 @@ impl super::Device {
             naga_stage,
             &source
         );
+        if let Some(capture) = stage.code_capture {
+            capture.capture("metal", source.as_bytes());
+        }
 
         let options = metal::CompileOptions::new();
         options.set_language_version(self.shared.private_caps.msl_version);
@@ -275,6 +279,92 @@ impl super::Device {
     pub fn raw_device(&self) -> &Mutex<metal::Device> {
         &self.shared.device
     }
+
+    /// Returns `true` if [`Self::create_iosurface_texture`] can create
+    /// textures of `format`.
+    pub fn supports_iosurface_format(format: wgt::TextureFormat) -> bool {
+        super::iosurface::map_pixel_format(format).is_some()
+    }
+
+    /// Creates a texture backed by a new IOSurface, which
+    /// [`super::Texture::iosurface`] returns to share it.
+    ///
+    /// # Safety
+    ///
+    /// - `desc` must be valid, like for [`crate::Device::create_texture`], and
+    ///   describe a single sampled 2D texture with one layer and mip level.
+    pub unsafe fn create_iosurface_texture(
+        &self,
+        desc: &crate::TextureDescriptor,
+    ) -> DeviceResult<super::Texture> {
+        objc::rc::autoreleasepool(|| {
+            let surface = unsafe { super::iosurface::create(desc) }
+                .ok_or(crate::DeviceError::ResourceCreationFailed)?;
+            // The texture keeps its own reference to the surface.
+            let texture = unsafe { self.texture_from_iosurface(desc, surface, 0) };
+            unsafe { super::iosurface::release(surface) };
+            texture
+        })
+    }
+
+    /// Wraps `plane` of `iosurface`, an `IOSurfaceRef`, in a texture.
+    ///
+    /// The texture retains the surface.
+    ///
+    /// # Safety
+    ///
+    /// - `iosurface` must be a valid `IOSurfaceRef`, whose `plane` holds an
+    ///   image of the size and format of `desc`.
+    /// - `desc` must describe a single sampled 2D texture with one layer and
+    ///   mip level.
+    pub unsafe fn texture_from_iosurface(
+        &self,
+        desc: &crate::TextureDescriptor,
+        iosurface: *mut std::os::raw::c_void,
+        plane: u32,
+    ) -> DeviceResult<super::Texture> {
+        use metal::foreign_types::ForeignType as _;
+
+        objc::rc::autoreleasepool(|| {
+            let descriptor = metal::TextureDescriptor::new();
+            descriptor.set_texture_type(metal::MTLTextureType::D2);
+            descriptor.set_width(desc.size.width as u64);
+            descriptor.set_height(desc.size.height as u64);
+            descriptor.set_pixel_format(self.shared.private_caps.map_format(desc.format));
+            descriptor.set_usage(conv::map_texture_usage(desc.format, desc.usage));
+            // IOSurfaces live in memory the CPU can see.
+            descriptor.set_storage_mode(if cfg!(target_os = "macos") {
+                metal::MTLStorageMode::Managed
+            } else {
+                metal::MTLStorageMode::Shared
+            });
+
+            let raw: *mut metal::MTLTexture = unsafe {
+                msg_send![
+                    self.shared.device.lock().as_ref(),
+                    newTextureWithDescriptor: descriptor.as_ref()
+                    iosurface: iosurface
+                    plane: plane as u64
+                ]
+            };
+            if raw.is_null() {
+                return Err(crate::DeviceError::ResourceCreationFailed);
+            }
+            let raw = unsafe { metal::Texture::from_ptr(raw) };
+            if let Some(label) = desc.label {
+                raw.set_label(label);
+            }
+
+            Ok(super::Texture {
+                raw,
+                format: desc.format,
+                raw_type: metal::MTLTextureType::D2,
+                mip_levels: 1,
+                array_layers: 1,
+                copy_size: desc.copy_extent(),
+            })
+        })
+    }
 }
 
 impl crate::Device for super::Device {
@@ -282,6 +372,8 @@ impl crate::Device for super::Device {
 
     unsafe fn exit(self, _queue: super::Queue) {}
 
+    unsafe fn destroy_queue(&self, _queue: super::Queue) {}
+
     unsafe fn create_buffer(&self, desc: &crate::BufferDescriptor) -> DeviceResult<super::Buffer> {
         let map_read = desc.usage.contains(crate::BufferUses::MAP_READ);
         let map_write = desc.usage.contains(crate::BufferUses::MAP_WRITE);
@@ -331,6 +423,9 @@ impl crate::Device for super::Device {
     }
     unsafe fn flush_mapped_ranges<I>(&self, _buffer: &super::Buffer, _ranges: I) {}
     unsafe fn invalidate_mapped_ranges<I>(&self, _buffer: &super::Buffer, _ranges: I) {}
+    unsafe fn get_buffer_device_address(&self, buffer: &super::Buffer) -> wgt::BufferAddress {
+        unsafe { msg_send![buffer.raw.as_ref(), gpuAddress] }
+    }
 
     unsafe fn create_texture(
         &self,
@@ -368,7 +463,13 @@ impl crate::Device for super::Device {
             descriptor.set_mipmap_level_count(desc.mip_level_count as u64);
             descriptor.set_pixel_format(mtl_format);
             descriptor.set_usage(conv::map_texture_usage(desc.format, desc.usage));
-            descriptor.set_storage_mode(metal::MTLStorageMode::Private);
+            if desc.memory_flags.contains(crate::MemoryFlags::MEMORYLESS)
+                && self.shared.private_caps.supports_memoryless_storage
+            {
+                descriptor.set_storage_mode(metal::MTLStorageMode::Memoryless);
+            } else {
+                descriptor.set_storage_mode(metal::MTLStorageMode::Private);
+            }
 
             let raw = self.shared.device.lock().new_texture(&descriptor);
             if raw.as_ptr().is_null() {
@@ -391,6 +492,56 @@ impl crate::Device for super::Device {
 
     unsafe fn destroy_texture(&self, _texture: super::Texture) {}
 
+    unsafe fn get_sparse_texture_tiles(
+        &self,
+        _texture: &super::Texture,
+    ) -> wgt::SparseTextureTiles {
+        unimplemented!()
+    }
+
+    unsafe fn create_memory_heap(
+        &self,
+        _desc: &crate::MemoryHeapDescriptor,
+    ) -> Result<(), crate::DeviceError> {
+        unimplemented!()
+    }
+
+    unsafe fn destroy_memory_heap(&self, _heap: ()) {
+        unimplemented!()
+    }
+
+    unsafe fn get_buffer_memory_requirements(
+        &self,
+        _desc: &crate::BufferDescriptor,
+    ) -> Result<wgt::MemoryRequirements, crate::DeviceError> {
+        unimplemented!()
+    }
+
+    unsafe fn get_texture_memory_requirements(
+        &self,
+        _desc: &crate::TextureDescriptor,
+    ) -> Result<wgt::MemoryRequirements, crate::DeviceError> {
+        unimplemented!()
+    }
+
+    unsafe fn create_placed_buffer(
+        &self,
+        _desc: &crate::BufferDescriptor,
+        _heap: &(),
+        _offset: wgt::BufferAddress,
+    ) -> Result<super::Buffer, crate::DeviceError> {
+        unimplemented!()
+    }
+
+    unsafe fn create_placed_texture(
+        &self,
+        _desc: &crate::TextureDescriptor,
+        _heap: &(),
+        _offset: wgt::BufferAddress,
+    ) -> Result<super::Texture, crate::DeviceError> {
+        unimplemented!()
+    }
+
     unsafe fn create_texture_view(
         &self,
         texture: &super::Texture,
@@ -415,7 +566,9 @@ impl crate::Device for super::Device {
             desc.range
                 .is_full_resource(desc.format, texture.mip_levels, texture.array_layers);
 
-        let raw = if format_equal && type_equal && range_full_resource {
+        let swizzle_identity = desc.swizzle.is_identity();
+
+        let raw = if format_equal && type_equal && range_full_resource && swizzle_identity {
             // Some images are marked as framebuffer-only, and we can't create aliases of them.
             // Also helps working around Metal bugs with aliased array textures.
             texture.raw.to_owned()
@@ -429,19 +582,36 @@ impl crate::Device for super::Device {
                 .array_layer_count
                 .unwrap_or(texture.array_layers - desc.range.base_array_layer);
 
+            let levels = metal::NSRange {
+                location: desc.range.base_mip_level as _,
+                length: mip_level_count as _,
+            };
+            let slices = metal::NSRange {
+                location: desc.range.base_array_layer as _,
+                length: array_layer_count as _,
+            };
+
             objc::rc::autoreleasepool(|| {
-                let raw = texture.raw.new_texture_view_from_slice(
-                    raw_format,
-                    raw_type,
-                    metal::NSRange {
-                        location: desc.range.base_mip_level as _,
-                        length: mip_level_count as _,
-                    },
-                    metal::NSRange {
-                        location: desc.range.base_array_layer as _,
-                        length: array_layer_count as _,
-                    },
-                );
+                let raw = if swizzle_identity {
+                    texture
+                        .raw
+                        .new_texture_view_from_slice(raw_format, raw_type, levels, slices)
+                } else {
+                    use metal::foreign_types::ForeignType as _;
+
+                    let swizzle = conv::map_swizzle(desc.swizzle);
+                    unsafe {
+                        let raw: *mut metal::MTLTexture = msg_send![
+                            texture.raw.as_ref(),
+                            newTextureViewWithPixelFormat: raw_format
+                            textureType: raw_type
+                            levels: levels
+                            slices: slices
+                            swizzle: swizzle
+                        ];
+                        metal::Texture::from_ptr(raw)
+                    }
+                };
                 if let Some(label) = desc.label {
                     raw.set_label(label);
                 }
@@ -644,6 +814,9 @@ impl crate::Device for super::Device {
                             };
                         }
                         wgt::BindingType::AccelerationStructure => unimplemented!(),
+                        wgt::BindingType::YcbcrTexture { .. } => {
+                            unreachable!("YcbcrTexture is gated by SAMPLER_YCBCR_CONVERSION")
+                        }
                     }
 
                     let br = naga::ResourceBinding {
@@ -743,27 +916,17 @@ impl crate::Device for super::Device {
                         let end = start + size as usize;
                         bg.buffers
                             .extend(desc.buffers[start..end].iter().map(|source| {
-                                // Given the restrictions on `BufferBinding::offset`,
-                                // this should never be `None`.
-                                let remaining_size =
-                                    wgt::BufferSize::new(source.buffer.size - source.offset);
-                                let binding_size = match ty {
-                                    wgt::BufferBindingType::Storage { .. } => {
-                                        source.size.or(remaining_size)
-                                    }
-                                    _ => None,
+                                let dynamic_index = if has_dynamic_offset {
+                                    Some(dynamic_offsets_count - 1)
+                                } else {
+                                    None
                                 };
-                                super::BufferResource {
-                                    ptr: source.buffer.as_raw(),
-                                    offset: source.offset,
-                                    dynamic_index: if has_dynamic_offset {
-                                        Some(dynamic_offsets_count - 1)
-                                    } else {
-                                        None
-                                    },
-                                    binding_size,
-                                    binding_location: layout.binding,
-                                }
+                                super::BufferResource::new(
+                                    source,
+                                    ty,
+                                    dynamic_index,
+                                    layout.binding,
+                                )
                             }));
                         counter.buffers += 1;
                     }
@@ -785,6 +948,9 @@ impl crate::Device for super::Device {
                         counter.textures += size;
                     }
                     wgt::BindingType::AccelerationStructure => unimplemented!(),
+                    wgt::BindingType::YcbcrTexture { .. } => {
+                        unreachable!("YcbcrTexture is gated by SAMPLER_YCBCR_CONVERSION")
+                    }
                 }
             }
         }
@@ -793,6 +959,7 @@ impl crate::Device for super::Device {
     }
 
     unsafe fn destroy_bind_group(&self, _group: super::BindGroup) {}
+    unsafe fn recycle_bind_groups(&self) {}
 
     unsafe fn create_shader_module(
         &self,
@@ -942,6 +1109,37 @@ impl crate::Device for super::Device {
                 }
                 None => None,
             };
+            let depth_compare_states = match desc.depth_stencil {
+                Some(ref ds)
+                    if self
+                        .features
+                        .contains(wgt::Features::EXTENDED_DYNAMIC_STATE)
+                        && ds.format.has_depth_aspect() =>
+                {
+                    use wgt::CompareFunction as Cf;
+                    let device = self.shared.device.lock();
+                    [
+                        Cf::Never,
+                        Cf::Less,
+                        Cf::Equal,
+                        Cf::LessEqual,
+                        Cf::Greater,
+                        Cf::NotEqual,
+                        Cf::GreaterEqual,
+                        Cf::Always,
+                    ]
+                    .into_iter()
+                    .map(|depth_compare| {
+                        let state = wgt::DepthStencilState {
+                            depth_compare,
+                            ..ds.clone()
+                        };
+                        device.new_depth_stencil_state(&create_depth_stencil_desc(&state))
+                    })
+                    .collect()
+                }
+                _ => Vec::new(),
+            };
 
             if desc.layout.total_counters.vs.buffers + (desc.vertex_buffers.len() as u32)
                 > self.shared.private_caps.max_vertex_buffers
@@ -1039,6 +1237,7 @@ impl crate::Device for super::Device {
                     None
                 },
                 depth_stencil,
+                depth_compare_states,
             })
         })
     }
@@ -1216,6 +1415,17 @@ impl crate::Device for super::Device {
         }
     }
 
+    unsafe fn signal_fence(
+        &self,
+        fence: &mut super::Fence,
+        value: crate::FenceValue,
+    ) -> DeviceResult<()> {
+        fence
+            .completed_value
+            .store(value, atomic::Ordering::Release);
+        Ok(())
+    }
+
     unsafe fn start_capture(&self) -> bool {
         if !self.shared.private_caps.supports_capture_manager {
             return false;
@@ -1236,6 +1446,28 @@ impl crate::Device for super::Device {
         shared_capture_manager.stop_capture();
     }
 
+    unsafe fn set_performance_hint(&self, hint: wgt::PerformanceHint) {
+        // Let the device compile shaders on all cores when latency matters.
+        // `setShouldMaximizeConcurrentCompilation:` needs macOS 13.3.
+        let device = self.shared.device.lock();
+        let supported: objc::runtime::BOOL = unsafe {
+            msg_send![
+                device.as_ref(),
+                respondsToSelector: sel!(setShouldMaximizeConcurrentCompilation:)
+            ]
+        };
+        if supported != objc::runtime::NO {
+            let maximize = if hint == wgt::PerformanceHint::Burst {
+                objc::runtime::YES
+            } else {
+                objc::runtime::NO
+            };
+            let () = unsafe {
+                msg_send![device.as_ref(), setShouldMaximizeConcurrentCompilation: maximize]
+            };
+        }
+    }
+
     unsafe fn get_acceleration_structure_build_sizes(
         &self,
         _desc: &crate::GetAccelerationStructureBuildSizesDescriptor<super::Api>,
@@ -0,0 +1,89 @@
+//! Textures backed by IOSurfaces, the images CoreVideo, AVFoundation and
+//! other processes share with Metal.
+
+use std::os::raw::c_void;
+
+use objc::{class, msg_send, runtime::Object, sel, sel_impl};
+
+#[cfg_attr(feature = "link", link(name = "IOSurface", kind = "framework"))]
+extern "C" {
+    #[allow(non_upper_case_globals)]
+    static kIOSurfaceWidth: *mut Object;
+    #[allow(non_upper_case_globals)]
+    static kIOSurfaceHeight: *mut Object;
+    #[allow(non_upper_case_globals)]
+    static kIOSurfaceBytesPerElement: *mut Object;
+    #[allow(non_upper_case_globals)]
+    static kIOSurfacePixelFormat: *mut Object;
+
+    fn IOSurfaceCreate(properties: *mut Object) -> *mut c_void;
+}
+
+#[cfg_attr(feature = "link", link(name = "CoreFoundation", kind = "framework"))]
+extern "C" {
+    fn CFRelease(cf: *const c_void);
+}
+
+/// Returns the CoreVideo four character code of the pixel format of
+/// IOSurfaces holding `format`, if they can hold it.
+pub fn map_pixel_format(format: wgt::TextureFormat) -> Option<u32> {
+    use wgt::TextureFormat as Tf;
+
+    let code = match format {
+        Tf::R8Unorm => b"L008",
+        Tf::Rg8Unorm => b"2C08",
+        Tf::R16Float => b"L00h",
+        Tf::Rg16Float => b"2C0h",
+        Tf::R32Float => b"L00f",
+        Tf::Rg32Float => b"2C0f",
+        Tf::Rgba8Unorm | Tf::Rgba8UnormSrgb => b"RGBA",
+        Tf::Bgra8Unorm | Tf::Bgra8UnormSrgb => b"BGRA",
+        Tf::Rgba16Float => b"RGhA",
+        Tf::Rgba32Float => b"RGfA",
+        _ => return None,
+    };
+    Some(u32::from_be_bytes(*code))
+}
+
+/// Creates an IOSurface large enough for the first mip level of `desc`,
+/// owned by the caller.
+///
+/// Must be called inside an autorelease pool.
+pub unsafe fn create(desc: &crate::TextureDescriptor) -> Option<*mut c_void> {
+    let pixel_format = map_pixel_format(desc.format)?;
+    let bytes_per_element = desc.format.block_copy_size(None)?;
+
+    let number = |value: u32| -> *mut Object {
+        unsafe { msg_send![class!(NSNumber), numberWithUnsignedInt: value] }
+    };
+    let keys = unsafe {
+        [
+            kIOSurfaceWidth,
+            kIOSurfaceHeight,
+            kIOSurfaceBytesPerElement,
+            kIOSurfacePixelFormat,
+        ]
+    };
+    let values = [
+        number(desc.size.width),
+        number(desc.size.height),
+        number(bytes_per_element),
+        number(pixel_format),
+    ];
+    let properties: *mut Object = unsafe {
+        msg_send![
+            class!(NSDictionary),
+            dictionaryWithObjects: values.as_ptr()
+            forKeys: keys.as_ptr()
+            count: keys.len()
+        ]
+    };
+
+    let surface = unsafe { IOSurfaceCreate(properties) };
+    (!surface.is_null()).then_some(surface)
+}
+
+/// Releases an IOSurface owned by the caller.
+pub unsafe fn release(surface: *mut c_void) {
+    unsafe { CFRelease(surface) };
+}
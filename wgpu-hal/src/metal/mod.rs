@@ -22,6 +22,7 @@ mod adapter;
 mod command;
 mod conv;
 mod device;
+mod iosurface;
 mod surface;
 mod time;
 
@@ -35,6 +36,7 @@ use std::{
 use arrayvec::ArrayVec;
 use bitflags::bitflags;
 use metal::foreign_types::ForeignTypeRef as _;
+use objc::{msg_send, sel, sel_impl};
 use parking_lot::{Mutex, RwLock};
 
 #[derive(Clone, Debug)]
@@ -58,6 +60,7 @@ impl crate::Api for Api {
     type TextureView = TextureView;
     type Sampler = Sampler;
     type QuerySet = QuerySet;
+    type MemoryHeap = ();
     type Fence = Fence;
 
     type BindGroupLayout = BindGroupLayout;
@@ -136,6 +139,12 @@ impl crate::Instance for Instance {
                         driver: String::new(),
                         driver_info: String::new(),
                         backend: wgt::Backend::Metal,
+                        driver_version: None,
+                        device_uuid: None,
+                        device_luid: None,
+                        memory_heaps: Vec::new(),
+                        unified_memory: shared.private_caps.has_unified_memory.unwrap_or(false),
+                        resizable_bar: false,
                     },
                     features: shared.private_caps.features(),
                     capabilities: shared.private_caps.capabilities(),
@@ -267,10 +276,15 @@ struct PrivateCapabilities {
     supports_depth_clip_control: bool,
     supports_preserve_invariance: bool,
     supports_shader_primitive_index: bool,
+    supports_multi_viewport: bool,
+    supports_raster_order_groups: bool,
     has_unified_memory: Option<bool>,
     timestamp_query_support: TimestampQuerySupport,
     supports_simd_scoped_operations: bool,
     int64: bool,
+    buffer_device_address: bool,
+    texture_swizzle: bool,
+    supports_memoryless_storage: bool,
 }
 
 #[derive(Clone, Debug)]
@@ -345,11 +359,28 @@ pub struct Surface {
     swapchain_format: RwLock<Option<wgt::TextureFormat>>,
     extent: RwLock<wgt::Extent3d>,
     main_thread_id: thread::ThreadId,
+    presentation: Arc<Mutex<PresentationState>>,
     // Useful for UI-intensive applications that are sensitive to
     // window resizing.
     pub present_with_transaction: bool,
 }
 
+/// Number of past presentation timings a [`Surface`] keeps until they're
+/// queried.
+const MAX_PRESENTATION_TIMINGS: usize = 64;
+
+/// The presentation timing state of a [`Surface`], shared with the presented
+/// handlers of its drawables.
+#[derive(Debug, Default)]
+struct PresentationState {
+    /// The number of presents since the surface was configured.
+    present_count: u64,
+    /// The desired present time of the next present, in nanoseconds.
+    next_present_time: Option<u128>,
+    /// The timings of the drawables presented since the last query.
+    timings: Vec<wgt::PresentationTiming>,
+}
+
 unsafe impl Send for Surface {}
 unsafe impl Sync for Surface {}
 
@@ -419,26 +450,107 @@ impl crate::Queue for Queue {
         });
         Ok(())
     }
+    unsafe fn wait_for_fence(
+        &self,
+        _fence: &Fence,
+        _value: crate::FenceValue,
+    ) -> Result<(), crate::DeviceError> {
+        // Additional queues aren't exposed, so every fence is signaled by this
+        // queue, in submission order.
+        Ok(())
+    }
+    unsafe fn bind_sparse_buffer(
+        &self,
+        _device: &Device,
+        _buffer: &Buffer,
+        _binds: &[wgt::SparseBufferBind],
+        _signal_fence: (&mut Fence, crate::FenceValue),
+    ) -> Result<(), crate::DeviceError> {
+        unimplemented!()
+    }
+    unsafe fn bind_sparse_texture(
+        &self,
+        _device: &Device,
+        _texture: &Texture,
+        _binds: &[wgt::SparseTextureBind],
+        _signal_fence: (&mut Fence, crate::FenceValue),
+    ) -> Result<(), crate::DeviceError> {
+        unimplemented!()
+    }
     unsafe fn present(
         &self,
-        _surface: &Surface,
+        surface: &Surface,
         texture: SurfaceTexture,
     ) -> Result<(), crate::SurfaceError> {
+        let (present_id, desired_present_time) = {
+            let mut presentation = surface.presentation.lock();
+            presentation.present_count += 1;
+            (
+                presentation.present_count,
+                presentation.next_present_time.take(),
+            )
+        };
+        // Present times are in `CACurrentMediaTime` seconds.
+        let present_time = desired_present_time.map(|time| time as f64 / 1e9);
+
         let queue = &self.raw.lock();
         objc::rc::autoreleasepool(|| {
+            // `addPresentedHandler:` needs macOS 10.15.4 or iOS 10.3.
+            let supports_presented_handler: objc::runtime::BOOL = unsafe {
+                msg_send![texture.drawable, respondsToSelector: sel!(addPresentedHandler:)]
+            };
+            if supports_presented_handler != objc::runtime::NO {
+                let presentation = Arc::clone(&surface.presentation);
+                let block =
+                    block::ConcreteBlock::new(move |drawable: *mut objc::runtime::Object| {
+                        let presented_time: f64 = unsafe { msg_send![drawable, presentedTime] };
+                        // Drawables that were never shown have a time of zero.
+                        if presented_time <= 0.0 {
+                            return;
+                        }
+                        let mut presentation = presentation.lock();
+                        if presentation.timings.len() == MAX_PRESENTATION_TIMINGS {
+                            presentation.timings.remove(0);
+                        }
+                        presentation.timings.push(wgt::PresentationTiming {
+                            present_id,
+                            desired_present_time: desired_present_time
+                                .map(wgt::PresentationTimestamp),
+                            actual_present_time: wgt::PresentationTimestamp(
+                                (presented_time * 1e9) as u128,
+                            ),
+                        });
+                    })
+                    .copy();
+                let () = unsafe { msg_send![texture.drawable, addPresentedHandler: &*block] };
+            }
+
             let command_buffer = queue.new_command_buffer();
             command_buffer.set_label("(wgpu internal) Present");
 
             // https://developer.apple.com/documentation/quartzcore/cametallayer/1478157-presentswithtransaction?language=objc
             if !texture.present_with_transaction {
-                command_buffer.present_drawable(&texture.drawable);
+                match present_time {
+                    Some(time) => {
+                        let drawable: &metal::DrawableRef = &texture.drawable;
+                        let () = unsafe {
+                            msg_send![command_buffer, presentDrawable: drawable atTime: time]
+                        };
+                    }
+                    None => command_buffer.present_drawable(&texture.drawable),
+                }
             }
 
             command_buffer.commit();
 
             if texture.present_with_transaction {
                 command_buffer.wait_until_scheduled();
-                texture.drawable.present();
+                match present_time {
+                    Some(time) => {
+                        let () = unsafe { msg_send![texture.drawable, presentAtTime: time] };
+                    }
+                    None => texture.drawable.present(),
+                }
             }
         });
         Ok(())
@@ -447,6 +559,26 @@ impl crate::Queue for Queue {
     unsafe fn get_timestamp_period(&self) -> f32 {
         self.timestamp_period
     }
+
+    unsafe fn get_timestamp_calibration(&self) -> Option<wgt::TimestampCalibration> {
+        let queue = self.raw.lock();
+        let device: &metal::DeviceRef = queue.device();
+        // `sampleTimestamps:gpuTimestamp:` needs macOS 10.15 or iOS 14.
+        let supported: objc::runtime::BOOL =
+            unsafe { msg_send![device, respondsToSelector: sel!(sampleTimestamps:gpuTimestamp:)] };
+        if supported == objc::runtime::NO {
+            return None;
+        }
+        let mut cpu_timestamp = 0u64;
+        let mut gpu_timestamp = 0u64;
+        let () = unsafe {
+            msg_send![device, sampleTimestamps: &mut cpu_timestamp gpuTimestamp: &mut gpu_timestamp]
+        };
+        Some(wgt::TimestampCalibration {
+            gpu_timestamp,
+            cpu_timestamp,
+        })
+    }
 }
 
 #[derive(Debug)]
@@ -477,6 +609,16 @@ pub struct Texture {
 unsafe impl Send for Texture {}
 unsafe impl Sync for Texture {}
 
+impl Texture {
+    /// The `IOSurfaceRef` the texture is backed by, or null if it isn't.
+    ///
+    /// The surface isn't retained, it lives as long as the texture unless the
+    /// caller retains it.
+    pub fn iosurface(&self) -> *mut std::os::raw::c_void {
+        unsafe { msg_send![self.raw.as_ref(), iosurface] }
+    }
+}
+
 #[derive(Debug)]
 pub struct TextureView {
     raw: metal::Texture,
@@ -658,6 +800,30 @@ struct BufferResource {
     binding_location: u32,
 }
 
+impl BufferResource {
+    fn new(
+        source: &crate::BufferBinding<Api>,
+        ty: wgt::BufferBindingType,
+        dynamic_index: Option<u32>,
+        binding_location: u32,
+    ) -> Self {
+        // Given the restrictions on `BufferBinding::offset`,
+        // this should never be `None`.
+        let remaining_size = wgt::BufferSize::new(source.buffer.size - source.offset);
+        let binding_size = match ty {
+            wgt::BufferBindingType::Storage { .. } => source.size.or(remaining_size),
+            _ => None,
+        };
+        Self {
+            ptr: source.buffer.as_raw(),
+            offset: source.offset,
+            dynamic_index,
+            binding_size,
+            binding_location,
+        }
+    }
+}
+
 #[derive(Debug, Default)]
 pub struct BindGroup {
     counters: MultiStageResourceCounters,
@@ -720,6 +886,9 @@ pub struct RenderPipeline {
     raw_cull_mode: metal::MTLCullMode,
     raw_depth_clip_mode: Option<metal::MTLDepthClipMode>,
     depth_stencil: Option<(metal::DepthStencilState, wgt::DepthBiasState)>,
+    /// Variants of the depth stencil state for each [`wgt::CompareFunction`], in order,
+    /// with [`wgt::Features::EXTENDED_DYNAMIC_STATE`] and a depth aspect.
+    depth_compare_states: Vec<metal::DepthStencilState>,
 }
 
 unsafe impl Send for RenderPipeline {}
@@ -787,6 +956,8 @@ struct IndexState {
 #[derive(Default)]
 struct Temp {
     binding_sizes: Vec<u32>,
+    /// Buffers holding fill patterns, which have to outlive the command buffer.
+    fill_patterns: Vec<metal::Buffer>,
 }
 
 struct CommandState {
@@ -824,6 +995,14 @@ struct CommandState {
 
     /// Timer query that should be executed when the next pass starts.
     pending_timer_queries: Vec<(QuerySet, u32)>,
+
+    /// Viewports and scissor rectangles of the current render pass, as
+    /// `setViewports:count:` sets all of them at once.
+    viewports: [metal::MTLViewport; wgt::MAX_VIEWPORTS as usize],
+    scissor_rects: [metal::MTLScissorRect; wgt::MAX_VIEWPORTS as usize],
+
+    /// [`RenderPipeline::depth_compare_states`] of the current pipeline.
+    depth_compare_states: Vec<metal::DepthStencilState>,
 }
 
 pub struct CommandEncoder {
@@ -849,6 +1028,7 @@ unsafe impl Sync for CommandEncoder {}
 #[derive(Debug)]
 pub struct CommandBuffer {
     raw: metal::CommandBuffer,
+    fill_patterns: Vec<metal::Buffer>,
 }
 
 unsafe impl Send for CommandBuffer {}
@@ -23,6 +23,13 @@ extern "C" {
     static kCAGravityTopLeft: *mut Object;
 }
 
+#[cfg(target_os = "macos")]
+#[cfg_attr(feature = "link", link(name = "CoreGraphics", kind = "framework"))]
+extern "C" {
+    fn CGColorSpaceCreateWithName(name: *mut Object) -> *mut c_void;
+    fn CGColorSpaceRelease(space: *mut c_void);
+}
+
 extern "C" fn layer_should_inherit_contents_scale_from_window(
     _: &Class,
     _: Sel,
@@ -66,6 +73,7 @@ impl super::Surface {
             swapchain_format: RwLock::new(None),
             extent: RwLock::new(wgt::Extent3d::default()),
             main_thread_id: thread::current().id(),
+            presentation: Default::default(),
             present_with_transaction: false,
         }
     }
@@ -182,6 +190,7 @@ impl crate::Surface for super::Surface {
         let caps = &device.shared.private_caps;
         *self.swapchain_format.write() = Some(config.format);
         *self.extent.write() = config.extent;
+        *self.presentation.lock() = Default::default();
 
         let render_layer = self.render_layer.lock();
         let framebuffer_only = config.usage == crate::TextureUses::COLOR_TARGET;
@@ -217,13 +226,49 @@ impl crate::Surface for super::Surface {
         render_layer.set_presents_with_transaction(self.present_with_transaction);
         // opt-in to Metal EDR
         // EDR potentially more power used in display and more bandwidth, memory footprint.
-        let wants_edr = config.format == wgt::TextureFormat::Rgba16Float;
+        let wants_edr = config.format == wgt::TextureFormat::Rgba16Float
+            || config.color_space == wgt::ColorSpace::Hdr10;
         if wants_edr != render_layer.wants_extended_dynamic_range_content() {
             render_layer.set_wants_extended_dynamic_range_content(wants_edr);
         }
 
+        // Without a color space the layer isn't color matched, which is how
+        // sRGB content has always been shown.
+        #[cfg(target_os = "macos")]
+        {
+            // Looked up by name, as the constants are missing on older systems.
+            let name = match (config.color_space, config.format) {
+                (wgt::ColorSpace::Srgb, wgt::TextureFormat::Rgba16Float) => {
+                    Some("kCGColorSpaceExtendedLinearSRGB\0")
+                }
+                (wgt::ColorSpace::Srgb, _) => None,
+                (wgt::ColorSpace::DisplayP3, wgt::TextureFormat::Rgba16Float) => {
+                    Some("kCGColorSpaceExtendedLinearDisplayP3\0")
+                }
+                (wgt::ColorSpace::DisplayP3, _) => Some("kCGColorSpaceDisplayP3\0"),
+                (wgt::ColorSpace::Bt2020, _) => Some("kCGColorSpaceExtendedLinearITUR_2020\0"),
+                (wgt::ColorSpace::Hdr10, _) => Some("kCGColorSpaceITUR_2100_PQ\0"),
+            };
+            let color_space = match name {
+                Some(name) => {
+                    let name: *mut Object =
+                        msg_send![class!(NSString), stringWithUTF8String: name.as_ptr()];
+                    let color_space = unsafe { CGColorSpaceCreateWithName(name) };
+                    if color_space.is_null() {
+                        return Err(crate::SurfaceError::Other("Unsupported color space"));
+                    }
+                    color_space
+                }
+                None => std::ptr::null_mut(),
+            };
+            let () = msg_send![*render_layer, setColorspace: color_space];
+            if !color_space.is_null() {
+                unsafe { CGColorSpaceRelease(color_space) };
+            }
+        }
+
         // this gets ignored on iOS for certain OS/device combinations (iphone5s iOS 10.3)
-        render_layer.set_maximum_drawable_count(config.maximum_frame_latency as u64 + 1);
+        render_layer.set_maximum_drawable_count(config.image_count as u64);
         render_layer.set_drawable_size(drawable_size);
         if caps.can_set_next_drawable_timeout {
             let () = msg_send![*render_layer, setAllowsNextDrawableTimeout:false];
@@ -280,4 +325,80 @@ impl crate::Surface for super::Surface {
     }
 
     unsafe fn discard_texture(&self, _texture: super::SurfaceTexture) {}
+
+    unsafe fn set_hdr_metadata(
+        &self,
+        metadata: &wgt::HdrMetadata,
+    ) -> Result<(), crate::SurfaceError> {
+        #[cfg(target_os = "macos")]
+        {
+            // `CAEDRMetadata` only takes the luminance range of the mastering
+            // display, the rest is left to the system.
+            let class = Class::get("CAEDRMetadata")
+                .ok_or(crate::SurfaceError::Other("CAEDRMetadata is not supported"))?;
+            let render_layer = self.render_layer.lock();
+            autoreleasepool(|| {
+                // PQ values are relative to an SDR white of 100 nits.
+                let edr_metadata: *mut Object = msg_send![
+                    class,
+                    HDR10MetadataWithMinLuminance: metadata.min_luminance
+                    maxLuminance: metadata.max_luminance
+                    opticalOutputScale: 100.0f32
+                ];
+                let () = msg_send![*render_layer, setEDRMetadata: edr_metadata];
+            });
+            Ok(())
+        }
+        #[cfg(not(target_os = "macos"))]
+        {
+            let _ = metadata;
+            Err(crate::SurfaceError::Other("HDR metadata is not supported"))
+        }
+    }
+
+    unsafe fn set_next_present_time(&self, time: wgt::PresentationTimestamp) -> bool {
+        self.presentation.lock().next_present_time = Some(time.0);
+        true
+    }
+
+    unsafe fn set_next_present_damage(&self, _damage: &[wgt::DamageRect]) -> bool {
+        false
+    }
+
+    unsafe fn presentation_timings(&self) -> Vec<wgt::PresentationTiming> {
+        mem::take(&mut self.presentation.lock().timings)
+    }
+
+    unsafe fn refresh_duration(&self) -> Option<std::time::Duration> {
+        // `maximumFramesPerSecond` needs macOS 12 or iOS 10.3.
+        #[cfg(target_os = "macos")]
+        let class = Class::get("NSScreen")?;
+        #[cfg(not(target_os = "macos"))]
+        let class = Class::get("UIScreen")?;
+        let screen: *mut Object = msg_send![class, mainScreen];
+        if screen.is_null() {
+            return None;
+        }
+        let supported: BOOL = msg_send![screen, respondsToSelector: sel!(maximumFramesPerSecond)];
+        if supported == NO {
+            return None;
+        }
+        let frames_per_second: isize = msg_send![screen, maximumFramesPerSecond];
+        (frames_per_second > 0)
+            .then(|| std::time::Duration::from_secs_f64(1.0 / frames_per_second as f64))
+    }
+
+    unsafe fn image_count(&self) -> u32 {
+        self.render_layer.lock().maximum_drawable_count() as u32
+    }
+
+    // `nextDrawable` already blocks until a drawable is available.
+    unsafe fn wait_for_present(
+        &self,
+        _timeout: Option<std::time::Duration>,
+    ) -> Result<bool, crate::SurfaceError> {
+        Ok(true)
+    }
+
+    unsafe fn set_maximum_frame_latency(&self, _maximum_frame_latency: u32) {}
 }
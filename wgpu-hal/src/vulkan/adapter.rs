@@ -1,6 +1,9 @@
 use super::conv;
 
-use ash::{extensions::khr, vk};
+use ash::{
+    extensions::{ext, khr},
+    vk,
+};
 use parking_lot::Mutex;
 
 use std::{collections::BTreeMap, ffi::CStr, sync::Arc};
@@ -70,22 +73,26 @@ pub struct PhysicalDeviceFeatures {
         vk::PhysicalDevice16BitStorageFeatures,
     )>,
 
+    /// Features provided by `VK_KHR_shader_atomic_int64`, promoted to Vulkan 1.2.
+    shader_atomic_int64: Option<vk::PhysicalDeviceShaderAtomicInt64Features>,
+
     /// Features provided by `VK_KHR_acceleration_structure`.
     acceleration_structure: Option<vk::PhysicalDeviceAccelerationStructureFeaturesKHR>,
 
     /// Features provided by `VK_KHR_buffer_device_address`, promoted to Vulkan 1.2.
     ///
-    /// We only use this feature for
+    /// We use this feature for [`Features::BUFFER_DEVICE_ADDRESS`], and for
     /// [`Features::RAY_TRACING_ACCELERATION_STRUCTURE`], which requires
     /// `VK_KHR_acceleration_structure`, which depends on
-    /// `VK_KHR_buffer_device_address`, so [`Instance::expose_adapter`] only
-    /// bothers to check if `VK_KHR_acceleration_structure` is available,
-    /// leaving this `None`.
+    /// `VK_KHR_buffer_device_address`. The extension requires the
+    /// `bufferDeviceAddress` feature, so [`Instance::expose_adapter`] only
+    /// bothers to check if the extensions are available, leaving this `None`.
     ///
-    /// However, we do populate this when creating a device if
-    /// [`Features::RAY_TRACING_ACCELERATION_STRUCTURE`] is requested.
+    /// However, we do populate this when creating a device if either feature
+    /// is requested.
     ///
     /// [`Instance::expose_adapter`]: super::Instance::expose_adapter
+    /// [`Features::BUFFER_DEVICE_ADDRESS`]: wgt::Features::BUFFER_DEVICE_ADDRESS
     /// [`Features::RAY_TRACING_ACCELERATION_STRUCTURE`]: wgt::Features::RAY_TRACING_ACCELERATION_STRUCTURE
     buffer_device_address: Option<vk::PhysicalDeviceBufferDeviceAddressFeaturesKHR>,
 
@@ -107,6 +114,22 @@ pub struct PhysicalDeviceFeatures {
 
     /// Features provided by `VK_EXT_subgroup_size_control`, promoted to Vulkan 1.3.
     subgroup_size_control: Option<vk::PhysicalDeviceSubgroupSizeControlFeatures>,
+
+    /// Features provided by `VK_EXT_extended_dynamic_state`, promoted to Vulkan 1.3.
+    extended_dynamic_state: Option<vk::PhysicalDeviceExtendedDynamicStateFeaturesEXT>,
+
+    /// Features provided by `VK_EXT_custom_border_color`.
+    custom_border_color: Option<vk::PhysicalDeviceCustomBorderColorFeaturesEXT>,
+
+    /// Features provided by `VK_KHR_present_id` and `VK_KHR_present_wait`. We
+    /// use these features together, or not at all.
+    present_wait: Option<(
+        vk::PhysicalDevicePresentIdFeaturesKHR,
+        vk::PhysicalDevicePresentWaitFeaturesKHR,
+    )>,
+
+    /// Features provided by `VK_EXT_fragment_shader_interlock`.
+    fragment_shader_interlock: Option<vk::PhysicalDeviceFragmentShaderInterlockFeaturesEXT>,
 }
 
 // This is safe because the structs have `p_next: *mut c_void`, which we null out/never read.
@@ -142,6 +165,9 @@ impl PhysicalDeviceFeatures {
             info = info.push_next(f16_i8_feature);
             info = info.push_next(_16bit_feature);
         }
+        if let Some(ref mut feature) = self.shader_atomic_int64 {
+            info = info.push_next(feature);
+        }
         if let Some(ref mut feature) = self.zero_initialize_workgroup_memory {
             info = info.push_next(feature);
         }
@@ -157,6 +183,19 @@ impl PhysicalDeviceFeatures {
         if let Some(ref mut feature) = self.subgroup_size_control {
             info = info.push_next(feature);
         }
+        if let Some(ref mut feature) = self.extended_dynamic_state {
+            info = info.push_next(feature);
+        }
+        if let Some(ref mut feature) = self.custom_border_color {
+            info = info.push_next(feature);
+        }
+        if let Some((ref mut id_feature, ref mut wait_feature)) = self.present_wait {
+            info = info.push_next(id_feature);
+            info = info.push_next(wait_feature);
+        }
+        if let Some(ref mut feature) = self.fragment_shader_interlock {
+            info = info.push_next(feature);
+        }
         info
     }
 
@@ -256,6 +295,17 @@ impl PhysicalDeviceFeatures {
                 .pipeline_statistics_query(
                     requested_features.contains(wgt::Features::PIPELINE_STATISTICS_QUERY),
                 )
+                .sparse_binding(
+                    requested_features
+                        .intersects(wgt::Features::SPARSE_BUFFER | wgt::Features::SPARSE_TEXTURE),
+                )
+                .sparse_residency_buffer(requested_features.contains(wgt::Features::SPARSE_BUFFER))
+                .sparse_residency_image2_d(
+                    requested_features.contains(wgt::Features::SPARSE_TEXTURE),
+                )
+                .sparse_residency_image3_d(
+                    requested_features.contains(wgt::Features::SPARSE_TEXTURE),
+                )
                 .vertex_pipeline_stores_and_atomics(
                     requested_features.contains(wgt::Features::VERTEX_WRITABLE_STORAGE),
                 )
@@ -274,11 +324,10 @@ impl PhysicalDeviceFeatures {
                 .shader_sampled_image_array_dynamic_indexing(
                     requested_features.contains(wgt::Features::TEXTURE_BINDING_ARRAY),
                 )
-                .shader_storage_buffer_array_dynamic_indexing(requested_features.contains(
+                .shader_storage_image_array_dynamic_indexing(requested_features.contains(
                     wgt::Features::TEXTURE_BINDING_ARRAY
                         | wgt::Features::STORAGE_RESOURCE_BINDING_ARRAY,
                 ))
-                //.shader_storage_image_array_dynamic_indexing(
                 //.shader_clip_distance(requested_features.contains(wgt::Features::SHADER_CLIP_DISTANCE))
                 //.shader_cull_distance(requested_features.contains(wgt::Features::SHADER_CULL_DISTANCE))
                 .shader_float64(requested_features.contains(wgt::Features::SHADER_F64))
@@ -288,6 +337,7 @@ impl PhysicalDeviceFeatures {
                 .geometry_shader(requested_features.contains(wgt::Features::SHADER_PRIMITIVE_INDEX))
                 .depth_clamp(requested_features.contains(wgt::Features::DEPTH_CLIP_CONTROL))
                 .dual_src_blend(requested_features.contains(wgt::Features::DUAL_SOURCE_BLENDING))
+                .multi_viewport(requested_features.contains(wgt::Features::MULTI_VIEWPORT))
                 .build(),
             descriptor_indexing: if requested_features.intersects(indexing_features()) {
                 Some(
@@ -372,7 +422,9 @@ impl PhysicalDeviceFeatures {
             {
                 Some(
                     vk::PhysicalDeviceSamplerYcbcrConversionFeatures::builder()
-                        // .sampler_ycbcr_conversion(requested_features.contains(wgt::Features::TEXTURE_FORMAT_NV12))
+                        .sampler_ycbcr_conversion(
+                            requested_features.contains(wgt::Features::SAMPLER_YCBCR_CONVERSION),
+                        )
                         .build(),
                 )
             } else {
@@ -400,6 +452,17 @@ impl PhysicalDeviceFeatures {
             } else {
                 None
             },
+            shader_atomic_int64: if requested_features.contains(wgt::Features::SHADER_INT64_ATOMICS)
+            {
+                Some(
+                    vk::PhysicalDeviceShaderAtomicInt64Features::builder()
+                        .shader_buffer_int64_atomics(true)
+                        .shader_shared_int64_atomics(true)
+                        .build(),
+                )
+            } else {
+                None
+            },
             acceleration_structure: if enabled_extensions
                 .contains(&vk::KhrAccelerationStructureFn::name())
             {
@@ -455,6 +518,51 @@ impl PhysicalDeviceFeatures {
             } else {
                 None
             },
+            extended_dynamic_state: if enabled_extensions
+                .contains(&vk::ExtExtendedDynamicStateFn::name())
+            {
+                Some(
+                    vk::PhysicalDeviceExtendedDynamicStateFeaturesEXT::builder()
+                        .extended_dynamic_state(true)
+                        .build(),
+                )
+            } else {
+                None
+            },
+            custom_border_color: if enabled_extensions.contains(&vk::ExtCustomBorderColorFn::name())
+            {
+                Some(
+                    vk::PhysicalDeviceCustomBorderColorFeaturesEXT::builder()
+                        .custom_border_colors(true)
+                        .custom_border_color_without_format(true)
+                        .build(),
+                )
+            } else {
+                None
+            },
+            present_wait: if requested_features.contains(wgt::Features::PRESENT_WAIT) {
+                Some((
+                    vk::PhysicalDevicePresentIdFeaturesKHR::builder()
+                        .present_id(true)
+                        .build(),
+                    vk::PhysicalDevicePresentWaitFeaturesKHR::builder()
+                        .present_wait(true)
+                        .build(),
+                ))
+            } else {
+                None
+            },
+            fragment_shader_interlock: if requested_features
+                .contains(wgt::Features::FRAGMENT_SHADER_INTERLOCK)
+            {
+                Some(
+                    vk::PhysicalDeviceFragmentShaderInterlockFeaturesEXT::builder()
+                        .fragment_shader_pixel_interlock(true)
+                        .build(),
+                )
+            } else {
+                None
+            },
         }
     }
 
@@ -477,6 +585,7 @@ impl PhysicalDeviceFeatures {
         let mut features = F::empty()
             | F::SPIRV_SHADER_PASSTHROUGH
             | F::MAPPABLE_PRIMARY_BUFFERS
+            | F::PERSISTENT_MAPPING
             | F::PUSH_CONSTANTS
             | F::ADDRESS_MODE_CLAMP_TO_BORDER
             | F::ADDRESS_MODE_CLAMP_TO_ZERO
@@ -484,7 +593,8 @@ impl PhysicalDeviceFeatures {
             | F::TIMESTAMP_QUERY_INSIDE_ENCODERS
             | F::TIMESTAMP_QUERY_INSIDE_PASSES
             | F::TEXTURE_ADAPTER_SPECIFIC_FORMAT_FEATURES
-            | F::CLEAR_TEXTURE;
+            | F::CLEAR_TEXTURE
+            | F::SEPARATE_STENCIL_STATE;
 
         let mut dl_flags = Df::COMPUTE_SHADERS
             | Df::BASE_VERTEX
@@ -500,7 +610,9 @@ impl PhysicalDeviceFeatures {
             | Df::VIEW_FORMATS
             | Df::UNRESTRICTED_EXTERNAL_TEXTURE_COPIES
             | Df::NONBLOCKING_QUERY_RESOLVE
-            | Df::VERTEX_AND_INSTANCE_INDEX_RESPECTS_RESPECTIVE_FIRST_VALUE_IN_INDIRECT_DRAW;
+            | Df::VERTEX_AND_INSTANCE_INDEX_RESPECTS_RESPECTIVE_FIRST_VALUE_IN_INDIRECT_DRAW
+            | Df::SEAMLESS_CUBE_MAP_FILTERING
+            | Df::SAMPLER_LOD_BIAS;
 
         dl_flags.set(
             Df::SURFACE_VIEW_FORMATS,
@@ -582,10 +694,20 @@ impl PhysicalDeviceFeatures {
         //if self.core.shader_cull_distance != 0 {
         features.set(F::SHADER_F64, self.core.shader_float64 != 0);
         features.set(F::SHADER_INT64, self.core.shader_int64 != 0);
+        features.set(
+            F::SHADER_INT64_ATOMICS,
+            self.core.shader_int64 != 0
+                && self.shader_atomic_int64.map_or(false, |ext| {
+                    ext.shader_buffer_int64_atomics != 0 && ext.shader_shared_int64_atomics != 0
+                }),
+        );
         features.set(F::SHADER_I16, self.core.shader_int16 != 0);
 
         //if caps.supports_extension(vk::KhrSamplerMirrorClampToEdgeFn::name()) {
-        //if caps.supports_extension(vk::ExtSamplerFilterMinmaxFn::name()) {
+        features.set(
+            F::SAMPLER_REDUCTION_MODE,
+            caps.supports_extension(vk::ExtSamplerFilterMinmaxFn::name()),
+        );
         features.set(
             F::MULTI_DRAW_INDIRECT_COUNT,
             caps.supports_extension(vk::KhrDrawIndirectCountFn::name()),
@@ -594,6 +716,42 @@ impl PhysicalDeviceFeatures {
             F::CONSERVATIVE_RASTERIZATION,
             caps.supports_extension(vk::ExtConservativeRasterizationFn::name()),
         );
+        features.set(
+            F::MULTI_VIEWPORT,
+            self.core.multi_viewport != 0
+                && caps.properties.limits.max_viewports >= wgt::MAX_VIEWPORTS
+                && caps.supports_extension(vk::ExtShaderViewportIndexLayerFn::name()),
+        );
+        features.set(
+            F::TIMELINE_SEMAPHORE,
+            self.timeline_semaphore
+                .map_or(false, |ext| ext.timeline_semaphore != 0),
+        );
+        features.set(
+            F::EXTENDED_DYNAMIC_STATE,
+            self.extended_dynamic_state
+                .map_or(false, |ext| ext.extended_dynamic_state != 0),
+        );
+        // Samplers don't know the format of the views they sample, so the
+        // border color has to work without one.
+        features.set(
+            F::ADDRESS_MODE_CLAMP_TO_CUSTOM_BORDER,
+            self.custom_border_color.map_or(false, |ext| {
+                ext.custom_border_colors != 0 && ext.custom_border_color_without_format != 0
+            }),
+        );
+        features.set(
+            F::PRESENT_WAIT,
+            self.present_wait.map_or(false, |(id, wait)| {
+                id.present_id != 0 && wait.present_wait != 0
+            }),
+        );
+        // Naga only emits ordered pixel interlocks.
+        features.set(
+            F::FRAGMENT_SHADER_INTERLOCK,
+            self.fragment_shader_interlock
+                .map_or(false, |ext| ext.fragment_shader_pixel_interlock != 0),
+        );
 
         let intel_windows = caps.properties.vendor_id == db::intel::VENDOR && cfg!(windows);
 
@@ -727,6 +885,16 @@ impl PhysicalDeviceFeatures {
             caps.supports_extension(vk::KhrRayQueryFn::name()),
         );
 
+        features.set(
+            F::BUFFER_DEVICE_ADDRESS,
+            caps.supports_extension(vk::KhrBufferDeviceAddressFn::name()),
+        );
+
+        features.set(
+            F::INLINE_BIND_GROUPS,
+            caps.supports_extension(vk::KhrPushDescriptorFn::name()),
+        );
+
         let rg11b10ufloat_renderable = supports_format(
             instance,
             phd,
@@ -748,24 +916,150 @@ impl PhysicalDeviceFeatures {
             is_float32_filterable_supported(instance, phd),
         );
 
-        if let Some(ref _sampler_ycbcr_conversion) = self.sampler_ycbcr_conversion {
-            features.set(
-                F::TEXTURE_FORMAT_NV12,
+        // Implementations of the portability subset may not support swizzled
+        // image views, see `imageViewFormatSwizzle`.
+        features.set(
+            F::TEXTURE_COMPONENT_SWIZZLE,
+            !caps.supports_extension(vk::KhrPortabilitySubsetFn::name()),
+        );
+
+        // Views of other formats need `VK_IMAGE_CREATE_EXTENDED_USAGE_BIT` and
+        // `VK_IMAGE_CREATE_BLOCK_TEXEL_VIEW_COMPATIBLE_BIT`, and their usage
+        // restricted to that of their format.
+        features.set(
+            F::TEXTURE_FORMAT_REINTERPRETATION,
+            caps.device_api_version >= vk::API_VERSION_1_1
+                || caps.supports_extension(vk::KhrMaintenance2Fn::name()),
+        );
+
+        // `vkCmdBlitImage` is core, and the blit features are mandatory for
+        // the formats that are filterable and color renderable.
+        features.insert(F::TEXTURE_BLIT);
+
+        if let Some(ref sampler_ycbcr_conversion) = self.sampler_ycbcr_conversion {
+            let is_moltenvk = caps
+                .driver
+                .map(|driver| driver.driver_id == vk::DriverId::MOLTENVK)
+                .unwrap_or_default();
+            let supports_planar_format = |format| {
                 supports_format(
                     instance,
                     phd,
-                    vk::Format::G8_B8R8_2PLANE_420_UNORM,
+                    format,
                     vk::ImageTiling::OPTIMAL,
                     vk::FormatFeatureFlags::SAMPLED_IMAGE
                         | vk::FormatFeatureFlags::TRANSFER_SRC
                         | vk::FormatFeatureFlags::TRANSFER_DST,
-                ) && !caps
-                    .driver
-                    .map(|driver| driver.driver_id == vk::DriverId::MOLTENVK)
-                    .unwrap_or_default(),
+                ) && !is_moltenvk
+            };
+            features.set(
+                F::TEXTURE_FORMAT_NV12,
+                supports_planar_format(vk::Format::G8_B8R8_2PLANE_420_UNORM),
+            );
+            features.set(
+                F::TEXTURE_FORMAT_P010,
+                supports_planar_format(vk::Format::G10X6_B10X6R10X6_2PLANE_420_UNORM_3PACK16),
+            );
+
+            // Every chroma location and filter of `SamplerYcbcrConversion` has to
+            // work with every multi-planar format that is supported.
+            let supports_conversion = |format| {
+                supports_format(
+                    instance,
+                    phd,
+                    format,
+                    vk::ImageTiling::OPTIMAL,
+                    vk::FormatFeatureFlags::MIDPOINT_CHROMA_SAMPLES
+                        | vk::FormatFeatureFlags::COSITED_CHROMA_SAMPLES
+                        | vk::FormatFeatureFlags::SAMPLED_IMAGE_YCBCR_CONVERSION_LINEAR_FILTER,
+                )
+            };
+            features.set(
+                F::SAMPLER_YCBCR_CONVERSION,
+                sampler_ycbcr_conversion.sampler_ycbcr_conversion != 0
+                    && caps.device_api_version >= vk::API_VERSION_1_1
+                    && features.contains(F::TEXTURE_FORMAT_NV12)
+                    && supports_conversion(vk::Format::G8_B8R8_2PLANE_420_UNORM)
+                    && (!features.contains(F::TEXTURE_FORMAT_P010)
+                        || supports_conversion(
+                            vk::Format::G10X6_B10X6R10X6_2PLANE_420_UNORM_3PACK16,
+                        )),
             );
         }
 
+        // External memory and dedicated allocations are core in Vulkan 1.1.
+        // Modifiers also need `VK_KHR_image_format_list`, which is core in 1.2.
+        let supports_external_memory_fd = cfg!(unix)
+            && caps.device_api_version >= vk::API_VERSION_1_1
+            && caps.supports_extension(vk::KhrExternalMemoryFdFn::name());
+        features.set(F::EXTERNAL_MEMORY_FD, supports_external_memory_fd);
+        features.set(
+            F::EXTERNAL_MEMORY_DMA_BUF,
+            supports_external_memory_fd
+                && caps.supports_extension(vk::ExtExternalMemoryDmaBufFn::name())
+                && caps.supports_extension(vk::ExtImageDrmFormatModifierFn::name())
+                && (caps.device_api_version >= vk::API_VERSION_1_2
+                    || caps.supports_extension(vk::KhrImageFormatListFn::name())),
+        );
+
+        // Timeline semaphores are shared through opaque file descriptors, and
+        // sync files go through binary semaphores.
+        let supports_external_semaphore = |semaphore_type, handle_type| {
+            let mut type_info =
+                vk::SemaphoreTypeCreateInfo::builder().semaphore_type(semaphore_type);
+            let info = vk::PhysicalDeviceExternalSemaphoreInfo::builder()
+                .handle_type(handle_type)
+                .push_next(&mut type_info);
+            let mut properties = vk::ExternalSemaphoreProperties::default();
+            unsafe {
+                instance.get_physical_device_external_semaphore_properties(
+                    phd,
+                    &info,
+                    &mut properties,
+                )
+            };
+            properties.external_semaphore_features.contains(
+                vk::ExternalSemaphoreFeatureFlags::EXPORTABLE
+                    | vk::ExternalSemaphoreFeatureFlags::IMPORTABLE,
+            )
+        };
+        features.set(
+            F::EXTERNAL_SEMAPHORE_FD,
+            cfg!(unix)
+                && caps.device_api_version >= vk::API_VERSION_1_1
+                && features.contains(F::TIMELINE_SEMAPHORE)
+                && caps.supports_extension(vk::KhrExternalSemaphoreFdFn::name())
+                && supports_external_semaphore(
+                    vk::SemaphoreType::TIMELINE,
+                    vk::ExternalSemaphoreHandleTypeFlags::OPAQUE_FD,
+                )
+                && supports_external_semaphore(
+                    vk::SemaphoreType::BINARY,
+                    vk::ExternalSemaphoreHandleTypeFlags::SYNC_FD,
+                ),
+        );
+
+        // Binds go through the primary queue, which is from the first family.
+        let sparse_queue = unsafe { instance.get_physical_device_queue_family_properties(phd) }
+            .first()
+            .map_or(false, |family| {
+                family.queue_flags.contains(vk::QueueFlags::SPARSE_BINDING)
+            });
+        features.set(
+            F::SPARSE_BUFFER,
+            self.core.sparse_binding != 0 && self.core.sparse_residency_buffer != 0 && sparse_queue,
+        );
+        features.set(
+            F::SPARSE_TEXTURE,
+            self.core.sparse_binding != 0
+                && self.core.sparse_residency_image2_d != 0
+                && self.core.sparse_residency_image3_d != 0
+                && sparse_queue,
+        );
+        // Memory can always be bound to resources at any offset that meets
+        // their requirements.
+        features.insert(F::PLACED_RESOURCES);
+
         (features, dl_flags)
     }
 
@@ -832,6 +1126,10 @@ pub struct PhysicalDeviceProperties {
     /// `VK_EXT_subgroup_size_control` extension, promoted to Vulkan 1.3.
     subgroup_size_control: Option<vk::PhysicalDeviceSubgroupSizeControlProperties>,
 
+    /// Additional `vk::PhysicalDevice` properties from Vulkan 1.1,
+    /// identifying the device across APIs.
+    id: Option<vk::PhysicalDeviceIDProperties>,
+
     /// The device API version.
     ///
     /// Which is the version of Vulkan supported for device-level functionality.
@@ -894,7 +1192,9 @@ impl PhysicalDeviceProperties {
             }
 
             // Require `VK_KHR_sampler_ycbcr_conversion` if the associated feature was requested
-            if requested_features.contains(wgt::Features::TEXTURE_FORMAT_NV12) {
+            if requested_features
+                .intersects(wgt::Features::TEXTURE_FORMAT_NV12 | wgt::Features::TEXTURE_FORMAT_P010)
+            {
                 extensions.push(vk::KhrSamplerYcbcrConversionFn::name());
             }
         }
@@ -938,6 +1238,11 @@ impl PhysicalDeviceProperties {
                 }
             }
 
+            // Require `VK_KHR_shader_atomic_int64` if the associated feature was requested
+            if requested_features.contains(wgt::Features::SHADER_INT64_ATOMICS) {
+                extensions.push(vk::KhrShaderAtomicInt64Fn::name());
+            }
+
             //extensions.push(vk::KhrSamplerMirrorClampToEdgeFn::name());
             //extensions.push(vk::ExtSamplerFilterMinmaxFn::name());
         }
@@ -952,6 +1257,11 @@ impl PhysicalDeviceProperties {
             if requested_features.contains(wgt::Features::SUBGROUP) {
                 extensions.push(vk::ExtSubgroupSizeControlFn::name());
             }
+
+            // Optional `VK_KHR_shader_non_semantic_info`
+            if self.supports_extension(vk::KhrShaderNonSemanticInfoFn::name()) {
+                extensions.push(vk::KhrShaderNonSemanticInfoFn::name());
+            }
         }
 
         // Optional `VK_KHR_swapchain_mutable_format`
@@ -964,6 +1274,31 @@ impl PhysicalDeviceProperties {
             extensions.push(vk::ExtRobustness2Fn::name());
         }
 
+        // Optional `VK_EXT_calibrated_timestamps`
+        if self.supports_extension(vk::ExtCalibratedTimestampsFn::name()) {
+            extensions.push(vk::ExtCalibratedTimestampsFn::name());
+        }
+
+        // Optional `VK_EXT_hdr_metadata`
+        if self.supports_extension(vk::ExtHdrMetadataFn::name()) {
+            extensions.push(vk::ExtHdrMetadataFn::name());
+        }
+
+        // Optional `VK_GOOGLE_display_timing`
+        if self.supports_extension(vk::GoogleDisplayTimingFn::name()) {
+            extensions.push(vk::GoogleDisplayTimingFn::name());
+        }
+
+        // Optional `VK_KHR_incremental_present`
+        if self.supports_extension(vk::KhrIncrementalPresentFn::name()) {
+            extensions.push(vk::KhrIncrementalPresentFn::name());
+        }
+
+        // Optional `VK_EXT_full_screen_exclusive`
+        if cfg!(windows) && self.supports_extension(vk::ExtFullScreenExclusiveFn::name()) {
+            extensions.push(vk::ExtFullScreenExclusiveFn::name());
+        }
+
         // Require `VK_KHR_draw_indirect_count` if the associated feature was requested
         // Even though Vulkan 1.2 has promoted the extension to core, we must require the extension to avoid
         // large amounts of spaghetti involved with using PhysicalDeviceVulkan12Features.
@@ -978,16 +1313,82 @@ impl PhysicalDeviceProperties {
             extensions.push(vk::KhrBufferDeviceAddressFn::name());
         }
 
+        // Require `VK_KHR_buffer_device_address` if the associated feature was requested
+        if requested_features.contains(wgt::Features::BUFFER_DEVICE_ADDRESS)
+            && !extensions.contains(&vk::KhrBufferDeviceAddressFn::name())
+        {
+            extensions.push(vk::KhrBufferDeviceAddressFn::name());
+        }
+
+        // Require `VK_KHR_push_descriptor` if the associated feature was requested
+        if requested_features.contains(wgt::Features::INLINE_BIND_GROUPS) {
+            extensions.push(vk::KhrPushDescriptorFn::name());
+        }
+
         // Require `VK_KHR_ray_query` if the associated feature was requested
         if requested_features.contains(wgt::Features::RAY_QUERY) {
             extensions.push(vk::KhrRayQueryFn::name());
         }
 
+        // Even though Vulkan 1.2 has promoted the extension to core, we must require the extension to avoid
+        // large amounts of spaghetti involved with using PhysicalDeviceVulkan12Features.
+        if requested_features.contains(wgt::Features::SAMPLER_REDUCTION_MODE) {
+            extensions.push(vk::ExtSamplerFilterMinmaxFn::name());
+        }
+
         // Require `VK_EXT_conservative_rasterization` if the associated feature was requested
         if requested_features.contains(wgt::Features::CONSERVATIVE_RASTERIZATION) {
             extensions.push(vk::ExtConservativeRasterizationFn::name());
         }
 
+        // Require `VK_EXT_shader_viewport_index_layer` if the associated feature was requested
+        // Like `VK_KHR_draw_indirect_count`, it is core in Vulkan 1.2, but only behind
+        // `PhysicalDeviceVulkan12Features`.
+        if requested_features.contains(wgt::Features::MULTI_VIEWPORT) {
+            extensions.push(vk::ExtShaderViewportIndexLayerFn::name());
+        }
+
+        // Require `VK_EXT_extended_dynamic_state` if the associated feature was requested
+        // Like `VK_KHR_draw_indirect_count`, it is core in Vulkan 1.3, but only behind
+        // `PhysicalDeviceVulkan13Features`.
+        if requested_features.contains(wgt::Features::EXTENDED_DYNAMIC_STATE) {
+            extensions.push(vk::ExtExtendedDynamicStateFn::name());
+        }
+
+        // Require `VK_EXT_custom_border_color` if the associated feature was requested
+        if requested_features.contains(wgt::Features::ADDRESS_MODE_CLAMP_TO_CUSTOM_BORDER) {
+            extensions.push(vk::ExtCustomBorderColorFn::name());
+        }
+
+        // Require `VK_KHR_present_id` and `VK_KHR_present_wait` if the associated feature was requested
+        if requested_features.contains(wgt::Features::PRESENT_WAIT) {
+            extensions.push(vk::KhrPresentIdFn::name());
+            extensions.push(vk::KhrPresentWaitFn::name());
+        }
+
+        // Require `VK_EXT_fragment_shader_interlock` if the associated feature was requested
+        if requested_features.contains(wgt::Features::FRAGMENT_SHADER_INTERLOCK) {
+            extensions.push(vk::ExtFragmentShaderInterlockFn::name());
+        }
+
+        // Require `VK_KHR_external_memory_fd` if external memory was requested,
+        // along with `VK_EXT_external_memory_dma_buf` and
+        // `VK_EXT_image_drm_format_modifier` for dma-bufs.
+        if requested_features
+            .intersects(wgt::Features::EXTERNAL_MEMORY_FD | wgt::Features::EXTERNAL_MEMORY_DMA_BUF)
+        {
+            extensions.push(vk::KhrExternalMemoryFdFn::name());
+        }
+        if requested_features.contains(wgt::Features::EXTERNAL_MEMORY_DMA_BUF) {
+            extensions.push(vk::ExtExternalMemoryDmaBufFn::name());
+            extensions.push(vk::ExtImageDrmFormatModifierFn::name());
+        }
+
+        // Require `VK_KHR_external_semaphore_fd` if external semaphores were requested
+        if requested_features.contains(wgt::Features::EXTERNAL_SEMAPHORE_FD) {
+            extensions.push(vk::KhrExternalSemaphoreFdFn::name());
+        }
+
         // Require `VK_KHR_portability_subset` on macOS/iOS
         #[cfg(any(target_os = "macos", target_os = "ios"))]
         extensions.push(vk::KhrPortabilitySubsetFn::name());
@@ -1077,6 +1478,7 @@ impl PhysicalDeviceProperties {
             max_compute_workgroups_per_dimension,
             max_buffer_size,
             max_non_sampler_bindings: std::u32::MAX,
+            max_additional_queues: 0,
         }
     }
 
@@ -1164,6 +1566,13 @@ impl super::InstanceShared {
                     builder = builder.push_next(next);
                 }
 
+                if capabilities.device_api_version >= vk::API_VERSION_1_1 {
+                    let next = capabilities
+                        .id
+                        .insert(vk::PhysicalDeviceIDProperties::default());
+                    builder = builder.push_next(next);
+                }
+
                 let mut properties2 = builder.build();
                 unsafe {
                     get_device_properties.get_physical_device_properties2(phd, &mut properties2);
@@ -1262,6 +1671,15 @@ impl super::InstanceShared {
                 builder = builder.push_next(&mut next.0);
                 builder = builder.push_next(&mut next.1);
             }
+            // `VK_KHR_shader_atomic_int64` is promoted to 1.2
+            if capabilities.device_api_version >= vk::API_VERSION_1_2
+                || capabilities.supports_extension(vk::KhrShaderAtomicInt64Fn::name())
+            {
+                let next = features
+                    .shader_atomic_int64
+                    .insert(vk::PhysicalDeviceShaderAtomicInt64Features::default());
+                builder = builder.push_next(next);
+            }
             if capabilities.supports_extension(vk::KhrAccelerationStructureFn::name()) {
                 let next = features
                     .acceleration_structure
@@ -1289,6 +1707,38 @@ impl super::InstanceShared {
                 builder = builder.push_next(next);
             }
 
+            if capabilities.supports_extension(vk::ExtExtendedDynamicStateFn::name()) {
+                let next = features
+                    .extended_dynamic_state
+                    .insert(vk::PhysicalDeviceExtendedDynamicStateFeaturesEXT::default());
+                builder = builder.push_next(next);
+            }
+
+            if capabilities.supports_extension(vk::ExtCustomBorderColorFn::name()) {
+                let next = features
+                    .custom_border_color
+                    .insert(vk::PhysicalDeviceCustomBorderColorFeaturesEXT::default());
+                builder = builder.push_next(next);
+            }
+
+            if capabilities.supports_extension(vk::KhrPresentIdFn::name())
+                && capabilities.supports_extension(vk::KhrPresentWaitFn::name())
+            {
+                let next = features.present_wait.insert((
+                    vk::PhysicalDevicePresentIdFeaturesKHR::default(),
+                    vk::PhysicalDevicePresentWaitFeaturesKHR::default(),
+                ));
+                builder = builder.push_next(&mut next.0);
+                builder = builder.push_next(&mut next.1);
+            }
+
+            if capabilities.supports_extension(vk::ExtFragmentShaderInterlockFn::name()) {
+                let next = features
+                    .fragment_shader_interlock
+                    .insert(vk::PhysicalDeviceFragmentShaderInterlockFeaturesEXT::default());
+                builder = builder.push_next(next);
+            }
+
             let mut features2 = builder.build();
             unsafe {
                 get_device_properties.get_physical_device_features2(phd, &mut features2);
@@ -1302,6 +1752,35 @@ impl super::InstanceShared {
     }
 }
 
+/// Decode `VkPhysicalDeviceProperties::driverVersion`.
+///
+/// The encoding of this field is vendor-specific; vendors that don't document their
+/// own scheme are assumed to follow the `VK_MAKE_API_VERSION` layout.
+fn parse_driver_version(vendor_id: u32, version: u32) -> wgt::DriverVersion {
+    use crate::auxil::db;
+
+    match vendor_id {
+        db::nvidia::VENDOR => wgt::DriverVersion {
+            major: (version >> 22) & 0x3ff,
+            minor: (version >> 14) & 0xff,
+            patch: (version >> 6) & 0xff,
+            build: version & 0x3f,
+        },
+        db::intel::VENDOR if cfg!(windows) => wgt::DriverVersion {
+            major: version >> 14,
+            minor: version & 0x3fff,
+            patch: 0,
+            build: 0,
+        },
+        _ => wgt::DriverVersion {
+            major: vk::api_version_major(version),
+            minor: vk::api_version_minor(version),
+            patch: vk::api_version_patch(version),
+            build: 0,
+        },
+    }
+}
+
 impl super::Instance {
     pub fn expose_adapter(
         &self,
@@ -1311,6 +1790,22 @@ impl super::Instance {
         use crate::auxil::db;
 
         let (phd_capabilities, phd_features) = self.shared.inspect(phd);
+        let mem_properties = unsafe { self.shared.raw.get_physical_device_memory_properties(phd) };
+        let memory_types =
+            &mem_properties.memory_types[..mem_properties.memory_type_count as usize];
+        let memory_heaps =
+            &mem_properties.memory_heaps[..mem_properties.memory_heap_count as usize];
+        let unified_memory = matches!(
+            phd_capabilities.properties.device_type,
+            vk::PhysicalDeviceType::INTEGRATED_GPU | vk::PhysicalDeviceType::CPU
+        );
+        // Without resizable BAR, the host visible part of VRAM is a 256 MiB window.
+        let resizable_bar = !unified_memory
+            && memory_types.iter().any(|memory_type| {
+                memory_type.property_flags.contains(
+                    vk::MemoryPropertyFlags::DEVICE_LOCAL | vk::MemoryPropertyFlags::HOST_VISIBLE,
+                ) && memory_heaps[memory_type.heap_index as usize].size > 256 * 1024 * 1024
+            });
 
         let info = wgt::AdapterInfo {
             name: {
@@ -1348,10 +1843,39 @@ impl super::Instance {
                     .to_owned()
             },
             backend: wgt::Backend::Vulkan,
+            driver_version: Some(parse_driver_version(
+                phd_capabilities.properties.vendor_id,
+                phd_capabilities.properties.driver_version,
+            )),
+            device_uuid: phd_capabilities.id.map(|id| id.device_uuid),
+            device_luid: phd_capabilities
+                .id
+                .filter(|id| id.device_luid_valid == vk::TRUE)
+                .map(|id| id.device_luid),
+            memory_heaps: memory_heaps
+                .iter()
+                .enumerate()
+                .map(|(index, heap)| wgt::MemoryHeapInfo {
+                    size: heap.size,
+                    device_local: heap.flags.contains(vk::MemoryHeapFlags::DEVICE_LOCAL),
+                    host_visible: memory_types.iter().any(|memory_type| {
+                        memory_type.heap_index as usize == index
+                            && memory_type
+                                .property_flags
+                                .contains(vk::MemoryPropertyFlags::HOST_VISIBLE)
+                    }),
+                })
+                .collect(),
+            unified_memory,
+            resizable_bar,
         };
 
-        let (available_features, downlevel_flags) =
+        let (available_features, mut downlevel_flags) =
             phd_features.to_wgpu(&self.shared.raw, phd, &phd_capabilities);
+        downlevel_flags.set(
+            wgt::DownlevelFlags::UNIFIED_MEMORY_MAPPABLE_BUFFERS,
+            unified_memory,
+        );
         let mut workarounds = super::Workarounds::empty();
         {
             // TODO: only enable for particular devices
@@ -1481,9 +2005,15 @@ impl super::Instance {
                 }),
             image_format_list: phd_capabilities.device_api_version >= vk::API_VERSION_1_2
                 || phd_capabilities.supports_extension(vk::KhrImageFormatListFn::name()),
+            max_sampler_lod_bias: phd_capabilities.properties.limits.max_sampler_lod_bias,
         };
         let capabilities = crate::Capabilities {
-            limits: phd_capabilities.to_wgpu_limits(),
+            limits: wgt::Limits {
+                // Additional queues are taken from the same family as the primary one,
+                // so command buffers can be submitted to any of them.
+                max_additional_queues: queue_families[0].queue_count.saturating_sub(1),
+                ..phd_capabilities.to_wgpu_limits()
+            },
             alignments: phd_capabilities.to_hal_alignments(),
             downlevel: wgt::DownlevelCapabilities {
                 flags: downlevel_flags,
@@ -1522,6 +2052,41 @@ impl super::Adapter {
         self.raw
     }
 
+    /// Find a queue family, other than the one of the main queue, that can
+    /// serve as the [`crate::OpenDevice::transfer_queue`].
+    ///
+    /// Only discrete GPUs back such families with DMA engines. Timeline
+    /// semaphores are needed for the queues to wait on each other on the GPU.
+    fn transfer_queue_family(&self) -> Option<u32> {
+        if self.phd_capabilities.properties.device_type != vk::PhysicalDeviceType::DISCRETE_GPU
+            || !self.private_caps.timeline_semaphores
+        {
+            return None;
+        }
+        let queue_families = unsafe {
+            self.instance
+                .raw
+                .get_physical_device_queue_family_properties(self.raw)
+        };
+        // Copies of any texture region need a granularity of a single texel.
+        let granularity = vk::Extent3D {
+            width: 1,
+            height: 1,
+            depth: 1,
+        };
+        queue_families
+            .iter()
+            .position(|family| {
+                family.queue_count != 0
+                    && family.queue_flags.contains(vk::QueueFlags::TRANSFER)
+                    && !family
+                        .queue_flags
+                        .intersects(vk::QueueFlags::GRAPHICS | vk::QueueFlags::COMPUTE)
+                    && family.min_image_transfer_granularity == granularity
+            })
+            .map(|index| index as u32)
+    }
+
     pub fn physical_device_capabilities(&self) -> &PhysicalDeviceProperties {
         &self.phd_capabilities
     }
@@ -1531,7 +2096,7 @@ impl super::Adapter {
     }
 
     pub fn required_device_extensions(&self, features: wgt::Features) -> Vec<&'static CStr> {
-        let (supported_extensions, unsupported_extensions) = self
+        let (mut supported_extensions, unsupported_extensions) = self
             .phd_capabilities
             .get_required_extensions(features)
             .iter()
@@ -1539,6 +2104,16 @@ impl super::Adapter {
                 self.phd_capabilities.supports_extension(extension)
             });
 
+        // `VK_EXT_full_screen_exclusive` also depends on an instance extension.
+        if !self
+            .instance
+            .extensions
+            .contains(&vk::KhrGetSurfaceCapabilities2Fn::name())
+        {
+            supported_extensions
+                .retain(|&extension| extension != vk::ExtFullScreenExclusiveFn::name());
+        }
+
         if !unsupported_extensions.is_empty() {
             log::warn!("Missing extensions: {:?}", unsupported_extensions);
         }
@@ -1589,6 +2164,32 @@ impl super::Adapter {
         features: wgt::Features,
         family_index: u32,
         queue_index: u32,
+    ) -> Result<crate::OpenDevice<super::Api>, crate::DeviceError> {
+        unsafe {
+            self.device_from_raw_impl(
+                raw_device,
+                handle_is_owned,
+                enabled_extensions,
+                features,
+                family_index,
+                queue_index,
+                None,
+            )
+        }
+    }
+
+    /// Like [`Self::device_from_raw`], with queue family `transfer_family_index`
+    /// providing the [`crate::OpenDevice::transfer_queue`].
+    #[allow(clippy::too_many_arguments)]
+    unsafe fn device_from_raw_impl(
+        &self,
+        raw_device: ash::Device,
+        handle_is_owned: bool,
+        enabled_extensions: &[&'static CStr],
+        features: wgt::Features,
+        family_index: u32,
+        queue_index: u32,
+        transfer_family_index: Option<u32>,
     ) -> Result<crate::OpenDevice<super::Api>, crate::DeviceError> {
         let mem_properties = {
             profiling::scope!("vkGetPhysicalDeviceMemoryProperties");
@@ -1607,6 +2208,17 @@ impl super::Adapter {
                 u
             }
         });
+        let lazily_allocated_memory_types =
+            memory_types.iter().enumerate().fold(0, |u, (i, mem)| {
+                if mem
+                    .property_flags
+                    .contains(vk::MemoryPropertyFlags::LAZILY_ALLOCATED)
+                {
+                    u | (1 << i)
+                } else {
+                    u
+                }
+            }) & valid_ash_memory_types;
 
         let swapchain_fn = khr::Swapchain::new(&self.instance.raw, &raw_device);
 
@@ -1626,6 +2238,63 @@ impl super::Adapter {
         } else {
             None
         };
+        let extended_dynamic_state_fn =
+            if enabled_extensions.contains(&ext::ExtendedDynamicState::name()) {
+                Some(ext::ExtendedDynamicState::new(
+                    &self.instance.raw,
+                    &raw_device,
+                ))
+            } else {
+                None
+            };
+        let calibrated_timestamps_fn =
+            if enabled_extensions.contains(&vk::ExtCalibratedTimestampsFn::name()) {
+                let instance = self.instance.raw.handle();
+                let fns = vk::ExtCalibratedTimestampsFn::load(|name| unsafe {
+                    std::mem::transmute(
+                        self.instance
+                            .entry
+                            .get_instance_proc_addr(instance, name.as_ptr()),
+                    )
+                });
+                let mut count = 0;
+                let mut domains = Vec::new();
+                let result = unsafe {
+                    (fns.get_physical_device_calibrateable_time_domains_ext)(
+                        self.raw,
+                        &mut count,
+                        std::ptr::null_mut(),
+                    )
+                }
+                .result()
+                .and_then(|()| {
+                    domains.resize(count as usize, vk::TimeDomainEXT::default());
+                    unsafe {
+                        (fns.get_physical_device_calibrateable_time_domains_ext)(
+                            self.raw,
+                            &mut count,
+                            domains.as_mut_ptr(),
+                        )
+                    }
+                    .result()
+                });
+                domains.truncate(count as usize);
+                match result {
+                    Ok(())
+                        if domains.contains(&vk::TimeDomainEXT::DEVICE)
+                            && domains.contains(&vk::TimeDomainEXT::CLOCK_MONOTONIC) =>
+                    {
+                        Some(fns)
+                    }
+                    Ok(()) => None,
+                    Err(err) => {
+                        log::warn!("Unable to query calibrateable time domains: {err}");
+                        None
+                    }
+                }
+            } else {
+                None
+            };
         let ray_tracing_fns = if enabled_extensions.contains(&khr::AccelerationStructure::name())
             && enabled_extensions.contains(&khr::BufferDeviceAddress::name())
         {
@@ -1643,6 +2312,90 @@ impl super::Adapter {
             None
         };
 
+        let buffer_device_address_fn =
+            if enabled_extensions.contains(&khr::BufferDeviceAddress::name()) {
+                Some(khr::BufferDeviceAddress::new(
+                    &self.instance.raw,
+                    &raw_device,
+                ))
+            } else {
+                None
+            };
+
+        let push_descriptor_fn = if enabled_extensions.contains(&khr::PushDescriptor::name()) {
+            Some(khr::PushDescriptor::new(&self.instance.raw, &raw_device))
+        } else {
+            None
+        };
+        let external_memory_fd_fn = if enabled_extensions.contains(&khr::ExternalMemoryFd::name()) {
+            Some(khr::ExternalMemoryFd::new(&self.instance.raw, &raw_device))
+        } else {
+            None
+        };
+        let external_semaphore_fd_fn =
+            if enabled_extensions.contains(&khr::ExternalSemaphoreFd::name()) {
+                Some(khr::ExternalSemaphoreFd::new(
+                    &self.instance.raw,
+                    &raw_device,
+                ))
+            } else {
+                None
+            };
+        let hdr_metadata_fn = if enabled_extensions.contains(&vk::ExtHdrMetadataFn::name()) {
+            Some(vk::ExtHdrMetadataFn::load(|name| unsafe {
+                std::mem::transmute(
+                    self.instance
+                        .raw
+                        .get_device_proc_addr(raw_device.handle(), name.as_ptr()),
+                )
+            }))
+        } else {
+            None
+        };
+        let display_timing_fn = if enabled_extensions.contains(&vk::GoogleDisplayTimingFn::name()) {
+            Some(vk::GoogleDisplayTimingFn::load(|name| unsafe {
+                std::mem::transmute(
+                    self.instance
+                        .raw
+                        .get_device_proc_addr(raw_device.handle(), name.as_ptr()),
+                )
+            }))
+        } else {
+            None
+        };
+        let present_wait_fn = if enabled_extensions.contains(&vk::KhrPresentWaitFn::name()) {
+            Some(vk::KhrPresentWaitFn::load(|name| unsafe {
+                std::mem::transmute(
+                    self.instance
+                        .raw
+                        .get_device_proc_addr(raw_device.handle(), name.as_ptr()),
+                )
+            }))
+        } else {
+            None
+        };
+        let full_screen_exclusive_fn =
+            if enabled_extensions.contains(&vk::ExtFullScreenExclusiveFn::name()) {
+                Some(ext::FullScreenExclusive::new(
+                    &self.instance.raw,
+                    &raw_device,
+                ))
+            } else {
+                None
+            };
+        let image_drm_format_modifier_fn =
+            if enabled_extensions.contains(&vk::ExtImageDrmFormatModifierFn::name()) {
+                Some(vk::ExtImageDrmFormatModifierFn::load(|name| unsafe {
+                    std::mem::transmute(
+                        self.instance
+                            .raw
+                            .get_device_proc_addr(raw_device.handle(), name.as_ptr()),
+                    )
+                }))
+            } else {
+                None
+            };
+
         let naga_options = {
             use naga::back::spv;
 
@@ -1680,6 +2433,11 @@ impl super::Adapter {
                 capabilities.push(spv::Capability::Geometry);
             }
 
+            if features.contains(wgt::Features::MULTI_VIEWPORT) {
+                capabilities.push(spv::Capability::MultiViewport);
+                capabilities.push(spv::Capability::ShaderViewportIndexLayerEXT);
+            }
+
             if features.intersects(wgt::Features::SUBGROUP | wgt::Features::SUBGROUP_VERTEX) {
                 capabilities.push(spv::Capability::GroupNonUniform);
                 capabilities.push(spv::Capability::GroupNonUniformVote);
@@ -1707,11 +2465,29 @@ impl super::Adapter {
                 capabilities.push(spv::Capability::Int64);
             }
 
+            if features.contains(wgt::Features::SHADER_INT64_ATOMICS) {
+                capabilities.push(spv::Capability::Int64Atomics);
+            }
+
+            if features.contains(wgt::Features::BUFFER_DEVICE_ADDRESS) {
+                capabilities.push(spv::Capability::PhysicalStorageBufferAddresses);
+            }
+
+            if features.contains(wgt::Features::FRAGMENT_SHADER_INTERLOCK) {
+                capabilities.push(spv::Capability::FragmentShaderPixelInterlockEXT);
+            }
+
             let mut flags = spv::WriterFlags::empty();
             flags.set(
                 spv::WriterFlags::DEBUG,
                 self.instance.flags.contains(wgt::InstanceFlags::DEBUG),
             );
+            flags.set(
+                spv::WriterFlags::NON_SEMANTIC_DEBUG_INFO,
+                self.instance.flags.contains(wgt::InstanceFlags::DEBUG)
+                    && (self.phd_capabilities.device_api_version >= vk::API_VERSION_1_3
+                        || enabled_extensions.contains(&vk::KhrShaderNonSemanticInfoFn::name())),
+            );
             flags.set(
                 spv::WriterFlags::LABEL_VARYINGS,
                 self.phd_capabilities.properties.vendor_id != crate::auxil::db::qualcomm::VENDOR,
@@ -1772,6 +2548,7 @@ impl super::Adapter {
             raw: raw_device,
             family_index,
             queue_index,
+            transfer_family_index,
             raw_queue,
             handle_is_owned,
             instance: Arc::clone(&self.instance),
@@ -1780,7 +2557,18 @@ impl super::Adapter {
             extension_fns: super::DeviceExtensionFunctions {
                 draw_indirect_count: indirect_count_fn,
                 timeline_semaphore: timeline_semaphore_fn,
+                extended_dynamic_state: extended_dynamic_state_fn,
+                calibrated_timestamps: calibrated_timestamps_fn,
                 ray_tracing: ray_tracing_fns,
+                buffer_device_address: buffer_device_address_fn,
+                push_descriptor: push_descriptor_fn,
+                external_memory_fd: external_memory_fd_fn,
+                image_drm_format_modifier: image_drm_format_modifier_fn,
+                external_semaphore_fd: external_semaphore_fd_fn,
+                hdr_metadata: hdr_metadata_fn,
+                display_timing: display_timing_fn,
+                full_screen_exclusive: full_screen_exclusive_fn,
+                present_wait: present_wait_fn,
             },
             vendor_id: self.phd_capabilities.properties.vendor_id,
             timestamp_period: self.phd_capabilities.properties.limits.timestamp_period,
@@ -1799,6 +2587,8 @@ impl super::Adapter {
             device: Arc::clone(&shared),
             family_index,
             relay_semaphores: Mutex::new(relay_semaphores),
+            fence_waits: Mutex::new(Vec::new()),
+            sync_files: Mutex::new(super::SyncFileSemaphores::default()),
         };
 
         let mem_allocator = {
@@ -1835,7 +2625,7 @@ impl super::Adapter {
             };
             gpu_alloc::GpuAllocator::new(config, properties)
         };
-        let desc_allocator = gpu_descriptor::DescriptorAllocator::new(
+        let desc_arenas = super::descriptor::DescriptorArenas::new(
             if let Some(di) = self.phd_capabilities.descriptor_indexing {
                 di.max_update_after_bind_descriptors_in_all_pools
             } else {
@@ -1846,14 +2636,20 @@ impl super::Adapter {
         let device = super::Device {
             shared,
             mem_allocator: Mutex::new(mem_allocator),
-            desc_allocator: Mutex::new(desc_allocator),
+            desc_arenas,
             valid_ash_memory_types,
+            lazily_allocated_memory_types,
             naga_options,
             #[cfg(feature = "renderdoc")]
             render_doc: Default::default(),
         };
 
-        Ok(crate::OpenDevice { device, queue })
+        Ok(crate::OpenDevice {
+            device,
+            queue,
+            additional_queues: Vec::new(),
+            transfer_queue: None,
+        })
     }
 }
 
@@ -1863,17 +2659,52 @@ impl crate::Adapter for super::Adapter {
     unsafe fn open(
         &self,
         features: wgt::Features,
-        _limits: &wgt::Limits,
+        limits: &wgt::Limits,
+        queue_priority: wgt::QueuePriority,
+        transfer_queue: bool,
     ) -> Result<crate::OpenDevice<super::Api>, crate::DeviceError> {
-        let enabled_extensions = self.required_device_extensions(features);
+        let mut enabled_extensions = self.required_device_extensions(features);
+        let mut global_priority = conv::map_queue_global_priority(queue_priority);
+        if global_priority.is_some() {
+            if self
+                .phd_capabilities
+                .supports_extension(vk::ExtGlobalPriorityFn::name())
+            {
+                enabled_extensions.push(vk::ExtGlobalPriorityFn::name());
+            } else {
+                log::warn!("Queue priority {queue_priority:?} requires VK_EXT_global_priority");
+                global_priority = None;
+            }
+        }
         let mut enabled_phd_features = self.physical_device_features(&enabled_extensions, features);
 
         let family_index = 0; //TODO
-        let family_info = vk::DeviceQueueCreateInfo::builder()
-            .queue_family_index(family_index)
-            .queue_priorities(&[1.0])
+        let queue_priorities = vec![1.0; 1 + limits.max_additional_queues as usize];
+        let mut global_priority_info = vk::DeviceQueueGlobalPriorityCreateInfoEXT::builder()
+            .global_priority(global_priority.unwrap_or(vk::QueueGlobalPriorityEXT::MEDIUM))
             .build();
-        let family_infos = [family_info];
+        let mut family_info = vk::DeviceQueueCreateInfo::builder()
+            .queue_family_index(family_index)
+            .queue_priorities(&queue_priorities);
+        if global_priority.is_some() {
+            family_info = family_info.push_next(&mut global_priority_info);
+        }
+        let family_info = family_info.build();
+        let transfer_family_index = if transfer_queue {
+            self.transfer_queue_family()
+        } else {
+            None
+        };
+        let transfer_priorities = [1.0];
+        let mut family_infos = vec![family_info];
+        if let Some(transfer_family_index) = transfer_family_index {
+            family_infos.push(
+                vk::DeviceQueueCreateInfo::builder()
+                    .queue_family_index(transfer_family_index)
+                    .queue_priorities(&transfer_priorities)
+                    .build(),
+            );
+        }
 
         let str_pointers = enabled_extensions
             .iter()
@@ -1889,21 +2720,52 @@ impl crate::Adapter for super::Adapter {
         let info = enabled_phd_features
             .add_to_device_create_builder(pre_info)
             .build();
-        let raw_device = {
+        let raw_device = loop {
             profiling::scope!("vkCreateDevice");
-            unsafe { self.instance.raw.create_device(self.raw, &info, None)? }
+            match unsafe { self.instance.raw.create_device(self.raw, &info, None) } {
+                // Global priorities above medium may be reserved to privileged
+                // processes, so step down until one is permitted.
+                Err(vk::Result::ERROR_NOT_PERMITTED_EXT)
+                    if global_priority_info.global_priority
+                        != vk::QueueGlobalPriorityEXT::MEDIUM =>
+                {
+                    let lower = match global_priority_info.global_priority {
+                        vk::QueueGlobalPriorityEXT::REALTIME => vk::QueueGlobalPriorityEXT::HIGH,
+                        _ => vk::QueueGlobalPriorityEXT::MEDIUM,
+                    };
+                    log::warn!(
+                        "Queue global priority {:?} is not permitted, trying {lower:?}",
+                        global_priority_info.global_priority
+                    );
+                    // `info` points to this through the queue create info.
+                    global_priority_info.global_priority = lower;
+                }
+                result => break result?,
+            }
         };
 
-        unsafe {
-            self.device_from_raw(
+        let mut open = unsafe {
+            self.device_from_raw_impl(
                 raw_device,
                 true,
                 &enabled_extensions,
                 features,
                 family_info.queue_family_index,
                 0,
+                transfer_family_index,
             )
+        }?;
+
+        for queue_index in 1..=limits.max_additional_queues {
+            let queue = unsafe { open.queue.sibling(queue_index) }?;
+            open.additional_queues.push(queue);
+        }
+        if let Some(transfer_family_index) = transfer_family_index {
+            let queue = unsafe { open.queue.in_family(transfer_family_index, 0) }?;
+            open.transfer_queue = Some(queue);
         }
+
+        Ok(open)
     }
 
     unsafe fn texture_format_capabilities(
@@ -1998,6 +2860,35 @@ impl crate::Adapter for super::Adapter {
             }
         };
 
+        // The limits are the counts every format of a kind can be sampled and
+        // rendered with, but some formats only support fewer.
+        let attachment_usage = if features.contains(vk::FormatFeatureFlags::COLOR_ATTACHMENT) {
+            vk::ImageUsageFlags::COLOR_ATTACHMENT
+        } else if features.contains(vk::FormatFeatureFlags::DEPTH_STENCIL_ATTACHMENT) {
+            vk::ImageUsageFlags::DEPTH_STENCIL_ATTACHMENT
+        } else {
+            vk::ImageUsageFlags::empty()
+        };
+        let sample_flags = if attachment_usage.is_empty() {
+            vk::SampleCountFlags::TYPE_1
+        } else {
+            match unsafe {
+                self.instance
+                    .raw
+                    .get_physical_device_image_format_properties(
+                        self.raw,
+                        vk_format,
+                        vk::ImageType::TYPE_2D,
+                        vk::ImageTiling::OPTIMAL,
+                        attachment_usage,
+                        vk::ImageCreateFlags::empty(),
+                    )
+            } {
+                Ok(image_properties) => image_properties.sample_counts & sample_flags,
+                Err(_) => vk::SampleCountFlags::TYPE_1,
+            }
+        };
+
         flags.set(
             Tfc::MULTISAMPLE_X2,
             sample_flags.contains(vk::SampleCountFlags::TYPE_2),
@@ -2108,10 +2999,46 @@ impl crate::Adapter for super::Adapter {
             }
         };
 
-        let formats = raw_surface_formats
-            .into_iter()
+        let formats: Vec<_> = raw_surface_formats
+            .iter()
+            .copied()
             .filter_map(conv::map_vk_surface_formats)
             .collect();
+        let mut color_spaces: Vec<_> = formats
+            .iter()
+            .map(|&format| (format, wgt::ColorSpace::Srgb))
+            .collect();
+        for (format, color_space) in raw_surface_formats
+            .into_iter()
+            .filter_map(conv::map_vk_wide_gamut_surface_format)
+        {
+            // Formats only offered in a wide gamut, like the 10 bit ones of
+            // many HDR10 displays, are left out, as every format has to be
+            // usable in sRGB.
+            if formats.contains(&format) && !color_spaces.contains(&(format, color_space)) {
+                color_spaces.push((format, color_space));
+            }
+        }
+
+        // Only Win32 surfaces can be told apart, elsewhere the driver decides.
+        let fullscreen_modes = if surface.hwnd.is_some()
+            && self
+                .phd_capabilities
+                .supports_extension(vk::ExtFullScreenExclusiveFn::name())
+            && self
+                .instance
+                .extensions
+                .contains(&vk::KhrGetSurfaceCapabilities2Fn::name())
+        {
+            vec![
+                wgt::FullscreenMode::Windowed,
+                wgt::FullscreenMode::Borderless,
+                wgt::FullscreenMode::Exclusive,
+            ]
+        } else {
+            vec![wgt::FullscreenMode::Windowed]
+        };
+
         Some(crate::SurfaceCapabilities {
             formats,
             // TODO: Right now we're always trunkating the swap chain
@@ -2119,6 +3046,7 @@ impl crate::Adapter for super::Adapter {
             // Instead, we should use extensions when available to wait in present.
             // See https://github.com/gfx-rs/wgpu/issues/2869
             maximum_frame_latency: (caps.min_image_count - 1)..=(max_image_count - 1), // Note this can't underflow since both `min_image_count` is at least one and we already patched `max_image_count`.
+            image_count: caps.min_image_count..=max_image_count,
             current_extent,
             usage: conv::map_vk_image_usage(caps.supported_usage_flags),
             present_modes: raw_present_modes
@@ -2126,6 +3054,67 @@ impl crate::Adapter for super::Adapter {
                 .flat_map(conv::map_vk_present_mode)
                 .collect(),
             composite_alpha_modes: conv::map_vk_composite_alpha(caps.supported_composite_alpha),
+            transforms: conv::map_vk_surface_transforms(caps.supported_transforms),
+            current_transform: conv::map_vk_surface_transform(caps.current_transform),
+            color_spaces,
+            max_array_layers: caps.max_image_array_layers,
+            // Display modes can't be chosen through Vulkan.
+            fullscreen_modes,
+            display_modes: Vec::new(),
+        })
+    }
+
+    unsafe fn headless_surface_capabilities(
+        &self,
+        display_handle: raw_window_handle::RawDisplayHandle,
+    ) -> Option<crate::SurfaceCapabilities> {
+        use raw_window_handle::RawDisplayHandle as Rdh;
+
+        // The WSI extension a window on this display would be created with.
+        let wsi_extension = match display_handle {
+            Rdh::Wayland(_) => khr::WaylandSurface::name(),
+            Rdh::Xlib(_) => khr::XlibSurface::name(),
+            Rdh::Xcb(_) => khr::XcbSurface::name(),
+            Rdh::Android(_) => khr::AndroidSurface::name(),
+            Rdh::Windows(_) => khr::Win32Surface::name(),
+            Rdh::AppKit(_) | Rdh::UiKit(_) => ext::MetalSurface::name(),
+            _ => return None,
+        };
+        let extensions = &self.instance.extensions;
+        if !extensions.contains(&wsi_extension)
+            || !extensions.contains(&ext::HeadlessSurface::name())
+        {
+            return None;
+        }
+
+        // A headless surface stands in for the window; only the extent is
+        // specific to a real one.
+        let raw = {
+            profiling::scope!("vkCreateHeadlessSurfaceEXT");
+            let headless_loader =
+                ext::HeadlessSurface::new(&self.instance.entry, &self.instance.raw);
+            let info = vk::HeadlessSurfaceCreateInfoEXT::builder();
+            match unsafe { headless_loader.create_headless_surface(&info, None) } {
+                Ok(raw) => raw,
+                Err(e) => {
+                    log::error!("create_headless_surface: {}", e);
+                    return None;
+                }
+            }
+        };
+        let surface = super::Surface {
+            raw,
+            functor: khr::Surface::new(&self.instance.entry, &self.instance.raw),
+            instance: Arc::clone(&self.instance),
+            swapchain: parking_lot::RwLock::new(None),
+            hwnd: None,
+        };
+        let caps = unsafe { self.surface_capabilities(&surface) };
+        unsafe { surface.functor.destroy_surface(surface.raw, None) };
+
+        caps.map(|caps| crate::SurfaceCapabilities {
+            current_extent: None,
+            ..caps
         })
     }
 
@@ -2270,3 +3259,55 @@ fn is_intel_igpu_outdated_for_robustness2(
     }
     is_outdated
 }
+
+#[cfg(test)]
+mod tests {
+    use super::{parse_driver_version, vk};
+    use crate::auxil::db;
+
+    fn version(major: u32, minor: u32, patch: u32, build: u32) -> wgt::DriverVersion {
+        wgt::DriverVersion {
+            major,
+            minor,
+            patch,
+            build,
+        }
+    }
+
+    #[test]
+    fn test_nvidia_driver_version() {
+        assert_eq!(
+            parse_driver_version(db::nvidia::VENDOR, (537 << 22) | (58 << 14)),
+            version(537, 58, 0, 0)
+        );
+        assert_eq!(
+            parse_driver_version(db::nvidia::VENDOR, (550 << 22) | (54 << 14) | (14 << 6) | 3),
+            version(550, 54, 14, 3)
+        );
+    }
+
+    #[test]
+    fn test_intel_driver_version() {
+        let raw = (101 << 14) | 4887;
+        let expected = if cfg!(windows) {
+            version(101, 4887, 0, 0)
+        } else {
+            // Mesa's ANV driver uses the standard Vulkan encoding.
+            version(
+                vk::api_version_major(raw),
+                vk::api_version_minor(raw),
+                vk::api_version_patch(raw),
+                0,
+            )
+        };
+        assert_eq!(parse_driver_version(db::intel::VENDOR, raw), expected);
+    }
+
+    #[test]
+    fn test_default_driver_version() {
+        assert_eq!(
+            parse_driver_version(db::amd::VENDOR, vk::make_api_version(0, 23, 1, 4)),
+            version(23, 1, 4, 0)
+        );
+    }
+}
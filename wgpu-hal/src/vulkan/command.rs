@@ -45,6 +45,39 @@ impl super::DeviceShared {
     }
 }
 
+/// Pipeline stages a transfer queue supports.
+const TRANSFER_STAGES: vk::PipelineStageFlags = vk::PipelineStageFlags::from_raw(
+    vk::PipelineStageFlags::TRANSFER.as_raw() | vk::PipelineStageFlags::HOST.as_raw(),
+);
+
+/// Accesses a transfer queue supports.
+const TRANSFER_ACCESSES: vk::AccessFlags = vk::AccessFlags::from_raw(
+    vk::AccessFlags::TRANSFER_READ.as_raw()
+        | vk::AccessFlags::TRANSFER_WRITE.as_raw()
+        | vk::AccessFlags::HOST_READ.as_raw()
+        | vk::AccessFlags::HOST_WRITE.as_raw(),
+);
+
+/// Restrict one side of a barrier to what the queue of an encoder supports.
+///
+/// On the transfer queue, anything but copies happened on another queue, and
+/// is ordered by the semaphores the submission waits for instead.
+fn barrier_scope(
+    transfer_only: bool,
+    (stage, access): (vk::PipelineStageFlags, vk::AccessFlags),
+) -> (vk::PipelineStageFlags, vk::AccessFlags) {
+    if !transfer_only {
+        return (stage, access);
+    }
+    let stage = stage & TRANSFER_STAGES;
+    let stage = if stage.is_empty() {
+        vk::PipelineStageFlags::TOP_OF_PIPE
+    } else {
+        stage
+    };
+    (stage, access & TRANSFER_ACCESSES)
+}
+
 impl super::CommandEncoder {
     fn write_pass_end_timestamp_if_requested(&mut self) {
         if let Some((query_set, index)) = self.end_of_pass_timer_query.take() {
@@ -135,13 +168,20 @@ impl crate::CommandEncoder for super::CommandEncoder {
         //Note: this is done so that we never end up with empty stage flags
         let mut src_stages = vk::PipelineStageFlags::TOP_OF_PIPE;
         let mut dst_stages = vk::PipelineStageFlags::BOTTOM_OF_PIPE;
+        let transfer_only = self.transfer_only;
         let vk_barriers = &mut self.temp.buffer_barriers;
         vk_barriers.clear();
 
         for bar in barriers {
-            let (src_stage, src_access) = conv::map_buffer_usage_to_barrier(bar.usage.start);
+            let (src_stage, src_access) = barrier_scope(
+                transfer_only,
+                conv::map_buffer_usage_to_barrier(bar.usage.start),
+            );
             src_stages |= src_stage;
-            let (dst_stage, dst_access) = conv::map_buffer_usage_to_barrier(bar.usage.end);
+            let (dst_stage, dst_access) = barrier_scope(
+                transfer_only,
+                conv::map_buffer_usage_to_barrier(bar.usage.end),
+            );
             dst_stages |= dst_stage;
 
             vk_barriers.push(
@@ -175,6 +215,7 @@ impl crate::CommandEncoder for super::CommandEncoder {
     {
         let mut src_stages = vk::PipelineStageFlags::empty();
         let mut dst_stages = vk::PipelineStageFlags::empty();
+        let transfer_only = self.transfer_only;
         let vk_barriers = &mut self.temp.image_barriers;
         vk_barriers.clear();
 
@@ -184,10 +225,16 @@ impl crate::CommandEncoder for super::CommandEncoder {
                 bar.texture.format,
                 &self.device.private_caps,
             );
-            let (src_stage, src_access) = conv::map_texture_usage_to_barrier(bar.usage.start);
+            let (src_stage, src_access) = barrier_scope(
+                transfer_only,
+                conv::map_texture_usage_to_barrier(bar.usage.start),
+            );
             let src_layout = conv::derive_image_layout(bar.usage.start, bar.texture.format);
             src_stages |= src_stage;
-            let (dst_stage, dst_access) = conv::map_texture_usage_to_barrier(bar.usage.end);
+            let (dst_stage, dst_access) = barrier_scope(
+                transfer_only,
+                conv::map_texture_usage_to_barrier(bar.usage.end),
+            );
             let dst_layout = conv::derive_image_layout(bar.usage.end, bar.texture.format);
             dst_stages |= dst_stage;
 
@@ -218,7 +265,34 @@ impl crate::CommandEncoder for super::CommandEncoder {
         }
     }
 
+    unsafe fn aliasing_barrier(&mut self) {
+        let barrier = vk::MemoryBarrier::builder()
+            .src_access_mask(vk::AccessFlags::MEMORY_WRITE)
+            .dst_access_mask(vk::AccessFlags::MEMORY_READ | vk::AccessFlags::MEMORY_WRITE)
+            .build();
+        unsafe {
+            self.device.raw.cmd_pipeline_barrier(
+                self.active,
+                vk::PipelineStageFlags::ALL_COMMANDS,
+                vk::PipelineStageFlags::ALL_COMMANDS,
+                vk::DependencyFlags::empty(),
+                &[barrier],
+                &[],
+                &[],
+            )
+        };
+    }
+
     unsafe fn clear_buffer(&mut self, buffer: &super::Buffer, range: crate::MemoryRange) {
+        unsafe { self.fill_buffer(buffer, range, 0) };
+    }
+
+    unsafe fn fill_buffer(
+        &mut self,
+        buffer: &super::Buffer,
+        range: crate::MemoryRange,
+        value: u32,
+    ) {
         let range_size = range.end - range.start;
         if self.device.workarounds.contains(
             super::Workarounds::FORCE_FILL_BUFFER_WITH_SIZE_GREATER_4096_ALIGNED_OFFSET_16,
@@ -234,7 +308,7 @@ impl crate::CommandEncoder for super::CommandEncoder {
                     buffer.raw,
                     range.start,
                     prefix_size,
-                    0,
+                    value,
                 )
             };
 
@@ -247,14 +321,18 @@ impl crate::CommandEncoder for super::CommandEncoder {
                     buffer.raw,
                     rounded_start,
                     suffix_size,
-                    0,
+                    value,
                 )
             };
         } else {
             unsafe {
-                self.device
-                    .raw
-                    .cmd_fill_buffer(self.active, buffer.raw, range.start, range_size, 0)
+                self.device.raw.cmd_fill_buffer(
+                    self.active,
+                    buffer.raw,
+                    range.start,
+                    range_size,
+                    value,
+                )
             };
         }
     }
@@ -322,6 +400,47 @@ impl crate::CommandEncoder for super::CommandEncoder {
         };
     }
 
+    unsafe fn blit_texture(
+        &mut self,
+        src: &super::Texture,
+        src_usage: crate::TextureUses,
+        dst: &super::Texture,
+        region: crate::TextureBlit,
+        filter: wgt::FilterMode,
+    ) {
+        let src_layout = conv::derive_image_layout(src_usage, src.format);
+
+        let map_bounds = |base: &crate::TextureCopyBase, size: &crate::CopyExtent| {
+            let (subresource, offset) = conv::map_subresource_layers(base);
+            let end = vk::Offset3D {
+                x: offset.x + size.width as i32,
+                y: offset.y + size.height as i32,
+                z: offset.z + size.depth as i32,
+            };
+            (subresource, [offset, end])
+        };
+        let (src_subresource, src_offsets) = map_bounds(&region.src_base, &region.src_size);
+        let (dst_subresource, dst_offsets) = map_bounds(&region.dst_base, &region.dst_size);
+        let vk_region = vk::ImageBlit {
+            src_subresource,
+            src_offsets,
+            dst_subresource,
+            dst_offsets,
+        };
+
+        unsafe {
+            self.device.raw.cmd_blit_image(
+                self.active,
+                src.raw,
+                src_layout,
+                dst.raw,
+                DST_IMAGE_LAYOUT,
+                &[vk_region],
+                conv::map_filter_mode(filter),
+            )
+        };
+    }
+
     unsafe fn copy_buffer_to_texture<T>(
         &mut self,
         src: &super::Buffer,
@@ -735,7 +854,7 @@ impl crate::CommandEncoder for super::CommandEncoder {
                 height: desc.extent.height,
             },
         };
-        let vk_viewports = [vk::Viewport {
+        let vk_viewport = vk::Viewport {
             x: 0.0,
             y: if self.device.private_caps.flip_y_requires_shift {
                 desc.extent.height as f32
@@ -746,7 +865,16 @@ impl crate::CommandEncoder for super::CommandEncoder {
             height: -(desc.extent.height as f32),
             min_depth: 0.0,
             max_depth: 1.0,
-        }];
+        };
+        // Pipelines of a device with `MULTI_VIEWPORT` use all viewports, which
+        // have to be set before drawing.
+        let viewport_count = if self.device.features.contains(wgt::Features::MULTI_VIEWPORT) {
+            wgt::MAX_VIEWPORTS as usize
+        } else {
+            1
+        };
+        let vk_viewports = [vk_viewport; wgt::MAX_VIEWPORTS as usize];
+        let vk_scissors = [render_area; wgt::MAX_VIEWPORTS as usize];
 
         let raw_pass = self.device.make_render_pass(rp_key).unwrap();
         let raw_framebuffer = self
@@ -792,10 +920,10 @@ impl crate::CommandEncoder for super::CommandEncoder {
         unsafe {
             self.device
                 .raw
-                .cmd_set_viewport(self.active, 0, &vk_viewports);
+                .cmd_set_viewport(self.active, 0, &vk_viewports[..viewport_count]);
             self.device
                 .raw
-                .cmd_set_scissor(self.active, 0, &[render_area]);
+                .cmd_set_scissor(self.active, 0, &vk_scissors[..viewport_count]);
             self.device.raw.cmd_begin_render_pass(
                 self.active,
                 &vk_info,
@@ -840,6 +968,46 @@ impl crate::CommandEncoder for super::CommandEncoder {
             )
         };
     }
+    unsafe fn set_bind_group_inline(
+        &mut self,
+        layout: &super::PipelineLayout,
+        index: u32,
+        group_layout: &super::BindGroupLayout,
+        buffers: &[crate::BufferBinding<super::Api>],
+    ) {
+        let ext = self.device.extension_fns.push_descriptor.as_ref();
+        let ext = ext.expect("Feature `INLINE_BIND_GROUPS` not enabled");
+
+        let buffer_infos = buffers
+            .iter()
+            .map(|binding| {
+                vk::DescriptorBufferInfo::builder()
+                    .buffer(binding.buffer.raw)
+                    .offset(binding.offset)
+                    .range(binding.size.map_or(vk::WHOLE_SIZE, wgt::BufferSize::get))
+                    .build()
+            })
+            .collect::<ArrayVec<_, { wgt::MAX_INLINE_BINDINGS as usize }>>();
+        // Entries of the layout are the bindings with a non-zero count, in binding order.
+        let writes = group_layout
+            .types
+            .iter()
+            .enumerate()
+            .filter(|&(_, &(_, count))| count != 0)
+            .zip(buffer_infos.iter())
+            .map(|((binding, &(ty, _)), info)| {
+                vk::WriteDescriptorSet::builder()
+                    .dst_binding(binding as u32)
+                    .descriptor_type(ty)
+                    .buffer_info(slice::from_ref(info))
+                    .build()
+            })
+            .collect::<ArrayVec<_, { wgt::MAX_INLINE_BINDINGS as usize }>>();
+
+        unsafe {
+            ext.cmd_push_descriptor_set(self.active, self.bind_point, layout.raw, index, &writes)
+        };
+    }
     unsafe fn set_push_constants(
         &mut self,
         layout: &super::PipelineLayout,
@@ -886,6 +1054,18 @@ impl crate::CommandEncoder for super::CommandEncoder {
                 pipeline.raw,
             )
         };
+        if let Some(state) = pipeline.dynamic_raster_state {
+            let ext = self.device.extension_fns.extended_dynamic_state.as_ref();
+            let ext = ext.expect("Feature `EXTENDED_DYNAMIC_STATE` not enabled");
+            unsafe {
+                ext.cmd_set_front_face(self.active, state.front_face);
+                ext.cmd_set_cull_mode(self.active, state.cull_mode);
+                ext.cmd_set_depth_compare_op(self.active, state.depth_compare);
+            }
+        }
+        if let Some([front, back]) = pipeline.stencil_write_masks {
+            unsafe { self.set_stencil_write_mask(front, back) };
+        }
     }
 
     unsafe fn set_index_buffer<'a>(
@@ -916,6 +1096,14 @@ impl crate::CommandEncoder for super::CommandEncoder {
         };
     }
     unsafe fn set_viewport(&mut self, rect: &crate::Rect<f32>, depth_range: Range<f32>) {
+        unsafe { self.set_indexed_viewport(0, rect, depth_range) };
+    }
+    unsafe fn set_indexed_viewport(
+        &mut self,
+        index: u32,
+        rect: &crate::Rect<f32>,
+        depth_range: Range<f32>,
+    ) {
         let vk_viewports = [vk::Viewport {
             x: rect.x,
             y: if self.device.private_caps.flip_y_requires_shift {
@@ -931,10 +1119,13 @@ impl crate::CommandEncoder for super::CommandEncoder {
         unsafe {
             self.device
                 .raw
-                .cmd_set_viewport(self.active, 0, &vk_viewports)
+                .cmd_set_viewport(self.active, index, &vk_viewports)
         };
     }
     unsafe fn set_scissor_rect(&mut self, rect: &crate::Rect<u32>) {
+        unsafe { self.set_indexed_scissor_rect(0, rect) };
+    }
+    unsafe fn set_indexed_scissor_rect(&mut self, index: u32, rect: &crate::Rect<u32>) {
         let vk_scissors = [vk::Rect2D {
             offset: vk::Offset2D {
                 x: rect.x as i32,
@@ -948,7 +1139,7 @@ impl crate::CommandEncoder for super::CommandEncoder {
         unsafe {
             self.device
                 .raw
-                .cmd_set_scissor(self.active, 0, &vk_scissors)
+                .cmd_set_scissor(self.active, index, &vk_scissors)
         };
     }
     unsafe fn set_stencil_reference(&mut self, value: u32) {
@@ -960,9 +1151,60 @@ impl crate::CommandEncoder for super::CommandEncoder {
             )
         };
     }
+    unsafe fn set_stencil_reference_separate(&mut self, front: u32, back: u32) {
+        unsafe {
+            self.device.raw.cmd_set_stencil_reference(
+                self.active,
+                vk::StencilFaceFlags::FRONT,
+                front,
+            );
+            self.device.raw.cmd_set_stencil_reference(
+                self.active,
+                vk::StencilFaceFlags::BACK,
+                back,
+            );
+        }
+    }
+    unsafe fn set_stencil_write_mask(&mut self, front: u32, back: u32) {
+        unsafe {
+            self.device.raw.cmd_set_stencil_write_mask(
+                self.active,
+                vk::StencilFaceFlags::FRONT,
+                front,
+            );
+            self.device.raw.cmd_set_stencil_write_mask(
+                self.active,
+                vk::StencilFaceFlags::BACK,
+                back,
+            );
+        }
+    }
     unsafe fn set_blend_constants(&mut self, color: &[f32; 4]) {
         unsafe { self.device.raw.cmd_set_blend_constants(self.active, color) };
     }
+    unsafe fn set_front_face(&mut self, front_face: wgt::FrontFace) {
+        match self.device.extension_fns.extended_dynamic_state {
+            Some(ref t) => unsafe {
+                t.cmd_set_front_face(self.active, conv::map_front_face(front_face))
+            },
+            None => panic!("Feature `EXTENDED_DYNAMIC_STATE` not enabled"),
+        }
+    }
+    unsafe fn set_cull_mode(&mut self, cull_mode: Option<wgt::Face>) {
+        let raw = cull_mode.map_or(vk::CullModeFlags::NONE, conv::map_cull_face);
+        match self.device.extension_fns.extended_dynamic_state {
+            Some(ref t) => unsafe { t.cmd_set_cull_mode(self.active, raw) },
+            None => panic!("Feature `EXTENDED_DYNAMIC_STATE` not enabled"),
+        }
+    }
+    unsafe fn set_depth_compare(&mut self, compare: wgt::CompareFunction) {
+        match self.device.extension_fns.extended_dynamic_state {
+            Some(ref t) => unsafe {
+                t.cmd_set_depth_compare_op(self.active, conv::map_comparison(compare))
+            },
+            None => panic!("Feature `EXTENDED_DYNAMIC_STATE` not enabled"),
+        }
+    }
 
     unsafe fn draw(
         &mut self,
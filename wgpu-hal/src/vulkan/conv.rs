@@ -75,6 +75,7 @@ impl super::PrivateCapabilities {
             }
             Tf::Depth16Unorm => F::D16_UNORM,
             Tf::NV12 => F::G8_B8R8_2PLANE_420_UNORM,
+            Tf::P010 => F::G10X6_B10X6R10X6_2PLANE_420_UNORM_3PACK16,
             Tf::Rgb9e5Ufloat => F::E5B9G9R9_UFLOAT_PACK32,
             Tf::Bc1RgbaUnorm => F::BC1_RGBA_UNORM_BLOCK,
             Tf::Bc1RgbaUnormSrgb => F::BC1_RGBA_SRGB_BLOCK,
@@ -178,6 +179,60 @@ pub fn map_vk_surface_formats(sf: vk::SurfaceFormatKHR) -> Option<wgt::TextureFo
     })
 }
 
+/// Returns the swapchain color space for surface textures of `format` in `color_space`.
+///
+/// Floating point formats use the linear variant of the color space,
+/// others the one with the sRGB transfer function. HDR10 is only available
+/// with 10 bit formats.
+pub fn map_color_space(
+    format: wgt::TextureFormat,
+    color_space: wgt::ColorSpace,
+) -> Option<vk::ColorSpaceKHR> {
+    use wgt::ColorSpace as Cs;
+    let linear = format == wgt::TextureFormat::Rgba16Float;
+    Some(match (color_space, linear) {
+        (Cs::Srgb, true) => vk::ColorSpaceKHR::EXTENDED_SRGB_LINEAR_EXT,
+        (Cs::Srgb, false) => vk::ColorSpaceKHR::SRGB_NONLINEAR,
+        (Cs::DisplayP3, true) => vk::ColorSpaceKHR::DISPLAY_P3_LINEAR_EXT,
+        (Cs::DisplayP3, false) => vk::ColorSpaceKHR::DISPLAY_P3_NONLINEAR_EXT,
+        (Cs::Bt2020, true) => vk::ColorSpaceKHR::BT2020_LINEAR_EXT,
+        (Cs::Bt2020, false) => return None,
+        (Cs::Hdr10, _) if format == wgt::TextureFormat::Rgb10a2Unorm => {
+            vk::ColorSpaceKHR::HDR10_ST2084_EXT
+        }
+        (Cs::Hdr10, _) => return None,
+    })
+}
+
+/// Maps a surface format in a wide gamut color space, as provided by
+/// `VK_EXT_swapchain_colorspace`.
+pub fn map_vk_wide_gamut_surface_format(
+    sf: vk::SurfaceFormatKHR,
+) -> Option<(wgt::TextureFormat, wgt::ColorSpace)> {
+    use wgt::ColorSpace as Cs;
+    let (color_space, srgb_color_space) = match sf.color_space {
+        vk::ColorSpaceKHR::DISPLAY_P3_NONLINEAR_EXT => {
+            (Cs::DisplayP3, vk::ColorSpaceKHR::SRGB_NONLINEAR)
+        }
+        vk::ColorSpaceKHR::DISPLAY_P3_LINEAR_EXT => {
+            (Cs::DisplayP3, vk::ColorSpaceKHR::EXTENDED_SRGB_LINEAR_EXT)
+        }
+        vk::ColorSpaceKHR::BT2020_LINEAR_EXT => {
+            (Cs::Bt2020, vk::ColorSpaceKHR::EXTENDED_SRGB_LINEAR_EXT)
+        }
+        vk::ColorSpaceKHR::HDR10_ST2084_EXT => {
+            (Cs::Hdr10, vk::ColorSpaceKHR::EXTENDED_SRGB_LINEAR_EXT)
+        }
+        _ => return None,
+    };
+    let format = map_vk_surface_formats(vk::SurfaceFormatKHR {
+        format: sf.format,
+        color_space: srgb_color_space,
+    })?;
+    // Only report what `map_color_space` would create the swapchain with.
+    (map_color_space(format, color_space) == Some(sf.color_space)).then_some((format, color_space))
+}
+
 impl crate::Attachment<'_, super::Api> {
     pub(super) fn make_attachment_key(
         &self,
@@ -496,6 +551,45 @@ pub fn map_vk_composite_alpha(flags: vk::CompositeAlphaFlagsKHR) -> Vec<wgt::Com
     modes
 }
 
+pub fn map_surface_transform(transform: wgt::SurfaceTransform) -> vk::SurfaceTransformFlagsKHR {
+    match transform {
+        wgt::SurfaceTransform::Identity => vk::SurfaceTransformFlagsKHR::IDENTITY,
+        wgt::SurfaceTransform::Rotate90 => vk::SurfaceTransformFlagsKHR::ROTATE_90,
+        wgt::SurfaceTransform::Rotate180 => vk::SurfaceTransformFlagsKHR::ROTATE_180,
+        wgt::SurfaceTransform::Rotate270 => vk::SurfaceTransformFlagsKHR::ROTATE_270,
+    }
+}
+
+/// Mirrored transforms aren't exposed, and map to `Identity`.
+pub fn map_vk_surface_transform(flags: vk::SurfaceTransformFlagsKHR) -> wgt::SurfaceTransform {
+    if flags.contains(vk::SurfaceTransformFlagsKHR::ROTATE_90) {
+        wgt::SurfaceTransform::Rotate90
+    } else if flags.contains(vk::SurfaceTransformFlagsKHR::ROTATE_180) {
+        wgt::SurfaceTransform::Rotate180
+    } else if flags.contains(vk::SurfaceTransformFlagsKHR::ROTATE_270) {
+        wgt::SurfaceTransform::Rotate270
+    } else {
+        wgt::SurfaceTransform::Identity
+    }
+}
+
+/// `Identity` is always listed, see `Device::create_swapchain`.
+pub fn map_vk_surface_transforms(
+    flags: vk::SurfaceTransformFlagsKHR,
+) -> Vec<wgt::SurfaceTransform> {
+    let mut transforms = vec![wgt::SurfaceTransform::Identity];
+    if flags.contains(vk::SurfaceTransformFlagsKHR::ROTATE_90) {
+        transforms.push(wgt::SurfaceTransform::Rotate90);
+    }
+    if flags.contains(vk::SurfaceTransformFlagsKHR::ROTATE_180) {
+        transforms.push(wgt::SurfaceTransform::Rotate180);
+    }
+    if flags.contains(vk::SurfaceTransformFlagsKHR::ROTATE_270) {
+        transforms.push(wgt::SurfaceTransform::Rotate270);
+    }
+    transforms
+}
+
 pub fn map_buffer_usage(usage: crate::BufferUses) -> vk::BufferUsageFlags {
     let mut flags = vk::BufferUsageFlags::empty();
     if usage.contains(crate::BufferUses::COPY_SRC) {
@@ -605,6 +699,26 @@ pub fn map_view_dimension(dim: wgt::TextureViewDimension) -> vk::ImageViewType {
     }
 }
 
+pub fn map_swizzle(swizzle: wgt::TextureComponentSwizzle) -> vk::ComponentMapping {
+    fn map_component(component: wgt::ComponentSwizzle) -> vk::ComponentSwizzle {
+        match component {
+            wgt::ComponentSwizzle::Zero => vk::ComponentSwizzle::ZERO,
+            wgt::ComponentSwizzle::One => vk::ComponentSwizzle::ONE,
+            wgt::ComponentSwizzle::R => vk::ComponentSwizzle::R,
+            wgt::ComponentSwizzle::G => vk::ComponentSwizzle::G,
+            wgt::ComponentSwizzle::B => vk::ComponentSwizzle::B,
+            wgt::ComponentSwizzle::A => vk::ComponentSwizzle::A,
+        }
+    }
+
+    vk::ComponentMapping {
+        r: map_component(swizzle.r),
+        g: map_component(swizzle.g),
+        b: map_component(swizzle.b),
+        a: map_component(swizzle.a),
+    }
+}
+
 pub fn map_copy_extent(extent: &crate::CopyExtent) -> vk::Extent3D {
     vk::Extent3D {
         width: extent.width,
@@ -666,6 +780,28 @@ pub fn map_filter_mode(mode: wgt::FilterMode) -> vk::Filter {
     }
 }
 
+pub fn map_ycbcr_model(model: wgt::YcbcrModel) -> vk::SamplerYcbcrModelConversion {
+    match model {
+        wgt::YcbcrModel::Bt601 => vk::SamplerYcbcrModelConversion::YCBCR_601,
+        wgt::YcbcrModel::Bt709 => vk::SamplerYcbcrModelConversion::YCBCR_709,
+        wgt::YcbcrModel::Bt2020 => vk::SamplerYcbcrModelConversion::YCBCR_2020,
+    }
+}
+
+pub fn map_ycbcr_range(range: wgt::YcbcrRange) -> vk::SamplerYcbcrRange {
+    match range {
+        wgt::YcbcrRange::Full => vk::SamplerYcbcrRange::ITU_FULL,
+        wgt::YcbcrRange::Narrow => vk::SamplerYcbcrRange::ITU_NARROW,
+    }
+}
+
+pub fn map_chroma_location(location: wgt::ChromaLocation) -> vk::ChromaLocation {
+    match location {
+        wgt::ChromaLocation::CositedEven => vk::ChromaLocation::COSITED_EVEN,
+        wgt::ChromaLocation::Midpoint => vk::ChromaLocation::MIDPOINT,
+    }
+}
+
 pub fn map_mip_filter_mode(mode: wgt::FilterMode) -> vk::SamplerMipmapMode {
     match mode {
         wgt::FilterMode::Nearest => vk::SamplerMipmapMode::NEAREST,
@@ -683,6 +819,14 @@ pub fn map_address_mode(mode: wgt::AddressMode) -> vk::SamplerAddressMode {
     }
 }
 
+pub fn map_reduction_mode(mode: wgt::SamplerReductionMode) -> vk::SamplerReductionMode {
+    match mode {
+        wgt::SamplerReductionMode::WeightedAverage => vk::SamplerReductionMode::WEIGHTED_AVERAGE,
+        wgt::SamplerReductionMode::Min => vk::SamplerReductionMode::MIN,
+        wgt::SamplerReductionMode::Max => vk::SamplerReductionMode::MAX,
+    }
+}
+
 pub fn map_border_color(border_color: wgt::SamplerBorderColor) -> vk::BorderColor {
     match border_color {
         wgt::SamplerBorderColor::TransparentBlack | wgt::SamplerBorderColor::Zero => {
@@ -690,6 +834,7 @@ pub fn map_border_color(border_color: wgt::SamplerBorderColor) -> vk::BorderColo
         }
         wgt::SamplerBorderColor::OpaqueBlack => vk::BorderColor::FLOAT_OPAQUE_BLACK,
         wgt::SamplerBorderColor::OpaqueWhite => vk::BorderColor::FLOAT_OPAQUE_WHITE,
+        wgt::SamplerBorderColor::Custom(_) => vk::BorderColor::FLOAT_CUSTOM_EXT,
     }
 }
 
@@ -741,6 +886,7 @@ pub fn map_binding_type(ty: wgt::BindingType) -> vk::DescriptorType {
         wgt::BindingType::Texture { .. } => vk::DescriptorType::SAMPLED_IMAGE,
         wgt::BindingType::StorageTexture { .. } => vk::DescriptorType::STORAGE_IMAGE,
         wgt::BindingType::AccelerationStructure => vk::DescriptorType::ACCELERATION_STRUCTURE_KHR,
+        wgt::BindingType::YcbcrTexture { .. } => vk::DescriptorType::COMBINED_IMAGE_SAMPLER,
     }
 }
 
@@ -964,3 +1110,51 @@ pub fn map_acceleration_structure_usage_to_barrier(
 
     (stages, access)
 }
+
+pub fn map_queue_global_priority(
+    priority: wgt::QueuePriority,
+) -> Option<vk::QueueGlobalPriorityEXT> {
+    match priority {
+        wgt::QueuePriority::Normal => None,
+        wgt::QueuePriority::High => Some(vk::QueueGlobalPriorityEXT::HIGH),
+        wgt::QueuePriority::Realtime => Some(vk::QueueGlobalPriorityEXT::REALTIME),
+    }
+}
+
+pub fn map_external_memory_handle_type(
+    handle_type: wgt::ExternalMemoryHandleType,
+) -> vk::ExternalMemoryHandleTypeFlags {
+    match handle_type {
+        wgt::ExternalMemoryHandleType::OpaqueFd => vk::ExternalMemoryHandleTypeFlags::OPAQUE_FD,
+        wgt::ExternalMemoryHandleType::DmaBuf => vk::ExternalMemoryHandleTypeFlags::DMA_BUF_EXT,
+        wgt::ExternalMemoryHandleType::D3D12Resource => {
+            vk::ExternalMemoryHandleTypeFlags::D3D12_RESOURCE
+        }
+    }
+}
+
+/// Returns the format features a texture needs for `usage`.
+pub fn map_texture_usage_to_format_features(usage: crate::TextureUses) -> vk::FormatFeatureFlags {
+    let mut features = vk::FormatFeatureFlags::empty();
+    if usage.contains(crate::TextureUses::COPY_SRC) {
+        features |= vk::FormatFeatureFlags::TRANSFER_SRC;
+    }
+    if usage.contains(crate::TextureUses::COPY_DST) {
+        features |= vk::FormatFeatureFlags::TRANSFER_DST;
+    }
+    if usage.contains(crate::TextureUses::RESOURCE) {
+        features |= vk::FormatFeatureFlags::SAMPLED_IMAGE;
+    }
+    if usage.contains(crate::TextureUses::COLOR_TARGET) {
+        features |= vk::FormatFeatureFlags::COLOR_ATTACHMENT;
+    }
+    if usage.intersects(
+        crate::TextureUses::DEPTH_STENCIL_READ | crate::TextureUses::DEPTH_STENCIL_WRITE,
+    ) {
+        features |= vk::FormatFeatureFlags::DEPTH_STENCIL_ATTACHMENT;
+    }
+    if usage.intersects(crate::TextureUses::STORAGE_READ | crate::TextureUses::STORAGE_READ_WRITE) {
+        features |= vk::FormatFeatureFlags::STORAGE_IMAGE;
+    }
+    features
+}
@@ -0,0 +1,116 @@
+//! Descriptor set allocation from per-thread arenas.
+//!
+//! Bind groups are often created from several threads at once. Rather than
+//! sharing a single [`gpu_descriptor::DescriptorAllocator`] behind a device-wide
+//! lock, every thread allocates from an arena of its own, so creating bind groups
+//! on different threads doesn't contend.
+//!
+//! Destroying a bind group doesn't touch the allocator either: its set is queued
+//! on the arena it came from, and all queued sets are returned to their pools at
+//! once by [`DescriptorArenas::recycle`], which wgpu-core calls when submissions
+//! complete.
+
+use std::{
+    cell::Cell,
+    sync::atomic::{AtomicUsize, Ordering},
+    thread,
+};
+
+use ash::vk;
+use parking_lot::Mutex;
+
+type Allocator = gpu_descriptor::DescriptorAllocator<vk::DescriptorPool, vk::DescriptorSet>;
+pub(super) type DescriptorSet = gpu_descriptor::DescriptorSet<vk::DescriptorSet>;
+
+/// Upper bound on the number of arenas, threads beyond that share arenas.
+const MAX_ARENAS: usize = 16;
+
+/// Slot of the next thread allocating descriptor sets.
+static NEXT_THREAD_SLOT: AtomicUsize = AtomicUsize::new(0);
+
+thread_local! {
+    static THREAD_SLOT: Cell<Option<usize>> = const { Cell::new(None) };
+}
+
+/// Returns a number unique to the current thread, assigned on first use.
+fn thread_slot() -> usize {
+    THREAD_SLOT.with(|slot| {
+        slot.get().unwrap_or_else(|| {
+            let new_slot = NEXT_THREAD_SLOT.fetch_add(1, Ordering::Relaxed);
+            slot.set(Some(new_slot));
+            new_slot
+        })
+    })
+}
+
+struct Arena {
+    /// Only locked by other threads if there are more threads than arenas, or
+    /// while recycling.
+    allocator: Mutex<Allocator>,
+    /// Sets of destroyed bind groups, waiting for [`DescriptorArenas::recycle`].
+    freed: Mutex<Vec<DescriptorSet>>,
+}
+
+pub(super) struct DescriptorArenas {
+    arenas: Box<[Arena]>,
+}
+
+impl DescriptorArenas {
+    pub fn new(max_update_after_bind_descriptors_in_all_pools: u32) -> Self {
+        let count = thread::available_parallelism()
+            .map_or(1, |count| count.get())
+            .min(MAX_ARENAS);
+        // The limit holds for all pools together, so split it between the arenas.
+        let max_update_after_bind = max_update_after_bind_descriptors_in_all_pools / count as u32;
+        let arenas = (0..count)
+            .map(|_| Arena {
+                allocator: Mutex::new(Allocator::new(max_update_after_bind)),
+                freed: Mutex::new(Vec::new()),
+            })
+            .collect();
+        Self { arenas }
+    }
+
+    /// Allocates a set from the arena of the current thread.
+    ///
+    /// Returns the index of the arena, which has to be passed to [`Self::free`].
+    pub unsafe fn allocate(
+        &self,
+        device: &super::DeviceShared,
+        layout: &super::BindGroupLayout,
+    ) -> Result<(usize, DescriptorSet), crate::DeviceError> {
+        let index = thread_slot() % self.arenas.len();
+        let mut sets = unsafe {
+            self.arenas[index].allocator.lock().allocate(
+                device,
+                &layout.raw,
+                gpu_descriptor::DescriptorSetLayoutCreateFlags::empty(),
+                &layout.desc_count,
+                1,
+            )?
+        };
+        Ok((index, sets.pop().unwrap()))
+    }
+
+    /// Queues `set` to be returned to the arena at `index` by the next [`Self::recycle`].
+    pub fn free(&self, index: usize, set: DescriptorSet) {
+        self.arenas[index].freed.lock().push(set);
+    }
+
+    /// Returns the sets queued by [`Self::free`] to their pools.
+    pub unsafe fn recycle(&self, device: &super::DeviceShared) {
+        for arena in self.arenas.iter() {
+            let freed = std::mem::take(&mut *arena.freed.lock());
+            if !freed.is_empty() {
+                unsafe { arena.allocator.lock().free(device, freed) };
+            }
+        }
+    }
+
+    pub unsafe fn cleanup(self, device: &super::DeviceShared) {
+        unsafe { self.recycle(device) };
+        for arena in self.arenas.into_vec() {
+            unsafe { arena.allocator.into_inner().cleanup(device) };
+        }
+    }
+}
@@ -399,6 +399,10 @@ impl
         //Note: ignoring other types, since they can't appear here
         let unfiltered_counts = [
             (vk::DescriptorType::SAMPLER, descriptor_count.sampler),
+            (
+                vk::DescriptorType::COMBINED_IMAGE_SAMPLER,
+                descriptor_count.combined_image_sampler,
+            ),
             (
                 vk::DescriptorType::SAMPLED_IMAGE,
                 descriptor_count.sampled_image,
@@ -531,6 +535,16 @@ impl
     }
 }
 
+/// How the memory of an image created by `create_raw_image` is shared.
+struct ExternalImage<'a> {
+    handle_type: wgt::ExternalMemoryHandleType,
+    /// For dma-bufs, the DRM format modifiers the driver picks the layout of
+    /// the image from, or the one it is laid out with if `planes` isn't empty.
+    drm_format_modifiers: &'a [u64],
+    /// For imported dma-bufs, the layout of the memory planes of the image.
+    planes: &'a [vk::SubresourceLayout],
+}
+
 struct CompiledStage {
     create_info: vk::PipelineShaderStageCreateInfo,
     _entry_point: CString,
@@ -552,13 +566,10 @@ impl super::Device {
             None => vk::SwapchainKHR::null(),
         };
 
-        let color_space = if config.format == wgt::TextureFormat::Rgba16Float {
-            // Enable wide color gamut mode
-            // Vulkan swapchain for Android only supports DISPLAY_P3_NONLINEAR_EXT and EXTENDED_SRGB_LINEAR_EXT
-            vk::ColorSpaceKHR::EXTENDED_SRGB_LINEAR_EXT
-        } else {
-            vk::ColorSpaceKHR::SRGB_NONLINEAR
-        };
+        // Rgba16Float enables wide color gamut mode, even for sRGB.
+        // Vulkan swapchain for Android only supports DISPLAY_P3_NONLINEAR_EXT and EXTENDED_SRGB_LINEAR_EXT
+        let color_space = conv::map_color_space(config.format, config.color_space)
+            .ok_or(crate::SurfaceError::Other("Unsupported color space"))?;
 
         let original_format = self.shared.private_caps.map_texture_format(config.format);
         let mut raw_flags = vk::SwapchainCreateFlagsKHR::empty();
@@ -577,10 +588,33 @@ impl super::Device {
             wgt_view_formats.push(config.format);
         }
 
+        // `Identity` is always accepted, so that the default configuration
+        // works everywhere. Surfaces that don't support it, like those of some
+        // rotated displays, are given their current transform instead, which
+        // they always support.
+        let pre_transform = match config.pre_transform {
+            wgt::SurfaceTransform::Identity => unsafe {
+                surface.functor.get_physical_device_surface_capabilities(
+                    self.shared.physical_device,
+                    surface.raw,
+                )
+            }
+            .ok()
+            .filter(|caps| {
+                !caps
+                    .supported_transforms
+                    .contains(vk::SurfaceTransformFlagsKHR::IDENTITY)
+            })
+            .map_or(vk::SurfaceTransformFlagsKHR::IDENTITY, |caps| {
+                caps.current_transform
+            }),
+            other => conv::map_surface_transform(other),
+        };
+
         let mut info = vk::SwapchainCreateInfoKHR::builder()
             .flags(raw_flags)
             .surface(surface.raw)
-            .min_image_count(config.maximum_frame_latency + 1) // TODO: https://github.com/gfx-rs/wgpu/issues/2869
+            .min_image_count(config.image_count)
             .image_format(original_format)
             .image_color_space(color_space)
             .image_extent(vk::Extent2D {
@@ -590,7 +624,7 @@ impl super::Device {
             .image_array_layers(config.extent.depth_or_array_layers)
             .image_usage(conv::map_texture_usage(config.usage))
             .image_sharing_mode(vk::SharingMode::EXCLUSIVE)
-            .pre_transform(vk::SurfaceTransformFlagsKHR::IDENTITY)
+            .pre_transform(pre_transform)
             .composite_alpha(conv::map_composite_alpha_mode(config.composite_alpha_mode))
             .present_mode(conv::map_present_mode(config.present_mode))
             .clipped(true)
@@ -602,6 +636,32 @@ impl super::Device {
             info = info.push_next(&mut format_list_info);
         }
 
+        // Without the extension, the driver decides on its own, like with
+        // `FullscreenMode::Windowed`.
+        let full_screen_exclusive = match config.fullscreen {
+            wgt::FullscreenMode::Windowed => vk::FullScreenExclusiveEXT::DEFAULT,
+            wgt::FullscreenMode::Borderless => vk::FullScreenExclusiveEXT::DISALLOWED,
+            wgt::FullscreenMode::Exclusive => vk::FullScreenExclusiveEXT::APPLICATION_CONTROLLED,
+        };
+        let mut full_screen_exclusive_info = vk::SurfaceFullScreenExclusiveInfoEXT::builder()
+            .full_screen_exclusive(full_screen_exclusive);
+        let mut full_screen_exclusive_win32_info =
+            vk::SurfaceFullScreenExclusiveWin32InfoEXT::builder();
+        if self.shared.extension_fns.full_screen_exclusive.is_some() {
+            info = info.push_next(&mut full_screen_exclusive_info);
+            #[cfg(windows)]
+            if let (wgt::FullscreenMode::Exclusive, Some(hwnd)) = (config.fullscreen, surface.hwnd)
+            {
+                use winapi::um::winuser;
+                let monitor = unsafe {
+                    winuser::MonitorFromWindow(hwnd.get() as _, winuser::MONITOR_DEFAULTTONEAREST)
+                };
+                full_screen_exclusive_win32_info =
+                    full_screen_exclusive_win32_info.hmonitor(monitor.cast());
+                info = info.push_next(&mut full_screen_exclusive_win32_info);
+            }
+        }
+
         let result = {
             profiling::scope!("vkCreateSwapchainKHR");
             unsafe { functor.create_swapchain(&info, None) }
@@ -625,6 +685,26 @@ impl super::Device {
             }
         };
 
+        if let (wgt::FullscreenMode::Exclusive, Some(fse)) = (
+            config.fullscreen,
+            self.shared.extension_fns.full_screen_exclusive.as_ref(),
+        ) {
+            // Fails while the window isn't in the foreground. Destroying the
+            // swapchain releases the mode.
+            profiling::scope!("vkAcquireFullScreenExclusiveModeEXT");
+            if let Err(error) = unsafe { fse.acquire_full_screen_exclusive_mode(raw) } {
+                log::error!("acquire_full_screen_exclusive_mode: {}", error);
+                unsafe { functor.destroy_swapchain(raw, None) };
+                return Err(match error {
+                    vk::Result::ERROR_SURFACE_LOST_KHR => crate::SurfaceError::Lost,
+                    vk::Result::ERROR_INITIALIZATION_FAILED => {
+                        crate::SurfaceError::Other("exclusive fullscreen")
+                    }
+                    other => crate::DeviceError::from(other).into(),
+                });
+            }
+        }
+
         let images =
             unsafe { functor.get_swapchain_images(raw) }.map_err(crate::DeviceError::from)?;
 
@@ -649,6 +729,9 @@ impl super::Device {
             view_formats: wgt_view_formats,
             surface_semaphores,
             next_semaphore_index: 0,
+            present_count: 0,
+            next_present_time: None,
+            next_damage: Vec::new(),
         })
     }
 
@@ -689,17 +772,862 @@ impl super::Device {
             raw_flags: vk::ImageCreateFlags::empty(),
             copy_size: desc.copy_extent(),
             view_formats,
+            sparse: None,
+            lazy_memory: None,
+            external_memory: None,
+        }
+    }
+
+    /// # Safety
+    ///
+    /// - `vk_buffer`'s memory must be managed by the caller
+    /// - Externally imported buffers can't be mapped by `wgpu`
+    pub unsafe fn buffer_from_raw(vk_buffer: vk::Buffer) -> super::Buffer {
+        super::Buffer {
+            raw: vk_buffer,
+            block: None,
+            sparse: None,
+            external_memory: None,
+        }
+    }
+
+    /// Returns the DRM format modifiers textures of `format` can be laid out
+    /// with in dma-bufs, if they are used as `usage`.
+    ///
+    /// Returns nothing if [`wgt::Features::EXTERNAL_MEMORY_DMA_BUF`] isn't enabled.
+    pub fn drm_format_modifiers(
+        &self,
+        format: wgt::TextureFormat,
+        usage: crate::TextureUses,
+    ) -> Vec<wgt::DrmFormatModifier> {
+        if self
+            .shared
+            .extension_fns
+            .image_drm_format_modifier
+            .is_none()
+        {
+            return Vec::new();
+        }
+        let instance = &self.shared.instance.raw;
+        let vk_format = self.shared.private_caps.map_texture_format(format);
+
+        let mut count_list = vk::DrmFormatModifierPropertiesListEXT::default();
+        unsafe {
+            instance.get_physical_device_format_properties2(
+                self.shared.physical_device,
+                vk_format,
+                &mut vk::FormatProperties2::builder().push_next(&mut count_list),
+            )
+        };
+        let mut modifiers = vec![
+            vk::DrmFormatModifierPropertiesEXT::default();
+            count_list.drm_format_modifier_count as usize
+        ];
+        let count = {
+            let mut list = vk::DrmFormatModifierPropertiesListEXT::builder()
+                .drm_format_modifier_properties(&mut modifiers);
+            unsafe {
+                instance.get_physical_device_format_properties2(
+                    self.shared.physical_device,
+                    vk_format,
+                    &mut vk::FormatProperties2::builder().push_next(&mut list),
+                )
+            };
+            list.drm_format_modifier_count as usize
+        };
+        modifiers.truncate(count);
+
+        let features = conv::map_texture_usage_to_format_features(usage);
+        modifiers
+            .into_iter()
+            .filter(|modifier| {
+                modifier
+                    .drm_format_modifier_tiling_features
+                    .contains(features)
+            })
+            .map(|modifier| wgt::DrmFormatModifier {
+                modifier: modifier.drm_format_modifier,
+                plane_count: modifier.drm_format_modifier_plane_count,
+            })
+            .collect()
+    }
+
+    /// Creates a buffer whose memory can be exported through `handle_type`
+    /// with [`Self::export_buffer_memory`].
+    ///
+    /// The buffer can't be mapped.
+    ///
+    /// # Safety
+    ///
+    /// - `desc` must be valid, like for [`crate::Device::create_buffer`].
+    /// - The feature of `handle_type` must be enabled.
+    pub unsafe fn create_exportable_buffer(
+        &self,
+        desc: &crate::BufferDescriptor,
+        handle_type: wgt::ExternalMemoryHandleType,
+    ) -> Result<super::Buffer, crate::DeviceError> {
+        let raw = unsafe { self.create_raw_buffer(desc, Some(handle_type))? };
+        let req = unsafe { self.shared.raw.get_buffer_memory_requirements(raw) };
+        let mut dedicated_info = vk::MemoryDedicatedAllocateInfo::builder().buffer(raw);
+        let memory = unsafe {
+            self.allocate_external_memory(&req, req.size, handle_type, None, &mut dedicated_info)
+        };
+        match memory {
+            Ok(memory) => unsafe { self.bind_external_buffer(raw, desc, memory) },
+            Err(err) => {
+                unsafe { self.shared.raw.destroy_buffer(raw, None) };
+                Err(err)
+            }
+        }
+    }
+
+    /// Creates a texture whose memory can be exported through `handle_type`
+    /// with [`Self::export_texture_memory`].
+    ///
+    /// For dma-bufs, the driver lays out the texture with one of
+    /// `drm_format_modifiers`, or of the modifiers [`Self::drm_format_modifiers`]
+    /// returns for its format and usage if there are none.
+    ///
+    /// # Safety
+    ///
+    /// - `desc` must be valid, like for [`crate::Device::create_texture`].
+    /// - The feature of `handle_type` must be enabled.
+    pub unsafe fn create_exportable_texture(
+        &self,
+        desc: &crate::TextureDescriptor,
+        handle_type: wgt::ExternalMemoryHandleType,
+        drm_format_modifiers: &[u64],
+    ) -> Result<super::Texture, crate::DeviceError> {
+        let supported_modifiers;
+        let drm_format_modifiers = if drm_format_modifiers.is_empty()
+            && handle_type == wgt::ExternalMemoryHandleType::DmaBuf
+        {
+            supported_modifiers = self
+                .drm_format_modifiers(desc.format, desc.usage)
+                .into_iter()
+                .map(|modifier| modifier.modifier)
+                .collect::<Vec<_>>();
+            if supported_modifiers.is_empty() {
+                return Err(crate::DeviceError::ResourceCreationFailed);
+            }
+            &supported_modifiers
+        } else {
+            drm_format_modifiers
+        };
+
+        let texture = unsafe {
+            self.create_raw_image(
+                desc,
+                Some(ExternalImage {
+                    handle_type,
+                    drm_format_modifiers,
+                    planes: &[],
+                }),
+            )?
+        };
+        let req = unsafe { self.shared.raw.get_image_memory_requirements(texture.raw) };
+        let mut dedicated_info = vk::MemoryDedicatedAllocateInfo::builder().image(texture.raw);
+        let memory = unsafe {
+            self.allocate_external_memory(&req, req.size, handle_type, None, &mut dedicated_info)
+        };
+        match memory {
+            Ok(memory) => unsafe { self.bind_external_texture(texture, desc, memory) },
+            Err(err) => {
+                unsafe { self.shared.raw.destroy_image(texture.raw, None) };
+                Err(err)
+            }
+        }
+    }
+
+    /// Returns a new file descriptor of the memory of `buffer`.
+    ///
+    /// # Panics
+    ///
+    /// - `buffer` wasn't created with [`Self::create_exportable_buffer`] or
+    ///   [`Self::import_buffer`].
+    #[cfg(unix)]
+    pub unsafe fn export_buffer_memory(
+        &self,
+        buffer: &super::Buffer,
+    ) -> Result<wgt::ExternalMemory, crate::DeviceError> {
+        let memory = buffer
+            .external_memory
+            .as_ref()
+            .expect("Buffer was not created with external memory");
+        Ok(wgt::ExternalMemory {
+            fd: unsafe { self.export_memory_fd(memory)? },
+            handle_type: memory.handle_type,
+            size: memory.size,
+            drm_format_modifier: None,
+            planes: Vec::new(),
+        })
+    }
+
+    /// Returns a new file descriptor of the memory of `texture`, along with the
+    /// layout of the texture in it for dma-bufs.
+    ///
+    /// # Panics
+    ///
+    /// - `texture` wasn't created with [`Self::create_exportable_texture`] or
+    ///   [`Self::import_texture`].
+    #[cfg(unix)]
+    pub unsafe fn export_texture_memory(
+        &self,
+        texture: &super::Texture,
+    ) -> Result<wgt::ExternalMemory, crate::DeviceError> {
+        let memory = texture
+            .external_memory
+            .as_ref()
+            .expect("Texture was not created with external memory");
+        let fd = unsafe { self.export_memory_fd(memory)? };
+
+        let (drm_format_modifier, planes) =
+            if memory.handle_type == wgt::ExternalMemoryHandleType::DmaBuf {
+                let fns = self
+                    .shared
+                    .extension_fns
+                    .image_drm_format_modifier
+                    .as_ref()
+                    .expect("Feature `EXTERNAL_MEMORY_DMA_BUF` not enabled");
+                let mut properties = vk::ImageDrmFormatModifierPropertiesEXT::default();
+                unsafe {
+                    (fns.get_image_drm_format_modifier_properties_ext)(
+                        self.shared.raw.handle(),
+                        texture.raw,
+                        &mut properties,
+                    )
+                }
+                .result()?;
+                let plane_count = self
+                    .drm_format_modifiers(texture.format, crate::TextureUses::empty())
+                    .into_iter()
+                    .find(|modifier| modifier.modifier == properties.drm_format_modifier)
+                    .map_or(1, |modifier| modifier.plane_count);
+                let planes = [
+                    vk::ImageAspectFlags::MEMORY_PLANE_0_EXT,
+                    vk::ImageAspectFlags::MEMORY_PLANE_1_EXT,
+                    vk::ImageAspectFlags::MEMORY_PLANE_2_EXT,
+                    vk::ImageAspectFlags::MEMORY_PLANE_3_EXT,
+                ]
+                .into_iter()
+                .take(plane_count as usize)
+                .map(|aspect_mask| {
+                    let layout = unsafe {
+                        self.shared.raw.get_image_subresource_layout(
+                            texture.raw,
+                            vk::ImageSubresource {
+                                aspect_mask,
+                                mip_level: 0,
+                                array_layer: 0,
+                            },
+                        )
+                    };
+                    wgt::MemoryPlaneLayout {
+                        offset: layout.offset,
+                        row_pitch: layout.row_pitch,
+                    }
+                })
+                .collect();
+                (Some(properties.drm_format_modifier), planes)
+            } else {
+                (None, Vec::new())
+            };
+
+        Ok(wgt::ExternalMemory {
+            fd,
+            handle_type: memory.handle_type,
+            size: memory.size,
+            drm_format_modifier,
+            planes,
+        })
+    }
+
+    /// Creates a buffer for `desc` in the external `memory`, which the buffer
+    /// takes ownership of.
+    ///
+    /// The buffer can't be mapped.
+    ///
+    /// # Safety
+    ///
+    /// - `desc` must be valid, like for [`crate::Device::create_buffer`].
+    /// - The feature of `memory.handle_type` must be enabled.
+    /// - `memory` must hold a buffer of at least `desc.size` bytes, and for
+    ///   opaque file descriptors, have been exported by the same driver and
+    ///   physical device for a buffer created with the same descriptor.
+    #[cfg(unix)]
+    pub unsafe fn import_buffer(
+        &self,
+        desc: &crate::BufferDescriptor,
+        memory: wgt::ExternalMemory,
+    ) -> Result<super::Buffer, crate::DeviceError> {
+        use std::os::fd::{AsRawFd as _, IntoRawFd as _};
+
+        let raw = unsafe { self.create_raw_buffer(desc, Some(memory.handle_type))? };
+        let req = unsafe { self.shared.raw.get_buffer_memory_requirements(raw) };
+        let mut dedicated_info = vk::MemoryDedicatedAllocateInfo::builder().buffer(raw);
+        let allocated = if memory.size < req.size {
+            Err(crate::DeviceError::ResourceCreationFailed)
+        } else {
+            unsafe {
+                self.allocate_external_memory(
+                    &req,
+                    memory.size,
+                    memory.handle_type,
+                    Some(memory.fd.as_raw_fd()),
+                    &mut dedicated_info,
+                )
+            }
+        };
+        match allocated {
+            Ok(allocated) => {
+                // The driver owns the file descriptor once it is imported.
+                let _ = memory.fd.into_raw_fd();
+                unsafe { self.bind_external_buffer(raw, desc, allocated) }
+            }
+            Err(err) => {
+                unsafe { self.shared.raw.destroy_buffer(raw, None) };
+                Err(err)
+            }
+        }
+    }
+
+    /// Creates a texture for `desc` in the external `memory`, which the texture
+    /// takes ownership of.
+    ///
+    /// Textures in dma-bufs are laid out as described by `memory.drm_format_modifier`
+    /// and `memory.planes`, which have to be given.
+    ///
+    /// # Safety
+    ///
+    /// - `desc` must be valid, like for [`crate::Device::create_texture`].
+    /// - The feature of `memory.handle_type` must be enabled.
+    /// - `memory` must hold a texture laid out as described, and for opaque
+    ///   file descriptors, have been exported by the same driver and physical
+    ///   device for a texture created with the same descriptor.
+    #[cfg(unix)]
+    pub unsafe fn import_texture(
+        &self,
+        desc: &crate::TextureDescriptor,
+        memory: wgt::ExternalMemory,
+    ) -> Result<super::Texture, crate::DeviceError> {
+        use std::os::fd::{AsRawFd as _, IntoRawFd as _};
+
+        let drm_format_modifiers = Vec::from_iter(memory.drm_format_modifier);
+        let planes = memory
+            .planes
+            .iter()
+            .map(|plane| vk::SubresourceLayout {
+                offset: plane.offset,
+                size: 0,
+                row_pitch: plane.row_pitch,
+                array_pitch: 0,
+                depth_pitch: 0,
+            })
+            .collect::<Vec<_>>();
+        if memory.handle_type == wgt::ExternalMemoryHandleType::DmaBuf
+            && (drm_format_modifiers.is_empty() || planes.is_empty())
+        {
+            return Err(crate::DeviceError::ResourceCreationFailed);
+        }
+
+        let texture = unsafe {
+            self.create_raw_image(
+                desc,
+                Some(ExternalImage {
+                    handle_type: memory.handle_type,
+                    drm_format_modifiers: &drm_format_modifiers,
+                    planes: &planes,
+                }),
+            )?
+        };
+        let req = unsafe { self.shared.raw.get_image_memory_requirements(texture.raw) };
+        let mut dedicated_info = vk::MemoryDedicatedAllocateInfo::builder().image(texture.raw);
+        let allocated = if memory.size < req.size {
+            Err(crate::DeviceError::ResourceCreationFailed)
+        } else {
+            unsafe {
+                self.allocate_external_memory(
+                    &req,
+                    memory.size,
+                    memory.handle_type,
+                    Some(memory.fd.as_raw_fd()),
+                    &mut dedicated_info,
+                )
+            }
+        };
+        match allocated {
+            Ok(allocated) => {
+                // The driver owns the file descriptor once it is imported.
+                let _ = memory.fd.into_raw_fd();
+                unsafe { self.bind_external_texture(texture, desc, allocated) }
+            }
+            Err(err) => {
+                unsafe { self.shared.raw.destroy_image(texture.raw, None) };
+                Err(err)
+            }
+        }
+    }
+
+    /// Allocates the dedicated memory of a resource with the requirements `req`,
+    /// which is shared through `handle_type`.
+    ///
+    /// The memory is imported from `import_fd` if it is `Some`, and can be
+    /// exported otherwise.
+    unsafe fn allocate_external_memory(
+        &self,
+        req: &vk::MemoryRequirements,
+        size: wgt::BufferAddress,
+        handle_type: wgt::ExternalMemoryHandleType,
+        import_fd: Option<i32>,
+        dedicated_info: &mut vk::MemoryDedicatedAllocateInfo,
+    ) -> Result<super::ExternalMemory, crate::DeviceError> {
+        let vk_handle_type = conv::map_external_memory_handle_type(handle_type);
+        let mut memory_types = req.memory_type_bits & self.valid_ash_memory_types;
+
+        let mut export_info = vk::ExportMemoryAllocateInfo::builder().handle_types(vk_handle_type);
+        let mut import_info = vk::ImportMemoryFdInfoKHR::builder().handle_type(vk_handle_type);
+        let mut vk_info = vk::MemoryAllocateInfo::builder()
+            .allocation_size(size)
+            .push_next(dedicated_info);
+        match import_fd {
+            Some(fd) => {
+                // Opaque file descriptors can't be queried, they are imported
+                // into the type of memory they were exported from, which is
+                // picked the same way.
+                if handle_type == wgt::ExternalMemoryHandleType::DmaBuf {
+                    let fns = self
+                        .shared
+                        .extension_fns
+                        .external_memory_fd
+                        .as_ref()
+                        .expect("Feature `EXTERNAL_MEMORY_DMA_BUF` not enabled");
+                    let mut properties = vk::MemoryFdPropertiesKHR::default();
+                    unsafe { fns.get_memory_fd_properties(vk_handle_type, fd, &mut properties)? };
+                    memory_types &= properties.memory_type_bits;
+                }
+                import_info = import_info.fd(fd);
+                vk_info = vk_info.push_next(&mut import_info);
+            }
+            None => vk_info = vk_info.push_next(&mut export_info),
+        }
+        if memory_types == 0 {
+            return Err(crate::DeviceError::ResourceCreationFailed);
+        }
+        vk_info = vk_info.memory_type_index(memory_types.trailing_zeros());
+
+        let raw = unsafe { self.shared.raw.allocate_memory(&vk_info, None)? };
+        Ok(super::ExternalMemory {
+            raw,
+            handle_type,
+            size,
+        })
+    }
+
+    /// Binds `raw` to its external `memory`, freeing both if it fails.
+    unsafe fn bind_external_buffer(
+        &self,
+        raw: vk::Buffer,
+        desc: &crate::BufferDescriptor,
+        memory: super::ExternalMemory,
+    ) -> Result<super::Buffer, crate::DeviceError> {
+        if let Err(err) = unsafe { self.shared.raw.bind_buffer_memory(raw, memory.raw, 0) } {
+            unsafe {
+                self.shared.raw.destroy_buffer(raw, None);
+                self.shared.raw.free_memory(memory.raw, None);
+            }
+            return Err(err.into());
+        }
+
+        if let Some(label) = desc.label {
+            unsafe {
+                self.shared
+                    .set_object_name(vk::ObjectType::BUFFER, raw, label)
+            };
+        }
+
+        Ok(super::Buffer {
+            raw,
+            block: None,
+            sparse: None,
+            external_memory: Some(memory),
+        })
+    }
+
+    /// Binds `texture` to its external `memory`, freeing both if it fails.
+    unsafe fn bind_external_texture(
+        &self,
+        mut texture: super::Texture,
+        desc: &crate::TextureDescriptor,
+        memory: super::ExternalMemory,
+    ) -> Result<super::Texture, crate::DeviceError> {
+        if let Err(err) = unsafe {
+            self.shared
+                .raw
+                .bind_image_memory(texture.raw, memory.raw, 0)
+        } {
+            unsafe {
+                self.shared.raw.destroy_image(texture.raw, None);
+                self.shared.raw.free_memory(memory.raw, None);
+            }
+            return Err(err.into());
+        }
+
+        if let Some(label) = desc.label {
+            unsafe {
+                self.shared
+                    .set_object_name(vk::ObjectType::IMAGE, texture.raw, label)
+            };
+        }
+
+        texture.external_memory = Some(memory);
+        Ok(texture)
+    }
+
+    /// Creates a timeline semaphore that can be exported with
+    /// [`Self::export_fence`], as an opaque file descriptor.
+    ///
+    /// # Safety
+    ///
+    /// - [`wgt::Features::EXTERNAL_SEMAPHORE_FD`] must be enabled.
+    #[cfg(unix)]
+    pub unsafe fn create_exportable_fence(&self) -> Result<super::Fence, crate::DeviceError> {
+        let mut sem_type_info =
+            vk::SemaphoreTypeCreateInfo::builder().semaphore_type(vk::SemaphoreType::TIMELINE);
+        let mut export_info = vk::ExportSemaphoreCreateInfo::builder()
+            .handle_types(vk::ExternalSemaphoreHandleTypeFlags::OPAQUE_FD);
+        let vk_info = vk::SemaphoreCreateInfo::builder()
+            .push_next(&mut sem_type_info)
+            .push_next(&mut export_info);
+        let raw = unsafe { self.shared.raw.create_semaphore(&vk_info, None) }?;
+        Ok(super::Fence::TimelineSemaphore(raw))
+    }
+
+    /// Creates a timeline semaphore sharing the payload of `semaphore`, which
+    /// the fence takes ownership of.
+    ///
+    /// # Safety
+    ///
+    /// - [`wgt::Features::EXTERNAL_SEMAPHORE_FD`] must be enabled.
+    /// - `semaphore` must be an opaque file descriptor of a timeline semaphore,
+    ///   exported by the same driver and physical device.
+    #[cfg(unix)]
+    pub unsafe fn import_fence(
+        &self,
+        semaphore: wgt::ExternalSemaphore,
+    ) -> Result<super::Fence, crate::DeviceError> {
+        use std::os::fd::{AsRawFd as _, IntoRawFd as _};
+
+        debug_assert_eq!(
+            semaphore.handle_type,
+            wgt::ExternalSemaphoreHandleType::OpaqueFd
+        );
+        let fns = self
+            .shared
+            .extension_fns
+            .external_semaphore_fd
+            .as_ref()
+            .expect("Feature `EXTERNAL_SEMAPHORE_FD` not enabled");
+        let mut sem_type_info =
+            vk::SemaphoreTypeCreateInfo::builder().semaphore_type(vk::SemaphoreType::TIMELINE);
+        let vk_info = vk::SemaphoreCreateInfo::builder().push_next(&mut sem_type_info);
+        let raw = unsafe { self.shared.raw.create_semaphore(&vk_info, None) }?;
+
+        let vk_info = vk::ImportSemaphoreFdInfoKHR::builder()
+            .semaphore(raw)
+            .handle_type(vk::ExternalSemaphoreHandleTypeFlags::OPAQUE_FD)
+            .fd(semaphore.fd.as_raw_fd());
+        if let Err(err) = unsafe { fns.import_semaphore_fd(&vk_info) } {
+            unsafe { self.shared.raw.destroy_semaphore(raw, None) };
+            return Err(err.into());
+        }
+        // The driver owns the file descriptor once it is imported.
+        let _ = semaphore.fd.into_raw_fd();
+
+        Ok(super::Fence::TimelineSemaphore(raw))
+    }
+
+    /// Returns a new opaque file descriptor of `fence`, owned by the caller.
+    ///
+    /// # Panics
+    ///
+    /// - `fence` isn't a timeline semaphore.
+    ///
+    /// # Safety
+    ///
+    /// - `fence` must have been created with [`Self::create_exportable_fence`]
+    ///   or [`Self::import_fence`].
+    #[cfg(unix)]
+    pub unsafe fn export_fence(
+        &self,
+        fence: &super::Fence,
+    ) -> Result<wgt::ExternalSemaphore, crate::DeviceError> {
+        use std::os::fd::FromRawFd as _;
+
+        let raw = match *fence {
+            super::Fence::TimelineSemaphore(raw) => raw,
+            super::Fence::FencePool { .. } => panic!("Fence is not a timeline semaphore"),
+        };
+        let fns = self
+            .shared
+            .extension_fns
+            .external_semaphore_fd
+            .as_ref()
+            .expect("Feature `EXTERNAL_SEMAPHORE_FD` not enabled");
+        let vk_info = vk::SemaphoreGetFdInfoKHR::builder()
+            .semaphore(raw)
+            .handle_type(vk::ExternalSemaphoreHandleTypeFlags::OPAQUE_FD);
+        let fd = unsafe { fns.get_semaphore_fd(&vk_info)? };
+        Ok(wgt::ExternalSemaphore {
+            fd: unsafe { std::os::fd::OwnedFd::from_raw_fd(fd) },
+            handle_type: wgt::ExternalSemaphoreHandleType::OpaqueFd,
+        })
+    }
+
+    /// Returns a new file descriptor of `memory`, owned by the caller.
+    #[cfg(unix)]
+    unsafe fn export_memory_fd(
+        &self,
+        memory: &super::ExternalMemory,
+    ) -> Result<std::os::fd::OwnedFd, crate::DeviceError> {
+        use std::os::fd::FromRawFd as _;
+
+        let fns = self
+            .shared
+            .extension_fns
+            .external_memory_fd
+            .as_ref()
+            .expect("Feature `EXTERNAL_MEMORY_FD` not enabled");
+        let vk_info = vk::MemoryGetFdInfoKHR::builder()
+            .memory(memory.raw)
+            .handle_type(conv::map_external_memory_handle_type(memory.handle_type));
+        let fd = unsafe { fns.get_memory_fd(&vk_info)? };
+        Ok(unsafe { std::os::fd::OwnedFd::from_raw_fd(fd) })
+    }
+
+    /// Creates a buffer for `desc`, without any memory bound to it.
+    ///
+    /// If `handle_type` is `Some`, the buffer can be bound to memory shared
+    /// through that type of handle.
+    unsafe fn create_raw_buffer(
+        &self,
+        desc: &crate::BufferDescriptor,
+        handle_type: Option<wgt::ExternalMemoryHandleType>,
+    ) -> Result<vk::Buffer, crate::DeviceError> {
+        let sharing_families = self.shared.sharing_families();
+        let mut usage = conv::map_buffer_usage(desc.usage);
+        if desc
+            .memory_flags
+            .contains(crate::MemoryFlags::DEVICE_ADDRESS)
+        {
+            usage |= vk::BufferUsageFlags::SHADER_DEVICE_ADDRESS;
+        }
+        let mut vk_info = vk::BufferCreateInfo::builder()
+            .size(desc.size)
+            .usage(usage)
+            .sharing_mode(vk::SharingMode::EXCLUSIVE);
+        if let Some(ref families) = sharing_families {
+            vk_info = vk_info
+                .sharing_mode(vk::SharingMode::CONCURRENT)
+                .queue_family_indices(families);
+        }
+        if desc.memory_flags.contains(crate::MemoryFlags::SPARSE) {
+            vk_info = vk_info.flags(
+                vk::BufferCreateFlags::SPARSE_BINDING | vk::BufferCreateFlags::SPARSE_RESIDENCY,
+            );
         }
+        let mut external_info = vk::ExternalMemoryBufferCreateInfo::builder();
+        if let Some(handle_type) = handle_type {
+            external_info =
+                external_info.handle_types(conv::map_external_memory_handle_type(handle_type));
+            vk_info = vk_info.push_next(&mut external_info);
+        }
+
+        Ok(unsafe { self.shared.raw.create_buffer(&vk_info, None)? })
+    }
+
+    /// Creates an image for `desc`, without any memory bound to it.
+    ///
+    /// If `external` is `Some`, the image can be bound to memory shared through
+    /// a handle.
+    unsafe fn create_raw_image(
+        &self,
+        desc: &crate::TextureDescriptor,
+        external: Option<ExternalImage<'_>>,
+    ) -> Result<super::Texture, crate::DeviceError> {
+        let copy_size = desc.copy_extent();
+
+        let mut raw_flags = vk::ImageCreateFlags::empty();
+        if desc.is_cube_compatible() {
+            raw_flags |= vk::ImageCreateFlags::CUBE_COMPATIBLE;
+        }
+
+        let original_format = self.shared.private_caps.map_texture_format(desc.format);
+        let mut vk_view_formats = vec![];
+        let mut wgt_view_formats = vec![];
+        if !desc.view_formats.is_empty() {
+            raw_flags |= vk::ImageCreateFlags::MUTABLE_FORMAT;
+            wgt_view_formats = desc.view_formats.clone();
+            wgt_view_formats.push(desc.format);
+
+            if self.shared.private_caps.image_format_list {
+                vk_view_formats = desc
+                    .view_formats
+                    .iter()
+                    .map(|f| self.shared.private_caps.map_texture_format(*f))
+                    .collect();
+                vk_view_formats.push(original_format)
+            }
+        }
+        if desc.format.is_multi_planar_format() {
+            raw_flags |= vk::ImageCreateFlags::MUTABLE_FORMAT;
+        }
+        // Views that reinterpret the texture may be used in ways its own format
+        // doesn't support.
+        if desc
+            .view_formats
+            .iter()
+            .any(|f| f.remove_srgb_suffix() != desc.format.remove_srgb_suffix())
+        {
+            raw_flags |= vk::ImageCreateFlags::EXTENDED_USAGE;
+            if desc.format.is_compressed() && desc.view_formats.iter().any(|f| !f.is_compressed()) {
+                raw_flags |= vk::ImageCreateFlags::BLOCK_TEXEL_VIEW_COMPATIBLE;
+            }
+        }
+        if desc.memory_flags.contains(crate::MemoryFlags::SPARSE) {
+            raw_flags |=
+                vk::ImageCreateFlags::SPARSE_BINDING | vk::ImageCreateFlags::SPARSE_RESIDENCY;
+        }
+        let mut usage = conv::map_texture_usage(desc.usage);
+        if desc.memory_flags.contains(crate::MemoryFlags::MEMORYLESS) {
+            usage |= vk::ImageUsageFlags::TRANSIENT_ATTACHMENT;
+        }
+
+        let sharing_families = self.shared.sharing_families();
+        let mut vk_info = vk::ImageCreateInfo::builder()
+            .flags(raw_flags)
+            .image_type(conv::map_texture_dimension(desc.dimension))
+            .format(original_format)
+            .extent(conv::map_copy_extent(&copy_size))
+            .mip_levels(desc.mip_level_count)
+            .array_layers(desc.array_layer_count())
+            .samples(vk::SampleCountFlags::from_raw(desc.sample_count))
+            .tiling(vk::ImageTiling::OPTIMAL)
+            .usage(usage)
+            .sharing_mode(vk::SharingMode::EXCLUSIVE)
+            .initial_layout(vk::ImageLayout::UNDEFINED);
+        if let Some(ref families) = sharing_families {
+            vk_info = vk_info
+                .sharing_mode(vk::SharingMode::CONCURRENT)
+                .queue_family_indices(families);
+        }
+
+        let mut format_list_info = vk::ImageFormatListCreateInfo::builder();
+        if !vk_view_formats.is_empty() {
+            format_list_info = format_list_info.view_formats(&vk_view_formats);
+            vk_info = vk_info.push_next(&mut format_list_info);
+        }
+
+        let mut external_info = vk::ExternalMemoryImageCreateInfo::builder();
+        let mut modifier_list_info = vk::ImageDrmFormatModifierListCreateInfoEXT::builder();
+        let mut modifier_explicit_info = vk::ImageDrmFormatModifierExplicitCreateInfoEXT::builder();
+        if let Some(ref external) = external {
+            external_info = external_info
+                .handle_types(conv::map_external_memory_handle_type(external.handle_type));
+            vk_info = vk_info.push_next(&mut external_info);
+            // Textures in dma-bufs are laid out as described by a modifier,
+            // which the driver picks when the image isn't imported.
+            if external.handle_type == wgt::ExternalMemoryHandleType::DmaBuf {
+                vk_info = vk_info.tiling(vk::ImageTiling::DRM_FORMAT_MODIFIER_EXT);
+                if external.planes.is_empty() {
+                    modifier_list_info =
+                        modifier_list_info.drm_format_modifiers(external.drm_format_modifiers);
+                    vk_info = vk_info.push_next(&mut modifier_list_info);
+                } else {
+                    modifier_explicit_info = modifier_explicit_info
+                        .drm_format_modifier(external.drm_format_modifiers[0])
+                        .plane_layouts(external.planes);
+                    vk_info = vk_info.push_next(&mut modifier_explicit_info);
+                }
+            }
+        }
+
+        let raw = unsafe { self.shared.raw.create_image(&vk_info, None)? };
+
+        Ok(super::Texture {
+            raw,
+            drop_guard: None,
+            block: None,
+            usage: desc.usage,
+            format: desc.format,
+            raw_flags,
+            copy_size,
+            view_formats: wgt_view_formats,
+            sparse: None,
+            lazy_memory: None,
+            external_memory: None,
+        })
+    }
+
+    /// Creates the Y'CbCr conversion of a `BindingType::YcbcrTexture` binding,
+    /// and the immutable sampler that samples with it.
+    unsafe fn create_ycbcr_sampler(
+        &self,
+        binding: u32,
+        format: wgt::TextureFormat,
+        conversion: wgt::SamplerYcbcrConversion,
+    ) -> Result<super::YcbcrSampler, crate::DeviceError> {
+        let chroma_filter = conv::map_filter_mode(conversion.chroma_filter);
+        let vk_info = vk::SamplerYcbcrConversionCreateInfo::builder()
+            .format(self.shared.private_caps.map_texture_format(format))
+            .ycbcr_model(conv::map_ycbcr_model(conversion.model))
+            .ycbcr_range(conv::map_ycbcr_range(conversion.range))
+            .components(vk::ComponentMapping::default())
+            .x_chroma_offset(conv::map_chroma_location(conversion.x_chroma_offset))
+            .y_chroma_offset(conv::map_chroma_location(conversion.y_chroma_offset))
+            .chroma_filter(chroma_filter)
+            .force_explicit_reconstruction(false);
+        let raw_conversion = unsafe {
+            self.shared
+                .raw
+                .create_sampler_ycbcr_conversion(&vk_info, None)?
+        };
+
+        // Samplers with a conversion have to filter like it, and clamp to the edge.
+        let mut conversion_info =
+            vk::SamplerYcbcrConversionInfo::builder().conversion(raw_conversion);
+        let vk_info = vk::SamplerCreateInfo::builder()
+            .mag_filter(chroma_filter)
+            .min_filter(chroma_filter)
+            .mipmap_mode(vk::SamplerMipmapMode::NEAREST)
+            .address_mode_u(vk::SamplerAddressMode::CLAMP_TO_EDGE)
+            .address_mode_v(vk::SamplerAddressMode::CLAMP_TO_EDGE)
+            .address_mode_w(vk::SamplerAddressMode::CLAMP_TO_EDGE)
+            .push_next(&mut conversion_info);
+        let raw_sampler = match unsafe { self.shared.raw.create_sampler(&vk_info, None) } {
+            Ok(raw) => raw,
+            Err(err) => {
+                unsafe {
+                    self.shared
+                        .raw
+                        .destroy_sampler_ycbcr_conversion(raw_conversion, None)
+                };
+                return Err(err.into());
+            }
+        };
+
+        Ok(super::YcbcrSampler {
+            binding,
+            conversion: raw_conversion,
+            sampler: raw_sampler,
+        })
     }
 
-    /// # Safety
-    ///
-    /// - `vk_buffer`'s memory must be managed by the caller
-    /// - Externally imported buffers can't be mapped by `wgpu`
-    pub unsafe fn buffer_from_raw(vk_buffer: vk::Buffer) -> super::Buffer {
-        super::Buffer {
-            raw: vk_buffer,
-            block: None,
+    unsafe fn destroy_ycbcr_sampler(&self, ycbcr_sampler: super::YcbcrSampler) {
+        unsafe {
+            self.shared.raw.destroy_sampler(ycbcr_sampler.sampler, None);
+            self.shared
+                .raw
+                .destroy_sampler_ycbcr_conversion(ycbcr_sampler.conversion, None);
         }
     }
 
@@ -783,6 +1711,10 @@ impl super::Device {
                     naga::back::spv::write_vec(&module, &info, options, Some(&pipeline_options))
                 }
                 .map_err(|e| crate::PipelineError::Linkage(stage_flags, format!("{e}")))?;
+                if let Some(capture) = stage.code_capture {
+                    let bytes = spv.iter().flat_map(|word| word.to_le_bytes());
+                    capture.capture("spv", bytes.collect::<Vec<_>>());
+                }
                 self.create_shader_module_impl(&spv)?
             }
         };
@@ -849,28 +1781,66 @@ impl crate::Device for super::Device {
 
     unsafe fn exit(self, queue: super::Queue) {
         unsafe { self.mem_allocator.into_inner().cleanup(&*self.shared) };
-        unsafe { self.desc_allocator.into_inner().cleanup(&*self.shared) };
+        unsafe { self.desc_arenas.cleanup(&*self.shared) };
         unsafe {
             queue
                 .relay_semaphores
                 .into_inner()
-                .destroy(&self.shared.raw)
+                .destroy(&self.shared.raw);
+            queue.sync_files.into_inner().destroy(&self.shared.raw);
         };
         unsafe { self.shared.free_resources() };
     }
 
+    unsafe fn destroy_queue(&self, queue: super::Queue) {
+        unsafe {
+            queue
+                .relay_semaphores
+                .into_inner()
+                .destroy(&self.shared.raw);
+            queue.sync_files.into_inner().destroy(&self.shared.raw);
+        };
+    }
+
     unsafe fn create_buffer(
         &self,
         desc: &crate::BufferDescriptor,
     ) -> Result<super::Buffer, crate::DeviceError> {
-        let vk_info = vk::BufferCreateInfo::builder()
-            .size(desc.size)
-            .usage(conv::map_buffer_usage(desc.usage))
-            .sharing_mode(vk::SharingMode::EXCLUSIVE);
+        let device_address = desc
+            .memory_flags
+            .contains(crate::MemoryFlags::DEVICE_ADDRESS);
+        let sparse = desc.memory_flags.contains(crate::MemoryFlags::SPARSE);
 
-        let raw = unsafe { self.shared.raw.create_buffer(&vk_info, None)? };
+        let raw = unsafe { self.create_raw_buffer(desc, None)? };
         let req = unsafe { self.shared.raw.get_buffer_memory_requirements(raw) };
 
+        if sparse {
+            // Pages are bound to memory one at a time, so they have to be
+            // aligned for it.
+            if wgt::SPARSE_BUFFER_PAGE_SIZE % req.alignment != 0 {
+                unsafe { self.shared.raw.destroy_buffer(raw, None) };
+                return Err(crate::DeviceError::ResourceCreationFailed);
+            }
+            if let Some(label) = desc.label {
+                unsafe {
+                    self.shared
+                        .set_object_name(vk::ObjectType::BUFFER, raw, label)
+                };
+            }
+            return Ok(super::Buffer {
+                raw,
+                block: None,
+                sparse: Some(Mutex::new(super::SparseMemory {
+                    size: req.size,
+                    align_mask: req.alignment - 1,
+                    memory_types: req.memory_type_bits & self.valid_ash_memory_types,
+                    pages: BTreeMap::new(),
+                    released: Vec::new(),
+                })),
+                external_memory: None,
+            });
+        }
+
         let mut alloc_usage = if desc
             .usage
             .intersects(crate::BufferUses::MAP_READ | crate::BufferUses::MAP_WRITE)
@@ -893,6 +1863,7 @@ impl crate::Device for super::Device {
             gpu_alloc::UsageFlags::TRANSIENT,
             desc.memory_flags.contains(crate::MemoryFlags::TRANSIENT),
         );
+        alloc_usage.set(gpu_alloc::UsageFlags::DEVICE_ADDRESS, device_address);
 
         let alignment_mask = if desc.usage.intersects(
             crate::BufferUses::TOP_LEVEL_ACCELERATION_STRUCTURE_INPUT
@@ -931,10 +1902,15 @@ impl crate::Device for super::Device {
         Ok(super::Buffer {
             raw,
             block: Some(Mutex::new(block)),
+            sparse: None,
+            external_memory: None,
         })
     }
     unsafe fn destroy_buffer(&self, buffer: super::Buffer) {
         unsafe { self.shared.raw.destroy_buffer(buffer.raw, None) };
+        if let Some(memory) = buffer.external_memory {
+            unsafe { self.shared.raw.free_memory(memory.raw, None) };
+        }
         if let Some(block) = buffer.block {
             unsafe {
                 self.mem_allocator
@@ -942,6 +1918,14 @@ impl crate::Device for super::Device {
                     .dealloc(&*self.shared, block.into_inner())
             };
         }
+        if let Some(sparse) = buffer.sparse {
+            let sparse = sparse.into_inner();
+            let mut allocator = self.mem_allocator.lock();
+            let released = sparse.released.into_iter().map(|(_, block)| block);
+            for block in sparse.pages.into_values().chain(released) {
+                unsafe { allocator.dealloc(&*self.shared, block) };
+            }
+        }
     }
 
     unsafe fn map_buffer(
@@ -1001,103 +1985,267 @@ impl crate::Device for super::Device {
         }
     }
 
+    unsafe fn get_buffer_device_address(&self, buffer: &super::Buffer) -> wgt::BufferAddress {
+        let buffer_device_address = self
+            .shared
+            .extension_fns
+            .buffer_device_address
+            .as_ref()
+            .expect("Feature `BUFFER_DEVICE_ADDRESS` not enabled");
+
+        unsafe {
+            buffer_device_address.get_buffer_device_address(
+                &vk::BufferDeviceAddressInfo::builder().buffer(buffer.raw),
+            )
+        }
+    }
+
     unsafe fn create_texture(
         &self,
         desc: &crate::TextureDescriptor,
     ) -> Result<super::Texture, crate::DeviceError> {
-        let copy_size = desc.copy_extent();
+        let mut texture = unsafe { self.create_raw_image(desc, None)? };
+        let raw = texture.raw;
+        let req = unsafe { self.shared.raw.get_image_memory_requirements(raw) };
+        let sparse = desc.memory_flags.contains(crate::MemoryFlags::SPARSE);
+
+        if sparse {
+            let sparse_reqs = unsafe { self.shared.raw.get_image_sparse_memory_requirements(raw) };
+            let color_req = sparse_reqs.into_iter().find(|sparse_req| {
+                sparse_req
+                    .format_properties
+                    .aspect_mask
+                    .contains(vk::ImageAspectFlags::COLOR)
+            });
+            // Mip tails are committed per layer, so one shared by all layers
+            // can't be supported.
+            let color_req = match color_req {
+                Some(sparse_req)
+                    if desc.array_layer_count() == 1
+                        || !sparse_req
+                            .format_properties
+                            .flags
+                            .contains(vk::SparseImageFormatFlags::SINGLE_MIPTAIL) =>
+                {
+                    sparse_req
+                }
+                _ => {
+                    unsafe { self.shared.raw.destroy_image(raw, None) };
+                    return Err(crate::DeviceError::ResourceCreationFailed);
+                }
+            };
+            if let Some(label) = desc.label {
+                unsafe {
+                    self.shared
+                        .set_object_name(vk::ObjectType::IMAGE, raw, label)
+                };
+            }
+            let granularity = color_req.format_properties.image_granularity;
+            texture.sparse = Some(Mutex::new(super::SparseImageMemory {
+                tiles: wgt::SparseTextureTiles {
+                    tile_size: wgt::Extent3d {
+                        width: granularity.width,
+                        height: granularity.height,
+                        depth_or_array_layers: granularity.depth,
+                    },
+                    mip_tail_first_level: color_req
+                        .image_mip_tail_first_lod
+                        .min(desc.mip_level_count),
+                },
+                size: conv::map_copy_extent(&texture.copy_size),
+                align_mask: req.alignment - 1,
+                memory_types: req.memory_type_bits & self.valid_ash_memory_types,
+                mip_tail_size: color_req.image_mip_tail_size,
+                mip_tail_offset: color_req.image_mip_tail_offset,
+                mip_tail_stride: color_req.image_mip_tail_stride,
+                committed: BTreeMap::new(),
+                released: Vec::new(),
+            }));
+            return Ok(texture);
+        }
+
+        // `gpu_alloc` never picks lazily allocated memory, so it is allocated
+        // here. Without any, the texture gets regular memory.
+        let lazy_memory_types = req.memory_type_bits & self.lazily_allocated_memory_types;
+        if desc.memory_flags.contains(crate::MemoryFlags::MEMORYLESS) && lazy_memory_types != 0 {
+            let info = vk::MemoryAllocateInfo::builder()
+                .allocation_size(req.size)
+                .memory_type_index(lazy_memory_types.trailing_zeros());
+            let memory = unsafe { self.shared.raw.allocate_memory(&info, None)? };
+            unsafe { self.shared.raw.bind_image_memory(raw, memory, 0)? };
+            texture.lazy_memory = Some(memory);
+        } else {
+            let block = unsafe {
+                self.mem_allocator.lock().alloc(
+                    &*self.shared,
+                    gpu_alloc::Request {
+                        size: req.size,
+                        align_mask: req.alignment - 1,
+                        usage: gpu_alloc::UsageFlags::FAST_DEVICE_ACCESS,
+                        memory_types: req.memory_type_bits & self.valid_ash_memory_types,
+                    },
+                )?
+            };
 
-        let mut raw_flags = vk::ImageCreateFlags::empty();
-        if desc.is_cube_compatible() {
-            raw_flags |= vk::ImageCreateFlags::CUBE_COMPATIBLE;
+            unsafe {
+                self.shared
+                    .raw
+                    .bind_image_memory(raw, *block.memory(), block.offset())?
+            };
+            texture.block = Some(block);
         }
 
-        let original_format = self.shared.private_caps.map_texture_format(desc.format);
-        let mut vk_view_formats = vec![];
-        let mut wgt_view_formats = vec![];
-        if !desc.view_formats.is_empty() {
-            raw_flags |= vk::ImageCreateFlags::MUTABLE_FORMAT;
-            wgt_view_formats = desc.view_formats.clone();
-            wgt_view_formats.push(desc.format);
+        if let Some(label) = desc.label {
+            unsafe {
+                self.shared
+                    .set_object_name(vk::ObjectType::IMAGE, raw, label)
+            };
+        }
 
-            if self.shared.private_caps.image_format_list {
-                vk_view_formats = desc
-                    .view_formats
-                    .iter()
-                    .map(|f| self.shared.private_caps.map_texture_format(*f))
-                    .collect();
-                vk_view_formats.push(original_format)
-            }
+        Ok(texture)
+    }
+    unsafe fn destroy_texture(&self, texture: super::Texture) {
+        if texture.drop_guard.is_none() {
+            unsafe { self.shared.raw.destroy_image(texture.raw, None) };
         }
-        if desc.format.is_multi_planar_format() {
-            raw_flags |= vk::ImageCreateFlags::MUTABLE_FORMAT;
+        if let Some(block) = texture.block {
+            unsafe { self.mem_allocator.lock().dealloc(&*self.shared, block) };
         }
-
-        let mut vk_info = vk::ImageCreateInfo::builder()
-            .flags(raw_flags)
-            .image_type(conv::map_texture_dimension(desc.dimension))
-            .format(original_format)
-            .extent(conv::map_copy_extent(&copy_size))
-            .mip_levels(desc.mip_level_count)
-            .array_layers(desc.array_layer_count())
-            .samples(vk::SampleCountFlags::from_raw(desc.sample_count))
-            .tiling(vk::ImageTiling::OPTIMAL)
-            .usage(conv::map_texture_usage(desc.usage))
-            .sharing_mode(vk::SharingMode::EXCLUSIVE)
-            .initial_layout(vk::ImageLayout::UNDEFINED);
-
-        let mut format_list_info = vk::ImageFormatListCreateInfo::builder();
-        if !vk_view_formats.is_empty() {
-            format_list_info = format_list_info.view_formats(&vk_view_formats);
-            vk_info = vk_info.push_next(&mut format_list_info);
+        if let Some(memory) = texture.lazy_memory {
+            unsafe { self.shared.raw.free_memory(memory, None) };
         }
+        if let Some(memory) = texture.external_memory {
+            unsafe { self.shared.raw.free_memory(memory.raw, None) };
+        }
+        if let Some(sparse) = texture.sparse {
+            let sparse = sparse.into_inner();
+            let mut allocator = self.mem_allocator.lock();
+            let released = sparse.released.into_iter().map(|(_, block)| block);
+            for block in sparse.committed.into_values().chain(released) {
+                unsafe { allocator.dealloc(&*self.shared, block) };
+            }
+        }
+    }
+    unsafe fn get_sparse_texture_tiles(&self, texture: &super::Texture) -> wgt::SparseTextureTiles {
+        texture
+            .sparse
+            .as_ref()
+            .expect("Texture was not created with `MemoryFlags::SPARSE`")
+            .lock()
+            .tiles
+    }
 
-        let raw = unsafe { self.shared.raw.create_image(&vk_info, None)? };
-        let req = unsafe { self.shared.raw.get_image_memory_requirements(raw) };
-
+    unsafe fn create_memory_heap(
+        &self,
+        desc: &crate::MemoryHeapDescriptor,
+    ) -> Result<super::MemoryHeap, crate::DeviceError> {
+        // The memory may be shared with other allocations, so it isn't labeled.
         let block = unsafe {
             self.mem_allocator.lock().alloc(
                 &*self.shared,
                 gpu_alloc::Request {
-                    size: req.size,
-                    align_mask: req.alignment - 1,
+                    size: desc.size,
+                    align_mask: super::MEMORY_HEAP_ALIGNMENT - 1,
                     usage: gpu_alloc::UsageFlags::FAST_DEVICE_ACCESS,
-                    memory_types: req.memory_type_bits & self.valid_ash_memory_types,
+                    memory_types: self.valid_ash_memory_types,
                 },
             )?
         };
-
-        unsafe {
-            self.shared
-                .raw
-                .bind_image_memory(raw, *block.memory(), block.offset())?
+        Ok(super::MemoryHeap { block })
+    }
+    unsafe fn destroy_memory_heap(&self, heap: super::MemoryHeap) {
+        unsafe { self.mem_allocator.lock().dealloc(&*self.shared, heap.block) };
+    }
+    unsafe fn get_buffer_memory_requirements(
+        &self,
+        desc: &crate::BufferDescriptor,
+    ) -> Result<wgt::MemoryRequirements, crate::DeviceError> {
+        let raw = unsafe { self.create_raw_buffer(desc, None)? };
+        let req = unsafe { self.shared.raw.get_buffer_memory_requirements(raw) };
+        unsafe { self.shared.raw.destroy_buffer(raw, None) };
+        Ok(wgt::MemoryRequirements {
+            size: req.size,
+            alignment: req.alignment,
+        })
+    }
+    unsafe fn get_texture_memory_requirements(
+        &self,
+        desc: &crate::TextureDescriptor,
+    ) -> Result<wgt::MemoryRequirements, crate::DeviceError> {
+        let raw = unsafe { self.create_raw_image(desc, None)? }.raw;
+        let req = unsafe { self.shared.raw.get_image_memory_requirements(raw) };
+        unsafe { self.shared.raw.destroy_image(raw, None) };
+        Ok(wgt::MemoryRequirements {
+            size: req.size,
+            alignment: req.alignment,
+        })
+    }
+    unsafe fn create_placed_buffer(
+        &self,
+        desc: &crate::BufferDescriptor,
+        heap: &super::MemoryHeap,
+        offset: wgt::BufferAddress,
+    ) -> Result<super::Buffer, crate::DeviceError> {
+        let raw = unsafe { self.create_raw_buffer(desc, None)? };
+        let req = unsafe { self.shared.raw.get_buffer_memory_requirements(raw) };
+        let bound = match heap.memory_offset(offset, &req) {
+            Some(memory_offset) => unsafe {
+                self.shared
+                    .raw
+                    .bind_buffer_memory(raw, *heap.block.memory(), memory_offset)
+                    .map_err(crate::DeviceError::from)
+            },
+            None => Err(crate::DeviceError::ResourceCreationFailed),
         };
+        if let Err(err) = bound {
+            unsafe { self.shared.raw.destroy_buffer(raw, None) };
+            return Err(err);
+        }
 
         if let Some(label) = desc.label {
             unsafe {
                 self.shared
-                    .set_object_name(vk::ObjectType::IMAGE, raw, label)
+                    .set_object_name(vk::ObjectType::BUFFER, raw, label)
             };
         }
 
-        Ok(super::Texture {
+        Ok(super::Buffer {
             raw,
-            drop_guard: None,
-            block: Some(block),
-            usage: desc.usage,
-            format: desc.format,
-            raw_flags,
-            copy_size,
-            view_formats: wgt_view_formats,
+            block: None,
+            sparse: None,
+            external_memory: None,
         })
     }
-    unsafe fn destroy_texture(&self, texture: super::Texture) {
-        if texture.drop_guard.is_none() {
+    unsafe fn create_placed_texture(
+        &self,
+        desc: &crate::TextureDescriptor,
+        heap: &super::MemoryHeap,
+        offset: wgt::BufferAddress,
+    ) -> Result<super::Texture, crate::DeviceError> {
+        let texture = unsafe { self.create_raw_image(desc, None)? };
+        let req = unsafe { self.shared.raw.get_image_memory_requirements(texture.raw) };
+        let bound = match heap.memory_offset(offset, &req) {
+            Some(memory_offset) => unsafe {
+                self.shared
+                    .raw
+                    .bind_image_memory(texture.raw, *heap.block.memory(), memory_offset)
+                    .map_err(crate::DeviceError::from)
+            },
+            None => Err(crate::DeviceError::ResourceCreationFailed),
+        };
+        if let Err(err) = bound {
             unsafe { self.shared.raw.destroy_image(texture.raw, None) };
+            return Err(err);
         }
-        if let Some(block) = texture.block {
-            unsafe { self.mem_allocator.lock().dealloc(&*self.shared, block) };
+
+        if let Some(label) = desc.label {
+            unsafe {
+                self.shared
+                    .set_object_name(vk::ObjectType::IMAGE, texture.raw, label)
+            };
         }
+
+        Ok(texture)
     }
 
     unsafe fn create_texture_view(
@@ -1111,6 +2259,7 @@ impl crate::Device for super::Device {
             .image(texture.raw)
             .view_type(conv::map_view_dimension(desc.dimension))
             .format(self.shared.private_caps.map_texture_format(desc.format))
+            .components(conv::map_swizzle(desc.swizzle))
             .subresource_range(subresource_range);
         let layers =
             NonZeroU32::new(subresource_range.layer_count).expect("Unexpected zero layer count");
@@ -1126,9 +2275,22 @@ impl crate::Device for super::Device {
             texture.usage
         };
 
-        let raw = unsafe { self.shared.raw.create_image_view(&vk_info, None) }?;
+        // Views of all the planes of a multi-planar texture are created by the bind
+        // groups they're bound to, with the Y'CbCr conversion of their layout.
+        let planar = desc.format.is_multi_planar_format().then_some((
+            texture.raw,
+            vk::ImageSubresourceRange {
+                aspect_mask: vk::ImageAspectFlags::COLOR,
+                ..subresource_range
+            },
+        ));
+        let raw = if planar.is_some() {
+            vk::ImageView::null()
+        } else {
+            unsafe { self.shared.raw.create_image_view(&vk_info, None) }?
+        };
 
-        if let Some(label) = desc.label {
+        if let Some(label) = desc.label.filter(|_| planar.is_none()) {
             unsafe {
                 self.shared
                     .set_object_name(vk::ObjectType::IMAGE_VIEW, raw, label)
@@ -1155,6 +2317,7 @@ impl crate::Device for super::Device {
             raw,
             layers,
             attachment,
+            planar,
         })
     }
     unsafe fn destroy_texture_view(&self, view: super::TextureView) {
@@ -1183,7 +2346,11 @@ impl crate::Device for super::Device {
             .address_mode_v(conv::map_address_mode(desc.address_modes[1]))
             .address_mode_w(conv::map_address_mode(desc.address_modes[2]))
             .min_lod(desc.lod_clamp.start)
-            .max_lod(desc.lod_clamp.end);
+            .max_lod(desc.lod_clamp.end)
+            .mip_lod_bias(desc.lod_bias.clamp(
+                -self.shared.private_caps.max_sampler_lod_bias,
+                self.shared.private_caps.max_sampler_lod_bias,
+            ));
 
         if let Some(fun) = desc.compare {
             vk_info = vk_info
@@ -1203,6 +2370,21 @@ impl crate::Device for super::Device {
             vk_info = vk_info.border_color(conv::map_border_color(color));
         }
 
+        let mut vk_reduction_info;
+        if desc.reduction_mode != wgt::SamplerReductionMode::WeightedAverage {
+            vk_reduction_info = vk::SamplerReductionModeCreateInfo::builder()
+                .reduction_mode(conv::map_reduction_mode(desc.reduction_mode));
+            vk_info = vk_info.push_next(&mut vk_reduction_info);
+        }
+
+        let mut vk_border_color_info;
+        if let Some(wgt::SamplerBorderColor::Custom(color)) = desc.border_color {
+            vk_border_color_info = vk::SamplerCustomBorderColorCreateInfoEXT::builder()
+                .custom_border_color(vk::ClearColorValue { float32: color })
+                .format(vk::Format::UNDEFINED);
+            vk_info = vk_info.push_next(&mut vk_border_color_info);
+        }
+
         let raw = unsafe { self.shared.raw.create_sampler(&vk_info, None)? };
 
         if let Some(label) = desc.label {
@@ -1238,6 +2420,7 @@ impl crate::Device for super::Device {
             discarded: Vec::new(),
             rpass_debug_marker_active: false,
             end_of_pass_timer_query: None,
+            transfer_only: Some(desc.queue.family_index) == self.shared.transfer_family_index,
         })
     }
     unsafe fn destroy_command_encoder(&self, cmd_encoder: super::CommandEncoder) {
@@ -1256,6 +2439,7 @@ impl crate::Device for super::Device {
     ) -> Result<super::BindGroupLayout, crate::DeviceError> {
         let mut desc_count = gpu_descriptor::DescriptorTotalCount::default();
         let mut types = Vec::new();
+        let mut ycbcr_samplers = Vec::new();
         for entry in desc.entries {
             let count = entry.count.map_or(1, |c| c.get());
             if entry.binding as usize >= types.len() {
@@ -1302,6 +2486,19 @@ impl crate::Device for super::Device {
                 wgt::BindingType::AccelerationStructure => {
                     desc_count.acceleration_structure += count;
                 }
+                wgt::BindingType::YcbcrTexture { format, conversion } => {
+                    // Implementations may take a descriptor for every plane.
+                    desc_count.combined_image_sampler += count * format.planes().unwrap_or(1);
+                    match unsafe { self.create_ycbcr_sampler(entry.binding, format, conversion) } {
+                        Ok(ycbcr_sampler) => ycbcr_samplers.push(ycbcr_sampler),
+                        Err(err) => {
+                            for ycbcr_sampler in ycbcr_samplers {
+                                unsafe { self.destroy_ycbcr_sampler(ycbcr_sampler) };
+                            }
+                            return Err(err);
+                        }
+                    }
+                }
             }
         }
 
@@ -1314,11 +2511,17 @@ impl crate::Device for super::Device {
                 descriptor_type: types[entry.binding as usize].0,
                 descriptor_count: types[entry.binding as usize].1,
                 stage_flags: conv::map_shader_stage(entry.visibility),
-                p_immutable_samplers: ptr::null(),
+                p_immutable_samplers: ycbcr_samplers
+                    .iter()
+                    .find(|ycbcr_sampler| ycbcr_sampler.binding == entry.binding)
+                    .map_or(ptr::null(), |ycbcr_sampler| &ycbcr_sampler.sampler),
             })
             .collect::<Vec<_>>();
 
-        let vk_info = vk::DescriptorSetLayoutCreateInfo::builder().bindings(&vk_bindings);
+        let mut vk_info = vk::DescriptorSetLayoutCreateInfo::builder().bindings(&vk_bindings);
+        if desc.flags.contains(crate::BindGroupLayoutFlags::INLINE) {
+            vk_info = vk_info.flags(vk::DescriptorSetLayoutCreateFlags::PUSH_DESCRIPTOR_KHR);
+        }
 
         let binding_arrays = desc
             .entries
@@ -1357,10 +2560,14 @@ impl crate::Device for super::Device {
             vk_info
         };
 
-        let raw = unsafe {
-            self.shared
-                .raw
-                .create_descriptor_set_layout(&vk_info, None)?
+        let raw = match unsafe { self.shared.raw.create_descriptor_set_layout(&vk_info, None) } {
+            Ok(raw) => raw,
+            Err(err) => {
+                for ycbcr_sampler in ycbcr_samplers {
+                    unsafe { self.destroy_ycbcr_sampler(ycbcr_sampler) };
+                }
+                return Err(err.into());
+            }
         };
 
         if let Some(label) = desc.label {
@@ -1375,6 +2582,7 @@ impl crate::Device for super::Device {
             desc_count,
             types: types.into_boxed_slice(),
             binding_arrays,
+            ycbcr_samplers,
         })
     }
     unsafe fn destroy_bind_group_layout(&self, bg_layout: super::BindGroupLayout) {
@@ -1383,6 +2591,9 @@ impl crate::Device for super::Device {
                 .raw
                 .destroy_descriptor_set_layout(bg_layout.raw, None)
         };
+        for ycbcr_sampler in bg_layout.ycbcr_samplers {
+            unsafe { self.destroy_ycbcr_sampler(ycbcr_sampler) };
+        }
     }
 
     unsafe fn create_pipeline_layout(
@@ -1432,9 +2643,19 @@ impl crate::Device for super::Device {
                     },
                     naga::back::spv::BindingInfo {
                         binding_array_size: Some(binding_array_size.get()),
+                        combined_image_sampler: false,
                     },
                 );
             }
+            for ycbcr_sampler in layout.ycbcr_samplers.iter() {
+                binding_arrays
+                    .entry(naga::ResourceBinding {
+                        group: group as u32,
+                        binding: ycbcr_sampler.binding,
+                    })
+                    .or_default()
+                    .combined_image_sampler = true;
+            }
         }
 
         Ok(super::PipelineLayout {
@@ -1454,17 +2675,7 @@ impl crate::Device for super::Device {
         &self,
         desc: &crate::BindGroupDescriptor<super::Api>,
     ) -> Result<super::BindGroup, crate::DeviceError> {
-        let mut vk_sets = unsafe {
-            self.desc_allocator.lock().allocate(
-                &*self.shared,
-                &desc.layout.raw,
-                gpu_descriptor::DescriptorSetLayoutCreateFlags::empty(),
-                &desc.layout.desc_count,
-                1,
-            )?
-        };
-
-        let set = vk_sets.pop().unwrap();
+        let (arena, set) = unsafe { self.desc_arenas.allocate(&self.shared, desc.layout)? };
         if let Some(label) = desc.label {
             unsafe {
                 self.shared
@@ -1480,6 +2691,7 @@ impl crate::Device for super::Device {
             Vec::with_capacity(desc.acceleration_structures.len());
         let mut raw_acceleration_structures =
             Vec::with_capacity(desc.acceleration_structures.len());
+        let mut ycbcr_views = Vec::new();
         for entry in desc.entries {
             let (ty, size) = desc.layout.types[entry.binding as usize];
             if size == 0 {
@@ -1524,6 +2736,60 @@ impl crate::Device for super::Device {
                     ));
                     write.image_info(&image_infos[index..])
                 }
+                vk::DescriptorType::COMBINED_IMAGE_SAMPLER => {
+                    let ycbcr_sampler = desc
+                        .layout
+                        .ycbcr_samplers
+                        .iter()
+                        .find(|ycbcr_sampler| ycbcr_sampler.binding == entry.binding)
+                        .unwrap();
+                    let index = image_infos.len();
+                    let start = entry.resource_index;
+                    let end = start + entry.count;
+                    for binding in desc.textures[start as usize..end as usize].iter() {
+                        let (image, subresource_range) = binding
+                            .view
+                            .planar
+                            .expect("Y'CbCr texture views view all the planes of a texture");
+                        let mut conversion_info = vk::SamplerYcbcrConversionInfo::builder()
+                            .conversion(ycbcr_sampler.conversion);
+                        let vk_info = vk::ImageViewCreateInfo::builder()
+                            .image(image)
+                            .view_type(vk::ImageViewType::TYPE_2D)
+                            .format(
+                                self.shared
+                                    .private_caps
+                                    .map_texture_format(binding.view.attachment.view_format),
+                            )
+                            .subresource_range(subresource_range)
+                            .push_next(&mut conversion_info);
+                        let raw = match unsafe { self.shared.raw.create_image_view(&vk_info, None) }
+                        {
+                            Ok(raw) => raw,
+                            Err(err) => {
+                                for raw in ycbcr_views {
+                                    unsafe { self.shared.raw.destroy_image_view(raw, None) };
+                                }
+                                self.desc_arenas.free(arena, set);
+                                return Err(err.into());
+                            }
+                        };
+                        ycbcr_views.push(raw);
+
+                        let layout = conv::derive_image_layout(
+                            binding.usage,
+                            binding.view.attachment.view_format,
+                        );
+                        image_infos.push(
+                            vk::DescriptorImageInfo::builder()
+                                .sampler(ycbcr_sampler.sampler)
+                                .image_view(raw)
+                                .image_layout(layout)
+                                .build(),
+                        );
+                    }
+                    write.image_info(&image_infos[index..])
+                }
                 vk::DescriptorType::UNIFORM_BUFFER
                 | vk::DescriptorType::UNIFORM_BUFFER_DYNAMIC
                 | vk::DescriptorType::STORAGE_BUFFER
@@ -1583,14 +2849,20 @@ impl crate::Device for super::Device {
         }
 
         unsafe { self.shared.raw.update_descriptor_sets(&writes, &[]) };
-        Ok(super::BindGroup { set })
+        Ok(super::BindGroup {
+            set,
+            arena,
+            ycbcr_views,
+        })
     }
     unsafe fn destroy_bind_group(&self, group: super::BindGroup) {
-        unsafe {
-            self.desc_allocator
-                .lock()
-                .free(&*self.shared, Some(group.set))
-        };
+        for raw in group.ycbcr_views {
+            unsafe { self.shared.raw.destroy_image_view(raw, None) };
+        }
+        self.desc_arenas.free(group.arena, group.set);
+    }
+    unsafe fn recycle_bind_groups(&self) {
+        unsafe { self.desc_arenas.recycle(&self.shared) };
     }
 
     unsafe fn create_shader_module(
@@ -1600,11 +2872,17 @@ impl crate::Device for super::Device {
     ) -> Result<super::ShaderModule, crate::ShaderError> {
         let spv = match shader {
             crate::ShaderInput::Naga(naga_shader) => {
+                // Which images are combined with Y'CbCr conversion samplers is
+                // only known from the pipeline layout.
                 if self
                     .shared
                     .workarounds
                     .contains(super::Workarounds::SEPARATE_ENTRY_POINTS)
                     || !naga_shader.module.overrides.is_empty()
+                    || self
+                        .shared
+                        .features
+                        .contains(wgt::Features::SAMPLER_YCBCR_CONVERSION)
                 {
                     return Ok(super::ShaderModule::Intermediate {
                         naga_shader,
@@ -1666,12 +2944,32 @@ impl crate::Device for super::Device {
         &self,
         desc: &crate::RenderPipelineDescriptor<super::Api>,
     ) -> Result<super::RenderPipeline, crate::PipelineError> {
-        let dynamic_states = [
+        let mut dynamic_states = ArrayVec::<_, 8>::from_iter([
             vk::DynamicState::VIEWPORT,
             vk::DynamicState::SCISSOR,
             vk::DynamicState::BLEND_CONSTANTS,
             vk::DynamicState::STENCIL_REFERENCE,
-        ];
+        ]);
+        // Passes can override these until the next pipeline is bound,
+        // see `CommandEncoder::set_render_pipeline`.
+        let has_dynamic_raster_state = self
+            .shared
+            .features
+            .contains(wgt::Features::EXTENDED_DYNAMIC_STATE);
+        if has_dynamic_raster_state {
+            dynamic_states.extend([
+                vk::DynamicState::FRONT_FACE_EXT,
+                vk::DynamicState::CULL_MODE_EXT,
+                vk::DynamicState::DEPTH_COMPARE_OP_EXT,
+            ]);
+        }
+        let has_dynamic_stencil_write_mask = self
+            .shared
+            .features
+            .contains(wgt::Features::SEPARATE_STENCIL_STATE);
+        if has_dynamic_stencil_write_mask {
+            dynamic_states.push(vk::DynamicState::STENCIL_WRITE_MASK);
+        }
         let mut compatible_rp_key = super::RenderPassKey {
             sample_count: desc.multisample.count,
             multiview: desc.multiview,
@@ -1758,7 +3056,9 @@ impl crate::Device for super::Device {
                 stencil_ops: crate::AttachmentOps::all(),
             });
 
-            if ds.is_depth_enabled() {
+            // With a dynamic compare function, the depth test has to be enabled for it
+            // to take effect. `Always` without writes is the same as no depth test.
+            if ds.is_depth_enabled() || (has_dynamic_raster_state && ds.format.has_depth_aspect()) {
                 vk_depth_stencil = vk_depth_stencil
                     .depth_test_enable(true)
                     .depth_write_enable(ds.depth_write_enabled)
@@ -1783,10 +3083,16 @@ impl crate::Device for super::Device {
             }
         }
 
+        // All viewports are dynamic state, see `begin_render_pass`.
+        let viewport_count = if self.shared.features.contains(wgt::Features::MULTI_VIEWPORT) {
+            wgt::MAX_VIEWPORTS
+        } else {
+            1
+        };
         let vk_viewport = vk::PipelineViewportStateCreateInfo::builder()
             .flags(vk::PipelineViewportStateCreateFlags::empty())
-            .scissor_count(1)
-            .viewport_count(1)
+            .scissor_count(viewport_count)
+            .viewport_count(viewport_count)
             .build();
 
         let vk_sample_mask = [
@@ -1894,7 +3200,30 @@ impl crate::Device for super::Device {
             unsafe { self.shared.raw.destroy_shader_module(raw_module, None) };
         }
 
-        Ok(super::RenderPipeline { raw })
+        let dynamic_raster_state = if has_dynamic_raster_state {
+            Some(super::DynamicRasterState {
+                front_face: vk_rasterization.front_face,
+                cull_mode: vk_rasterization.cull_mode,
+                depth_compare: vk_depth_stencil.depth_compare_op,
+            })
+        } else {
+            None
+        };
+
+        let stencil_write_masks = if has_dynamic_stencil_write_mask {
+            Some([
+                vk_depth_stencil.front.write_mask,
+                vk_depth_stencil.back.write_mask,
+            ])
+        } else {
+            None
+        };
+
+        Ok(super::RenderPipeline {
+            raw,
+            dynamic_raster_state,
+            stencil_write_masks,
+        })
     }
     unsafe fn destroy_render_pipeline(&self, pipeline: super::RenderPipeline) {
         unsafe { self.shared.raw.destroy_pipeline(pipeline.raw, None) };
@@ -2036,6 +3365,33 @@ impl crate::Device for super::Device {
         let timeout_ns = timeout_ms as u64 * super::MILLIS_TO_NANOS;
         self.shared.wait_for_fence(fence, wait_value, timeout_ns)
     }
+    unsafe fn signal_fence(
+        &self,
+        fence: &mut super::Fence,
+        value: crate::FenceValue,
+    ) -> Result<(), crate::DeviceError> {
+        match *fence {
+            super::Fence::TimelineSemaphore(raw) => {
+                let vk_info = vk::SemaphoreSignalInfo::builder()
+                    .semaphore(raw)
+                    .value(value);
+                match self.shared.extension_fns.timeline_semaphore {
+                    Some(super::ExtensionFn::Extension(ref ext)) => unsafe {
+                        ext.signal_semaphore(&vk_info)
+                    },
+                    Some(super::ExtensionFn::Promoted) => unsafe {
+                        self.shared.raw.signal_semaphore(&vk_info)
+                    },
+                    None => unreachable!(),
+                }?;
+            }
+            super::Fence::FencePool {
+                ref mut last_completed,
+                ..
+            } => *last_completed = value,
+        }
+        Ok(())
+    }
 
     unsafe fn start_capture(&self) -> bool {
         #[cfg(feature = "renderdoc")]
@@ -2067,6 +3423,10 @@ impl crate::Device for super::Device {
         }
     }
 
+    unsafe fn set_performance_hint(&self, _hint: wgt::PerformanceHint) {
+        // Vulkan has no power hints of its own.
+    }
+
     unsafe fn get_acceleration_structure_build_sizes<'a>(
         &self,
         desc: &crate::GetAccelerationStructureBuildSizesDescriptor<'a, super::Api>,
@@ -2294,6 +3654,13 @@ impl crate::Device for super::Device {
 }
 
 impl super::DeviceShared {
+    /// Queue families that buffers and images are used from, if there are
+    /// several of them.
+    fn sharing_families(&self) -> Option<[u32; 2]> {
+        self.transfer_family_index
+            .map(|transfer| [self.family_index, transfer])
+    }
+
     pub(super) fn new_binary_semaphore(&self) -> Result<vk::Semaphore, crate::DeviceError> {
         unsafe {
             self.raw
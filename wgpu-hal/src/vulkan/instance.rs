@@ -273,6 +273,15 @@ impl super::Instance {
             // VK_KHR_wayland_surface
             extensions.push(khr::WaylandSurface::name());
         }
+        if cfg!(target_os = "linux") {
+            // VK_KHR_display
+            extensions.push(khr::Display::name());
+            // VK_EXT_direct_mode_display
+            extensions.push(vk::ExtDirectModeDisplayFn::name());
+            // VK_EXT_acquire_drm_display
+            // Lets a display be driven through a DRM master without a windowing system
+            extensions.push(vk::ExtAcquireDrmDisplayFn::name());
+        }
         if cfg!(target_os = "android") {
             // VK_KHR_android_surface
             extensions.push(khr::AndroidSurface::name());
@@ -280,6 +289,9 @@ impl super::Instance {
         if cfg!(target_os = "windows") {
             // VK_KHR_win32_surface
             extensions.push(khr::Win32Surface::name());
+            // VK_KHR_get_surface_capabilities2
+            // Needed by VK_EXT_full_screen_exclusive
+            extensions.push(vk::KhrGetSurfaceCapabilities2Fn::name());
         }
         if cfg!(target_os = "macos") {
             // VK_EXT_metal_surface
@@ -292,6 +304,10 @@ impl super::Instance {
             extensions.push(ext::DebugUtils::name());
         }
 
+        // VK_EXT_headless_surface
+        // Lets surface capabilities be queried before a window exists
+        extensions.push(ext::HeadlessSurface::name());
+
         // VK_EXT_swapchain_colorspace
         // Provides wide color gamut
         extensions.push(vk::ExtSwapchainColorspaceFn::name());
@@ -525,7 +541,9 @@ impl super::Instance {
             }
         };
 
-        Ok(self.create_surface_from_vk_surface_khr(surface))
+        let mut surface = self.create_surface_from_vk_surface_khr(surface);
+        surface.hwnd = std::num::NonZeroIsize::new(hwnd as isize);
+        Ok(surface)
     }
 
     #[cfg(metal)]
@@ -556,6 +574,175 @@ impl super::Instance {
         Ok(self.create_surface_from_vk_surface_khr(surface))
     }
 
+    /// Creates a surface presenting directly to the display connected to the
+    /// DRM connector `connector_id` of the DRM device `fd`, without a windowing
+    /// system, through `VK_KHR_display`.
+    ///
+    /// The display is acquired from DRM and driven in `mode` on the display
+    /// plane `plane`.
+    ///
+    /// # Safety
+    ///
+    /// - `fd` must be a file descriptor of a DRM device, with DRM master
+    ///   rights, that stays open as long as the surface exists.
+    #[cfg(target_os = "linux")]
+    pub unsafe fn create_surface_from_drm(
+        &self,
+        fd: i32,
+        plane: u32,
+        connector_id: u32,
+        mode: wgt::DisplayMode,
+    ) -> Result<super::Surface, crate::InstanceError> {
+        for name in [khr::Display::name(), vk::ExtAcquireDrmDisplayFn::name()] {
+            if !self.shared.extensions.contains(&name) {
+                return Err(crate::InstanceError::new(format!(
+                    "Vulkan driver does not support {}",
+                    name.to_string_lossy()
+                )));
+            }
+        }
+        let get_device_properties = self
+            .shared
+            .get_physical_device_properties
+            .as_ref()
+            .ok_or_else(|| {
+                crate::InstanceError::new(String::from(
+                    "Vulkan driver does not support VK_KHR_get_physical_device_properties2",
+                ))
+            })?;
+
+        let drm_stat = {
+            let mut stat = std::mem::MaybeUninit::<libc::stat>::uninit();
+            if unsafe { libc::fstat(fd, stat.as_mut_ptr()) } != 0 {
+                return Err(crate::InstanceError::new(format!(
+                    "Unable to stat the DRM device: {}",
+                    std::io::Error::last_os_error()
+                )));
+            }
+            unsafe { stat.assume_init() }
+        };
+        let (drm_major, drm_minor) = (
+            libc::major(drm_stat.st_rdev) as i64,
+            libc::minor(drm_stat.st_rdev) as i64,
+        );
+
+        // Find the physical device the DRM device belongs to, through its
+        // primary or render node.
+        let raw_devices = unsafe { self.shared.raw.enumerate_physical_devices() }.map_err(|e| {
+            crate::InstanceError::with_source(
+                String::from("enumerate_physical_devices() failed"),
+                e,
+            )
+        })?;
+        let physical_device = raw_devices
+            .into_iter()
+            .find(|&phd| {
+                let mut drm_properties = vk::PhysicalDeviceDrmPropertiesEXT::default();
+                let mut properties2 =
+                    vk::PhysicalDeviceProperties2KHR::builder().push_next(&mut drm_properties);
+                unsafe {
+                    get_device_properties.get_physical_device_properties2(phd, &mut properties2)
+                };
+                (drm_properties.has_primary == vk::TRUE
+                    && (drm_properties.primary_major, drm_properties.primary_minor)
+                        == (drm_major, drm_minor))
+                    || (drm_properties.has_render == vk::TRUE
+                        && (drm_properties.render_major, drm_properties.render_minor)
+                            == (drm_major, drm_minor))
+            })
+            .ok_or_else(|| {
+                crate::InstanceError::new(String::from("No physical device matches the DRM device"))
+            })?;
+
+        let acquire_drm_display = vk::ExtAcquireDrmDisplayFn::load(|name| unsafe {
+            std::mem::transmute(
+                self.shared
+                    .entry
+                    .get_instance_proc_addr(self.shared.raw.handle(), name.as_ptr()),
+            )
+        });
+        let display_loader = khr::Display::new(&self.shared.entry, &self.shared.raw);
+
+        let mut display = vk::DisplayKHR::null();
+        unsafe {
+            (acquire_drm_display.get_drm_display_ext)(
+                physical_device,
+                fd,
+                connector_id,
+                &mut display,
+            )
+        }
+        .result()
+        .map_err(|e| {
+            crate::InstanceError::with_source(
+                format!("Unable to get the display of DRM connector {connector_id}"),
+                e,
+            )
+        })?;
+
+        let modes = unsafe { display_loader.get_display_mode_properties(physical_device, display) }
+            .map_err(|e| {
+                crate::InstanceError::with_source(
+                    String::from("get_display_mode_properties() failed"),
+                    e,
+                )
+            })?;
+        let display_mode = modes
+            .iter()
+            .find(|properties| {
+                let parameters = properties.parameters;
+                parameters.visible_region.width == mode.width
+                    && parameters.visible_region.height == mode.height
+                    && parameters.refresh_rate == mode.refresh_rate_millihertz
+            })
+            .ok_or_else(|| {
+                crate::InstanceError::new(format!(
+                    "The display of DRM connector {connector_id} doesn't support {mode:?}"
+                ))
+            })?;
+
+        let supported_displays =
+            unsafe { display_loader.get_display_plane_supported_displays(physical_device, plane) }
+                .map_err(|e| {
+                    crate::InstanceError::with_source(
+                        format!("Unable to query the displays of plane {plane}"),
+                        e,
+                    )
+                })?;
+        if !supported_displays.contains(&display) {
+            return Err(crate::InstanceError::new(format!(
+                "Plane {plane} can't show the display of DRM connector {connector_id}"
+            )));
+        }
+
+        unsafe { (acquire_drm_display.acquire_drm_display_ext)(physical_device, fd, display) }
+            .result()
+            .map_err(|e| {
+                crate::InstanceError::with_source(
+                    format!("Unable to acquire the display of DRM connector {connector_id}"),
+                    e,
+                )
+            })?;
+
+        let surface = {
+            profiling::scope!("vkCreateDisplayPlaneSurfaceKHR");
+            let info = vk::DisplaySurfaceCreateInfoKHR::builder()
+                .display_mode(display_mode.display_mode)
+                .plane_index(plane)
+                .transform(vk::SurfaceTransformFlagsKHR::IDENTITY)
+                .alpha_mode(vk::DisplayPlaneAlphaFlagsKHR::OPAQUE)
+                .image_extent(display_mode.parameters.visible_region);
+            unsafe { display_loader.create_display_plane_surface(&info, None) }.map_err(|e| {
+                crate::InstanceError::with_source(
+                    String::from("create_display_plane_surface() failed"),
+                    e,
+                )
+            })?
+        };
+
+        Ok(self.create_surface_from_vk_surface_khr(surface))
+    }
+
     fn create_surface_from_vk_surface_khr(&self, surface: vk::SurfaceKHR) -> super::Surface {
         let functor = khr::Surface::new(&self.shared.entry, &self.shared.raw);
         super::Surface {
@@ -563,6 +750,7 @@ impl super::Instance {
             functor,
             instance: Arc::clone(&self.shared),
             swapchain: RwLock::new(None),
+            hwnd: None,
         }
     }
 }
@@ -1060,12 +1248,16 @@ impl crate::Surface for super::Surface {
             Err(error) => {
                 return match error {
                     vk::Result::TIMEOUT => Ok(None),
-                    vk::Result::NOT_READY | vk::Result::ERROR_OUT_OF_DATE_KHR => {
+                    // Exclusive fullscreen is lost with the focus, and
+                    // reacquired by reconfiguring.
+                    vk::Result::NOT_READY
+                    | vk::Result::ERROR_OUT_OF_DATE_KHR
+                    | vk::Result::ERROR_FULL_SCREEN_EXCLUSIVE_MODE_LOST_EXT => {
                         Err(crate::SurfaceError::Outdated)
                     }
                     vk::Result::ERROR_SURFACE_LOST_KHR => Err(crate::SurfaceError::Lost),
                     other => Err(crate::DeviceError::from(other).into()),
-                }
+                };
             }
         };
 
@@ -1104,6 +1296,9 @@ impl crate::Surface for super::Surface {
                     depth: 1,
                 },
                 view_formats: swapchain.view_formats.clone(),
+                sparse: None,
+                lazy_memory: None,
+                external_memory: None,
             },
             surface_semaphores: swapchain_semaphores_arc,
         };
@@ -1114,4 +1309,192 @@ impl crate::Surface for super::Surface {
     }
 
     unsafe fn discard_texture(&self, _texture: super::SurfaceTexture) {}
+
+    unsafe fn set_hdr_metadata(
+        &self,
+        metadata: &wgt::HdrMetadata,
+    ) -> Result<(), crate::SurfaceError> {
+        let swapchain = self.swapchain.read();
+        let swapchain = swapchain.as_ref().unwrap();
+        let fns = swapchain.device.extension_fns.hdr_metadata.as_ref().ok_or(
+            crate::SurfaceError::Other("VK_EXT_hdr_metadata is not supported"),
+        )?;
+
+        let xy = |[x, y]: [f32; 2]| vk::XYColorEXT { x, y };
+        let raw_metadata = vk::HdrMetadataEXT::builder()
+            .display_primary_red(xy(metadata.red_primary))
+            .display_primary_green(xy(metadata.green_primary))
+            .display_primary_blue(xy(metadata.blue_primary))
+            .white_point(xy(metadata.white_point))
+            .max_luminance(metadata.max_luminance)
+            .min_luminance(metadata.min_luminance)
+            .max_content_light_level(metadata.max_content_light_level)
+            .max_frame_average_light_level(metadata.max_frame_average_light_level);
+        unsafe {
+            (fns.set_hdr_metadata_ext)(
+                swapchain.device.raw.handle(),
+                1,
+                &swapchain.raw,
+                &*raw_metadata,
+            )
+        };
+        Ok(())
+    }
+
+    unsafe fn set_next_present_time(&self, time: wgt::PresentationTimestamp) -> bool {
+        let mut swapchain = self.swapchain.write();
+        let swapchain = swapchain.as_mut().unwrap();
+        if swapchain.device.extension_fns.display_timing.is_none() {
+            return false;
+        }
+        swapchain.next_present_time = Some(time.0 as u64);
+        true
+    }
+
+    unsafe fn set_next_present_damage(&self, damage: &[wgt::DamageRect]) -> bool {
+        let mut swapchain = self.swapchain.write();
+        let swapchain = swapchain.as_mut().unwrap();
+        if !swapchain
+            .device
+            .enabled_extensions
+            .contains(&vk::KhrIncrementalPresentFn::name())
+        {
+            return false;
+        }
+        swapchain.next_damage = damage
+            .iter()
+            .map(|rect| vk::RectLayerKHR {
+                offset: vk::Offset2D {
+                    x: rect.x as i32,
+                    y: rect.y as i32,
+                },
+                extent: vk::Extent2D {
+                    width: rect.width,
+                    height: rect.height,
+                },
+                layer: 0,
+            })
+            .collect();
+        true
+    }
+
+    unsafe fn presentation_timings(&self) -> Vec<wgt::PresentationTiming> {
+        let swapchain = self.swapchain.read();
+        let swapchain = swapchain.as_ref().unwrap();
+        let Some(fns) = swapchain.device.extension_fns.display_timing.as_ref() else {
+            return Vec::new();
+        };
+
+        let device = swapchain.device.raw.handle();
+        let mut count = 0;
+        let mut timings = Vec::new();
+        let result = unsafe {
+            (fns.get_past_presentation_timing_google)(
+                device,
+                swapchain.raw,
+                &mut count,
+                std::ptr::null_mut(),
+            )
+        }
+        .result()
+        .and_then(|()| {
+            timings.resize(count as usize, vk::PastPresentationTimingGOOGLE::default());
+            unsafe {
+                (fns.get_past_presentation_timing_google)(
+                    device,
+                    swapchain.raw,
+                    &mut count,
+                    timings.as_mut_ptr(),
+                )
+            }
+            .result()
+        });
+        if let Err(err) = result {
+            log::warn!("Unable to get past presentation timings: {err}");
+            return Vec::new();
+        }
+        timings.truncate(count as usize);
+
+        timings
+            .into_iter()
+            .map(|timing| wgt::PresentationTiming {
+                present_id: timing.present_id.into(),
+                desired_present_time: (timing.desired_present_time != 0)
+                    .then(|| wgt::PresentationTimestamp(timing.desired_present_time.into())),
+                actual_present_time: wgt::PresentationTimestamp(timing.actual_present_time.into()),
+            })
+            .collect()
+    }
+
+    unsafe fn refresh_duration(&self) -> Option<std::time::Duration> {
+        let swapchain = self.swapchain.read();
+        let swapchain = swapchain.as_ref().unwrap();
+        let fns = swapchain.device.extension_fns.display_timing.as_ref()?;
+
+        let mut properties = vk::RefreshCycleDurationGOOGLE::default();
+        unsafe {
+            (fns.get_refresh_cycle_duration_google)(
+                swapchain.device.raw.handle(),
+                swapchain.raw,
+                &mut properties,
+            )
+        }
+        .result()
+        .ok()?;
+        Some(std::time::Duration::from_nanos(properties.refresh_duration))
+    }
+
+    unsafe fn image_count(&self) -> u32 {
+        let swapchain = self.swapchain.read();
+        swapchain.as_ref().unwrap().images.len() as u32
+    }
+
+    unsafe fn wait_for_present(
+        &self,
+        timeout: Option<std::time::Duration>,
+    ) -> Result<bool, crate::SurfaceError> {
+        let swapchain = self.swapchain.read();
+        let swapchain = swapchain.as_ref().unwrap();
+        let Some(fns) = swapchain.device.extension_fns.present_wait.as_ref() else {
+            return Ok(true);
+        };
+
+        // Once the next frame is presented, at most `maximum_frame_latency`
+        // frames may be waiting to be shown, so every frame up to this one
+        // has to be shown already.
+        let present_id = match (swapchain.present_count + 1)
+            .checked_sub(swapchain.config.maximum_frame_latency)
+        {
+            Some(present_id) if present_id > 0 => u64::from(present_id),
+            _ => return Ok(true),
+        };
+        let timeout_ns = match timeout {
+            Some(duration) => duration.as_nanos() as u64,
+            None => u64::MAX,
+        };
+
+        profiling::scope!("vkWaitForPresentKHR");
+        match unsafe {
+            (fns.wait_for_present_khr)(
+                swapchain.device.raw.handle(),
+                swapchain.raw,
+                present_id,
+                timeout_ns,
+            )
+        } {
+            vk::Result::SUCCESS | vk::Result::SUBOPTIMAL_KHR => Ok(true),
+            vk::Result::TIMEOUT => Ok(false),
+            vk::Result::ERROR_OUT_OF_DATE_KHR
+            | vk::Result::ERROR_FULL_SCREEN_EXCLUSIVE_MODE_LOST_EXT => {
+                Err(crate::SurfaceError::Outdated)
+            }
+            vk::Result::ERROR_SURFACE_LOST_KHR => Err(crate::SurfaceError::Lost),
+            other => Err(crate::DeviceError::from(other).into()),
+        }
+    }
+
+    unsafe fn set_maximum_frame_latency(&self, maximum_frame_latency: u32) {
+        let mut swapchain = self.swapchain.write();
+        swapchain.as_mut().unwrap().config.maximum_frame_latency = maximum_frame_latency;
+    }
 }
@@ -28,10 +28,18 @@ Otherwise, we manage a pool of `VkFence` objects behind each `hal::Fence`.
 mod adapter;
 mod command;
 mod conv;
+mod descriptor;
 mod device;
 mod instance;
 
-use std::{borrow::Borrow, collections::HashSet, ffi::CStr, fmt, mem, num::NonZeroU32, sync::Arc};
+use std::{
+    borrow::Borrow,
+    collections::{BTreeMap, HashSet},
+    ffi::CStr,
+    fmt, mem,
+    num::NonZeroU32,
+    sync::Arc,
+};
 
 use arrayvec::ArrayVec;
 use ash::{
@@ -42,6 +50,8 @@ use parking_lot::{Mutex, RwLock};
 
 const MILLIS_TO_NANOS: u64 = 1_000_000;
 const MAX_TOTAL_ATTACHMENTS: usize = crate::MAX_COLOR_ATTACHMENTS * 2 + 1;
+/// Alignment of the memory of heaps, enough for any resource placed in them.
+const MEMORY_HEAP_ALIGNMENT: u64 = 1 << 16;
 
 #[derive(Clone, Debug)]
 pub struct Api;
@@ -62,6 +72,7 @@ impl crate::Api for Api {
     type TextureView = TextureView;
     type Sampler = Sampler;
     type QuerySet = QuerySet;
+    type MemoryHeap = MemoryHeap;
     type Fence = Fence;
     type AccelerationStructure = AccelerationStructure;
 
@@ -325,6 +336,13 @@ struct Swapchain {
     /// index as the image index, but we need to specify the semaphore as an argument
     /// to the acquire_next_image function which is what tells us which image to use.
     next_semaphore_index: usize,
+    /// The number of presents to the swapchain, the ID of the last present
+    /// for `VK_GOOGLE_display_timing` and `VK_KHR_present_id`.
+    present_count: u32,
+    /// The desired present time of the next present, in nanoseconds.
+    next_present_time: Option<u64>,
+    /// The regions the next present changes, for `VK_KHR_incremental_present`.
+    next_damage: Vec<vk::RectLayerKHR>,
 }
 
 impl Swapchain {
@@ -343,6 +361,9 @@ pub struct Surface {
     functor: khr::Surface,
     instance: Arc<InstanceShared>,
     swapchain: RwLock<Option<Swapchain>>,
+    /// The window of Win32 surfaces, whose monitor exclusive fullscreen
+    /// takes over.
+    hwnd: Option<std::num::NonZeroIsize>,
 }
 
 #[derive(Debug)]
@@ -381,7 +402,29 @@ enum ExtensionFn<T> {
 struct DeviceExtensionFunctions {
     draw_indirect_count: Option<khr::DrawIndirectCount>,
     timeline_semaphore: Option<ExtensionFn<khr::TimelineSemaphore>>,
+    extended_dynamic_state: Option<ext::ExtendedDynamicState>,
+    /// Loaded if the device can calibrate its timestamps against `CLOCK_MONOTONIC`.
+    calibrated_timestamps: Option<vk::ExtCalibratedTimestampsFn>,
     ray_tracing: Option<RayTracingDeviceExtensionFunctions>,
+    /// Loaded if [`wgt::Features::BUFFER_DEVICE_ADDRESS`] or ray tracing is enabled.
+    buffer_device_address: Option<khr::BufferDeviceAddress>,
+    /// Loaded if [`wgt::Features::INLINE_BIND_GROUPS`] is enabled.
+    push_descriptor: Option<khr::PushDescriptor>,
+    /// Loaded if [`wgt::Features::EXTERNAL_MEMORY_FD`] or
+    /// [`wgt::Features::EXTERNAL_MEMORY_DMA_BUF`] is enabled.
+    external_memory_fd: Option<khr::ExternalMemoryFd>,
+    /// Loaded if [`wgt::Features::EXTERNAL_MEMORY_DMA_BUF`] is enabled.
+    image_drm_format_modifier: Option<vk::ExtImageDrmFormatModifierFn>,
+    /// Loaded if [`wgt::Features::EXTERNAL_SEMAPHORE_FD`] is enabled.
+    external_semaphore_fd: Option<khr::ExternalSemaphoreFd>,
+    /// Loaded if the device supports `VK_EXT_hdr_metadata`.
+    hdr_metadata: Option<vk::ExtHdrMetadataFn>,
+    /// Loaded if the device supports `VK_GOOGLE_display_timing`.
+    display_timing: Option<vk::GoogleDisplayTimingFn>,
+    /// Loaded if the device supports `VK_EXT_full_screen_exclusive`.
+    full_screen_exclusive: Option<ext::FullScreenExclusive>,
+    /// Loaded if [`wgt::Features::PRESENT_WAIT`] is enabled.
+    present_wait: Option<vk::KhrPresentWaitFn>,
 }
 
 struct RayTracingDeviceExtensionFunctions {
@@ -412,6 +455,8 @@ struct PrivateCapabilities {
     robust_image_access2: bool,
     zero_initialize_workgroup_memory: bool,
     image_format_list: bool,
+    /// `maxSamplerLodBias`, which sampler LOD biases are clamped to.
+    max_sampler_lod_bias: f32,
 }
 
 bitflags::bitflags!(
@@ -507,6 +552,11 @@ struct DeviceShared {
     raw: ash::Device,
     family_index: u32,
     queue_index: u32,
+    /// Family of the [`crate::OpenDevice::transfer_queue`], if there is one.
+    ///
+    /// Buffers and images are shared between this family and `family_index`,
+    /// so that they don't need ownership transfers.
+    transfer_family_index: Option<u32>,
     raw_queue: ash::vk::Queue,
     handle_is_owned: bool,
     instance: Arc<InstanceShared>,
@@ -525,9 +575,10 @@ struct DeviceShared {
 pub struct Device {
     shared: Arc<DeviceShared>,
     mem_allocator: Mutex<gpu_alloc::GpuAllocator<vk::DeviceMemory>>,
-    desc_allocator:
-        Mutex<gpu_descriptor::DescriptorAllocator<vk::DescriptorPool, vk::DescriptorSet>>,
+    desc_arenas: descriptor::DescriptorArenas,
     valid_ash_memory_types: u32,
+    /// The memory types that are lazily allocated.
+    lazily_allocated_memory_types: u32,
     naga_options: naga::back::spv::Options<'static>,
     #[cfg(feature = "renderdoc")]
     render_doc: crate::auxil::renderdoc::RenderDoc,
@@ -608,18 +659,413 @@ impl RelaySemaphores {
     }
 }
 
+/// Binary semaphores that Linux sync files are imported into and exported
+/// from, see [`Queue::wait_for_sync_fd`] and [`Queue::export_sync_fd`].
+///
+/// Timeline semaphores can't hold sync files, so each imported sync file gets
+/// a binary semaphore of its own, which the next submission waits on.
+#[derive(Default)]
+struct SyncFileSemaphores {
+    /// Semaphores holding imported sync files, for the next submission to wait on.
+    waits: Vec<vk::Semaphore>,
+    /// Semaphores waited on by submissions that may still be running, with
+    /// the fence each submission signals.
+    retired: Vec<(vk::Fence, Vec<vk::Semaphore>)>,
+    /// The semaphore sync files are exported from. Exporting a sync file
+    /// unsignals it, so it can be signaled again right after.
+    export: Option<vk::Semaphore>,
+}
+
+impl SyncFileSemaphores {
+    /// Destroy the semaphores of submissions that have completed.
+    fn free_completed(&mut self, device: &ash::Device) -> Result<(), crate::DeviceError> {
+        let mut result = Ok(());
+        self.retired.retain(|&(fence, ref semaphores)| {
+            match unsafe { device.get_fence_status(fence) } {
+                Ok(true) => {}
+                Ok(false) => return true,
+                Err(err) => {
+                    result = Err(err.into());
+                    return true;
+                }
+            }
+            unsafe {
+                for &semaphore in semaphores {
+                    device.destroy_semaphore(semaphore, None);
+                }
+                device.destroy_fence(fence, None);
+            }
+            false
+        });
+        result
+    }
+
+    /// Destroys the semaphores.
+    ///
+    /// The device must be idle.
+    unsafe fn destroy(self, device: &ash::Device) {
+        unsafe {
+            for semaphore in self.waits.into_iter().chain(self.export) {
+                device.destroy_semaphore(semaphore, None);
+            }
+            for (fence, semaphores) in self.retired {
+                for semaphore in semaphores {
+                    device.destroy_semaphore(semaphore, None);
+                }
+                device.destroy_fence(fence, None);
+            }
+        }
+    }
+}
+
 pub struct Queue {
     raw: vk::Queue,
     swapchain_fn: khr::Swapchain,
     device: Arc<DeviceShared>,
     family_index: u32,
     relay_semaphores: Mutex<RelaySemaphores>,
+    /// Timeline semaphore values the next submission must wait on, as
+    /// requested by [`crate::Queue::wait_for_fence`].
+    fence_waits: Mutex<Vec<(vk::Semaphore, crate::FenceValue)>>,
+    sync_files: Mutex<SyncFileSemaphores>,
+}
+
+impl Queue {
+    /// Create a handle to another queue of the same family as `self`.
+    ///
+    /// # Safety
+    ///
+    /// - The device must have been created with more than `queue_index`
+    ///   queues in `self`'s family.
+    unsafe fn sibling(&self, queue_index: u32) -> Result<Self, crate::DeviceError> {
+        unsafe { self.in_family(self.family_index, queue_index) }
+    }
+
+    /// Create a handle to a queue of the family `family_index`.
+    ///
+    /// # Safety
+    ///
+    /// - The device must have been created with more than `queue_index`
+    ///   queues in that family.
+    unsafe fn in_family(
+        &self,
+        family_index: u32,
+        queue_index: u32,
+    ) -> Result<Self, crate::DeviceError> {
+        let raw = {
+            profiling::scope!("vkGetDeviceQueue");
+            unsafe { self.device.raw.get_device_queue(family_index, queue_index) }
+        };
+        Ok(Self {
+            raw,
+            swapchain_fn: self.swapchain_fn.clone(),
+            device: Arc::clone(&self.device),
+            family_index,
+            relay_semaphores: Mutex::new(RelaySemaphores::new(&self.device)?),
+            fence_waits: Mutex::new(Vec::new()),
+            sync_files: Mutex::new(SyncFileSemaphores::default()),
+        })
+    }
+
+    /// Makes the next submission to this queue wait until the Linux sync
+    /// file `fd` signals.
+    ///
+    /// # Safety
+    ///
+    /// - [`wgt::Features::EXTERNAL_SEMAPHORE_FD`] must be enabled.
+    /// - `fd` must be a sync file.
+    #[cfg(unix)]
+    pub unsafe fn wait_for_sync_fd(
+        &self,
+        fd: std::os::fd::OwnedFd,
+    ) -> Result<(), crate::DeviceError> {
+        use std::os::fd::{AsRawFd as _, IntoRawFd as _};
+
+        let fns = self
+            .device
+            .extension_fns
+            .external_semaphore_fd
+            .as_ref()
+            .expect("Feature `EXTERNAL_SEMAPHORE_FD` not enabled");
+        let semaphore = self.device.new_binary_semaphore()?;
+        let vk_info = vk::ImportSemaphoreFdInfoKHR::builder()
+            .semaphore(semaphore)
+            .flags(vk::SemaphoreImportFlags::TEMPORARY)
+            .handle_type(vk::ExternalSemaphoreHandleTypeFlags::SYNC_FD)
+            .fd(fd.as_raw_fd());
+        if let Err(err) = unsafe { fns.import_semaphore_fd(&vk_info) } {
+            unsafe { self.device.raw.destroy_semaphore(semaphore, None) };
+            return Err(err.into());
+        }
+        // The driver owns the file descriptor once it is imported.
+        let _ = fd.into_raw_fd();
+
+        self.sync_files.lock().waits.push(semaphore);
+        Ok(())
+    }
+
+    /// Returns a Linux sync file that signals once `fence` reaches `value`.
+    ///
+    /// # Safety
+    ///
+    /// - [`wgt::Features::EXTERNAL_SEMAPHORE_FD`] must be enabled.
+    /// - `fence` must be signaled with `value` eventually, by a submission or
+    ///   the host.
+    #[cfg(unix)]
+    pub unsafe fn export_sync_fd(
+        &self,
+        fence: &Fence,
+        value: crate::FenceValue,
+    ) -> Result<std::os::fd::OwnedFd, crate::DeviceError> {
+        use std::os::fd::FromRawFd as _;
+
+        let fns = self
+            .device
+            .extension_fns
+            .external_semaphore_fd
+            .as_ref()
+            .expect("Feature `EXTERNAL_SEMAPHORE_FD` not enabled");
+        let timeline = match *fence {
+            Fence::TimelineSemaphore(raw) => raw,
+            Fence::FencePool { .. } => {
+                panic!("Feature `EXTERNAL_SEMAPHORE_FD` needs timeline semaphores")
+            }
+        };
+
+        let mut sync_files = self.sync_files.lock();
+        let semaphore = match sync_files.export {
+            Some(semaphore) => semaphore,
+            None => {
+                let mut export_info = vk::ExportSemaphoreCreateInfo::builder()
+                    .handle_types(vk::ExternalSemaphoreHandleTypeFlags::SYNC_FD);
+                let vk_info = vk::SemaphoreCreateInfo::builder().push_next(&mut export_info);
+                let semaphore = unsafe { self.device.raw.create_semaphore(&vk_info, None)? };
+                *sync_files.export.insert(semaphore)
+            }
+        };
+
+        // An empty submission turns the timeline value into a binary signal.
+        let wait_semaphores = [timeline];
+        let wait_values = [value];
+        let wait_stage_masks = [vk::PipelineStageFlags::ALL_COMMANDS];
+        let signal_semaphores = [semaphore];
+        let signal_values = [!0];
+        let mut vk_timeline_info = vk::TimelineSemaphoreSubmitInfo::builder()
+            .wait_semaphore_values(&wait_values)
+            .signal_semaphore_values(&signal_values);
+        let vk_info = vk::SubmitInfo::builder()
+            .wait_semaphores(&wait_semaphores)
+            .wait_dst_stage_mask(&wait_stage_masks)
+            .signal_semaphores(&signal_semaphores)
+            .push_next(&mut vk_timeline_info);
+        unsafe {
+            self.device
+                .raw
+                .queue_submit(self.raw, &[vk_info.build()], vk::Fence::null())?
+        };
+
+        let vk_info = vk::SemaphoreGetFdInfoKHR::builder()
+            .semaphore(semaphore)
+            .handle_type(vk::ExternalSemaphoreHandleTypeFlags::SYNC_FD);
+        let fd = unsafe { fns.get_semaphore_fd(&vk_info)? };
+        Ok(unsafe { std::os::fd::OwnedFd::from_raw_fd(fd) })
+    }
+
+    /// Arrange for `fence` to be signalled with `value` by the next operation
+    /// on this queue.
+    ///
+    /// A timeline semaphore is added to `signal_semaphores`, and a fence of the
+    /// pool is returned, or a null handle if there is none to pass.
+    fn signal_fence(
+        &self,
+        fence: &mut Fence,
+        value: crate::FenceValue,
+        signal_semaphores: &mut Vec<vk::Semaphore>,
+        signal_values: &mut Vec<u64>,
+    ) -> Result<vk::Fence, crate::DeviceError> {
+        fence.maintain(&self.device.raw)?;
+        match *fence {
+            Fence::TimelineSemaphore(raw) => {
+                signal_semaphores.push(raw);
+                signal_values.push(value);
+                Ok(vk::Fence::null())
+            }
+            Fence::FencePool {
+                ref mut active,
+                ref mut free,
+                ..
+            } => {
+                let raw = match free.pop() {
+                    Some(raw) => raw,
+                    None => unsafe {
+                        self.device
+                            .raw
+                            .create_fence(&vk::FenceCreateInfo::default(), None)?
+                    },
+                };
+                active.push((value, raw));
+                Ok(raw)
+            }
+        }
+    }
+    /// Free the memory of decommitted pages or tiles in `released` that the
+    /// device is done with, according to `fence`.
+    unsafe fn free_released_memory(
+        &self,
+        fence: &mut Fence,
+        released: &mut Vec<(crate::FenceValue, gpu_alloc::MemoryBlock<vk::DeviceMemory>)>,
+        allocator: &mut gpu_alloc::GpuAllocator<vk::DeviceMemory>,
+    ) -> Result<(), crate::DeviceError> {
+        let last_done = fence.get_latest(
+            &self.device.raw,
+            self.device.extension_fns.timeline_semaphore.as_ref(),
+        )?;
+        let (done, pending): (Vec<_>, Vec<_>) = mem::take(released)
+            .into_iter()
+            .partition(|&(value, _)| value <= last_done);
+        *released = pending;
+        for (_, block) in done {
+            unsafe { allocator.dealloc(&*self.device, block) };
+        }
+        Ok(())
+    }
+
+    /// Allocate the memory of all `requests`, or none of it if that fails, so
+    /// that nothing changes if the memory runs out.
+    unsafe fn alloc_sparse_memory<K>(
+        &self,
+        allocator: &mut gpu_alloc::GpuAllocator<vk::DeviceMemory>,
+        requests: impl Iterator<Item = (K, gpu_alloc::Request)>,
+    ) -> Result<Vec<(K, gpu_alloc::MemoryBlock<vk::DeviceMemory>)>, crate::DeviceError> {
+        let mut blocks = Vec::new();
+        for (key, request) in requests {
+            match unsafe { allocator.alloc(&*self.device, request) } {
+                Ok(block) => blocks.push((key, block)),
+                Err(error) => {
+                    for (_, block) in blocks {
+                        unsafe { allocator.dealloc(&*self.device, block) };
+                    }
+                    return Err(error.into());
+                }
+            }
+        }
+        Ok(blocks)
+    }
+
+    /// Submit sparse binds, ordered with the submissions to this queue, and
+    /// signal `fence` with `value` once they are done.
+    unsafe fn submit_sparse_binds(
+        &self,
+        (signal_fence, signal_value): (&mut Fence, crate::FenceValue),
+        buffer_binds: &[vk::SparseBufferMemoryBindInfo],
+        image_opaque_binds: &[vk::SparseImageOpaqueMemoryBindInfo],
+        image_binds: &[vk::SparseImageMemoryBindInfo],
+    ) -> Result<(), crate::DeviceError> {
+        // Binds are ordered with the submissions through the same semaphores.
+        let semaphore_state = self.relay_semaphores.lock().advance(&self.device)?;
+        let mut wait_semaphores = Vec::new();
+        let mut wait_values = Vec::new();
+        if let Some(sem) = semaphore_state.wait {
+            wait_semaphores.push(sem);
+            wait_values.push(0);
+        }
+        for (raw, value) in self.fence_waits.lock().drain(..) {
+            wait_semaphores.push(raw);
+            wait_values.push(value);
+        }
+        let mut signal_semaphores = vec![semaphore_state.signal];
+        let mut signal_values = vec![!0];
+        let fence_raw = self.signal_fence(
+            signal_fence,
+            signal_value,
+            &mut signal_semaphores,
+            &mut signal_values,
+        )?;
+
+        let mut vk_info = vk::BindSparseInfo::builder()
+            .wait_semaphores(&wait_semaphores)
+            .buffer_binds(buffer_binds)
+            .image_opaque_binds(image_opaque_binds)
+            .image_binds(image_binds)
+            .signal_semaphores(&signal_semaphores);
+
+        let mut vk_timeline_info;
+
+        if self.device.private_caps.timeline_semaphores {
+            vk_timeline_info = vk::TimelineSemaphoreSubmitInfo::builder()
+                .wait_semaphore_values(&wait_values)
+                .signal_semaphore_values(&signal_values);
+            vk_info = vk_info.push_next(&mut vk_timeline_info);
+        }
+
+        profiling::scope!("vkQueueBindSparse");
+        unsafe {
+            self.device
+                .raw
+                .queue_bind_sparse(self.raw, &[vk_info.build()], fence_raw)?
+        };
+        Ok(())
+    }
+}
+
+#[derive(Debug)]
+pub struct MemoryHeap {
+    block: gpu_alloc::MemoryBlock<vk::DeviceMemory>,
+}
+
+impl MemoryHeap {
+    /// Returns the offset in the memory of `block` of a resource with the
+    /// requirements `req` placed at `offset`, if it can be placed there.
+    fn memory_offset(&self, offset: u64, req: &vk::MemoryRequirements) -> Option<u64> {
+        let fits = offset
+            .checked_add(req.size)
+            .map_or(false, |end| end <= self.block.size());
+        let memory_offset = self.block.offset() + offset;
+        let compatible = req.memory_type_bits & (1 << self.block.memory_type()) != 0;
+        (fits && compatible && memory_offset % req.alignment == 0).then_some(memory_offset)
+    }
 }
 
 #[derive(Debug)]
 pub struct Buffer {
     raw: vk::Buffer,
     block: Option<Mutex<gpu_alloc::MemoryBlock<vk::DeviceMemory>>>,
+    sparse: Option<Mutex<SparseMemory>>,
+    external_memory: Option<ExternalMemory>,
+}
+
+impl Buffer {
+    /// The handle type the memory of the buffer can be exported through, if
+    /// it was created exportable or imported.
+    pub fn external_memory_handle_type(&self) -> Option<wgt::ExternalMemoryHandleType> {
+        self.external_memory
+            .as_ref()
+            .map(|memory| memory.handle_type)
+    }
+}
+
+/// The dedicated memory of a resource that is exported or imported, which is
+/// never shared with other resources, so it isn't allocated with `mem_allocator`.
+#[derive(Debug)]
+struct ExternalMemory {
+    raw: vk::DeviceMemory,
+    handle_type: wgt::ExternalMemoryHandleType,
+    size: wgt::BufferAddress,
+}
+
+/// The memory committed to the pages of a buffer created with
+/// [`crate::MemoryFlags::SPARSE`].
+#[derive(Debug)]
+struct SparseMemory {
+    /// The size of the buffer in memory, which the last page is clipped to.
+    size: wgt::BufferAddress,
+    align_mask: u64,
+    memory_types: u32,
+    /// Committed pages, by their offset.
+    pages: BTreeMap<wgt::BufferAddress, gpu_alloc::MemoryBlock<vk::DeviceMemory>>,
+    /// Memory of decommitted pages, with the fence value after which the
+    /// device doesn't use it anymore.
+    released: Vec<(crate::FenceValue, gpu_alloc::MemoryBlock<vk::DeviceMemory>)>,
 }
 
 #[derive(Debug)]
@@ -639,6 +1085,56 @@ pub struct Texture {
     raw_flags: vk::ImageCreateFlags,
     copy_size: crate::CopyExtent,
     view_formats: Vec<wgt::TextureFormat>,
+    sparse: Option<Mutex<SparseImageMemory>>,
+    /// The lazily allocated memory of a texture created with
+    /// [`crate::MemoryFlags::MEMORYLESS`], which is never shared with other
+    /// resources, so it isn't allocated with `mem_allocator`.
+    lazy_memory: Option<vk::DeviceMemory>,
+    external_memory: Option<ExternalMemory>,
+}
+
+/// A tile of a texture created with [`crate::MemoryFlags::SPARSE`].
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord)]
+enum SparseTile {
+    /// A tile of a mip level before the mip tail, by its position in tiles.
+    Region {
+        mip_level: u32,
+        array_layer: u32,
+        x: u32,
+        y: u32,
+        z: u32,
+    },
+    /// The mip tail of an array layer.
+    MipTail { array_layer: u32 },
+}
+
+/// The memory committed to the tiles of a texture created with
+/// [`crate::MemoryFlags::SPARSE`].
+#[derive(Debug)]
+struct SparseImageMemory {
+    tiles: wgt::SparseTextureTiles,
+    /// Size of the first mip level, which the tiles at the edges are clipped to.
+    size: vk::Extent3D,
+    /// Tiles are bound with one block of this alignment each.
+    align_mask: u64,
+    memory_types: u32,
+    mip_tail_size: u64,
+    mip_tail_offset: u64,
+    mip_tail_stride: u64,
+    committed: BTreeMap<SparseTile, gpu_alloc::MemoryBlock<vk::DeviceMemory>>,
+    /// Memory of decommitted tiles, with the fence value after which the
+    /// device doesn't use it anymore.
+    released: Vec<(crate::FenceValue, gpu_alloc::MemoryBlock<vk::DeviceMemory>)>,
+}
+
+impl SparseImageMemory {
+    fn level_size(&self, mip_level: u32) -> vk::Extent3D {
+        vk::Extent3D {
+            width: (self.size.width >> mip_level).max(1),
+            height: (self.size.height >> mip_level).max(1),
+            depth: (self.size.depth >> mip_level).max(1),
+        }
+    }
 }
 
 impl Texture {
@@ -648,6 +1144,14 @@ impl Texture {
     pub unsafe fn raw_handle(&self) -> vk::Image {
         self.raw
     }
+
+    /// The handle type the memory of the texture can be exported through, if
+    /// it was created exportable or imported.
+    pub fn external_memory_handle_type(&self) -> Option<wgt::ExternalMemoryHandleType> {
+        self.external_memory
+            .as_ref()
+            .map(|memory| memory.handle_type)
+    }
 }
 
 #[derive(Debug)]
@@ -655,6 +1159,10 @@ pub struct TextureView {
     raw: vk::ImageView,
     layers: NonZeroU32,
     attachment: FramebufferAttachment,
+    /// For views of all the planes of a multi-planar texture, the image and
+    /// range that bind groups create views with a Y'CbCr conversion of, as
+    /// such views can't be created without one. `raw` is null for these.
+    planar: Option<(vk::Image, vk::ImageSubresourceRange)>,
 }
 
 impl TextureView {
@@ -678,6 +1186,16 @@ pub struct BindGroupLayout {
     types: Box<[(vk::DescriptorType, u32)]>,
     /// Map of binding index to size,
     binding_arrays: Vec<(u32, NonZeroU32)>,
+    /// The Y'CbCr conversions of `BindingType::YcbcrTexture` bindings,
+    /// with the immutable samplers made for them.
+    ycbcr_samplers: Vec<YcbcrSampler>,
+}
+
+#[derive(Debug)]
+struct YcbcrSampler {
+    binding: u32,
+    conversion: vk::SamplerYcbcrConversion,
+    sampler: vk::Sampler,
 }
 
 #[derive(Debug)]
@@ -688,7 +1206,11 @@ pub struct PipelineLayout {
 
 #[derive(Debug)]
 pub struct BindGroup {
-    set: gpu_descriptor::DescriptorSet<vk::DescriptorSet>,
+    set: descriptor::DescriptorSet,
+    /// Index of the arena `set` was allocated from.
+    arena: usize,
+    /// Views created with the Y'CbCr conversions of the layout.
+    ycbcr_views: Vec<vk::ImageView>,
 }
 
 /// Miscellaneous allocation recycling pool for `CommandAllocator`.
@@ -753,6 +1275,10 @@ pub struct CommandEncoder {
     /// If set, the end of the next render/compute pass will write a timestamp at
     /// the given pool & location.
     end_of_pass_timer_query: Option<(vk::QueryPool, u32)>,
+
+    /// True if this encoder records for the transfer queue, whose barriers
+    /// may only name transfer stages.
+    transfer_only: bool,
 }
 
 impl CommandEncoder {
@@ -790,6 +1316,19 @@ pub enum ShaderModule {
 #[derive(Debug)]
 pub struct RenderPipeline {
     raw: vk::Pipeline,
+    /// Raster state that is dynamic with [`wgt::Features::EXTENDED_DYNAMIC_STATE`],
+    /// which has to be set again whenever the pipeline is bound.
+    dynamic_raster_state: Option<DynamicRasterState>,
+    /// Front and back stencil write masks, which are dynamic with
+    /// [`wgt::Features::SEPARATE_STENCIL_STATE`].
+    stencil_write_masks: Option<[u32; 2]>,
+}
+
+#[derive(Clone, Copy, Debug)]
+struct DynamicRasterState {
+    front_face: vk::FrontFace,
+    cull_mode: vk::CullModeFlags,
+    depth_compare: vk::CompareOp,
 }
 
 #[derive(Debug)]
@@ -947,8 +1486,6 @@ impl crate::Queue for Queue {
         surface_textures: &[&SurfaceTexture],
         (signal_fence, signal_value): (&mut Fence, crate::FenceValue),
     ) -> Result<(), crate::DeviceError> {
-        let mut fence_raw = vk::Fence::null();
-
         let mut wait_stage_masks = Vec::new();
         let mut wait_semaphores = Vec::new();
         let mut signal_semaphores = Vec::new();
@@ -1004,32 +1541,47 @@ impl crate::Queue for Queue {
             wait_semaphores.push(sem);
         }
 
+        let mut sync_files = self.sync_files.lock();
+        sync_files.free_completed(&self.device.raw)?;
+        let sync_file_waits = mem::take(&mut sync_files.waits);
+        for &raw in sync_file_waits.iter() {
+            wait_stage_masks.push(vk::PipelineStageFlags::ALL_COMMANDS);
+            wait_semaphores.push(raw);
+        }
+
+        // Binary semaphores ignore their wait value, so only the waits
+        // requested through `wait_for_fence` need a meaningful one.
+        let mut wait_values = vec![0; wait_semaphores.len()];
+        for (raw, value) in self.fence_waits.lock().drain(..) {
+            wait_stage_masks.push(vk::PipelineStageFlags::ALL_COMMANDS);
+            wait_semaphores.push(raw);
+            wait_values.push(value);
+        }
+
         signal_semaphores.push(semaphore_state.signal);
         signal_values.push(!0);
 
         // We need to signal our wgpu::Fence if we have one, this adds it to the signal list.
-        signal_fence.maintain(&self.device.raw)?;
-        match *signal_fence {
-            Fence::TimelineSemaphore(raw) => {
-                signal_semaphores.push(raw);
-                signal_values.push(signal_value);
-            }
-            Fence::FencePool {
-                ref mut active,
-                ref mut free,
-                ..
-            } => {
-                fence_raw = match free.pop() {
-                    Some(raw) => raw,
-                    None => unsafe {
-                        self.device
-                            .raw
-                            .create_fence(&vk::FenceCreateInfo::default(), None)?
-                    },
-                };
-                active.push((signal_value, fence_raw));
-            }
+        let mut fence_raw = self.signal_fence(
+            signal_fence,
+            signal_value,
+            &mut signal_semaphores,
+            &mut signal_values,
+        )?;
+
+        // Sync files are only supported with timeline semaphores, which leaves
+        // the fence of the submission free to tell when their semaphores can
+        // be destroyed.
+        if !sync_file_waits.is_empty() {
+            debug_assert_eq!(fence_raw, vk::Fence::null());
+            fence_raw = unsafe {
+                self.device
+                    .raw
+                    .create_fence(&vk::FenceCreateInfo::default(), None)?
+            };
+            sync_files.retired.push((fence_raw, sync_file_waits));
         }
+        drop(sync_files);
 
         let vk_cmd_buffers = command_buffers
             .iter()
@@ -1046,8 +1598,9 @@ impl crate::Queue for Queue {
         let mut vk_timeline_info;
 
         if self.device.private_caps.timeline_semaphores {
-            vk_timeline_info =
-                vk::TimelineSemaphoreSubmitInfo::builder().signal_semaphore_values(&signal_values);
+            vk_timeline_info = vk::TimelineSemaphoreSubmitInfo::builder()
+                .wait_semaphore_values(&wait_values)
+                .signal_semaphore_values(&signal_values);
             vk_info = vk_info.push_next(&mut vk_timeline_info);
         }
 
@@ -1060,6 +1613,272 @@ impl crate::Queue for Queue {
         Ok(())
     }
 
+    unsafe fn wait_for_fence(
+        &self,
+        fence: &Fence,
+        value: crate::FenceValue,
+    ) -> Result<(), crate::DeviceError> {
+        match *fence {
+            Fence::TimelineSemaphore(raw) => {
+                self.fence_waits.lock().push((raw, value));
+            }
+            Fence::FencePool { .. } => {
+                // Plain fences can't be waited on by the GPU, so wait on the CPU instead.
+                self.device.wait_for_fence(fence, value, u64::MAX)?;
+            }
+        }
+        Ok(())
+    }
+
+    unsafe fn bind_sparse_buffer(
+        &self,
+        device: &Device,
+        buffer: &Buffer,
+        binds: &[wgt::SparseBufferBind],
+        (signal_fence, signal_value): (&mut Fence, crate::FenceValue),
+    ) -> Result<(), crate::DeviceError> {
+        let mut guard = buffer
+            .sparse
+            .as_ref()
+            .expect("Buffer was not created with `MemoryFlags::SPARSE`")
+            .lock();
+        let sparse = &mut *guard;
+        let mut allocator = device.mem_allocator.lock();
+        unsafe { self.free_released_memory(signal_fence, &mut sparse.released, &mut allocator) }?;
+
+        // Later binds of a page replace earlier ones.
+        let mut pages = BTreeMap::new();
+        for bind in binds {
+            let end = (bind.offset + bind.size).min(sparse.size);
+            let mut offset = bind.offset;
+            while offset < end {
+                pages.insert(offset, bind.commit);
+                offset += wgt::SPARSE_BUFFER_PAGE_SIZE;
+            }
+        }
+        let size = sparse.size;
+        let page_size =
+            |offset: wgt::BufferAddress| wgt::SPARSE_BUFFER_PAGE_SIZE.min(size - offset);
+
+        let requests = pages
+            .iter()
+            .filter(|&(offset, &commit)| commit && !sparse.pages.contains_key(offset))
+            .map(|(&offset, _)| {
+                let request = gpu_alloc::Request {
+                    size: page_size(offset),
+                    align_mask: sparse.align_mask,
+                    usage: gpu_alloc::UsageFlags::FAST_DEVICE_ACCESS,
+                    memory_types: sparse.memory_types,
+                };
+                (offset, request)
+            });
+        let committed = unsafe { self.alloc_sparse_memory(&mut allocator, requests) }?;
+        drop(allocator);
+
+        let mut memory_binds = Vec::with_capacity(pages.len());
+        for (offset, block) in committed {
+            memory_binds.push(
+                vk::SparseMemoryBind::builder()
+                    .resource_offset(offset)
+                    .size(page_size(offset))
+                    .memory(*block.memory())
+                    .memory_offset(block.offset())
+                    .build(),
+            );
+            sparse.pages.insert(offset, block);
+        }
+        for (&offset, _) in pages.iter().filter(|&(_, &commit)| !commit) {
+            if let Some(block) = sparse.pages.remove(&offset) {
+                memory_binds.push(
+                    vk::SparseMemoryBind::builder()
+                        .resource_offset(offset)
+                        .size(page_size(offset))
+                        .build(),
+                );
+                sparse.released.push((signal_value, block));
+            }
+        }
+
+        let buffer_binds = [vk::SparseBufferMemoryBindInfo::builder()
+            .buffer(buffer.raw)
+            .binds(&memory_binds)
+            .build()];
+        let buffer_binds = if memory_binds.is_empty() {
+            &buffer_binds[..0]
+        } else {
+            &buffer_binds[..]
+        };
+        unsafe { self.submit_sparse_binds((signal_fence, signal_value), buffer_binds, &[], &[]) }
+    }
+
+    unsafe fn bind_sparse_texture(
+        &self,
+        device: &Device,
+        texture: &Texture,
+        binds: &[wgt::SparseTextureBind],
+        (signal_fence, signal_value): (&mut Fence, crate::FenceValue),
+    ) -> Result<(), crate::DeviceError> {
+        let mut guard = texture
+            .sparse
+            .as_ref()
+            .expect("Texture was not created with `MemoryFlags::SPARSE`")
+            .lock();
+        let sparse = &mut *guard;
+        let mut allocator = device.mem_allocator.lock();
+        unsafe { self.free_released_memory(signal_fence, &mut sparse.released, &mut allocator) }?;
+
+        // Later binds of a tile replace earlier ones.
+        let tile_size = sparse.tiles.tile_size;
+        let mut tiles = BTreeMap::new();
+        for bind in binds {
+            if bind.mip_level >= sparse.tiles.mip_tail_first_level {
+                let tile = SparseTile::MipTail {
+                    array_layer: bind.array_layer,
+                };
+                tiles.insert(tile, bind.commit);
+                continue;
+            }
+            let level_size = sparse.level_size(bind.mip_level);
+            let end_x = (bind.origin.x + bind.size.width).min(level_size.width);
+            let end_y = (bind.origin.y + bind.size.height).min(level_size.height);
+            let end_z = (bind.origin.z + bind.size.depth_or_array_layers).min(level_size.depth);
+            for z in bind.origin.z / tile_size.depth_or_array_layers
+                ..end_z.div_ceil(tile_size.depth_or_array_layers)
+            {
+                for y in bind.origin.y / tile_size.height..end_y.div_ceil(tile_size.height) {
+                    for x in bind.origin.x / tile_size.width..end_x.div_ceil(tile_size.width) {
+                        let tile = SparseTile::Region {
+                            mip_level: bind.mip_level,
+                            array_layer: bind.array_layer,
+                            x,
+                            y,
+                            z,
+                        };
+                        tiles.insert(tile, bind.commit);
+                    }
+                }
+            }
+        }
+
+        let requests = tiles
+            .iter()
+            .filter(|&(tile, &commit)| commit && !sparse.committed.contains_key(tile))
+            .map(|(&tile, _)| {
+                let size = match tile {
+                    SparseTile::Region { .. } => sparse.align_mask + 1,
+                    SparseTile::MipTail { .. } => sparse.mip_tail_size,
+                };
+                let request = gpu_alloc::Request {
+                    size,
+                    align_mask: sparse.align_mask,
+                    usage: gpu_alloc::UsageFlags::FAST_DEVICE_ACCESS,
+                    memory_types: sparse.memory_types,
+                };
+                (tile, request)
+            });
+        let committed = unsafe { self.alloc_sparse_memory(&mut allocator, requests) }?;
+        drop(allocator);
+
+        let mut image_binds = Vec::new();
+        let mut mip_tail_binds = Vec::new();
+        let mut push_bind =
+            |sparse: &SparseImageMemory,
+             tile: SparseTile,
+             block: Option<&gpu_alloc::MemoryBlock<vk::DeviceMemory>>| {
+                let (memory, memory_offset) = block
+                    .map_or((vk::DeviceMemory::null(), 0), |block| {
+                        (*block.memory(), block.offset())
+                    });
+                match tile {
+                    SparseTile::Region {
+                        mip_level,
+                        array_layer,
+                        x,
+                        y,
+                        z,
+                    } => {
+                        let level_size = sparse.level_size(mip_level);
+                        let offset = vk::Offset3D {
+                            x: (x * tile_size.width) as i32,
+                            y: (y * tile_size.height) as i32,
+                            z: (z * tile_size.depth_or_array_layers) as i32,
+                        };
+                        // Tiles at the edges are clipped to the level.
+                        let extent = vk::Extent3D {
+                            width: tile_size.width.min(level_size.width - offset.x as u32),
+                            height: tile_size.height.min(level_size.height - offset.y as u32),
+                            depth: tile_size
+                                .depth_or_array_layers
+                                .min(level_size.depth - offset.z as u32),
+                        };
+                        image_binds.push(
+                            vk::SparseImageMemoryBind::builder()
+                                .subresource(vk::ImageSubresource {
+                                    aspect_mask: vk::ImageAspectFlags::COLOR,
+                                    mip_level,
+                                    array_layer,
+                                })
+                                .offset(offset)
+                                .extent(extent)
+                                .memory(memory)
+                                .memory_offset(memory_offset)
+                                .build(),
+                        );
+                    }
+                    SparseTile::MipTail { array_layer } => {
+                        mip_tail_binds.push(
+                            vk::SparseMemoryBind::builder()
+                                .resource_offset(
+                                    sparse.mip_tail_offset
+                                        + array_layer as u64 * sparse.mip_tail_stride,
+                                )
+                                .size(sparse.mip_tail_size)
+                                .memory(memory)
+                                .memory_offset(memory_offset)
+                                .build(),
+                        );
+                    }
+                }
+            };
+        for (tile, block) in committed {
+            push_bind(sparse, tile, Some(&block));
+            sparse.committed.insert(tile, block);
+        }
+        for (tile, _) in tiles.iter().filter(|&(_, &commit)| !commit) {
+            if let Some(block) = sparse.committed.remove(tile) {
+                push_bind(sparse, *tile, None);
+                sparse.released.push((signal_value, block));
+            }
+        }
+
+        let image_bind_infos = [vk::SparseImageMemoryBindInfo::builder()
+            .image(texture.raw)
+            .binds(&image_binds)
+            .build()];
+        let image_bind_infos = if image_binds.is_empty() {
+            &image_bind_infos[..0]
+        } else {
+            &image_bind_infos[..]
+        };
+        let mip_tail_bind_infos = [vk::SparseImageOpaqueMemoryBindInfo::builder()
+            .image(texture.raw)
+            .binds(&mip_tail_binds)
+            .build()];
+        let mip_tail_bind_infos = if mip_tail_binds.is_empty() {
+            &mip_tail_bind_infos[..0]
+        } else {
+            &mip_tail_bind_infos[..]
+        };
+        unsafe {
+            self.submit_sparse_binds(
+                (signal_fence, signal_value),
+                &[],
+                mip_tail_bind_infos,
+                image_bind_infos,
+            )
+        }
+    }
+
     unsafe fn present(
         &self,
         surface: &Surface,
@@ -1071,16 +1890,46 @@ impl crate::Queue for Queue {
 
         let swapchains = [ssc.raw];
         let image_indices = [texture.index];
-        let vk_info = vk::PresentInfoKHR::builder()
+        let mut vk_info = vk::PresentInfoKHR::builder()
             .swapchains(&swapchains)
             .image_indices(&image_indices)
             .wait_semaphores(swapchain_semaphores.get_present_wait_semaphores());
 
+        // Every present gets an ID, for `presentation_timings` to report.
+        ssc.present_count += 1;
+        let next_present_time = ssc.next_present_time.take();
+        let present_times = [vk::PresentTimeGOOGLE {
+            present_id: ssc.present_count,
+            desired_present_time: next_present_time.unwrap_or(0),
+        }];
+        let mut present_times_info = vk::PresentTimesInfoGOOGLE::builder().times(&present_times);
+        if ssc.device.extension_fns.display_timing.is_some() {
+            vk_info = vk_info.push_next(&mut present_times_info);
+        }
+
+        let present_ids = [u64::from(ssc.present_count)];
+        let mut present_id_info = vk::PresentIdKHR::builder().present_ids(&present_ids);
+        if ssc.device.extension_fns.present_wait.is_some() {
+            vk_info = vk_info.push_next(&mut present_id_info);
+        }
+
+        let next_damage = std::mem::take(&mut ssc.next_damage);
+        let present_regions = [vk::PresentRegionKHR::builder()
+            .rectangles(&next_damage)
+            .build()];
+        let mut present_regions_info = vk::PresentRegionsKHR::builder().regions(&present_regions);
+        if !next_damage.is_empty() {
+            vk_info = vk_info.push_next(&mut present_regions_info);
+        }
+
         let suboptimal = {
             profiling::scope!("vkQueuePresentKHR");
             unsafe { self.swapchain_fn.queue_present(self.raw, &vk_info) }.map_err(|error| {
                 match error {
-                    vk::Result::ERROR_OUT_OF_DATE_KHR => crate::SurfaceError::Outdated,
+                    vk::Result::ERROR_OUT_OF_DATE_KHR
+                    | vk::Result::ERROR_FULL_SCREEN_EXCLUSIVE_MODE_LOST_EXT => {
+                        crate::SurfaceError::Outdated
+                    }
                     vk::Result::ERROR_SURFACE_LOST_KHR => crate::SurfaceError::Lost,
                     _ => crate::DeviceError::from(error).into(),
                 }
@@ -1100,6 +1949,39 @@ impl crate::Queue for Queue {
     unsafe fn get_timestamp_period(&self) -> f32 {
         self.device.timestamp_period
     }
+
+    unsafe fn get_timestamp_calibration(&self) -> Option<wgt::TimestampCalibration> {
+        let fns = self.device.extension_fns.calibrated_timestamps.as_ref()?;
+        // Presentation timestamps use `CLOCK_MONOTONIC` too.
+        let infos = [
+            vk::TimeDomainEXT::DEVICE,
+            vk::TimeDomainEXT::CLOCK_MONOTONIC,
+        ]
+        .map(|time_domain| {
+            vk::CalibratedTimestampInfoEXT::builder()
+                .time_domain(time_domain)
+                .build()
+        });
+        let mut timestamps = [0u64; 2];
+        let mut max_deviation = 0u64;
+        let result = unsafe {
+            (fns.get_calibrated_timestamps_ext)(
+                self.device.raw.handle(),
+                infos.len() as u32,
+                infos.as_ptr(),
+                timestamps.as_mut_ptr(),
+                &mut max_deviation,
+            )
+        };
+        if result != vk::Result::SUCCESS {
+            log::error!("vkGetCalibratedTimestampsEXT failed: {result}");
+            return None;
+        }
+        Some(wgt::TimestampCalibration {
+            gpu_timestamp: timestamps[0],
+            cpu_timestamp: timestamps[1],
+        })
+    }
 }
 
 impl From<vk::Result> for crate::DeviceError {
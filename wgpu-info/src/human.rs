@@ -61,6 +61,13 @@ fn print_empty_string(input: &str) -> &str {
     }
 }
 
+fn print_id_bytes(input: Option<&[u8]>) -> String {
+    match input {
+        Some(bytes) => bytes.iter().map(|byte| format!("{byte:02x}")).collect(),
+        None => String::from("<unknown>"),
+    }
+}
+
 #[derive(Debug, Clone, Copy)]
 pub enum PrintingVerbosity {
     /// Corresponds to the `-q` flag
@@ -102,6 +109,19 @@ fn print_adapter(output: &mut impl io::Write, report: &AdapterReport, idx: usize
     writeln!(output, "\t            Type: {:?}", info.device_type)?;
     writeln!(output, "\t          Driver: {}", print_empty_string(&info.driver))?;
     writeln!(output, "\t      DriverInfo: {}", print_empty_string(&info.driver_info))?;
+    match info.driver_version {
+        Some(version) => writeln!(output, "\t   DriverVersion: {version}")?,
+        None => writeln!(output, "\t   DriverVersion: <unknown>")?,
+    }
+    writeln!(output, "\t      DeviceUUID: {}", print_id_bytes(info.device_uuid.as_ref().map(|id| &id[..])))?;
+    writeln!(output, "\t      DeviceLUID: {}", print_id_bytes(info.device_luid.as_ref().map(|id| &id[..])))?;
+    for (i, heap) in info.memory_heaps.iter().enumerate() {
+        let location = if heap.device_local { "device-local" } else { "host" };
+        let mappable = if heap.host_visible { ", host-visible" } else { "" };
+        writeln!(output, "\t    MemoryHeap {i}: {} MiB ({location}{mappable})", heap.size / (1024 * 1024))?;
+    }
+    writeln!(output, "\t   UnifiedMemory: {}", info.unified_memory)?;
+    writeln!(output, "\t    ResizableBAR: {}", info.resizable_bar)?;
     writeln!(output, "\tWebGPU Compliant: {:?}", downlevel.is_webgpu_compliant())?;
 
     if matches!(verbosity, PrintingVerbosity::Information) {
@@ -158,6 +178,7 @@ fn print_adapter(output: &mut impl io::Write, report: &AdapterReport, idx: usize
         max_compute_workgroup_size_z,
         max_compute_workgroups_per_dimension,
         max_non_sampler_bindings,
+        max_additional_queues,
     } = limits;
     writeln!(output, "\t\t                        Max Texture Dimension 1d: {max_texture_dimension_1d}")?;
     writeln!(output, "\t\t                        Max Texture Dimension 2d: {max_texture_dimension_2d}")?;
@@ -192,6 +213,7 @@ fn print_adapter(output: &mut impl io::Write, report: &AdapterReport, idx: usize
     writeln!(output, "\t\t                    Max Compute Workgroup Size Y: {max_compute_workgroup_size_y}")?;
     writeln!(output, "\t\t                    Max Compute Workgroup Size Z: {max_compute_workgroup_size_z}")?;
     writeln!(output, "\t\t            Max Compute Workgroups Per Dimension: {max_compute_workgroups_per_dimension}")?;
+    writeln!(output, "\t\t                           Max Additional Queues: {max_additional_queues}")?;
 
     // This one reflects more of a wgpu implementation limitations than a hardware limit
     // so don't show it here.
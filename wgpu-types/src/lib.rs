@@ -82,6 +82,8 @@ pub const QUERY_RESOLVE_BUFFER_ALIGNMENT: BufferAddress = 256;
 pub const COPY_BUFFER_ALIGNMENT: BufferAddress = 4;
 /// Size to align mappings.
 pub const MAP_ALIGNMENT: BufferAddress = 8;
+/// Size of the pages of sparse buffers, which [`SparseBufferBind`] offsets and sizes must be aligned to.
+pub const SPARSE_BUFFER_PAGE_SIZE: BufferAddress = 1 << 16;
 /// Vertex buffer strides have to be aligned to this number.
 pub const VERTEX_STRIDE_ALIGNMENT: BufferAddress = 4;
 /// Alignment all push constants need
@@ -90,6 +92,10 @@ pub const PUSH_CONSTANT_ALIGNMENT: u32 = 4;
 pub const QUERY_SET_MAX_QUERIES: u32 = 8192;
 /// Size of a single piece of query data.
 pub const QUERY_SIZE: u32 = 8;
+/// Number of viewports and scissor rectangles of a render pass with [`Features::MULTI_VIEWPORT`].
+pub const MAX_VIEWPORTS: u32 = 16;
+/// Maximum number of bindings in a bind group layout created with [`Features::INLINE_BIND_GROUPS`].
+pub const MAX_INLINE_BINDINGS: u32 = 8;
 
 /// Backends supported by wgpu.
 #[repr(u8)]
@@ -187,6 +193,15 @@ bitflags::bitflags! {
     }
 }
 
+impl Backends {
+    /// Backend that accepts all calls but does no work, useful for measuring the
+    /// CPU overhead of wgpu itself. Requires the `noop` feature of `wgpu`.
+    ///
+    /// Defined outside of the flags so that it is not part of [`Backends::all`],
+    /// it has to be requested explicitly.
+    pub const NOOP: Self = Self::from_bits_retain(1 << Backend::Empty as u32);
+}
+
 impl Default for Backends {
     fn default() -> Self {
         Self::all()
@@ -197,7 +212,7 @@ impl_bitflags!(Backends);
 
 impl From<Backend> for Backends {
     fn from(backend: Backend) -> Self {
-        Self::from_bits(1 << backend as u32).unwrap()
+        Self::from_bits_retain(1 << backend as u32)
     }
 }
 
@@ -246,7 +261,7 @@ bitflags::bitflags! {
     #[repr(transparent)]
     #[derive(Default)]
     #[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
-    pub struct Features: u64 {
+    pub struct Features: u128 {
         //
         // ---- Start numbering at 1 << 0 ----
         //
@@ -530,6 +545,9 @@ bitflags::bitflags! {
         /// on a system that doesn't, this can severely hinder performance. Only use if you understand
         /// the consequences.
         ///
+        /// Adapters that share memory report [`DownlevelFlags::UNIFIED_MEMORY_MAPPABLE_BUFFERS`],
+        /// which lifts the requirement without this feature.
+        ///
         /// Supported platforms:
         /// - Vulkan
         /// - DX12
@@ -914,6 +932,366 @@ bitflags::bitflags! {
         ///
         /// This is a native only feature.
         const SUBGROUP_BARRIER = 1 << 58;
+        /// Allows render passes to set up to [`MAX_VIEWPORTS`] viewports and scissor rectangles,
+        /// and vertex shaders to select one of them per primitive with the `viewport_index`
+        /// built-in, so that an object can be drawn into several regions of a target, such as
+        /// the cascades of a shadow map atlas, in a single pass.
+        ///
+        /// Supported Platforms:
+        /// - Vulkan (with `VK_EXT_shader_viewport_index_layer`)
+        /// - DX12
+        /// - Metal (macOS 10.13+)
+        ///
+        /// This is a native only feature.
+        const MULTI_VIEWPORT = 1 << 59;
+        /// Allows the creation of timeline semaphores: counters on the device that
+        /// [`Queue::submit`] calls can wait for and signal with arbitrary values, and
+        /// that can also be signaled and waited on from the host. They can be shared
+        /// with other APIs through the `wgpu-hal` interop functions.
+        ///
+        /// Supported Platforms:
+        /// - Vulkan (with timeline semaphores)
+        /// - DX12
+        ///
+        /// This is a native only feature.
+        ///
+        /// [`Queue::submit`]: ../wgpu/struct.Queue.html#method.submit
+        const TIMELINE_SEMAPHORE = 1 << 60;
+        /// Allows render passes to change the front face, cull mode and depth compare
+        /// function of the current pipeline, so that these don't each need their own
+        /// pipeline. Changes last until the next pipeline is set.
+        ///
+        /// With this feature, pipelines writing to the stencil aspect are treated as such
+        /// regardless of their cull mode, since it can change when drawing.
+        ///
+        /// Supported Platforms:
+        /// - Vulkan (with `VK_EXT_extended_dynamic_state`)
+        /// - Metal
+        ///
+        /// This is a native only feature.
+        const EXTENDED_DYNAMIC_STATE = 1 << 61;
+        /// Allows render passes to use different stencil reference values for front and
+        /// back faces with [`RenderPass::set_stencil_reference_separate`], and to change
+        /// the stencil write masks of the current pipeline with
+        /// [`RenderPass::set_stencil_write_mask`]. Write masks last until the next
+        /// pipeline is set, and can't be non-zero in a pass with a read-only stencil aspect.
+        ///
+        /// Supported Platforms:
+        /// - Vulkan
+        /// - OpenGL (but not WebGL, which requires the same values for both faces)
+        ///
+        /// This is a native only feature.
+        ///
+        /// [`RenderPass::set_stencil_reference_separate`]: ../wgpu/struct.RenderPass.html#method.set_stencil_reference_separate
+        /// [`RenderPass::set_stencil_write_mask`]: ../wgpu/struct.RenderPass.html#method.set_stencil_write_mask
+        const SEPARATE_STENCIL_STATE = 1 << 62;
+        /// Allows buffers with [`BufferUsages::MAP_WRITE`] to be used by submissions
+        /// while they are mapped for writing, so they can stay mapped instead of being
+        /// mapped and unmapped every time they are updated.
+        ///
+        /// The mapped range of such a buffer is flushed to the device with every
+        /// submission that uses it, if the memory isn't coherent. It has to be written
+        /// through the unsafe `BufferSlice::get_mapped_range_mut_unchecked`, and only
+        /// while no submission using the buffer is running. Views taken with the safe
+        /// accessors keep the buffer from being submitted until it is unmapped.
+        ///
+        /// Supported Platforms:
+        /// - Vulkan
+        /// - DX12
+        /// - Metal
+        ///
+        /// This is a native only feature.
+        const PERSISTENT_MAPPING = 1 << 63;
+        /// Allows the creation of sparse buffers with `Device::create_sparse_buffer`,
+        /// which reserve their size in the address space of the device without
+        /// using any memory. Pages of [`SPARSE_BUFFER_PAGE_SIZE`] bytes are committed
+        /// to memory and decommitted again with `Queue::bind_sparse`.
+        ///
+        /// Committed pages are zeroed before their first use. Reads from pages that
+        /// aren't committed return undefined values, and writes to them are discarded.
+        ///
+        /// Supported Platforms:
+        /// - Vulkan, with `sparseBinding` and `sparseResidencyBuffer`
+        ///
+        /// This is a native only feature.
+        const SPARSE_BUFFER = 1 << 64;
+        /// Allows taking the address of buffers in the address space of the device
+        /// with `Buffer::gpu_address`, so that GPU-driven structures can store raw
+        /// pointers to them. Only buffers with [`BufferUsages::STORAGE`] have an
+        /// address.
+        ///
+        /// WGSL shaders can turn an address into a pointer after
+        /// `enable naga_buffer_device_address;`, with
+        /// `bitcast<ptr<physical_storage, T, read_write>>(address)`, where `address`
+        /// is a `u64` (see [`Features::SHADER_INT64`]). Such pointers can't be used
+        /// in shaders translated to HLSL, and need MSL 3.0.
+        ///
+        /// Supported Platforms:
+        /// - Vulkan, with `VK_KHR_buffer_device_address`
+        /// - DX12
+        /// - Metal 3
+        ///
+        /// This is a native only feature.
+        const BUFFER_DEVICE_ADDRESS = 1 << 65;
+        /// Allows shaders to use `atomic<u64>` and `atomic<i64>` in storage buffers and
+        /// workgroup memory, with all atomic operations. GPU-driven culling and
+        /// visibility buffers commonly pack a depth and an id into one 64-bit atomic.
+        ///
+        /// Requires [`Features::SHADER_INT64`].
+        ///
+        /// Supported Platforms:
+        /// - Vulkan, with `shaderBufferInt64Atomics` and `shaderSharedInt64Atomics`
+        /// - DX12, with Shader Model 6.6 and `AtomicInt64OnGroupSharedSupported`
+        ///
+        /// This is a native only feature.
+        const SHADER_INT64_ATOMICS = 1 << 66;
+        /// Allows the creation of inline bind group layouts with
+        /// `Device::create_inline_bind_group_layout`. The bindings of an inline group
+        /// are passed directly to `RenderPass::set_bind_group_inline` every time they
+        /// change, without creating a `BindGroup`, which suits bindings that change
+        /// every draw.
+        ///
+        /// Inline bind group layouts may only contain up to [`MAX_INLINE_BINDINGS`]
+        /// buffer bindings, without dynamic offsets or arrays.
+        ///
+        /// Supported Platforms:
+        /// - Vulkan, with `VK_KHR_push_descriptor`
+        /// - DX12
+        /// - Metal
+        /// - OpenGL
+        ///
+        /// This is a native only feature.
+        const INLINE_BIND_GROUPS = 1 << 67;
+        /// Allows the creation of sparse textures with `Device::create_sparse_texture`,
+        /// which are created without any memory. Their tiles are committed to memory
+        /// and decommitted again with `Queue::bind_sparse_texture`, and the size of
+        /// the tiles is queried with `Texture::sparse_tiles`.
+        ///
+        /// Sparse textures must be 2D or 3D, single sampled, and of a color format.
+        ///
+        /// Committed tiles are zeroed before their first use. Reads from tiles that
+        /// aren't committed return undefined values, and writes to them are discarded.
+        ///
+        /// Supported Platforms:
+        /// - Vulkan, with `sparseBinding`, `sparseResidencyImage2D` and `sparseResidencyImage3D`
+        ///
+        /// This is a native only feature.
+        const SPARSE_TEXTURE = 1 << 68;
+        /// Allows the creation of memory heaps with `Device::create_memory_heap`, and of
+        /// buffers and textures placed in them at explicit offsets with
+        /// `Device::create_placed_buffer` and `Device::create_placed_texture`. The memory
+        /// a placed resource needs is queried with `Device::buffer_memory_requirements` and
+        /// `Device::texture_memory_requirements`.
+        ///
+        /// Placed resources may overlap, so that transient resources used at different
+        /// times share memory. Using one makes the contents of those overlapping it
+        /// undefined, and the barriers this takes are inserted automatically between
+        /// command buffers. Overlapping resources can't be used by the same command buffer.
+        ///
+        /// Supported Platforms:
+        /// - Vulkan
+        ///
+        /// This is a native only feature.
+        const PLACED_RESOURCES = 1 << 69;
+        /// Allows for creation of textures of format [`TextureFormat::P010`]
+        ///
+        /// Supported platforms:
+        /// - DX12
+        /// - Vulkan
+        ///
+        /// This is a native only feature.
+        const TEXTURE_FORMAT_P010 = 1 << 70;
+        /// Allows the use of [`BindingType::YcbcrTexture`], which samples multi-planar
+        /// YUV textures such as [`TextureFormat::NV12`] as RGB by converting them in the sampler.
+        ///
+        /// Supported platforms:
+        /// - Vulkan 1.1
+        ///
+        /// This is a native only feature.
+        const SAMPLER_YCBCR_CONVERSION = 1 << 71;
+        /// Allows texture views to be created with a [`TextureComponentSwizzle`] other than
+        /// the identity, which rearranges the components read from them by shaders.
+        ///
+        /// Swizzled views can only be bound as sampled textures.
+        ///
+        /// Supported platforms:
+        /// - Vulkan (except for some portability subset implementations)
+        /// - DX12
+        /// - Metal (macOS 10.15+, iOS 13+)
+        /// - OpenGL (not WebGL)
+        ///
+        /// This is a native only feature.
+        const TEXTURE_COMPONENT_SWIZZLE = 1 << 72;
+        /// Allows [`TextureDescriptor::view_formats`] to list formats that reinterpret
+        /// the texels of the texture rather than only change its srgb-ness, such as
+        /// [`TextureFormat::Rgba8Uint`] for a [`TextureFormat::Rgba8Unorm`] texture,
+        /// or [`TextureFormat::Rg32Uint`] for a [`TextureFormat::Bc1RgbaUnorm`] texture,
+        /// see [`TextureFormat::is_reinterpretable_as`].
+        ///
+        /// A texture can also have the usages that any of these view formats allows
+        /// for shader bindings, so a compressed texture can be written block by block
+        /// through a storage view. Each view only has the usages of its own format.
+        ///
+        /// Textures of formats that have the same block size and block dimensions
+        /// can also be copied into each other with
+        /// [`CommandEncoder::copy_texture_to_texture`](../wgpu/struct.CommandEncoder.html#method.copy_texture_to_texture).
+        ///
+        /// Supported platforms:
+        /// - Vulkan 1.1
+        ///
+        /// This is a native only feature.
+        const TEXTURE_FORMAT_REINTERPRETATION = 1 << 73;
+        /// Allows [`CommandEncoder::blit_texture`](../wgpu/struct.CommandEncoder.html#method.blit_texture)
+        /// to copy a region of a texture into a differently sized region of another
+        /// texture, scaling it with the given [`FilterMode`].
+        ///
+        /// Both textures have to be single-sampled 2D textures of color formats with a
+        /// float sample type, and only a single array layer is blitted at a time.
+        ///
+        /// Supported platforms:
+        /// - Vulkan
+        /// - OpenGL
+        ///
+        /// This is a native only feature.
+        const TEXTURE_BLIT = 1 << 74;
+        /// Allows sharing the memory of buffers and textures with other APIs and
+        /// processes through opaque file descriptors, see [`ExternalMemoryHandleType::OpaqueFd`].
+        ///
+        /// Resources are exported with
+        /// [`Device::create_exportable_buffer`](../wgpu/struct.Device.html#method.create_exportable_buffer)
+        /// and [`Device::create_exportable_texture`](../wgpu/struct.Device.html#method.create_exportable_texture),
+        /// and imported with [`Device::import_buffer`](../wgpu/struct.Device.html#method.import_buffer)
+        /// and [`Device::import_texture`](../wgpu/struct.Device.html#method.import_texture).
+        ///
+        /// Supported platforms:
+        /// - Vulkan 1.1 on Linux and Android, with `VK_KHR_external_memory_fd`
+        ///
+        /// This is a native only feature.
+        const EXTERNAL_MEMORY_FD = 1 << 75;
+        /// Allows sharing the memory of buffers and textures as Linux dma-bufs, see
+        /// [`ExternalMemoryHandleType::DmaBuf`], like [`Features::EXTERNAL_MEMORY_FD`]
+        /// does with opaque file descriptors.
+        ///
+        /// The layout of textures in dma-bufs is described by a DRM format modifier,
+        /// which is negotiated with the other side out of those returned by
+        /// [`Device::drm_format_modifiers`](../wgpu/struct.Device.html#method.drm_format_modifiers).
+        /// This allows zero-copy interop with Wayland compositors, VA-API and PipeWire.
+        ///
+        /// Supported platforms:
+        /// - Vulkan 1.1 on Linux and Android, with `VK_KHR_external_memory_fd`,
+        ///   `VK_EXT_external_memory_dma_buf` and `VK_EXT_image_drm_format_modifier`
+        ///
+        /// This is a native only feature.
+        const EXTERNAL_MEMORY_DMA_BUF = 1 << 76;
+        /// Allows sharing the memory of buffers and textures with other APIs and
+        /// processes through NT handles, see [`ExternalMemoryHandleType::D3D12Resource`],
+        /// like [`Features::EXTERNAL_MEMORY_FD`] does with file descriptors.
+        ///
+        /// Accesses from both sides are synchronized with fences rather than keyed
+        /// mutexes, which D3D12 doesn't have: timeline semaphores are shared as
+        /// fences through [`ExternalSemaphoreHandleType::D3D12Fence`], which
+        /// `ID3D11Device5::OpenSharedFence` and `ID3D12Device::OpenSharedHandle` open.
+        ///
+        /// Supported platforms:
+        /// - DX12
+        ///
+        /// This is a native only feature.
+        const EXTERNAL_MEMORY_WIN32 = 1 << 77;
+        /// Allows wrapping IOSurfaces in textures, and creating textures backed by
+        /// IOSurfaces to share them, so frames from CoreVideo and AVFoundation and
+        /// windows of other processes can be used without copies.
+        ///
+        /// These textures are 2D, single sampled, and have a single layer and mip level.
+        ///
+        /// Supported platforms:
+        /// - Metal
+        ///
+        /// This is a native only feature.
+        const IOSURFACE = 1 << 78;
+        /// Allows wrapping GL textures and EGLImages of other GL contexts in textures,
+        /// and sharing textures as GL texture names and EGLImages, so wgpu can be
+        /// embedded into existing GL applications without reading frames back.
+        ///
+        /// EGLImages are 2D, single sampled, and have a single layer and mip level.
+        ///
+        /// Supported platforms:
+        /// - OpenGL ES / OpenGL, with EGL and `GL_OES_EGL_image`
+        ///
+        /// This is a native only feature.
+        const GL_TEXTURE_INTEROP = 1 << 79;
+        /// Allows sharing timeline semaphores with other APIs and processes through
+        /// opaque file descriptors, see [`ExternalSemaphoreHandleType::OpaqueFd`], and
+        /// synchronizing with them through Linux sync files.
+        ///
+        /// Semaphores are exported with
+        /// [`Device::create_exportable_timeline_semaphore`](../wgpu/struct.Device.html#method.create_exportable_timeline_semaphore)
+        /// and imported with [`Device::import_timeline_semaphore`](../wgpu/struct.Device.html#method.import_timeline_semaphore).
+        /// Sync files are waited for with [`Queue::wait_for_sync_file`](../wgpu/struct.Queue.html#method.wait_for_sync_file)
+        /// and created with [`Queue::export_sync_file`](../wgpu/struct.Queue.html#method.export_sync_file).
+        /// This lets accesses to [`Features::EXTERNAL_MEMORY_FD`] and
+        /// [`Features::EXTERNAL_MEMORY_DMA_BUF`] memory be ordered on the GPU.
+        ///
+        /// Supported platforms:
+        /// - Vulkan 1.1 on Linux and Android, with timeline semaphores and
+        ///   `VK_KHR_external_semaphore_fd`
+        ///
+        /// This is a native only feature.
+        const EXTERNAL_SEMAPHORE_FD = 1 << 80;
+        /// Allows samplers to return the minimum or maximum of the texels they
+        /// filter instead of their weighted average, see
+        /// [`SamplerReductionMode`]. Used to build and test hierarchical depth
+        /// buffers with a single sample.
+        ///
+        /// Min/max filtering is only guaranteed for single-channel formats, like
+        /// [`TextureFormat::R32Float`] and the depth formats.
+        ///
+        /// Supported platforms:
+        /// - Vulkan, with `VK_EXT_sampler_filter_minmax`
+        /// - DX12, with tiled resources tier 2
+        ///
+        /// This is a native only feature.
+        const SAMPLER_REDUCTION_MODE = 1 << 81;
+        /// Allows the use of [`AddressMode::ClampToBorder`] with an arbitrary
+        /// border color, [`SamplerBorderColor::Custom`].
+        ///
+        /// Where this is unsupported, the border can be emulated, see
+        /// [`SamplerBorderColor::Custom`].
+        ///
+        /// Supported platforms:
+        /// - DX12
+        /// - Vulkan, with `VK_EXT_custom_border_color`
+        /// - OpenGL, where [`Features::ADDRESS_MODE_CLAMP_TO_BORDER`] is supported
+        ///
+        /// This is a native only feature.
+        const ADDRESS_MODE_CLAMP_TO_CUSTOM_BORDER = 1 << 82;
+        /// Allows waiting for a surface to be able to take a new frame without
+        /// queueing more than [`SurfaceConfiguration::desired_maximum_frame_latency`]
+        /// frames, with `wgpu::Surface::wait_for_present`. Waiting right before
+        /// reading input and recording a frame, rather than in
+        /// `wgpu::Surface::get_current_texture`, shows the frame a refresh
+        /// earlier.
+        ///
+        /// Supported platforms:
+        /// - DX12, with frame latency waitable objects
+        /// - Vulkan, with `VK_KHR_present_id` and `VK_KHR_present_wait`
+        ///
+        /// This is a native only feature.
+        const PRESENT_WAIT = 1 << 83;
+        /// Allows fragment shaders to have a critical section, between
+        /// `beginInvocationInterlock()` and `endInvocationInterlock()` calls
+        /// in the body of the entry point. The critical sections of the
+        /// fragments of a pixel run one at a time, in the order of the
+        /// primitives, so they can blend into storage buffers and textures
+        /// without racing, e.g. for single-pass order-independent
+        /// transparency.
+        ///
+        /// Supported platforms:
+        /// - Vulkan, with `VK_EXT_fragment_shader_interlock`
+        /// - DX12, with rasterizer ordered views
+        /// - Metal, with raster order groups
+        ///
+        /// This is a native only feature.
+        const FRAGMENT_SHADER_INTERLOCK = 1 << 84;
     }
 }
 
@@ -966,6 +1344,19 @@ bitflags::bitflags! {
         /// - Vulkan, via the `VK_LAYER_KHRONOS_validation` layer; called ["GPU-Assisted
         ///   Validation"](https://github.com/KhronosGroup/Vulkan-ValidationLayers/blob/e45aeb85079e0835694cb8f03e6681fd18ae72c9/docs/gpu_validation.md#gpu-assisted-validation)
         const GPU_BASED_VALIDATION = 1 << 4;
+        /// Replace the names in shaders with hashes of themselves before passing them to
+        /// wgpu-hal, and never embed shader sources in the shaders it generates, even with
+        /// [`Self::DEBUG`]. The labels of these shader modules aren't passed to wgpu-hal either.
+        ///
+        /// This keeps the sources of shipped shaders out of driver caches and captures. Names
+        /// in crash reports can still be recovered: if the `WGPU_SHADER_SYMBOL_MAP`
+        /// environment variable names a file, the hashes and the names they replace are
+        /// appended to it as they are created, one tab-separated pair per line. Hashes don't
+        /// depend on the platform or build, so the file can be written ahead of time by any
+        /// build creating the same shaders.
+        ///
+        /// The names of entry points and overrides are kept.
+        const STRIP_SHADER_NAMES = 1 << 5;
     }
 }
 
@@ -1029,6 +1420,9 @@ impl InstanceFlags {
         if let Some(bit) = env("WGPU_GPU_BASED_VALIDATION") {
             self.set(Self::GPU_BASED_VALIDATION, bit);
         }
+        if let Some(bit) = env("WGPU_STRIP_SHADER_NAMES") {
+            self.set(Self::STRIP_SHADER_NAMES, bit);
+        }
 
         self
     }
@@ -1180,6 +1574,19 @@ pub struct Limits {
     /// This limit only affects the d3d12 backend. Using a large number will allow the device
     /// to create many bind groups at the cost of a large up-front allocation at device creation.
     pub max_non_sampler_bindings: u32,
+    /// Number of hardware queues that can be used in addition to the device's primary queue.
+    /// Defaults to 0. Higher is "better".
+    ///
+    /// A device is created with as many additional queues as this limit requests, each of
+    /// which can be retrieved with `Device::create_queue`. Submissions made to different
+    /// queues may execute concurrently, which allows running long compute workloads
+    /// without holding up the primary queue.
+    ///
+    /// Expect the number to be:
+    /// - Vulkan: the number of queues in the graphics queue family, minus one
+    /// - DX12: 4
+    /// - Metal, OpenGL and WebGPU: 0
+    pub max_additional_queues: u32,
 }
 
 impl Default for Limits {
@@ -1227,6 +1634,7 @@ impl Limits {
             max_subgroup_size: 0,
             max_push_constant_size: 0,
             max_non_sampler_bindings: 1_000_000,
+            max_additional_queues: 0,
         }
     }
 
@@ -1270,6 +1678,7 @@ impl Limits {
     ///     max_compute_workgroups_per_dimension: 65535,
     ///     max_buffer_size: 256 << 20, // (256 MiB)
     ///     max_non_sampler_bindings: 1_000_000,
+    ///     max_additional_queues: 0,
     /// });
     /// ```
     pub const fn downlevel_defaults() -> Self {
@@ -1326,6 +1735,7 @@ impl Limits {
     ///     max_compute_workgroups_per_dimension: 0, // +
     ///     max_buffer_size: 256 << 20, // (256 MiB),
     ///     max_non_sampler_bindings: 1_000_000,
+    ///     max_additional_queues: 0,
     /// });
     /// ```
     pub const fn downlevel_webgl2_defaults() -> Self {
@@ -1452,6 +1862,7 @@ impl Limits {
         compare!(max_compute_workgroups_per_dimension, Less);
         compare!(max_buffer_size, Less);
         compare!(max_non_sampler_bindings, Less);
+        compare!(max_additional_queues, Less);
     }
 }
 
@@ -1484,7 +1895,10 @@ pub struct DownlevelCapabilities {
 impl Default for DownlevelCapabilities {
     fn default() -> Self {
         Self {
-            flags: DownlevelFlags::all(),
+            // Unified memory depends on the hardware, and LOD biases on the API.
+            flags: DownlevelFlags::all()
+                - DownlevelFlags::UNIFIED_MEMORY_MAPPABLE_BUFFERS
+                - DownlevelFlags::SAMPLER_LOD_BIAS,
             limits: DownlevelLimits::default(),
             shader_model: ShaderModel::Sm5,
         }
@@ -1662,6 +2076,41 @@ bitflags::bitflags! {
         /// Will be implemented in the future by:
         /// - DX12 ([#2471](https://github.com/gfx-rs/wgpu/issues/2471))
         const VERTEX_AND_INSTANCE_INDEX_RESPECTS_RESPECTIVE_FIRST_VALUE_IN_INDIRECT_DRAW = 1 << 23;
+
+        /// Buffers with [`BufferUsages::MAP_READ`] or [`BufferUsages::MAP_WRITE`] may have any other
+        /// usage, as if [`Features::MAPPABLE_PRIMARY_BUFFERS`] was enabled.
+        ///
+        /// Only reported by adapters where the GPU uses the same memory as the CPU, so that
+        /// mapping such buffers is as fast as mapping staging buffers, and copying through a
+        /// staging buffer would only cost extra time and memory. Use [`AdapterInfo::unified_memory`]
+        /// to check for such an adapter otherwise.
+        ///
+        /// Supported By:
+        /// - Vulkan on integrated GPUs
+        /// - DX12 on UMA adapters
+        /// - Metal on Apple silicon and other GPUs with unified memory
+        ///
+        /// This is not part of WebGPU.
+        const UNIFIED_MEMORY_MAPPABLE_BUFFERS = 1 << 24;
+
+        /// Cube textures are filtered across the edges of their faces, without seams.
+        ///
+        /// WebGL2 and OpenGL ES 3.0 always filter cube textures seamlessly. On desktop
+        /// OpenGL, seamless filtering is enabled for the whole device.
+        const SEAMLESS_CUBE_MAP_FILTERING = 1 << 25;
+
+        /// Samplers may have a non-zero [`lod_bias`], which is clamped to the range the
+        /// adapter supports.
+        ///
+        /// Supported By:
+        /// - Vulkan
+        /// - DX12
+        /// - Desktop OpenGL
+        ///
+        /// This is not part of WebGPU.
+        ///
+        /// [`lod_bias`]: ../wgpu/struct.SamplerDescriptor.html#structfield.lod_bias
+        const SAMPLER_LOD_BIAS = 1 << 26;
     }
 }
 
@@ -1672,8 +2121,33 @@ impl DownlevelFlags {
     pub const fn compliant() -> Self {
         // We use manual bit twiddling to make this a const fn as `Sub` and `.remove` aren't const
 
-        // WebGPU doesn't actually require aniso
-        Self::from_bits_truncate(Self::all().bits() & !Self::ANISOTROPIC_FILTERING.bits())
+        // WebGPU doesn't actually require aniso, and doesn't know unified memory
+        // or sampler LOD biases.
+        Self::from_bits_truncate(
+            Self::all().bits()
+                & !Self::ANISOTROPIC_FILTERING.bits()
+                & !Self::UNIFIED_MEMORY_MAPPABLE_BUFFERS.bits()
+                & !Self::SAMPLER_LOD_BIAS.bits(),
+        )
+    }
+
+    /// The flags of WebGPU compatibility mode devices, which devices created
+    /// with [`DeviceFlags::COMPATIBILITY_MODE`] are limited to.
+    ///
+    /// Compatibility mode lacks cube array textures, format reinterpretation,
+    /// independent blending, depth bias clamping, storage buffers in vertex
+    /// shaders and per-sample shading.
+    pub const fn compatibility_mode() -> Self {
+        Self::from_bits_truncate(
+            Self::compliant().bits()
+                & !Self::CUBE_ARRAY_TEXTURES.bits()
+                & !Self::VIEW_FORMATS.bits()
+                & !Self::SURFACE_VIEW_FORMATS.bits()
+                & !Self::INDEPENDENT_BLEND.bits()
+                & !Self::DEPTH_BIAS_CLAMP.bits()
+                & !Self::VERTEX_STORAGE.bits()
+                & !Self::MULTISAMPLED_SHADING.bits(),
+        )
     }
 }
 
@@ -1746,8 +2220,246 @@ pub struct AdapterInfo {
     pub driver_info: String,
     /// Backend used for device
     pub backend: Backend,
+    /// Parsed version of the driver, if the backend exposes one.
+    ///
+    /// Unlike [`AdapterInfo::driver_info`], this can be compared against known-good
+    /// or known-bad driver releases.
+    ///
+    /// * For [`Backend::Vulkan`], this is decoded from `VkPhysicalDeviceProperties::driverVersion`
+    ///     using the vendor-specific encoding where one is known.
+    /// * For [`Backend::Dx12`], this is the user mode driver version reported by DXGI.
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub driver_version: Option<DriverVersion>,
+    /// Universally unique identifier of the physical device.
+    ///
+    /// This matches the UUID reported by other APIs (Vulkan's `deviceUUID`, CUDA's
+    /// `cudaDeviceProp::uuid`, OpenGL's `GL_DEVICE_UUID_EXT`) for the same device.
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub device_uuid: Option<[u8; 16]>,
+    /// Locally unique identifier of the physical device.
+    ///
+    /// This is only available on Windows, and matches `DXGI_ADAPTER_DESC::AdapterLuid`
+    /// as well as Vulkan's `deviceLUID` for the same device.
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub device_luid: Option<[u8; 8]>,
+    /// Memory heaps available to the adapter.
+    ///
+    /// Empty if the backend does not expose this information.
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub memory_heaps: Vec<MemoryHeapInfo>,
+    /// Whether the adapter shares its memory with the host (UMA), as integrated
+    /// GPUs do.
+    ///
+    /// Buffers that the host writes can then be used by the device directly, so
+    /// uploading through a staging buffer only costs an extra copy.
+    ///
+    /// `false` if the backend does not expose this information.
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub unified_memory: bool,
+    /// Whether the host can map all of the device-local memory of a discrete
+    /// adapter (resizable BAR), instead of only a small window of it.
+    ///
+    /// `false` if the backend does not expose this information.
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub resizable_bar: bool,
 }
 
+impl AdapterInfo {
+    /// Returns the 16-bit PCI vendor ID of the adapter, if [`AdapterInfo::vendor`] holds one.
+    ///
+    /// Vendors without a PCI ID (e.g. Vulkan's Khronos vendor IDs, which start at `0x10000`)
+    /// return `None`.
+    pub fn pci_vendor_id(&self) -> Option<u16> {
+        match self.vendor {
+            0 => None,
+            vendor => u16::try_from(vendor).ok(),
+        }
+    }
+
+    /// Returns the 16-bit PCI device ID of the adapter, if [`AdapterInfo::device`] holds one.
+    pub fn pci_device_id(&self) -> Option<u16> {
+        self.pci_vendor_id()?;
+        u16::try_from(self.device).ok()
+    }
+
+    /// Returns the total size of the [`memory_heaps`](Self::memory_heaps) local to the device, in bytes.
+    pub fn device_local_memory(&self) -> u64 {
+        self.memory_heaps
+            .iter()
+            .filter(|heap| heap.device_local)
+            .map(|heap| heap.size)
+            .sum()
+    }
+
+    /// Returns the total size of the [`memory_heaps`](Self::memory_heaps) the host can map, in bytes.
+    pub fn host_visible_memory(&self) -> u64 {
+        self.memory_heaps
+            .iter()
+            .filter(|heap| heap.host_visible)
+            .map(|heap| heap.size)
+            .sum()
+    }
+
+    /// Returns `true` if `self` and `other` describe the same physical device.
+    ///
+    /// Devices are compared by UUID or LUID when both sides have one, falling back
+    /// to the PCI vendor and device IDs otherwise. Adapters exposed through different
+    /// backends can be matched this way.
+    pub fn is_same_device(&self, other: &AdapterInfo) -> bool {
+        if let (Some(a), Some(b)) = (self.device_uuid, other.device_uuid) {
+            return a == b;
+        }
+        if let (Some(a), Some(b)) = (self.device_luid, other.device_luid) {
+            return a == b;
+        }
+        match (self.pci_device_id(), other.pci_device_id()) {
+            (Some(device), Some(other_device)) => {
+                device == other_device && self.pci_vendor_id() == other.pci_vendor_id()
+            }
+            _ => false,
+        }
+    }
+}
+
+/// Version of the driver backing an adapter.
+///
+/// Versions are ordered component-wise, starting with [`DriverVersion::major`].
+/// The meaning of each component is vendor-specific; versions are only meaningfully
+/// comparable between adapters with the same vendor and [`Backend`].
+#[derive(Clone, Copy, Debug, Default, Hash, Eq, PartialEq, Ord, PartialOrd)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct DriverVersion {
+    /// Major version.
+    pub major: u32,
+    /// Minor version.
+    pub minor: u32,
+    /// Patch version.
+    pub patch: u32,
+    /// Build number, or 0 if the vendor's encoding has none.
+    pub build: u32,
+}
+
+impl std::fmt::Display for DriverVersion {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "{}.{}.{}.{}",
+            self.major, self.minor, self.patch, self.build
+        )
+    }
+}
+
+/// Description of a memory heap available to an adapter.
+#[derive(Clone, Copy, Debug, Default, Hash, Eq, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct MemoryHeapInfo {
+    /// Size of the heap in bytes.
+    pub size: u64,
+    /// Whether the heap is local to the device (VRAM on discrete GPUs).
+    pub device_local: bool,
+    /// Whether the host can map memory of the heap.
+    ///
+    /// On discrete GPUs without resizable BAR, a device-local heap can be host
+    /// visible even though only a small part of it can be mapped at once.
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub host_visible: bool,
+}
+
+bitflags::bitflags! {
+    /// Flags controlling the behavior of a [`Device`](../wgpu/struct.Device.html).
+    ///
+    /// These are not part of the webgpu standard, and are ignored when targeting WebGPU.
+    #[repr(transparent)]
+    #[derive(Debug, Copy, Clone, Default, PartialEq, Eq, Hash)]
+    pub struct DeviceFlags: u32 {
+        /// Invoke buffer mapping callbacks and `on_submitted_work_done` closures
+        /// in submission order, and only from within `Device::poll`.
+        ///
+        /// By default, callbacks may fire from `Queue::submit` or from `Device::poll`,
+        /// and all mapping callbacks that become ready in one maintenance pass fire
+        /// before all work done closures, so the exact interleaving depends on how
+        /// quickly the GPU completes each submission. With this flag:
+        ///
+        /// - Callbacks are never invoked from `Queue::submit`, `Buffer::map_async`,
+        ///   `Buffer::unmap` or `Surface::configure`. They are queued on the device,
+        ///   and invoked by the next call to `Device::poll` or `Instance::poll_all`.
+        /// - Callbacks are invoked one submission at a time: the mapping callbacks
+        ///   for buffers last used by a submission, followed by the work done
+        ///   closures registered for it, before anything belonging to a later
+        ///   submission.
+        ///
+        /// This is intended for making tests of code built on wgpu reproducible.
+        const DETERMINISTIC_CALLBACKS = 1 << 0;
+        /// Poll the device from a thread wgpu spawns, so that `Buffer::map_async`
+        /// callbacks and `Queue::on_submitted_work_done` closures fire without the
+        /// application calling `Device::poll`.
+        ///
+        /// The thread sleeps while the device has no work in flight and exits when
+        /// the device is dropped or lost. Callbacks are invoked on this thread, so
+        /// they must not block on anything that waits for them to run.
+        ///
+        /// Ignored on the web, where the browser drives callbacks, and on other
+        /// platforms without threads. Combined with `DETERMINISTIC_CALLBACKS`, the
+        /// callbacks still fire one submission at a time, but no longer only from
+        /// calls of the application to `Device::poll`.
+        const BACKGROUND_POLL = 1 << 1;
+        /// When tracing the device, only record the creation of shader modules,
+        /// pipelines and their layouts, along with the code each pipeline stage
+        /// is compiled to by the backend, such as SPIR-V, HLSL and DXIL, or MSL.
+        ///
+        /// The trace then makes a small bundle reproducing the compilation of
+        /// every pipeline without the rest of the application, to be replayed
+        /// with the `replay-shaders` binary of the player. This is meant for
+        /// reporting miscompilations to driver vendors.
+        ///
+        /// Ignored if the device isn't traced.
+        const TRACE_SHADERS_ONLY = 1 << 3;
+        /// Reject shaders that index a binding array with a value that isn't
+        /// known to be uniform, unless the index is marked with the WGSL
+        /// `nonuniform` builtin (or `nonuniformEXT` in GLSL).
+        ///
+        /// Unmarked non-uniform indices are undefined behavior, and some drivers,
+        /// notably on AMD hardware, silently read from the wrong resource. The
+        /// shader compiler already marks such accesses when it can tell they are
+        /// non-uniform; this flag makes the annotation required instead, so the
+        /// intent of the shader is explicit.
+        const EXPLICIT_NON_UNIFORM_INDEXING = 1 << 4;
+        /// Enforce the restrictions of WebGPU compatibility mode, so that an
+        /// application developed natively runs in browsers that only offer
+        /// compatibility mode devices, which are backed by OpenGL ES 3.1 or D3D11.
+        ///
+        /// The device behaves as if it didn't have the downlevel flags missing
+        /// from [`DownlevelFlags::compatibility_mode`], so that, among others,
+        /// cube array views, texture format reinterpretation, independent blend
+        /// states per color target and depth bias clamping fail validation.
+        /// Texture views bound as textures must also include all the array
+        /// layers of their texture, rather than switching between views of
+        /// different layers and dimensions.
+        ///
+        /// Limits aren't lowered, the limits requested for the device should
+        /// be those of [`Limits::downlevel_defaults`] or lower.
+        const COMPATIBILITY_MODE = 1 << 5;
+        /// When mapping a buffer fails because the device ran out of memory,
+        /// free the memory the device keeps around for reuse, such as its pool
+        /// of command encoders, and try mapping the buffer once more before
+        /// failing the `Buffer::map_async` callback.
+        ///
+        /// Without this flag, the callback fails right away, and the application
+        /// can tell from the error that mapping again later may succeed.
+        const RETRY_FAILED_MAPPINGS = 1 << 6;
+        /// On discrete GPUs with a dedicated copy queue, run the staging copies
+        /// of `Queue::write_buffer` and `Queue::write_texture` on that queue, so
+        /// that large uploads stop stalling the rest of the work.
+        ///
+        /// Buffers and textures of the device are then shared between both
+        /// queues, which may make them slower to use on some GPUs. Only
+        /// supported on Vulkan, ignored elsewhere.
+        const TRANSFER_QUEUE = 1 << 7;
+    }
+}
+
+impl_bitflags!(DeviceFlags);
+
 /// Describes a [`Device`](../wgpu/struct.Device.html).
 ///
 /// Corresponds to [WebGPU `GPUDeviceDescriptor`](
@@ -1770,6 +2482,17 @@ pub struct DeviceDescriptor<L> {
     /// Exactly the specified limits, and no better or worse,
     /// will be allowed in validation of API calls on the resulting device.
     pub required_limits: Limits,
+    /// Native-only flags controlling the behavior of the device.
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub flags: DeviceFlags,
+    /// Scheduling priority of the device's queues relative to those of other
+    /// devices and processes.
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub queue_priority: QueuePriority,
+    /// How much of the usage of the device is validated when it is created, see
+    /// [`DeviceValidation`].
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub validation: DeviceValidation,
 }
 
 impl<L> DeviceDescriptor<L> {
@@ -1779,10 +2502,382 @@ impl<L> DeviceDescriptor<L> {
             label: fun(&self.label),
             required_features: self.required_features,
             required_limits: self.required_limits.clone(),
+            flags: self.flags,
+            queue_priority: self.queue_priority,
+            validation: self.validation,
+        }
+    }
+}
+
+/// How much validation a device performs, see [`DeviceDescriptor::validation`]
+/// and [`Device::set_validation_level`](../wgpu/struct.Device.html#method.set_validation_level).
+///
+/// Each level validates a subset of what the level above it validates. The
+/// checks of the lower levels are the ones whose cost grows with the amount of
+/// work recorded rather than with the number of objects created, so an
+/// application can turn them off once it is known to be valid while still
+/// getting errors from everything else.
+///
+/// Devices are created at [`ValidationLevel::Full`] unless their descriptor
+/// says otherwise. Anything that isn't
+/// validated is passed on to the backend as it is, so invalid usage below
+/// [`ValidationLevel::Full`] is undefined behavior and may result in
+/// out-of-bounds accesses by the GPU, device loss or driver crashes. Shader
+/// modules are always validated in full, since the backends rely on it.
+///
+/// The level is ignored on the web, where the browser always validates in full.
+#[repr(u8)]
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq, PartialOrd, Ord, Hash)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub enum ValidationLevel {
+    /// Validate the creation of objects and the commands recorded outside of
+    /// render and compute passes.
+    ///
+    /// The commands of render bundles are only checked as far as needed to
+    /// record them, like those of passes at [`ValidationLevel::Standard`].
+    Minimal = 0,
+    /// Validate everything but the commands recorded into render and compute
+    /// passes.
+    ///
+    /// Draws and dispatches in passes are no longer checked against the bind
+    /// groups and vertex and index buffers the bound pipeline expects, though a
+    /// pipeline, and an index buffer for indexed draws, still has to be bound.
+    /// Resource states are still tracked, so passes are still synchronized with
+    /// the rest of the work.
+    Standard = 1,
+    /// Validate all usage of the device, as WebGPU requires.
+    #[default]
+    Full = 2,
+}
+
+/// The validation level a device is created with, see [`DeviceDescriptor::validation`].
+///
+/// Lowering it is unsafe, so it can only be done through
+/// [`DeviceValidation::with_level`]. Creating a device at
+/// [`ValidationLevel::Standard`] skips the validation of passes from its first
+/// command encoder on, for shipping builds whose usage was validated in testing.
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct DeviceValidation {
+    level: ValidationLevel,
+}
+
+impl DeviceValidation {
+    /// Creates a new configuration where the device validates in full.
+    pub fn new() -> Self {
+        Self {
+            level: ValidationLevel::Full,
+        }
+    }
+
+    /// Creates a new configuration where the device validates at `level`.
+    ///
+    /// # Safety
+    ///
+    /// Below [`ValidationLevel::Full`], usage that is no longer validated is
+    /// passed on to the backend as-is. The caller MUST ensure that all such
+    /// usage of the device is valid, invalid usage is undefined behavior.
+    pub unsafe fn with_level(level: ValidationLevel) -> Self {
+        Self { level }
+    }
+
+    /// Query the validation level of this configuration.
+    pub fn level(&self) -> ValidationLevel {
+        self.level
+    }
+}
+
+/// Scheduling priority of the queues of a device, see [`DeviceDescriptor::queue_priority`].
+///
+/// Work submitted to queues of a higher priority can preempt work of other
+/// applications on the GPU, which compositors and XR runtimes need to present
+/// in time. The priority is a request: when the platform doesn't allow it, the
+/// device is created with the highest priority that is permitted.
+///
+/// Vulkan needs `VK_EXT_global_priority`, and DX12 maps this to the command
+/// queue priority. Other backends ignore it.
+#[repr(C)]
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub enum QueuePriority {
+    /// The default priority.
+    #[default]
+    Normal,
+    /// Above the default priority.
+    High,
+    /// The highest priority, which usually requires the process to be privileged.
+    Realtime,
+}
+
+/// How a device or process should trade performance for power, see
+/// [`Device::set_performance_hint`](../wgpu/struct.Device.html#method.set_performance_hint)
+/// and [`Instance::set_process_performance_hint`](../wgpu/struct.Instance.html#method.set_process_performance_hint).
+///
+/// The hint is a request, which platforms are free to ignore. For a device,
+/// Metal compiles shaders on all cores for [`PerformanceHint::Burst`]. For the
+/// process, [`PerformanceHint::LowPower`] opts it into EcoQoS on Windows, and on
+/// Apple platforms [`PerformanceHint::Sustained`] and [`PerformanceHint::Burst`]
+/// declare it as active to `NSProcessInfo`, the latter as latency critical.
+/// Other platforms have no equivalent and ignore it, but applications can
+/// still keep the GPU clocks down by presenting less often, for example with
+/// [`util::PresentThrottle`](../wgpu/util/struct.PresentThrottle.html).
+#[repr(C)]
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub enum PerformanceHint {
+    /// Keep power usage as low as possible, at the cost of performance, for
+    /// example for a tool that is idle most of the time.
+    LowPower,
+    /// Run at clocks that can be sustained without throttling, so that frame
+    /// times stay consistent, for example for benchmarks and profiling.
+    Sustained,
+    /// Let the platform raise the clocks to finish work as fast as possible.
+    #[default]
+    Burst,
+}
+
+/// Describes an additional [`Queue`](../wgpu/struct.Queue.html) of a device.
+///
+/// A device can hand out up to [`Limits::max_additional_queues`] queues besides
+/// the one it was created with.
+#[repr(C)]
+#[derive(Clone, Debug, Default)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct QueueDescriptor<L> {
+    /// Debug label for the queue.
+    pub label: L,
+}
+
+impl<L> QueueDescriptor<L> {
+    /// Takes a closure and maps the label of the queue descriptor into another.
+    pub fn map_label<K>(&self, fun: impl FnOnce(&L) -> K) -> QueueDescriptor<K> {
+        QueueDescriptor {
+            label: fun(&self.label),
         }
     }
 }
 
+/// Describes a [`TimelineSemaphore`](../wgpu/struct.TimelineSemaphore.html).
+///
+/// Requires [`Features::TIMELINE_SEMAPHORE`].
+#[repr(C)]
+#[derive(Clone, Debug, Default)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct TimelineSemaphoreDescriptor<L> {
+    /// Debug label for the semaphore.
+    pub label: L,
+    /// Value of the semaphore right after creation.
+    pub initial_value: u64,
+}
+
+impl<L> TimelineSemaphoreDescriptor<L> {
+    /// Takes a closure and maps the label of the semaphore descriptor into another.
+    pub fn map_label<K>(&self, fun: impl FnOnce(&L) -> K) -> TimelineSemaphoreDescriptor<K> {
+        TimelineSemaphoreDescriptor {
+            label: fun(&self.label),
+            initial_value: self.initial_value,
+        }
+    }
+}
+
+/// Describes a [`MemoryHeap`](../wgpu/struct.MemoryHeap.html).
+///
+/// Requires [`Features::PLACED_RESOURCES`].
+#[repr(C)]
+#[derive(Clone, Debug, Default)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct MemoryHeapDescriptor<L> {
+    /// Debug label for the heap.
+    pub label: L,
+    /// Size of the heap, in bytes.
+    pub size: BufferAddress,
+}
+
+impl<L> MemoryHeapDescriptor<L> {
+    /// Takes a closure and maps the label of the heap descriptor into another.
+    pub fn map_label<K>(&self, fun: impl FnOnce(&L) -> K) -> MemoryHeapDescriptor<K> {
+        MemoryHeapDescriptor {
+            label: fun(&self.label),
+            size: self.size,
+        }
+    }
+}
+
+/// The memory a buffer or texture needs when it is placed in a memory heap.
+///
+/// Returned by [`Device::buffer_memory_requirements`](../wgpu/struct.Device.html#method.buffer_memory_requirements)
+/// and [`Device::texture_memory_requirements`](../wgpu/struct.Device.html#method.texture_memory_requirements),
+/// see [`Features::PLACED_RESOURCES`].
+#[repr(C)]
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct MemoryRequirements {
+    /// Size of the memory, in bytes.
+    pub size: BufferAddress,
+    /// Alignment the offset of the resource in the heap must have, in bytes.
+    pub alignment: BufferAddress,
+}
+
+/// Type of handle the memory of a buffer or texture is shared through with
+/// other APIs and processes.
+#[repr(C)]
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub enum ExternalMemoryHandleType {
+    /// A file descriptor that can only be imported by the same driver on the
+    /// same physical device, with the same layout for textures.
+    ///
+    /// Requires [`Features::EXTERNAL_MEMORY_FD`].
+    OpaqueFd,
+    /// A Linux dma-buf, which can be imported by other drivers and kernel
+    /// subsystems. Textures are laid out in it as described by a DRM format modifier.
+    ///
+    /// Requires [`Features::EXTERNAL_MEMORY_DMA_BUF`].
+    DmaBuf,
+    /// An NT handle to a D3D12 resource, which D3D11 can open with
+    /// `ID3D11Device1::OpenSharedResource1` and D3D12 with
+    /// `ID3D12Device::OpenSharedHandle`, in this process or others.
+    ///
+    /// Requires [`Features::EXTERNAL_MEMORY_WIN32`].
+    D3D12Resource,
+}
+
+impl ExternalMemoryHandleType {
+    /// Returns the features required to share memory through this type of handle.
+    pub fn required_features(self) -> Features {
+        match self {
+            Self::OpaqueFd => Features::EXTERNAL_MEMORY_FD,
+            Self::DmaBuf => Features::EXTERNAL_MEMORY_DMA_BUF,
+            Self::D3D12Resource => Features::EXTERNAL_MEMORY_WIN32,
+        }
+    }
+}
+
+/// A DRM format modifier a texture format can be laid out in memory with,
+/// returned by [`Device::drm_format_modifiers`](../wgpu/struct.Device.html#method.drm_format_modifiers).
+///
+/// See [`Features::EXTERNAL_MEMORY_DMA_BUF`].
+#[repr(C)]
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct DrmFormatModifier {
+    /// The modifier, as defined in `drm_fourcc.h`.
+    pub modifier: u64,
+    /// Number of memory planes of textures laid out with this modifier, each
+    /// with its own [`MemoryPlaneLayout`].
+    pub plane_count: u32,
+}
+
+/// Layout of a memory plane of a texture shared as a dma-buf.
+///
+/// See [`Features::EXTERNAL_MEMORY_DMA_BUF`].
+#[repr(C)]
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct MemoryPlaneLayout {
+    /// Offset of the plane in the dma-buf, in bytes.
+    pub offset: BufferAddress,
+    /// Distance between the starts of two rows of the plane, in bytes.
+    pub row_pitch: BufferAddress,
+}
+
+/// Memory of a buffer or texture shared with other APIs and processes through
+/// a file descriptor, or an NT handle on Windows.
+///
+/// See [`Features::EXTERNAL_MEMORY_FD`], [`Features::EXTERNAL_MEMORY_DMA_BUF`]
+/// and [`Features::EXTERNAL_MEMORY_WIN32`].
+#[cfg(any(unix, windows))]
+#[derive(Debug)]
+pub struct ExternalMemory {
+    /// The file descriptor of the memory.
+    #[cfg(unix)]
+    pub fd: std::os::fd::OwnedFd,
+    /// The NT handle of the memory.
+    #[cfg(windows)]
+    pub handle: std::os::windows::io::OwnedHandle,
+    /// What the file descriptor or handle is.
+    pub handle_type: ExternalMemoryHandleType,
+    /// Size of the memory, in bytes.
+    pub size: BufferAddress,
+    /// For textures in dma-bufs, the DRM format modifier the texture is laid out with.
+    pub drm_format_modifier: Option<u64>,
+    /// For textures in dma-bufs, the layout of every memory plane of the
+    /// modifier. The planes all have to be in the same dma-buf.
+    pub planes: Vec<MemoryPlaneLayout>,
+}
+
+/// The expiry of a texture holding a frame of a producer, like a video
+/// decoder, that reuses its frames, as `GPUExternalTexture`s expire in
+/// WebGPU.
+///
+/// Once expired, the texture fails validation when bind groups are created
+/// with views of it, and when command buffers using it, including through
+/// bind groups created before the expiry, are submitted. The producer calls
+/// [`TextureExpiry::expire`] when it reuses the frame, so that stale uses are
+/// reported instead of showing whatever the frame was reused for.
+///
+/// Clones share the same expiry. Expiry can't be undone.
+#[derive(Clone, Debug, Default)]
+pub struct TextureExpiry(std::sync::Arc<std::sync::atomic::AtomicBool>);
+
+impl TextureExpiry {
+    /// Expires the texture.
+    pub fn expire(&self) {
+        self.0.store(true, std::sync::atomic::Ordering::Release);
+    }
+
+    /// Returns `true` if the texture was expired.
+    pub fn is_expired(&self) -> bool {
+        self.0.load(std::sync::atomic::Ordering::Acquire)
+    }
+}
+
+/// Type of handle a timeline semaphore is shared through with other APIs and
+/// processes.
+#[repr(C)]
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub enum ExternalSemaphoreHandleType {
+    /// A file descriptor of a Vulkan timeline semaphore, that can only be
+    /// imported by the same driver on the same physical device.
+    ///
+    /// Requires [`Features::EXTERNAL_SEMAPHORE_FD`].
+    OpaqueFd,
+    /// An NT handle to a shared D3D12 fence, which D3D11 can open with
+    /// `ID3D11Device5::OpenSharedFence` and D3D12 with
+    /// `ID3D12Device::OpenSharedHandle`, in this process or others.
+    ///
+    /// Requires [`Features::EXTERNAL_MEMORY_WIN32`].
+    D3D12Fence,
+}
+
+impl ExternalSemaphoreHandleType {
+    /// Returns the features required to share a semaphore through this type of handle.
+    pub fn required_features(self) -> Features {
+        match self {
+            Self::OpaqueFd => Features::EXTERNAL_SEMAPHORE_FD,
+            Self::D3D12Fence => Features::EXTERNAL_MEMORY_WIN32,
+        }
+    }
+}
+
+/// A timeline semaphore shared with other APIs and processes through a file
+/// descriptor, or an NT handle on Windows.
+///
+/// See [`Features::EXTERNAL_SEMAPHORE_FD`] and [`Features::EXTERNAL_MEMORY_WIN32`].
+#[cfg(any(unix, windows))]
+#[derive(Debug)]
+pub struct ExternalSemaphore {
+    /// The file descriptor of the semaphore.
+    #[cfg(unix)]
+    pub fd: std::os::fd::OwnedFd,
+    /// The NT handle of the semaphore.
+    #[cfg(windows)]
+    pub handle: std::os::windows::io::OwnedHandle,
+    /// What the file descriptor or handle is.
+    pub handle_type: ExternalSemaphoreHandleType,
+}
+
 bitflags::bitflags! {
     /// Describes the shader stages that a binding will be visible from.
     ///
@@ -2284,6 +3379,16 @@ impl TextureFormatFeatureFlags {
             .filter(|&sc| self.sample_count_supported(sc))
             .collect()
     }
+
+    /// Mask of the supported sample counts, in which the bit of value `n` is set
+    /// if a sample count of `n` is supported, like in `VkSampleCountFlags`.
+    ///
+    /// A sample count of 1 is always supported, so bit `1` is always set.
+    pub fn sample_count_mask(&self) -> u32 {
+        self.supported_sample_counts()
+            .into_iter()
+            .fold(0, |mask, count| mask | count)
+    }
 }
 
 impl_bitflags!(TextureFormatFeatureFlags);
@@ -2504,6 +3609,21 @@ pub enum TextureFormat {
     ///
     /// [`Features::TEXTURE_FORMAT_NV12`] must be enabled to use this texture format.
     NV12,
+    /// YUV 4:2:0 chroma subsampled format with 10 bits per channel.
+    ///
+    /// Contains two planes:
+    /// - 0: Single 16 bit channel luminance, of which the 10 most significant bits are used.
+    /// - 1: Dual 16 bit channel chrominance at half width and half height, of which the
+    ///   10 most significant bits are used.
+    ///
+    /// Valid view formats for luminance are [`TextureFormat::R16Unorm`].
+    ///
+    /// Valid view formats for chrominance are [`TextureFormat::Rg16Unorm`].
+    ///
+    /// Width and height must be even.
+    ///
+    /// [`Features::TEXTURE_FORMAT_P010`] must be enabled to use this texture format.
+    P010,
 
     // Compressed textures usable with `TEXTURE_COMPRESSION_BC` feature.
     /// 4x4 block compressed texture. 8 bytes per block (4 bit/px). 4 color + alpha pallet. 5 bit R + 6 bit G + 5 bit B + 1 bit alpha.
@@ -2735,6 +3855,7 @@ impl<'de> Deserialize<'de> for TextureFormat {
                     "depth24plus" => TextureFormat::Depth24Plus,
                     "depth24plus-stencil8" => TextureFormat::Depth24PlusStencil8,
                     "nv12" => TextureFormat::NV12,
+                    "p010" => TextureFormat::P010,
                     "rgb9e5ufloat" => TextureFormat::Rgb9e5Ufloat,
                     "bc1-rgba-unorm" => TextureFormat::Bc1RgbaUnorm,
                     "bc1-rgba-unorm-srgb" => TextureFormat::Bc1RgbaUnormSrgb,
@@ -2863,6 +3984,7 @@ impl Serialize for TextureFormat {
             TextureFormat::Depth24Plus => "depth24plus",
             TextureFormat::Depth24PlusStencil8 => "depth24plus-stencil8",
             TextureFormat::NV12 => "nv12",
+            TextureFormat::P010 => "p010",
             TextureFormat::Rgb9e5Ufloat => "rgb9e5ufloat",
             TextureFormat::Bc1RgbaUnorm => "bc1-rgba-unorm",
             TextureFormat::Bc1RgbaUnormSrgb => "bc1-rgba-unorm-srgb",
@@ -2951,6 +4073,8 @@ impl TextureFormat {
             (Self::Depth32FloatStencil8, TextureAspect::DepthOnly) => Some(Self::Depth32Float),
             (Self::NV12, TextureAspect::Plane0) => Some(Self::R8Unorm),
             (Self::NV12, TextureAspect::Plane1) => Some(Self::Rg8Unorm),
+            (Self::P010, TextureAspect::Plane0) => Some(Self::R16Unorm),
+            (Self::P010, TextureAspect::Plane1) => Some(Self::Rg16Unorm),
             // views to multi-planar formats must specify the plane
             (format, TextureAspect::All) if !format.is_multi_planar_format() => Some(format),
             _ => None,
@@ -3000,7 +4124,7 @@ impl TextureFormat {
     /// Returns the number of planes a multi-planar format has.
     pub fn planes(&self) -> Option<u32> {
         match *self {
-            Self::NV12 => Some(2),
+            Self::NV12 | Self::P010 => Some(2),
             _ => None,
         }
     }
@@ -3033,7 +4157,7 @@ impl TextureFormat {
     /// Returns the size multiple requirement for a texture using this format.
     pub fn size_multiple_requirement(&self) -> (u32, u32) {
         match *self {
-            Self::NV12 => (2, 2),
+            Self::NV12 | Self::P010 => (2, 2),
             _ => self.block_dimensions(),
         }
     }
@@ -3092,7 +4216,8 @@ impl TextureFormat {
             | Self::Depth24PlusStencil8
             | Self::Depth32Float
             | Self::Depth32FloatStencil8
-            | Self::NV12 => (1, 1),
+            | Self::NV12
+            | Self::P010 => (1, 1),
 
             Self::Bc1RgbaUnorm
             | Self::Bc1RgbaUnormSrgb
@@ -3193,6 +4318,7 @@ impl TextureFormat {
             Self::Depth32FloatStencil8 => Features::DEPTH32FLOAT_STENCIL8,
 
             Self::NV12 => Features::TEXTURE_FORMAT_NV12,
+            Self::P010 => Features::TEXTURE_FORMAT_P010,
 
             Self::R16Unorm
             | Self::R16Snorm
@@ -3310,8 +4436,9 @@ impl TextureFormat {
             Self::Depth32Float =>         (        msaa, attachment),
             Self::Depth32FloatStencil8 => (        msaa, attachment),
 
-            // We only support sampling nv12 textures until we implement transfer plane data.
+            // We only support sampling nv12 and p010 textures until we implement transfer plane data.
             Self::NV12 =>                 (        noaa,    binding),
+            Self::P010 =>                 (        noaa,    binding),
 
             Self::R16Unorm =>             (        msaa,    storage),
             Self::R16Snorm =>             (        msaa,    storage),
@@ -3435,7 +4562,7 @@ impl TextureFormat {
                 _ => None,
             },
 
-            Self::NV12 => match aspect {
+            Self::NV12 | Self::P010 => match aspect {
                 Some(TextureAspect::Plane0) | Some(TextureAspect::Plane1) => {
                     Some(unfilterable_float)
                 }
@@ -3566,6 +4693,12 @@ impl TextureFormat {
                 _ => None,
             },
 
+            Self::P010 => match aspect {
+                Some(TextureAspect::Plane0) => Some(2),
+                Some(TextureAspect::Plane1) => Some(4),
+                _ => None,
+            },
+
             Self::Bc1RgbaUnorm | Self::Bc1RgbaUnormSrgb | Self::Bc4RUnorm | Self::Bc4RSnorm => {
                 Some(8)
             }
@@ -3738,7 +4871,7 @@ impl TextureFormat {
                 _ => 2,
             },
 
-            Self::NV12 => match aspect {
+            Self::NV12 | Self::P010 => match aspect {
                 TextureAspect::Plane0 => 1,
                 TextureAspect::Plane1 => 2,
                 _ => 3,
@@ -3818,6 +4951,43 @@ impl TextureFormat {
     pub fn is_srgb(&self) -> bool {
         *self != self.remove_srgb_suffix()
     }
+
+    /// Returns `true` if a texture of this format can be viewed as `other` with
+    /// [`Features::TEXTURE_FORMAT_REINTERPRETATION`].
+    ///
+    /// Both formats have to be single-plane color formats whose texel blocks
+    /// occupy the same number of bytes. A compressed format can be viewed as an
+    /// uncompressed one, which then sees each block as a single texel, so the
+    /// view is smaller than the texture by the block dimensions. The opposite
+    /// isn't allowed.
+    pub fn is_reinterpretable_as(&self, other: TextureFormat) -> bool {
+        let is_color =
+            |format: &TextureFormat| format.has_color_aspect() && !format.is_multi_planar_format();
+        if !is_color(self) || !is_color(&other) {
+            return false;
+        }
+        if self.block_copy_size(None) != other.block_copy_size(None) {
+            return false;
+        }
+        self.block_dimensions() == other.block_dimensions() || !other.is_compressed()
+    }
+}
+
+#[test]
+fn texture_format_reinterpretation() {
+    use TextureFormat as Tf;
+
+    assert!(Tf::Rgba8Unorm.is_reinterpretable_as(Tf::Rgba8Uint));
+    assert!(Tf::Rgba8Unorm.is_reinterpretable_as(Tf::R32Float));
+    assert!(Tf::Bc1RgbaUnorm.is_reinterpretable_as(Tf::Rg32Uint));
+    assert!(Tf::Bc7RgbaUnorm.is_reinterpretable_as(Tf::Rgba32Uint));
+    assert!(Tf::Bc1RgbaUnorm.is_reinterpretable_as(Tf::Bc4RUnorm));
+    // Only compressed formats can be viewed as uncompressed ones.
+    assert!(!Tf::Rg32Uint.is_reinterpretable_as(Tf::Bc1RgbaUnorm));
+    assert!(!Tf::Bc1RgbaUnorm.is_reinterpretable_as(Tf::Rgba32Uint));
+    assert!(!Tf::Rgba8Unorm.is_reinterpretable_as(Tf::Rg8Unorm));
+    assert!(!Tf::R32Float.is_reinterpretable_as(Tf::Depth32Float));
+    assert!(!Tf::Rg16Unorm.is_reinterpretable_as(Tf::NV12));
 }
 
 #[test]
@@ -4116,6 +5286,21 @@ fn texture_format_serialize() {
     );
 }
 
+#[test]
+fn sample_count_mask() {
+    use TextureFormatFeatureFlags as Tfff;
+
+    assert_eq!(Tfff::empty().sample_count_mask(), 1);
+    assert_eq!(
+        (Tfff::FILTERABLE | Tfff::MULTISAMPLE_X4).sample_count_mask(),
+        1 | 4
+    );
+    assert_eq!(
+        (Tfff::MULTISAMPLE_X2 | Tfff::MULTISAMPLE_X8 | Tfff::MULTISAMPLE_X16).sample_count_mask(),
+        1 | 2 | 8 | 16
+    );
+}
+
 #[test]
 fn texture_format_deserialize() {
     assert_eq!(
@@ -4456,6 +5641,11 @@ pub enum Maintain<T> {
     Wait,
     /// Check the device for a single time without blocking.
     Poll,
+    /// Same as Poll, but only retire submissions up to and including the
+    /// given one, even if later ones have completed too.
+    ///
+    /// On WebGPU, this has no effect.
+    PollForSubmissionIndex(T),
 }
 
 impl<T> Maintain<T> {
@@ -4479,7 +5669,15 @@ impl<T> Maintain<T> {
     pub fn is_wait(&self) -> bool {
         match *self {
             Self::WaitForSubmissionIndex(..) | Self::Wait => true,
-            Self::Poll => false,
+            Self::Poll | Self::PollForSubmissionIndex(..) => false,
+        }
+    }
+
+    /// The submission index of this maintain, if it has one.
+    pub fn submission_index(&self) -> Option<&T> {
+        match *self {
+            Self::WaitForSubmissionIndex(ref i) | Self::PollForSubmissionIndex(ref i) => Some(i),
+            Self::Wait | Self::Poll => None,
         }
     }
 
@@ -4492,10 +5690,43 @@ impl<T> Maintain<T> {
             Self::WaitForSubmissionIndex(i) => Maintain::WaitForSubmissionIndex(func(i)),
             Self::Wait => Maintain::Wait,
             Self::Poll => Maintain::Poll,
+            Self::PollForSubmissionIndex(i) => Maintain::PollForSubmissionIndex(func(i)),
         }
     }
 }
 
+bitflags::bitflags! {
+    /// The work a maintain operation does, passed to `Device::poll_work`.
+    ///
+    /// Polling with only some of the flags keeps latency-critical threads from
+    /// doing the rest, which another thread can take care of by polling with
+    /// the remaining flags.
+    #[repr(transparent)]
+    #[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
+    pub struct MaintainWork: u8 {
+        /// Retire the submissions that have completed, and invoke their
+        /// `on_submitted_work_done` closures.
+        const SUBMISSIONS = 1 << 0;
+        /// Map the buffers whose last submission has been retired, and invoke
+        /// their `map_async` callbacks.
+        ///
+        /// With `DeviceFlags::DETERMINISTIC_CALLBACKS`, mappings and
+        /// submissions are handled one submission at a time, so they are only
+        /// handled when both flags are passed.
+        const MAPPINGS = 1 << 1;
+        /// Find the resources that are no longer used, and destroy them.
+        const CLEANUP = 1 << 2;
+    }
+}
+
+impl_bitflags!(MaintainWork);
+
+impl Default for MaintainWork {
+    fn default() -> Self {
+        Self::all()
+    }
+}
+
 /// Result of a maintain operation.
 pub enum MaintainResult {
     /// There are no active submissions in flight as of the beginning of the poll call.
@@ -5018,13 +6249,15 @@ bitflags::bitflags! {
         /// Allow a buffer to be mapped for reading using [`Buffer::map_async`] + [`Buffer::get_mapped_range`].
         /// This does not include creating a buffer with [`BufferDescriptor::mapped_at_creation`] set.
         ///
-        /// If [`Features::MAPPABLE_PRIMARY_BUFFERS`] isn't enabled, the only other usage a buffer
+        /// If [`Features::MAPPABLE_PRIMARY_BUFFERS`] isn't enabled and the adapter doesn't report
+        /// [`DownlevelFlags::UNIFIED_MEMORY_MAPPABLE_BUFFERS`], the only other usage a buffer
         /// may have is COPY_DST.
         const MAP_READ = 1 << 0;
         /// Allow a buffer to be mapped for writing using [`Buffer::map_async`] + [`Buffer::get_mapped_range_mut`].
         /// This does not include creating a buffer with `mapped_at_creation` set.
         ///
-        /// If [`Features::MAPPABLE_PRIMARY_BUFFERS`] feature isn't enabled, the only other usage a buffer
+        /// If [`Features::MAPPABLE_PRIMARY_BUFFERS`] feature isn't enabled and the adapter doesn't report
+        /// [`DownlevelFlags::UNIFIED_MEMORY_MAPPABLE_BUFFERS`], the only other usage a buffer
         /// may have is COPY_SRC.
         const MAP_WRITE = 1 << 1;
         /// Allow a buffer to be the source buffer for a [`CommandEncoder::copy_buffer_to_buffer`] or [`CommandEncoder::copy_buffer_to_texture`]
@@ -5068,21 +6301,91 @@ pub struct BufferDescriptor<L> {
     /// Allows a buffer to be mapped immediately after they are made. It does not have to be [`BufferUsages::MAP_READ`] or
     /// [`BufferUsages::MAP_WRITE`], all buffers are allowed to be mapped at creation.
     ///
-    /// If this is `true`, [`size`](#structfield.size) must be a multiple of
-    /// [`COPY_BUFFER_ALIGNMENT`].
-    pub mapped_at_creation: bool,
+    /// If this is `true`, [`size`](#structfield.size) must be a multiple of
+    /// [`COPY_BUFFER_ALIGNMENT`].
+    pub mapped_at_creation: bool,
+}
+
+impl<L> BufferDescriptor<L> {
+    /// Takes a closure and maps the label of the buffer descriptor into another.
+    pub fn map_label<K>(&self, fun: impl FnOnce(&L) -> K) -> BufferDescriptor<K> {
+        BufferDescriptor {
+            label: fun(&self.label),
+            size: self.size,
+            usage: self.usage,
+            mapped_at_creation: self.mapped_at_creation,
+        }
+    }
+}
+
+/// Commits a range of a sparse buffer to memory, or decommits it.
+///
+/// `offset` and `size` must be multiples of [`SPARSE_BUFFER_PAGE_SIZE`], except
+/// that the range can end at the end of the buffer.
+///
+/// Used by [`Queue::bind_sparse`](../wgpu/struct.Queue.html#method.bind_sparse),
+/// see [`Features::SPARSE_BUFFER`].
+#[repr(C)]
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct SparseBufferBind {
+    /// Offset of the range in the buffer, in bytes.
+    pub offset: BufferAddress,
+    /// Size of the range, in bytes.
+    pub size: BufferAddress,
+    /// Whether the pages of the range are committed to memory or decommitted.
+    ///
+    /// Pages that already are committed keep their memory, but are zeroed
+    /// again like newly committed ones. Decommitting pages that are not
+    /// committed does nothing.
+    pub commit: bool,
+}
+
+/// The tiles the memory of a sparse texture is committed in.
+///
+/// Returned by [`Texture::sparse_tiles`](../wgpu/struct.Texture.html#method.sparse_tiles),
+/// see [`Features::SPARSE_TEXTURE`].
+#[repr(C)]
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct SparseTextureTiles {
+    /// Size of a tile, in texels.
+    pub tile_size: Extent3d,
+    /// The first mip level of the mip tail.
+    ///
+    /// The levels from this one on are too small to be split into tiles, and are
+    /// committed and decommitted all at once, for each array layer. It is equal
+    /// to the mip level count of the texture if there is no mip tail.
+    pub mip_tail_first_level: u32,
 }
 
-impl<L> BufferDescriptor<L> {
-    /// Takes a closure and maps the label of the buffer descriptor into another.
-    pub fn map_label<K>(&self, fun: impl FnOnce(&L) -> K) -> BufferDescriptor<K> {
-        BufferDescriptor {
-            label: fun(&self.label),
-            size: self.size,
-            usage: self.usage,
-            mapped_at_creation: self.mapped_at_creation,
-        }
-    }
+/// Commits a region of a mip level of a sparse texture to memory, or decommits it.
+///
+/// `origin` and `size` must be multiples of [`SparseTextureTiles::tile_size`],
+/// except that the region can end at the edge of the mip level. Regions of levels
+/// in the mip tail must cover the whole level, and commit or decommit the whole
+/// mip tail of `array_layer`.
+///
+/// Used by [`Queue::bind_sparse_texture`](../wgpu/struct.Queue.html#method.bind_sparse_texture),
+/// see [`Features::SPARSE_TEXTURE`].
+#[repr(C)]
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct SparseTextureBind {
+    /// Mip level of the region.
+    pub mip_level: u32,
+    /// Array layer of the region, which must be 0 for 3D textures.
+    pub array_layer: u32,
+    /// Origin of the region, in texels.
+    pub origin: Origin3d,
+    /// Size of the region, in texels.
+    pub size: Extent3d,
+    /// Whether the tiles of the region are committed to memory or decommitted.
+    ///
+    /// Tiles that already are committed keep their memory, but are zeroed
+    /// again like newly committed ones. Decommitting tiles that are not
+    /// committed does nothing.
+    pub commit: bool,
 }
 
 /// Describes a [`CommandEncoder`](../wgpu/struct.CommandEncoder.html).
@@ -5221,6 +6524,276 @@ impl Default for CompositeAlphaMode {
     }
 }
 
+/// Rotation between the orientation surface textures are rendered in and the
+/// orientation of the display.
+///
+/// On Android and many embedded displays, the panel has a native orientation
+/// and the compositor rotates every frame of a surface configured with
+/// [`Identity`] to match how the device is held. Rendering with the surface's
+/// current transform instead, and configuring it as
+/// [`SurfaceConfiguration::pre_transform`], lets the frame go to the display
+/// unchanged, saving that full-screen pass.
+///
+/// Rotations are clockwise.
+///
+/// [`Identity`]: SurfaceTransform::Identity
+#[repr(C)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub enum SurfaceTransform {
+    /// The surface textures are displayed as is.
+    #[default]
+    Identity = 0,
+    /// The surface textures are rotated by 90 degrees.
+    Rotate90 = 1,
+    /// The surface textures are rotated by 180 degrees.
+    Rotate180 = 2,
+    /// The surface textures are rotated by 270 degrees.
+    Rotate270 = 3,
+}
+
+impl SurfaceTransform {
+    /// Returns true if the width and height of the display are swapped
+    /// relative to the surface textures.
+    pub fn swaps_dimensions(self) -> bool {
+        matches!(self, Self::Rotate90 | Self::Rotate270)
+    }
+
+    /// Returns the matrix rotating clip space positions so that content appears
+    /// upright on a surface configured with this transform.
+    ///
+    /// The matrix is column-major and is meant to be applied after the
+    /// projection, i.e. `compensation_matrix * projection * view * model`.
+    /// The projection's aspect ratio should be that of the display, which has
+    /// width and height swapped if [`Self::swaps_dimensions`] is true.
+    pub fn compensation_matrix(self) -> [[f32; 4]; 4] {
+        // (sin, cos) of the clockwise rotation, in clip space where y is up.
+        let (sin, cos) = match self {
+            Self::Identity => (0.0, 1.0),
+            Self::Rotate90 => (-1.0, 0.0),
+            Self::Rotate180 => (0.0, -1.0),
+            Self::Rotate270 => (1.0, 0.0),
+        };
+        [
+            [cos, sin, 0.0, 0.0],
+            [-sin, cos, 0.0, 0.0],
+            [0.0, 0.0, 1.0, 0.0],
+            [0.0, 0.0, 0.0, 1.0],
+        ]
+    }
+
+    /// Folds the rotation of [`Self::compensation_matrix`] into the column-major
+    /// `projection` matrix, returning `compensation_matrix * projection`.
+    ///
+    /// Only the first two rows of `projection` change, so the depth of the
+    /// projected positions is kept.
+    pub fn prerotate(self, projection: [[f32; 4]; 4]) -> [[f32; 4]; 4] {
+        let rotation = self.compensation_matrix();
+        projection.map(|column| {
+            let mut rotated = column;
+            for (row, value) in rotated.iter_mut().enumerate().take(2) {
+                *value = rotation[0][row] * column[0] + rotation[1][row] * column[1];
+            }
+            rotated
+        })
+    }
+}
+
+#[test]
+fn test_surface_transform_prerotate() {
+    const IDENTITY: [[f32; 4]; 4] = [
+        [1.0, 0.0, 0.0, 0.0],
+        [0.0, 1.0, 0.0, 0.0],
+        [0.0, 0.0, 1.0, 0.0],
+        [0.0, 0.0, 0.0, 1.0],
+    ];
+    let transforms = [
+        SurfaceTransform::Identity,
+        SurfaceTransform::Rotate90,
+        SurfaceTransform::Rotate180,
+        SurfaceTransform::Rotate270,
+    ];
+    for transform in transforms {
+        assert_eq!(
+            transform.prerotate(IDENTITY),
+            transform.compensation_matrix()
+        );
+    }
+    assert_eq!(SurfaceTransform::Identity.prerotate(IDENTITY), IDENTITY);
+    assert_eq!(
+        SurfaceTransform::Rotate90.prerotate(SurfaceTransform::Rotate270.compensation_matrix()),
+        IDENTITY
+    );
+    assert_eq!(
+        SurfaceTransform::Rotate180.prerotate(SurfaceTransform::Rotate180.compensation_matrix()),
+        IDENTITY
+    );
+
+    // x and y are rotated, depth is kept.
+    let projection = [
+        [2.0, 0.0, 0.0, 0.0],
+        [0.0, 3.0, 0.0, 0.0],
+        [0.0, 0.0, 0.5, 0.0],
+        [0.0, 0.0, 0.5, 1.0],
+    ];
+    assert_eq!(
+        SurfaceTransform::Rotate90.prerotate(projection),
+        [
+            [0.0, -2.0, 0.0, 0.0],
+            [3.0, 0.0, 0.0, 0.0],
+            [0.0, 0.0, 0.5, 0.0],
+            [0.0, 0.0, 0.5, 1.0],
+        ]
+    );
+}
+
+/// Color primaries that the color values of a surface or texture refer to.
+///
+/// All of these use the D65 white point. The transfer function is given by the
+/// texture format, like it is for sRGB: values of `*Srgb` and other 8 and
+/// 10 bit normalized formats are encoded with the sRGB curve, while floating
+/// point formats hold linear values, possibly outside of `0.0..=1.0`.
+///
+/// A surface configured with [`SurfaceConfiguration::color_space`] is
+/// displayed accordingly. The presentation engine does not convert the colors
+/// of a frame between spaces; [`ColorSpace::conversion_matrix`] does this for
+/// linear values.
+#[repr(C)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub enum ColorSpace {
+    /// sRGB / BT.709 primaries.
+    #[default]
+    Srgb,
+    /// Display P3 primaries, used by most wide gamut displays.
+    DisplayP3,
+    /// BT.2020 primaries. Only supported by surfaces with a floating point format.
+    Bt2020,
+    /// BT.2020 primaries with the SMPTE ST 2084 (PQ) transfer function, as
+    /// used by HDR10.
+    ///
+    /// Unlike the other color spaces, values are encoded with the PQ curve
+    /// whatever the format, where `1.0` is 10000 nits. Only supported by
+    /// surfaces of [`TextureFormat::Rgb10a2Unorm`]. Use
+    /// [`HdrMetadata`] to describe the content to the display.
+    Hdr10,
+}
+
+impl ColorSpace {
+    /// Matrix from linear RGB in this color space to CIE XYZ, row-major.
+    fn to_xyz(self) -> [[f64; 3]; 3] {
+        match self {
+            Self::Srgb => [
+                [0.4123908, 0.3575843, 0.1804808],
+                [0.2126390, 0.7151687, 0.0721923],
+                [0.0193308, 0.1191948, 0.9505322],
+            ],
+            Self::DisplayP3 => [
+                [0.4865709, 0.2656677, 0.1982173],
+                [0.2289746, 0.6917385, 0.0792869],
+                [0.0000000, 0.0451134, 1.0439444],
+            ],
+            Self::Bt2020 | Self::Hdr10 => [
+                [0.6369580, 0.1446169, 0.1688810],
+                [0.2627002, 0.6779981, 0.0593017],
+                [0.0000000, 0.0280727, 1.0609851],
+            ],
+        }
+    }
+
+    /// Returns the matrix converting linear color values in this color space
+    /// to linear values in `target`.
+    ///
+    /// The matrix is column-major, as expected by shaders. Colors outside of
+    /// the gamut of `target` get components outside of `0.0..=1.0`. Values in
+    /// [`ColorSpace::Hdr10`] have to be decoded from PQ first.
+    pub fn conversion_matrix(self, target: Self) -> [[f32; 3]; 3] {
+        let from = self.to_xyz();
+        let m = target.to_xyz();
+        // Invert `m` through its adjugate.
+        let cofactor = |r: usize, c: usize| {
+            let (r0, r1) = ((r + 1) % 3, (r + 2) % 3);
+            let (c0, c1) = ((c + 1) % 3, (c + 2) % 3);
+            m[r0][c0] * m[r1][c1] - m[r0][c1] * m[r1][c0]
+        };
+        let det = (0..3).map(|c| m[0][c] * cofactor(0, c)).sum::<f64>();
+        let inverse = |r: usize, c: usize| cofactor(c, r) / det;
+
+        let mut columns = [[0.0; 3]; 3];
+        for (c, column) in columns.iter_mut().enumerate() {
+            for (r, value) in column.iter_mut().enumerate() {
+                *value = (0..3).map(|k| inverse(r, k) * from[k][c]).sum::<f64>() as f32;
+            }
+        }
+        columns
+    }
+}
+
+impl From<PredefinedColorSpace> for ColorSpace {
+    fn from(color_space: PredefinedColorSpace) -> Self {
+        match color_space {
+            PredefinedColorSpace::Srgb => Self::Srgb,
+            PredefinedColorSpace::DisplayP3 => Self::DisplayP3,
+        }
+    }
+}
+
+/// Static HDR metadata describing the content of a surface to the display, as
+/// defined by SMPTE ST 2086 and CTA-861.3 for HDR10.
+///
+/// Displays use it to tone map content they can't show as is. Chromaticities
+/// are CIE 1931 `[x, y]` coordinates, luminances are in nits.
+#[repr(C)]
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct HdrMetadata {
+    /// Chromaticity of the red primary of the mastering display.
+    pub red_primary: [f32; 2],
+    /// Chromaticity of the green primary of the mastering display.
+    pub green_primary: [f32; 2],
+    /// Chromaticity of the blue primary of the mastering display.
+    pub blue_primary: [f32; 2],
+    /// Chromaticity of the white point of the mastering display.
+    pub white_point: [f32; 2],
+    /// Maximum luminance of the mastering display.
+    pub max_luminance: f32,
+    /// Minimum luminance of the mastering display.
+    pub min_luminance: f32,
+    /// Maximum luminance of any pixel of the content, or `0.0` if unknown.
+    pub max_content_light_level: f32,
+    /// Maximum average luminance of any frame of the content, or `0.0` if
+    /// unknown.
+    pub max_frame_average_light_level: f32,
+}
+
+impl HdrMetadata {
+    /// The D65 white point all [`ColorSpace`]s use.
+    pub const WHITE_POINT_D65: [f32; 2] = [0.3127, 0.3290];
+
+    /// Returns metadata for content mastered with the primaries of
+    /// `color_space`, between `0.0` and `max_luminance` nits, leaving the light
+    /// levels of the content unknown.
+    pub fn new(color_space: ColorSpace, max_luminance: f32) -> Self {
+        let [red_primary, green_primary, blue_primary] = match color_space {
+            ColorSpace::Srgb => [[0.640, 0.330], [0.300, 0.600], [0.150, 0.060]],
+            ColorSpace::DisplayP3 => [[0.680, 0.320], [0.265, 0.690], [0.150, 0.060]],
+            ColorSpace::Bt2020 | ColorSpace::Hdr10 => {
+                [[0.708, 0.292], [0.170, 0.797], [0.131, 0.046]]
+            }
+        };
+        Self {
+            red_primary,
+            green_primary,
+            blue_primary,
+            white_point: Self::WHITE_POINT_D65,
+            max_luminance,
+            min_luminance: 0.0,
+            max_content_light_level: 0.0,
+            max_frame_average_light_level: 0.0,
+        }
+    }
+}
+
 bitflags::bitflags! {
     /// Different ways that you can use a texture.
     ///
@@ -5244,11 +6817,64 @@ bitflags::bitflags! {
         const STORAGE_BINDING = 1 << 3;
         /// Allows a texture to be an output attachment of a render pass.
         const RENDER_ATTACHMENT = 1 << 4;
+        /// Allows a texture to be backed by no memory at all, or by memory that is only
+        /// allocated if the GPU runs out of on-chip tile memory.
+        ///
+        /// Must be combined with [`TextureUsages::RENDER_ATTACHMENT`] and nothing else. The
+        /// contents of such a texture never leave the render pass that uses it: every
+        /// attachment of it must have a [`LoadOp::Clear`] and a [`StoreOp::Discard`], and it
+        /// can't be a resolve target or cleared with [`CommandEncoder::clear_texture`]. This
+        /// suits depth buffers and multisampled color targets that are resolved in the pass.
+        ///
+        /// This is a hint: Vulkan uses lazily allocated memory if there is any, and Metal uses
+        /// memoryless textures on Apple GPUs. Other backends, and WebGPU, allocate such a
+        /// texture like any other.
+        ///
+        /// [`LoadOp::Clear`]: ../wgpu/enum.LoadOp.html#variant.Clear
+        /// [`StoreOp::Discard`]: ../wgpu/enum.StoreOp.html#variant.Discard
+        const TRANSIENT = 1 << 5;
     }
 }
 
 impl_bitflags!(TextureUsages);
 
+/// How a surface covers the display it's shown on, see
+/// [`SurfaceConfiguration::fullscreen`].
+#[repr(C)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub enum FullscreenMode {
+    /// The surface is presented like any window, and the platform decides on
+    /// its own whether it bypasses the compositor when it covers the display.
+    #[default]
+    Windowed,
+    /// The surface covers the display, and stays composited with the desktop,
+    /// so that other windows can show over it and switching away is instant.
+    /// The platform may still present it directly while nothing else shows,
+    /// like the independent flip of DXGI, but never takes the display over.
+    ///
+    /// The window of the surface has to be made to cover the display by the
+    /// application.
+    Borderless,
+    /// The surface owns the display, bypassing the compositor, and can drive it
+    /// in a [`DisplayMode`] of its choosing. Losing the focus leaves the mode.
+    Exclusive,
+}
+
+/// A resolution and refresh rate a display can be driven in, see
+/// [`SurfaceConfiguration::display_mode`].
+#[repr(C)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct DisplayMode {
+    /// Width of the mode, in pixels.
+    pub width: u32,
+    /// Height of the mode, in pixels.
+    pub height: u32,
+    /// Refresh rate of the mode, in millihertz.
+    pub refresh_rate_millihertz: u32,
+}
+
 /// Defines the capabilities of a given surface and adapter.
 #[derive(Debug)]
 pub struct SurfaceCapabilities {
@@ -5268,6 +6894,44 @@ pub struct SurfaceCapabilities {
     ///
     /// The usage TextureUsages::RENDER_ATTACHMENT is guaranteed.
     pub usages: TextureUsages,
+    /// List of supported values for [`SurfaceConfiguration::pre_transform`].
+    ///
+    /// Always contains [`SurfaceTransform::Identity`]. Surfaces that don't
+    /// support it natively are configured with [`Self::current_transform`]
+    /// instead, so their textures may show up rotated unless rendered with
+    /// that transform.
+    pub transforms: Vec<SurfaceTransform>,
+    /// The rotation of the display relative to its native orientation.
+    ///
+    /// Configuring the surface with this as its `pre_transform`, and rendering
+    /// accordingly, avoids the compositor rotating each frame.
+    pub current_transform: SurfaceTransform,
+    /// Combinations of a format of `formats` and the [`ColorSpace`] the
+    /// surface can be configured with.
+    ///
+    /// Every format is listed at least with [`ColorSpace::Srgb`].
+    pub color_spaces: Vec<(TextureFormat, ColorSpace)>,
+    /// The largest [`SurfaceConfiguration::array_layer_count`] the surface can
+    /// be configured with.
+    ///
+    /// This is 1 unless the surface is backed by a display showing several
+    /// views at once, such as a stereoscopic or holographic display.
+    pub max_array_layers: u32,
+    /// List of supported values for [`SurfaceConfiguration::fullscreen`].
+    ///
+    /// Will return at least one element, FullscreenMode::Windowed.
+    pub fullscreen_modes: Vec<FullscreenMode>,
+    /// The modes the display the surface is on can be driven in with
+    /// [`FullscreenMode::Exclusive`], from the largest and fastest.
+    ///
+    /// Empty if the backend can't choose the mode of the display.
+    pub display_modes: Vec<DisplayMode>,
+    /// The smallest [`SurfaceConfiguration::desired_minimum_image_count`]
+    /// the surface can be configured with.
+    pub min_image_count: u32,
+    /// The largest [`SurfaceConfiguration::desired_minimum_image_count`]
+    /// the surface can be configured with.
+    pub max_image_count: u32,
 }
 
 impl Default for SurfaceCapabilities {
@@ -5277,6 +6941,14 @@ impl Default for SurfaceCapabilities {
             present_modes: Vec::new(),
             alpha_modes: vec![CompositeAlphaMode::Opaque],
             usages: TextureUsages::RENDER_ATTACHMENT,
+            transforms: vec![SurfaceTransform::Identity],
+            current_transform: SurfaceTransform::Identity,
+            color_spaces: Vec::new(),
+            max_array_layers: 1,
+            fullscreen_modes: vec![FullscreenMode::Windowed],
+            display_modes: Vec::new(),
+            min_image_count: 2,
+            max_image_count: 2,
         }
     }
 }
@@ -5322,6 +6994,17 @@ pub struct SurfaceConfiguration<V> {
     /// It is currently not possible to query this. See <https://github.com/gfx-rs/wgpu/issues/2869>.
     /// * A value of 0 is generally not supported and always clamped to a higher value.
     pub desired_maximum_frame_latency: u32,
+    /// Desired minimum number of textures in the swap chain, or `None` to
+    /// use `desired_maximum_frame_latency + 1`.
+    ///
+    /// Choose 2 for double buffering, with the lowest latency, or 3 and more
+    /// to keep rendering while previous frames wait to be shown. This is
+    /// clamped to [`SurfaceCapabilities::min_image_count`] and
+    /// [`SurfaceCapabilities::max_image_count`], and the presentation engine
+    /// may create more textures than requested; the actual number is
+    /// returned by `wgpu::Surface::image_count`.
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub desired_minimum_image_count: Option<u32>,
     /// Specifies how the alpha channel of the textures should be handled during compositing.
     pub alpha_mode: CompositeAlphaMode,
     /// Specifies what view formats will be allowed when calling create_view() on texture returned by get_current_texture().
@@ -5330,6 +7013,41 @@ pub struct SurfaceConfiguration<V> {
     ///
     /// Note: currently, only the srgb-ness is allowed to change. (ex: Rgba8Unorm texture + Rgba8UnormSrgb view)
     pub view_formats: V,
+    /// The rotation the application renders the surface textures with, which
+    /// the presentation engine then doesn't have to apply.
+    ///
+    /// Must be one of [`SurfaceCapabilities::transforms`]. If this swaps
+    /// dimensions, `width` and `height` are those of the display in its native
+    /// orientation. Use [`SurfaceTransform::compensation_matrix`] to render
+    /// rotated content.
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub pre_transform: SurfaceTransform,
+    /// The color space the surface textures are displayed in.
+    ///
+    /// Together with `format`, must be one of [`SurfaceCapabilities::color_spaces`].
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub color_space: ColorSpace,
+    /// The number of array layers of the surface textures, one for each of
+    /// the views presented at once, such as the eyes of a stereoscopic display.
+    ///
+    /// Must be at least 1, and at most [`SurfaceCapabilities::max_array_layers`].
+    /// The surface textures of a configuration with more than one layer are 2D
+    /// array textures, which can be rendered to with a view of each layer, or
+    /// with a single multiview render pass.
+    #[cfg_attr(feature = "serde", serde(default = "default_depth"))]
+    pub array_layer_count: u32,
+    /// How the surface covers the display it's shown on.
+    ///
+    /// Must be one of [`SurfaceCapabilities::fullscreen_modes`].
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub fullscreen: FullscreenMode,
+    /// The mode to drive the display in, or `None` to keep its current one.
+    ///
+    /// Can only be set with [`FullscreenMode::Exclusive`], to one of
+    /// [`SurfaceCapabilities::display_modes`]. `width` and `height` should
+    /// match the mode, the display scales the surface textures otherwise.
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub display_mode: Option<DisplayMode>,
 }
 
 impl<V: Clone> SurfaceConfiguration<V> {
@@ -5342,8 +7060,14 @@ impl<V: Clone> SurfaceConfiguration<V> {
             height: self.height,
             present_mode: self.present_mode,
             desired_maximum_frame_latency: self.desired_maximum_frame_latency,
+            desired_minimum_image_count: self.desired_minimum_image_count,
             alpha_mode: self.alpha_mode,
             view_formats: fun(self.view_formats.clone()),
+            pre_transform: self.pre_transform,
+            color_space: self.color_space,
+            array_layer_count: self.array_layer_count,
+            fullscreen: self.fullscreen,
+            display_mode: self.display_mode,
         }
     }
 }
@@ -5407,6 +7131,75 @@ impl PresentationTimestamp {
     }
 }
 
+/// When a frame presented to a surface was shown on the display.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub struct PresentationTiming {
+    /// Which present of the surface the frame was shown by, counting the
+    /// presents since the surface was last configured from 1.
+    pub present_id: u64,
+    /// The time the present was scheduled for, if it was.
+    pub desired_present_time: Option<PresentationTimestamp>,
+    /// The time the frame started being shown.
+    pub actual_present_time: PresentationTimestamp,
+}
+
+/// Feedback on a frame presented to a surface, part of a
+/// [`PresentationReport`].
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub struct FramePresentation {
+    /// Which present of the surface the frame was presented by, counting the
+    /// presents since the surface was last configured from 1.
+    pub present_id: u64,
+    /// Whether the frame was dropped, because a later frame was shown before
+    /// it was.
+    pub dropped: bool,
+    /// The time the frame started being shown, if it was.
+    pub actual_present_time: Option<PresentationTimestamp>,
+    /// How long the frame waited between being presented and being shown,
+    /// if it was shown and the backend has a presentation clock.
+    pub wait: Option<std::time::Duration>,
+}
+
+/// Presentation feedback of a surface, gathered from the presentation engine
+/// rather than guessed from CPU timers.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct PresentationReport {
+    /// The number of presents to the surface since it was last configured.
+    pub present_count: u64,
+    /// The frames that were shown or dropped since the last report, oldest
+    /// first. Frames still queued for presentation are part of a later report.
+    pub frames: Vec<FramePresentation>,
+}
+
+impl PresentationReport {
+    /// The number of frames of the report that were dropped.
+    pub fn dropped_frames(&self) -> usize {
+        self.frames.iter().filter(|frame| frame.dropped).count()
+    }
+
+    /// The longest wait of the frames of the report that were shown, if any
+    /// is known.
+    pub fn max_wait(&self) -> Option<std::time::Duration> {
+        self.frames.iter().filter_map(|frame| frame.wait).max()
+    }
+}
+
+/// A GPU timestamp of a queue, and the time of the CPU it was sampled at.
+///
+/// Two calibrations taken some time apart measure the actual rate of the GPU
+/// timestamp clock, which can drift away from the nominal timestamp period as
+/// the GPU changes power states. They also relate GPU timestamps to the time
+/// on the CPU.
+///
+/// The CPU time is taken from the same clock as [`PresentationTimestamp`].
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub struct TimestampCalibration {
+    /// A value of the timestamp queries of the queue, in GPU ticks.
+    pub gpu_timestamp: u64,
+    /// The time of the CPU at which `gpu_timestamp` was sampled, in nanoseconds.
+    pub cpu_timestamp: u64,
+}
+
 /// RGBA double precision color.
 ///
 /// This is not to be used as a generic color type, only for specific wgpu interfaces.
@@ -5465,6 +7258,77 @@ impl Color {
     };
 }
 
+/// Viewport of a render pass, mapping normalized device coordinates to a region of the
+/// attachments.
+///
+/// All coordinates are in pixels, `min_depth` and `max_depth` must be in `0.0..=1.0`.
+#[repr(C)]
+#[derive(Clone, Copy, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "camelCase"))]
+pub struct Viewport {
+    /// Left edge.
+    pub x: f32,
+    /// Top edge.
+    pub y: f32,
+    /// Width, must be greater than 0.
+    pub width: f32,
+    /// Height, must be greater than 0.
+    pub height: f32,
+    /// Depth that normalized device depth 0 is mapped to.
+    pub min_depth: f32,
+    /// Depth that normalized device depth 1 is mapped to.
+    pub max_depth: f32,
+}
+
+/// Scissor rectangle of a render pass, in pixels. Fragments outside of it are discarded.
+#[repr(C)]
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "camelCase"))]
+pub struct ScissorRect {
+    /// Left edge.
+    pub x: u32,
+    /// Top edge.
+    pub y: u32,
+    /// Width.
+    pub width: u32,
+    /// Height.
+    pub height: u32,
+}
+
+/// A region of a frame that changed since the previous frame presented to the
+/// surface, in pixels from the top-left corner of the surface.
+#[repr(C)]
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "camelCase"))]
+pub struct DamageRect {
+    /// Left edge.
+    pub x: u32,
+    /// Top edge.
+    pub y: u32,
+    /// Width.
+    pub width: u32,
+    /// Height.
+    pub height: u32,
+}
+
+impl DamageRect {
+    /// Returns the part of this rectangle within a surface of `width` by
+    /// `height` pixels, or `None` if there is none.
+    pub fn clip(self, width: u32, height: u32) -> Option<Self> {
+        let right = self.x.saturating_add(self.width).min(width);
+        let bottom = self.y.saturating_add(self.height).min(height);
+        (self.x < right && self.y < bottom).then(|| Self {
+            x: self.x,
+            y: self.y,
+            width: right - self.x,
+            height: bottom - self.y,
+        })
+    }
+}
+
 /// Dimensionality of a texture.
 ///
 /// Corresponds to [WebGPU `GPUTextureDimension`](
@@ -5788,7 +7652,8 @@ pub struct TextureDescriptor<L, V> {
     ///
     /// View formats of the same format as the texture are always allowed.
     ///
-    /// Note: currently, only the srgb-ness is allowed to change. (ex: Rgba8Unorm texture + Rgba8UnormSrgb view)
+    /// Note: only the srgb-ness is allowed to change (ex: Rgba8Unorm texture + Rgba8UnormSrgb view),
+    /// unless [`Features::TEXTURE_FORMAT_REINTERPRETATION`] is enabled.
     pub view_formats: V,
 }
 
@@ -5915,6 +7780,76 @@ pub enum TextureAspect {
     Plane2,
 }
 
+/// Source of a component of the texels read through a swizzled texture view.
+///
+/// See [`TextureComponentSwizzle`].
+#[repr(C)]
+#[derive(Copy, Clone, Debug, Hash, Eq, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "kebab-case"))]
+pub enum ComponentSwizzle {
+    /// Always 0.
+    Zero,
+    /// Always 1.
+    One,
+    /// The red component of the texel.
+    R,
+    /// The green component of the texel.
+    G,
+    /// The blue component of the texel.
+    B,
+    /// The alpha component of the texel.
+    A,
+}
+
+/// Rearrangement of the components of texels read through a texture view.
+///
+/// Each field names where the component of the same name, as seen by shaders,
+/// is taken from. Components that are missing from the format of the view read
+/// as they would without a swizzle, so `G` of an `R8Unorm` texture is 0, and `A`
+/// is 1.
+///
+/// For example, a single channel coverage mask can be sampled as the alpha of
+/// white texels with `{ r: One, g: One, b: One, a: R }`, and a texture holding
+/// BGRA data in an RGBA format can be read as RGBA with `{ r: B, g: G, b: R, a: A }`.
+///
+/// Anything other than [`TextureComponentSwizzle::IDENTITY`] requires
+/// [`Features::TEXTURE_COMPONENT_SWIZZLE`].
+#[repr(C)]
+#[derive(Copy, Clone, Debug, Hash, Eq, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct TextureComponentSwizzle {
+    /// Source of the red component.
+    pub r: ComponentSwizzle,
+    /// Source of the green component.
+    pub g: ComponentSwizzle,
+    /// Source of the blue component.
+    pub b: ComponentSwizzle,
+    /// Source of the alpha component.
+    pub a: ComponentSwizzle,
+}
+
+impl TextureComponentSwizzle {
+    /// Every component is read from itself.
+    pub const IDENTITY: Self = Self {
+        r: ComponentSwizzle::R,
+        g: ComponentSwizzle::G,
+        b: ComponentSwizzle::B,
+        a: ComponentSwizzle::A,
+    };
+
+    /// Returns true if this swizzle leaves every component as it is.
+    pub fn is_identity(&self) -> bool {
+        *self == Self::IDENTITY
+    }
+}
+
+impl Default for TextureComponentSwizzle {
+    fn default() -> Self {
+        Self::IDENTITY
+    }
+}
+
 /// How edges should be handled in texture addressing.
 ///
 /// Corresponds to [WebGPU `GPUAddressMode`](
@@ -5968,6 +7903,92 @@ pub enum FilterMode {
     Linear = 1,
 }
 
+/// Color model that Y'CbCr values are converted to RGB with.
+///
+/// Corresponds to [Vulkan `VkSamplerYcbcrModelConversion`](
+/// https://registry.khronos.org/vulkan/specs/1.3-extensions/man/html/VkSamplerYcbcrModelConversion.html).
+#[repr(C)]
+#[derive(Copy, Clone, Debug, Default, Hash, Eq, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "kebab-case"))]
+pub enum YcbcrModel {
+    /// ITU-R BT.601, as used by standard definition video.
+    Bt601,
+    /// ITU-R BT.709, as used by high definition video.
+    #[default]
+    Bt709,
+    /// ITU-R BT.2020, as used by ultra high definition video.
+    Bt2020,
+}
+
+/// Range of the encoded Y'CbCr values.
+///
+/// Corresponds to [Vulkan `VkSamplerYcbcrRange`](
+/// https://registry.khronos.org/vulkan/specs/1.3-extensions/man/html/VkSamplerYcbcrRange.html).
+#[repr(C)]
+#[derive(Copy, Clone, Debug, Default, Hash, Eq, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "kebab-case"))]
+pub enum YcbcrRange {
+    /// The values use the full range of their bits.
+    Full,
+    /// The values leave headroom and footroom, such as luma in \[16, 235\] for 8 bits.
+    #[default]
+    Narrow,
+}
+
+/// Location of the chroma samples relative to the luma samples they cover, along an axis.
+///
+/// Corresponds to [Vulkan `VkChromaLocation`](
+/// https://registry.khronos.org/vulkan/specs/1.3-extensions/man/html/VkChromaLocation.html).
+#[repr(C)]
+#[derive(Copy, Clone, Debug, Default, Hash, Eq, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "kebab-case"))]
+pub enum ChromaLocation {
+    /// Chroma samples are at the location of the even luma samples.
+    #[default]
+    CositedEven,
+    /// Chroma samples are halfway between the even and odd luma samples.
+    Midpoint,
+}
+
+/// Describes how a multi-planar YUV texture is converted to RGB when sampled.
+///
+/// Used by [`BindingType::YcbcrTexture`]. The default is limited range BT.709 with
+/// the chroma siting of MPEG-2 and later codecs, which is what most video decoders output.
+///
+/// Corresponds to [Vulkan `VkSamplerYcbcrConversionCreateInfo`](
+/// https://registry.khronos.org/vulkan/specs/1.3-extensions/man/html/VkSamplerYcbcrConversionCreateInfo.html).
+#[derive(Copy, Clone, Debug, Hash, Eq, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct SamplerYcbcrConversion {
+    /// Color model to convert the values with.
+    pub model: YcbcrModel,
+    /// Range of the encoded values.
+    pub range: YcbcrRange,
+    /// Horizontal location of the chroma samples.
+    pub x_chroma_offset: ChromaLocation,
+    /// Vertical location of the chroma samples.
+    pub y_chroma_offset: ChromaLocation,
+    /// How the chroma planes are filtered to the size of the luma plane.
+    ///
+    /// This is also the min and mag filter of the sampler the texture is sampled with.
+    pub chroma_filter: FilterMode,
+}
+
+impl Default for SamplerYcbcrConversion {
+    fn default() -> Self {
+        Self {
+            model: YcbcrModel::default(),
+            range: YcbcrRange::default(),
+            x_chroma_offset: ChromaLocation::CositedEven,
+            y_chroma_offset: ChromaLocation::Midpoint,
+            chroma_filter: FilterMode::Linear,
+        }
+    }
+}
+
 /// A range of push constant memory to pass to a shader stage.
 #[derive(Clone, Debug, PartialEq, Eq, Hash)]
 #[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
@@ -6469,6 +8490,36 @@ pub enum BindingType {
     /// uniform accelerationStructureEXT as;
     /// ```
     AccelerationStructure,
+
+    /// A multi-planar YUV texture that is converted to RGB when it is sampled.
+    ///
+    /// The texture is sampled as a 2D float texture. The sampler it is sampled
+    /// with in the shader is replaced by one made for `conversion`, which clamps
+    /// to the edge and filters with [`SamplerYcbcrConversion::chroma_filter`].
+    /// Only sampling without offsets is supported, and the texture can't be loaded from.
+    ///
+    /// The bound texture view must view all the planes of a single 2D layer and mip
+    /// level of a texture of `format`.
+    ///
+    /// Example WGSL syntax:
+    /// ```rust,ignore
+    /// @group(0) @binding(0)
+    /// var t: texture_2d<f32>;
+    /// ```
+    ///
+    /// Example GLSL syntax:
+    /// ```cpp,ignore
+    /// layout(binding = 0)
+    /// uniform sampler2D t;
+    /// ```
+    ///
+    /// [`Features::SAMPLER_YCBCR_CONVERSION`] must be enabled to use this binding type.
+    YcbcrTexture {
+        /// Multi-planar format of the texture, such as [`TextureFormat::NV12`].
+        format: TextureFormat,
+        /// How the texture is converted to RGB.
+        conversion: SamplerYcbcrConversion,
+    },
 }
 
 impl BindingType {
@@ -6804,7 +8855,7 @@ impl ImageSubresourceRange {
 
 /// Color variation to use when sampler addressing mode is [`AddressMode::ClampToBorder`]
 #[repr(C)]
-#[derive(Copy, Clone, Debug, Eq, PartialEq, Hash)]
+#[derive(Copy, Clone, Debug, PartialEq)]
 #[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum SamplerBorderColor {
     /// [0, 0, 0, 0]
@@ -6820,6 +8871,40 @@ pub enum SamplerBorderColor {
     /// this is equivalent to `TransparentBlack`. Requires
     /// [`Features::ADDRESS_MODE_CLAMP_TO_ZERO`]. Not supported on the web.
     Zero,
+
+    /// An arbitrary linear RGBA color, in the `[0, 1]` range for normalized
+    /// formats. Channels the texture doesn't have are ignored, like they would
+    /// be when sampling inside it. Requires
+    /// [`Features::ADDRESS_MODE_CLAMP_TO_CUSTOM_BORDER`]. Not supported on the
+    /// web.
+    ///
+    /// Where the feature is missing, the border can be emulated:
+    /// - with [`AddressMode::ClampToEdge`] and textures surrounded by a
+    ///   one texel wide border of the color, with the texture coordinates
+    ///   remapped to skip it. This filters like the hardware would.
+    /// - in the shader, by selecting the color when the coordinates are
+    ///   outside of `[0, 1]`. Filtering doesn't blend the edge texels with the
+    ///   color then, which is fine for comparison samplers used on shadow maps
+    ///   as long as the comparison is done on the selected value.
+    Custom([f32; 4]),
+}
+
+/// How a sampler combines the texels it filters.
+///
+/// Modes other than [`SamplerReductionMode::WeightedAverage`] require
+/// [`Features::SAMPLER_REDUCTION_MODE`], and can't be used by comparison samplers.
+#[repr(C)]
+#[derive(Copy, Clone, Debug, Default, Eq, PartialEq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum SamplerReductionMode {
+    /// Average of the texels, weighted by the filter. This is how all
+    /// samplers filter in WebGPU.
+    #[default]
+    WeightedAverage,
+    /// Component-wise minimum of the texels the filter would average.
+    Min,
+    /// Component-wise maximum of the texels the filter would average.
+    Max,
 }
 
 /// Describes how to create a QuerySet.
@@ -7036,6 +9121,50 @@ impl Default for ShaderBoundChecks {
     }
 }
 
+/// How the diagnostics a [`DiagnosticRule`] triggers in a WGSL shader are reported.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum DiagnosticSeverity {
+    /// The diagnostic is ignored.
+    Off,
+    /// The diagnostic is logged at the info level.
+    Info,
+    /// The diagnostic is logged as a warning.
+    Warning,
+    /// The diagnostic is a shader-creation error.
+    Error,
+}
+
+/// A rule that triggers diagnostics while parsing a WGSL shader.
+///
+/// WGSL names rules in `diagnostic` directives and attributes, given here with
+/// each variant.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum DiagnosticRule {
+    /// `derivative_uniformity`, for derivatives in non-uniform control flow.
+    /// Defaults to [`DiagnosticSeverity::Error`].
+    ///
+    /// Not analyzed by wgpu yet, so it never triggers.
+    DerivativeUniformity,
+    /// `naga.unreachable_code`, for statements following a `return`, `break`,
+    /// `continue` or `discard`. Defaults to [`DiagnosticSeverity::Warning`].
+    UnreachableCode,
+}
+
+/// Sets the severity of the diagnostics triggered by `rule` for a whole WGSL
+/// shader, as a `diagnostic` directive would.
+///
+/// Directives and attributes in the shader override it.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct DiagnosticFilter {
+    /// The rule the filter applies to.
+    pub rule: DiagnosticRule,
+    /// The severity the rule's diagnostics are reported with.
+    pub severity: DiagnosticSeverity,
+}
+
 /// Selects which DX12 shader compiler to use.
 ///
 /// If the `wgpu-hal/dx12-shader-compiler` feature isn't enabled then this will fall back
@@ -7243,3 +9372,76 @@ pub enum DeviceLostReason {
     /// will call the callback immediately, with this reason.
     DeviceInvalid = 4,
 }
+
+#[cfg(test)]
+mod tests {
+    use super::{AdapterInfo, Backend, DeviceType};
+
+    fn adapter_info(backend: Backend) -> AdapterInfo {
+        AdapterInfo {
+            name: String::new(),
+            vendor: 0x10DE,
+            device: 0x2684,
+            device_type: DeviceType::DiscreteGpu,
+            driver: String::new(),
+            driver_info: String::new(),
+            backend,
+            driver_version: None,
+            device_uuid: None,
+            device_luid: None,
+            memory_heaps: Vec::new(),
+            unified_memory: false,
+            resizable_bar: false,
+        }
+    }
+
+    #[test]
+    fn same_device_by_uuid() {
+        let mut vulkan = adapter_info(Backend::Vulkan);
+        let mut gl = adapter_info(Backend::Gl);
+        vulkan.device_uuid = Some([1; 16]);
+        gl.device_uuid = Some([1; 16]);
+        assert!(vulkan.is_same_device(&gl));
+
+        // The UUID wins over matching LUIDs and PCI IDs.
+        vulkan.device_luid = Some([2; 8]);
+        gl.device_luid = Some([2; 8]);
+        gl.device_uuid = Some([3; 16]);
+        assert!(!vulkan.is_same_device(&gl));
+    }
+
+    #[test]
+    fn same_device_by_luid() {
+        let mut vulkan = adapter_info(Backend::Vulkan);
+        let mut dx12 = adapter_info(Backend::Dx12);
+        vulkan.device_uuid = Some([1; 16]);
+        vulkan.device_luid = Some([2; 8]);
+        dx12.device_luid = Some([2; 8]);
+        assert!(vulkan.is_same_device(&dx12));
+
+        // The LUID wins over matching PCI IDs.
+        dx12.device_luid = Some([3; 8]);
+        assert!(!vulkan.is_same_device(&dx12));
+    }
+
+    #[test]
+    fn same_device_by_pci_ids() {
+        let mut vulkan = adapter_info(Backend::Vulkan);
+        let mut gl = adapter_info(Backend::Gl);
+        vulkan.device_uuid = Some([1; 16]);
+        gl.device_luid = Some([2; 8]);
+        assert!(vulkan.is_same_device(&gl));
+
+        gl.device = 0x2704;
+        assert!(!vulkan.is_same_device(&gl));
+
+        gl.device = vulkan.device;
+        gl.vendor = 0x1002;
+        assert!(!vulkan.is_same_device(&gl));
+
+        // Without a PCI vendor ID, there is nothing left to compare.
+        vulkan.vendor = 0;
+        gl.vendor = 0;
+        assert!(!vulkan.is_same_device(&gl));
+    }
+}
@@ -0,0 +1,149 @@
+//! Measures how creating bind groups scales with the number of threads creating them.
+//!
+//! Every thread creates its share of bind groups from the same layout and drops
+//! them again. With descriptors allocated from per-thread arenas, the time per
+//! bind group should stay flat as threads are added, rather than grow with the
+//! contention on a device-wide lock.
+#![cfg(not(target_arch = "wasm32"))]
+
+use criterion::*;
+use std::{
+    thread,
+    time::{Duration, Instant},
+};
+
+const BIND_GROUPS_PER_THREAD: usize = 1000;
+
+struct Context {
+    device: wgpu::Device,
+    queue: wgpu::Queue,
+    layout: wgpu::BindGroupLayout,
+    buffer: wgpu::Buffer,
+}
+
+impl Context {
+    fn new() -> Option<Self> {
+        let instance = wgpu::Instance::new(wgpu::InstanceDescriptor {
+            backends: wgpu::util::backend_bits_from_env().unwrap_or_default(),
+            flags: wgpu::InstanceFlags::empty(),
+            dx12_shader_compiler: wgpu::util::dx12_shader_compiler_from_env().unwrap_or_default(),
+            gles_minor_version: wgpu::util::gles_minor_version_from_env().unwrap_or_default(),
+        });
+        let adapter = pollster::block_on(wgpu::util::initialize_adapter_from_env_or_default(
+            &instance, None,
+        ))?;
+        let (device, queue) =
+            pollster::block_on(adapter.request_device(&wgpu::DeviceDescriptor::default(), None))
+                .ok()?;
+
+        let layout = device.create_bind_group_layout(&wgpu::BindGroupLayoutDescriptor {
+            label: None,
+            entries: &[
+                wgpu::BindGroupLayoutEntry {
+                    binding: 0,
+                    visibility: wgpu::ShaderStages::VERTEX_FRAGMENT,
+                    ty: wgpu::BindingType::Buffer {
+                        ty: wgpu::BufferBindingType::Uniform,
+                        has_dynamic_offset: false,
+                        min_binding_size: None,
+                    },
+                    count: None,
+                },
+                wgpu::BindGroupLayoutEntry {
+                    binding: 1,
+                    visibility: wgpu::ShaderStages::FRAGMENT,
+                    ty: wgpu::BindingType::Buffer {
+                        ty: wgpu::BufferBindingType::Storage { read_only: true },
+                        has_dynamic_offset: false,
+                        min_binding_size: None,
+                    },
+                    count: None,
+                },
+            ],
+        });
+        let buffer = device.create_buffer(&wgpu::BufferDescriptor {
+            label: None,
+            size: 256,
+            usage: wgpu::BufferUsages::UNIFORM | wgpu::BufferUsages::STORAGE,
+            mapped_at_creation: false,
+        });
+
+        Some(Self {
+            device,
+            queue,
+            layout,
+            buffer,
+        })
+    }
+
+    fn create_bind_groups(&self) {
+        let bind_groups: Vec<_> = (0..BIND_GROUPS_PER_THREAD)
+            .map(|_| {
+                self.device.create_bind_group(&wgpu::BindGroupDescriptor {
+                    label: None,
+                    layout: &self.layout,
+                    entries: &[
+                        wgpu::BindGroupEntry {
+                            binding: 0,
+                            resource: self.buffer.as_entire_binding(),
+                        },
+                        wgpu::BindGroupEntry {
+                            binding: 1,
+                            resource: self.buffer.as_entire_binding(),
+                        },
+                    ],
+                })
+            })
+            .collect();
+        drop(bind_groups);
+    }
+
+    /// Completes a submission, so the descriptors of dropped bind groups are recycled.
+    fn recycle(&self) {
+        self.queue.submit([]);
+        self.device.poll(wgpu::Maintain::Wait);
+    }
+}
+
+fn bind_group_creation(c: &mut Criterion) {
+    let Some(ctx) = Context::new() else {
+        eprintln!("No adapter found, skipping bind group creation benchmarks");
+        return;
+    };
+
+    let max_threads = thread::available_parallelism().map_or(1, |count| count.get());
+    let thread_counts = [1, 2, 4, 8, 16]
+        .into_iter()
+        .filter(|&threads| threads <= max_threads);
+
+    let mut group = c.benchmark_group("bind_group_creation");
+    for threads in thread_counts {
+        group.throughput(Throughput::Elements(
+            (threads * BIND_GROUPS_PER_THREAD) as u64,
+        ));
+        group.bench_with_input(
+            BenchmarkId::from_parameter(threads),
+            &threads,
+            |b, &threads| {
+                b.iter_custom(|iters| {
+                    let mut total = Duration::ZERO;
+                    for _ in 0..iters {
+                        let start = Instant::now();
+                        thread::scope(|scope| {
+                            for _ in 0..threads {
+                                scope.spawn(|| ctx.create_bind_groups());
+                            }
+                        });
+                        total += start.elapsed();
+                        ctx.recycle();
+                    }
+                    total
+                })
+            },
+        );
+    }
+    group.finish();
+}
+
+criterion_group!(benches, bind_group_creation);
+criterion_main!(benches);
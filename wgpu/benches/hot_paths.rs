@@ -0,0 +1,342 @@
+//! Measures the CPU cost of frequently called functions.
+//!
+//! Runs against the noop backend by default, so the numbers only contain the
+//! overhead of wgpu and wgpu-core. Set `WGPU_BACKEND` to measure a real backend
+//! instead, e.g. `WGPU_BACKEND=vulkan cargo bench --features noop --bench hot_paths`.
+//! Add the `ahash` feature to compare the hashers of wgpu-core's internal maps.
+//!
+//! Every benchmark performs [`CALLS`] calls per iteration and reports the
+//! throughput per call.
+#![cfg(not(target_arch = "wasm32"))]
+
+use criterion::*;
+use std::time::{Duration, Instant};
+
+const CALLS: usize = 1000;
+
+const SHADER: &str = r#"
+@group(0) @binding(0)
+var<uniform> color: vec4<f32>;
+
+@vertex
+fn vs_main(@builtin(vertex_index) index: u32) -> @builtin(position) vec4<f32> {
+    let uv = vec2<f32>(f32((index << 1u) & 2u), f32(index & 2u));
+    return vec4<f32>(uv * 2.0 - 1.0, 0.0, 1.0);
+}
+
+@fragment
+fn fs_main() -> @location(0) vec4<f32> {
+    return color;
+}
+"#;
+
+struct Context {
+    device: wgpu::Device,
+    queue: wgpu::Queue,
+    pipeline: wgpu::RenderPipeline,
+    bind_groups: [wgpu::BindGroup; 2],
+    view: wgpu::TextureView,
+}
+
+impl Context {
+    fn new() -> Option<Self> {
+        let instance = wgpu::Instance::new(wgpu::InstanceDescriptor {
+            backends: wgpu::util::backend_bits_from_env().unwrap_or(wgpu::Backends::NOOP),
+            flags: wgpu::InstanceFlags::empty(),
+            dx12_shader_compiler: wgpu::util::dx12_shader_compiler_from_env().unwrap_or_default(),
+            gles_minor_version: wgpu::util::gles_minor_version_from_env().unwrap_or_default(),
+        });
+        let adapter = pollster::block_on(instance.request_adapter(&Default::default()))?;
+        let (device, queue) =
+            pollster::block_on(adapter.request_device(&wgpu::DeviceDescriptor::default(), None))
+                .ok()?;
+
+        let layout = device.create_bind_group_layout(&wgpu::BindGroupLayoutDescriptor {
+            label: None,
+            entries: &[wgpu::BindGroupLayoutEntry {
+                binding: 0,
+                visibility: wgpu::ShaderStages::FRAGMENT,
+                ty: wgpu::BindingType::Buffer {
+                    ty: wgpu::BufferBindingType::Uniform,
+                    has_dynamic_offset: false,
+                    min_binding_size: wgpu::BufferSize::new(16),
+                },
+                count: None,
+            }],
+        });
+        let bind_groups = [0, 1].map(|_| {
+            let buffer = device.create_buffer(&wgpu::BufferDescriptor {
+                label: None,
+                size: 16,
+                usage: wgpu::BufferUsages::UNIFORM,
+                mapped_at_creation: false,
+            });
+            device.create_bind_group(&wgpu::BindGroupDescriptor {
+                label: None,
+                layout: &layout,
+                entries: &[wgpu::BindGroupEntry {
+                    binding: 0,
+                    resource: buffer.as_entire_binding(),
+                }],
+            })
+        });
+
+        let pipeline_layout = device.create_pipeline_layout(&wgpu::PipelineLayoutDescriptor {
+            label: None,
+            bind_group_layouts: &[&layout],
+            push_constant_ranges: &[],
+        });
+        let shader = device.create_shader_module(wgpu::ShaderModuleDescriptor {
+            label: None,
+            source: wgpu::ShaderSource::Wgsl(SHADER.into()),
+            diagnostic_filters: &[],
+        });
+        let pipeline = device.create_render_pipeline(&wgpu::RenderPipelineDescriptor {
+            label: None,
+            layout: Some(&pipeline_layout),
+            vertex: wgpu::VertexState {
+                module: &shader,
+                entry_point: "vs_main",
+                compilation_options: Default::default(),
+                buffers: &[],
+            },
+            primitive: wgpu::PrimitiveState::default(),
+            depth_stencil: None,
+            multisample: wgpu::MultisampleState::default(),
+            fragment: Some(wgpu::FragmentState {
+                module: &shader,
+                entry_point: "fs_main",
+                compilation_options: Default::default(),
+                targets: &[Some(wgpu::TextureFormat::Rgba8Unorm.into())],
+            }),
+            multiview: None,
+        });
+
+        let texture = device.create_texture(&wgpu::TextureDescriptor {
+            label: None,
+            size: wgpu::Extent3d {
+                width: 64,
+                height: 64,
+                depth_or_array_layers: 1,
+            },
+            mip_level_count: 1,
+            sample_count: 1,
+            dimension: wgpu::TextureDimension::D2,
+            format: wgpu::TextureFormat::Rgba8Unorm,
+            usage: wgpu::TextureUsages::RENDER_ATTACHMENT,
+            view_formats: &[],
+        });
+        let view = texture.create_view(&wgpu::TextureViewDescriptor::default());
+
+        Some(Self {
+            device,
+            queue,
+            pipeline,
+            bind_groups,
+            view,
+        })
+    }
+
+    fn create_buffer(&self, usage: wgpu::BufferUsages) -> wgpu::Buffer {
+        self.device.create_buffer(&wgpu::BufferDescriptor {
+            label: None,
+            size: 256,
+            usage,
+            mapped_at_creation: false,
+        })
+    }
+
+    /// Records a render pass with `record` and finishes the encoder.
+    fn encode_render_pass(
+        &self,
+        record: impl FnOnce(&mut wgpu::RenderPass<'_>),
+    ) -> wgpu::CommandBuffer {
+        let mut encoder = self
+            .device
+            .create_command_encoder(&wgpu::CommandEncoderDescriptor::default());
+        {
+            let mut pass = encoder.begin_render_pass(&wgpu::RenderPassDescriptor {
+                label: None,
+                color_attachments: &[Some(wgpu::RenderPassColorAttachment {
+                    view: &self.view,
+                    resolve_target: None,
+                    ops: wgpu::Operations::default(),
+                })],
+                depth_stencil_attachment: None,
+                timestamp_writes: None,
+                occlusion_query_set: None,
+            });
+            pass.set_pipeline(&self.pipeline);
+            record(&mut pass);
+        }
+        encoder.finish()
+    }
+
+    /// Waits for all submissions, so dropped resources are freed.
+    fn wait_idle(&self) {
+        self.queue.submit([]);
+        self.device.poll(wgpu::Maintain::Wait);
+    }
+}
+
+/// Times `iters` runs of `routine`, calling `cleanup` outside the measurement after each.
+fn measure<T>(
+    ctx: &Context,
+    iters: u64,
+    mut routine: impl FnMut() -> T,
+    mut cleanup: impl FnMut(T),
+) -> Duration {
+    let mut total = Duration::ZERO;
+    for _ in 0..iters {
+        let start = Instant::now();
+        let output = routine();
+        total += start.elapsed();
+        cleanup(output);
+        ctx.wait_idle();
+    }
+    total
+}
+
+fn hot_paths(c: &mut Criterion) {
+    let Some(ctx) = Context::new() else {
+        eprintln!("No adapter found, skipping hot path benchmarks");
+        return;
+    };
+
+    let mut group = c.benchmark_group("hot_paths");
+    group.throughput(Throughput::Elements(CALLS as u64));
+
+    group.bench_function("create_buffer", |b| {
+        b.iter_custom(|iters| {
+            measure(
+                &ctx,
+                iters,
+                || {
+                    (0..CALLS)
+                        .map(|_| ctx.create_buffer(wgpu::BufferUsages::UNIFORM))
+                        .collect::<Vec<_>>()
+                },
+                drop,
+            )
+        })
+    });
+
+    group.bench_function("set_bind_group", |b| {
+        b.iter_custom(|iters| {
+            measure(
+                &ctx,
+                iters,
+                || {
+                    ctx.encode_render_pass(|pass| {
+                        for i in 0..CALLS {
+                            pass.set_bind_group(0, &ctx.bind_groups[i % 2], &[]);
+                        }
+                    })
+                },
+                drop,
+            )
+        })
+    });
+
+    group.bench_function("draw", |b| {
+        b.iter_custom(|iters| {
+            measure(
+                &ctx,
+                iters,
+                || {
+                    ctx.encode_render_pass(|pass| {
+                        pass.set_bind_group(0, &ctx.bind_groups[0], &[]);
+                        for _ in 0..CALLS {
+                            pass.draw(0..3, 0..1);
+                        }
+                    })
+                },
+                drop,
+            )
+        })
+    });
+
+    group.bench_function("submit", |b| {
+        b.iter_custom(|iters| {
+            let mut total = Duration::ZERO;
+            for _ in 0..iters {
+                let command_buffers: Vec<_> = (0..CALLS)
+                    .map(|_| {
+                        ctx.device
+                            .create_command_encoder(&wgpu::CommandEncoderDescriptor::default())
+                            .finish()
+                    })
+                    .collect();
+                let start = Instant::now();
+                for command_buffer in command_buffers {
+                    ctx.queue.submit(Some(command_buffer));
+                }
+                total += start.elapsed();
+                ctx.wait_idle();
+            }
+            total
+        })
+    });
+
+    // Every buffer is used by a submission and dropped before polling, so the
+    // poll has to check and free all of them.
+    group.bench_function("poll_suspected_resources", |b| {
+        b.iter_custom(|iters| {
+            let mut total = Duration::ZERO;
+            for _ in 0..iters {
+                let mut encoder = ctx
+                    .device
+                    .create_command_encoder(&wgpu::CommandEncoderDescriptor::default());
+                for _ in 0..CALLS {
+                    let buffer = ctx.create_buffer(wgpu::BufferUsages::COPY_DST);
+                    encoder.clear_buffer(&buffer, 0, None);
+                }
+                ctx.queue.submit(Some(encoder.finish()));
+
+                let start = Instant::now();
+                ctx.device.poll(wgpu::Maintain::Wait);
+                total += start.elapsed();
+            }
+            total
+        })
+    });
+
+    // Every call creates, uses and drops a buffer and a bind group on it, and
+    // polls right away, so each poll only has a few suspected resources.
+    group.bench_function("churn_suspected_resources", |b| {
+        b.iter_custom(|iters| {
+            let layout = ctx.pipeline.get_bind_group_layout(0);
+            let mut total = Duration::ZERO;
+            for _ in 0..iters {
+                let start = Instant::now();
+                for _ in 0..CALLS {
+                    let buffer = ctx
+                        .create_buffer(wgpu::BufferUsages::UNIFORM | wgpu::BufferUsages::COPY_DST);
+                    let bind_group = ctx.device.create_bind_group(&wgpu::BindGroupDescriptor {
+                        label: None,
+                        layout: &layout,
+                        entries: &[wgpu::BindGroupEntry {
+                            binding: 0,
+                            resource: buffer.as_entire_binding(),
+                        }],
+                    });
+                    let command_buffer = ctx.encode_render_pass(|pass| {
+                        pass.set_bind_group(0, &bind_group, &[]);
+                        pass.draw(0..3, 0..1);
+                    });
+                    ctx.queue.submit(Some(command_buffer));
+                    drop((bind_group, buffer));
+                    ctx.device.poll(wgpu::Maintain::Poll);
+                }
+                total += start.elapsed();
+                ctx.wait_idle();
+            }
+            total
+        })
+    });
+
+    group.finish();
+}
+
+criterion_group!(benches, hot_paths);
+criterion_main!(benches);
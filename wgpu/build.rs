@@ -11,6 +11,15 @@ fn main() {
         ) },
         dx12: { all(target_os = "windows", feature = "dx12") },
         metal: { all(any(target_os = "ios", target_os = "macos"), feature = "metal") },
+        // D3D11 devices layered on the DX12 backend.
+        d3d11on12: { all(dx12, feature = "d3d11on12") },
+        // Platforms wgpu-core shares memory with other APIs on, through fds with Vulkan
+        // or NT handles with DX12.
+        external_memory: { any(all(unix, native, not(any(target_os = "ios", target_os = "macos"))), dx12) },
+        // Platforms surfaces drive displays on directly, through DRM/KMS and Vulkan.
+        drm: { all(native, target_os = "linux") },
+        // Platforms wgpu-core shares textures with other GL contexts on, through EGL.
+        gl_interop: { all(unix, native, not(any(target_os = "ios", target_os = "macos"))) },
         // This alias is _only_ if _we_ need naga in the wrapper. wgpu-core provides
         // its own re-export of naga, which can be used in other situations
         naga: { any(feature = "naga-ir", feature = "spirv", feature = "glsl") },
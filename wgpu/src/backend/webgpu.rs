@@ -741,6 +741,7 @@ fn map_wgt_limits(limits: webgpu_sys::GpuSupportedLimits) -> wgt::Limits {
         max_subgroup_size: wgt::Limits::default().max_subgroup_size,
         max_push_constant_size: wgt::Limits::default().max_push_constant_size,
         max_non_sampler_bindings: wgt::Limits::default().max_non_sampler_bindings,
+        max_additional_queues: wgt::Limits::default().max_additional_queues,
     }
 }
 
@@ -949,6 +950,47 @@ impl ContextWebGpu {
     ) -> js_sys::ArrayBuffer {
         buffer_data.0.get_mapped_array_buffer(sub_range)
     }
+
+    /// Capabilities shared by every canvas context.
+    fn canvas_capabilities(&self) -> wgt::SurfaceCapabilities {
+        let mut formats = vec![
+            wgt::TextureFormat::Rgba8Unorm,
+            wgt::TextureFormat::Bgra8Unorm,
+            wgt::TextureFormat::Rgba16Float,
+        ];
+        let mut mapped_formats = formats.iter().map(|format| map_texture_format(*format));
+        // Preferred canvas format will only be either "rgba8unorm" or "bgra8unorm".
+        // https://www.w3.org/TR/webgpu/#dom-gpu-getpreferredcanvasformat
+        let preferred_format = self.0.get_preferred_canvas_format();
+        if let Some(index) = mapped_formats.position(|format| format == preferred_format) {
+            formats.swap(0, index);
+        }
+        // https://gpuweb.github.io/gpuweb/#dom-gpucanvasconfiguration-colorspace
+        let color_spaces = [wgt::ColorSpace::Srgb, wgt::ColorSpace::DisplayP3]
+            .into_iter()
+            .flat_map(|color_space| formats.iter().map(move |&format| (format, color_space)))
+            .collect();
+
+        wgt::SurfaceCapabilities {
+            // https://gpuweb.github.io/gpuweb/#supported-context-formats
+            formats,
+            // Doesn't really have meaning on the web.
+            present_modes: vec![wgt::PresentMode::Fifo],
+            alpha_modes: vec![wgt::CompositeAlphaMode::Opaque],
+            // Statically set to RENDER_ATTACHMENT for now. See https://gpuweb.github.io/gpuweb/#dom-gpucanvasconfiguration-usage
+            usages: wgt::TextureUsages::RENDER_ATTACHMENT,
+            transforms: vec![wgt::SurfaceTransform::Identity],
+            current_transform: wgt::SurfaceTransform::Identity,
+            color_spaces,
+            max_array_layers: 1,
+            // Canvases are made fullscreen through the DOM.
+            fullscreen_modes: vec![wgt::FullscreenMode::Windowed],
+            display_modes: Vec::new(),
+            // The browser manages the textures of the canvas.
+            min_image_count: 1,
+            max_image_count: 1,
+        }
+    }
 }
 
 // Represents the global object in the JavaScript context.
@@ -1038,10 +1080,14 @@ impl crate::context::Context for ContextWebGpu {
     type RenderBundleData = Sendable<webgpu_sys::GpuRenderBundle>;
     type SurfaceId = Identified<(Canvas, webgpu_sys::GpuCanvasContext)>;
     type SurfaceData = Sendable<(Canvas, webgpu_sys::GpuCanvasContext)>;
+    type TimelineSemaphoreId = Unused;
+    type TimelineSemaphoreData = ();
+    type MemoryHeapId = Unused;
+    type MemoryHeapData = ();
 
     type SurfaceOutputDetail = SurfaceOutputDetail;
     type SubmissionIndex = Unused;
-    type SubmissionIndexData = ();
+    type SubmissionIndexData = u64;
 
     type RequestAdapterFuture = MakeSendFuture<
         wasm_bindgen_futures::JsFuture,
@@ -1212,6 +1258,19 @@ impl crate::context::Context for ContextWebGpu {
         true
     }
 
+    fn adapter_get_surface_capabilities_headless(
+        &self,
+        _adapter: &Self::AdapterId,
+        _adapter_data: &Self::AdapterData,
+        display_handle: raw_window_handle::RawDisplayHandle,
+    ) -> wgt::SurfaceCapabilities {
+        match display_handle {
+            // Every canvas supports the same configurations.
+            raw_window_handle::RawDisplayHandle::Web(_) => self.canvas_capabilities(),
+            _ => wgt::SurfaceCapabilities::default(),
+        }
+    }
+
     fn adapter_features(
         &self,
         _adapter: &Self::AdapterId,
@@ -1251,6 +1310,12 @@ impl crate::context::Context for ContextWebGpu {
             driver: String::new(),
             driver_info: String::new(),
             backend: wgt::Backend::BrowserWebGpu,
+            driver_version: None,
+            device_uuid: None,
+            device_luid: None,
+            memory_heaps: Vec::new(),
+            unified_memory: false,
+            resizable_bar: false,
         }
     }
 
@@ -1278,28 +1343,7 @@ impl crate::context::Context for ContextWebGpu {
         _adapter: &Self::AdapterId,
         _adapter_data: &Self::AdapterData,
     ) -> wgt::SurfaceCapabilities {
-        let mut formats = vec![
-            wgt::TextureFormat::Rgba8Unorm,
-            wgt::TextureFormat::Bgra8Unorm,
-            wgt::TextureFormat::Rgba16Float,
-        ];
-        let mut mapped_formats = formats.iter().map(|format| map_texture_format(*format));
-        // Preferred canvas format will only be either "rgba8unorm" or "bgra8unorm".
-        // https://www.w3.org/TR/webgpu/#dom-gpu-getpreferredcanvasformat
-        let preferred_format = self.0.get_preferred_canvas_format();
-        if let Some(index) = mapped_formats.position(|format| format == preferred_format) {
-            formats.swap(0, index);
-        }
-
-        wgt::SurfaceCapabilities {
-            // https://gpuweb.github.io/gpuweb/#supported-context-formats
-            formats,
-            // Doesn't really have meaning on the web.
-            present_modes: vec![wgt::PresentMode::Fifo],
-            alpha_modes: vec![wgt::CompositeAlphaMode::Opaque],
-            // Statically set to RENDER_ATTACHMENT for now. See https://gpuweb.github.io/gpuweb/#dom-gpucanvasconfiguration-usage
-            usages: wgt::TextureUsages::RENDER_ATTACHMENT,
-        }
+        self.canvas_capabilities()
     }
 
     fn surface_configure(
@@ -1329,6 +1373,16 @@ impl crate::context::Context for ContextWebGpu {
         {
             panic!("Only Opaque/Auto or PreMultiplied alpha mode are supported on web");
         }
+        if config.array_layer_count != 1 {
+            panic!("Only surfaces with a single array layer are supported on web");
+        }
+        let color_space = match config.color_space {
+            wgt::ColorSpace::Srgb => "srgb",
+            wgt::ColorSpace::DisplayP3 => "display-p3",
+            wgt::ColorSpace::Bt2020 | wgt::ColorSpace::Hdr10 => {
+                panic!("Only sRGB or Display P3 color spaces are supported on web")
+            }
+        };
         let alpha_mode = match config.alpha_mode {
             wgt::CompositeAlphaMode::PreMultiplied => webgpu_sys::GpuCanvasAlphaMode::Premultiplied,
             _ => webgpu_sys::GpuCanvasAlphaMode::Opaque,
@@ -1345,6 +1399,13 @@ impl crate::context::Context for ContextWebGpu {
             .map(|format| JsValue::from(map_texture_format(*format)))
             .collect::<js_sys::Array>();
         mapped.view_formats(&mapped_view_formats);
+        // TODO: Migrate to a web_sys api once `colorSpace` is part of the generated bindings.
+        js_sys::Reflect::set(
+            &mapped,
+            &JsValue::from("colorSpace"),
+            &JsValue::from(color_space),
+        )
+        .expect("Setting Object properties should never fail.");
         surface_data.0 .1.configure(&mapped);
     }
 
@@ -1387,6 +1448,22 @@ impl crate::context::Context for ContextWebGpu {
         map_wgt_features(device_data.0.features())
     }
 
+    fn device_get_texture_format_features(
+        &self,
+        device: &Self::DeviceId,
+        device_data: &Self::DeviceData,
+        format: wgt::TextureFormat,
+    ) -> wgt::TextureFormatFeatures {
+        let features = self.device_features(device, device_data);
+        if !features.contains(format.required_features()) {
+            return wgt::TextureFormatFeatures {
+                allowed_usages: wgt::TextureUsages::empty(),
+                flags: wgt::TextureFormatFeatureFlags::empty(),
+            };
+        }
+        format.guaranteed_format_features(features)
+    }
+
     fn device_limits(
         &self,
         _device: &Self::DeviceId,
@@ -1595,6 +1672,11 @@ impl crate::context::Context for ContextWebGpu {
                         mapped_entry.storage_texture(&storage_texture);
                     }
                     wgt::BindingType::AccelerationStructure => todo!(),
+                    wgt::BindingType::YcbcrTexture { .. } => {
+                        // SAMPLER_YCBCR_CONVERSION is never supported in the
+                        // browser. Leaving the entry without any binding
+                        // layout makes it fail validation there.
+                    }
                 }
 
                 mapped_entry
@@ -1608,6 +1690,15 @@ impl crate::context::Context for ContextWebGpu {
         create_identified(device_data.0.create_bind_group_layout(&mapped_desc))
     }
 
+    fn device_create_inline_bind_group_layout(
+        &self,
+        _device: &Self::DeviceId,
+        _device_data: &Self::DeviceData,
+        _desc: &crate::BindGroupLayoutDescriptor<'_>,
+    ) -> (Self::BindGroupLayoutId, Self::BindGroupLayoutData) {
+        panic!("INLINE_BIND_GROUPS feature must be enabled to call create_inline_bind_group_layout")
+    }
+
     fn device_create_bind_group(
         &self,
         _device: &Self::DeviceId,
@@ -1836,16 +1927,35 @@ impl crate::context::Context for ContextWebGpu {
         ))
     }
 
+    fn device_create_sparse_buffer(
+        &self,
+        _device: &Self::DeviceId,
+        _device_data: &Self::DeviceData,
+        _desc: &crate::BufferDescriptor<'_>,
+    ) -> (Self::BufferId, Self::BufferData) {
+        panic!("Sparse buffers are not supported on WebGPU")
+    }
+
+    fn device_create_sparse_texture(
+        &self,
+        _device: &Self::DeviceId,
+        _device_data: &Self::DeviceData,
+        _desc: &crate::TextureDescriptor<'_>,
+    ) -> (Self::TextureId, Self::TextureData) {
+        panic!("Sparse textures are not supported on WebGPU")
+    }
+
     fn device_create_texture(
         &self,
         _device: &Self::DeviceId,
         device_data: &Self::DeviceData,
         desc: &crate::TextureDescriptor<'_>,
     ) -> (Self::TextureId, Self::TextureData) {
+        // `TRANSIENT` is only a hint, which WebGPU doesn't take.
         let mut mapped_desc = webgpu_sys::GpuTextureDescriptor::new(
             map_texture_format(desc.format),
             &map_extent_3d(desc.size),
-            desc.usage.bits(),
+            (desc.usage - wgt::TextureUsages::TRANSIENT).bits(),
         );
         if let Some(label) = desc.label {
             mapped_desc.label(label);
@@ -1906,6 +2016,76 @@ impl crate::context::Context for ContextWebGpu {
         create_identified(device_data.0.create_query_set(&mapped_desc))
     }
 
+    fn device_create_queue(
+        &self,
+        _device: &Self::DeviceId,
+        _device_data: &Self::DeviceData,
+        _desc: &crate::QueueDescriptor<'_>,
+    ) -> (Self::QueueId, Self::QueueData) {
+        panic!("Additional queues are not supported on WebGPU")
+    }
+
+    fn device_create_timeline_semaphore(
+        &self,
+        _device: &Self::DeviceId,
+        _device_data: &Self::DeviceData,
+        _desc: &crate::TimelineSemaphoreDescriptor<'_>,
+    ) -> (Self::TimelineSemaphoreId, Self::TimelineSemaphoreData) {
+        panic!("Timeline semaphores are not supported on WebGPU")
+    }
+
+    fn device_create_memory_heap(
+        &self,
+        _device: &Self::DeviceId,
+        _device_data: &Self::DeviceData,
+        _desc: &crate::MemoryHeapDescriptor<'_>,
+    ) -> (Self::MemoryHeapId, Self::MemoryHeapData) {
+        panic!("Memory heaps are not supported on WebGPU")
+    }
+
+    // Memory heaps can't be created on WebGPU.
+    fn device_create_placed_buffer(
+        &self,
+        _device: &Self::DeviceId,
+        _device_data: &Self::DeviceData,
+        _heap: &Self::MemoryHeapId,
+        _heap_data: &Self::MemoryHeapData,
+        _offset: wgt::BufferAddress,
+        _desc: &crate::BufferDescriptor<'_>,
+    ) -> (Self::BufferId, Self::BufferData) {
+        unreachable!()
+    }
+
+    fn device_create_placed_texture(
+        &self,
+        _device: &Self::DeviceId,
+        _device_data: &Self::DeviceData,
+        _heap: &Self::MemoryHeapId,
+        _heap_data: &Self::MemoryHeapData,
+        _offset: wgt::BufferAddress,
+        _desc: &crate::TextureDescriptor<'_>,
+    ) -> (Self::TextureId, Self::TextureData) {
+        unreachable!()
+    }
+
+    fn device_buffer_memory_requirements(
+        &self,
+        _device: &Self::DeviceId,
+        _device_data: &Self::DeviceData,
+        _desc: &crate::BufferDescriptor<'_>,
+    ) -> wgt::MemoryRequirements {
+        panic!("Memory heaps are not supported on WebGPU")
+    }
+
+    fn device_texture_memory_requirements(
+        &self,
+        _device: &Self::DeviceId,
+        _device_data: &Self::DeviceData,
+        _desc: &crate::TextureDescriptor<'_>,
+    ) -> wgt::MemoryRequirements {
+        panic!("Memory heaps are not supported on WebGPU")
+    }
+
     fn device_create_command_encoder(
         &self,
         _device: &Self::DeviceId,
@@ -1975,6 +2155,51 @@ impl crate::context::Context for ContextWebGpu {
         // with a callback.
     }
 
+    #[cfg(any(debug_assertions, feature = "test-hooks"))]
+    fn device_simulate_loss(
+        &self,
+        _device: &Self::DeviceId,
+        _device_data: &Self::DeviceData,
+        _reason: wgt::DeviceLostReason,
+    ) {
+        // Browsers can't lose devices on demand.
+    }
+
+    fn device_set_pipeline_compile_timeout(
+        &self,
+        _device: &Self::DeviceId,
+        _device_data: &Self::DeviceData,
+        _timeout: Option<std::time::Duration>,
+    ) {
+        // Compiling pipelines is up to the browser.
+    }
+
+    unsafe fn device_set_validation_level(
+        &self,
+        _device: &Self::DeviceId,
+        _device_data: &Self::DeviceData,
+        _level: wgt::ValidationLevel,
+    ) {
+        // The browser always validates in full.
+    }
+
+    fn device_validation_level(
+        &self,
+        _device: &Self::DeviceId,
+        _device_data: &Self::DeviceData,
+    ) -> wgt::ValidationLevel {
+        wgt::ValidationLevel::Full
+    }
+
+    fn device_set_performance_hint(
+        &self,
+        _device: &Self::DeviceId,
+        _device_data: &Self::DeviceData,
+        _hint: wgt::PerformanceHint,
+    ) {
+        // Power management is up to the browser.
+    }
+
     fn queue_drop(&self, _queue: &Self::QueueId, _queue_data: &Self::QueueData) {
         // Queue is dropped automatically
     }
@@ -2006,11 +2231,37 @@ impl crate::context::Context for ContextWebGpu {
         _device: &Self::DeviceId,
         _device_data: &Self::DeviceData,
         _maintain: crate::Maintain,
+        _work: crate::MaintainWork,
     ) -> crate::MaintainResult {
         // Device is polled automatically
         crate::MaintainResult::SubmissionQueueEmpty
     }
 
+    fn device_wait_for_submission(
+        &self,
+        _device: &Self::DeviceId,
+        _device_data: &Self::DeviceData,
+        _submission_index: &Self::SubmissionIndexData,
+        _timeout: std::time::Duration,
+    ) -> bool {
+        panic!("Waiting for submissions is not supported on WebGPU")
+    }
+
+    // Submissions are batched by the browser already.
+    fn queue_set_submit_batching(
+        &self,
+        _queue: &Self::QueueId,
+        _queue_data: &Self::QueueData,
+        _enabled: bool,
+    ) {
+    }
+
+    fn queue_flush(&self, _queue: &Self::QueueId, _queue_data: &Self::QueueData) {}
+
+    fn submission_index_token(&self, submission_index: &Self::SubmissionIndexData) -> u64 {
+        *submission_index
+    }
+
     fn device_on_uncaptured_error(
         &self,
         _device: &Self::DeviceId,
@@ -2069,7 +2320,14 @@ impl crate::context::Context for ContextWebGpu {
 
         buffer_data.0.set_mapped_range(range);
 
-        register_then_closures(&map_promise, callback, Ok(()), Err(crate::BufferAsyncError));
+        register_then_closures(
+            &map_promise,
+            callback,
+            Ok(()),
+            Err(crate::BufferAsyncError::new(
+                crate::BufferAsyncErrorKind::Other,
+            )),
+        );
     }
 
     fn buffer_get_mapped_range(
@@ -2086,11 +2344,37 @@ impl crate::context::Context for ContextWebGpu {
         })
     }
 
+    unsafe fn buffer_get_mapped_range_unchecked(
+        &self,
+        buffer: &Self::BufferId,
+        buffer_data: &Self::BufferData,
+        sub_range: Range<wgt::BufferAddress>,
+    ) -> Box<dyn crate::context::BufferMappedRange> {
+        // WebGPU has no persistent mapping, so there is nothing to skip.
+        self.buffer_get_mapped_range(buffer, buffer_data, sub_range)
+    }
+
     fn buffer_unmap(&self, _buffer: &Self::BufferId, buffer_data: &Self::BufferData) {
         buffer_data.0.buffer.unmap();
         buffer_data.0.mapping.borrow_mut().mapped_buffer = None;
     }
 
+    fn buffer_gpu_address(
+        &self,
+        _buffer: &Self::BufferId,
+        _buffer_data: &Self::BufferData,
+    ) -> wgt::BufferAddress {
+        panic!("Buffer device addresses are not supported on WebGPU")
+    }
+
+    fn buffer_label(
+        &self,
+        _buffer: &Self::BufferId,
+        buffer_data: &Self::BufferData,
+    ) -> Option<String> {
+        Some(buffer_data.0.label()).filter(|label| !label.is_empty())
+    }
+
     fn texture_create_view(
         &self,
         _texture: &Self::TextureId,
@@ -2139,6 +2423,14 @@ impl crate::context::Context for ContextWebGpu {
         texture_data.0.destroy();
     }
 
+    fn texture_sparse_tiles(
+        &self,
+        _texture: &Self::TextureId,
+        _texture_data: &Self::TextureData,
+    ) -> Option<wgt::SparseTextureTiles> {
+        None
+    }
+
     fn texture_drop(&self, _texture: &Self::TextureId, _texture_data: &Self::TextureData) {
         // Dropped automatically
     }
@@ -2159,6 +2451,47 @@ impl crate::context::Context for ContextWebGpu {
         // Dropped automatically
     }
 
+    // Timeline semaphores can't be created on WebGPU.
+    fn timeline_semaphore_drop(
+        &self,
+        _semaphore: &Self::TimelineSemaphoreId,
+        _semaphore_data: &Self::TimelineSemaphoreData,
+    ) {
+        unreachable!()
+    }
+
+    // Memory heaps can't be created on WebGPU.
+    fn memory_heap_drop(&self, _heap: &Self::MemoryHeapId, _heap_data: &Self::MemoryHeapData) {
+        unreachable!()
+    }
+
+    fn timeline_semaphore_value(
+        &self,
+        _semaphore: &Self::TimelineSemaphoreId,
+        _semaphore_data: &Self::TimelineSemaphoreData,
+    ) -> u64 {
+        unreachable!()
+    }
+
+    fn timeline_semaphore_signal(
+        &self,
+        _semaphore: &Self::TimelineSemaphoreId,
+        _semaphore_data: &Self::TimelineSemaphoreData,
+        _value: u64,
+    ) {
+        unreachable!()
+    }
+
+    fn timeline_semaphore_wait(
+        &self,
+        _semaphore: &Self::TimelineSemaphoreId,
+        _semaphore_data: &Self::TimelineSemaphoreData,
+        _value: u64,
+        _timeout: std::time::Duration,
+    ) -> bool {
+        unreachable!()
+    }
+
     fn bind_group_drop(
         &self,
         _bind_group: &Self::BindGroupId,
@@ -2323,6 +2656,19 @@ impl crate::context::Context for ContextWebGpu {
             )
     }
 
+    fn command_encoder_blit_texture(
+        &self,
+        _encoder: &Self::CommandEncoderId,
+        _encoder_data: &Self::CommandEncoderData,
+        _source: crate::ImageCopyTexture<'_>,
+        _source_size: wgt::Extent3d,
+        _destination: crate::ImageCopyTexture<'_>,
+        _destination_size: wgt::Extent3d,
+        _filter: wgt::FilterMode,
+    ) {
+        unreachable!("TEXTURE_BLIT is not enabled for this backend")
+    }
+
     fn command_encoder_begin_compute_pass(
         &self,
         _encoder: &Self::CommandEncoderId,
@@ -2519,6 +2865,19 @@ impl crate::context::Context for ContextWebGpu {
         }
     }
 
+    fn command_encoder_fill_buffer(
+        &self,
+        encoder: &Self::CommandEncoderId,
+        encoder_data: &Self::CommandEncoderData,
+        buffer: &crate::Buffer,
+        offset: wgt::BufferAddress,
+        size: Option<wgt::BufferAddress>,
+        value: u32,
+    ) {
+        assert_eq!(value, 0, "WebGPU can only fill buffers with zero");
+        self.command_encoder_clear_buffer(encoder, encoder_data, buffer, offset, size)
+    }
+
     fn command_encoder_insert_debug_marker(
         &self,
         _encoder: &Self::CommandEncoderId,
@@ -2735,6 +3094,63 @@ impl crate::context::Context for ContextWebGpu {
             );
     }
 
+    fn queue_create_staging_texture(
+        &self,
+        _queue: &Self::QueueId,
+        _queue_data: &Self::QueueData,
+        texture: crate::ImageCopyTexture<'_>,
+        size: wgt::Extent3d,
+    ) -> Option<(Box<dyn QueueWriteBuffer>, wgt::ImageDataLayout)> {
+        // `writeTexture` has no alignment requirements, so rows are tightly packed.
+        let format = texture.texture.format();
+        let (block_width, block_height) = format.block_dimensions();
+        let block_size = format.block_copy_size(Some(texture.aspect))?;
+        let bytes_per_row = size.width / block_width * block_size;
+        let rows_per_image = size.height / block_height;
+        let data_size =
+            bytes_per_row as usize * rows_per_image as usize * size.depth_or_array_layers as usize;
+        Some((
+            Box::new(WebQueueWriteBuffer(vec![0; data_size].into_boxed_slice())),
+            wgt::ImageDataLayout {
+                offset: 0,
+                bytes_per_row: Some(bytes_per_row),
+                rows_per_image: Some(rows_per_image),
+            },
+        ))
+    }
+
+    fn queue_write_staging_texture(
+        &self,
+        queue: &Self::QueueId,
+        queue_data: &Self::QueueData,
+        texture: crate::ImageCopyTexture<'_>,
+        size: wgt::Extent3d,
+        staging_buffer: &dyn QueueWriteBuffer,
+    ) {
+        let format = texture.texture.format();
+        let (block_width, block_height) = format.block_dimensions();
+        let Some(block_size) = format.block_copy_size(Some(texture.aspect)) else {
+            return;
+        };
+        let staging_buffer = staging_buffer
+            .as_any()
+            .downcast_ref::<WebQueueWriteBuffer>()
+            .unwrap()
+            .slice();
+        self.queue_write_texture(
+            queue,
+            queue_data,
+            texture,
+            staging_buffer,
+            wgt::ImageDataLayout {
+                offset: 0,
+                bytes_per_row: Some(size.width / block_width * block_size),
+                rows_per_image: Some(size.height / block_height),
+            },
+            size,
+        )
+    }
+
     fn queue_copy_external_image_to_texture(
         &self,
         _queue: &Self::QueueId,
@@ -2764,7 +3180,33 @@ impl crate::context::Context for ContextWebGpu {
 
         queue_data.0.submit(&temp_command_buffers);
 
-        (Unused, ())
+        // The browser runs submissions in the order they are made on its
+        // thread, so counting them is enough.
+        static LAST_TOKEN: std::sync::atomic::AtomicU64 = std::sync::atomic::AtomicU64::new(0);
+        let last = LAST_TOKEN
+            .fetch_update(
+                std::sync::atomic::Ordering::Relaxed,
+                std::sync::atomic::Ordering::Relaxed,
+                |last| last.checked_add(1),
+            )
+            .expect("Ran out of submission tokens");
+
+        (Unused, last + 1)
+    }
+
+    fn queue_submit_with_semaphores<
+        I: Iterator<Item = (Self::CommandBufferId, Self::CommandBufferData)>,
+    >(
+        &self,
+        queue: &Self::QueueId,
+        queue_data: &Self::QueueData,
+        command_buffers: I,
+        waits: &[(Self::TimelineSemaphoreId, u64)],
+        signals: &[(Self::TimelineSemaphoreId, u64)],
+    ) -> (Self::SubmissionIndex, Self::SubmissionIndexData) {
+        // Without semaphores, there is nothing to wait for or signal.
+        debug_assert!(waits.is_empty() && signals.is_empty());
+        self.queue_submit(queue, queue_data, command_buffers)
     }
 
     fn queue_get_timestamp_period(
@@ -2776,6 +3218,36 @@ impl crate::context::Context for ContextWebGpu {
         1.0
     }
 
+    fn queue_get_timestamp_calibration(
+        &self,
+        _queue: &Self::QueueId,
+        _queue_data: &Self::QueueData,
+    ) -> Option<wgt::TimestampCalibration> {
+        None
+    }
+
+    fn queue_bind_sparse(
+        &self,
+        _queue: &Self::QueueId,
+        _queue_data: &Self::QueueData,
+        _buffer: &Self::BufferId,
+        _buffer_data: &Self::BufferData,
+        _binds: &[wgt::SparseBufferBind],
+    ) {
+        unreachable!()
+    }
+
+    fn queue_bind_sparse_texture(
+        &self,
+        _queue: &Self::QueueId,
+        _queue_data: &Self::QueueData,
+        _texture: &Self::TextureId,
+        _texture_data: &Self::TextureData,
+        _binds: &[wgt::SparseTextureBind],
+    ) {
+        unreachable!()
+    }
+
     fn queue_on_submitted_work_done(
         &self,
         _queue: &Self::QueueId,
@@ -2785,6 +3257,15 @@ impl crate::context::Context for ContextWebGpu {
         unimplemented!()
     }
 
+    fn queue_is_submission_complete(
+        &self,
+        _queue: &Self::QueueId,
+        _queue_data: &Self::QueueData,
+        _submission_index: &Self::SubmissionIndexData,
+    ) -> bool {
+        panic!("Waiting for submissions is not supported on WebGPU")
+    }
+
     fn device_start_capture(&self, _device: &Self::DeviceId, _device_data: &Self::DeviceData) {}
     fn device_stop_capture(&self, _device: &Self::DeviceId, _device_data: &Self::DeviceData) {}
 
@@ -3175,6 +3656,16 @@ impl crate::context::Context for ContextWebGpu {
         }
     }
 
+    fn render_pass_set_bind_group_inline(
+        &self,
+        _pass: &mut Self::RenderPassId,
+        _pass_data: &mut Self::RenderPassData,
+        _index: u32,
+        _entries: &[crate::BindGroupEntry<'_>],
+    ) {
+        panic!("INLINE_BIND_GROUPS feature must be enabled to call set_bind_group_inline")
+    }
+
     fn render_pass_set_index_buffer(
         &self,
         _pass: &mut Self::RenderPassId,
@@ -3401,6 +3892,38 @@ impl crate::context::Context for ContextWebGpu {
             .set_viewport(x, y, width, height, min_depth, max_depth);
     }
 
+    fn render_pass_set_viewports(
+        &self,
+        _pass: &mut Self::RenderPassId,
+        pass_data: &mut Self::RenderPassData,
+        viewports: &[wgt::Viewport],
+    ) {
+        assert!(
+            viewports.len() <= 1,
+            "Multiple viewports are not supported on WebGPU"
+        );
+        if let Some(v) = viewports.first() {
+            pass_data
+                .0
+                .set_viewport(v.x, v.y, v.width, v.height, v.min_depth, v.max_depth);
+        }
+    }
+
+    fn render_pass_set_scissor_rects(
+        &self,
+        _pass: &mut Self::RenderPassId,
+        pass_data: &mut Self::RenderPassData,
+        rects: &[wgt::ScissorRect],
+    ) {
+        assert!(
+            rects.len() <= 1,
+            "Multiple scissor rectangles are not supported on WebGPU"
+        );
+        if let Some(r) = rects.first() {
+            pass_data.0.set_scissor_rect(r.x, r.y, r.width, r.height);
+        }
+    }
+
     fn render_pass_set_stencil_reference(
         &self,
         _pass: &mut Self::RenderPassId,
@@ -3410,6 +3933,55 @@ impl crate::context::Context for ContextWebGpu {
         pass_data.0.set_stencil_reference(reference);
     }
 
+    fn render_pass_set_stencil_reference_separate(
+        &self,
+        _pass: &mut Self::RenderPassId,
+        _pass_data: &mut Self::RenderPassData,
+        _front: u32,
+        _back: u32,
+    ) {
+        panic!(
+            "SEPARATE_STENCIL_STATE feature must be enabled to call set_stencil_reference_separate"
+        )
+    }
+
+    fn render_pass_set_stencil_write_mask(
+        &self,
+        _pass: &mut Self::RenderPassId,
+        _pass_data: &mut Self::RenderPassData,
+        _front: u32,
+        _back: u32,
+    ) {
+        panic!("SEPARATE_STENCIL_STATE feature must be enabled to call set_stencil_write_mask")
+    }
+
+    fn render_pass_set_front_face(
+        &self,
+        _pass: &mut Self::RenderPassId,
+        _pass_data: &mut Self::RenderPassData,
+        _front_face: wgt::FrontFace,
+    ) {
+        panic!("EXTENDED_DYNAMIC_STATE feature must be enabled to call set_front_face")
+    }
+
+    fn render_pass_set_cull_mode(
+        &self,
+        _pass: &mut Self::RenderPassId,
+        _pass_data: &mut Self::RenderPassData,
+        _cull_mode: Option<wgt::Face>,
+    ) {
+        panic!("EXTENDED_DYNAMIC_STATE feature must be enabled to call set_cull_mode")
+    }
+
+    fn render_pass_set_depth_compare(
+        &self,
+        _pass: &mut Self::RenderPassId,
+        _pass_data: &mut Self::RenderPassData,
+        _compare: wgt::CompareFunction,
+    ) {
+        panic!("EXTENDED_DYNAMIC_STATE feature must be enabled to call set_depth_compare")
+    }
+
     fn render_pass_insert_debug_marker(
         &self,
         _pass: &mut Self::RenderPassId,
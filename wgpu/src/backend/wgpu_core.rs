@@ -1,11 +1,12 @@
 use crate::{
     context::{ObjectId, Unused},
-    AdapterInfo, BindGroupDescriptor, BindGroupLayoutDescriptor, BindingResource, BufferBinding,
-    BufferDescriptor, CommandEncoderDescriptor, ComputePassDescriptor, ComputePipelineDescriptor,
-    DownlevelCapabilities, Features, Label, Limits, LoadOp, MapMode, Operations,
-    PipelineLayoutDescriptor, RenderBundleEncoderDescriptor, RenderPipelineDescriptor,
-    SamplerDescriptor, ShaderModuleDescriptor, ShaderModuleDescriptorSpirV, ShaderSource, StoreOp,
-    SurfaceStatus, SurfaceTargetUnsafe, TextureDescriptor, TextureViewDescriptor,
+    AdapterInfo, BindGroupDescriptor, BindGroupEntry, BindGroupLayoutDescriptor, BindingResource,
+    BufferBinding, BufferDescriptor, CommandEncoderDescriptor, ComputePassDescriptor,
+    ComputePipelineDescriptor, DownlevelCapabilities, Features, Label, Limits, LoadOp, MapMode,
+    MemoryHeapDescriptor, Operations, PipelineLayoutDescriptor, QueueDescriptor,
+    RenderBundleEncoderDescriptor, RenderPipelineDescriptor, SamplerDescriptor,
+    ShaderModuleDescriptor, ShaderModuleDescriptorSpirV, ShaderSource, StoreOp, SurfaceStatus,
+    SurfaceTargetUnsafe, TextureDescriptor, TextureViewDescriptor, TimelineSemaphoreDescriptor,
     UncapturedErrorHandler,
 };
 
@@ -21,6 +22,7 @@ use std::{
     ops::Range,
     slice,
     sync::Arc,
+    time::Duration,
 };
 use wgc::{
     command::{bundle_ffi::*, compute_commands::*, render_commands::*},
@@ -184,6 +186,501 @@ impl ContextWgpuCore {
         )
     }
 
+    #[cfg(external_memory)]
+    pub fn device_drm_format_modifiers(
+        &self,
+        device: &Device,
+        format: wgt::TextureFormat,
+        usage: wgt::TextureUsages,
+    ) -> Vec<wgt::DrmFormatModifier> {
+        match self.0.device_drm_format_modifiers(device.id, format, usage) {
+            Ok(modifiers) => modifiers,
+            Err(cause) => {
+                self.handle_error_nolabel(
+                    &device.error_sink,
+                    cause,
+                    "Device::drm_format_modifiers",
+                );
+                Vec::new()
+            }
+        }
+    }
+
+    #[cfg(external_memory)]
+    pub fn create_exportable_buffer(
+        &self,
+        device: &Device,
+        desc: &BufferDescriptor<'_>,
+        handle_type: wgt::ExternalMemoryHandleType,
+    ) -> (wgc::id::BufferId, Buffer) {
+        let (id, error) = self.0.device_create_exportable_buffer(
+            device.id,
+            &desc.map_label(|l| l.map(Borrowed)),
+            handle_type,
+            None,
+        );
+        if let Some(cause) = error {
+            self.handle_error(
+                &device.error_sink,
+                cause,
+                LABEL,
+                desc.label,
+                "Device::create_exportable_buffer",
+            );
+        }
+        (
+            id,
+            Buffer {
+                error_sink: Arc::clone(&device.error_sink),
+            },
+        )
+    }
+
+    #[cfg(external_memory)]
+    pub unsafe fn import_buffer(
+        &self,
+        device: &Device,
+        desc: &BufferDescriptor<'_>,
+        memory: wgt::ExternalMemory,
+    ) -> (wgc::id::BufferId, Buffer) {
+        let (id, error) = unsafe {
+            self.0.device_import_buffer(
+                device.id,
+                &desc.map_label(|l| l.map(Borrowed)),
+                memory,
+                None,
+            )
+        };
+        if let Some(cause) = error {
+            self.handle_error(
+                &device.error_sink,
+                cause,
+                LABEL,
+                desc.label,
+                "Device::import_buffer",
+            );
+        }
+        (
+            id,
+            Buffer {
+                error_sink: Arc::clone(&device.error_sink),
+            },
+        )
+    }
+
+    #[cfg(external_memory)]
+    pub fn create_exportable_texture(
+        &self,
+        device: &Device,
+        desc: &TextureDescriptor<'_>,
+        handle_type: wgt::ExternalMemoryHandleType,
+        drm_format_modifiers: &[u64],
+    ) -> Texture {
+        let descriptor = desc.map_label_and_view_formats(|l| l.map(Borrowed), |v| v.to_vec());
+        let (id, error) = self.0.device_create_exportable_texture(
+            device.id,
+            &descriptor,
+            handle_type,
+            drm_format_modifiers,
+            None,
+        );
+        if let Some(cause) = error {
+            self.handle_error(
+                &device.error_sink,
+                cause,
+                LABEL,
+                desc.label,
+                "Device::create_exportable_texture",
+            );
+        }
+        Texture {
+            id,
+            error_sink: Arc::clone(&device.error_sink),
+        }
+    }
+
+    #[cfg(external_memory)]
+    pub unsafe fn import_texture(
+        &self,
+        device: &Device,
+        desc: &TextureDescriptor<'_>,
+        memory: wgt::ExternalMemory,
+    ) -> Texture {
+        let descriptor = desc.map_label_and_view_formats(|l| l.map(Borrowed), |v| v.to_vec());
+        let (id, error) = unsafe {
+            self.0
+                .device_import_texture(device.id, &descriptor, memory, None)
+        };
+        if let Some(cause) = error {
+            self.handle_error(
+                &device.error_sink,
+                cause,
+                LABEL,
+                desc.label,
+                "Device::import_texture",
+            );
+        }
+        Texture {
+            id,
+            error_sink: Arc::clone(&device.error_sink),
+        }
+    }
+
+    #[cfg(external_memory)]
+    pub fn buffer_export_memory(
+        &self,
+        buffer: &wgc::id::BufferId,
+        buffer_data: &Buffer,
+    ) -> Option<wgt::ExternalMemory> {
+        match self.0.buffer_export_memory(*buffer) {
+            Ok(memory) => Some(memory),
+            Err(cause) => {
+                self.handle_error_nolabel(&buffer_data.error_sink, cause, "Buffer::export_memory");
+                None
+            }
+        }
+    }
+
+    pub fn texture_expiry(&self, texture: &Texture) -> wgt::TextureExpiry {
+        // Invalid textures already fail validation, their expiry is never checked.
+        wgc::gfx_select!(texture.id => self.0.texture_expiry(texture.id)).unwrap_or_default()
+    }
+
+    #[cfg(external_memory)]
+    pub fn texture_export_memory(&self, texture: &Texture) -> Option<wgt::ExternalMemory> {
+        match self.0.texture_export_memory(texture.id) {
+            Ok(memory) => Some(memory),
+            Err(cause) => {
+                self.handle_error_nolabel(&texture.error_sink, cause, "Texture::export_memory");
+                None
+            }
+        }
+    }
+
+    #[cfg(external_memory)]
+    pub fn create_exportable_timeline_semaphore(
+        &self,
+        device: &Device,
+        desc: &TimelineSemaphoreDescriptor<'_>,
+        handle_type: wgt::ExternalSemaphoreHandleType,
+    ) -> (wgc::id::TimelineSemaphoreId, TimelineSemaphore) {
+        let (id, error) = self.0.device_create_exportable_timeline_semaphore(
+            device.id,
+            &desc.map_label(|l| l.map(Borrowed)),
+            handle_type,
+            None,
+        );
+        if let Some(cause) = error {
+            self.handle_error(
+                &device.error_sink,
+                cause,
+                LABEL,
+                desc.label,
+                "Device::create_exportable_timeline_semaphore",
+            );
+        }
+        (
+            id,
+            TimelineSemaphore {
+                error_sink: Arc::clone(&device.error_sink),
+            },
+        )
+    }
+
+    #[cfg(external_memory)]
+    pub unsafe fn import_timeline_semaphore(
+        &self,
+        device: &Device,
+        desc: &TimelineSemaphoreDescriptor<'_>,
+        semaphore: wgt::ExternalSemaphore,
+    ) -> (wgc::id::TimelineSemaphoreId, TimelineSemaphore) {
+        let (id, error) = unsafe {
+            self.0.device_import_timeline_semaphore(
+                device.id,
+                &desc.map_label(|l| l.map(Borrowed)),
+                semaphore,
+                None,
+            )
+        };
+        if let Some(cause) = error {
+            self.handle_error(
+                &device.error_sink,
+                cause,
+                LABEL,
+                desc.label,
+                "Device::import_timeline_semaphore",
+            );
+        }
+        (
+            id,
+            TimelineSemaphore {
+                error_sink: Arc::clone(&device.error_sink),
+            },
+        )
+    }
+
+    #[cfg(external_memory)]
+    pub fn timeline_semaphore_export(
+        &self,
+        semaphore: &wgc::id::TimelineSemaphoreId,
+        semaphore_data: &TimelineSemaphore,
+    ) -> Option<wgt::ExternalSemaphore> {
+        match self.0.timeline_semaphore_export(*semaphore) {
+            Ok(semaphore) => Some(semaphore),
+            Err(cause) => {
+                self.handle_error_nolabel(
+                    &semaphore_data.error_sink,
+                    cause,
+                    "TimelineSemaphore::export",
+                );
+                None
+            }
+        }
+    }
+
+    #[cfg(all(external_memory, unix))]
+    pub unsafe fn queue_wait_for_sync_file(&self, queue: &Queue, fd: std::os::fd::OwnedFd) {
+        if let Err(cause) = unsafe { self.0.queue_wait_for_sync_file(queue.id, fd) } {
+            self.handle_error_nolabel(&queue.error_sink, cause, "Queue::wait_for_sync_file");
+        }
+    }
+
+    #[cfg(all(external_memory, unix))]
+    pub fn queue_export_sync_file(
+        &self,
+        queue: &Queue,
+        semaphore: &wgc::id::TimelineSemaphoreId,
+        value: u64,
+    ) -> Option<std::os::fd::OwnedFd> {
+        match self.0.queue_export_sync_file(queue.id, *semaphore, value) {
+            Ok(fd) => Some(fd),
+            Err(cause) => {
+                self.handle_error_nolabel(&queue.error_sink, cause, "Queue::export_sync_file");
+                None
+            }
+        }
+    }
+
+    #[cfg(d3d11on12)]
+    pub fn queue_create_d3d11on12_device(
+        &self,
+        queue: &Queue,
+    ) -> Option<hal::dx12::D3D11On12Device> {
+        match self.0.queue_create_d3d11on12_device(queue.id) {
+            Ok(device) => Some(device),
+            Err(cause) => {
+                self.handle_error_nolabel(
+                    &queue.error_sink,
+                    cause,
+                    "Queue::create_d3d11on12_device",
+                );
+                None
+            }
+        }
+    }
+
+    #[cfg(d3d11on12)]
+    pub unsafe fn texture_wrap_for_d3d11(
+        &self,
+        texture: &Texture,
+        d3d11_device: &hal::dx12::D3D11On12Device,
+    ) -> Option<hal::dx12::D3D11Texture> {
+        match unsafe { self.0.texture_wrap_for_d3d11(texture.id, d3d11_device) } {
+            Ok(wrapped) => Some(wrapped),
+            Err(cause) => {
+                self.handle_error_nolabel(&texture.error_sink, cause, "Texture::wrap_for_d3d11");
+                None
+            }
+        }
+    }
+
+    #[cfg(d3d11on12)]
+    pub unsafe fn queue_acquire_texture_for_d3d11(
+        &self,
+        queue: &Queue,
+        texture: &Texture,
+        wrapped: &hal::dx12::D3D11Texture,
+        d3d11_device: &hal::dx12::D3D11On12Device,
+    ) {
+        if let Err(cause) = unsafe {
+            self.0
+                .queue_acquire_texture_for_d3d11(queue.id, texture.id, d3d11_device, wrapped)
+        } {
+            self.handle_error_nolabel(&queue.error_sink, cause, "Queue::acquire_texture_for_d3d11");
+        }
+    }
+
+    #[cfg(metal)]
+    pub fn create_iosurface_texture(
+        &self,
+        device: &Device,
+        desc: &TextureDescriptor<'_>,
+    ) -> Texture {
+        let descriptor = desc.map_label_and_view_formats(|l| l.map(Borrowed), |v| v.to_vec());
+        let (id, error) = self
+            .0
+            .device_create_iosurface_texture(device.id, &descriptor, None);
+        if let Some(cause) = error {
+            self.handle_error(
+                &device.error_sink,
+                cause,
+                LABEL,
+                desc.label,
+                "Device::create_iosurface_texture",
+            );
+        }
+        Texture {
+            id,
+            error_sink: Arc::clone(&device.error_sink),
+        }
+    }
+
+    #[cfg(metal)]
+    pub unsafe fn import_iosurface(
+        &self,
+        device: &Device,
+        desc: &TextureDescriptor<'_>,
+        iosurface: *mut std::ffi::c_void,
+        plane: u32,
+    ) -> Texture {
+        let descriptor = desc.map_label_and_view_formats(|l| l.map(Borrowed), |v| v.to_vec());
+        let (id, error) = unsafe {
+            self.0
+                .device_import_iosurface(device.id, &descriptor, iosurface, plane, None)
+        };
+        if let Some(cause) = error {
+            self.handle_error(
+                &device.error_sink,
+                cause,
+                LABEL,
+                desc.label,
+                "Device::import_iosurface",
+            );
+        }
+        Texture {
+            id,
+            error_sink: Arc::clone(&device.error_sink),
+        }
+    }
+
+    #[cfg(metal)]
+    pub fn texture_iosurface(&self, texture: &Texture) -> Option<*mut std::ffi::c_void> {
+        match self.0.texture_iosurface(texture.id) {
+            Ok(iosurface) => Some(iosurface),
+            Err(cause) => {
+                self.handle_error_nolabel(&texture.error_sink, cause, "Texture::iosurface");
+                None
+            }
+        }
+    }
+
+    #[cfg(gl_interop)]
+    pub unsafe fn import_gl_texture(
+        &self,
+        device: &Device,
+        desc: &TextureDescriptor<'_>,
+        name: std::num::NonZeroU32,
+    ) -> Texture {
+        let descriptor = desc.map_label_and_view_formats(|l| l.map(Borrowed), |v| v.to_vec());
+        let (id, error) = unsafe {
+            self.0
+                .device_import_gl_texture(device.id, &descriptor, name, None)
+        };
+        if let Some(cause) = error {
+            self.handle_error(
+                &device.error_sink,
+                cause,
+                LABEL,
+                desc.label,
+                "Device::import_gl_texture",
+            );
+        }
+        Texture {
+            id,
+            error_sink: Arc::clone(&device.error_sink),
+        }
+    }
+
+    #[cfg(gl_interop)]
+    pub unsafe fn import_egl_image(
+        &self,
+        device: &Device,
+        desc: &TextureDescriptor<'_>,
+        image: *mut std::ffi::c_void,
+    ) -> Texture {
+        let descriptor = desc.map_label_and_view_formats(|l| l.map(Borrowed), |v| v.to_vec());
+        let (id, error) = unsafe {
+            self.0
+                .device_import_egl_image(device.id, &descriptor, image, None)
+        };
+        if let Some(cause) = error {
+            self.handle_error(
+                &device.error_sink,
+                cause,
+                LABEL,
+                desc.label,
+                "Device::import_egl_image",
+            );
+        }
+        Texture {
+            id,
+            error_sink: Arc::clone(&device.error_sink),
+        }
+    }
+
+    #[cfg(gl_interop)]
+    pub fn texture_gl_name(&self, texture: &Texture) -> Option<std::num::NonZeroU32> {
+        match self.0.texture_gl_name(texture.id) {
+            Ok(name) => Some(name),
+            Err(cause) => {
+                self.handle_error_nolabel(&texture.error_sink, cause, "Texture::gl_name");
+                None
+            }
+        }
+    }
+
+    #[cfg(gl_interop)]
+    pub fn texture_create_egl_image(&self, texture: &Texture) -> Option<*mut std::ffi::c_void> {
+        match self.0.texture_create_egl_image(texture.id) {
+            Ok(image) => Some(image),
+            Err(cause) => {
+                self.handle_error_nolabel(&texture.error_sink, cause, "Texture::create_egl_image");
+                None
+            }
+        }
+    }
+
+    pub unsafe fn create_timeline_semaphore_from_hal<A: wgc::hal_api::HalApi>(
+        &self,
+        hal_fence: A::Fence,
+        device: &Device,
+        desc: &TimelineSemaphoreDescriptor<'_>,
+    ) -> (wgc::id::TimelineSemaphoreId, TimelineSemaphore) {
+        let (id, error) = unsafe {
+            self.0.create_timeline_semaphore_from_hal::<A>(
+                hal_fence,
+                device.id,
+                &desc.map_label(|l| l.map(Borrowed)),
+                None,
+            )
+        };
+        if let Some(cause) = error {
+            self.handle_error(
+                &device.error_sink,
+                cause,
+                LABEL,
+                desc.label,
+                "Device::create_timeline_semaphore_from_hal",
+            );
+        }
+        (
+            id,
+            TimelineSemaphore {
+                error_sink: Arc::clone(&device.error_sink),
+            },
+        )
+    }
+
     pub unsafe fn device_as_hal<A: wgc::hal_api::HalApi, F: FnOnce(Option<&A::Device>) -> R, R>(
         &self,
         device: &Device,
@@ -195,6 +692,29 @@ impl ContextWgpuCore {
         }
     }
 
+    pub unsafe fn queue_as_hal<A: wgc::hal_api::HalApi, F: FnOnce(Option<&A::Queue>) -> R, R>(
+        &self,
+        queue: &Queue,
+        hal_queue_callback: F,
+    ) -> R {
+        unsafe { self.0.queue_as_hal::<A, F, R>(queue.id, hal_queue_callback) }
+    }
+
+    pub unsafe fn timeline_semaphore_as_hal<
+        A: wgc::hal_api::HalApi,
+        F: FnOnce(Option<&A::Fence>) -> R,
+        R,
+    >(
+        &self,
+        semaphore: wgc::id::TimelineSemaphoreId,
+        hal_fence_callback: F,
+    ) -> R {
+        unsafe {
+            self.0
+                .timeline_semaphore_as_hal::<A, F, R>(semaphore, hal_fence_callback)
+        }
+    }
+
     pub unsafe fn surface_as_hal<
         A: wgc::hal_api::HalApi,
         F: FnOnce(Option<&A::Surface>) -> R,
@@ -210,6 +730,127 @@ impl ContextWgpuCore {
         }
     }
 
+    pub fn surface_set_hdr_metadata(&self, surface: &Surface, metadata: &wgt::HdrMetadata) -> bool {
+        let device_id = surface
+            .configured_device
+            .lock()
+            .expect("Surface was not configured?");
+        match wgc::gfx_select!(
+            device_id => self.0.surface_set_hdr_metadata(surface.id, metadata)
+        ) {
+            Ok(()) => true,
+            Err(wgc::present::SurfaceError::HdrMetadata(msg)) => {
+                log::info!("Unable to set HDR metadata: {msg}");
+                false
+            }
+            Err(err) => self.handle_error_fatal(err, "Surface::set_hdr_metadata"),
+        }
+    }
+
+    pub fn surface_set_next_present_time(
+        &self,
+        surface: &Surface,
+        time: wgt::PresentationTimestamp,
+    ) -> bool {
+        let device_id = surface
+            .configured_device
+            .lock()
+            .expect("Surface was not configured?");
+        match wgc::gfx_select!(
+            device_id => self.0.surface_set_next_present_time(surface.id, time)
+        ) {
+            Ok(scheduled) => scheduled,
+            Err(err) => self.handle_error_fatal(err, "Surface::set_next_present_time"),
+        }
+    }
+
+    pub fn surface_set_next_present_damage(
+        &self,
+        texture: &Texture,
+        detail: &SurfaceOutputDetail,
+        damage: &[wgt::DamageRect],
+    ) -> bool {
+        match wgc::gfx_select!(
+            texture.id => self.0.surface_set_next_present_damage(detail.surface_id, damage)
+        ) {
+            Ok(partial) => partial,
+            Err(err) => self.handle_error_fatal(err, "SurfaceTexture::present_with_damage"),
+        }
+    }
+
+    pub fn surface_presentation_timings(&self, surface: &Surface) -> Vec<wgt::PresentationTiming> {
+        let device_id = surface
+            .configured_device
+            .lock()
+            .expect("Surface was not configured?");
+        match wgc::gfx_select!(device_id => self.0.surface_presentation_timings(surface.id)) {
+            Ok(timings) => timings,
+            Err(err) => self.handle_error_fatal(err, "Surface::presentation_timings"),
+        }
+    }
+
+    pub fn surface_presentation_report(&self, surface: &Surface) -> wgt::PresentationReport {
+        let device_id = surface
+            .configured_device
+            .lock()
+            .expect("Surface was not configured?");
+        match wgc::gfx_select!(device_id => self.0.surface_presentation_report(surface.id)) {
+            Ok(report) => report,
+            Err(err) => self.handle_error_fatal(err, "Surface::presentation_report"),
+        }
+    }
+
+    pub fn surface_refresh_duration(&self, surface: &Surface) -> Option<std::time::Duration> {
+        let device_id = surface
+            .configured_device
+            .lock()
+            .expect("Surface was not configured?");
+        match wgc::gfx_select!(device_id => self.0.surface_refresh_duration(surface.id)) {
+            Ok(duration) => duration,
+            Err(err) => self.handle_error_fatal(err, "Surface::refresh_duration"),
+        }
+    }
+
+    pub fn surface_image_count(&self, surface: &Surface) -> u32 {
+        let device_id = surface
+            .configured_device
+            .lock()
+            .expect("Surface was not configured?");
+        match wgc::gfx_select!(device_id => self.0.surface_image_count(surface.id)) {
+            Ok(count) => count,
+            Err(err) => self.handle_error_fatal(err, "Surface::image_count"),
+        }
+    }
+
+    pub fn surface_wait_for_present(
+        &self,
+        surface: &Surface,
+        timeout: Option<std::time::Duration>,
+    ) -> wgt::SurfaceStatus {
+        let device_id = surface
+            .configured_device
+            .lock()
+            .expect("Surface was not configured?");
+        match wgc::gfx_select!(
+            device_id => self.0.surface_wait_for_present(surface.id, timeout)
+        ) {
+            Ok(status) => status,
+            Err(err) => self.handle_error_fatal(err, "Surface::wait_for_present"),
+        }
+    }
+
+    pub fn surface_set_maximum_frame_latency(&self, surface: &Surface, maximum_frame_latency: u32) {
+        let device_id = surface
+            .configured_device
+            .lock()
+            .expect("Surface was not configured?");
+        if let Err(err) = wgc::gfx_select!(
+            device_id => self.0.surface_set_maximum_frame_latency(surface.id, maximum_frame_latency)
+        ) {
+            self.handle_error_fatal(err, "Surface::set_maximum_frame_latency");
+        }
+    }
+
     pub unsafe fn texture_as_hal<
         A: wgc::hal_api::HalApi,
         F: FnOnce(Option<&A::Texture>) -> R,
@@ -225,6 +866,21 @@ impl ContextWgpuCore {
         }
     }
 
+    pub unsafe fn surface_texture_as_hal<
+        A: wgc::hal_api::HalApi,
+        F: FnOnce(Option<&A::SurfaceTexture>) -> R,
+        R,
+    >(
+        &self,
+        texture: &Texture,
+        hal_surface_texture_callback: F,
+    ) -> R {
+        unsafe {
+            self.0
+                .surface_texture_as_hal::<A, F, R>(texture.id, hal_surface_texture_callback)
+        }
+    }
+
     pub unsafe fn texture_view_as_hal<
         A: wgc::hal_api::HalApi,
         F: FnOnce(Option<&A::TextureView>) -> R,
@@ -262,6 +918,10 @@ impl ContextWgpuCore {
         self.0.generate_report()
     }
 
+    pub fn set_process_performance_hint(&self, hint: wgt::PerformanceHint) {
+        self.0.set_process_performance_hint(hint)
+    }
+
     fn handle_error(
         &self,
         sink_mutex: &Mutex<ErrorSinkRaw>,
@@ -367,6 +1027,19 @@ fn map_texture_tagged_copy_view(
     }
 }
 
+fn buffer_async_error(error: &wgc::resource::BufferAccessError) -> crate::BufferAsyncError {
+    use crate::BufferAsyncErrorKind as Kind;
+    use wgc::{device::DeviceError, resource::BufferAccessError};
+
+    crate::BufferAsyncError::new(match *error {
+        BufferAccessError::MapAborted => Kind::Aborted,
+        BufferAccessError::Destroyed | BufferAccessError::Invalid => Kind::Destroyed,
+        BufferAccessError::Device(DeviceError::Lost | DeviceError::Invalid) => Kind::DeviceLost,
+        BufferAccessError::Device(DeviceError::OutOfMemory) => Kind::OutOfMemory,
+        _ => Kind::Other,
+    })
+}
+
 fn map_store_op(op: StoreOp) -> wgc::command::StoreOp {
     match op {
         StoreOp::Store => wgc::command::StoreOp::Store,
@@ -475,6 +1148,11 @@ pub struct CommandEncoder {
     open: bool,
 }
 
+#[derive(Debug)]
+pub struct TimelineSemaphore {
+    error_sink: ErrorSink,
+}
+
 impl crate::Context for ContextWgpuCore {
     type AdapterId = wgc::id::AdapterId;
     type AdapterData = ();
@@ -519,6 +1197,10 @@ impl crate::Context for ContextWgpuCore {
 
     type SurfaceId = wgc::id::SurfaceId;
     type SurfaceData = Surface;
+    type TimelineSemaphoreId = wgc::id::TimelineSemaphoreId;
+    type TimelineSemaphoreData = TimelineSemaphore;
+    type MemoryHeapId = wgc::id::MemoryHeapId;
+    type MemoryHeapData = ();
     type SurfaceOutputDetail = SurfaceOutputDetail;
     type SubmissionIndex = Unused;
     type SubmissionIndexData = wgc::device::queue::WrappedSubmissionIndex;
@@ -578,6 +1260,17 @@ impl crate::Context for ContextWgpuCore {
                 self.0
                     .instance_create_surface_from_swap_chain_panel(swap_chain_panel, None)
             },
+
+            #[cfg(drm)]
+            SurfaceTargetUnsafe::Drm {
+                fd,
+                plane,
+                connector_id,
+                mode,
+            } => unsafe {
+                self.0
+                    .instance_create_surface_from_drm(fd, plane, connector_id, mode, None)
+            },
         }?;
 
         Ok((
@@ -631,7 +1324,7 @@ impl crate::Context for ContextWgpuCore {
             id: queue_id,
             error_sink,
         };
-        ready(Ok((device_id, device, device_id.into_queue_id(), queue)))
+        ready(Ok((device_id, device, queue_id, queue)))
     }
 
     fn instance_poll_all_devices(&self, force_wait: bool) -> bool {
@@ -640,6 +1333,23 @@ impl crate::Context for ContextWgpuCore {
             Err(err) => self.handle_error_fatal(err, "Device::poll"),
         }
     }
+    fn device_wait_for_submission(
+        &self,
+        device: &Self::DeviceId,
+        _device_data: &Self::DeviceData,
+        submission_index: &Self::SubmissionIndexData,
+        timeout: Duration,
+    ) -> bool {
+        let timeout_ms = timeout.as_millis().try_into().unwrap_or(u32::MAX);
+        match wgc::gfx_select!(device => self.0.device_wait_for_submission(
+            *device,
+            *submission_index,
+            timeout_ms
+        )) {
+            Ok(done) => done,
+            Err(err) => self.handle_error_fatal(err, "Device::wait_for_submission"),
+        }
+    }
 
     fn adapter_is_surface_supported(
         &self,
@@ -654,6 +1364,22 @@ impl crate::Context for ContextWgpuCore {
         }
     }
 
+    fn adapter_get_surface_capabilities_headless(
+        &self,
+        adapter: &Self::AdapterId,
+        _adapter_data: &Self::AdapterData,
+        display_handle: raw_window_handle::RawDisplayHandle,
+    ) -> wgt::SurfaceCapabilities {
+        match wgc::gfx_select!(adapter => self.0.adapter_get_surface_capabilities_headless(*adapter, display_handle))
+        {
+            Ok(caps) => caps,
+            Err(wgc::instance::GetSurfaceSupportError::Unsupported) => {
+                wgt::SurfaceCapabilities::default()
+            }
+            Err(err) => self.handle_error_fatal(err, "Adapter::get_surface_capabilities_headless"),
+        }
+    }
+
     fn adapter_features(
         &self,
         adapter: &Self::AdapterId,
@@ -831,6 +1557,19 @@ impl crate::Context for ContextWgpuCore {
         }
     }
 
+    fn device_get_texture_format_features(
+        &self,
+        device: &Self::DeviceId,
+        _device_data: &Self::DeviceData,
+        format: wgt::TextureFormat,
+    ) -> wgt::TextureFormatFeatures {
+        match wgc::gfx_select!(device => self.0.device_get_texture_format_features(*device, format))
+        {
+            Ok(features) => features,
+            Err(err) => self.handle_error_fatal(err, "Device::get_texture_format_features"),
+        }
+    }
+
     fn device_downlevel_properties(
         &self,
         device: &Self::DeviceId,
@@ -861,6 +1600,7 @@ impl crate::Context for ContextWgpuCore {
         let descriptor = wgc::pipeline::ShaderModuleDescriptor {
             label: desc.label.map(Borrowed),
             shader_bound_checks,
+            diagnostic_filters: Borrowed(desc.diagnostic_filters),
         };
         let source = match desc.source {
             #[cfg(feature = "spirv")]
@@ -914,6 +1654,7 @@ impl crate::Context for ContextWgpuCore {
             // Doesn't matter the value since spirv shaders aren't mutated to include
             // runtime checks
             shader_bound_checks: unsafe { wgt::ShaderBoundChecks::unchecked() },
+            diagnostic_filters: Borrowed(&[]),
         };
         let (id, error) = wgc::gfx_select!(
             device => self.0.device_create_shader_module_spirv(*device, &descriptor, Borrowed(&desc.source), None)
@@ -954,6 +1695,30 @@ impl crate::Context for ContextWgpuCore {
         }
         (id, ())
     }
+    fn device_create_inline_bind_group_layout(
+        &self,
+        device: &Self::DeviceId,
+        device_data: &Self::DeviceData,
+        desc: &BindGroupLayoutDescriptor<'_>,
+    ) -> (Self::BindGroupLayoutId, Self::BindGroupLayoutData) {
+        let descriptor = wgc::binding_model::BindGroupLayoutDescriptor {
+            label: desc.label.map(Borrowed),
+            entries: Borrowed(desc.entries),
+        };
+        let (id, error) = wgc::gfx_select!(
+            device => self.0.device_create_inline_bind_group_layout(*device, &descriptor, None)
+        );
+        if let Some(cause) = error {
+            self.handle_error(
+                &device_data.error_sink,
+                cause,
+                LABEL,
+                desc.label,
+                "Device::create_inline_bind_group_layout",
+            );
+        }
+        (id, ())
+    }
     fn device_create_bind_group(
         &self,
         device: &Self::DeviceId,
@@ -1269,6 +2034,33 @@ impl crate::Context for ContextWgpuCore {
             },
         )
     }
+    fn device_create_sparse_buffer(
+        &self,
+        device: &Self::DeviceId,
+        device_data: &Self::DeviceData,
+        desc: &crate::BufferDescriptor<'_>,
+    ) -> (Self::BufferId, Self::BufferData) {
+        let (id, error) = wgc::gfx_select!(device => self.0.device_create_sparse_buffer(
+            *device,
+            &desc.map_label(|l| l.map(Borrowed)),
+            None
+        ));
+        if let Some(cause) = error {
+            self.handle_error(
+                &device_data.error_sink,
+                cause,
+                LABEL,
+                desc.label,
+                "Device::create_sparse_buffer",
+            );
+        }
+        (
+            id,
+            Buffer {
+                error_sink: Arc::clone(&device_data.error_sink),
+            },
+        )
+    }
     fn device_create_texture(
         &self,
         device: &Self::DeviceId,
@@ -1298,6 +2090,35 @@ impl crate::Context for ContextWgpuCore {
             },
         )
     }
+    fn device_create_sparse_texture(
+        &self,
+        device: &Self::DeviceId,
+        device_data: &Self::DeviceData,
+        desc: &TextureDescriptor<'_>,
+    ) -> (Self::TextureId, Self::TextureData) {
+        let wgt_desc = desc.map_label_and_view_formats(|l| l.map(Borrowed), |v| v.to_vec());
+        let (id, error) = wgc::gfx_select!(device => self.0.device_create_sparse_texture(
+            *device,
+            &wgt_desc,
+            None
+        ));
+        if let Some(cause) = error {
+            self.handle_error(
+                &device_data.error_sink,
+                cause,
+                LABEL,
+                desc.label,
+                "Device::create_sparse_texture",
+            );
+        }
+        (
+            id,
+            Texture {
+                id,
+                error_sink: Arc::clone(&device_data.error_sink),
+            },
+        )
+    }
     fn device_create_sampler(
         &self,
         device: &Self::DeviceId,
@@ -1316,9 +2137,11 @@ impl crate::Context for ContextWgpuCore {
             mipmap_filter: desc.mipmap_filter,
             lod_min_clamp: desc.lod_min_clamp,
             lod_max_clamp: desc.lod_max_clamp,
+            lod_bias: desc.lod_bias,
             compare: desc.compare,
             anisotropy_clamp: desc.anisotropy_clamp,
             border_color: desc.border_color,
+            reduction_mode: desc.reduction_mode,
         };
 
         let (id, error) = wgc::gfx_select!(device => self.0.device_create_sampler(
@@ -1353,6 +2176,196 @@ impl crate::Context for ContextWgpuCore {
         }
         (id, ())
     }
+    fn device_create_queue(
+        &self,
+        device: &Self::DeviceId,
+        device_data: &Self::DeviceData,
+        desc: &QueueDescriptor<'_>,
+    ) -> (Self::QueueId, Self::QueueData) {
+        let (id, error) = wgc::gfx_select!(device => self.0.device_create_queue(
+            *device,
+            &desc.map_label(|l| l.map(Borrowed)),
+            None
+        ));
+        if let Some(cause) = error {
+            self.handle_error(
+                &device_data.error_sink,
+                cause,
+                LABEL,
+                desc.label,
+                "Device::create_queue",
+            );
+        }
+        (
+            id,
+            Queue {
+                id,
+                error_sink: Arc::clone(&device_data.error_sink),
+            },
+        )
+    }
+    fn device_create_timeline_semaphore(
+        &self,
+        device: &Self::DeviceId,
+        device_data: &Self::DeviceData,
+        desc: &TimelineSemaphoreDescriptor<'_>,
+    ) -> (Self::TimelineSemaphoreId, Self::TimelineSemaphoreData) {
+        let (id, error) = wgc::gfx_select!(device => self.0.device_create_timeline_semaphore(
+            *device,
+            &desc.map_label(|l| l.map(Borrowed)),
+            None
+        ));
+        if let Some(cause) = error {
+            self.handle_error(
+                &device_data.error_sink,
+                cause,
+                LABEL,
+                desc.label,
+                "Device::create_timeline_semaphore",
+            );
+        }
+        (
+            id,
+            TimelineSemaphore {
+                error_sink: Arc::clone(&device_data.error_sink),
+            },
+        )
+    }
+    fn device_create_memory_heap(
+        &self,
+        device: &Self::DeviceId,
+        device_data: &Self::DeviceData,
+        desc: &MemoryHeapDescriptor<'_>,
+    ) -> (Self::MemoryHeapId, Self::MemoryHeapData) {
+        let (id, error) = wgc::gfx_select!(device => self.0.device_create_memory_heap(
+            *device,
+            &desc.map_label(|l| l.map(Borrowed)),
+            None
+        ));
+        if let Some(cause) = error {
+            self.handle_error(
+                &device_data.error_sink,
+                cause,
+                LABEL,
+                desc.label,
+                "Device::create_memory_heap",
+            );
+        }
+        (id, ())
+    }
+    fn device_create_placed_buffer(
+        &self,
+        device: &Self::DeviceId,
+        device_data: &Self::DeviceData,
+        heap: &Self::MemoryHeapId,
+        _heap_data: &Self::MemoryHeapData,
+        offset: wgt::BufferAddress,
+        desc: &BufferDescriptor<'_>,
+    ) -> (Self::BufferId, Self::BufferData) {
+        let (id, error) = wgc::gfx_select!(device => self.0.device_create_placed_buffer(
+            *device,
+            *heap,
+            offset,
+            &desc.map_label(|l| l.map(Borrowed)),
+            None
+        ));
+        if let Some(cause) = error {
+            self.handle_error(
+                &device_data.error_sink,
+                cause,
+                LABEL,
+                desc.label,
+                "Device::create_placed_buffer",
+            );
+        }
+        (
+            id,
+            Buffer {
+                error_sink: Arc::clone(&device_data.error_sink),
+            },
+        )
+    }
+    fn device_create_placed_texture(
+        &self,
+        device: &Self::DeviceId,
+        device_data: &Self::DeviceData,
+        heap: &Self::MemoryHeapId,
+        _heap_data: &Self::MemoryHeapData,
+        offset: wgt::BufferAddress,
+        desc: &TextureDescriptor<'_>,
+    ) -> (Self::TextureId, Self::TextureData) {
+        let wgt_desc = desc.map_label_and_view_formats(|l| l.map(Borrowed), |v| v.to_vec());
+        let (id, error) = wgc::gfx_select!(device => self.0.device_create_placed_texture(
+            *device,
+            *heap,
+            offset,
+            &wgt_desc,
+            None
+        ));
+        if let Some(cause) = error {
+            self.handle_error(
+                &device_data.error_sink,
+                cause,
+                LABEL,
+                desc.label,
+                "Device::create_placed_texture",
+            );
+        }
+        (
+            id,
+            Texture {
+                id,
+                error_sink: Arc::clone(&device_data.error_sink),
+            },
+        )
+    }
+    fn device_buffer_memory_requirements(
+        &self,
+        device: &Self::DeviceId,
+        device_data: &Self::DeviceData,
+        desc: &BufferDescriptor<'_>,
+    ) -> wgt::MemoryRequirements {
+        match wgc::gfx_select!(device => self.0.device_buffer_memory_requirements(
+            *device,
+            &desc.map_label(|l| l.map(Borrowed))
+        )) {
+            Ok(requirements) => requirements,
+            Err(cause) => {
+                self.handle_error(
+                    &device_data.error_sink,
+                    cause,
+                    LABEL,
+                    desc.label,
+                    "Device::buffer_memory_requirements",
+                );
+                wgt::MemoryRequirements::default()
+            }
+        }
+    }
+    fn device_texture_memory_requirements(
+        &self,
+        device: &Self::DeviceId,
+        device_data: &Self::DeviceData,
+        desc: &TextureDescriptor<'_>,
+    ) -> wgt::MemoryRequirements {
+        let wgt_desc = desc.map_label_and_view_formats(|l| l.map(Borrowed), |v| v.to_vec());
+        match wgc::gfx_select!(device => self.0.device_texture_memory_requirements(
+            *device,
+            &wgt_desc
+        )) {
+            Ok(requirements) => requirements,
+            Err(cause) => {
+                self.handle_error(
+                    &device_data.error_sink,
+                    cause,
+                    LABEL,
+                    desc.label,
+                    "Device::texture_memory_requirements",
+                );
+                wgt::MemoryRequirements::default()
+            }
+        }
+    }
     fn device_create_command_encoder(
         &self,
         device: &Self::DeviceId,
@@ -1439,16 +2452,76 @@ impl crate::Context for ContextWgpuCore {
         // destroyed (which this is not) are "unknown".
         wgc::gfx_select!(device => self.0.device_mark_lost(*device, message));
     }
+    #[cfg(any(debug_assertions, feature = "test-hooks"))]
+    fn device_simulate_loss(
+        &self,
+        device: &Self::DeviceId,
+        _device_data: &Self::DeviceData,
+        reason: wgt::DeviceLostReason,
+    ) {
+        if let Err(cause) = wgc::gfx_select!(device => self.0.device_simulate_loss(*device, reason))
+        {
+            self.handle_error_fatal(cause, "Device::simulate_loss");
+        }
+    }
+    fn device_set_pipeline_compile_timeout(
+        &self,
+        device: &Self::DeviceId,
+        _device_data: &Self::DeviceData,
+        timeout: Option<std::time::Duration>,
+    ) {
+        if let Err(cause) = wgc::gfx_select!(
+            device => self.0.device_set_pipeline_compile_timeout(*device, timeout)
+        ) {
+            self.handle_error_fatal(cause, "Device::set_pipeline_compile_timeout");
+        }
+    }
+    unsafe fn device_set_validation_level(
+        &self,
+        device: &Self::DeviceId,
+        _device_data: &Self::DeviceData,
+        level: wgt::ValidationLevel,
+    ) {
+        if let Err(cause) =
+            wgc::gfx_select!(device => self.0.device_set_validation_level(*device, level))
+        {
+            self.handle_error_fatal(cause, "Device::set_validation_level");
+        }
+    }
+    fn device_validation_level(
+        &self,
+        device: &Self::DeviceId,
+        _device_data: &Self::DeviceData,
+    ) -> wgt::ValidationLevel {
+        match wgc::gfx_select!(device => self.0.device_validation_level(*device)) {
+            Ok(level) => level,
+            Err(cause) => self.handle_error_fatal(cause, "Device::validation_level"),
+        }
+    }
+    fn device_set_performance_hint(
+        &self,
+        device: &Self::DeviceId,
+        _device_data: &Self::DeviceData,
+        hint: wgt::PerformanceHint,
+    ) {
+        if let Err(cause) =
+            wgc::gfx_select!(device => self.0.device_set_performance_hint(*device, hint))
+        {
+            self.handle_error_fatal(cause, "Device::set_performance_hint");
+        }
+    }
     fn device_poll(
         &self,
         device: &Self::DeviceId,
         _device_data: &Self::DeviceData,
         maintain: crate::Maintain,
+        work: wgt::MaintainWork,
     ) -> wgt::MaintainResult {
         let maintain_inner = maintain.map_index(|i| *i.1.as_ref().downcast_ref().unwrap());
-        match wgc::gfx_select!(device => self.0.device_poll(
+        match wgc::gfx_select!(device => self.0.device_poll_work(
             *device,
-            maintain_inner
+            maintain_inner,
+            work
         )) {
             Ok(done) => match done {
                 true => wgt::MaintainResult::SubmissionQueueEmpty,
@@ -1503,7 +2576,7 @@ impl crate::Context for ContextWgpuCore {
             },
             callback: Some(wgc::resource::BufferMapCallback::from_rust(Box::new(
                 |status| {
-                    let res = status.map_err(|_| crate::BufferAsyncError);
+                    let res = status.map_err(|error| buffer_async_error(&error));
                     callback(res);
                 },
             ))),
@@ -1517,14 +2590,34 @@ impl crate::Context for ContextWgpuCore {
             }
         }
     }
-    fn buffer_get_mapped_range(
+    fn buffer_get_mapped_range(
+        &self,
+        buffer: &Self::BufferId,
+        _buffer_data: &Self::BufferData,
+        sub_range: Range<wgt::BufferAddress>,
+    ) -> Box<dyn crate::context::BufferMappedRange> {
+        let size = sub_range.end - sub_range.start;
+        match wgc::gfx_select!(buffer => self.0.buffer_get_mapped_range(
+            *buffer,
+            sub_range.start,
+            Some(size)
+        )) {
+            Ok((ptr, size)) => Box::new(BufferMappedRange {
+                ptr,
+                size: size as usize,
+            }),
+            Err(err) => self.handle_error_fatal(err, "Buffer::get_mapped_range"),
+        }
+    }
+
+    unsafe fn buffer_get_mapped_range_unchecked(
         &self,
         buffer: &Self::BufferId,
         _buffer_data: &Self::BufferData,
         sub_range: Range<wgt::BufferAddress>,
     ) -> Box<dyn crate::context::BufferMappedRange> {
         let size = sub_range.end - sub_range.start;
-        match wgc::gfx_select!(buffer => self.0.buffer_get_mapped_range(
+        match wgc::gfx_select!(buffer => self.0.buffer_get_mapped_range_unchecked(
             *buffer,
             sub_range.start,
             Some(size)
@@ -1533,7 +2626,7 @@ impl crate::Context for ContextWgpuCore {
                 ptr,
                 size: size as usize,
             }),
-            Err(err) => self.handle_error_fatal(err, "Buffer::get_mapped_range"),
+            Err(err) => self.handle_error_fatal(err, "Buffer::get_mapped_range_unchecked"),
         }
     }
 
@@ -1546,6 +2639,34 @@ impl crate::Context for ContextWgpuCore {
         }
     }
 
+    fn buffer_gpu_address(
+        &self,
+        buffer: &Self::BufferId,
+        buffer_data: &Self::BufferData,
+    ) -> wgt::BufferAddress {
+        match wgc::gfx_select!(buffer => self.0.buffer_device_address(*buffer)) {
+            Ok(address) => address,
+            Err(cause) => {
+                self.handle_error_nolabel(&buffer_data.error_sink, cause, "Buffer::gpu_address");
+                0
+            }
+        }
+    }
+
+    fn buffer_label(
+        &self,
+        buffer: &Self::BufferId,
+        buffer_data: &Self::BufferData,
+    ) -> Option<String> {
+        match wgc::gfx_select!(buffer => self.0.buffer_get_label(*buffer)) {
+            Ok(label) => Some(label).filter(|label| !label.is_empty()),
+            Err(cause) => {
+                self.handle_error_nolabel(&buffer_data.error_sink, cause, "Buffer::label");
+                None
+            }
+        }
+    }
+
     fn texture_create_view(
         &self,
         texture: &Self::TextureId,
@@ -1563,6 +2684,7 @@ impl crate::Context for ContextWgpuCore {
                 base_array_layer: desc.base_array_layer,
                 array_layer_count: desc.array_layer_count,
             },
+            swizzle: desc.swizzle,
         };
         let (id, error) = wgc::gfx_select!(
             texture => self.0.texture_create_view(*texture, &descriptor, None)
@@ -1601,6 +2723,14 @@ impl crate::Context for ContextWgpuCore {
         let _ = wgc::gfx_select!(texture => self.0.texture_destroy(*texture));
     }
 
+    fn texture_sparse_tiles(
+        &self,
+        texture: &Self::TextureId,
+        _texture_data: &Self::TextureData,
+    ) -> Option<wgt::SparseTextureTiles> {
+        wgc::gfx_select!(texture => self.0.texture_sparse_tiles(*texture))
+    }
+
     fn texture_drop(&self, texture: &Self::TextureId, _texture_data: &Self::TextureData) {
         wgc::gfx_select!(texture => self.0.texture_drop(*texture, false))
     }
@@ -1621,6 +2751,61 @@ impl crate::Context for ContextWgpuCore {
         wgc::gfx_select!(*query_set => self.0.query_set_drop(*query_set))
     }
 
+    fn timeline_semaphore_drop(
+        &self,
+        semaphore: &Self::TimelineSemaphoreId,
+        _semaphore_data: &Self::TimelineSemaphoreData,
+    ) {
+        wgc::gfx_select!(*semaphore => self.0.timeline_semaphore_drop(*semaphore))
+    }
+
+    fn memory_heap_drop(&self, heap: &Self::MemoryHeapId, _heap_data: &Self::MemoryHeapData) {
+        wgc::gfx_select!(*heap => self.0.memory_heap_drop(*heap))
+    }
+
+    fn timeline_semaphore_value(
+        &self,
+        semaphore: &Self::TimelineSemaphoreId,
+        _semaphore_data: &Self::TimelineSemaphoreData,
+    ) -> u64 {
+        match wgc::gfx_select!(*semaphore => self.0.timeline_semaphore_get_value(*semaphore)) {
+            Ok(value) => value,
+            Err(cause) => self.handle_error_fatal(cause, "TimelineSemaphore::value"),
+        }
+    }
+
+    fn timeline_semaphore_signal(
+        &self,
+        semaphore: &Self::TimelineSemaphoreId,
+        semaphore_data: &Self::TimelineSemaphoreData,
+        value: u64,
+    ) {
+        if let Err(cause) =
+            wgc::gfx_select!(*semaphore => self.0.timeline_semaphore_signal(*semaphore, value))
+        {
+            self.handle_error_nolabel(
+                &semaphore_data.error_sink,
+                cause,
+                "TimelineSemaphore::signal",
+            );
+        }
+    }
+
+    fn timeline_semaphore_wait(
+        &self,
+        semaphore: &Self::TimelineSemaphoreId,
+        _semaphore_data: &Self::TimelineSemaphoreData,
+        value: u64,
+        timeout: Duration,
+    ) -> bool {
+        let timeout_ms = timeout.as_millis().try_into().unwrap_or(u32::MAX);
+        match wgc::gfx_select!(*semaphore => self.0.timeline_semaphore_wait(*semaphore, value, timeout_ms))
+        {
+            Ok(done) => done,
+            Err(cause) => self.handle_error_fatal(cause, "TimelineSemaphore::wait"),
+        }
+    }
+
     fn bind_group_drop(
         &self,
         bind_group: &Self::BindGroupId,
@@ -1813,6 +2998,32 @@ impl crate::Context for ContextWgpuCore {
         }
     }
 
+    fn command_encoder_blit_texture(
+        &self,
+        encoder: &Self::CommandEncoderId,
+        encoder_data: &Self::CommandEncoderData,
+        source: crate::ImageCopyTexture<'_>,
+        source_size: wgt::Extent3d,
+        destination: crate::ImageCopyTexture<'_>,
+        destination_size: wgt::Extent3d,
+        filter: wgt::FilterMode,
+    ) {
+        if let Err(cause) = wgc::gfx_select!(encoder => self.0.command_encoder_blit_texture(
+            *encoder,
+            &map_texture_copy_view(source),
+            &source_size,
+            &map_texture_copy_view(destination),
+            &destination_size,
+            filter
+        )) {
+            self.handle_error_nolabel(
+                &encoder_data.error_sink,
+                cause,
+                "CommandEncoder::blit_texture",
+            );
+        }
+    }
+
     fn command_encoder_begin_compute_pass(
         &self,
         encoder: &Self::CommandEncoderId,
@@ -1990,6 +3201,28 @@ impl crate::Context for ContextWgpuCore {
             *encoder,
             buffer.id.into(),
             offset, size
+        )) {
+            self.handle_error_nolabel(
+                &encoder_data.error_sink,
+                cause,
+                "CommandEncoder::clear_buffer",
+            );
+        }
+    }
+
+    fn command_encoder_fill_buffer(
+        &self,
+        encoder: &Self::CommandEncoderId,
+        encoder_data: &Self::CommandEncoderData,
+        buffer: &crate::Buffer,
+        offset: wgt::BufferAddress,
+        size: Option<wgt::BufferAddress>,
+        value: u32,
+    ) {
+        if let Err(cause) = wgc::gfx_select!(encoder => self.0.command_encoder_fill_buffer(
+            *encoder,
+            buffer.id.into(),
+            offset, size, value
         )) {
             self.handle_error_nolabel(
                 &encoder_data.error_sink,
@@ -2223,6 +3456,64 @@ impl crate::Context for ContextWgpuCore {
         }
     }
 
+    fn queue_create_staging_texture(
+        &self,
+        queue: &Self::QueueId,
+        queue_data: &Self::QueueData,
+        texture: crate::ImageCopyTexture<'_>,
+        size: wgt::Extent3d,
+    ) -> Option<(
+        Box<dyn crate::context::QueueWriteBuffer>,
+        wgt::ImageDataLayout,
+    )> {
+        match wgc::gfx_select!(*queue => self.0.queue_create_staging_texture(
+            *queue,
+            &map_texture_copy_view(texture),
+            &size,
+            None
+        )) {
+            Ok((buffer_id, ptr, data_size, layout)) => Some((
+                Box::new(QueueWriteBuffer {
+                    buffer_id,
+                    mapping: BufferMappedRange {
+                        ptr,
+                        size: data_size as usize,
+                    },
+                }),
+                layout,
+            )),
+            Err(err) => {
+                self.handle_error_nolabel(&queue_data.error_sink, err, "Queue::write_texture_with");
+                None
+            }
+        }
+    }
+
+    fn queue_write_staging_texture(
+        &self,
+        queue: &Self::QueueId,
+        queue_data: &Self::QueueData,
+        texture: crate::ImageCopyTexture<'_>,
+        size: wgt::Extent3d,
+        staging_buffer: &dyn crate::context::QueueWriteBuffer,
+    ) {
+        let staging_buffer = staging_buffer
+            .as_any()
+            .downcast_ref::<QueueWriteBuffer>()
+            .unwrap();
+        match wgc::gfx_select!(*queue => self.0.queue_write_staging_texture(
+            *queue,
+            &map_texture_copy_view(texture),
+            &size,
+            staging_buffer.buffer_id
+        )) {
+            Ok(()) => (),
+            Err(err) => {
+                self.handle_error_nolabel(&queue_data.error_sink, err, "Queue::write_texture_with");
+            }
+        }
+    }
+
     #[cfg(any(webgpu, webgl))]
     fn queue_copy_external_image_to_texture(
         &self,
@@ -2266,6 +3557,67 @@ impl crate::Context for ContextWgpuCore {
         (Unused, index)
     }
 
+    fn queue_submit_with_semaphores<
+        I: Iterator<Item = (Self::CommandBufferId, Self::CommandBufferData)>,
+    >(
+        &self,
+        queue: &Self::QueueId,
+        _queue_data: &Self::QueueData,
+        command_buffers: I,
+        waits: &[(Self::TimelineSemaphoreId, u64)],
+        signals: &[(Self::TimelineSemaphoreId, u64)],
+    ) -> (Self::SubmissionIndex, Self::SubmissionIndexData) {
+        let temp_command_buffers = command_buffers
+            .map(|(i, _)| i)
+            .collect::<SmallVec<[_; 4]>>();
+
+        let index = match wgc::gfx_select!(*queue => self.0.queue_submit_with_semaphores(
+            *queue,
+            &temp_command_buffers,
+            waits,
+            signals
+        )) {
+            Ok(index) => index,
+            Err(err) => self.handle_error_fatal(err, "Queue::submit_with_semaphores"),
+        };
+
+        (Unused, index)
+    }
+
+    fn queue_bind_sparse(
+        &self,
+        queue: &Self::QueueId,
+        queue_data: &Self::QueueData,
+        buffer: &Self::BufferId,
+        _buffer_data: &Self::BufferData,
+        binds: &[wgt::SparseBufferBind],
+    ) {
+        match wgc::gfx_select!(*queue => self.0.queue_bind_sparse(*queue, *buffer, binds)) {
+            Ok(_) => (),
+            Err(err) => {
+                self.handle_error_nolabel(&queue_data.error_sink, err, "Queue::bind_sparse")
+            }
+        }
+    }
+
+    fn queue_bind_sparse_texture(
+        &self,
+        queue: &Self::QueueId,
+        queue_data: &Self::QueueData,
+        texture: &Self::TextureId,
+        _texture_data: &Self::TextureData,
+        binds: &[wgt::SparseTextureBind],
+    ) {
+        match wgc::gfx_select!(
+            *queue => self.0.queue_bind_sparse_texture(*queue, *texture, binds)
+        ) {
+            Ok(_) => (),
+            Err(err) => {
+                self.handle_error_nolabel(&queue_data.error_sink, err, "Queue::bind_sparse_texture")
+            }
+        }
+    }
+
     fn queue_get_timestamp_period(
         &self,
         queue: &Self::QueueId,
@@ -2282,6 +3634,22 @@ impl crate::Context for ContextWgpuCore {
         }
     }
 
+    fn queue_get_timestamp_calibration(
+        &self,
+        queue: &Self::QueueId,
+        _queue_data: &Self::QueueData,
+    ) -> Option<wgt::TimestampCalibration> {
+        let res = wgc::gfx_select!(queue => self.0.queue_get_timestamp_calibration(
+            *queue
+        ));
+        match res {
+            Ok(calibration) => calibration,
+            Err(cause) => {
+                self.handle_error_fatal(cause, "Queue::get_timestamp_calibration");
+            }
+        }
+    }
+
     fn queue_on_submitted_work_done(
         &self,
         queue: &Self::QueueId,
@@ -2296,6 +3664,42 @@ impl crate::Context for ContextWgpuCore {
         }
     }
 
+    fn queue_is_submission_complete(
+        &self,
+        queue: &Self::QueueId,
+        _queue_data: &Self::QueueData,
+        submission_index: &Self::SubmissionIndexData,
+    ) -> bool {
+        match wgc::gfx_select!(queue => self.0.queue_is_submission_complete(*queue, *submission_index))
+        {
+            Ok(done) => done,
+            Err(err) => self.handle_error_fatal(err, "Queue::is_submission_complete"),
+        }
+    }
+
+    fn queue_set_submit_batching(
+        &self,
+        queue: &Self::QueueId,
+        _queue_data: &Self::QueueData,
+        enabled: bool,
+    ) {
+        if let Err(err) =
+            wgc::gfx_select!(queue => self.0.queue_set_submit_batching(*queue, enabled))
+        {
+            self.handle_error_fatal(err, "Queue::set_submit_batching");
+        }
+    }
+
+    fn queue_flush(&self, queue: &Self::QueueId, _queue_data: &Self::QueueData) {
+        if let Err(err) = wgc::gfx_select!(queue => self.0.queue_flush(*queue)) {
+            self.handle_error_fatal(err, "Queue::flush");
+        }
+    }
+
+    fn submission_index_token(&self, submission_index: &Self::SubmissionIndexData) -> u64 {
+        submission_index.token
+    }
+
     fn device_start_capture(&self, device: &Self::DeviceId, _device_data: &Self::DeviceData) {
         wgc::gfx_select!(device => self.0.device_start_capture(*device));
     }
@@ -2621,6 +4025,34 @@ impl crate::Context for ContextWgpuCore {
         wgpu_render_pass_set_bind_group(pass_data, index, *bind_group, offsets)
     }
 
+    fn render_pass_set_bind_group_inline(
+        &self,
+        _pass: &mut Self::RenderPassId,
+        pass_data: &mut Self::RenderPassData,
+        index: u32,
+        entries: &[BindGroupEntry<'_>],
+    ) {
+        let bindings = entries
+            .iter()
+            .map(|entry| match entry.resource {
+                BindingResource::Buffer(BufferBinding {
+                    buffer,
+                    offset,
+                    size,
+                }) => wgc::binding_model::InlineBinding {
+                    binding: entry.binding,
+                    buffer: wgc::binding_model::BufferBinding {
+                        buffer_id: buffer.id.into(),
+                        offset,
+                        size,
+                    },
+                },
+                _ => panic!("Inline bind groups may only contain buffer bindings"),
+            })
+            .collect::<SmallVec<[_; wgt::MAX_INLINE_BINDINGS as usize]>>();
+        wgpu_render_pass_set_bind_group_inline(pass_data, index, &bindings)
+    }
+
     fn render_pass_set_index_buffer(
         &self,
         _pass: &mut Self::RenderPassId,
@@ -2822,6 +4254,24 @@ impl crate::Context for ContextWgpuCore {
         wgpu_render_pass_set_viewport(pass_data, x, y, width, height, min_depth, max_depth)
     }
 
+    fn render_pass_set_viewports(
+        &self,
+        _pass: &mut Self::RenderPassId,
+        pass_data: &mut Self::RenderPassData,
+        viewports: &[wgt::Viewport],
+    ) {
+        wgpu_render_pass_set_viewports(pass_data, viewports)
+    }
+
+    fn render_pass_set_scissor_rects(
+        &self,
+        _pass: &mut Self::RenderPassId,
+        pass_data: &mut Self::RenderPassData,
+        rects: &[wgt::ScissorRect],
+    ) {
+        wgpu_render_pass_set_scissor_rects(pass_data, rects)
+    }
+
     fn render_pass_set_stencil_reference(
         &self,
         _pass: &mut Self::RenderPassId,
@@ -2831,6 +4281,53 @@ impl crate::Context for ContextWgpuCore {
         wgpu_render_pass_set_stencil_reference(pass_data, reference)
     }
 
+    fn render_pass_set_stencil_reference_separate(
+        &self,
+        _pass: &mut Self::RenderPassId,
+        pass_data: &mut Self::RenderPassData,
+        front: u32,
+        back: u32,
+    ) {
+        wgpu_render_pass_set_stencil_reference_separate(pass_data, front, back)
+    }
+
+    fn render_pass_set_stencil_write_mask(
+        &self,
+        _pass: &mut Self::RenderPassId,
+        pass_data: &mut Self::RenderPassData,
+        front: u32,
+        back: u32,
+    ) {
+        wgpu_render_pass_set_stencil_write_mask(pass_data, front, back)
+    }
+
+    fn render_pass_set_front_face(
+        &self,
+        _pass: &mut Self::RenderPassId,
+        pass_data: &mut Self::RenderPassData,
+        front_face: wgt::FrontFace,
+    ) {
+        wgpu_render_pass_set_front_face(pass_data, front_face)
+    }
+
+    fn render_pass_set_cull_mode(
+        &self,
+        _pass: &mut Self::RenderPassId,
+        pass_data: &mut Self::RenderPassData,
+        cull_mode: Option<wgt::Face>,
+    ) {
+        wgpu_render_pass_set_cull_mode(pass_data, cull_mode)
+    }
+
+    fn render_pass_set_depth_compare(
+        &self,
+        _pass: &mut Self::RenderPassId,
+        pass_data: &mut Self::RenderPassData,
+        compare: wgt::CompareFunction,
+    ) {
+        wgpu_render_pass_set_depth_compare(pass_data, compare)
+    }
+
     fn render_pass_insert_debug_marker(
         &self,
         _pass: &mut Self::RenderPassId,
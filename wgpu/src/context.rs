@@ -1,21 +1,27 @@
-use std::{any::Any, fmt::Debug, future::Future, num::NonZeroU64, ops::Range, pin::Pin, sync::Arc};
+use std::{
+    any::Any, fmt::Debug, future::Future, num::NonZeroU64, ops::Range, pin::Pin, sync::Arc,
+    time::Duration,
+};
 
 use wgt::{
     strict_assert, strict_assert_eq, AdapterInfo, BufferAddress, BufferSize, Color,
-    DeviceLostReason, DownlevelCapabilities, DynamicOffset, Extent3d, Features, ImageDataLayout,
-    ImageSubresourceRange, IndexFormat, Limits, ShaderStages, SurfaceStatus, TextureFormat,
-    TextureFormatFeatures, WasmNotSend, WasmNotSendSync,
+    CompareFunction, DeviceLostReason, DownlevelCapabilities, DynamicOffset, Extent3d, Face,
+    Features, FilterMode, FrontFace, ImageDataLayout, ImageSubresourceRange, IndexFormat, Limits,
+    MemoryRequirements, ScissorRect, ShaderStages, SparseBufferBind, SparseTextureBind,
+    SparseTextureTiles, SurfaceStatus, TextureFormat, TextureFormatFeatures, TimestampCalibration,
+    Viewport, WasmNotSend, WasmNotSendSync,
 };
 
 use crate::{
-    AnyWasmNotSendSync, BindGroupDescriptor, BindGroupLayoutDescriptor, Buffer, BufferAsyncError,
-    BufferDescriptor, CommandEncoderDescriptor, ComputePassDescriptor, ComputePipelineDescriptor,
-    DeviceDescriptor, Error, ErrorFilter, ImageCopyBuffer, ImageCopyTexture, Maintain,
-    MaintainResult, MapMode, PipelineLayoutDescriptor, QuerySetDescriptor, RenderBundleDescriptor,
+    AnyWasmNotSendSync, BindGroupDescriptor, BindGroupEntry, BindGroupLayoutDescriptor, Buffer,
+    BufferAsyncError, BufferDescriptor, CommandEncoderDescriptor, ComputePassDescriptor,
+    ComputePipelineDescriptor, DeviceDescriptor, Error, ErrorFilter, ImageCopyBuffer,
+    ImageCopyTexture, Maintain, MaintainResult, MaintainWork, MapMode, MemoryHeapDescriptor,
+    PipelineLayoutDescriptor, QuerySetDescriptor, QueueDescriptor, RenderBundleDescriptor,
     RenderBundleEncoderDescriptor, RenderPassDescriptor, RenderPipelineDescriptor,
     RequestAdapterOptions, RequestDeviceError, SamplerDescriptor, ShaderModuleDescriptor,
     ShaderModuleDescriptorSpirV, SurfaceTargetUnsafe, Texture, TextureDescriptor,
-    TextureViewDescriptor, UncapturedErrorHandler,
+    TextureViewDescriptor, TimelineSemaphoreDescriptor, UncapturedErrorHandler,
 };
 
 /// Meta trait for an id tracked by a context.
@@ -73,6 +79,10 @@ pub trait Context: Debug + WasmNotSendSync + Sized {
     type RenderBundleData: ContextData;
     type SurfaceId: ContextId + WasmNotSendSync;
     type SurfaceData: ContextData;
+    type TimelineSemaphoreId: ContextId + WasmNotSendSync;
+    type TimelineSemaphoreData: ContextData;
+    type MemoryHeapId: ContextId + WasmNotSendSync;
+    type MemoryHeapData: ContextData;
 
     type SurfaceOutputDetail: WasmNotSendSync + 'static;
     type SubmissionIndex: ContextId + Clone + Copy + WasmNotSendSync;
@@ -119,6 +129,12 @@ pub trait Context: Debug + WasmNotSendSync + Sized {
         surface: &Self::SurfaceId,
         surface_data: &Self::SurfaceData,
     ) -> bool;
+    fn adapter_get_surface_capabilities_headless(
+        &self,
+        adapter: &Self::AdapterId,
+        adapter_data: &Self::AdapterData,
+        display_handle: raw_window_handle::RawDisplayHandle,
+    ) -> wgt::SurfaceCapabilities;
     fn adapter_features(
         &self,
         adapter: &Self::AdapterId,
@@ -183,6 +199,12 @@ pub trait Context: Debug + WasmNotSendSync + Sized {
 
     fn device_features(&self, device: &Self::DeviceId, device_data: &Self::DeviceData) -> Features;
     fn device_limits(&self, device: &Self::DeviceId, device_data: &Self::DeviceData) -> Limits;
+    fn device_get_texture_format_features(
+        &self,
+        device: &Self::DeviceId,
+        device_data: &Self::DeviceData,
+        format: TextureFormat,
+    ) -> TextureFormatFeatures;
     fn device_downlevel_properties(
         &self,
         device: &Self::DeviceId,
@@ -207,6 +229,12 @@ pub trait Context: Debug + WasmNotSendSync + Sized {
         device_data: &Self::DeviceData,
         desc: &BindGroupLayoutDescriptor<'_>,
     ) -> (Self::BindGroupLayoutId, Self::BindGroupLayoutData);
+    fn device_create_inline_bind_group_layout(
+        &self,
+        device: &Self::DeviceId,
+        device_data: &Self::DeviceData,
+        desc: &BindGroupLayoutDescriptor<'_>,
+    ) -> (Self::BindGroupLayoutId, Self::BindGroupLayoutData);
     fn device_create_bind_group(
         &self,
         device: &Self::DeviceId,
@@ -237,12 +265,24 @@ pub trait Context: Debug + WasmNotSendSync + Sized {
         device_data: &Self::DeviceData,
         desc: &BufferDescriptor<'_>,
     ) -> (Self::BufferId, Self::BufferData);
+    fn device_create_sparse_buffer(
+        &self,
+        device: &Self::DeviceId,
+        device_data: &Self::DeviceData,
+        desc: &BufferDescriptor<'_>,
+    ) -> (Self::BufferId, Self::BufferData);
     fn device_create_texture(
         &self,
         device: &Self::DeviceId,
         device_data: &Self::DeviceData,
         desc: &TextureDescriptor<'_>,
     ) -> (Self::TextureId, Self::TextureData);
+    fn device_create_sparse_texture(
+        &self,
+        device: &Self::DeviceId,
+        device_data: &Self::DeviceData,
+        desc: &TextureDescriptor<'_>,
+    ) -> (Self::TextureId, Self::TextureData);
     fn device_create_sampler(
         &self,
         device: &Self::DeviceId,
@@ -255,6 +295,54 @@ pub trait Context: Debug + WasmNotSendSync + Sized {
         device_data: &Self::DeviceData,
         desc: &QuerySetDescriptor<'_>,
     ) -> (Self::QuerySetId, Self::QuerySetData);
+    fn device_create_queue(
+        &self,
+        device: &Self::DeviceId,
+        device_data: &Self::DeviceData,
+        desc: &QueueDescriptor<'_>,
+    ) -> (Self::QueueId, Self::QueueData);
+    fn device_create_timeline_semaphore(
+        &self,
+        device: &Self::DeviceId,
+        device_data: &Self::DeviceData,
+        desc: &TimelineSemaphoreDescriptor<'_>,
+    ) -> (Self::TimelineSemaphoreId, Self::TimelineSemaphoreData);
+    fn device_create_memory_heap(
+        &self,
+        device: &Self::DeviceId,
+        device_data: &Self::DeviceData,
+        desc: &MemoryHeapDescriptor<'_>,
+    ) -> (Self::MemoryHeapId, Self::MemoryHeapData);
+    fn device_create_placed_buffer(
+        &self,
+        device: &Self::DeviceId,
+        device_data: &Self::DeviceData,
+        heap: &Self::MemoryHeapId,
+        heap_data: &Self::MemoryHeapData,
+        offset: BufferAddress,
+        desc: &BufferDescriptor<'_>,
+    ) -> (Self::BufferId, Self::BufferData);
+    fn device_create_placed_texture(
+        &self,
+        device: &Self::DeviceId,
+        device_data: &Self::DeviceData,
+        heap: &Self::MemoryHeapId,
+        heap_data: &Self::MemoryHeapData,
+        offset: BufferAddress,
+        desc: &TextureDescriptor<'_>,
+    ) -> (Self::TextureId, Self::TextureData);
+    fn device_buffer_memory_requirements(
+        &self,
+        device: &Self::DeviceId,
+        device_data: &Self::DeviceData,
+        desc: &BufferDescriptor<'_>,
+    ) -> MemoryRequirements;
+    fn device_texture_memory_requirements(
+        &self,
+        device: &Self::DeviceId,
+        device_data: &Self::DeviceData,
+        desc: &TextureDescriptor<'_>,
+    ) -> MemoryRequirements;
     fn device_create_command_encoder(
         &self,
         device: &Self::DeviceId,
@@ -283,13 +371,51 @@ pub trait Context: Debug + WasmNotSendSync + Sized {
         device_data: &Self::DeviceData,
         message: &str,
     );
+    #[cfg(any(debug_assertions, feature = "test-hooks"))]
+    fn device_simulate_loss(
+        &self,
+        device: &Self::DeviceId,
+        device_data: &Self::DeviceData,
+        reason: DeviceLostReason,
+    );
+    fn device_set_pipeline_compile_timeout(
+        &self,
+        device: &Self::DeviceId,
+        device_data: &Self::DeviceData,
+        timeout: Option<std::time::Duration>,
+    );
+    unsafe fn device_set_validation_level(
+        &self,
+        device: &Self::DeviceId,
+        device_data: &Self::DeviceData,
+        level: wgt::ValidationLevel,
+    );
+    fn device_validation_level(
+        &self,
+        device: &Self::DeviceId,
+        device_data: &Self::DeviceData,
+    ) -> wgt::ValidationLevel;
+    fn device_set_performance_hint(
+        &self,
+        device: &Self::DeviceId,
+        device_data: &Self::DeviceData,
+        hint: wgt::PerformanceHint,
+    );
     fn queue_drop(&self, queue: &Self::QueueId, queue_data: &Self::QueueData);
     fn device_poll(
         &self,
         device: &Self::DeviceId,
         device_data: &Self::DeviceData,
         maintain: Maintain,
+        work: MaintainWork,
     ) -> MaintainResult;
+    fn device_wait_for_submission(
+        &self,
+        device: &Self::DeviceId,
+        device_data: &Self::DeviceData,
+        submission_index: &Self::SubmissionIndexData,
+        timeout: Duration,
+    ) -> bool;
     fn device_on_uncaptured_error(
         &self,
         device: &Self::DeviceId,
@@ -322,7 +448,23 @@ pub trait Context: Debug + WasmNotSendSync + Sized {
         buffer_data: &Self::BufferData,
         sub_range: Range<BufferAddress>,
     ) -> Box<dyn BufferMappedRange>;
+    unsafe fn buffer_get_mapped_range_unchecked(
+        &self,
+        buffer: &Self::BufferId,
+        buffer_data: &Self::BufferData,
+        sub_range: Range<BufferAddress>,
+    ) -> Box<dyn BufferMappedRange>;
     fn buffer_unmap(&self, buffer: &Self::BufferId, buffer_data: &Self::BufferData);
+    fn buffer_gpu_address(
+        &self,
+        buffer: &Self::BufferId,
+        buffer_data: &Self::BufferData,
+    ) -> BufferAddress;
+    fn buffer_label(
+        &self,
+        buffer: &Self::BufferId,
+        buffer_data: &Self::BufferData,
+    ) -> Option<String>;
     fn texture_create_view(
         &self,
         texture: &Self::TextureId,
@@ -335,6 +477,11 @@ pub trait Context: Debug + WasmNotSendSync + Sized {
     fn buffer_destroy(&self, buffer: &Self::BufferId, buffer_data: &Self::BufferData);
     fn buffer_drop(&self, buffer: &Self::BufferId, buffer_data: &Self::BufferData);
     fn texture_destroy(&self, texture: &Self::TextureId, texture_data: &Self::TextureData);
+    fn texture_sparse_tiles(
+        &self,
+        texture: &Self::TextureId,
+        texture_data: &Self::TextureData,
+    ) -> Option<SparseTextureTiles>;
     fn texture_drop(&self, texture: &Self::TextureId, texture_data: &Self::TextureData);
     fn texture_view_drop(
         &self,
@@ -343,6 +490,30 @@ pub trait Context: Debug + WasmNotSendSync + Sized {
     );
     fn sampler_drop(&self, sampler: &Self::SamplerId, sampler_data: &Self::SamplerData);
     fn query_set_drop(&self, query_set: &Self::QuerySetId, query_set_data: &Self::QuerySetData);
+    fn timeline_semaphore_drop(
+        &self,
+        semaphore: &Self::TimelineSemaphoreId,
+        semaphore_data: &Self::TimelineSemaphoreData,
+    );
+    fn memory_heap_drop(&self, heap: &Self::MemoryHeapId, heap_data: &Self::MemoryHeapData);
+    fn timeline_semaphore_value(
+        &self,
+        semaphore: &Self::TimelineSemaphoreId,
+        semaphore_data: &Self::TimelineSemaphoreData,
+    ) -> u64;
+    fn timeline_semaphore_signal(
+        &self,
+        semaphore: &Self::TimelineSemaphoreId,
+        semaphore_data: &Self::TimelineSemaphoreData,
+        value: u64,
+    );
+    fn timeline_semaphore_wait(
+        &self,
+        semaphore: &Self::TimelineSemaphoreId,
+        semaphore_data: &Self::TimelineSemaphoreData,
+        value: u64,
+        timeout: Duration,
+    ) -> bool;
     fn bind_group_drop(
         &self,
         bind_group: &Self::BindGroupId,
@@ -439,6 +610,17 @@ pub trait Context: Debug + WasmNotSendSync + Sized {
         destination: ImageCopyTexture<'_>,
         copy_size: Extent3d,
     );
+    #[allow(clippy::too_many_arguments)]
+    fn command_encoder_blit_texture(
+        &self,
+        encoder: &Self::CommandEncoderId,
+        encoder_data: &Self::CommandEncoderData,
+        source: ImageCopyTexture<'_>,
+        source_size: Extent3d,
+        destination: ImageCopyTexture<'_>,
+        destination_size: Extent3d,
+        filter: FilterMode,
+    );
 
     fn command_encoder_begin_compute_pass(
         &self,
@@ -487,6 +669,15 @@ pub trait Context: Debug + WasmNotSendSync + Sized {
         offset: BufferAddress,
         size: Option<BufferAddress>,
     );
+    fn command_encoder_fill_buffer(
+        &self,
+        encoder: &Self::CommandEncoderId,
+        encoder_data: &Self::CommandEncoderData,
+        buffer: &Buffer,
+        offset: BufferAddress,
+        size: Option<BufferAddress>,
+        value: u32,
+    );
 
     fn command_encoder_insert_debug_marker(
         &self,
@@ -576,6 +767,21 @@ pub trait Context: Debug + WasmNotSendSync + Sized {
         data_layout: ImageDataLayout,
         size: Extent3d,
     );
+    fn queue_create_staging_texture(
+        &self,
+        queue: &Self::QueueId,
+        queue_data: &Self::QueueData,
+        texture: ImageCopyTexture<'_>,
+        size: Extent3d,
+    ) -> Option<(Box<dyn QueueWriteBuffer>, ImageDataLayout)>;
+    fn queue_write_staging_texture(
+        &self,
+        queue: &Self::QueueId,
+        queue_data: &Self::QueueData,
+        texture: ImageCopyTexture<'_>,
+        size: Extent3d,
+        staging_buffer: &dyn QueueWriteBuffer,
+    );
     #[cfg(any(webgl, webgpu))]
     fn queue_copy_external_image_to_texture(
         &self,
@@ -591,17 +797,62 @@ pub trait Context: Debug + WasmNotSendSync + Sized {
         queue_data: &Self::QueueData,
         command_buffers: I,
     ) -> (Self::SubmissionIndex, Self::SubmissionIndexData);
+    fn queue_submit_with_semaphores<
+        I: Iterator<Item = (Self::CommandBufferId, Self::CommandBufferData)>,
+    >(
+        &self,
+        queue: &Self::QueueId,
+        queue_data: &Self::QueueData,
+        command_buffers: I,
+        waits: &[(Self::TimelineSemaphoreId, u64)],
+        signals: &[(Self::TimelineSemaphoreId, u64)],
+    ) -> (Self::SubmissionIndex, Self::SubmissionIndexData);
+    fn queue_bind_sparse(
+        &self,
+        queue: &Self::QueueId,
+        queue_data: &Self::QueueData,
+        buffer: &Self::BufferId,
+        buffer_data: &Self::BufferData,
+        binds: &[SparseBufferBind],
+    );
+    fn queue_bind_sparse_texture(
+        &self,
+        queue: &Self::QueueId,
+        queue_data: &Self::QueueData,
+        texture: &Self::TextureId,
+        texture_data: &Self::TextureData,
+        binds: &[SparseTextureBind],
+    );
     fn queue_get_timestamp_period(
         &self,
         queue: &Self::QueueId,
         queue_data: &Self::QueueData,
     ) -> f32;
+    fn queue_get_timestamp_calibration(
+        &self,
+        queue: &Self::QueueId,
+        queue_data: &Self::QueueData,
+    ) -> Option<TimestampCalibration>;
     fn queue_on_submitted_work_done(
         &self,
         queue: &Self::QueueId,
         queue_data: &Self::QueueData,
         callback: SubmittedWorkDoneCallback,
     );
+    fn queue_is_submission_complete(
+        &self,
+        queue: &Self::QueueId,
+        queue_data: &Self::QueueData,
+        submission_index: &Self::SubmissionIndexData,
+    ) -> bool;
+    fn queue_set_submit_batching(
+        &self,
+        queue: &Self::QueueId,
+        queue_data: &Self::QueueData,
+        enabled: bool,
+    );
+    fn queue_flush(&self, queue: &Self::QueueId, queue_data: &Self::QueueData);
+    fn submission_index_token(&self, submission_index: &Self::SubmissionIndexData) -> u64;
 
     fn device_start_capture(&self, device: &Self::DeviceId, device_data: &Self::DeviceData);
     fn device_stop_capture(&self, device: &Self::DeviceId, device_data: &Self::DeviceData);
@@ -822,6 +1073,13 @@ pub trait Context: Debug + WasmNotSendSync + Sized {
         bind_group_data: &Self::BindGroupData,
         offsets: &[DynamicOffset],
     );
+    fn render_pass_set_bind_group_inline(
+        &self,
+        pass: &mut Self::RenderPassId,
+        pass_data: &mut Self::RenderPassData,
+        index: u32,
+        entries: &[BindGroupEntry<'_>],
+    );
     #[allow(clippy::too_many_arguments)]
     fn render_pass_set_index_buffer(
         &self,
@@ -954,12 +1212,56 @@ pub trait Context: Debug + WasmNotSendSync + Sized {
         min_depth: f32,
         max_depth: f32,
     );
+    fn render_pass_set_viewports(
+        &self,
+        pass: &mut Self::RenderPassId,
+        pass_data: &mut Self::RenderPassData,
+        viewports: &[Viewport],
+    );
+    fn render_pass_set_scissor_rects(
+        &self,
+        pass: &mut Self::RenderPassId,
+        pass_data: &mut Self::RenderPassData,
+        rects: &[ScissorRect],
+    );
     fn render_pass_set_stencil_reference(
         &self,
         pass: &mut Self::RenderPassId,
         pass_data: &mut Self::RenderPassData,
         reference: u32,
     );
+    fn render_pass_set_stencil_reference_separate(
+        &self,
+        pass: &mut Self::RenderPassId,
+        pass_data: &mut Self::RenderPassData,
+        front: u32,
+        back: u32,
+    );
+    fn render_pass_set_stencil_write_mask(
+        &self,
+        pass: &mut Self::RenderPassId,
+        pass_data: &mut Self::RenderPassData,
+        front: u32,
+        back: u32,
+    );
+    fn render_pass_set_front_face(
+        &self,
+        pass: &mut Self::RenderPassId,
+        pass_data: &mut Self::RenderPassData,
+        front_face: FrontFace,
+    );
+    fn render_pass_set_cull_mode(
+        &self,
+        pass: &mut Self::RenderPassId,
+        pass_data: &mut Self::RenderPassData,
+        cull_mode: Option<Face>,
+    );
+    fn render_pass_set_depth_compare(
+        &self,
+        pass: &mut Self::RenderPassId,
+        pass_data: &mut Self::RenderPassData,
+        compare: CompareFunction,
+    );
     fn render_pass_insert_debug_marker(
         &self,
         pass: &mut Self::RenderPassId,
@@ -1161,6 +1463,12 @@ pub(crate) trait DynContext: Debug + WasmNotSendSync {
         surface: &ObjectId,
         surface_data: &crate::Data,
     ) -> bool;
+    fn adapter_get_surface_capabilities_headless(
+        &self,
+        adapter: &ObjectId,
+        adapter_data: &crate::Data,
+        display_handle: raw_window_handle::RawDisplayHandle,
+    ) -> wgt::SurfaceCapabilities;
     fn adapter_features(&self, adapter: &ObjectId, adapter_data: &crate::Data) -> Features;
     fn adapter_limits(&self, adapter: &ObjectId, adapter_data: &crate::Data) -> Limits;
     fn adapter_downlevel_capabilities(
@@ -1211,6 +1519,12 @@ pub(crate) trait DynContext: Debug + WasmNotSendSync {
 
     fn device_features(&self, device: &ObjectId, device_data: &crate::Data) -> Features;
     fn device_limits(&self, device: &ObjectId, device_data: &crate::Data) -> Limits;
+    fn device_get_texture_format_features(
+        &self,
+        device: &ObjectId,
+        device_data: &crate::Data,
+        format: TextureFormat,
+    ) -> TextureFormatFeatures;
     fn device_downlevel_properties(
         &self,
         device: &ObjectId,
@@ -1235,6 +1549,12 @@ pub(crate) trait DynContext: Debug + WasmNotSendSync {
         device_data: &crate::Data,
         desc: &BindGroupLayoutDescriptor<'_>,
     ) -> (ObjectId, Box<crate::Data>);
+    fn device_create_inline_bind_group_layout(
+        &self,
+        device: &ObjectId,
+        device_data: &crate::Data,
+        desc: &BindGroupLayoutDescriptor<'_>,
+    ) -> (ObjectId, Box<crate::Data>);
     fn device_create_bind_group(
         &self,
         device: &ObjectId,
@@ -1265,12 +1585,24 @@ pub(crate) trait DynContext: Debug + WasmNotSendSync {
         device_data: &crate::Data,
         desc: &BufferDescriptor<'_>,
     ) -> (ObjectId, Box<crate::Data>);
+    fn device_create_sparse_buffer(
+        &self,
+        device: &ObjectId,
+        device_data: &crate::Data,
+        desc: &BufferDescriptor<'_>,
+    ) -> (ObjectId, Box<crate::Data>);
     fn device_create_texture(
         &self,
         device: &ObjectId,
         device_data: &crate::Data,
         desc: &TextureDescriptor<'_>,
     ) -> (ObjectId, Box<crate::Data>);
+    fn device_create_sparse_texture(
+        &self,
+        device: &ObjectId,
+        device_data: &crate::Data,
+        desc: &TextureDescriptor<'_>,
+    ) -> (ObjectId, Box<crate::Data>);
     fn device_create_sampler(
         &self,
         device: &ObjectId,
@@ -1283,6 +1615,54 @@ pub(crate) trait DynContext: Debug + WasmNotSendSync {
         device_data: &crate::Data,
         desc: &QuerySetDescriptor<'_>,
     ) -> (ObjectId, Box<crate::Data>);
+    fn device_create_queue(
+        &self,
+        device: &ObjectId,
+        device_data: &crate::Data,
+        desc: &QueueDescriptor<'_>,
+    ) -> (ObjectId, Box<crate::Data>);
+    fn device_create_timeline_semaphore(
+        &self,
+        device: &ObjectId,
+        device_data: &crate::Data,
+        desc: &TimelineSemaphoreDescriptor<'_>,
+    ) -> (ObjectId, Box<crate::Data>);
+    fn device_create_memory_heap(
+        &self,
+        device: &ObjectId,
+        device_data: &crate::Data,
+        desc: &MemoryHeapDescriptor<'_>,
+    ) -> (ObjectId, Box<crate::Data>);
+    fn device_create_placed_buffer(
+        &self,
+        device: &ObjectId,
+        device_data: &crate::Data,
+        heap: &ObjectId,
+        heap_data: &crate::Data,
+        offset: BufferAddress,
+        desc: &BufferDescriptor<'_>,
+    ) -> (ObjectId, Box<crate::Data>);
+    fn device_create_placed_texture(
+        &self,
+        device: &ObjectId,
+        device_data: &crate::Data,
+        heap: &ObjectId,
+        heap_data: &crate::Data,
+        offset: BufferAddress,
+        desc: &TextureDescriptor<'_>,
+    ) -> (ObjectId, Box<crate::Data>);
+    fn device_buffer_memory_requirements(
+        &self,
+        device: &ObjectId,
+        device_data: &crate::Data,
+        desc: &BufferDescriptor<'_>,
+    ) -> MemoryRequirements;
+    fn device_texture_memory_requirements(
+        &self,
+        device: &ObjectId,
+        device_data: &crate::Data,
+        desc: &TextureDescriptor<'_>,
+    ) -> MemoryRequirements;
     fn device_create_command_encoder(
         &self,
         device: &ObjectId,
@@ -1306,35 +1686,73 @@ pub(crate) trait DynContext: Debug + WasmNotSendSync {
     );
     fn device_destroy(&self, device: &ObjectId, device_data: &crate::Data);
     fn device_mark_lost(&self, device: &ObjectId, device_data: &crate::Data, message: &str);
-    fn queue_drop(&self, queue: &ObjectId, queue_data: &crate::Data);
-    fn device_poll(
+    #[cfg(any(debug_assertions, feature = "test-hooks"))]
+    fn device_simulate_loss(
         &self,
         device: &ObjectId,
         device_data: &crate::Data,
-        maintain: Maintain,
-    ) -> MaintainResult;
-    fn device_on_uncaptured_error(
+        reason: DeviceLostReason,
+    );
+    fn device_set_pipeline_compile_timeout(
         &self,
         device: &ObjectId,
         device_data: &crate::Data,
-        handler: Box<dyn UncapturedErrorHandler>,
+        timeout: Option<std::time::Duration>,
     );
-    fn device_push_error_scope(
+    unsafe fn device_set_validation_level(
         &self,
         device: &ObjectId,
         device_data: &crate::Data,
-        filter: ErrorFilter,
+        level: wgt::ValidationLevel,
     );
-    fn device_pop_error_scope(
+    fn device_validation_level(
         &self,
         device: &ObjectId,
         device_data: &crate::Data,
-    ) -> Pin<DevicePopErrorFuture>;
-    fn buffer_map_async(
+    ) -> wgt::ValidationLevel;
+    fn device_set_performance_hint(
         &self,
-        buffer: &ObjectId,
-        buffer_data: &crate::Data,
-        mode: MapMode,
+        device: &ObjectId,
+        device_data: &crate::Data,
+        hint: wgt::PerformanceHint,
+    );
+    fn queue_drop(&self, queue: &ObjectId, queue_data: &crate::Data);
+    fn device_poll(
+        &self,
+        device: &ObjectId,
+        device_data: &crate::Data,
+        maintain: Maintain,
+        work: MaintainWork,
+    ) -> MaintainResult;
+    fn device_wait_for_submission(
+        &self,
+        device: &ObjectId,
+        device_data: &crate::Data,
+        submission_index: &crate::Data,
+        timeout: Duration,
+    ) -> bool;
+    fn device_on_uncaptured_error(
+        &self,
+        device: &ObjectId,
+        device_data: &crate::Data,
+        handler: Box<dyn UncapturedErrorHandler>,
+    );
+    fn device_push_error_scope(
+        &self,
+        device: &ObjectId,
+        device_data: &crate::Data,
+        filter: ErrorFilter,
+    );
+    fn device_pop_error_scope(
+        &self,
+        device: &ObjectId,
+        device_data: &crate::Data,
+    ) -> Pin<DevicePopErrorFuture>;
+    fn buffer_map_async(
+        &self,
+        buffer: &ObjectId,
+        buffer_data: &crate::Data,
+        mode: MapMode,
         range: Range<BufferAddress>,
         callback: BufferMapCallback,
     );
@@ -1344,7 +1762,15 @@ pub(crate) trait DynContext: Debug + WasmNotSendSync {
         buffer_data: &crate::Data,
         sub_range: Range<BufferAddress>,
     ) -> Box<dyn BufferMappedRange>;
+    unsafe fn buffer_get_mapped_range_unchecked(
+        &self,
+        buffer: &ObjectId,
+        buffer_data: &crate::Data,
+        sub_range: Range<BufferAddress>,
+    ) -> Box<dyn BufferMappedRange>;
     fn buffer_unmap(&self, buffer: &ObjectId, buffer_data: &crate::Data);
+    fn buffer_gpu_address(&self, buffer: &ObjectId, buffer_data: &crate::Data) -> BufferAddress;
+    fn buffer_label(&self, buffer: &ObjectId, buffer_data: &crate::Data) -> Option<String>;
     fn texture_create_view(
         &self,
         texture: &ObjectId,
@@ -1357,10 +1783,31 @@ pub(crate) trait DynContext: Debug + WasmNotSendSync {
     fn buffer_destroy(&self, buffer: &ObjectId, buffer_data: &crate::Data);
     fn buffer_drop(&self, buffer: &ObjectId, buffer_data: &crate::Data);
     fn texture_destroy(&self, buffer: &ObjectId, buffer_data: &crate::Data);
+    fn texture_sparse_tiles(
+        &self,
+        texture: &ObjectId,
+        texture_data: &crate::Data,
+    ) -> Option<SparseTextureTiles>;
     fn texture_drop(&self, texture: &ObjectId, texture_data: &crate::Data);
     fn texture_view_drop(&self, texture_view: &ObjectId, texture_view_data: &crate::Data);
     fn sampler_drop(&self, sampler: &ObjectId, sampler_data: &crate::Data);
     fn query_set_drop(&self, query_set: &ObjectId, query_set_data: &crate::Data);
+    fn timeline_semaphore_drop(&self, semaphore: &ObjectId, semaphore_data: &crate::Data);
+    fn memory_heap_drop(&self, heap: &ObjectId, heap_data: &crate::Data);
+    fn timeline_semaphore_value(&self, semaphore: &ObjectId, semaphore_data: &crate::Data) -> u64;
+    fn timeline_semaphore_signal(
+        &self,
+        semaphore: &ObjectId,
+        semaphore_data: &crate::Data,
+        value: u64,
+    );
+    fn timeline_semaphore_wait(
+        &self,
+        semaphore: &ObjectId,
+        semaphore_data: &crate::Data,
+        value: u64,
+        timeout: Duration,
+    ) -> bool;
     fn bind_group_drop(&self, bind_group: &ObjectId, bind_group_data: &crate::Data);
     fn bind_group_layout_drop(
         &self,
@@ -1425,6 +1872,17 @@ pub(crate) trait DynContext: Debug + WasmNotSendSync {
         destination: ImageCopyTexture<'_>,
         copy_size: Extent3d,
     );
+    #[allow(clippy::too_many_arguments)]
+    fn command_encoder_blit_texture(
+        &self,
+        encoder: &ObjectId,
+        encoder_data: &crate::Data,
+        source: ImageCopyTexture<'_>,
+        source_size: Extent3d,
+        destination: ImageCopyTexture<'_>,
+        destination_size: Extent3d,
+        filter: FilterMode,
+    );
 
     fn command_encoder_begin_compute_pass(
         &self,
@@ -1473,6 +1931,15 @@ pub(crate) trait DynContext: Debug + WasmNotSendSync {
         offset: BufferAddress,
         size: Option<BufferAddress>,
     );
+    fn command_encoder_fill_buffer(
+        &self,
+        encoder: &ObjectId,
+        encoder_data: &crate::Data,
+        buffer: &Buffer,
+        offset: BufferAddress,
+        size: Option<BufferAddress>,
+        value: u32,
+    );
 
     fn command_encoder_insert_debug_marker(
         &self,
@@ -1558,6 +2025,21 @@ pub(crate) trait DynContext: Debug + WasmNotSendSync {
         data_layout: ImageDataLayout,
         size: Extent3d,
     );
+    fn queue_create_staging_texture(
+        &self,
+        queue: &ObjectId,
+        queue_data: &crate::Data,
+        texture: ImageCopyTexture<'_>,
+        size: Extent3d,
+    ) -> Option<(Box<dyn QueueWriteBuffer>, ImageDataLayout)>;
+    fn queue_write_staging_texture(
+        &self,
+        queue: &ObjectId,
+        queue_data: &crate::Data,
+        texture: ImageCopyTexture<'_>,
+        size: Extent3d,
+        staging_buffer: &dyn QueueWriteBuffer,
+    );
     #[cfg(any(webgpu, webgl))]
     fn queue_copy_external_image_to_texture(
         &self,
@@ -1573,13 +2055,51 @@ pub(crate) trait DynContext: Debug + WasmNotSendSync {
         queue_data: &crate::Data,
         command_buffers: &mut dyn Iterator<Item = (ObjectId, Box<crate::Data>)>,
     ) -> (ObjectId, Arc<crate::Data>);
+    fn queue_submit_with_semaphores(
+        &self,
+        queue: &ObjectId,
+        queue_data: &crate::Data,
+        command_buffers: &mut dyn Iterator<Item = (ObjectId, Box<crate::Data>)>,
+        waits: &[(ObjectId, u64)],
+        signals: &[(ObjectId, u64)],
+    ) -> (ObjectId, Arc<crate::Data>);
+    fn queue_bind_sparse(
+        &self,
+        queue: &ObjectId,
+        queue_data: &crate::Data,
+        buffer: &ObjectId,
+        buffer_data: &crate::Data,
+        binds: &[SparseBufferBind],
+    );
+    fn queue_bind_sparse_texture(
+        &self,
+        queue: &ObjectId,
+        queue_data: &crate::Data,
+        texture: &ObjectId,
+        texture_data: &crate::Data,
+        binds: &[SparseTextureBind],
+    );
     fn queue_get_timestamp_period(&self, queue: &ObjectId, queue_data: &crate::Data) -> f32;
+    fn queue_get_timestamp_calibration(
+        &self,
+        queue: &ObjectId,
+        queue_data: &crate::Data,
+    ) -> Option<TimestampCalibration>;
     fn queue_on_submitted_work_done(
         &self,
         queue: &ObjectId,
         queue_data: &crate::Data,
         callback: SubmittedWorkDoneCallback,
     );
+    fn queue_is_submission_complete(
+        &self,
+        queue: &ObjectId,
+        queue_data: &crate::Data,
+        submission_index: &crate::Data,
+    ) -> bool;
+    fn queue_set_submit_batching(&self, queue: &ObjectId, queue_data: &crate::Data, enabled: bool);
+    fn queue_flush(&self, queue: &ObjectId, queue_data: &crate::Data);
+    fn submission_index_token(&self, submission_index: &crate::Data) -> u64;
 
     fn device_start_capture(&self, device: &ObjectId, data: &crate::Data);
     fn device_stop_capture(&self, device: &ObjectId, data: &crate::Data);
@@ -1796,6 +2316,13 @@ pub(crate) trait DynContext: Debug + WasmNotSendSync {
         bind_group_data: &crate::Data,
         offsets: &[DynamicOffset],
     );
+    fn render_pass_set_bind_group_inline(
+        &self,
+        pass: &mut ObjectId,
+        pass_data: &mut crate::Data,
+        index: u32,
+        entries: &[BindGroupEntry<'_>],
+    );
     #[allow(clippy::too_many_arguments)]
     fn render_pass_set_index_buffer(
         &self,
@@ -1928,12 +2455,56 @@ pub(crate) trait DynContext: Debug + WasmNotSendSync {
         min_depth: f32,
         max_depth: f32,
     );
+    fn render_pass_set_viewports(
+        &self,
+        pass: &mut ObjectId,
+        pass_data: &mut crate::Data,
+        viewports: &[Viewport],
+    );
+    fn render_pass_set_scissor_rects(
+        &self,
+        pass: &mut ObjectId,
+        pass_data: &mut crate::Data,
+        rects: &[ScissorRect],
+    );
     fn render_pass_set_stencil_reference(
         &self,
         pass: &mut ObjectId,
         pass_data: &mut crate::Data,
         reference: u32,
     );
+    fn render_pass_set_stencil_reference_separate(
+        &self,
+        pass: &mut ObjectId,
+        pass_data: &mut crate::Data,
+        front: u32,
+        back: u32,
+    );
+    fn render_pass_set_stencil_write_mask(
+        &self,
+        pass: &mut ObjectId,
+        pass_data: &mut crate::Data,
+        front: u32,
+        back: u32,
+    );
+    fn render_pass_set_front_face(
+        &self,
+        pass: &mut ObjectId,
+        pass_data: &mut crate::Data,
+        front_face: FrontFace,
+    );
+    fn render_pass_set_cull_mode(
+        &self,
+        pass: &mut ObjectId,
+        pass_data: &mut crate::Data,
+        cull_mode: Option<Face>,
+    );
+    fn render_pass_set_depth_compare(
+        &self,
+        pass: &mut ObjectId,
+        pass_data: &mut crate::Data,
+        compare: CompareFunction,
+    );
     fn render_pass_insert_debug_marker(
         &self,
         pass: &mut ObjectId,
@@ -2051,6 +2622,22 @@ where
         Context::adapter_is_surface_supported(self, &adapter, adapter_data, &surface, surface_data)
     }
 
+    fn adapter_get_surface_capabilities_headless(
+        &self,
+        adapter: &ObjectId,
+        adapter_data: &crate::Data,
+        display_handle: raw_window_handle::RawDisplayHandle,
+    ) -> wgt::SurfaceCapabilities {
+        let adapter = <T::AdapterId>::from(*adapter);
+        let adapter_data = downcast_ref(adapter_data);
+        Context::adapter_get_surface_capabilities_headless(
+            self,
+            &adapter,
+            adapter_data,
+            display_handle,
+        )
+    }
+
     fn adapter_features(&self, adapter: &ObjectId, adapter_data: &crate::Data) -> Features {
         let adapter = <T::AdapterId>::from(*adapter);
         let adapter_data = downcast_ref(adapter_data);
@@ -2173,6 +2760,17 @@ where
         Context::device_limits(self, &device, device_data)
     }
 
+    fn device_get_texture_format_features(
+        &self,
+        device: &ObjectId,
+        device_data: &crate::Data,
+        format: TextureFormat,
+    ) -> TextureFormatFeatures {
+        let device = <T::DeviceId>::from(*device);
+        let device_data = downcast_ref(device_data);
+        Context::device_get_texture_format_features(self, &device, device_data, format)
+    }
+
     fn device_downlevel_properties(
         &self,
         device: &ObjectId,
@@ -2228,6 +2826,19 @@ where
         (bind_group_layout.into(), Box::new(data) as _)
     }
 
+    fn device_create_inline_bind_group_layout(
+        &self,
+        device: &ObjectId,
+        device_data: &crate::Data,
+        desc: &BindGroupLayoutDescriptor<'_>,
+    ) -> (ObjectId, Box<crate::Data>) {
+        let device = <T::DeviceId>::from(*device);
+        let device_data = downcast_ref(device_data);
+        let (bind_group_layout, data) =
+            Context::device_create_inline_bind_group_layout(self, &device, device_data, desc);
+        (bind_group_layout.into(), Box::new(data) as _)
+    }
+
     fn device_create_bind_group(
         &self,
         device: &ObjectId,
@@ -2292,6 +2903,18 @@ where
         (buffer.into(), Box::new(data) as _)
     }
 
+    fn device_create_sparse_buffer(
+        &self,
+        device: &ObjectId,
+        device_data: &crate::Data,
+        desc: &BufferDescriptor<'_>,
+    ) -> (ObjectId, Box<crate::Data>) {
+        let device = <T::DeviceId>::from(*device);
+        let device_data = downcast_ref(device_data);
+        let (buffer, data) = Context::device_create_sparse_buffer(self, &device, device_data, desc);
+        (buffer.into(), Box::new(data) as _)
+    }
+
     fn device_create_texture(
         &self,
         device: &ObjectId,
@@ -2304,6 +2927,19 @@ where
         (texture.into(), Box::new(data) as _)
     }
 
+    fn device_create_sparse_texture(
+        &self,
+        device: &ObjectId,
+        device_data: &crate::Data,
+        desc: &TextureDescriptor<'_>,
+    ) -> (ObjectId, Box<crate::Data>) {
+        let device = <T::DeviceId>::from(*device);
+        let device_data = downcast_ref(device_data);
+        let (texture, data) =
+            Context::device_create_sparse_texture(self, &device, device_data, desc);
+        (texture.into(), Box::new(data) as _)
+    }
+
     fn device_create_sampler(
         &self,
         device: &ObjectId,
@@ -2328,6 +2964,115 @@ where
         (query_set.into(), Box::new(data) as _)
     }
 
+    fn device_create_queue(
+        &self,
+        device: &ObjectId,
+        device_data: &crate::Data,
+        desc: &QueueDescriptor<'_>,
+    ) -> (ObjectId, Box<crate::Data>) {
+        let device = <T::DeviceId>::from(*device);
+        let device_data = downcast_ref(device_data);
+        let (queue, data) = Context::device_create_queue(self, &device, device_data, desc);
+        (queue.into(), Box::new(data) as _)
+    }
+
+    fn device_create_timeline_semaphore(
+        &self,
+        device: &ObjectId,
+        device_data: &crate::Data,
+        desc: &TimelineSemaphoreDescriptor<'_>,
+    ) -> (ObjectId, Box<crate::Data>) {
+        let device = <T::DeviceId>::from(*device);
+        let device_data = downcast_ref(device_data);
+        let (semaphore, data) =
+            Context::device_create_timeline_semaphore(self, &device, device_data, desc);
+        (semaphore.into(), Box::new(data) as _)
+    }
+
+    fn device_create_memory_heap(
+        &self,
+        device: &ObjectId,
+        device_data: &crate::Data,
+        desc: &MemoryHeapDescriptor<'_>,
+    ) -> (ObjectId, Box<crate::Data>) {
+        let device = <T::DeviceId>::from(*device);
+        let device_data = downcast_ref(device_data);
+        let (heap, data) = Context::device_create_memory_heap(self, &device, device_data, desc);
+        (heap.into(), Box::new(data) as _)
+    }
+
+    fn device_create_placed_buffer(
+        &self,
+        device: &ObjectId,
+        device_data: &crate::Data,
+        heap: &ObjectId,
+        heap_data: &crate::Data,
+        offset: BufferAddress,
+        desc: &BufferDescriptor<'_>,
+    ) -> (ObjectId, Box<crate::Data>) {
+        let device = <T::DeviceId>::from(*device);
+        let device_data = downcast_ref(device_data);
+        let heap = <T::MemoryHeapId>::from(*heap);
+        let heap_data = downcast_ref(heap_data);
+        let (buffer, data) = Context::device_create_placed_buffer(
+            self,
+            &device,
+            device_data,
+            &heap,
+            heap_data,
+            offset,
+            desc,
+        );
+        (buffer.into(), Box::new(data) as _)
+    }
+
+    fn device_create_placed_texture(
+        &self,
+        device: &ObjectId,
+        device_data: &crate::Data,
+        heap: &ObjectId,
+        heap_data: &crate::Data,
+        offset: BufferAddress,
+        desc: &TextureDescriptor<'_>,
+    ) -> (ObjectId, Box<crate::Data>) {
+        let device = <T::DeviceId>::from(*device);
+        let device_data = downcast_ref(device_data);
+        let heap = <T::MemoryHeapId>::from(*heap);
+        let heap_data = downcast_ref(heap_data);
+        let (texture, data) = Context::device_create_placed_texture(
+            self,
+            &device,
+            device_data,
+            &heap,
+            heap_data,
+            offset,
+            desc,
+        );
+        (texture.into(), Box::new(data) as _)
+    }
+
+    fn device_buffer_memory_requirements(
+        &self,
+        device: &ObjectId,
+        device_data: &crate::Data,
+        desc: &BufferDescriptor<'_>,
+    ) -> MemoryRequirements {
+        let device = <T::DeviceId>::from(*device);
+        let device_data = downcast_ref(device_data);
+        Context::device_buffer_memory_requirements(self, &device, device_data, desc)
+    }
+
+    fn device_texture_memory_requirements(
+        &self,
+        device: &ObjectId,
+        device_data: &crate::Data,
+        desc: &TextureDescriptor<'_>,
+    ) -> MemoryRequirements {
+        let device = <T::DeviceId>::from(*device);
+        let device_data = downcast_ref(device_data);
+        Context::device_texture_memory_requirements(self, &device, device_data, desc)
+    }
+
     fn device_create_command_encoder(
         &self,
         device: &ObjectId,
@@ -2364,30 +3109,85 @@ where
     fn device_drop(&self, device: &ObjectId, device_data: &crate::Data) {
         let device = <T::DeviceId>::from(*device);
         let device_data = downcast_ref(device_data);
-        Context::device_drop(self, &device, device_data)
+        Context::device_drop(self, &device, device_data)
+    }
+
+    fn device_set_device_lost_callback(
+        &self,
+        device: &ObjectId,
+        device_data: &crate::Data,
+        device_lost_callback: DeviceLostCallback,
+    ) {
+        let device = <T::DeviceId>::from(*device);
+        let device_data = downcast_ref(device_data);
+        Context::device_set_device_lost_callback(self, &device, device_data, device_lost_callback)
+    }
+
+    fn device_destroy(&self, device: &ObjectId, device_data: &crate::Data) {
+        let device = <T::DeviceId>::from(*device);
+        let device_data = downcast_ref(device_data);
+        Context::device_destroy(self, &device, device_data)
+    }
+
+    fn device_mark_lost(&self, device: &ObjectId, device_data: &crate::Data, message: &str) {
+        let device = <T::DeviceId>::from(*device);
+        let device_data = downcast_ref(device_data);
+        Context::device_mark_lost(self, &device, device_data, message)
+    }
+
+    #[cfg(any(debug_assertions, feature = "test-hooks"))]
+    fn device_simulate_loss(
+        &self,
+        device: &ObjectId,
+        device_data: &crate::Data,
+        reason: DeviceLostReason,
+    ) {
+        let device = <T::DeviceId>::from(*device);
+        let device_data = downcast_ref(device_data);
+        Context::device_simulate_loss(self, &device, device_data, reason)
+    }
+
+    fn device_set_pipeline_compile_timeout(
+        &self,
+        device: &ObjectId,
+        device_data: &crate::Data,
+        timeout: Option<std::time::Duration>,
+    ) {
+        let device = <T::DeviceId>::from(*device);
+        let device_data = downcast_ref(device_data);
+        Context::device_set_pipeline_compile_timeout(self, &device, device_data, timeout)
     }
 
-    fn device_set_device_lost_callback(
+    unsafe fn device_set_validation_level(
         &self,
         device: &ObjectId,
         device_data: &crate::Data,
-        device_lost_callback: DeviceLostCallback,
+        level: wgt::ValidationLevel,
     ) {
         let device = <T::DeviceId>::from(*device);
         let device_data = downcast_ref(device_data);
-        Context::device_set_device_lost_callback(self, &device, device_data, device_lost_callback)
+        unsafe { Context::device_set_validation_level(self, &device, device_data, level) }
     }
 
-    fn device_destroy(&self, device: &ObjectId, device_data: &crate::Data) {
+    fn device_validation_level(
+        &self,
+        device: &ObjectId,
+        device_data: &crate::Data,
+    ) -> wgt::ValidationLevel {
         let device = <T::DeviceId>::from(*device);
         let device_data = downcast_ref(device_data);
-        Context::device_destroy(self, &device, device_data)
+        Context::device_validation_level(self, &device, device_data)
     }
 
-    fn device_mark_lost(&self, device: &ObjectId, device_data: &crate::Data, message: &str) {
+    fn device_set_performance_hint(
+        &self,
+        device: &ObjectId,
+        device_data: &crate::Data,
+        hint: wgt::PerformanceHint,
+    ) {
         let device = <T::DeviceId>::from(*device);
         let device_data = downcast_ref(device_data);
-        Context::device_mark_lost(self, &device, device_data, message)
+        Context::device_set_performance_hint(self, &device, device_data, hint)
     }
 
     fn queue_drop(&self, queue: &ObjectId, queue_data: &crate::Data) {
@@ -2401,10 +3201,24 @@ where
         device: &ObjectId,
         device_data: &crate::Data,
         maintain: Maintain,
+        work: MaintainWork,
     ) -> MaintainResult {
         let device = <T::DeviceId>::from(*device);
         let device_data = downcast_ref(device_data);
-        Context::device_poll(self, &device, device_data, maintain)
+        Context::device_poll(self, &device, device_data, maintain, work)
+    }
+
+    fn device_wait_for_submission(
+        &self,
+        device: &ObjectId,
+        device_data: &crate::Data,
+        submission_index: &crate::Data,
+        timeout: Duration,
+    ) -> bool {
+        let device = <T::DeviceId>::from(*device);
+        let device_data = downcast_ref(device_data);
+        let submission_index = downcast_ref(submission_index);
+        Context::device_wait_for_submission(self, &device, device_data, submission_index, timeout)
     }
 
     fn device_on_uncaptured_error(
@@ -2463,12 +3277,35 @@ where
         Context::buffer_get_mapped_range(self, &buffer, buffer_data, sub_range)
     }
 
+    unsafe fn buffer_get_mapped_range_unchecked(
+        &self,
+        buffer: &ObjectId,
+        buffer_data: &crate::Data,
+        sub_range: Range<BufferAddress>,
+    ) -> Box<dyn BufferMappedRange> {
+        let buffer = <T::BufferId>::from(*buffer);
+        let buffer_data = downcast_ref(buffer_data);
+        unsafe { Context::buffer_get_mapped_range_unchecked(self, &buffer, buffer_data, sub_range) }
+    }
+
     fn buffer_unmap(&self, buffer: &ObjectId, buffer_data: &crate::Data) {
         let buffer = <T::BufferId>::from(*buffer);
         let buffer_data = downcast_ref(buffer_data);
         Context::buffer_unmap(self, &buffer, buffer_data)
     }
 
+    fn buffer_gpu_address(&self, buffer: &ObjectId, buffer_data: &crate::Data) -> BufferAddress {
+        let buffer = <T::BufferId>::from(*buffer);
+        let buffer_data = downcast_ref(buffer_data);
+        Context::buffer_gpu_address(self, &buffer, buffer_data)
+    }
+
+    fn buffer_label(&self, buffer: &ObjectId, buffer_data: &crate::Data) -> Option<String> {
+        let buffer = <T::BufferId>::from(*buffer);
+        let buffer_data = downcast_ref(buffer_data);
+        Context::buffer_label(self, &buffer, buffer_data)
+    }
+
     fn texture_create_view(
         &self,
         texture: &ObjectId,
@@ -2511,6 +3348,16 @@ where
         Context::texture_destroy(self, &texture, texture_data)
     }
 
+    fn texture_sparse_tiles(
+        &self,
+        texture: &ObjectId,
+        texture_data: &crate::Data,
+    ) -> Option<SparseTextureTiles> {
+        let texture = <T::TextureId>::from(*texture);
+        let texture_data = downcast_ref(texture_data);
+        Context::texture_sparse_tiles(self, &texture, texture_data)
+    }
+
     fn texture_drop(&self, texture: &ObjectId, texture_data: &crate::Data) {
         let texture = <T::TextureId>::from(*texture);
         let texture_data = downcast_ref(texture_data);
@@ -2535,6 +3382,47 @@ where
         Context::query_set_drop(self, &query_set, query_set_data)
     }
 
+    fn timeline_semaphore_drop(&self, semaphore: &ObjectId, semaphore_data: &crate::Data) {
+        let semaphore = <T::TimelineSemaphoreId>::from(*semaphore);
+        let semaphore_data = downcast_ref(semaphore_data);
+        Context::timeline_semaphore_drop(self, &semaphore, semaphore_data)
+    }
+
+    fn memory_heap_drop(&self, heap: &ObjectId, heap_data: &crate::Data) {
+        let heap = <T::MemoryHeapId>::from(*heap);
+        let heap_data = downcast_ref(heap_data);
+        Context::memory_heap_drop(self, &heap, heap_data)
+    }
+
+    fn timeline_semaphore_value(&self, semaphore: &ObjectId, semaphore_data: &crate::Data) -> u64 {
+        let semaphore = <T::TimelineSemaphoreId>::from(*semaphore);
+        let semaphore_data = downcast_ref(semaphore_data);
+        Context::timeline_semaphore_value(self, &semaphore, semaphore_data)
+    }
+
+    fn timeline_semaphore_signal(
+        &self,
+        semaphore: &ObjectId,
+        semaphore_data: &crate::Data,
+        value: u64,
+    ) {
+        let semaphore = <T::TimelineSemaphoreId>::from(*semaphore);
+        let semaphore_data = downcast_ref(semaphore_data);
+        Context::timeline_semaphore_signal(self, &semaphore, semaphore_data, value)
+    }
+
+    fn timeline_semaphore_wait(
+        &self,
+        semaphore: &ObjectId,
+        semaphore_data: &crate::Data,
+        value: u64,
+        timeout: Duration,
+    ) -> bool {
+        let semaphore = <T::TimelineSemaphoreId>::from(*semaphore);
+        let semaphore_data = downcast_ref(semaphore_data);
+        Context::timeline_semaphore_wait(self, &semaphore, semaphore_data, value, timeout)
+    }
+
     fn bind_group_drop(&self, bind_group: &ObjectId, bind_group_data: &crate::Data) {
         let bind_group = <T::BindGroupId>::from(*bind_group);
         let bind_group_data = downcast_ref(bind_group_data);
@@ -2711,6 +3599,30 @@ where
         )
     }
 
+    fn command_encoder_blit_texture(
+        &self,
+        encoder: &ObjectId,
+        encoder_data: &crate::Data,
+        source: ImageCopyTexture<'_>,
+        source_size: Extent3d,
+        destination: ImageCopyTexture<'_>,
+        destination_size: Extent3d,
+        filter: FilterMode,
+    ) {
+        let encoder = <T::CommandEncoderId>::from(*encoder);
+        let encoder_data = downcast_ref(encoder_data);
+        Context::command_encoder_blit_texture(
+            self,
+            &encoder,
+            encoder_data,
+            source,
+            source_size,
+            destination,
+            destination_size,
+            filter,
+        )
+    }
+
     fn command_encoder_begin_compute_pass(
         &self,
         encoder: &ObjectId,
@@ -2812,6 +3724,28 @@ where
         Context::command_encoder_clear_buffer(self, &encoder, encoder_data, buffer, offset, size)
     }
 
+    fn command_encoder_fill_buffer(
+        &self,
+        encoder: &ObjectId,
+        encoder_data: &crate::Data,
+        buffer: &Buffer,
+        offset: BufferAddress,
+        size: Option<BufferAddress>,
+        value: u32,
+    ) {
+        let encoder = <T::CommandEncoderId>::from(*encoder);
+        let encoder_data = downcast_ref(encoder_data);
+        Context::command_encoder_fill_buffer(
+            self,
+            &encoder,
+            encoder_data,
+            buffer,
+            offset,
+            size,
+            value,
+        )
+    }
+
     fn command_encoder_insert_debug_marker(
         &self,
         encoder: &ObjectId,
@@ -2995,6 +3929,38 @@ where
         Context::queue_write_texture(self, &queue, queue_data, texture, data, data_layout, size)
     }
 
+    fn queue_create_staging_texture(
+        &self,
+        queue: &ObjectId,
+        queue_data: &crate::Data,
+        texture: ImageCopyTexture<'_>,
+        size: Extent3d,
+    ) -> Option<(Box<dyn QueueWriteBuffer>, ImageDataLayout)> {
+        let queue = <T::QueueId>::from(*queue);
+        let queue_data = downcast_ref(queue_data);
+        Context::queue_create_staging_texture(self, &queue, queue_data, texture, size)
+    }
+
+    fn queue_write_staging_texture(
+        &self,
+        queue: &ObjectId,
+        queue_data: &crate::Data,
+        texture: ImageCopyTexture<'_>,
+        size: Extent3d,
+        staging_buffer: &dyn QueueWriteBuffer,
+    ) {
+        let queue = <T::QueueId>::from(*queue);
+        let queue_data = downcast_ref(queue_data);
+        Context::queue_write_staging_texture(
+            self,
+            &queue,
+            queue_data,
+            texture,
+            size,
+            staging_buffer,
+        )
+    }
+
     #[cfg(any(webgpu, webgl))]
     fn queue_copy_external_image_to_texture(
         &self,
@@ -3026,12 +3992,82 @@ where
         (submission_index.into(), Arc::new(data) as _)
     }
 
+    fn queue_submit_with_semaphores(
+        &self,
+        queue: &ObjectId,
+        queue_data: &crate::Data,
+        command_buffers: &mut dyn Iterator<Item = (ObjectId, Box<crate::Data>)>,
+        waits: &[(ObjectId, u64)],
+        signals: &[(ObjectId, u64)],
+    ) -> (ObjectId, Arc<crate::Data>) {
+        let queue = <T::QueueId>::from(*queue);
+        let queue_data = downcast_ref(queue_data);
+        let command_buffers = command_buffers.map(|(id, data)| {
+            let command_buffer_data: <T as Context>::CommandBufferData = *data.downcast().unwrap();
+            (<T::CommandBufferId>::from(id), command_buffer_data)
+        });
+        let semaphore_values = |list: &[(ObjectId, u64)]| {
+            list.iter()
+                .map(|&(id, value)| (<T::TimelineSemaphoreId>::from(id), value))
+                .collect::<Vec<_>>()
+        };
+        let (submission_index, data) = Context::queue_submit_with_semaphores(
+            self,
+            &queue,
+            queue_data,
+            command_buffers,
+            &semaphore_values(waits),
+            &semaphore_values(signals),
+        );
+        (submission_index.into(), Arc::new(data) as _)
+    }
+
+    fn queue_bind_sparse(
+        &self,
+        queue: &ObjectId,
+        queue_data: &crate::Data,
+        buffer: &ObjectId,
+        buffer_data: &crate::Data,
+        binds: &[SparseBufferBind],
+    ) {
+        let queue = <T::QueueId>::from(*queue);
+        let queue_data = downcast_ref(queue_data);
+        let buffer = <T::BufferId>::from(*buffer);
+        let buffer_data = downcast_ref(buffer_data);
+        Context::queue_bind_sparse(self, &queue, queue_data, &buffer, buffer_data, binds)
+    }
+
+    fn queue_bind_sparse_texture(
+        &self,
+        queue: &ObjectId,
+        queue_data: &crate::Data,
+        texture: &ObjectId,
+        texture_data: &crate::Data,
+        binds: &[SparseTextureBind],
+    ) {
+        let queue = <T::QueueId>::from(*queue);
+        let queue_data = downcast_ref(queue_data);
+        let texture = <T::TextureId>::from(*texture);
+        let texture_data = downcast_ref(texture_data);
+        Context::queue_bind_sparse_texture(self, &queue, queue_data, &texture, texture_data, binds)
+    }
+
     fn queue_get_timestamp_period(&self, queue: &ObjectId, queue_data: &crate::Data) -> f32 {
         let queue = <T::QueueId>::from(*queue);
         let queue_data = downcast_ref(queue_data);
         Context::queue_get_timestamp_period(self, &queue, queue_data)
     }
 
+    fn queue_get_timestamp_calibration(
+        &self,
+        queue: &ObjectId,
+        queue_data: &crate::Data,
+    ) -> Option<TimestampCalibration> {
+        let queue = <T::QueueId>::from(*queue);
+        let queue_data = downcast_ref(queue_data);
+        Context::queue_get_timestamp_calibration(self, &queue, queue_data)
+    }
+
     fn queue_on_submitted_work_done(
         &self,
         queue: &ObjectId,
@@ -3043,6 +4079,35 @@ where
         Context::queue_on_submitted_work_done(self, &queue, queue_data, callback)
     }
 
+    fn queue_is_submission_complete(
+        &self,
+        queue: &ObjectId,
+        queue_data: &crate::Data,
+        submission_index: &crate::Data,
+    ) -> bool {
+        let queue = <T::QueueId>::from(*queue);
+        let queue_data = downcast_ref(queue_data);
+        let submission_index = downcast_ref(submission_index);
+        Context::queue_is_submission_complete(self, &queue, queue_data, submission_index)
+    }
+
+    fn queue_set_submit_batching(&self, queue: &ObjectId, queue_data: &crate::Data, enabled: bool) {
+        let queue = <T::QueueId>::from(*queue);
+        let queue_data = downcast_ref(queue_data);
+        Context::queue_set_submit_batching(self, &queue, queue_data, enabled)
+    }
+
+    fn queue_flush(&self, queue: &ObjectId, queue_data: &crate::Data) {
+        let queue = <T::QueueId>::from(*queue);
+        let queue_data = downcast_ref(queue_data);
+        Context::queue_flush(self, &queue, queue_data)
+    }
+
+    fn submission_index_token(&self, submission_index: &crate::Data) -> u64 {
+        let submission_index = downcast_ref(submission_index);
+        Context::submission_index_token(self, submission_index)
+    }
+
     fn device_start_capture(&self, device: &ObjectId, device_data: &crate::Data) {
         let device = <T::DeviceId>::from(*device);
         let device_data = downcast_ref(device_data);
@@ -3564,6 +4629,18 @@ where
         )
     }
 
+    fn render_pass_set_bind_group_inline(
+        &self,
+        pass: &mut ObjectId,
+        pass_data: &mut crate::Data,
+        index: u32,
+        entries: &[BindGroupEntry<'_>],
+    ) {
+        let mut pass = <T::RenderPassId>::from(*pass);
+        let pass_data = downcast_mut::<T::RenderPassData>(pass_data);
+        Context::render_pass_set_bind_group_inline(self, &mut pass, pass_data, index, entries)
+    }
+
     fn render_pass_set_index_buffer(
         &self,
         pass: &mut ObjectId,
@@ -3860,6 +4937,28 @@ where
         )
     }
 
+    fn render_pass_set_viewports(
+        &self,
+        pass: &mut ObjectId,
+        pass_data: &mut crate::Data,
+        viewports: &[Viewport],
+    ) {
+        let mut pass = <T::RenderPassId>::from(*pass);
+        let pass_data = downcast_mut::<T::RenderPassData>(pass_data);
+        Context::render_pass_set_viewports(self, &mut pass, pass_data, viewports)
+    }
+
+    fn render_pass_set_scissor_rects(
+        &self,
+        pass: &mut ObjectId,
+        pass_data: &mut crate::Data,
+        rects: &[ScissorRect],
+    ) {
+        let mut pass = <T::RenderPassId>::from(*pass);
+        let pass_data = downcast_mut::<T::RenderPassData>(pass_data);
+        Context::render_pass_set_scissor_rects(self, &mut pass, pass_data, rects)
+    }
+
     fn render_pass_set_stencil_reference(
         &self,
         pass: &mut ObjectId,
@@ -3871,6 +4970,63 @@ where
         Context::render_pass_set_stencil_reference(self, &mut pass, pass_data, reference)
     }
 
+    fn render_pass_set_stencil_reference_separate(
+        &self,
+        pass: &mut ObjectId,
+        pass_data: &mut crate::Data,
+        front: u32,
+        back: u32,
+    ) {
+        let mut pass = <T::RenderPassId>::from(*pass);
+        let pass_data = downcast_mut::<T::RenderPassData>(pass_data);
+        Context::render_pass_set_stencil_reference_separate(self, &mut pass, pass_data, front, back)
+    }
+
+    fn render_pass_set_stencil_write_mask(
+        &self,
+        pass: &mut ObjectId,
+        pass_data: &mut crate::Data,
+        front: u32,
+        back: u32,
+    ) {
+        let mut pass = <T::RenderPassId>::from(*pass);
+        let pass_data = downcast_mut::<T::RenderPassData>(pass_data);
+        Context::render_pass_set_stencil_write_mask(self, &mut pass, pass_data, front, back)
+    }
+
+    fn render_pass_set_front_face(
+        &self,
+        pass: &mut ObjectId,
+        pass_data: &mut crate::Data,
+        front_face: FrontFace,
+    ) {
+        let mut pass = <T::RenderPassId>::from(*pass);
+        let pass_data = downcast_mut::<T::RenderPassData>(pass_data);
+        Context::render_pass_set_front_face(self, &mut pass, pass_data, front_face)
+    }
+
+    fn render_pass_set_cull_mode(
+        &self,
+        pass: &mut ObjectId,
+        pass_data: &mut crate::Data,
+        cull_mode: Option<Face>,
+    ) {
+        let mut pass = <T::RenderPassId>::from(*pass);
+        let pass_data = downcast_mut::<T::RenderPassData>(pass_data);
+        Context::render_pass_set_cull_mode(self, &mut pass, pass_data, cull_mode)
+    }
+
+    fn render_pass_set_depth_compare(
+        &self,
+        pass: &mut ObjectId,
+        pass_data: &mut crate::Data,
+        compare: CompareFunction,
+    ) {
+        let mut pass = <T::RenderPassId>::from(*pass);
+        let pass_data = downcast_mut::<T::RenderPassData>(pass_data);
+        Context::render_pass_set_depth_compare(self, &mut pass, pass_data, compare)
+    }
+
     fn render_pass_insert_debug_marker(
         &self,
         pass: &mut ObjectId,
@@ -36,6 +36,7 @@ use std::{
     ops::{Bound, Deref, DerefMut, Range, RangeBounds},
     sync::Arc,
     thread,
+    time::Duration,
 };
 
 #[allow(unused_imports)] // Unused if all backends are disabled.
@@ -48,23 +49,32 @@ use raw_window_handle::{HasDisplayHandle, HasWindowHandle};
 pub use wgt::{
     AdapterInfo, AddressMode, AstcBlock, AstcChannel, Backend, Backends, BindGroupLayoutEntry,
     BindingType, BlendComponent, BlendFactor, BlendOperation, BlendState, BufferAddress,
-    BufferBindingType, BufferSize, BufferUsages, Color, ColorTargetState, ColorWrites,
-    CommandBufferDescriptor, CompareFunction, CompositeAlphaMode, DepthBiasState,
-    DepthStencilState, DeviceLostReason, DeviceType, DownlevelCapabilities, DownlevelFlags,
-    Dx12Compiler, DynamicOffset, Extent3d, Face, Features, FilterMode, FrontFace,
-    Gles3MinorVersion, ImageDataLayout, ImageSubresourceRange, IndexFormat, InstanceDescriptor,
-    InstanceFlags, Limits, MaintainResult, MultisampleState, Origin2d, Origin3d,
-    PipelineStatisticsTypes, PolygonMode, PowerPreference, PredefinedColorSpace, PresentMode,
-    PresentationTimestamp, PrimitiveState, PrimitiveTopology, PushConstantRange, QueryType,
-    RenderBundleDepthStencil, SamplerBindingType, SamplerBorderColor, ShaderLocation, ShaderModel,
-    ShaderStages, StencilFaceState, StencilOperation, StencilState, StorageTextureAccess,
-    SurfaceCapabilities, SurfaceStatus, TextureAspect, TextureDimension, TextureFormat,
+    BufferBindingType, BufferSize, BufferUsages, ChromaLocation, Color, ColorSpace,
+    ColorTargetState, ColorWrites, CommandBufferDescriptor, CompareFunction, ComponentSwizzle,
+    CompositeAlphaMode, DamageRect, DepthBiasState, DepthStencilState, DeviceFlags,
+    DeviceLostReason, DeviceType, DeviceValidation, DiagnosticFilter, DiagnosticRule,
+    DiagnosticSeverity, DisplayMode, DownlevelCapabilities, DownlevelFlags, DrmFormatModifier,
+    Dx12Compiler, DynamicOffset, Extent3d, ExternalMemoryHandleType, ExternalSemaphoreHandleType,
+    Face, Features, FilterMode, FramePresentation, FrontFace, FullscreenMode, Gles3MinorVersion,
+    HdrMetadata, ImageDataLayout, ImageSubresourceRange, IndexFormat, InstanceDescriptor,
+    InstanceFlags, Limits, MaintainResult, MaintainWork, MemoryPlaneLayout, MemoryRequirements,
+    MultisampleState, Origin2d, Origin3d, PerformanceHint, PipelineStatisticsTypes, PolygonMode,
+    PowerPreference, PredefinedColorSpace, PresentMode, PresentationTimestamp, PresentationTiming,
+    PrimitiveState, PrimitiveTopology, PushConstantRange, QueryType, QueuePriority,
+    RenderBundleDepthStencil, SamplerBindingType, SamplerBorderColor, SamplerReductionMode,
+    SamplerYcbcrConversion, ScissorRect, ShaderLocation, ShaderModel, ShaderStages,
+    SparseBufferBind, SparseTextureBind, SparseTextureTiles, StencilFaceState, StencilOperation,
+    StencilState, StorageTextureAccess, SurfaceCapabilities, SurfaceStatus, SurfaceTransform,
+    TextureAspect, TextureComponentSwizzle, TextureDimension, TextureExpiry, TextureFormat,
     TextureFormatFeatureFlags, TextureFormatFeatures, TextureSampleType, TextureUsages,
-    TextureViewDimension, VertexAttribute, VertexFormat, VertexStepMode, WasmNotSend,
-    WasmNotSendSync, WasmNotSync, COPY_BUFFER_ALIGNMENT, COPY_BYTES_PER_ROW_ALIGNMENT,
-    MAP_ALIGNMENT, PUSH_CONSTANT_ALIGNMENT, QUERY_RESOLVE_BUFFER_ALIGNMENT, QUERY_SET_MAX_QUERIES,
-    QUERY_SIZE, VERTEX_STRIDE_ALIGNMENT,
+    TextureViewDimension, TimestampCalibration, ValidationLevel, VertexAttribute, VertexFormat,
+    VertexStepMode, Viewport, WasmNotSend, WasmNotSendSync, WasmNotSync, YcbcrModel, YcbcrRange,
+    COPY_BUFFER_ALIGNMENT, COPY_BYTES_PER_ROW_ALIGNMENT, MAP_ALIGNMENT, MAX_INLINE_BINDINGS,
+    MAX_VIEWPORTS, PUSH_CONSTANT_ALIGNMENT, QUERY_RESOLVE_BUFFER_ALIGNMENT, QUERY_SET_MAX_QUERIES,
+    QUERY_SIZE, SPARSE_BUFFER_PAGE_SIZE, VERTEX_STRIDE_ALIGNMENT,
 };
+#[cfg(external_memory)]
+pub use wgt::{ExternalMemory, ExternalSemaphore};
 
 /// Re-export of our `wgpu-core` dependency.
 ///
@@ -183,13 +193,92 @@ static_assertions::assert_impl_all!(Device: Send, Sync);
 /// as part of an argument to [`Device::poll`] to block for a particular
 /// submission to finish.
 ///
+/// Submission indices compare and hash by their [`SubmissionToken`].
+///
 /// This type is unique to the Rust API of `wgpu`.
 /// There is no analogue in the WebGPU specification.
 #[derive(Debug, Clone)]
-pub struct SubmissionIndex(ObjectId, Arc<crate::Data>);
+pub struct SubmissionIndex(ObjectId, Arc<crate::Data>, SubmissionToken);
 #[cfg(send_sync)]
 static_assertions::assert_impl_all!(SubmissionIndex: Send, Sync);
 
+impl SubmissionIndex {
+    fn new(context: &dyn DynContext, id: ObjectId, data: Arc<crate::Data>) -> Self {
+        let token = DynContext::submission_index_token(context, data.as_ref());
+        Self(id, data, SubmissionToken::from_raw(token).unwrap())
+    }
+
+    /// The token of this submission, which can be stored and compared
+    /// without keeping the submission index alive.
+    pub fn token(&self) -> SubmissionToken {
+        self.2
+    }
+}
+
+impl PartialEq for SubmissionIndex {
+    fn eq(&self, other: &Self) -> bool {
+        self.2 == other.2
+    }
+}
+
+impl Eq for SubmissionIndex {}
+
+impl PartialOrd for SubmissionIndex {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for SubmissionIndex {
+    fn cmp(&self, other: &Self) -> Ordering {
+        self.2.cmp(&other.2)
+    }
+}
+
+impl std::hash::Hash for SubmissionIndex {
+    fn hash<H: std::hash::Hasher>(&self, state: &mut H) {
+        self.2.hash(state)
+    }
+}
+
+/// Opaque 64-bit token of a [`SubmissionIndex`].
+///
+/// Tokens are handed out by a counter shared by all instances, devices and
+/// queues of the process. A submission gets its token along with its index,
+/// while it holds the lock that orders the submissions of its device, so a
+/// token is greater than the token of every submission made before it, even on
+/// a queue that has since been dropped and recreated. Tokens of different
+/// submissions never compare equal, including submissions collected into the
+/// same batch with [`Queue::set_submit_batching`].
+///
+/// The counter is 64 bits wide, so it doesn't wrap around in practice: at one
+/// submission per nanosecond, it runs out after more than 500 years. It
+/// panics instead of wrapping if it does.
+///
+/// Tokens are not meaningful across processes.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct SubmissionToken(NonZeroU64);
+
+impl SubmissionToken {
+    /// The value of the token, for storing it outside of `wgpu`.
+    pub fn get(self) -> u64 {
+        self.0.get()
+    }
+
+    /// Recreates a token from a value returned by [`SubmissionToken::get`].
+    ///
+    /// Returns `None` for 0, which is never a token.
+    pub fn from_raw(value: u64) -> Option<Self> {
+        NonZeroU64::new(value).map(Self)
+    }
+}
+
+impl From<SubmissionToken> for u64 {
+    fn from(token: SubmissionToken) -> Self {
+        token.get()
+    }
+}
+
 /// The mapped portion of a buffer, if any, and its outstanding views.
 ///
 /// This ensures that views fall within the mapped range and don't overlap, and
@@ -464,6 +553,7 @@ pub struct Texture {
     data: Box<Data>,
     owned: bool,
     descriptor: TextureDescriptor<'static>,
+    color_space: ColorSpace,
 }
 #[cfg(send_sync)]
 static_assertions::assert_impl_all!(Texture: Send, Sync);
@@ -479,6 +569,8 @@ pub struct TextureView {
     context: Arc<C>,
     id: ObjectId,
     data: Box<Data>,
+    /// The texture this is a view of.
+    texture: Id<Texture>,
 }
 #[cfg(send_sync)]
 static_assertions::assert_impl_all!(TextureView: Send, Sync);
@@ -706,6 +798,30 @@ pub enum SurfaceTargetUnsafe {
     /// - visual must be a valid SwapChainPanel to create a surface upon.
     #[cfg(dx12)]
     SwapChainPanel(*mut std::ffi::c_void),
+
+    /// Surface presenting directly to a display through DRM/KMS, without a
+    /// windowing system, for kiosks and embedded devices.
+    ///
+    /// Only supported by the Vulkan backend, through `VK_EXT_acquire_drm_display`.
+    /// The display is acquired from DRM, and driven in `mode`, which has to be
+    /// one of the modes of the connector.
+    ///
+    /// # Safety
+    ///
+    /// - `fd` must be a file descriptor of a DRM device, like `/dev/dri/card0`,
+    ///   with DRM master rights, that stays open until after the returned
+    ///   [`Surface`] is dropped.
+    #[cfg(drm)]
+    Drm {
+        /// File descriptor of the DRM device.
+        fd: i32,
+        /// Index of the display plane the surface is shown on.
+        plane: u32,
+        /// The DRM connector the display is connected to.
+        connector_id: u32,
+        /// The resolution and refresh rate the display is driven in.
+        mode: DisplayMode,
+    },
 }
 
 impl SurfaceTargetUnsafe {
@@ -859,6 +975,13 @@ pub struct ShaderModuleDescriptor<'a> {
     pub label: Label<'a>,
     /// Source code for the shader.
     pub source: ShaderSource<'a>,
+    /// Severities of the WGSL diagnostic rules for the whole shader, for
+    /// example to make [`DiagnosticRule::UnreachableCode`] an error in CI.
+    /// `diagnostic` directives and attributes in the shader override them.
+    ///
+    /// Diagnostics reported as warnings are logged. Ignored for other shader
+    /// sources and on WebGPU.
+    pub diagnostic_filters: &'a [DiagnosticFilter],
 }
 static_assertions::assert_impl_all!(ShaderModuleDescriptor<'_>: Send, Sync);
 
@@ -1125,6 +1248,11 @@ pub struct QuerySet {
     context: Arc<C>,
     id: ObjectId,
     data: Box<Data>,
+    ty: QueryType,
+    count: u32,
+    /// Buffers used by [`QuerySet::read_results_async`], reused once their
+    /// results have been read.
+    readbacks: Mutex<Vec<Arc<QueryReadback>>>,
 }
 #[cfg(send_sync)]
 #[cfg(send_sync)]
@@ -1138,6 +1266,118 @@ impl Drop for QuerySet {
     }
 }
 
+/// Results of queries, read with [`QuerySet::read_results_async`].
+#[derive(Clone, Debug, PartialEq)]
+pub enum QueryResults {
+    /// The number of samples that passed the depth and stencil tests, for each
+    /// [`QueryType::Occlusion`] query.
+    Occlusion(Vec<u64>),
+    /// The statistics of each [`QueryType::PipelineStatistics`] query, with one
+    /// value for each of the [`PipelineStatisticsTypes`] of the set, in the
+    /// order of their bits.
+    PipelineStatistics(Vec<Vec<u64>>),
+    /// The time of each [`QueryType::Timestamp`] query in nanoseconds, already
+    /// multiplied by [`Queue::get_timestamp_period`].
+    Timestamp(Vec<f64>),
+}
+
+/// A resolve buffer of a [`QuerySet`] and the buffer its results are copied to
+/// for reading.
+#[derive(Debug)]
+struct QueryReadback {
+    resolve: Buffer,
+    read: Buffer,
+    /// Set from the submission that resolves into the buffers until their
+    /// results have been read.
+    in_use: std::sync::atomic::AtomicBool,
+}
+
+/// The state shared by a [`QueryResultsFuture`] and its mapping callback.
+#[derive(Default)]
+struct QueryResultsState {
+    results: Option<Result<QueryResults, BufferAsyncError>>,
+    waker: Option<std::task::Waker>,
+}
+
+/// The future returned by [`QuerySet::read_results_async`].
+struct QueryResultsFuture(Arc<Mutex<QueryResultsState>>);
+
+impl Future for QueryResultsFuture {
+    type Output = Result<QueryResults, BufferAsyncError>;
+
+    fn poll(
+        self: std::pin::Pin<&mut Self>,
+        cx: &mut std::task::Context<'_>,
+    ) -> std::task::Poll<Self::Output> {
+        let mut state = self.0.lock();
+        match state.results.take() {
+            Some(results) => std::task::Poll::Ready(results),
+            None => {
+                state.waker = Some(cx.waker().clone());
+                std::task::Poll::Pending
+            }
+        }
+    }
+}
+
+/// Handle to a timeline semaphore.
+///
+/// A timeline semaphore holds a 64-bit value that only ever increases. Queue
+/// submissions made with [`Queue::submit_with_semaphores`] can wait for it to
+/// reach a value before they start, and set it to a value once they are done;
+/// the host can do the same with [`TimelineSemaphore::wait`] and
+/// [`TimelineSemaphore::signal`]. This orders work between queues, threads,
+/// and, through [`TimelineSemaphore::export`] and
+/// [`Device::import_timeline_semaphore`], other APIs and processes.
+///
+/// It can be created with [`Device::create_timeline_semaphore`], which
+/// requires [`Features::TIMELINE_SEMAPHORE`].
+#[derive(Debug)]
+pub struct TimelineSemaphore {
+    context: Arc<C>,
+    id: ObjectId,
+    data: Box<Data>,
+}
+#[cfg(send_sync)]
+static_assertions::assert_impl_all!(TimelineSemaphore: Send, Sync);
+
+impl Drop for TimelineSemaphore {
+    fn drop(&mut self) {
+        if !thread::panicking() {
+            self.context
+                .timeline_semaphore_drop(&self.id, self.data.as_ref());
+        }
+    }
+}
+
+/// Handle to a memory heap.
+///
+/// A memory heap is a block of device memory that buffers and textures can be
+/// placed in with [`Device::create_placed_buffer`] and
+/// [`Device::create_placed_texture`]. Placed resources may overlap, aliasing
+/// each other's memory: when a submission or queue write uses one of them, it
+/// takes the memory over, and the contents of the resources it overlaps become
+/// undefined. Overlapping resources can't be used in the same command buffer.
+///
+/// It can be created with [`Device::create_memory_heap`], which requires
+/// [`Features::PLACED_RESOURCES`].
+#[derive(Debug)]
+pub struct MemoryHeap {
+    context: Arc<C>,
+    id: ObjectId,
+    data: Box<Data>,
+}
+#[cfg(send_sync)]
+static_assertions::assert_impl_all!(MemoryHeap: Send, Sync);
+
+impl Drop for MemoryHeap {
+    fn drop(&mut self) {
+        if !thread::panicking() {
+            self.context.memory_heap_drop(&self.id, self.data.as_ref());
+        }
+    }
+}
+
 /// Handle to a command queue on a device.
 ///
 /// A `Queue` executes recorded [`CommandBuffer`] objects and provides convenience methods
@@ -1150,6 +1390,7 @@ pub struct Queue {
     context: Arc<C>,
     id: ObjectId,
     data: Box<Data>,
+    middleware: Mutex<Vec<Arc<dyn SubmitMiddleware>>>,
 }
 #[cfg(send_sync)]
 static_assertions::assert_impl_all!(Queue: Send, Sync);
@@ -1394,6 +1635,15 @@ static_assertions::assert_impl_all!(RequestAdapterOptions<'_, '_>: Send, Sync);
 /// https://gpuweb.github.io/gpuweb/#dictdef-gpudevicedescriptor).
 pub type DeviceDescriptor<'a> = wgt::DeviceDescriptor<Label<'a>>;
 static_assertions::assert_impl_all!(DeviceDescriptor<'_>: Send, Sync);
+/// Describes an additional [`Queue`] created with [`Device::create_queue`].
+pub type QueueDescriptor<'a> = wgt::QueueDescriptor<Label<'a>>;
+static_assertions::assert_impl_all!(QueueDescriptor<'_>: Send, Sync);
+/// Describes a [`TimelineSemaphore`] created with [`Device::create_timeline_semaphore`].
+pub type TimelineSemaphoreDescriptor<'a> = wgt::TimelineSemaphoreDescriptor<Label<'a>>;
+static_assertions::assert_impl_all!(TimelineSemaphoreDescriptor<'_>: Send, Sync);
+/// Describes a [`MemoryHeap`] created with [`Device::create_memory_heap`].
+pub type MemoryHeapDescriptor<'a> = wgt::MemoryHeapDescriptor<Label<'a>>;
+static_assertions::assert_impl_all!(MemoryHeapDescriptor<'_>: Send, Sync);
 /// Describes a [`Buffer`].
 ///
 /// For use with [`Device::create_buffer`].
@@ -1470,6 +1720,10 @@ pub struct TextureViewDescriptor<'a> {
     /// If `Some(count)`, `base_array_layer + count` must be less or equal to the underlying array count.
     /// If `None`, considered to include the rest of the array layers, but at least 1 in total.
     pub array_layer_count: Option<u32>,
+    /// Rearrangement of the components read through the view. Anything but the identity
+    /// requires [`Features::TEXTURE_COMPONENT_SWIZZLE`], and views with it can only be
+    /// bound as sampled textures.
+    pub swizzle: TextureComponentSwizzle,
 }
 static_assertions::assert_impl_all!(TextureViewDescriptor<'_>: Send, Sync);
 
@@ -1522,12 +1776,23 @@ pub struct SamplerDescriptor<'a> {
     pub lod_min_clamp: f32,
     /// Maximum level of detail (i.e. mip level) to use
     pub lod_max_clamp: f32,
+    /// Bias added to the level of detail computed by the sampler, before it's
+    /// clamped to `lod_min_clamp..=lod_max_clamp`. Must be finite.
+    ///
+    /// Non-zero biases require [`DownlevelFlags::SAMPLER_LOD_BIAS`], and are
+    /// clamped to the range the adapter supports, like [-16, 15.99] on DX12 and
+    /// `maxSamplerLodBias` on Vulkan.
+    pub lod_bias: f32,
     /// If this is enabled, this is a comparison sampler using the given comparison function.
     pub compare: Option<CompareFunction>,
     /// Must be at least 1. If this is not 1, all filter modes must be linear.
     pub anisotropy_clamp: u16,
     /// Border color to use when address_mode is [`AddressMode::ClampToBorder`]
     pub border_color: Option<SamplerBorderColor>,
+    /// How the sampler combines the texels it filters. Modes other than
+    /// [`SamplerReductionMode::WeightedAverage`] require
+    /// [`Features::SAMPLER_REDUCTION_MODE`].
+    pub reduction_mode: SamplerReductionMode,
 }
 static_assertions::assert_impl_all!(SamplerDescriptor<'_>: Send, Sync);
 
@@ -1543,9 +1808,11 @@ impl Default for SamplerDescriptor<'_> {
             mipmap_filter: Default::default(),
             lod_min_clamp: 0.0,
             lod_max_clamp: 32.0,
+            lod_bias: 0.0,
             compare: None,
             anisotropy_clamp: 1,
             border_color: None,
+            reduction_mode: SamplerReductionMode::WeightedAverage,
         }
     }
 }
@@ -1968,6 +2235,10 @@ impl Instance {
             if cfg!(target_os = "macos") && cfg!(feature = "angle") {
                 backends = backends.union(Backends::GL);
             }
+
+            if cfg!(feature = "noop") {
+                backends = backends.union(Backends::NOOP);
+            }
         } else {
             if cfg!(webgpu) {
                 backends = backends.union(Backends::BROWSER_WEBGPU);
@@ -2291,6 +2562,26 @@ impl Instance {
             .downcast_ref::<crate::backend::ContextWgpuCore>()
             .map(|ctx| ctx.generate_report())
     }
+
+    /// Tells the OS how the whole process should trade performance for power,
+    /// see [`PerformanceHint`].
+    ///
+    /// Unlike [`Device::set_performance_hint`], which only changes the state of
+    /// one device, this opts the process into EcoQoS on Windows, or declares
+    /// it as active to `NSProcessInfo` on Apple platforms, which also affects
+    /// threads that have nothing to do with wgpu. Other platforms ignore it.
+    ///
+    /// Has no effect if the instance targets WebGPU.
+    #[cfg(wgpu_core)]
+    pub fn set_process_performance_hint(&self, hint: PerformanceHint) {
+        if let Some(ctx) = self
+            .context
+            .as_any()
+            .downcast_ref::<crate::backend::ContextWgpuCore>()
+        {
+            ctx.set_process_performance_hint(hint);
+        }
+    }
 }
 
 impl Adapter {
@@ -2341,6 +2632,7 @@ impl Adapter {
                             context,
                             id: queue_id,
                             data: queue_data,
+                            middleware: Mutex::default(),
                         },
                     )
                 },
@@ -2382,6 +2674,7 @@ impl Adapter {
                     context,
                     id: queue.id().into(),
                     data: Box::new(queue),
+                    middleware: Mutex::default(),
                 },
             )
         })
@@ -2433,6 +2726,30 @@ impl Adapter {
         )
     }
 
+    /// Returns the capabilities a surface created on `display` would likely have
+    /// with this adapter, without needing a window.
+    ///
+    /// This lets launchers fill in display settings before the real window
+    /// exists. The capabilities of the actual surface may still differ, so call
+    /// [`Surface::get_capabilities`] once it is created.
+    ///
+    /// Returns specified values (see [`SurfaceCapabilities`]) if the backend
+    /// can't tell without a surface, or the display can't be presented to.
+    pub fn get_surface_capabilities_headless(
+        &self,
+        display: &impl HasDisplayHandle,
+    ) -> SurfaceCapabilities {
+        let Ok(display_handle) = display.display_handle() else {
+            return SurfaceCapabilities::default();
+        };
+        DynContext::adapter_get_surface_capabilities_headless(
+            &*self.context,
+            &self.id,
+            self.data.as_ref(),
+            display_handle.as_raw(),
+        )
+    }
+
     /// The features which can be used to create devices on this adapter.
     pub fn features(&self) -> Features {
         DynContext::adapter_features(&*self.context, &self.id, self.data.as_ref())
@@ -2503,7 +2820,38 @@ impl Device {
     ///
     /// When running on WebGPU, this is a no-op. `Device`s are automatically polled.
     pub fn poll(&self, maintain: Maintain) -> MaintainResult {
-        DynContext::device_poll(&*self.context, &self.id, self.data.as_ref(), maintain)
+        self.poll_work(maintain, MaintainWork::all())
+    }
+
+    /// Like [`poll()`](Self::poll), but only does the parts of the maintenance in `work`.
+    ///
+    /// A latency-critical thread can poll for mappings or submissions alone, and
+    /// leave destroying unused resources to a worker thread polling with
+    /// [`MaintainWork::CLEANUP`]. With [`Maintain::PollForSubmissionIndex`],
+    /// submissions after the given one aren't retired, even if they have completed.
+    ///
+    /// When running on WebGPU, this is a no-op. `Device`s are automatically polled.
+    pub fn poll_work(&self, maintain: Maintain, work: MaintainWork) -> MaintainResult {
+        DynContext::device_poll(&*self.context, &self.id, self.data.as_ref(), maintain, work)
+    }
+
+    /// Blocks until the submission `index`, and all submissions before it, have
+    /// completed on the GPU, or `timeout` has passed.
+    ///
+    /// Returns `true` if they completed. Unlike [`poll()`](Self::poll), this doesn't
+    /// call any callbacks or map buffers, so it can pace the frames in flight of a
+    /// renderer that polls the device elsewhere. See also
+    /// [`Queue::is_submission_complete`].
+    ///
+    /// This is not supported on WebGPU.
+    pub fn wait_for_submission(&self, index: &SubmissionIndex, timeout: Duration) -> bool {
+        DynContext::device_wait_for_submission(
+            &*self.context,
+            &self.id,
+            self.data.as_ref(),
+            index.1.as_ref(),
+            timeout,
+        )
     }
 
     /// The features which can be used on this device.
@@ -2520,6 +2868,35 @@ impl Device {
         DynContext::device_limits(&*self.context, &self.id, self.data.as_ref())
     }
 
+    /// Returns the features of a given texture format on this device.
+    ///
+    /// Unlike [`Adapter::get_texture_format_features`], this is what the device
+    /// accepts: the features WebGPU guarantees, unless the device was created
+    /// with [`Features::TEXTURE_ADAPTER_SPECIFIC_FORMAT_FEATURES`]. For example,
+    /// renderers can pick the sample counts of their multisampled targets from
+    /// [`TextureFormatFeatureFlags::sample_count_mask`] without causing errors:
+    ///
+    /// ```no_run
+    /// # fn pick(device: &wgpu::Device) -> u32 {
+    /// let mask = device
+    ///     .get_texture_format_features(wgpu::TextureFormat::Rgba16Float)
+    ///     .flags
+    ///     .sample_count_mask();
+    /// // The highest supported sample count, up to 8.
+    /// [8, 4, 2, 1].into_iter().find(|count| mask & count != 0).unwrap()
+    /// # }
+    /// ```
+    ///
+    /// Formats that need features the device doesn't have support nothing.
+    pub fn get_texture_format_features(&self, format: TextureFormat) -> TextureFormatFeatures {
+        DynContext::device_get_texture_format_features(
+            &*self.context,
+            &self.id,
+            self.data.as_ref(),
+            format,
+        )
+    }
+
     /// Creates a shader module from either SPIR-V or WGSL source code.
     ///
     /// <div class="warning">
@@ -2604,6 +2981,62 @@ impl Device {
         }
     }
 
+    /// Creates one of the additional queues of this device.
+    ///
+    /// Up to [`Limits::max_additional_queues`], as requested when creating the
+    /// device, can exist at a time. Work submitted to different queues may run
+    /// concurrently; a submission waits for earlier submissions to other queues
+    /// that used the same buffers or textures.
+    ///
+    /// Dropping the returned queue makes it available again.
+    pub fn create_queue(&self, desc: &QueueDescriptor<'_>) -> Queue {
+        let (id, data) =
+            DynContext::device_create_queue(&*self.context, &self.id, self.data.as_ref(), desc);
+        Queue {
+            context: Arc::clone(&self.context),
+            id,
+            data,
+            middleware: Mutex::default(),
+        }
+    }
+
+    /// Creates a new [`TimelineSemaphore`].
+    ///
+    /// Requires [`Features::TIMELINE_SEMAPHORE`].
+    pub fn create_timeline_semaphore(
+        &self,
+        desc: &TimelineSemaphoreDescriptor<'_>,
+    ) -> TimelineSemaphore {
+        let (id, data) = DynContext::device_create_timeline_semaphore(
+            &*self.context,
+            &self.id,
+            self.data.as_ref(),
+            desc,
+        );
+        TimelineSemaphore {
+            context: Arc::clone(&self.context),
+            id,
+            data,
+        }
+    }
+
+    /// Creates a new [`MemoryHeap`].
+    ///
+    /// Requires [`Features::PLACED_RESOURCES`].
+    pub fn create_memory_heap(&self, desc: &MemoryHeapDescriptor<'_>) -> MemoryHeap {
+        let (id, data) = DynContext::device_create_memory_heap(
+            &*self.context,
+            &self.id,
+            self.data.as_ref(),
+            desc,
+        );
+        MemoryHeap {
+            context: Arc::clone(&self.context),
+            id,
+            data,
+        }
+    }
+
     /// Creates an empty [`CommandEncoder`].
     pub fn create_command_encoder(&self, desc: &CommandEncoderDescriptor<'_>) -> CommandEncoder {
         let (id, data) = DynContext::device_create_command_encoder(
@@ -2672,6 +3105,31 @@ impl Device {
         }
     }
 
+    /// Creates a [`BindGroupLayout`] for an inline bind group.
+    ///
+    /// Instead of creating a [`BindGroup`], the bindings of an inline layout are set
+    /// directly on a render pass with [`RenderPass::set_bind_group_inline`]. The layout
+    /// may only contain up to [`MAX_INLINE_BINDINGS`] buffer bindings without dynamic
+    /// offsets, and a [`PipelineLayout`] may contain at most one inline layout.
+    ///
+    /// Requires [`Features::INLINE_BIND_GROUPS`].
+    pub fn create_inline_bind_group_layout(
+        &self,
+        desc: &BindGroupLayoutDescriptor<'_>,
+    ) -> BindGroupLayout {
+        let (id, data) = DynContext::device_create_inline_bind_group_layout(
+            &*self.context,
+            &self.id,
+            self.data.as_ref(),
+            desc,
+        );
+        BindGroupLayout {
+            context: Arc::clone(&self.context),
+            id,
+            data,
+        }
+    }
+
     /// Creates a [`PipelineLayout`].
     pub fn create_pipeline_layout(&self, desc: &PipelineLayoutDescriptor<'_>) -> PipelineLayout {
         let (id, data) = DynContext::device_create_pipeline_layout(
@@ -2737,6 +3195,81 @@ impl Device {
         }
     }
 
+    /// Creates a sparse [`Buffer`], which reserves `desc.size` bytes of the
+    /// address space of the device without using any memory.
+    ///
+    /// Pages are committed to memory with [`Queue::bind_sparse`]. Sparse
+    /// buffers can't be mapped, neither with [`BufferUsages::MAP_READ`] or
+    /// [`BufferUsages::MAP_WRITE`] nor at creation.
+    ///
+    /// Requires [`Features::SPARSE_BUFFER`].
+    pub fn create_sparse_buffer(&self, desc: &BufferDescriptor<'_>) -> Buffer {
+        let (id, data) = DynContext::device_create_sparse_buffer(
+            &*self.context,
+            &self.id,
+            self.data.as_ref(),
+            desc,
+        );
+
+        Buffer {
+            context: Arc::clone(&self.context),
+            id,
+            data,
+            map_context: Mutex::new(MapContext::new(desc.size)),
+            size: desc.size,
+            usage: desc.usage,
+        }
+    }
+
+    /// Creates a [`Buffer`] placed at `offset` in the memory of `heap`.
+    ///
+    /// `offset` must be a multiple of the alignment returned by
+    /// [`Device::buffer_memory_requirements`] for `desc`, and the buffer must
+    /// fit in the heap. Placed buffers can't be mapped, neither with
+    /// [`BufferUsages::MAP_READ`] or [`BufferUsages::MAP_WRITE`] nor at
+    /// creation. Their contents are zeroed whenever they take the memory over
+    /// from a resource they overlap, see [`MemoryHeap`].
+    ///
+    /// Requires [`Features::PLACED_RESOURCES`].
+    pub fn create_placed_buffer(
+        &self,
+        heap: &MemoryHeap,
+        offset: BufferAddress,
+        desc: &BufferDescriptor<'_>,
+    ) -> Buffer {
+        let (id, data) = DynContext::device_create_placed_buffer(
+            &*self.context,
+            &self.id,
+            self.data.as_ref(),
+            &heap.id,
+            heap.data.as_ref(),
+            offset,
+            desc,
+        );
+
+        Buffer {
+            context: Arc::clone(&self.context),
+            id,
+            data,
+            map_context: Mutex::new(MapContext::new(desc.size)),
+            size: desc.size,
+            usage: desc.usage,
+        }
+    }
+
+    /// Returns the size and alignment of the memory a buffer created from
+    /// `desc` needs in a [`MemoryHeap`].
+    ///
+    /// Requires [`Features::PLACED_RESOURCES`].
+    pub fn buffer_memory_requirements(&self, desc: &BufferDescriptor<'_>) -> MemoryRequirements {
+        DynContext::device_buffer_memory_requirements(
+            &*self.context,
+            &self.id,
+            self.data.as_ref(),
+            desc,
+        )
+    }
+
     /// Creates a new [`Texture`].
     ///
     /// `desc` specifies the general format of the texture.
@@ -2753,20 +3286,101 @@ impl Device {
                 view_formats: &[],
                 ..desc.clone()
             },
+            color_space: ColorSpace::Srgb,
         }
     }
 
-    /// Creates a [`Texture`] from a wgpu-hal Texture.
+    /// Creates a sparse [`Texture`], which reserves the address space of its
+    /// subresources without using any memory.
     ///
-    /// # Safety
+    /// Tiles are committed to memory with [`Queue::bind_sparse_texture`], and
+    /// [`Texture::sparse_tiles`] returns their size. Sparse textures must be
+    /// two or three dimensional, single-sampled and have a color format with
+    /// a single plane.
     ///
-    /// - `hal_texture` must be created from this device internal handle
-    /// - `hal_texture` must be created respecting `desc`
-    /// - `hal_texture` must be initialized
-    #[cfg(wgpu_core)]
-    pub unsafe fn create_texture_from_hal<A: wgc::hal_api::HalApi>(
+    /// Requires [`Features::SPARSE_TEXTURE`].
+    pub fn create_sparse_texture(&self, desc: &TextureDescriptor<'_>) -> Texture {
+        let (id, data) = DynContext::device_create_sparse_texture(
+            &*self.context,
+            &self.id,
+            self.data.as_ref(),
+            desc,
+        );
+        Texture {
+            context: Arc::clone(&self.context),
+            id,
+            data,
+            owned: true,
+            descriptor: TextureDescriptor {
+                label: None,
+                view_formats: &[],
+                ..desc.clone()
+            },
+            color_space: ColorSpace::Srgb,
+        }
+    }
+
+    /// Creates a [`Texture`] placed at `offset` in the memory of `heap`.
+    ///
+    /// `offset` must be a multiple of the alignment returned by
+    /// [`Device::texture_memory_requirements`] for `desc`, and the texture
+    /// must fit in the heap. Its contents are cleared whenever it takes the
+    /// memory over from a resource it overlaps, see [`MemoryHeap`].
+    ///
+    /// Requires [`Features::PLACED_RESOURCES`].
+    pub fn create_placed_texture(
         &self,
-        hal_texture: A::Texture,
+        heap: &MemoryHeap,
+        offset: BufferAddress,
+        desc: &TextureDescriptor<'_>,
+    ) -> Texture {
+        let (id, data) = DynContext::device_create_placed_texture(
+            &*self.context,
+            &self.id,
+            self.data.as_ref(),
+            &heap.id,
+            heap.data.as_ref(),
+            offset,
+            desc,
+        );
+        Texture {
+            context: Arc::clone(&self.context),
+            id,
+            data,
+            owned: true,
+            descriptor: TextureDescriptor {
+                label: None,
+                view_formats: &[],
+                ..desc.clone()
+            },
+            color_space: ColorSpace::Srgb,
+        }
+    }
+
+    /// Returns the size and alignment of the memory a texture created from
+    /// `desc` needs in a [`MemoryHeap`].
+    ///
+    /// Requires [`Features::PLACED_RESOURCES`].
+    pub fn texture_memory_requirements(&self, desc: &TextureDescriptor<'_>) -> MemoryRequirements {
+        DynContext::device_texture_memory_requirements(
+            &*self.context,
+            &self.id,
+            self.data.as_ref(),
+            desc,
+        )
+    }
+
+    /// Creates a [`Texture`] from a wgpu-hal Texture.
+    ///
+    /// # Safety
+    ///
+    /// - `hal_texture` must be created from this device internal handle
+    /// - `hal_texture` must be created respecting `desc`
+    /// - `hal_texture` must be initialized
+    #[cfg(wgpu_core)]
+    pub unsafe fn create_texture_from_hal<A: wgc::hal_api::HalApi>(
+        &self,
+        hal_texture: A::Texture,
         desc: &TextureDescriptor<'_>,
     ) -> Texture {
         let texture = unsafe {
@@ -2792,6 +3406,7 @@ impl Device {
                 view_formats: &[],
                 ..desc.clone()
             },
+            color_space: ColorSpace::Srgb,
         }
     }
 
@@ -2837,6 +3452,448 @@ impl Device {
         }
     }
 
+    /// Returns the DRM format modifiers textures of `format` with `usage` can
+    /// be shared as dma-bufs with, to negotiate with the other side.
+    ///
+    /// Returns an empty list if the device doesn't have
+    /// [`Features::EXTERNAL_MEMORY_DMA_BUF`].
+    #[cfg(external_memory)]
+    pub fn drm_format_modifiers(
+        &self,
+        format: TextureFormat,
+        usage: TextureUsages,
+    ) -> Vec<DrmFormatModifier> {
+        self.context
+            .as_any()
+            .downcast_ref::<crate::backend::ContextWgpuCore>()
+            .map(|ctx| {
+                ctx.device_drm_format_modifiers(
+                    self.data.as_ref().downcast_ref().unwrap(),
+                    format,
+                    usage,
+                )
+            })
+            .unwrap_or_default()
+    }
+
+    /// Creates a [`Buffer`] whose memory can be shared with other APIs and
+    /// processes through [`Buffer::export_memory`].
+    ///
+    /// Requires the feature `handle_type` needs, see
+    /// [`ExternalMemoryHandleType::required_features`]. The buffer can't be mappable.
+    #[cfg(external_memory)]
+    pub fn create_exportable_buffer(
+        &self,
+        desc: &BufferDescriptor<'_>,
+        handle_type: ExternalMemoryHandleType,
+    ) -> Buffer {
+        let (id, buffer) = self
+            .context
+            .as_any()
+            .downcast_ref::<crate::backend::ContextWgpuCore>()
+            // Only wgpu-core contexts can be created on the platforms with external memory.
+            .unwrap()
+            .create_exportable_buffer(
+                self.data.as_ref().downcast_ref().unwrap(),
+                desc,
+                handle_type,
+            );
+
+        Buffer {
+            context: Arc::clone(&self.context),
+            id: ObjectId::from(id),
+            data: Box::new(buffer),
+            map_context: Mutex::new(MapContext::new(desc.size)),
+            size: desc.size,
+            usage: desc.usage,
+        }
+    }
+
+    /// Creates a [`Buffer`] backed by memory exported by another device, API
+    /// or process.
+    ///
+    /// Requires the feature the handle type of `memory` needs, see
+    /// [`ExternalMemoryHandleType::required_features`]. The buffer can't be mappable.
+    ///
+    /// # Safety
+    ///
+    /// - `memory` must hold a valid handle of its handle type, at least as
+    ///   large as the buffer described by `desc`.
+    /// - Accesses to the memory from elsewhere must be synchronized with those
+    ///   through the buffer, e.g. with a [`TimelineSemaphore`].
+    #[cfg(external_memory)]
+    pub unsafe fn import_buffer(
+        &self,
+        desc: &BufferDescriptor<'_>,
+        memory: ExternalMemory,
+    ) -> Buffer {
+        let (id, buffer) = unsafe {
+            self.context
+                .as_any()
+                .downcast_ref::<crate::backend::ContextWgpuCore>()
+                .unwrap()
+                .import_buffer(self.data.as_ref().downcast_ref().unwrap(), desc, memory)
+        };
+
+        Buffer {
+            context: Arc::clone(&self.context),
+            id: ObjectId::from(id),
+            data: Box::new(buffer),
+            map_context: Mutex::new(MapContext::new(desc.size)),
+            size: desc.size,
+            usage: desc.usage,
+        }
+    }
+
+    /// Creates a [`Texture`] whose memory can be shared with other APIs and
+    /// processes through [`Texture::export_memory`].
+    ///
+    /// For dma-bufs, the texture is laid out with one of `drm_format_modifiers`,
+    /// or any modifier from [`Device::drm_format_modifiers`] if it is empty.
+    /// The modifier chosen is returned with the exported memory.
+    ///
+    /// Requires the feature `handle_type` needs, see
+    /// [`ExternalMemoryHandleType::required_features`].
+    #[cfg(external_memory)]
+    pub fn create_exportable_texture(
+        &self,
+        desc: &TextureDescriptor<'_>,
+        handle_type: ExternalMemoryHandleType,
+        drm_format_modifiers: &[u64],
+    ) -> Texture {
+        let texture = self
+            .context
+            .as_any()
+            .downcast_ref::<crate::backend::ContextWgpuCore>()
+            .unwrap()
+            .create_exportable_texture(
+                self.data.as_ref().downcast_ref().unwrap(),
+                desc,
+                handle_type,
+                drm_format_modifiers,
+            );
+
+        Texture {
+            context: Arc::clone(&self.context),
+            id: ObjectId::from(texture.id()),
+            data: Box::new(texture),
+            owned: true,
+            descriptor: TextureDescriptor {
+                label: None,
+                view_formats: &[],
+                ..desc.clone()
+            },
+            color_space: ColorSpace::Srgb,
+        }
+    }
+
+    /// Creates a [`Texture`] backed by memory exported by another device, API
+    /// or process.
+    ///
+    /// Requires the feature the handle type of `memory` needs, see
+    /// [`ExternalMemoryHandleType::required_features`].
+    ///
+    /// # Safety
+    ///
+    /// - `memory` must hold a valid handle of its handle type, holding an image
+    ///   described by `desc`. Dma-bufs must be laid out as described by the
+    ///   DRM format modifier and planes of `memory`.
+    /// - Accesses to the memory from elsewhere must be synchronized with those
+    ///   through the texture, e.g. with a [`TimelineSemaphore`].
+    #[cfg(external_memory)]
+    pub unsafe fn import_texture(
+        &self,
+        desc: &TextureDescriptor<'_>,
+        memory: ExternalMemory,
+    ) -> Texture {
+        let texture = unsafe {
+            self.context
+                .as_any()
+                .downcast_ref::<crate::backend::ContextWgpuCore>()
+                .unwrap()
+                .import_texture(self.data.as_ref().downcast_ref().unwrap(), desc, memory)
+        };
+
+        Texture {
+            context: Arc::clone(&self.context),
+            id: ObjectId::from(texture.id()),
+            data: Box::new(texture),
+            owned: true,
+            descriptor: TextureDescriptor {
+                label: None,
+                view_formats: &[],
+                ..desc.clone()
+            },
+            color_space: ColorSpace::Srgb,
+        }
+    }
+
+    /// Creates a [`TimelineSemaphore`] that can be shared with other APIs and
+    /// processes through [`TimelineSemaphore::export`].
+    ///
+    /// Requires [`Features::TIMELINE_SEMAPHORE`] and the feature `handle_type`
+    /// needs, see [`ExternalSemaphoreHandleType::required_features`].
+    #[cfg(external_memory)]
+    pub fn create_exportable_timeline_semaphore(
+        &self,
+        desc: &TimelineSemaphoreDescriptor<'_>,
+        handle_type: ExternalSemaphoreHandleType,
+    ) -> TimelineSemaphore {
+        let (id, semaphore) = self
+            .context
+            .as_any()
+            .downcast_ref::<crate::backend::ContextWgpuCore>()
+            .unwrap()
+            .create_exportable_timeline_semaphore(
+                self.data.as_ref().downcast_ref().unwrap(),
+                desc,
+                handle_type,
+            );
+
+        TimelineSemaphore {
+            context: Arc::clone(&self.context),
+            id: ObjectId::from(id),
+            data: Box::new(semaphore),
+        }
+    }
+
+    /// Creates a [`TimelineSemaphore`] sharing a semaphore exported by another
+    /// device, API or process, whose submissions can then be ordered with
+    /// those of this device.
+    ///
+    /// The semaphore starts with the current value of the shared one, rather
+    /// than `desc.initial_value`.
+    ///
+    /// Requires [`Features::TIMELINE_SEMAPHORE`] and the feature the handle
+    /// type of `semaphore` needs, see [`ExternalSemaphoreHandleType::required_features`].
+    ///
+    /// # Safety
+    ///
+    /// - `semaphore` must hold a valid handle of its handle type.
+    /// - The semaphore must only be signaled with increasing values, by every
+    ///   device, API and process it is shared with.
+    #[cfg(external_memory)]
+    pub unsafe fn import_timeline_semaphore(
+        &self,
+        desc: &TimelineSemaphoreDescriptor<'_>,
+        semaphore: ExternalSemaphore,
+    ) -> TimelineSemaphore {
+        let (id, semaphore) = unsafe {
+            self.context
+                .as_any()
+                .downcast_ref::<crate::backend::ContextWgpuCore>()
+                .unwrap()
+                .import_timeline_semaphore(
+                    self.data.as_ref().downcast_ref().unwrap(),
+                    desc,
+                    semaphore,
+                )
+        };
+
+        TimelineSemaphore {
+            context: Arc::clone(&self.context),
+            id: ObjectId::from(id),
+            data: Box::new(semaphore),
+        }
+    }
+
+    /// Creates a [`Texture`] backed by a new IOSurface, which
+    /// [`Texture::iosurface`] returns to share it with CoreVideo, AVFoundation
+    /// or other processes.
+    ///
+    /// Requires [`Features::IOSURFACE`]. The texture must be 2D, single
+    /// sampled, and have a single layer and mip level.
+    #[cfg(metal)]
+    pub fn create_iosurface_texture(&self, desc: &TextureDescriptor<'_>) -> Texture {
+        let texture = self
+            .context
+            .as_any()
+            .downcast_ref::<crate::backend::ContextWgpuCore>()
+            // Only wgpu-core contexts can be created on the platforms with Metal.
+            .unwrap()
+            .create_iosurface_texture(self.data.as_ref().downcast_ref().unwrap(), desc);
+
+        Texture {
+            context: Arc::clone(&self.context),
+            id: ObjectId::from(texture.id()),
+            data: Box::new(texture),
+            owned: true,
+            descriptor: TextureDescriptor {
+                label: None,
+                view_formats: &[],
+                ..desc.clone()
+            },
+            color_space: ColorSpace::Srgb,
+        }
+    }
+
+    /// Creates a [`Texture`] on `plane` of `iosurface`, an `IOSurfaceRef`,
+    /// like one from a `CVPixelBuffer` or another process. The texture retains
+    /// the surface.
+    ///
+    /// Requires [`Features::IOSURFACE`]. The texture must be 2D, single
+    /// sampled, and have a single layer and mip level.
+    ///
+    /// # Safety
+    ///
+    /// - `iosurface` must be a valid `IOSurfaceRef`, whose `plane` holds an
+    ///   image of the size and format of `desc`.
+    /// - Accesses to the surface from elsewhere must be synchronized with those
+    ///   through the texture, e.g. by waiting for [`Queue::on_submitted_work_done`]
+    ///   before handing the surface on.
+    #[cfg(metal)]
+    pub unsafe fn import_iosurface(
+        &self,
+        desc: &TextureDescriptor<'_>,
+        iosurface: *mut std::ffi::c_void,
+        plane: u32,
+    ) -> Texture {
+        let texture = unsafe {
+            self.context
+                .as_any()
+                .downcast_ref::<crate::backend::ContextWgpuCore>()
+                .unwrap()
+                .import_iosurface(
+                    self.data.as_ref().downcast_ref().unwrap(),
+                    desc,
+                    iosurface,
+                    plane,
+                )
+        };
+
+        Texture {
+            context: Arc::clone(&self.context),
+            id: ObjectId::from(texture.id()),
+            data: Box::new(texture),
+            owned: true,
+            descriptor: TextureDescriptor {
+                label: None,
+                view_formats: &[],
+                ..desc.clone()
+            },
+            color_space: ColorSpace::Srgb,
+        }
+    }
+
+    /// Creates a [`Texture`] on `name`, a texture of a GL context sharing
+    /// objects with the device, like the context of a Qt or GTK application
+    /// wgpu is embedded in. The texture isn't deleted when the [`Texture`] is
+    /// dropped.
+    ///
+    /// Requires [`Features::GL_TEXTURE_INTEROP`].
+    ///
+    /// # Safety
+    ///
+    /// - `name` must be a texture of the size, format, dimension and mip level
+    ///   count of `desc`, which outlives the [`Texture`].
+    /// - Accesses to the texture from the other context must be synchronized
+    ///   with those through wgpu.
+    #[cfg(gl_interop)]
+    pub unsafe fn import_gl_texture(
+        &self,
+        desc: &TextureDescriptor<'_>,
+        name: std::num::NonZeroU32,
+    ) -> Texture {
+        let texture = unsafe {
+            self.context
+                .as_any()
+                .downcast_ref::<crate::backend::ContextWgpuCore>()
+                // Only wgpu-core contexts can be created on the platforms with EGL.
+                .unwrap()
+                .import_gl_texture(self.data.as_ref().downcast_ref().unwrap(), desc, name)
+        };
+
+        Texture {
+            context: Arc::clone(&self.context),
+            id: ObjectId::from(texture.id()),
+            data: Box::new(texture),
+            owned: true,
+            descriptor: TextureDescriptor {
+                label: None,
+                view_formats: &[],
+                ..desc.clone()
+            },
+            color_space: ColorSpace::Srgb,
+        }
+    }
+
+    /// Creates a [`Texture`] on `image`, an `EGLImage`, like one another GL
+    /// context or a video decoder shares. The image may be destroyed once the
+    /// texture is created.
+    ///
+    /// Requires [`Features::GL_TEXTURE_INTEROP`]. The texture must be 2D,
+    /// single sampled, and have a single layer and mip level.
+    ///
+    /// # Safety
+    ///
+    /// - `image` must be a valid `EGLImage` of the display of the device,
+    ///   holding an image of the size and format of `desc`.
+    /// - Accesses to the image from elsewhere must be synchronized with those
+    ///   through the texture.
+    #[cfg(gl_interop)]
+    pub unsafe fn import_egl_image(
+        &self,
+        desc: &TextureDescriptor<'_>,
+        image: *mut std::ffi::c_void,
+    ) -> Texture {
+        let texture = unsafe {
+            self.context
+                .as_any()
+                .downcast_ref::<crate::backend::ContextWgpuCore>()
+                .unwrap()
+                .import_egl_image(self.data.as_ref().downcast_ref().unwrap(), desc, image)
+        };
+
+        Texture {
+            context: Arc::clone(&self.context),
+            id: ObjectId::from(texture.id()),
+            data: Box::new(texture),
+            owned: true,
+            descriptor: TextureDescriptor {
+                label: None,
+                view_formats: &[],
+                ..desc.clone()
+            },
+            color_space: ColorSpace::Srgb,
+        }
+    }
+
+    /// Creates a [`TimelineSemaphore`] from a wgpu-hal Fence, to share it
+    /// with another API.
+    ///
+    /// Requires [`Features::TIMELINE_SEMAPHORE`].
+    ///
+    /// # Safety
+    ///
+    /// - `hal_fence` must be created from this device internal handle
+    /// - `hal_fence` must be backed by a timeline semaphore on Vulkan
+    /// - The current value of `hal_fence` must be `desc.initial_value`
+    #[cfg(wgpu_core)]
+    pub unsafe fn create_timeline_semaphore_from_hal<A: wgc::hal_api::HalApi>(
+        &self,
+        hal_fence: A::Fence,
+        desc: &TimelineSemaphoreDescriptor<'_>,
+    ) -> TimelineSemaphore {
+        let (id, semaphore) = unsafe {
+            self.context
+                .as_any()
+                .downcast_ref::<crate::backend::ContextWgpuCore>()
+                // Part of the safety requirements is that the fence was generated from the same hal device.
+                // Therefore, unwrap is fine here since only WgpuCoreContext has the ability to create hal fences.
+                .unwrap()
+                .create_timeline_semaphore_from_hal::<A>(
+                    hal_fence,
+                    self.data.as_ref().downcast_ref().unwrap(),
+                    desc,
+                )
+        };
+        TimelineSemaphore {
+            context: Arc::clone(&self.context),
+            id: ObjectId::from(id),
+            data: Box::new(semaphore),
+        }
+    }
+
     /// Creates a new [`Sampler`].
     ///
     /// `desc` specifies the behavior of the sampler.
@@ -2858,6 +3915,9 @@ impl Device {
             context: Arc::clone(&self.context),
             id,
             data,
+            ty: desc.ty,
+            count: desc.count,
+            readbacks: Mutex::default(),
         }
     }
 
@@ -2942,6 +4002,99 @@ impl Device {
         )
     }
 
+    /// Loses this device as if its adapter had been reset, for testing how the
+    /// application recovers from device loss.
+    ///
+    /// The callback set with [`Device::set_device_lost_callback`] is called with
+    /// `reason`, and the device and its resources become invalid. Work that was
+    /// already submitted still runs on the GPU, and this blocks until it is done,
+    /// so that the callbacks of pending [`BufferSlice::map_async`] calls and of
+    /// [`Queue::on_submitted_work_done`] have been called when it returns.
+    ///
+    /// Does nothing if the device is already lost, and on WebGPU, which can't
+    /// lose devices on demand.
+    ///
+    /// Only available in debug builds, or with the `test-hooks` feature.
+    #[cfg(any(debug_assertions, feature = "test-hooks"))]
+    pub fn simulate_loss(&self, reason: DeviceLostReason) {
+        DynContext::device_simulate_loss(&*self.context, &self.id, self.data.as_ref(), reason)
+    }
+
+    /// Makes creating pipelines fail if compiling them takes longer than `timeout`.
+    ///
+    /// Some drivers hang compiling pathological shaders. With a timeout set, such
+    /// a pipeline creation raises a validation error once the timeout elapses,
+    /// and returns an invalid pipeline, instead of blocking forever. Drivers can't
+    /// cancel a compile though: pipelines are compiled on a thread of their own,
+    /// which keeps running after the timeout and throws the pipeline away if it
+    /// finishes after all.
+    ///
+    /// `None`, the default, waits for pipelines as long as they take to compile.
+    ///
+    /// Has no effect on the web and on other platforms without threads.
+    pub fn set_pipeline_compile_timeout(&self, timeout: Option<std::time::Duration>) {
+        DynContext::device_set_pipeline_compile_timeout(
+            &*self.context,
+            &self.id,
+            self.data.as_ref(),
+            timeout,
+        )
+    }
+
+    /// Changes how much of the usage of this device is validated, see
+    /// [`ValidationLevel`].
+    ///
+    /// Devices are created at the level of [`DeviceDescriptor::validation`],
+    /// [`ValidationLevel::Full`] by default. This lets an application lower the
+    /// validation of a workload once it is known to be
+    /// valid, and raise it again to track down an error, for example from a
+    /// debug menu. Commands are validated at the level set when they are
+    /// recorded.
+    ///
+    /// Has no effect on the web, where the browser always validates in full.
+    ///
+    /// # Safety
+    ///
+    /// Below [`ValidationLevel::Full`], usage that is no longer validated is
+    /// passed on to the backend as-is. Any such usage that is invalid is
+    /// undefined behavior, and can result in out-of-bounds accesses by the GPU,
+    /// device loss or driver crashes.
+    pub unsafe fn set_validation_level(&self, level: ValidationLevel) {
+        unsafe {
+            DynContext::device_set_validation_level(
+                &*self.context,
+                &self.id,
+                self.data.as_ref(),
+                level,
+            )
+        }
+    }
+
+    /// Returns how much of the usage of this device is validated, see
+    /// [`Device::set_validation_level`].
+    ///
+    /// Always [`ValidationLevel::Full`] on the web.
+    pub fn validation_level(&self) -> ValidationLevel {
+        DynContext::device_validation_level(&*self.context, &self.id, self.data.as_ref())
+    }
+
+    /// Tells the platform how this device should trade performance for power,
+    /// see [`PerformanceHint`].
+    ///
+    /// Tools that are idle most of the time can set [`PerformanceHint::LowPower`]
+    /// to save battery, and go back to [`PerformanceHint::Burst`] while the user
+    /// interacts with them. The hint only changes the state of this device, such
+    /// as how many cores Metal compiles shaders on, and most platforms ignore
+    /// it; `Instance::set_process_performance_hint` tells the OS about the whole
+    /// process, and presenting less often, for example with
+    /// `util::PresentThrottle` on native platforms, keeps the GPU clocks down
+    /// everywhere.
+    ///
+    /// Has no effect on the web.
+    pub fn set_performance_hint(&self, hint: PerformanceHint) {
+        DynContext::device_set_performance_hint(&*self.context, &self.id, self.data.as_ref(), hint)
+    }
+
     /// Test-only function to make this device invalid.
     #[doc(hidden)]
     pub fn make_invalid(&self) {
@@ -3076,13 +4229,64 @@ impl From<wgc::instance::CreateSurfaceError> for CreateSurfaceError {
 }
 
 /// Error occurred when trying to async map a buffer.
+///
+/// [`BufferAsyncError::kind`] tells fatal failures from those that mapping the
+/// buffer again can recover from.
 #[derive(Clone, PartialEq, Eq, Debug)]
-pub struct BufferAsyncError;
+pub struct BufferAsyncError {
+    kind: BufferAsyncErrorKind,
+}
 static_assertions::assert_impl_all!(BufferAsyncError: Send, Sync);
 
+/// Why mapping a buffer failed, see [`BufferAsyncError::kind`].
+#[derive(Clone, Copy, PartialEq, Eq, Debug, Hash)]
+#[non_exhaustive]
+pub enum BufferAsyncErrorKind {
+    /// The mapping was cancelled by [`Buffer::unmap`], or by destroying or
+    /// dropping the buffer, before it completed.
+    Aborted,
+    /// The buffer is destroyed or invalid.
+    Destroyed,
+    /// The device is lost or invalid. Mapping any of its buffers fails from now on.
+    DeviceLost,
+    /// The device ran out of memory while mapping the buffer. Mapping the buffer
+    /// again can succeed once memory is freed, see also
+    /// [`DeviceFlags::RETRY_FAILED_MAPPINGS`].
+    OutOfMemory,
+    /// Mapping failed for another reason, like a validation error of the
+    /// mapping, which is also reported to the error handler of the device.
+    ///
+    /// All failures are of this kind on WebGPU, which doesn't tell why.
+    Other,
+}
+
+impl BufferAsyncError {
+    pub(crate) fn new(kind: BufferAsyncErrorKind) -> Self {
+        Self { kind }
+    }
+
+    /// Why mapping the buffer failed.
+    pub fn kind(&self) -> BufferAsyncErrorKind {
+        self.kind
+    }
+
+    /// Returns whether the failure is transient, so that mapping the buffer
+    /// again, e.g. after freeing memory, can succeed.
+    pub fn is_retryable(&self) -> bool {
+        self.kind == BufferAsyncErrorKind::OutOfMemory
+    }
+}
+
 impl fmt::Display for BufferAsyncError {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
-        write!(f, "Error occurred when trying to async map a buffer")
+        write!(f, "Error occurred when trying to async map a buffer")?;
+        match self.kind {
+            BufferAsyncErrorKind::Aborted => write!(f, ": the mapping was aborted"),
+            BufferAsyncErrorKind::Destroyed => write!(f, ": the buffer is destroyed"),
+            BufferAsyncErrorKind::DeviceLost => write!(f, ": the device is lost"),
+            BufferAsyncErrorKind::OutOfMemory => write!(f, ": out of memory"),
+            BufferAsyncErrorKind::Other => Ok(()),
+        }
     }
 }
 
@@ -3256,6 +4460,23 @@ impl Buffer {
         DynContext::buffer_destroy(&*self.context, &self.id, self.data.as_ref());
     }
 
+    /// Exports the memory of a buffer created with [`Device::create_exportable_buffer`]
+    /// or [`Device::import_buffer`], returning a new handle to it.
+    ///
+    /// Returns `None` and reports an error if the memory can't be exported.
+    #[cfg(external_memory)]
+    pub fn export_memory(&self) -> Option<ExternalMemory> {
+        self.context
+            .as_any()
+            .downcast_ref::<crate::backend::ContextWgpuCore>()
+            .and_then(|ctx| {
+                ctx.buffer_export_memory(
+                    &self.id.into(),
+                    self.data.as_ref().downcast_ref().unwrap(),
+                )
+            })
+    }
+
     /// Returns the length of the buffer allocation in bytes.
     ///
     /// This is always equal to the `size` that was specified when creating the buffer.
@@ -3269,6 +4490,69 @@ impl Buffer {
     pub fn usage(&self) -> BufferUsages {
         self.usage
     }
+
+    /// Replaces the memory of the buffer with a new allocation of `new_size`
+    /// bytes, for pools that grow or shrink, like dynamic vertex buffers.
+    ///
+    /// If `encoder` is given, a copy of the contents of the old allocation, up to
+    /// the smaller of both sizes rounded down to [`COPY_BUFFER_ALIGNMENT`], is
+    /// recorded into it. This requires [`BufferUsages::COPY_SRC`] and
+    /// [`BufferUsages::COPY_DST`]. The rest of the new allocation is zeroed. It
+    /// keeps the label and usage of the buffer.
+    ///
+    /// The old allocation is dropped, and freed once the submissions using it,
+    /// including the one of `encoder`, have completed, so there's no need to wait
+    /// for the GPU. Bind groups created with the buffer keep using the old
+    /// allocation until they are dropped, so they have to be created again, and
+    /// [`Buffer::global_id`] changes.
+    ///
+    /// `device` must be the device that created the buffer.
+    ///
+    /// # Panics
+    ///
+    /// - The buffer is mapped, or a mapping is pending.
+    pub fn resize(
+        &mut self,
+        device: &Device,
+        new_size: BufferAddress,
+        encoder: Option<&mut CommandEncoder>,
+    ) {
+        assert_eq!(
+            self.map_context.lock().initial_range,
+            0..0,
+            "Buffer {:?} can't be resized while it is mapped",
+            self.id
+        );
+
+        let label = DynContext::buffer_label(&*self.context, &self.id, self.data.as_ref());
+        let mut buffer = device.create_buffer(&BufferDescriptor {
+            label: label.as_deref(),
+            size: new_size,
+            usage: self.usage,
+            mapped_at_creation: false,
+        });
+        if let Some(encoder) = encoder {
+            let size = self.size.min(new_size) / COPY_BUFFER_ALIGNMENT * COPY_BUFFER_ALIGNMENT;
+            if size > 0 {
+                encoder.copy_buffer_to_buffer(self, 0, &buffer, 0, size);
+            }
+        }
+        // Dropping the old buffer hands its allocation to the lifetime tracker.
+        std::mem::swap(self, &mut buffer);
+    }
+
+    /// Returns the address of the buffer in the address space of the device,
+    /// which shaders can load from and store to like a storage buffer.
+    ///
+    /// The address stays the same for the lifetime of the buffer, so that it
+    /// can be stored in other buffers, for GPU-driven structures.
+    ///
+    /// Requires [`Features::BUFFER_DEVICE_ADDRESS`], and a buffer created with
+    /// [`BufferUsages::STORAGE`]. Returns `0` after reporting a validation error
+    /// otherwise.
+    pub fn gpu_address(&self) -> BufferAddress {
+        DynContext::buffer_gpu_address(&*self.context, &self.id, self.data.as_ref())
+    }
 }
 
 impl<'a> BufferSlice<'a> {
@@ -3359,6 +4643,37 @@ impl<'a> BufferSlice<'a> {
             readable: self.buffer.usage.contains(BufferUsages::MAP_READ),
         }
     }
+
+    /// Like [`BufferSlice::get_mapped_range_mut`], but leaves the buffer usable
+    /// by submissions while it is mapped, with [`Features::PERSISTENT_MAPPING`].
+    ///
+    /// A buffer whose range was taken with [`BufferSlice::get_mapped_range_mut`]
+    /// or [`BufferSlice::get_mapped_range`] can't be submitted until it is
+    /// unmapped, and taking one while a submission using the buffer is running
+    /// panics.
+    ///
+    /// # Safety
+    ///
+    /// - The returned view must not be accessed while a submission using the
+    ///   buffer is running. Submissions are known to have completed once
+    ///   [`Queue::is_submission_complete`] returns `true`, or after polling the
+    ///   device with [`Maintain::WaitForSubmissionIndex`].
+    pub unsafe fn get_mapped_range_mut_unchecked(&self) -> BufferViewMut<'a> {
+        let end = self.buffer.map_context.lock().add(self.offset, self.size);
+        let data = unsafe {
+            DynContext::buffer_get_mapped_range_unchecked(
+                &*self.buffer.context,
+                &self.buffer.id,
+                self.buffer.data.as_ref(),
+                self.offset..end,
+            )
+        };
+        BufferViewMut {
+            slice: *self,
+            data,
+            readable: self.buffer.usage.contains(BufferUsages::MAP_READ),
+        }
+    }
 }
 
 impl Drop for Buffer {
@@ -3402,6 +4717,7 @@ impl Texture {
             context: Arc::clone(&self.context),
             id,
             data,
+            texture: self.global_id(),
         }
     }
 
@@ -3410,6 +4726,115 @@ impl Texture {
         DynContext::texture_destroy(&*self.context, &self.id, self.data.as_ref());
     }
 
+    /// Exports the memory of a texture created with [`Device::create_exportable_texture`]
+    /// or [`Device::import_texture`], returning a new handle to it.
+    ///
+    /// Returns `None` and reports an error if the memory can't be exported.
+    #[cfg(external_memory)]
+    pub fn export_memory(&self) -> Option<ExternalMemory> {
+        self.context
+            .as_any()
+            .downcast_ref::<crate::backend::ContextWgpuCore>()
+            .and_then(|ctx| ctx.texture_export_memory(self.data.as_ref().downcast_ref().unwrap()))
+    }
+
+    /// Returns the expiry of the texture, for the producer of the frame it
+    /// holds, like a video decoder, to expire it when it reuses the frame.
+    ///
+    /// Expired textures fail validation when bind groups are created with
+    /// their views, rather than sampling a frame the producer is writing
+    /// again. Submitting command buffers using them, including through bind
+    /// groups created before the expiry, is an error like for destroyed
+    /// textures. On the web, the expiry isn't checked; use
+    /// `GPUExternalTexture`s there.
+    pub fn expiry(&self) -> TextureExpiry {
+        #[cfg(wgpu_core)]
+        if let Some(ctx) = self
+            .context
+            .as_any()
+            .downcast_ref::<crate::backend::ContextWgpuCore>()
+        {
+            return ctx.texture_expiry(self.data.as_ref().downcast_ref().unwrap());
+        }
+        TextureExpiry::default()
+    }
+
+    /// Returns the `IOSurfaceRef` of a texture created with
+    /// [`Device::create_iosurface_texture`] or [`Device::import_iosurface`].
+    ///
+    /// The surface isn't retained, it lives as long as the texture unless it
+    /// is retained with `CFRetain`. Returns `None` and reports an error for
+    /// other textures.
+    #[cfg(metal)]
+    pub fn iosurface(&self) -> Option<*mut std::ffi::c_void> {
+        self.context
+            .as_any()
+            .downcast_ref::<crate::backend::ContextWgpuCore>()
+            .and_then(|ctx| ctx.texture_iosurface(self.data.as_ref().downcast_ref().unwrap()))
+    }
+
+    /// Returns the GL texture name of the texture, for GL contexts sharing
+    /// objects with the device.
+    ///
+    /// Textures with only [`TextureUsages::RENDER_ATTACHMENT`] are renderbuffers
+    /// and have no texture name. Returns `None` and reports an error for them,
+    /// and on other backends.
+    #[cfg(gl_interop)]
+    pub fn gl_texture_name(&self) -> Option<std::num::NonZeroU32> {
+        self.context
+            .as_any()
+            .downcast_ref::<crate::backend::ContextWgpuCore>()
+            .and_then(|ctx| ctx.texture_gl_name(self.data.as_ref().downcast_ref().unwrap()))
+    }
+
+    /// Creates an `EGLImage` sharing the first mip level of the texture with
+    /// other GL contexts, APIs or processes. The caller owns the image, and
+    /// destroys it with `eglDestroyImageKHR`.
+    ///
+    /// The texture must be 2D, and not only have
+    /// [`TextureUsages::RENDER_ATTACHMENT`]. Returns `None` and reports an
+    /// error otherwise, and on other backends.
+    #[cfg(gl_interop)]
+    pub fn create_egl_image(&self) -> Option<*mut std::ffi::c_void> {
+        self.context
+            .as_any()
+            .downcast_ref::<crate::backend::ContextWgpuCore>()
+            .and_then(|ctx| {
+                ctx.texture_create_egl_image(self.data.as_ref().downcast_ref().unwrap())
+            })
+    }
+
+    /// Wraps the texture as a D3D11 resource of `d3d11_device`, bindable as allowed by the
+    /// usage of the texture.
+    ///
+    /// The texture has to be handed to D3D11 with [`Queue::acquire_texture_for_d3d11`] before
+    /// each use through D3D11. Returns `None` and reports an error if the texture can't be
+    /// wrapped, like on other backends than DX12.
+    ///
+    /// # Safety
+    ///
+    /// - `d3d11_device` must have been created with [`Queue::create_d3d11on12_device`] on a
+    ///   queue of the device of the texture.
+    /// - The returned texture must not outlive the texture.
+    #[cfg(d3d11on12)]
+    pub unsafe fn wrap_for_d3d11(
+        &self,
+        d3d11_device: &hal::dx12::D3D11On12Device,
+    ) -> Option<hal::dx12::D3D11Texture> {
+        self.context
+            .as_any()
+            .downcast_ref::<crate::backend::ContextWgpuCore>()
+            .and_then(|ctx| unsafe {
+                ctx.texture_wrap_for_d3d11(self.data.as_ref().downcast_ref().unwrap(), d3d11_device)
+            })
+    }
+
+    /// Returns the tiling of a texture created with [`Device::create_sparse_texture`],
+    /// or `None` for any other texture.
+    pub fn sparse_tiles(&self) -> Option<SparseTextureTiles> {
+        DynContext::texture_sparse_tiles(&*self.context, &self.id, self.data.as_ref())
+    }
+
     /// Make an `ImageCopyTexture` representing the whole texture.
     pub fn as_image_copy(&self) -> ImageCopyTexture<'_> {
         ImageCopyTexture {
@@ -3482,6 +4907,22 @@ impl Texture {
     pub fn usage(&self) -> TextureUsages {
         self.descriptor.usage
     }
+
+    /// Returns the color space the contents of this `Texture` are in.
+    ///
+    /// This is [`ColorSpace::Srgb`] unless changed with [`Texture::set_color_space`],
+    /// or the color space the surface was configured with for a surface texture.
+    pub fn color_space(&self) -> ColorSpace {
+        self.color_space
+    }
+
+    /// Tags the contents of this `Texture` as being in `color_space`.
+    ///
+    /// This doesn't change how the texture is sampled or copied; it is used by
+    /// [`util::ColorSpaceConverter`] to convert the contents to other textures.
+    pub fn set_color_space(&mut self, color_space: ColorSpace) {
+        self.color_space = color_space;
+    }
 }
 
 impl Drop for Texture {
@@ -3640,6 +5081,42 @@ impl CommandEncoder {
         );
     }
 
+    /// Copy a region of a texture into a region of another texture, scaling it
+    /// with `filter` if the two sizes differ.
+    ///
+    /// This saves having to draw a full-screen quad to downsample a texture into
+    /// a smaller one, or to upscale it. Only a single array layer is blitted.
+    ///
+    /// Requires [`Features::TEXTURE_BLIT`].
+    ///
+    /// # Panics
+    ///
+    /// - Source texture is not a single-sampled 2D texture with `COPY_SRC` usage
+    /// - Destination texture is not a single-sampled 2D texture with `COPY_DST` usage
+    /// - Either format isn't an uncompressed color format with a float sample type
+    /// - Source format isn't filterable, and `filter` is [`FilterMode::Linear`]
+    /// - Destination format can't be used as a render attachment
+    /// - Either region has more than one layer, or overruns its texture
+    pub fn blit_texture(
+        &mut self,
+        source: ImageCopyTexture<'_>,
+        source_size: Extent3d,
+        destination: ImageCopyTexture<'_>,
+        destination_size: Extent3d,
+        filter: FilterMode,
+    ) {
+        DynContext::command_encoder_blit_texture(
+            &*self.context,
+            self.id.as_ref().unwrap(),
+            self.data.as_ref(),
+            source,
+            source_size,
+            destination,
+            destination_size,
+            filter,
+        );
+    }
+
     /// Clears texture to zero.
     ///
     /// Note that unlike with clear_buffer, `COPY_DST` usage is not required.
@@ -3663,25 +5140,55 @@ impl CommandEncoder {
         );
     }
 
-    /// Clears buffer to zero.
+    /// Clears buffer to zero.
+    ///
+    /// # Panics
+    ///
+    /// - Buffer does not have `COPY_DST` usage.
+    /// - Range is out of bounds
+    pub fn clear_buffer(
+        &mut self,
+        buffer: &Buffer,
+        offset: BufferAddress,
+        size: Option<BufferAddress>,
+    ) {
+        DynContext::command_encoder_clear_buffer(
+            &*self.context,
+            self.id.as_ref().unwrap(),
+            self.data.as_ref(),
+            buffer,
+            offset,
+            size,
+        );
+    }
+
+    /// Fills buffer with `value`, repeated every 4 bytes.
+    ///
+    /// The bytes of `value` are written in little-endian order, so filling with
+    /// `0xDEADBEEF` writes the bytes `EF BE AD DE`.
+    ///
+    /// On WebGPU, `value` must be zero.
     ///
     /// # Panics
     ///
     /// - Buffer does not have `COPY_DST` usage.
+    /// - `offset` or the size are not multiples of [`COPY_BUFFER_ALIGNMENT`].
     /// - Range is out of bounds
-    pub fn clear_buffer(
+    pub fn fill_buffer(
         &mut self,
         buffer: &Buffer,
         offset: BufferAddress,
         size: Option<BufferAddress>,
+        value: u32,
     ) {
-        DynContext::command_encoder_clear_buffer(
+        DynContext::command_encoder_fill_buffer(
             &*self.context,
             self.id.as_ref().unwrap(),
             self.data.as_ref(),
             buffer,
             offset,
             size,
+            value,
         );
     }
 
@@ -3817,6 +5324,26 @@ impl<'a> RenderPass<'a> {
         )
     }
 
+    /// Sets the bindings of an inline bind group for a given bind group index.
+    ///
+    /// The active pipeline must have been created with a layout that has a layout created by
+    /// [`Device::create_inline_bind_group_layout`] at `index`. `entries` must provide a buffer
+    /// binding for every entry of that layout.
+    ///
+    /// Unlike [`RenderPass::set_bind_group`], no [`BindGroup`] has to be created, which makes
+    /// this cheap to call between draws with different buffers.
+    ///
+    /// Requires [`Features::INLINE_BIND_GROUPS`].
+    pub fn set_bind_group_inline(&mut self, index: u32, entries: &[BindGroupEntry<'a>]) {
+        DynContext::render_pass_set_bind_group_inline(
+            &*self.parent.context,
+            &mut self.id,
+            self.data.as_mut(),
+            index,
+            entries,
+        )
+    }
+
     /// Sets the active render pipeline.
     ///
     /// Subsequent draw calls will exhibit the behavior defined by `pipeline`.
@@ -3925,6 +5452,41 @@ impl<'a> RenderPass<'a> {
         );
     }
 
+    /// Sets the viewports used during the rasterization stage, the first one in place of
+    /// [`set_viewport()`](Self::set_viewport).
+    ///
+    /// Vertex shaders select the viewport of each primitive with the `viewport_index`
+    /// built-in, which defaults to the first one. Viewports after the given ones keep
+    /// their value.
+    ///
+    /// More than one viewport, up to [`MAX_VIEWPORTS`], requires
+    /// [`Features::MULTI_VIEWPORT`].
+    pub fn set_viewports(&mut self, viewports: &[Viewport]) {
+        DynContext::render_pass_set_viewports(
+            &*self.parent.context,
+            &mut self.id,
+            self.data.as_mut(),
+            viewports,
+        );
+    }
+
+    /// Sets the scissor rectangles used during the rasterization stage, the first one in place
+    /// of [`set_scissor_rect()`](Self::set_scissor_rect).
+    ///
+    /// Each primitive uses the scissor rectangle with the index of its viewport, see
+    /// [`set_viewports()`](Self::set_viewports).
+    ///
+    /// More than one scissor rectangle, up to [`MAX_VIEWPORTS`], requires
+    /// [`Features::MULTI_VIEWPORT`].
+    pub fn set_scissor_rects(&mut self, rects: &[ScissorRect]) {
+        DynContext::render_pass_set_scissor_rects(
+            &*self.parent.context,
+            &mut self.id,
+            self.data.as_mut(),
+            rects,
+        );
+    }
+
     /// Sets the stencil reference.
     ///
     /// Subsequent stencil tests will test against this value.
@@ -3938,6 +5500,80 @@ impl<'a> RenderPass<'a> {
         );
     }
 
+    /// Sets different stencil reference values for front and back faces, see
+    /// [`set_stencil_reference()`](Self::set_stencil_reference).
+    ///
+    /// Requires [`Features::SEPARATE_STENCIL_STATE`].
+    pub fn set_stencil_reference_separate(&mut self, front: u32, back: u32) {
+        DynContext::render_pass_set_stencil_reference_separate(
+            &*self.parent.context,
+            &mut self.id,
+            self.data.as_mut(),
+            front,
+            back,
+        );
+    }
+
+    /// Sets the stencil write masks for front and back faces used by subsequent
+    /// draw calls, in place of [`StencilState::write_mask`] of the current pipeline.
+    ///
+    /// The masks can't be non-zero if the pass has a read-only stencil aspect.
+    /// This lasts until the next call to [`set_pipeline()`](Self::set_pipeline).
+    /// Requires [`Features::SEPARATE_STENCIL_STATE`].
+    pub fn set_stencil_write_mask(&mut self, front: u32, back: u32) {
+        DynContext::render_pass_set_stencil_write_mask(
+            &*self.parent.context,
+            &mut self.id,
+            self.data.as_mut(),
+            front,
+            back,
+        );
+    }
+
+    /// Sets the front face used by subsequent draw calls, in place of
+    /// [`PrimitiveState::front_face`] of the current pipeline.
+    ///
+    /// This lasts until the next call to [`set_pipeline()`](Self::set_pipeline).
+    /// Requires [`Features::EXTENDED_DYNAMIC_STATE`].
+    pub fn set_front_face(&mut self, front_face: FrontFace) {
+        DynContext::render_pass_set_front_face(
+            &*self.parent.context,
+            &mut self.id,
+            self.data.as_mut(),
+            front_face,
+        );
+    }
+
+    /// Sets the cull mode used by subsequent draw calls, in place of
+    /// [`PrimitiveState::cull_mode`] of the current pipeline.
+    ///
+    /// This lasts until the next call to [`set_pipeline()`](Self::set_pipeline).
+    /// Requires [`Features::EXTENDED_DYNAMIC_STATE`].
+    pub fn set_cull_mode(&mut self, cull_mode: Option<Face>) {
+        DynContext::render_pass_set_cull_mode(
+            &*self.parent.context,
+            &mut self.id,
+            self.data.as_mut(),
+            cull_mode,
+        );
+    }
+
+    /// Sets the depth compare function used by subsequent draw calls, in place of
+    /// [`DepthStencilState::depth_compare`] of the current pipeline.
+    ///
+    /// Pipelines without a depth stencil state, or one with a stencil-only format,
+    /// aren't affected. This lasts until the next call to
+    /// [`set_pipeline()`](Self::set_pipeline). Requires
+    /// [`Features::EXTENDED_DYNAMIC_STATE`].
+    pub fn set_depth_compare(&mut self, compare: CompareFunction) {
+        DynContext::render_pass_set_depth_compare(
+            &*self.parent.context,
+            &mut self.id,
+            self.data.as_mut(),
+            compare,
+        );
+    }
+
     /// Inserts debug marker.
     pub fn insert_debug_marker(&mut self, label: &str) {
         DynContext::render_pass_insert_debug_marker(
@@ -4849,6 +6485,72 @@ impl<'a> Drop for QueueWriteBufferView<'a> {
     }
 }
 
+/// A write-only view into a staging buffer for a texture write.
+///
+/// The rows of texel blocks are [`bytes_per_row`](Self::bytes_per_row) apart,
+/// which may be more than the size of a row of the written region, and the
+/// images of the layers are [`rows_per_image`](Self::rows_per_image) rows apart.
+/// Padding between rows is ignored.
+///
+/// Reading into this buffer won't yield the contents of the texture from the
+/// GPU and is likely to be slow. Because of this, although [`AsMut`] is
+/// implemented for this type, [`AsRef`] is not.
+pub struct QueueWriteTextureView<'a> {
+    queue: &'a Queue,
+    texture: ImageCopyTexture<'a>,
+    size: Extent3d,
+    layout: ImageDataLayout,
+    inner: Box<dyn context::QueueWriteBuffer>,
+}
+#[cfg(send_sync)]
+static_assertions::assert_impl_all!(QueueWriteTextureView<'_>: Send, Sync);
+
+impl QueueWriteTextureView<'_> {
+    /// Distance in bytes between the starts of consecutive rows of texel blocks.
+    pub fn bytes_per_row(&self) -> u32 {
+        self.layout.bytes_per_row.unwrap()
+    }
+
+    /// Number of rows of texel blocks between the starts of consecutive images.
+    pub fn rows_per_image(&self) -> u32 {
+        self.layout.rows_per_image.unwrap()
+    }
+}
+
+impl Deref for QueueWriteTextureView<'_> {
+    type Target = [u8];
+
+    fn deref(&self) -> &Self::Target {
+        log::warn!("Reading from a QueueWriteTextureView won't yield the contents of the texture and may be slow.");
+        self.inner.slice()
+    }
+}
+
+impl DerefMut for QueueWriteTextureView<'_> {
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        self.inner.slice_mut()
+    }
+}
+
+impl<'a> AsMut<[u8]> for QueueWriteTextureView<'a> {
+    fn as_mut(&mut self) -> &mut [u8] {
+        self.inner.slice_mut()
+    }
+}
+
+impl<'a> Drop for QueueWriteTextureView<'a> {
+    fn drop(&mut self) {
+        DynContext::queue_write_staging_texture(
+            &*self.queue.context,
+            &self.queue.id,
+            self.queue.data.as_ref(),
+            self.texture,
+            self.size,
+            &*self.inner,
+        );
+    }
+}
+
 impl Queue {
     /// Schedule a data write into `buffer` starting at `offset`.
     ///
@@ -4945,41 +6647,314 @@ impl Queue {
         )
     }
 
-    /// Schedule a copy of data from `image` into `texture`.
-    #[cfg(any(webgpu, webgl))]
-    pub fn copy_external_image_to_texture(
+    /// Schedule a write into a texture via the returned [`QueueWriteTextureView`].
+    ///
+    /// The view is laid out the way the copy into the texture needs it, so the
+    /// texels can be written in place instead of being copied again like the
+    /// `data` of [`Queue::write_texture`]. Rows have to be written
+    /// [`bytes_per_row`](QueueWriteTextureView::bytes_per_row) apart.
+    ///
+    /// Reading from this buffer is slow and will not yield the actual contents of the texture.
+    ///
+    /// This method is intended to have low performance costs.
+    /// As such, the write is not immediately submitted, and instead enqueued
+    /// internally to happen at the start of the next `submit()` call.
+    ///
+    /// This method fails if `size` overruns the size of `texture`.
+    #[must_use]
+    pub fn write_texture_with<'a>(
+        &'a self,
+        texture: ImageCopyTexture<'a>,
+        size: Extent3d,
+    ) -> Option<QueueWriteTextureView<'a>> {
+        profiling::scope!("Queue::write_texture_with");
+        let (staging_buffer, layout) = DynContext::queue_create_staging_texture(
+            &*self.context,
+            &self.id,
+            self.data.as_ref(),
+            texture,
+            size,
+        )?;
+        Some(QueueWriteTextureView {
+            queue: self,
+            texture,
+            size,
+            layout,
+            inner: staging_buffer,
+        })
+    }
+
+    /// Schedule a copy of data from `image` into `texture`.
+    #[cfg(any(webgpu, webgl))]
+    pub fn copy_external_image_to_texture(
+        &self,
+        source: &wgt::ImageCopyExternalImage,
+        dest: ImageCopyTextureTagged<'_>,
+        size: Extent3d,
+    ) {
+        DynContext::queue_copy_external_image_to_texture(
+            &*self.context,
+            &self.id,
+            self.data.as_ref(),
+            source,
+            dest,
+            size,
+        )
+    }
+
+    /// Submits a series of finished command buffers for execution.
+    ///
+    /// The command buffers are passed through the [`SubmitMiddleware`] of the
+    /// queue first.
+    pub fn submit<I: IntoIterator<Item = CommandBuffer>>(
+        &self,
+        command_buffers: I,
+    ) -> SubmissionIndex {
+        let mut command_buffers = self
+            .apply_submit_middleware(command_buffers)
+            .into_iter()
+            .map(|mut comb| (comb.id.take().unwrap(), comb.data.take().unwrap()));
+
+        let (raw, data) = DynContext::queue_submit(
+            &*self.context,
+            &self.id,
+            self.data.as_ref(),
+            &mut command_buffers,
+        );
+
+        SubmissionIndex::new(&*self.context, raw, data)
+    }
+
+    /// Submits a series of finished command buffers for execution, like
+    /// [`Queue::submit`], ordered with [`TimelineSemaphore`]s.
+    ///
+    /// The command buffers don't begin executing before each semaphore in
+    /// `waits` has reached the paired value, and each semaphore in `signals`
+    /// is set to the paired value once they have completed. A signal value has
+    /// to be greater than any the semaphore was signaled with before.
+    pub fn submit_with_semaphores<I: IntoIterator<Item = CommandBuffer>>(
+        &self,
+        command_buffers: I,
+        waits: &[(&TimelineSemaphore, u64)],
+        signals: &[(&TimelineSemaphore, u64)],
+    ) -> SubmissionIndex {
+        let mut command_buffers = self
+            .apply_submit_middleware(command_buffers)
+            .into_iter()
+            .map(|mut comb| (comb.id.take().unwrap(), comb.data.take().unwrap()));
+        let semaphore_values = |list: &[(&TimelineSemaphore, u64)]| {
+            list.iter()
+                .map(|&(semaphore, value)| (semaphore.id, value))
+                .collect::<Vec<_>>()
+        };
+
+        let (raw, data) = DynContext::queue_submit_with_semaphores(
+            &*self.context,
+            &self.id,
+            self.data.as_ref(),
+            &mut command_buffers,
+            &semaphore_values(waits),
+            &semaphore_values(signals),
+        );
+
+        SubmissionIndex::new(&*self.context, raw, data)
+    }
+
+    /// Returns the inner hal Queue using a callback. The hal queue will be `None` if the
+    /// backend type argument does not match with this wgpu Queue
+    ///
+    /// Work submitted to the hal queue is ordered with the submissions made through `wgpu`, which
+    /// allows native commands to be recorded in between passes of `wgpu`.
+    ///
+    /// # Safety
+    ///
+    /// - The raw handle obtained from the hal Queue must not be manually destroyed
+    /// - Work must not be submitted to the hal Queue concurrently with [`Queue::submit`]
+    #[cfg(wgpu_core)]
+    pub unsafe fn as_hal<A: wgc::hal_api::HalApi, F: FnOnce(Option<&A::Queue>) -> R, R>(
+        &self,
+        hal_queue_callback: F,
+    ) -> R {
+        if let Some(ctx) = self
+            .context
+            .as_any()
+            .downcast_ref::<crate::backend::ContextWgpuCore>()
+        {
+            unsafe {
+                ctx.queue_as_hal::<A, F, R>(
+                    self.data.as_ref().downcast_ref().unwrap(),
+                    hal_queue_callback,
+                )
+            }
+        } else {
+            hal_queue_callback(None)
+        }
+    }
+
+    /// Makes the next submission to this queue wait until the Linux sync file
+    /// `fd` signals, like those that dma-buf producers and consumers hand out.
+    ///
+    /// Requires [`Features::EXTERNAL_SEMAPHORE_FD`].
+    ///
+    /// # Safety
+    ///
+    /// - `fd` must be a sync file.
+    #[cfg(all(external_memory, unix))]
+    pub unsafe fn wait_for_sync_file(&self, fd: std::os::fd::OwnedFd) {
+        if let Some(ctx) = self
+            .context
+            .as_any()
+            .downcast_ref::<crate::backend::ContextWgpuCore>()
+        {
+            unsafe { ctx.queue_wait_for_sync_file(self.data.as_ref().downcast_ref().unwrap(), fd) }
+        }
+    }
+
+    /// Returns a Linux sync file that signals once `semaphore` reaches `value`,
+    /// to hand to dma-buf consumers.
+    ///
+    /// `value` has to be signaled eventually, by a submission or the host.
+    ///
+    /// Requires [`Features::EXTERNAL_SEMAPHORE_FD`]. Returns `None` and reports
+    /// an error if the sync file can't be created.
+    #[cfg(all(external_memory, unix))]
+    pub fn export_sync_file(
+        &self,
+        semaphore: &TimelineSemaphore,
+        value: u64,
+    ) -> Option<std::os::fd::OwnedFd> {
+        self.context
+            .as_any()
+            .downcast_ref::<crate::backend::ContextWgpuCore>()
+            .and_then(|ctx| {
+                ctx.queue_export_sync_file(
+                    self.data.as_ref().downcast_ref().unwrap(),
+                    &semaphore.id.into(),
+                    value,
+                )
+            })
+    }
+
+    /// Creates a D3D11 device submitting its work to this queue through D3D11On12, for
+    /// APIs that only speak D3D11, like Media Foundation, DXVA and WinRT composition.
+    ///
+    /// Textures are shared with the device through [`Texture::wrap_for_d3d11`]. Returns `None`
+    /// and reports an error if the device can't be created, like on other backends than DX12.
+    #[cfg(d3d11on12)]
+    pub fn create_d3d11on12_device(&self) -> Option<hal::dx12::D3D11On12Device> {
+        self.context
+            .as_any()
+            .downcast_ref::<crate::backend::ContextWgpuCore>()
+            .and_then(|ctx| {
+                ctx.queue_create_d3d11on12_device(self.data.as_ref().downcast_ref().unwrap())
+            })
+    }
+
+    /// Hands `texture` over to `d3d11_device`, after the work submitted to this queue so far,
+    /// and submits the work of this queue that isn't submitted yet.
+    ///
+    /// The texture has to be released with [`hal::dx12::D3D11On12Device::release_texture`],
+    /// which submits the work recorded by the D3D11 device, before it's used through `wgpu`
+    /// again.
+    ///
+    /// # Safety
+    ///
+    /// - `wrapped` must have been returned by [`Texture::wrap_for_d3d11`] for `texture` and
+    ///   `d3d11_device`, which must have been created on this queue.
+    #[cfg(d3d11on12)]
+    pub unsafe fn acquire_texture_for_d3d11(
+        &self,
+        texture: &Texture,
+        wrapped: &hal::dx12::D3D11Texture,
+        d3d11_device: &hal::dx12::D3D11On12Device,
+    ) {
+        if let Some(ctx) = self
+            .context
+            .as_any()
+            .downcast_ref::<crate::backend::ContextWgpuCore>()
+        {
+            unsafe {
+                ctx.queue_acquire_texture_for_d3d11(
+                    self.data.as_ref().downcast_ref().unwrap(),
+                    texture.data.as_ref().downcast_ref().unwrap(),
+                    wrapped,
+                    d3d11_device,
+                )
+            }
+        }
+    }
+
+    /// Adds `middleware` to run on every submission to this queue, after the
+    /// middleware that was added before it.
+    pub fn add_submit_middleware(&self, middleware: impl SubmitMiddleware) {
+        self.middleware.lock().push(Arc::new(middleware));
+    }
+
+    /// Removes all [`SubmitMiddleware`] from this queue.
+    pub fn clear_submit_middleware(&self) {
+        self.middleware.lock().clear();
+    }
+
+    fn apply_submit_middleware<I: IntoIterator<Item = CommandBuffer>>(
         &self,
-        source: &wgt::ImageCopyExternalImage,
-        dest: ImageCopyTextureTagged<'_>,
-        size: Extent3d,
-    ) {
-        DynContext::queue_copy_external_image_to_texture(
+        command_buffers: I,
+    ) -> Vec<CommandBuffer> {
+        let mut command_buffers: Vec<_> = command_buffers.into_iter().collect();
+        // Run without the lock, so that middleware can add or remove middleware.
+        let middleware = self.middleware.lock().clone();
+        for middleware in middleware {
+            middleware.on_submit(self, &mut command_buffers);
+        }
+        command_buffers
+    }
+
+    /// Commits pages of a buffer created with [`Device::create_sparse_buffer`]
+    /// to memory, or decommits them.
+    ///
+    /// The binds are ordered with the submissions to this queue: they happen
+    /// after the command buffers submitted before, and before those submitted
+    /// after, along with writes like [`Queue::write_buffer`] that haven't been
+    /// submitted yet. Committed pages are zeroed before they are first used.
+    /// The memory of decommitted pages is released once the device is done
+    /// with them.
+    ///
+    /// Requires [`Features::SPARSE_BUFFER`], and the queue must be the one
+    /// returned by [`Adapter::request_device`].
+    pub fn bind_sparse(&self, buffer: &Buffer, binds: &[SparseBufferBind]) {
+        DynContext::queue_bind_sparse(
             &*self.context,
             &self.id,
             self.data.as_ref(),
-            source,
-            dest,
-            size,
+            &buffer.id,
+            buffer.data.as_ref(),
+            binds,
         )
     }
 
-    /// Submits a series of finished command buffers for execution.
-    pub fn submit<I: IntoIterator<Item = CommandBuffer>>(
-        &self,
-        command_buffers: I,
-    ) -> SubmissionIndex {
-        let mut command_buffers = command_buffers
-            .into_iter()
-            .map(|mut comb| (comb.id.take().unwrap(), comb.data.take().unwrap()));
-
-        let (raw, data) = DynContext::queue_submit(
+    /// Commits tiles of a texture created with [`Device::create_sparse_texture`]
+    /// to memory, or decommits them.
+    ///
+    /// Every bind covers whole tiles of one subresource: its origin must be a
+    /// multiple of [`SparseTextureTiles::tile_size`], and so must its size,
+    /// unless the region ends at the edge of the mip level. Mip levels from
+    /// [`SparseTextureTiles::mip_tail_first_level`] on share the tail of the
+    /// array layer, and are committed as a whole.
+    ///
+    /// Binds are ordered with the submissions to this queue like those of
+    /// [`Queue::bind_sparse`]. Committed tiles are zeroed before they are first
+    /// used.
+    ///
+    /// Requires [`Features::SPARSE_TEXTURE`], and the queue must be the one
+    /// returned by [`Adapter::request_device`].
+    pub fn bind_sparse_texture(&self, texture: &Texture, binds: &[SparseTextureBind]) {
+        DynContext::queue_bind_sparse_texture(
             &*self.context,
             &self.id,
             self.data.as_ref(),
-            &mut command_buffers,
-        );
-
-        SubmissionIndex(raw, data)
+            &texture.id,
+            texture.data.as_ref(),
+            binds,
+        )
     }
 
     /// Gets the amount of nanoseconds each tick of a timestamp query represents.
@@ -4992,6 +6967,18 @@ impl Queue {
         DynContext::queue_get_timestamp_period(&*self.context, &self.id, self.data.as_ref())
     }
 
+    /// Samples the timestamp clock of this queue together with the time of the
+    /// CPU, for measuring how far the clock drifts from
+    /// [`Queue::get_timestamp_period`] and relating timestamps to CPU time.
+    ///
+    /// Returns `None` where the backend can't calibrate timestamps, which are
+    /// Vulkan without `VK_EXT_calibrated_timestamps` or `CLOCK_MONOTONIC`,
+    /// OpenGL and WebGPU. See [`util::TimestampNormalizer`] for keeping
+    /// timestamps calibrated.
+    pub fn get_timestamp_calibration(&self) -> Option<TimestampCalibration> {
+        DynContext::queue_get_timestamp_calibration(&*self.context, &self.id, self.data.as_ref())
+    }
+
     /// Registers a callback when the previous call to submit finishes running on the gpu. This callback
     /// being called implies that all mapped buffer callbacks which were registered before this call will
     /// have been called.
@@ -5011,6 +6998,45 @@ impl Queue {
             Box::new(callback),
         )
     }
+
+    /// Returns whether the submission `index`, and all submissions before it, have
+    /// completed on the GPU, without blocking.
+    ///
+    /// `index` can come from any queue of the same device. Like
+    /// [`Device::wait_for_submission`], this doesn't call any callbacks.
+    ///
+    /// This is not supported on WebGPU.
+    pub fn is_submission_complete(&self, index: &SubmissionIndex) -> bool {
+        DynContext::queue_is_submission_complete(
+            &*self.context,
+            &self.id,
+            self.data.as_ref(),
+            index.1.as_ref(),
+        )
+    }
+
+    /// Turns submission batching on or off.
+    ///
+    /// While batching is on, [`Queue::submit`] validates the command buffers
+    /// but only collects them, to be submitted together as a single
+    /// submission later, which saves the overhead of many small submissions
+    /// per frame. All of them get the [`SubmissionIndex`] of the batch. The
+    /// batch is submitted by [`Queue::flush`], [`SurfaceTexture::present`],
+    /// [`Device::poll`] and [`Device::wait_for_submission`], by submitting to
+    /// another queue of the device, when batching is turned off again, and
+    /// before the `write_*` calls of the queue and before mapping or
+    /// destroying a buffer or texture, so that they are ordered after it.
+    ///
+    /// Submissions are batched by the browser on WebGPU, so this has no effect there.
+    pub fn set_submit_batching(&self, enabled: bool) {
+        DynContext::queue_set_submit_batching(&*self.context, &self.id, self.data.as_ref(), enabled)
+    }
+
+    /// Submits the command buffers batched since the last flush, see
+    /// [`Queue::set_submit_batching`].
+    pub fn flush(&self) {
+        DynContext::queue_flush(&*self.context, &self.id, self.data.as_ref())
+    }
 }
 
 impl SurfaceTexture {
@@ -5033,6 +7059,64 @@ impl SurfaceTexture {
             self.detail.as_ref(),
         );
     }
+
+    /// Schedule this texture to be presented on the owning surface, like
+    /// [`SurfaceTexture::present`], telling the compositor that only the
+    /// regions of `damage` changed since the previous frame.
+    ///
+    /// The regions are in pixels from the top-left corner of the surface, and
+    /// are clipped to it. They are a hint: compositors may repaint only the
+    /// damaged regions, saving power, but the texture still has to hold the
+    /// whole frame.
+    ///
+    /// Damage is used on Vulkan with `VK_KHR_incremental_present`, on EGL
+    /// with `EGL_KHR_swap_buffers_with_damage`, and on DX12. Other backends
+    /// present the whole frame.
+    pub fn present_with_damage(self, damage: &[DamageRect]) {
+        #[cfg(wgpu_core)]
+        if let Some(ctx) = self
+            .texture
+            .context
+            .as_any()
+            .downcast_ref::<crate::backend::ContextWgpuCore>()
+        {
+            ctx.surface_set_next_present_damage(
+                self.texture.data.as_ref().downcast_ref().unwrap(),
+                self.detail.downcast_ref().unwrap(),
+                damage,
+            );
+        }
+        let _ = damage;
+        self.present();
+    }
+
+    /// Returns the inner hal SurfaceTexture using a callback. The hal surface texture will be `None`
+    /// if the backend type argument does not match with this wgpu SurfaceTexture
+    ///
+    /// Unlike [`Texture::as_hal`] on [`SurfaceTexture::texture`], this gives access to the
+    /// backend specific state of the acquired image, such as its swapchain index.
+    ///
+    /// # Safety
+    ///
+    /// - The raw handle obtained from the hal SurfaceTexture must not be manually destroyed or presented
+    #[cfg(wgpu_core)]
+    pub unsafe fn as_hal<A: wgc::hal_api::HalApi, F: FnOnce(Option<&A::SurfaceTexture>) -> R, R>(
+        &self,
+        hal_surface_texture_callback: F,
+    ) -> R {
+        let texture = self.texture.data.as_ref().downcast_ref().unwrap();
+
+        if let Some(ctx) = self
+            .texture
+            .context
+            .as_any()
+            .downcast_ref::<crate::backend::ContextWgpuCore>()
+        {
+            unsafe { ctx.surface_texture_as_hal::<A, F, R>(texture, hal_surface_texture_callback) }
+        } else {
+            hal_surface_texture_callback(None)
+        }
+    }
 }
 
 impl Drop for SurfaceTexture {
@@ -5079,9 +7163,15 @@ impl Surface<'_> {
             width,
             height,
             desired_maximum_frame_latency: 2,
+            desired_minimum_image_count: None,
             present_mode: *caps.present_modes.first()?,
             alpha_mode: wgt::CompositeAlphaMode::Auto,
             view_formats: vec![],
+            pre_transform: wgt::SurfaceTransform::Identity,
+            color_space: wgt::ColorSpace::Srgb,
+            array_layer_count: 1,
+            fullscreen: wgt::FullscreenMode::Windowed,
+            display_mode: None,
         })
     }
 
@@ -5139,7 +7229,7 @@ impl Surface<'_> {
             size: Extent3d {
                 width: config.width,
                 height: config.height,
-                depth_or_array_layers: 1,
+                depth_or_array_layers: config.array_layer_count,
             },
             format: config.format,
             usage: config.usage,
@@ -5149,6 +7239,8 @@ impl Surface<'_> {
             view_formats: &[],
         };
 
+        let color_space = config.color_space;
+
         texture_id
             .zip(texture_data)
             .map(|(id, data)| SurfaceTexture {
@@ -5158,6 +7250,7 @@ impl Surface<'_> {
                     data,
                     owned: false,
                     descriptor,
+                    color_space,
                 },
                 suboptimal,
                 presented: false,
@@ -5166,6 +7259,210 @@ impl Surface<'_> {
             .ok_or(SurfaceError::Lost)
     }
 
+    /// Describes the HDR content presented to this surface to the display,
+    /// which uses it to tone map what it can't show as is.
+    ///
+    /// Metadata is meant for surfaces configured with
+    /// [`ColorSpace::Hdr10`], or with [`TextureFormat::Rgba16Float`] to
+    /// present scRGB. It stays in effect until it's set again or the surface
+    /// is reconfigured.
+    ///
+    /// Returns `false` if the backend or the display doesn't take HDR
+    /// metadata, which is always the case on the web.
+    ///
+    /// # Panics
+    ///
+    /// - The surface isn't configured.
+    pub fn set_hdr_metadata(&self, metadata: &HdrMetadata) -> bool {
+        #[cfg(wgpu_core)]
+        if let Some(ctx) = self
+            .context
+            .as_any()
+            .downcast_ref::<crate::backend::ContextWgpuCore>()
+        {
+            return ctx
+                .surface_set_hdr_metadata(self.surface_data.downcast_ref().unwrap(), metadata);
+        }
+        let _ = metadata;
+        false
+    }
+
+    /// Asks for the next frame presented to this surface to be shown no earlier
+    /// than `time`, a timestamp in the clock of
+    /// [`Adapter::get_presentation_timestamp`].
+    ///
+    /// Returns `false` if the backend can't schedule presents, in which case
+    /// the next frame is shown as soon as possible. Scheduling is supported on
+    /// Vulkan with `VK_GOOGLE_display_timing` and on Metal.
+    ///
+    /// # Panics
+    ///
+    /// - The surface isn't configured.
+    pub fn set_next_present_time(&self, time: PresentationTimestamp) -> bool {
+        #[cfg(wgpu_core)]
+        if let Some(ctx) = self
+            .context
+            .as_any()
+            .downcast_ref::<crate::backend::ContextWgpuCore>()
+        {
+            return ctx
+                .surface_set_next_present_time(self.surface_data.downcast_ref().unwrap(), time);
+        }
+        let _ = time;
+        false
+    }
+
+    /// Returns the timings of the frames presented to this surface that were
+    /// shown since the last call, oldest first.
+    ///
+    /// Timings are reported on Vulkan with `VK_GOOGLE_display_timing`, on
+    /// Metal, and for the last shown frame on DX12. Other backends, and the
+    /// web, return none.
+    ///
+    /// # Panics
+    ///
+    /// - The surface isn't configured.
+    pub fn presentation_timings(&self) -> Vec<PresentationTiming> {
+        #[cfg(wgpu_core)]
+        if let Some(ctx) = self
+            .context
+            .as_any()
+            .downcast_ref::<crate::backend::ContextWgpuCore>()
+        {
+            return ctx.surface_presentation_timings(self.surface_data.downcast_ref().unwrap());
+        }
+        Vec::new()
+    }
+
+    /// Returns feedback on the frames presented to this surface that were
+    /// shown or dropped since the last report: whether each was dropped, and
+    /// how long it waited between being presented and being shown.
+    ///
+    /// The report is built from the same timings as
+    /// [`Surface::presentation_timings`], and takes them, so only one of the
+    /// two should be used. Backends without presentation timings, and the
+    /// web, only report the number of presents. DX12 only reports the last
+    /// frame shown, so the frames shown between two reports are reported as
+    /// dropped.
+    ///
+    /// # Panics
+    ///
+    /// - The surface isn't configured.
+    pub fn presentation_report(&self) -> PresentationReport {
+        #[cfg(wgpu_core)]
+        if let Some(ctx) = self
+            .context
+            .as_any()
+            .downcast_ref::<crate::backend::ContextWgpuCore>()
+        {
+            return ctx.surface_presentation_report(self.surface_data.downcast_ref().unwrap());
+        }
+        PresentationReport::default()
+    }
+
+    /// Returns the duration of a refresh cycle of the display this surface is
+    /// presented to, if the backend knows it.
+    ///
+    /// # Panics
+    ///
+    /// - The surface isn't configured.
+    pub fn refresh_duration(&self) -> Option<std::time::Duration> {
+        #[cfg(wgpu_core)]
+        if let Some(ctx) = self
+            .context
+            .as_any()
+            .downcast_ref::<crate::backend::ContextWgpuCore>()
+        {
+            return ctx.surface_refresh_duration(self.surface_data.downcast_ref().unwrap());
+        }
+        None
+    }
+
+    /// Returns the number of textures the presentation engine created for
+    /// this surface, at least [`SurfaceConfiguration::desired_minimum_image_count`]
+    /// once clamped to the capabilities of the surface.
+    ///
+    /// Returns `None` on the web, where the browser manages the textures.
+    ///
+    /// # Panics
+    ///
+    /// - The surface isn't configured.
+    pub fn image_count(&self) -> Option<u32> {
+        #[cfg(wgpu_core)]
+        if let Some(ctx) = self
+            .context
+            .as_any()
+            .downcast_ref::<crate::backend::ContextWgpuCore>()
+        {
+            return Some(ctx.surface_image_count(self.surface_data.downcast_ref().unwrap()));
+        }
+        None
+    }
+
+    /// Blocks until this surface can take a new frame without more than
+    /// [`SurfaceConfiguration::desired_maximum_frame_latency`] frames waiting
+    /// to be shown, or until `timeout` elapses. If `timeout` is `None`, waits
+    /// indefinitely.
+    ///
+    /// Call it right before reading input and recording a frame: the frame is
+    /// then rendered with the latest input and shown as soon as it's ready,
+    /// rather than waiting in [`Surface::get_current_texture`] with input
+    /// that was read earlier.
+    ///
+    /// Returns the errors [`Surface::get_current_texture`] would for a lost or
+    /// outdated surface, and [`SurfaceError::Timeout`] if `timeout` elapses.
+    ///
+    /// # Panics
+    ///
+    /// - The surface isn't configured.
+    /// - The device wasn't created with [`Features::PRESENT_WAIT`].
+    pub fn wait_for_present(
+        &self,
+        timeout: Option<std::time::Duration>,
+    ) -> Result<(), SurfaceError> {
+        #[cfg(wgpu_core)]
+        if let Some(ctx) = self
+            .context
+            .as_any()
+            .downcast_ref::<crate::backend::ContextWgpuCore>()
+        {
+            return match ctx
+                .surface_wait_for_present(self.surface_data.downcast_ref().unwrap(), timeout)
+            {
+                SurfaceStatus::Good | SurfaceStatus::Suboptimal => Ok(()),
+                SurfaceStatus::Timeout => Err(SurfaceError::Timeout),
+                SurfaceStatus::Outdated => Err(SurfaceError::Outdated),
+                SurfaceStatus::Lost => Err(SurfaceError::Lost),
+            };
+        }
+        let _ = timeout;
+        Ok(())
+    }
+
+    /// Changes the [`SurfaceConfiguration::desired_maximum_frame_latency`] of
+    /// this surface without configuring it again, which would recreate the
+    /// swap chain. The latency is clamped like when configuring.
+    ///
+    /// # Panics
+    ///
+    /// - The surface isn't configured.
+    pub fn set_maximum_frame_latency(&self, maximum_frame_latency: u32) {
+        #[cfg(wgpu_core)]
+        if let Some(ctx) = self
+            .context
+            .as_any()
+            .downcast_ref::<crate::backend::ContextWgpuCore>()
+        {
+            ctx.surface_set_maximum_frame_latency(
+                self.surface_data.downcast_ref().unwrap(),
+                maximum_frame_latency,
+            );
+        }
+        if let Some(config) = self.config.lock().as_mut() {
+            config.desired_maximum_frame_latency = maximum_frame_latency;
+        }
+    }
+
     /// Returns the inner hal Surface using a callback. The hal surface will be `None` if the
     /// backend type argument does not match with this wgpu Surface
     ///
@@ -5384,6 +7681,227 @@ impl QuerySet {
     pub fn global_id(&self) -> Id<Self> {
         Id(self.id.global_id(), PhantomData)
     }
+
+    /// Reads the results of the queries in `range`, once the work submitted to
+    /// `queue` before has completed.
+    ///
+    /// The queries are resolved and copied into a buffer that can be mapped by
+    /// a submission of their own. The buffers are kept with the query set, and
+    /// reused by later calls once their results have been read, so a query set
+    /// that is read every frame doesn't allocate after the first few frames.
+    ///
+    /// Like [`BufferSlice::map_async`], the future only resolves once `device`
+    /// is polled, by [`Device::poll`] on native. It fails if the buffer holding
+    /// the results couldn't be mapped, for example because the device was lost.
+    ///
+    /// # Panics
+    ///
+    /// - `range` is empty or goes past the end of the query set.
+    pub fn read_results_async(
+        &self,
+        device: &Device,
+        queue: &Queue,
+        range: Range<u32>,
+    ) -> impl Future<Output = Result<QueryResults, BufferAsyncError>> + WasmNotSend {
+        assert!(
+            range.start < range.end && range.end <= self.count,
+            "query range {range:?} is out of bounds for a query set of {} queries",
+            self.count
+        );
+        let values_per_query = match self.ty {
+            QueryType::PipelineStatistics(types) => types.bits().count_ones(),
+            QueryType::Occlusion | QueryType::Timestamp => 1,
+        };
+        let stride = values_per_query as BufferAddress * wgt::QUERY_SIZE as BufferAddress;
+        let size = (range.end - range.start) as BufferAddress * stride;
+
+        let readback = {
+            let mut readbacks = self.readbacks.lock();
+            let free = readbacks.iter().find(|readback| {
+                readback
+                    .in_use
+                    .compare_exchange(
+                        false,
+                        true,
+                        std::sync::atomic::Ordering::Acquire,
+                        std::sync::atomic::Ordering::Relaxed,
+                    )
+                    .is_ok()
+            });
+            match free {
+                Some(readback) => Arc::clone(readback),
+                None => {
+                    let set_size = self.count as BufferAddress * stride;
+                    let readback = Arc::new(QueryReadback {
+                        resolve: device.create_buffer(&BufferDescriptor {
+                            label: Some("wgpu::QuerySet::read_results_async resolve"),
+                            size: set_size,
+                            usage: BufferUsages::QUERY_RESOLVE | BufferUsages::COPY_SRC,
+                            mapped_at_creation: false,
+                        }),
+                        read: device.create_buffer(&BufferDescriptor {
+                            label: Some("wgpu::QuerySet::read_results_async read"),
+                            size: set_size,
+                            usage: BufferUsages::COPY_DST | BufferUsages::MAP_READ,
+                            mapped_at_creation: false,
+                        }),
+                        in_use: std::sync::atomic::AtomicBool::new(true),
+                    });
+                    readbacks.push(Arc::clone(&readback));
+                    readback
+                }
+            }
+        };
+
+        let mut encoder = device.create_command_encoder(&CommandEncoderDescriptor {
+            label: Some("wgpu::QuerySet::read_results_async"),
+        });
+        encoder.resolve_query_set(self, range, &readback.resolve, 0);
+        encoder.copy_buffer_to_buffer(&readback.resolve, 0, &readback.read, 0, size);
+        queue.submit(Some(encoder.finish()));
+
+        let ty = self.ty;
+        let timestamp_period = queue.get_timestamp_period() as f64;
+        let state = Arc::new(Mutex::new(QueryResultsState::default()));
+        let callback_state = Arc::clone(&state);
+        // The query set keeps the buffers alive. If it is dropped first, the mapping
+        // is aborted, and the callback finds them gone.
+        let callback_readback = Arc::downgrade(&readback);
+        readback
+            .read
+            .slice(..size)
+            .map_async(MapMode::Read, move |result| {
+                let readback = callback_readback
+                    .upgrade()
+                    .ok_or(BufferAsyncError::new(BufferAsyncErrorKind::Aborted));
+                let results = result.and(readback).map(|readback| {
+                    let values: Vec<u64> = readback
+                        .read
+                        .slice(..size)
+                        .get_mapped_range()
+                        .chunks_exact(wgt::QUERY_SIZE as usize)
+                        .map(|bytes| u64::from_ne_bytes(bytes.try_into().unwrap()))
+                        .collect();
+                    readback.read.unmap();
+                    // A buffer that failed to map stays in use, as it may still be
+                    // marked as mapped.
+                    readback
+                        .in_use
+                        .store(false, std::sync::atomic::Ordering::Release);
+                    match ty {
+                        QueryType::Occlusion => QueryResults::Occlusion(values),
+                        QueryType::PipelineStatistics(_) => QueryResults::PipelineStatistics(
+                            values
+                                .chunks_exact(values_per_query as usize)
+                                .map(<[u64]>::to_vec)
+                                .collect(),
+                        ),
+                        QueryType::Timestamp => QueryResults::Timestamp(
+                            values
+                                .into_iter()
+                                .map(|value| value as f64 * timestamp_period)
+                                .collect(),
+                        ),
+                    }
+                });
+
+                let mut state = callback_state.lock();
+                state.results = Some(results);
+                if let Some(waker) = state.waker.take() {
+                    waker.wake();
+                }
+            });
+        QueryResultsFuture(state)
+    }
+}
+
+impl TimelineSemaphore {
+    /// Returns the current value of the semaphore.
+    pub fn value(&self) -> u64 {
+        DynContext::timeline_semaphore_value(&*self.context, &self.id, self.data.as_ref())
+    }
+
+    /// Sets the semaphore to `value` from the host.
+    ///
+    /// `value` has to be greater than any the semaphore was signaled with before.
+    pub fn signal(&self, value: u64) {
+        DynContext::timeline_semaphore_signal(&*self.context, &self.id, self.data.as_ref(), value)
+    }
+
+    /// Blocks until the semaphore reaches `value`, or `timeout` has passed.
+    ///
+    /// Returns `true` if the semaphore reached `value`.
+    pub fn wait(&self, value: u64, timeout: Duration) -> bool {
+        DynContext::timeline_semaphore_wait(
+            &*self.context,
+            &self.id,
+            self.data.as_ref(),
+            value,
+            timeout,
+        )
+    }
+
+    /// Exports a semaphore created with [`Device::create_exportable_timeline_semaphore`]
+    /// or [`Device::import_timeline_semaphore`], returning a new handle to it.
+    ///
+    /// Returns `None` and reports an error if the semaphore can't be exported.
+    #[cfg(external_memory)]
+    pub fn export(&self) -> Option<ExternalSemaphore> {
+        self.context
+            .as_any()
+            .downcast_ref::<crate::backend::ContextWgpuCore>()
+            .and_then(|ctx| {
+                ctx.timeline_semaphore_export(
+                    &self.id.into(),
+                    self.data.as_ref().downcast_ref().unwrap(),
+                )
+            })
+    }
+
+    /// Returns the inner hal Fence using a callback. The hal fence will be `None` if the
+    /// backend type argument does not match with this wgpu TimelineSemaphore.
+    ///
+    /// On Vulkan, this is a timeline semaphore, on DX12 an `ID3D12Fence` created
+    /// as shared, both of which can be exported to other APIs.
+    ///
+    /// # Safety
+    ///
+    /// - The raw handle obtained from the hal Fence must not be manually destroyed
+    /// - The fence must not be signaled with values lower than those signaled
+    ///   through `wgpu`
+    #[cfg(wgpu_core)]
+    pub unsafe fn as_hal<A: wgc::hal_api::HalApi, F: FnOnce(Option<&A::Fence>) -> R, R>(
+        &self,
+        hal_fence_callback: F,
+    ) -> R {
+        if let Some(ctx) = self
+            .context
+            .as_any()
+            .downcast_ref::<crate::backend::ContextWgpuCore>()
+        {
+            unsafe { ctx.timeline_semaphore_as_hal::<A, F, R>(self.id.into(), hal_fence_callback) }
+        } else {
+            hal_fence_callback(None)
+        }
+    }
+
+    /// Returns a globally-unique identifier for this `TimelineSemaphore`.
+    ///
+    /// Calling this method multiple times on the same object will always return the same value.
+    /// The returned value is guaranteed to be different for all resources created from the same `Instance`.
+    pub fn global_id(&self) -> Id<Self> {
+        Id(self.id.global_id(), PhantomData)
+    }
+}
+
+impl MemoryHeap {
+    /// Returns a globally-unique identifier for this `MemoryHeap`.
+    ///
+    /// Calling this method multiple times on the same object will always return the same value.
+    /// The returned value is guaranteed to be different for all resources created from the same `Instance`.
+    pub fn global_id(&self) -> Id<Self> {
+        Id(self.id.global_id(), PhantomData)
+    }
 }
 
 impl PipelineLayout {
@@ -5436,6 +7954,26 @@ impl Surface<'_> {
     }
 }
 
+/// Observes and changes the command buffers submitted to a [`Queue`], see
+/// [`Queue::add_submit_middleware`].
+///
+/// Middleware can add command buffers before or after the ones of the
+/// application, like profiling passes, watermarks or capture triggers, or
+/// inspect and reorder them, without changes to the application's renderer.
+/// Command buffers added by middleware are part of the same submission, so
+/// resources they use are tracked like any other.
+///
+/// Middleware runs in the order it was added to the queue, each seeing the
+/// command buffers left by the one before. It holds whatever it needs to
+/// record command buffers, like the [`Device`] of the queue, itself.
+pub trait SubmitMiddleware: fmt::Debug + WasmNotSendSync + 'static {
+    /// Called by [`Queue::submit`] and [`Queue::submit_with_semaphores`] with
+    /// the command buffers about to be submitted to `queue`, in order.
+    ///
+    /// Submitting to `queue` from here runs the middleware again.
+    fn on_submit(&self, queue: &Queue, command_buffers: &mut Vec<CommandBuffer>);
+}
+
 /// Type for the callback of uncaptured error handler
 pub trait UncapturedErrorHandler: Fn(Error) + Send + 'static {}
 impl<T> UncapturedErrorHandler for T where T: Fn(Error) + Send + 'static {}
@@ -5455,6 +7993,10 @@ pub enum Error {
         source: Box<dyn error::Error + 'static>,
     },
     /// Validation error, signifying a bug in code or data
+    ///
+    /// Which errors are raised depends on the [`ValidationLevel`] of the
+    /// device: below [`ValidationLevel::Full`], the usage it no longer checks
+    /// raises no error at all.
     Validation {
         /// Lower level source of the error.
         #[cfg(send_sync)]
@@ -55,6 +55,7 @@ macro_rules! include_spirv {
             $crate::ShaderModuleDescriptor {
                 label: Some($($token)*),
                 source: $crate::util::make_spirv(include_bytes!($($token)*)),
+                diagnostic_filters: &[],
             }
         }
     };
@@ -96,6 +97,7 @@ macro_rules! include_wgsl {
             $crate::ShaderModuleDescriptor {
                 label: Some($($token)*),
                 source: $crate::ShaderSource::Wgsl(include_str!($($token)*).into()),
+                diagnostic_filters: &[],
             }
         }
     };
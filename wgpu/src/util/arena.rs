@@ -0,0 +1,273 @@
+use std::{collections::VecDeque, ops::Range, sync::Arc};
+
+use crate::{
+    util::align_to, Buffer, BufferAddress, BufferBinding, BufferDescriptor, BufferSize,
+    BufferSlice, BufferUsages, Device, DynamicOffset, Queue, SubmissionIndex,
+    COPY_BUFFER_ALIGNMENT,
+};
+
+/// A large buffer of a [`BufferArena`] and its free ranges.
+#[derive(Debug)]
+struct Block {
+    buffer: Arc<Buffer>,
+    /// Free ranges, sorted and with none next to another.
+    free: Vec<Range<BufferAddress>>,
+}
+
+impl Block {
+    fn allocate(&mut self, size: BufferAddress) -> Option<BufferAddress> {
+        let index = self
+            .free
+            .iter()
+            .position(|range| range.end - range.start >= size)?;
+        let range = &mut self.free[index];
+        let offset = range.start;
+        range.start += size;
+        if range.start == range.end {
+            self.free.remove(index);
+        }
+        Some(offset)
+    }
+
+    fn free(&mut self, range: Range<BufferAddress>) {
+        let index = self.free.partition_point(|free| free.start < range.start);
+        let joins_previous = index > 0 && self.free[index - 1].end == range.start;
+        let joins_next = self
+            .free
+            .get(index)
+            .map_or(false, |next| next.start == range.end);
+        match (joins_previous, joins_next) {
+            (true, true) => {
+                self.free[index - 1].end = self.free[index].end;
+                self.free.remove(index);
+            }
+            (true, false) => self.free[index - 1].end = range.end,
+            (false, true) => self.free[index].start = range.start,
+            (false, false) => self.free.insert(index, range),
+        }
+    }
+}
+
+/// A range of a buffer handed out by [`BufferArena::allocate`].
+///
+/// It stays allocated until it is given back with [`BufferArena::free`].
+#[derive(Debug)]
+pub struct BufferAllocation {
+    buffer: Arc<Buffer>,
+    block: usize,
+    offset: BufferAddress,
+    size: BufferSize,
+    /// The size reserved in the block, rounded up to the alignment of the arena.
+    reserved: BufferAddress,
+}
+
+impl BufferAllocation {
+    /// The buffer the allocation is a part of, shared with other allocations.
+    pub fn buffer(&self) -> &Buffer {
+        &self.buffer
+    }
+
+    /// The offset of the allocation in [`BufferAllocation::buffer`].
+    pub fn offset(&self) -> BufferAddress {
+        self.offset
+    }
+
+    /// The size that was requested for the allocation.
+    pub fn size(&self) -> BufferSize {
+        self.size
+    }
+
+    /// The allocated range of the buffer.
+    pub fn slice(&self) -> BufferSlice<'_> {
+        self.buffer
+            .slice(self.offset..self.offset + self.size.get())
+    }
+
+    /// A binding of the allocated range, for a layout entry without a dynamic
+    /// offset.
+    pub fn binding(&self) -> BufferBinding<'_> {
+        BufferBinding {
+            buffer: &self.buffer,
+            offset: self.offset,
+            size: Some(self.size),
+        }
+    }
+
+    /// A binding of the start of the buffer, as large as this allocation, to
+    /// create a bind group for a layout entry with a dynamic offset.
+    ///
+    /// The bind group can be used for every allocation of the same buffer that
+    /// is no larger, by passing [`BufferAllocation::dynamic_offset`] to
+    /// `set_bind_group`.
+    pub fn dynamic_binding(&self) -> BufferBinding<'_> {
+        BufferBinding {
+            buffer: &self.buffer,
+            offset: 0,
+            size: Some(self.size),
+        }
+    }
+
+    /// The offset to pass to `set_bind_group` for a bind group made with
+    /// [`BufferAllocation::dynamic_binding`], to bind this allocation.
+    ///
+    /// # Panics
+    ///
+    /// - The allocation starts 4 GiB or more into its buffer.
+    pub fn dynamic_offset(&self) -> DynamicOffset {
+        self.offset
+            .try_into()
+            .expect("allocation offset doesn't fit into a dynamic offset")
+    }
+}
+
+/// Sub-allocates ranges of a few large buffers, like uniforms or vertex data
+/// that change every frame, instead of creating a buffer for each.
+///
+/// Ranges are aligned for the usages of the arena, so they can be bound with
+/// [`BufferAllocation::binding`], or with [`BufferAllocation::dynamic_offset`]
+/// through a bind group shared by all the allocations of a buffer.
+///
+/// Freed ranges aren't reused before the GPU is done with them:
+/// 1. Give allocations back with [`BufferArena::free()`] once the last commands
+///    using them have been recorded.
+/// 2. Submit the command buffers using them.
+/// 3. Call [`BufferArena::retire()`] with the index the submission returned.
+/// 4. Call [`BufferArena::recall()`] ahead of the next allocations, which
+///    makes the ranges of completed submissions available again.
+///
+/// This is the counterpart of [`StagingBelt`](crate::util::StagingBelt) for
+/// buffers the GPU reads from directly.
+#[derive(Debug)]
+pub struct BufferArena {
+    label: Option<String>,
+    usage: BufferUsages,
+    block_size: BufferAddress,
+    alignment: BufferAddress,
+    blocks: Vec<Block>,
+    /// Allocations freed since the last call to `retire`.
+    freed: Vec<(usize, Range<BufferAddress>)>,
+    /// Allocations freed before a submission that may still be running.
+    retired: VecDeque<(SubmissionIndex, Vec<(usize, Range<BufferAddress>)>)>,
+}
+
+impl BufferArena {
+    /// Creates an empty arena, which allocates buffers of `block_size` bytes with
+    /// `usage` and [`BufferUsages::COPY_DST`], so allocations can be filled
+    /// with [`Queue::write_buffer`].
+    ///
+    /// Allocations are aligned to [`Limits::min_uniform_buffer_offset_alignment`]
+    /// for [`BufferUsages::UNIFORM`] and to
+    /// [`Limits::min_storage_buffer_offset_alignment`] for
+    /// [`BufferUsages::STORAGE`], so they can be bound with dynamic offsets.
+    ///
+    /// [`Limits::min_uniform_buffer_offset_alignment`]: crate::Limits::min_uniform_buffer_offset_alignment
+    /// [`Limits::min_storage_buffer_offset_alignment`]: crate::Limits::min_storage_buffer_offset_alignment
+    pub fn new(
+        device: &Device,
+        label: Option<&str>,
+        usage: BufferUsages,
+        block_size: BufferAddress,
+    ) -> Self {
+        let limits = device.limits();
+        let mut alignment = COPY_BUFFER_ALIGNMENT;
+        if usage.contains(BufferUsages::UNIFORM) {
+            alignment = alignment.max(limits.min_uniform_buffer_offset_alignment.into());
+        }
+        if usage.contains(BufferUsages::STORAGE) {
+            alignment = alignment.max(limits.min_storage_buffer_offset_alignment.into());
+        }
+        Self {
+            label: label.map(str::to_owned),
+            usage: usage | BufferUsages::COPY_DST,
+            block_size: align_to(block_size, alignment),
+            alignment,
+            blocks: Vec::new(),
+            freed: Vec::new(),
+            retired: VecDeque::new(),
+        }
+    }
+
+    /// The alignment of the offsets of allocations.
+    pub fn alignment(&self) -> BufferAddress {
+        self.alignment
+    }
+
+    /// Allocates `size` bytes, creating another buffer if none of the existing
+    /// ones has a free range large enough.
+    ///
+    /// An allocation larger than the block size of the arena gets a buffer of
+    /// its own.
+    pub fn allocate(&mut self, device: &Device, size: BufferSize) -> BufferAllocation {
+        let reserved = align_to(size.get(), self.alignment);
+        let found = self
+            .blocks
+            .iter_mut()
+            .enumerate()
+            .find_map(|(index, block)| Some((index, block.allocate(reserved)?)));
+        let (block, offset) = found.unwrap_or_else(|| {
+            let block_size = self.block_size.max(reserved);
+            let buffer = device.create_buffer(&BufferDescriptor {
+                label: self.label.as_deref(),
+                size: block_size,
+                usage: self.usage,
+                mapped_at_creation: false,
+            });
+            #[allow(clippy::arc_with_non_send_sync)] // False positive on emscripten
+            let mut block = Block {
+                buffer: Arc::new(buffer),
+                free: vec![0..block_size],
+            };
+            let offset = block.allocate(reserved).unwrap();
+            self.blocks.push(block);
+            (self.blocks.len() - 1, offset)
+        });
+        BufferAllocation {
+            buffer: Arc::clone(&self.blocks[block].buffer),
+            block,
+            offset,
+            size,
+            reserved,
+        }
+    }
+
+    /// Gives `allocation` back to the arena.
+    ///
+    /// Its range is reused once the submission passed to the next call to
+    /// [`BufferArena::retire`] has completed, so this can be called as soon as
+    /// the last commands using it have been recorded.
+    pub fn free(&mut self, allocation: BufferAllocation) {
+        self.freed.push((
+            allocation.block,
+            allocation.offset..allocation.offset + allocation.reserved,
+        ));
+    }
+
+    /// Marks the allocations freed since the last call as used by the
+    /// submission of `index`.
+    ///
+    /// Call this after submitting the command buffers that last used them.
+    pub fn retire(&mut self, index: SubmissionIndex) {
+        if self.freed.is_empty() {
+            return;
+        }
+        self.retired
+            .push_back((index, std::mem::take(&mut self.freed)));
+    }
+
+    /// Makes the ranges of retired allocations whose submission has completed
+    /// on `queue` available again.
+    ///
+    /// Submissions are only known to have completed once the device has been
+    /// polled, like with any other callback.
+    pub fn recall(&mut self, queue: &Queue) {
+        while let Some((index, _)) = self.retired.front() {
+            if !queue.is_submission_complete(index) {
+                break;
+            }
+            let (_, ranges) = self.retired.pop_front().unwrap();
+            for (block, range) in ranges {
+                self.blocks[block].free(range);
+            }
+        }
+    }
+}
@@ -0,0 +1,308 @@
+use std::ops::Range;
+
+use super::RenderEncoder;
+use crate::{
+    BindGroup, Buffer, BufferAddress, BufferSize, BufferSlice, DynamicOffset, Id, IndexFormat,
+    PrimitiveTopology, RenderPipeline,
+};
+
+/// A draw call with the state it needs, recorded into a [`DrawBatcher`].
+#[derive(Clone, Debug)]
+pub struct BatchedDraw<'a> {
+    pipeline: &'a RenderPipeline,
+    bind_groups: Vec<Option<(&'a BindGroup, Vec<DynamicOffset>)>>,
+    vertex_buffers: Vec<Option<BufferSlice<'a>>>,
+    index: Option<IndexState<'a>>,
+    /// Vertices, or indices if `index` is set.
+    elements: Range<u32>,
+    instances: Range<u32>,
+    topology: Option<PrimitiveTopology>,
+}
+
+#[derive(Clone, Copy, Debug)]
+struct IndexState<'a> {
+    buffer: BufferSlice<'a>,
+    format: IndexFormat,
+    base_vertex: i32,
+}
+
+impl<'a> BatchedDraw<'a> {
+    /// A draw of `vertices`, see [`RenderEncoder::draw`].
+    pub fn new(pipeline: &'a RenderPipeline, vertices: Range<u32>, instances: Range<u32>) -> Self {
+        Self {
+            pipeline,
+            bind_groups: Vec::new(),
+            vertex_buffers: Vec::new(),
+            index: None,
+            elements: vertices,
+            instances,
+            topology: None,
+        }
+    }
+
+    /// A draw of `indices` from `index_buffer`, see [`RenderEncoder::draw_indexed`].
+    pub fn indexed(
+        pipeline: &'a RenderPipeline,
+        index_buffer: BufferSlice<'a>,
+        index_format: IndexFormat,
+        indices: Range<u32>,
+        base_vertex: i32,
+        instances: Range<u32>,
+    ) -> Self {
+        Self {
+            index: Some(IndexState {
+                buffer: index_buffer,
+                format: index_format,
+                base_vertex,
+            }),
+            ..Self::new(pipeline, indices, instances)
+        }
+    }
+
+    /// Binds `bind_group` at `index` for this draw, see [`RenderEncoder::set_bind_group`].
+    pub fn bind_group(
+        mut self,
+        index: u32,
+        bind_group: &'a BindGroup,
+        offsets: &[DynamicOffset],
+    ) -> Self {
+        let index = index as usize;
+        if self.bind_groups.len() <= index {
+            self.bind_groups.resize(index + 1, None);
+        }
+        self.bind_groups[index] = Some((bind_group, offsets.to_vec()));
+        self
+    }
+
+    /// Binds `buffer_slice` to `slot` for this draw, see [`RenderEncoder::set_vertex_buffer`].
+    pub fn vertex_buffer(mut self, slot: u32, buffer_slice: BufferSlice<'a>) -> Self {
+        let slot = slot as usize;
+        if self.vertex_buffers.len() <= slot {
+            self.vertex_buffers.resize(slot + 1, None);
+        }
+        self.vertex_buffers[slot] = Some(buffer_slice);
+        self
+    }
+
+    /// Declares the [`PrimitiveState::topology`](crate::PrimitiveState::topology)
+    /// of the pipeline.
+    ///
+    /// Only draws of a list topology can be merged with draws of the vertex or
+    /// index range that follows, as long as both draw whole primitives. Draws
+    /// of the same range with consecutive instances are merged regardless.
+    pub fn topology(mut self, topology: PrimitiveTopology) -> Self {
+        self.topology = Some(topology);
+        self
+    }
+
+    fn state_key(&self) -> StateKey {
+        StateKey {
+            pipeline: self.pipeline.global_id(),
+            bind_groups: self
+                .bind_groups
+                .iter()
+                .map(|bind_group| {
+                    bind_group
+                        .as_ref()
+                        .map(|&(bind_group, ref offsets)| (bind_group.global_id(), offsets.clone()))
+                })
+                .collect(),
+            vertex_buffers: self
+                .vertex_buffers
+                .iter()
+                .map(|slice| slice.as_ref().map(slice_key))
+                .collect(),
+            index: self.index.as_ref().map(|index| {
+                (
+                    slice_key(&index.buffer),
+                    index.format as u8,
+                    index.base_vertex,
+                )
+            }),
+        }
+    }
+
+    /// Extends `self` by `other` if both draw with the same state and their
+    /// ranges line up, so that a single draw call does the work of both.
+    fn try_merge(&mut self, other: &Self, key: &StateKey, other_key: &StateKey) -> bool {
+        if key != other_key {
+            return false;
+        }
+        let primitive_size = match self.topology {
+            Some(PrimitiveTopology::PointList) => Some(1),
+            Some(PrimitiveTopology::LineList) => Some(2),
+            Some(PrimitiveTopology::TriangleList) => Some(3),
+            Some(PrimitiveTopology::LineStrip | PrimitiveTopology::TriangleStrip) | None => None,
+        };
+        let whole_primitives = primitive_size.map_or(false, |size| {
+            self.elements.len() % size == 0 && other.elements.len() % size == 0
+        });
+        if whole_primitives
+            && self.instances == other.instances
+            && self.elements.end == other.elements.start
+        {
+            self.elements.end = other.elements.end;
+            true
+        } else if self.elements == other.elements && self.instances.end == other.instances.start {
+            self.instances.end = other.instances.end;
+            true
+        } else {
+            false
+        }
+    }
+}
+
+type SliceKey = (Id<Buffer>, BufferAddress, Option<BufferSize>);
+
+fn slice_key(slice: &BufferSlice<'_>) -> SliceKey {
+    (slice.buffer.global_id(), slice.offset, slice.size)
+}
+
+/// Everything a draw binds, ordered by how costly it is to change.
+#[derive(Clone, Debug, PartialEq, Eq, PartialOrd, Ord)]
+struct StateKey {
+    pipeline: Id<RenderPipeline>,
+    bind_groups: Vec<Option<(Id<BindGroup>, Vec<DynamicOffset>)>>,
+    vertex_buffers: Vec<Option<SliceKey>>,
+    index: Option<(SliceKey, u8, i32)>,
+}
+
+/// Collects draw calls to sort them by pipeline and bound resources, merge
+/// the ones that can share a draw call, and record them with as few state
+/// changes as possible.
+///
+/// Sorting changes the order of the draws, except among draws with the same
+/// state, so batch draws whose order doesn't matter, like opaque geometry
+/// with a depth test. The draws are recorded into any [`RenderEncoder`], so a
+/// [`RenderPass`](crate::RenderPass) or a
+/// [`RenderBundleEncoder`](crate::RenderBundleEncoder).
+///
+/// ```no_run
+/// # let pipeline: wgpu::RenderPipeline = todo!();
+/// # let bind_group: wgpu::BindGroup = todo!();
+/// # let vertex_buffer: wgpu::Buffer = todo!();
+/// # let mut pass: wgpu::RenderPass = todo!();
+/// use wgpu::util::{BatchedDraw, DrawBatcher};
+///
+/// let mut batcher = DrawBatcher::new();
+/// for first_vertex in [0, 6, 12] {
+///     batcher.push(
+///         BatchedDraw::new(&pipeline, first_vertex..first_vertex + 6, 0..1)
+///             .topology(wgpu::PrimitiveTopology::TriangleList)
+///             .bind_group(0, &bind_group, &[])
+///             .vertex_buffer(0, vertex_buffer.slice(..)),
+///     );
+/// }
+/// // Records a single draw of vertices `0..18`.
+/// batcher.encode(&mut pass);
+/// ```
+#[derive(Debug, Default)]
+pub struct DrawBatcher<'a> {
+    draws: Vec<BatchedDraw<'a>>,
+}
+
+impl<'a> DrawBatcher<'a> {
+    /// Creates an empty batcher.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Adds a draw to the batch.
+    pub fn push(&mut self, draw: BatchedDraw<'a>) {
+        self.draws.push(draw);
+    }
+
+    /// Returns the number of draws in the batch.
+    pub fn len(&self) -> usize {
+        self.draws.len()
+    }
+
+    /// Returns `true` if there are no draws in the batch.
+    pub fn is_empty(&self) -> bool {
+        self.draws.is_empty()
+    }
+
+    /// Removes all draws from the batch.
+    pub fn clear(&mut self) {
+        self.draws.clear();
+    }
+
+    /// Sorts and merges the draws in the batch and records them into `encoder`,
+    /// leaving the batch empty.
+    ///
+    /// Returns the number of draw calls recorded. Bind groups are set again
+    /// after each change of pipeline, vertex and index buffers only when they
+    /// change.
+    pub fn encode<E: RenderEncoder<'a>>(&mut self, encoder: &mut E) -> usize {
+        let mut draws: Vec<_> = self
+            .draws
+            .drain(..)
+            .map(|draw| (draw.state_key(), draw))
+            .collect();
+        // The sort is stable, so draws with the same state keep their order.
+        draws.sort_by(|(a, _), (b, _)| a.cmp(b));
+
+        let mut merged: Vec<(StateKey, BatchedDraw<'a>)> = Vec::with_capacity(draws.len());
+        for (key, draw) in draws {
+            if let Some((last_key, last)) = merged.last_mut() {
+                if last.try_merge(&draw, last_key, &key) {
+                    continue;
+                }
+            }
+            merged.push((key, draw));
+        }
+
+        let mut current: Option<&StateKey> = None;
+        for (key, draw) in &merged {
+            let pipeline_changed = current.map_or(true, |current| current.pipeline != key.pipeline);
+            if pipeline_changed {
+                encoder.set_pipeline(draw.pipeline);
+            }
+            for (index, bind_group) in draw.bind_groups.iter().enumerate() {
+                let Some((bind_group, offsets)) = bind_group else {
+                    continue;
+                };
+                let unchanged = !pipeline_changed
+                    && current.map_or(false, |current| {
+                        current.bind_groups.get(index) == key.bind_groups.get(index)
+                    });
+                if !unchanged {
+                    encoder.set_bind_group(index as u32, *bind_group, offsets);
+                }
+            }
+            for (slot, slice) in draw.vertex_buffers.iter().enumerate() {
+                let Some(slice) = slice else {
+                    continue;
+                };
+                let unchanged = current.map_or(false, |current| {
+                    current.vertex_buffers.get(slot) == key.vertex_buffers.get(slot)
+                });
+                if !unchanged {
+                    encoder.set_vertex_buffer(slot as u32, *slice);
+                }
+            }
+            match draw.index {
+                Some(ref index) => {
+                    let unchanged = current.map_or(false, |current| {
+                        current
+                            .index
+                            .as_ref()
+                            .map(|(slice, format, _)| (slice, format))
+                            == key.index.as_ref().map(|(slice, format, _)| (slice, format))
+                    });
+                    if !unchanged {
+                        encoder.set_index_buffer(index.buffer, index.format);
+                    }
+                    encoder.draw_indexed(
+                        draw.elements.clone(),
+                        index.base_vertex,
+                        draw.instances.clone(),
+                    );
+                }
+                None => encoder.draw(draw.elements.clone(), draw.instances.clone()),
+            }
+            current = Some(key);
+        }
+        merged.len()
+    }
+}
@@ -0,0 +1,205 @@
+use super::{BufferInitDescriptor, DeviceExt};
+use crate::{
+    BindGroupDescriptor, BindGroupEntry, BindGroupLayout, BindGroupLayoutDescriptor,
+    BindGroupLayoutEntry, BindingResource, BindingType, BufferBindingType, BufferUsages,
+    ColorSpace, CommandEncoder, Device, FragmentState, LoadOp, MultisampleState, Operations,
+    PipelineLayoutDescriptor, PrimitiveState, RenderPassColorAttachment, RenderPassDescriptor,
+    RenderPipeline, RenderPipelineDescriptor, ShaderModuleDescriptor, ShaderSource, ShaderStages,
+    StoreOp, Texture, TextureFormat, TextureSampleType, TextureUsages, TextureViewDescriptor,
+    TextureViewDimension, VertexState,
+};
+
+/// Converts the contents of a texture to another [`ColorSpace`] with a render pass.
+///
+/// Copies can't change the color space, so a texture tagged with
+/// [`Texture::set_color_space`] is drawn into one in another color space
+/// instead, for example into a surface texture of a surface configured with
+/// [`SurfaceConfiguration::color_space`](crate::SurfaceConfiguration::color_space).
+///
+/// Values of `*Srgb` and other normalized formats are treated as sRGB encoded,
+/// floating point values as linear; see [`ColorSpace`]. Values in
+/// [`ColorSpace::Hdr10`] are PQ encoded, and converted to and from linear
+/// values where `1.0` is 80 nits, the white of scRGB.
+pub struct ColorSpaceConverter {
+    format: TextureFormat,
+    bind_group_layout: BindGroupLayout,
+    pipeline: RenderPipeline,
+}
+
+impl ColorSpaceConverter {
+    /// Creates a converter into textures of `format`.
+    pub fn new(device: &Device, format: TextureFormat) -> Self {
+        let module = device.create_shader_module(ShaderModuleDescriptor {
+            label: Some("wgpu::util::ColorSpaceConverter"),
+            source: ShaderSource::Wgsl(include_str!("color_space.wgsl").into()),
+            diagnostic_filters: &[],
+        });
+        let bind_group_layout = device.create_bind_group_layout(&BindGroupLayoutDescriptor {
+            label: Some("wgpu::util::ColorSpaceConverter"),
+            entries: &[
+                BindGroupLayoutEntry {
+                    binding: 0,
+                    visibility: ShaderStages::FRAGMENT,
+                    ty: BindingType::Texture {
+                        sample_type: TextureSampleType::Float { filterable: false },
+                        view_dimension: TextureViewDimension::D2,
+                        multisampled: false,
+                    },
+                    count: None,
+                },
+                BindGroupLayoutEntry {
+                    binding: 1,
+                    visibility: ShaderStages::FRAGMENT,
+                    ty: BindingType::Buffer {
+                        ty: BufferBindingType::Uniform,
+                        has_dynamic_offset: false,
+                        min_binding_size: None,
+                    },
+                    count: None,
+                },
+            ],
+        });
+        let layout = device.create_pipeline_layout(&PipelineLayoutDescriptor {
+            label: Some("wgpu::util::ColorSpaceConverter"),
+            bind_group_layouts: &[&bind_group_layout],
+            push_constant_ranges: &[],
+        });
+        let pipeline = device.create_render_pipeline(&RenderPipelineDescriptor {
+            label: Some("wgpu::util::ColorSpaceConverter"),
+            layout: Some(&layout),
+            vertex: VertexState {
+                module: &module,
+                entry_point: "vs_main",
+                compilation_options: Default::default(),
+                buffers: &[],
+            },
+            primitive: PrimitiveState::default(),
+            depth_stencil: None,
+            multisample: MultisampleState::default(),
+            fragment: Some(FragmentState {
+                module: &module,
+                entry_point: "fs_main",
+                compilation_options: Default::default(),
+                targets: &[Some(format.into())],
+            }),
+            multiview: None,
+        });
+
+        Self {
+            format,
+            bind_group_layout,
+            pipeline,
+        }
+    }
+
+    /// Records drawing the first mip level and layer of `source` into those of
+    /// `target`, converting from the color space of `source` to that of `target`.
+    ///
+    /// # Panics
+    ///
+    /// - `source` doesn't have [`TextureUsages::TEXTURE_BINDING`].
+    /// - `target` doesn't have [`TextureUsages::RENDER_ATTACHMENT`] or the format
+    ///   this converter was created for.
+    /// - The textures differ in width or height.
+    pub fn convert(
+        &self,
+        device: &Device,
+        encoder: &mut CommandEncoder,
+        source: &Texture,
+        target: &Texture,
+    ) {
+        assert!(source.usage().contains(TextureUsages::TEXTURE_BINDING));
+        assert!(target.usage().contains(TextureUsages::RENDER_ATTACHMENT));
+        assert_eq!(target.format(), self.format);
+        assert_eq!(
+            (source.width(), source.height()),
+            (target.width(), target.height())
+        );
+
+        let matrix = source.color_space().conversion_matrix(target.color_space());
+        // `mat3x3<f32>` columns are padded to 16 bytes, and the struct to 64 bytes.
+        let mut params = [0u32; 16];
+        for (column, values) in params.chunks_mut(4).zip(matrix) {
+            for (param, value) in column.iter_mut().zip(values) {
+                *param = value.to_bits();
+            }
+        }
+        params[12] = transfer_function(source);
+        params[13] = transfer_function(target);
+        let params: Vec<u8> = params.iter().flat_map(|p| p.to_ne_bytes()).collect();
+        let buffer = device.create_buffer_init(&BufferInitDescriptor {
+            label: Some("wgpu::util::ColorSpaceConverter"),
+            contents: &params,
+            usage: BufferUsages::UNIFORM,
+        });
+
+        let view_desc = TextureViewDescriptor {
+            dimension: Some(TextureViewDimension::D2),
+            mip_level_count: Some(1),
+            array_layer_count: Some(1),
+            ..Default::default()
+        };
+        let source_view = source.create_view(&view_desc);
+        let target_view = target.create_view(&view_desc);
+        let bind_group = device.create_bind_group(&BindGroupDescriptor {
+            label: Some("wgpu::util::ColorSpaceConverter"),
+            layout: &self.bind_group_layout,
+            entries: &[
+                BindGroupEntry {
+                    binding: 0,
+                    resource: BindingResource::TextureView(&source_view),
+                },
+                BindGroupEntry {
+                    binding: 1,
+                    resource: buffer.as_entire_binding(),
+                },
+            ],
+        });
+
+        let mut pass = encoder.begin_render_pass(&RenderPassDescriptor {
+            label: Some("wgpu::util::ColorSpaceConverter"),
+            color_attachments: &[Some(RenderPassColorAttachment {
+                view: &target_view,
+                resolve_target: None,
+                ops: Operations {
+                    load: LoadOp::Load,
+                    store: StoreOp::Store,
+                },
+            })],
+            depth_stencil_attachment: None,
+            timestamp_writes: None,
+            occlusion_query_set: None,
+        });
+        pass.set_pipeline(&self.pipeline);
+        pass.set_bind_group(0, &bind_group, &[]);
+        pass.draw(0..3, 0..1);
+    }
+}
+
+/// The transfer function the shader has to apply itself for `texture`, one of
+/// the `TRANSFER_*` constants of the shader.
+fn transfer_function(texture: &Texture) -> u32 {
+    if texture.color_space() == ColorSpace::Hdr10 {
+        2
+    } else {
+        is_srgb_encoded(texture.format()) as u32
+    }
+}
+
+/// Whether the shader has to apply the sRGB transfer function itself, as
+/// neither the format nor a floating point representation takes care of it.
+pub(super) fn is_srgb_encoded(format: TextureFormat) -> bool {
+    use TextureFormat as Tf;
+    !format.is_srgb()
+        && !matches!(
+            format,
+            Tf::R16Float
+                | Tf::Rg16Float
+                | Tf::Rgba16Float
+                | Tf::R32Float
+                | Tf::Rg32Float
+                | Tf::Rgba32Float
+                | Tf::Rg11b10Float
+                | Tf::Rgb9e5Ufloat
+        )
+}
@@ -0,0 +1,65 @@
+struct Params {
+    matrix: mat3x3<f32>,
+    source_transfer: u32,
+    target_transfer: u32,
+}
+
+const TRANSFER_SRGB: u32 = 1u;
+const TRANSFER_PQ: u32 = 2u;
+
+// Linear values are relative to the 80 nits of scRGB white, PQ values to 10000 nits.
+const PQ_SCALE: f32 = 125.0;
+const PQ_M1: f32 = 0.1593017578125;
+const PQ_M2: f32 = 78.84375;
+const PQ_C1: f32 = 0.8359375;
+const PQ_C2: f32 = 18.8515625;
+const PQ_C3: f32 = 18.6875;
+
+@group(0) @binding(0)
+var source: texture_2d<f32>;
+@group(0) @binding(1)
+var<uniform> params: Params;
+
+@vertex
+fn vs_main(@builtin(vertex_index) index: u32) -> @builtin(position) vec4<f32> {
+    // A triangle covering the whole target.
+    let uv = vec2<f32>(f32((index << 1u) & 2u), f32(index & 2u));
+    return vec4<f32>(uv * 2.0 - 1.0, 0.0, 1.0);
+}
+
+fn srgb_to_linear(c: vec3<f32>) -> vec3<f32> {
+    return select(pow((c + 0.055) / 1.055, vec3<f32>(2.4)), c / 12.92, c <= vec3<f32>(0.04045));
+}
+
+fn linear_to_srgb(c: vec3<f32>) -> vec3<f32> {
+    return select(1.055 * pow(c, vec3<f32>(1.0 / 2.4)) - 0.055, c * 12.92, c <= vec3<f32>(0.0031308));
+}
+
+fn pq_to_linear(c: vec3<f32>) -> vec3<f32> {
+    let p = pow(max(c, vec3<f32>(0.0)), vec3<f32>(1.0 / PQ_M2));
+    let l = max(p - PQ_C1, vec3<f32>(0.0)) / (PQ_C2 - PQ_C3 * p);
+    return pow(l, vec3<f32>(1.0 / PQ_M1)) * PQ_SCALE;
+}
+
+fn linear_to_pq(c: vec3<f32>) -> vec3<f32> {
+    let l = pow(clamp(c / PQ_SCALE, vec3<f32>(0.0), vec3<f32>(1.0)), vec3<f32>(PQ_M1));
+    return pow((PQ_C1 + PQ_C2 * l) / (1.0 + PQ_C3 * l), vec3<f32>(PQ_M2));
+}
+
+@fragment
+fn fs_main(@builtin(position) position: vec4<f32>) -> @location(0) vec4<f32> {
+    let texel = textureLoad(source, vec2<i32>(position.xy), 0);
+    var color = texel.rgb;
+    if params.source_transfer == TRANSFER_SRGB {
+        color = srgb_to_linear(color);
+    } else if params.source_transfer == TRANSFER_PQ {
+        color = pq_to_linear(color);
+    }
+    color = params.matrix * color;
+    if params.target_transfer == TRANSFER_SRGB {
+        color = linear_to_srgb(clamp(color, vec3<f32>(0.0), vec3<f32>(1.0)));
+    } else if params.target_transfer == TRANSFER_PQ {
+        color = linear_to_pq(color);
+    }
+    return vec4<f32>(color, texel.a);
+}
@@ -0,0 +1,402 @@
+use std::num::NonZeroU64;
+
+use crate::{
+    util::{align_to, BufferInitDescriptor, DeviceExt},
+    BindGroup, BindGroupDescriptor, BindGroupEntry, BindGroupLayout, BindGroupLayoutDescriptor,
+    BindGroupLayoutEntry, BindingResource, BindingType, Buffer, BufferAddress, BufferBinding,
+    BufferBindingType, BufferUsages, Device, ShaderStages,
+};
+
+/// A type that can be the element of a table in [`ConstantTables`].
+///
+/// It is implemented for the 32-bit scalars and for arrays of 2 or 4 of
+/// them, which become `vec2` and `vec4` in WGSL. Arrays of 3 aren't
+/// supported, as `vec3` elements are padded to 16 bytes in storage buffers.
+pub trait ConstantTableElement: Copy {
+    /// The WGSL type of the element, like `vec4<f32>`.
+    const WGSL_TYPE: &'static str;
+
+    /// Appends the little-endian bytes of the element to `bytes`.
+    fn write_bytes(&self, bytes: &mut Vec<u8>);
+}
+
+macro_rules! impl_constant_table_element {
+    ($($ty:ty => $wgsl:literal),* $(,)?) => {$(
+        impl ConstantTableElement for $ty {
+            const WGSL_TYPE: &'static str = $wgsl;
+
+            fn write_bytes(&self, bytes: &mut Vec<u8>) {
+                bytes.extend_from_slice(&self.to_le_bytes());
+            }
+        }
+
+        impl ConstantTableElement for [$ty; 2] {
+            const WGSL_TYPE: &'static str = concat!("vec2<", $wgsl, ">");
+
+            fn write_bytes(&self, bytes: &mut Vec<u8>) {
+                self.iter().for_each(|v| v.write_bytes(bytes));
+            }
+        }
+
+        impl ConstantTableElement for [$ty; 4] {
+            const WGSL_TYPE: &'static str = concat!("vec4<", $wgsl, ">");
+
+            fn write_bytes(&self, bytes: &mut Vec<u8>) {
+                self.iter().for_each(|v| v.write_bytes(bytes));
+            }
+        }
+    )*};
+}
+
+impl_constant_table_element!(f32 => "f32", u32 => "u32", i32 => "i32");
+
+/// A table added to a [`ConstantTablesBuilder`].
+#[derive(Debug)]
+struct Table {
+    name: String,
+    element_type: &'static str,
+    len: usize,
+    offset: BufferAddress,
+    size: BufferAddress,
+}
+
+/// Collects the tables of a [`ConstantTables`].
+#[derive(Debug, Default)]
+pub struct ConstantTablesBuilder {
+    tables: Vec<Table>,
+    data: Vec<u8>,
+}
+
+impl ConstantTablesBuilder {
+    /// Creates a builder without tables.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Adds the table `name`, which shaders see as a read-only
+    /// `array<T, N>` with the `values`.
+    ///
+    /// # Panics
+    ///
+    /// - `values` is empty.
+    pub fn table<T: ConstantTableElement>(mut self, name: &str, values: &[T]) -> Self {
+        assert!(!values.is_empty(), "constant table `{name}` is empty");
+        let offset = self.data.len() as BufferAddress;
+        for value in values {
+            value.write_bytes(&mut self.data);
+        }
+        self.tables.push(Table {
+            name: name.to_owned(),
+            element_type: T::WGSL_TYPE,
+            len: values.len(),
+            offset,
+            size: self.data.len() as BufferAddress - offset,
+        });
+        self
+    }
+
+    /// Moves the `const` arrays of the WGSL `source` that have at least
+    /// `min_len` elements into tables of the same name, and returns `source`
+    /// without their declarations, to be passed to [`ConstantTables::wgsl`].
+    ///
+    /// Only arrays of [`ConstantTableElement`]s whose values are known when
+    /// parsing `source` are moved. Once moved, they are no longer constant
+    /// expressions, so they can't be used to initialize other constants.
+    ///
+    /// ```no_run
+    /// # fn example(device: &wgpu::Device, source: &str) -> Result<(), wgpu::naga::front::wgsl::ParseError> {
+    /// let (builder, source) = wgpu::util::ConstantTablesBuilder::new().tables_from_wgsl(source, 256)?;
+    /// let tables = builder.build(device, 1, wgpu::ShaderStages::FRAGMENT);
+    /// let source = tables.wgsl(&source);
+    /// # Ok(())
+    /// # }
+    /// ```
+    #[cfg(all(wgpu_core, feature = "wgsl"))]
+    pub fn tables_from_wgsl(
+        mut self,
+        source: &str,
+        min_len: usize,
+    ) -> Result<(Self, String), crate::naga::front::wgsl::ParseError> {
+        use crate::naga::{ArraySize, TypeInner};
+
+        let module = crate::naga::front::wgsl::parse_str(source)?;
+        let mut moved = Vec::new();
+        for (handle, constant) in module.constants.iter() {
+            let (Some(name), Some(span)) = (
+                constant.name.as_deref(),
+                module.constants.get_span(handle).to_range(),
+            ) else {
+                continue;
+            };
+            let TypeInner::Array {
+                base,
+                size: ArraySize::Constant(len),
+                ..
+            } = module.types[constant.ty].inner
+            else {
+                continue;
+            };
+            let len = len.get() as usize;
+            let Some(element_type) = wgsl_element_type(&module.types[base].inner) else {
+                continue;
+            };
+            if len < min_len {
+                continue;
+            }
+
+            let offset = self.data.len();
+            let element_size = module.types[base].inner.size(module.to_ctx()) as usize;
+            if write_constant(&module, constant.init, &mut self.data).is_none()
+                || self.data.len() - offset != len * element_size
+            {
+                self.data.truncate(offset);
+                continue;
+            }
+            self.tables.push(Table {
+                name: name.to_owned(),
+                element_type,
+                len,
+                offset: offset as BufferAddress,
+                size: (self.data.len() - offset) as BufferAddress,
+            });
+            moved.push(span);
+        }
+
+        // Keep the line breaks of the declarations, so that the rest of the
+        // shader only moves by the lines `ConstantTables::wgsl` prepends.
+        moved.sort_by_key(|span| span.start);
+        let mut stripped = String::with_capacity(source.len());
+        let mut end = 0;
+        for span in moved {
+            stripped.push_str(&source[end..span.start]);
+            stripped.extend(source[span.clone()].matches('\n'));
+            end = span.end;
+        }
+        stripped.push_str(&source[end..]);
+
+        Ok((self, stripped))
+    }
+
+    /// Uploads the tables into a single buffer of `device`, and creates the
+    /// bind group that binds them to `group`, starting at binding `0`, for
+    /// the shader `visibility`.
+    ///
+    /// Each table starts at a multiple of
+    /// [`Limits::min_storage_buffer_offset_alignment`] of the buffer.
+    ///
+    /// # Panics
+    ///
+    /// - There are more tables than
+    ///   [`Limits::max_storage_buffers_per_shader_stage`] of `device`, as each
+    ///   table is bound as a storage buffer.
+    ///
+    /// [`Limits::min_storage_buffer_offset_alignment`]: crate::Limits::min_storage_buffer_offset_alignment
+    /// [`Limits::max_storage_buffers_per_shader_stage`]: crate::Limits::max_storage_buffers_per_shader_stage
+    pub fn build(
+        mut self,
+        device: &Device,
+        group: u32,
+        visibility: ShaderStages,
+    ) -> ConstantTables {
+        let max_tables = device.limits().max_storage_buffers_per_shader_stage;
+        assert!(
+            self.tables.len() <= max_tables as usize,
+            "{} constant tables don't fit in the {max_tables} storage buffers of a shader stage",
+            self.tables.len()
+        );
+
+        let alignment = device.limits().min_storage_buffer_offset_alignment as BufferAddress;
+        let mut contents = Vec::with_capacity(self.data.len());
+        for table in &mut self.tables {
+            let start = table.offset as usize;
+            table.offset = align_to(contents.len() as BufferAddress, alignment);
+            contents.resize(table.offset as usize, 0);
+            contents.extend_from_slice(&self.data[start..start + table.size as usize]);
+        }
+
+        let buffer = device.create_buffer_init(&BufferInitDescriptor {
+            label: Some("wgpu::util::ConstantTables"),
+            contents: &contents,
+            usage: BufferUsages::STORAGE,
+        });
+        let layout_entries = (0..self.tables.len() as u32)
+            .map(|binding| BindGroupLayoutEntry {
+                binding,
+                visibility,
+                ty: BindingType::Buffer {
+                    ty: BufferBindingType::Storage { read_only: true },
+                    has_dynamic_offset: false,
+                    min_binding_size: None,
+                },
+                count: None,
+            })
+            .collect::<Vec<_>>();
+        let bind_group_layout = device.create_bind_group_layout(&BindGroupLayoutDescriptor {
+            label: Some("wgpu::util::ConstantTables"),
+            entries: &layout_entries,
+        });
+        let entries = self
+            .tables
+            .iter()
+            .zip(0..)
+            .map(|(table, binding)| BindGroupEntry {
+                binding,
+                resource: BindingResource::Buffer(BufferBinding {
+                    buffer: &buffer,
+                    offset: table.offset,
+                    size: NonZeroU64::new(table.size),
+                }),
+            })
+            .collect::<Vec<_>>();
+        let bind_group = device.create_bind_group(&BindGroupDescriptor {
+            label: Some("wgpu::util::ConstantTables"),
+            layout: &bind_group_layout,
+            entries: &entries,
+        });
+
+        let declarations = self
+            .tables
+            .iter()
+            .zip(0..)
+            .map(|(table, binding)| {
+                format!(
+                    "@group({group}) @binding({binding}) var<storage, read> {}: array<{}, {}>;\n",
+                    table.name, table.element_type, table.len
+                )
+            })
+            .collect();
+
+        ConstantTables {
+            buffer,
+            bind_group_layout,
+            bind_group,
+            declarations,
+        }
+    }
+}
+
+/// Returns the WGSL type of the [`ConstantTableElement`] `inner` is, if any.
+#[cfg(all(wgpu_core, feature = "wgsl"))]
+fn wgsl_element_type(inner: &crate::naga::TypeInner) -> Option<&'static str> {
+    use crate::naga::{Scalar, TypeInner, VectorSize};
+
+    Some(match *inner {
+        TypeInner::Scalar(Scalar::F32) => f32::WGSL_TYPE,
+        TypeInner::Scalar(Scalar::U32) => u32::WGSL_TYPE,
+        TypeInner::Scalar(Scalar::I32) => i32::WGSL_TYPE,
+        TypeInner::Vector {
+            size: VectorSize::Bi,
+            scalar,
+        } => match scalar {
+            Scalar::F32 => <[f32; 2]>::WGSL_TYPE,
+            Scalar::U32 => <[u32; 2]>::WGSL_TYPE,
+            Scalar::I32 => <[i32; 2]>::WGSL_TYPE,
+            _ => return None,
+        },
+        TypeInner::Vector {
+            size: VectorSize::Quad,
+            scalar,
+        } => match scalar {
+            Scalar::F32 => <[f32; 4]>::WGSL_TYPE,
+            Scalar::U32 => <[u32; 4]>::WGSL_TYPE,
+            Scalar::I32 => <[i32; 4]>::WGSL_TYPE,
+            _ => return None,
+        },
+        _ => return None,
+    })
+}
+
+/// Appends the little-endian bytes of the constant expression `expr` of
+/// `module` to `bytes`, or returns `None` if it isn't made of 32-bit literals.
+#[cfg(all(wgpu_core, feature = "wgsl"))]
+fn write_constant(
+    module: &crate::naga::Module,
+    expr: crate::naga::Handle<crate::naga::Expression>,
+    bytes: &mut Vec<u8>,
+) -> Option<()> {
+    use crate::naga::{Expression, Literal};
+
+    match module.global_expressions[expr] {
+        Expression::Literal(Literal::F32(value)) => value.write_bytes(bytes),
+        Expression::Literal(Literal::U32(value)) => value.write_bytes(bytes),
+        Expression::Literal(Literal::I32(value)) => value.write_bytes(bytes),
+        Expression::ZeroValue(ty) => {
+            let size = module.types[ty].inner.size(module.to_ctx()) as usize;
+            bytes.resize(bytes.len() + size, 0);
+        }
+        Expression::Splat { size, value } => {
+            for _ in 0..size as u8 {
+                write_constant(module, value, bytes)?;
+            }
+        }
+        Expression::Compose { ref components, .. } => {
+            for &component in components {
+                write_constant(module, component, bytes)?;
+            }
+        }
+        _ => return None,
+    }
+    Some(())
+}
+
+/// Large constant arrays, like lookup tables, uploaded once into a buffer
+/// instead of being inlined into shader code.
+///
+/// Backends compile shaders with large `const` arrays slowly, as they are
+/// turned into code that builds the array. Tables here are read-only storage
+/// buffer bindings instead, so [`ConstantTables::wgsl`] prepends their
+/// declarations to the WGSL of a shader, which then indexes them like the
+/// arrays they replace. Pipelines using the shader bind
+/// [`ConstantTables::bind_group`] at the group the tables were built for.
+///
+/// Tables are either added one by one with [`ConstantTablesBuilder::table`],
+/// or moved out of the `const` arrays of existing WGSL with
+/// [`ConstantTablesBuilder::tables_from_wgsl`].
+///
+/// ```no_run
+/// # fn example(device: &wgpu::Device, gamma: &[f32]) {
+/// let tables = wgpu::util::ConstantTablesBuilder::new()
+///     .table("GAMMA", gamma)
+///     .build(device, 1, wgpu::ShaderStages::FRAGMENT);
+/// let source = tables.wgsl("
+///     @fragment
+///     fn main(@location(0) c: vec4<f32>) -> @location(0) vec4<f32> {
+///         return vec4(GAMMA[u32(c.r * 255.0)], GAMMA[u32(c.g * 255.0)], GAMMA[u32(c.b * 255.0)], c.a);
+///     }
+/// ");
+/// # }
+/// ```
+#[derive(Debug)]
+pub struct ConstantTables {
+    buffer: Buffer,
+    bind_group_layout: BindGroupLayout,
+    bind_group: BindGroup,
+    declarations: String,
+}
+
+impl ConstantTables {
+    /// The declarations of the tables, one per line.
+    pub fn declarations(&self) -> &str {
+        &self.declarations
+    }
+
+    /// Returns `source` with the declarations of the tables prepended.
+    pub fn wgsl(&self, source: &str) -> String {
+        format!("{}{source}", self.declarations)
+    }
+
+    /// The buffer holding all tables.
+    pub fn buffer(&self) -> &Buffer {
+        &self.buffer
+    }
+
+    /// The layout of [`ConstantTables::bind_group`], for pipeline layouts.
+    pub fn bind_group_layout(&self) -> &BindGroupLayout {
+        &self.bind_group_layout
+    }
+
+    /// The bind group binding the tables.
+    pub fn bind_group(&self) -> &BindGroup {
+        &self.bind_group
+    }
+}
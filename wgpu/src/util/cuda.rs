@@ -0,0 +1,296 @@
+use crate::{
+    Buffer, ExternalMemory, ExternalMemoryHandleType, ExternalSemaphoreHandleType, Texture,
+    TextureDimension, TextureFormat, TextureUsages, TimelineSemaphore,
+};
+
+/// `cudaExternalMemoryDedicated`: the memory belongs to a single resource.
+pub const CUDA_EXTERNAL_MEMORY_DEDICATED: u32 = 0x1;
+
+/// `cudaArrayLayered`: the array has layers, counted by the depth of its extent.
+pub const CUDA_ARRAY_LAYERED: u32 = 0x01;
+/// `cudaArraySurfaceLoadStore`: the array can be bound to surface references.
+pub const CUDA_ARRAY_SURFACE_LOAD_STORE: u32 = 0x02;
+/// `cudaArrayColorAttachment`: the array can be a color attachment of another API.
+pub const CUDA_ARRAY_COLOR_ATTACHMENT: u32 = 0x20;
+
+/// A value of `cudaExternalMemoryHandleType`, the type of handle memory
+/// exported by wgpu is imported into CUDA with.
+#[repr(i32)]
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum CudaExternalMemoryHandleType {
+    /// `cudaExternalMemoryHandleTypeOpaqueFd`, for Vulkan memory exported
+    /// through [`ExternalMemoryHandleType::OpaqueFd`].
+    OpaqueFd = 1,
+    /// `cudaExternalMemoryHandleTypeD3D12Resource`, for DX12 resources
+    /// exported through [`ExternalMemoryHandleType::D3D12Resource`].
+    D3D12Resource = 5,
+}
+
+/// A value of `cudaExternalSemaphoreHandleType`, the type of handle timeline
+/// semaphores exported by wgpu are imported into CUDA with.
+#[repr(i32)]
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum CudaExternalSemaphoreHandleType {
+    /// `cudaExternalSemaphoreHandleTypeD3D12Fence`, for DX12 fences exported
+    /// through [`ExternalSemaphoreHandleType::D3D12Fence`].
+    D3D12Fence = 4,
+    /// `cudaExternalSemaphoreHandleTypeTimelineSemaphoreFd`, for Vulkan
+    /// timeline semaphores exported through [`ExternalSemaphoreHandleType::OpaqueFd`].
+    TimelineSemaphoreFd = 9,
+}
+
+/// A value of `cudaChannelFormatKind`.
+#[repr(i32)]
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum CudaChannelFormatKind {
+    /// `cudaChannelFormatKindSigned`, for signed integer and normalized formats.
+    Signed = 0,
+    /// `cudaChannelFormatKindUnsigned`, for unsigned integer and normalized formats.
+    Unsigned = 1,
+    /// `cudaChannelFormatKindFloat`, for float formats.
+    Float = 2,
+}
+
+/// The fields of `cudaChannelFormatDesc`: the size of each channel of a
+/// texel, in bits, and how the channels are interpreted.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct CudaChannelFormatDesc {
+    /// Bits of the first channel.
+    pub x: i32,
+    /// Bits of the second channel, or 0 if there is none.
+    pub y: i32,
+    /// Bits of the third channel, or 0 if there is none.
+    pub z: i32,
+    /// Bits of the fourth channel, or 0 if there is none.
+    pub w: i32,
+    /// How the channels are interpreted.
+    pub kind: CudaChannelFormatKind,
+}
+
+/// The memory of a buffer or texture, in the form `cudaImportExternalMemory`
+/// takes it as a `cudaExternalMemoryHandleDesc`.
+///
+/// `size` is the size of the whole memory, which may exceed that of the
+/// resource. The memory is always dedicated to the resource, so `flags`
+/// holds [`CUDA_EXTERNAL_MEMORY_DEDICATED`], which CUDA requires for DX12
+/// resources and Vulkan dedicated allocations alike.
+#[derive(Debug)]
+pub struct CudaExternalMemory {
+    /// The handle the memory is shared through, of which CUDA takes
+    /// ownership once the memory is imported on Unix, but not on Windows.
+    pub memory: ExternalMemory,
+    /// The type of the handle, `type` of `cudaExternalMemoryHandleDesc`.
+    pub handle_type: CudaExternalMemoryHandleType,
+    /// `size` of `cudaExternalMemoryHandleDesc`.
+    pub size: u64,
+    /// `flags` of `cudaExternalMemoryHandleDesc`.
+    pub flags: u32,
+}
+
+/// Where a buffer is in its memory, in the form `cudaExternalMemoryGetMappedBuffer`
+/// takes it as a `cudaExternalMemoryBufferDesc`.
+///
+/// The buffer starts at the beginning of its memory and is laid out linearly,
+/// so the pointer CUDA maps addresses the same bytes as the buffer does.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct CudaBufferDesc {
+    /// `offset` of `cudaExternalMemoryBufferDesc`.
+    pub offset: u64,
+    /// `size` of `cudaExternalMemoryBufferDesc`, the size of the buffer.
+    pub size: u64,
+    /// `flags` of `cudaExternalMemoryBufferDesc`, which must be 0.
+    pub flags: u32,
+}
+
+/// How a texture is laid out in its memory, in the form
+/// `cudaExternalMemoryGetMappedMipmappedArray` takes it as a
+/// `cudaExternalMemoryMipmappedArrayDesc`.
+///
+/// Textures are laid out in a way only the driver knows, so they can only be
+/// mapped as CUDA arrays, with the same format, extent, layers and mip levels
+/// as the texture, rather than as linear memory.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct CudaMipmappedArrayDesc {
+    /// `offset` of `cudaExternalMemoryMipmappedArrayDesc`.
+    pub offset: u64,
+    /// `formatDesc` of `cudaExternalMemoryMipmappedArrayDesc`.
+    pub format: CudaChannelFormatDesc,
+    /// `extent` of `cudaExternalMemoryMipmappedArrayDesc`, as width, height
+    /// and depth. Unused dimensions are 0, and the depth of layered arrays is
+    /// their number of layers.
+    pub extent: [usize; 3],
+    /// `flags` of `cudaExternalMemoryMipmappedArrayDesc`, a combination of
+    /// [`CUDA_ARRAY_LAYERED`], [`CUDA_ARRAY_SURFACE_LOAD_STORE`] and
+    /// [`CUDA_ARRAY_COLOR_ATTACHMENT`].
+    pub flags: u32,
+    /// `numLevels` of `cudaExternalMemoryMipmappedArrayDesc`.
+    pub num_levels: u32,
+}
+
+/// A timeline semaphore, in the form `cudaImportExternalSemaphore` takes it as
+/// a `cudaExternalSemaphoreHandleDesc`.
+///
+/// CUDA signals and waits for the same values as wgpu: the `fence.value` of
+/// `cudaExternalSemaphoreSignalParams` and `cudaExternalSemaphoreWaitParams`
+/// are values of the semaphore, which have to increase like those of
+/// [`TimelineSemaphore::signal`].
+#[derive(Debug)]
+pub struct CudaExternalSemaphore {
+    /// The handle the semaphore is shared through, of which CUDA takes
+    /// ownership once the semaphore is imported on Unix, but not on Windows.
+    pub semaphore: crate::ExternalSemaphore,
+    /// The type of the handle, `type` of `cudaExternalSemaphoreHandleDesc`.
+    pub handle_type: CudaExternalSemaphoreHandleType,
+}
+
+/// Returns the `cudaChannelFormatDesc` of CUDA arrays holding textures of
+/// `format`, or `None` if CUDA has no matching format.
+///
+/// CUDA arrays only know the size and type of channels, so normalized formats
+/// are integer formats to them, which texture objects can read normalized.
+/// sRGB, packed, depth, stencil and compressed formats aren't supported.
+pub fn cuda_channel_format(format: TextureFormat) -> Option<CudaChannelFormatDesc> {
+    use CudaChannelFormatKind::{Float, Signed, Unsigned};
+    use TextureFormat as Tf;
+
+    let (bits, channels, kind) = match format {
+        Tf::R8Unorm | Tf::R8Uint => (8, 1, Unsigned),
+        Tf::R8Snorm | Tf::R8Sint => (8, 1, Signed),
+        Tf::Rg8Unorm | Tf::Rg8Uint => (8, 2, Unsigned),
+        Tf::Rg8Snorm | Tf::Rg8Sint => (8, 2, Signed),
+        Tf::Rgba8Unorm | Tf::Rgba8Uint => (8, 4, Unsigned),
+        Tf::Rgba8Snorm | Tf::Rgba8Sint => (8, 4, Signed),
+        Tf::R16Unorm | Tf::R16Uint => (16, 1, Unsigned),
+        Tf::R16Snorm | Tf::R16Sint => (16, 1, Signed),
+        Tf::R16Float => (16, 1, Float),
+        Tf::Rg16Unorm | Tf::Rg16Uint => (16, 2, Unsigned),
+        Tf::Rg16Snorm | Tf::Rg16Sint => (16, 2, Signed),
+        Tf::Rg16Float => (16, 2, Float),
+        Tf::Rgba16Unorm | Tf::Rgba16Uint => (16, 4, Unsigned),
+        Tf::Rgba16Snorm | Tf::Rgba16Sint => (16, 4, Signed),
+        Tf::Rgba16Float => (16, 4, Float),
+        Tf::R32Uint => (32, 1, Unsigned),
+        Tf::R32Sint => (32, 1, Signed),
+        Tf::R32Float => (32, 1, Float),
+        Tf::Rg32Uint => (32, 2, Unsigned),
+        Tf::Rg32Sint => (32, 2, Signed),
+        Tf::Rg32Float => (32, 2, Float),
+        Tf::Rgba32Uint => (32, 4, Unsigned),
+        Tf::Rgba32Sint => (32, 4, Signed),
+        Tf::Rgba32Float => (32, 4, Float),
+        _ => return None,
+    };
+    let channel = |index| if index < channels { bits } else { 0 };
+    Some(CudaChannelFormatDesc {
+        x: channel(0),
+        y: channel(1),
+        z: channel(2),
+        w: channel(3),
+        kind,
+    })
+}
+
+/// Exports the memory of `buffer` for CUDA, along with where the buffer is
+/// in it.
+///
+/// `buffer` has to be created with [`Device::create_exportable_buffer`] with
+/// [`ExternalMemoryHandleType::OpaqueFd`] or [`ExternalMemoryHandleType::D3D12Resource`],
+/// or imported with one of those. Returns `None` otherwise, or if the memory
+/// can't be exported.
+///
+/// Accesses from CUDA have to be ordered with those of wgpu through a
+/// semaphore from [`export_semaphore_for_cuda`].
+///
+/// [`Device::create_exportable_buffer`]: crate::Device::create_exportable_buffer
+pub fn export_buffer_for_cuda(buffer: &Buffer) -> Option<(CudaExternalMemory, CudaBufferDesc)> {
+    let memory = cuda_external_memory(buffer.export_memory()?)?;
+    let desc = CudaBufferDesc {
+        offset: 0,
+        size: buffer.size(),
+        flags: 0,
+    };
+    Some((memory, desc))
+}
+
+/// Exports the memory of `texture` for CUDA, along with how the texture is
+/// laid out in it.
+///
+/// `texture` has to be created with [`Device::create_exportable_texture`] with
+/// [`ExternalMemoryHandleType::OpaqueFd`] or [`ExternalMemoryHandleType::D3D12Resource`],
+/// or imported with one of those, and have a format [`cuda_channel_format`]
+/// supports. Returns `None` otherwise, or if the memory can't be exported.
+///
+/// Accesses from CUDA have to be ordered with those of wgpu through a
+/// semaphore from [`export_semaphore_for_cuda`].
+///
+/// [`Device::create_exportable_texture`]: crate::Device::create_exportable_texture
+pub fn export_texture_for_cuda(
+    texture: &Texture,
+) -> Option<(CudaExternalMemory, CudaMipmappedArrayDesc)> {
+    let format = cuda_channel_format(texture.format())?;
+    let size = texture.size();
+    let (extent, mut flags) = match texture.dimension() {
+        TextureDimension::D1 => ([size.width, 0, 0], 0),
+        TextureDimension::D2 if size.depth_or_array_layers == 1 => {
+            ([size.width, size.height, 0], 0)
+        }
+        TextureDimension::D2 => (
+            [size.width, size.height, size.depth_or_array_layers],
+            CUDA_ARRAY_LAYERED,
+        ),
+        TextureDimension::D3 => ([size.width, size.height, size.depth_or_array_layers], 0),
+    };
+    if texture.usage().contains(TextureUsages::STORAGE_BINDING) {
+        flags |= CUDA_ARRAY_SURFACE_LOAD_STORE;
+    }
+    if texture.usage().contains(TextureUsages::RENDER_ATTACHMENT) {
+        flags |= CUDA_ARRAY_COLOR_ATTACHMENT;
+    }
+
+    let memory = cuda_external_memory(texture.export_memory()?)?;
+    let desc = CudaMipmappedArrayDesc {
+        offset: 0,
+        format,
+        extent: extent.map(|extent| extent as usize),
+        flags,
+        num_levels: texture.mip_level_count(),
+    };
+    Some((memory, desc))
+}
+
+/// Exports `semaphore` for CUDA.
+///
+/// `semaphore` has to be created with [`Device::create_exportable_timeline_semaphore`]
+/// or imported with [`Device::import_timeline_semaphore`]. Returns `None`
+/// otherwise, or if the semaphore can't be exported.
+///
+/// [`Device::create_exportable_timeline_semaphore`]: crate::Device::create_exportable_timeline_semaphore
+/// [`Device::import_timeline_semaphore`]: crate::Device::import_timeline_semaphore
+pub fn export_semaphore_for_cuda(semaphore: &TimelineSemaphore) -> Option<CudaExternalSemaphore> {
+    let semaphore = semaphore.export()?;
+    let handle_type = match semaphore.handle_type {
+        ExternalSemaphoreHandleType::OpaqueFd => {
+            CudaExternalSemaphoreHandleType::TimelineSemaphoreFd
+        }
+        ExternalSemaphoreHandleType::D3D12Fence => CudaExternalSemaphoreHandleType::D3D12Fence,
+    };
+    Some(CudaExternalSemaphore {
+        semaphore,
+        handle_type,
+    })
+}
+
+/// Wraps memory for CUDA, which can't import dma-bufs.
+fn cuda_external_memory(memory: ExternalMemory) -> Option<CudaExternalMemory> {
+    let handle_type = match memory.handle_type {
+        ExternalMemoryHandleType::OpaqueFd => CudaExternalMemoryHandleType::OpaqueFd,
+        ExternalMemoryHandleType::D3D12Resource => CudaExternalMemoryHandleType::D3D12Resource,
+        ExternalMemoryHandleType::DmaBuf => return None,
+    };
+    Some(CudaExternalMemory {
+        handle_type,
+        size: memory.size,
+        flags: CUDA_EXTERNAL_MEMORY_DEDICATED,
+        memory,
+    })
+}
@@ -58,6 +58,31 @@ pub trait DeviceExt {
         order: TextureDataOrder,
         data: &[u8],
     ) -> crate::Texture;
+
+    /// Upload an entire texture and its mipmaps like [`Self::create_texture_with_data`],
+    /// decoding compressed data on the CPU if the device doesn't support its format.
+    ///
+    /// If the features required by `desc.format` aren't enabled, the texture is created
+    /// with the uncompressed format returned by [`transcoded_format`] instead, and `data`
+    /// is decoded with [`transcode_texture_data`]. Check [`Texture::format`] to find out
+    /// which format the texture ended up with.
+    ///
+    /// # Panics
+    ///
+    /// If the device doesn't support `desc.format` and [`transcoded_format`] returns `None`
+    /// for it.
+    ///
+    /// [`transcoded_format`]: super::transcoded_format
+    /// [`transcode_texture_data`]: super::transcode_texture_data
+    /// [`Texture::format`]: crate::Texture::format
+    #[cfg(feature = "texture-transcode")]
+    fn create_texture_with_data_or_transcode(
+        &self,
+        queue: &crate::Queue,
+        desc: &crate::TextureDescriptor<'_>,
+        order: TextureDataOrder,
+        data: &[u8],
+    ) -> crate::Texture;
 }
 
 impl DeviceExt for crate::Device {
@@ -186,4 +211,41 @@ impl DeviceExt for crate::Device {
 
         texture
     }
+
+    #[cfg(feature = "texture-transcode")]
+    fn create_texture_with_data_or_transcode(
+        &self,
+        queue: &crate::Queue,
+        desc: &crate::TextureDescriptor<'_>,
+        order: TextureDataOrder,
+        data: &[u8],
+    ) -> crate::Texture {
+        if self.features().contains(desc.format.required_features()) {
+            return self.create_texture_with_data(queue, desc, order, data);
+        }
+
+        let format = super::transcoded_format(desc.format).unwrap_or_else(|| {
+            panic!(
+                "Texture format {:?} isn't supported by the device, and can't be transcoded",
+                desc.format
+            )
+        });
+        log::debug!(
+            "Transcoding texture {:?} from {:?} to {format:?}",
+            desc.label,
+            desc.format
+        );
+        let data = super::transcode_texture_data(desc, order, data);
+        let view_formats = desc
+            .view_formats
+            .iter()
+            .map(|&view_format| super::transcoded_format(view_format).unwrap_or(view_format))
+            .collect::<Vec<_>>();
+        let desc = crate::TextureDescriptor {
+            format,
+            view_formats: &view_formats,
+            ..desc.clone()
+        };
+        self.create_texture_with_data(queue, &desc, order, &data)
+    }
 }
@@ -0,0 +1,139 @@
+use std::os::fd::OwnedFd;
+
+use crate::{ExternalMemoryHandleType, Texture, TextureDimension, TextureFormat};
+
+/// `DRM_FORMAT_MOD_INVALID`: no explicit modifier, the layout is negotiated
+/// implicitly by the drivers. Exported textures always have an explicit one.
+pub const DRM_FORMAT_MOD_INVALID: u64 = 0x00ff_ffff_ffff_ffff;
+/// `DRM_FORMAT_MOD_LINEAR`: rows of texels one after the other.
+pub const DRM_FORMAT_MOD_LINEAR: u64 = 0;
+
+/// Builds a DRM fourcc code, like `fourcc_code` of `drm_fourcc.h`.
+const fn fourcc(code: &[u8; 4]) -> u32 {
+    u32::from_le_bytes(*code)
+}
+
+/// A plane of a texture exported as a dma-buf, in the form
+/// `zwp_linux_buffer_params_v1.add` and the `spa_data` of a PipeWire buffer
+/// take it.
+#[derive(Debug)]
+pub struct DmaBufPlane {
+    /// A file descriptor of the dma-buf holding the plane. Every plane has its
+    /// own descriptor, even if they all refer to the same dma-buf, since
+    /// receivers take ownership of each one.
+    pub fd: OwnedFd,
+    /// Offset of the plane in the dma-buf, in bytes.
+    pub offset: u32,
+    /// Distance between the starts of two rows of the plane, in bytes.
+    pub stride: u32,
+}
+
+/// A texture exported as a dma-buf, with what Wayland compositors and
+/// PipeWire need to import it without copying: the DRM fourcc format, the
+/// DRM format modifier, and the file descriptor, offset and stride of every
+/// plane.
+///
+/// Only the first mip level of the texture is described.
+#[derive(Debug)]
+pub struct DmaBufTexture {
+    /// Width of the texture, in texels.
+    pub width: u32,
+    /// Height of the texture, in texels.
+    pub height: u32,
+    /// The DRM fourcc code of the format of the texture, see [`drm_fourcc`].
+    pub fourcc: u32,
+    /// The DRM format modifier the texture is laid out with.
+    pub modifier: u64,
+    /// The planes of the modifier, in order.
+    pub planes: Vec<DmaBufPlane>,
+}
+
+impl DmaBufTexture {
+    /// The upper 32 bits of the modifier, `modifier_hi` of
+    /// `zwp_linux_buffer_params_v1.add`.
+    pub fn modifier_hi(&self) -> u32 {
+        (self.modifier >> 32) as u32
+    }
+
+    /// The lower 32 bits of the modifier, `modifier_lo` of
+    /// `zwp_linux_buffer_params_v1.add`.
+    pub fn modifier_lo(&self) -> u32 {
+        self.modifier as u32
+    }
+}
+
+/// Returns the DRM fourcc code with the same layout of texels in memory as
+/// `format`, or `None` if there is none.
+///
+/// DRM fourcc codes name the channels from the most to the least significant
+/// bits of a little-endian texel, so [`TextureFormat::Rgba8Unorm`] is
+/// `DRM_FORMAT_ABGR8888`. DRM formats don't distinguish sRGB encodings, which
+/// map to the code of their linear format. Formats with alpha map to codes with
+/// alpha; receivers ignoring it may expect the matching `X` code instead, like
+/// `DRM_FORMAT_XBGR8888`.
+pub fn drm_fourcc(format: TextureFormat) -> Option<u32> {
+    Some(match format {
+        TextureFormat::R8Unorm => fourcc(b"R8  "),
+        TextureFormat::Rg8Unorm => fourcc(b"GR88"),
+        TextureFormat::R16Unorm => fourcc(b"R16 "),
+        TextureFormat::Rg16Unorm => fourcc(b"GR32"),
+        TextureFormat::Rgba8Unorm | TextureFormat::Rgba8UnormSrgb => fourcc(b"AB24"),
+        TextureFormat::Bgra8Unorm | TextureFormat::Bgra8UnormSrgb => fourcc(b"AR24"),
+        TextureFormat::Rgb10a2Unorm => fourcc(b"AB30"),
+        TextureFormat::Rgba16Unorm => fourcc(b"AB48"),
+        TextureFormat::Rgba16Float => fourcc(b"AB4H"),
+        TextureFormat::NV12 => fourcc(b"NV12"),
+        _ => return None,
+    })
+}
+
+/// Exports `texture` as a dma-buf, for a Wayland compositor or PipeWire.
+///
+/// `texture` has to be a 2D texture with a single layer, created with
+/// [`Device::create_exportable_texture`] and
+/// [`ExternalMemoryHandleType::DmaBuf`], of a format [`drm_fourcc`] knows.
+/// Returns `None` otherwise, or if the memory can't be exported.
+///
+/// The texture has to be done being written by the GPU before the other side
+/// reads it, e.g. by waiting for the submission with
+/// [`Device::wait_for_submission`] or by exporting a [`TimelineSemaphore`]
+/// the submission signals.
+///
+/// [`Device::create_exportable_texture`]: crate::Device::create_exportable_texture
+/// [`Device::wait_for_submission`]: crate::Device::wait_for_submission
+/// [`TimelineSemaphore`]: crate::TimelineSemaphore
+pub fn export_texture_as_dmabuf(texture: &Texture) -> Option<DmaBufTexture> {
+    if texture.dimension() != TextureDimension::D2 || texture.depth_or_array_layers() != 1 {
+        return None;
+    }
+    let fourcc = drm_fourcc(texture.format())?;
+
+    let memory = texture.export_memory()?;
+    if memory.handle_type != ExternalMemoryHandleType::DmaBuf {
+        return None;
+    }
+    let modifier = memory.drm_format_modifier?;
+
+    let planes = memory
+        .planes
+        .iter()
+        .map(|layout| {
+            Some(DmaBufPlane {
+                fd: memory.fd.try_clone().ok()?,
+                offset: u32::try_from(layout.offset).ok()?,
+                stride: u32::try_from(layout.row_pitch).ok()?,
+            })
+        })
+        .collect::<Option<Vec<_>>>()?;
+    if planes.is_empty() {
+        return None;
+    }
+
+    Some(DmaBufTexture {
+        width: texture.width(),
+        height: texture.height(),
+        fourcc,
+        modifier,
+        planes,
+    })
+}
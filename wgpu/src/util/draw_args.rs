@@ -0,0 +1,210 @@
+use super::{BufferInitDescriptor, DeviceExt};
+use crate::{
+    BindGroupDescriptor, BindGroupEntry, BindGroupLayout, BindGroupLayoutDescriptor,
+    BindGroupLayoutEntry, BindingResource, BindingType, BufferAddress, BufferBinding,
+    BufferBindingType, BufferSlice, BufferUsages, CommandEncoder, ComputePassDescriptor,
+    ComputePipeline, ComputePipelineDescriptor, Device, Features, PipelineLayoutDescriptor,
+    ShaderModuleDescriptor, ShaderSource, ShaderStages,
+};
+
+use wgt::{DrawIndexedIndirectArgs, DrawIndirectArgs};
+
+const WORKGROUP_SIZE: u32 = 64;
+
+/// How draws written by the CPU or a culling shader are laid out in a buffer.
+///
+/// This is the canonical format [`DrawArgsConverter`] reads: every `stride`
+/// bytes, a [`DrawIndirectArgs`] or, if `indexed`, a [`DrawIndexedIndirectArgs`],
+/// followed by whatever else the writer stores per draw.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct DrawArgsLayout {
+    /// Whether the draws are [`DrawIndexedIndirectArgs`].
+    pub indexed: bool,
+    /// The distance between draws in bytes, a multiple of 4 at least as large
+    /// as the arguments.
+    pub stride: BufferAddress,
+}
+
+impl DrawArgsLayout {
+    /// Tightly packed [`DrawIndirectArgs`].
+    pub const DRAW: Self = Self {
+        indexed: false,
+        stride: std::mem::size_of::<DrawIndirectArgs>() as BufferAddress,
+    };
+
+    /// Tightly packed [`DrawIndexedIndirectArgs`].
+    pub const DRAW_INDEXED: Self = Self {
+        indexed: true,
+        stride: std::mem::size_of::<DrawIndexedIndirectArgs>() as BufferAddress,
+    };
+
+    fn args_size(&self) -> BufferAddress {
+        if self.indexed {
+            Self::DRAW_INDEXED.stride
+        } else {
+            Self::DRAW.stride
+        }
+    }
+}
+
+/// Converts draws from the canonical [`DrawArgsLayout`] into the layout the
+/// indirect draws of a device read, with a compute pass.
+///
+/// Draws come out tightly packed for [`RenderPass::multi_draw_indirect`] and
+/// [`RenderPass::multi_draw_indexed_indirect`]. On devices without
+/// [`Features::INDIRECT_FIRST_INSTANCE`], whose backends can't start draws at
+/// an instance other than 0, such draws are skipped like WebGPU does, instead
+/// of drawing different instances on different backends.
+///
+/// [`RenderPass::multi_draw_indirect`]: crate::RenderPass::multi_draw_indirect
+/// [`RenderPass::multi_draw_indexed_indirect`]: crate::RenderPass::multi_draw_indexed_indirect
+pub struct DrawArgsConverter {
+    first_instance: bool,
+    bind_group_layout: BindGroupLayout,
+    pipeline: ComputePipeline,
+}
+
+impl DrawArgsConverter {
+    /// Creates a converter for draws on `device`.
+    pub fn new(device: &Device) -> Self {
+        let module = device.create_shader_module(ShaderModuleDescriptor {
+            label: Some("wgpu::util::DrawArgsConverter"),
+            source: ShaderSource::Wgsl(include_str!("draw_args.wgsl").into()),
+            diagnostic_filters: &[],
+        });
+        let buffer_entry = |binding, ty| BindGroupLayoutEntry {
+            binding,
+            visibility: ShaderStages::COMPUTE,
+            ty: BindingType::Buffer {
+                ty,
+                has_dynamic_offset: false,
+                min_binding_size: None,
+            },
+            count: None,
+        };
+        let bind_group_layout = device.create_bind_group_layout(&BindGroupLayoutDescriptor {
+            label: Some("wgpu::util::DrawArgsConverter"),
+            entries: &[
+                buffer_entry(0, BufferBindingType::Uniform),
+                buffer_entry(1, BufferBindingType::Storage { read_only: true }),
+                buffer_entry(2, BufferBindingType::Storage { read_only: false }),
+            ],
+        });
+        let layout = device.create_pipeline_layout(&PipelineLayoutDescriptor {
+            label: Some("wgpu::util::DrawArgsConverter"),
+            bind_group_layouts: &[&bind_group_layout],
+            push_constant_ranges: &[],
+        });
+        let pipeline = device.create_compute_pipeline(&ComputePipelineDescriptor {
+            label: Some("wgpu::util::DrawArgsConverter"),
+            layout: Some(&layout),
+            module: &module,
+            entry_point: "main",
+            compilation_options: Default::default(),
+        });
+
+        Self {
+            first_instance: device
+                .features()
+                .contains(Features::INDIRECT_FIRST_INSTANCE),
+            bind_group_layout,
+            pipeline,
+        }
+    }
+
+    /// Records converting the draws in `source`, laid out as `layout`, into
+    /// `target`, and returns how many draws there are.
+    ///
+    /// # Panics
+    ///
+    /// - `source` doesn't have [`BufferUsages::STORAGE`], or `target` doesn't
+    ///   have [`BufferUsages::STORAGE`] and [`BufferUsages::INDIRECT`].
+    /// - The offsets of the slices or `layout.stride` aren't multiples of 4, or
+    ///   `layout.stride` is smaller than the arguments.
+    /// - `target` is too small for the draws in `source`.
+    pub fn convert(
+        &self,
+        device: &Device,
+        encoder: &mut CommandEncoder,
+        source: BufferSlice<'_>,
+        layout: DrawArgsLayout,
+        target: BufferSlice<'_>,
+    ) -> u32 {
+        assert!(source.buffer.usage().contains(BufferUsages::STORAGE));
+        assert!(target
+            .buffer
+            .usage()
+            .contains(BufferUsages::STORAGE | BufferUsages::INDIRECT));
+        assert_eq!(source.offset % 4, 0);
+        assert_eq!(target.offset % 4, 0);
+        assert_eq!(layout.stride % 4, 0);
+        assert!(layout.stride >= layout.args_size());
+
+        let source_size = source
+            .size
+            .map_or(source.buffer.size() - source.offset, |size| size.get());
+        let target_size = target
+            .size
+            .map_or(target.buffer.size() - target.offset, |size| size.get());
+        // The last draw doesn't need the padding after its arguments.
+        let draw_count = (source_size + layout.stride - layout.args_size()) / layout.stride;
+        assert!(draw_count * layout.args_size() <= target_size);
+        if draw_count == 0 {
+            return 0;
+        }
+
+        let params = [
+            draw_count as u32,
+            (layout.stride / 4) as u32,
+            (layout.args_size() / 4) as u32,
+            (source.offset / 4) as u32,
+            (target.offset / 4) as u32,
+            self.first_instance as u32,
+            0,
+            0,
+        ];
+        let params: Vec<u8> = params.iter().flat_map(|p| p.to_ne_bytes()).collect();
+        let buffer = device.create_buffer_init(&BufferInitDescriptor {
+            label: Some("wgpu::util::DrawArgsConverter"),
+            contents: &params,
+            usage: BufferUsages::UNIFORM,
+        });
+
+        // Storage bindings have to be aligned more than draws, the offsets
+        // are applied in the shader instead.
+        let whole = |slice: &BufferSlice<'_>| {
+            BindingResource::Buffer(BufferBinding {
+                buffer: slice.buffer,
+                offset: 0,
+                size: None,
+            })
+        };
+        let bind_group = device.create_bind_group(&BindGroupDescriptor {
+            label: Some("wgpu::util::DrawArgsConverter"),
+            layout: &self.bind_group_layout,
+            entries: &[
+                BindGroupEntry {
+                    binding: 0,
+                    resource: buffer.as_entire_binding(),
+                },
+                BindGroupEntry {
+                    binding: 1,
+                    resource: whole(&source),
+                },
+                BindGroupEntry {
+                    binding: 2,
+                    resource: whole(&target),
+                },
+            ],
+        });
+
+        let mut pass = encoder.begin_compute_pass(&ComputePassDescriptor {
+            label: Some("wgpu::util::DrawArgsConverter"),
+            timestamp_writes: None,
+        });
+        pass.set_pipeline(&self.pipeline);
+        pass.set_bind_group(0, &bind_group, &[]);
+        pass.dispatch_workgroups((draw_count as u32).div_ceil(WORKGROUP_SIZE), 1, 1);
+        draw_count as u32
+    }
+}
@@ -0,0 +1,37 @@
+struct Params {
+    draw_count: u32,
+    // In words, the distance between draws in the source and the target.
+    source_stride: u32,
+    target_stride: u32,
+    // In words, where the draws start in the source and the target.
+    source_offset: u32,
+    target_offset: u32,
+    // Whether draws may start at instances other than 0.
+    first_instance: u32,
+}
+
+@group(0) @binding(0) var<uniform> params: Params;
+@group(0) @binding(1) var<storage, read> source_args: array<u32>;
+@group(0) @binding(2) var<storage, read_write> target_args: array<u32>;
+
+@compute @workgroup_size(64)
+fn main(@builtin(global_invocation_id) id: vec3<u32>) {
+    let draw = id.x;
+    if draw >= params.draw_count {
+        return;
+    }
+
+    let source_base = params.source_offset + draw * params.source_stride;
+    let target_base = params.target_offset + draw * params.target_stride;
+    for (var i = 0u; i < params.target_stride; i++) {
+        target_args[target_base + i] = source_args[source_base + i];
+    }
+
+    // The first instance is the last argument of both kinds of draws. Draws
+    // that need one are skipped where it can't be drawn, as WebGPU does.
+    let last = params.target_stride - 1u;
+    if params.first_instance == 0u && source_args[source_base + last] != 0u {
+        target_args[target_base + 1u] = 0u;
+        target_args[target_base + last] = 0u;
+    }
+}
@@ -0,0 +1,260 @@
+use super::{color_space::is_srgb_encoded, BufferInitDescriptor, DeviceExt};
+use crate::{
+    BindGroupDescriptor, BindGroupEntry, BindGroupLayout, BindGroupLayoutDescriptor,
+    BindGroupLayoutEntry, BindingResource, BindingType, BufferBindingType, BufferUsages,
+    ColorSpace, CommandEncoderDescriptor, Device, Extent3d, FragmentState, ImageCopyTexture,
+    ImageCopyTextureTagged, ImageDataLayout, LoadOp, MultisampleState, Operations, Origin2d,
+    Origin3d, PipelineLayoutDescriptor, PredefinedColorSpace, PrimitiveState, Queue,
+    RenderPassColorAttachment, RenderPassDescriptor, RenderPipeline, RenderPipelineDescriptor,
+    ShaderModuleDescriptor, ShaderSource, ShaderStages, StoreOp, TextureAspect, TextureDescriptor,
+    TextureDimension, TextureFormat, TextureSampleType, TextureUsages, TextureViewDescriptor,
+    TextureViewDimension, VertexState,
+};
+
+/// Texels in CPU memory to copy into a texture with an [`ExternalImageCopier`].
+///
+/// The native counterpart of the `wgt::ImageCopyExternalImage` taken by
+/// `Queue::copy_external_image_to_texture` on the web, for images decoded by
+/// the application instead of the browser.
+#[derive(Clone, Copy, Debug)]
+pub struct ImageCopyPixels<'a> {
+    /// Rows of [`TextureFormat::Rgba8Unorm`] texels from top to bottom,
+    /// without padding between them.
+    pub data: &'a [u8],
+    /// The width of the image in texels.
+    pub width: u32,
+    /// The height of the image in texels.
+    pub height: u32,
+    /// The color space of the texels, which are sRGB encoded.
+    pub color_space: PredefinedColorSpace,
+    /// Whether the color channels are premultiplied by alpha.
+    pub premultiplied_alpha: bool,
+    /// The top-left texel of the region to copy.
+    pub origin: Origin2d,
+    /// Whether the region is flipped vertically, so that its bottom row is
+    /// copied into the first row of the destination.
+    pub flip_y: bool,
+}
+
+/// Copies images in CPU memory into textures, converting them on the GPU.
+///
+/// Like `Queue::copy_external_image_to_texture`, the texels are converted
+/// from the color space and alpha premultiplication of the source to those
+/// of the destination, and can be flipped vertically on the way. Applications
+/// can share an upload path between native and the web by decoding images with
+/// the browser there and with this copier everywhere else.
+///
+/// Values of `*Srgb` and other normalized formats are treated as sRGB encoded,
+/// floating point values as linear, see [`ColorSpaceConverter`](super::ColorSpaceConverter).
+pub struct ExternalImageCopier {
+    format: TextureFormat,
+    bind_group_layout: BindGroupLayout,
+    pipeline: RenderPipeline,
+}
+
+impl ExternalImageCopier {
+    /// Creates a copier into textures of `format`.
+    pub fn new(device: &Device, format: TextureFormat) -> Self {
+        let module = device.create_shader_module(ShaderModuleDescriptor {
+            label: Some("wgpu::util::ExternalImageCopier"),
+            source: ShaderSource::Wgsl(include_str!("external_image.wgsl").into()),
+            diagnostic_filters: &[],
+        });
+        let bind_group_layout = device.create_bind_group_layout(&BindGroupLayoutDescriptor {
+            label: Some("wgpu::util::ExternalImageCopier"),
+            entries: &[
+                BindGroupLayoutEntry {
+                    binding: 0,
+                    visibility: ShaderStages::FRAGMENT,
+                    ty: BindingType::Texture {
+                        sample_type: TextureSampleType::Float { filterable: false },
+                        view_dimension: TextureViewDimension::D2,
+                        multisampled: false,
+                    },
+                    count: None,
+                },
+                BindGroupLayoutEntry {
+                    binding: 1,
+                    visibility: ShaderStages::FRAGMENT,
+                    ty: BindingType::Buffer {
+                        ty: BufferBindingType::Uniform,
+                        has_dynamic_offset: false,
+                        min_binding_size: None,
+                    },
+                    count: None,
+                },
+            ],
+        });
+        let layout = device.create_pipeline_layout(&PipelineLayoutDescriptor {
+            label: Some("wgpu::util::ExternalImageCopier"),
+            bind_group_layouts: &[&bind_group_layout],
+            push_constant_ranges: &[],
+        });
+        let pipeline = device.create_render_pipeline(&RenderPipelineDescriptor {
+            label: Some("wgpu::util::ExternalImageCopier"),
+            layout: Some(&layout),
+            vertex: VertexState {
+                module: &module,
+                entry_point: "vs_main",
+                compilation_options: Default::default(),
+                buffers: &[],
+            },
+            primitive: PrimitiveState::default(),
+            depth_stencil: None,
+            multisample: MultisampleState::default(),
+            fragment: Some(FragmentState {
+                module: &module,
+                entry_point: "fs_main",
+                compilation_options: Default::default(),
+                targets: &[Some(format.into())],
+            }),
+            multiview: None,
+        });
+
+        Self {
+            format,
+            bind_group_layout,
+            pipeline,
+        }
+    }
+
+    /// Schedules a copy of the region of `source` at its origin into `dest`,
+    /// submitting it to `queue`.
+    ///
+    /// The region is uploaded into a temporary texture and drawn into `dest`,
+    /// so texels of `dest` outside of the copy are left as they are.
+    ///
+    /// # Panics
+    ///
+    /// - `size.depth_or_array_layers` isn't 1.
+    /// - `source.data` doesn't hold `source.width` by `source.height` texels, or
+    ///   the region doesn't fit into them.
+    /// - `dest.texture` doesn't have [`TextureUsages::RENDER_ATTACHMENT`] or the
+    ///   format this copier was created for.
+    pub fn copy_external_image_to_texture(
+        &self,
+        device: &Device,
+        queue: &Queue,
+        source: &ImageCopyPixels<'_>,
+        dest: ImageCopyTextureTagged<'_>,
+        size: Extent3d,
+    ) {
+        assert_eq!(size.depth_or_array_layers, 1);
+        assert_eq!(
+            source.data.len() as u64,
+            source.width as u64 * source.height as u64 * 4
+        );
+        assert!(source.origin.x as u64 + size.width as u64 <= source.width as u64);
+        assert!(source.origin.y as u64 + size.height as u64 <= source.height as u64);
+        assert!(dest
+            .texture
+            .usage()
+            .contains(TextureUsages::RENDER_ATTACHMENT));
+        assert_eq!(dest.texture.format(), self.format);
+        if size.width == 0 || size.height == 0 {
+            return;
+        }
+
+        let staging = device.create_texture(&TextureDescriptor {
+            label: Some("wgpu::util::ExternalImageCopier"),
+            size,
+            mip_level_count: 1,
+            sample_count: 1,
+            dimension: TextureDimension::D2,
+            format: TextureFormat::Rgba8Unorm,
+            usage: TextureUsages::TEXTURE_BINDING | TextureUsages::COPY_DST,
+            view_formats: &[],
+        });
+        let bytes_per_row = source.width * 4;
+        let offset =
+            source.origin.y as usize * bytes_per_row as usize + source.origin.x as usize * 4;
+        queue.write_texture(
+            ImageCopyTexture {
+                texture: &staging,
+                mip_level: 0,
+                origin: Origin3d::ZERO,
+                aspect: TextureAspect::All,
+            },
+            &source.data[offset..],
+            ImageDataLayout {
+                offset: 0,
+                bytes_per_row: Some(bytes_per_row),
+                rows_per_image: None,
+            },
+            size,
+        );
+
+        let matrix =
+            ColorSpace::from(source.color_space).conversion_matrix(dest.color_space.into());
+        // `mat3x3<f32>` columns are padded to 16 bytes, and the struct to 80 bytes.
+        let mut params = [0u32; 20];
+        for (column, values) in params.chunks_mut(4).zip(matrix) {
+            for (param, value) in column.iter_mut().zip(values) {
+                *param = value.to_bits();
+            }
+        }
+        params[12] = dest.origin.x;
+        params[13] = dest.origin.y;
+        params[14] = size.height;
+        params[15] = source.flip_y as u32;
+        params[16] = source.premultiplied_alpha as u32;
+        params[17] = dest.premultiplied_alpha as u32;
+        params[18] = is_srgb_encoded(self.format) as u32;
+        let params: Vec<u8> = params.iter().flat_map(|p| p.to_ne_bytes()).collect();
+        let buffer = device.create_buffer_init(&BufferInitDescriptor {
+            label: Some("wgpu::util::ExternalImageCopier"),
+            contents: &params,
+            usage: BufferUsages::UNIFORM,
+        });
+
+        let staging_view = staging.create_view(&TextureViewDescriptor::default());
+        let dest_view = dest.texture.create_view(&TextureViewDescriptor {
+            dimension: Some(TextureViewDimension::D2),
+            aspect: dest.aspect,
+            base_mip_level: dest.mip_level,
+            mip_level_count: Some(1),
+            base_array_layer: dest.origin.z,
+            array_layer_count: Some(1),
+            ..Default::default()
+        });
+        let bind_group = device.create_bind_group(&BindGroupDescriptor {
+            label: Some("wgpu::util::ExternalImageCopier"),
+            layout: &self.bind_group_layout,
+            entries: &[
+                BindGroupEntry {
+                    binding: 0,
+                    resource: BindingResource::TextureView(&staging_view),
+                },
+                BindGroupEntry {
+                    binding: 1,
+                    resource: buffer.as_entire_binding(),
+                },
+            ],
+        });
+
+        let mut encoder = device.create_command_encoder(&CommandEncoderDescriptor {
+            label: Some("wgpu::util::ExternalImageCopier"),
+        });
+        {
+            let mut pass = encoder.begin_render_pass(&RenderPassDescriptor {
+                label: Some("wgpu::util::ExternalImageCopier"),
+                color_attachments: &[Some(RenderPassColorAttachment {
+                    view: &dest_view,
+                    resolve_target: None,
+                    ops: Operations {
+                        load: LoadOp::Load,
+                        store: StoreOp::Store,
+                    },
+                })],
+                depth_stencil_attachment: None,
+                timestamp_writes: None,
+                occlusion_query_set: None,
+            });
+            pass.set_pipeline(&self.pipeline);
+            pass.set_bind_group(0, &bind_group, &[]);
+            pass.set_scissor_rect(dest.origin.x, dest.origin.y, size.width, size.height);
+            pass.draw(0..3, 0..1);
+        }
+        queue.submit(Some(encoder.finish()));
+    }
+}
@@ -0,0 +1,51 @@
+struct Params {
+    matrix: mat3x3<f32>,
+    // Top-left texel of the copy in the destination.
+    origin: vec2<u32>,
+    height: u32,
+    flip_y: u32,
+    unpremultiply_source: u32,
+    premultiply_target: u32,
+    encode_target: u32,
+}
+
+@group(0) @binding(0)
+var source: texture_2d<f32>;
+@group(0) @binding(1)
+var<uniform> params: Params;
+
+@vertex
+fn vs_main(@builtin(vertex_index) index: u32) -> @builtin(position) vec4<f32> {
+    // A triangle covering the whole target, the scissor rect limits it to the copy.
+    let uv = vec2<f32>(f32((index << 1u) & 2u), f32(index & 2u));
+    return vec4<f32>(uv * 2.0 - 1.0, 0.0, 1.0);
+}
+
+fn srgb_to_linear(c: vec3<f32>) -> vec3<f32> {
+    return select(pow((c + 0.055) / 1.055, vec3<f32>(2.4)), c / 12.92, c <= vec3<f32>(0.04045));
+}
+
+fn linear_to_srgb(c: vec3<f32>) -> vec3<f32> {
+    return select(1.055 * pow(c, vec3<f32>(1.0 / 2.4)) - 0.055, c * 12.92, c <= vec3<f32>(0.0031308));
+}
+
+@fragment
+fn fs_main(@builtin(position) position: vec4<f32>) -> @location(0) vec4<f32> {
+    var coords = vec2<u32>(position.xy) - params.origin;
+    if params.flip_y != 0u {
+        coords.y = params.height - 1u - coords.y;
+    }
+    let texel = textureLoad(source, coords, 0);
+    var color = texel.rgb;
+    if params.unpremultiply_source != 0u && texel.a > 0.0 {
+        color /= texel.a;
+    }
+    color = params.matrix * srgb_to_linear(color);
+    if params.encode_target != 0u {
+        color = linear_to_srgb(clamp(color, vec3<f32>(0.0), vec3<f32>(1.0)));
+    }
+    if params.premultiply_target != 0u {
+        color *= texel.a;
+    }
+    return vec4<f32>(color, texel.a);
+}
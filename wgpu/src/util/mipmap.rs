@@ -0,0 +1,155 @@
+use crate::{
+    BindGroupDescriptor, BindGroupEntry, BindGroupLayout, BindGroupLayoutDescriptor,
+    BindGroupLayoutEntry, BindingResource, BindingType, Color, CommandEncoder, Device,
+    FragmentState, LoadOp, MultisampleState, Operations, PipelineLayoutDescriptor, PrimitiveState,
+    RenderPassColorAttachment, RenderPassDescriptor, RenderPipeline, RenderPipelineDescriptor,
+    ShaderModuleDescriptor, ShaderSource, ShaderStages, StoreOp, Texture, TextureDimension,
+    TextureFormat, TextureSampleType, TextureUsages, TextureViewDescriptor, TextureViewDimension,
+    VertexState,
+};
+
+/// Generates the mip levels of textures from their first level with render
+/// passes.
+///
+/// Every texel is the average of the texels of the level above that it covers,
+/// weighted by how much of them it covers. That way, levels of textures whose
+/// size isn't a power of two are neither shifted nor skip texels. Values of
+/// `*Srgb` formats are averaged in linear space. Each array layer, including
+/// each face of a cube map, gets a mip chain of its own.
+pub struct MipmapGenerator {
+    format: TextureFormat,
+    bind_group_layout: BindGroupLayout,
+    pipeline: RenderPipeline,
+}
+
+impl MipmapGenerator {
+    /// Creates a generator for textures of `format`, which has to be a
+    /// renderable format with a floating point sample type.
+    pub fn new(device: &Device, format: TextureFormat) -> Self {
+        let module = device.create_shader_module(ShaderModuleDescriptor {
+            label: Some("wgpu::util::MipmapGenerator"),
+            source: ShaderSource::Wgsl(include_str!("mipmap.wgsl").into()),
+            diagnostic_filters: &[],
+        });
+        let bind_group_layout = device.create_bind_group_layout(&BindGroupLayoutDescriptor {
+            label: Some("wgpu::util::MipmapGenerator"),
+            entries: &[BindGroupLayoutEntry {
+                binding: 0,
+                visibility: ShaderStages::FRAGMENT,
+                ty: BindingType::Texture {
+                    sample_type: TextureSampleType::Float { filterable: false },
+                    view_dimension: TextureViewDimension::D2,
+                    multisampled: false,
+                },
+                count: None,
+            }],
+        });
+        let layout = device.create_pipeline_layout(&PipelineLayoutDescriptor {
+            label: Some("wgpu::util::MipmapGenerator"),
+            bind_group_layouts: &[&bind_group_layout],
+            push_constant_ranges: &[],
+        });
+        let pipeline = device.create_render_pipeline(&RenderPipelineDescriptor {
+            label: Some("wgpu::util::MipmapGenerator"),
+            layout: Some(&layout),
+            vertex: VertexState {
+                module: &module,
+                entry_point: "vs_main",
+                compilation_options: Default::default(),
+                buffers: &[],
+            },
+            primitive: PrimitiveState::default(),
+            depth_stencil: None,
+            multisample: MultisampleState::default(),
+            fragment: Some(FragmentState {
+                module: &module,
+                entry_point: "fs_main",
+                compilation_options: Default::default(),
+                targets: &[Some(format.into())],
+            }),
+            multiview: None,
+        });
+
+        Self {
+            format,
+            bind_group_layout,
+            pipeline,
+        }
+    }
+
+    /// Records generating every mip level of every array layer of `texture`
+    /// after the first level, from the first level.
+    ///
+    /// # Panics
+    ///
+    /// - `texture` doesn't have [`TextureUsages::TEXTURE_BINDING`] and
+    ///   [`TextureUsages::RENDER_ATTACHMENT`], or the format this generator
+    ///   was created for.
+    /// - `texture` isn't a 2D texture, or is multisampled.
+    pub fn generate(&self, device: &Device, encoder: &mut CommandEncoder, texture: &Texture) {
+        assert!(texture
+            .usage()
+            .contains(TextureUsages::TEXTURE_BINDING | TextureUsages::RENDER_ATTACHMENT));
+        assert_eq!(texture.format(), self.format);
+        assert_eq!(texture.dimension(), TextureDimension::D2);
+        assert_eq!(texture.sample_count(), 1);
+
+        for layer in 0..texture.depth_or_array_layers() {
+            let view = |mip_level| {
+                texture.create_view(&TextureViewDescriptor {
+                    label: Some("wgpu::util::MipmapGenerator"),
+                    dimension: Some(TextureViewDimension::D2),
+                    base_mip_level: mip_level,
+                    mip_level_count: Some(1),
+                    base_array_layer: layer,
+                    array_layer_count: Some(1),
+                    ..Default::default()
+                })
+            };
+
+            let mut source_view = view(0);
+            for mip_level in 1..texture.mip_level_count() {
+                let target_view = view(mip_level);
+                let bind_group = device.create_bind_group(&BindGroupDescriptor {
+                    label: Some("wgpu::util::MipmapGenerator"),
+                    layout: &self.bind_group_layout,
+                    entries: &[BindGroupEntry {
+                        binding: 0,
+                        resource: BindingResource::TextureView(&source_view),
+                    }],
+                });
+
+                let mut pass = encoder.begin_render_pass(&RenderPassDescriptor {
+                    label: Some("wgpu::util::MipmapGenerator"),
+                    color_attachments: &[Some(RenderPassColorAttachment {
+                        view: &target_view,
+                        resolve_target: None,
+                        // Every texel is drawn, so there's nothing to load.
+                        ops: Operations {
+                            load: LoadOp::Clear(Color::TRANSPARENT),
+                            store: StoreOp::Store,
+                        },
+                    })],
+                    depth_stencil_attachment: None,
+                    timestamp_writes: None,
+                    occlusion_query_set: None,
+                });
+                pass.set_pipeline(&self.pipeline);
+                pass.set_bind_group(0, &bind_group, &[]);
+                pass.draw(0..3, 0..1);
+                drop(pass);
+
+                source_view = target_view;
+            }
+        }
+    }
+}
+
+/// Records generating the mip levels of `texture` from its first level, as
+/// [`MipmapGenerator::generate`] does.
+///
+/// This creates a [`MipmapGenerator`] on every call, so keep one around
+/// instead to generate the mip levels of many textures.
+pub fn generate_mipmaps(device: &Device, encoder: &mut CommandEncoder, texture: &Texture) {
+    MipmapGenerator::new(device, texture.format()).generate(device, encoder, texture);
+}
@@ -0,0 +1,53 @@
+@group(0) @binding(0)
+var source: texture_2d<f32>;
+
+@vertex
+fn vs_main(@builtin(vertex_index) index: u32) -> @builtin(position) vec4<f32> {
+    // A triangle covering the whole target.
+    let uv = vec2<f32>(f32((index << 1u) & 2u), f32(index & 2u));
+    return vec4<f32>(uv * 2.0 - 1.0, 0.0, 1.0);
+}
+
+// The weight of the `i`th of the source texels starting at `2 * x` that cover
+// texel `x` of the target, along an axis of `source_size` texels.
+fn weight(i: u32, x: u32, source_size: u32) -> f32 {
+    if source_size == 1u {
+        return select(0.0, 1.0, i == 0u);
+    }
+    if source_size % 2u == 0u {
+        return select(0.0, 0.5, i < 2u);
+    }
+    // Each target texel covers 2 + 1 / m source texels, where m is the target size.
+    let m = f32(source_size / 2u);
+    let n = f32(source_size);
+    if i == 0u {
+        return (m - f32(x)) / n;
+    }
+    if i == 1u {
+        return m / n;
+    }
+    return (f32(x) + 1.0) / n;
+}
+
+@fragment
+fn fs_main(@builtin(position) position: vec4<f32>) -> @location(0) vec4<f32> {
+    let size = textureDimensions(source);
+    let texel_position = vec2<u32>(position.xy);
+    let base = select(texel_position * 2u, texel_position, size == vec2<u32>(1u));
+    var color = vec4<f32>(0.0);
+    for (var y = 0u; y < 3u; y++) {
+        let weight_y = weight(y, texel_position.y, size.y);
+        if weight_y == 0.0 {
+            continue;
+        }
+        for (var x = 0u; x < 3u; x++) {
+            let weight_x = weight(x, texel_position.x, size.x);
+            if weight_x == 0.0 {
+                continue;
+            }
+            let texel = textureLoad(source, base + vec2<u32>(x, y), 0);
+            color += texel * weight_x * weight_y;
+        }
+    }
+    return color;
+}
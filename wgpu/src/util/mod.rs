@@ -3,10 +3,40 @@
 //! Nothing in this module is a part of the WebGPU API specification;
 //! they are unique to the `wgpu` library.
 
+mod arena;
+mod batch;
 mod belt;
+#[cfg(feature = "wgsl")]
+mod color_space;
+mod constant_table;
+#[cfg(all(feature = "cuda-interop", external_memory))]
+mod cuda;
 mod device;
+#[cfg(all(unix, external_memory))]
+mod dmabuf;
+#[cfg(feature = "wgsl")]
+mod draw_args;
 mod encoder;
+#[cfg(feature = "wgsl")]
+mod external_image;
 mod init;
+#[cfg(feature = "wgsl")]
+mod mipmap;
+#[cfg(native)]
+mod multi_device;
+#[cfg(feature = "debug-overlay")]
+mod overlay;
+mod pass_graph;
+mod peer_copy;
+mod readback;
+mod registry;
+#[cfg(feature = "sprite")]
+mod sprite;
+#[cfg(native)]
+mod throttle;
+mod timestamp;
+#[cfg(feature = "texture-transcode")]
+mod transcode;
 
 use std::sync::Arc;
 use std::{
@@ -15,10 +45,58 @@ use std::{
     ptr::copy_nonoverlapping,
 };
 
+pub use arena::{BufferAllocation, BufferArena};
+pub use batch::{BatchedDraw, DrawBatcher};
 pub use belt::StagingBelt;
+#[cfg(feature = "wgsl")]
+pub use color_space::ColorSpaceConverter;
+pub use constant_table::{ConstantTableElement, ConstantTables, ConstantTablesBuilder};
+#[cfg(all(feature = "cuda-interop", external_memory))]
+pub use cuda::{
+    cuda_channel_format, export_buffer_for_cuda, export_semaphore_for_cuda,
+    export_texture_for_cuda, CudaBufferDesc, CudaChannelFormatDesc, CudaChannelFormatKind,
+    CudaExternalMemory, CudaExternalMemoryHandleType, CudaExternalSemaphore,
+    CudaExternalSemaphoreHandleType, CudaMipmappedArrayDesc, CUDA_ARRAY_COLOR_ATTACHMENT,
+    CUDA_ARRAY_LAYERED, CUDA_ARRAY_SURFACE_LOAD_STORE, CUDA_EXTERNAL_MEMORY_DEDICATED,
+};
 pub use device::{BufferInitDescriptor, DeviceExt, TextureDataOrder};
+#[cfg(all(unix, external_memory))]
+pub use dmabuf::{
+    drm_fourcc, export_texture_as_dmabuf, DmaBufPlane, DmaBufTexture, DRM_FORMAT_MOD_INVALID,
+    DRM_FORMAT_MOD_LINEAR,
+};
+#[cfg(feature = "wgsl")]
+pub use draw_args::{DrawArgsConverter, DrawArgsLayout};
 pub use encoder::RenderEncoder;
+#[cfg(feature = "wgsl")]
+pub use external_image::{ExternalImageCopier, ImageCopyPixels};
 pub use init::*;
+#[cfg(feature = "wgsl")]
+pub use mipmap::{generate_mipmaps, MipmapGenerator};
+#[cfg(native)]
+pub use multi_device::{
+    MultiDeviceRenderer, Tile, TileContext, TileProgress, TileRenderDescriptor,
+};
+#[cfg(feature = "debug-overlay")]
+pub use overlay::DebugOverlay;
+pub use pass_graph::{PassGraph, PassKind, PassQueue, PassResource, PassSchedule, PassUsage};
+pub use peer_copy::{copy_buffer_to_device, copy_texture_to_device};
+pub use readback::{read_buffer, read_texture};
+pub use registry::{
+    RegistryBindGroupEntry, RegistryBindingResource, RegistryHandle, RegistrySnapshot,
+    ResourceRegistry,
+};
+#[cfg(feature = "sprite")]
+pub use sprite::{Sprite, SpriteRenderer, SpriteTexture};
+#[cfg(native)]
+pub use throttle::PresentThrottle;
+pub use timestamp::TimestampNormalizer;
+#[cfg(feature = "texture-transcode")]
+pub use transcode::{transcode_texture_data, transcoded_format};
+#[cfg(all(wgpu_core, feature = "shader-translation"))]
+pub use wgc::shader_translation::{
+    translate_wgsl, TranslatedShader, TranslationDescriptor, TranslationError, TranslationTarget,
+};
 pub use wgt::{math::*, DispatchIndirectArgs, DrawIndexedIndirectArgs, DrawIndirectArgs};
 
 /// Treat the given byte slice as a SPIR-V module.
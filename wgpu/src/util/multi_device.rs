@@ -0,0 +1,395 @@
+use std::sync::Arc;
+
+use parking_lot::Mutex;
+
+use crate::{
+    Buffer, BufferAddress, BufferAsyncError, BufferDescriptor, BufferUsages, CommandEncoder,
+    CommandEncoderDescriptor, Device, Extent3d, ImageCopyBuffer, ImageDataLayout, Maintain,
+    MapMode, Queue, SubmissionIndex, Texture, TextureDescriptor, TextureDimension, TextureFormat,
+    TextureUsages, COPY_BYTES_PER_ROW_ALIGNMENT,
+};
+
+/// Describes the image rendered by [`MultiDeviceRenderer::render`].
+#[derive(Clone, Debug)]
+pub struct TileRenderDescriptor<'a> {
+    /// Debug label of the tile targets.
+    pub label: Option<&'a str>,
+    /// Width of the image, in texels.
+    pub width: u32,
+    /// Height of the image, in texels.
+    pub height: u32,
+    /// Width and height of the tiles the image is split into. The tiles of the
+    /// last column and row are cut to the size of the image.
+    pub tile_size: u32,
+    /// Format of the image, which must be an uncompressed format with a single aspect.
+    pub format: TextureFormat,
+    /// Usages of the tile targets, which are rendered into. `COPY_SRC` is
+    /// always added.
+    pub usage: TextureUsages,
+}
+
+/// A rectangle of the image rendered by [`MultiDeviceRenderer::render`].
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct Tile {
+    /// Left edge of the tile in the image, in texels.
+    pub x: u32,
+    /// Top edge of the tile in the image, in texels.
+    pub y: u32,
+    /// Width of the tile, in texels.
+    pub width: u32,
+    /// Height of the tile, in texels.
+    pub height: u32,
+}
+
+/// What a tile is rendered with, passed to the recording callback of
+/// [`MultiDeviceRenderer::render`].
+#[derive(Debug)]
+pub struct TileContext<'a, R> {
+    /// Index of the device rendering the tile, in the order the devices were
+    /// given to [`MultiDeviceRenderer::new`].
+    pub device_index: usize,
+    /// The device rendering the tile.
+    pub device: &'a Device,
+    /// The queue the tile is submitted to.
+    pub queue: &'a Queue,
+    /// The resources created for the device by [`MultiDeviceRenderer::new`].
+    pub resources: &'a R,
+    /// The texture to render the tile into. It is [`TileRenderDescriptor::tile_size`]
+    /// texels wide and high, and only the `tile.width` by `tile.height` texels in
+    /// its top left corner are read back.
+    pub target: &'a Texture,
+    /// The rectangle of the image to render.
+    pub tile: Tile,
+}
+
+/// Progress of [`MultiDeviceRenderer::render`], passed to its progress callback
+/// every time a tile has been read back.
+#[derive(Debug)]
+pub struct TileProgress<'a> {
+    /// The tile that was just read back.
+    pub tile: Tile,
+    /// Index of the device that rendered it.
+    pub device_index: usize,
+    /// How many tiles each device has rendered so far.
+    pub tiles_per_device: &'a [usize],
+    /// How many tiles have been rendered so far, by all devices.
+    pub tiles_done: usize,
+    /// How many tiles the image is split into.
+    pub tile_count: usize,
+}
+
+/// Renders images split into tiles on several devices at once, for offline
+/// rendering like path tracing.
+///
+/// Resources can't be shared between devices, so every device gets resources
+/// of its own, created once by the callback given to [`MultiDeviceRenderer::new`].
+/// [`MultiDeviceRenderer::render`] then hands out the tiles of an image to the
+/// devices as they finish the previous ones, so faster devices render more of
+/// them, and gathers the results into host memory.
+///
+/// The devices can be of different adapters, or the same device several times
+/// to keep more tiles in flight on it. This doesn't need any driver support
+/// for linked adapters, like SLI or CrossFire.
+///
+/// Waits for the devices with [`Device::poll`], so this is only available on
+/// native platforms.
+///
+/// ```no_run
+/// # fn render(devices: &[(wgpu::Device, wgpu::Queue)]) {
+/// let mut renderer = wgpu::util::MultiDeviceRenderer::new(
+///     devices.iter().map(|(device, queue)| (device, queue)),
+///     |device, _queue| {
+///         // Create the pipelines and upload the scene on each device.
+///     },
+/// );
+/// let image = renderer
+///     .render(
+///         &wgpu::util::TileRenderDescriptor {
+///             label: Some("path tracer"),
+///             width: 1920,
+///             height: 1080,
+///             tile_size: 256,
+///             format: wgpu::TextureFormat::Rgba32Float,
+///             usage: wgpu::TextureUsages::STORAGE_BINDING,
+///         },
+///         |context, encoder| {
+///             // Trace the rays of `context.tile` into `context.target`.
+///         },
+///         |progress| println!("{}/{} tiles", progress.tiles_done, progress.tile_count),
+///     )
+///     .unwrap();
+/// # }
+/// ```
+#[derive(Debug)]
+pub struct MultiDeviceRenderer<'a, R> {
+    devices: Vec<DeviceSlot<'a, R>>,
+}
+
+#[derive(Debug)]
+struct DeviceSlot<'a, R> {
+    device: &'a Device,
+    queue: &'a Queue,
+    resources: R,
+}
+
+/// The target of a device and the tile it is rendering.
+struct TileSlot {
+    target: Texture,
+    staging: Buffer,
+    in_flight: Option<InFlightTile>,
+}
+
+struct InFlightTile {
+    tile: Tile,
+    /// Order in which the tile was submitted, to wait for the oldest first.
+    order: usize,
+    submission: SubmissionIndex,
+    mapped: Arc<Mutex<Option<Result<(), BufferAsyncError>>>>,
+}
+
+impl<'a, R> MultiDeviceRenderer<'a, R> {
+    /// Creates a renderer for `devices`, calling `replicate` once for every
+    /// device to create the resources its tiles are rendered with.
+    ///
+    /// # Panics
+    ///
+    /// - `devices` is empty.
+    pub fn new<I, F>(devices: I, mut replicate: F) -> Self
+    where
+        I: IntoIterator<Item = (&'a Device, &'a Queue)>,
+        F: FnMut(&Device, &Queue) -> R,
+    {
+        let devices: Vec<_> = devices
+            .into_iter()
+            .map(|(device, queue)| DeviceSlot {
+                device,
+                queue,
+                resources: replicate(device, queue),
+            })
+            .collect();
+        assert!(!devices.is_empty(), "at least one device is needed");
+        Self { devices }
+    }
+
+    /// The number of devices tiles are rendered on.
+    pub fn device_count(&self) -> usize {
+        self.devices.len()
+    }
+
+    /// The resources created for the device at `device_index`, for example to
+    /// update the scene between two images.
+    pub fn resources(&self, device_index: usize) -> &R {
+        &self.devices[device_index].resources
+    }
+
+    /// Mutable access to the resources created for the device at `device_index`.
+    pub fn resources_mut(&mut self, device_index: usize) -> &mut R {
+        &mut self.devices[device_index].resources
+    }
+
+    /// Renders an image, and returns its texels, in rows of `desc.width` tightly
+    /// packed texels from the top.
+    ///
+    /// `record` is called for every tile, with the device it is rendered on and
+    /// an encoder of that device, which is submitted along with the copy of the
+    /// tile once `record` returns. `progress` is called every time a tile has
+    /// been read back.
+    ///
+    /// Fails if a tile couldn't be read back, for example because its device
+    /// was lost.
+    ///
+    /// # Panics
+    ///
+    /// - `desc.format` is compressed, or has more than one aspect.
+    /// - `desc.tile_size` is zero.
+    pub fn render<F, P>(
+        &mut self,
+        desc: &TileRenderDescriptor<'_>,
+        mut record: F,
+        mut progress: P,
+    ) -> Result<Vec<u8>, BufferAsyncError>
+    where
+        F: FnMut(&TileContext<'_, R>, &mut CommandEncoder),
+        P: FnMut(&TileProgress<'_>),
+    {
+        assert_eq!(
+            desc.format.block_dimensions(),
+            (1, 1),
+            "compressed formats can't be rendered into"
+        );
+        let texel_size = desc
+            .format
+            .block_copy_size(None)
+            .expect("formats with several aspects can't be read back");
+        assert_ne!(desc.tile_size, 0, "the tile size must not be zero");
+
+        let row_size = (desc.width * texel_size) as usize;
+        let mut image = vec![0; row_size * desc.height as usize];
+        let tile_bytes_per_row =
+            (desc.tile_size * texel_size).next_multiple_of(COPY_BYTES_PER_ROW_ALIGNMENT);
+
+        let mut tiles = (0..desc.height)
+            .step_by(desc.tile_size as usize)
+            .flat_map(|y| {
+                (0..desc.width)
+                    .step_by(desc.tile_size as usize)
+                    .map(move |x| Tile {
+                        x,
+                        y,
+                        width: desc.tile_size.min(desc.width - x),
+                        height: desc.tile_size.min(desc.height - y),
+                    })
+            });
+        let tile_count =
+            (desc.width.div_ceil(desc.tile_size) * desc.height.div_ceil(desc.tile_size)) as usize;
+
+        let tile_extent = Extent3d {
+            width: desc.tile_size,
+            height: desc.tile_size,
+            depth_or_array_layers: 1,
+        };
+        let mut slots: Vec<_> = self
+            .devices
+            .iter()
+            .map(|slot| TileSlot {
+                target: slot.device.create_texture(&TextureDescriptor {
+                    label: desc.label,
+                    size: tile_extent,
+                    mip_level_count: 1,
+                    sample_count: 1,
+                    dimension: TextureDimension::D2,
+                    format: desc.format,
+                    usage: desc.usage | TextureUsages::COPY_SRC,
+                    view_formats: &[],
+                }),
+                staging: slot.device.create_buffer(&BufferDescriptor {
+                    label: desc.label,
+                    size: tile_bytes_per_row as BufferAddress * desc.tile_size as BufferAddress,
+                    usage: BufferUsages::COPY_DST | BufferUsages::MAP_READ,
+                    mapped_at_creation: false,
+                }),
+                in_flight: None,
+            })
+            .collect();
+
+        let mut tiles_per_device = vec![0; self.devices.len()];
+        let mut tiles_done = 0;
+        let mut submitted = 0;
+        loop {
+            // Hand out the next tiles to the devices that are done with theirs.
+            for (device_index, (device_slot, tile_slot)) in
+                self.devices.iter().zip(&mut slots).enumerate()
+            {
+                if tile_slot.in_flight.is_some() {
+                    continue;
+                }
+                let Some(tile) = tiles.next() else {
+                    break;
+                };
+                let context = TileContext {
+                    device_index,
+                    device: device_slot.device,
+                    queue: device_slot.queue,
+                    resources: &device_slot.resources,
+                    target: &tile_slot.target,
+                    tile,
+                };
+                let mut encoder = device_slot
+                    .device
+                    .create_command_encoder(&CommandEncoderDescriptor { label: desc.label });
+                record(&context, &mut encoder);
+                encoder.copy_texture_to_buffer(
+                    tile_slot.target.as_image_copy(),
+                    ImageCopyBuffer {
+                        buffer: &tile_slot.staging,
+                        layout: ImageDataLayout {
+                            offset: 0,
+                            bytes_per_row: Some(tile_bytes_per_row),
+                            rows_per_image: None,
+                        },
+                    },
+                    Extent3d {
+                        width: tile.width,
+                        height: tile.height,
+                        depth_or_array_layers: 1,
+                    },
+                );
+                let submission = device_slot.queue.submit(Some(encoder.finish()));
+
+                let mapped = Arc::new(Mutex::new(None));
+                let callback_mapped = Arc::clone(&mapped);
+                tile_slot
+                    .staging
+                    .slice(..)
+                    .map_async(MapMode::Read, move |result| {
+                        *callback_mapped.lock() = Some(result);
+                    });
+                tile_slot.in_flight = Some(InFlightTile {
+                    tile,
+                    order: submitted,
+                    submission,
+                    mapped,
+                });
+                submitted += 1;
+            }
+
+            // Wait for the oldest tile, and pick up the others that are done
+            // by then.
+            let Some(oldest) = slots
+                .iter()
+                .enumerate()
+                .filter_map(|(index, slot)| Some((index, slot.in_flight.as_ref()?)))
+                .min_by_key(|(_, in_flight)| in_flight.order)
+                .map(|(index, in_flight)| (index, in_flight.submission.clone()))
+            else {
+                break;
+            };
+            for (index, device_slot) in self.devices.iter().enumerate() {
+                if index == oldest.0 {
+                    device_slot
+                        .device
+                        .poll(Maintain::wait_for(oldest.1.clone()));
+                } else {
+                    device_slot.device.poll(Maintain::Poll);
+                }
+            }
+
+            for (device_index, tile_slot) in slots.iter_mut().enumerate() {
+                let Some(ref in_flight) = tile_slot.in_flight else {
+                    continue;
+                };
+                let Some(result) = in_flight.mapped.lock().take() else {
+                    continue;
+                };
+                result?;
+                let tile = in_flight.tile;
+                tile_slot.in_flight = None;
+
+                {
+                    let data = tile_slot.staging.slice(..).get_mapped_range();
+                    let tile_row_size = (tile.width * texel_size) as usize;
+                    for row in 0..tile.height as usize {
+                        let source = row * tile_bytes_per_row as usize;
+                        let destination =
+                            (tile.y as usize + row) * row_size + (tile.x * texel_size) as usize;
+                        image[destination..destination + tile_row_size]
+                            .copy_from_slice(&data[source..source + tile_row_size]);
+                    }
+                }
+                tile_slot.staging.unmap();
+
+                tiles_per_device[device_index] += 1;
+                tiles_done += 1;
+                progress(&TileProgress {
+                    tile,
+                    device_index,
+                    tiles_per_device: &tiles_per_device,
+                    tiles_done,
+                    tile_count,
+                });
+            }
+        }
+
+        Ok(image)
+    }
+}
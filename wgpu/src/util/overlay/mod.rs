@@ -0,0 +1,532 @@
+mod font;
+
+use std::{fmt, time::Duration};
+
+use super::{DeviceExt, TextureDataOrder};
+use crate::{
+    AddressMode, BindGroup, BindGroupDescriptor, BindGroupEntry, BindGroupLayoutDescriptor,
+    BindGroupLayoutEntry, BindingResource, BindingType, BlendState, Buffer, BufferAddress,
+    BufferBindingType, BufferDescriptor, BufferUsages, ColorTargetState, ColorWrites,
+    CommandEncoder, CommandEncoderDescriptor, Device, Extent3d, FilterMode, FragmentState, LoadOp,
+    MultisampleState, Operations, PipelineLayoutDescriptor, PrimitiveState, PrimitiveTopology,
+    Queue, RenderPassColorAttachment, RenderPassDescriptor, RenderPipeline,
+    RenderPipelineDescriptor, SamplerBindingType, SamplerDescriptor, ShaderModuleDescriptor,
+    ShaderSource, ShaderStages, StoreOp, SurfaceTexture, TextureDescriptor, TextureDimension,
+    TextureFormat, TextureSampleType, TextureUsages, TextureView, TextureViewDescriptor,
+    TextureViewDimension, VertexAttribute, VertexBufferLayout, VertexFormat, VertexState,
+    VertexStepMode,
+};
+
+/// The size of a glyph in font texels.
+const GLYPH_WIDTH: u32 = 5;
+const GLYPH_HEIGHT: u32 = 7;
+/// The number of glyphs in a row of the font texture.
+const GLYPHS_PER_ROW: u32 = 16;
+/// The size of a quad in the instance buffer: its corners, glyph and color.
+const INSTANCE_SIZE: BufferAddress = 16 * 4;
+/// The width of the area of a timing bar that represents its whole budget,
+/// in font texels.
+const TIMING_BAR_WIDTH: f32 = 100.0;
+
+/// Draws text, lines and gizmos over a rendered frame, for debugging.
+///
+/// The overlay has its own pipeline, so it is independent of the renderer of
+/// the application. Everything added during a frame is drawn on top of it by
+/// [`present`](Self::present), right before the frame is presented, and
+/// cleared afterwards. Positions are in pixels from the top-left corner of
+/// the frame, and colors are in linear RGBA without premultiplied alpha.
+///
+/// Stats and timing bars are listed in a panel at the top-left corner, like
+/// the GPU time of a frame measured with timestamp queries, or the resource
+/// counts of [`memory_report`](Self::memory_report).
+///
+/// ```no_run
+/// # let device: wgpu::Device = todo!();
+/// # let queue: wgpu::Queue = todo!();
+/// # let surface: wgpu::Surface = todo!();
+/// # let frame_time: std::time::Duration = todo!();
+/// use wgpu::util::DebugOverlay;
+///
+/// let mut overlay = DebugOverlay::new(&device, &queue, wgpu::TextureFormat::Bgra8UnormSrgb);
+/// # loop {
+/// let frame = surface.get_current_texture().unwrap();
+/// // Render the frame...
+/// overlay.timing_bar("frame", frame_time, std::time::Duration::from_millis(16));
+/// overlay.text([200.0, 100.0], [1.0, 1.0, 0.0, 1.0], "player");
+/// overlay.present(&device, &queue, frame);
+/// # }
+/// ```
+#[derive(Debug)]
+pub struct DebugOverlay {
+    enabled: bool,
+    scale: f32,
+    pipeline: RenderPipeline,
+    globals: Buffer,
+    bind_group: BindGroup,
+    instances: Option<Buffer>,
+    quads: Vec<[f32; 16]>,
+    panel_lines: u32,
+}
+
+impl DebugOverlay {
+    /// Creates an overlay for frames of `format`, enabled and at a scale of 2,
+    /// uploading its font with `queue`.
+    pub fn new(device: &Device, queue: &Queue, format: TextureFormat) -> Self {
+        let module = device.create_shader_module(ShaderModuleDescriptor {
+            label: Some("wgpu::util::DebugOverlay"),
+            source: ShaderSource::Wgsl(include_str!("overlay.wgsl").into()),
+            diagnostic_filters: &[],
+        });
+        let bind_group_layout = device.create_bind_group_layout(&BindGroupLayoutDescriptor {
+            label: Some("wgpu::util::DebugOverlay"),
+            entries: &[
+                BindGroupLayoutEntry {
+                    binding: 0,
+                    visibility: ShaderStages::VERTEX,
+                    ty: BindingType::Buffer {
+                        ty: BufferBindingType::Uniform,
+                        has_dynamic_offset: false,
+                        min_binding_size: None,
+                    },
+                    count: None,
+                },
+                BindGroupLayoutEntry {
+                    binding: 1,
+                    visibility: ShaderStages::FRAGMENT,
+                    ty: BindingType::Texture {
+                        sample_type: TextureSampleType::Float { filterable: false },
+                        view_dimension: TextureViewDimension::D2,
+                        multisampled: false,
+                    },
+                    count: None,
+                },
+                BindGroupLayoutEntry {
+                    binding: 2,
+                    visibility: ShaderStages::FRAGMENT,
+                    ty: BindingType::Sampler(SamplerBindingType::NonFiltering),
+                    count: None,
+                },
+            ],
+        });
+        let layout = device.create_pipeline_layout(&PipelineLayoutDescriptor {
+            label: Some("wgpu::util::DebugOverlay"),
+            bind_group_layouts: &[&bind_group_layout],
+            push_constant_ranges: &[],
+        });
+        let attribute = |index: u32| VertexAttribute {
+            format: VertexFormat::Float32x4,
+            offset: index as BufferAddress * 16,
+            shader_location: index,
+        };
+        let pipeline = device.create_render_pipeline(&RenderPipelineDescriptor {
+            label: Some("wgpu::util::DebugOverlay"),
+            layout: Some(&layout),
+            vertex: VertexState {
+                module: &module,
+                entry_point: "vs_main",
+                compilation_options: Default::default(),
+                buffers: &[VertexBufferLayout {
+                    array_stride: INSTANCE_SIZE,
+                    step_mode: VertexStepMode::Instance,
+                    attributes: &[attribute(0), attribute(1), attribute(2), attribute(3)],
+                }],
+            },
+            primitive: PrimitiveState {
+                topology: PrimitiveTopology::TriangleStrip,
+                ..Default::default()
+            },
+            depth_stencil: None,
+            multisample: MultisampleState::default(),
+            fragment: Some(FragmentState {
+                module: &module,
+                entry_point: "fs_main",
+                compilation_options: Default::default(),
+                targets: &[Some(ColorTargetState {
+                    format,
+                    blend: Some(BlendState::PREMULTIPLIED_ALPHA_BLENDING),
+                    write_mask: ColorWrites::ALL,
+                })],
+            }),
+            multiview: None,
+        });
+
+        let (width, height) = font_texture_size();
+        let mut texels = vec![0u8; (width * height) as usize];
+        for (index, glyph) in font::GLYPHS.iter().enumerate() {
+            let (x0, y0) = glyph_origin(index as u32);
+            for (x, column) in glyph.iter().enumerate() {
+                for y in 0..GLYPH_HEIGHT {
+                    if column & (1 << y) != 0 {
+                        let texel = (y0 + y) * width + x0 + x as u32;
+                        texels[texel as usize] = 0xFF;
+                    }
+                }
+            }
+        }
+        let font = device.create_texture_with_data(
+            queue,
+            &TextureDescriptor {
+                label: Some("wgpu::util::DebugOverlay font"),
+                size: Extent3d {
+                    width,
+                    height,
+                    depth_or_array_layers: 1,
+                },
+                mip_level_count: 1,
+                sample_count: 1,
+                dimension: TextureDimension::D2,
+                format: TextureFormat::R8Unorm,
+                usage: TextureUsages::TEXTURE_BINDING,
+                view_formats: &[],
+            },
+            TextureDataOrder::LayerMajor,
+            &texels,
+        );
+        let sampler = device.create_sampler(&SamplerDescriptor {
+            label: Some("wgpu::util::DebugOverlay"),
+            address_mode_u: AddressMode::ClampToEdge,
+            address_mode_v: AddressMode::ClampToEdge,
+            mag_filter: FilterMode::Nearest,
+            min_filter: FilterMode::Nearest,
+            ..Default::default()
+        });
+        // `Globals` is padded to the 16 bytes uniform buffers are sized in.
+        let globals = device.create_buffer(&BufferDescriptor {
+            label: Some("wgpu::util::DebugOverlay globals"),
+            size: 16,
+            usage: BufferUsages::UNIFORM | BufferUsages::COPY_DST,
+            mapped_at_creation: false,
+        });
+        let font_view = font.create_view(&TextureViewDescriptor::default());
+        let bind_group = device.create_bind_group(&BindGroupDescriptor {
+            label: Some("wgpu::util::DebugOverlay"),
+            layout: &bind_group_layout,
+            entries: &[
+                BindGroupEntry {
+                    binding: 0,
+                    resource: globals.as_entire_binding(),
+                },
+                BindGroupEntry {
+                    binding: 1,
+                    resource: BindingResource::TextureView(&font_view),
+                },
+                BindGroupEntry {
+                    binding: 2,
+                    resource: BindingResource::Sampler(&sampler),
+                },
+            ],
+        });
+
+        Self {
+            enabled: true,
+            scale: 2.0,
+            pipeline,
+            globals,
+            bind_group,
+            instances: None,
+            quads: Vec::new(),
+            panel_lines: 0,
+        }
+    }
+
+    /// Enables or disables the overlay. A disabled overlay ignores everything
+    /// added to it and presents frames as they are.
+    pub fn set_enabled(&mut self, enabled: bool) {
+        self.enabled = enabled;
+        if !enabled {
+            self.clear();
+        }
+    }
+
+    /// Returns whether the overlay is enabled.
+    pub fn is_enabled(&self) -> bool {
+        self.enabled
+    }
+
+    /// Sets the size of a font texel in pixels, which scales text and the panel.
+    pub fn set_scale(&mut self, scale: f32) {
+        self.scale = scale;
+    }
+
+    /// Removes everything added since the last frame.
+    pub fn clear(&mut self) {
+        self.quads.clear();
+        self.panel_lines = 0;
+    }
+
+    /// Draws `text` with its top-left corner at `position`.
+    ///
+    /// Lines are separated by `\n`, characters outside of printable ASCII are
+    /// drawn as `?`.
+    pub fn text(&mut self, position: [f32; 2], color: [f32; 4], text: &str) {
+        if !self.enabled {
+            return;
+        }
+        let (advance, line_height) = self.glyph_advance();
+        let [mut x, mut y] = position;
+        for c in text.chars() {
+            if c == '\n' {
+                x = position[0];
+                y += line_height;
+                continue;
+            }
+            let glyph = match c {
+                ' '..='~' => c as u32 - font::FIRST as u32,
+                _ => '?' as u32 - font::FIRST as u32,
+            };
+            self.glyph(
+                [x, y],
+                [GLYPH_WIDTH as f32 * self.scale, 0.0],
+                [0.0, GLYPH_HEIGHT as f32 * self.scale],
+                glyph,
+                color,
+            );
+            x += advance;
+        }
+    }
+
+    /// Draws a line from `from` to `to` that is `thickness` pixels wide.
+    pub fn line(&mut self, from: [f32; 2], to: [f32; 2], thickness: f32, color: [f32; 4]) {
+        if !self.enabled {
+            return;
+        }
+        let edge = [to[0] - from[0], to[1] - from[1]];
+        let length = (edge[0] * edge[0] + edge[1] * edge[1]).sqrt();
+        if length == 0.0 {
+            return;
+        }
+        let normal = [-edge[1] / length * thickness, edge[0] / length * thickness];
+        let origin = [from[0] - normal[0] / 2.0, from[1] - normal[1] / 2.0];
+        self.glyph(origin, edge, normal, solid_glyph(), color);
+    }
+
+    /// Draws a filled rectangle with its top-left corner at `position`.
+    pub fn rect(&mut self, position: [f32; 2], size: [f32; 2], color: [f32; 4]) {
+        if !self.enabled {
+            return;
+        }
+        self.glyph(
+            position,
+            [size[0], 0.0],
+            [0.0, size[1]],
+            solid_glyph(),
+            color,
+        );
+    }
+
+    /// Draws the X, Y and Z axes of a coordinate system at `origin` in red,
+    /// green and blue, given the ends of the axes projected into the frame
+    /// relative to `origin`.
+    pub fn gizmo(&mut self, origin: [f32; 2], axes: [[f32; 2]; 3]) {
+        const COLORS: [[f32; 4]; 3] = [
+            [1.0, 0.0, 0.0, 1.0],
+            [0.0, 1.0, 0.0, 1.0],
+            [0.0, 0.0, 1.0, 1.0],
+        ];
+        for (axis, color) in axes.iter().zip(COLORS) {
+            let end = [origin[0] + axis[0], origin[1] + axis[1]];
+            self.line(origin, end, self.scale, color);
+        }
+    }
+
+    /// Adds a line `label: value` to the panel.
+    pub fn stat(&mut self, label: &str, value: impl fmt::Display) {
+        if !self.enabled {
+            return;
+        }
+        let position = self.next_panel_line();
+        self.text(position, [1.0; 4], &format!("{label}: {value}"));
+    }
+
+    /// Adds a bar to the panel that shows `duration` as a part of `budget`,
+    /// in green while it fits and in red once it doesn't.
+    pub fn timing_bar(&mut self, label: &str, duration: Duration, budget: Duration) {
+        if !self.enabled {
+            return;
+        }
+        let [x, y] = self.next_panel_line();
+        let bar_height = GLYPH_HEIGHT as f32 * self.scale;
+        let full_width = TIMING_BAR_WIDTH * self.scale;
+        let fraction = if budget.is_zero() {
+            1.0
+        } else {
+            (duration.as_secs_f32() / budget.as_secs_f32()).min(1.0)
+        };
+        let color = if duration <= budget {
+            [0.0, 0.8, 0.0, 1.0]
+        } else {
+            [0.9, 0.0, 0.0, 1.0]
+        };
+        self.rect([x, y], [full_width, bar_height], [0.0, 0.0, 0.0, 0.5]);
+        self.rect([x, y], [full_width * fraction, bar_height], color);
+        self.text(
+            [x + full_width + self.glyph_advance().0, y],
+            [1.0; 4],
+            &format!("{label}: {:.2} ms", duration.as_secs_f64() * 1000.0),
+        );
+    }
+
+    /// Adds the number of live resources of each kind on `backend` to the panel.
+    #[cfg(wgpu_core)]
+    pub fn memory_report(&mut self, instance: &crate::Instance, backend: crate::Backend) {
+        if !self.enabled {
+            return;
+        }
+        let Some(report) = instance.generate_report() else {
+            return;
+        };
+        let hub = report.hub_report(backend);
+        for (label, registry) in [
+            ("buffers", &hub.buffers),
+            ("textures", &hub.textures),
+            ("texture views", &hub.texture_views),
+            ("samplers", &hub.samplers),
+            ("bind groups", &hub.bind_groups),
+            ("shader modules", &hub.shader_modules),
+            ("render pipelines", &hub.render_pipelines),
+            ("compute pipelines", &hub.compute_pipelines),
+        ] {
+            self.stat(label, registry.num_allocated);
+        }
+    }
+
+    /// Records drawing everything added since the last frame into `target`,
+    /// which is `target_size` pixels large, and clears the overlay.
+    pub fn render(
+        &mut self,
+        device: &Device,
+        queue: &Queue,
+        encoder: &mut CommandEncoder,
+        target: &TextureView,
+        target_size: [u32; 2],
+    ) {
+        let quads = std::mem::take(&mut self.quads);
+        self.panel_lines = 0;
+        if quads.is_empty() {
+            return;
+        }
+
+        let globals: Vec<u8> = [target_size[0] as f32, target_size[1] as f32, 0.0, 0.0]
+            .iter()
+            .flat_map(|v| v.to_ne_bytes())
+            .collect();
+        queue.write_buffer(&self.globals, 0, &globals);
+        let contents: Vec<u8> = quads
+            .iter()
+            .flatten()
+            .flat_map(|v| v.to_ne_bytes())
+            .collect();
+        let size = contents.len() as BufferAddress;
+        if self
+            .instances
+            .as_ref()
+            .map_or(true, |buffer| buffer.size() < size)
+        {
+            self.instances = Some(device.create_buffer(&BufferDescriptor {
+                label: Some("wgpu::util::DebugOverlay instances"),
+                size: size.next_power_of_two(),
+                usage: BufferUsages::VERTEX | BufferUsages::COPY_DST,
+                mapped_at_creation: false,
+            }));
+        }
+        let instances = self.instances.as_ref().unwrap();
+        queue.write_buffer(instances, 0, &contents);
+        // Keep the allocation for the next frame.
+        self.quads = quads;
+        self.quads.clear();
+
+        let mut pass = encoder.begin_render_pass(&RenderPassDescriptor {
+            label: Some("wgpu::util::DebugOverlay"),
+            color_attachments: &[Some(RenderPassColorAttachment {
+                view: target,
+                resolve_target: None,
+                ops: Operations {
+                    load: LoadOp::Load,
+                    store: StoreOp::Store,
+                },
+            })],
+            depth_stencil_attachment: None,
+            timestamp_writes: None,
+            occlusion_query_set: None,
+        });
+        pass.set_pipeline(&self.pipeline);
+        pass.set_bind_group(0, &self.bind_group, &[]);
+        pass.set_vertex_buffer(0, instances.slice(..size));
+        pass.draw(0..4, 0..(size / INSTANCE_SIZE) as u32);
+    }
+
+    /// Draws everything added since the last frame over `frame` and presents it.
+    pub fn present(&mut self, device: &Device, queue: &Queue, frame: SurfaceTexture) {
+        if self.enabled && !self.quads.is_empty() {
+            let view = frame.texture.create_view(&TextureViewDescriptor::default());
+            let mut encoder = device.create_command_encoder(&CommandEncoderDescriptor {
+                label: Some("wgpu::util::DebugOverlay"),
+            });
+            let size = [frame.texture.width(), frame.texture.height()];
+            self.render(device, queue, &mut encoder, &view, size);
+            queue.submit(Some(encoder.finish()));
+        }
+        self.clear();
+        frame.present();
+    }
+
+    /// Returns the distance between characters and lines in pixels.
+    fn glyph_advance(&self) -> (f32, f32) {
+        (
+            (GLYPH_WIDTH + 1) as f32 * self.scale,
+            (GLYPH_HEIGHT + 2) as f32 * self.scale,
+        )
+    }
+
+    /// Returns the position of the next line of the panel, and reserves it.
+    fn next_panel_line(&mut self) -> [f32; 2] {
+        let (_, line_height) = self.glyph_advance();
+        let margin = 4.0 * self.scale;
+        let position = [margin, margin + self.panel_lines as f32 * line_height];
+        self.panel_lines += 1;
+        position
+    }
+
+    /// Adds a quad with corners `origin`, `origin + x` and `origin + y`,
+    /// showing `glyph`.
+    fn glyph(&mut self, origin: [f32; 2], x: [f32; 2], y: [f32; 2], glyph: u32, color: [f32; 4]) {
+        let (width, height) = font_texture_size();
+        let (u, v) = glyph_origin(glyph);
+        self.quads.push([
+            origin[0],
+            origin[1],
+            x[0],
+            x[1],
+            y[0],
+            y[1],
+            0.0,
+            0.0,
+            u as f32 / width as f32,
+            v as f32 / height as f32,
+            GLYPH_WIDTH as f32 / width as f32,
+            GLYPH_HEIGHT as f32 / height as f32,
+            color[0],
+            color[1],
+            color[2],
+            color[3],
+        ]);
+    }
+}
+
+/// The solid block at the end of the font, to draw shapes with.
+fn solid_glyph() -> u32 {
+    font::GLYPHS.len() as u32 - 1
+}
+
+fn font_texture_size() -> (u32, u32) {
+    let rows = (font::GLYPHS.len() as u32).div_ceil(GLYPHS_PER_ROW);
+    (GLYPHS_PER_ROW * GLYPH_WIDTH, rows * GLYPH_HEIGHT)
+}
+
+/// Returns the top-left texel of `glyph` in the font texture.
+fn glyph_origin(glyph: u32) -> (u32, u32) {
+    (
+        glyph % GLYPHS_PER_ROW * GLYPH_WIDTH,
+        glyph / GLYPHS_PER_ROW * GLYPH_HEIGHT,
+    )
+}
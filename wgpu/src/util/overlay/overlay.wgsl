@@ -0,0 +1,47 @@
+struct Globals {
+    // Size of the render target in pixels.
+    target_size: vec2<f32>,
+}
+
+struct Instance {
+    // Position of the first corner, and the edge to the second one in pixels.
+    @location(0) origin_and_x: vec4<f32>,
+    // The edge from the first to the third corner in pixels.
+    @location(1) y: vec4<f32>,
+    // Top-left corner and size of the glyph in font texture coordinates.
+    @location(2) uv_rect: vec4<f32>,
+    @location(3) color: vec4<f32>,
+}
+
+struct VertexOutput {
+    @builtin(position) position: vec4<f32>,
+    @location(0) uv: vec2<f32>,
+    @location(1) color: vec4<f32>,
+}
+
+@group(0) @binding(0)
+var<uniform> globals: Globals;
+@group(0) @binding(1)
+var font: texture_2d<f32>;
+@group(0) @binding(2)
+var font_sampler: sampler;
+
+@vertex
+fn vs_main(@builtin(vertex_index) index: u32, instance: Instance) -> VertexOutput {
+    // The corners of a quad as a triangle strip.
+    let corner = vec2<f32>(f32(index & 1u), f32(index >> 1u));
+    let pixel = instance.origin_and_x.xy + corner.x * instance.origin_and_x.zw + corner.y * instance.y.xy;
+    let ndc = pixel / globals.target_size * 2.0 - 1.0;
+
+    var out: VertexOutput;
+    out.position = vec4<f32>(ndc.x, -ndc.y, 0.0, 1.0);
+    out.uv = instance.uv_rect.xy + corner * instance.uv_rect.zw;
+    out.color = instance.color;
+    return out;
+}
+
+@fragment
+fn fs_main(in: VertexOutput) -> @location(0) vec4<f32> {
+    let coverage = textureSample(font, font_sampler, in.uv).r * in.color.a;
+    return vec4<f32>(in.color.rgb * coverage, coverage);
+}
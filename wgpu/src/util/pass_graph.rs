@@ -0,0 +1,312 @@
+use std::collections::HashMap;
+
+use crate::{
+    BindGroupEntry, BindGroupLayoutEntry, BindingResource, BindingType, Buffer, BufferBindingType,
+    Id, StorageTextureAccess, Texture, TextureView,
+};
+
+/// A buffer or texture used by a pass of a [`PassGraph`].
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub enum PassResource {
+    /// A buffer, as a whole.
+    Buffer(Id<Buffer>),
+    /// A texture, with all of its views.
+    Texture(Id<Texture>),
+}
+
+/// The resources a pass reads and writes, declared ahead of encoding it.
+///
+/// Writes include reads, so a resource that is read and written only has to
+/// be declared as written.
+#[derive(Clone, Debug, Default)]
+pub struct PassUsage {
+    reads: Vec<PassResource>,
+    writes: Vec<PassResource>,
+}
+
+impl PassUsage {
+    /// A pass that doesn't use any resources.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Declares that the pass reads `buffer`.
+    pub fn read_buffer(mut self, buffer: &Buffer) -> Self {
+        self.reads.push(PassResource::Buffer(buffer.global_id()));
+        self
+    }
+
+    /// Declares that the pass writes `buffer`.
+    pub fn write_buffer(mut self, buffer: &Buffer) -> Self {
+        self.writes.push(PassResource::Buffer(buffer.global_id()));
+        self
+    }
+
+    /// Declares that the pass reads `texture`.
+    pub fn read_texture(mut self, texture: &Texture) -> Self {
+        self.reads.push(PassResource::Texture(texture.global_id()));
+        self
+    }
+
+    /// Declares that the pass writes `texture`.
+    pub fn write_texture(mut self, texture: &Texture) -> Self {
+        self.writes.push(PassResource::Texture(texture.global_id()));
+        self
+    }
+
+    /// Declares the resources of a bind group the pass uses, given the entries
+    /// of its layout and the entries it was created with.
+    ///
+    /// Read-only storage buffers and textures, uniform buffers and sampled
+    /// textures are read, other storage bindings are written.
+    pub fn bind_group(
+        mut self,
+        layout_entries: &[BindGroupLayoutEntry],
+        entries: &[BindGroupEntry<'_>],
+    ) -> Self {
+        for entry in entries {
+            let Some(layout_entry) = layout_entries
+                .iter()
+                .find(|layout_entry| layout_entry.binding == entry.binding)
+            else {
+                continue;
+            };
+            let writes = match layout_entry.ty {
+                BindingType::Buffer {
+                    ty: BufferBindingType::Storage { read_only },
+                    ..
+                } => !read_only,
+                BindingType::StorageTexture { access, .. } => {
+                    access != StorageTextureAccess::ReadOnly
+                }
+                BindingType::Buffer { .. }
+                | BindingType::Texture { .. }
+                | BindingType::YcbcrTexture { .. } => false,
+                BindingType::Sampler(_) | BindingType::AccelerationStructure => continue,
+            };
+            let list = if writes {
+                &mut self.writes
+            } else {
+                &mut self.reads
+            };
+            let view_resource = |view: &&TextureView| PassResource::Texture(view.texture);
+            match entry.resource {
+                BindingResource::Buffer(ref binding) => {
+                    list.push(PassResource::Buffer(binding.buffer.global_id()))
+                }
+                BindingResource::BufferArray(bindings) => list.extend(
+                    bindings
+                        .iter()
+                        .map(|binding| PassResource::Buffer(binding.buffer.global_id())),
+                ),
+                BindingResource::TextureView(ref view) => list.push(view_resource(view)),
+                BindingResource::TextureViewArray(views) => {
+                    list.extend(views.iter().map(view_resource))
+                }
+                BindingResource::Sampler(_) | BindingResource::SamplerArray(_) => {}
+            }
+        }
+        self
+    }
+
+    /// Returns the resources the pass only reads.
+    pub fn reads(&self) -> &[PassResource] {
+        &self.reads
+    }
+
+    /// Returns the resources the pass writes.
+    pub fn writes(&self) -> &[PassResource] {
+        &self.writes
+    }
+}
+
+/// The kind of a pass of a [`PassGraph`].
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum PassKind {
+    /// A render pass, or other work that has to stay on the main queue.
+    Render,
+    /// A compute pass, which can move to an async compute queue.
+    Compute,
+}
+
+/// The queue a pass is scheduled on by [`PassGraph::schedule`].
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum PassQueue {
+    /// The queue the passes were recorded for.
+    Main,
+    /// A second queue for compute work, like one created with
+    /// [`Device::create_queue`](crate::Device::create_queue).
+    AsyncCompute,
+}
+
+/// Collects the passes of a frame with the resources they use, to find out
+/// which ones depend on each other and which compute passes can run on an
+/// async compute queue.
+///
+/// Passes are added in the order they would be submitted on a single queue.
+/// A pass depends on the passes before it that write a resource it uses, and
+/// on the ones that read a resource it writes.
+///
+/// ```no_run
+/// # let simulation: wgpu::util::PassUsage = todo!();
+/// # let particles: wgpu::util::PassUsage = todo!();
+/// # let color: wgpu::util::PassUsage = todo!();
+/// use wgpu::util::{PassGraph, PassKind, PassQueue};
+///
+/// let mut graph = PassGraph::new();
+/// let simulate = graph.add_pass(PassKind::Compute, simulation);
+/// let draw_particles = graph.add_pass(PassKind::Render, particles);
+/// let draw_scene = graph.add_pass(PassKind::Render, color);
+/// let schedule = graph.schedule();
+/// if schedule.queue(simulate) == PassQueue::AsyncCompute {
+///     // Encode `simulate` for the async queue, and make the main queue wait
+///     // for it before the passes of `schedule.cross_queue_dependencies(draw_particles)`.
+/// }
+/// ```
+#[derive(Clone, Debug, Default)]
+pub struct PassGraph {
+    passes: Vec<(PassKind, PassUsage)>,
+}
+
+impl PassGraph {
+    /// Creates an empty graph.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Adds a pass after the ones added before, and returns its index.
+    pub fn add_pass(&mut self, kind: PassKind, usage: PassUsage) -> usize {
+        self.passes.push((kind, usage));
+        self.passes.len() - 1
+    }
+
+    /// Returns the number of passes in the graph.
+    pub fn len(&self) -> usize {
+        self.passes.len()
+    }
+
+    /// Returns `true` if there are no passes in the graph.
+    pub fn is_empty(&self) -> bool {
+        self.passes.is_empty()
+    }
+
+    /// Analyzes the dependencies between the passes and assigns them to queues.
+    ///
+    /// A compute pass moves to the async compute queue if it is independent
+    /// of at least one render pass, so that there is work to overlap it with.
+    pub fn schedule(&self) -> PassSchedule {
+        #[derive(Default)]
+        struct ResourceState {
+            last_write: Option<usize>,
+            reads_since_write: Vec<usize>,
+        }
+
+        let mut resources = HashMap::<PassResource, ResourceState>::new();
+        let mut dependencies = Vec::with_capacity(self.passes.len());
+        for (index, (_, usage)) in self.passes.iter().enumerate() {
+            let mut pass_dependencies = Vec::new();
+            for resource in &usage.reads {
+                let state = resources.entry(*resource).or_default();
+                pass_dependencies.extend(state.last_write);
+                state.reads_since_write.push(index);
+            }
+            for resource in &usage.writes {
+                let state = resources.entry(*resource).or_default();
+                pass_dependencies.extend(state.last_write);
+                pass_dependencies.extend(state.reads_since_write.drain(..));
+                state.last_write = Some(index);
+            }
+            pass_dependencies.retain(|&dependency| dependency != index);
+            pass_dependencies.sort_unstable();
+            pass_dependencies.dedup();
+            dependencies.push(pass_dependencies);
+        }
+
+        // `ancestors[pass][other]` is set if `pass` depends on `other`, directly or not.
+        let mut ancestors: Vec<Vec<bool>> = Vec::with_capacity(self.passes.len());
+        for (index, pass_dependencies) in dependencies.iter().enumerate() {
+            let mut pass_ancestors = vec![false; index];
+            for &dependency in pass_dependencies {
+                pass_ancestors[dependency] = true;
+                for (ancestor, &is_ancestor) in ancestors[dependency].iter().enumerate() {
+                    pass_ancestors[ancestor] |= is_ancestor;
+                }
+            }
+            ancestors.push(pass_ancestors);
+        }
+
+        let mut schedule = PassSchedule {
+            dependencies,
+            ancestors,
+            queues: Vec::with_capacity(self.passes.len()),
+        };
+        for (index, &(kind, _)) in self.passes.iter().enumerate() {
+            let queue = match kind {
+                PassKind::Render => PassQueue::Main,
+                PassKind::Compute => {
+                    let overlaps = self.passes.iter().enumerate().any(|(other, &(kind, _))| {
+                        kind == PassKind::Render && schedule.is_independent(index, other)
+                    });
+                    if overlaps {
+                        PassQueue::AsyncCompute
+                    } else {
+                        PassQueue::Main
+                    }
+                }
+            };
+            schedule.queues.push(queue);
+        }
+        schedule
+    }
+}
+
+/// The dependencies between the passes of a [`PassGraph`] and the queues they
+/// are scheduled on.
+#[derive(Clone, Debug)]
+pub struct PassSchedule {
+    dependencies: Vec<Vec<usize>>,
+    ancestors: Vec<Vec<bool>>,
+    queues: Vec<PassQueue>,
+}
+
+impl PassSchedule {
+    /// Returns the passes `pass` directly depends on, in order.
+    pub fn dependencies(&self, pass: usize) -> &[usize] {
+        &self.dependencies[pass]
+    }
+
+    /// Returns `true` if `pass` depends on `other`, directly or through other passes.
+    pub fn depends_on(&self, pass: usize, other: usize) -> bool {
+        self.ancestors[pass].get(other).copied().unwrap_or(false)
+    }
+
+    /// Returns `true` if neither of the passes depends on the other, so they
+    /// can run at the same time.
+    pub fn is_independent(&self, a: usize, b: usize) -> bool {
+        a != b && !self.depends_on(a, b) && !self.depends_on(b, a)
+    }
+
+    /// Returns the queue `pass` is scheduled on.
+    pub fn queue(&self, pass: usize) -> PassQueue {
+        self.queues[pass]
+    }
+
+    /// Returns the passes scheduled on `queue`, in the order to submit them.
+    pub fn passes(&self, queue: PassQueue) -> impl Iterator<Item = usize> + '_ {
+        self.queues
+            .iter()
+            .enumerate()
+            .filter(move |&(_, &pass_queue)| pass_queue == queue)
+            .map(|(pass, _)| pass)
+    }
+
+    /// Returns the passes `pass` directly depends on that are scheduled on
+    /// another queue, which the queue of `pass` has to wait for.
+    pub fn cross_queue_dependencies(&self, pass: usize) -> impl Iterator<Item = usize> + '_ {
+        let queue = self.queues[pass];
+        self.dependencies[pass]
+            .iter()
+            .copied()
+            .filter(move |&dependency| self.queues[dependency] != queue)
+    }
+}
@@ -0,0 +1,128 @@
+use std::sync::mpsc;
+
+use crate::{
+    Buffer, BufferAddress, BufferAsyncError, BufferAsyncErrorKind, BufferDescriptor, BufferSlice,
+    BufferUsages, CommandEncoderDescriptor, Device, Extent3d, ImageCopyBuffer, ImageCopyTexture,
+    ImageDataLayout, Maintain, MapMode, Queue, COPY_BYTES_PER_ROW_ALIGNMENT,
+};
+
+/// Copies `source` on `source_device` into `destination` at `destination_offset`,
+/// which belongs to the device of `destination_queue`.
+///
+/// Resources can't be used by a device other than the one that created them,
+/// so on multi-GPU systems this is the way to move data between adapters. The
+/// source is copied into a staging buffer on its device, which is mapped and
+/// written straight into the staging memory of [`Queue::write_buffer`] on the
+/// destination, so the data doesn't pass through memory of the application.
+///
+/// Blocks with [`Device::poll`] until the source is read, and fails if it
+/// couldn't be mapped. On the web, where polling doesn't wait, it always fails.
+///
+/// The destination is written by the next submission to `destination_queue`.
+pub fn copy_buffer_to_device(
+    source_device: &Device,
+    source_queue: &Queue,
+    source: &BufferSlice<'_>,
+    destination_queue: &Queue,
+    destination: &Buffer,
+    destination_offset: BufferAddress,
+) -> Result<(), BufferAsyncError> {
+    let size = match source.size {
+        Some(size) => size.get(),
+        None => source.buffer.size() - source.offset,
+    };
+    let staging = source_device.create_buffer(&BufferDescriptor {
+        label: Some("wgpu::util::copy_buffer_to_device"),
+        size,
+        usage: BufferUsages::COPY_DST | BufferUsages::MAP_READ,
+        mapped_at_creation: false,
+    });
+    let mut encoder = source_device.create_command_encoder(&CommandEncoderDescriptor {
+        label: Some("wgpu::util::copy_buffer_to_device"),
+    });
+    encoder.copy_buffer_to_buffer(source.buffer, source.offset, &staging, 0, size);
+    source_queue.submit(Some(encoder.finish()));
+
+    read_staging(source_device, &staging)?;
+    destination_queue.write_buffer(
+        destination,
+        destination_offset,
+        &staging.slice(..).get_mapped_range(),
+    );
+    Ok(())
+}
+
+/// Copies `size` texels from `source` on `source_device` into `destination`,
+/// which belongs to the device of `destination_queue`.
+///
+/// The texture counterpart of [`copy_buffer_to_device`], going through a
+/// staging buffer the same way and writing with [`Queue::write_texture`].
+///
+/// # Panics
+///
+/// - The aspect of `source` can't be copied into a buffer, like the depth aspect of
+///   [`TextureFormat::Depth24Plus`](crate::TextureFormat::Depth24Plus).
+pub fn copy_texture_to_device(
+    source_device: &Device,
+    source_queue: &Queue,
+    source: ImageCopyTexture<'_>,
+    destination_queue: &Queue,
+    destination: ImageCopyTexture<'_>,
+    size: Extent3d,
+) -> Result<(), BufferAsyncError> {
+    let format = source.texture.format();
+    let block_size = format
+        .block_copy_size(Some(source.aspect))
+        .expect("the aspect can't be copied into a buffer");
+    let (block_width, block_height) = format.block_dimensions();
+    let bytes_per_row = size.width.div_ceil(block_width) * block_size;
+    let bytes_per_row = bytes_per_row.next_multiple_of(COPY_BYTES_PER_ROW_ALIGNMENT);
+    let rows_per_image = size.height.div_ceil(block_height);
+    let layout = ImageDataLayout {
+        offset: 0,
+        bytes_per_row: Some(bytes_per_row),
+        rows_per_image: Some(rows_per_image),
+    };
+
+    let staging = source_device.create_buffer(&BufferDescriptor {
+        label: Some("wgpu::util::copy_texture_to_device"),
+        size: bytes_per_row as BufferAddress
+            * rows_per_image as BufferAddress
+            * size.depth_or_array_layers as BufferAddress,
+        usage: BufferUsages::COPY_DST | BufferUsages::MAP_READ,
+        mapped_at_creation: false,
+    });
+    let mut encoder = source_device.create_command_encoder(&CommandEncoderDescriptor {
+        label: Some("wgpu::util::copy_texture_to_device"),
+    });
+    encoder.copy_texture_to_buffer(
+        source,
+        ImageCopyBuffer {
+            buffer: &staging,
+            layout,
+        },
+        size,
+    );
+    source_queue.submit(Some(encoder.finish()));
+
+    read_staging(source_device, &staging)?;
+    destination_queue.write_texture(
+        destination,
+        &staging.slice(..).get_mapped_range(),
+        layout,
+        size,
+    );
+    Ok(())
+}
+
+/// Maps `staging` for reading, waiting for the device to finish writing it.
+fn read_staging(device: &Device, staging: &Buffer) -> Result<(), BufferAsyncError> {
+    let (sender, receiver) = mpsc::channel();
+    staging.slice(..).map_async(MapMode::Read, move |result| {
+        let _ = sender.send(result);
+    });
+    device.poll(Maintain::Wait);
+    receiver
+        .try_recv()
+        .unwrap_or(Err(BufferAsyncError::new(BufferAsyncErrorKind::Other)))
+}
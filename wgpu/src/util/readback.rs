@@ -0,0 +1,276 @@
+use std::{
+    future::Future,
+    ops::RangeBounds,
+    pin::Pin,
+    sync::Arc,
+    task::{Context, Poll, Waker},
+};
+
+use parking_lot::Mutex;
+
+use crate::{
+    util::align_to, Buffer, BufferAddress, BufferAsyncError, BufferDescriptor, BufferUsages,
+    CommandEncoderDescriptor, Device, Extent3d, ImageCopyBuffer, ImageCopyTexture, ImageDataLayout,
+    Maintain, MapMode, Origin3d, Queue, SubmissionIndex, WasmNotSend, COPY_BYTES_PER_ROW_ALIGNMENT,
+};
+
+/// Reads the bytes of `range` of `buffer` back, once the work submitted to
+/// `queue` before has completed.
+///
+/// The range is copied into a staging buffer by a submission of its own, which
+/// is mapped, copied out and unmapped again, so `buffer` only needs
+/// [`BufferUsages::COPY_SRC`]. This takes care of the steps that are easy to
+/// get wrong with [`BufferSlice::map_async`](crate::BufferSlice::map_async),
+/// like keeping the mapping alive until the callback and polling the device.
+///
+/// On native, the future waits for the copy with [`Device::poll`] the first
+/// time it is polled. On the web, it resolves from the event loop like any
+/// other mapping. It fails if the staging buffer couldn't be mapped, for
+/// example because the device was lost.
+///
+/// ```no_run
+/// # async fn example(device: &wgpu::Device, queue: &wgpu::Queue, buffer: &wgpu::Buffer) {
+/// let data = wgpu::util::read_buffer(device, queue, buffer, ..).await.unwrap();
+/// # }
+/// ```
+///
+/// # Panics
+///
+/// - The start or the size of `range` isn't a multiple of [`COPY_BUFFER_ALIGNMENT`].
+///
+/// [`COPY_BUFFER_ALIGNMENT`]: crate::COPY_BUFFER_ALIGNMENT
+pub fn read_buffer<'a, S: RangeBounds<BufferAddress>>(
+    device: &'a Device,
+    queue: &Queue,
+    buffer: &Buffer,
+    range: S,
+) -> impl Future<Output = Result<Vec<u8>, BufferAsyncError>> + WasmNotSend + 'a {
+    let slice = buffer.slice(range);
+    let offset = slice.offset;
+    let size = match slice.size {
+        Some(size) => size.get(),
+        None => buffer.size() - offset,
+    };
+
+    let staging = device.create_buffer(&BufferDescriptor {
+        label: Some("wgpu::util::read_buffer"),
+        size,
+        usage: BufferUsages::COPY_DST | BufferUsages::MAP_READ,
+        mapped_at_creation: false,
+    });
+    let mut encoder = device.create_command_encoder(&CommandEncoderDescriptor {
+        label: Some("wgpu::util::read_buffer"),
+    });
+    encoder.copy_buffer_to_buffer(buffer, offset, &staging, 0, size);
+    let submission = queue.submit(Some(encoder.finish()));
+
+    let mapping = MapRead::new(device, submission, &staging);
+
+    async move {
+        mapping.await?;
+        let data = staging.slice(..).get_mapped_range().to_vec();
+        staging.unmap();
+        Ok(data)
+    }
+}
+
+/// Reads the texels of a region of a texture back, once the work submitted to
+/// `queue` before has completed, with bounded staging memory.
+///
+/// `size` texels from `texture.origin` of `texture.mip_level` are returned
+/// tightly packed: blocks of [`TextureFormat::block_copy_size`] bytes, rows
+/// of blocks one after the other, then layers or depth slices.
+///
+/// Each layer of the region is split into tiles whose padded rows fit in
+/// `staging_budget` bytes, and `device.limits().max_buffer_size`. The tiles
+/// are copied one after the other through a single staging buffer, so even
+/// regions of the largest textures can be read without ever allocating more
+/// than `staging_budget` bytes of staging memory, at the cost of a submission
+/// and a wait per tile. The copies are submitted while the future is polled.
+///
+/// It fails if the staging buffer couldn't be mapped, for example because the
+/// device was lost.
+///
+/// ```no_run
+/// # async fn example(device: &wgpu::Device, queue: &wgpu::Queue, texture: &wgpu::Texture) {
+/// let size = wgpu::Extent3d { width: 4096, height: 4096, depth_or_array_layers: 1 };
+/// let texels = wgpu::util::read_texture(device, queue, texture.as_image_copy(), size, 1 << 20)
+///     .await
+///     .unwrap();
+/// # }
+/// ```
+///
+/// # Panics
+///
+/// - `texture` doesn't have [`TextureUsages::COPY_SRC`](crate::TextureUsages::COPY_SRC).
+/// - The format of `texture` can't be copied from `texture.aspect`, see
+///   [`TextureFormat::block_copy_size`].
+/// - The origin or the size of the region isn't a multiple of the block
+///   dimensions of the format, or the region isn't within the mip level.
+/// - `staging_budget` is smaller than [`COPY_BYTES_PER_ROW_ALIGNMENT`].
+///
+/// [`TextureFormat::block_copy_size`]: crate::TextureFormat::block_copy_size
+pub fn read_texture<'a>(
+    device: &'a Device,
+    queue: &'a Queue,
+    texture: ImageCopyTexture<'a>,
+    size: Extent3d,
+    staging_budget: BufferAddress,
+) -> impl Future<Output = Result<Vec<u8>, BufferAsyncError>> + WasmNotSend + 'a {
+    assert!(
+        staging_budget >= COPY_BYTES_PER_ROW_ALIGNMENT as BufferAddress,
+        "The staging budget has to fit a row of {COPY_BYTES_PER_ROW_ALIGNMENT} bytes"
+    );
+    let format = texture.texture.format();
+    let block_size = format
+        .block_copy_size(Some(texture.aspect))
+        .unwrap_or_else(|| panic!("{:?} of {format:?} can't be copied", texture.aspect))
+        as BufferAddress;
+    let (block_width, block_height) = format.block_dimensions();
+    let blocks_x = size.width / block_width;
+    let blocks_y = size.height / block_height;
+    let row_size = blocks_x as BufferAddress * block_size;
+
+    let budget = staging_budget.min(device.limits().max_buffer_size);
+    let padded_row_size = |blocks: u32| {
+        align_to(
+            blocks as BufferAddress * block_size,
+            COPY_BYTES_PER_ROW_ALIGNMENT as BufferAddress,
+        )
+    };
+    // The padded rows of tiles this wide are at most the aligned budget.
+    let tile_blocks_x = if padded_row_size(blocks_x) <= budget {
+        blocks_x
+    } else {
+        let aligned_budget = budget - budget % COPY_BYTES_PER_ROW_ALIGNMENT as BufferAddress;
+        (aligned_budget / block_size) as u32
+    }
+    .max(1);
+    let bytes_per_row = padded_row_size(tile_blocks_x);
+    let tile_blocks_y = (budget / bytes_per_row).clamp(1, blocks_y.max(1) as BufferAddress) as u32;
+
+    async move {
+        let mut data = vec![
+            0;
+            (row_size * blocks_y as BufferAddress) as usize
+                * size.depth_or_array_layers as usize
+        ];
+        if data.is_empty() {
+            return Ok(data);
+        }
+
+        let staging = device.create_buffer(&BufferDescriptor {
+            label: Some("wgpu::util::read_texture"),
+            size: bytes_per_row * tile_blocks_y as BufferAddress,
+            usage: BufferUsages::COPY_DST | BufferUsages::MAP_READ,
+            mapped_at_creation: false,
+        });
+        for layer in 0..size.depth_or_array_layers {
+            for y in (0..blocks_y).step_by(tile_blocks_y as usize) {
+                for x in (0..blocks_x).step_by(tile_blocks_x as usize) {
+                    let tile_x = tile_blocks_x.min(blocks_x - x);
+                    let tile_y = tile_blocks_y.min(blocks_y - y);
+
+                    let mut encoder = device.create_command_encoder(&CommandEncoderDescriptor {
+                        label: Some("wgpu::util::read_texture"),
+                    });
+                    encoder.copy_texture_to_buffer(
+                        ImageCopyTexture {
+                            origin: Origin3d {
+                                x: texture.origin.x + x * block_width,
+                                y: texture.origin.y + y * block_height,
+                                z: texture.origin.z + layer,
+                            },
+                            ..texture
+                        },
+                        ImageCopyBuffer {
+                            buffer: &staging,
+                            layout: ImageDataLayout {
+                                offset: 0,
+                                bytes_per_row: Some(bytes_per_row as u32),
+                                rows_per_image: None,
+                            },
+                        },
+                        Extent3d {
+                            width: tile_x * block_width,
+                            height: tile_y * block_height,
+                            depth_or_array_layers: 1,
+                        },
+                    );
+                    let submission = queue.submit(Some(encoder.finish()));
+                    MapRead::new(device, submission, &staging).await?;
+
+                    {
+                        let tile = staging.slice(..).get_mapped_range();
+                        let tile_row_size = (tile_x as BufferAddress * block_size) as usize;
+                        for row in 0..tile_y {
+                            let source = row as usize * bytes_per_row as usize;
+                            let target = ((layer * blocks_y + y + row) as BufferAddress * row_size
+                                + x as BufferAddress * block_size)
+                                as usize;
+                            data[target..target + tile_row_size]
+                                .copy_from_slice(&tile[source..source + tile_row_size]);
+                        }
+                    }
+                    staging.unmap();
+                }
+            }
+        }
+        Ok(data)
+    }
+}
+
+/// The state shared by a [`MapRead`] and its mapping callback.
+#[derive(Default)]
+struct MapReadState {
+    result: Option<Result<(), BufferAsyncError>>,
+    waker: Option<Waker>,
+}
+
+/// A future resolving once a staging buffer is mapped for reading.
+struct MapRead<'a> {
+    device: &'a Device,
+    /// Taken once the device has been polled for the copy.
+    submission: Option<SubmissionIndex>,
+    state: Arc<Mutex<MapReadState>>,
+}
+
+impl<'a> MapRead<'a> {
+    /// Maps all of `staging` for reading, once `submission` is done with it.
+    fn new(device: &'a Device, submission: SubmissionIndex, staging: &Buffer) -> Self {
+        let state = Arc::new(Mutex::new(MapReadState::default()));
+        let callback_state = Arc::clone(&state);
+        staging.slice(..).map_async(MapMode::Read, move |result| {
+            let mut state = callback_state.lock();
+            state.result = Some(result);
+            if let Some(waker) = state.waker.take() {
+                waker.wake();
+            }
+        });
+
+        Self {
+            device,
+            submission: Some(submission),
+            state,
+        }
+    }
+}
+
+impl Future for MapRead<'_> {
+    type Output = Result<(), BufferAsyncError>;
+
+    fn poll(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        if let Some(submission) = self.submission.take() {
+            self.device.poll(Maintain::wait_for(submission));
+        }
+
+        let mut state = self.state.lock();
+        match state.result.take() {
+            Some(result) => Poll::Ready(result),
+            None => {
+                state.waker = Some(cx.waker().clone());
+                Poll::Pending
+            }
+        }
+    }
+}
@@ -0,0 +1,771 @@
+use std::{collections::HashMap, fmt, marker::PhantomData, rc::Rc, sync::Arc};
+
+use crate::{
+    BindGroup, BindGroupDescriptor, BindGroupEntry, BindGroupLayout, BindGroupLayoutDescriptor,
+    BindGroupLayoutEntry, BindingResource, Buffer, BufferAddress, BufferBinding, BufferDescriptor,
+    BufferSize, ComputePipeline, Device, PipelineLayout, PipelineLayoutDescriptor,
+    PushConstantRange, Queue, RenderPipeline, Sampler, SamplerDescriptor, ShaderModule,
+    ShaderModuleDescriptor, ShaderSource, Texture, TextureDescriptor, TextureFormat, TextureView,
+    TextureViewDescriptor,
+};
+
+/// A resource created through a [`ResourceRegistry`].
+///
+/// Handles stay valid when the registry rebuilds its resources on another
+/// device, so they can be kept instead of the resources themselves.
+pub struct RegistryHandle<T> {
+    index: usize,
+    marker: PhantomData<fn() -> T>,
+}
+
+impl<T> RegistryHandle<T> {
+    fn new(index: usize) -> Self {
+        Self {
+            index,
+            marker: PhantomData,
+        }
+    }
+}
+
+impl<T> Clone for RegistryHandle<T> {
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+
+impl<T> Copy for RegistryHandle<T> {}
+
+impl<T> PartialEq for RegistryHandle<T> {
+    fn eq(&self, other: &Self) -> bool {
+        self.index == other.index
+    }
+}
+
+impl<T> Eq for RegistryHandle<T> {}
+
+impl<T> std::hash::Hash for RegistryHandle<T> {
+    fn hash<H: std::hash::Hasher>(&self, state: &mut H) {
+        self.index.hash(state);
+    }
+}
+
+impl<T> fmt::Debug for RegistryHandle<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_tuple("RegistryHandle").field(&self.index).finish()
+    }
+}
+
+/// A resource bound by a bind group of a [`ResourceRegistry`].
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum RegistryBindingResource {
+    /// A range of a buffer, like [`BufferBinding`].
+    Buffer {
+        /// The buffer to bind.
+        buffer: RegistryHandle<Buffer>,
+        /// The offset of the range in bytes.
+        offset: BufferAddress,
+        /// The size of the range, or `None` for the rest of the buffer.
+        size: Option<BufferSize>,
+    },
+    /// A sampler.
+    Sampler(RegistryHandle<Sampler>),
+    /// A texture view.
+    TextureView(RegistryHandle<TextureView>),
+}
+
+/// An entry of a bind group of a [`ResourceRegistry`], like [`BindGroupEntry`].
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct RegistryBindGroupEntry {
+    /// The binding of the entry in the layout.
+    pub binding: u32,
+    /// The resource to bind.
+    pub resource: RegistryBindingResource,
+}
+
+type BufferContent = Box<dyn Fn(&Queue, &Buffer)>;
+type TextureContent = Box<dyn Fn(&Queue, &Texture)>;
+type RenderPipelineFn = Rc<dyn Fn(&Device, &ResourceRegistry) -> RenderPipeline>;
+type ComputePipelineFn = Rc<dyn Fn(&Device, &ResourceRegistry) -> ComputePipeline>;
+
+struct Record<D, T> {
+    desc: D,
+    resource: T,
+}
+
+struct BufferDesc {
+    desc: wgt::BufferDescriptor<Option<String>>,
+    content: Option<BufferContent>,
+}
+
+struct TextureDesc {
+    desc: wgt::TextureDescriptor<Option<String>, Vec<TextureFormat>>,
+    content: Option<TextureContent>,
+}
+
+struct TextureViewDesc {
+    texture: RegistryHandle<Texture>,
+    label: Option<String>,
+    desc: TextureViewDescriptor<'static>,
+}
+
+#[derive(Clone)]
+struct SamplerDesc {
+    label: Option<String>,
+    desc: SamplerDescriptor<'static>,
+}
+
+struct ShaderModuleDesc {
+    label: Option<String>,
+    source: ShaderSource<'static>,
+}
+
+#[derive(Clone)]
+struct BindGroupLayoutDesc {
+    label: Option<String>,
+    entries: Vec<BindGroupLayoutEntry>,
+}
+
+#[derive(Clone)]
+struct PipelineLayoutDesc {
+    label: Option<String>,
+    bind_group_layouts: Vec<RegistryHandle<BindGroupLayout>>,
+    push_constant_ranges: Vec<PushConstantRange>,
+}
+
+#[derive(Clone)]
+struct BindGroupDesc {
+    label: Option<String>,
+    layout: RegistryHandle<BindGroupLayout>,
+    entries: Vec<RegistryBindGroupEntry>,
+}
+
+/// Creates resources and records how they were created, so they can all be
+/// created again on another device after the device is lost.
+///
+/// Surviving a device loss, like one caused by a GPU reset, means creating
+/// every resource again on a new device, with the same descriptors and the
+/// same links between them. Resources created through the registry are
+/// referred to with [`RegistryHandle`]s, which still point to the right
+/// resources after [`ResourceRegistry::rebuild_on`]:
+///
+/// - Buffers and textures can be given a callback that fills them, which is
+///   called once they are created, and again after every rebuild.
+/// - Texture views, bind groups and pipeline layouts refer to other resources
+///   of the registry by handle.
+/// - Pipelines are created by a callback, which gets the registry to look up
+///   the shader modules and layouts it needs.
+///
+/// Resources are rebuilt by kind, in the order of the methods creating them.
+/// They stay alive as long as the registry.
+///
+/// ```no_run
+/// # let (device, queue, new_device, new_queue): (wgpu::Device, wgpu::Queue, wgpu::Device, wgpu::Queue) = todo!();
+/// use wgpu::util::ResourceRegistry;
+///
+/// let mut registry = ResourceRegistry::new();
+/// let vertices = registry.create_buffer_with_content(
+///     &device,
+///     &queue,
+///     &wgpu::BufferDescriptor {
+///         label: Some("vertices"),
+///         size: 1024,
+///         usage: wgpu::BufferUsages::VERTEX | wgpu::BufferUsages::COPY_DST,
+///         mapped_at_creation: false,
+///     },
+///     |queue, buffer| queue.write_buffer(buffer, 0, &[0; 1024]),
+/// );
+///
+/// // After the device was lost:
+/// registry.rebuild_on(&new_device, &new_queue);
+/// let buffer = registry.buffer(vertices);
+/// ```
+#[derive(Default)]
+pub struct ResourceRegistry {
+    buffers: Vec<Record<BufferDesc, Buffer>>,
+    textures: Vec<Record<TextureDesc, Texture>>,
+    texture_views: Vec<Record<TextureViewDesc, TextureView>>,
+    samplers: Vec<Record<SamplerDesc, Arc<Sampler>>>,
+    shader_modules: Vec<Record<ShaderModuleDesc, ShaderModule>>,
+    bind_group_layouts: Vec<Record<BindGroupLayoutDesc, Arc<BindGroupLayout>>>,
+    pipeline_layouts: Vec<Record<PipelineLayoutDesc, Arc<PipelineLayout>>>,
+    bind_groups: Vec<Record<BindGroupDesc, BindGroup>>,
+    render_pipelines: Vec<Record<RenderPipelineFn, RenderPipeline>>,
+    compute_pipelines: Vec<Record<ComputePipelineFn, ComputePipeline>>,
+}
+
+impl fmt::Debug for ResourceRegistry {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("ResourceRegistry")
+            .field("buffers", &self.buffers.len())
+            .field("textures", &self.textures.len())
+            .field("texture_views", &self.texture_views.len())
+            .field("samplers", &self.samplers.len())
+            .field("shader_modules", &self.shader_modules.len())
+            .field("bind_group_layouts", &self.bind_group_layouts.len())
+            .field("pipeline_layouts", &self.pipeline_layouts.len())
+            .field("bind_groups", &self.bind_groups.len())
+            .field("render_pipelines", &self.render_pipelines.len())
+            .field("compute_pipelines", &self.compute_pipelines.len())
+            .finish()
+    }
+}
+
+/// The descriptors of the samplers, bind group layouts, pipeline layouts, bind
+/// groups and pipelines of a [`ResourceRegistry`], taken by
+/// [`ResourceRegistry::snapshot`].
+///
+/// Snapshots hold no GPU resources, and can be restored any number of times.
+#[derive(Clone)]
+pub struct RegistrySnapshot {
+    samplers: Vec<SamplerDesc>,
+    bind_group_layouts: Vec<BindGroupLayoutDesc>,
+    pipeline_layouts: Vec<PipelineLayoutDesc>,
+    bind_groups: Vec<BindGroupDesc>,
+    render_pipelines: Vec<RenderPipelineFn>,
+    compute_pipelines: Vec<ComputePipelineFn>,
+}
+
+impl fmt::Debug for RegistrySnapshot {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("RegistrySnapshot")
+            .field("samplers", &self.samplers.len())
+            .field("bind_group_layouts", &self.bind_group_layouts.len())
+            .field("pipeline_layouts", &self.pipeline_layouts.len())
+            .field("bind_groups", &self.bind_groups.len())
+            .field("render_pipelines", &self.render_pipelines.len())
+            .field("compute_pipelines", &self.compute_pipelines.len())
+            .finish()
+    }
+}
+
+fn push<D, T>(records: &mut Vec<Record<D, T>>, desc: D, resource: T) -> RegistryHandle<T> {
+    records.push(Record { desc, resource });
+    RegistryHandle::new(records.len() - 1)
+}
+
+impl ResourceRegistry {
+    /// Creates an empty registry.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Creates a buffer. Its contents are lost when the device is.
+    ///
+    /// `desc.mapped_at_creation` only applies to this first creation, use
+    /// [`ResourceRegistry::create_buffer_with_content`] to fill the buffer again
+    /// after a rebuild.
+    pub fn create_buffer(
+        &mut self,
+        device: &Device,
+        desc: &BufferDescriptor<'_>,
+    ) -> RegistryHandle<Buffer> {
+        let buffer = device.create_buffer(desc);
+        let desc = BufferDesc {
+            desc: wgt::BufferDescriptor {
+                mapped_at_creation: false,
+                ..desc.map_label(|label| label.map(str::to_owned))
+            },
+            content: None,
+        };
+        push(&mut self.buffers, desc, buffer)
+    }
+
+    /// Creates a buffer, and fills it by calling `content`, now and after every
+    /// rebuild.
+    pub fn create_buffer_with_content(
+        &mut self,
+        device: &Device,
+        queue: &Queue,
+        desc: &BufferDescriptor<'_>,
+        content: impl Fn(&Queue, &Buffer) + 'static,
+    ) -> RegistryHandle<Buffer> {
+        let handle = self.create_buffer(device, desc);
+        let record = &mut self.buffers[handle.index];
+        content(queue, &record.resource);
+        record.desc.content = Some(Box::new(content));
+        handle
+    }
+
+    /// Creates a texture. Its contents are lost when the device is.
+    pub fn create_texture(
+        &mut self,
+        device: &Device,
+        desc: &TextureDescriptor<'_>,
+    ) -> RegistryHandle<Texture> {
+        let texture = device.create_texture(desc);
+        let desc = TextureDesc {
+            desc: desc.map_label_and_view_formats(
+                |label| label.map(str::to_owned),
+                |view_formats| view_formats.to_vec(),
+            ),
+            content: None,
+        };
+        push(&mut self.textures, desc, texture)
+    }
+
+    /// Creates a texture, and fills it by calling `content`, now and after every
+    /// rebuild.
+    pub fn create_texture_with_content(
+        &mut self,
+        device: &Device,
+        queue: &Queue,
+        desc: &TextureDescriptor<'_>,
+        content: impl Fn(&Queue, &Texture) + 'static,
+    ) -> RegistryHandle<Texture> {
+        let handle = self.create_texture(device, desc);
+        let record = &mut self.textures[handle.index];
+        content(queue, &record.resource);
+        record.desc.content = Some(Box::new(content));
+        handle
+    }
+
+    /// Creates a view of a texture of the registry.
+    pub fn create_texture_view(
+        &mut self,
+        texture: RegistryHandle<Texture>,
+        desc: &TextureViewDescriptor<'_>,
+    ) -> RegistryHandle<TextureView> {
+        let view = self.texture(texture).create_view(desc);
+        let desc = TextureViewDesc {
+            texture,
+            label: desc.label.map(str::to_owned),
+            desc: TextureViewDescriptor {
+                label: None,
+                format: desc.format,
+                dimension: desc.dimension,
+                aspect: desc.aspect,
+                base_mip_level: desc.base_mip_level,
+                mip_level_count: desc.mip_level_count,
+                base_array_layer: desc.base_array_layer,
+                array_layer_count: desc.array_layer_count,
+                swizzle: desc.swizzle,
+            },
+        };
+        push(&mut self.texture_views, desc, view)
+    }
+
+    /// Creates a sampler.
+    pub fn create_sampler(
+        &mut self,
+        device: &Device,
+        desc: &SamplerDescriptor<'_>,
+    ) -> RegistryHandle<Sampler> {
+        let sampler = device.create_sampler(desc);
+        let desc = SamplerDesc {
+            label: desc.label.map(str::to_owned),
+            desc: SamplerDescriptor {
+                label: None,
+                address_mode_u: desc.address_mode_u,
+                address_mode_v: desc.address_mode_v,
+                address_mode_w: desc.address_mode_w,
+                mag_filter: desc.mag_filter,
+                min_filter: desc.min_filter,
+                mipmap_filter: desc.mipmap_filter,
+                lod_min_clamp: desc.lod_min_clamp,
+                lod_max_clamp: desc.lod_max_clamp,
+                lod_bias: desc.lod_bias,
+                compare: desc.compare,
+                anisotropy_clamp: desc.anisotropy_clamp,
+                border_color: desc.border_color,
+                reduction_mode: desc.reduction_mode,
+            },
+        };
+        push(&mut self.samplers, desc, Arc::new(sampler))
+    }
+
+    /// Creates a shader module, keeping its source to compile it again on
+    /// rebuilds.
+    pub fn create_shader_module(
+        &mut self,
+        device: &Device,
+        label: Option<&str>,
+        source: ShaderSource<'static>,
+    ) -> RegistryHandle<ShaderModule> {
+        let module = device.create_shader_module(ShaderModuleDescriptor {
+            label,
+            source: source.clone(),
+            diagnostic_filters: &[],
+        });
+        let desc = ShaderModuleDesc {
+            label: label.map(str::to_owned),
+            source,
+        };
+        push(&mut self.shader_modules, desc, module)
+    }
+
+    /// Creates a bind group layout.
+    pub fn create_bind_group_layout(
+        &mut self,
+        device: &Device,
+        desc: &BindGroupLayoutDescriptor<'_>,
+    ) -> RegistryHandle<BindGroupLayout> {
+        let layout = device.create_bind_group_layout(desc);
+        let desc = BindGroupLayoutDesc {
+            label: desc.label.map(str::to_owned),
+            entries: desc.entries.to_vec(),
+        };
+        push(&mut self.bind_group_layouts, desc, Arc::new(layout))
+    }
+
+    /// Creates a pipeline layout out of bind group layouts of the registry.
+    pub fn create_pipeline_layout(
+        &mut self,
+        device: &Device,
+        label: Option<&str>,
+        bind_group_layouts: &[RegistryHandle<BindGroupLayout>],
+        push_constant_ranges: &[PushConstantRange],
+    ) -> RegistryHandle<PipelineLayout> {
+        let desc = PipelineLayoutDesc {
+            label: label.map(str::to_owned),
+            bind_group_layouts: bind_group_layouts.to_vec(),
+            push_constant_ranges: push_constant_ranges.to_vec(),
+        };
+        let layout = self.build_pipeline_layout(device, &desc);
+        push(&mut self.pipeline_layouts, desc, Arc::new(layout))
+    }
+
+    /// Creates a bind group out of resources of the registry.
+    pub fn create_bind_group(
+        &mut self,
+        device: &Device,
+        label: Option<&str>,
+        layout: RegistryHandle<BindGroupLayout>,
+        entries: &[RegistryBindGroupEntry],
+    ) -> RegistryHandle<BindGroup> {
+        let desc = BindGroupDesc {
+            label: label.map(str::to_owned),
+            layout,
+            entries: entries.to_vec(),
+        };
+        let bind_group = self.build_bind_group(device, &desc);
+        push(&mut self.bind_groups, desc, bind_group)
+    }
+
+    /// Creates a render pipeline by calling `create`, now and on every rebuild.
+    ///
+    /// `create` looks up the shader modules and layouts of the pipeline in the
+    /// registry it is given, after the other kinds of resources are rebuilt.
+    pub fn create_render_pipeline(
+        &mut self,
+        device: &Device,
+        create: impl Fn(&Device, &ResourceRegistry) -> RenderPipeline + 'static,
+    ) -> RegistryHandle<RenderPipeline> {
+        let pipeline = create(device, self);
+        push(&mut self.render_pipelines, Rc::new(create), pipeline)
+    }
+
+    /// Creates a compute pipeline by calling `create`, now and on every rebuild,
+    /// like [`ResourceRegistry::create_render_pipeline`].
+    pub fn create_compute_pipeline(
+        &mut self,
+        device: &Device,
+        create: impl Fn(&Device, &ResourceRegistry) -> ComputePipeline + 'static,
+    ) -> RegistryHandle<ComputePipeline> {
+        let pipeline = create(device, self);
+        push(&mut self.compute_pipelines, Rc::new(create), pipeline)
+    }
+
+    /// Creates all the resources of the registry again on `device`, and fills
+    /// the buffers and textures that have content callbacks with `queue`.
+    ///
+    /// The resources of the old device are dropped. Handles refer to the new
+    /// resources afterwards.
+    pub fn rebuild_on(&mut self, device: &Device, queue: &Queue) {
+        for record in &mut self.buffers {
+            let desc = &record.desc.desc;
+            record.resource = device.create_buffer(&desc.map_label(|label| label.as_deref()));
+        }
+        for record in &mut self.textures {
+            let desc = &record.desc.desc;
+            record.resource = device.create_texture(&desc.map_label_and_view_formats(
+                |label| label.as_deref(),
+                |view_formats| view_formats.as_slice(),
+            ));
+        }
+        for record in &mut self.texture_views {
+            let desc = &record.desc;
+            record.resource =
+                self.textures[desc.texture.index]
+                    .resource
+                    .create_view(&TextureViewDescriptor {
+                        label: desc.label.as_deref(),
+                        ..desc.desc.clone()
+                    });
+        }
+        for record in &mut self.samplers {
+            let desc = &record.desc;
+            record.resource = Arc::new(device.create_sampler(&SamplerDescriptor {
+                label: desc.label.as_deref(),
+                ..desc.desc.clone()
+            }));
+        }
+        for record in &mut self.shader_modules {
+            let desc = &record.desc;
+            record.resource = device.create_shader_module(ShaderModuleDescriptor {
+                label: desc.label.as_deref(),
+                source: desc.source.clone(),
+                diagnostic_filters: &[],
+            });
+        }
+        for record in &mut self.bind_group_layouts {
+            let desc = &record.desc;
+            record.resource =
+                Arc::new(device.create_bind_group_layout(&BindGroupLayoutDescriptor {
+                    label: desc.label.as_deref(),
+                    entries: &desc.entries,
+                }));
+        }
+        // These refer to other resources of the registry, so they are collected
+        // before they replace the old ones.
+        let pipeline_layouts: Vec<_> = self
+            .pipeline_layouts
+            .iter()
+            .map(|record| Arc::new(self.build_pipeline_layout(device, &record.desc)))
+            .collect();
+        for (record, layout) in self.pipeline_layouts.iter_mut().zip(pipeline_layouts) {
+            record.resource = layout;
+        }
+        let bind_groups: Vec<_> = self
+            .bind_groups
+            .iter()
+            .map(|record| self.build_bind_group(device, &record.desc))
+            .collect();
+        for (record, bind_group) in self.bind_groups.iter_mut().zip(bind_groups) {
+            record.resource = bind_group;
+        }
+        let render_pipelines: Vec<_> = self
+            .render_pipelines
+            .iter()
+            .map(|record| (record.desc)(device, self))
+            .collect();
+        for (record, pipeline) in self.render_pipelines.iter_mut().zip(render_pipelines) {
+            record.resource = pipeline;
+        }
+        let compute_pipelines: Vec<_> = self
+            .compute_pipelines
+            .iter()
+            .map(|record| (record.desc)(device, self))
+            .collect();
+        for (record, pipeline) in self.compute_pipelines.iter_mut().zip(compute_pipelines) {
+            record.resource = pipeline;
+        }
+
+        for record in &self.buffers {
+            if let Some(ref content) = record.desc.content {
+                content(queue, &record.resource);
+            }
+        }
+        for record in &self.textures {
+            if let Some(ref content) = record.desc.content {
+                content(queue, &record.resource);
+            }
+        }
+    }
+
+    /// Takes a snapshot of the descriptors of the samplers, bind group layouts,
+    /// pipeline layouts, bind groups and pipelines of the registry, for
+    /// [`ResourceRegistry::restore`] to create them again.
+    ///
+    /// Buffers, textures, texture views and shader modules aren't part of
+    /// snapshots, they are referred to by handle and stay as they are.
+    pub fn snapshot(&self) -> RegistrySnapshot {
+        fn descs<D: Clone, T>(records: &[Record<D, T>]) -> Vec<D> {
+            records.iter().map(|record| record.desc.clone()).collect()
+        }
+        RegistrySnapshot {
+            samplers: descs(&self.samplers),
+            bind_group_layouts: descs(&self.bind_group_layouts),
+            pipeline_layouts: descs(&self.pipeline_layouts),
+            bind_groups: descs(&self.bind_groups),
+            render_pipelines: descs(&self.render_pipelines),
+            compute_pipelines: descs(&self.compute_pipelines),
+        }
+    }
+
+    /// Drops the samplers, bind group layouts, pipeline layouts, bind groups and
+    /// pipelines of the registry, and creates the ones of `snapshot` on
+    /// `device`, e.g. to tear down and rebuild the state of a level when it's
+    /// reloaded.
+    ///
+    /// Handles of these kinds taken before the snapshot refer to the restored
+    /// resources afterwards, the ones taken after it are invalid. Identical
+    /// samplers, bind group layouts and pipeline layouts are only created once
+    /// and shared by their handles, with the label of the first one.
+    ///
+    /// The buffers, texture views and shader modules `snapshot` refers to have
+    /// to be part of the registry.
+    pub fn restore(&mut self, device: &Device, snapshot: &RegistrySnapshot) {
+        // Dependent resources go first, so the old ones are all dropped before
+        // the new ones are created.
+        self.compute_pipelines.clear();
+        self.render_pipelines.clear();
+        self.bind_groups.clear();
+        self.pipeline_layouts.clear();
+        self.bind_group_layouts.clear();
+        self.samplers.clear();
+
+        // Samplers can't be hashed because of their LOD clamps, and are few.
+        let mut samplers: Vec<(&SamplerDescriptor<'static>, Arc<Sampler>)> = Vec::new();
+        for desc in &snapshot.samplers {
+            let sampler = match samplers.iter().find(|&&(other, _)| *other == desc.desc) {
+                Some((_, sampler)) => Arc::clone(sampler),
+                None => {
+                    let sampler = Arc::new(device.create_sampler(&SamplerDescriptor {
+                        label: desc.label.as_deref(),
+                        ..desc.desc.clone()
+                    }));
+                    samplers.push((&desc.desc, Arc::clone(&sampler)));
+                    sampler
+                }
+            };
+            push(&mut self.samplers, desc.clone(), sampler);
+        }
+
+        let mut bind_group_layouts = HashMap::new();
+        for desc in &snapshot.bind_group_layouts {
+            let layout = bind_group_layouts
+                .entry(desc.entries.as_slice())
+                .or_insert_with(|| {
+                    Arc::new(device.create_bind_group_layout(&BindGroupLayoutDescriptor {
+                        label: desc.label.as_deref(),
+                        entries: &desc.entries,
+                    }))
+                });
+            push(
+                &mut self.bind_group_layouts,
+                desc.clone(),
+                Arc::clone(layout),
+            );
+        }
+
+        // Pipeline layouts are identical if their bind group layouts are the
+        // same shared ones.
+        let mut pipeline_layouts = HashMap::new();
+        for desc in &snapshot.pipeline_layouts {
+            let key = (
+                desc.bind_group_layouts
+                    .iter()
+                    .map(|&layout| Arc::as_ptr(&self.bind_group_layouts[layout.index].resource))
+                    .collect::<Vec<_>>(),
+                desc.push_constant_ranges.clone(),
+            );
+            let layout = match pipeline_layouts.get(&key) {
+                Some(layout) => Arc::clone(layout),
+                None => {
+                    let layout = Arc::new(self.build_pipeline_layout(device, desc));
+                    pipeline_layouts.insert(key, Arc::clone(&layout));
+                    layout
+                }
+            };
+            push(&mut self.pipeline_layouts, desc.clone(), layout);
+        }
+
+        for desc in &snapshot.bind_groups {
+            let bind_group = self.build_bind_group(device, desc);
+            push(&mut self.bind_groups, desc.clone(), bind_group);
+        }
+        for create in &snapshot.render_pipelines {
+            let pipeline = create(device, self);
+            push(&mut self.render_pipelines, Rc::clone(create), pipeline);
+        }
+        for create in &snapshot.compute_pipelines {
+            let pipeline = create(device, self);
+            push(&mut self.compute_pipelines, Rc::clone(create), pipeline);
+        }
+    }
+
+    fn build_pipeline_layout(&self, device: &Device, desc: &PipelineLayoutDesc) -> PipelineLayout {
+        let bind_group_layouts: Vec<_> = desc
+            .bind_group_layouts
+            .iter()
+            .map(|&layout| self.bind_group_layout(layout))
+            .collect();
+        device.create_pipeline_layout(&PipelineLayoutDescriptor {
+            label: desc.label.as_deref(),
+            bind_group_layouts: &bind_group_layouts,
+            push_constant_ranges: &desc.push_constant_ranges,
+        })
+    }
+
+    fn build_bind_group(&self, device: &Device, desc: &BindGroupDesc) -> BindGroup {
+        let entries: Vec<_> = desc
+            .entries
+            .iter()
+            .map(|entry| BindGroupEntry {
+                binding: entry.binding,
+                resource: match entry.resource {
+                    RegistryBindingResource::Buffer {
+                        buffer,
+                        offset,
+                        size,
+                    } => BindingResource::Buffer(BufferBinding {
+                        buffer: self.buffer(buffer),
+                        offset,
+                        size,
+                    }),
+                    RegistryBindingResource::Sampler(sampler) => {
+                        BindingResource::Sampler(self.sampler(sampler))
+                    }
+                    RegistryBindingResource::TextureView(view) => {
+                        BindingResource::TextureView(self.texture_view(view))
+                    }
+                },
+            })
+            .collect();
+        device.create_bind_group(&BindGroupDescriptor {
+            label: desc.label.as_deref(),
+            layout: self.bind_group_layout(desc.layout),
+            entries: &entries,
+        })
+    }
+
+    /// Returns the buffer of `handle`.
+    pub fn buffer(&self, handle: RegistryHandle<Buffer>) -> &Buffer {
+        &self.buffers[handle.index].resource
+    }
+
+    /// Returns the texture of `handle`.
+    pub fn texture(&self, handle: RegistryHandle<Texture>) -> &Texture {
+        &self.textures[handle.index].resource
+    }
+
+    /// Returns the texture view of `handle`.
+    pub fn texture_view(&self, handle: RegistryHandle<TextureView>) -> &TextureView {
+        &self.texture_views[handle.index].resource
+    }
+
+    /// Returns the sampler of `handle`.
+    pub fn sampler(&self, handle: RegistryHandle<Sampler>) -> &Sampler {
+        &self.samplers[handle.index].resource
+    }
+
+    /// Returns the shader module of `handle`.
+    pub fn shader_module(&self, handle: RegistryHandle<ShaderModule>) -> &ShaderModule {
+        &self.shader_modules[handle.index].resource
+    }
+
+    /// Returns the bind group layout of `handle`.
+    pub fn bind_group_layout(&self, handle: RegistryHandle<BindGroupLayout>) -> &BindGroupLayout {
+        &self.bind_group_layouts[handle.index].resource
+    }
+
+    /// Returns the pipeline layout of `handle`.
+    pub fn pipeline_layout(&self, handle: RegistryHandle<PipelineLayout>) -> &PipelineLayout {
+        &self.pipeline_layouts[handle.index].resource
+    }
+
+    /// Returns the bind group of `handle`.
+    pub fn bind_group(&self, handle: RegistryHandle<BindGroup>) -> &BindGroup {
+        &self.bind_groups[handle.index].resource
+    }
+
+    /// Returns the render pipeline of `handle`.
+    pub fn render_pipeline(&self, handle: RegistryHandle<RenderPipeline>) -> &RenderPipeline {
+        &self.render_pipelines[handle.index].resource
+    }
+
+    /// Returns the compute pipeline of `handle`.
+    pub fn compute_pipeline(&self, handle: RegistryHandle<ComputePipeline>) -> &ComputePipeline {
+        &self.compute_pipelines[handle.index].resource
+    }
+}
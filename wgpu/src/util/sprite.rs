@@ -0,0 +1,352 @@
+use std::{ops::Range, sync::Arc};
+
+use crate::{
+    AddressMode, BindGroup, BindGroupDescriptor, BindGroupEntry, BindGroupLayout,
+    BindGroupLayoutDescriptor, BindGroupLayoutEntry, BindingResource, BindingType, BlendState,
+    Buffer, BufferAddress, BufferBindingType, BufferDescriptor, BufferUsages, ColorTargetState,
+    ColorWrites, Device, FilterMode, FragmentState, MultisampleState, PipelineLayoutDescriptor,
+    PrimitiveState, PrimitiveTopology, Queue, RenderPass, RenderPipeline, RenderPipelineDescriptor,
+    Sampler, SamplerBindingType, SamplerDescriptor, ShaderModuleDescriptor, ShaderSource,
+    ShaderStages, Texture, TextureFormat, TextureSampleType, TextureUsages, TextureViewDescriptor,
+    TextureViewDimension, VertexAttribute, VertexBufferLayout, VertexFormat, VertexState,
+    VertexStepMode,
+};
+
+/// The size of a sprite in the instance buffer: its rectangle, texture
+/// rectangle and color.
+const INSTANCE_SIZE: BufferAddress = 12 * 4;
+
+/// A texture that sprites are drawn from, created with
+/// [`SpriteRenderer::create_texture`].
+///
+/// Cloning is cheap. As the sprites of a batch are drawn together as long as
+/// they use the same texture, packing images into an atlas and picking them
+/// with [`Sprite::source`] saves draw calls.
+#[derive(Clone, Debug)]
+pub struct SpriteTexture {
+    bind_group: Arc<BindGroup>,
+    width: u32,
+    height: u32,
+}
+
+impl SpriteTexture {
+    /// Returns the width of the texture in texels.
+    pub fn width(&self) -> u32 {
+        self.width
+    }
+
+    /// Returns the height of the texture in texels.
+    pub fn height(&self) -> u32 {
+        self.height
+    }
+}
+
+/// A textured quad to draw with a [`SpriteRenderer`].
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Sprite {
+    /// The top-left corner on the render target in pixels.
+    pub position: [f32; 2],
+    /// The width and height on the render target in pixels.
+    pub size: [f32; 2],
+    /// The region of the texture to draw as the top-left corner and size in
+    /// texels. Negative sizes flip the sprite.
+    pub source: [f32; 4],
+    /// The color to multiply the texels with, with premultiplied alpha.
+    pub color: [f32; 4],
+}
+
+impl Sprite {
+    /// A sprite of `size` pixels at `position` showing the region `source`
+    /// of its texture as it is.
+    pub fn new(position: [f32; 2], size: [f32; 2], source: [f32; 4]) -> Self {
+        Self {
+            position,
+            size,
+            source,
+            color: [1.0; 4],
+        }
+    }
+}
+
+/// Draws batches of 2D sprites with premultiplied alpha blending.
+///
+/// Sprites are queued with [`draw`](Self::draw), uploaded with
+/// [`prepare`](Self::prepare) and recorded into a render pass with
+/// [`render`](Self::render), as one instanced draw call per run of sprites
+/// using the same [`SpriteTexture`]. Sprites are drawn in the order they were
+/// queued, so later sprites are drawn on top of earlier ones.
+///
+/// ```no_run
+/// # let device: wgpu::Device = todo!();
+/// # let queue: wgpu::Queue = todo!();
+/// # let atlas: wgpu::Texture = todo!();
+/// # let mut pass: wgpu::RenderPass = todo!();
+/// use wgpu::util::{Sprite, SpriteRenderer};
+///
+/// let mut sprites = SpriteRenderer::new(&device, wgpu::TextureFormat::Bgra8UnormSrgb);
+/// let atlas = sprites.create_texture(&device, &atlas);
+///
+/// sprites.draw(&atlas, Sprite::new([10.0, 10.0], [32.0, 32.0], [0.0, 0.0, 16.0, 16.0]));
+/// sprites.draw(&atlas, Sprite::new([42.0, 10.0], [32.0, 32.0], [16.0, 0.0, 16.0, 16.0]));
+/// sprites.prepare(&device, &queue, [800, 600]);
+/// // Records a single draw of both sprites.
+/// sprites.render(&mut pass);
+/// ```
+#[derive(Debug)]
+pub struct SpriteRenderer {
+    pipeline: RenderPipeline,
+    texture_layout: BindGroupLayout,
+    sampler: Sampler,
+    globals: Buffer,
+    globals_bind_group: BindGroup,
+    instances: Option<Buffer>,
+    queued: Vec<(SpriteTexture, Sprite)>,
+    batches: Vec<(SpriteTexture, Range<u32>)>,
+}
+
+impl SpriteRenderer {
+    /// Creates a renderer into render targets of `format`.
+    pub fn new(device: &Device, format: TextureFormat) -> Self {
+        let module = device.create_shader_module(ShaderModuleDescriptor {
+            label: Some("wgpu::util::SpriteRenderer"),
+            source: ShaderSource::Wgsl(include_str!("sprite.wgsl").into()),
+            diagnostic_filters: &[],
+        });
+        let globals_layout = device.create_bind_group_layout(&BindGroupLayoutDescriptor {
+            label: Some("wgpu::util::SpriteRenderer globals"),
+            entries: &[BindGroupLayoutEntry {
+                binding: 0,
+                visibility: ShaderStages::VERTEX,
+                ty: BindingType::Buffer {
+                    ty: BufferBindingType::Uniform,
+                    has_dynamic_offset: false,
+                    min_binding_size: None,
+                },
+                count: None,
+            }],
+        });
+        let texture_layout = device.create_bind_group_layout(&BindGroupLayoutDescriptor {
+            label: Some("wgpu::util::SpriteRenderer texture"),
+            entries: &[
+                BindGroupLayoutEntry {
+                    binding: 0,
+                    visibility: ShaderStages::FRAGMENT,
+                    ty: BindingType::Texture {
+                        sample_type: TextureSampleType::Float { filterable: true },
+                        view_dimension: TextureViewDimension::D2,
+                        multisampled: false,
+                    },
+                    count: None,
+                },
+                BindGroupLayoutEntry {
+                    binding: 1,
+                    visibility: ShaderStages::FRAGMENT,
+                    ty: BindingType::Sampler(SamplerBindingType::Filtering),
+                    count: None,
+                },
+            ],
+        });
+        let layout = device.create_pipeline_layout(&PipelineLayoutDescriptor {
+            label: Some("wgpu::util::SpriteRenderer"),
+            bind_group_layouts: &[&globals_layout, &texture_layout],
+            push_constant_ranges: &[],
+        });
+        let pipeline = device.create_render_pipeline(&RenderPipelineDescriptor {
+            label: Some("wgpu::util::SpriteRenderer"),
+            layout: Some(&layout),
+            vertex: VertexState {
+                module: &module,
+                entry_point: "vs_main",
+                compilation_options: Default::default(),
+                buffers: &[VertexBufferLayout {
+                    array_stride: INSTANCE_SIZE,
+                    step_mode: VertexStepMode::Instance,
+                    attributes: &[
+                        VertexAttribute {
+                            format: VertexFormat::Float32x4,
+                            offset: 0,
+                            shader_location: 0,
+                        },
+                        VertexAttribute {
+                            format: VertexFormat::Float32x4,
+                            offset: 16,
+                            shader_location: 1,
+                        },
+                        VertexAttribute {
+                            format: VertexFormat::Float32x4,
+                            offset: 32,
+                            shader_location: 2,
+                        },
+                    ],
+                }],
+            },
+            primitive: PrimitiveState {
+                topology: PrimitiveTopology::TriangleStrip,
+                ..Default::default()
+            },
+            depth_stencil: None,
+            multisample: MultisampleState::default(),
+            fragment: Some(FragmentState {
+                module: &module,
+                entry_point: "fs_main",
+                compilation_options: Default::default(),
+                targets: &[Some(ColorTargetState {
+                    format,
+                    blend: Some(BlendState::PREMULTIPLIED_ALPHA_BLENDING),
+                    write_mask: ColorWrites::ALL,
+                })],
+            }),
+            multiview: None,
+        });
+        let sampler = device.create_sampler(&SamplerDescriptor {
+            label: Some("wgpu::util::SpriteRenderer"),
+            address_mode_u: AddressMode::ClampToEdge,
+            address_mode_v: AddressMode::ClampToEdge,
+            mag_filter: FilterMode::Linear,
+            min_filter: FilterMode::Linear,
+            ..Default::default()
+        });
+        // `Globals` is padded to the 16 bytes uniform buffers are sized in.
+        let globals = device.create_buffer(&BufferDescriptor {
+            label: Some("wgpu::util::SpriteRenderer globals"),
+            size: 16,
+            usage: BufferUsages::UNIFORM | BufferUsages::COPY_DST,
+            mapped_at_creation: false,
+        });
+        let globals_bind_group = device.create_bind_group(&BindGroupDescriptor {
+            label: Some("wgpu::util::SpriteRenderer globals"),
+            layout: &globals_layout,
+            entries: &[BindGroupEntry {
+                binding: 0,
+                resource: globals.as_entire_binding(),
+            }],
+        });
+
+        Self {
+            pipeline,
+            texture_layout,
+            sampler,
+            globals,
+            globals_bind_group,
+            instances: None,
+            queued: Vec::new(),
+            batches: Vec::new(),
+        }
+    }
+
+    /// Makes the first mip level and layer of `texture` available to sprites.
+    ///
+    /// # Panics
+    ///
+    /// - `texture` doesn't have [`TextureUsages::TEXTURE_BINDING`].
+    pub fn create_texture(&self, device: &Device, texture: &Texture) -> SpriteTexture {
+        assert!(texture.usage().contains(TextureUsages::TEXTURE_BINDING));
+        let view = texture.create_view(&TextureViewDescriptor {
+            dimension: Some(TextureViewDimension::D2),
+            mip_level_count: Some(1),
+            array_layer_count: Some(1),
+            ..Default::default()
+        });
+        let bind_group = device.create_bind_group(&BindGroupDescriptor {
+            label: Some("wgpu::util::SpriteRenderer texture"),
+            layout: &self.texture_layout,
+            entries: &[
+                BindGroupEntry {
+                    binding: 0,
+                    resource: BindingResource::TextureView(&view),
+                },
+                BindGroupEntry {
+                    binding: 1,
+                    resource: BindingResource::Sampler(&self.sampler),
+                },
+            ],
+        });
+        SpriteTexture {
+            bind_group: Arc::new(bind_group),
+            width: texture.width(),
+            height: texture.height(),
+        }
+    }
+
+    /// Queues `sprite` to be drawn from `texture` by the next
+    /// [`prepare`](Self::prepare).
+    pub fn draw(&mut self, texture: &SpriteTexture, sprite: Sprite) {
+        self.queued.push((texture.clone(), sprite));
+    }
+
+    /// Uploads the queued sprites for a render target of `target_size` pixels,
+    /// replacing the ones of the previous call, and clears the queue.
+    ///
+    /// The instance buffer grows to fit all sprites and is reused otherwise.
+    pub fn prepare(&mut self, device: &Device, queue: &Queue, target_size: [u32; 2]) {
+        let globals: Vec<u8> = [target_size[0] as f32, target_size[1] as f32, 0.0, 0.0]
+            .iter()
+            .flat_map(|v| v.to_ne_bytes())
+            .collect();
+        queue.write_buffer(&self.globals, 0, &globals);
+
+        self.batches.clear();
+        let mut contents = Vec::with_capacity(self.queued.len() * INSTANCE_SIZE as usize);
+        for (index, (texture, sprite)) in self.queued.drain(..).enumerate() {
+            let (width, height) = (texture.width as f32, texture.height as f32);
+            let instance = [
+                sprite.position[0],
+                sprite.position[1],
+                sprite.size[0],
+                sprite.size[1],
+                sprite.source[0] / width,
+                sprite.source[1] / height,
+                sprite.source[2] / width,
+                sprite.source[3] / height,
+                sprite.color[0],
+                sprite.color[1],
+                sprite.color[2],
+                sprite.color[3],
+            ];
+            contents.extend(instance.iter().flat_map(|v| v.to_ne_bytes()));
+
+            let index = index as u32;
+            match self.batches.last_mut() {
+                Some((last, range)) if Arc::ptr_eq(&last.bind_group, &texture.bind_group) => {
+                    range.end = index + 1;
+                }
+                _ => self.batches.push((texture, index..index + 1)),
+            }
+        }
+        if contents.is_empty() {
+            return;
+        }
+
+        let size = contents.len() as BufferAddress;
+        if self
+            .instances
+            .as_ref()
+            .map_or(true, |buffer| buffer.size() < size)
+        {
+            self.instances = Some(device.create_buffer(&BufferDescriptor {
+                label: Some("wgpu::util::SpriteRenderer instances"),
+                size: size.next_power_of_two(),
+                usage: BufferUsages::VERTEX | BufferUsages::COPY_DST,
+                mapped_at_creation: false,
+            }));
+        }
+        queue.write_buffer(self.instances.as_ref().unwrap(), 0, &contents);
+    }
+
+    /// Records the sprites of the last [`prepare`](Self::prepare) into `pass`.
+    ///
+    /// Returns the number of draw calls recorded.
+    pub fn render<'a>(&'a self, pass: &mut RenderPass<'a>) -> usize {
+        let Some(instances) = self.instances.as_ref().filter(|_| !self.batches.is_empty()) else {
+            return 0;
+        };
+        pass.set_pipeline(&self.pipeline);
+        pass.set_bind_group(0, &self.globals_bind_group, &[]);
+        pass.set_vertex_buffer(0, instances.slice(..));
+        for (texture, range) in &self.batches {
+            pass.set_bind_group(1, &texture.bind_group, &[]);
+            pass.draw(0..4, range.clone());
+        }
+        self.batches.len()
+    }
+}
@@ -0,0 +1,45 @@
+struct Globals {
+    // Size of the render target in pixels.
+    target_size: vec2<f32>,
+}
+
+struct Instance {
+    // Top-left corner and size in pixels.
+    @location(0) rect: vec4<f32>,
+    // Top-left corner and size in texture coordinates.
+    @location(1) uv_rect: vec4<f32>,
+    // Premultiplied color to multiply the texels with.
+    @location(2) color: vec4<f32>,
+}
+
+struct VertexOutput {
+    @builtin(position) position: vec4<f32>,
+    @location(0) uv: vec2<f32>,
+    @location(1) color: vec4<f32>,
+}
+
+@group(0) @binding(0)
+var<uniform> globals: Globals;
+@group(1) @binding(0)
+var sprite_texture: texture_2d<f32>;
+@group(1) @binding(1)
+var sprite_sampler: sampler;
+
+@vertex
+fn vs_main(@builtin(vertex_index) index: u32, instance: Instance) -> VertexOutput {
+    // The corners of a quad as a triangle strip.
+    let corner = vec2<f32>(f32(index & 1u), f32(index >> 1u));
+    let pixel = instance.rect.xy + corner * instance.rect.zw;
+    let ndc = pixel / globals.target_size * 2.0 - 1.0;
+
+    var out: VertexOutput;
+    out.position = vec4<f32>(ndc.x, -ndc.y, 0.0, 1.0);
+    out.uv = instance.uv_rect.xy + corner * instance.uv_rect.zw;
+    out.color = instance.color;
+    return out;
+}
+
+@fragment
+fn fs_main(in: VertexOutput) -> @location(0) vec4<f32> {
+    return textureSample(sprite_texture, sprite_sampler, in.uv) * in.color;
+}
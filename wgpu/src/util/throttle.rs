@@ -0,0 +1,125 @@
+use std::{
+    thread,
+    time::{Duration, Instant},
+};
+
+use crate::SurfaceTexture;
+
+/// Limits the rate at which frames are presented, to keep the GPU clocks down.
+///
+/// Rendering as fast as the display allows keeps the GPU busy even when nothing
+/// changes on screen, which drains batteries. With a throttle, the render loop
+/// sleeps between frames instead, so that they are at least
+/// [`PresentThrottle::interval`] apart. This works on every backend, and can be
+/// combined with [`PerformanceHint::LowPower`](crate::PerformanceHint::LowPower)
+/// where the platform supports it.
+///
+/// Call [`PresentThrottle::wait`] before acquiring the next frame with
+/// [`Surface::get_current_texture`](crate::Surface::get_current_texture), so
+/// that the swapchain isn't held while sleeping, or simply present frames with
+/// [`PresentThrottle::present`].
+///
+/// ```no_run
+/// # fn render_loop(surface: &wgpu::Surface<'_>) {
+/// let mut throttle = wgpu::util::PresentThrottle::with_frame_rate(30.0);
+/// loop {
+///     throttle.wait();
+///     let frame = surface.get_current_texture().unwrap();
+///     // Render into the frame...
+///     frame.present();
+/// }
+/// # }
+/// ```
+#[derive(Debug)]
+pub struct PresentThrottle {
+    interval: Duration,
+    next: Option<Instant>,
+}
+
+impl PresentThrottle {
+    /// Creates a throttle that spaces frames at least `interval` apart.
+    pub fn new(interval: Duration) -> Self {
+        Self {
+            interval,
+            next: None,
+        }
+    }
+
+    /// Creates a throttle that presents at most `frames_per_second` frames a second.
+    ///
+    /// # Panics
+    ///
+    /// - `frames_per_second` is not positive.
+    pub fn with_frame_rate(frames_per_second: f64) -> Self {
+        assert!(
+            frames_per_second > 0.0,
+            "frame rate must be positive, got {frames_per_second}"
+        );
+        Self::new(Duration::from_secs_f64(1.0 / frames_per_second))
+    }
+
+    /// The minimum time between two frames.
+    pub fn interval(&self) -> Duration {
+        self.interval
+    }
+
+    /// Changes the minimum time between two frames, for example to render at
+    /// the full rate of the display while the user interacts with the
+    /// application, with a zero interval, and slow down once it is idle.
+    ///
+    /// The next frame is still due at the time set by the previous interval.
+    pub fn set_interval(&mut self, interval: Duration) {
+        self.interval = interval;
+    }
+
+    /// Sleeps until the next frame is due.
+    ///
+    /// Returns immediately on the first call, and when rendering the previous
+    /// frame took longer than the interval. Frames that are late don't make the
+    /// following ones rush to catch up.
+    pub fn wait(&mut self) {
+        if let Some(next) = self.next {
+            let now = Instant::now();
+            if next > now {
+                thread::sleep(next - now);
+            }
+        }
+        let now = Instant::now();
+        let start = self.next.map_or(now, |next| next.max(now));
+        self.next = Some(start + self.interval);
+    }
+
+    /// Sleeps until the next frame is due, and presents `frame`.
+    pub fn present(&mut self, frame: SurfaceTexture) {
+        self.wait();
+        frame.present();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::PresentThrottle;
+    use std::time::{Duration, Instant};
+
+    #[test]
+    fn present_throttle_interval() {
+        let interval = Duration::from_millis(20);
+        let mut throttle = PresentThrottle::new(interval);
+        assert_eq!(throttle.interval(), interval);
+
+        // The first frame is never delayed.
+        let start = Instant::now();
+        throttle.wait();
+        for _ in 0..3 {
+            throttle.wait();
+        }
+        assert!(start.elapsed() >= interval * 3);
+
+        // A late frame doesn't make the next one early.
+        std::thread::sleep(interval * 2);
+        let late = Instant::now();
+        throttle.wait();
+        throttle.wait();
+        assert!(late.elapsed() >= interval);
+    }
+}
@@ -0,0 +1,100 @@
+use crate::{Queue, TimestampCalibration};
+
+/// Converts the timestamps of queries into monotonic nanoseconds of the CPU
+/// clock, corrected for the drift of the timestamp clock of a queue.
+///
+/// [`Queue::get_timestamp_period`] is the nominal period of the clock, but on
+/// some backends its frequency changes with the power state of the GPU, so
+/// durations computed with it are off, and passes can end up with negative
+/// durations in profiler traces. The normalizer measures the actual period
+/// from [`Queue::get_timestamp_calibration`] samples instead.
+///
+/// Use one normalizer per queue, and call [`TimestampNormalizer::recalibrate`]
+/// about once per frame, before normalizing the timestamps of that frame.
+/// Where the backend can't calibrate timestamps, they are left in GPU time and
+/// only made monotonic.
+#[derive(Debug)]
+pub struct TimestampNormalizer {
+    nominal_period: f64,
+    period: f64,
+    calibration: Option<TimestampCalibration>,
+    last: f64,
+}
+
+impl TimestampNormalizer {
+    /// Creates a normalizer for the timestamps of `queue`, and calibrates it.
+    pub fn new(queue: &Queue) -> Self {
+        let nominal_period = queue.get_timestamp_period() as f64;
+        Self {
+            nominal_period,
+            period: nominal_period,
+            calibration: queue.get_timestamp_calibration(),
+            last: f64::MIN,
+        }
+    }
+
+    /// Samples the timestamp clock of `queue` again, and measures its period
+    /// over the time since the last sample.
+    pub fn recalibrate(&mut self, queue: &Queue) {
+        let Some(calibration) = queue.get_timestamp_calibration() else {
+            return;
+        };
+        if let Some(previous) = self.calibration {
+            let gpu_ticks = calibration
+                .gpu_timestamp
+                .saturating_sub(previous.gpu_timestamp);
+            let cpu_time = calibration
+                .cpu_timestamp
+                .saturating_sub(previous.cpu_timestamp);
+            if gpu_ticks > 0 && cpu_time > 0 {
+                self.period = cpu_time as f64 / gpu_ticks as f64;
+            }
+        }
+        self.calibration = Some(calibration);
+    }
+
+    /// The measured length of a tick of the timestamp clock, in nanoseconds.
+    ///
+    /// This is the nominal period until the clock has been calibrated twice.
+    pub fn period(&self) -> f64 {
+        self.period
+    }
+
+    /// How far the measured period is off the nominal one, as a fraction of it.
+    ///
+    /// A drift of `0.01` means that the clock runs 1% slower than
+    /// [`Queue::get_timestamp_period`] claims.
+    pub fn drift(&self) -> f64 {
+        if self.nominal_period == 0.0 {
+            return 0.0;
+        }
+        self.period / self.nominal_period - 1.0
+    }
+
+    /// Converts `timestamps` in place from nanoseconds computed with the
+    /// nominal period, like [`QueryResults::Timestamp`], to nanoseconds of the
+    /// clock of [`TimestampCalibration::cpu_timestamp`].
+    ///
+    /// Timestamps are expected in the order the GPU wrote them. Each of them is
+    /// moved up to the one before, including those of earlier calls, if it
+    /// would be earlier.
+    ///
+    /// [`QueryResults::Timestamp`]: crate::QueryResults::Timestamp
+    pub fn normalize(&mut self, timestamps: &mut [f64]) {
+        if self.nominal_period == 0.0 {
+            return;
+        }
+        for timestamp in timestamps {
+            let ticks = *timestamp / self.nominal_period;
+            let normalized = match self.calibration {
+                Some(calibration) => {
+                    calibration.cpu_timestamp as f64
+                        + (ticks - calibration.gpu_timestamp as f64) * self.period
+                }
+                None => ticks * self.period,
+            };
+            self.last = normalized.max(self.last);
+            *timestamp = self.last;
+        }
+    }
+}
@@ -0,0 +1,699 @@
+//! Decoder for 2D ASTC blocks of the LDR profile, as specified by the
+//! [Khronos Data Format Specification](https://registry.khronos.org/DataFormat/specs/1.3/dataformat.1.3.html#ASTC).
+//!
+//! Blocks that are invalid, or use HDR endpoints, decode to the error color.
+
+/// The color of texels of invalid blocks.
+const ERROR_COLOR: [u8; 4] = [255, 0, 255, 255];
+
+/// Reads bits of a block, treating bits at or past `end` as zero.
+struct BitReader {
+    bits: u128,
+    position: u32,
+    end: u32,
+}
+
+impl BitReader {
+    fn new(bits: u128, start: u32, end: u32) -> Self {
+        Self {
+            bits,
+            position: start,
+            end,
+        }
+    }
+
+    fn read(&mut self, count: u32) -> u32 {
+        let mut value = 0;
+        for i in 0..count {
+            if self.position < self.end {
+                value |= ((self.bits >> self.position) as u32 & 1) << i;
+            }
+            self.position += 1;
+        }
+        value
+    }
+}
+
+fn bits(block: u128, start: u32, count: u32) -> u32 {
+    (block >> start) as u32 & ((1 << count) - 1)
+}
+
+/// How the values of a range are encoded by the integer sequence encoding.
+#[derive(Clone, Copy)]
+struct Encoding {
+    /// Whether every value has a trit, or a quint, in addition to its bits.
+    trits: bool,
+    quints: bool,
+    bits: u32,
+}
+
+/// The encodings of the ranges, with 2, 3, 4, 5, 6, 8, 10, 12, 16, 20, 24,
+/// 32, 40, 48, 64, 80, 96, 128, 160, 192 and 256 values.
+const ENCODINGS: [Encoding; 21] = {
+    const fn bits(bits: u32) -> Encoding {
+        Encoding {
+            trits: false,
+            quints: false,
+            bits,
+        }
+    }
+    const fn trits(bits: u32) -> Encoding {
+        Encoding {
+            trits: true,
+            quints: false,
+            bits,
+        }
+    }
+    const fn quints(bits: u32) -> Encoding {
+        Encoding {
+            trits: false,
+            quints: true,
+            bits,
+        }
+    }
+    [
+        bits(1),
+        trits(0),
+        bits(2),
+        quints(0),
+        trits(1),
+        bits(3),
+        quints(1),
+        trits(2),
+        bits(4),
+        quints(2),
+        trits(3),
+        bits(5),
+        quints(3),
+        trits(4),
+        bits(6),
+        quints(4),
+        trits(5),
+        bits(7),
+        quints(5),
+        trits(6),
+        bits(8),
+    ]
+};
+
+impl Encoding {
+    /// Returns the number of bits `count` values take.
+    fn sequence_bits(self, count: u32) -> u32 {
+        let extra = if self.trits {
+            (8 * count).div_ceil(5)
+        } else if self.quints {
+            (7 * count).div_ceil(3)
+        } else {
+            0
+        };
+        self.bits * count + extra
+    }
+
+    /// Decodes `count` values, as `(trit or quint, bits)` pairs.
+    fn decode(self, reader: &mut BitReader, count: usize) -> Vec<(u32, u32)> {
+        let mut values = Vec::with_capacity(count);
+        while values.len() < count {
+            if self.trits {
+                let mut m = [0; 5];
+                let mut t = 0;
+                for (i, (m, t_bits)) in m.iter_mut().zip([2, 2, 1, 2, 1]).enumerate() {
+                    *m = reader.read(self.bits);
+                    let shift = [0, 2, 4, 5, 7][i];
+                    t |= reader.read(t_bits) << shift;
+                }
+                values.extend(decode_trits(t).into_iter().zip(m));
+            } else if self.quints {
+                let mut m = [0; 3];
+                let mut q = 0;
+                for (i, (m, q_bits)) in m.iter_mut().zip([3, 2, 2]).enumerate() {
+                    *m = reader.read(self.bits);
+                    let shift = [0, 3, 5][i];
+                    q |= reader.read(q_bits) << shift;
+                }
+                values.extend(decode_quints(q).into_iter().zip(m));
+            } else {
+                values.push((0, reader.read(self.bits)));
+            }
+        }
+        values.truncate(count);
+        values
+    }
+}
+
+fn bit(value: u32, index: u32) -> u32 {
+    (value >> index) & 1
+}
+
+/// Decodes the 8 bits shared by a block of 5 trits.
+fn decode_trits(t: u32) -> [u32; 5] {
+    let (c, t3, t4);
+    if (t >> 2) & 0b111 == 0b111 {
+        c = (((t >> 5) & 0b111) << 2) | (t & 0b11);
+        t4 = 2;
+        t3 = 2;
+    } else {
+        c = t & 0x1F;
+        if (t >> 5) & 0b11 == 0b11 {
+            t4 = 2;
+            t3 = bit(t, 7);
+        } else {
+            t4 = bit(t, 7);
+            t3 = (t >> 5) & 0b11;
+        }
+    }
+    let (t0, t1, t2);
+    if c & 0b11 == 0b11 {
+        t2 = 2;
+        t1 = bit(c, 4);
+        t0 = (bit(c, 3) << 1) | (bit(c, 2) & !bit(c, 3) & 1);
+    } else if (c >> 2) & 0b11 == 0b11 {
+        t2 = 2;
+        t1 = 2;
+        t0 = c & 0b11;
+    } else {
+        t2 = bit(c, 4);
+        t1 = (c >> 2) & 0b11;
+        t0 = (bit(c, 1) << 1) | (bit(c, 0) & !bit(c, 1) & 1);
+    }
+    [t0, t1, t2, t3, t4]
+}
+
+/// Decodes the 7 bits shared by a block of 3 quints.
+fn decode_quints(q: u32) -> [u32; 3] {
+    if (q >> 1) & 0b11 == 0b11 && (q >> 5) & 0b11 == 0 {
+        let q0 = bit(q, 0);
+        let q2 = (q0 << 2) | ((bit(q, 4) & !q0 & 1) << 1) | (bit(q, 3) & !q0 & 1);
+        return [4, 4, q2];
+    }
+    let (c, q2);
+    if (q >> 1) & 0b11 == 0b11 {
+        q2 = 4;
+        c = (((q >> 3) & 0b11) << 3) | ((!(q >> 5) & 0b11) << 1) | bit(q, 0);
+    } else {
+        q2 = (q >> 5) & 0b11;
+        c = q & 0x1F;
+    }
+    if c & 0b111 == 0b101 {
+        [(c >> 3) & 0b11, 4, q2]
+    } else {
+        [c & 0b111, (c >> 3) & 0b11, q2]
+    }
+}
+
+/// Replicates the lowest `from` bits of `value` to fill `to` bits.
+fn replicate(value: u32, from: u32, to: u32) -> u32 {
+    if from == 0 {
+        return 0;
+    }
+    let mut result = 0;
+    let mut filled = 0;
+    while filled < to {
+        result = (result << from) | value;
+        filled += from;
+    }
+    result >> (filled - to)
+}
+
+/// Scales a color endpoint value to 8 bits.
+fn unquantize_color(encoding: Encoding, (d, m): (u32, u32)) -> u32 {
+    if !encoding.trits && !encoding.quints {
+        return replicate(m, encoding.bits, 8);
+    }
+    let a = if m & 1 == 1 { 0x1FF } else { 0 };
+    let (b, c) = match (encoding.trits, encoding.bits) {
+        (true, 1) => (0, 204),
+        (true, 2) => {
+            let b = bit(m, 1);
+            ((b << 8) | (b << 4) | (b << 2) | (b << 1), 93)
+        }
+        (true, 3) => {
+            let cb = (m >> 1) & 0b11;
+            ((cb << 7) | (cb << 2) | cb, 44)
+        }
+        (true, 4) => {
+            let dcb = (m >> 1) & 0b111;
+            ((dcb << 6) | dcb, 22)
+        }
+        (true, 5) => {
+            let edcb = (m >> 1) & 0b1111;
+            ((edcb << 5) | (edcb >> 2), 11)
+        }
+        (true, _) => {
+            let fedcb = (m >> 1) & 0b11111;
+            ((fedcb << 4) | (fedcb >> 4), 5)
+        }
+        (false, 1) => (0, 113),
+        (false, 2) => {
+            let b = bit(m, 1);
+            ((b << 8) | (b << 3) | (b << 2), 54)
+        }
+        (false, 3) => {
+            let cb = (m >> 1) & 0b11;
+            ((cb << 7) | (cb << 1) | (cb >> 1), 26)
+        }
+        (false, 4) => {
+            let dcb = (m >> 1) & 0b111;
+            ((dcb << 6) | (dcb >> 1), 13)
+        }
+        (false, _) => {
+            let edcb = (m >> 1) & 0b1111;
+            ((edcb << 5) | (edcb >> 3), 6)
+        }
+    };
+    let t = (d * c + b) ^ a;
+    (a & 0x80) | (t >> 2)
+}
+
+/// Scales a weight to the range from 0 to 64.
+fn unquantize_weight(encoding: Encoding, (d, m): (u32, u32)) -> u32 {
+    let value = if !encoding.trits && !encoding.quints {
+        replicate(m, encoding.bits, 6)
+    } else if encoding.bits == 0 {
+        if encoding.trits {
+            [0, 32, 63][d as usize]
+        } else {
+            [0, 16, 32, 47, 63][d as usize]
+        }
+    } else {
+        let a = if m & 1 == 1 { 0x7F } else { 0 };
+        let (b, c) = match (encoding.trits, encoding.bits) {
+            (true, 1) => (0, 50),
+            (true, 2) => {
+                let b = bit(m, 1);
+                ((b << 6) | (b << 2) | b, 23)
+            }
+            (true, _) => {
+                let cb = (m >> 1) & 0b11;
+                ((cb << 5) | cb, 11)
+            }
+            (false, 1) => (0, 28),
+            (false, _) => {
+                let b = bit(m, 1);
+                ((b << 6) | (b << 1), 13)
+            }
+        };
+        let t = (d * c + b) ^ a;
+        (a & 0x20) | (t >> 2)
+    };
+    if value > 32 {
+        value + 1
+    } else {
+        value
+    }
+}
+
+/// Hashes the seed of a partition.
+fn hash52(mut p: u32) -> u32 {
+    p ^= p >> 15;
+    p = p.wrapping_sub(p << 17);
+    p = p.wrapping_add(p << 7);
+    p = p.wrapping_add(p << 4);
+    p ^= p >> 5;
+    p = p.wrapping_add(p << 16);
+    p ^= p >> 7;
+    p ^= p >> 3;
+    p ^= p << 6;
+    p ^= p >> 17;
+    p
+}
+
+/// Returns the partition of the texel at `x` and `y`.
+fn select_partition(seed: u32, mut x: u32, mut y: u32, count: u32, small_block: bool) -> usize {
+    if small_block {
+        x <<= 1;
+        y <<= 1;
+    }
+    let seed = seed + (count - 1) * 1024;
+    let rnum = hash52(seed);
+    let mut seeds = [
+        rnum & 0xF,
+        (rnum >> 4) & 0xF,
+        (rnum >> 8) & 0xF,
+        (rnum >> 12) & 0xF,
+        (rnum >> 16) & 0xF,
+        (rnum >> 20) & 0xF,
+        (rnum >> 24) & 0xF,
+        (rnum >> 28) & 0xF,
+    ];
+    for seed in seeds.iter_mut() {
+        *seed *= *seed;
+    }
+    let (sh1, sh2) = if seed & 1 == 1 {
+        (
+            if seed & 2 == 2 { 4 } else { 5 },
+            if count == 3 { 6 } else { 5 },
+        )
+    } else {
+        (
+            if count == 3 { 6 } else { 5 },
+            if seed & 2 == 2 { 4 } else { 5 },
+        )
+    };
+    for (i, seed) in seeds.iter_mut().enumerate() {
+        *seed >>= if i % 2 == 0 { sh1 } else { sh2 };
+    }
+    // The seeds for z don't matter, as 2D blocks have no depth.
+    let a = (seeds[0] * x + seeds[1] * y + (rnum >> 14)) & 0x3F;
+    let b = (seeds[2] * x + seeds[3] * y + (rnum >> 10)) & 0x3F;
+    let c = if count >= 3 {
+        (seeds[4] * x + seeds[5] * y + (rnum >> 6)) & 0x3F
+    } else {
+        0
+    };
+    let d = if count >= 4 {
+        (seeds[6] * x + seeds[7] * y + (rnum >> 2)) & 0x3F
+    } else {
+        0
+    };
+    if a >= b && a >= c && a >= d {
+        0
+    } else if b >= c && b >= d {
+        1
+    } else if c >= d {
+        2
+    } else {
+        3
+    }
+}
+
+/// Transfers a bit of `b` to `a`, returning `a` as a signed value.
+fn bit_transfer_signed(a: i32, b: i32) -> (i32, i32) {
+    let b = (b >> 1) | (a & 0x80);
+    let mut a = (a >> 1) & 0x3F;
+    if a & 0x20 != 0 {
+        a -= 0x40;
+    }
+    (a, b)
+}
+
+fn blue_contract([r, g, b, a]: [i32; 4]) -> [i32; 4] {
+    [(r + b) >> 1, (g + b) >> 1, b, a]
+}
+
+fn clamp(color: [i32; 4]) -> [i32; 4] {
+    color.map(|c| c.clamp(0, 255))
+}
+
+/// Decodes the endpoints of a color endpoint mode, or `None` for HDR modes.
+fn decode_endpoints(mode: u32, v: &[i32]) -> Option<[[i32; 4]; 2]> {
+    Some(match mode {
+        0 => [[v[0], v[0], v[0], 255], [v[1], v[1], v[1], 255]],
+        1 => {
+            let l0 = (v[0] >> 2) | (v[1] & 0xC0);
+            let l1 = (l0 + (v[1] & 0x3F)).min(255);
+            [[l0, l0, l0, 255], [l1, l1, l1, 255]]
+        }
+        4 => [[v[0], v[0], v[0], v[2]], [v[1], v[1], v[1], v[3]]],
+        5 => {
+            let (d0, l) = bit_transfer_signed(v[1], v[0]);
+            let (d1, a) = bit_transfer_signed(v[3], v[2]);
+            [[l, l, l, a], clamp([l + d0, l + d0, l + d0, a + d1])]
+        }
+        6 => [
+            [
+                (v[0] * v[3]) >> 8,
+                (v[1] * v[3]) >> 8,
+                (v[2] * v[3]) >> 8,
+                255,
+            ],
+            [v[0], v[1], v[2], 255],
+        ],
+        8 | 12 => {
+            let (a0, a1) = if mode == 12 { (v[6], v[7]) } else { (255, 255) };
+            if v[1] + v[3] + v[5] >= v[0] + v[2] + v[4] {
+                [[v[0], v[2], v[4], a0], [v[1], v[3], v[5], a1]]
+            } else {
+                [
+                    blue_contract([v[1], v[3], v[5], a1]),
+                    blue_contract([v[0], v[2], v[4], a0]),
+                ]
+            }
+        }
+        9 | 13 => {
+            let (d0, r) = bit_transfer_signed(v[1], v[0]);
+            let (d1, g) = bit_transfer_signed(v[3], v[2]);
+            let (d2, b) = bit_transfer_signed(v[5], v[4]);
+            let (d3, a) = if mode == 13 {
+                bit_transfer_signed(v[7], v[6])
+            } else {
+                (0, 255)
+            };
+            let base = [r, g, b, a];
+            let offset = [r + d0, g + d1, b + d2, a + d3];
+            if d0 + d1 + d2 >= 0 {
+                [base, clamp(offset)]
+            } else {
+                [clamp(blue_contract(offset)), clamp(blue_contract(base))]
+            }
+        }
+        10 => [
+            [
+                (v[0] * v[3]) >> 8,
+                (v[1] * v[3]) >> 8,
+                (v[2] * v[3]) >> 8,
+                v[4],
+            ],
+            [v[0], v[1], v[2], v[5]],
+        ],
+        _ => return None,
+    })
+}
+
+/// The layout of the weights of a block.
+struct BlockMode {
+    grid_width: u32,
+    grid_height: u32,
+    dual_plane: bool,
+    weights: Encoding,
+}
+
+fn decode_block_mode(mode: u32) -> Option<BlockMode> {
+    let mut dual_plane = bit(mode, 10) == 1;
+    let mut high_precision = bit(mode, 9) == 1;
+    let a = (mode >> 5) & 0b11;
+    let (range, width, height);
+    if mode & 0b11 != 0 {
+        range = bit(mode, 4) | ((mode & 0b11) << 1);
+        let b = (mode >> 7) & 0b11;
+        (width, height) = match (mode >> 2) & 0b11 {
+            0 => (b + 4, a + 2),
+            1 => (b + 8, a + 2),
+            2 => (a + 2, b + 8),
+            _ if bit(mode, 8) == 0 => (a + 2, (b & 1) + 6),
+            _ => ((b & 1) + 2, a + 2),
+        };
+    } else {
+        range = bit(mode, 4) | (((mode >> 2) & 0b11) << 1);
+        if (mode >> 2) & 0b11 == 0 {
+            return None;
+        }
+        let b = (mode >> 9) & 0b11;
+        (width, height) = match (mode >> 7) & 0b11 {
+            0 => (12, a + 2),
+            1 => (a + 2, 12),
+            2 => {
+                dual_plane = false;
+                high_precision = false;
+                (a + 6, b + 6)
+            }
+            _ => match a {
+                0 => (6, 10),
+                1 => (10, 6),
+                _ => return None,
+            },
+        };
+    }
+    // Weights have at most 32 values, so they use the first 12 encodings.
+    let index = range - 2 + 6 * high_precision as u32;
+    Some(BlockMode {
+        grid_width: width,
+        grid_height: height,
+        dual_plane,
+        weights: ENCODINGS[index as usize],
+    })
+}
+
+/// Decodes a 16 byte block of `width` by `height` texels into RGBA.
+///
+/// `srgb` selects the interpolation of sRGB formats, which interpolates
+/// the endpoints at a higher precision.
+pub(super) fn decode(block: &[u8], width: u32, height: u32, srgb: bool, out: &mut [[u8; 4]]) {
+    match decode_texels(block, width, height, srgb, out) {
+        Some(()) => {}
+        None => out.fill(ERROR_COLOR),
+    }
+}
+
+fn decode_texels(
+    block: &[u8],
+    width: u32,
+    height: u32,
+    srgb: bool,
+    out: &mut [[u8; 4]],
+) -> Option<()> {
+    let block = u128::from_le_bytes(block.try_into().unwrap());
+
+    // Void extent blocks have a single color.
+    if bits(block, 0, 9) == 0x1FC {
+        if bit(block as u32, 9) == 1 {
+            // HDR colors.
+            return None;
+        }
+        let color: [u8; 4] =
+            std::array::from_fn(|c| (bits(block, 64 + 16 * c as u32, 16) >> 8) as u8);
+        out.fill(color);
+        return Some(());
+    }
+
+    let mode = decode_block_mode(bits(block, 0, 11))?;
+    if mode.grid_width > width || mode.grid_height > height {
+        return None;
+    }
+    let planes = if mode.dual_plane { 2 } else { 1 };
+    let weight_count = mode.grid_width * mode.grid_height * planes;
+    if weight_count > 64 {
+        return None;
+    }
+    let weight_bits = mode.weights.sequence_bits(weight_count);
+    if !(24..=96).contains(&weight_bits) {
+        return None;
+    }
+
+    let partitions = bits(block, 11, 2) + 1;
+    if partitions == 4 && mode.dual_plane {
+        return None;
+    }
+
+    // The color endpoint modes of every partition, and the first bit past
+    // the data of the color endpoints.
+    let mut color_modes = [0; 4];
+    let mut color_end = 128 - weight_bits;
+    let color_start;
+    let mut seed = 0;
+    if partitions == 1 {
+        color_modes[0] = bits(block, 13, 4);
+        color_start = 17;
+    } else {
+        seed = bits(block, 13, 10);
+        color_start = 29;
+        let low = bits(block, 23, 6);
+        if low & 0b11 == 0 {
+            color_modes = [low >> 2; 4];
+        } else {
+            let extra_bits = 3 * partitions - 4;
+            color_end -= extra_bits;
+            let encoded = low | (bits(block, color_end, extra_bits) << 6);
+            let base_class = (encoded & 0b11) - 1;
+            let classes = encoded >> 2;
+            let modes = classes >> partitions;
+            for i in 0..partitions {
+                let class = base_class + bit(classes, i);
+                color_modes[i as usize] = (class << 2) | ((modes >> (2 * i)) & 0b11);
+            }
+        }
+    }
+    let dual_plane_channel = if mode.dual_plane {
+        color_end -= 2;
+        Some(bits(block, color_end, 2) as usize)
+    } else {
+        None
+    };
+
+    // Use the largest range of color values that fits.
+    let color_modes = &color_modes[..partitions as usize];
+    let color_count: u32 = color_modes.iter().map(|mode| 2 * ((mode >> 2) + 1)).sum();
+    if color_count > 18 || color_end < color_start {
+        return None;
+    }
+    let available = color_end - color_start;
+    let color_range = (0..ENCODINGS.len())
+        .rev()
+        .find(|&range| ENCODINGS[range].sequence_bits(color_count) <= available)?;
+    // At least 6 values.
+    if color_range < 4 {
+        return None;
+    }
+    let color_encoding = ENCODINGS[color_range];
+    let mut reader = BitReader::new(block, color_start, color_end);
+    let color_values: Vec<i32> = color_encoding
+        .decode(&mut reader, color_count as usize)
+        .into_iter()
+        .map(|value| unquantize_color(color_encoding, value) as i32)
+        .collect();
+
+    let mut endpoints = [[[0; 4]; 2]; 4];
+    let mut values = &color_values[..];
+    for (endpoints, &mode) in endpoints.iter_mut().zip(color_modes) {
+        let count = 2 * ((mode >> 2) + 1) as usize;
+        *endpoints = decode_endpoints(mode, &values[..count])?;
+        values = &values[count..];
+    }
+
+    // Weights are stored from the most significant bit down.
+    let mut reader = BitReader::new(block.reverse_bits(), 0, weight_bits);
+    let weights: Vec<u32> = mode
+        .weights
+        .decode(&mut reader, weight_count as usize)
+        .into_iter()
+        .map(|value| unquantize_weight(mode.weights, value))
+        .collect();
+
+    let ds = (1024 + width / 2) / (width - 1);
+    let dt = (1024 + height / 2) / (height - 1);
+    let small_block = width * height < 31;
+    for t in 0..height {
+        for s in 0..width {
+            let gs = (ds * s * (mode.grid_width - 1) + 32) >> 6;
+            let gt = (dt * t * (mode.grid_height - 1) + 32) >> 6;
+            let (js, fs) = (gs >> 4, gs & 0xF);
+            let (jt, ft) = (gt >> 4, gt & 0xF);
+            let w11 = (fs * ft + 8) >> 4;
+            let w10 = ft - w11;
+            let w01 = fs - w11;
+            let w00 = 16 + w11 - fs - ft;
+            let v0 = js + jt * mode.grid_width;
+            let infill = |plane: u32| {
+                let weight = |index: u32| {
+                    weights
+                        .get(((index * planes) + plane) as usize)
+                        .copied()
+                        .unwrap_or(0)
+                };
+                (weight(v0) * w00
+                    + weight(v0 + 1) * w01
+                    + weight(v0 + mode.grid_width) * w10
+                    + weight(v0 + mode.grid_width + 1) * w11
+                    + 8)
+                    >> 4
+            };
+            let plane_weights = [infill(0), if mode.dual_plane { infill(1) } else { 0 }];
+
+            let partition = if partitions > 1 {
+                select_partition(seed, s, t, partitions, small_block)
+            } else {
+                0
+            };
+            let [e0, e1] = endpoints[partition];
+            let texel = &mut out[(t * width + s) as usize];
+            for channel in 0..4 {
+                let weight = if dual_plane_channel == Some(channel) {
+                    plane_weights[1]
+                } else {
+                    plane_weights[0]
+                };
+                let expand = |e: i32| {
+                    let e = e as u32;
+                    if srgb {
+                        (e << 8) | 0x80
+                    } else {
+                        (e << 8) | e
+                    }
+                };
+                let c =
+                    (expand(e0[channel]) * (64 - weight) + expand(e1[channel]) * weight + 32) >> 6;
+                texel[channel] = (c >> 8) as u8;
+            }
+        }
+    }
+    Some(())
+}
@@ -0,0 +1,882 @@
+//! Decoders for BC1 through BC7 blocks, as specified by the
+//! [Khronos Data Format Specification](https://registry.khronos.org/DataFormat/specs/1.3/dataformat.1.3.html#S3TC).
+//!
+//! Every decoder writes the 4x4 texels of a block in row-major order.
+
+/// Expands an RGB565 color to 8 bits per channel.
+fn rgb565(color: u16) -> [u8; 3] {
+    let r = (color >> 11) as u8 & 0x1F;
+    let g = (color >> 5) as u8 & 0x3F;
+    let b = color as u8 & 0x1F;
+    [
+        (r << 3) | (r >> 2),
+        (g << 2) | (g >> 4),
+        (b << 3) | (b >> 2),
+    ]
+}
+
+/// Decodes the color half of a BC1, BC2 or BC3 block into RGBA.
+///
+/// `punch_through` enables the three color mode of BC1, where the fourth
+/// color is transparent black.
+fn decode_color(block: &[u8], punch_through: bool, out: &mut [[u8; 4]; 16]) {
+    let c0 = u16::from_le_bytes([block[0], block[1]]);
+    let c1 = u16::from_le_bytes([block[2], block[3]]);
+    let indices = u32::from_le_bytes([block[4], block[5], block[6], block[7]]);
+    let (e0, e1) = (rgb565(c0), rgb565(c1));
+
+    let mut palette = [[0; 4]; 4];
+    palette[0] = [e0[0], e0[1], e0[2], 255];
+    palette[1] = [e1[0], e1[1], e1[2], 255];
+    if c0 > c1 || !punch_through {
+        for c in 0..3 {
+            let (a, b) = (e0[c] as u16, e1[c] as u16);
+            palette[2][c] = ((2 * a + b) / 3) as u8;
+            palette[3][c] = ((a + 2 * b) / 3) as u8;
+        }
+        palette[2][3] = 255;
+        palette[3][3] = 255;
+    } else {
+        for c in 0..3 {
+            palette[2][c] = ((e0[c] as u16 + e1[c] as u16) / 2) as u8;
+        }
+        palette[2][3] = 255;
+    }
+
+    for (i, texel) in out.iter_mut().enumerate() {
+        *texel = palette[(indices >> (2 * i)) as usize & 3];
+    }
+}
+
+/// Decodes a BC4 block, or a channel of a BC3 or BC5 block, into 16 values.
+fn decode_unorm_channel(block: &[u8]) -> [u8; 16] {
+    let (a0, a1) = (block[0] as u16, block[1] as u16);
+    let mut palette = [a0 as u8, a1 as u8, 0, 0, 0, 0, 0, 255];
+    if a0 > a1 {
+        for i in 1..7 {
+            palette[i + 1] = (((7 - i as u16) * a0 + i as u16 * a1) / 7) as u8;
+        }
+    } else {
+        for i in 1..5 {
+            palette[i + 1] = (((5 - i as u16) * a0 + i as u16 * a1) / 5) as u8;
+        }
+    }
+
+    let indices = u64::from_le_bytes([
+        block[2], block[3], block[4], block[5], block[6], block[7], 0, 0,
+    ]);
+    std::array::from_fn(|i| palette[(indices >> (3 * i)) as usize & 7])
+}
+
+/// Decodes a signed BC4 block, or a channel of a signed BC5 block, into 16 values.
+fn decode_snorm_channel(block: &[u8]) -> [i8; 16] {
+    // -128 and -127 both map to -1.0.
+    let (a0, a1) = (
+        (block[0] as i8).max(-127) as i16,
+        (block[1] as i8).max(-127) as i16,
+    );
+    let mut palette = [a0 as i8, a1 as i8, 0, 0, 0, 0, -127, 127];
+    if a0 > a1 {
+        for i in 1..7 {
+            palette[i + 1] = (((7 - i as i16) * a0 + i as i16 * a1) / 7) as i8;
+        }
+    } else {
+        for i in 1..5 {
+            palette[i + 1] = (((5 - i as i16) * a0 + i as i16 * a1) / 5) as i8;
+        }
+    }
+
+    let indices = u64::from_le_bytes([
+        block[2], block[3], block[4], block[5], block[6], block[7], 0, 0,
+    ]);
+    std::array::from_fn(|i| palette[(indices >> (3 * i)) as usize & 7])
+}
+
+/// Decodes a 8 byte BC1 block into RGBA.
+pub(super) fn decode_bc1(block: &[u8], out: &mut [[u8; 4]; 16]) {
+    decode_color(block, true, out);
+}
+
+/// Decodes a 16 byte BC2 block into RGBA.
+pub(super) fn decode_bc2(block: &[u8], out: &mut [[u8; 4]; 16]) {
+    decode_color(&block[8..], false, out);
+    let alpha = u64::from_le_bytes(block[..8].try_into().unwrap());
+    for (i, texel) in out.iter_mut().enumerate() {
+        texel[3] = (alpha >> (4 * i)) as u8 & 0xF;
+        texel[3] |= texel[3] << 4;
+    }
+}
+
+/// Decodes a 16 byte BC3 block into RGBA.
+pub(super) fn decode_bc3(block: &[u8], out: &mut [[u8; 4]; 16]) {
+    decode_color(&block[8..], false, out);
+    let alpha = decode_unorm_channel(&block[..8]);
+    for (texel, alpha) in out.iter_mut().zip(alpha) {
+        texel[3] = alpha;
+    }
+}
+
+/// Decodes a 8 byte BC4 block into R.
+pub(super) fn decode_bc4_unorm(block: &[u8], out: &mut [[u8; 1]; 16]) {
+    for (texel, r) in out.iter_mut().zip(decode_unorm_channel(block)) {
+        *texel = [r];
+    }
+}
+
+/// Decodes a signed 8 byte BC4 block into R.
+pub(super) fn decode_bc4_snorm(block: &[u8], out: &mut [[u8; 1]; 16]) {
+    for (texel, r) in out.iter_mut().zip(decode_snorm_channel(block)) {
+        *texel = [r as u8];
+    }
+}
+
+/// Decodes a 16 byte BC5 block into RG.
+pub(super) fn decode_bc5_unorm(block: &[u8], out: &mut [[u8; 2]; 16]) {
+    let r = decode_unorm_channel(&block[..8]);
+    let g = decode_unorm_channel(&block[8..]);
+    for (i, texel) in out.iter_mut().enumerate() {
+        *texel = [r[i], g[i]];
+    }
+}
+
+/// Decodes a signed 16 byte BC5 block into RG.
+pub(super) fn decode_bc5_snorm(block: &[u8], out: &mut [[u8; 2]; 16]) {
+    let r = decode_snorm_channel(&block[..8]);
+    let g = decode_snorm_channel(&block[8..]);
+    for (i, texel) in out.iter_mut().enumerate() {
+        *texel = [r[i] as u8, g[i] as u8];
+    }
+}
+
+/// Reads the bits of a 128 bit block from the least significant one up.
+struct BitReader {
+    bits: u128,
+}
+
+impl BitReader {
+    fn new(block: &[u8]) -> Self {
+        Self {
+            bits: u128::from_le_bytes(block.try_into().unwrap()),
+        }
+    }
+
+    fn read(&mut self, count: u32) -> u32 {
+        let value = (self.bits & ((1 << count) - 1)) as u32;
+        self.bits >>= count;
+        value
+    }
+}
+
+/// Subset of each texel in the 64 partitions of two subsets, one bit per texel.
+const PARTITIONS_2: [u16; 64] = [
+    0xCCCC, 0x8888, 0xEEEE, 0xECC8, 0xC880, 0xFEEC, 0xFEC8, 0xEC80, 0xC800, 0xFFEC, 0xFE80, 0xE800,
+    0xFFE8, 0xFF00, 0xFFF0, 0xF000, 0xF710, 0x008E, 0x7100, 0x08CE, 0x008C, 0x7310, 0x3100, 0x8CCE,
+    0x088C, 0x3110, 0x6666, 0x366C, 0x17E8, 0x0FF0, 0x718E, 0x399C, 0xAAAA, 0xF0F0, 0x5A5A, 0x33CC,
+    0x3C3C, 0x55AA, 0x9696, 0xA55A, 0x73CE, 0x13C8, 0x324C, 0x3BDC, 0x6996, 0xC33C, 0x9966, 0x0660,
+    0x0272, 0x04E4, 0x4E40, 0x2720, 0xC936, 0x936C, 0x39C6, 0x639C, 0x9336, 0x9CC6, 0x817E, 0xE718,
+    0xCCF0, 0x0FCC, 0x7744, 0xEE22,
+];
+
+/// Subset of each texel in the 64 partitions of three subsets, two bits per texel.
+const PARTITIONS_3: [u32; 64] = [
+    0xAA685050, 0x6A5A5040, 0x5A5A4200, 0x5450A0A8, 0xA5A50000, 0xA0A05050, 0x5555A0A0, 0x5A5A5050,
+    0xAA550000, 0xAA555500, 0xAAAA5500, 0x90909090, 0x94949494, 0xA4A4A4A4, 0xA9A59450, 0x2A0A4250,
+    0xA5945040, 0x0A425054, 0xA5A5A500, 0x55A0A0A0, 0xA8A85454, 0x6A6A4040, 0xA4A45000, 0x1A1A0500,
+    0x0050A4A4, 0xAAA59090, 0x14696914, 0x69691400, 0xA08585A0, 0xAA821414, 0x50A4A450, 0x6A5A0200,
+    0xA9A58000, 0x5090A0A8, 0xA8A09050, 0x24242424, 0x00AA5500, 0x24924924, 0x24499224, 0x50A50A50,
+    0x500AA550, 0xAAAA4444, 0x66660000, 0xA5A0A5A0, 0x50A050A0, 0x69286928, 0x44AAAA44, 0x66666600,
+    0xAA444444, 0x54A854A8, 0x95809580, 0x96969600, 0xA85454A8, 0x80959580, 0xAA141414, 0x96960000,
+    0xAAAA1414, 0xA05050A0, 0xA0A5A5A0, 0x96000000, 0x40804080, 0xA9A8A9A8, 0xAAAAAA44, 0x2A4A5254,
+];
+
+/// Index of the anchor texel of the second subset of the partitions of two subsets.
+const ANCHORS_2: [u8; 64] = [
+    15, 15, 15, 15, 15, 15, 15, 15, 15, 15, 15, 15, 15, 15, 15, 15, 15, 2, 8, 2, 2, 8, 8, 15, 2, 8,
+    2, 2, 8, 8, 2, 2, 15, 15, 6, 8, 2, 8, 15, 15, 2, 8, 2, 2, 2, 15, 15, 6, 6, 2, 6, 8, 15, 15, 2,
+    2, 15, 15, 15, 15, 15, 2, 2, 15,
+];
+
+/// Index of the anchor texel of the second subset of the partitions of three subsets.
+const ANCHORS_3_SECOND: [u8; 64] = [
+    3, 3, 15, 15, 8, 3, 15, 15, 8, 8, 6, 6, 6, 5, 3, 3, 3, 3, 8, 15, 3, 3, 6, 10, 5, 8, 8, 6, 8, 5,
+    15, 15, 8, 15, 3, 5, 6, 10, 8, 15, 15, 3, 15, 5, 15, 15, 15, 15, 3, 15, 5, 5, 5, 8, 5, 10, 5,
+    10, 8, 13, 15, 12, 3, 3,
+];
+
+/// Index of the anchor texel of the third subset of the partitions of three subsets.
+const ANCHORS_3_THIRD: [u8; 64] = [
+    15, 8, 8, 3, 15, 15, 3, 8, 15, 15, 15, 15, 15, 15, 15, 8, 15, 8, 15, 3, 15, 8, 15, 8, 3, 15, 6,
+    10, 15, 15, 10, 8, 15, 3, 15, 10, 10, 8, 9, 10, 6, 15, 8, 15, 3, 6, 6, 8, 15, 3, 15, 15, 15,
+    15, 15, 15, 15, 15, 15, 15, 3, 15, 15, 8,
+];
+
+/// Returns the subset of each texel in a partition of `subsets` subsets.
+fn partition(subsets: usize, index: usize) -> [usize; 16] {
+    std::array::from_fn(|i| match subsets {
+        1 => 0,
+        2 => (PARTITIONS_2[index] >> i) as usize & 1,
+        _ => (PARTITIONS_3[index] >> (2 * i)) as usize & 3,
+    })
+}
+
+/// Returns whether texel `i` is the anchor of its subset, whose index is
+/// stored with one bit less.
+fn is_anchor(subsets: usize, index: usize, i: usize) -> bool {
+    i == 0
+        || match subsets {
+            1 => false,
+            2 => i == ANCHORS_2[index] as usize,
+            _ => i == ANCHORS_3_SECOND[index] as usize || i == ANCHORS_3_THIRD[index] as usize,
+        }
+}
+
+/// Reads the indices of the 16 texels of a block.
+fn read_indices(reader: &mut BitReader, bits: u32, anchor: impl Fn(usize) -> bool) -> [u32; 16] {
+    std::array::from_fn(|i| reader.read(bits - anchor(i) as u32))
+}
+
+/// Interpolation weights of indices of 2, 3 and 4 bits, out of 64.
+fn weights(bits: u32) -> &'static [u32] {
+    match bits {
+        2 => &[0, 21, 43, 64],
+        3 => &[0, 9, 18, 27, 37, 46, 55, 64],
+        _ => &[0, 4, 9, 13, 17, 21, 26, 30, 34, 38, 43, 47, 51, 55, 60, 64],
+    }
+}
+
+fn interpolate(e0: u32, e1: u32, weight: u32) -> u32 {
+    ((64 - weight) * e0 + weight * e1 + 32) >> 6
+}
+
+/// Properties of a BC7 mode.
+struct Bc7Mode {
+    subsets: usize,
+    partition_bits: u32,
+    rotation_bits: u32,
+    index_selection_bits: u32,
+    color_bits: u32,
+    alpha_bits: u32,
+    endpoint_p_bits: bool,
+    shared_p_bits: bool,
+    index_bits: u32,
+    secondary_index_bits: u32,
+}
+
+#[allow(clippy::too_many_arguments)]
+const fn bc7_mode(
+    subsets: usize,
+    partition_bits: u32,
+    rotation_bits: u32,
+    index_selection_bits: u32,
+    color_bits: u32,
+    alpha_bits: u32,
+    endpoint_p_bits: bool,
+    shared_p_bits: bool,
+    index_bits: u32,
+    secondary_index_bits: u32,
+) -> Bc7Mode {
+    Bc7Mode {
+        subsets,
+        partition_bits,
+        rotation_bits,
+        index_selection_bits,
+        color_bits,
+        alpha_bits,
+        endpoint_p_bits,
+        shared_p_bits,
+        index_bits,
+        secondary_index_bits,
+    }
+}
+
+const BC7_MODES: [Bc7Mode; 8] = [
+    bc7_mode(3, 4, 0, 0, 4, 0, true, false, 3, 0),
+    bc7_mode(2, 6, 0, 0, 6, 0, false, true, 3, 0),
+    bc7_mode(3, 6, 0, 0, 5, 0, false, false, 2, 0),
+    bc7_mode(2, 6, 0, 0, 7, 0, true, false, 2, 0),
+    bc7_mode(1, 0, 2, 1, 5, 6, false, false, 2, 3),
+    bc7_mode(1, 0, 2, 0, 7, 8, false, false, 2, 2),
+    bc7_mode(1, 0, 0, 0, 7, 7, true, false, 4, 0),
+    bc7_mode(2, 6, 0, 0, 5, 5, true, false, 2, 0),
+];
+
+/// Decodes a 16 byte BC7 block into RGBA.
+pub(super) fn decode_bc7(block: &[u8], out: &mut [[u8; 4]; 16]) {
+    let mut reader = BitReader::new(block);
+    let Some(mode_index) = (0..8).find(|_| reader.read(1) == 1) else {
+        // Reserved mode.
+        *out = [[0; 4]; 16];
+        return;
+    };
+    let mode = &BC7_MODES[mode_index];
+
+    let partition_index = reader.read(mode.partition_bits) as usize;
+    let rotation = reader.read(mode.rotation_bits);
+    let index_selection = reader.read(mode.index_selection_bits);
+
+    // Endpoints of every subset, channel by channel.
+    let mut endpoints = [[[0u32; 4]; 2]; 3];
+    let channels = if mode.alpha_bits > 0 { 4 } else { 3 };
+    for channel in 0..channels {
+        let bits = if channel == 3 {
+            mode.alpha_bits
+        } else {
+            mode.color_bits
+        };
+        for subset in endpoints.iter_mut().take(mode.subsets) {
+            for endpoint in subset.iter_mut() {
+                endpoint[channel] = reader.read(bits);
+            }
+        }
+    }
+
+    let mut color_bits = mode.color_bits;
+    let mut alpha_bits = mode.alpha_bits;
+    if mode.endpoint_p_bits || mode.shared_p_bits {
+        let mut p_bits = [[0; 2]; 3];
+        for subset in p_bits.iter_mut().take(mode.subsets) {
+            if mode.endpoint_p_bits {
+                *subset = [reader.read(1), reader.read(1)];
+            } else {
+                let p_bit = reader.read(1);
+                *subset = [p_bit, p_bit];
+            }
+        }
+        for (subset, p_bits) in endpoints.iter_mut().zip(p_bits) {
+            for (endpoint, p_bit) in subset.iter_mut().zip(p_bits) {
+                for value in endpoint.iter_mut().take(channels) {
+                    *value = (*value << 1) | p_bit;
+                }
+            }
+        }
+        color_bits += 1;
+        if alpha_bits > 0 {
+            alpha_bits += 1;
+        }
+    }
+
+    for endpoint in endpoints.iter_mut().flatten() {
+        for (channel, value) in endpoint.iter_mut().enumerate() {
+            let bits = if channel == 3 { alpha_bits } else { color_bits };
+            *value = if bits == 0 {
+                255
+            } else {
+                (*value << (8 - bits)) | (*value >> (2 * bits - 8))
+            };
+        }
+    }
+
+    let subsets = partition(mode.subsets, partition_index);
+    let indices = read_indices(&mut reader, mode.index_bits, |i| {
+        is_anchor(mode.subsets, partition_index, i)
+    });
+    let secondary_indices = if mode.secondary_index_bits > 0 {
+        Some(read_indices(&mut reader, mode.secondary_index_bits, |i| {
+            i == 0
+        }))
+    } else {
+        None
+    };
+
+    for (i, texel) in out.iter_mut().enumerate() {
+        let [e0, e1] = endpoints[subsets[i]];
+        let (color_weight, alpha_weight) = match secondary_indices {
+            None => {
+                let weight = weights(mode.index_bits)[indices[i] as usize];
+                (weight, weight)
+            }
+            Some(secondary) => {
+                let primary = weights(mode.index_bits)[indices[i] as usize];
+                let secondary = weights(mode.secondary_index_bits)[secondary[i] as usize];
+                if index_selection == 0 {
+                    (primary, secondary)
+                } else {
+                    (secondary, primary)
+                }
+            }
+        };
+        for channel in 0..4 {
+            let weight = if channel == 3 {
+                alpha_weight
+            } else {
+                color_weight
+            };
+            texel[channel] = interpolate(e0[channel], e1[channel], weight) as u8;
+        }
+        match rotation {
+            1 => texel.swap(0, 3),
+            2 => texel.swap(1, 3),
+            3 => texel.swap(2, 3),
+            _ => {}
+        }
+    }
+}
+
+/// Where a BC6H mode stores the bits of its endpoints.
+///
+/// Every field is stored least significant bit first, and reads `count`
+/// bits of `endpoint`'s `channel` starting at `shift`. A negative `count`
+/// stores the bits in reverse order, starting at the most significant one.
+struct Bc6hField {
+    endpoint: usize,
+    channel: usize,
+    shift: u32,
+    count: i32,
+}
+
+const fn field(endpoint: usize, channel: usize, shift: u32, count: i32) -> Bc6hField {
+    Bc6hField {
+        endpoint,
+        channel,
+        shift,
+        count,
+    }
+}
+
+// Shorthands for the endpoints and channels, named like in the specification:
+// `w` and `x` are the endpoints of the first subset, `y` and `z` those of the
+// second one.
+const W: usize = 0;
+const X: usize = 1;
+const Y: usize = 2;
+const Z: usize = 3;
+const R: usize = 0;
+const G: usize = 1;
+const B: usize = 2;
+
+/// Properties of a BC6H mode.
+struct Bc6hMode {
+    /// Whether the mode has two subsets and 3 bit indices, or one and 4 bit indices.
+    two_subsets: bool,
+    /// Whether endpoints other than `w` are stored as deltas to `w`.
+    transformed: bool,
+    /// Precision of `w`.
+    endpoint_bits: u32,
+    /// Precision of the other endpoints, per channel.
+    delta_bits: [u32; 3],
+    fields: &'static [Bc6hField],
+}
+
+/// BC6H modes, by the value of their mode bits.
+fn bc6h_mode(mode: u32) -> Option<Bc6hMode> {
+    const MODE_1: &[Bc6hField] = &[
+        field(Y, G, 4, 1),
+        field(Y, B, 4, 1),
+        field(Z, B, 4, 1),
+        field(W, R, 0, 10),
+        field(W, G, 0, 10),
+        field(W, B, 0, 10),
+        field(X, R, 0, 5),
+        field(Z, G, 4, 1),
+        field(Y, G, 0, 4),
+        field(X, G, 0, 5),
+        field(Z, B, 0, 1),
+        field(Z, G, 0, 4),
+        field(X, B, 0, 5),
+        field(Z, B, 1, 1),
+        field(Y, B, 0, 4),
+        field(Y, R, 0, 5),
+        field(Z, B, 2, 1),
+        field(Z, R, 0, 5),
+        field(Z, B, 3, 1),
+    ];
+    const MODE_2: &[Bc6hField] = &[
+        field(Y, G, 5, 1),
+        field(Z, G, 4, 1),
+        field(Z, G, 5, 1),
+        field(W, R, 0, 7),
+        field(Z, B, 0, 1),
+        field(Z, B, 1, 1),
+        field(Y, B, 4, 1),
+        field(W, G, 0, 7),
+        field(Y, B, 5, 1),
+        field(Z, B, 2, 1),
+        field(Y, G, 4, 1),
+        field(W, B, 0, 7),
+        field(Z, B, 3, 1),
+        field(Z, B, 5, 1),
+        field(Z, B, 4, 1),
+        field(X, R, 0, 6),
+        field(Y, G, 0, 4),
+        field(X, G, 0, 6),
+        field(Z, G, 0, 4),
+        field(X, B, 0, 6),
+        field(Y, B, 0, 4),
+        field(Y, R, 0, 6),
+        field(Z, R, 0, 6),
+    ];
+    const MODE_3: &[Bc6hField] = &[
+        field(W, R, 0, 10),
+        field(W, G, 0, 10),
+        field(W, B, 0, 10),
+        field(X, R, 0, 5),
+        field(W, R, 10, 1),
+        field(Y, G, 0, 4),
+        field(X, G, 0, 4),
+        field(W, G, 10, 1),
+        field(Z, B, 0, 1),
+        field(Z, G, 0, 4),
+        field(X, B, 0, 4),
+        field(W, B, 10, 1),
+        field(Z, B, 1, 1),
+        field(Y, B, 0, 4),
+        field(Y, R, 0, 5),
+        field(Z, B, 2, 1),
+        field(Z, R, 0, 5),
+        field(Z, B, 3, 1),
+    ];
+    const MODE_4: &[Bc6hField] = &[
+        field(W, R, 0, 10),
+        field(W, G, 0, 10),
+        field(W, B, 0, 10),
+        field(X, R, 0, 4),
+        field(W, R, 10, 1),
+        field(Z, G, 4, 1),
+        field(Y, G, 0, 4),
+        field(X, G, 0, 5),
+        field(W, G, 10, 1),
+        field(Z, G, 0, 4),
+        field(X, B, 0, 4),
+        field(W, B, 10, 1),
+        field(Z, B, 1, 1),
+        field(Y, B, 0, 4),
+        field(Y, R, 0, 4),
+        field(Z, B, 0, 1),
+        field(Z, B, 2, 1),
+        field(Z, R, 0, 4),
+        field(Y, G, 4, 1),
+        field(Z, B, 3, 1),
+    ];
+    const MODE_5: &[Bc6hField] = &[
+        field(W, R, 0, 10),
+        field(W, G, 0, 10),
+        field(W, B, 0, 10),
+        field(X, R, 0, 4),
+        field(W, R, 10, 1),
+        field(Y, B, 4, 1),
+        field(Y, G, 0, 4),
+        field(X, G, 0, 4),
+        field(W, G, 10, 1),
+        field(Z, B, 0, 1),
+        field(Z, G, 0, 4),
+        field(X, B, 0, 5),
+        field(W, B, 10, 1),
+        field(Y, B, 0, 4),
+        field(Y, R, 0, 4),
+        field(Z, B, 1, 1),
+        field(Z, B, 2, 1),
+        field(Z, R, 0, 4),
+        field(Z, B, 4, 1),
+        field(Z, B, 3, 1),
+    ];
+    const MODE_6: &[Bc6hField] = &[
+        field(W, R, 0, 9),
+        field(Y, B, 4, 1),
+        field(W, G, 0, 9),
+        field(Y, G, 4, 1),
+        field(W, B, 0, 9),
+        field(Z, B, 4, 1),
+        field(X, R, 0, 5),
+        field(Z, G, 4, 1),
+        field(Y, G, 0, 4),
+        field(X, G, 0, 5),
+        field(Z, B, 0, 1),
+        field(Z, G, 0, 4),
+        field(X, B, 0, 5),
+        field(Z, B, 1, 1),
+        field(Y, B, 0, 4),
+        field(Y, R, 0, 5),
+        field(Z, B, 2, 1),
+        field(Z, R, 0, 5),
+        field(Z, B, 3, 1),
+    ];
+    const MODE_7: &[Bc6hField] = &[
+        field(W, R, 0, 8),
+        field(Z, G, 4, 1),
+        field(Y, B, 4, 1),
+        field(W, G, 0, 8),
+        field(Z, B, 2, 1),
+        field(Y, G, 4, 1),
+        field(W, B, 0, 8),
+        field(Z, B, 3, 1),
+        field(Z, B, 4, 1),
+        field(X, R, 0, 6),
+        field(Y, G, 0, 4),
+        field(X, G, 0, 5),
+        field(Z, B, 0, 1),
+        field(Z, G, 0, 4),
+        field(X, B, 0, 5),
+        field(Z, B, 1, 1),
+        field(Y, B, 0, 4),
+        field(Y, R, 0, 6),
+        field(Z, R, 0, 6),
+    ];
+    const MODE_8: &[Bc6hField] = &[
+        field(W, R, 0, 8),
+        field(Z, B, 0, 1),
+        field(Y, B, 4, 1),
+        field(W, G, 0, 8),
+        field(Y, G, 5, 1),
+        field(Y, G, 4, 1),
+        field(W, B, 0, 8),
+        field(Z, G, 5, 1),
+        field(Z, B, 4, 1),
+        field(X, R, 0, 5),
+        field(Z, G, 4, 1),
+        field(Y, G, 0, 4),
+        field(X, G, 0, 6),
+        field(Z, G, 0, 4),
+        field(X, B, 0, 5),
+        field(Z, B, 1, 1),
+        field(Y, B, 0, 4),
+        field(Y, R, 0, 5),
+        field(Z, B, 2, 1),
+        field(Z, R, 0, 5),
+        field(Z, B, 3, 1),
+    ];
+    const MODE_9: &[Bc6hField] = &[
+        field(W, R, 0, 8),
+        field(Z, B, 1, 1),
+        field(Y, B, 4, 1),
+        field(W, G, 0, 8),
+        field(Y, B, 5, 1),
+        field(Y, G, 4, 1),
+        field(W, B, 0, 8),
+        field(Z, B, 5, 1),
+        field(Z, B, 4, 1),
+        field(X, R, 0, 5),
+        field(Z, G, 4, 1),
+        field(Y, G, 0, 4),
+        field(X, G, 0, 5),
+        field(Z, B, 0, 1),
+        field(Z, G, 0, 4),
+        field(X, B, 0, 6),
+        field(Y, B, 0, 4),
+        field(Y, R, 0, 5),
+        field(Z, B, 2, 1),
+        field(Z, R, 0, 5),
+        field(Z, B, 3, 1),
+    ];
+    const MODE_10: &[Bc6hField] = &[
+        field(W, R, 0, 6),
+        field(Z, G, 4, 1),
+        field(Z, B, 0, 1),
+        field(Z, B, 1, 1),
+        field(Y, B, 4, 1),
+        field(W, G, 0, 6),
+        field(Y, G, 5, 1),
+        field(Y, B, 5, 1),
+        field(Z, B, 2, 1),
+        field(Y, G, 4, 1),
+        field(W, B, 0, 6),
+        field(Z, G, 5, 1),
+        field(Z, B, 3, 1),
+        field(Z, B, 5, 1),
+        field(Z, B, 4, 1),
+        field(X, R, 0, 6),
+        field(Y, G, 0, 4),
+        field(X, G, 0, 6),
+        field(Z, G, 0, 4),
+        field(X, B, 0, 6),
+        field(Y, B, 0, 4),
+        field(Y, R, 0, 6),
+        field(Z, R, 0, 6),
+    ];
+    const MODE_11: &[Bc6hField] = &[
+        field(W, R, 0, 10),
+        field(W, G, 0, 10),
+        field(W, B, 0, 10),
+        field(X, R, 0, 10),
+        field(X, G, 0, 10),
+        field(X, B, 0, 10),
+    ];
+    const MODE_12: &[Bc6hField] = &[
+        field(W, R, 0, 10),
+        field(W, G, 0, 10),
+        field(W, B, 0, 10),
+        field(X, R, 0, 9),
+        field(W, R, 10, 1),
+        field(X, G, 0, 9),
+        field(W, G, 10, 1),
+        field(X, B, 0, 9),
+        field(W, B, 10, 1),
+    ];
+    const MODE_13: &[Bc6hField] = &[
+        field(W, R, 0, 10),
+        field(W, G, 0, 10),
+        field(W, B, 0, 10),
+        field(X, R, 0, 8),
+        field(W, R, 10, -2),
+        field(X, G, 0, 8),
+        field(W, G, 10, -2),
+        field(X, B, 0, 8),
+        field(W, B, 10, -2),
+    ];
+    const MODE_14: &[Bc6hField] = &[
+        field(W, R, 0, 10),
+        field(W, G, 0, 10),
+        field(W, B, 0, 10),
+        field(X, R, 0, 4),
+        field(W, R, 10, -6),
+        field(X, G, 0, 4),
+        field(W, G, 10, -6),
+        field(X, B, 0, 4),
+        field(W, B, 10, -6),
+    ];
+
+    let two = |transformed, endpoint_bits, delta_bits, fields| Bc6hMode {
+        two_subsets: true,
+        transformed,
+        endpoint_bits,
+        delta_bits,
+        fields,
+    };
+    let one = |transformed, endpoint_bits, delta_bits, fields| Bc6hMode {
+        two_subsets: false,
+        transformed,
+        endpoint_bits,
+        delta_bits,
+        fields,
+    };
+    Some(match mode {
+        0b00 => two(true, 10, [5, 5, 5], MODE_1),
+        0b01 => two(true, 7, [6, 6, 6], MODE_2),
+        0b00010 => two(true, 11, [5, 4, 4], MODE_3),
+        0b00110 => two(true, 11, [4, 5, 4], MODE_4),
+        0b01010 => two(true, 11, [4, 4, 5], MODE_5),
+        0b01110 => two(true, 9, [5, 5, 5], MODE_6),
+        0b10010 => two(true, 8, [6, 5, 5], MODE_7),
+        0b10110 => two(true, 8, [5, 6, 5], MODE_8),
+        0b11010 => two(true, 8, [5, 5, 6], MODE_9),
+        0b11110 => two(false, 6, [6, 6, 6], MODE_10),
+        0b00011 => one(false, 10, [10, 10, 10], MODE_11),
+        0b00111 => one(true, 11, [9, 9, 9], MODE_12),
+        0b01011 => one(true, 12, [8, 8, 8], MODE_13),
+        0b01111 => one(true, 16, [4, 4, 4], MODE_14),
+        _ => return None,
+    })
+}
+
+fn sign_extend(value: u32, bits: u32) -> i32 {
+    let shift = 32 - bits;
+    ((value << shift) as i32) >> shift
+}
+
+/// Scales an endpoint of `bits` bits to 16 bits.
+fn unquantize_bc6h(value: i32, bits: u32, signed: bool) -> i32 {
+    if !signed {
+        if bits >= 15 || value == 0 {
+            value
+        } else if value == (1 << bits) - 1 {
+            0xFFFF
+        } else {
+            ((value << 15) + 0x4000) >> (bits - 1)
+        }
+    } else {
+        let magnitude = value.abs();
+        let unquantized = if bits >= 16 || magnitude == 0 {
+            magnitude
+        } else if magnitude >= (1 << (bits - 1)) - 1 {
+            0x7FFF
+        } else {
+            ((magnitude << 15) + 0x4000) >> (bits - 1)
+        };
+        if value < 0 {
+            -unquantized
+        } else {
+            unquantized
+        }
+    }
+}
+
+/// Converts an interpolated value to the bits of a half float.
+fn finish_bc6h(value: i32, signed: bool) -> u16 {
+    if !signed {
+        ((value * 31) >> 6) as u16
+    } else if value < 0 {
+        0x8000 | (((-value) * 31) >> 5) as u16
+    } else {
+        ((value * 31) >> 5) as u16
+    }
+}
+
+/// Decodes a 16 byte BC6H block into RGBA half floats, as little endian bytes.
+pub(super) fn decode_bc6h(block: &[u8], signed: bool, out: &mut [[u8; 8]; 16]) {
+    let mut reader = BitReader::new(block);
+    let mut mode_bits = reader.read(2);
+    if mode_bits > 1 {
+        mode_bits |= reader.read(3) << 2;
+    }
+    let Some(mode) = bc6h_mode(mode_bits) else {
+        // Reserved mode.
+        *out = [[0; 8]; 16];
+        return;
+    };
+
+    let mut endpoints = [[0u32; 3]; 4];
+    for field in mode.fields {
+        let value = &mut endpoints[field.endpoint][field.channel];
+        for i in 0..field.count.unsigned_abs() {
+            let bit = if field.count < 0 {
+                field.count.unsigned_abs() - 1 - i
+            } else {
+                i
+            };
+            *value |= reader.read(1) << (field.shift + bit);
+        }
+    }
+    let partition_index = if mode.two_subsets {
+        reader.read(5) as usize
+    } else {
+        0
+    };
+
+    let endpoint_count = if mode.two_subsets { 4 } else { 2 };
+    let mut unquantized = [[0i32; 3]; 4];
+    for channel in 0..3 {
+        let bits = mode.endpoint_bits;
+        let w = endpoints[W][channel];
+        let base = if signed {
+            sign_extend(w, bits)
+        } else {
+            w as i32
+        };
+        unquantized[W][channel] = unquantize_bc6h(base, bits, signed);
+        for endpoint in 1..endpoint_count {
+            let value = endpoints[endpoint][channel];
+            let value = if mode.transformed {
+                let delta = sign_extend(value, mode.delta_bits[channel]);
+                let sum = (w as i32 + delta) as u32 & ((1 << bits) - 1);
+                if signed {
+                    sign_extend(sum, bits)
+                } else {
+                    sum as i32
+                }
+            } else if signed {
+                sign_extend(value, bits)
+            } else {
+                value as i32
+            };
+            unquantized[endpoint][channel] = unquantize_bc6h(value, bits, signed);
+        }
+    }
+
+    let subsets = if mode.two_subsets { 2 } else { 1 };
+    let index_bits = if mode.two_subsets { 3 } else { 4 };
+    let texel_subsets = partition(subsets, partition_index);
+    let indices = read_indices(&mut reader, index_bits, |i| {
+        is_anchor(subsets, partition_index, i)
+    });
+    for (i, texel) in out.iter_mut().enumerate() {
+        let subset = texel_subsets[i];
+        let weight = weights(index_bits)[indices[i] as usize] as i32;
+        let (e0, e1) = (unquantized[2 * subset], unquantized[2 * subset + 1]);
+        for channel in 0..3 {
+            let value = ((64 - weight) * e0[channel] + weight * e1[channel] + 32) >> 6;
+            let half = finish_bc6h(value, signed);
+            texel[2 * channel..2 * channel + 2].copy_from_slice(&half.to_le_bytes());
+        }
+        // 1.0
+        texel[6..].copy_from_slice(&0x3C00u16.to_le_bytes());
+    }
+}
@@ -0,0 +1,190 @@
+//! Decoding of compressed textures on the CPU, for devices that don't support
+//! their format.
+//!
+//! BC formats are mostly supported on desktop, and ASTC on mobile, so rather
+//! than shipping every texture in both, an application can upload them with
+//! [`DeviceExt::create_texture_with_data_or_transcode`], which decodes the
+//! texture to an uncompressed format if needed. That costs memory and upload
+//! time, so it's meant as a fallback rather than the usual path.
+//!
+//! [`DeviceExt::create_texture_with_data_or_transcode`]: super::DeviceExt::create_texture_with_data_or_transcode
+
+mod astc;
+mod bc;
+
+use crate::{AstcChannel, TextureDescriptor, TextureFormat};
+
+use super::TextureDataOrder;
+
+/// Returns the uncompressed format that [`transcode_texture_data`] decodes
+/// textures of `format` to, or `None` if it can't decode `format`.
+///
+/// BC1, BC2, BC3, BC7 and ASTC formats are decoded to [`TextureFormat::Rgba8Unorm`]
+/// or [`TextureFormat::Rgba8UnormSrgb`], BC4 and BC5 to 8 bit formats with one
+/// or two channels, and BC6H to [`TextureFormat::Rgba16Float`].
+///
+/// ASTC formats with HDR channels, ETC2 and EAC formats can't be decoded.
+pub fn transcoded_format(format: TextureFormat) -> Option<TextureFormat> {
+    use TextureFormat as Tf;
+    Some(match format {
+        Tf::Bc1RgbaUnorm | Tf::Bc2RgbaUnorm | Tf::Bc3RgbaUnorm | Tf::Bc7RgbaUnorm => Tf::Rgba8Unorm,
+        Tf::Bc1RgbaUnormSrgb
+        | Tf::Bc2RgbaUnormSrgb
+        | Tf::Bc3RgbaUnormSrgb
+        | Tf::Bc7RgbaUnormSrgb => Tf::Rgba8UnormSrgb,
+        Tf::Bc4RUnorm => Tf::R8Unorm,
+        Tf::Bc4RSnorm => Tf::R8Snorm,
+        Tf::Bc5RgUnorm => Tf::Rg8Unorm,
+        Tf::Bc5RgSnorm => Tf::Rg8Snorm,
+        Tf::Bc6hRgbUfloat | Tf::Bc6hRgbFloat => Tf::Rgba16Float,
+        Tf::Astc {
+            channel: AstcChannel::Unorm,
+            ..
+        } => Tf::Rgba8Unorm,
+        Tf::Astc {
+            channel: AstcChannel::UnormSrgb,
+            ..
+        } => Tf::Rgba8UnormSrgb,
+        _ => return None,
+    })
+}
+
+/// Decodes a block of `format` into `out`, which receives its texels in
+/// row-major order in the format returned by [`transcoded_format`].
+fn decode_block(format: TextureFormat, block: &[u8], out: &mut [u8]) {
+    use TextureFormat as Tf;
+
+    fn copy<const N: usize>(texels: &[[u8; N]], out: &mut [u8]) {
+        for (out, texel) in out.iter_mut().zip(texels.iter().flatten()) {
+            *out = *texel;
+        }
+    }
+
+    match format {
+        Tf::Bc1RgbaUnorm | Tf::Bc1RgbaUnormSrgb => {
+            let mut texels = [[0; 4]; 16];
+            bc::decode_bc1(block, &mut texels);
+            copy(&texels, out);
+        }
+        Tf::Bc2RgbaUnorm | Tf::Bc2RgbaUnormSrgb => {
+            let mut texels = [[0; 4]; 16];
+            bc::decode_bc2(block, &mut texels);
+            copy(&texels, out);
+        }
+        Tf::Bc3RgbaUnorm | Tf::Bc3RgbaUnormSrgb => {
+            let mut texels = [[0; 4]; 16];
+            bc::decode_bc3(block, &mut texels);
+            copy(&texels, out);
+        }
+        Tf::Bc4RUnorm => {
+            let mut texels = [[0; 1]; 16];
+            bc::decode_bc4_unorm(block, &mut texels);
+            copy(&texels, out);
+        }
+        Tf::Bc4RSnorm => {
+            let mut texels = [[0; 1]; 16];
+            bc::decode_bc4_snorm(block, &mut texels);
+            copy(&texels, out);
+        }
+        Tf::Bc5RgUnorm => {
+            let mut texels = [[0; 2]; 16];
+            bc::decode_bc5_unorm(block, &mut texels);
+            copy(&texels, out);
+        }
+        Tf::Bc5RgSnorm => {
+            let mut texels = [[0; 2]; 16];
+            bc::decode_bc5_snorm(block, &mut texels);
+            copy(&texels, out);
+        }
+        Tf::Bc6hRgbUfloat | Tf::Bc6hRgbFloat => {
+            let mut texels = [[0; 8]; 16];
+            bc::decode_bc6h(block, format == Tf::Bc6hRgbFloat, &mut texels);
+            copy(&texels, out);
+        }
+        Tf::Bc7RgbaUnorm | Tf::Bc7RgbaUnormSrgb => {
+            let mut texels = [[0; 4]; 16];
+            bc::decode_bc7(block, &mut texels);
+            copy(&texels, out);
+        }
+        Tf::Astc { block: _, channel } => {
+            let (width, height) = format.block_dimensions();
+            let mut texels = vec![[0; 4]; (width * height) as usize];
+            astc::decode(
+                block,
+                width,
+                height,
+                channel == AstcChannel::UnormSrgb,
+                &mut texels,
+            );
+            copy(&texels, out);
+        }
+        _ => unreachable!(),
+    }
+}
+
+/// Decodes the compressed data of an entire texture and its mipmaps, laid out
+/// as for [`DeviceExt::create_texture_with_data`], to the format returned by
+/// [`transcoded_format`].
+///
+/// The decoded data is laid out in the same `order`, so it can be uploaded
+/// with [`DeviceExt::create_texture_with_data`] to a texture of the
+/// uncompressed format.
+///
+/// # Panics
+///
+/// - If [`transcoded_format`] returns `None` for `desc.format`.
+/// - If `data` is too short for the texture.
+///
+/// [`DeviceExt::create_texture_with_data`]: super::DeviceExt::create_texture_with_data
+pub fn transcode_texture_data(
+    desc: &TextureDescriptor<'_>,
+    order: TextureDataOrder,
+    data: &[u8],
+) -> Vec<u8> {
+    let target = transcoded_format(desc.format)
+        .unwrap_or_else(|| panic!("Unable to transcode textures of {:?}", desc.format));
+    let (block_width, block_height) = desc.format.block_dimensions();
+    let block_size = desc.format.block_copy_size(None).unwrap() as usize;
+    let texel_size = target.block_copy_size(None).unwrap() as usize;
+
+    let layers = desc.array_layer_count();
+    let mip_levels = (0..layers * desc.mip_level_count).map(|i| match order {
+        TextureDataOrder::LayerMajor => i % desc.mip_level_count,
+        TextureDataOrder::MipMajor => i / layers,
+    });
+
+    let mut output = Vec::new();
+    let mut blocks = data.chunks_exact(block_size);
+    let mut decoded = vec![0; (block_width * block_height) as usize * texel_size];
+    for mip in mip_levels {
+        let mut mip_size = desc.mip_level_size(mip).unwrap();
+        if desc.dimension != wgt::TextureDimension::D3 {
+            mip_size.depth_or_array_layers = 1;
+        }
+        let (width, height) = (mip_size.width as usize, mip_size.height as usize);
+        let row_size = width * texel_size;
+
+        for _ in 0..mip_size.depth_or_array_layers {
+            let start = output.len();
+            output.resize(start + row_size * height, 0);
+            let image = &mut output[start..];
+            for y in (0..height).step_by(block_height as usize) {
+                for x in (0..width).step_by(block_width as usize) {
+                    let block = blocks.next().expect("Texture data is too short");
+                    decode_block(desc.format, block, &mut decoded);
+
+                    // Blocks at the edges of mips can extend past the texture.
+                    let block_row_size = block_width as usize * texel_size;
+                    let copy_size = (width - x).min(block_width as usize) * texel_size;
+                    let rows = (height - y).min(block_height as usize);
+                    for row in 0..rows {
+                        let dst = (y + row) * row_size + x * texel_size;
+                        let src = row * block_row_size;
+                        image[dst..dst + copy_size].copy_from_slice(&decoded[src..src + copy_size]);
+                    }
+                }
+            }
+        }
+    }
+    output
+}